@@ -74,6 +74,7 @@ def get_default_system_parameters(
         "enable_0dt_deployment": "true" if zero_downtime else "false",
         "enable_alter_swap": "true",
         "enable_assert_not_null": "true",
+        "enable_async_builtin_table_migrations": "true",
         "enable_columnation_lgalloc": "true",
         "enable_comment": "true",
         "enable_compute_chunked_stack": "true",
@@ -87,6 +88,7 @@ def get_default_system_parameters(
         "enable_logical_compaction_window": "true",
         "enable_multi_worker_storage_persist_sink": "true",
         "enable_mysql_source": "true",
+        "enable_peek_deduplication": "true",
         "enable_rbac_checks": "true",
         "enable_reduce_mfp_fusion": "true",
         "enable_refresh_every_mvs": "true",
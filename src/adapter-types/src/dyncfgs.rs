@@ -64,6 +64,14 @@ pub const ENABLE_INTROSPECTION_SUBSCRIBES: Config<bool> = Config::new(
     "Enable installation of introspection subscribes.",
 );
 
+/// The factor by which the output of an operator must exceed its input to be reported as a
+/// cardinality blowup.
+pub const CARDINALITY_BLOWUP_FACTOR: Config<f64> = Config::new(
+    "cardinality_blowup_factor",
+    100.0,
+    "The factor by which the number of records an operator of an installed dataflow produces must exceed the number of records it receives to be reported in mz_internal.mz_compute_cardinality_advisories, as a hint at accidental cross joins. A value of zero disables the reports. Changes apply to introspection subscribes installed afterwards.",
+);
+
 /// The plan insights notice will not investigate fast path clusters if plan optimization took longer than this.
 pub const PLAN_INSIGHTS_NOTICE_FAST_PATH_CLUSTERS_OPTIMIZE_DURATION: Config<Duration> = Config::new(
     "plan_insights_notice fast_path_clusters_optimize_duration",
@@ -84,6 +92,312 @@ pub const DEFAULT_SINK_PARTITION_STRATEGY: Config<&str> = Config::new(
     "The default sink partitioning strategy for an environment. It defaults to 'v0'.",
 );
 
+/// The maximum number of upstream tables whose schemas are fetched concurrently while
+/// purifying a `CREATE SOURCE`.
+pub const SOURCE_PURIFICATION_CONCURRENCY: Config<usize> = Config::new(
+    "source_purification_concurrency",
+    16,
+    "The maximum number of upstream tables whose schemas are fetched concurrently while \
+     purifying a source.",
+);
+
+/// The maximum number of items dropped in a single catalog transaction by a `DROP ... CASCADE`.
+pub const DROP_OBJECTS_BATCH_SIZE: Config<usize> = Config::new(
+    "drop_objects_batch_size",
+    1000,
+    "The maximum number of items to drop in a single catalog transaction. Larger drops are split into dependency-ordered batches that yield to the coordinator in between.",
+);
+
+/// How often to vacuum the durable catalog.
+pub const CATALOG_VACUUM_INTERVAL: Config<Duration> = Config::new(
+    "catalog_vacuum_interval",
+    Duration::from_secs(60 * 60),
+    "How often to vacuum the durable catalog, pruning expired audit log events. A value of zero disables scheduled vacuums.",
+);
+
+/// How long to retain audit log events before a catalog vacuum prunes them.
+pub const AUDIT_LOG_RETENTION_PERIOD: Config<Duration> = Config::new(
+    "audit_log_retention_period",
+    Duration::ZERO,
+    "How long to retain audit log events before a catalog vacuum prunes them. A value of zero retains audit log events forever.",
+);
+
+/// How often a read-only replica checks the durable catalog for changes made by the leader.
+pub const CATALOG_FOLLOWER_SYNC_INTERVAL: Config<Duration> = Config::new(
+    "catalog_follower_sync_interval",
+    Duration::from_secs(1),
+    "How often a read-only replica checks the durable catalog for changes made by the leader.",
+);
+
+/// Rules that classify statements into workload classes.
+///
+/// The configuration value must be a JSON array of objects. Rules are checked in the order they
+/// are defined, and the first rule whose `role`, `application_name` and `sql` conditions all match
+/// a statement determines its workload class, which names the `cluster`, `priority`,
+/// `statement_timeout` and `sample_rate` for the statement.
+pub const WORKLOAD_CLASS_RULES: Config<fn() -> serde_json::Value> = Config::new(
+    "workload_class_rules",
+    || serde_json::json!([]),
+    "Rules that classify statements into workload classes, which determine their default cluster, priority, statement timeout, and statement logging sample rate.",
+);
+
+/// The number of rows buffered for a `SUBSCRIBE` driven by `FETCH` at which it is paused.
+pub const SUBSCRIBE_CURSOR_BUFFER_HIGH_WATERMARK: Config<usize> = Config::new(
+    "subscribe_cursor_buffer_high_watermark",
+    100_000,
+    "The number of rows a cursor's SUBSCRIBE may buffer before its replicas stop sending updates. A value of zero disables pausing.",
+);
+
+/// The number of rows buffered for a paused `SUBSCRIBE` driven by `FETCH` at which it resumes.
+pub const SUBSCRIBE_CURSOR_BUFFER_LOW_WATERMARK: Config<usize> = Config::new(
+    "subscribe_cursor_buffer_low_watermark",
+    10_000,
+    "The number of rows the buffer of a paused cursor's SUBSCRIBE must drain to before its replicas resume sending updates.",
+);
+
+/// A daily window, in UTC, during which disruptive background work is deferred.
+///
+/// The configuration value must be formatted as `HH:MM-HH:MM`. A window whose end precedes its
+/// start wraps around midnight.
+pub const MAINTENANCE_WINDOW: Config<&str> = Config::new(
+    "maintenance_window",
+    "",
+    "A daily window, in UTC and formatted as HH:MM-HH:MM, during which disruptive background work like storage usage collection, scheduled cluster shutdowns and catalog vacuums is deferred. An empty value disables the window.",
+);
+
+/// The minimum time between two runs of the same kind of disruptive background work.
+pub const MAINTENANCE_THROTTLE_INTERVAL: Config<Duration> = Config::new(
+    "maintenance_throttle_interval",
+    Duration::ZERO,
+    "The minimum time between two runs of the same kind of disruptive background work outside of the maintenance window. A value of zero disables throttling.",
+);
+
+/// How often to record the load of each database in `mz_database_metrics`.
+pub const DATABASE_METRICS_INTERVAL: Config<Duration> = Config::new(
+    "database_metrics_interval",
+    Duration::from_secs(10),
+    "How often to record the load of each database in `mz_internal.mz_database_metrics`. A value of zero disables recording.",
+);
+
+/// How often to record the lag of each sink in `mz_sink_lag`.
+pub const SINK_LAG_INTERVAL: Config<Duration> = Config::new(
+    "sink_lag_interval",
+    Duration::from_secs(10),
+    "How often to record the lag of each sink behind its upstream relation in `mz_internal.mz_sink_lag`. A value of zero disables recording.",
+);
+
+/// The lag of a sink above which a notice is sent to all sessions.
+pub const SINK_LAG_NOTICE_THRESHOLD: Config<Duration> = Config::new(
+    "sink_lag_notice_threshold",
+    Duration::from_secs(5 * 60),
+    "The lag of a sink behind its upstream relation above which a notice is sent to all sessions. A value of zero disables the notices.",
+);
+
+/// The maximum number of concurrently active compute sinks per cluster.
+pub const MAX_COMPUTE_SINKS_PER_CLUSTER: Config<usize> = Config::new(
+    "max_compute_sinks_per_cluster",
+    0,
+    "The maximum number of subscribes and copy tos that can be active on a cluster at the same time. A value of zero disables the limit.",
+);
+
+/// Whether statements that would exceed `max_compute_sinks_per_cluster` wait instead of failing.
+pub const ENABLE_COMPUTE_SINK_WAITLIST: Config<bool> = Config::new(
+    "enable_compute_sink_waitlist",
+    false,
+    "Whether subscribes and copy tos that would exceed `max_compute_sinks_per_cluster` wait for another one on the cluster to finish instead of failing. Waiting statements of internal users start first.",
+);
+
+/// How often to retire the compute sinks whose clients have gone away.
+pub const COMPUTE_SINK_LIVENESS_CHECK_INTERVAL: Config<Duration> = Config::new(
+    "compute_sink_liveness_check_interval",
+    Duration::from_secs(60),
+    "How often to retire the subscribes and copy tos whose connection is no longer active or whose client has stopped listening for their results. A value of zero disables the checks.",
+);
+
+/// How often to record the delivery progress of each webhook sink.
+pub const WEBHOOK_SINK_DELIVERY_RECORD_INTERVAL: Config<Duration> = Config::new(
+    "webhook_sink_delivery_record_interval",
+    Duration::from_secs(1),
+    "How often to record the timestamps up to which each webhook sink has delivered its updates in `mz_internal.mz_webhook_sink_delivery_history`. Webhook sinks deliver the timestamps after the latest recording again when they restart.",
+);
+
+/// How long `SUBSCRIBE ... INTO` appends updates to a segment before it starts the next one.
+pub const SUBSCRIBE_ARCHIVE_SEGMENT_INTERVAL: Config<Duration> = Config::new(
+    "subscribe_archive_segment_interval",
+    Duration::from_secs(60),
+    "How long `SUBSCRIBE ... INTO` appends updates to a segment before it finishes the segment at the latest complete timestamp and starts the next one.",
+);
+
+/// How long turning off a scheduled cluster waits for the queries and subscriptions running on it.
+pub const CLUSTER_SCHEDULE_SUSPENSION_GRACE_PERIOD: Config<Duration> = Config::new(
+    "cluster_schedule_suspension_grace_period",
+    Duration::from_secs(60),
+    "How long turning off a cluster because of its schedule is deferred while queries or subscriptions are running on it. A value of zero turns off the cluster right away.",
+);
+
+/// How long frequent Segment events are batched before they are sent.
+pub const SEGMENT_EVENT_BATCH_INTERVAL: Config<Duration> = Config::new(
+    "segment_event_batch_interval",
+    Duration::from_secs(10),
+    "How long frequent Segment events, like cluster status changes, are batched and deduplicated before they are sent. A value of zero sends the events immediately.",
+);
+
+/// The maximum number of frequent Segment events sent per batch.
+pub const SEGMENT_EVENT_BATCH_LIMIT: Config<usize> = Config::new(
+    "segment_event_batch_limit",
+    100,
+    "The maximum number of frequent Segment events, like cluster status changes, sent per batch. Events beyond the limit are dropped. A value of zero disables the limit.",
+);
+
+/// Whether to pause the hydration of dataflows that make a replica crash loop with OOM kills.
+pub const ENABLE_OOM_CRASH_LOOP_PROTECTION: Config<bool> = Config::new(
+    "enable_oom_crash_loop_protection",
+    false,
+    "Whether to pause the hydration of the dataflows that were hydrating on a replica whenever it was repeatedly OOM-killed.",
+);
+
+/// How many OOM kills of a replica make up a crash loop.
+pub const OOM_CRASH_LOOP_THRESHOLD: Config<usize> = Config::new(
+    "oom_crash_loop_threshold",
+    3,
+    "The number of OOM kills of a replica within `oom_crash_loop_window` after which the replica is considered to be crash looping.",
+);
+
+/// The window in which OOM kills of a replica are counted towards a crash loop.
+pub const OOM_CRASH_LOOP_WINDOW: Config<Duration> = Config::new(
+    "oom_crash_loop_window",
+    Duration::from_secs(15 * 60),
+    "The window in which OOM kills of a replica are counted towards a crash loop.",
+);
+
+/// How long the dataflows paused after an OOM crash loop stay paused.
+pub const OOM_CRASH_LOOP_PAUSE_DURATION: Config<Duration> = Config::new(
+    "oom_crash_loop_pause_duration",
+    Duration::from_secs(60 * 60),
+    "How long the dataflows paused on a replica after an OOM crash loop stay paused. Zero keeps them paused until the replica is dropped.",
+);
+
+/// How long a cursor handed off with `HANDOFF CURSOR` waits to be claimed before it is closed.
+pub const CURSOR_HANDOFF_TIMEOUT: Config<Duration> = Config::new(
+    "cursor_handoff_timeout",
+    Duration::from_secs(5 * 60),
+    "How long a cursor handed off with `HANDOFF CURSOR` waits to be claimed before it is closed.",
+);
+
+/// The number of rows in each page of a cursor over a `SELECT` that reads from an index.
+pub const CURSOR_PAGE_SIZE: Config<usize> = Config::new(
+    "cursor_page_size",
+    0,
+    "The number of rows that each FETCH of a cursor over an index computes at a time. A value of zero makes cursors compute their whole result at their first FETCH.",
+);
+
+/// How long a cursor whose result is computed in pages may stay open.
+pub const MAX_CURSOR_LIFETIME: Config<Duration> = Config::new(
+    "max_cursor_lifetime",
+    Duration::from_secs(60 * 60),
+    "How long a cursor whose result is computed in pages may stay open before further FETCHes fail.",
+);
+
+/// Whether to check that the orchestrator has the capacity to run new cluster replicas before
+/// creating them: `off`, `notice` to create the replicas anyway with a notice, `queue` to wait
+/// for capacity, or `error`.
+pub const REPLICA_CAPACITY_CHECK: Config<&str> = Config::new(
+    "replica_capacity_check",
+    "off",
+    "Whether to check that the orchestrator has the capacity to run new cluster replicas before creating them: 'off', 'notice' to create the replicas anyway with a notice, 'queue' to wait with a notice until there is capacity, or 'error' to fail the statement.",
+);
+
+/// How long the capacity check of new cluster replicas may take before it is skipped.
+pub const REPLICA_CAPACITY_CHECK_TIMEOUT: Config<Duration> = Config::new(
+    "replica_capacity_check_timeout",
+    Duration::from_secs(2),
+    "How long the capacity check of new cluster replicas may take before it is skipped.",
+);
+
+/// How long the creation of cluster replicas waits for capacity, if `replica_capacity_check` is
+/// `queue`, before it fails.
+pub const REPLICA_CAPACITY_QUEUE_TIMEOUT: Config<Duration> = Config::new(
+    "replica_capacity_queue_timeout",
+    Duration::from_secs(10 * 60),
+    "How long the creation of cluster replicas waits for capacity, if replica_capacity_check is 'queue', before it fails.",
+);
+
+/// Whether to record advisories to move replicas away from availability zones whose replicas are
+/// all not ready.
+pub const ENABLE_AZ_REBALANCE_ADVISORIES: Config<bool> = Config::new(
+    "enable_az_rebalance_advisories",
+    true,
+    "Whether to record advisories in `mz_cluster_replica_az_advisories` to move replicas away from availability zones in which no replica has been ready for `az_rebalance_advisory_threshold`.",
+);
+
+/// How long no replica of an availability zone must be ready before we advise moving its
+/// replicas.
+pub const AZ_REBALANCE_ADVISORY_THRESHOLD: Config<Duration> = Config::new(
+    "az_rebalance_advisory_threshold",
+    Duration::from_secs(10 * 60),
+    "How long no replica of an availability zone must be ready before we advise moving its replicas to other availability zones.",
+);
+
+/// The sampling of the spans of coordinator messages, by message kind.
+pub const COORD_MESSAGE_SPAN_SAMPLING: Config<&str> = Config::new(
+    "coord_message_span_sampling",
+    "",
+    "The sampling of the spans of coordinator messages, as a comma-separated list of `kind=level` or `kind=level/n` entries, where `level` is one of 'trace', 'debug', 'info', or 'off', and only every `n`th message of the kind is traced. Messages of kinds that are not listed are always traced at 'info'.",
+);
+
+/// Whether the storage usage collection also estimates the number of distinct keys of each data
+/// shard.
+pub const ENABLE_STORAGE_SHARD_CARDINALITY_COLLECTION: Config<bool> = Config::new(
+    "enable_storage_shard_cardinality_collection",
+    false,
+    "Whether the storage usage collection also estimates the number of distinct keys of each data shard and records them in `mz_storage_shard_cardinality`. This requires reading all the data of the shards.",
+);
+
+/// Whether identical fast-path peeks that are in flight at the same time share one compute peek.
+pub const ENABLE_PEEK_DEDUPLICATION: Config<bool> = Config::new(
+    "enable_peek_deduplication",
+    false,
+    "Whether a fast-path peek that is identical to a peek in flight, reading the same collection on the same cluster at the same timestamp, waits for the response of that peek instead of issuing another one.",
+);
+
+/// Whether the contents of non-critical builtin tables are migrated in the background after
+/// startup, instead of before the environment becomes queryable.
+pub const ENABLE_ASYNC_BUILTIN_TABLE_MIGRATIONS: Config<bool> = Config::new(
+    "enable_async_builtin_table_migrations",
+    false,
+    "Whether the contents of large, non-critical builtin tables, like `mz_audit_events` and `mz_storage_usage_by_shard`, are migrated in the background after startup, instead of blocking startup. The progress of the migrations is shown in `mz_builtin_table_migrations`.",
+);
+
+/// The maximum number of purification and connection validation tasks that contact the same
+/// external host at the same time.
+pub const EXTERNAL_TASK_CONCURRENCY_PER_HOST: Config<usize> = Config::new(
+    "external_task_concurrency_per_host",
+    16,
+    "The maximum number of source and sink purifications and connection validations that contact the same external host, like a Kafka broker or a PostgreSQL server, at the same time. Further ones are queued. 0 means no limit.",
+);
+
+/// How long a deferred write can wait for the write lock before the state of the lock is logged.
+pub const WRITE_LOCK_WAIT_WARNING_THRESHOLD: Config<Duration> = Config::new(
+    "write_lock_wait_warning_threshold",
+    Duration::from_secs(60),
+    "How long a deferred write can wait for the write lock before the holder of the lock and the writes waiting for it are logged. A value of zero disables the check.",
+);
+
+/// Whether to abort the oldest write that has waited for the write lock beyond the threshold.
+pub const ABORT_STARVED_WRITE_LOCK_WAITERS: Config<bool> = Config::new(
+    "abort_starved_write_lock_waiters",
+    false,
+    "Whether to abort the oldest write that has waited for the write lock for longer than write_lock_wait_warning_threshold.",
+);
+
+/// How long rejected cancellation requests from the same origin are aggregated before they are
+/// recorded.
+pub const REJECTED_CANCELLATION_AGGREGATION_INTERVAL: Config<Duration> = Config::new(
+    "rejected_cancellation_aggregation_interval",
+    Duration::from_secs(60),
+    "How long rejected cancellation requests from the same origin are aggregated into one row of `mz_cancellation_history`.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -95,6 +409,48 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&WITH_0DT_CAUGHT_UP_CHECK_ALLOWED_LAG)
         .add(&ENABLE_STATEMENT_LIFECYCLE_LOGGING)
         .add(&ENABLE_INTROSPECTION_SUBSCRIBES)
+        .add(&CARDINALITY_BLOWUP_FACTOR)
         .add(&PLAN_INSIGHTS_NOTICE_FAST_PATH_CLUSTERS_OPTIMIZE_DURATION)
         .add(&DEFAULT_SINK_PARTITION_STRATEGY)
+        .add(&SOURCE_PURIFICATION_CONCURRENCY)
+        .add(&DROP_OBJECTS_BATCH_SIZE)
+        .add(&CATALOG_VACUUM_INTERVAL)
+        .add(&AUDIT_LOG_RETENTION_PERIOD)
+        .add(&CATALOG_FOLLOWER_SYNC_INTERVAL)
+        .add(&WORKLOAD_CLASS_RULES)
+        .add(&SUBSCRIBE_CURSOR_BUFFER_HIGH_WATERMARK)
+        .add(&SUBSCRIBE_CURSOR_BUFFER_LOW_WATERMARK)
+        .add(&MAINTENANCE_WINDOW)
+        .add(&MAINTENANCE_THROTTLE_INTERVAL)
+        .add(&DATABASE_METRICS_INTERVAL)
+        .add(&SINK_LAG_INTERVAL)
+        .add(&SINK_LAG_NOTICE_THRESHOLD)
+        .add(&COMPUTE_SINK_LIVENESS_CHECK_INTERVAL)
+        .add(&MAX_COMPUTE_SINKS_PER_CLUSTER)
+        .add(&ENABLE_COMPUTE_SINK_WAITLIST)
+        .add(&WEBHOOK_SINK_DELIVERY_RECORD_INTERVAL)
+        .add(&SUBSCRIBE_ARCHIVE_SEGMENT_INTERVAL)
+        .add(&CLUSTER_SCHEDULE_SUSPENSION_GRACE_PERIOD)
+        .add(&SEGMENT_EVENT_BATCH_INTERVAL)
+        .add(&SEGMENT_EVENT_BATCH_LIMIT)
+        .add(&ENABLE_OOM_CRASH_LOOP_PROTECTION)
+        .add(&OOM_CRASH_LOOP_THRESHOLD)
+        .add(&OOM_CRASH_LOOP_WINDOW)
+        .add(&OOM_CRASH_LOOP_PAUSE_DURATION)
+        .add(&CURSOR_HANDOFF_TIMEOUT)
+        .add(&CURSOR_PAGE_SIZE)
+        .add(&MAX_CURSOR_LIFETIME)
+        .add(&REPLICA_CAPACITY_CHECK)
+        .add(&REPLICA_CAPACITY_CHECK_TIMEOUT)
+        .add(&REPLICA_CAPACITY_QUEUE_TIMEOUT)
+        .add(&ENABLE_AZ_REBALANCE_ADVISORIES)
+        .add(&AZ_REBALANCE_ADVISORY_THRESHOLD)
+        .add(&COORD_MESSAGE_SPAN_SAMPLING)
+        .add(&ENABLE_STORAGE_SHARD_CARDINALITY_COLLECTION)
+        .add(&ENABLE_PEEK_DEDUPLICATION)
+        .add(&ENABLE_ASYNC_BUILTIN_TABLE_MIGRATIONS)
+        .add(&EXTERNAL_TASK_CONCURRENCY_PER_HOST)
+        .add(&WRITE_LOCK_WAIT_WARNING_THRESHOLD)
+        .add(&ABORT_STARVED_WRITE_LOCK_WAITERS)
+        .add(&REJECTED_CANCELLATION_AGGREGATION_INTERVAL)
 }
@@ -26,3 +26,9 @@ pub const DEFAULT_PG_TIMESTAMP_ORACLE_CONNECT_TIMEOUT: Duration = Duration::from
 
 /// Default value for `DynamicConfig::pg_connection_pool_tcp_user_timeout`.
 pub const DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default value for `DynamicConfig::batch_window`.
+pub const DEFAULT_TIMESTAMP_ORACLE_BATCH_WINDOW: Duration = Duration::ZERO;
+
+/// Default value for `DynamicConfig::circuit_breaker_timeout`.
+pub const DEFAULT_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT: Duration = Duration::from_secs(30);
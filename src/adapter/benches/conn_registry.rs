@@ -0,0 +1,130 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mz_adapter::{ConnRegistry, ConnRegistryKey};
+use mz_adapter_types::connection::ConnectionId;
+use uuid::Uuid;
+
+fn bench_conn_registry(c: &mut Criterion) {
+    bench_retire(c, 10, 100);
+    bench_retire(c, 1_000, 10);
+    bench_retire(c, 10_000, 1);
+    bench_cancel(c, 10, 100);
+    bench_cancel(c, 1_000, 10);
+}
+
+/// The bookkeeping of pending peeks the registry replaces: a map of all pending peeks, and a map
+/// of the pending peeks of each connection.
+#[derive(Default)]
+struct Maps {
+    pending: BTreeMap<Uuid, (ConnectionId, u64)>,
+    by_conn: BTreeMap<ConnectionId, BTreeMap<Uuid, u64>>,
+}
+
+impl Maps {
+    fn insert(&mut self, conn_id: ConnectionId, value: u64) -> Uuid {
+        let uuid = Uuid::new_v4();
+        self.by_conn
+            .entry(conn_id.clone())
+            .or_default()
+            .insert(uuid, value);
+        self.pending.insert(uuid, (conn_id, value));
+        uuid
+    }
+
+    fn remove(&mut self, uuid: &Uuid) -> Option<u64> {
+        let (conn_id, value) = self.pending.remove(uuid)?;
+        let peeks = self.by_conn.get_mut(&conn_id).expect("tracked");
+        peeks.remove(uuid);
+        if peeks.is_empty() {
+            self.by_conn.remove(&conn_id);
+        }
+        Some(value)
+    }
+
+    fn remove_connection(&mut self, conn_id: &ConnectionId) -> Vec<u64> {
+        let peeks = self.by_conn.remove(conn_id).unwrap_or_default();
+        peeks
+            .keys()
+            .filter_map(|uuid| self.pending.remove(uuid).map(|(_, value)| value))
+            .collect()
+    }
+}
+
+fn conn(i: u64) -> ConnectionId {
+    ConnectionId::Static(u32::try_from(i).expect("few connections"))
+}
+
+// Keeps `pending` peeks registered for `conns` connections each, then registers and retires one
+// peek per connection in a loop.
+fn bench_retire(c: &mut Criterion, conns: u64, pending: u64) {
+    c.bench_function(&format!("retire_registry_{conns}_{pending}"), |b| {
+        let mut registry = ConnRegistry::default();
+        for i in 0..conns * pending {
+            registry.insert(conn(i % conns), i);
+        }
+        let mut keys: Vec<ConnRegistryKey> = Vec::with_capacity(usize::try_from(conns).unwrap());
+        b.iter(|| {
+            for i in 0..conns {
+                keys.push(registry.insert(conn(i), i));
+            }
+            for key in keys.drain(..) {
+                black_box(registry.remove(key));
+            }
+        });
+    });
+    c.bench_function(&format!("retire_maps_{conns}_{pending}"), |b| {
+        let mut maps = Maps::default();
+        for i in 0..conns * pending {
+            maps.insert(conn(i % conns), i);
+        }
+        let mut uuids: Vec<Uuid> = Vec::with_capacity(usize::try_from(conns).unwrap());
+        b.iter(|| {
+            for i in 0..conns {
+                uuids.push(maps.insert(conn(i), i));
+            }
+            for uuid in uuids.drain(..) {
+                black_box(maps.remove(&uuid));
+            }
+        });
+    });
+}
+
+// Registers `pending` peeks for `conns` connections each, then cancels all peeks of each
+// connection in a loop.
+fn bench_cancel(c: &mut Criterion, conns: u64, pending: u64) {
+    c.bench_function(&format!("cancel_registry_{conns}_{pending}"), |b| {
+        let mut registry = ConnRegistry::default();
+        b.iter(|| {
+            for i in 0..conns * pending {
+                registry.insert(conn(i % conns), i);
+            }
+            for i in 0..conns {
+                black_box(registry.remove_connection(&conn(i)));
+            }
+        });
+    });
+    c.bench_function(&format!("cancel_maps_{conns}_{pending}"), |b| {
+        let mut maps = Maps::default();
+        b.iter(|| {
+            for i in 0..conns * pending {
+                maps.insert(conn(i % conns), i);
+            }
+            for i in 0..conns {
+                black_box(maps.remove_connection(&conn(i)));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, bench_conn_registry);
+criterion_main!(benches);
@@ -12,8 +12,13 @@
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::iter;
+use std::pin::Pin;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{ready, Context, Poll};
 
 use anyhow::anyhow;
+use futures::Stream;
 use itertools::Itertools;
 use mz_adapter_types::connection::ConnectionId;
 use mz_compute_client::protocol::response::SubscribeBatch;
@@ -22,13 +27,15 @@ use mz_expr::compare_columns;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_repr::adt::numeric;
-use mz_repr::{Datum, GlobalId, IntoRowIterator, Row, Timestamp};
+use mz_repr::{Datum, GlobalId, IntoRowIterator, Row, RowIterator, Timestamp};
 use mz_sql::plan::SubscribeOutput;
 use timely::progress::Antichain;
 use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
 use crate::coord::peek::PeekResponseUnary;
+use crate::coord::Message;
+use crate::session::RowBatchStream;
 use crate::{AdapterError, ExecuteResponse};
 
 #[derive(Debug)]
@@ -90,6 +97,17 @@ pub enum ActiveComputeSinkRetireReason {
     DependencyDropped(String),
 }
 
+impl ActiveComputeSinkRetireReason {
+    /// Returns the name of the reason, as recorded in `mz_subscription_history`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActiveComputeSinkRetireReason::Finished => "finished",
+            ActiveComputeSinkRetireReason::Canceled => "canceled",
+            ActiveComputeSinkRetireReason::DependencyDropped(_) => "dependency_dropped",
+        }
+    }
+}
+
 /// A description of an active subscribe from coord's perspective
 #[derive(Debug)]
 pub struct ActiveSubscribe {
@@ -115,6 +133,25 @@ pub struct ActiveSubscribe {
     pub start_time: EpochMillis,
     /// How to present the subscribe's output.
     pub output: SubscribeOutput,
+    /// Whether to consolidate the updates of each timestamp, i.e., to sum the diffs of equal
+    /// rows and drop the rows whose diffs sum to zero.
+    pub deduplicate: bool,
+    /// The number of rows the subscribe has emitted, excluding progress messages.
+    pub rows_emitted: u64,
+    /// The number of bytes of the rows the subscribe has emitted, excluding progress messages.
+    pub bytes_emitted: u64,
+    /// The rows that have been sent to the client but not yet received by it.
+    pub buffer: Arc<SubscribeBuffer>,
+    /// Whether the subscribe's replicas have been told to hold back its updates.
+    pub paused: bool,
+    /// The buffered rows and pause state last recorded in `mz_subscription_buffers`, if any. This
+    /// is only updated when the subscribe is paused or resumed.
+    pub reported_buffer: Option<(usize, bool)>,
+    /// Notified once the subscribe has sent all of its updates, if anyone is interested.
+    ///
+    /// Unlike the closing of `channel`, this distinguishes a complete subscribe from one that was
+    /// retired early.
+    pub complete_tx: Option<oneshot::Sender<()>>,
 }
 
 impl ActiveSubscribe {
@@ -168,6 +205,12 @@ impl ActiveSubscribe {
             }
         };
 
+        if self.deduplicate {
+            // Updates are `(time, row, diff)`, so this consolidates the updates of each
+            // timestamp.
+            differential_dataflow::consolidation::consolidate_updates(&mut rows);
+        }
+
         // Sort results by time. We use stable sort here because it will produce
         // deterministic results since the cursor will always produce rows in
         // the same order. Compute doesn't guarantee that the results are sorted
@@ -350,6 +393,11 @@ impl ActiveSubscribe {
                 row_buf.clone()
             })
             .collect();
+        self.rows_emitted += u64::cast_from(rows.len());
+        self.bytes_emitted += rows
+            .iter()
+            .map(|row| u64::cast_from(row.byte_len()))
+            .sum::<u64>();
         let rows = Box::new(rows.into_row_iter());
 
         self.send(PeekResponseUnary::Rows(rows));
@@ -362,7 +410,13 @@ impl ActiveSubscribe {
             self.send_progress_message(&batch.upper);
         }
 
-        batch.upper.is_empty()
+        let complete = batch.upper.is_empty();
+        if complete {
+            if let Some(complete_tx) = self.complete_tx.take() {
+                let _ = complete_tx.send(());
+            }
+        }
+        complete
     }
 
     /// Retires the subscribe with the specified reason.
@@ -384,9 +438,137 @@ impl ActiveSubscribe {
     /// Sends a message to the client if the subscribe has not already completed
     /// and if the client has not already gone away.
     fn send(&self, response: PeekResponseUnary) {
-        // TODO(benesch): the lack of backpressure here can result in
-        // unbounded memory usage.
-        let _ = self.channel.send(response);
+        // Subscribes driven by a cursor are paused once their client falls too far behind, see
+        // `Coordinator::limit_subscribe_buffer`. Other subscribes are not, so the lack of
+        // backpressure here can result in unbounded memory usage.
+        self.buffer.send(&self.channel, response);
+    }
+}
+
+/// Accounting of the rows a subscribe has sent to its client, but the client has not yet
+/// received.
+///
+/// Shared between the [`ActiveSubscribe`], which sends rows, and the [`SubscribeRowStream`] from
+/// which its client receives them.
+#[derive(Debug)]
+pub struct SubscribeBuffer {
+    /// The ID of the subscribe.
+    sink_id: GlobalId,
+    /// The number of rows in the buffer.
+    rows: AtomicUsize,
+    /// The name of the cursor whose `FETCH`es drive the subscribe, if any.
+    cursor: OnceLock<String>,
+    /// While the subscribe is paused, the number of rows to which the buffer must drain before
+    /// the subscribe is resumed.
+    resume_at: Mutex<Option<usize>>,
+    /// Notifies the coordinator that the subscribe should be resumed.
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+}
+
+impl SubscribeBuffer {
+    /// Creates an empty buffer for the subscribe `sink_id`.
+    pub fn new(sink_id: GlobalId, internal_cmd_tx: mpsc::UnboundedSender<Message>) -> Self {
+        SubscribeBuffer {
+            sink_id,
+            rows: AtomicUsize::new(0),
+            cursor: OnceLock::new(),
+            resume_at: Mutex::new(None),
+            internal_cmd_tx,
+        }
+    }
+
+    /// Reports the number of rows in the buffer.
+    pub fn rows(&self) -> usize {
+        self.rows.load(atomic::Ordering::SeqCst)
+    }
+
+    /// Reports the name of the cursor whose `FETCH`es drive the subscribe, if any.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.get().map(|name| name.as_str())
+    }
+
+    /// Marks the buffer as paused, until it has drained to `low_watermark` rows.
+    ///
+    /// Returns `false`, without pausing, if the buffer has already drained that far.
+    pub fn pause(&self, low_watermark: usize) -> bool {
+        let mut resume_at = self.resume_at.lock().expect("lock poisoned");
+        if self.rows() <= low_watermark {
+            return false;
+        }
+        *resume_at = Some(low_watermark);
+        true
+    }
+
+    /// Sends `response` on `channel`, from which a [`SubscribeRowStream`] of this buffer
+    /// receives it, and accounts for its rows.
+    pub fn send(
+        &self,
+        channel: &mpsc::UnboundedSender<PeekResponseUnary>,
+        response: PeekResponseUnary,
+    ) {
+        let rows = match &response {
+            PeekResponseUnary::Rows(rows) => rows.count(),
+            PeekResponseUnary::Error(_) | PeekResponseUnary::Canceled => 0,
+        };
+        // Count the rows before sending them, so the client can't receive them first.
+        self.sent(rows);
+        if channel.send(response).is_err() {
+            self.unsent(rows);
+        }
+    }
+
+    fn sent(&self, rows: usize) {
+        self.rows.fetch_add(rows, atomic::Ordering::SeqCst);
+    }
+
+    fn unsent(&self, rows: usize) {
+        self.rows.fetch_sub(rows, atomic::Ordering::SeqCst);
+    }
+
+    fn received(&self, rows: usize) {
+        let remaining = self.rows.fetch_sub(rows, atomic::Ordering::SeqCst) - rows;
+        let mut resume_at = self.resume_at.lock().expect("lock poisoned");
+        if resume_at.map_or(false, |low_watermark| remaining <= low_watermark) {
+            *resume_at = None;
+            // If sending fails, the coordinator has shut down.
+            let _ = self
+                .internal_cmd_tx
+                .send(Message::ResumeSubscribe(self.sink_id));
+        }
+    }
+}
+
+/// The stream of responses from which the client of a subscribe receives its rows.
+#[derive(Debug)]
+pub struct SubscribeRowStream {
+    rx: RowBatchStream,
+    buffer: Arc<SubscribeBuffer>,
+}
+
+impl SubscribeRowStream {
+    /// Creates a stream that receives the rows of `rx` from `buffer`.
+    pub fn new(rx: RowBatchStream, buffer: Arc<SubscribeBuffer>) -> Self {
+        SubscribeRowStream { rx, buffer }
+    }
+
+    /// Marks the subscribe as driven by `FETCH`es of the cursor `name`.
+    ///
+    /// This subjects the subscribe to the buffer limits of cursors, which pause it while its
+    /// client falls behind.
+    pub fn set_cursor(&self, name: &str) {
+        let _ = self.buffer.cursor.set(name.to_string());
+    }
+}
+
+impl Stream for SubscribeRowStream {
+    type Item = PeekResponseUnary;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let response = ready!(self.rx.poll_recv(cx));
+        if let Some(PeekResponseUnary::Rows(rows)) = &response {
+            self.buffer.received(rows.count());
+        }
+        Poll::Ready(response)
     }
 }
 
@@ -19,18 +19,18 @@ use std::sync::Arc;
 use futures::Future;
 use itertools::Itertools;
 use mz_adapter_types::connection::ConnectionId;
-use mz_audit_log::{EventType, FullNameV1, ObjectType};
+use mz_audit_log::{EventType, FullNameV1, ObjectType, VersionedEvent};
 use mz_build_info::DUMMY_BUILD_INFO;
 use mz_catalog::builtin::{
     BuiltinCluster, BuiltinLog, BuiltinSource, BuiltinTable, BUILTINS, BUILTIN_PREFIXES,
     MZ_CATALOG_SERVER_CLUSTER,
 };
 use mz_catalog::config::{BuiltinItemMigrationConfig, ClusterReplicaSizeMap, Config, StateConfig};
-#[cfg(test)]
-use mz_catalog::durable::CatalogError;
-use mz_catalog::durable::{test_bootstrap_args, DurableCatalogState};
+use mz_catalog::durable::{test_bootstrap_args, CatalogError, DurableCatalogState};
 use mz_catalog::memory::error::{Error, ErrorKind};
-use mz_catalog::memory::objects::{CatalogEntry, Cluster, ClusterReplica, Database, Role, Schema};
+use mz_catalog::memory::objects::{
+    CatalogEntry, Cluster, ClusterReplica, Database, Role, Schema, StateUpdate,
+};
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_controller::clusters::ReplicaLocation;
 use mz_controller_types::{ClusterId, ReplicaId};
@@ -63,7 +63,7 @@ use mz_sql::rbac;
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::{MZ_SYSTEM_ROLE_ID, SUPPORT_USER, SYSTEM_USER};
 use mz_sql::session::vars::{ConnectionCounter, SystemVars};
-use mz_sql_parser::ast::QualifiedReplica;
+use mz_sql_parser::ast::{QualifiedReplica, Raw, Statement};
 use mz_storage_types::connections::inline::{ConnectionResolver, InlinedConnection};
 use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::read_policy::ReadPolicy;
@@ -76,6 +76,8 @@ use uuid::Uuid;
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
 pub use crate::catalog::builtin_table_updates::BuiltinTableUpdate;
+pub(crate) use crate::catalog::bundle::BundleStatement;
+pub use crate::catalog::bundle::CatalogBundle;
 pub use crate::catalog::open::{
     BuiltinMigrationMetadata, InitializeStateResult, OpenCatalogResult,
 };
@@ -90,6 +92,7 @@ use crate::util::ResultExt;
 use crate::{AdapterError, AdapterNotice, ExecuteResponse};
 
 mod builtin_table_updates;
+mod bundle;
 pub(crate) mod consistency;
 mod migrate;
 
@@ -401,6 +404,19 @@ impl Catalog {
         self.transient_revision
     }
 
+    /// Applies `updates`, which a read-only replica observed in the durable catalog, to the
+    /// in-memory catalog.
+    ///
+    /// The leader maintains the builtin tables, so the builtin table updates are dropped.
+    pub(crate) fn apply_follower_updates(
+        &mut self,
+        updates: Vec<StateUpdate>,
+    ) -> Result<(), CatalogError> {
+        let _builtin_table_updates = self.state.apply_updates(updates)?;
+        self.transient_revision += 1;
+        Ok(())
+    }
+
     /// Creates a debug catalog from the current
     /// `COCKROACH_URL` with parameters set appropriately for debug contexts,
     /// like in tests.
@@ -761,7 +777,9 @@ impl Catalog {
                 Ok(self.resolve_builtin_cluster(&MZ_CATALOG_SERVER_CLUSTER))
             }
             TargetCluster::Active => self.active_cluster(session),
-            TargetCluster::Transaction(cluster_id) => self
+            TargetCluster::Transaction(cluster_id)
+            | TargetCluster::Failover(cluster_id)
+            | TargetCluster::Replica(cluster_id, _) => self
                 .try_get_cluster(cluster_id)
                 .ok_or(AdapterError::ConcurrentClusterDrop),
         }
@@ -771,16 +789,18 @@ impl Catalog {
         // TODO(benesch): this check here is not sufficiently protective. It'd
         // be very easy for a code path to accidentally avoid this check by
         // calling `resolve_cluster(session.vars().cluster())`.
+        let names = self.state.cluster_failover_list(session.vars().cluster());
         if session.user().name != SYSTEM_USER.name
             && session.user().name != SUPPORT_USER.name
-            && session.vars().cluster() == SYSTEM_USER.name
+            && names.contains(&SYSTEM_USER.name.as_str())
         {
             coord_bail!(
                 "system cluster '{}' cannot execute user queries",
                 SYSTEM_USER.name
             );
         }
-        let cluster = self.resolve_cluster(session.vars().cluster())?;
+        let name = self.state.preferred_cluster_name(session.vars().cluster());
+        let cluster = self.resolve_cluster(name)?;
         Ok(cluster)
     }
 
@@ -971,6 +991,26 @@ impl Catalog {
         Ok(self.storage().await.confirm_leadership().await?)
     }
 
+    /// Returns the oldest audit log events that occurred before `cutoff`, up to `limit` of them.
+    ///
+    /// The returned future doesn't borrow the catalog, so that the scan of the audit log can run
+    /// off the coordinator's main loop.
+    pub fn expired_audit_log_events(
+        &self,
+        cutoff: EpochMillis,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<VersionedEvent>, AdapterError>> + Send + 'static {
+        let storage = Arc::clone(&self.storage);
+        async move {
+            let events = storage.lock().await.get_audit_logs().await?;
+            Ok(events
+                .into_iter()
+                .filter(|event| event.occurred_at() < cutoff)
+                .take(limit)
+                .collect())
+        }
+    }
+
     /// Return the ids of all log sources the given object depends on.
     pub fn introspection_dependencies(&self, id: GlobalId) -> Vec<GlobalId> {
         self.state.introspection_dependencies(id)
@@ -1495,6 +1535,31 @@ impl ExprHumanizer for ConnCatalog<'_> {
     fn id_exists(&self, id: GlobalId) -> bool {
         self.state.entry_by_id.contains_key(&id)
     }
+
+    fn comments_for_id(&self, id: GlobalId) -> Vec<(Option<String>, String)> {
+        let Some(entry) = self.state.entry_by_id.get(&id) else {
+            return vec![];
+        };
+        let comment_id = self.state.get_comment_id(ObjectId::Item(id));
+        let Some(comments) = self.state.comments.get_object_comments(comment_id) else {
+            return vec![];
+        };
+        let desc = entry.desc(&self.resolve_full_name(entry.name())).ok();
+
+        comments
+            .iter()
+            .map(|(pos, comment)| {
+                // Column comments are keyed by the 1-based position of the column.
+                let column = pos.map(|pos| {
+                    desc.as_ref()
+                        .and_then(|desc| desc.iter_names().nth(pos - 1))
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| format!("#{}", pos - 1))
+                });
+                (column, comment.clone())
+            })
+            .collect()
+    }
 }
 
 impl SessionCatalog for ConnCatalog<'_> {
@@ -1509,6 +1574,13 @@ impl SessionCatalog for ConnCatalog<'_> {
             .flatten()
     }
 
+    fn get_prepared_statement(&self, name: &str) -> Option<&Statement<Raw>> {
+        self.prepared_statements
+            .as_ref()
+            .and_then(|ps| ps.get(name))
+            .and_then(|ps| ps.stmt())
+    }
+
     fn active_database(&self) -> Option<&DatabaseId> {
         self.database.as_ref()
     }
@@ -594,6 +594,8 @@ impl CatalogState {
                             },
                         ),
                         is_retained_metrics_object: table.is_retained_metrics_object,
+                        change_audit: false,
+                        row_count_hint: None,
                     }),
                     MZ_SYSTEM_ROLE_ID,
                     PrivilegeMap::from_mz_acl_items(acl_items),
@@ -1407,6 +1409,7 @@ impl CatalogState {
                     &log.variant.desc(),
                     &log.variant.index_by(),
                 ),
+                predicate: None,
                 conn_id: None,
                 resolved_ids: ResolvedIds(BTreeSet::from_iter([log_id])),
                 cluster_id,
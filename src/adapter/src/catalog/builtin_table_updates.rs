@@ -9,31 +9,37 @@
 
 mod notice;
 
+use std::collections::BTreeMap;
 use std::net::Ipv4Addr;
+use std::time::Duration;
 
 use bytesize::ByteSize;
 use mz_adapter_types::compaction::CompactionWindow;
+use mz_adapter_types::connection::ConnectionId;
 use mz_audit_log::{EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage};
 use mz_catalog::builtin::{
     BuiltinTable, MZ_AGGREGATES, MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_AWS_CONNECTIONS,
-    MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
-    MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES,
-    MZ_CLUSTER_SCHEDULES, MZ_CLUSTER_WORKLOAD_CLASSES, MZ_COLUMNS, MZ_COMMENTS, MZ_CONNECTIONS,
-    MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
+    MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BACKGROUND_TASKS, MZ_BASE_TYPES,
+    MZ_BUILTIN_TABLE_MIGRATIONS, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS, MZ_CLUSTER_REPLICA_METRICS,
+    MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES, MZ_CLUSTER_SCHEDULES,
+    MZ_CLUSTER_SCHEDULING_DECISIONS, MZ_CLUSTER_WORKLOAD_CLASSES, MZ_COLUMNS, MZ_COMMENTS,
+    MZ_CONNECTIONS, MZ_DATABASES, MZ_DATABASE_METRICS, MZ_DEFAULT_PRIVILEGES,
+    MZ_DEFERRED_MAINTENANCE, MZ_DEPENDENCY_GRAPH, MZ_EGRESS_IPS, MZ_FUNCTIONS,
     MZ_HISTORY_RETENTION_STRATEGIES, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS,
     MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES,
     MZ_MATERIALIZED_VIEWS, MZ_MATERIALIZED_VIEW_REFRESH_STRATEGIES, MZ_MYSQL_SOURCE_TABLES,
     MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_POSTGRES_SOURCE_TABLES,
-    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS, MZ_SCHEMAS, MZ_SECRETS,
-    MZ_SESSIONS, MZ_SINKS, MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD,
-    MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS,
-    MZ_WEBHOOKS_SOURCES,
+    MZ_PSEUDO_TYPES, MZ_REPLICA_UTILIZATION, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_ROLE_PARAMETERS,
+    MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SINK_LAG, MZ_SOURCES,
+    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STARVED_WRITE_LOCK_WAITERS, MZ_STORAGE_USAGE_BY_SHARD,
+    MZ_SUBSCRIPTIONS, MZ_SUBSCRIPTION_BUFFERS, MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES,
+    MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogItem, ClusterReplicaProcessStatus, ClusterVariant, Connection, DataSourceDesc, Func,
-    Index, MaterializedView, Sink, Table, Type, View,
+    CatalogItem, ClusterReplica, ClusterReplicaProcessStatus, ClusterVariant, Connection,
+    DataSourceDesc, Func, Index, MaterializedView, Sink, Table, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -43,8 +49,9 @@ use mz_controller::clusters::{
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::MirScalarExpr;
 use mz_orchestrator::{CpuLimit, DiskLimit, MemoryLimit, ServiceProcessMetrics};
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 use mz_ore::collections::CollectionExt;
+use mz_ore::now::EpochMillis;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
@@ -54,14 +61,14 @@ use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker, ScalarType, Timestamp};
 use mz_sql::ast::{CreateIndexStatement, Statement, UnresolvedItemName};
 use mz_sql::catalog::{
-    CatalogCluster, CatalogDatabase, CatalogSchema, CatalogType, DefaultPrivilegeObject,
-    TypeCategory,
+    CatalogCluster, CatalogDatabase, CatalogItem as SqlCatalogItem, CatalogSchema, CatalogType,
+    DefaultPrivilegeObject, TypeCategory,
 };
 use mz_sql::func::FuncImplCatalogDetails;
 use mz_sql::names::{
-    CommentObjectId, DatabaseId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
+    CommentObjectId, DatabaseId, ObjectId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
 };
-use mz_sql::plan::ClusterSchedule;
+use mz_sql::plan::{humanize_sql_for_show_create, ClusterSchedule};
 use mz_sql::session::user::SYSTEM_USER;
 use mz_sql::session::vars::SessionVars;
 use mz_sql_parser::ast::display::AstDisplay;
@@ -76,9 +83,41 @@ use mz_storage_types::sources::{
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
 use crate::active_compute_sink::ActiveSubscribe;
-use crate::catalog::CatalogState;
+use crate::catalog::{catalog_type_to_audit_object_type, CatalogState};
 use crate::coord::ConnMeta;
 
+/// An object as it is described in `mz_catalog_changes`.
+#[derive(Debug)]
+pub(super) struct CatalogChangeObject {
+    id: String,
+    object_type: ObjectType,
+    name: String,
+    /// The `CREATE` statement of items.
+    sql: Option<String>,
+}
+
+impl CatalogChangeObject {
+    /// Packs the row of `mz_catalog_changes` for the `change_type` change to the object, which
+    /// `user` made at `occurred_at`.
+    pub(super) fn pack(
+        &self,
+        change_type: &str,
+        user: Option<&str>,
+        occurred_at: EpochMillis,
+    ) -> Row {
+        let occurred_at = mz_ore::now::to_datetime(occurred_at);
+        Row::pack_slice(&[
+            Datum::String(&self.id),
+            Datum::String(&self.object_type.to_string()),
+            Datum::String(change_type),
+            Datum::String(&self.name),
+            Datum::from(self.sql.as_deref()),
+            Datum::from(user),
+            Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+        ])
+    }
+}
+
 /// An update to a built-in table.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BuiltinTableUpdate<T = GlobalId> {
@@ -91,13 +130,21 @@ pub struct BuiltinTableUpdate<T = GlobalId> {
 }
 
 impl CatalogState {
+    /// Resolves the builtin tables of `builtin_table_update`, each of which is looked up once.
     pub fn resolve_builtin_table_updates(
         &self,
         builtin_table_update: Vec<BuiltinTableUpdate<&'static BuiltinTable>>,
     ) -> Vec<BuiltinTableUpdate<GlobalId>> {
+        // Batches usually contain many updates to few tables.
+        let mut ids = BTreeMap::new();
         builtin_table_update
             .into_iter()
-            .map(|builtin_table_update| self.resolve_builtin_table_update(builtin_table_update))
+            .map(|BuiltinTableUpdate { id, row, diff }| {
+                let id = *ids
+                    .entry((id.schema, id.name))
+                    .or_insert_with(|| self.resolve_builtin_table(id));
+                BuiltinTableUpdate { id, row, diff }
+            })
             .collect()
     }
 
@@ -125,6 +172,36 @@ impl CatalogState {
         }
     }
 
+    /// Packs the edge of `mz_dependency_graph` from `depender` to `dependee`, which `depender`
+    /// references.
+    fn pack_dependency_graph_update(
+        &self,
+        depender: GlobalId,
+        dependee: GlobalId,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let dependency_type = match (
+            self.get_entry(&depender).item(),
+            self.get_entry(&dependee).item(),
+        ) {
+            (CatalogItem::Index(index), _) if index.on == dependee => "index",
+            (_, CatalogItem::Connection(_)) => "connection",
+            (_, CatalogItem::Secret(_)) => "secret",
+            (_, CatalogItem::Type(_)) => "type",
+            (_, CatalogItem::Func(_)) => "function",
+            _ => "data",
+        };
+        BuiltinTableUpdate {
+            id: &*MZ_DEPENDENCY_GRAPH,
+            row: Row::pack_slice(&[
+                Datum::String(&depender.to_string()),
+                Datum::String(&dependee.to_string()),
+                Datum::String(dependency_type),
+            ]),
+            diff,
+        }
+    }
+
     pub(super) fn pack_database_update(
         &self,
         database_id: &DatabaseId,
@@ -597,7 +674,8 @@ impl CatalogState {
             // Populate or clean up the `mz_object_dependencies` table.
             // TODO(jkosh44) Unclear if this table wants to include all uses or only references.
             for dependee in &entry.item().references().0 {
-                updates.push(self.pack_depends_update(id, *dependee, diff))
+                updates.push(self.pack_depends_update(id, *dependee, diff));
+                updates.push(self.pack_dependency_graph_update(id, *dependee, diff));
             }
         }
 
@@ -1264,6 +1342,7 @@ impl CatalogState {
                     diff,
                 });
             }
+            StorageSinkConnection::Webhook(_) => {}
         };
 
         let create_stmt = mz_sql::parse::parse(&sink.create_sql)
@@ -1689,6 +1768,77 @@ impl CatalogState {
         })
     }
 
+    /// Describes the object `id` for `mz_catalog_changes`, or returns `None` if the object does
+    /// not exist in this state.
+    pub(super) fn catalog_change_object(&self, id: &ObjectId) -> Option<CatalogChangeObject> {
+        let object = match id {
+            ObjectId::Item(id) => {
+                let entry = self.try_get_entry(id)?;
+                let name = self.resolve_full_name(entry.name(), entry.conn_id());
+                // Report the statement the way `SHOW CREATE` does, which is easier to diff than
+                // the statement with resolved IDs.
+                let sql = humanize_sql_for_show_create(
+                    &self.for_system_session(),
+                    *id,
+                    entry.create_sql(),
+                )
+                .unwrap_or_else(|_| entry.create_sql().to_string());
+                CatalogChangeObject {
+                    id: id.to_string(),
+                    object_type: catalog_type_to_audit_object_type(entry.item_type()),
+                    name: name.to_string(),
+                    sql: Some(sql),
+                }
+            }
+            ObjectId::Cluster(id) => CatalogChangeObject {
+                id: id.to_string(),
+                object_type: ObjectType::Cluster,
+                name: self.try_get_cluster(*id)?.name.clone(),
+                sql: None,
+            },
+            ObjectId::ClusterReplica((cluster_id, replica_id)) => {
+                let cluster = self.try_get_cluster(*cluster_id)?;
+                let replica = cluster.replica(*replica_id)?;
+                CatalogChangeObject {
+                    id: replica_id.to_string(),
+                    object_type: ObjectType::ClusterReplica,
+                    name: format!("{}.{}", cluster.name, replica.name),
+                    sql: None,
+                }
+            }
+            ObjectId::Database(id) => CatalogChangeObject {
+                id: id.to_string(),
+                object_type: ObjectType::Database,
+                name: self.database_by_id.get(id)?.name.clone(),
+                sql: None,
+            },
+            ObjectId::Schema((database_spec, SchemaSpecifier::Id(schema_id))) => {
+                let schema = match database_spec {
+                    ResolvedDatabaseSpecifier::Ambient => self.ambient_schemas_by_id.get(schema_id),
+                    ResolvedDatabaseSpecifier::Id(id) => self
+                        .database_by_id
+                        .get(id)
+                        .and_then(|database| database.schemas_by_id.get(schema_id)),
+                }?;
+                CatalogChangeObject {
+                    id: schema_id.to_string(),
+                    object_type: ObjectType::Schema,
+                    name: self.resolve_full_schema_name(&schema.name).to_string(),
+                    sql: None,
+                }
+            }
+            // Temporary schemas are not changed through catalog transactions.
+            ObjectId::Schema((_, SchemaSpecifier::Temporary)) => return None,
+            ObjectId::Role(id) => CatalogChangeObject {
+                id: id.to_string(),
+                object_type: ObjectType::Role,
+                name: self.try_get_role(id)?.name.clone(),
+                sql: None,
+            },
+        };
+        Some(object)
+    }
+
     pub fn pack_storage_usage_update(
         &self,
         VersionedStorageUsage::V1(event): &VersionedStorageUsage,
@@ -1748,6 +1898,64 @@ impl CatalogState {
         updates
     }
 
+    /// Packs the row of `mz_replica_utilization` for `replica`, from the latest metrics of its
+    /// processes, which the coordinator received at `heartbeat`.
+    pub fn pack_replica_utilization_update(
+        &self,
+        replica: &ClusterReplica,
+        metrics: &[ServiceProcessMetrics],
+        hydration_fraction: Option<f64>,
+        heartbeat: EpochMillis,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let (size, allocated_cpu_nano_cores, allocated_memory_bytes) =
+            match &replica.config.location {
+                ReplicaLocation::Managed(ManagedReplicaLocation {
+                    size, allocation, ..
+                }) => {
+                    let scale = u64::from(allocation.scale);
+                    let cpu = allocation
+                        .cpu_limit
+                        .map(|cpu_limit| cpu_limit.as_nanocpus().saturating_mul(scale));
+                    let memory = allocation
+                        .memory_limit
+                        .map(|MemoryLimit(ByteSize(bytes))| bytes.saturating_mul(scale));
+                    (Some(size.as_str()), cpu, memory)
+                }
+                ReplicaLocation::Unmanaged(_) => (None, None, None),
+            };
+        // The usage is only known if it is known for all processes.
+        let cpu_nano_cores: Option<u64> = metrics.iter().map(|m| m.cpu_nano_cores).sum();
+        let memory_bytes: Option<u64> = metrics.iter().map(|m| m.memory_bytes).sum();
+        let percent = |used: Option<u64>, allocated: Option<u64>| match (used, allocated) {
+            (Some(used), Some(allocated)) if allocated > 0 => {
+                Some(f64::cast_lossy(used) / f64::cast_lossy(allocated) * 100.0)
+            }
+            _ => None,
+        };
+        let heartbeat = mz_ore::now::to_datetime(heartbeat);
+
+        let row = Row::pack_slice(&[
+            Datum::String(&replica.replica_id.to_string()),
+            Datum::String(&replica.cluster_id.to_string()),
+            Datum::from(size),
+            u64::cast_from(metrics.len()).into(),
+            allocated_cpu_nano_cores.into(),
+            allocated_memory_bytes.into(),
+            cpu_nano_cores.into(),
+            memory_bytes.into(),
+            percent(cpu_nano_cores, allocated_cpu_nano_cores).into(),
+            percent(memory_bytes, allocated_memory_bytes).into(),
+            hydration_fraction.into(),
+            Datum::TimestampTz(heartbeat.try_into().expect("must fit")),
+        ]);
+        BuiltinTableUpdate {
+            id: &*MZ_REPLICA_UTILIZATION,
+            row,
+            diff,
+        }
+    }
+
     pub fn pack_all_replica_size_updates(&self) -> Vec<BuiltinTableUpdate<&'static BuiltinTable>> {
         let id = &*MZ_CLUSTER_REPLICA_SIZES;
         let updates = self
@@ -1823,6 +2031,207 @@ impl CatalogState {
         }
     }
 
+    /// Packs the buffer state of a cursor's subscribe last recorded in `reported_buffer`, if any.
+    pub fn pack_subscribe_buffer_update(
+        &self,
+        id: GlobalId,
+        subscribe: &ActiveSubscribe,
+        diff: Diff,
+    ) -> Option<BuiltinTableUpdate<&'static BuiltinTable>> {
+        let cursor = subscribe.buffer.cursor()?;
+        let (buffered_rows, paused) = subscribe.reported_buffer?;
+        Some(BuiltinTableUpdate {
+            id: &*MZ_SUBSCRIPTION_BUFFERS,
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::String(cursor),
+                Datum::UInt64(u64::cast_from(buffered_rows)),
+                Datum::from(paused),
+            ]),
+            diff,
+        })
+    }
+
+    /// Packs a row of `mz_background_tasks` for the background task `name`.
+    pub fn pack_background_task_update(
+        &self,
+        name: &str,
+        last_run_at: Option<EpochMillis>,
+        next_run_at: Option<EpochMillis>,
+        skip_next_run: bool,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let pack_time = |time: Option<EpochMillis>| match time {
+            Some(time) => {
+                Datum::TimestampTz(mz_ore::now::to_datetime(time).try_into().expect("must fit"))
+            }
+            None => Datum::Null,
+        };
+        BuiltinTableUpdate {
+            id: &*MZ_BACKGROUND_TASKS,
+            row: Row::pack_slice(&[
+                Datum::String(name),
+                pack_time(last_run_at),
+                pack_time(next_run_at),
+                Datum::from(skip_next_run),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_builtin_table_migrations` for the migration of the builtin table
+    /// `table_id`, which is complete if it has a `finished_at` time.
+    pub fn pack_builtin_table_migration_update(
+        &self,
+        table_id: GlobalId,
+        started_at: EpochMillis,
+        finished_at: Option<EpochMillis>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let pack_time =
+            |time| Datum::TimestampTz(mz_ore::now::to_datetime(time).try_into().expect("must fit"));
+        let status = if finished_at.is_some() {
+            "complete"
+        } else {
+            "pending"
+        };
+        BuiltinTableUpdate {
+            id: &*MZ_BUILTIN_TABLE_MIGRATIONS,
+            row: Row::pack_slice(&[
+                Datum::String(&table_id.to_string()),
+                Datum::String(status),
+                pack_time(started_at),
+                finished_at.map_or(Datum::Null, pack_time),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_database_metrics` for the database `database_id`.
+    pub fn pack_database_metrics_update(
+        &self,
+        database_id: &DatabaseId,
+        statements: u64,
+        statements_per_second: f64,
+        errors: u64,
+        result_bytes: u64,
+        peeks: u64,
+        mean_peek_seconds: Option<f64>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        BuiltinTableUpdate {
+            id: &*MZ_DATABASE_METRICS,
+            row: Row::pack_slice(&[
+                Datum::String(&database_id.to_string()),
+                Datum::UInt64(statements),
+                Datum::Float64(statements_per_second.into()),
+                Datum::UInt64(errors),
+                Datum::UInt64(result_bytes),
+                Datum::UInt64(peeks),
+                mean_peek_seconds
+                    .map(|seconds| Datum::Float64(seconds.into()))
+                    .unwrap_or(Datum::Null),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_sink_lag` for the sink `sink_id`.
+    pub fn pack_sink_lag_update(
+        &self,
+        sink_id: GlobalId,
+        upstream_frontier: Option<Timestamp>,
+        write_frontier: Option<Timestamp>,
+        lag: Option<Duration>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        BuiltinTableUpdate {
+            id: &*MZ_SINK_LAG,
+            row: Row::pack_slice(&[
+                Datum::String(&sink_id.to_string()),
+                Datum::from(upstream_frontier),
+                Datum::from(write_frontier),
+                lag.map(|lag| {
+                    Datum::Interval(Interval::from_duration(&lag).expect("lag fits an interval"))
+                })
+                .unwrap_or(Datum::Null),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_starved_write_lock_waiters` for the statement of connection `conn_id`.
+    pub fn pack_starved_write_lock_waiter_update(
+        &self,
+        conn_id: &ConnectionId,
+        statement: &str,
+        deferred_at: EpochMillis,
+        lock_holder: Option<&str>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let deferred_at = mz_ore::now::to_datetime(deferred_at);
+        BuiltinTableUpdate {
+            id: &*MZ_STARVED_WRITE_LOCK_WAITERS,
+            row: Row::pack_slice(&[
+                Datum::UInt32(conn_id.unhandled()),
+                Datum::String(statement),
+                Datum::TimestampTz(deferred_at.try_into().expect("must fit")),
+                Datum::from(lock_holder),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_deferred_maintenance` for the deferred maintenance `work`.
+    pub fn pack_deferred_maintenance_update(
+        &self,
+        work: &str,
+        reason: &str,
+        deferred_at: EpochMillis,
+        resumes_at: EpochMillis,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let deferred_at = mz_ore::now::to_datetime(deferred_at);
+        let resumes_at = mz_ore::now::to_datetime(resumes_at);
+        BuiltinTableUpdate {
+            id: &*MZ_DEFERRED_MAINTENANCE,
+            row: Row::pack_slice(&[
+                Datum::String(work),
+                Datum::String(reason),
+                Datum::TimestampTz(deferred_at.try_into().expect("must fit")),
+                Datum::TimestampTz(resumes_at.try_into().expect("must fit")),
+            ]),
+            diff,
+        }
+    }
+
+    /// Packs a row of `mz_cluster_scheduling_decisions` for the cluster `cluster_id`.
+    pub fn pack_cluster_scheduling_decision_update(
+        &self,
+        cluster_id: ClusterId,
+        cluster_on: bool,
+        suspension_deferred_until: Option<EpochMillis>,
+        diff: Diff,
+    ) -> BuiltinTableUpdate<&'static BuiltinTable> {
+        let suspension_deferred_until = match suspension_deferred_until {
+            Some(until) => Datum::TimestampTz(
+                mz_ore::now::to_datetime(until)
+                    .try_into()
+                    .expect("must fit"),
+            ),
+            None => Datum::Null,
+        };
+        BuiltinTableUpdate {
+            id: &*MZ_CLUSTER_SCHEDULING_DECISIONS,
+            row: Row::pack_slice(&[
+                Datum::String(&cluster_id.to_string()),
+                Datum::from(cluster_on),
+                suspension_deferred_until,
+            ]),
+            diff,
+        }
+    }
+
     pub fn pack_session_update(
         &self,
         conn: &ConnMeta,
@@ -95,6 +95,7 @@ impl Catalog {
         DataflowMetainfo {
             optimizer_notices,
             index_usage_types: df_meta.index_usage_types,
+            row_count_hints: df_meta.row_count_hints,
         }
     }
 }
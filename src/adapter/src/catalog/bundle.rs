@@ -0,0 +1,395 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Catalog bundles, which describe the user objects of a catalog as SQL.
+//!
+//! A bundle holds the statements that create the user databases, schemas and items of a
+//! catalog, in an order in which they can be executed: every object follows the objects
+//! it references. Applying the bundle to another environment recreates the objects. Roles,
+//! clusters and privileges are not part of a bundle, so the clusters that the objects are
+//! installed in must already exist in the target environment.
+//!
+//! Secrets are exported with redacted values, and must be updated with `ALTER SECRET` after
+//! the bundle is applied.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_repr::GlobalId;
+use mz_sql::ast::display::AstDisplay;
+use mz_sql::ast::visit::Visit;
+use mz_sql::ast::{
+    CreateDatabaseStatement, CreateSchemaStatement, DropObjectsStatement, Ident, ObjectType, Raw,
+    RawClusterName, RawItemName, Statement, UnresolvedDatabaseName, UnresolvedItemName,
+    UnresolvedObjectName, UnresolvedSchemaName,
+};
+use mz_sql::catalog::{CatalogItem as SqlCatalogItem, SessionCatalog};
+use mz_sql::names::PartialItemName;
+use mz_sql::normalize;
+use mz_sql::plan::humanize_sql_for_show_create;
+use mz_sql_parser::parser::StatementParseResult;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::Catalog;
+use crate::AdapterError;
+
+/// The user objects of a catalog, as the SQL statements that create them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CatalogBundle {
+    /// The statements, in an order in which they can be executed.
+    pub statements: Vec<String>,
+}
+
+/// A statement of a [`CatalogBundle`] that passed validation.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleStatement {
+    pub stmt: Statement<Raw>,
+    pub sql: String,
+    /// The statement that drops the object that `stmt` creates, if `stmt` creates an object
+    /// that needs to be dropped explicitly to undo it.
+    pub undo: Option<Statement<Raw>>,
+}
+
+impl Catalog {
+    /// Exports the user databases, schemas and items of the catalog as a [`CatalogBundle`].
+    ///
+    /// Temporary items are not exported, nor are subsources and progress collections, which
+    /// are created by the statements of their sources.
+    pub fn export_bundle(&self) -> Result<CatalogBundle, AdapterError> {
+        let conn_catalog = self.for_system_session();
+        let mut statements = Vec::new();
+
+        for database in self.databases().filter(|database| database.id.is_user()) {
+            let database_name = Ident::new_unchecked(database.name.clone());
+            statements.push(Statement::<Raw>::CreateDatabase(CreateDatabaseStatement {
+                name: UnresolvedDatabaseName(database_name.clone()),
+                if_not_exists: true,
+            }));
+            for schema in database.schemas_by_id.values() {
+                statements.push(Statement::CreateSchema(CreateSchemaStatement {
+                    name: UnresolvedSchemaName(vec![
+                        database_name.clone(),
+                        Ident::new_unchecked(schema.name.schema.clone()),
+                    ]),
+                    if_not_exists: true,
+                }));
+            }
+        }
+        let mut statements: Vec<_> = statements
+            .into_iter()
+            .map(|stmt| stmt.to_ast_string_stable())
+            .collect();
+
+        // Subsources and progress collections are created with their sources, so references to
+        // them are references to their sources.
+        let mut created_by = BTreeMap::new();
+        for entry in self.entries() {
+            if let Some((ingestion_id, _)) = entry.subsource_details() {
+                created_by.insert(entry.id(), ingestion_id);
+            }
+            if let Some(progress_id) = entry.progress_id() {
+                created_by.insert(progress_id, entry.id());
+            }
+        }
+        let items: BTreeMap<_, _> = self
+            .entries()
+            .filter(|entry| {
+                entry.id().is_user()
+                    && !entry.is_temporary()
+                    && !created_by.contains_key(&entry.id())
+            })
+            .map(|entry| (entry.id(), entry))
+            .collect();
+
+        // Order the items topologically, breaking ties by ID, which is the order in which
+        // most of them were created.
+        let mut references: BTreeMap<GlobalId, BTreeSet<GlobalId>> = BTreeMap::new();
+        let mut referenced_by: BTreeMap<GlobalId, BTreeSet<GlobalId>> = BTreeMap::new();
+        for (id, entry) in &items {
+            let item_references = references.entry(*id).or_default();
+            for reference in &entry.references().0 {
+                let reference = created_by.get(reference).unwrap_or(reference);
+                if reference != id && items.contains_key(reference) {
+                    item_references.insert(*reference);
+                    referenced_by.entry(*reference).or_default().insert(*id);
+                }
+            }
+        }
+        let mut ready: BTreeSet<_> = references
+            .iter()
+            .filter(|(_, item_references)| item_references.is_empty())
+            .map(|(id, _)| *id)
+            .collect();
+        let mut order = Vec::with_capacity(items.len());
+        while let Some(id) = ready.pop_first() {
+            order.push(id);
+            for dependent in referenced_by.remove(&id).into_iter().flatten() {
+                let dependent_references = references
+                    .get_mut(&dependent)
+                    .expect("dependent is an item");
+                dependent_references.remove(&id);
+                if dependent_references.is_empty() {
+                    ready.insert(dependent);
+                }
+            }
+        }
+        if order.len() != items.len() {
+            return Err(AdapterError::Internal(
+                "catalog items have cyclic references".into(),
+            ));
+        }
+
+        for id in order {
+            let item = conn_catalog.get_item(&id);
+            statements.push(humanize_sql_for_show_create(
+                &conn_catalog,
+                id,
+                item.create_sql(),
+            )?);
+        }
+
+        Ok(CatalogBundle { statements })
+    }
+}
+
+impl CatalogBundle {
+    /// Validates that the statements of the bundle can be applied to `catalog`.
+    ///
+    /// Each statement must be a single `CREATE` statement for a database, schema or item. The
+    /// items must not exist yet, and the clusters they are installed in must exist. Databases
+    /// and schemas that exist are left untouched if they are created with `IF NOT EXISTS`.
+    ///
+    /// The statements are not planned, as they may depend on the objects that earlier statements
+    /// create. Instead, the schemas that items are created in, and the fully qualified items that
+    /// statements reference, must exist in `catalog` or be created by an earlier statement, so
+    /// that a bundle that can't be applied is rejected before the first object is created.
+    pub(crate) fn validate(
+        &self,
+        catalog: &dyn SessionCatalog,
+    ) -> Result<Vec<BundleStatement>, AdapterError> {
+        let mut created = CreatedNames::default();
+        let mut validated = Vec::with_capacity(self.statements.len());
+        for sql in &self.statements {
+            let mut stmts = mz_sql::parse::parse(sql)?;
+            if stmts.len() != 1 {
+                coord_bail!(
+                    "catalog bundle statements must contain exactly one statement: {}",
+                    sql
+                );
+            }
+            let StatementParseResult { ast: stmt, sql } = stmts.pop().expect("known to exist");
+            let undo = validate_statement(catalog, &mut created, &stmt)?;
+            validated.push(BundleStatement {
+                stmt,
+                sql: sql.to_string(),
+                undo,
+            });
+        }
+        Ok(validated)
+    }
+}
+
+/// The names of the objects that the validated statements of a [`CatalogBundle`] create.
+#[derive(Debug, Default)]
+struct CreatedNames {
+    schemas: BTreeSet<(Option<String>, String)>,
+    items: BTreeSet<PartialItemName>,
+    /// The schemas that sources are created in. Sources create their subsources and progress
+    /// collections when they are purified, so their names aren't known in advance.
+    source_schemas: BTreeSet<(Option<String>, Option<String>)>,
+}
+
+impl CreatedNames {
+    fn schema_exists(
+        &self,
+        catalog: &dyn SessionCatalog,
+        database: Option<&String>,
+        schema: &String,
+    ) -> bool {
+        self.schemas.contains(&(database.cloned(), schema.clone()))
+            || catalog
+                .resolve_schema(database.map(|d| d.as_str()), schema)
+                .is_ok()
+    }
+
+    fn item_exists(&self, catalog: &dyn SessionCatalog, name: &PartialItemName) -> bool {
+        self.items.contains(name)
+            || self
+                .source_schemas
+                .contains(&(name.database.clone(), name.schema.clone()))
+            || catalog.resolve_item_or_type(name).is_ok()
+    }
+}
+
+/// Collects the fully qualified items that a statement references.
+#[derive(Debug, Default)]
+struct QualifiedReferences {
+    names: Vec<UnresolvedItemName>,
+    by_id: bool,
+}
+
+impl<'ast> Visit<'ast, Raw> for QualifiedReferences {
+    fn visit_item_name(&mut self, name: &'ast RawItemName) {
+        match name {
+            // Unqualified names may refer to common table expressions.
+            RawItemName::Name(name) if name.0.len() == 3 => self.names.push(name.clone()),
+            RawItemName::Name(_) => {}
+            RawItemName::Id(..) => self.by_id = true,
+        }
+    }
+}
+
+/// Validates one statement of a [`CatalogBundle`], records the objects it creates in
+/// `created`, and returns the statement that undoes it.
+fn validate_statement(
+    catalog: &dyn SessionCatalog,
+    created: &mut CreatedNames,
+    stmt: &Statement<Raw>,
+) -> Result<Option<Statement<Raw>>, AdapterError> {
+    let (object_type, name, in_cluster) = match stmt {
+        Statement::CreateDatabase(stmt) => {
+            let database = normalize::ident(stmt.name.0.clone());
+            // Every database is created with a `public` schema.
+            created
+                .schemas
+                .insert((Some(database.clone()), "public".into()));
+            if catalog.resolve_database(&database).is_ok() {
+                if !stmt.if_not_exists {
+                    coord_bail!("database '{}' already exists", stmt.name);
+                }
+                return Ok(None);
+            }
+            return Ok(Some(drop_statement(
+                ObjectType::Database,
+                UnresolvedObjectName::Database(stmt.name.clone()),
+            )));
+        }
+        Statement::CreateSchema(stmt) => {
+            let name = normalize::unresolved_schema_name(stmt.name.clone())?;
+            let exists = created.schema_exists(catalog, name.database.as_ref(), &name.schema);
+            created.schemas.insert((name.database, name.schema));
+            if exists {
+                if !stmt.if_not_exists {
+                    coord_bail!("schema '{}' already exists", stmt.name);
+                }
+                return Ok(None);
+            }
+            return Ok(Some(drop_statement(
+                ObjectType::Schema,
+                UnresolvedObjectName::Schema(stmt.name.clone()),
+            )));
+        }
+        Statement::CreateTable(stmt) => (ObjectType::Table, Some(stmt.name.clone()), None),
+        Statement::CreateTableFromSource(stmt) => {
+            (ObjectType::Table, Some(stmt.name.clone()), None)
+        }
+        Statement::CreateView(stmt) => (ObjectType::View, Some(stmt.definition.name.clone()), None),
+        Statement::CreateMaterializedView(stmt) => (
+            ObjectType::MaterializedView,
+            Some(stmt.name.clone()),
+            stmt.in_cluster.as_ref(),
+        ),
+        Statement::CreateSource(stmt) => (
+            ObjectType::Source,
+            Some(stmt.name.clone()),
+            stmt.in_cluster.as_ref(),
+        ),
+        Statement::CreateWebhookSource(stmt) => (
+            ObjectType::Source,
+            Some(stmt.name.clone()),
+            stmt.in_cluster.as_ref(),
+        ),
+        Statement::CreateSink(stmt) => (
+            ObjectType::Sink,
+            stmt.name.clone(),
+            stmt.in_cluster.as_ref(),
+        ),
+        Statement::CreateIndex(stmt) => {
+            // Indexes live in the schema of the relation they are on.
+            let name = stmt.name.as_ref().map(|name| {
+                let mut qualified = stmt.on_name.name().0.clone();
+                qualified.pop();
+                qualified.push(name.clone());
+                UnresolvedItemName(qualified)
+            });
+            (ObjectType::Index, name, stmt.in_cluster.as_ref())
+        }
+        Statement::CreateType(stmt) => (ObjectType::Type, Some(stmt.name.clone()), None),
+        Statement::CreateSecret(stmt) => (ObjectType::Secret, Some(stmt.name.clone()), None),
+        Statement::CreateConnection(stmt) => {
+            (ObjectType::Connection, Some(stmt.name.clone()), None)
+        }
+        _ => coord_bail!(
+            "catalog bundles only support CREATE statements for databases, schemas and items: {}",
+            stmt.to_ast_string_stable()
+        ),
+    };
+
+    if let Some(RawClusterName::Unresolved(cluster)) = in_cluster {
+        catalog.resolve_cluster(Some(cluster.as_str()))?;
+    }
+
+    let mut references = QualifiedReferences::default();
+    references.visit_statement(stmt);
+    if references.by_id {
+        coord_bail!(
+            "catalog bundle statements must not reference items by ID: {}",
+            stmt.to_ast_string_stable()
+        );
+    }
+    for reference in references.names {
+        let partial_name = normalize::unresolved_item_name(reference.clone())?;
+        if !created.item_exists(catalog, &partial_name) {
+            coord_bail!(
+                "catalog item '{}' is neither in the catalog nor created by an earlier statement",
+                reference
+            );
+        }
+    }
+
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let partial_name = normalize::unresolved_item_name(name.clone())?;
+    if let Some(schema) = &partial_name.schema {
+        if !created.schema_exists(catalog, partial_name.database.as_ref(), schema) {
+            coord_bail!(
+                "schema of catalog item '{}' is neither in the catalog nor created by an earlier statement",
+                name
+            );
+        }
+    }
+    if created.items.contains(&partial_name) || catalog.resolve_item_or_type(&partial_name).is_ok()
+    {
+        coord_bail!("catalog item '{}' already exists", name);
+    }
+    if object_type == ObjectType::Source {
+        created
+            .source_schemas
+            .insert((partial_name.database.clone(), partial_name.schema.clone()));
+    }
+    created.items.insert(partial_name);
+    Ok(Some(drop_statement(
+        object_type,
+        UnresolvedObjectName::Item(name),
+    )))
+}
+
+/// Returns the statement that drops the object `name`, if it exists.
+///
+/// The statement doesn't cascade: the statements of a bundle are undone in reverse order, so the
+/// objects that depend on the object are dropped first, unless they were created by someone else
+/// in the meantime, in which case the object is left in place.
+fn drop_statement(object_type: ObjectType, name: UnresolvedObjectName) -> Statement<Raw> {
+    Statement::DropObjects(DropObjectsStatement {
+        object_type,
+        if_exists: true,
+        names: vec![name],
+        cascade: false,
+    })
+}
@@ -1322,6 +1322,8 @@ mod builtin_migration_tests {
                     resolved_ids: ResolvedIds(BTreeSet::new()),
                     custom_logical_compaction_window: None,
                     is_retained_metrics_object: false,
+                    change_audit: false,
+                    row_count_hint: None,
                 }),
                 SimplifiedItem::MaterializedView { referenced_names } => {
                     let table_list = referenced_names
@@ -1369,6 +1371,7 @@ mod builtin_migration_tests {
                         create_sql: format!("CREATE INDEX idx ON materialize.public.{on} (a)"),
                         on: on_id,
                         keys: Vec::new(),
+                        predicate: None,
                         conn_id: None,
                         resolved_ids: ResolvedIds(BTreeSet::from_iter([on_id])),
                         cluster_id: ClusterId::User(1),
@@ -209,7 +209,7 @@ impl CatalogState {
             state,
             unresolvable_ids: BTreeSet::new(),
             conn_id: session.conn_id().clone(),
-            cluster: session.vars().cluster().into(),
+            cluster: self.preferred_cluster_name(session.vars().cluster()).into(),
             database,
             search_path,
             role_id: session.current_role_id().clone(),
@@ -814,6 +814,8 @@ impl CatalogState {
                 custom_logical_compaction_window: custom_logical_compaction_window
                     .or(table.compaction_window),
                 is_retained_metrics_object,
+                change_audit: table.change_audit,
+                row_count_hint: table.row_count_hint,
             }),
             Plan::CreateSource(CreateSourcePlan {
                 source,
@@ -922,6 +924,7 @@ impl CatalogState {
                 create_sql: index.create_sql,
                 on: index.on,
                 keys: index.keys,
+                predicate: index.predicate,
                 conn_id: None,
                 resolved_ids,
                 cluster_id: index.cluster_id,
@@ -1489,6 +1492,29 @@ impl CatalogState {
         Ok(&self.clusters_by_id[id])
     }
 
+    /// Returns the names of the clusters in `cluster`, the value of a `cluster` session variable,
+    /// in order of preference.
+    ///
+    /// The variable holds either the name of a single cluster or an ordered, comma-separated
+    /// failover list of clusters. An existing cluster whose name contains commas takes precedence
+    /// over the list.
+    pub fn cluster_failover_list<'a>(&self, cluster: &'a str) -> Vec<&'a str> {
+        if !cluster.contains(',') || self.clusters_by_name.contains_key(cluster) {
+            return vec![cluster];
+        }
+        cluster.split(',').map(str::trim).collect()
+    }
+
+    /// Returns the name of the first cluster in `cluster`, the value of a `cluster` session
+    /// variable, that exists, or the first name if none does.
+    pub fn preferred_cluster_name<'a>(&self, cluster: &'a str) -> &'a str {
+        let names = self.cluster_failover_list(cluster);
+        names
+            .iter()
+            .find(|name| self.clusters_by_name.contains_key(**name))
+            .unwrap_or(&names[0])
+    }
+
     pub fn resolve_builtin_cluster(&self, cluster: &BuiltinCluster) -> &Cluster {
         let id = self
             .clusters_by_name
@@ -33,7 +33,7 @@ use mz_ore::instrument;
 use mz_ore::now::EpochMillis;
 use mz_repr::adt::mz_acl_item::{merge_mz_acl_items, AclMode, MzAclItem, PrivilegeMap};
 use mz_repr::role_id::RoleId;
-use mz_repr::{strconv, GlobalId};
+use mz_repr::{strconv, GlobalId, Row};
 use mz_sql::catalog::{
     CatalogDatabase, CatalogError as SqlCatalogError, CatalogItem as SqlCatalogItem, CatalogRole,
     CatalogSchema, DefaultPrivilegeAclItem, DefaultPrivilegeObject, RoleAttributes, RoleMembership,
@@ -58,6 +58,7 @@ use crate::catalog::{
     UpdatePrivilegeVariant,
 };
 use crate::coord::cluster_scheduling::SchedulingDecision;
+use crate::coord::failpoints::{self, FailpointAction};
 use crate::coord::ConnMeta;
 use crate::util::ResultExt;
 use crate::AdapterError;
@@ -69,6 +70,11 @@ pub enum Op {
         value: Option<Value>,
         window: CompactionWindow,
     },
+    AlterTableRowCountHint {
+        id: GlobalId,
+        value: Option<Value>,
+        hint: Option<u64>,
+    },
     AlterRole {
         id: RoleId,
         name: String,
@@ -167,6 +173,11 @@ pub enum Op {
         name: String,
         config: ClusterConfig,
     },
+    UpdateDatabaseDefaultCluster {
+        id: DatabaseId,
+        name: String,
+        default_cluster_id: Option<ClusterId>,
+    },
     UpdateItem {
         id: GlobalId,
         name: QualifiedItemName,
@@ -177,6 +188,11 @@ pub enum Op {
         size_bytes: u64,
         collection_timestamp: EpochMillis,
     },
+    /// Permanently removes `events` from the audit log, e.g. because they have outlived the
+    /// audit log retention period.
+    PruneAuditLog {
+        events: Vec<VersionedEvent>,
+    },
     UpdateSystemConfiguration {
         name: String,
         value: OwnedVarInput,
@@ -295,6 +311,8 @@ impl ReplicaCreateDropReason {
 pub struct TransactionResult {
     pub builtin_table_updates: Vec<BuiltinTableUpdate>,
     pub audit_events: Vec<VersionedEvent>,
+    /// The rows of `mz_catalog_changes` that record the changes of the transaction.
+    pub catalog_changes: Vec<Row>,
 }
 
 impl Catalog {
@@ -348,11 +366,17 @@ impl Catalog {
         ops: Vec<Op>,
     ) -> Result<TransactionResult, AdapterError> {
         trace!("transact: {:?}", ops);
-        fail::fail_point!("catalog_transact", |arg| {
-            Err(AdapterError::Unstructured(anyhow::anyhow!(
-                "failpoint: {arg:?}"
-            )))
-        });
+        match failpoints::eval(failpoints::CATALOG_TRANSACT) {
+            Some(FailpointAction::Error(arg)) => return Err(failpoints::failpoint_error(arg)),
+            Some(FailpointAction::Delay(_)) => {
+                // Delaying the transaction would hold up the coordinator.
+                return Err(AdapterError::Unstructured(anyhow::anyhow!(
+                    "failpoint {} doesn't support delays",
+                    failpoints::CATALOG_TRANSACT
+                )));
+            }
+            None => {}
+        }
 
         let drop_ids: BTreeSet<GlobalId> = ops
             .iter()
@@ -382,6 +406,7 @@ impl Catalog {
         let temporary_ids = self.temporary_ids(&ops, temporary_drops)?;
         let mut builtin_table_updates = vec![];
         let mut audit_events = vec![];
+        let mut changed_objects = BTreeSet::new();
         let mut storage = self.storage().await;
         let mut tx = storage
             .transaction()
@@ -398,6 +423,7 @@ impl Catalog {
             temporary_ids,
             &mut builtin_table_updates,
             &mut audit_events,
+            &mut changed_objects,
             &mut tx,
             &mut state,
         )
@@ -414,6 +440,25 @@ impl Catalog {
         // Dropping here keeps the mutable borrow on self, preventing us accidentally
         // mutating anything until after f is executed.
         drop(storage);
+
+        // `mz_catalog_changes` is not derived from the durable catalog state, but from the
+        // changes of this transaction.
+        let user = session.map(|session| session.user().name.as_str());
+        let occurred_at = oracle_write_ts.into();
+        let mut catalog_changes = Vec::new();
+        for id in changed_objects.into_iter().filter(|id| id.is_user()) {
+            let before = self.state.catalog_change_object(&id);
+            let after = state.catalog_change_object(&id);
+            let row = match (before, after) {
+                (None, Some(object)) => object.pack("created", user, occurred_at),
+                (Some(_), Some(object)) => object.pack("altered", user, occurred_at),
+                (Some(object), None) => object.pack("dropped", user, occurred_at),
+                // The object was created and dropped in this transaction.
+                (None, None) => continue,
+            };
+            catalog_changes.push(row);
+        }
+
         self.state = state;
         self.transient_revision += 1;
 
@@ -431,10 +476,12 @@ impl Catalog {
         Ok(TransactionResult {
             builtin_table_updates,
             audit_events,
+            catalog_changes,
         })
     }
 
-    /// Performs the transaction described by `ops`.
+    /// Performs the transaction described by `ops`, and adds the IDs of the objects it changes to
+    /// `changed_objects`.
     ///
     /// # Panics
     /// - If `ops` contains [`Op::TransactionDryRun`] and the value is not the
@@ -449,6 +496,7 @@ impl Catalog {
         temporary_ids: Vec<GlobalId>,
         builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
         audit_events: &mut Vec<VersionedEvent>,
+        changed_objects: &mut BTreeSet<ObjectId>,
         tx: &mut Transaction<'_>,
         state: &mut CatalogState,
     ) -> Result<(), AdapterError> {
@@ -507,6 +555,27 @@ impl Catalog {
                     });
 
             let mut updates: Vec<_> = tx.get_and_commit_op_updates();
+            changed_objects.extend(updates.iter().filter_map(|update| match &update.kind {
+                StateUpdateKind::Item(item) => Some(ObjectId::Item(item.id)),
+                StateUpdateKind::Cluster(cluster) => Some(ObjectId::Cluster(cluster.id)),
+                StateUpdateKind::ClusterReplica(replica) => Some(ObjectId::ClusterReplica((
+                    replica.cluster_id,
+                    replica.replica_id,
+                ))),
+                StateUpdateKind::Database(database) => Some(ObjectId::Database(database.id)),
+                StateUpdateKind::Schema(schema) => {
+                    let database_spec = match schema.database_id {
+                        Some(id) => ResolvedDatabaseSpecifier::Id(id),
+                        None => ResolvedDatabaseSpecifier::Ambient,
+                    };
+                    Some(ObjectId::Schema((
+                        database_spec,
+                        SchemaSpecifier::Id(schema.id),
+                    )))
+                }
+                StateUpdateKind::Role(role) => Some(ObjectId::Role(role.id)),
+                _ => None,
+            }));
             updates.extend(temporary_item_updates);
             let op_builtin_table_updates = state.apply_updates(updates)?;
             let op_builtin_table_updates =
@@ -611,6 +680,31 @@ impl Catalog {
 
                 Self::log_update(state, &id);
             }
+            Op::AlterTableRowCountHint { id, value, hint } => {
+                let entry = state.get_entry(&id);
+                if id.is_system() {
+                    let name = entry.name();
+                    let full_name =
+                        state.resolve_full_name(name, session.map(|session| session.conn_id()));
+                    return Err(AdapterError::Catalog(Error::new(ErrorKind::ReadOnlyItem(
+                        full_name.to_string(),
+                    ))));
+                }
+
+                let mut new_entry = entry.clone();
+                new_entry
+                    .item
+                    .update_row_count_hint(value, hint)
+                    .map_err(|_| {
+                        AdapterError::Catalog(Error::new(ErrorKind::Internal(
+                            "planner should have rejected row count hint on non-table".to_string(),
+                        )))
+                    })?;
+
+                tx.update_item(id, new_entry.into())?;
+
+                Self::log_update(state, &id);
+            }
             Op::AlterRole {
                 id,
                 name,
@@ -1725,8 +1819,8 @@ impl Catalog {
                     Ok::<_, AdapterError>(())
                 };
 
-                // Update all of the items in the schema.
-                for (_name, item_id) in &schema.items {
+                // Update all of the items in the schema, including its types.
+                for (_name, item_id) in schema.items.iter().chain(&schema.types) {
                     // Update the item itself.
                     update_item(item_id)?;
 
@@ -1913,6 +2007,30 @@ impl Catalog {
                     }),
                 )?;
             }
+            Op::UpdateDatabaseDefaultCluster {
+                id,
+                name,
+                default_cluster_id,
+            } => {
+                let mut database = state.get_database(&id).clone();
+                database.default_cluster_id = default_cluster_id;
+                tx.update_database(id, database.into())?;
+                info!("update default cluster of database {}", name);
+
+                CatalogState::add_to_audit_log(
+                    &state.system_configuration,
+                    oracle_write_ts,
+                    session,
+                    tx,
+                    audit_events,
+                    EventType::Alter,
+                    ObjectType::Database,
+                    EventDetails::IdNameV1(mz_audit_log::IdNameV1 {
+                        id: id.to_string(),
+                        name,
+                    }),
+                )?;
+            }
             Op::UpdateItem { id, name, to_item } => {
                 let mut entry = state.get_entry(&id).clone();
                 entry.name = name.clone();
@@ -1949,6 +2067,9 @@ impl Catalog {
             } => {
                 tx.insert_storage_usage_event(shard_id, size_bytes, collection_timestamp)?;
             }
+            Op::PruneAuditLog { events } => {
+                tx.remove_audit_log_events(events);
+            }
             Op::UpdateSystemConfiguration { name, value } => {
                 let parsed_value = state.parse_system_configuration(&name, value.borrow())?;
                 tx.upsert_system_config(&name, parsed_value.clone())?;
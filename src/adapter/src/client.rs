@@ -11,6 +11,7 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
+use std::net::IpAddr;
 use std::pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -23,6 +24,7 @@ use itertools::Itertools;
 use mz_adapter_types::connection::{ConnectionId, ConnectionIdType};
 use mz_build_info::BuildInfo;
 use mz_compute_types::ComputeInstanceId;
+use mz_ore::cast::CastFrom;
 use mz_ore::channel::OneshotReceiverExt;
 use mz_ore::collections::CollectionExt;
 use mz_ore::id_gen::{org_id_conn_bits, IdAllocator, IdAllocatorInnerBitSet, MAX_ORG_ID};
@@ -33,6 +35,7 @@ use mz_ore::task::AbortOnDropHandle;
 use mz_ore::thread::JoinOnDropHandle;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::{GlobalId, Row, RowIterator, ScalarType};
+use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{Raw, Statement};
 use mz_sql::catalog::{EnvironmentId, SessionCatalog};
 use mz_sql::session::hint::ApplicationNameHint;
@@ -46,10 +49,11 @@ use tokio::sync::{mpsc, oneshot};
 use tracing::error;
 use uuid::Uuid;
 
-use crate::catalog::Catalog;
+use crate::catalog::{BundleStatement, Catalog, CatalogBundle};
 use crate::command::{
     CatalogDump, CatalogSnapshot, Command, ExecuteResponse, GetVariablesResponse, Response,
 };
+use crate::coord::simulation::SimulationStep;
 use crate::coord::{Coordinator, ExecuteContextExtra};
 use crate::error::AdapterError;
 use crate::metrics::Metrics;
@@ -239,7 +243,7 @@ impl Client {
 
         let cluster_active = session.vars().cluster().to_string();
         if session.vars().welcome_message() {
-            let cluster_info = if catalog.resolve_cluster(Some(&cluster_active)).is_err() {
+            let cluster_info = if catalog.resolve_cluster(None).is_err() {
                 format!("{cluster_active} (does not exist)")
             } else {
                 cluster_active.to_string()
@@ -283,7 +287,7 @@ Issue a SQL query to get started. Need help?
             .vars()
             .inspect(CLUSTER.name())
             .expect("cluster should exist");
-        if catalog.resolve_cluster(Some(&cluster_active)).is_err() {
+        if catalog.resolve_cluster(None).is_err() {
             let cluster_notice = 'notice: {
                 // If the user provided a cluster via a connection configuration parameter, do not
                 // notify them if that cluster does not exist. We omit the notice here because even
@@ -334,10 +338,19 @@ Issue a SQL query to get started. Need help?
     }
 
     /// Cancels the query currently running on the specified connection.
-    pub fn cancel_request(&mut self, conn_id: ConnectionIdType, secret_key: u32) {
+    ///
+    /// `origin` is the address of the client that sent the request, if known, which is recorded
+    /// in `mz_internal.mz_cancellation_history`.
+    pub fn cancel_request(
+        &mut self,
+        conn_id: ConnectionIdType,
+        secret_key: u32,
+        origin: Option<IpAddr>,
+    ) {
         self.send(Command::CancelRequest {
             conn_id,
             secret_key,
+            origin,
         });
     }
 
@@ -670,6 +683,83 @@ impl SessionClient {
         catalog.dump().map_err(AdapterError::from)
     }
 
+    /// Exports the user objects of the catalog as a [`CatalogBundle`].
+    ///
+    /// No authorization is performed, so access to this function must be limited to internal
+    /// servers or superusers.
+    pub async fn export_catalog_bundle(&mut self) -> Result<CatalogBundle, AdapterError> {
+        let catalog = self.catalog_snapshot().await;
+        catalog.export_bundle()
+    }
+
+    /// Creates the objects of `bundle`, and returns the number of statements in the bundle.
+    ///
+    /// All statements are validated against the catalog before the first one is executed,
+    /// including the objects they reference. With `dry_run`, none of them is executed. Otherwise
+    /// they are executed one after the other as this session's user, which purifies, plans and
+    /// authorizes them as usual. If a statement fails, the objects that the earlier statements
+    /// created are dropped, in reverse order and without cascading to objects that others created
+    /// in the meantime, before the error is returned.
+    pub async fn apply_catalog_bundle(
+        &mut self,
+        bundle: CatalogBundle,
+        dry_run: bool,
+    ) -> Result<usize, AdapterError> {
+        let catalog = self.catalog_snapshot().await;
+        let statements = bundle.validate(&catalog.for_session(self.session()))?;
+        let num_statements = statements.len();
+        if dry_run {
+            return Ok(num_statements);
+        }
+
+        let mut undo = Vec::new();
+        for BundleStatement {
+            stmt,
+            sql,
+            undo: stmt_undo,
+        } in statements
+        {
+            if let Err(e) = self.execute_bundle_statement(stmt, sql).await {
+                for stmt in undo.into_iter().rev() {
+                    let sql = stmt.to_ast_string_stable();
+                    if let Err(undo_err) = self.execute_bundle_statement(stmt, sql.clone()).await {
+                        error!("unable to undo catalog bundle with {sql}: {undo_err}");
+                    }
+                }
+                return Err(e);
+            }
+            undo.extend(stmt_undo);
+        }
+        Ok(num_statements)
+    }
+
+    /// Executes a statement of a [`CatalogBundle`] in its own transaction.
+    async fn execute_bundle_statement(
+        &mut self,
+        stmt: Statement<Raw>,
+        sql: String,
+    ) -> Result<(), AdapterError> {
+        const EMPTY_PORTAL: &str = "";
+        self.start_transaction(Some(1))?;
+        let result = match self.declare(EMPTY_PORTAL.into(), stmt, sql).await {
+            Ok(()) => self
+                .execute(EMPTY_PORTAL.into(), futures::future::pending(), None)
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => self
+                .end_transaction(EndTransactionAction::Commit)
+                .await
+                .map(|_| ()),
+            Err(e) => {
+                let _ = self.end_transaction(EndTransactionAction::Rollback).await;
+                Err(e)
+            }
+        }
+    }
+
     /// Checks the catalog for internal consistency, returning a JSON object describing the
     /// inconsistencies, if there are any.
     ///
@@ -695,10 +785,57 @@ impl SessionClient {
             })
     }
 
+    /// Runs a deterministic simulation of `script` against the coordinator, taking steps in the
+    /// order of `schedule`. Returns the steps that were taken or, if the coordinator became
+    /// inconsistent, a JSON object describing the steps and the inconsistencies.
+    ///
+    /// The coordinator processes nothing else while the simulation runs. No authorization is
+    /// performed, so access to this function must be limited to internal servers or superusers.
+    pub async fn simulate_coordinator(
+        &mut self,
+        script: Vec<Vec<SimulationStep>>,
+        schedule: Vec<usize>,
+    ) -> Result<Vec<(usize, String)>, serde_json::Value> {
+        let conns = script
+            .iter()
+            .flatten()
+            .filter_map(SimulationStep::conn)
+            .max()
+            .map_or(0, |conn| conn + 1);
+        let conn_ids = (0..conns)
+            .map(|_| self.inner().new_conn_id())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| serde_json::Value::String(e.to_string()))?;
+        let result = self
+            .send_without_session(|tx| Command::Simulate {
+                script,
+                schedule,
+                conn_ids,
+                tx,
+            })
+            .await
+            .map_err(|e| serde_json::Value::String(e.to_string()))?;
+        result.map_err(|failure| {
+            serde_json::to_value(failure).unwrap_or_else(|_| {
+                serde_json::Value::String("failed to serialize simulation failure".to_string())
+            })
+        })
+    }
+
     pub async fn dump_coordinator_state(&mut self) -> Result<serde_json::Value, anyhow::Error> {
         self.send_without_session(|tx| Command::Dump { tx }).await
     }
 
+    /// Returns the plan graph of the last statement of connection `conn_id`
+    /// whose optimization was traced, if any.
+    ///
+    /// No authorization is performed, so access to this function must be
+    /// limited to internal servers or superusers.
+    pub async fn get_plan_graph(&mut self, conn_id: ConnectionIdType) -> Option<serde_json::Value> {
+        self.send_without_session(|tx| Command::GetPlanGraph { conn_id, tx })
+            .await
+    }
+
     /// Allow the controller (and clusters they control) to now affect changes
     /// to external systems.
     ///
@@ -716,6 +853,13 @@ impl SessionClient {
         data: ExecuteContextExtra,
         reason: StatementEndedExecutionReason,
     ) {
+        // Statements that stream their results, like `SUBSCRIBE`, fail after the coordinator
+        // responded to them.
+        if let StatementEndedExecutionReason::Errored { .. } = &reason {
+            if let Some(errors) = self.session().metrics().database_statement_errors() {
+                errors.inc();
+            }
+        }
         if !data.is_trivial() {
             let cmd = Command::RetireExecute { data, reason };
             self.inner().send(cmd);
@@ -727,13 +871,21 @@ impl SessionClient {
     /// The rows only contain the columns positions in `columns`, so they
     /// must be re-encoded for adding the default values for the remaining
     /// ones.
+    ///
+    /// `buffered_bytes` is the number of bytes the caller buffered to decode
+    /// the rows, which is recorded in the statement log along with the size of
+    /// the rows themselves.
     pub async fn insert_rows(
         &mut self,
         id: GlobalId,
         columns: Vec<usize>,
         rows: Vec<Row>,
+        buffered_bytes: usize,
         ctx_extra: ExecuteContextExtra,
     ) -> Result<ExecuteResponse, AdapterError> {
+        let rows_bytes: usize = rows.iter().map(|row| row.byte_len()).sum();
+        let max_result_memory_bytes = u64::cast_from(buffered_bytes.saturating_add(rows_bytes));
+
         // TODO: Remove this clone once we always have the session. It's currently needed because
         // self.session returns a mut ref, so we can't call it twice.
         let pcx = self.session().pcx().clone();
@@ -752,9 +904,23 @@ impl SessionClient {
                 .and_then(|values| optimizer.optimize(values).err_into())
                 .and_then(|values| {
                     // Copied rows must always be constants.
-                    Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
+                    Coordinator::insert_constant(
+                        &catalog,
+                        self.session(),
+                        id,
+                        values.into_inner(),
+                        false,
+                    )
                 });
-        self.retire_execute(ctx_extra, (&result).into());
+        let mut reason = (&result).into();
+        if let StatementEndedExecutionReason::Success {
+            max_result_memory_bytes: max,
+            ..
+        } = &mut reason
+        {
+            *max = Some(max_result_memory_bytes);
+        }
+        self.retire_execute(ctx_extra, reason);
         result
     }
 
@@ -877,8 +1043,9 @@ impl SessionClient {
                 | Command::Terminate { .. }
                 | Command::RetireExecute { .. }
                 | Command::CheckConsistency { .. }
-                | Command::Dump { .. } => {}
-                Command::AllowWrites { .. } => {}
+                | Command::Dump { .. }
+                | Command::GetPlanGraph { .. } => {}
+                Command::AllowWrites { .. } | Command::Simulate { .. } => {}
             };
             cmd
         });
@@ -9,12 +9,14 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::Arc;
 
 use derivative::Derivative;
 use enum_kinds::EnumKind;
 use futures::future::BoxFuture;
+use futures::Stream;
 use mz_adapter_types::connection::{ConnectionId, ConnectionIdType};
 use mz_compute_types::ComputeInstanceId;
 use mz_ore::collections::CollectionExt;
@@ -32,12 +34,14 @@ use mz_sql_parser::ast::{AlterObjectRenameStatement, AlterOwnerStatement, DropOb
 use tokio::sync::{mpsc, oneshot};
 use uuid::Uuid;
 
+use crate::active_compute_sink::SubscribeRowStream;
 use crate::catalog::Catalog;
 use crate::coord::consistency::CoordinatorInconsistencies;
 use crate::coord::peek::PeekResponseUnary;
+use crate::coord::simulation::{SimulationResult, SimulationStep};
 use crate::coord::ExecuteContextExtra;
 use crate::error::AdapterError;
-use crate::session::{EndTransactionAction, RowBatchStream, Session};
+use crate::session::{EndTransactionAction, Session};
 use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
 use crate::util::Transmittable;
 use crate::webhook::AppendWebhookResponse;
@@ -84,6 +88,8 @@ pub enum Command {
     CancelRequest {
         conn_id: ConnectionIdType,
         secret_key: u32,
+        /// The address of the client that sent the request, if known.
+        origin: Option<IpAddr>,
     },
 
     PrivilegedCancelRequest {
@@ -132,9 +138,24 @@ pub enum Command {
         tx: oneshot::Sender<Result<serde_json::Value, anyhow::Error>>,
     },
 
+    GetPlanGraph {
+        conn_id: ConnectionIdType,
+        tx: oneshot::Sender<Option<serde_json::Value>>,
+    },
+
     AllowWrites {
         tx: oneshot::Sender<Result<bool, anyhow::Error>>,
     },
+
+    /// Runs a deterministic simulation against the coordinator. See
+    /// [`crate::coord::simulation`].
+    Simulate {
+        script: Vec<Vec<SimulationStep>>,
+        schedule: Vec<usize>,
+        /// The connection IDs of the simulated connections.
+        conn_ids: Vec<ConnectionId>,
+        tx: oneshot::Sender<Result<SimulationResult, AdapterError>>,
+    },
 }
 
 impl Command {
@@ -152,7 +173,9 @@ impl Command {
             | Command::RetireExecute { .. }
             | Command::CheckConsistency { .. }
             | Command::Dump { .. }
-            | Command::AllowWrites { .. } => None,
+            | Command::GetPlanGraph { .. }
+            | Command::AllowWrites { .. }
+            | Command::Simulate { .. } => None,
         }
     }
 
@@ -170,7 +193,9 @@ impl Command {
             | Command::RetireExecute { .. }
             | Command::CheckConsistency { .. }
             | Command::Dump { .. }
-            | Command::AllowWrites { .. } => None,
+            | Command::GetPlanGraph { .. }
+            | Command::AllowWrites { .. }
+            | Command::Simulate { .. } => None,
         }
     }
 }
@@ -184,6 +209,8 @@ pub struct Response<T> {
 
 pub type RowsFuture = Pin<Box<dyn Future<Output = PeekResponseUnary> + Send>>;
 
+pub type RowsStream = Box<dyn Stream<Item = PeekResponseUnary> + Unpin + Send + Sync>;
+
 /// The response to [`Client::startup`](crate::Client::startup).
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -274,6 +301,10 @@ pub enum ExecuteResponse {
     AlteredRole,
     /// The system configuration was altered.
     AlteredSystemConfiguration,
+    /// The query running on the requested connection was canceled.
+    CanceledQuery,
+    /// The cursor handed off by another connection was claimed.
+    ClaimedHandoff,
     /// The requested cursor was closed.
     ClosedCursor,
     /// The provided comment was created.
@@ -347,6 +378,8 @@ pub enum ExecuteResponse {
         count: Option<FetchDirection>,
         /// How long to wait for results to arrive.
         timeout: ExecuteTimeout,
+        /// Whether to return as soon as a progress message was fetched.
+        progress: bool,
         ctx_extra: ExecuteContextExtra,
     },
     /// The requested privilege was granted.
@@ -378,6 +411,14 @@ pub enum ExecuteResponse {
         #[derivative(Debug = "ignore")]
         rows: Box<dyn RowIterator + Send + Sync>,
     },
+    /// Like `SendingRows`, but the rows are delivered in batches via the
+    /// specified stream, which computes each batch when it is polled.
+    SendingRowsStreaming {
+        #[derivative(Debug = "ignore")]
+        rows: RowsStream,
+        instance_id: ComputeInstanceId,
+        strategy: StatementExecutionStrategy,
+    },
     /// The specified variable was set to a new value.
     SetVariable {
         name: String,
@@ -389,7 +430,7 @@ pub enum ExecuteResponse {
     /// Updates to the requested source or view will be streamed to the
     /// contained receiver.
     Subscribing {
-        rx: RowBatchStream,
+        rx: SubscribeRowStream,
         ctx_extra: ExecuteContextExtra,
         instance_id: ComputeInstanceId,
     },
@@ -465,6 +506,8 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::AlteredSystemConfiguration => {
                 Ok(ExecuteResponse::AlteredSystemConfiguration)
             }
+            ExecuteResponseKind::CanceledQuery => Ok(ExecuteResponse::CanceledQuery),
+            ExecuteResponseKind::ClaimedHandoff => Ok(ExecuteResponse::ClaimedHandoff),
             ExecuteResponseKind::ClosedCursor => Ok(ExecuteResponse::ClosedCursor),
             ExecuteResponseKind::Comment => Ok(ExecuteResponse::Comment),
             ExecuteResponseKind::Copied => Err(()),
@@ -515,6 +558,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::Updated => Err(()),
             ExecuteResponseKind::ValidatedConnection => Ok(ExecuteResponse::ValidatedConnection),
             ExecuteResponseKind::SendingRowsImmediate => Err(()),
+            ExecuteResponseKind::SendingRowsStreaming => Err(()),
             ExecuteResponseKind::CreatedIntrospectionSubscribe => {
                 Ok(ExecuteResponse::CreatedIntrospectionSubscribe)
             }
@@ -530,6 +574,8 @@ impl ExecuteResponse {
             AlteredObject(o) => Some(format!("ALTER {}", o)),
             AlteredRole => Some("ALTER ROLE".into()),
             AlteredSystemConfiguration => Some("ALTER SYSTEM".into()),
+            CanceledQuery => Some("CANCEL QUERY".into()),
+            ClaimedHandoff => Some("CLAIM HANDOFF".into()),
             ClosedCursor => Some("CLOSE CURSOR".into()),
             Comment => Some("COMMENT".into()),
             Copied(n) => Some(format!("COPY {}", n)),
@@ -576,7 +622,7 @@ impl ExecuteResponse {
             ReassignOwned => Some("REASSIGN OWNED".into()),
             RevokedPrivilege => Some("REVOKE".into()),
             RevokedRole => Some("REVOKE ROLE".into()),
-            SendingRows { .. } | SendingRowsImmediate { .. } => None,
+            SendingRows { .. } | SendingRowsImmediate { .. } | SendingRowsStreaming { .. } => None,
             SetVariable { reset: true, .. } => Some("RESET".into()),
             SetVariable { reset: false, .. } => Some("SET".into()),
             StartedTransaction { .. } => Some("BEGIN".into()),
@@ -613,13 +659,17 @@ impl ExecuteResponse {
             | AlterConnection
             | AlterSource
             | AlterSink
-            | AlterTableAddColumn => &[AlteredObject],
+            | AlterTableAddColumn
+            | AlterTableRowCountHint => &[AlteredObject],
             AlterDefaultPrivileges => &[AlteredDefaultPrivileges],
             AlterSetCluster => &[AlteredObject],
             AlterRole => &[AlteredRole],
             AlterSystemSet | AlterSystemReset | AlterSystemResetAll => {
                 &[AlteredSystemConfiguration]
             }
+            AlterSystemTerminateSessions => &[SendingRowsImmediate],
+            CancelQuery => &[CanceledQuery],
+            ClaimHandoff => &[ClaimedHandoff],
             Close => &[ClosedCursor],
             PlanKind::CopyFrom => &[ExecuteResponseKind::CopyFrom],
             PlanKind::CopyTo => &[ExecuteResponseKind::Copied],
@@ -646,11 +696,22 @@ impl ExecuteResponse {
             DropObjects => &[DroppedObject],
             DropOwned => &[DroppedOwned],
             PlanKind::EmptyQuery => &[ExecuteResponseKind::EmptyQuery],
-            ExplainPlan | ExplainPushdown | ExplainTimestamp | Select | ShowAllVariables
-            | ShowCreate | ShowColumns | ShowVariable | InspectShard | ExplainSinkSchema => &[
+            ExplainPlan
+            | ExplainPushdown
+            | ExplainAnalyze
+            | ExplainTimestamp
+            | Select
+            | ShowAllVariables
+            | ShowCreate
+            | ShowColumns
+            | ShowVariable
+            | ShowTransactionWrites
+            | InspectShard
+            | ExplainSinkSchema => &[
                 ExecuteResponseKind::CopyTo,
                 SendingRows,
                 SendingRowsImmediate,
+                SendingRowsStreaming,
             ],
             Execute | ReadThenWrite => &[
                 Deleted,
@@ -662,6 +723,7 @@ impl ExecuteResponse {
             PlanKind::Fetch => &[ExecuteResponseKind::Fetch],
             GrantPrivileges => &[GrantedPrivilege],
             GrantRole => &[GrantedRole],
+            HandoffCursor => &[SendingRowsImmediate],
             Insert => &[Inserted, SendingRowsImmediate],
             PlanKind::Prepare => &[ExecuteResponseKind::Prepare],
             PlanKind::Raise => &[ExecuteResponseKind::Raised],
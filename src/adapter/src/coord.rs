@@ -73,7 +73,7 @@ use mz_adapter_types::dyncfgs::{
     WITH_0DT_DEPLOYMENT_HYDRATION_CHECK_INTERVAL,
 };
 use mz_ore::channel::trigger;
-use mz_sql::names::ResolvedIds;
+use mz_sql::names::{ObjectId, ResolvedIds};
 use mz_sql::session::user::User;
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
@@ -110,6 +110,7 @@ use mz_compute_types::ComputeInstanceId;
 use mz_controller::clusters::{ClusterConfig, ClusterEvent, ClusterStatus, ProcessId};
 use mz_controller::ControllerConfig;
 use mz_controller_types::{ClusterId, ReplicaId, WatchSetId};
+use mz_expr::explain::ExplainSubscribe;
 use mz_expr::{MapFilterProject, OptimizedMirRelationExpr};
 use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::cast::CastFrom;
@@ -129,7 +130,7 @@ use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::{SecretsController, SecretsReader};
 use mz_sql::ast::{Raw, Statement};
-use mz_sql::catalog::{CatalogCluster, EnvironmentId};
+use mz_sql::catalog::{CatalogCluster, EnvironmentId, ObjectType};
 use mz_sql::optimizer_metrics::OptimizerMetrics;
 use mz_sql::plan::{self, AlterSinkPlan, CreateConnectionPlan, Params, QueryWhen};
 use mz_sql::session::vars::{ConnectionCounter, SystemVars};
@@ -140,6 +141,7 @@ use mz_storage_client::controller::{CollectionDescription, DataSource, DataSourc
 use mz_storage_types::connections::inline::{IntoInlineConnection, ReferencedConnection};
 use mz_storage_types::connections::Connection as StorageConnection;
 use mz_storage_types::connections::ConnectionContext;
+use mz_storage_types::controller::StorageError;
 use mz_storage_types::sinks::S3SinkFormat;
 use mz_storage_types::sources::Timeline;
 use mz_timestamp_oracle::postgres_oracle::{
@@ -156,7 +158,7 @@ use tokio::runtime::Handle as TokioHandle;
 use tokio::select;
 use tokio::sync::{mpsc, oneshot, watch, OwnedMutexGuard};
 use tokio::time::MissedTickBehavior;
-use tracing::{debug, info, info_span, span, warn, Instrument, Level, Span};
+use tracing::{debug, info, info_span, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use uuid::Uuid;
 
@@ -168,51 +170,112 @@ use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParam
 use crate::coord::appends::{
     BuiltinTableAppendNotify, Deferred, GroupCommitPermit, PendingWriteTxn,
 };
-use crate::coord::cluster_scheduling::SchedulingDecision;
+use crate::coord::az_advisories::AzAdvisories;
+use crate::coord::background_tasks::BackgroundTasks;
+use crate::coord::builtin_table_ids::BuiltinTableIds;
+use crate::coord::builtin_table_migrations::BuiltinTableMigration;
+use crate::coord::cancellation::RejectedCancellations;
+use crate::coord::cluster_scheduling::{ClusterSuspensions, SchedulingDecision};
+use crate::coord::compute_sink_limits::ComputeSinkWaitlist;
+use crate::coord::conn_registry::{ConnRegistry, ConnRegistryKey};
+use crate::coord::cursor_handoff::CursorHandoffs;
+use crate::coord::database_metrics::DatabaseMetrics;
+use crate::coord::external_tasks::{ExternalHost, ExternalTasks};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::introspection::IntrospectionSubscribe;
+use crate::coord::maintenance::MaintenanceState;
+use crate::coord::message_spans::MessageSpans;
+use crate::coord::oom_crash_loops::OomCrashLoops;
+use crate::coord::paged_cursors::{ComputedCursorPage, CursorPage, PagedCursors};
 use crate::coord::peek::PendingPeek;
+use crate::coord::peek_dedup::PeekDedup;
+use crate::coord::peek_mirror::PeekMirrorDiscrepancy;
 use crate::coord::read_policy::ReadHoldsInner;
+use crate::coord::segment_events::SegmentEvents;
+use crate::coord::sequencer::{InsufficientReplicaCapacity, ReplicaCreation};
+use crate::coord::sink_lag::SinkLag;
 use crate::coord::timeline::{TimelineContext, TimelineState};
 use crate::coord::timestamp_selection::{TimestampContext, TimestampDetermination};
 use crate::coord::validity::PlanValidity;
+use crate::coord::webhook_sinks::WebhookSinkDeliveries;
+use crate::coord::workload_class::WorkloadClassifier;
+use crate::coord::write_lock_waits::WriteLockWaits;
 use crate::error::AdapterError;
+use crate::explain::graph::PlanGraph;
 use crate::explain::insights::PlanInsightsContext;
 use crate::explain::optimizer_trace::{DispatchGuard, OptimizerTrace};
 use crate::metrics::Metrics;
+use crate::notice::DroppedInUseIndex;
 use crate::optimize::dataflows::{
     dataflow_import_id_bundle, ComputeInstanceSnapshot, DataflowBuilder,
 };
 use crate::optimize::{self, Optimize, OptimizerConfig};
 use crate::session::{EndTransactionAction, Session};
-use crate::statement_logging::{StatementEndedExecutionReason, StatementLifecycleEvent};
+use crate::statement_logging::{
+    StatementEndedExecutionReason, StatementExecutionMetadata, StatementLifecycleEvent,
+    StatementRetirement, StatementRetirementHooks,
+};
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ResultExt};
 use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter};
 use crate::{flags, AdapterNotice, ReadHolds, TimestampProvider};
 
 use self::statement_logging::{StatementLogging, StatementLoggingId};
 
+pub(crate) mod failpoints;
 pub(crate) mod id_bundle;
 pub(crate) mod in_memory_oracle;
+pub(crate) mod maintenance;
 pub(crate) mod peek;
 pub(crate) mod statement_logging;
 pub(crate) mod timeline;
 pub(crate) mod timestamp_selection;
 
 mod appends;
+mod az_advisories;
+mod background_tasks;
+mod builtin_table_ids;
+mod builtin_table_migrations;
+mod cancellation;
+mod catalog_follower;
 mod catalog_serving;
+mod catalog_vacuum;
+mod cluster_failover;
 pub mod cluster_scheduling;
 mod command_handler;
+mod compute_sink_limits;
+pub mod conn_registry;
 pub mod consistency;
+mod cursor_handoff;
+mod database_metrics;
+mod dataflow_shutdowns;
 mod ddl;
+mod external_tasks;
 mod indexes;
 mod introspection;
+mod leadership;
 mod message_handler;
+mod message_spans;
+mod oom_crash_loops;
+mod paged_cursors;
+mod peek_dedup;
+mod peek_mirror;
 mod privatelink_status;
 pub mod read_policy;
+mod segment_events;
 mod sequencer;
+pub mod simulation;
+mod sink_lag;
+mod sink_liveness;
 mod sql;
+mod statement_log_buffer;
+mod subscribe_archive;
+mod subscribe_backpressure;
+mod subscription_history;
+mod table_change_audit;
 mod validity;
+mod webhook_sinks;
+mod workload_class;
+mod write_lock_waits;
 
 #[derive(Debug)]
 pub enum Message<T = mz_repr::Timestamp> {
@@ -246,6 +309,70 @@ pub enum Message<T = mz_repr::Timestamp> {
     StorageUsageSchedule,
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    CatalogVacuum,
+    /// The expired audit log events found by a catalog vacuum, ready to be pruned.
+    CatalogVacuumReady {
+        ctx: Option<ExecuteContext>,
+        expired: Result<Vec<mz_audit_log::VersionedEvent>, AdapterError>,
+    },
+    DatabaseMetricsReport,
+    SinkLagReport,
+    /// Retires the compute sinks whose clients have gone away.
+    CheckComputeSinkLiveness,
+    /// Admits the statements that wait for the cluster to have compute sink capacity.
+    AdmitWaitingComputeSink(ClusterId),
+    /// A task that contacted the external systems has finished.
+    ExternalTaskFinished(BTreeSet<ExternalHost>),
+    /// The capacity check of cluster replicas that are to be created finished.
+    ReplicaCapacityChecked {
+        ctx: ExecuteContext,
+        creation: ReplicaCreation,
+        insufficient: Vec<InsufficientReplicaCapacity>,
+    },
+    /// Checks for deferred writes that have waited too long for the write lock.
+    CheckWriteLockWaits,
+    FlushSegmentEvents,
+    WebhookSinkDeliveryRecord,
+    /// Resumes a paused subscribe, whose client has drained its buffer.
+    ResumeSubscribe(GlobalId),
+    /// Closes a handed off cursor that was not claimed in time.
+    CursorHandoffExpired(String),
+    /// Resumes the dataflows paused on a replica after an OOM crash loop.
+    ResumeOomPausedHydrations {
+        replica_id: ReplicaId,
+        generation: u64,
+    },
+    /// Records advisories for the availability zones that have been degraded for long enough.
+    CheckAzAdvisories,
+    /// The delay injected by the `controller_process` failpoint has elapsed.
+    ControllerProcessDelayElapsed,
+    /// Records the aggregated rejected cancellation requests.
+    FlushRejectedCancellations,
+    /// Computes the next page of a paged cursor.
+    FetchCursorPage {
+        id: Uuid,
+        tx: oneshot::Sender<Option<CursorPage>>,
+    },
+    /// Records a page of a paged cursor that has been computed.
+    CursorPageComputed {
+        id: Uuid,
+        page: Result<ComputedCursorPage, StatementEndedExecutionReason>,
+    },
+    /// Closes a paged cursor whose client is done with it.
+    ClosePagedCursor(Uuid),
+    /// Releases the read holds of a paged cursor that has been open for too long.
+    PagedCursorExpired(Uuid),
+    /// Records a peek whose result differed from the result of its mirror.
+    PeekMirrorDiscrepancy(PeekMirrorDiscrepancy),
+    /// Migrates a builtin table whose previous contents have been read.
+    BuiltinTableMigrationSnapshot {
+        id: GlobalId,
+        contents: Result<Vec<(Row, Diff)>, StorageError<T>>,
+    },
+    /// Records that the migration of a builtin table has been written.
+    BuiltinTableMigrationComplete(GlobalId),
+    /// Changes of the leader to the durable catalog, observed by a read-only replica.
+    CatalogFollowerUpdates(Vec<mz_catalog::memory::objects::StateUpdate>),
 
     /// Performs any cleanup and logging actions necessary for
     /// finalizing a statement execution.
@@ -289,6 +416,8 @@ pub enum Message<T = mz_repr::Timestamp> {
         span: Span,
         stage: IntrospectionSubscribeStage,
     },
+    /// Reinstalls the failed introspection subscribe with the given ID, after a backoff.
+    ReinstallIntrospectionSubscribe(GlobalId),
     SecretStageReady {
         ctx: ExecuteContext,
         span: Span,
@@ -304,6 +433,11 @@ pub enum Message<T = mz_repr::Timestamp> {
         span: Span,
         stage: ExplainTimestampStage,
     },
+    DropObjectsStageReady {
+        ctx: ExecuteContext,
+        span: Span,
+        stage: DropObjectsStage,
+    },
     DrainStatementLog,
     PrivateLinkVpcEndpointEvents(Vec<VpcEndpointEvent>),
     CheckSchedulingPolicies,
@@ -333,7 +467,9 @@ impl Message {
                 Command::RetireExecute { .. } => "command-retire_execute",
                 Command::CheckConsistency { .. } => "command-check_consistency",
                 Command::Dump { .. } => "command-dump",
+                Command::GetPlanGraph { .. } => "command-get_plan_graph",
                 Command::AllowWrites { .. } => "command-allow-writes",
+                Command::Simulate { .. } => "command-simulate",
             },
             Message::ControllerReady => "controller_ready",
             Message::PurifiedStatementReady(_) => "purified_statement_ready",
@@ -349,6 +485,31 @@ impl Message {
             Message::StorageUsageSchedule => "storage_usage_schedule",
             Message::StorageUsageFetch => "storage_usage_fetch",
             Message::StorageUsageUpdate(_) => "storage_usage_update",
+            Message::CatalogVacuum => "catalog_vacuum",
+            Message::CatalogVacuumReady { .. } => "catalog_vacuum_ready",
+            Message::DatabaseMetricsReport => "database_metrics_report",
+            Message::SinkLagReport => "sink_lag_report",
+            Message::CheckComputeSinkLiveness => "check_compute_sink_liveness",
+            Message::AdmitWaitingComputeSink(_) => "admit_waiting_compute_sink",
+            Message::ExternalTaskFinished(_) => "external_task_finished",
+            Message::ReplicaCapacityChecked { .. } => "replica_capacity_checked",
+            Message::CheckWriteLockWaits => "check_write_lock_waits",
+            Message::FlushSegmentEvents => "flush_segment_events",
+            Message::WebhookSinkDeliveryRecord => "webhook_sink_delivery_record",
+            Message::ResumeSubscribe(_) => "resume_subscribe",
+            Message::CursorHandoffExpired(_) => "cursor_handoff_expired",
+            Message::ResumeOomPausedHydrations { .. } => "resume_oom_paused_hydrations",
+            Message::CheckAzAdvisories => "check_az_advisories",
+            Message::ControllerProcessDelayElapsed => "controller_process_delay_elapsed",
+            Message::FlushRejectedCancellations => "flush_rejected_cancellations",
+            Message::FetchCursorPage { .. } => "fetch_cursor_page",
+            Message::CursorPageComputed { .. } => "cursor_page_computed",
+            Message::ClosePagedCursor(_) => "close_paged_cursor",
+            Message::PagedCursorExpired(_) => "paged_cursor_expired",
+            Message::PeekMirrorDiscrepancy(_) => "peek_mirror_discrepancy",
+            Message::BuiltinTableMigrationSnapshot { .. } => "builtin_table_migration_snapshot",
+            Message::BuiltinTableMigrationComplete(_) => "builtin_table_migration_complete",
+            Message::CatalogFollowerUpdates(_) => "catalog_follower_updates",
             Message::RetireExecute { .. } => "retire_execute",
             Message::ExecuteSingleStatementTransaction { .. } => {
                 "execute_single_statement_transaction"
@@ -364,8 +525,10 @@ impl Message {
             Message::IntrospectionSubscribeStageReady { .. } => {
                 "introspection_subscribe_stage_ready"
             }
+            Message::ReinstallIntrospectionSubscribe(_) => "reinstall_introspection_subscribe",
             Message::SecretStageReady { .. } => "secret_stage_ready",
             Message::ClusterStageReady { .. } => "cluster_stage_ready",
+            Message::DropObjectsStageReady { .. } => "drop_objects_stage_ready",
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
@@ -536,6 +699,48 @@ pub struct PeekStageExplainPlan {
     df_meta: DataflowMetainfo,
     explain_ctx: ExplainPlanContext,
     insights_ctx: Option<PlanInsightsContext>,
+    /// The number of rows of each source, estimated from persist statistics.
+    row_estimates: BTreeMap<GlobalId, usize>,
+    /// The context needed to assemble the bundle of an `EXPLAIN BUNDLE`.
+    bundle_ctx: Option<PeekExplainBundleContext>,
+}
+
+/// The parts of an [`crate::explain::bundle::ExplainBundle`] that are only
+/// known before the peek is optimized.
+#[derive(Debug)]
+pub struct PeekExplainBundleContext {
+    sql: Option<String>,
+    source_ids: BTreeSet<GlobalId>,
+    id_bundle: CollectionIdBundle,
+    determination: TimestampDetermination<mz_repr::Timestamp>,
+}
+
+impl PeekExplainBundleContext {
+    /// Returns the bundle context of `plan` if `explain_ctx` explains a bundle.
+    fn new(
+        explain_ctx: &ExplainPlanContext,
+        plan: &mz_sql::plan::SelectPlan,
+        source_ids: BTreeSet<GlobalId>,
+        id_bundle: CollectionIdBundle,
+        determination: TimestampDetermination<mz_repr::Timestamp>,
+    ) -> Option<Self> {
+        if explain_ctx.stage != ExplainStage::Bundle {
+            return None;
+        }
+        let sql = plan.select.as_ref().map(|select| {
+            if explain_ctx.config.redacted {
+                select.to_ast_string_redacted()
+            } else {
+                select.to_ast_string()
+            }
+        });
+        Some(PeekExplainBundleContext {
+            sql,
+            source_ids,
+            id_bundle,
+            determination,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -759,6 +964,7 @@ pub enum SubscribeStage {
     OptimizeMir(SubscribeOptimizeMir),
     TimestampOptimizeLir(SubscribeTimestampOptimizeLir),
     Finish(SubscribeFinish),
+    Explain(SubscribeExplain),
 }
 
 #[derive(Debug)]
@@ -769,6 +975,7 @@ pub struct SubscribeOptimizeMir {
     dependency_ids: BTreeSet<GlobalId>,
     cluster_id: ComputeInstanceId,
     replica_id: Option<ReplicaId>,
+    explain_ctx: ExplainContext,
 }
 
 #[derive(Debug)]
@@ -780,6 +987,7 @@ pub struct SubscribeTimestampOptimizeLir {
     global_mir_plan: optimize::subscribe::GlobalMirPlan<optimize::subscribe::Unresolved>,
     dependency_ids: BTreeSet<GlobalId>,
     replica_id: Option<ReplicaId>,
+    explain_ctx: ExplainContext,
 }
 
 #[derive(Debug)]
@@ -792,6 +1000,19 @@ pub struct SubscribeFinish {
     dependency_ids: BTreeSet<GlobalId>,
 }
 
+#[derive(Debug)]
+pub struct SubscribeExplain {
+    validity: PlanValidity,
+    sink_id: GlobalId,
+    cluster_id: ComputeInstanceId,
+    plan: plan::SubscribePlan,
+    df_meta: DataflowMetainfo,
+    explain_ctx: ExplainPlanContext,
+    /// The parameters of the subscribe sink, if the optimizer got far enough to
+    /// determine its `as_of`.
+    subscribe: Option<ExplainSubscribe>,
+}
+
 #[derive(Debug)]
 pub enum IntrospectionSubscribeStage {
     OptimizeMir(IntrospectionSubscribeOptimizeMir),
@@ -866,6 +1087,30 @@ pub struct AlterSecret {
     plan: plan::AlterSecretPlan,
 }
 
+#[derive(Debug)]
+pub enum DropObjectsStage {
+    Batch(DropObjectsBatch),
+}
+
+#[derive(Debug)]
+pub struct DropObjectsBatch {
+    validity: PlanValidity,
+    object_type: ObjectType,
+    /// Whether the drop is part of an `ALTER` of an object of type `object_type`, e.g. `ALTER
+    /// SOURCE ... DROP SUBSOURCE`, rather than a `DROP`.
+    alter: bool,
+    /// The objects that remain to be dropped, split into batches in reverse dependency order.
+    /// Each batch is dropped in its own catalog transaction.
+    batches: VecDeque<Vec<ObjectId>>,
+    /// The number of objects dropped by earlier batches.
+    dropped: usize,
+    /// The total number of objects being dropped.
+    total: usize,
+    dropped_active_db: bool,
+    dropped_active_cluster: bool,
+    dropped_in_use_indexes: Vec<DroppedInUseIndex>,
+}
+
 /// An enum describing which cluster to run a statement on.
 ///
 /// One example usage would be that if a query depends only on system tables, we might
@@ -878,6 +1123,10 @@ pub enum TargetCluster {
     Active,
     /// The cluster selected at the start of a transaction.
     Transaction(ClusterId),
+    /// The cluster selected from the session's cluster failover list.
+    Failover(ClusterId),
+    /// A specific replica of a cluster, overriding the session's target replica.
+    Replica(ClusterId, ReplicaId),
 }
 
 /// Result types for each stage of a sequence.
@@ -979,6 +1228,12 @@ pub struct Config {
     /// A trigger that signals that all clusters have been hydrated. Only used
     /// during 0dt deployment, while in read-only mode.
     pub clusters_hydrated_trigger: Option<trigger::Trigger>,
+    /// A read-only handle to the durable catalog, used to follow the catalog
+    /// changes of the leader. Only set when running as a read-only replica,
+    /// which serves serializable reads and never becomes the leader.
+    pub catalog_follower: Option<Box<dyn mz_catalog::durable::DurableCatalogState>>,
+    /// The hooks to invoke after every statement execution.
+    pub statement_retirement_hooks: StatementRetirementHooks,
 }
 
 /// Soft-state metadata about a compute replica
@@ -986,6 +1241,8 @@ pub struct Config {
 pub struct ReplicaMetadata {
     /// The last known CPU and memory metrics
     pub metrics: Option<Vec<ServiceProcessMetrics>>,
+    /// The row of the replica in `mz_replica_utilization`.
+    pub utilization: Option<Row>,
 }
 
 /// Metadata about an active connection.
@@ -1019,6 +1276,10 @@ pub struct ConnMeta {
     /// WARNING: This role reference is not updated when the role is dropped.
     /// Consumers should not assume that this role exist.
     authenticated_role: RoleId,
+
+    /// The plan graph of the last statement whose optimization was traced, if any.
+    #[serde(skip)]
+    plan_graph: Option<PlanGraph>,
 }
 
 impl ConnMeta {
@@ -1207,33 +1468,52 @@ impl PendingRead {
 #[must_use]
 pub struct ExecuteContextExtra {
     statement_uuid: Option<StatementLoggingId>,
+    /// The metadata to pass to the statement retirement hooks, if any are installed.
+    retirement_metadata: Option<StatementExecutionMetadata>,
 }
 
 impl ExecuteContextExtra {
-    pub(crate) fn new(statement_uuid: Option<StatementLoggingId>) -> Self {
-        Self { statement_uuid }
+    pub(crate) fn new(
+        statement_uuid: Option<StatementLoggingId>,
+        retirement_metadata: Option<StatementExecutionMetadata>,
+    ) -> Self {
+        Self {
+            statement_uuid,
+            retirement_metadata,
+        }
     }
     pub fn is_trivial(&self) -> bool {
-        let Self { statement_uuid } = self;
-        statement_uuid.is_none()
+        let Self {
+            statement_uuid,
+            retirement_metadata,
+        } = self;
+        statement_uuid.is_none() && retirement_metadata.is_none()
     }
     pub fn contents(&self) -> Option<StatementLoggingId> {
-        let Self { statement_uuid } = self;
+        let Self { statement_uuid, .. } = self;
         *statement_uuid
     }
     /// Take responsibility for the contents.  This should only be
     /// called from code that knows what to do to finish up logging
     /// based on the inner value.
     #[must_use]
-    fn retire(mut self) -> Option<StatementLoggingId> {
-        let Self { statement_uuid } = &mut self;
-        statement_uuid.take()
+    fn retire(
+        mut self,
+    ) -> (
+        Option<StatementLoggingId>,
+        Option<StatementExecutionMetadata>,
+    ) {
+        let Self {
+            statement_uuid,
+            retirement_metadata,
+        } = &mut self;
+        (statement_uuid.take(), retirement_metadata.take())
     }
 }
 
 impl Drop for ExecuteContextExtra {
     fn drop(&mut self) {
-        let Self { statement_uuid } = &*self;
+        let Self { statement_uuid, .. } = &*self;
         if let Some(statement_uuid) = statement_uuid {
             // Note: the impact when this error hits
             // is that the statement will never be marked
@@ -1333,6 +1613,11 @@ impl ExecuteContext {
         } else {
             Some((&result).into())
         };
+        if result.is_err() {
+            if let Some(errors) = session.metrics().database_statement_errors() {
+                errors.inc();
+            }
+        }
         tx.send(result, session);
         if let Some(reason) = reason {
             if let Err(e) = internal_cmd_tx.send(Message::RetireExecute {
@@ -1594,11 +1879,13 @@ pub struct Coordinator {
     txn_read_holds: BTreeMap<ConnectionId, read_policy::ReadHolds<Timestamp>>,
 
     /// Access to the peek fields should be restricted to methods in the [`peek`] API.
-    /// A map from pending peek ids to the queue into which responses are sent, and
-    /// the connection id of the client that initiated the peek.
-    pending_peeks: BTreeMap<Uuid, PendingPeek>,
-    /// A map from client connection ids to a set of all pending peeks for that client.
-    client_pending_peeks: BTreeMap<ConnectionId, BTreeMap<Uuid, ClusterId>>,
+    /// The pending peeks of each client connection, including the queue into which
+    /// responses are sent.
+    pending_peeks: ConnRegistry<PendingPeek>,
+    /// The prefix of the UUIDs of all peeks issued by this coordinator.
+    peek_uuid_prefix: u64,
+    /// The compute peeks in flight that identical peeks can share.
+    peek_dedup: PeekDedup,
 
     /// A map from client connection ids to pending linearize read transaction.
     pending_linearize_read_txns: BTreeMap<ConnectionId, PendingReadTxn>,
@@ -1610,11 +1897,16 @@ pub struct Coordinator {
     /// A map from connection ids to a watch channel that is set to `true` if the connection
     /// received a cancel request.
     staged_cancellation: BTreeMap<ConnectionId, (watch::Sender<bool>, watch::Receiver<bool>)>,
+    /// Connections that were terminated with `ALTER SYSTEM TERMINATE SESSIONS`, which are closed
+    /// the next time they execute a statement.
+    terminated_conns: BTreeSet<ConnectionId>,
     /// Active introspection subscribes.
     introspection_subscribes: BTreeMap<GlobalId, IntrospectionSubscribe>,
 
     /// Holds plans deferred due to write lock.
     write_lock_wait_group: LockedVecDeque<Deferred>,
+    /// The last recorded holder of the write lock, and the starved waiters that were reported.
+    write_lock_waits: WriteLockWaits,
     /// Pending writes waiting for a group commit.
     pending_writes: Vec<PendingWriteTxn>,
     /// For the realtime timeline, an explicit SELECT or INSERT on a table will bump the
@@ -1663,6 +1955,8 @@ pub struct Coordinator {
     /// Segment analytics client.
     #[derivative(Debug = "ignore")]
     segment_client: Option<mz_segment::Client>,
+    /// Segment events that are batched before they are sent.
+    segment_events: SegmentEvents,
 
     /// Coordinator metrics.
     metrics: Metrics,
@@ -1675,6 +1969,9 @@ pub struct Coordinator {
     /// Data used by the statement logging feature.
     statement_logging: StatementLogging,
 
+    /// The hooks to invoke after every statement execution.
+    statement_retirement_hooks: StatementRetirementHooks,
+
     /// Limit for how many concurrent webhook requests we allow.
     webhook_concurrency_limit: WebhookConcurrencyLimiter,
 
@@ -1691,6 +1988,36 @@ pub struct Coordinator {
     /// periodically cleaned up from this Map.)
     cluster_scheduling_decisions: BTreeMap<ClusterId, BTreeMap<&'static str, SchedulingDecision>>,
 
+    /// The deferred shutdowns of scheduled clusters, and the decisions last recorded in
+    /// `mz_cluster_scheduling_decisions`.
+    cluster_suspensions: ClusterSuspensions,
+
+    /// The OOM kills of replicas, and the dataflows whose hydration is paused because they made
+    /// replicas crash loop.
+    oom_crash_loops: OomCrashLoops,
+
+    /// Whether processing the responses of the controller is delayed by the `controller_process`
+    /// failpoint.
+    controller_process_delayed: bool,
+
+    /// The cursors that have been handed off to other connections, but not yet claimed.
+    cursor_handoffs: CursorHandoffs,
+
+    /// The degraded availability zones, and whether we advised moving their replicas.
+    az_advisories: AzAdvisories,
+
+    /// The sampling of the spans of the messages the coordinator handles.
+    message_spans: MessageSpans,
+
+    /// The statements that wait for compute sinks of their clusters to be retired.
+    compute_sink_waitlist: ComputeSinkWaitlist,
+
+    /// The purification and connection validation tasks that contact external systems.
+    external_tasks: ExternalTasks,
+
+    /// The cursors whose results are computed in pages, as their clients fetch them.
+    paged_cursors: PagedCursors,
+
     /// When doing 0dt upgrades/in read-only mode, periodically ask all known
     /// clusters whether they are hydrated.
     check_clusters_hydrated_interval: tokio::time::Interval,
@@ -1699,11 +2026,11 @@ pub struct Coordinator {
     /// during 0dt deployment, while in read-only mode.
     clusters_hydrated_trigger: Option<trigger::Trigger>,
 
-    /// Tracks the state associated with the currently installed watchsets.
-    installed_watch_sets: BTreeMap<WatchSetId, (ConnectionId, WatchSetResponse)>,
+    /// Tracks the currently installed watchsets for each connection and their state.
+    installed_watch_sets: ConnRegistry<(WatchSetId, WatchSetResponse)>,
 
-    /// Tracks the currently installed watchsets for each connection.
-    connection_watch_sets: BTreeMap<ConnectionId, BTreeSet<WatchSetId>>,
+    /// Tracks the key in `installed_watch_sets` of each currently installed watchset.
+    watch_set_keys: BTreeMap<WatchSetId, ConnRegistryKey>,
 
     /// Tracks the statuses of all cluster replicas.
     cluster_replica_statuses: ClusterReplicaStatuses,
@@ -1713,6 +2040,11 @@ pub struct Coordinator {
     /// and should go away once we have proper orchestration during upgrades.
     read_only_controllers: bool,
 
+    /// Whether we are a read-only replica, which serves serializable reads
+    /// while following the catalog of the leader. Read-only replicas are never
+    /// promoted to leader.
+    read_only_replica: bool,
+
     /// Updates to builtin tables that are being buffered while we are in
     /// read-only mode. We apply these all at once when coming out of read-only
     /// mode.
@@ -1721,6 +2053,35 @@ pub struct Coordinator {
     /// `None` when we transition out of read-only mode and write out any
     /// buffered updates.
     buffered_builtin_table_updates: Option<Vec<BuiltinTableUpdate>>,
+
+    /// The decoded rules that classify statements into workload classes.
+    workload_classifier: WorkloadClassifier,
+
+    /// When disruptive background work last ran and which of it is deferred.
+    maintenance: MaintenanceState,
+
+    /// When each long-lived background task last ran and runs next.
+    background_tasks: BackgroundTasks,
+
+    /// The migrations of builtin tables that are still in progress after startup.
+    builtin_table_migrations: BTreeMap<GlobalId, BuiltinTableMigration>,
+
+    /// The IDs of the builtin tables resolved since the last catalog change.
+    builtin_table_ids: BuiltinTableIds,
+
+    /// The per-database load last recorded in `mz_database_metrics`.
+    database_metrics: DatabaseMetrics,
+
+    /// The lag of each sink last recorded in `mz_sink_lag`.
+    sink_lag: SinkLag,
+
+    /// The delivery progress of each webhook sink last recorded in
+    /// `mz_webhook_sink_delivery_history`.
+    webhook_sink_deliveries: WebhookSinkDeliveries,
+
+    /// The rejected cancellation requests that are not yet recorded in
+    /// `mz_cancellation_history`.
+    rejected_cancellations: RejectedCancellations,
 }
 
 impl Coordinator {
@@ -1862,6 +2223,10 @@ impl Coordinator {
 
         let mut privatelink_connections = BTreeMap::new();
 
+        // Webhook sinks resume from the delivery progress they last recorded.
+        self.bootstrap_webhook_sink_deliveries().await?;
+        self.bootstrap_az_advisories().await?;
+
         let local_read_ts_for_index_bootstrapping = self.get_local_read_ts().await;
 
         for entry in &entries {
@@ -2070,6 +2435,12 @@ impl Coordinator {
                     // point compute will have put in its own read holds.
                     drop(read_holds);
                 }
+                CatalogItem::Sink(sink) if sink.connection.is_compute_sink() => {
+                    let id = entry.id();
+                    self.create_webhook_sink_dataflow(id, sink)
+                        .await
+                        .unwrap_or_terminate("cannot fail to create webhook sink dataflows");
+                }
                 CatalogItem::Sink(sink) => {
                     let id = entry.id();
                     self.create_storage_export(id, sink)
@@ -2347,10 +2718,21 @@ impl Coordinator {
         // Add builtin table updates the clear the contents of all system tables
         debug!("coordinator init: resetting system tables");
         let read_ts = self.get_local_read_ts().await;
+        let async_migrations = self.defer_builtin_table_migrations(&mut builtin_table_updates);
         for system_table in entries
             .iter()
             .filter(|entry| entry.is_table() && entry.id().is_system())
         {
+            if async_migrations.contains(&system_table.id()) {
+                debug!(
+                    "coordinator init: migrating system table {} ({}) in the background",
+                    self.catalog().resolve_full_name(system_table.name(), None),
+                    system_table.id()
+                );
+                self.start_builtin_table_migration(system_table.id(), read_ts)
+                    .await;
+                continue;
+            }
             debug!(
                 "coordinator init: resetting system table {} ({})",
                 self.catalog().resolve_full_name(system_table.name(), None),
@@ -2553,7 +2935,12 @@ impl Coordinator {
                     );
 
                     // MIR ⇒ MIR optimization (global)
-                    let index_plan = optimize::index::Index::new(entry.name(), &idx.on, &idx.keys);
+                    let index_plan = optimize::index::Index::new(
+                        entry.name(),
+                        &idx.on,
+                        &idx.keys,
+                        idx.predicate.as_ref(),
+                    );
                     let global_mir_plan = optimizer.optimize(index_plan)?;
                     let optimized_plan = global_mir_plan.df_desc().clone();
 
@@ -2989,10 +3376,18 @@ impl Coordinator {
                 }
             });
 
+            self.report_background_tasks();
             self.schedule_storage_usage_collection().await;
+            self.schedule_catalog_vacuum();
+            self.schedule_database_metrics_report();
+            self.schedule_sink_lag_report();
+            self.schedule_write_lock_wait_check();
+            self.schedule_compute_sink_liveness_check();
+            self.schedule_webhook_sink_delivery_record();
             self.spawn_privatelink_vpc_endpoints_watch_task();
             self.spawn_statement_logging_task();
             flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
+            self.update_message_span_sampling();
 
             // Report if the handling of a single message takes longer than this threshold.
             let warn_threshold = self
@@ -3017,7 +3412,7 @@ impl Coordinator {
                     Some(event) = cluster_events.next() => Message::ClusterEvent(event),
                     // See [`mz_controller::Controller::Controller::ready`] for notes
                     // on why this is cancel-safe.
-                    () = self.controller.ready() => {
+                    () = self.controller.ready(), if !self.controller_process_delayed => {
                         Message::ControllerReady
                     }
                     // See [`appends::GroupCommitWaiter`] for notes on why this is cancel safe.
@@ -3048,6 +3443,9 @@ impl Coordinator {
                     m = cmd_rx.recv() => match m {
                         None => break,
                         Some((otel_ctx, m)) => {
+                            self.metrics
+                                .pending_commands
+                                .set(u64::cast_from(cmd_rx.len()));
                             Message::Command(otel_ctx, m)
 
                         }
@@ -3120,14 +3518,10 @@ impl Coordinator {
                 };
 
                 // All message processing functions trace. Start a parent span
-                // for them to make it easy to find slow messages.
+                // for them to make it easy to find slow messages, unless the
+                // messages of this kind are sampled.
                 let msg_kind = msg.kind();
-                let span = span!(
-                    target: "mz_adapter::coord::handle_message_loop",
-                    Level::INFO,
-                    "coord::handle_message",
-                    kind = msg_kind
-                );
+                let span = self.message_spans.span(msg_kind);
                 let otel_context = span.context().span().span_context().clone();
 
                 // Record the last kind of message in case we get stuck. For
@@ -3150,6 +3544,37 @@ impl Coordinator {
                     },
                 };
 
+                // Simulations take over the main loop until they finish. See
+                // [`simulation::simulate`].
+                let msg = match msg {
+                    Message::Command(
+                        _,
+                        Command::Simulate {
+                            script,
+                            schedule,
+                            conn_ids,
+                            tx,
+                        },
+                    ) => {
+                        let result = simulation::simulate(
+                            &mut self,
+                            simulation::Inputs {
+                                internal_cmd_rx: &mut internal_cmd_rx,
+                                strict_serializable_reads_rx: &mut strict_serializable_reads_rx,
+                                dropped_read_holds_rx: &mut dropped_read_holds_rx,
+                            },
+                            conn_ids,
+                            script,
+                            schedule,
+                        )
+                        .instrument(span)
+                        .await;
+                        let _ = tx.send(result);
+                        continue;
+                    }
+                    msg => msg,
+                };
+
                 let start = Instant::now();
                 self.handle_message(span, msg).await;
                 let duration = start.elapsed();
@@ -3205,6 +3630,16 @@ impl Coordinator {
         // object. This makes this method and owned_catalog both very cheap: at
         // most one clone per catalog mutation, but only if there's a read-only
         // reference to it.
+        //
+        // Those clones are the price mutations pay for long-lived snapshots, so
+        // we keep track of them.
+        let snapshots = Arc::strong_count(&self.catalog) - 1;
+        self.metrics
+            .catalog_outstanding_snapshots
+            .set(u64::cast_from(snapshots));
+        let _timer =
+            (snapshots > 0).then(|| self.metrics.catalog_copy_on_write_seconds.start_timer());
+        self.builtin_table_ids.invalidate();
         Arc::make_mut(&mut self.catalog)
     }
 
@@ -3225,6 +3660,27 @@ impl Coordinator {
         }
     }
 
+    /// Publishes a notice message to the session of `conn_id`, if it is still connected.
+    pub(crate) fn send_notice(&self, conn_id: &ConnectionId, notice: AdapterNotice) {
+        if let Some(meta) = self.active_conns.get(conn_id) {
+            let _ = meta.notice_tx.send(notice);
+        }
+    }
+
+    /// Publishes a notice message to the sessions whose role is a member of `owner_id`, i.e. the
+    /// sessions that own the object the notice is about.
+    pub(crate) fn send_notice_to_owner(&self, owner_id: &RoleId, notice: AdapterNotice) {
+        for (conn_id, conn) in &self.active_conns {
+            let roles = self
+                .catalog()
+                .state()
+                .collect_role_membership(conn.authenticated_role_id());
+            if roles.contains(owner_id) {
+                self.send_notice(conn_id, notice.clone());
+            }
+        }
+    }
+
     pub(crate) fn active_conns(&self) -> &BTreeMap<ConnectionId, ConnMeta> {
         &self.active_conns
     }
@@ -3235,8 +3691,15 @@ impl Coordinator {
         reason: StatementEndedExecutionReason,
         ctx_extra: ExecuteContextExtra,
     ) {
-        if let Some(uuid) = ctx_extra.retire() {
-            self.end_statement_execution(uuid, reason);
+        let (statement_uuid, retirement_metadata) = ctx_extra.retire();
+        let began = statement_uuid.map(|uuid| self.end_statement_execution(uuid, reason.clone()));
+        if let Some(metadata) = retirement_metadata {
+            self.statement_retirement_hooks.invoke(StatementRetirement {
+                metadata,
+                began,
+                reason,
+                ended_at: self.now(),
+            });
         }
     }
 
@@ -3288,11 +3751,8 @@ impl Coordinator {
         state: WatchSetResponse,
     ) {
         let ws_id = self.controller.install_compute_watch_set(objects, t);
-        self.connection_watch_sets
-            .entry(conn_id.clone())
-            .or_default()
-            .insert(ws_id);
-        self.installed_watch_sets.insert(ws_id, (conn_id, state));
+        let key = self.installed_watch_sets.insert(conn_id, (ws_id, state));
+        self.watch_set_keys.insert(ws_id, key);
     }
 
     /// Install a _watch set_ in the controller that is automatically associated with the given
@@ -3306,19 +3766,14 @@ impl Coordinator {
         state: WatchSetResponse,
     ) {
         let ws_id = self.controller.install_storage_watch_set(objects, t);
-        self.connection_watch_sets
-            .entry(conn_id.clone())
-            .or_default()
-            .insert(ws_id);
-        self.installed_watch_sets.insert(ws_id, (conn_id, state));
+        let key = self.installed_watch_sets.insert(conn_id, (ws_id, state));
+        self.watch_set_keys.insert(ws_id, key);
     }
 
     /// Cancels pending watchsets associated with the provided connection id.
     pub fn cancel_pending_watchsets(&mut self, conn_id: &ConnectionId) {
-        if let Some(ws_ids) = self.connection_watch_sets.remove(conn_id) {
-            for ws_id in ws_ids {
-                self.installed_watch_sets.remove(&ws_id);
-            }
+        for (_, (ws_id, _)) in self.installed_watch_sets.remove_connection(conn_id) {
+            self.watch_set_keys.remove(&ws_id);
         }
     }
 
@@ -3352,21 +3807,16 @@ impl Coordinator {
             .map(|(id, capability)| (id.unhandled().to_string(), format!("{capability:?}")))
             .collect();
         let pending_peeks: BTreeMap<_, _> = self
-            .pending_peeks
-            .iter()
+            .pending_peeks()
             .map(|(id, peek)| (id.to_string(), format!("{peek:?}")))
             .collect();
-        let client_pending_peeks: BTreeMap<_, _> = self
-            .client_pending_peeks
-            .iter()
-            .map(|(id, peek)| {
-                let peek: BTreeMap<_, _> = peek
-                    .iter()
-                    .map(|(uuid, storage_id)| (uuid.to_string(), storage_id))
-                    .collect();
-                (id.to_string(), peek)
-            })
-            .collect();
+        let mut client_pending_peeks: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+        for (uuid, peek) in self.pending_peeks() {
+            client_pending_peeks
+                .entry(peek.conn_id.to_string())
+                .or_default()
+                .insert(uuid.to_string(), peek.cluster_id);
+        }
         let pending_linearize_read_txns: BTreeMap<_, _> = self
             .pending_linearize_read_txns
             .iter()
@@ -3621,6 +4071,8 @@ pub fn serve(
         read_only_controllers,
         enable_0dt_deployment,
         clusters_hydrated_trigger,
+        catalog_follower,
+        statement_retirement_hooks,
     }: Config,
 ) -> BoxFuture<'static, Result<(Handle, Client), AdapterError>> {
     async move {
@@ -3778,6 +4230,7 @@ pub fn serve(
 
         let session_id = catalog.config().session_id;
         let start_instant = catalog.config().start_instant;
+        let deploy_generation = controller_config.deploy_generation;
 
         // In order for the coordinator to support Rc and Refcell types, it cannot be
         // sent across threads. Spawn it in a thread and have this parent thread wait
@@ -3865,15 +4318,18 @@ pub fn serve(
                     storage_read_capabilities: Default::default(),
                     compute_read_capabilities: Default::default(),
                     txn_read_holds: Default::default(),
-                    pending_peeks: BTreeMap::new(),
-                    client_pending_peeks: BTreeMap::new(),
+                    pending_peeks: ConnRegistry::default(),
+                    peek_uuid_prefix: Uuid::new_v4().as_u64_pair().0,
+                    peek_dedup: PeekDedup::default(),
                     pending_linearize_read_txns: BTreeMap::new(),
                     serialized_ddl: LockedVecDeque::new(),
                     active_compute_sinks: BTreeMap::new(),
                     active_webhooks: BTreeMap::new(),
                     staged_cancellation: BTreeMap::new(),
+                    terminated_conns: BTreeSet::new(),
                     introspection_subscribes: BTreeMap::new(),
                     write_lock_wait_group: LockedVecDeque::new(),
+                    write_lock_waits: WriteLockWaits::default(),
                     pending_writes: Vec::new(),
                     advance_timelines_interval,
                     secrets_controller,
@@ -3883,21 +4339,42 @@ pub fn serve(
                     storage_usage_client,
                     storage_usage_collection_interval,
                     segment_client,
+                    segment_events: SegmentEvents::default(),
                     metrics,
                     optimizer_metrics,
                     tracing_handle,
                     statement_logging: StatementLogging::new(coord_now.clone()),
+                    statement_retirement_hooks,
                     webhook_concurrency_limit,
                     pg_timestamp_oracle_config,
                     check_cluster_scheduling_policies_interval: check_scheduling_policies_interval,
                     cluster_scheduling_decisions: BTreeMap::new(),
+                    cluster_suspensions: ClusterSuspensions::default(),
+                    oom_crash_loops: OomCrashLoops::default(),
+                    controller_process_delayed: false,
+                    cursor_handoffs: CursorHandoffs::default(),
+                    az_advisories: AzAdvisories::default(),
+                    message_spans: MessageSpans::default(),
+                    compute_sink_waitlist: ComputeSinkWaitlist::default(),
+                    external_tasks: ExternalTasks::default(),
+                    paged_cursors: PagedCursors::default(),
                     check_clusters_hydrated_interval,
-                    installed_watch_sets: BTreeMap::new(),
-                    connection_watch_sets: BTreeMap::new(),
+                    installed_watch_sets: ConnRegistry::default(),
+                    watch_set_keys: BTreeMap::new(),
                     cluster_replica_statuses: ClusterReplicaStatuses::new(),
                     read_only_controllers,
+                    read_only_replica: catalog_follower.is_some(),
                     clusters_hydrated_trigger,
                     buffered_builtin_table_updates: Some(Vec::new()),
+                    workload_classifier: WorkloadClassifier::default(),
+                    maintenance: MaintenanceState::default(),
+                    background_tasks: BackgroundTasks::default(),
+                    builtin_table_migrations: BTreeMap::new(),
+                    builtin_table_ids: BuiltinTableIds::default(),
+                    database_metrics: DatabaseMetrics::default(),
+                    sink_lag: SinkLag::default(),
+                    webhook_sink_deliveries: WebhookSinkDeliveries::default(),
+                    rejected_cancellations: RejectedCancellations::default(),
                 };
                 let bootstrap = handle.block_on(async {
                     coord
@@ -3907,6 +4384,13 @@ pub fn serve(
                             builtin_table_updates,
                         )
                         .await?;
+                    coord
+                        .record_leadership_transition(
+                            controller_envd_epoch,
+                            deploy_generation,
+                            enable_0dt_deployment,
+                        )
+                        .await?;
                     coord
                         .controller
                         .remove_orphaned_replicas(
@@ -3915,6 +4399,9 @@ pub fn serve(
                         )
                         .await
                         .map_err(AdapterError::Orchestrator)?;
+                    if let Some(catalog_follower) = catalog_follower {
+                        coord.spawn_catalog_follower_task(catalog_follower);
+                    }
                     Ok(())
                 });
                 let ok = bootstrap.is_ok();
@@ -4131,6 +4618,8 @@ impl StorageConstraints {
 pub(crate) enum WatchSetResponse {
     StatementDependenciesReady(StatementLoggingId, StatementLifecycleEvent),
     AlterSinkReady(AlterSinkReadyContext),
+    /// The dependencies of an `AS OF AT LEAST` peek have become readable at its timestamp.
+    PeekDependenciesReadable(oneshot::Sender<()>),
 }
 
 #[derive(Debug)]
@@ -4168,7 +4657,8 @@ impl Drop for AlterSinkReadyContext {
 /// lock is freed.
 #[derive(Debug)]
 struct LockedVecDeque<T> {
-    items: VecDeque<T>,
+    /// The items, with the time at which they were pushed.
+    items: VecDeque<(Instant, T)>,
     lock: Arc<tokio::sync::Mutex<()>>,
 }
 
@@ -4193,19 +4683,26 @@ impl<T> LockedVecDeque<T> {
     }
 
     pub fn push_back(&mut self, value: T) {
-        self.items.push_back(value)
+        self.items.push_back((Instant::now(), value))
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        self.items.pop_front()
+        self.items.pop_front().map(|(_, value)| value)
     }
 
     pub fn remove(&mut self, index: usize) -> Option<T> {
-        self.items.remove(index)
+        self.items.remove(index).map(|(_, value)| value)
     }
 
-    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
-        self.items.iter()
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().map(|(_, value)| value)
+    }
+
+    /// Iterates over the items, with the time at which they were pushed.
+    pub fn iter_with_pushed_at(&self) -> impl Iterator<Item = (Instant, &T)> {
+        self.items
+            .iter()
+            .map(|(pushed_at, value)| (*pushed_at, value))
     }
 }
 
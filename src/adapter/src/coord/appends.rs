@@ -21,18 +21,32 @@ use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::vec::VecExt;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
-use mz_sql::plan::Plan;
+use mz_sql::plan::{ConsistencyToken, Plan};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_storage_client::client::TimestamplessUpdate;
+use mz_storage_client::controller::IntrospectionType;
+use mz_storage_types::sources::Timeline;
 use mz_timestamp_oracle::WriteTimestamp;
 use tokio::sync::{oneshot, Notify, OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug_span, warn, Instrument, Span};
 
 use crate::catalog::BuiltinTableUpdate;
+use crate::coord::failpoints::{self, FailpointAction};
+use crate::coord::write_lock_waits::WriteLockGrantee;
 use crate::coord::{Coordinator, Message, PendingTxn, PlanValidity};
 use crate::session::{Session, WriteOp};
 use crate::util::{CompletedClientTransmitter, ResultExt};
-use crate::ExecuteContext;
+use crate::{AdapterError, ExecuteContext};
+
+/// Returns the consistency token of a write to tables at `timestamp`.
+///
+/// Tables are written in the timeline of the local timestamp oracle.
+fn local_consistency_token(timestamp: Timestamp) -> ConsistencyToken {
+    ConsistencyToken {
+        timeline: Timeline::EpochMilliseconds,
+        timestamp,
+    }
+}
 
 /// An operation that is deferred while waiting for a lock.
 #[derive(Debug)]
@@ -161,6 +175,10 @@ impl Coordinator {
     /// writes.
     #[instrument(level = "debug")]
     pub(crate) async fn try_group_commit(&mut self, permit: Option<GroupCommitPermit>) {
+        if self.timestamp_oracle_unavailable() {
+            self.group_commit_initiate(None, permit).await;
+            return;
+        }
         let timestamp = self.peek_local_write_ts().await;
         let now = Timestamp::from((self.catalog().config().now)());
 
@@ -260,6 +278,37 @@ impl Coordinator {
                 .collect();
             (None, pending_writes)
         };
+        if write_lock_guard.is_some() {
+            let conn_ids = pending_writes
+                .iter()
+                .filter_map(|write| match write {
+                    PendingWriteTxn::User {
+                        pending_txn: PendingTxn { ctx, .. },
+                        ..
+                    } => Some(ctx.session().conn_id().clone()),
+                    PendingWriteTxn::System { .. } => None,
+                })
+                .collect();
+            self.record_write_lock_grant(WriteLockGrantee::GroupCommit { conn_ids });
+        }
+
+        // Getting a write timestamp would block the coordinator for as long as the
+        // timestamp oracle is unavailable. Reject the user writes instead, and hold
+        // on to the system writes to apply them once the oracle is available again.
+        if self.timestamp_oracle_unavailable() {
+            for pending_write_txn in pending_writes {
+                match pending_write_txn {
+                    PendingWriteTxn::User {
+                        pending_txn: PendingTxn { ctx, .. },
+                        ..
+                    } => ctx.retire(Err(AdapterError::TimestampOracleUnavailable)),
+                    system @ PendingWriteTxn::System { .. } => self.pending_writes.push(system),
+                }
+            }
+            drop(permit);
+            drop(write_lock_guard);
+            return;
+        }
 
         // The value returned here still might be ahead of `now()` if `now()` has gone backwards at
         // any point during this method or if this was triggered from DDL. We will still commit the
@@ -289,6 +338,7 @@ impl Coordinator {
         let mut appends: BTreeMap<GlobalId, Vec<(Row, Diff)>> = BTreeMap::new();
         let mut responses = Vec::with_capacity(self.pending_writes.len());
         let mut notifies = Vec::new();
+        let mut table_changes = Vec::new();
 
         for pending_write_txn in pending_writes {
             match pending_write_txn {
@@ -303,6 +353,7 @@ impl Coordinator {
                             action,
                         },
                 } => {
+                    table_changes.extend(self.pack_table_changes(&ctx, &writes, timestamp));
                     for WriteOp { id, rows } in writes {
                         // If the table that some write was targeting has been deleted while the
                         // write was waiting, then the write will be ignored and we respond to the
@@ -364,10 +415,12 @@ impl Coordinator {
             .expect("invalid updates")
             .wall_time()
             .observe(histogram);
+        self.record_table_changes(table_changes).await;
 
         // Spawn a task to do the table writes.
         let internal_cmd_tx = self.internal_cmd_tx.clone();
         let apply_write_fut = self.apply_local_write(timestamp);
+        let consistency_tokens = self.catalog().system_config().enable_consistency_tokens();
 
         let mut span = debug_span!(parent: None, "group_commit_apply");
         OpenTelemetryContext::obtain().attach_as_parent_to(&mut span);
@@ -394,6 +447,10 @@ impl Coordinator {
                 for response in responses {
                     let (mut ctx, result) = response.finalize();
                     ctx.session_mut().apply_write(timestamp);
+                    if consistency_tokens {
+                        ctx.session_mut()
+                            .set_consistency_token(local_consistency_token(timestamp));
+                    }
                     ctx.retire(result);
                 }
 
@@ -436,10 +493,33 @@ impl Coordinator {
         _permit: Option<GroupCommitPermit>,
     ) {
         self.apply_local_write(timestamp).await;
+        let consistency_tokens = self.catalog().system_config().enable_consistency_tokens();
+        let failpoint = failpoints::eval(failpoints::GROUP_COMMIT_APPLY);
+        let mut delayed = Vec::new();
         for response in responses {
             let (mut ctx, result) = response.finalize();
             ctx.session_mut().apply_write(timestamp);
-            ctx.retire(result);
+            if consistency_tokens {
+                ctx.session_mut()
+                    .set_consistency_token(local_consistency_token(timestamp));
+            }
+            match &failpoint {
+                Some(FailpointAction::Error(arg)) => {
+                    ctx.retire(Err(failpoints::failpoint_error(arg.clone())))
+                }
+                Some(FailpointAction::Delay(_)) => delayed.push((ctx, result)),
+                None => ctx.retire(result),
+            }
+        }
+        if let Some(FailpointAction::Delay(delay)) = failpoint {
+            if !delayed.is_empty() {
+                task::spawn(|| "group_commit_apply_failpoint", async move {
+                    tokio::time::sleep(delay).await;
+                    for (ctx, result) in delayed {
+                        ctx.retire(result);
+                    }
+                });
+            }
         }
 
         // Advancing timelines will update all timeline read holds, and update the read timestamps
@@ -471,6 +551,24 @@ impl Coordinator {
         BuiltinTableAppend { coord: self }
     }
 
+    /// Appends `updates` to the storage-managed collection of `introspection_type`.
+    ///
+    /// Read-only environments must not write to introspection collections, so the updates are
+    /// dropped in them.
+    pub(crate) async fn append_introspection_updates(
+        &mut self,
+        introspection_type: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    ) {
+        if self.controller.read_only() || updates.is_empty() {
+            return;
+        }
+        self.controller
+            .storage
+            .append_introspection_updates(introspection_type, updates)
+            .await;
+    }
+
     /// Defers executing `deferred` until the write lock becomes available; waiting
     /// occurs in a green-thread, so callers of this function likely want to
     /// return after calling it.
@@ -497,12 +595,16 @@ impl Coordinator {
     /// Attempts to immediately grant `session` access to the write lock or
     /// errors if the lock is currently held.
     pub(crate) fn try_grant_session_write_lock(
-        &self,
+        &mut self,
         session: &mut Session,
     ) -> Result<(), tokio::sync::TryLockError> {
-        self.write_lock_wait_group.try_lock_owned().map(|p| {
-            session.grant_write_lock(p);
-        })
+        let guard = self.write_lock_wait_group.try_lock_owned()?;
+        session.grant_write_lock(guard);
+        self.record_write_lock_grant(WriteLockGrantee::Statement {
+            conn_id: session.conn_id().clone(),
+            statement: None,
+        });
+        Ok(())
     }
 }
 
@@ -0,0 +1,289 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Advisories to move replicas away from degraded availability zones.
+//!
+//! A single replica that is not ready says little about its availability zone, but when none of
+//! the replicas pinned to an availability zone is ready, the zone itself is likely degraded. When
+//! that has been the case for `az_rebalance_advisory_threshold`, we record an advisory in
+//! `mz_internal.mz_cluster_replica_az_advisories` for each of the zone's replicas, recommending
+//! the healthy zone with the fewest replicas of the same cluster, among the zones the cluster
+//! allows.
+//!
+//! The degraded zones that have been advised about are loaded from the advisories at startup, so
+//! a restarted coordinator doesn't record advisories for a degradation again.
+//!
+//! Only replicas pinned to a single availability zone are considered, as the orchestrator is free
+//! to move the others. We only advise: acting on an advisory means recreating the replica, which
+//! requires the approval of its owner.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use mz_adapter_types::dyncfgs::{AZ_REBALANCE_ADVISORY_THRESHOLD, ENABLE_AZ_REBALANCE_ADVISORIES};
+use mz_catalog::builtin::MZ_CLUSTER_REPLICA_AZ_ADVISORIES;
+use mz_catalog::memory::objects::{Cluster, ClusterReplica, ClusterVariant};
+use mz_controller::clusters::{
+    ClusterStatus, ManagedReplicaAvailabilityZones, ManagedReplicaLocation, ReplicaLocation,
+};
+use mz_ore::task;
+use mz_repr::{Datum, Row};
+use mz_storage_client::controller::IntrospectionType;
+use tracing::warn;
+
+use crate::coord::maintenance::MaintenanceWork;
+use crate::coord::{ClusterReplicaStatuses, Coordinator, Message};
+use crate::error::AdapterError;
+
+/// The number of replicas that must be pinned to an availability zone for their statuses to say
+/// something about the zone.
+const MIN_REPLICAS_PER_ZONE: usize = 2;
+
+/// The degraded availability zones, and the ones we have advised moving replicas away from.
+#[derive(Debug, Default)]
+pub(crate) struct AzAdvisories {
+    /// The time since which no replica pinned to each degraded availability zone has been ready.
+    degraded_since: BTreeMap<String, DateTime<Utc>>,
+    /// The degraded availability zones whose replicas we have recorded advisories for.
+    advised: BTreeSet<String>,
+}
+
+/// Returns the availability zone the replica is pinned to, if it is pinned to a single one.
+fn pinned_availability_zone(replica: &ClusterReplica) -> Option<&str> {
+    match &replica.config.location {
+        ReplicaLocation::Managed(ManagedReplicaLocation {
+            availability_zones, ..
+        }) => match availability_zones {
+            ManagedReplicaAvailabilityZones::FromReplica(Some(az)) => Some(az.as_str()),
+            ManagedReplicaAvailabilityZones::FromCluster(Some(azs)) if azs.len() == 1 => {
+                Some(azs[0].as_str())
+            }
+            _ => None,
+        },
+        ReplicaLocation::Unmanaged(_) => None,
+    }
+}
+
+/// Returns whether replicas of the cluster may be placed in the availability zone `az`.
+fn allows_availability_zone(cluster: &Cluster, az: &str) -> bool {
+    match &cluster.config.variant {
+        ClusterVariant::Managed(managed) if !managed.availability_zones.is_empty() => managed
+            .availability_zones
+            .iter()
+            .any(|allowed| allowed == az),
+        _ => true,
+    }
+}
+
+impl Coordinator {
+    /// Loads the degraded availability zones that have been advised about from
+    /// `mz_cluster_replica_az_advisories`.
+    ///
+    /// Zones that have recovered in the meantime are forgotten with the next status update of a
+    /// replica.
+    pub(crate) async fn bootstrap_az_advisories(&mut self) -> Result<(), AdapterError> {
+        let id = self
+            .catalog()
+            .resolve_builtin_storage_collection(&MZ_CLUSTER_REPLICA_AZ_ADVISORIES);
+        let rows = self.controller.storage.snapshot_latest(id).await?;
+
+        let degraded_since = &mut self.az_advisories.degraded_since;
+        for row in rows {
+            // Skip `occurred_at`, `cluster_id` and `replica_id`.
+            let mut iter = row.iter().skip(3);
+            let az = iter.next().expect("missing availability_zone").unwrap_str();
+            let since = *iter
+                .nth(1)
+                .expect("missing degraded_since")
+                .unwrap_timestamptz();
+            // An availability zone may have degraded repeatedly, of which the latest degradation
+            // is the one that may still be ongoing.
+            let latest = degraded_since.entry(az.to_string()).or_insert(since);
+            *latest = (*latest).max(since);
+        }
+        self.az_advisories.advised = degraded_since.keys().cloned().collect();
+        Ok(())
+    }
+
+    /// Returns the availability zones with enough pinned replicas, none of which is ready.
+    fn degraded_availability_zones(&self) -> BTreeSet<String> {
+        // The number of pinned replicas of each availability zone, and whether any is ready.
+        let mut zones: BTreeMap<&str, (usize, bool)> = BTreeMap::new();
+        for cluster in self.catalog().clusters() {
+            for replica in cluster.replicas() {
+                let Some(az) = pinned_availability_zone(replica) else {
+                    continue;
+                };
+                // Replicas that are being created have no statuses yet.
+                let Some(statuses) = self
+                    .cluster_replica_statuses
+                    .try_get_cluster_replica_statuses(cluster.id, replica.replica_id)
+                else {
+                    continue;
+                };
+                let ready = ClusterReplicaStatuses::cluster_replica_status(statuses)
+                    == ClusterStatus::Ready;
+                let (count, any_ready) = zones.entry(az).or_default();
+                *count += 1;
+                *any_ready |= ready;
+            }
+        }
+        zones
+            .into_iter()
+            .filter(|(_, (count, any_ready))| *count >= MIN_REPLICAS_PER_ZONE && !any_ready)
+            .map(|(az, _)| az.to_string())
+            .collect()
+    }
+
+    /// Updates which availability zones are degraded, after the status of a replica changed at
+    /// `time`, and schedules the recording of advisories for the newly degraded ones.
+    pub(crate) fn update_az_health(&mut self, time: DateTime<Utc>) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        if !ENABLE_AZ_REBALANCE_ADVISORIES.get(dyncfgs) {
+            self.az_advisories = AzAdvisories::default();
+            return;
+        }
+        let threshold = AZ_REBALANCE_ADVISORY_THRESHOLD.get(dyncfgs);
+
+        let degraded = self.degraded_availability_zones();
+        self.az_advisories
+            .degraded_since
+            .retain(|az, _| degraded.contains(az));
+        self.az_advisories
+            .advised
+            .retain(|az| degraded.contains(az));
+
+        let mut newly_degraded = false;
+        for az in degraded {
+            self.az_advisories
+                .degraded_since
+                .entry(az)
+                .or_insert_with(|| {
+                    newly_degraded = true;
+                    time
+                });
+        }
+        if newly_degraded {
+            self.schedule_az_advisories_check(threshold);
+        }
+    }
+
+    /// Schedules recording the advisories of the degraded availability zones after `delay`.
+    fn schedule_az_advisories_check(&self, delay: std::time::Duration) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "az_advisories_check", async move {
+            tokio::time::sleep(delay).await;
+            if internal_cmd_tx.send(Message::CheckAzAdvisories).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Records advisories for the replicas of the availability zones that have been degraded for
+    /// `az_rebalance_advisory_threshold`.
+    pub(crate) async fn check_az_advisories(&mut self) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        if !ENABLE_AZ_REBALANCE_ADVISORIES.get(dyncfgs) {
+            return;
+        }
+        let threshold = AZ_REBALANCE_ADVISORY_THRESHOLD.get(dyncfgs);
+        let now = mz_ore::now::to_datetime((self.catalog().config().now)());
+
+        // Availability zones may have recovered since the check was scheduled, so only the
+        // currently degraded ones count.
+        let degraded = self.degraded_availability_zones();
+        let mut due = Vec::new();
+        let mut next_check: Option<std::time::Duration> = None;
+        for (az, since) in &self.az_advisories.degraded_since {
+            if !degraded.contains(az) || self.az_advisories.advised.contains(az) {
+                continue;
+            }
+            let elapsed = (now - *since).to_std().unwrap_or_default();
+            match threshold.checked_sub(elapsed) {
+                Some(remaining) if !remaining.is_zero() => {
+                    next_check = Some(next_check.map_or(remaining, |next| next.min(remaining)));
+                }
+                _ => due.push((az.clone(), *since)),
+            }
+        }
+        if let Some(delay) = next_check {
+            self.schedule_az_advisories_check(delay);
+        }
+        if due.is_empty() {
+            return;
+        }
+        // Recording advisories may prompt owners to recreate replicas, so it waits for the
+        // maintenance window like other disruptive work.
+        if let Some(delay) = self.defer_maintenance(MaintenanceWork::AzAdvisoryCheck) {
+            self.retry_maintenance_after(delay, Message::CheckAzAdvisories);
+            return;
+        }
+
+        let healthy: Vec<&String> = self
+            .catalog()
+            .state()
+            .availability_zones()
+            .iter()
+            .filter(|az| !degraded.contains(*az))
+            .collect();
+        let mut updates = Vec::new();
+        for (az, since) in &due {
+            for cluster in self.catalog().clusters() {
+                let replicas: Vec<_> = cluster.replicas().collect();
+                if !replicas
+                    .iter()
+                    .any(|replica| pinned_availability_zone(replica) == Some(az.as_str()))
+                {
+                    continue;
+                }
+                // Spread the replicas of the cluster across as many availability zones as
+                // possible.
+                let recommended = healthy
+                    .iter()
+                    .filter(|candidate| allows_availability_zone(cluster, candidate))
+                    .min_by_key(|candidate| {
+                        replicas
+                            .iter()
+                            .filter(|replica| {
+                                pinned_availability_zone(replica) == Some(candidate.as_str())
+                            })
+                            .count()
+                    })
+                    .map(|candidate| candidate.as_str());
+                for replica in &replicas {
+                    if pinned_availability_zone(replica) != Some(az.as_str()) {
+                        continue;
+                    }
+                    warn!(
+                        cluster_id = %cluster.id,
+                        replica_id = %replica.replica_id,
+                        %az,
+                        ?recommended,
+                        "advising to move replica away from degraded availability zone"
+                    );
+                    let row = Row::pack_slice(&[
+                        Datum::TimestampTz(now.try_into().expect("must fit")),
+                        Datum::String(&cluster.id.to_string()),
+                        Datum::String(&replica.replica_id.to_string()),
+                        Datum::String(az),
+                        Datum::from(recommended),
+                        Datum::TimestampTz((*since).try_into().expect("must fit")),
+                    ]);
+                    updates.push((row, 1));
+                }
+            }
+        }
+        self.az_advisories
+            .advised
+            .extend(due.into_iter().map(|(az, _)| az));
+
+        self.append_introspection_updates(IntrospectionType::ReplicaAzAdvisories, updates)
+            .await;
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Bookkeeping of the coordinator's long-lived background tasks.
+//!
+//! The coordinator repeatedly runs background tasks, like storage usage collection or draining
+//! the statement log. The last and next run of each task is recorded in
+//! `mz_internal.mz_background_tasks`.
+//!
+//! Superusers can run a task right away with `mz_internal.mz_trigger_background_task`, or skip
+//! its next scheduled run with `mz_internal.mz_skip_background_task`. Triggered runs don't
+//! count as scheduled runs, so they are never skipped and don't move the next scheduled run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use tracing::debug;
+
+use crate::coord::{Coordinator, Message};
+use crate::AdapterError;
+
+/// How long periodic tasks that are disabled wait before checking again whether they have been
+/// enabled.
+const DISABLED_RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A long-lived background task of the coordinator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum BackgroundTask {
+    /// Collecting the storage usage of all shards.
+    StorageUsageCollection,
+    /// Writing pending statement log events to the statement log.
+    StatementLogDrain,
+    /// Checking the scheduling policies of clusters.
+    SchedulingPolicyCheck,
+    /// Retrying strict serializable reads that wait for the timestamp oracle.
+    DeferredReadRetry,
+}
+
+impl BackgroundTask {
+    const ALL: [BackgroundTask; 4] = [
+        BackgroundTask::StorageUsageCollection,
+        BackgroundTask::StatementLogDrain,
+        BackgroundTask::SchedulingPolicyCheck,
+        BackgroundTask::DeferredReadRetry,
+    ];
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            BackgroundTask::StorageUsageCollection => "storage-usage-collection",
+            BackgroundTask::StatementLogDrain => "statement-log-drain",
+            BackgroundTask::SchedulingPolicyCheck => "scheduling-policy-check",
+            BackgroundTask::DeferredReadRetry => "deferred-read-retry",
+        }
+    }
+
+    fn parse(name: &str) -> Result<BackgroundTask, AdapterError> {
+        BackgroundTask::ALL
+            .into_iter()
+            .find(|task| task.as_str() == name)
+            .ok_or_else(|| AdapterError::Unstructured(anyhow!("unknown background task: {name}")))
+    }
+
+    /// The message that runs the task.
+    fn message(&self) -> Message {
+        match self {
+            BackgroundTask::StorageUsageCollection => Message::StorageUsageFetch,
+            BackgroundTask::StatementLogDrain => Message::DrainStatementLog,
+            BackgroundTask::SchedulingPolicyCheck => Message::CheckSchedulingPolicies,
+            BackgroundTask::DeferredReadRetry => Message::LinearizeReads,
+        }
+    }
+}
+
+/// The runs of a background task, as recorded in `mz_background_tasks`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct BackgroundTaskRuns {
+    /// When the task last ran.
+    last_run_at: Option<EpochMillis>,
+    /// When the task is scheduled to run next.
+    next_run_at: Option<EpochMillis>,
+    /// Whether the next scheduled run will be skipped.
+    skip_next_run: bool,
+}
+
+/// The coordinator's bookkeeping of its background tasks.
+#[derive(Debug)]
+pub(crate) struct BackgroundTasks {
+    runs: BTreeMap<BackgroundTask, BackgroundTaskRuns>,
+    /// The tasks with a triggered run that hasn't started yet.
+    triggered: BTreeSet<BackgroundTask>,
+}
+
+impl Default for BackgroundTasks {
+    fn default() -> Self {
+        BackgroundTasks {
+            runs: BackgroundTask::ALL
+                .into_iter()
+                .map(|task| (task, BackgroundTaskRuns::default()))
+                .collect(),
+            triggered: BTreeSet::new(),
+        }
+    }
+}
+
+impl Coordinator {
+    /// Records all background tasks in `mz_background_tasks`.
+    ///
+    /// This must be called once, before any background task runs.
+    pub(crate) fn report_background_tasks(&mut self) {
+        let state = self.catalog().state();
+        let updates = self
+            .background_tasks
+            .runs
+            .iter()
+            .map(|(task, runs)| {
+                let update = state.pack_background_task_update(
+                    task.as_str(),
+                    runs.last_run_at,
+                    runs.next_run_at,
+                    runs.skip_next_run,
+                    1,
+                );
+                state.resolve_builtin_table_update(update)
+            })
+            .collect();
+        self.builtin_table_update().background(updates);
+    }
+
+    /// Records that a run of `task` starts now, or returns `false` if it must be skipped.
+    ///
+    /// `interval` is the time between two scheduled runs of `task`, if it runs at a fixed
+    /// interval. Other tasks record their next run with [`Coordinator::schedule_background_task`].
+    pub(crate) fn begin_background_task(
+        &mut self,
+        task: BackgroundTask,
+        interval: Option<Duration>,
+    ) -> bool {
+        let now: EpochMillis = (self.catalog().config().now)();
+        let triggered = self.background_tasks.triggered.remove(&task);
+        self.update_background_task(task, |runs| {
+            if !triggered {
+                runs.next_run_at = interval.map(|interval| add_duration(now, interval));
+                if std::mem::take(&mut runs.skip_next_run) {
+                    debug!(task = task.as_str(), "skipping background task run");
+                    return false;
+                }
+            }
+            runs.last_run_at = Some(now);
+            true
+        })
+    }
+
+    /// Sends `message` to the coordinator once `interval` has passed.
+    ///
+    /// Periodic tasks are disabled with an interval of zero. They still wake up every
+    /// `DISABLED_RECHECK_INTERVAL` to notice when they are enabled again.
+    pub(crate) fn schedule_periodic_message(
+        &self,
+        name: &'static str,
+        interval: Duration,
+        message: Message,
+    ) {
+        let interval = if interval.is_zero() {
+            DISABLED_RECHECK_INTERVAL
+        } else {
+            interval
+        };
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| name, async move {
+            tokio::time::sleep(interval).await;
+            if internal_cmd_tx.send(message).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Records that `task` is scheduled to run next once `delay` has passed.
+    pub(crate) fn schedule_background_task(&mut self, task: BackgroundTask, delay: Duration) {
+        let now: EpochMillis = (self.catalog().config().now)();
+        self.update_background_task(task, |runs| {
+            runs.next_run_at = Some(add_duration(now, delay))
+        });
+    }
+
+    /// Reports whether `task` has a scheduled or triggered run that hasn't started yet.
+    pub(crate) fn background_task_pending(&self, task: BackgroundTask) -> bool {
+        self.background_tasks.triggered.contains(&task)
+            || self.background_tasks.runs[&task].next_run_at.is_some()
+    }
+
+    /// Runs the background task `name` as soon as possible.
+    ///
+    /// Returns `false` if a triggered run of the task is already pending.
+    pub(crate) fn trigger_background_task(&mut self, name: &str) -> Result<bool, AdapterError> {
+        let task = BackgroundTask::parse(name)?;
+        if !self.background_tasks.triggered.insert(task) {
+            return Ok(false);
+        }
+        debug!(task = task.as_str(), "triggering background task run");
+        if self.internal_cmd_tx.send(task.message()).is_err() {
+            // If sending fails, the main thread has shutdown.
+        }
+        Ok(true)
+    }
+
+    /// Skips the next scheduled run of the background task `name`.
+    ///
+    /// Returns `false` if the next run is already skipped.
+    pub(crate) fn skip_background_task(&mut self, name: &str) -> Result<bool, AdapterError> {
+        let task = BackgroundTask::parse(name)?;
+        Ok(self.update_background_task(task, |runs| {
+            !std::mem::replace(&mut runs.skip_next_run, true)
+        }))
+    }
+
+    /// Applies `f` to the runs of `task` and records any change in `mz_background_tasks`.
+    fn update_background_task<R>(
+        &mut self,
+        task: BackgroundTask,
+        f: impl FnOnce(&mut BackgroundTaskRuns) -> R,
+    ) -> R {
+        let runs = self
+            .background_tasks
+            .runs
+            .get_mut(&task)
+            .expect("all tasks are known");
+        let previous = runs.clone();
+        let result = f(runs);
+        if *runs == previous {
+            return result;
+        }
+
+        let current = runs.clone();
+        let state = self.catalog().state();
+        let updates = [(previous, -1), (current, 1)]
+            .into_iter()
+            .map(|(runs, diff)| {
+                let update = state.pack_background_task_update(
+                    task.as_str(),
+                    runs.last_run_at,
+                    runs.next_run_at,
+                    runs.skip_next_run,
+                    diff,
+                );
+                state.resolve_builtin_table_update(update)
+            })
+            .collect();
+        self.builtin_table_update().background(updates);
+        result
+    }
+}
+
+fn add_duration(time: EpochMillis, duration: Duration) -> EpochMillis {
+    let duration = EpochMillis::try_from(duration.as_millis()).unwrap_or(EpochMillis::MAX);
+    time.saturating_add(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_background_task() {
+        for task in BackgroundTask::ALL {
+            assert_eq!(BackgroundTask::parse(task.as_str()).ok(), Some(task));
+        }
+        assert!(BackgroundTask::parse("catalog-vacuum").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A cache of the IDs of builtin tables.
+//!
+//! The coordinator resolves the builtin table of every update that it writes to one, which looks
+//! up the schema and the name of the table in the catalog. Some updates are written at a high
+//! rate, like those to `mz_cluster_replica_metrics` and `mz_cluster_replica_statuses`, which
+//! change whenever a replica reports new metrics or a new status. The coordinator caches the IDs
+//! that it resolves until the catalog changes next. Lookups in the cache are reported in the
+//! `mz_builtin_table_id_cache_lookups_total` metric, by whether they hit the cache.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::builtin::BuiltinTable;
+use mz_repr::GlobalId;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::Coordinator;
+
+/// The IDs of the builtin tables that the coordinator has resolved since the last catalog change.
+#[derive(Debug, Default)]
+pub(crate) struct BuiltinTableIds {
+    /// The IDs of builtin tables, by schema and name.
+    ids: BTreeMap<(&'static str, &'static str), GlobalId>,
+}
+
+impl BuiltinTableIds {
+    /// Forgets all resolved IDs.
+    pub(crate) fn invalidate(&mut self) {
+        self.ids.clear();
+    }
+}
+
+impl Coordinator {
+    /// Resolves the ID of `builtin`, from the cache if possible.
+    ///
+    /// Panics if the builtin table doesn't exist in the catalog.
+    pub(crate) fn resolve_cached_builtin_table(
+        &mut self,
+        builtin: &'static BuiltinTable,
+    ) -> GlobalId {
+        let key = (builtin.schema, builtin.name);
+        if let Some(id) = self.builtin_table_ids.ids.get(&key) {
+            self.metrics
+                .builtin_table_id_cache_lookups
+                .with_label_values(&["hit"])
+                .inc();
+            return *id;
+        }
+        self.metrics
+            .builtin_table_id_cache_lookups
+            .with_label_values(&["miss"])
+            .inc();
+        let id = self.catalog().resolve_builtin_table(builtin);
+        self.builtin_table_ids.ids.insert(key, id);
+        id
+    }
+
+    /// Resolves the builtin table of `update`, from the cache if possible.
+    pub(crate) fn resolve_cached_builtin_table_update(
+        &mut self,
+        BuiltinTableUpdate { id, row, diff }: BuiltinTableUpdate<&'static BuiltinTable>,
+    ) -> BuiltinTableUpdate<GlobalId> {
+        let id = self.resolve_cached_builtin_table(id);
+        BuiltinTableUpdate { id, row, diff }
+    }
+
+    /// Resolves the builtin tables of `updates`, from the cache if possible.
+    pub(crate) fn resolve_cached_builtin_table_updates(
+        &mut self,
+        updates: impl IntoIterator<Item = BuiltinTableUpdate<&'static BuiltinTable>>,
+    ) -> Vec<BuiltinTableUpdate<GlobalId>> {
+        updates
+            .into_iter()
+            .map(|update| self.resolve_cached_builtin_table_update(update))
+            .collect()
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Asynchronous migrations of builtin tables.
+//!
+//! At startup, the coordinator resets the contents of all builtin tables: it retracts their
+//! previous contents and appends the contents derived from the catalog. Reading the previous
+//! contents of large tables, like `mz_audit_events` and `mz_storage_usage_by_shard`, can delay
+//! the time at which the environment becomes queryable by minutes.
+//!
+//! With `enable_async_builtin_table_migrations`, the tables that aren't needed for the
+//! environment to be usable are instead migrated in the background, after startup. Their previous
+//! contents are read at the timestamp at which the other tables are reset, so the migration
+//! commutes with the writes to the tables after startup. Until the migration of a table is
+//! complete, the table may be out of date, and queries that depend on it receive a notice,
+//! followed by another notice once the migration is complete. The progress of the migrations is
+//! shown in `mz_internal.mz_builtin_table_migrations`.
+
+use std::collections::BTreeSet;
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::ENABLE_ASYNC_BUILTIN_TABLE_MIGRATIONS;
+use mz_catalog::builtin::{BuiltinTable, MZ_AUDIT_EVENTS, MZ_STORAGE_USAGE_BY_SHARD};
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_ore::vec::VecExt;
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::controller::SnapshotCursor;
+use mz_storage_types::controller::StorageError;
+use tracing::{debug, info};
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+use crate::util::ResultExt;
+use crate::AdapterNotice;
+
+/// Returns the builtin tables that are migrated in the background, which are large and not needed
+/// for the environment to be usable.
+fn async_migrated_tables() -> [&'static BuiltinTable; 2] {
+    [&*MZ_AUDIT_EVENTS, &*MZ_STORAGE_USAGE_BY_SHARD]
+}
+
+/// Reads the contents of a builtin table from `cursor`.
+async fn read_contents(
+    mut cursor: SnapshotCursor<Timestamp>,
+) -> Result<Vec<(Row, Diff)>, StorageError<Timestamp>> {
+    let mut contents = Vec::new();
+    while let Some(updates) = cursor.next().await {
+        for ((data, _), _, diff) in updates {
+            let row = data.expect("invalid protobuf data").0?;
+            contents.push((row, diff));
+        }
+    }
+    Ok(contents)
+}
+
+/// The migration of a builtin table that is still in progress.
+#[derive(Debug)]
+pub(crate) struct BuiltinTableMigration {
+    /// The updates that append the contents of the table that were derived from the catalog at
+    /// startup. They are taken once the migration is applied.
+    updates: Vec<BuiltinTableUpdate>,
+    started_at: EpochMillis,
+    /// The connections that were notified that the table is being migrated.
+    notified_conns: BTreeSet<ConnectionId>,
+}
+
+impl Coordinator {
+    /// Takes the updates to the builtin tables that are migrated in the background out of
+    /// `builtin_table_updates`, and returns the IDs of those tables.
+    ///
+    /// Returns no tables if `enable_async_builtin_table_migrations` is off. Otherwise the updates
+    /// that record the migrations as pending are added to `builtin_table_updates`.
+    pub(crate) fn defer_builtin_table_migrations(
+        &mut self,
+        builtin_table_updates: &mut Vec<BuiltinTableUpdate>,
+    ) -> BTreeSet<GlobalId> {
+        if !ENABLE_ASYNC_BUILTIN_TABLE_MIGRATIONS.get(self.catalog().system_config().dyncfgs()) {
+            return BTreeSet::new();
+        }
+
+        let started_at = self.now();
+        let ids: BTreeSet<_> = async_migrated_tables()
+            .into_iter()
+            .map(|table| self.catalog().resolve_builtin_table(table))
+            .collect();
+        for id in &ids {
+            let updates = builtin_table_updates
+                .drain_filter_swapping(|update| update.id == *id)
+                .collect();
+            self.builtin_table_migrations.insert(
+                *id,
+                BuiltinTableMigration {
+                    updates,
+                    started_at,
+                    notified_conns: BTreeSet::new(),
+                },
+            );
+            let state = self.catalog().state();
+            let update = state.pack_builtin_table_migration_update(*id, started_at, None, 1);
+            builtin_table_updates.push(state.resolve_builtin_table_update(update));
+        }
+        ids
+    }
+
+    /// Starts to read the contents of the builtin table `id` at `read_ts` in the background, to
+    /// migrate the table once they are read.
+    pub(crate) async fn start_builtin_table_migration(&mut self, id: GlobalId, read_ts: Timestamp) {
+        let cursor = self
+            .controller
+            .storage
+            .snapshot_cursor(id, read_ts)
+            .await
+            .unwrap_or_terminate("cannot fail to fetch snapshot");
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| format!("builtin_table_migration:{id}"), async move {
+            let contents = read_contents(cursor).await;
+            // It is not an error for this to fail. The coordinator could be shutting down.
+            let _ = internal_cmd_tx.send(Message::BuiltinTableMigrationSnapshot { id, contents });
+        });
+    }
+
+    /// Migrates the builtin table `id`, whose previous `contents` have been read, by retracting
+    /// them and appending the contents derived from the catalog at startup.
+    pub(crate) fn apply_builtin_table_migration(
+        &mut self,
+        id: GlobalId,
+        contents: Result<Vec<(Row, Diff)>, StorageError<Timestamp>>,
+    ) {
+        let contents = contents.unwrap_or_terminate("cannot fail to fetch snapshot");
+        let Some(migration) = self.builtin_table_migrations.get_mut(&id) else {
+            return;
+        };
+        debug!(
+            "migrating builtin table {id}: retracting {} rows",
+            contents.len()
+        );
+        let started_at = migration.started_at;
+        let mut updates = std::mem::take(&mut migration.updates);
+        updates.extend(contents.into_iter().map(|(row, diff)| BuiltinTableUpdate {
+            id,
+            row,
+            diff: -diff,
+        }));
+        let state = self.catalog().state();
+        let finished_at = self.now();
+        for update in [
+            state.pack_builtin_table_migration_update(id, started_at, None, -1),
+            state.pack_builtin_table_migration_update(id, started_at, Some(finished_at), 1),
+        ] {
+            updates.push(state.resolve_builtin_table_update(update));
+        }
+
+        let notify = self.builtin_table_update().defer(updates);
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(
+            || format!("builtin_table_migration_notify:{id}"),
+            async move {
+                notify.await;
+                // It is not an error for this to fail. The coordinator could be shutting down.
+                let _ = internal_cmd_tx.send(Message::BuiltinTableMigrationComplete(id));
+            },
+        );
+    }
+
+    /// Records that the migration of the builtin table `id` is complete, and notifies the
+    /// connections that were told that it was pending.
+    pub(crate) fn complete_builtin_table_migration(&mut self, id: GlobalId) {
+        let Some(migration) = self.builtin_table_migrations.remove(&id) else {
+            return;
+        };
+        let name = self.catalog().get_entry(&id).name().item.clone();
+        info!("migration of builtin table {name} ({id}) complete");
+        for conn_id in &migration.notified_conns {
+            self.send_notice(
+                conn_id,
+                AdapterNotice::BuiltinTableMigrationComplete { name: name.clone() },
+            );
+        }
+    }
+
+    /// Notifies `session` if any of `source_ids` transitively depend on a builtin table that is
+    /// still being migrated.
+    pub(crate) fn notice_pending_builtin_table_migrations(
+        &mut self,
+        session: &mut Session,
+        source_ids: &BTreeSet<GlobalId>,
+    ) {
+        if self.builtin_table_migrations.is_empty() {
+            return;
+        }
+        let state = self.catalog.state();
+        let pending: BTreeSet<_> = source_ids
+            .iter()
+            .flat_map(|id| state.transitive_uses(*id))
+            .filter(|id| self.builtin_table_migrations.contains_key(id))
+            .collect();
+        for id in pending {
+            let name = state.get_entry(&id).name().item.clone();
+            session.add_notice(AdapterNotice::BuiltinTableMigrationPending { name });
+            self.builtin_table_migrations
+                .get_mut(&id)
+                .expect("migration is pending")
+                .notified_conns
+                .insert(session.conn_id().clone());
+        }
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking of query cancellations.
+//!
+//! Queries can be canceled with a pgwire `CancelRequest`, which is authenticated with the secret
+//! key of the target connection, or from SQL with `pg_cancel_backend` or `CANCEL QUERY`. We
+//! record every attempt, including the ones we reject, in `mz_internal.mz_cancellation_history`,
+//! so that rogue cancellation requests can be traced back to where they came from. Rejected
+//! `CancelRequest`s are not authenticated, so anyone who can reach us can send any number of them.
+//! We aggregate the ones from the same origin and record them once per
+//! `rejected_cancellation_aggregation_interval`, which bounds what they add to the history.
+//!
+//! `ALTER SYSTEM TERMINATE SESSIONS` cancels the work of all sessions that match its filters, like
+//! `CANCEL QUERY` does for one connection, and additionally closes them the next time they
+//! execute a statement.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use mz_adapter_types::connection::ConnectionIdType;
+use mz_adapter_types::dyncfgs::REJECTED_CANCELLATION_AGGREGATION_INTERVAL;
+use mz_ore::cast::CastFrom;
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_repr::{Datum, Row};
+use mz_sql::plan::{AlterSystemTerminateSessionsPlan, CancelQueryPlan};
+use mz_sql::session::metadata::SessionMetadata;
+use mz_storage_client::controller::IntrospectionType;
+
+use crate::active_compute_sink::ActiveComputeSink;
+use crate::command::ExecuteResponse;
+use crate::coord::{Coordinator, Message};
+use crate::error::AdapterError;
+use crate::ExecuteContext;
+
+/// The maximum number of origins whose rejected cancellation requests are aggregated separately.
+/// Requests from further origins are aggregated as if their origin was unknown.
+const MAX_REJECTED_CANCELLATION_ORIGINS: usize = 1024;
+
+/// How a cancellation was requested.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum CancellationMethod {
+    /// A pgwire `CancelRequest`, or the equivalent request of another protocol.
+    Protocol,
+    /// The `pg_cancel_backend` function.
+    PgCancelBackend,
+    /// The `CANCEL QUERY` statement.
+    CancelQuery,
+    /// The `ALTER SYSTEM TERMINATE SESSIONS` statement.
+    TerminateSessions,
+}
+
+impl CancellationMethod {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CancellationMethod::Protocol => "protocol",
+            CancellationMethod::PgCancelBackend => "pg_cancel_backend",
+            CancellationMethod::CancelQuery => "cancel_query",
+            CancellationMethod::TerminateSessions => "terminate_sessions",
+        }
+    }
+}
+
+/// What became of a cancellation request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum CancellationOutcome {
+    /// The ongoing work of the target connection, if any, was canceled.
+    Canceled,
+    /// The request did not carry the secret key of the target connection.
+    InvalidSecretKey,
+    /// The target connection does not exist.
+    UnknownConnection,
+}
+
+impl CancellationOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CancellationOutcome::Canceled => "canceled",
+            CancellationOutcome::InvalidSecretKey => "invalid_secret_key",
+            CancellationOutcome::UnknownConnection => "unknown_connection",
+        }
+    }
+}
+
+/// Rejected cancellation requests that are not yet recorded in `mz_cancellation_history`.
+#[derive(Debug, Default)]
+pub(crate) struct RejectedCancellations {
+    /// The aggregated requests, by origin and outcome.
+    pending: BTreeMap<(Option<IpAddr>, CancellationOutcome), RejectedCancellation>,
+}
+
+/// Rejected cancellation requests of the same origin and outcome.
+#[derive(Debug)]
+struct RejectedCancellation {
+    /// When the first of the requests occurred.
+    first_occurred_at: EpochMillis,
+    /// The connection the requests targeted, or `None` if they targeted different ones.
+    conn_id: Option<ConnectionIdType>,
+    /// The number of requests.
+    attempts: u64,
+}
+
+impl Coordinator {
+    /// Records a request to cancel the work of `conn_id` in `mz_cancellation_history`.
+    ///
+    /// `origin` is the address of the client that sent a protocol-level request, and
+    /// `requested_by` is the user that requested the cancellation from SQL.
+    pub(crate) async fn record_cancellation(
+        &mut self,
+        conn_id: ConnectionIdType,
+        method: CancellationMethod,
+        origin: Option<IpAddr>,
+        requested_by: Option<&str>,
+        outcome: CancellationOutcome,
+    ) {
+        let occurred_at = (self.catalog().config().now)();
+        let row = cancellation_row(
+            occurred_at,
+            Some(conn_id),
+            origin,
+            requested_by,
+            method,
+            outcome,
+            1,
+        );
+        self.append_introspection_updates(IntrospectionType::CancellationHistory, vec![(row, 1)])
+            .await;
+    }
+
+    /// Aggregates a rejected `CancelRequest` for `conn_id` from `origin`, to be recorded by the
+    /// next [`Coordinator::flush_rejected_cancellations`].
+    pub(crate) fn aggregate_rejected_cancellation(
+        &mut self,
+        conn_id: ConnectionIdType,
+        origin: Option<IpAddr>,
+        outcome: CancellationOutcome,
+    ) {
+        let interval = REJECTED_CANCELLATION_AGGREGATION_INTERVAL
+            .get(self.catalog().system_config().dyncfgs());
+        let now = (self.catalog().config().now)();
+        let pending = &mut self.rejected_cancellations.pending;
+        if pending.is_empty() {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "flush_rejected_cancellations", async move {
+                tokio::time::sleep(interval).await;
+                if internal_cmd_tx
+                    .send(Message::FlushRejectedCancellations)
+                    .is_err()
+                {
+                    // If sending fails, the main thread has shutdown.
+                }
+            });
+        }
+
+        let mut key = (origin, outcome);
+        if !pending.contains_key(&key) && pending.len() >= MAX_REJECTED_CANCELLATION_ORIGINS {
+            key = (None, outcome);
+        }
+        let rejected = pending.entry(key).or_insert(RejectedCancellation {
+            first_occurred_at: now,
+            conn_id: Some(conn_id),
+            attempts: 0,
+        });
+        if rejected.conn_id != Some(conn_id) {
+            rejected.conn_id = None;
+        }
+        rejected.attempts += 1;
+    }
+
+    /// Records the aggregated rejected cancellation requests in `mz_cancellation_history`.
+    pub(crate) async fn flush_rejected_cancellations(&mut self) {
+        let pending = std::mem::take(&mut self.rejected_cancellations.pending);
+        let updates = pending
+            .into_iter()
+            .map(|((origin, outcome), rejected)| {
+                let row = cancellation_row(
+                    rejected.first_occurred_at,
+                    rejected.conn_id,
+                    origin,
+                    None,
+                    CancellationMethod::Protocol,
+                    outcome,
+                    rejected.attempts,
+                );
+                (row, 1)
+            })
+            .collect();
+        self.append_introspection_updates(IntrospectionType::CancellationHistory, updates)
+            .await;
+    }
+
+    /// Cancels the ongoing work of the connection named by a `CANCEL QUERY` statement.
+    pub(super) async fn sequence_cancel_query(
+        &mut self,
+        ctx: ExecuteContext,
+        CancelQueryPlan { connection_id }: CancelQueryPlan,
+    ) {
+        let requested_by = ctx.session().user().name.clone();
+        let res = if ctx.session().conn_id().unhandled() == connection_id {
+            // As with `pg_cancel_backend`, canceling ourselves cancels the statement that is
+            // doing the canceling.
+            Err(AdapterError::Canceled)
+        } else if let Some((id_handle, _conn_meta)) =
+            self.active_conns.get_key_value(&connection_id)
+        {
+            // `check_plan` already verified that we are a superuser.
+            self.handle_privileged_cancel(id_handle.clone()).await;
+            Ok(ExecuteResponse::CanceledQuery)
+        } else {
+            Err(AdapterError::UnknownConnection(connection_id))
+        };
+
+        let outcome = match &res {
+            Err(AdapterError::UnknownConnection(_)) => CancellationOutcome::UnknownConnection,
+            _ => CancellationOutcome::Canceled,
+        };
+        self.record_cancellation(
+            connection_id,
+            CancellationMethod::CancelQuery,
+            None,
+            Some(&requested_by),
+            outcome,
+        )
+        .await;
+        ctx.retire(res);
+    }
+
+    /// Cancels the ongoing work of the sessions that match the filters of an
+    /// `ALTER SYSTEM TERMINATE SESSIONS` statement, marks them as terminated, and reports what was
+    /// canceled for each of them.
+    pub(super) async fn sequence_alter_system_terminate_sessions(
+        &mut self,
+        ctx: ExecuteContext,
+        plan: AlterSystemTerminateSessionsPlan,
+    ) {
+        let requested_by = ctx.session().user().name.clone();
+        // The session that is doing the terminating never terminates itself.
+        let conn_ids: Vec<_> = self
+            .active_conns
+            .iter()
+            .filter(|(conn_id, conn)| {
+                *conn_id != ctx.session().conn_id()
+                    && plan.matches(&conn.user().name, conn.application_name())
+            })
+            .map(|(conn_id, _)| conn_id.clone())
+            .collect();
+
+        let mut rows = Vec::with_capacity(conn_ids.len());
+        for conn_id in conn_ids {
+            let canceled_peeks = self.pending_peeks.connection_keys(&conn_id).count();
+            let canceled_subscribes = self
+                .active_compute_sinks
+                .values()
+                .filter(|sink| {
+                    matches!(sink, ActiveComputeSink::Subscribe(_))
+                        && *sink.connection_id() == conn_id
+                })
+                .count();
+            // `check_plan` already verified that we are a superuser.
+            let canceled_statement = self.handle_privileged_cancel(conn_id.clone()).await;
+            self.terminated_conns.insert(conn_id.clone());
+
+            let conn = self.active_conns.get(&conn_id).expect("known to exist");
+            rows.push(Row::pack_slice(&[
+                Datum::UInt32(conn_id.unhandled()),
+                Datum::String(&conn.user().name),
+                Datum::String(conn.application_name()),
+                Datum::UInt64(u64::cast_from(canceled_peeks)),
+                Datum::UInt64(u64::cast_from(canceled_subscribes)),
+                Datum::from(canceled_statement),
+            ]));
+            self.record_cancellation(
+                conn_id.unhandled(),
+                CancellationMethod::TerminateSessions,
+                None,
+                Some(&requested_by),
+                CancellationOutcome::Canceled,
+            )
+            .await;
+        }
+        ctx.retire(Ok(Self::send_immediate_rows(rows)));
+    }
+}
+
+/// Packs a row of `mz_cancellation_history`.
+fn cancellation_row(
+    occurred_at: EpochMillis,
+    conn_id: Option<ConnectionIdType>,
+    origin: Option<IpAddr>,
+    requested_by: Option<&str>,
+    method: CancellationMethod,
+    outcome: CancellationOutcome,
+    attempts: u64,
+) -> Row {
+    let occurred_at = mz_ore::now::to_datetime(occurred_at);
+    let origin = origin.map(|origin| origin.to_string());
+    Row::pack_slice(&[
+        Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+        Datum::from(conn_id),
+        Datum::from(origin.as_deref()),
+        Datum::from(requested_by),
+        Datum::String(method.as_str()),
+        Datum::String(outcome.as_str()),
+        Datum::UInt64(attempts),
+    ])
+}
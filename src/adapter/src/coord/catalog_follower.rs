@@ -0,0 +1,125 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Following of the durable catalog by read-only replicas.
+//!
+//! A read-only replica serves serializable reads against its in-memory catalog, while the leader
+//! handles writes and DDL. The replica follows the leader's changes to the durable catalog and
+//! applies them to its in-memory catalog as they arrive. Only changes to the objects that the
+//! controllers maintain (clusters, replicas and collections) make the replica restart, so that it
+//! bootstraps again from the latest catalog. Restarting is cheap because the replica's compute
+//! controller reconciles with the dataflows that are already running on its replicas.
+
+use std::collections::BTreeSet;
+
+use mz_adapter_types::dyncfgs::CATALOG_FOLLOWER_SYNC_INTERVAL;
+use mz_catalog::durable::DurableCatalogState;
+use mz_catalog::memory::objects::{StateUpdate, StateUpdateKind};
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::{halt, task};
+use mz_repr::GlobalId;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, warn};
+
+use crate::coord::{Coordinator, Message};
+
+impl Coordinator {
+    /// Spawns a task that follows the durable catalog, through the read-only handle `follower`,
+    /// and sends the leader's changes to the coordinator.
+    ///
+    /// `follower` must already have consumed all updates that were visible when this process
+    /// opened its own catalog.
+    pub(crate) fn spawn_catalog_follower_task(&self, mut follower: Box<dyn DurableCatalogState>) {
+        let interval = CATALOG_FOLLOWER_SYNC_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "catalog_follower", async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let updates = match follower.sync_to_current_updates().await {
+                    Ok(updates) => updates,
+                    Err(e) => {
+                        warn!("failed to follow the durable catalog: {e}");
+                        continue;
+                    }
+                };
+                debug!("followed {} durable catalog updates", updates.len());
+                if updates.is_empty() {
+                    continue;
+                }
+                // The coordinator shut down.
+                if internal_cmd_tx
+                    .send(Message::CatalogFollowerUpdates(updates))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Applies the leader's changes to the durable catalog to the in-memory catalog, and restarts
+    /// this process if they affect objects that the controllers maintain.
+    pub(crate) fn apply_catalog_follower_updates(&mut self, updates: Vec<StateUpdate>) {
+        let system_config_changed = updates
+            .iter()
+            .any(|update| matches!(update.kind, StateUpdateKind::SystemConfiguration(_)));
+
+        let before = self.controller_objects();
+        if let Err(e) = self.catalog_mut().apply_follower_updates(updates) {
+            halt!("failed to apply the leader's catalog changes: {e}; restarting to follow");
+        }
+        if self.controller_objects() != before {
+            halt!("leader changed objects maintained by the controllers; restarting to follow");
+        }
+
+        if system_config_changed {
+            self.update_all_system_config_dependents();
+        }
+    }
+
+    /// Returns the objects that the controllers maintain, which a read-only replica can't change
+    /// without bootstrapping again.
+    fn controller_objects(&self) -> ControllerObjects {
+        let catalog = self.catalog();
+        let collections = catalog
+            .entries()
+            .filter(|entry| {
+                let item = entry.item();
+                item.is_storage_collection()
+                    || entry.is_sink()
+                    || item.is_compute_object_on_cluster().is_some()
+            })
+            .map(|entry| (entry.id(), entry.item().cluster_id()))
+            .collect();
+        let replicas = catalog
+            .clusters()
+            .flat_map(|cluster| {
+                cluster
+                    .replicas()
+                    .map(move |replica| (cluster.id, replica.replica_id))
+            })
+            .collect();
+        let clusters = catalog.clusters().map(|cluster| cluster.id).collect();
+        ControllerObjects {
+            clusters,
+            replicas,
+            collections,
+        }
+    }
+}
+
+/// The objects that the controllers maintain.
+#[derive(Debug, PartialEq, Eq)]
+struct ControllerObjects {
+    clusters: BTreeSet<ClusterId>,
+    replicas: BTreeSet<(ClusterId, ReplicaId)>,
+    collections: BTreeSet<(GlobalId, Option<ClusterId>)>,
+}
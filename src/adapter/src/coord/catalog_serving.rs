@@ -84,6 +84,8 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::CreateIndex(_)
         | Plan::CreateType(_)
         | Plan::Comment(_)
+        | Plan::CancelQuery(_)
+        | Plan::AlterSystemTerminateSessions(_)
         | Plan::DiscardTemp
         | Plan::DiscardAll
         | Plan::DropObjects(_)
@@ -92,6 +94,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::ShowAllVariables
         | Plan::ShowCreate(_)
         | Plan::ShowVariable(_)
+        | Plan::ShowTransactionWrites
         | Plan::InspectShard(_)
         | Plan::SetVariable(_)
         | Plan::ResetVariable(_)
@@ -103,6 +106,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::CopyTo(_)
         | Plan::ExplainPlan(_)
         | Plan::ExplainPushdown(_)
+        | Plan::ExplainAnalyze(_)
         | Plan::ExplainSinkSchema(_)
         | Plan::Insert(_)
         | Plan::AlterNoop(_)
@@ -111,6 +115,7 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::AlterClusterReplicaRename(_)
         | Plan::AlterCluster(_)
         | Plan::AlterConnection(_)
+        | Plan::AlterDatabase(_)
         | Plan::AlterSource(_)
         | Plan::AlterSetCluster(_)
         | Plan::AlterItemRename(_)
@@ -126,9 +131,12 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         | Plan::AlterRole(_)
         | Plan::AlterOwner(_)
         | Plan::AlterTableAddColumn(_)
+        | Plan::AlterTableRowCountHint(_)
         | Plan::Declare(_)
         | Plan::Fetch(_)
         | Plan::Close(_)
+        | Plan::HandoffCursor(_)
+        | Plan::ClaimHandoff(_)
         | Plan::ReadThenWrite(_)
         | Plan::Prepare(_)
         | Plan::Execute(_)
@@ -154,6 +162,11 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         return TargetCluster::Active;
     }
 
+    // Per-replica introspection relations are only routed if the user has opted in, because we
+    // then read the introspection data of the `mz_catalog_server` cluster rather than that of the
+    // active cluster.
+    let route_replica_introspection = session.vars().auto_route_replica_introspection_queries();
+
     // These dependencies are just existing dataflows that are referenced in the plan.
     let mut depends_on = depends_on.into_iter().peekable();
     let has_dependencies = depends_on.peek().is_some();
@@ -165,7 +178,8 @@ pub fn auto_run_on_catalog_server<'a, 's, 'p>(
         let schema = entry.name().qualifiers.schema_spec;
 
         let system_only = catalog.state().is_system_schema_specifier(schema);
-        let non_replica = catalog.state().introspection_dependencies(id).is_empty();
+        let non_replica = route_replica_introspection
+            || catalog.state().introspection_dependencies(id).is_empty();
 
         system_only && non_replica
     });
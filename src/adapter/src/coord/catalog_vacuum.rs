@@ -0,0 +1,134 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Periodic and on-demand vacuuming of the durable catalog.
+//!
+//! A vacuum prunes audit log events that have outlived `audit_log_retention_period`. The
+//! expired events are found by a task off the coordinator's main loop, and pruned in batches of
+//! bounded size. Persist physically compacts away the retracted events, since the catalog
+//! downgrades its since on every write.
+
+use mz_adapter_types::dyncfgs::{AUDIT_LOG_RETENTION_PERIOD, CATALOG_VACUUM_INTERVAL};
+use mz_audit_log::VersionedEvent;
+use mz_ore::cast::CastFrom;
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_repr::{Datum, Row};
+use tracing::{info, warn};
+
+use crate::catalog;
+use crate::coord::maintenance::MaintenanceWork;
+use crate::coord::{Coordinator, ExecuteContext, Message};
+use crate::error::AdapterError;
+
+/// The maximum number of audit log events that a single vacuum prunes.
+const MAX_PRUNED_AUDIT_LOG_EVENTS: usize = 10_000;
+
+impl Coordinator {
+    /// Schedules the next vacuum of the durable catalog, `catalog_vacuum_interval` from now.
+    pub(crate) fn schedule_catalog_vacuum(&self) {
+        let interval = CATALOG_VACUUM_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message("catalog_vacuum_schedule", interval, Message::CatalogVacuum);
+    }
+
+    /// Starts a scheduled vacuum of the durable catalog, if enabled. The next vacuum is scheduled
+    /// once it has finished.
+    pub(crate) fn scheduled_catalog_vacuum(&mut self) {
+        let interval = CATALOG_VACUUM_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        // Read-only environments are not allowed to write to the catalog.
+        if interval.is_zero() || self.controller.read_only() {
+            self.schedule_catalog_vacuum();
+            return;
+        }
+        if let Some(delay) = self.defer_maintenance(MaintenanceWork::CatalogVacuum) {
+            self.retry_maintenance_after(delay, Message::CatalogVacuum);
+            return;
+        }
+        self.spawn_catalog_vacuum(None);
+    }
+
+    /// Starts a vacuum of the durable catalog, which looks for expired audit log events off the
+    /// coordinator's main loop, and prunes them in [`Coordinator::finish_catalog_vacuum`].
+    ///
+    /// `ctx` is the `mz_vacuum_catalog()` call that requested the vacuum, if any, and is retired
+    /// with the number of pruned events.
+    pub(crate) fn spawn_catalog_vacuum(&self, ctx: Option<ExecuteContext>) {
+        let retention_period =
+            AUDIT_LOG_RETENTION_PERIOD.get(self.catalog().system_config().dyncfgs());
+        let expired = if retention_period.is_zero() {
+            None
+        } else {
+            let now: EpochMillis = (self.catalog().config().now)();
+            let cutoff = now.saturating_sub(
+                EpochMillis::try_from(retention_period.as_millis()).unwrap_or(EpochMillis::MAX),
+            );
+            Some(
+                self.catalog()
+                    .expired_audit_log_events(cutoff, MAX_PRUNED_AUDIT_LOG_EVENTS),
+            )
+        };
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "catalog_vacuum", async move {
+            let expired = match expired {
+                Some(expired) => expired.await,
+                None => Ok(Vec::new()),
+            };
+            if let Err(e) = internal_cmd_tx.send(Message::CatalogVacuumReady { ctx, expired }) {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Prunes the expired audit log events found by [`Coordinator::spawn_catalog_vacuum`].
+    pub(crate) async fn finish_catalog_vacuum(
+        &mut self,
+        ctx: Option<ExecuteContext>,
+        expired: Result<Vec<VersionedEvent>, AdapterError>,
+    ) {
+        let result = match expired {
+            Ok(events) => self.prune_audit_log(events).await,
+            Err(e) => Err(e),
+        };
+        match ctx {
+            Some(ctx) => ctx.retire(result.map(|pruned_events| {
+                Self::send_immediate_rows(Row::pack_slice(&[Datum::UInt64(u64::cast_from(
+                    pruned_events,
+                ))]))
+            })),
+            // Keep vacuuming right away if more events might have expired.
+            None => match result {
+                Ok(pruned_events) if pruned_events == MAX_PRUNED_AUDIT_LOG_EVENTS => {
+                    let _ = self.internal_cmd_tx.send(Message::CatalogVacuum);
+                }
+                Ok(_) => self.schedule_catalog_vacuum(),
+                Err(e) => {
+                    warn!("failed to vacuum catalog: {e:?}");
+                    self.schedule_catalog_vacuum();
+                }
+            },
+        }
+    }
+
+    /// Removes `events` from the audit log, and returns how many were removed.
+    async fn prune_audit_log(
+        &mut self,
+        events: Vec<VersionedEvent>,
+    ) -> Result<usize, AdapterError> {
+        let pruned_events = events.len();
+        if !events.is_empty() {
+            self.catalog_transact(None, vec![catalog::Op::PruneAuditLog { events }])
+                .await?;
+        }
+        info!(pruned_audit_log_events = pruned_events, "vacuumed catalog");
+        self.metrics
+            .catalog_vacuum_pruned_audit_log_events
+            .inc_by(u64::cast_from(pruned_events));
+        Ok(pruned_events)
+    }
+}
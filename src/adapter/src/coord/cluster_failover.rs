@@ -0,0 +1,73 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Failover between the clusters in a session's cluster failover list.
+//!
+//! Sessions can set `cluster` to an ordered, comma-separated list of clusters. Queries target the
+//! first cluster in the list that is live, i.e. that has a ready replica on which all of its
+//! dataflows are hydrated. If no cluster in the list is live, queries target the first cluster
+//! that exists, as they would without a list.
+
+use mz_catalog::memory::objects::Cluster;
+use mz_controller::clusters::ClusterStatus;
+
+use crate::coord::{ClusterReplicaStatuses, Coordinator, TargetCluster};
+use crate::notice::AdapterNotice;
+use crate::session::Session;
+
+impl Coordinator {
+    /// Selects the cluster from the session's cluster failover list that a query should target,
+    /// notifying the session if that isn't the preferred cluster.
+    pub(crate) fn fail_over_active_cluster(&self, session: &Session) -> TargetCluster {
+        // Any errors are reported once the active cluster is resolved for the query.
+        let Ok(preferred) = self.catalog().active_cluster(session) else {
+            return TargetCluster::Active;
+        };
+        let names = self
+            .catalog()
+            .state()
+            .cluster_failover_list(session.vars().cluster());
+        if names.len() <= 1 {
+            return TargetCluster::Active;
+        }
+
+        let live = names
+            .into_iter()
+            .filter_map(|name| self.catalog().resolve_cluster(name).ok())
+            .find(|cluster| self.cluster_is_live(cluster));
+        match live {
+            Some(cluster) if cluster.id != preferred.id => {
+                session.add_notice(AdapterNotice::ClusterFailover {
+                    from: preferred.name.clone(),
+                    to: cluster.name.clone(),
+                });
+                TargetCluster::Failover(cluster.id)
+            }
+            // Either the preferred cluster is live, or no cluster is.
+            _ => TargetCluster::Active,
+        }
+    }
+
+    /// Reports whether `cluster` has a ready replica on which all of its dataflows are hydrated.
+    fn cluster_is_live(&self, cluster: &Cluster) -> bool {
+        let Ok(hydrated_replicas) = self.controller.compute.hydrated_replicas(cluster.id) else {
+            return false;
+        };
+        hydrated_replicas.into_iter().any(|replica_id| {
+            self.cluster_replica_statuses
+                .try_get_cluster_replica_statuses(cluster.id, replica_id)
+                .map_or(false, |statuses| {
+                    matches!(
+                        ClusterReplicaStatuses::cluster_replica_status(statuses),
+                        ClusterStatus::Ready
+                    )
+                })
+        })
+    }
+}
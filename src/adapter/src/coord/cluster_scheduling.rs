@@ -7,17 +7,24 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use crate::coord::maintenance::MaintenanceWork;
 use crate::coord::{Coordinator, Message};
+use crate::util::diff_keyed_rows;
+use crate::AdapterNotice;
 use itertools::Itertools;
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::CLUSTER_SCHEDULE_SUSPENSION_GRACE_PERIOD;
 use mz_audit_log::SchedulingDecisionsWithReasonsV1;
 use mz_catalog::memory::objects::{CatalogItem, ClusterVariant, ClusterVariantManaged};
 use mz_controller_types::ClusterId;
 use mz_ore::collections::CollectionExt;
+use mz_ore::now::EpochMillis;
 use mz_ore::soft_panic_or_log;
 use mz_repr::adt::interval::Interval;
 use mz_repr::GlobalId;
 use mz_sql::catalog::CatalogCluster;
 use mz_sql::plan::ClusterSchedule;
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 
@@ -54,6 +61,17 @@ pub struct RefreshDecision {
     hydration_time_estimate: Duration,
 }
 
+/// The deferred shutdowns of scheduled clusters.
+#[derive(Debug, Default)]
+pub(crate) struct ClusterSuspensions {
+    /// The clusters whose shutdown is deferred for running queries and subscriptions, and until
+    /// when it is deferred at most.
+    deferred: BTreeMap<ClusterId, EpochMillis>,
+    /// The combined decision and the deferral of each cluster, as last recorded in
+    /// `mz_cluster_scheduling_decisions`.
+    reported: BTreeMap<ClusterId, (bool, Option<EpochMillis>)>,
+}
+
 impl SchedulingDecision {
     pub fn reasons_to_audit_log_reasons<'a, I>(reasons: I) -> SchedulingDecisionsWithReasonsV1
     where
@@ -213,6 +231,7 @@ impl Coordinator {
     ///   decisions.
     /// 3. For each cluster, it sums up `cluster_scheduling_decisions`, checks the summed up decision
     ///   against the cluster state, and turns cluster On/Off if needed.
+    /// 4. Records the summed up decisions in `mz_cluster_scheduling_decisions`.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) async fn handle_scheduling_decisions(
         &mut self,
@@ -265,8 +284,14 @@ impl Coordinator {
             }
         }
 
+        // Also forget the deferred shutdowns of these clusters.
+        self.cluster_suspensions
+            .deferred
+            .retain(|cluster_id, _| self.cluster_scheduling_decisions.contains_key(cluster_id));
+
         // 3. Act on `scheduling_decisions` where needed.
         let mut altered_a_cluster = false;
+        let mut cluster_decisions = BTreeMap::new();
         for (cluster_id, decisions) in self.cluster_scheduling_decisions.clone() {
             // We touch a cluster only when all policies have made a decision about it. This is
             // to ensure that after an envd restart all policies have a chance to run at least once
@@ -285,7 +310,21 @@ impl Coordinator {
                     panic!("cleaned up unmanaged clusters above");
                 };
                 let has_replica = managed_config.replication_factor > 0; // Is it On?
+                cluster_decisions.insert(cluster_id, needs_replica);
+                if needs_replica || !has_replica {
+                    self.cluster_suspensions.deferred.remove(&cluster_id);
+                }
                 if needs_replica != has_replica {
+                    // Turning a cluster Off is disruptive, so it may have to be deferred. If so,
+                    // we'll try again in the next scheduling round.
+                    if !needs_replica
+                        && (self
+                            .defer_maintenance(MaintenanceWork::ClusterShutdown)
+                            .is_some()
+                            || self.defer_cluster_suspension(cluster_id))
+                    {
+                        continue;
+                    }
                     // Turn the cluster On or Off.
                     altered_a_cluster = true;
                     managed_config.replication_factor = if needs_replica { 1 } else { 0 };
@@ -321,12 +360,99 @@ impl Coordinator {
             }
         }
 
+        // 4. Record the decisions in `mz_cluster_scheduling_decisions`.
+        let rows = cluster_decisions
+            .into_iter()
+            .map(|(cluster_id, cluster_on)| {
+                let deferred_until = self.cluster_suspensions.deferred.get(&cluster_id).copied();
+                (cluster_id, (cluster_on, deferred_until))
+            })
+            .collect();
+        self.replace_cluster_scheduling_decisions(rows);
+
         self.metrics
             .handle_scheduling_decisions_seconds
             .with_label_values(&[altered_a_cluster.to_string().as_str()])
             .observe((Instant::now() - start_time).as_secs_f64());
     }
 
+    /// Decides whether turning off the cluster `cluster_id` has to wait for the queries and
+    /// subscriptions running on it.
+    ///
+    /// When the cluster is first about to be turned off, the sessions running queries or
+    /// subscriptions on it are notified, and turning off the cluster is deferred until they
+    /// finish, but at most for `cluster_schedule_suspension_grace_period`.
+    fn defer_cluster_suspension(&mut self, cluster_id: ClusterId) -> bool {
+        let conn_ids: BTreeSet<ConnectionId> = self
+            .pending_peeks
+            .values()
+            .filter(|peek| peek.cluster_id == cluster_id)
+            .map(|peek| peek.conn_id.clone())
+            .chain(
+                self.active_compute_sinks
+                    .values()
+                    .filter(|sink| sink.cluster_id() == cluster_id)
+                    .map(|sink| sink.connection_id().clone()),
+            )
+            .collect();
+        let now = self.now();
+
+        if let Some(deferred_until) = self.cluster_suspensions.deferred.get(&cluster_id) {
+            if !conn_ids.is_empty() && now < *deferred_until {
+                return true;
+            }
+            debug!(%cluster_id, "turning off cluster after deferring it");
+            self.cluster_suspensions.deferred.remove(&cluster_id);
+            return false;
+        }
+
+        let grace_period =
+            CLUSTER_SCHEDULE_SUSPENSION_GRACE_PERIOD.get(self.catalog().system_config().dyncfgs());
+        let defer = !conn_ids.is_empty() && !grace_period.is_zero();
+        let notice = AdapterNotice::ScheduledClusterSuspension {
+            cluster: self.catalog().get_cluster(cluster_id).name.clone(),
+            grace_period: if defer { grace_period } else { Duration::ZERO },
+        };
+        for conn_id in &conn_ids {
+            self.send_notice(conn_id, notice.clone());
+        }
+        if defer {
+            let grace_period =
+                EpochMillis::try_from(grace_period.as_millis()).unwrap_or(EpochMillis::MAX);
+            debug!(%cluster_id, ?conn_ids, "deferring turning off cluster");
+            self.cluster_suspensions
+                .deferred
+                .insert(cluster_id, now.saturating_add(grace_period));
+        }
+        defer
+    }
+
+    /// Replaces the rows of `mz_cluster_scheduling_decisions` with `rows`, updating only the rows
+    /// of the clusters whose decision changed.
+    fn replace_cluster_scheduling_decisions(
+        &mut self,
+        rows: BTreeMap<ClusterId, (bool, Option<EpochMillis>)>,
+    ) {
+        if rows == self.cluster_suspensions.reported {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.cluster_suspensions.reported, rows);
+        let state = self.catalog().state();
+        let updates: Vec<_> = diff_keyed_rows(&previous, &self.cluster_suspensions.reported)
+            .into_iter()
+            .map(|((cluster_id, (cluster_on, deferred_until)), diff)| {
+                let update = state.pack_cluster_scheduling_decision_update(
+                    *cluster_id,
+                    *cluster_on,
+                    *deferred_until,
+                    diff,
+                );
+                state.resolve_builtin_table_update(update)
+            })
+            .collect();
+        self.builtin_table_update().background(updates);
+    }
+
     /// Returns the managed config for a cluster. Returns None if the cluster doesn't exist or if
     /// it's an unmanaged cluster.
     fn get_managed_cluster_config(&self, cluster_id: ClusterId) -> Option<ClusterVariantManaged> {
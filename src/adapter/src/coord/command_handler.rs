@@ -14,6 +14,7 @@ use differential_dataflow::lattice::Lattice;
 use mz_adapter_types::dyncfgs::ALLOW_USER_SESSIONS;
 use mz_sql::session::metadata::SessionMetadata;
 use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use futures::future::LocalBoxFuture;
@@ -21,7 +22,6 @@ use futures::FutureExt;
 use mz_adapter_types::connection::{ConnectionId, ConnectionIdType};
 use mz_catalog::memory::objects::{CatalogItem, DataSourceDesc, Source};
 use mz_catalog::SYSTEM_CONN_ID;
-use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{instrument, soft_panic_or_log};
 use mz_repr::role_id::RoleId;
@@ -38,6 +38,7 @@ use mz_sql::plan::{
 };
 use mz_sql::pure::{
     materialized_view_option_contains_temporal, purify_create_materialized_view_options,
+    PurificationProgress,
 };
 use mz_sql::rbac;
 use mz_sql::rbac::CREATE_ITEM_USAGE;
@@ -60,6 +61,8 @@ use crate::command::{
     CatalogSnapshot, Command, ExecuteResponse, GetVariablesResponse, StartupResponse,
 };
 use crate::coord::appends::{Deferred, PendingWriteTxn};
+use crate::coord::cancellation::{CancellationMethod, CancellationOutcome};
+use crate::coord::failpoints::{self, FailpointAction};
 use crate::coord::{
     ConnMeta, Coordinator, DeferredPlanStatement, Message, PendingTxn, PlanStatement, PlanValidity,
     PurifiedStatementReady,
@@ -67,6 +70,7 @@ use crate::coord::{
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
 use crate::session::{Session, TransactionOps, TransactionStatus};
+use crate::statement_logging::StatementExecutionMetadata;
 use crate::util::{ClientTransmitter, ResultExt};
 use crate::webhook::{
     AppendWebhookResponse, AppendWebhookValidator, WebhookAppender, WebhookAppenderInvalidator,
@@ -84,6 +88,12 @@ impl Coordinator {
             if let Some(session) = cmd.session_mut() {
                 session.apply_external_metadata_updates();
             }
+            if let Some(session) = cmd.session() {
+                // The connection has finished its previous statement, so a statement admitted to
+                // install a compute sink that hasn't installed it has failed.
+                let conn_id = session.conn_id().clone();
+                self.release_compute_sink_admission(&conn_id);
+            }
             match cmd {
                 Command::Startup {
                     tx,
@@ -125,8 +135,9 @@ impl Coordinator {
                 Command::CancelRequest {
                     conn_id,
                     secret_key,
+                    origin,
                 } => {
-                    self.handle_cancel(conn_id, secret_key).await;
+                    self.handle_cancel(conn_id, secret_key, origin).await;
                 }
 
                 Command::PrivilegedCancelRequest { conn_id } => {
@@ -235,9 +246,21 @@ impl Coordinator {
                     let _ = tx.send(self.dump());
                 }
 
+                Command::GetPlanGraph { conn_id, tx } => {
+                    let _ = tx.send(self.handle_get_plan_graph(conn_id));
+                }
+
                 Command::AllowWrites { tx } => {
                     self.handle_allow_writes(tx).await;
                 }
+
+                Command::Simulate { tx, .. } => {
+                    // Simulations are run by the main loop, which only hands us the commands
+                    // that a simulation step applies.
+                    let _ = tx.send(Err(AdapterError::Internal(
+                        "simulations can't be nested".into(),
+                    )));
+                }
             }
         }
         .instrument(debug_span!("handle_command"))
@@ -325,6 +348,7 @@ impl Coordinator {
                     conn_id: conn_id.clone(),
                     authenticated_role: role_id,
                     deferred_lock: None,
+                    plan_graph: None,
                 };
                 let update = self.catalog().state().pack_session_update(&conn, 1);
                 let update = self.catalog().state().resolve_builtin_table_update(update);
@@ -412,19 +436,24 @@ impl Coordinator {
         // outer execute should be considered finished once the inner one is.
         outer_context: Option<ExecuteContextExtra>,
     ) {
+        let span_context = tracing::Span::current()
+            .context()
+            .span()
+            .span_context()
+            .clone();
+        let trace_id = span_context.is_valid().then(|| span_context.trace_id());
         if session.vars().emit_trace_id_notice() {
-            let span_context = tracing::Span::current()
-                .context()
-                .span()
-                .span_context()
-                .clone();
-            if span_context.is_valid() {
-                session.add_notice(AdapterNotice::QueryTrace {
-                    trace_id: span_context.trace_id(),
-                });
+            if let Some(trace_id) = trace_id {
+                session.add_notice(AdapterNotice::QueryTrace { trace_id });
             }
         }
 
+        if self.terminated_conns.remove(session.conn_id()) {
+            let extra = outer_context.unwrap_or_else(Default::default);
+            let ctx = ExecuteContext::from_parts(tx, self.internal_cmd_tx.clone(), session, extra);
+            return ctx.retire(Err(AdapterError::SessionTerminated));
+        }
+
         if let Err(err) = self.verify_portal(&mut session, &portal_name) {
             // If statement logging hasn't started yet, we don't need
             // to add any "end" event, so just make up a no-op
@@ -442,6 +471,8 @@ impl Coordinator {
             return ctx.retire(Err(err));
         }
 
+        self.prepare_paged_cursor(&session, &portal_name, outer_context.is_some());
+
         // The reference to `portal` can't outlive `session`, which we
         // use to construct the context, so scope the reference to this block where we
         // get everything we need from the portal for later.
@@ -459,11 +490,35 @@ impl Coordinator {
                 // being executed is the one that should be retired once this finishes.
                 extra
             } else {
-                // This is a new statement, log it and return the context
+                // This is a new statement, classify it and log it, and return the context
+                if let Some(stmt) = &stmt {
+                    self.apply_workload_class(&mut session, stmt);
+                }
                 let maybe_uuid =
                     self.begin_statement_execution(&mut session, params.clone(), &logging);
+                // Only logged statements have lifecycle events to look up by correlation id.
+                if let Some(id) = maybe_uuid {
+                    if session.vars().emit_statement_correlation_id_notice() {
+                        session.add_notice(AdapterNotice::StatementCorrelationId {
+                            trace_id,
+                            statement_id: id.uuid(),
+                        });
+                    }
+                }
+
+                // Retirement hooks are passed the metadata of every execution, sampled or not.
+                let retirement_metadata =
+                    (!self.statement_retirement_hooks.is_empty()).then(|| {
+                        StatementExecutionMetadata {
+                            session_id: session.uuid(),
+                            user: session.user().name.clone(),
+                            application_name: session.application_name().to_string(),
+                            database_name: session.vars().database().to_string(),
+                            began_at: self.now(),
+                        }
+                    });
 
-                ExecuteContextExtra::new(maybe_uuid)
+                ExecuteContextExtra::new(maybe_uuid, retirement_metadata)
             };
             let ctx = ExecuteContext::from_parts(tx, self.internal_cmd_tx.clone(), session, extra);
             (stmt, ctx, params)
@@ -480,6 +535,16 @@ impl Coordinator {
             .query_total
             .with_label_values(&[session_type, stmt_type])
             .inc();
+        let database = self.database_metrics_label(ctx.session());
+        if let Some(database) = &database {
+            self.metrics
+                .database_statements
+                .with_label_values(&[database])
+                .inc();
+        }
+        ctx.session_mut()
+            .metrics_mut()
+            .set_database(database.as_deref());
         match &*stmt {
             Statement::Subscribe(SubscribeStatement { output, .. })
             | Statement::Copy(CopyStatement {
@@ -579,6 +644,12 @@ impl Coordinator {
                         "DECLARE CURSOR".into(),
                     )));
                 }
+                if let Statement::ClaimHandoff(_) = &*stmt {
+                    // Like a declared cursor, a claimed cursor would be closed right away.
+                    return ctx.retire(Err(AdapterError::OperationRequiresTransaction(
+                        "CLAIM HANDOFF".into(),
+                    )));
+                }
             }
 
             // Implicit or explicit transactions.
@@ -594,7 +665,10 @@ impl Coordinator {
                 match &*stmt {
                     // Statements that are safe in a transaction. We still need to verify that we
                     // don't interleave reads and writes since we can't perform those serializably.
-                    Statement::Close(_)
+                    Statement::AlterSystemTerminateSessions(_)
+                    | Statement::Cancel(_)
+                    | Statement::ClaimHandoff(_)
+                    | Statement::Close(_)
                     | Statement::Commit(_)
                     | Statement::Copy(_)
                     | Statement::Deallocate(_)
@@ -603,9 +677,11 @@ impl Coordinator {
                     | Statement::Execute(_)
                     | Statement::ExplainPlan(_)
                     | Statement::ExplainPushdown(_)
+                    | Statement::ExplainAnalyze(_)
                     | Statement::ExplainTimestamp(_)
                     | Statement::ExplainSinkSchema(_)
                     | Statement::Fetch(_)
+                    | Statement::HandoffCursor(_)
                     | Statement::Prepare(_)
                     | Statement::Rollback(_)
                     | Statement::Select(_)
@@ -646,6 +722,7 @@ impl Coordinator {
                     // Statements below must by run singly (in Started).
                     Statement::AlterCluster(_)
                     | Statement::AlterConnection(_)
+                    | Statement::AlterDatabase(_)
                     | Statement::AlterDefaultPrivileges(_)
                     | Statement::AlterIndex(_)
                     | Statement::AlterSetCluster(_)
@@ -659,6 +736,7 @@ impl Coordinator {
                     | Statement::AlterSystemResetAll(_)
                     | Statement::AlterSystemSet(_)
                     | Statement::AlterTableAddColumn(_)
+                    | Statement::AlterTableRowCountHint(_)
                     | Statement::CreateCluster(_)
                     | Statement::CreateClusterReplica(_)
                     | Statement::CreateConnection(_)
@@ -787,57 +865,83 @@ impl Coordinator {
         let (stmt, resolved_ids) = match stmt {
             // Various statements must be purified off the main coordinator thread of control.
             stmt if Self::must_spawn_purification(&stmt) => {
+                let hosts = self.purification_external_hosts(ctx.session(), &stmt, &resolved_ids);
                 let internal_cmd_tx = self.internal_cmd_tx.clone();
-                let conn_id = ctx.session().conn_id().clone();
                 let catalog = self.owned_catalog();
                 let now = self.now();
                 let otel_ctx = OpenTelemetryContext::obtain();
                 let current_storage_configuration = self.controller.storage.config().clone();
-                task::spawn(|| format!("purify:{conn_id}"), async move {
-                    let transient_revision = catalog.transient_revision();
-                    let catalog = catalog.for_session(ctx.session());
-
-                    // Checks if the session is authorized to purify a statement. Usually
-                    // authorization is checked after planning, however purification happens before
-                    // planning, which may require the use of some connections and secrets.
-                    if let Err(e) = rbac::check_usage(
-                        &catalog,
-                        ctx.session(),
-                        &resolved_ids,
-                        &CREATE_ITEM_USAGE,
-                    ) {
-                        return ctx.retire(Err(e.into()));
-                    }
+                self.spawn_external_task("purify", hosts, ctx, move |ctx| {
+                    async move {
+                        let transient_revision = catalog.transient_revision();
+                        let catalog = catalog.for_session(ctx.session());
+
+                        // Checks if the session is authorized to purify a statement. Usually
+                        // authorization is checked after planning, however purification happens
+                        // before planning, which may require the use of some connections and
+                        // secrets.
+                        if let Err(e) = rbac::check_usage(
+                            &catalog,
+                            ctx.session(),
+                            &resolved_ids,
+                            &CREATE_ITEM_USAGE,
+                        ) {
+                            return ctx.retire(Err(e.into()));
+                        }
 
-                    let (result, cluster_id) = mz_sql::pure::purify_statement(
-                        catalog,
-                        now,
-                        stmt,
-                        &current_storage_configuration,
-                    )
-                    .await;
-                    let result = result.map_err(|e| e.into());
-                    let plan_validity = PlanValidity::new(
-                        transient_revision,
-                        resolved_ids.0,
-                        cluster_id,
-                        None,
-                        ctx.session().role_metadata().clone(),
-                    );
-                    // It is not an error for purification to complete after `internal_cmd_rx` is dropped.
-                    let result = internal_cmd_tx.send(Message::PurifiedStatementReady(
-                        PurifiedStatementReady {
-                            ctx,
-                            result,
-                            params,
-                            plan_validity,
-                            original_stmt,
-                            otel_ctx,
-                        },
-                    ));
-                    if let Err(e) = result {
-                        tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                        // Purification may take a while, so keep the client informed about its
+                        // progress.
+                        let session = ctx.session();
+                        let progress = |progress| match progress {
+                            PurificationProgress::TablesValidated { validated, total } => {
+                                session.add_progress_notice(
+                                    AdapterNotice::SourcePurificationProgress { validated, total },
+                                )
+                            }
+                        };
+                        let (result, cluster_id) = mz_sql::pure::purify_statement(
+                            catalog,
+                            now,
+                            stmt,
+                            &current_storage_configuration,
+                            &progress,
+                        )
+                        .await;
+                        let mut result = result.map_err(|e| e.into());
+                        // Purification runs off the coordinator, so delays can sleep here.
+                        match failpoints::eval(failpoints::PURIFICATION_COMPLETE) {
+                            Some(FailpointAction::Error(arg)) => {
+                                result = Err(failpoints::failpoint_error(arg));
+                            }
+                            Some(FailpointAction::Delay(delay)) => {
+                                tokio::time::sleep(delay).await;
+                            }
+                            None => {}
+                        }
+                        let plan_validity = PlanValidity::new(
+                            transient_revision,
+                            resolved_ids.0,
+                            cluster_id,
+                            None,
+                            ctx.session().role_metadata().clone(),
+                        );
+                        // It is not an error for purification to complete after `internal_cmd_rx`
+                        // is dropped.
+                        let result = internal_cmd_tx.send(Message::PurifiedStatementReady(
+                            PurifiedStatementReady {
+                                ctx,
+                                result,
+                                params,
+                                plan_validity,
+                                original_stmt,
+                                otel_ctx,
+                            },
+                        ));
+                        if let Err(e) = result {
+                            tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                        }
                     }
+                    .boxed()
                 });
                 return;
             }
@@ -900,6 +1004,7 @@ impl Coordinator {
             }
 
             Statement::ExplainPlan(ExplainPlanStatement {
+                parameters,
                 stage,
                 with_options,
                 format,
@@ -930,6 +1035,7 @@ impl Coordinator {
                 );
 
                 let purified_stmt = Statement::ExplainPlan(ExplainPlanStatement {
+                    parameters,
                     stage,
                     with_options,
                     format,
@@ -1130,6 +1236,12 @@ impl Coordinator {
         }
     }
 
+    /// Returns the plan graph recorded for connection `conn_id`, rendered as JSON.
+    fn handle_get_plan_graph(&self, conn_id: ConnectionIdType) -> Option<serde_json::Value> {
+        let graph = self.active_conns.get(&conn_id)?.plan_graph.as_ref()?;
+        Some(serde_json::to_value(graph).expect("plan graphs are valid JSON"))
+    }
+
     /// Instruct the dataflow layer to cancel any ongoing, interactive work for
     /// the named `conn_id` if the correct secret key is specified.
     ///
@@ -1137,26 +1249,53 @@ impl Coordinator {
     /// `ConnectionId` because this method gets called by external clients when
     /// they request to cancel a request.
     #[mz_ore::instrument(level = "debug")]
-    async fn handle_cancel(&mut self, conn_id: ConnectionIdType, secret_key: u32) {
-        if let Some((id_handle, conn_meta)) = self.active_conns.get_key_value(&conn_id) {
+    async fn handle_cancel(
+        &mut self,
+        conn_id: ConnectionIdType,
+        secret_key: u32,
+        origin: Option<IpAddr>,
+    ) {
+        let outcome = match self.active_conns.get_key_value(&conn_id) {
             // If the secret key specified by the client doesn't match the
             // actual secret key for the target connection, we treat this as a
             // rogue cancellation request and ignore it.
-            if conn_meta.secret_key != secret_key {
-                return;
+            Some((_id_handle, conn_meta)) if conn_meta.secret_key != secret_key => {
+                CancellationOutcome::InvalidSecretKey
+            }
+            Some((id_handle, _conn_meta)) => {
+                // Now that we've verified the secret key, this is a privileged
+                // cancellation request. We can upgrade the raw connection ID to a
+                // proper `IdHandle`.
+                self.handle_privileged_cancel(id_handle.clone()).await;
+                CancellationOutcome::Canceled
+            }
+            None => CancellationOutcome::UnknownConnection,
+        };
+        match outcome {
+            CancellationOutcome::Canceled => {
+                self.record_cancellation(
+                    conn_id,
+                    CancellationMethod::Protocol,
+                    origin,
+                    None,
+                    outcome,
+                )
+                .await;
+            }
+            // Anyone can send rejected requests, so we only record them in aggregate.
+            CancellationOutcome::InvalidSecretKey | CancellationOutcome::UnknownConnection => {
+                self.aggregate_rejected_cancellation(conn_id, origin, outcome);
             }
-
-            // Now that we've verified the secret key, this is a privileged
-            // cancellation request. We can upgrade the raw connection ID to a
-            // proper `IdHandle`.
-            self.handle_privileged_cancel(id_handle.clone()).await;
         }
     }
 
     /// Unconditionally instructs the dataflow layer to cancel any ongoing,
     /// interactive work for the named `conn_id`.
+    ///
+    /// Returns whether a statement of the connection that was waiting or
+    /// staged was canceled.
     #[mz_ore::instrument(level = "debug")]
-    pub(crate) async fn handle_privileged_cancel(&mut self, conn_id: ConnectionId) {
+    pub(crate) async fn handle_privileged_cancel(&mut self, conn_id: ConnectionId) -> bool {
         let mut maybe_ctx = None;
 
         // Cancel pending writes. There is at most one pending write per session.
@@ -1200,6 +1339,18 @@ impl Coordinator {
             maybe_ctx = Some(deferred.ctx);
         }
 
+        // Cancel statements waiting for compute sinks to be retired. There is at most one per
+        // session.
+        if let Some(ctx) = self.cancel_waiting_compute_sink(&conn_id) {
+            maybe_ctx = Some(ctx);
+        }
+
+        // Cancel statements whose purification or connection validation is queued. There is at
+        // most one per session.
+        if let Some(ctx) = self.cancel_queued_external_task(&conn_id) {
+            maybe_ctx = Some(ctx);
+        }
+
         // Cancel reads waiting on being linearized. There is at most one linearized read per
         // session.
         if let Some(pending_read_txn) = self.pending_linearize_read_txns.remove(&conn_id) {
@@ -1207,6 +1358,7 @@ impl Coordinator {
             maybe_ctx = Some(ctx);
         }
 
+        let mut canceled = maybe_ctx.is_some();
         if let Some(ctx) = maybe_ctx {
             ctx.retire(Err(AdapterError::Canceled));
         }
@@ -1215,8 +1367,9 @@ impl Coordinator {
         self.cancel_pending_watchsets(&conn_id);
         self.cancel_compute_sinks_for_conn(&conn_id).await;
         if let Some((tx, _rx)) = self.staged_cancellation.get_mut(&conn_id) {
-            let _ = tx.send(true);
+            canceled |= !tx.send_replace(true);
         }
+        canceled
     }
 
     /// Handle termination of a client session.
@@ -1235,12 +1388,14 @@ impl Coordinator {
         // We do not need to call clear_transaction here because there are no side effects to run
         // based on any session transaction state.
         self.clear_connection(&conn_id).await;
+        self.release_compute_sink_admission(&conn_id);
 
         self.drop_temp_items(&conn_id).await;
         self.catalog_mut()
             .drop_temporary_schema(&conn_id)
             .unwrap_or_terminate("unable to drop temporary schema");
         let conn = self.active_conns.remove(&conn_id).expect("conn must exist");
+        self.terminated_conns.remove(&conn_id);
         let session_type = metrics::session_type_label_value(conn.user());
         self.metrics
             .active_sessions
@@ -1248,6 +1403,7 @@ impl Coordinator {
             .dec();
         self.cancel_pending_peeks(conn.conn_id());
         self.cancel_pending_watchsets(&conn_id);
+        self.forget_starting_paged_cursor(&conn_id);
         self.end_session_for_statement_logging(conn.uuid());
 
         // Queue the builtin table update, but do not wait for it to complete. We explicitly do
@@ -0,0 +1,260 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Limits on the number of concurrently active compute sinks per cluster.
+//!
+//! `max_compute_sinks_per_cluster` caps the number of subscribes and copy tos that can be active
+//! on a cluster at the same time. The limit is enforced when a statement that creates a compute
+//! sink is sequenced: an active compute sink counts towards the limit from the time it is
+//! installed until it is retired, so a few statements sequenced at the same time can exceed the
+//! limit by the number of sinks still being optimized.
+//!
+//! By default, statements that would exceed the limit fail. With
+//! `enable_compute_sink_waitlist`, they instead wait until the cluster has capacity again.
+//! Waiting statements of internal users are admitted before the others, which are admitted in the
+//! order in which they started waiting. An admitted statement counts towards the limit until it
+//! installs its compute sink, or until its connection issues the next command or terminates,
+//! which means it failed. Admission runs whenever a compute sink is retired, an admitted
+//! statement fails, or the system configuration changes, and admits as many waiting statements
+//! as the limit allows.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::{ENABLE_COMPUTE_SINK_WAITLIST, MAX_COMPUTE_SINKS_PER_CLUSTER};
+use mz_controller_types::ClusterId;
+use mz_sql::names::ResolvedIds;
+use mz_sql::plan::Plan;
+use mz_sql::session::metadata::SessionMetadata;
+
+use crate::coord::{Coordinator, Message};
+use crate::error::AdapterError;
+use crate::notice::AdapterNotice;
+use crate::ExecuteContext;
+
+/// A statement that waits for a compute sink of its cluster to be retired.
+#[derive(Debug)]
+struct WaitingComputeSink {
+    ctx: ExecuteContext,
+    plan: Plan,
+    resolved_ids: ResolvedIds,
+}
+
+/// The statements that wait for compute sinks of their clusters to be retired.
+#[derive(Debug, Default)]
+pub(crate) struct ComputeSinkWaitlist {
+    waiting: BTreeMap<ClusterId, VecDeque<WaitingComputeSink>>,
+    /// The connections whose waiting statements have been admitted, but have not installed their
+    /// compute sinks yet, with the clusters they were admitted to.
+    admitted: BTreeMap<ConnectionId, ClusterId>,
+}
+
+impl ComputeSinkWaitlist {
+    /// Removes the next statement to admit to the cluster `cluster_id`, if any.
+    fn pop(&mut self, cluster_id: ClusterId) -> Option<WaitingComputeSink> {
+        let waiting = self.waiting.get_mut(&cluster_id)?;
+        let idx = waiting
+            .iter()
+            .position(|waiting| waiting.ctx.session().user().is_internal())
+            .unwrap_or(0);
+        let next = waiting.remove(idx);
+        if waiting.is_empty() {
+            self.waiting.remove(&cluster_id);
+        }
+        next
+    }
+}
+
+impl Coordinator {
+    /// Returns the number of compute sinks that are active on the cluster `cluster_id`, including
+    /// the compute sinks of admitted statements that have not been installed yet.
+    fn active_compute_sinks_on(&self, cluster_id: ClusterId) -> usize {
+        let active = self
+            .active_compute_sinks
+            .values()
+            .filter(|sink| sink.cluster_id() == cluster_id)
+            .count();
+        let admitted = self
+            .compute_sink_waitlist
+            .admitted
+            .values()
+            .filter(|admitted_to| **admitted_to == cluster_id)
+            .count();
+        active + admitted
+    }
+
+    /// Reports whether another compute sink can be installed on the cluster `cluster_id`.
+    fn has_compute_sink_capacity(&self, cluster_id: ClusterId) -> bool {
+        let limit = MAX_COMPUTE_SINKS_PER_CLUSTER.get(self.catalog().system_config().dyncfgs());
+        limit == 0 || self.active_compute_sinks_on(cluster_id) < limit
+    }
+
+    /// Admits the statement that creates a compute sink on the cluster `cluster_id`.
+    ///
+    /// Returns the statement if it can be sequenced right away. Otherwise, the statement is
+    /// either retired with an error or waits until it can be admitted.
+    pub(crate) fn admit_compute_sink(
+        &mut self,
+        cluster_id: ClusterId,
+        mut ctx: ExecuteContext,
+        plan: Plan,
+        resolved_ids: ResolvedIds,
+    ) -> Option<(ExecuteContext, Plan, ResolvedIds)> {
+        // The admitted statement is sequenced again. It stays admitted until it has installed its
+        // compute sink.
+        if self
+            .compute_sink_waitlist
+            .admitted
+            .contains_key(ctx.session().conn_id())
+        {
+            return Some((ctx, plan, resolved_ids));
+        }
+
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let waitlist = ENABLE_COMPUTE_SINK_WAITLIST.get(dyncfgs);
+        let has_capacity = self.has_compute_sink_capacity(cluster_id);
+        // Statements don't overtake the ones that are already waiting.
+        let has_waiting = self.compute_sink_waitlist.waiting.contains_key(&cluster_id);
+        if has_capacity && !(waitlist && has_waiting) {
+            return Some((ctx, plan, resolved_ids));
+        }
+
+        if !waitlist {
+            let limit = MAX_COMPUTE_SINKS_PER_CLUSTER.get(dyncfgs);
+            let current = self.active_compute_sinks_on(cluster_id);
+            ctx.retire(Err(AdapterError::ResourceExhaustion {
+                resource_type: "compute sink".to_string(),
+                limit_name: MAX_COMPUTE_SINKS_PER_CLUSTER.name().to_string(),
+                desired: (current + 1).to_string(),
+                limit: limit.to_string(),
+                current: current.to_string(),
+            }));
+            return None;
+        }
+
+        ctx.session_mut()
+            .add_notice(AdapterNotice::ComputeSinkWaiting {
+                cluster: self.catalog().get_cluster(cluster_id).name.clone(),
+            });
+        self.compute_sink_waitlist
+            .waiting
+            .entry(cluster_id)
+            .or_default()
+            .push_back(WaitingComputeSink {
+                ctx,
+                plan,
+                resolved_ids,
+            });
+        if has_capacity {
+            // The limit has been raised since the waiting statements started waiting.
+            self.schedule_compute_sink_admission(cluster_id);
+        }
+        None
+    }
+
+    /// Schedules the admission of the statements that wait for the cluster `cluster_id` to have
+    /// compute sink capacity, if any.
+    pub(crate) fn schedule_compute_sink_admission(&self, cluster_id: ClusterId) {
+        if self.compute_sink_waitlist.waiting.contains_key(&cluster_id) {
+            let _ = self
+                .internal_cmd_tx
+                .send(Message::AdmitWaitingComputeSink(cluster_id));
+        }
+    }
+
+    /// Schedules the admission of the waiting statements of all clusters, for example because
+    /// `max_compute_sinks_per_cluster` has changed.
+    pub(crate) fn schedule_all_compute_sink_admissions(&self) {
+        for cluster_id in self.compute_sink_waitlist.waiting.keys() {
+            self.schedule_compute_sink_admission(*cluster_id);
+        }
+    }
+
+    /// Records that the statement of the connection `conn_id` has installed its compute sink, if
+    /// it was admitted from the waitlist. The installed compute sink counts towards the limit in
+    /// its place.
+    pub(crate) fn compute_sink_installed(&mut self, conn_id: &ConnectionId) {
+        self.compute_sink_waitlist.admitted.remove(conn_id);
+    }
+
+    /// Releases the capacity held by the admitted statement of the connection `conn_id`, if any,
+    /// because the statement has ended without installing its compute sink.
+    pub(crate) fn release_compute_sink_admission(&mut self, conn_id: &ConnectionId) {
+        if let Some(cluster_id) = self.compute_sink_waitlist.admitted.remove(conn_id) {
+            self.schedule_compute_sink_admission(cluster_id);
+        }
+    }
+
+    /// Sequences the statements that wait for the cluster `cluster_id` to have compute sink
+    /// capacity, as long as the cluster has the capacity for them.
+    pub(crate) async fn admit_waiting_compute_sinks(&mut self, cluster_id: ClusterId) {
+        if self.catalog().try_get_cluster(cluster_id).is_none() {
+            while let Some(waiting) = self.compute_sink_waitlist.pop(cluster_id) {
+                waiting.ctx.retire(Err(AdapterError::ChangedPlan(format!(
+                    "cluster {cluster_id} was removed"
+                ))));
+            }
+            return;
+        }
+        while self.has_compute_sink_capacity(cluster_id) {
+            let Some(WaitingComputeSink {
+                ctx,
+                plan,
+                resolved_ids,
+            }) = self.compute_sink_waitlist.pop(cluster_id)
+            else {
+                return;
+            };
+            // Objects the statement depends on might have been dropped while it was waiting.
+            if let Some(id) = resolved_ids
+                .0
+                .iter()
+                .find(|id| self.catalog().try_get_entry(id).is_none())
+            {
+                ctx.retire(Err(AdapterError::ChangedPlan(format!(
+                    "dependency {id} was removed"
+                ))));
+                continue;
+            }
+            self.compute_sink_waitlist
+                .admitted
+                .insert(ctx.session().conn_id().clone(), cluster_id);
+            self.sequence_plan(ctx, plan, resolved_ids).await;
+        }
+    }
+
+    /// Removes the statement of the connection `conn_id` that waits for a compute sink to be
+    /// retired, if any.
+    pub(crate) fn cancel_waiting_compute_sink(
+        &mut self,
+        conn_id: &ConnectionId,
+    ) -> Option<ExecuteContext> {
+        self.release_compute_sink_admission(conn_id);
+        let (cluster_id, idx) =
+            self.compute_sink_waitlist
+                .waiting
+                .iter()
+                .find_map(|(cluster_id, waiting)| {
+                    let idx = waiting
+                        .iter()
+                        .position(|waiting| waiting.ctx.session().conn_id() == conn_id)?;
+                    Some((*cluster_id, idx))
+                })?;
+        let waiting = self
+            .compute_sink_waitlist
+            .waiting
+            .get_mut(&cluster_id)
+            .expect("known to exist");
+        let removed = waiting.remove(idx).expect("known to exist");
+        if waiting.is_empty() {
+            self.compute_sink_waitlist.waiting.remove(&cluster_id);
+        }
+        Some(removed.ctx)
+    }
+}
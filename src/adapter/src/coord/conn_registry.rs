@@ -0,0 +1,330 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A registry of state that belongs to client connections, like pending peeks
+//! and installed watch sets.
+//!
+//! Entries live in a slab and are addressed by generation-tagged keys. Once the
+//! slab has grown to the number of concurrently registered entries, inserting and
+//! removing entries does not allocate, and keys of removed entries, like those of
+//! canceled peeks whose responses arrive late, never address a newer entry. The
+//! entries of each connection form an intrusive, doubly linked list through the
+//! slab, so that all entries of a connection can be removed without maintaining a
+//! collection per connection.
+
+use std::collections::btree_map::Entry as BTreeEntry;
+use std::collections::BTreeMap;
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_ore::cast::CastFrom;
+
+/// The key of an entry in a [`ConnRegistry`].
+///
+/// A key is only valid for as long as its entry exists. Entries that are
+/// inserted later may reuse the slot of the entry, but never its key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConnRegistryKey {
+    index: u32,
+    generation: u32,
+}
+
+impl ConnRegistryKey {
+    /// Returns the key encoded as a `u64`.
+    pub fn to_u64(self) -> u64 {
+        (u64::from(self.generation) << 32) | u64::from(self.index)
+    }
+
+    /// Returns the key that `key` encodes, as returned by
+    /// [`ConnRegistryKey::to_u64`].
+    pub fn from_u64(key: u64) -> ConnRegistryKey {
+        ConnRegistryKey {
+            index: u32::try_from(key & u64::from(u32::MAX)).expect("masked to 32 bits"),
+            generation: u32::try_from(key >> 32).expect("shifted to 32 bits"),
+        }
+    }
+}
+
+/// A registry of values that belong to client connections.
+#[derive(Debug)]
+pub struct ConnRegistry<V> {
+    slots: Vec<Slot<V>>,
+    /// The indexes of the unoccupied slots.
+    free: Vec<u32>,
+    /// The index of the most recently inserted entry of each connection.
+    heads: BTreeMap<ConnectionId, u32>,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct Slot<V> {
+    /// Incremented each time the entry of the slot is removed, which
+    /// invalidates its key.
+    generation: u32,
+    entry: Option<Entry<V>>,
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    conn_id: ConnectionId,
+    /// The next more recently inserted entry of the connection.
+    prev: Option<u32>,
+    /// The next less recently inserted entry of the connection.
+    next: Option<u32>,
+    value: V,
+}
+
+impl<V> Default for ConnRegistry<V> {
+    fn default() -> Self {
+        ConnRegistry {
+            slots: Vec::new(),
+            free: Vec::new(),
+            heads: BTreeMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<V> ConnRegistry<V> {
+    /// Returns the number of entries in the registry.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the registry has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts `value` for the connection `conn_id` and returns its key.
+    pub fn insert(&mut self, conn_id: ConnectionId, value: V) -> ConnRegistryKey {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = u32::try_from(self.slots.len()).expect("too many entries");
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: None,
+                });
+                index
+            }
+        };
+        let next = match self.heads.entry(conn_id.clone()) {
+            BTreeEntry::Occupied(mut head) => Some(std::mem::replace(head.get_mut(), index)),
+            BTreeEntry::Vacant(head) => {
+                head.insert(index);
+                None
+            }
+        };
+        if let Some(next) = next {
+            self.linked_mut(next).prev = Some(index);
+        }
+        let slot = &mut self.slots[usize::cast_from(index)];
+        slot.entry = Some(Entry {
+            conn_id,
+            prev: None,
+            next,
+            value,
+        });
+        self.len += 1;
+        ConnRegistryKey {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    /// Returns whether `key` addresses an entry.
+    pub fn contains_key(&self, key: ConnRegistryKey) -> bool {
+        self.entry(key).is_some()
+    }
+
+    /// Returns the value of the entry with `key`, if it exists.
+    pub fn get(&self, key: ConnRegistryKey) -> Option<&V> {
+        self.entry(key).map(|entry| &entry.value)
+    }
+
+    /// Returns the value of the entry with `key` mutably, if it exists.
+    pub fn get_mut(&mut self, key: ConnRegistryKey) -> Option<&mut V> {
+        let slot = self.slots.get_mut(usize::cast_from(key.index))?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        slot.entry.as_mut().map(|entry| &mut entry.value)
+    }
+
+    /// Removes the entry with `key` and returns its value, if it exists.
+    pub fn remove(&mut self, key: ConnRegistryKey) -> Option<V> {
+        let slot = self.slots.get_mut(usize::cast_from(key.index))?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        let Entry {
+            conn_id,
+            prev,
+            next,
+            value,
+        } = slot.entry.take()?;
+        self.release(key.index);
+
+        match prev {
+            Some(prev) => self.linked_mut(prev).next = next,
+            None => match next {
+                Some(next) => {
+                    *self
+                        .heads
+                        .get_mut(&conn_id)
+                        .expect("connection of linked entry has a head") = next;
+                }
+                None => {
+                    self.heads.remove(&conn_id);
+                }
+            },
+        }
+        if let Some(next) = next {
+            self.linked_mut(next).prev = prev;
+        }
+        Some(value)
+    }
+
+    /// Removes all entries of the connection `conn_id` and returns their keys
+    /// and values, the most recently inserted first.
+    pub fn remove_connection(&mut self, conn_id: &ConnectionId) -> Vec<(ConnRegistryKey, V)> {
+        let mut removed = Vec::new();
+        let mut next = self.heads.remove(conn_id);
+        while let Some(index) = next {
+            let slot = &mut self.slots[usize::cast_from(index)];
+            let key = ConnRegistryKey {
+                index,
+                generation: slot.generation,
+            };
+            let entry = slot.entry.take().expect("linked entries are occupied");
+            self.release(index);
+            removed.push((key, entry.value));
+            next = entry.next;
+        }
+        removed
+    }
+
+    /// Returns the keys of the entries of the connection `conn_id`, the most
+    /// recently inserted first.
+    pub fn connection_keys<'a>(
+        &'a self,
+        conn_id: &ConnectionId,
+    ) -> impl Iterator<Item = ConnRegistryKey> + 'a {
+        let mut next = self.heads.get(conn_id).copied();
+        std::iter::from_fn(move || {
+            let index = next?;
+            let slot = &self.slots[usize::cast_from(index)];
+            let entry = slot.entry.as_ref().expect("linked entries are occupied");
+            next = entry.next;
+            Some(ConnRegistryKey {
+                index,
+                generation: slot.generation,
+            })
+        })
+    }
+
+    /// Returns the connections that have entries.
+    pub fn connections(&self) -> impl Iterator<Item = &ConnectionId> {
+        self.heads.keys()
+    }
+
+    /// Returns the key, the connection and the value of each entry, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (ConnRegistryKey, &ConnectionId, &V)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let entry = slot.entry.as_ref()?;
+            let key = ConnRegistryKey {
+                index: u32::try_from(index).expect("slots are addressed by u32"),
+                generation: slot.generation,
+            };
+            Some((key, &entry.conn_id, &entry.value))
+        })
+    }
+
+    /// Returns the value of each entry, in no particular order.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.entry.as_ref().map(|entry| &entry.value))
+    }
+
+    fn entry(&self, key: ConnRegistryKey) -> Option<&Entry<V>> {
+        let slot = self.slots.get(usize::cast_from(key.index))?;
+        if slot.generation != key.generation {
+            return None;
+        }
+        slot.entry.as_ref()
+    }
+
+    fn linked_mut(&mut self, index: u32) -> &mut Entry<V> {
+        self.slots[usize::cast_from(index)]
+            .entry
+            .as_mut()
+            .expect("linked entries are occupied")
+    }
+
+    /// Invalidates the key of the slot at `index`, whose entry has been taken,
+    /// and makes the slot available for reuse.
+    fn release(&mut self, index: u32) {
+        let slot = &mut self.slots[usize::cast_from(index)];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        self.len -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_conn_registry() {
+        let conn_a = ConnectionId::Static(1);
+        let conn_b = ConnectionId::Static(2);
+        let mut registry = ConnRegistry::default();
+
+        let a1 = registry.insert(conn_a.clone(), "a1");
+        let a2 = registry.insert(conn_a.clone(), "a2");
+        let b1 = registry.insert(conn_b.clone(), "b1");
+        let a3 = registry.insert(conn_a.clone(), "a3");
+        assert_eq!(registry.len(), 4);
+        assert_eq!(
+            registry.connection_keys(&conn_a).collect::<Vec<_>>(),
+            vec![a3, a2, a1]
+        );
+
+        // Removing from the middle of a list keeps the rest linked.
+        assert_eq!(registry.remove(a2), Some("a2"));
+        assert_eq!(registry.remove(a2), None);
+        assert_eq!(
+            registry.connection_keys(&conn_a).collect::<Vec<_>>(),
+            vec![a3, a1]
+        );
+
+        // The slot is reused, but the stale key doesn't address the new entry.
+        let b2 = registry.insert(conn_b.clone(), "b2");
+        assert_eq!(b2.index, a2.index);
+        assert_eq!(registry.get(a2), None);
+        assert_eq!(registry.get(b2), Some(&"b2"));
+        assert_eq!(ConnRegistryKey::from_u64(b2.to_u64()), b2);
+
+        assert_eq!(
+            registry.remove_connection(&conn_a),
+            vec![(a3, "a3"), (a1, "a1")]
+        );
+        assert!(!registry.contains_key(a1));
+        assert_eq!(registry.connections().collect::<Vec<_>>(), vec![&conn_b]);
+
+        assert_eq!(registry.remove(b1), Some("b1"));
+        assert_eq!(registry.remove(b2), Some("b2"));
+        assert!(registry.is_empty());
+        assert_eq!(registry.connections().count(), 0);
+        assert_eq!(registry.iter().count(), 0);
+    }
+}
@@ -29,6 +29,10 @@ pub struct CoordinatorInconsistencies {
     active_webhooks: Vec<ActiveWebhookInconsistency>,
     /// Inconsistencies found with our map of cluster statuses.
     cluster_statuses: Vec<ClusterStatusInconsistency>,
+    /// Inconsistencies found with our maps of pending peeks.
+    pending_peeks: Vec<PendingPeekInconsistency>,
+    /// Inconsistencies found with our maps of installed watch sets.
+    watch_sets: Vec<WatchSetInconsistency>,
 }
 
 impl CoordinatorInconsistencies {
@@ -37,6 +41,8 @@ impl CoordinatorInconsistencies {
             && self.read_capabilities.is_empty()
             && self.active_webhooks.is_empty()
             && self.cluster_statuses.is_empty()
+            && self.pending_peeks.is_empty()
+            && self.watch_sets.is_empty()
     }
 }
 
@@ -62,6 +68,14 @@ impl Coordinator {
             inconsistencies.cluster_statuses = cluster_statuses;
         }
 
+        if let Err(pending_peeks) = self.check_pending_peeks() {
+            inconsistencies.pending_peeks = pending_peeks;
+        }
+
+        if let Err(watch_sets) = self.check_watch_sets() {
+            inconsistencies.watch_sets = watch_sets;
+        }
+
         if inconsistencies.is_empty() {
             Ok(())
         } else {
@@ -181,6 +195,67 @@ impl Coordinator {
             Err(inconsistencies)
         }
     }
+
+    /// # Invariants
+    ///
+    /// * All pending peeks should belong to active connections.
+    ///
+    fn check_pending_peeks(&self) -> Result<(), Vec<PendingPeekInconsistency>> {
+        let mut inconsistencies = vec![];
+        for (uuid, peek) in self.pending_peeks() {
+            if !self.active_conns.contains_key(&peek.conn_id) {
+                inconsistencies.push(PendingPeekInconsistency::InactiveConnection(
+                    peek.conn_id.unhandled(),
+                    uuid.to_string(),
+                ));
+            }
+        }
+
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
+        }
+    }
+
+    /// # Invariants
+    ///
+    /// * All installed watch sets should belong to active connections.
+    /// * `installed_watch_sets` and `watch_set_keys` should track the same watch sets.
+    ///
+    fn check_watch_sets(&self) -> Result<(), Vec<WatchSetInconsistency>> {
+        let mut inconsistencies = vec![];
+        for (key, conn_id, (ws_id, _)) in self.installed_watch_sets.iter() {
+            if !self.active_conns.contains_key(conn_id) {
+                inconsistencies.push(WatchSetInconsistency::InactiveConnection(
+                    conn_id.unhandled(),
+                    format!("{ws_id:?}"),
+                ));
+            }
+            if self.watch_set_keys.get(ws_id) != Some(&key) {
+                inconsistencies.push(WatchSetInconsistency::UntrackedWatchSet(format!(
+                    "{ws_id:?}"
+                )));
+            }
+        }
+        for (ws_id, key) in &self.watch_set_keys {
+            let exists = self
+                .installed_watch_sets
+                .get(*key)
+                .map_or(false, |(installed_id, _)| installed_id == ws_id);
+            if !exists {
+                inconsistencies.push(WatchSetInconsistency::NonExistentWatchSet(format!(
+                    "{ws_id:?}"
+                )));
+            }
+        }
+
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
+        }
+    }
 }
 
 #[derive(Debug, Serialize, PartialEq, Eq)]
@@ -202,3 +277,15 @@ enum ClusterStatusInconsistency {
     NonExistentClusterStatus(String, ClusterId),
     NonExistentReplicaStatus(String, String, ClusterId, ReplicaId),
 }
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+enum PendingPeekInconsistency {
+    InactiveConnection(ConnectionIdType, String),
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+enum WatchSetInconsistency {
+    InactiveConnection(ConnectionIdType, String),
+    UntrackedWatchSet(String),
+    NonExistentWatchSet(String),
+}
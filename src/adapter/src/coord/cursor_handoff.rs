@@ -0,0 +1,240 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Handoffs of cursors between connections.
+//!
+//! `HANDOFF CURSOR` detaches a cursor that is fetching from a `SUBSCRIBE` from its connection
+//! and returns a token. Until the token is claimed with `CLAIM HANDOFF`, the subscribe keeps
+//! running and buffering rows, and keeps holding back the compaction of its inputs, even if
+//! the connection that handed it off goes away. Claiming the token in a transaction of another
+//! connection of the same role installs the cursor, under its original name, in that
+//! connection, which continues fetching where the old one left off.
+//!
+//! Handoffs are keyed by their token rather than by connection, as they outlive the connection
+//! that created them. Handoffs that are not claimed within `cursor_handoff_timeout` are closed.
+
+use std::collections::BTreeMap;
+
+use maplit::btreemap;
+use mz_adapter_types::dyncfgs::CURSOR_HANDOFF_TIMEOUT;
+use mz_ore::task;
+use mz_repr::role_id::RoleId;
+use mz_repr::{Datum, GlobalId, Row};
+use mz_sql::ast::display::AstDisplay;
+use mz_sql::plan::{ClaimHandoffPlan, HandoffCursorPlan};
+use mz_sql::session::metadata::SessionMetadata;
+use uuid::Uuid;
+
+use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
+use crate::coord::{Coordinator, Message};
+use crate::metrics;
+use crate::session::{Portal, PortalState, Session, TransactionOps};
+use crate::{AdapterError, ExecuteResponse};
+
+/// The cursors that have been handed off but not yet claimed, by token.
+#[derive(Debug, Default)]
+pub(crate) struct CursorHandoffs {
+    handoffs: BTreeMap<String, CursorHandoff>,
+}
+
+/// A cursor that has been handed off but not yet claimed.
+#[derive(Debug)]
+struct CursorHandoff {
+    /// The ID of the subscribe that the cursor fetches from.
+    sink_id: GlobalId,
+    /// The name of the cursor.
+    portal_name: String,
+    /// The cursor, with the rows it has received but not yet returned.
+    portal: Portal,
+    /// The role that handed off the cursor, and is allowed to claim it.
+    role_id: RoleId,
+    /// The session type label of the subscribe's metrics.
+    session_type: &'static str,
+}
+
+impl Coordinator {
+    /// Detaches the cursor `name` from the connection of `session`, and returns the token with
+    /// which another connection can claim it.
+    pub(crate) fn sequence_handoff_cursor(
+        &mut self,
+        session: &mut Session,
+        HandoffCursorPlan { name }: HandoffCursorPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let Some(portal) = session.get_portal_unverified(&name) else {
+            return Err(AdapterError::UnknownCursor(name));
+        };
+        // Only cursors that have started fetching from a `SUBSCRIBE` own a dataflow that can
+        // outlive the connection. Other cursors can simply be declared again.
+        let subscribe = match portal.state {
+            PortalState::InProgress(Some(_)) => {
+                self.active_compute_sinks
+                    .iter()
+                    .find_map(|(id, sink)| match sink {
+                        ActiveComputeSink::Subscribe(subscribe)
+                            if subscribe.conn_id == *session.conn_id()
+                                && subscribe.buffer.cursor() == Some(name.as_str()) =>
+                        {
+                            Some((*id, subscribe))
+                        }
+                        _ => None,
+                    })
+            }
+            _ => None,
+        };
+        let Some((sink_id, subscribe)) = subscribe else {
+            return Err(AdapterError::Unsupported(
+                "handoffs of cursors that are not fetching from a SUBSCRIBE",
+            ));
+        };
+        // Temporary objects are dropped with the connection that created them.
+        if subscribe.depends_on.iter().any(|id| {
+            self.catalog()
+                .try_get_entry(id)
+                .map_or(false, |entry| entry.is_temporary())
+        }) {
+            return Err(AdapterError::Unsupported(
+                "handoffs of cursors that depend on temporary objects",
+            ));
+        }
+
+        let portal = session.take_portal(&name).expect("known to exist");
+        self.active_conns
+            .get_mut(session.conn_id())
+            .expect("must exist for active session")
+            .drop_sinks
+            .remove(&sink_id);
+
+        let token = Uuid::new_v4().to_string();
+        self.cursor_handoffs.handoffs.insert(
+            token.clone(),
+            CursorHandoff {
+                sink_id,
+                portal_name: name,
+                portal,
+                role_id: session.role_metadata().authenticated_role,
+                session_type: metrics::session_type_label_value(session.user()),
+            },
+        );
+
+        let timeout = CURSOR_HANDOFF_TIMEOUT.get(self.catalog().system_config().dyncfgs());
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let expired_token = token.clone();
+        task::spawn(|| "cursor_handoff_timeout", async move {
+            tokio::time::sleep(timeout).await;
+            if internal_cmd_tx
+                .send(Message::CursorHandoffExpired(expired_token))
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+
+        Ok(Self::send_immediate_rows(Row::pack_slice(&[
+            Datum::String(&token),
+        ])))
+    }
+
+    /// Installs the cursor handed off with `token` in the connection of `session`.
+    pub(crate) async fn sequence_claim_handoff(
+        &mut self,
+        session: &mut Session,
+        ClaimHandoffPlan { token }: ClaimHandoffPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        // Handoffs of other roles are indistinguishable from unknown ones, so that trying tokens
+        // reveals nothing about them.
+        let portal_name = match self.cursor_handoffs.handoffs.get(&token) {
+            Some(handoff) if handoff.role_id == session.role_metadata().authenticated_role => {
+                handoff.portal_name.clone()
+            }
+            _ => return Err(AdapterError::UnknownCursorHandoff),
+        };
+        if session.get_portal_unverified(&portal_name).is_some() {
+            return Err(AdapterError::DuplicateCursor(portal_name));
+        }
+        session.add_transaction_ops(TransactionOps::Subscribe)?;
+
+        let CursorHandoff {
+            sink_id, portal, ..
+        } = self
+            .cursor_handoffs
+            .handoffs
+            .remove(&token)
+            .expect("known to exist");
+        let sql = portal
+            .stmt
+            .as_ref()
+            .map(|stmt| stmt.to_ast_string_stable())
+            .unwrap_or_default();
+        session.insert_portal(portal_name, portal, sql, self.now())?;
+
+        // Handoffs are removed with their subscribes.
+        let Some(ActiveComputeSink::Subscribe(mut subscribe)) =
+            self.active_compute_sinks.remove(&sink_id)
+        else {
+            unreachable!("handed off cursors fetch from active subscribes");
+        };
+        let state = self.catalog().state();
+        let mut updates = vec![state.pack_subscribe_update(sink_id, &subscribe, -1)];
+        subscribe.conn_id = session.conn_id().clone();
+        subscribe.session_uuid = session.uuid();
+        updates.push(state.pack_subscribe_update(sink_id, &subscribe, 1));
+        let updates = updates
+            .into_iter()
+            .map(|update| state.resolve_builtin_table_update(update))
+            .collect();
+        self.active_compute_sinks
+            .insert(sink_id, ActiveComputeSink::Subscribe(subscribe));
+        self.active_conns
+            .get_mut(session.conn_id())
+            .expect("must exist for active session")
+            .drop_sinks
+            .insert(sink_id);
+        self.builtin_table_update().blocking(updates).await;
+
+        Ok(ExecuteResponse::ClaimedHandoff)
+    }
+
+    /// Closes the cursor handed off with `token`, if it has not been claimed yet.
+    pub(crate) async fn expire_cursor_handoff(&mut self, token: String) {
+        let Some(handoff) = self.cursor_handoffs.handoffs.get(&token) else {
+            return;
+        };
+        let sink_id = handoff.sink_id;
+        self.retire_compute_sinks(btreemap! {
+            sink_id => ActiveComputeSinkRetireReason::Canceled,
+        })
+        .await;
+    }
+
+    /// Reports whether the cursor that fetches from the subscribe `sink_id` has been handed off
+    /// but not yet claimed.
+    pub(crate) fn is_cursor_handed_off(&self, sink_id: GlobalId) -> bool {
+        self.cursor_handoffs
+            .handoffs
+            .values()
+            .any(|handoff| handoff.sink_id == sink_id)
+    }
+
+    /// Removes the handoff of the cursor that fetches from the subscribe `sink_id`, if it has
+    /// been handed off but not claimed, and returns the session type label of its metrics.
+    pub(crate) fn remove_cursor_handoff(&mut self, sink_id: GlobalId) -> Option<&'static str> {
+        let token = self
+            .cursor_handoffs
+            .handoffs
+            .iter()
+            .find(|(_, handoff)| handoff.sink_id == sink_id)
+            .map(|(token, _)| token.clone())?;
+        let handoff = self
+            .cursor_handoffs
+            .handoffs
+            .remove(&token)
+            .expect("known to exist");
+        Some(handoff.session_type)
+    }
+}
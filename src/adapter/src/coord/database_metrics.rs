@@ -0,0 +1,293 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Per-database aggregation of adapter load.
+//!
+//! Statements, failed statements, peek latency and the bytes returned by peeks are attributed to
+//! the current database of the session that issued them and counted in the `mz_database_*`
+//! metrics, which are labeled by the ID of the database. Every `database_metrics_interval`, the
+//! coordinator records a snapshot of these metrics for each database in
+//! `mz_internal.mz_database_metrics`, along with the rate of statements and the mean peek latency
+//! since the previous snapshot.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+use mz_adapter_types::dyncfgs::DATABASE_METRICS_INTERVAL;
+use mz_compute_client::protocol::response::PeekResponse;
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_sql::names::DatabaseId;
+
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+use crate::util::diff_keyed_rows;
+
+/// The load of a database since the process started, as counted by its metrics.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct DatabaseLoad {
+    statements: u64,
+    errors: u64,
+    result_bytes: u64,
+    peeks: u64,
+    peek_seconds: f64,
+}
+
+/// A row of `mz_database_metrics`.
+#[derive(Clone, Debug, PartialEq)]
+struct DatabaseMetricsRow {
+    load: DatabaseLoad,
+    statements_per_second: f64,
+    mean_peek_seconds: Option<f64>,
+}
+
+impl DatabaseMetricsRow {
+    /// Computes the row for `load`, given the `previous` load of the database that was recorded
+    /// `elapsed` ago.
+    fn new(load: DatabaseLoad, previous: Option<&DatabaseLoad>, elapsed: Duration) -> Self {
+        let previous = previous.cloned().unwrap_or_default();
+        let statements = load.statements.saturating_sub(previous.statements);
+        let statements_per_second = if elapsed.is_zero() {
+            0.0
+        } else {
+            f64::cast_lossy(statements) / elapsed.as_secs_f64()
+        };
+        let peeks = load.peeks.saturating_sub(previous.peeks);
+        let mean_peek_seconds = (peeks > 0)
+            .then(|| (load.peek_seconds - previous.peek_seconds) / f64::cast_lossy(peeks));
+        DatabaseMetricsRow {
+            load,
+            statements_per_second,
+            mean_peek_seconds,
+        }
+    }
+}
+
+/// The rows last recorded in `mz_database_metrics`.
+#[derive(Debug, Default)]
+pub(crate) struct DatabaseMetrics {
+    reported: BTreeMap<DatabaseId, DatabaseMetricsRow>,
+    reported_at: Option<Instant>,
+    /// The databases that existed at the previous snapshot, whose metrics are removed once they
+    /// are dropped.
+    known: BTreeSet<DatabaseId>,
+}
+
+impl Coordinator {
+    /// Returns the label of the current database of `session` in the `mz_database_*` metrics.
+    ///
+    /// Databases are labeled by their ID, so that the metrics only ever describe databases that
+    /// exist, rather than any name that sessions set their `database` to. Sessions whose current
+    /// database doesn't exist are not attributed to any database.
+    pub(crate) fn database_metrics_label(&self, session: &Session) -> Option<String> {
+        self.catalog()
+            .resolve_database(session.vars().database())
+            .ok()
+            .map(|database| database.id.to_string())
+    }
+
+    /// Counts a peek issued by a session connected to the database labeled `database` that took
+    /// `elapsed` to produce `response`.
+    pub(crate) fn record_database_peek(
+        &self,
+        database: Option<&str>,
+        elapsed: Duration,
+        response: &PeekResponse,
+    ) {
+        let Some(database) = database else {
+            return;
+        };
+        match response {
+            PeekResponse::Rows(rows) => {
+                self.metrics
+                    .database_peek_seconds
+                    .with_label_values(&[database])
+                    .observe(elapsed.as_secs_f64());
+                self.metrics
+                    .database_result_bytes
+                    .with_label_values(&[database])
+                    .inc_by(u64::cast_from(rows.byte_len()));
+            }
+            // Peeks fail after the coordinator responded to their statement, so their errors are
+            // counted here rather than when the statement is retired.
+            PeekResponse::Error(_) => {
+                self.metrics
+                    .database_peek_seconds
+                    .with_label_values(&[database])
+                    .observe(elapsed.as_secs_f64());
+                self.metrics
+                    .database_statement_errors
+                    .with_label_values(&[database])
+                    .inc();
+            }
+            PeekResponse::Canceled => {}
+        }
+    }
+
+    /// Schedules the next snapshot of `mz_database_metrics`, `database_metrics_interval` from now.
+    pub(crate) fn schedule_database_metrics_report(&self) {
+        let interval = DATABASE_METRICS_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message(
+            "database_metrics_report",
+            interval,
+            Message::DatabaseMetricsReport,
+        );
+    }
+
+    /// Records a snapshot of the load of each database in `mz_database_metrics`, if enabled, and
+    /// schedules the next one.
+    pub(crate) fn report_database_metrics(&mut self) {
+        self.remove_dropped_database_metrics();
+        let interval = DATABASE_METRICS_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        if interval.is_zero() {
+            self.replace_database_metrics(BTreeMap::new());
+            self.database_metrics.reported_at = None;
+        } else {
+            let now = Instant::now();
+            let elapsed = self
+                .database_metrics
+                .reported_at
+                .map_or(Duration::ZERO, |reported_at| now - reported_at);
+            let rows = self
+                .catalog()
+                .databases()
+                .map(|database| {
+                    let label = database.id.to_string();
+                    let label = label.as_str();
+                    let peek_seconds = self
+                        .metrics
+                        .database_peek_seconds
+                        .with_label_values(&[label]);
+                    let load = DatabaseLoad {
+                        statements: self
+                            .metrics
+                            .database_statements
+                            .with_label_values(&[label])
+                            .get(),
+                        errors: self
+                            .metrics
+                            .database_statement_errors
+                            .with_label_values(&[label])
+                            .get(),
+                        result_bytes: self
+                            .metrics
+                            .database_result_bytes
+                            .with_label_values(&[label])
+                            .get(),
+                        peeks: peek_seconds.get_sample_count(),
+                        peek_seconds: peek_seconds.get_sample_sum(),
+                    };
+                    let previous = self
+                        .database_metrics
+                        .reported
+                        .get(&database.id)
+                        .map(|row| &row.load);
+                    let row = DatabaseMetricsRow::new(load, previous, elapsed);
+                    (database.id, row)
+                })
+                .collect();
+            self.replace_database_metrics(rows);
+            self.database_metrics.reported_at = Some(now);
+        }
+        self.schedule_database_metrics_report();
+    }
+
+    /// Removes the metrics of the databases that were dropped since the previous snapshot.
+    fn remove_dropped_database_metrics(&mut self) {
+        let databases: BTreeSet<_> = self
+            .catalog()
+            .databases()
+            .map(|database| database.id)
+            .collect();
+        let known = std::mem::replace(&mut self.database_metrics.known, databases);
+        for database_id in known.difference(&self.database_metrics.known) {
+            let label = database_id.to_string();
+            // The metrics of a database only exist once a statement was attributed to it.
+            let _ = self
+                .metrics
+                .database_statements
+                .remove_label_values(&[&label]);
+            let _ = self
+                .metrics
+                .database_statement_errors
+                .remove_label_values(&[&label]);
+            let _ = self
+                .metrics
+                .database_result_bytes
+                .remove_label_values(&[&label]);
+            let _ = self
+                .metrics
+                .database_peek_seconds
+                .remove_label_values(&[&label]);
+        }
+    }
+
+    /// Replaces the rows of `mz_database_metrics` with `rows`, updating only the rows of the
+    /// databases whose load changed.
+    fn replace_database_metrics(&mut self, rows: BTreeMap<DatabaseId, DatabaseMetricsRow>) {
+        let previous = std::mem::replace(&mut self.database_metrics.reported, rows);
+        let state = self.catalog().state();
+        let updates: Vec<_> = diff_keyed_rows(&previous, &self.database_metrics.reported)
+            .into_iter()
+            .map(|((database_id, row), diff)| {
+                state.pack_database_metrics_update(
+                    database_id,
+                    row.load.statements,
+                    row.statements_per_second,
+                    row.load.errors,
+                    row.load.result_bytes,
+                    row.load.peeks,
+                    row.mean_peek_seconds,
+                    diff,
+                )
+            })
+            .collect();
+        let updates = self.resolve_cached_builtin_table_updates(updates);
+        if !updates.is_empty() {
+            self.builtin_table_update().background(updates);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_database_metrics_row() {
+        let previous = DatabaseLoad {
+            statements: 10,
+            errors: 1,
+            result_bytes: 100,
+            peeks: 4,
+            peek_seconds: 2.0,
+        };
+        let load = DatabaseLoad {
+            statements: 30,
+            errors: 2,
+            result_bytes: 300,
+            peeks: 6,
+            peek_seconds: 3.0,
+        };
+
+        let row = DatabaseMetricsRow::new(load.clone(), Some(&previous), Duration::from_secs(10));
+        assert_eq!(row.statements_per_second, 2.0);
+        assert_eq!(row.mean_peek_seconds, Some(0.5));
+        assert_eq!(row.load, load);
+
+        // Without peeks since the previous snapshot, there is no mean peek latency.
+        let row = DatabaseMetricsRow::new(load.clone(), Some(&load), Duration::from_secs(10));
+        assert_eq!(row.statements_per_second, 0.0);
+        assert_eq!(row.mean_peek_seconds, None);
+
+        // The first snapshot has no rate.
+        let row = DatabaseMetricsRow::new(load, None, Duration::ZERO);
+        assert_eq!(row.statements_per_second, 0.0);
+        assert_eq!(row.mean_peek_seconds, Some(0.5));
+    }
+}
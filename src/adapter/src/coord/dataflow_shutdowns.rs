@@ -0,0 +1,145 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The history of compute dataflow shutdowns.
+//!
+//! Whenever the coordinator shuts down the dataflow of an index, materialized view, or compute
+//! sink, it records why in `mz_internal.mz_dataflow_shutdown_history`: because the object was
+//! dropped, because a subscribe or `COPY TO` finished, was canceled, or lost one of its
+//! dependencies, or because the replica that ran the dataflow was dropped, e.g. when a managed
+//! cluster is resized, or stopped being ready, e.g. because it was OOM-killed or restarted. The
+//! session whose statement initiated the shutdown is recorded along with it, so the statement can
+//! be found in `mz_statement_execution_history`.
+//!
+//! The transient dataflows of slow-path `SELECT`s are not recorded, and only the dataflows of
+//! user objects are recorded when a replica is dropped or stops being ready. The entries are
+//! retained for `dataflow_shutdown_history_retention_interval`.
+
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::now::to_datetime;
+use mz_repr::{Datum, GlobalId, Row};
+use mz_storage_client::controller::IntrospectionType;
+use uuid::Uuid;
+
+use crate::active_compute_sink::ActiveComputeSinkRetireReason;
+use crate::coord::Coordinator;
+
+/// Why a dataflow was shut down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DataflowShutdownReason {
+    /// The object that the dataflow maintains was dropped.
+    Dropped,
+    /// The replica that ran the dataflow was dropped.
+    ReplicaDropped,
+    /// The replica that ran the dataflow was OOM-killed.
+    ReplicaOomKilled,
+    /// The replica that ran the dataflow stopped being ready for another reason, e.g. because it
+    /// crashed or its pod was rescheduled.
+    ReplicaRestarted,
+    /// The compute sink of the dataflow finished.
+    Finished,
+    /// The compute sink of the dataflow was canceled.
+    Canceled,
+    /// An object that the compute sink of the dataflow depends on was dropped.
+    DependencyDropped,
+}
+
+impl DataflowShutdownReason {
+    /// Returns the name of the reason, as recorded in `mz_dataflow_shutdown_history`.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            DataflowShutdownReason::Dropped => "dropped",
+            DataflowShutdownReason::ReplicaDropped => "replica_dropped",
+            DataflowShutdownReason::ReplicaOomKilled => "replica_oom_killed",
+            DataflowShutdownReason::ReplicaRestarted => "replica_restarted",
+            DataflowShutdownReason::Finished => "finished",
+            DataflowShutdownReason::Canceled => "canceled",
+            DataflowShutdownReason::DependencyDropped => "dependency_dropped",
+        }
+    }
+}
+
+impl From<&ActiveComputeSinkRetireReason> for DataflowShutdownReason {
+    fn from(reason: &ActiveComputeSinkRetireReason) -> Self {
+        match reason {
+            ActiveComputeSinkRetireReason::Finished => DataflowShutdownReason::Finished,
+            ActiveComputeSinkRetireReason::Canceled => DataflowShutdownReason::Canceled,
+            ActiveComputeSinkRetireReason::DependencyDropped(_) => {
+                DataflowShutdownReason::DependencyDropped
+            }
+        }
+    }
+}
+
+/// A dataflow that was shut down.
+#[derive(Debug)]
+pub(crate) struct DataflowShutdown {
+    /// The ID of the object that the dataflow maintains or exports.
+    pub(crate) object_id: GlobalId,
+    pub(crate) cluster_id: ClusterId,
+    /// The replica on which the dataflow was shut down, or `None` if it was shut down on all
+    /// replicas of the cluster.
+    pub(crate) replica_id: Option<ReplicaId>,
+    pub(crate) reason: DataflowShutdownReason,
+    /// The session whose statement initiated the shutdown, if any.
+    pub(crate) session_id: Option<Uuid>,
+}
+
+impl Coordinator {
+    /// Returns the shutdowns of the dataflows of user objects that ran on the replica
+    /// `replica_id` of the cluster `cluster_id`, which is about to be dropped or stopped being
+    /// ready for `reason`.
+    pub(crate) fn replica_dataflow_shutdowns(
+        &self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        reason: DataflowShutdownReason,
+        session_id: Option<Uuid>,
+    ) -> Vec<DataflowShutdown> {
+        let Ok(instance) = self.controller.compute.instance_ref(cluster_id) else {
+            return Vec::new();
+        };
+        instance
+            .collections()
+            .map(|(id, _)| *id)
+            .filter(|id| id.is_user())
+            .map(|object_id| DataflowShutdown {
+                object_id,
+                cluster_id,
+                replica_id: Some(replica_id),
+                reason,
+                session_id,
+            })
+            .collect()
+    }
+
+    /// Records `shutdowns` in `mz_dataflow_shutdown_history`.
+    pub(crate) async fn record_dataflow_shutdowns(&mut self, shutdowns: Vec<DataflowShutdown>) {
+        let occurred_at = to_datetime((self.catalog().config().now)());
+        let updates = shutdowns
+            .into_iter()
+            .map(|shutdown| {
+                let object_id = shutdown.object_id.to_string();
+                let cluster_id = shutdown.cluster_id.to_string();
+                let replica_id = shutdown.replica_id.map(|id| id.to_string());
+                let row = Row::pack_slice(&[
+                    Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+                    Datum::String(&object_id),
+                    Datum::String(&cluster_id),
+                    Datum::from(replica_id.as_deref()),
+                    Datum::String(shutdown.reason.as_str()),
+                    Datum::from(shutdown.session_id),
+                ]);
+                (row, 1)
+            })
+            .collect();
+        self.append_introspection_updates(IntrospectionType::DataflowShutdownHistory, updates)
+            .await;
+    }
+}
@@ -19,7 +19,12 @@ use futures::Future;
 use maplit::{btreemap, btreeset};
 use mz_adapter_types::compaction::SINCE_GRANULARITY;
 use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::{
+    CARDINALITY_BLOWUP_FACTOR, COORD_MESSAGE_SPAN_SAMPLING, EXTERNAL_TASK_CONCURRENCY_PER_HOST,
+    MAX_COMPUTE_SINKS_PER_CLUSTER,
+};
 use mz_audit_log::VersionedEvent;
+use mz_catalog::builtin::MZ_REPLICA_UTILIZATION;
 use mz_catalog::memory::objects::{
     CatalogItem, Connection, DataSourceDesc, Index, MaterializedView, Sink,
 };
@@ -35,7 +40,7 @@ use mz_ore::retry::Retry;
 use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::adt::numeric::Numeric;
-use mz_repr::{GlobalId, Timestamp};
+use mz_repr::{Datum, GlobalId, Row, Timestamp};
 use mz_sql::catalog::{CatalogCluster, CatalogSchema};
 use mz_sql::names::ResolvedDatabaseSpecifier;
 use mz_sql::session::metadata::SessionMetadata;
@@ -46,7 +51,7 @@ use mz_sql::session::vars::{
     MAX_REPLICAS_PER_CLUSTER, MAX_ROLES, MAX_SCHEMAS_PER_DATABASE, MAX_SECRETS, MAX_SINKS,
     MAX_SOURCES, MAX_TABLES,
 };
-use mz_storage_client::controller::ExportDescription;
+use mz_storage_client::controller::{ExportDescription, IntrospectionType};
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::PostgresConnection;
 use mz_storage_types::read_policy::ReadPolicy;
@@ -55,8 +60,9 @@ use serde_json::json;
 use tracing::{event, info_span, warn, Instrument, Level};
 
 use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
-use crate::catalog::{DropObjectInfo, Op, TransactionResult};
+use crate::catalog::{BuiltinTableUpdate, DropObjectInfo, Op, TransactionResult};
 use crate::coord::appends::BuiltinTableAppendNotify;
+use crate::coord::dataflow_shutdowns::{DataflowShutdown, DataflowShutdownReason};
 use crate::coord::timeline::{TimelineContext, TimelineState};
 use crate::coord::{Coordinator, ReplicaMetadata};
 use crate::session::{Session, Transaction, TransactionOps};
@@ -195,6 +201,7 @@ impl Coordinator {
         let mut webhook_sources_to_restart = BTreeSet::new();
         let mut tables_to_drop = vec![];
         let mut storage_sinks_to_drop = vec![];
+        let mut webhook_sinks_to_drop = vec![];
         let mut indexes_to_drop = vec![];
         let mut materialized_views_to_drop = vec![];
         let mut views_to_drop = vec![];
@@ -216,6 +223,10 @@ impl Coordinator {
         let mut update_cluster_scheduling_config = false;
         let mut update_arrangement_exert_proportionality = false;
         let mut update_http_config = false;
+        let mut update_message_span_sampling = false;
+        let mut update_compute_sink_admission = false;
+        let mut update_introspection_subscribe_params = false;
+        let mut update_external_task_concurrency = false;
         let mut log_indexes_to_drop = Vec::new();
 
         for op in &ops {
@@ -249,6 +260,13 @@ impl Coordinator {
                                             }
                                         }
                                     }
+                                    CatalogItem::Sink(Sink {
+                                        connection,
+                                        cluster_id,
+                                        ..
+                                    }) if connection.is_compute_sink() => {
+                                        webhook_sinks_to_drop.push((*cluster_id, *id));
+                                    }
                                     CatalogItem::Sink(Sink { .. }) => {
                                         storage_sinks_to_drop.push(*id);
                                     }
@@ -325,6 +343,12 @@ impl Coordinator {
                     update_arrangement_exert_proportionality |=
                         name == vars::ARRANGEMENT_EXERT_PROPORTIONALITY.name();
                     update_http_config |= vars::is_http_config_var(name);
+                    update_message_span_sampling |= name == COORD_MESSAGE_SPAN_SAMPLING.name();
+                    update_compute_sink_admission |= name == MAX_COMPUTE_SINKS_PER_CLUSTER.name();
+                    update_introspection_subscribe_params |=
+                        name == CARDINALITY_BLOWUP_FACTOR.name();
+                    update_external_task_concurrency |=
+                        name == EXTERNAL_TASK_CONCURRENCY_PER_HOST.name();
                 }
                 catalog::Op::ResetAllSystemConfiguration => {
                     // Assume they all need to be updated.
@@ -339,6 +363,10 @@ impl Coordinator {
                     update_cluster_scheduling_config = true;
                     update_arrangement_exert_proportionality = true;
                     update_http_config = true;
+                    update_message_span_sampling = true;
+                    update_compute_sink_admission = true;
+                    update_introspection_subscribe_params = true;
+                    update_external_task_concurrency = true;
                 }
                 catalog::Op::RenameItem { id, .. } => {
                     let item = self.catalog().get_entry(id);
@@ -391,6 +419,7 @@ impl Coordinator {
             .iter()
             .chain(tables_to_drop.iter())
             .chain(storage_sinks_to_drop.iter())
+            .chain(webhook_sinks_to_drop.iter().map(|(_, id)| id))
             .chain(indexes_to_drop.iter().map(|(_, id)| id))
             .chain(materialized_views_to_drop.iter().map(|(_, id)| id))
             .chain(views_to_drop.iter())
@@ -430,7 +459,7 @@ impl Coordinator {
         }
 
         // Clean up any pending peeks that rely on dropped relations or clusters.
-        for (uuid, pending_peek) in &self.pending_peeks {
+        for (uuid, pending_peek) in self.pending_peeks() {
             if let Some(id) = pending_peek
                 .depends_on
                 .iter()
@@ -440,13 +469,10 @@ impl Coordinator {
                 let name = self
                     .catalog()
                     .resolve_full_name(entry.name(), Some(&pending_peek.conn_id));
-                peeks_to_drop.push((
-                    format!("relation {}", name.to_string().quoted()),
-                    uuid.clone(),
-                ));
+                peeks_to_drop.push((format!("relation {}", name.to_string().quoted()), uuid));
             } else if clusters_to_drop.contains(&pending_peek.cluster_id) {
                 let name = self.catalog().get_cluster(pending_peek.cluster_id).name();
-                peeks_to_drop.push((format!("cluster {}", name.quoted()), uuid.clone()));
+                peeks_to_drop.push((format!("cluster {}", name.quoted()), uuid));
             }
         }
 
@@ -499,6 +525,14 @@ impl Coordinator {
         // regress or pause for 10s.
         let oracle_write_ts = self.get_local_write_ts().await.timestamp;
 
+        // Remember the values of the system parameters that the ops change, to
+        // record the changes in `mz_system_parameter_history`.
+        let system_parameters_before = self.system_parameter_values(&ops);
+
+        // Copy the catalog, if necessary, through `catalog_mut` so that the copy is accounted for.
+        self.catalog_mut();
+        let transact_timer = self.metrics.catalog_transact_seconds.start_timer();
+
         let Coordinator {
             catalog,
             active_conns,
@@ -512,9 +546,11 @@ impl Coordinator {
         let TransactionResult {
             mut builtin_table_updates,
             audit_events,
+            catalog_changes,
         } = catalog
             .transact(Some(&mut *controller.storage), oracle_write_ts, conn, ops)
             .await?;
+        transact_timer.observe_duration();
 
         // Update in-memory cluster replica statuses.
         // TODO(jkosh44) All these builtin table updates should be handled as a builtin source
@@ -599,9 +635,38 @@ impl Coordinator {
             .execute(builtin_table_updates)
             .await;
 
+        // Record the dataflows that are shut down, before the replicas that run them are dropped.
+        let session_id = conn_id
+            .and_then(|id| self.active_conns.get(id))
+            .map(|conn| conn.uuid());
+        let mut dataflow_shutdowns: Vec<_> = indexes_to_drop
+            .iter()
+            .chain(&materialized_views_to_drop)
+            .map(|(cluster_id, id)| DataflowShutdown {
+                object_id: *id,
+                cluster_id: *cluster_id,
+                replica_id: None,
+                reason: DataflowShutdownReason::Dropped,
+                session_id,
+            })
+            .collect();
+        for (cluster_id, replica_id) in &cluster_replicas_to_drop {
+            // The dataflows of a dropped cluster are recorded with the objects that are dropped
+            // along with it.
+            if !clusters_to_drop.contains(cluster_id) {
+                dataflow_shutdowns.extend(self.replica_dataflow_shutdowns(
+                    *cluster_id,
+                    *replica_id,
+                    DataflowShutdownReason::ReplicaDropped,
+                    session_id,
+                ));
+            }
+        }
+
         // No error returns are allowed after this point. Enforce this at compile time
         // by using this odd structure so we don't accidentally add a stray `?`.
         let _: () = async {
+            self.record_dataflow_shutdowns(dataflow_shutdowns).await;
             if !timeline_associations.is_empty() {
                 for (timeline, (should_be_empty, id_bundle)) in timeline_associations {
                     let became_empty =
@@ -622,16 +687,21 @@ impl Coordinator {
             if !storage_sinks_to_drop.is_empty() {
                 self.drop_storage_sinks(storage_sinks_to_drop);
             }
+            if !webhook_sinks_to_drop.is_empty() {
+                self.drop_webhook_sinks(webhook_sinks_to_drop);
+            }
             if !compute_sinks_to_drop.is_empty() {
                 self.retire_compute_sinks(compute_sinks_to_drop).await;
             }
             if !peeks_to_drop.is_empty() {
                 for (dropped_name, uuid) in peeks_to_drop {
                     if let Some(pending_peek) = self.remove_pending_peek(&uuid) {
-                        self.controller
-                            .compute
-                            .cancel_peek(pending_peek.cluster_id, uuid)
-                            .unwrap_or_terminate("unable to cancel peek");
+                        if let Some(uuid) = self.peek_dedup.leave(uuid) {
+                            self.controller
+                                .compute
+                                .cancel_peek(pending_peek.cluster_id, uuid)
+                                .unwrap_or_terminate("unable to cancel peek");
+                        }
                         self.retire_execution(
                             StatementEndedExecutionReason::Canceled,
                             pending_peek.ctx_extra,
@@ -767,6 +837,35 @@ impl Coordinator {
             if update_http_config {
                 self.update_http_config();
             }
+            if update_message_span_sampling {
+                self.update_message_span_sampling();
+            }
+            if update_compute_sink_admission {
+                // The limit might have been raised.
+                self.schedule_all_compute_sink_admissions();
+            }
+            if update_introspection_subscribe_params {
+                self.update_introspection_subscribe_params().await;
+            }
+            if update_external_task_concurrency {
+                // The limit might have been raised.
+                self.start_queued_external_tasks();
+            }
+            if !catalog_changes.is_empty() {
+                let updates = catalog_changes.into_iter().map(|row| (row, 1)).collect();
+                self.controller
+                    .storage
+                    .append_introspection_updates(IntrospectionType::CatalogChangeHistory, updates)
+                    .await;
+            }
+            if !system_parameters_before.is_empty() {
+                self.record_system_parameter_changes(
+                    conn_id,
+                    oracle_write_ts,
+                    system_parameters_before,
+                )
+                .await;
+            }
         }
         .instrument(info_span!("coord::catalog_transact_with::finalize"))
         .await;
@@ -806,8 +905,10 @@ impl Coordinator {
     }
 
     async fn drop_replica(&mut self, cluster_id: ClusterId, replica_id: ReplicaId) {
-        if let Some(Some(ReplicaMetadata { metrics })) =
-            self.transient_replica_metadata.insert(replica_id, None)
+        if let Some(Some(ReplicaMetadata {
+            metrics,
+            utilization,
+        })) = self.transient_replica_metadata.insert(replica_id, None)
         {
             let mut updates = vec![];
             if let Some(metrics) = metrics {
@@ -821,10 +922,24 @@ impl Coordinator {
                     .resolve_builtin_table_updates(retractions);
                 updates.extend(retractions);
             }
+            if let Some(row) = utilization {
+                let retraction = BuiltinTableUpdate {
+                    id: &*MZ_REPLICA_UTILIZATION,
+                    row,
+                    diff: -1,
+                };
+                updates.push(
+                    self.catalog()
+                        .state()
+                        .resolve_builtin_table_update(retraction),
+                );
+            }
             self.builtin_table_update().background(updates);
         }
 
         self.drop_introspection_subscribes(replica_id).await;
+        self.forget_replica_segment_events(replica_id);
+        self.forget_replica_oom_crash_loops(replica_id);
 
         self.controller
             .drop_replica(cluster_id, replica_id)
@@ -921,12 +1036,35 @@ impl Coordinator {
         mut reasons: BTreeMap<GlobalId, ActiveComputeSinkRetireReason>,
     ) {
         let sink_ids = reasons.keys().cloned();
+        let mut events = Vec::new();
+        let mut dataflow_shutdowns = Vec::new();
         for (id, sink) in self.drop_compute_sinks(sink_ids).await {
             let reason = reasons
                 .remove(&id)
                 .expect("all returned IDs are in `reasons`");
+            if let ActiveComputeSink::Subscribe(subscribe) = &sink {
+                events.push(self.pack_subscription_event(id, subscribe, Some(&reason)));
+            }
+            let reason_kind = DataflowShutdownReason::from(&reason);
+            // The statement that dropped a dependency is recorded with the dropped objects.
+            let session_id = match reason_kind {
+                DataflowShutdownReason::DependencyDropped => None,
+                _ => self
+                    .active_conns
+                    .get(sink.connection_id())
+                    .map(|conn| conn.uuid()),
+            };
+            dataflow_shutdowns.push(DataflowShutdown {
+                object_id: id,
+                cluster_id: sink.cluster_id(),
+                replica_id: None,
+                reason: reason_kind,
+                session_id,
+            });
             sink.retire(reason);
         }
+        self.record_subscription_events(events).await;
+        self.record_dataflow_shutdowns(dataflow_shutdowns).await;
     }
 
     /// Cancels all active compute sinks for the identified connection.
@@ -1083,6 +1221,23 @@ impl Coordinator {
         self.caching_secrets_reader.set_policy(config);
     }
 
+    /// Propagates the whole system configuration to everything that depends on it, as if all
+    /// system variables changed.
+    pub(crate) fn update_all_system_config_dependents(&mut self) {
+        self.update_tracing_config();
+        self.update_compute_config();
+        self.update_storage_config();
+        self.update_pg_timestamp_oracle_config();
+        self.update_metrics_retention();
+        self.update_secrets_caching_config();
+        self.update_cluster_scheduling_config();
+        self.update_arrangement_exert_proportionality();
+        self.update_http_config();
+        self.update_message_span_sampling();
+        self.schedule_all_compute_sink_admissions();
+        self.start_queued_external_tasks();
+    }
+
     fn update_tracing_config(&mut self) {
         let tracing = flags::tracing_config(self.catalog().system_config());
         tracing.apply(&self.tracing_handle);
@@ -1098,6 +1253,69 @@ impl Coordinator {
         self.controller.storage.update_parameters(config_params);
     }
 
+    /// Returns the current values of the system parameters that `ops` change.
+    fn system_parameter_values(&self, ops: &[catalog::Op]) -> BTreeMap<String, String> {
+        let system_config = self.catalog().system_config();
+        let mut values = BTreeMap::new();
+        for op in ops {
+            match op {
+                catalog::Op::UpdateSystemConfiguration { name, .. }
+                | catalog::Op::ResetSystemConfiguration { name } => {
+                    if let Ok(var) = system_config.get(name) {
+                        values.insert(var.name().to_string(), var.value());
+                    }
+                }
+                catalog::Op::ResetAllSystemConfiguration => {
+                    values.extend(
+                        system_config
+                            .iter()
+                            .map(|var| (var.name().to_string(), var.value())),
+                    );
+                }
+                _ => {}
+            }
+        }
+        values
+    }
+
+    /// Appends the system parameters whose values differ from `before` to
+    /// `mz_system_parameter_history`, attributed to the user of `conn_id`.
+    async fn record_system_parameter_changes(
+        &mut self,
+        conn_id: Option<&ConnectionId>,
+        occurred_at: Timestamp,
+        before: BTreeMap<String, String>,
+    ) {
+        let system_config = self.catalog().system_config();
+        let actor = conn_id
+            .and_then(|id| self.active_conns.get(id))
+            .map(|conn| conn.user().name.clone());
+        let occurred_at = to_datetime(occurred_at.into());
+        let updates: Vec<_> = before
+            .into_iter()
+            .filter_map(|(name, old_value)| {
+                let new_value = system_config.get(&name).ok()?.value();
+                (old_value != new_value).then(|| {
+                    let row = Row::pack_slice(&[
+                        Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+                        Datum::String(&name),
+                        Datum::String(&old_value),
+                        Datum::String(&new_value),
+                        Datum::from(actor.as_deref()),
+                    ]);
+                    (row, 1)
+                })
+            })
+            .collect();
+        if updates.is_empty() {
+            return;
+        }
+        self.controller
+            .storage
+            .append_introspection_updates(IntrospectionType::SystemParameterHistory, updates)
+            .await;
+    }
+
     fn update_pg_timestamp_oracle_config(&mut self) {
         let config_params = flags::pg_timstamp_oracle_config(self.catalog().system_config());
         if let Some(config) = self.pg_timestamp_oracle_config.as_ref() {
@@ -1433,6 +1651,7 @@ impl Coordinator {
                 },
                 Op::AlterRole { .. }
                 | Op::AlterRetainHistory { .. }
+                | Op::AlterTableRowCountHint { .. }
                 | Op::UpdatePrivilege { .. }
                 | Op::UpdateDefaultPrivilege { .. }
                 | Op::GrantRole { .. }
@@ -1443,7 +1662,9 @@ impl Coordinator {
                 | Op::UpdateOwner { .. }
                 | Op::RevokeRole { .. }
                 | Op::UpdateClusterConfig { .. }
+                | Op::UpdateDatabaseDefaultCluster { .. }
                 | Op::UpdateStorageUsage { .. }
+                | Op::PruneAuditLog { .. }
                 | Op::UpdateSystemConfiguration { .. }
                 | Op::ResetSystemConfiguration { .. }
                 | Op::ResetAllSystemConfiguration { .. }
@@ -0,0 +1,368 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Limits on the number of concurrent tasks that contact external systems.
+//!
+//! Purifying `CREATE SOURCE`, `ALTER SOURCE` and `CREATE SINK` statements and validating
+//! connections contact the external systems that the statements refer to, in tasks that are
+//! spawned off the coordinator. `external_task_concurrency_per_host` caps the number of those
+//! tasks that run at the same time for each external host, like a Kafka broker or a PostgreSQL
+//! server, so that a burst of statements can't exhaust the connections that the host accepts. A
+//! task counts towards the limit of every host that its connections refer to, including their
+//! SSH bastions and schema registries. AWS connections don't refer to a single host, so the
+//! limit applies to each AWS connection instead.
+//!
+//! Tasks beyond the limit are queued, and their sessions are notified. Queued tasks start in the
+//! order in which they were queued, except that a task that waits for a busy host doesn't hold up
+//! the tasks that contact other hosts. Queued tasks also start when the limit is raised. They can
+//! be canceled like any other statement. The number of running and queued tasks of each kind of
+//! external system are reported in the `mz_external_tasks_running` and `mz_external_tasks_queued`
+//! metrics.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+use futures::future::BoxFuture;
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::EXTERNAL_TASK_CONCURRENCY_PER_HOST;
+use mz_catalog::memory::objects::CatalogItem;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_sql::catalog::{CatalogItem as _, SessionCatalog};
+use mz_sql::names::{Aug, ResolvedIds};
+use mz_sql::normalize;
+use mz_sql_parser::ast::Statement;
+use mz_storage_types::connections::inline::ReferencedConnection;
+use mz_storage_types::connections::Connection;
+
+use crate::coord::{Coordinator, Message};
+use crate::notice::AdapterNotice;
+use crate::session::Session;
+use crate::ExecuteContext;
+
+/// A kind of external system that tasks contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ExternalSystem {
+    Kafka,
+    ConfluentSchemaRegistry,
+    Postgres,
+    MySql,
+    Ssh,
+    Aws,
+}
+
+impl ExternalSystem {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ExternalSystem::Kafka => "kafka",
+            ExternalSystem::ConfluentSchemaRegistry => "confluent-schema-registry",
+            ExternalSystem::Postgres => "postgres",
+            ExternalSystem::MySql => "mysql",
+            ExternalSystem::Ssh => "ssh",
+            ExternalSystem::Aws => "aws",
+        }
+    }
+}
+
+/// An external host that tasks contact.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ExternalHost {
+    system: ExternalSystem,
+    /// The host and port, or the ID of the connection for AWS connections.
+    address: String,
+}
+
+impl ExternalHost {
+    /// Returns the hosts that the connection `id`, whose details are `connection`, contacts
+    /// directly, not counting the connections that it refers to.
+    pub(crate) fn for_connection(
+        id: GlobalId,
+        connection: &Connection<ReferencedConnection>,
+    ) -> Vec<Self> {
+        let host = |system, address: String| ExternalHost {
+            system,
+            address: address.to_lowercase(),
+        };
+        match connection {
+            Connection::Kafka(kafka) if !kafka.brokers.is_empty() => kafka
+                .brokers
+                .iter()
+                .map(|broker| host(ExternalSystem::Kafka, broker.address.clone()))
+                .collect(),
+            // Connections without brokers discover them through their default tunnel.
+            Connection::Kafka(_) => vec![host(ExternalSystem::Kafka, id.to_string())],
+            Connection::Csr(csr) => {
+                let address = match (csr.url.host_str(), csr.url.port_or_known_default()) {
+                    (Some(h), Some(port)) => format!("{h}:{port}"),
+                    (Some(h), None) => h.to_string(),
+                    (None, _) => id.to_string(),
+                };
+                vec![host(ExternalSystem::ConfluentSchemaRegistry, address)]
+            }
+            Connection::Postgres(pg) => vec![host(
+                ExternalSystem::Postgres,
+                format!("{}:{}", pg.host, pg.port),
+            )],
+            Connection::MySql(mysql) => vec![host(
+                ExternalSystem::MySql,
+                format!("{}:{}", mysql.host, mysql.port),
+            )],
+            Connection::Ssh(ssh) => vec![host(
+                ExternalSystem::Ssh,
+                format!("{}:{}", ssh.host, ssh.port),
+            )],
+            Connection::Aws(_) | Connection::AwsPrivatelink(_) => {
+                vec![host(ExternalSystem::Aws, id.to_string())]
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExternalHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.address, self.system.as_str())
+    }
+}
+
+/// Returns the kinds of external systems of `hosts`, by which metrics are labeled.
+fn external_systems(hosts: &BTreeSet<ExternalHost>) -> BTreeSet<ExternalSystem> {
+    hosts.iter().map(|host| host.system).collect()
+}
+
+/// Starts a task, given the context of its statement, by returning the future to run.
+type StartExternalTask = Box<dyn FnOnce(ExecuteContext) -> BoxFuture<'static, ()> + Send>;
+
+/// A task that waits for the external hosts that it contacts to have capacity.
+struct QueuedExternalTask {
+    kind: &'static str,
+    hosts: BTreeSet<ExternalHost>,
+    ctx: ExecuteContext,
+    start: StartExternalTask,
+}
+
+impl fmt::Debug for QueuedExternalTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueuedExternalTask")
+            .field("kind", &self.kind)
+            .field("hosts", &self.hosts)
+            .field("conn_id", self.ctx.session().conn_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The tasks that contact external systems, which are running or queued.
+#[derive(Debug, Default)]
+pub(crate) struct ExternalTasks {
+    /// The number of running tasks that contact each external host.
+    running: BTreeMap<ExternalHost, usize>,
+    queued: VecDeque<QueuedExternalTask>,
+}
+
+impl Coordinator {
+    /// Returns the external hosts that the connections among `ids` contact, including the
+    /// connections that they refer to.
+    pub(crate) fn external_hosts(
+        &self,
+        ids: impl IntoIterator<Item = GlobalId>,
+    ) -> BTreeSet<ExternalHost> {
+        let mut hosts = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut to_visit: Vec<_> = ids.into_iter().collect();
+        while let Some(id) = to_visit.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(CatalogItem::Connection(connection)) =
+                self.catalog().try_get_entry(&id).map(|entry| entry.item())
+            {
+                hosts.extend(ExternalHost::for_connection(id, &connection.connection));
+                to_visit.extend(connection.resolved_ids.0.iter().copied());
+            }
+        }
+        hosts
+    }
+
+    /// Returns the external hosts that purifying `stmt` contacts.
+    pub(crate) fn purification_external_hosts(
+        &self,
+        session: &Session,
+        stmt: &Statement<Aug>,
+        resolved_ids: &ResolvedIds,
+    ) -> BTreeSet<ExternalHost> {
+        let mut ids = resolved_ids.0.clone();
+        // `ALTER SOURCE` contacts the external hosts of the source that it alters, which is
+        // referred to by the source itself or, for subsources, by the source they belong to.
+        if let Statement::AlterSource(stmt) = stmt {
+            let catalog = self.catalog().for_session(session);
+            let source = normalize::unresolved_item_name(stmt.source_name.clone())
+                .ok()
+                .and_then(|name| catalog.resolve_item(&name).ok());
+            if let Some(source) = source {
+                for id in &source.references().0 {
+                    ids.insert(*id);
+                    if let Some(entry) = self.catalog().try_get_entry(id) {
+                        ids.extend(entry.references().0.iter().copied());
+                    }
+                }
+            }
+        }
+        self.external_hosts(ids)
+    }
+
+    /// Reports whether another task that contacts `hosts` can start.
+    fn has_external_task_capacity(&self, hosts: &BTreeSet<ExternalHost>) -> bool {
+        let limit =
+            EXTERNAL_TASK_CONCURRENCY_PER_HOST.get(self.catalog().system_config().dyncfgs());
+        limit == 0
+            || hosts
+                .iter()
+                .all(|host| self.external_tasks.running.get(host).copied().unwrap_or(0) < limit)
+    }
+
+    /// Spawns the task of the statement of `ctx` that contacts `hosts`, which is described by
+    /// `kind`, or queues it until the hosts have the capacity for it.
+    ///
+    /// `start` returns the future to run once the task starts. The statement is expected to be
+    /// retired once the future completes.
+    pub(crate) fn spawn_external_task(
+        &mut self,
+        kind: &'static str,
+        hosts: BTreeSet<ExternalHost>,
+        mut ctx: ExecuteContext,
+        start: impl FnOnce(ExecuteContext) -> BoxFuture<'static, ()> + Send + 'static,
+    ) {
+        // Tasks don't overtake the queued tasks that contact the same hosts.
+        let has_queued = self
+            .external_tasks
+            .queued
+            .iter()
+            .any(|queued| !queued.hosts.is_disjoint(&hosts));
+        if self.has_external_task_capacity(&hosts) && !has_queued {
+            self.start_external_task(kind, hosts, ctx, Box::new(start));
+            return;
+        }
+
+        ctx.session_mut()
+            .add_notice(AdapterNotice::ExternalTaskQueued {
+                hosts: hosts.iter().map(|host| host.to_string()).collect(),
+            });
+        for system in external_systems(&hosts) {
+            self.metrics
+                .external_tasks_queued
+                .with_label_values(&[system.as_str()])
+                .inc();
+        }
+        self.external_tasks.queued.push_back(QueuedExternalTask {
+            kind,
+            hosts,
+            ctx,
+            start: Box::new(start),
+        });
+    }
+
+    fn start_external_task(
+        &mut self,
+        kind: &'static str,
+        hosts: BTreeSet<ExternalHost>,
+        ctx: ExecuteContext,
+        start: StartExternalTask,
+    ) {
+        for host in &hosts {
+            *self.external_tasks.running.entry(host.clone()).or_default() += 1;
+        }
+        for system in external_systems(&hosts) {
+            self.metrics
+                .external_tasks_running
+                .with_label_values(&[system.as_str()])
+                .inc();
+        }
+        let conn_id = ctx.session().conn_id().clone();
+        let future = start(ctx);
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| format!("{kind}:{conn_id}"), async move {
+            future.await;
+            // It is not an error for this to fail. The coordinator could be shutting down.
+            let _ = internal_cmd_tx.send(Message::ExternalTaskFinished(hosts));
+        });
+    }
+
+    /// Records that a task that contacted `hosts` has finished, and starts the queued tasks that
+    /// can start now.
+    pub(crate) fn finish_external_task(&mut self, hosts: BTreeSet<ExternalHost>) {
+        for host in &hosts {
+            let running = self
+                .external_tasks
+                .running
+                .get_mut(host)
+                .expect("task is running");
+            *running -= 1;
+            if *running == 0 {
+                self.external_tasks.running.remove(host);
+            }
+        }
+        for system in external_systems(&hosts) {
+            self.metrics
+                .external_tasks_running
+                .with_label_values(&[system.as_str()])
+                .dec();
+        }
+        self.start_queued_external_tasks();
+    }
+
+    /// Starts the queued tasks whose hosts have the capacity for them.
+    ///
+    /// This needs to be called when a task finishes, when a queued task is removed, and when
+    /// the limit changes.
+    pub(crate) fn start_queued_external_tasks(&mut self) {
+        // The hosts that an earlier queued task is still waiting for.
+        let mut blocked = BTreeSet::new();
+        let mut idx = 0;
+        while idx < self.external_tasks.queued.len() {
+            let hosts = &self.external_tasks.queued[idx].hosts;
+            if !hosts.is_disjoint(&blocked) || !self.has_external_task_capacity(hosts) {
+                blocked.extend(hosts.iter().cloned());
+                idx += 1;
+                continue;
+            }
+            let queued = self.dequeue_external_task(idx);
+            self.start_external_task(queued.kind, queued.hosts, queued.ctx, queued.start);
+        }
+    }
+
+    fn dequeue_external_task(&mut self, idx: usize) -> QueuedExternalTask {
+        let queued = self
+            .external_tasks
+            .queued
+            .remove(idx)
+            .expect("known to exist");
+        for system in external_systems(&queued.hosts) {
+            self.metrics
+                .external_tasks_queued
+                .with_label_values(&[system.as_str()])
+                .dec();
+        }
+        queued
+    }
+
+    /// Removes the queued task of the connection `conn_id`, if any.
+    pub(crate) fn cancel_queued_external_task(
+        &mut self,
+        conn_id: &ConnectionId,
+    ) -> Option<ExecuteContext> {
+        let idx = self
+            .external_tasks
+            .queued
+            .iter()
+            .position(|queued| queued.ctx.session().conn_id() == conn_id)?;
+        let queued = self.dequeue_external_task(idx);
+        // The tasks that were queued behind the canceled task might be able to start now.
+        self.start_queued_external_tasks();
+        Some(queued.ctx)
+    }
+}
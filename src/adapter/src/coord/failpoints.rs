@@ -0,0 +1,182 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Failpoints for testing the resilience of clients and of the coordinator's own retry logic.
+//!
+//! The coordinator evaluates failpoints at key points, like catalog transactions or the completion
+//! of group commits. When `enable_adapter_failpoints` is on, superusers can configure a failpoint
+//! with `mz_internal.mz_set_failpoint(name, actions)`, where `actions` uses the syntax of the
+//! [`fail`] crate. For example, `return(error)` fails the work guarded by the failpoint with an
+//! error that mentions `error`, `return(delay=1000)` delays it by a second, and `off` disables
+//! the failpoint again.
+//!
+//! The `sleep`, `delay` and `pause` actions of the [`fail`] crate block the thread that evaluates
+//! the failpoint, which is the coordinator's for most failpoints, so they are rejected. Delays
+//! instead only hold up the work guarded by the failpoint, while the coordinator keeps serving
+//! other work.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use mz_ore::task;
+use tracing::warn;
+
+use crate::coord::{Coordinator, Message};
+use crate::AdapterError;
+
+/// Fails the catalog transaction of a DDL statement. This failpoint doesn't support delays.
+pub(crate) const CATALOG_TRANSACT: &str = "catalog_transact";
+/// Fails the writes of a group commit after they are committed, so the clients that issued them
+/// can't tell whether they were applied, or delays the responses to them.
+pub(crate) const GROUP_COMMIT_APPLY: &str = "group_commit_apply";
+/// Delays processing the responses of the controller. This failpoint doesn't support errors.
+pub(crate) const CONTROLLER_PROCESS: &str = "controller_process";
+/// Fails or delays a statement once its purification completes.
+pub(crate) const PURIFICATION_COMPLETE: &str = "purification_complete";
+
+/// The failpoints that can be configured with `mz_set_failpoint`.
+const FAILPOINTS: [&str; 4] = [
+    CATALOG_TRANSACT,
+    GROUP_COMMIT_APPLY,
+    CONTROLLER_PROCESS,
+    PURIFICATION_COMPLETE,
+];
+
+/// The `fail` tasks that block the thread evaluating the failpoint.
+const BLOCKING_TASKS: [&str; 3] = ["sleep", "delay", "pause"];
+
+/// The prefix of the `return` argument that delays the work guarded by a failpoint by a number of
+/// milliseconds.
+const DELAY_PREFIX: &str = "delay=";
+
+/// What a triggered failpoint injects into the work it guards.
+#[derive(Debug)]
+pub(crate) enum FailpointAction {
+    /// Fail the work, with the argument of the `return` action.
+    Error(Option<String>),
+    /// Delay the work.
+    Delay(Duration),
+}
+
+/// Evaluates the failpoint `name`, and returns what to inject into the work it guards if it
+/// triggers.
+pub(crate) fn eval(name: &str) -> Option<FailpointAction> {
+    let arg = fail::eval(name, |arg| arg)?;
+    let delay = arg
+        .as_deref()
+        .and_then(|arg| arg.strip_prefix(DELAY_PREFIX))
+        .and_then(|millis| millis.parse().ok());
+    match delay {
+        Some(millis) => Some(FailpointAction::Delay(Duration::from_millis(millis))),
+        None => Some(FailpointAction::Error(arg)),
+    }
+}
+
+/// Returns the error to fail the work guarded by a failpoint with, given the argument of its
+/// `return` action.
+pub(crate) fn failpoint_error(arg: Option<String>) -> AdapterError {
+    AdapterError::Unstructured(anyhow!("failpoint: {arg:?}"))
+}
+
+/// Reports whether `actions` uses a task that blocks the thread evaluating the failpoint.
+fn blocks_thread(actions: &str) -> bool {
+    actions.split("->").any(|action| {
+        // Actions are of the form `[p%][cnt*]task[(arg)]`.
+        let task = action.split('(').next().unwrap_or_default();
+        let task = task.rsplit(['%', '*']).next().unwrap_or_default();
+        BLOCKING_TASKS.contains(&task.trim())
+    })
+}
+
+/// Configures the failpoint `name` with `actions`, and returns the actions it was configured with
+/// before, if any.
+pub(crate) fn set_failpoint(name: &str, actions: &str) -> Result<Option<String>, AdapterError> {
+    if !FAILPOINTS.contains(&name) {
+        return Err(AdapterError::Unstructured(anyhow!(
+            "unknown failpoint: {name}"
+        )));
+    }
+    if blocks_thread(actions) {
+        return Err(AdapterError::Unstructured(anyhow!(
+            "failpoint actions can't block the coordinator, use return({DELAY_PREFIX}<ms>) to \
+             delay failpoint {name}"
+        )));
+    }
+    let previous = fail::list()
+        .into_iter()
+        .find_map(|(failpoint, actions)| (failpoint == name).then_some(actions));
+    fail::cfg(name, actions).map_err(|e| {
+        AdapterError::Unstructured(anyhow!("invalid actions for failpoint {name}: {e}"))
+    })?;
+    Ok(previous)
+}
+
+impl Coordinator {
+    /// Delays processing the responses of the controller if the `controller_process` failpoint
+    /// triggers, and reports whether it did.
+    ///
+    /// Rather than sleeping, this stops waiting for the controller to become ready until the delay
+    /// elapses, so the coordinator keeps serving other messages in the meantime.
+    pub(crate) fn delay_controller_process(&mut self) -> bool {
+        let delay = match eval(CONTROLLER_PROCESS) {
+            Some(FailpointAction::Delay(delay)) => delay,
+            Some(FailpointAction::Error(arg)) => {
+                warn!("failpoint {CONTROLLER_PROCESS} doesn't support errors: {arg:?}");
+                return false;
+            }
+            None => return false,
+        };
+
+        self.controller_process_delayed = true;
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "controller_process_failpoint", async move {
+            tokio::time::sleep(delay).await;
+            if internal_cmd_tx
+                .send(Message::ControllerProcessDelayElapsed)
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fail::FailScenario;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_set_failpoint() {
+        let scenario = FailScenario::setup();
+
+        assert!(set_failpoint("after_catalog_drop_replica", "off").is_err());
+        assert!(set_failpoint(PURIFICATION_COMPLETE, "not an action").is_err());
+        assert!(set_failpoint(PURIFICATION_COMPLETE, "sleep(1000)").is_err());
+        assert!(set_failpoint(PURIFICATION_COMPLETE, "return->50%2*pause").is_err());
+
+        set_failpoint(PURIFICATION_COMPLETE, "return(error)").expect("valid failpoint");
+        assert!(matches!(
+            eval(PURIFICATION_COMPLETE),
+            Some(FailpointAction::Error(Some(arg))) if arg == "error"
+        ));
+        set_failpoint(PURIFICATION_COMPLETE, "return(delay=1000)").expect("valid failpoint");
+        assert!(matches!(
+            eval(PURIFICATION_COMPLETE),
+            Some(FailpointAction::Delay(delay)) if delay == Duration::from_secs(1)
+        ));
+        let previous = set_failpoint(PURIFICATION_COMPLETE, "off").expect("valid failpoint");
+        assert_eq!(previous.as_deref(), Some("return(delay=1000)"));
+        assert!(eval(PURIFICATION_COMPLETE).is_none());
+
+        scenario.teardown();
+    }
+}
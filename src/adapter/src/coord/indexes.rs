@@ -39,6 +39,18 @@ impl DataflowBuilder<'_> {
 
         // Iteratively extract the largest element, potentially introducing lesser elements.
         while let Some(id) = todo.iter().rev().next().cloned() {
+            // Partial indexes can stand in for their collection in the queries that imply their
+            // predicates, which is only known once the queries are optimized.
+            let partial_indexes: BTreeSet<_> =
+                self.partial_indexes_on(id).map(|(id, _)| id).collect();
+            if !partial_indexes.is_empty() {
+                id_bundle
+                    .compute_ids
+                    .entry(self.compute.instance_id())
+                    .or_default()
+                    .extend(partial_indexes);
+            }
+
             // Extract available indexes as those that are enabled, and installed on the cluster.
             let mut available_indexes = self.indexes_on(id).map(|(id, _)| id).peekable();
 
@@ -75,7 +87,22 @@ impl DataflowBuilder<'_> {
         id_bundle
     }
 
+    /// Returns the indexes on `id` that are available on the cluster. Partial indexes are only
+    /// returned if they were imported by
+    /// [`DataflowBuilder::import_implied_partial_indexes`].
     pub fn indexes_on(&self, id: GlobalId) -> impl Iterator<Item = (GlobalId, &Index)> {
+        self.available_indexes_on(id).filter(|(idx_id, idx)| {
+            idx.predicate.is_none() || self.partial_indexes.contains(idx_id)
+        })
+    }
+
+    /// Returns the partial indexes on `id` that are available on the cluster.
+    pub fn partial_indexes_on(&self, id: GlobalId) -> impl Iterator<Item = (GlobalId, &Index)> {
+        self.available_indexes_on(id)
+            .filter(|(_idx_id, idx)| idx.predicate.is_some())
+    }
+
+    fn available_indexes_on(&self, id: GlobalId) -> impl Iterator<Item = (GlobalId, &Index)> {
         self.catalog
             .get_indexes_on(id, self.compute.instance_id())
             .filter(|(idx_id, _idx)| self.compute.contains_collection(idx_id))
@@ -25,25 +25,40 @@
 //! * Before a replica is dropped, the coordinator calls `drop_introspection_subscribes` to drop
 //!   all introspection subscribes previously installed on the replica.
 //! * When a replica disconnects without being dropped (e.g. because of a crash or network
-//!   failure), `handle_introspection_subscribe_batch` reacts on the corresponding error responses
-//!   by reinstalling the failed introspection subscribes.
+//!   failure), or an introspection subscribe fails for any other reason,
+//!   `handle_introspection_subscribe_batch` reacts on the corresponding error responses by
+//!   reinstalling the failed introspection subscribes. Subscribes that fail repeatedly are
+//!   reinstalled with an exponential backoff.
+//!
+//! # Cardinality Advisories
+//!
+//! The `ComputeCardinalityAdvisories` subscribe reports the operators of installed dataflows that
+//! produce more than `cardinality_blowup_factor` times as many records as they receive, which is
+//! a typical symptom of an accidental cross join. In addition to writing them to their storage
+//! collection, `handle_introspection_subscribe_batch` sends a notice for each newly reported
+//! operator to the sessions of the owner of its dataflow. When `cardinality_blowup_factor` is
+//! altered, `update_introspection_subscribe_params` reinstalls the subscribes with the new factor.
+
+use std::time::Duration;
 
 use anyhow::bail;
 use derivative::Derivative;
-use mz_adapter_types::dyncfgs::ENABLE_INTROSPECTION_SUBSCRIBES;
+use mz_adapter_types::dyncfgs::{CARDINALITY_BLOWUP_FACTOR, ENABLE_INTROSPECTION_SUBSCRIBES};
 use mz_cluster_client::ReplicaId;
 use mz_compute_client::controller::error::ERROR_TARGET_REPLICA_FAILED;
 use mz_compute_client::protocol::response::SubscribeBatch;
 use mz_controller_types::ClusterId;
+use mz_dyncfg::ConfigSet;
 use mz_ore::collections::CollectionExt;
 use mz_ore::soft_panic_or_log;
+use mz_ore::task;
 use mz_repr::optimize::OverrideFrom;
-use mz_repr::{Datum, GlobalId, Row};
+use mz_repr::{Datum, GlobalId, Row, ScalarType};
 use mz_sql::catalog::SessionCatalog;
 use mz_sql::plan::{Params, Plan, SubscribePlan};
 use mz_sql::session::user::{RoleMetadata, MZ_SYSTEM_ROLE_ID};
 use mz_storage_client::controller::{IntrospectionType, StorageWriteOp};
-use tracing::{info, Span};
+use tracing::{debug, info, warn, Span};
 
 use crate::coord::{
     Coordinator, IntrospectionSubscribeFinish, IntrospectionSubscribeOptimizeMir,
@@ -51,7 +66,12 @@ use crate::coord::{
     StageResult, Staged,
 };
 use crate::optimize::Optimize;
-use crate::{optimize, AdapterError, ExecuteResponse};
+use crate::{optimize, AdapterError, AdapterNotice, ExecuteResponse};
+
+/// The backoff before the second reinstallation of an introspection subscribe that keeps failing.
+const REINSTALL_BACKOFF_INITIAL: Duration = Duration::from_secs(1);
+/// The maximum backoff between reinstallations of an introspection subscribe.
+const REINSTALL_BACKOFF_MAX: Duration = Duration::from_secs(60);
 
 // State tracked about an active introspection subscribe.
 #[derive(Derivative)]
@@ -63,6 +83,8 @@ pub(super) struct IntrospectionSubscribe {
     replica_id: ReplicaId,
     /// The spec from which this subscribe was created.
     spec: &'static SubscribeSpec,
+    /// The parameters the subscribe was planned with.
+    params: Params,
     /// A storage write to be applied the next time the introspection subscribe produces any
     /// output.
     ///
@@ -72,6 +94,11 @@ pub(super) struct IntrospectionSubscribe {
     /// introspection data around in the meantime makes for a better UX than removing it.
     #[derivative(Debug = "ignore")]
     deferred_write: Option<StorageWriteOp>,
+    /// The number of times this subscribe was reinstalled since it last produced a batch without
+    /// error.
+    restarts: u32,
+    /// Whether a reinstallation of this subscribe is already scheduled.
+    reinstall_scheduled: bool,
 }
 
 impl IntrospectionSubscribe {
@@ -116,17 +143,84 @@ impl Coordinator {
             return;
         }
 
-        for spec in SUBSCRIBES {
-            self.install_introspection_subscribe(cluster_id, replica_id, spec)
+        // Specs can be disabled by the configuration of their parameters.
+        let specs: Vec<_> = SUBSCRIBES
+            .iter()
+            .filter_map(|spec| Some((spec, (spec.params)(dyncfgs)?)))
+            .collect();
+        for (spec, params) in specs {
+            self.install_introspection_subscribe(cluster_id, replica_id, spec, params)
                 .await;
         }
     }
 
+    /// Brings the installed introspection subscribes in line with the parameters of their specs
+    /// in the current system configuration.
+    ///
+    /// Subscribes whose parameters changed are reinstalled with the new parameters, the subscribes
+    /// of specs that their parameters disabled are dropped, and the subscribes of specs that their
+    /// parameters enabled are installed on all replicas.
+    pub(super) async fn update_introspection_subscribe_params(&mut self) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        if !ENABLE_INTROSPECTION_SUBSCRIBES.get(dyncfgs) {
+            return;
+        }
+        let specs: Vec<_> = SUBSCRIBES
+            .iter()
+            .map(|spec| (spec, (spec.params)(dyncfgs)))
+            .collect();
+        let mut cluster_replicas = Vec::new();
+        for cluster in self.catalog.clusters() {
+            for replica in cluster.replicas() {
+                cluster_replicas.push((cluster.id, replica.replica_id));
+            }
+        }
+
+        for (spec, params) in specs {
+            let installed: Vec<_> = self
+                .introspection_subscribes
+                .iter()
+                .filter(|(_, s)| s.spec.introspection_type == spec.introspection_type)
+                .map(|(id, s)| (*id, s.replica_id, s.params.clone()))
+                .collect();
+            let Some(params) = params else {
+                for (id, _, _) in installed {
+                    self.drop_introspection_subscribe(id).await;
+                }
+                continue;
+            };
+
+            for (id, _, installed_params) in &installed {
+                if installed_params.datums == params.datums
+                    && installed_params.types == params.types
+                {
+                    continue;
+                }
+                if let Some(subscribe) = self.introspection_subscribes.get_mut(id) {
+                    subscribe.params = params.clone();
+                }
+                self.reinstall_introspection_subscribe(*id).await;
+            }
+            for (cluster_id, replica_id) in &cluster_replicas {
+                if !installed.iter().any(|(_, r, _)| r == replica_id) {
+                    self.install_introspection_subscribe(
+                        *cluster_id,
+                        *replica_id,
+                        spec,
+                        params.clone(),
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
     async fn install_introspection_subscribe(
         &mut self,
         cluster_id: ClusterId,
         replica_id: ReplicaId,
         spec: &'static SubscribeSpec,
+        params: Params,
     ) {
         let id = self.allocate_transient_id();
         info!(
@@ -143,11 +237,14 @@ impl Coordinator {
             cluster_id,
             replica_id,
             spec,
+            params: params.clone(),
             deferred_write: None,
+            restarts: 0,
+            reinstall_scheduled: false,
         };
         self.introspection_subscribes.insert(id, subscribe);
 
-        self.sequence_introspection_subscribe(id, spec, cluster_id, replica_id)
+        self.sequence_introspection_subscribe(id, spec, &params, cluster_id, replica_id)
             .await;
     }
 
@@ -155,11 +252,12 @@ impl Coordinator {
         &mut self,
         subscribe_id: GlobalId,
         spec: &'static SubscribeSpec,
+        params: &Params,
         cluster_id: ClusterId,
         replica_id: ReplicaId,
     ) {
         let catalog = self.catalog().for_system_session();
-        let plan = spec.to_plan(&catalog).expect("valid spec");
+        let plan = spec.to_plan(&catalog, params).expect("valid spec");
 
         let role_metadata = RoleMetadata::new(MZ_SYSTEM_ROLE_ID);
         let validity = PlanValidity::new(
@@ -385,6 +483,7 @@ impl Coordinator {
             spec,
             ..
         } = subscribe;
+        let params = subscribe.params.clone();
         let old_id = id;
         let new_id = self.allocate_transient_id();
 
@@ -408,16 +507,70 @@ impl Coordinator {
         // Ensure that the contents of the target storage collection are cleaned when the new
         // subscribe starts reporting data.
         subscribe.deferred_write = Some(subscribe.delete_write_op());
+        subscribe.reinstall_scheduled = false;
 
         self.introspection_subscribes.insert(new_id, subscribe);
-        self.sequence_introspection_subscribe(new_id, spec, cluster_id, replica_id)
+        self.sequence_introspection_subscribe(new_id, spec, &params, cluster_id, replica_id)
             .await;
     }
 
+    /// Schedules the reinstallation of a failed introspection subscribe.
+    ///
+    /// The first reinstallation after a subscribe failed happens immediately. If the subscribe
+    /// keeps failing, the backoff before each further reinstallation doubles, up to
+    /// `REINSTALL_BACKOFF_MAX`.
+    fn schedule_introspection_subscribe_reinstall(&mut self, id: GlobalId) {
+        let Some(subscribe) = self.introspection_subscribes.get_mut(&id) else {
+            soft_panic_or_log!("attempt to reinstall unknown introspection subscribe (id={id})");
+            return;
+        };
+        if subscribe.reinstall_scheduled {
+            return;
+        }
+
+        let backoff = reinstall_backoff(subscribe.restarts);
+        subscribe.restarts += 1;
+        subscribe.reinstall_scheduled = true;
+        self.metrics
+            .introspection_subscribe_restarts
+            .with_label_values(&[&format!("{:?}", subscribe.spec.introspection_type)])
+            .inc();
+        info!(
+            %id,
+            replica_id = %subscribe.replica_id,
+            type_ = ?subscribe.spec.introspection_type,
+            restarts = subscribe.restarts,
+            ?backoff,
+            "scheduling reinstallation of introspection subscribe",
+        );
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "reinstall_introspection_subscribe", async move {
+            tokio::time::sleep(backoff).await;
+            if internal_cmd_tx
+                .send(Message::ReinstallIntrospectionSubscribe(id))
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Reinstalls a failed introspection subscribe once its backoff has passed.
+    pub(super) async fn message_reinstall_introspection_subscribe(&mut self, id: GlobalId) {
+        // The target replica may have been dropped in the meantime.
+        if !self.introspection_subscribes.contains_key(&id) {
+            debug!(%id, "not reinstalling dropped introspection subscribe");
+            return;
+        }
+        self.reinstall_introspection_subscribe(id).await;
+    }
+
     /// Processes a batch returned by an introspection subscribe.
     ///
     /// Depending on the contents of the batch, this either appends received updates to the
-    /// corresponding storage-managed collection, or reinstalls a disconnected subscribe.
+    /// corresponding storage-managed collection, or schedules the reinstallation of a failed
+    /// subscribe.
     pub(super) async fn handle_introspection_subscribe_batch(
         &mut self,
         id: GlobalId,
@@ -429,22 +582,52 @@ impl Coordinator {
         };
 
         let updates = match batch.updates {
-            Ok(updates) if updates.is_empty() => return,
-            Ok(updates) => updates,
-            Err(error) if error == ERROR_TARGET_REPLICA_FAILED => {
-                // The target replica disconnected, reinstall the subscribe.
-                self.reinstall_introspection_subscribe(id).await;
-                return;
+            Ok(updates) => {
+                // The subscribe is healthy again.
+                subscribe.restarts = 0;
+                if updates.is_empty() {
+                    return;
+                }
+                updates
             }
             Err(error) => {
-                soft_panic_or_log!(
-                    "introspection subscribe produced an error: {error} \
-                     (id={id}, subscribe={subscribe:?})",
-                );
+                // If the target replica disconnected that is expected, otherwise we still try to
+                // recover by reinstalling the subscribe.
+                if error != ERROR_TARGET_REPLICA_FAILED {
+                    warn!(
+                        %id,
+                        ?subscribe,
+                        "introspection subscribe produced an error: {error}",
+                    );
+                }
+                self.schedule_introspection_subscribe_reinstall(id);
                 return;
             }
         };
 
+        // Newly reported cardinality blowups are also sent as notices.
+        let blowups: Vec<_> = match subscribe.spec.introspection_type {
+            IntrospectionType::ComputeCardinalityAdvisories => updates
+                .iter()
+                .filter(|(_time, _row, diff)| *diff > 0)
+                .map(|(_time, row, _diff)| {
+                    let mut datums = row.iter();
+                    let object_id = datums.next().unwrap().unwrap_str().to_string();
+                    let operator_name = datums.nth(1).unwrap().unwrap_str().to_string();
+                    (object_id, operator_name)
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let cluster_id = subscribe.cluster_id;
+        let target_replica_id = subscribe.replica_id;
+        let factor = subscribe
+            .params
+            .datums
+            .iter()
+            .next()
+            .map(|d| d.unwrap_float64());
+
         // Prepend the `replica_id` to each row.
         let replica_id = subscribe.replica_id.to_string();
         let mut new_updates = Vec::with_capacity(updates.len());
@@ -474,6 +657,48 @@ impl Coordinator {
                 },
             )
             .await;
+
+        if let Some(factor) = factor.filter(|_| !blowups.is_empty()) {
+            let cluster = self.catalog().get_cluster(cluster_id);
+            let replica = match cluster.replica(target_replica_id) {
+                Some(replica) => format!("{}.{}", cluster.name, replica.name),
+                None => target_replica_id.to_string(),
+            };
+            // Dataflows of objects that have been dropped in the meantime have no owner left to
+            // notify.
+            let notices: Vec<_> = blowups
+                .into_iter()
+                .filter_map(|(object_id, operator)| {
+                    let id = object_id.parse().ok()?;
+                    let entry = self.catalog().try_get_entry(&id)?;
+                    let object = self
+                        .catalog()
+                        .resolve_full_name(entry.name(), None)
+                        .to_string();
+                    let notice = AdapterNotice::CardinalityBlowup {
+                        object,
+                        replica: replica.clone(),
+                        operator,
+                        factor,
+                    };
+                    Some((*entry.owner_id(), notice))
+                })
+                .collect();
+            for (owner_id, notice) in notices {
+                self.send_notice_to_owner(&owner_id, notice);
+            }
+        }
+    }
+}
+
+/// Returns the backoff before reinstalling an introspection subscribe that was already reinstalled
+/// `restarts` times without producing a batch in between.
+fn reinstall_backoff(restarts: u32) -> Duration {
+    match restarts.checked_sub(1) {
+        None => Duration::ZERO,
+        Some(exponent) => REINSTALL_BACKOFF_INITIAL
+            .saturating_mul(2u32.saturating_pow(exponent))
+            .min(REINSTALL_BACKOFF_MAX),
     }
 }
 
@@ -519,13 +744,20 @@ pub(super) struct SubscribeSpec {
     introspection_type: IntrospectionType,
     /// The SQL definition of the subscribe.
     sql: &'static str,
+    /// Returns the values of the parameters of `sql` for the given configuration, or `None` if
+    /// the configuration disables the subscribe.
+    params: fn(&ConfigSet) -> Option<Params>,
 }
 
 impl SubscribeSpec {
-    fn to_plan(&self, catalog: &dyn SessionCatalog) -> Result<SubscribePlan, anyhow::Error> {
+    fn to_plan(
+        &self,
+        catalog: &dyn SessionCatalog,
+        params: &Params,
+    ) -> Result<SubscribePlan, anyhow::Error> {
         let parsed = mz_sql::parse::parse(self.sql)?.into_element();
         let (stmt, resolved_ids) = mz_sql::names::resolve(catalog, parsed.ast)?;
-        let plan = mz_sql::plan::plan(None, catalog, stmt, &Params::empty(), &resolved_ids)?;
+        let plan = mz_sql::plan::plan(None, catalog, stmt, params, &resolved_ids)?;
         match plan {
             Plan::Subscribe(plan) => Ok(plan),
             _ => bail!("unexpected plan type: {plan:?}"),
@@ -541,6 +773,7 @@ const SUBSCRIBES: &[SubscribeSpec] = &[
             FROM mz_introspection.mz_compute_error_counts_raw
             GROUP BY export_id
         )",
+        params: |_| Some(Params::empty()),
     },
     SubscribeSpec {
         introspection_type: IntrospectionType::ComputeHydrationTimes,
@@ -556,5 +789,90 @@ const SUBSCRIBES: &[SubscribeSpec] = &[
             GROUP BY export_id
             OPTIONS (AGGREGATE INPUT GROUP SIZE = 1)
         )",
+        params: |_| Some(Params::empty()),
+    },
+    SubscribeSpec {
+        introspection_type: IntrospectionType::ComputeCardinalityAdvisories,
+        sql: "SUBSCRIBE (
+            WITH
+                channels AS (
+                    SELECT c.from_operator_id, c.to_operator_id, m.sent, m.received
+                    FROM mz_introspection.mz_dataflow_channel_operators c
+                    JOIN mz_introspection.mz_message_counts m ON c.id = m.channel_id
+                ),
+                received AS (
+                    SELECT to_operator_id AS id, sum(received) AS records
+                    FROM channels
+                    GROUP BY to_operator_id
+                ),
+                arrangements AS (
+                    SELECT DISTINCT operator_id AS id
+                    FROM mz_introspection.mz_arrangement_sizes
+                ),
+                -- Arrangements send batches rather than records, so the records of an
+                -- arrangement are those its operator received.
+                inputs AS (
+                    SELECT
+                        c.to_operator_id AS id,
+                        sum(CASE WHEN a.id IS NULL THEN c.received ELSE r.records END) AS records
+                    FROM channels c
+                    LEFT JOIN arrangements a ON c.from_operator_id = a.id
+                    LEFT JOIN received r ON c.from_operator_id = r.id
+                    GROUP BY c.to_operator_id
+                ),
+                outputs AS (
+                    SELECT from_operator_id AS id, sum(sent) AS records
+                    FROM channels
+                    GROUP BY from_operator_id
+                )
+            SELECT e.export_id, o.id, o.name
+            FROM mz_introspection.mz_dataflow_operator_dataflows o
+            JOIN mz_introspection.mz_compute_exports e ON o.dataflow_id = e.dataflow_id
+            JOIN inputs i ON o.id = i.id
+            JOIN outputs u ON o.id = u.id
+            WHERE e.export_id NOT LIKE 't%' AND i.records > 0 AND u.records > $1 * i.records
+        )",
+        params: cardinality_advisories_params,
     },
 ];
+
+/// Returns the parameters of the `ComputeCardinalityAdvisories` subscribe: the factor by which the
+/// output of an operator must exceed its input.
+fn cardinality_advisories_params(config: &ConfigSet) -> Option<Params> {
+    let factor = CARDINALITY_BLOWUP_FACTOR.get(config);
+    (factor > 0.0).then(|| Params {
+        datums: Row::pack_slice(&[Datum::Float64(factor.into())]),
+        types: vec![ScalarType::Float64],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_cardinality_advisories_params() {
+        let config = mz_adapter_types::dyncfgs::all_dyncfgs(ConfigSet::default());
+        let params = cardinality_advisories_params(&config).expect("enabled by default");
+        assert_eq!(
+            params.datums.unpack_first(),
+            Datum::Float64((*CARDINALITY_BLOWUP_FACTOR.default()).into())
+        );
+        assert_eq!(params.types, vec![ScalarType::Float64]);
+
+        let mut updates = mz_dyncfg::ConfigUpdates::default();
+        updates.add(&CARDINALITY_BLOWUP_FACTOR, 0.0);
+        updates.apply(&config);
+        assert!(cardinality_advisories_params(&config).is_none());
+    }
+
+    #[mz_ore::test]
+    fn test_reinstall_backoff() {
+        assert_eq!(reinstall_backoff(0), Duration::ZERO);
+        assert_eq!(reinstall_backoff(1), Duration::from_secs(1));
+        assert_eq!(reinstall_backoff(2), Duration::from_secs(2));
+        assert_eq!(reinstall_backoff(5), Duration::from_secs(16));
+        assert_eq!(reinstall_backoff(7), REINSTALL_BACKOFF_MAX);
+        assert_eq!(reinstall_backoff(u32::MAX), REINSTALL_BACKOFF_MAX);
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking of `environmentd` leadership transitions.
+//!
+//! Every time an `environmentd` boots with writes allowed it has fenced out the previous leader
+//! by bumping the catalog epoch. We record each transition, together with the reason the
+//! previous leader was fenced, in `mz_internal.mz_leadership_history`.
+
+use std::num::NonZeroI64;
+
+use mz_catalog::builtin::MZ_LEADERSHIP_HISTORY;
+use mz_repr::{Datum, Row};
+use mz_storage_client::controller::IntrospectionType;
+use tracing::info;
+
+use crate::coord::Coordinator;
+use crate::error::AdapterError;
+
+impl Coordinator {
+    /// Records that this `environmentd`, running with `epoch` at `deploy_generation`, has taken
+    /// over leadership from whichever leader is last recorded in `mz_leadership_history`.
+    pub(crate) async fn record_leadership_transition(
+        &mut self,
+        epoch: NonZeroI64,
+        deploy_generation: u64,
+        enable_0dt_deployment: bool,
+    ) -> Result<(), AdapterError> {
+        // Read-only environments have not fenced out anyone (yet).
+        if self.controller.read_only() {
+            return Ok(());
+        }
+
+        let id = self
+            .catalog()
+            .resolve_builtin_storage_collection(&MZ_LEADERSHIP_HISTORY);
+        let previous = self
+            .controller
+            .storage
+            .snapshot_latest(id)
+            .await?
+            .into_iter()
+            .map(|row| {
+                // Skip `occurred_at`.
+                let mut iter = row.iter().skip(1);
+                let epoch = iter.next().expect("missing epoch").unwrap_int64();
+                let deploy_generation = iter
+                    .next()
+                    .expect("missing deploy_generation")
+                    .unwrap_uint64();
+                (epoch, deploy_generation)
+            })
+            .max();
+
+        let reason = fencing_reason(
+            previous.map(|(_, generation)| generation),
+            deploy_generation,
+            enable_0dt_deployment,
+        );
+        info!(
+            %epoch,
+            %deploy_generation,
+            ?previous,
+            reason,
+            "recording leadership transition"
+        );
+
+        let occurred_at = mz_ore::now::to_datetime((self.catalog().config().now)());
+        let mz_version = self.catalog().config().build_info.human_version();
+        let row = Row::pack_slice(&[
+            Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+            Datum::Int64(epoch.get()),
+            Datum::UInt64(deploy_generation),
+            Datum::String(&mz_version),
+            previous.map_or(Datum::Null, |(epoch, _)| Datum::Int64(epoch)),
+            previous.map_or(Datum::Null, |(_, generation)| Datum::UInt64(generation)),
+            Datum::String(reason),
+        ]);
+        self.controller
+            .storage
+            .append_introspection_updates(IntrospectionType::LeadershipHistory, vec![(row, 1)])
+            .await;
+        Ok(())
+    }
+}
+
+/// Describes why the leader at `previous_generation` was fenced out by a leader at
+/// `deploy_generation`.
+fn fencing_reason(
+    previous_generation: Option<u64>,
+    deploy_generation: u64,
+    enable_0dt_deployment: bool,
+) -> &'static str {
+    match previous_generation {
+        // Either a brand new environment or the first boot of a version that records leadership.
+        None => "bootstrap",
+        Some(previous) if previous < deploy_generation => {
+            if enable_0dt_deployment {
+                "0dt-deployment"
+            } else {
+                "deployment"
+            }
+        }
+        // The same generation booting again, e.g. after a crash or when its pod was rescheduled.
+        Some(previous) if previous == deploy_generation => "restart",
+        // Preflight checks refuse to boot an older generation, so this shouldn't happen.
+        Some(_) => "unknown",
+    }
+}
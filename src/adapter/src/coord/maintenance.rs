@@ -0,0 +1,293 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Scheduling of disruptive background work around the environment's maintenance window.
+//!
+//! The `maintenance_window` system variable holds a daily window in UTC, e.g. `02:00-04:30`.
+//! While the window is open, the coordinator defers disruptive background work, like storage
+//! usage collection, scheduled cluster shutdowns, catalog vacuums and recording availability zone
+//! advisories, until the window closes. Outside of the window, `maintenance_throttle_interval`
+//! limits how often each kind of work runs.
+//!
+//! Background tasks that only decide on disruptive work, like checking the scheduling policies of
+//! clusters, are not deferred themselves, and neither is draining the statement log, whose
+//! backlog would otherwise grow for the length of the window.
+//!
+//! Deferred work is recorded in `mz_internal.mz_deferred_maintenance` until it runs.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use mz_adapter_types::dyncfgs::{MAINTENANCE_THROTTLE_INTERVAL, MAINTENANCE_WINDOW};
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use tracing::{debug, warn};
+
+use crate::coord::{Coordinator, Message};
+
+const MILLIS_PER_MINUTE: EpochMillis = 60 * 1000;
+const MILLIS_PER_DAY: EpochMillis = 24 * 60 * MILLIS_PER_MINUTE;
+
+/// A kind of disruptive background work.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum MaintenanceWork {
+    /// Scanning persist to collect the storage usage of all shards.
+    StorageUsageCollection,
+    /// Turning off a cluster because of its schedule.
+    ClusterShutdown,
+    /// Vacuuming the durable catalog.
+    CatalogVacuum,
+    /// Recording advisories to move replicas away from degraded availability zones.
+    AzAdvisoryCheck,
+}
+
+impl MaintenanceWork {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            MaintenanceWork::StorageUsageCollection => "storage-usage-collection",
+            MaintenanceWork::ClusterShutdown => "cluster-shutdown",
+            MaintenanceWork::CatalogVacuum => "catalog-vacuum",
+            MaintenanceWork::AzAdvisoryCheck => "az-advisory-check",
+        }
+    }
+}
+
+/// Why a run of maintenance work was deferred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DeferralReason {
+    /// The maintenance window is open.
+    MaintenanceWindow,
+    /// The work already ran within the last `maintenance_throttle_interval`.
+    Throttled,
+}
+
+impl DeferralReason {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            DeferralReason::MaintenanceWindow => "maintenance-window",
+            DeferralReason::Throttled => "throttled",
+        }
+    }
+}
+
+/// Maintenance work that is waiting to run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DeferredMaintenance {
+    pub reason: DeferralReason,
+    /// When the work was first deferred.
+    pub deferred_at: EpochMillis,
+    /// When the work may run next.
+    pub resumes_at: EpochMillis,
+}
+
+/// The coordinator's bookkeeping of maintenance work.
+#[derive(Debug, Default)]
+pub(crate) struct MaintenanceState {
+    /// When each kind of work last ran.
+    last_runs: BTreeMap<MaintenanceWork, EpochMillis>,
+    /// The work that is currently deferred, as recorded in `mz_deferred_maintenance`.
+    deferred: BTreeMap<MaintenanceWork, DeferredMaintenance>,
+}
+
+/// A daily maintenance window in UTC, as milliseconds since midnight.
+///
+/// A window whose end precedes its start wraps around midnight.
+#[derive(Debug, PartialEq, Eq)]
+struct MaintenanceWindow {
+    start: EpochMillis,
+    end: EpochMillis,
+}
+
+impl MaintenanceWindow {
+    /// Parses a window formatted as `HH:MM-HH:MM`, or `None` if `s` is empty.
+    fn parse(s: &str) -> Result<Option<MaintenanceWindow>, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(None);
+        }
+        let parse_time = |time: &str| {
+            let (hours, minutes) = time
+                .trim()
+                .split_once(':')
+                .ok_or_else(|| format!("invalid time {time:?}"))?;
+            let hours: EpochMillis = hours
+                .parse()
+                .map_err(|_| format!("invalid hours {hours:?}"))?;
+            let minutes: EpochMillis = minutes
+                .parse()
+                .map_err(|_| format!("invalid minutes {minutes:?}"))?;
+            if hours >= 24 || minutes >= 60 {
+                return Err(format!("invalid time {time:?}"));
+            }
+            Ok((hours * 60 + minutes) * MILLIS_PER_MINUTE)
+        };
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("expected HH:MM-HH:MM, got {s:?}"))?;
+        Ok(Some(MaintenanceWindow {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        }))
+    }
+
+    /// Returns when the window closes if it is open at `now`.
+    fn closes_at(&self, now: EpochMillis) -> Option<EpochMillis> {
+        let time_of_day = now % MILLIS_PER_DAY;
+        let midnight = now - time_of_day;
+        if self.start <= self.end {
+            (self.start..self.end)
+                .contains(&time_of_day)
+                .then_some(midnight + self.end)
+        } else if time_of_day >= self.start {
+            Some(midnight + MILLIS_PER_DAY + self.end)
+        } else if time_of_day < self.end {
+            Some(midnight + self.end)
+        } else {
+            None
+        }
+    }
+}
+
+impl Coordinator {
+    /// Decides whether `work` may run now.
+    ///
+    /// If it may, this records the run and returns `None`. Otherwise this records the work as
+    /// deferred and returns how long to wait before trying again.
+    pub(crate) fn defer_maintenance(&mut self, work: MaintenanceWork) -> Option<Duration> {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let window =
+            MaintenanceWindow::parse(&MAINTENANCE_WINDOW.get(dyncfgs)).unwrap_or_else(|e| {
+                warn!("ignoring invalid maintenance_window: {e}");
+                None
+            });
+        let throttle_interval = MAINTENANCE_THROTTLE_INTERVAL.get(dyncfgs);
+        let throttle_interval =
+            EpochMillis::try_from(throttle_interval.as_millis()).unwrap_or(EpochMillis::MAX);
+        let now: EpochMillis = (self.catalog().config().now)();
+
+        let deferral = match window.and_then(|window| window.closes_at(now)) {
+            Some(closes_at) => Some((DeferralReason::MaintenanceWindow, closes_at)),
+            None => self
+                .maintenance
+                .last_runs
+                .get(&work)
+                .map(|last_run| last_run.saturating_add(throttle_interval))
+                .filter(|next_run| *next_run > now)
+                .map(|next_run| (DeferralReason::Throttled, next_run)),
+        };
+
+        let previous = self.maintenance.deferred.get(&work).cloned();
+        let Some((reason, resumes_at)) = deferral else {
+            self.maintenance.last_runs.insert(work, now);
+            if let Some(previous) = previous {
+                debug!(work = work.as_str(), "running deferred maintenance work");
+                self.maintenance.deferred.remove(&work);
+                self.report_deferred_maintenance(work, Some(previous), None);
+            }
+            return None;
+        };
+
+        let deferred = DeferredMaintenance {
+            reason,
+            deferred_at: previous
+                .as_ref()
+                .map_or(now, |previous| previous.deferred_at),
+            resumes_at,
+        };
+        if previous.as_ref() != Some(&deferred) {
+            debug!(
+                work = work.as_str(),
+                reason = reason.as_str(),
+                resumes_at,
+                "deferring maintenance work"
+            );
+            self.maintenance.deferred.insert(work, deferred.clone());
+            self.report_deferred_maintenance(work, previous, Some(deferred));
+        }
+        Some(Duration::from_millis(resumes_at - now))
+    }
+
+    /// Sends `message` to the coordinator once `delay` has passed, to retry deferred work.
+    pub(crate) fn retry_maintenance_after(&self, delay: Duration, message: Message) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "maintenance_retry", async move {
+            tokio::time::sleep(delay).await;
+            if internal_cmd_tx.send(message).is_err() {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Replaces the `previous` row of `work` in `mz_deferred_maintenance` with `current`.
+    fn report_deferred_maintenance(
+        &mut self,
+        work: MaintenanceWork,
+        previous: Option<DeferredMaintenance>,
+        current: Option<DeferredMaintenance>,
+    ) {
+        let state = self.catalog().state();
+        let updates: Vec<_> = previous
+            .map(|deferred| (deferred, -1))
+            .into_iter()
+            .chain(current.map(|deferred| (deferred, 1)))
+            .map(|(deferred, diff)| {
+                let update = state.pack_deferred_maintenance_update(
+                    work.as_str(),
+                    deferred.reason.as_str(),
+                    deferred.deferred_at,
+                    deferred.resumes_at,
+                    diff,
+                );
+                state.resolve_builtin_table_update(update)
+            })
+            .collect();
+        self.builtin_table_update().background(updates);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_maintenance_window() {
+        const HOUR: EpochMillis = 60 * MILLIS_PER_MINUTE;
+        let day = 19_000 * MILLIS_PER_DAY;
+
+        assert_eq!(MaintenanceWindow::parse(""), Ok(None));
+        let window = MaintenanceWindow::parse("02:00-04:30").unwrap().unwrap();
+        assert_eq!(window.closes_at(day + HOUR), None);
+        assert_eq!(
+            window.closes_at(day + 3 * HOUR),
+            Some(day + 4 * HOUR + 30 * MILLIS_PER_MINUTE)
+        );
+        assert_eq!(window.closes_at(day + 5 * HOUR), None);
+
+        // Windows may wrap around midnight.
+        let window = MaintenanceWindow::parse("22:00 - 01:00").unwrap().unwrap();
+        assert_eq!(
+            window.closes_at(day + 23 * HOUR),
+            Some(day + MILLIS_PER_DAY + HOUR)
+        );
+        assert_eq!(
+            window.closes_at(day + 30 * MILLIS_PER_MINUTE),
+            Some(day + HOUR)
+        );
+        assert_eq!(window.closes_at(day + 12 * HOUR), None);
+
+        // Empty windows are never open.
+        let window = MaintenanceWindow::parse("03:00-03:00").unwrap().unwrap();
+        assert_eq!(window.closes_at(day + 3 * HOUR), None);
+
+        assert!(MaintenanceWindow::parse("02:00").is_err());
+        assert!(MaintenanceWindow::parse("24:00-01:00").is_err());
+        assert!(MaintenanceWindow::parse("02:60-03:00").is_err());
+        assert!(MaintenanceWindow::parse("two-three").is_err());
+    }
+}
@@ -16,17 +16,22 @@ use std::time::{Duration, Instant};
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
 use maplit::btreemap;
+use mz_adapter_types::dyncfgs::ENABLE_STORAGE_SHARD_CARDINALITY_COLLECTION;
+use mz_catalog::builtin::MZ_REPLICA_UTILIZATION;
 use mz_catalog::memory::objects::ClusterReplicaProcessStatus;
 use mz_controller::clusters::{ClusterEvent, ClusterStatus};
 use mz_controller::ControllerResponse;
+use mz_orchestrator::NotReadyReason;
 use mz_ore::now::EpochMillis;
 use mz_ore::option::OptionExt;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{soft_assert_or_log, task};
 use mz_persist_client::usage::ShardsUsageReferenced;
+use mz_repr::{Datum, Row};
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::pure::PurifiedStatement;
+use mz_storage_client::controller::IntrospectionType;
 use mz_storage_types::controller::CollectionMetadata;
 use opentelemetry::trace::TraceContextExt;
 use rand::{rngs, Rng, SeedableRng};
@@ -35,13 +40,19 @@ use tracing::{event, info_span, warn, Instrument, Level};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
+use crate::catalog::BuiltinTableUpdate;
 use crate::command::Command;
 use crate::coord::appends::Deferred;
+use crate::coord::background_tasks::BackgroundTask;
+use crate::coord::dataflow_shutdowns::DataflowShutdownReason;
+use crate::coord::maintenance::MaintenanceWork;
+use crate::coord::statement_logging::STATEMENT_LOG_DRAIN_INTERVAL;
+use crate::coord::write_lock_waits::WriteLockGrantee;
 use crate::coord::{
     AlterConnectionValidationReady, ClusterReplicaStatuses, Coordinator,
     CreateConnectionValidationReady, Message, PurifiedStatementReady, WatchSetResponse,
 };
-use crate::telemetry::{EventDetails, SegmentClientExt};
+use crate::telemetry::BufferedEvent;
 use crate::{catalog, AdapterNotice, TimestampContext};
 
 impl Coordinator {
@@ -70,6 +81,9 @@ impl Coordinator {
                     self.message_command(cmd).instrument(span).await
                 }
                 Message::ControllerReady => {
+                    if self.delay_controller_process() {
+                        return;
+                    }
                     let Coordinator {
                         controller,
                         catalog,
@@ -128,6 +142,89 @@ impl Coordinator {
                 Message::StorageUsageUpdate(sizes) => {
                     self.storage_usage_update(sizes).await;
                 }
+                Message::CatalogVacuum => {
+                    self.scheduled_catalog_vacuum();
+                }
+                Message::CatalogVacuumReady { ctx, expired } => {
+                    self.finish_catalog_vacuum(ctx, expired).await;
+                }
+                Message::DatabaseMetricsReport => {
+                    self.report_database_metrics();
+                }
+                Message::SinkLagReport => {
+                    self.report_sink_lag();
+                }
+                Message::CheckComputeSinkLiveness => {
+                    self.check_compute_sink_liveness().await;
+                }
+                Message::AdmitWaitingComputeSink(cluster_id) => {
+                    self.admit_waiting_compute_sinks(cluster_id).await;
+                }
+                Message::ExternalTaskFinished(hosts) => {
+                    self.finish_external_task(hosts);
+                }
+                Message::ReplicaCapacityChecked {
+                    ctx,
+                    creation,
+                    insufficient,
+                } => {
+                    self.handle_replica_capacity_checked(ctx, creation, insufficient)
+                        .await;
+                }
+                Message::CheckWriteLockWaits => {
+                    self.check_write_lock_waits();
+                }
+                Message::FlushSegmentEvents => {
+                    self.flush_segment_events();
+                }
+                Message::WebhookSinkDeliveryRecord => {
+                    self.record_webhook_sink_deliveries().await;
+                }
+                Message::ResumeSubscribe(sink_id) => {
+                    self.resume_subscribe(sink_id);
+                }
+                Message::CursorHandoffExpired(token) => {
+                    self.expire_cursor_handoff(token).await;
+                }
+                Message::ResumeOomPausedHydrations {
+                    replica_id,
+                    generation,
+                } => {
+                    self.resume_oom_paused_hydrations(replica_id, generation);
+                }
+                Message::ControllerProcessDelayElapsed => {
+                    self.controller_process_delayed = false;
+                }
+                Message::CheckAzAdvisories => {
+                    self.check_az_advisories().await;
+                }
+                Message::FlushRejectedCancellations => {
+                    self.flush_rejected_cancellations().await;
+                }
+                Message::FetchCursorPage { id, tx } => {
+                    self.fetch_cursor_page(id, tx).await;
+                }
+                Message::CursorPageComputed { id, page } => {
+                    self.cursor_page_computed(id, page);
+                }
+                Message::ClosePagedCursor(id) => {
+                    self.close_paged_cursor(id);
+                }
+                Message::PagedCursorExpired(id) => {
+                    self.expire_paged_cursor(id);
+                }
+                Message::PeekMirrorDiscrepancy(discrepancy) => {
+                    self.record_peek_mirror_discrepancy(discrepancy).await;
+                }
+                Message::BuiltinTableMigrationSnapshot { id, contents } => {
+                    self.apply_builtin_table_migration(id, contents);
+                }
+                Message::BuiltinTableMigrationComplete(id) => {
+                    self.complete_builtin_table_migration(id);
+                }
+                Message::CatalogFollowerUpdates(updates) => {
+                    self.apply_catalog_follower_updates(updates);
+                }
                 Message::RetireExecute {
                     otel_ctx,
                     data,
@@ -187,6 +284,9 @@ impl Coordinator {
                 } => {
                     self.sequence_staged((), span, stage).await;
                 }
+                Message::ReinstallIntrospectionSubscribe(id) => {
+                    self.message_reinstall_introspection_subscribe(id).await;
+                }
                 Message::ExplainTimestampStageReady {
                     ctx,
                     span,
@@ -208,8 +308,20 @@ impl Coordinator {
                 } => {
                     self.sequence_staged(ctx, span, stage).await;
                 }
+                Message::DropObjectsStageReady {
+                    ctx,
+                    span,
+                    stage,
+                } => {
+                    self.sequence_staged(ctx, span, stage).await;
+                }
                 Message::DrainStatementLog => {
-                    self.drain_statement_log().await;
+                    if self.begin_background_task(
+                        BackgroundTask::StatementLogDrain,
+                        Some(STATEMENT_LOG_DRAIN_INTERVAL),
+                    ) {
+                        self.drain_statement_log().await;
+                    }
                 }
                 Message::PrivateLinkVpcEndpointEvents(events) => {
                     if !self.controller.read_only() {
@@ -226,7 +338,16 @@ impl Coordinator {
                     }
                 }
                 Message::CheckSchedulingPolicies => {
-                    self.check_scheduling_policies().await;
+                    let interval = self
+                        .catalog()
+                        .system_config()
+                        .cluster_check_scheduling_policies_interval();
+                    if self.begin_background_task(
+                        BackgroundTask::SchedulingPolicyCheck,
+                        Some(interval),
+                    ) {
+                        self.check_scheduling_policies().await;
+                    }
                 }
                 Message::SchedulingDecisions(decisions) => {
                     self.handle_scheduling_decisions(decisions).await;
@@ -242,14 +363,35 @@ impl Coordinator {
 
     #[mz_ore::instrument(level = "debug")]
     pub async fn storage_usage_fetch(&mut self) {
+        if let Some(delay) = self.defer_maintenance(MaintenanceWork::StorageUsageCollection) {
+            self.schedule_background_task(BackgroundTask::StorageUsageCollection, delay);
+            self.retry_maintenance_after(delay, Message::StorageUsageFetch);
+            return;
+        }
+        if !self.begin_background_task(BackgroundTask::StorageUsageCollection, None) {
+            self.schedule_storage_usage_collection().await;
+            return;
+        }
+
         let internal_cmd_tx = self.internal_cmd_tx.clone();
         let client = self.storage_usage_client.clone();
 
         // Record the currently live shards.
-        let live_shards: BTreeSet<_> = self
-            .controller
-            .storage
-            .active_collection_metadatas()
+        let collection_metadatas = self.controller.storage.active_collection_metadatas();
+        // Estimating the number of distinct keys requires reading all the data of the shards, so
+        // it's opt-in, and skipped by read-only environments, which couldn't record the estimates.
+        let cardinality_shards: BTreeSet<_> = if ENABLE_STORAGE_SHARD_CARDINALITY_COLLECTION
+            .get(self.catalog().system_config().dyncfgs())
+            && !self.controller.read_only()
+        {
+            collection_metadatas
+                .iter()
+                .map(|(_id, collection_metadata)| collection_metadata.data_shard)
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+        let live_shards: BTreeSet<_> = collection_metadatas
             .into_iter()
             .flat_map(|(_id, collection_metadata)| {
                 let CollectionMetadata {
@@ -281,7 +423,9 @@ impl Coordinator {
         // requires a slow scan of the underlying storage engine.
         task::spawn(|| "storage_usage_fetch", async move {
             let collection_metric_timer = collection_metric.start_timer();
-            let shard_sizes = client.shards_usage_referenced(live_shards).await;
+            let shard_sizes = client
+                .shards_usage_referenced(live_shards, &cardinality_shards)
+                .await;
             collection_metric_timer.observe_duration();
 
             // It is not an error for shard sizes to become ready after
@@ -307,13 +451,28 @@ impl Coordinator {
         };
 
         let mut ops = vec![];
+        let mut cardinality_updates = vec![];
         for (shard_id, shard_usage) in shards_usage.by_shard {
+            if let Some(approx_distinct_keys) = shard_usage.approx_distinct_keys() {
+                let collection_timestamp = mz_ore::now::to_datetime(collection_timestamp);
+                let row = Row::pack_slice(&[
+                    Datum::String(&shard_id.to_string()),
+                    Datum::UInt64(approx_distinct_keys),
+                    Datum::TimestampTz(collection_timestamp.try_into().expect("must fit")),
+                ]);
+                cardinality_updates.push((row, 1));
+            }
             ops.push(catalog::Op::UpdateStorageUsage {
                 shard_id: Some(shard_id.to_string()),
                 size_bytes: shard_usage.size_bytes(),
                 collection_timestamp,
             });
         }
+        self.append_introspection_updates(
+            IntrospectionType::StorageShardCardinality,
+            cardinality_updates,
+        )
+        .await;
 
         match self.catalog_transact_inner(None, ops).await {
             Ok(table_updates) => {
@@ -333,12 +492,17 @@ impl Coordinator {
         }
     }
 
-    pub async fn schedule_storage_usage_collection(&self) {
+    pub async fn schedule_storage_usage_collection(&mut self) {
         // Instead of using an `tokio::timer::Interval`, we calculate the time until the next
         // usage collection and wait for that amount of time. This is so we can keep the intervals
         // consistent even across restarts. If collection takes too long, it is possible that
         // we miss an interval.
 
+        // A collection is already scheduled if this follows a triggered collection.
+        if self.background_task_pending(BackgroundTask::StorageUsageCollection) {
+            return;
+        }
+
         // 1) Deterministically pick some offset within the collection interval to prevent
         // thundering herds across environments.
         const SEED_LEN: usize = 32;
@@ -372,6 +536,10 @@ impl Coordinator {
             previous_collection_ts + storage_usage_collection_interval_ms
         };
         let next_collection_interval = Duration::from_millis(next_collection_ts - now_ts);
+        self.schedule_background_task(
+            BackgroundTask::StorageUsageCollection,
+            next_collection_interval,
+        );
 
         // 3) Sleep for that amount of time, then initiate another storage usage collection.
         let internal_cmd_tx = self.internal_cmd_tx.clone();
@@ -405,6 +573,8 @@ impl Coordinator {
                             sink_id => ActiveComputeSinkRetireReason::Finished,
                         })
                         .await;
+                    } else {
+                        self.limit_subscribe_buffer(sink_id);
                     }
 
                     soft_assert_or_log!(
@@ -429,55 +599,85 @@ impl Coordinator {
                     }
                 }
             }
+            ControllerResponse::ComputeSinkFailure(sink_id, replica_id, error) => {
+                self.record_webhook_sink_failure(sink_id, replica_id, error)
+                    .await;
+            }
             ControllerResponse::ComputeReplicaMetrics(replica_id, new) => {
-                let m = match self
+                let md = match self
                     .transient_replica_metadata
                     .entry(replica_id)
                     .or_insert_with(|| Some(Default::default()))
                 {
                     // `None` is the tombstone for a removed replica
                     None => return,
-                    Some(md) => &mut md.metrics,
+                    Some(md) => md,
                 };
-                let old = std::mem::replace(m, Some(new.clone()));
+                let old = std::mem::replace(&mut md.metrics, Some(new.clone()));
+                let old_utilization = md.utilization.take();
+                let mut updates = Vec::new();
                 if old.as_ref() != Some(&new) {
-                    let retractions = old.map(|old| {
+                    if let Some(old) = old {
+                        updates.extend(
+                            self.catalog()
+                                .state()
+                                .pack_replica_metric_updates(replica_id, &old, -1),
+                        );
+                    }
+                    updates.extend(
                         self.catalog()
                             .state()
-                            .pack_replica_metric_updates(replica_id, &old, -1)
+                            .pack_replica_metric_updates(replica_id, &new, 1),
+                    );
+                }
+
+                // Refresh the utilization of the replica, which includes the time at which its
+                // metrics were received, even if they didn't change.
+                if let Some(row) = old_utilization {
+                    updates.push(BuiltinTableUpdate {
+                        id: &*MZ_REPLICA_UTILIZATION,
+                        row,
+                        diff: -1,
                     });
-                    let insertions = self
-                        .catalog()
-                        .state()
-                        .pack_replica_metric_updates(replica_id, &new, 1);
-                    let updates = if let Some(retractions) = retractions {
-                        retractions
-                            .into_iter()
-                            .chain(insertions.into_iter())
-                            .collect()
-                    } else {
-                        insertions
-                    };
-                    let updates = self
-                        .catalog()
-                        .state()
-                        .resolve_builtin_table_updates(updates);
-                    self.builtin_table_update().background(updates);
                 }
+                let replica = self
+                    .catalog()
+                    .clusters()
+                    .find_map(|cluster| cluster.replica(replica_id));
+                if let Some(replica) = replica {
+                    let hydration_fraction = self
+                        .controller
+                        .compute
+                        .replica_hydration_fraction(replica.cluster_id, replica_id)
+                        .ok()
+                        .flatten();
+                    let insertion = self.catalog().state().pack_replica_utilization_update(
+                        replica,
+                        &new,
+                        hydration_fraction,
+                        self.now(),
+                        1,
+                    );
+                    let row = insertion.row.clone();
+                    updates.push(insertion);
+                    if let Some(Some(md)) = self.transient_replica_metadata.get_mut(&replica_id) {
+                        md.utilization = Some(row);
+                    }
+                }
+
+                let updates = self.resolve_cached_builtin_table_updates(updates);
+                self.builtin_table_update().background(updates);
             }
             ControllerResponse::WatchSetFinished(ws_ids) => {
                 let now = self.now();
                 for ws_id in ws_ids {
-                    let Some((conn_id, rsp)) = self.installed_watch_sets.remove(&ws_id) else {
+                    let Some(key) = self.watch_set_keys.remove(&ws_id) else {
                         continue;
                     };
-                    self.connection_watch_sets
-                        .get_mut(&conn_id)
-                        .expect("corrupted coordinator state: unknown connection id")
-                        .remove(&ws_id);
-                    if self.connection_watch_sets[&conn_id].is_empty() {
-                        self.connection_watch_sets.remove(&conn_id);
-                    }
+                    let (_, rsp) = self
+                        .installed_watch_sets
+                        .remove(key)
+                        .expect("corrupted coordinator state: unknown watch set key");
 
                     match rsp {
                         WatchSetResponse::StatementDependenciesReady(id, ev) => {
@@ -486,6 +686,10 @@ impl Coordinator {
                         WatchSetResponse::AlterSinkReady(ctx) => {
                             self.sequence_alter_sink_finish(ctx).await;
                         }
+                        WatchSetResponse::PeekDependenciesReadable(tx) => {
+                            // If sending fails, the peek was canceled.
+                            let _ = tx.send(());
+                        }
                     }
                 }
             }
@@ -673,6 +877,10 @@ impl Coordinator {
             match ready {
                 Deferred::Plan(mut ready) => {
                     ready.ctx.session_mut().grant_write_lock(write_lock_guard);
+                    self.record_write_lock_grant(WriteLockGrantee::Statement {
+                        conn_id: ready.ctx.session().conn_id().clone(),
+                        statement: Some(ready.plan.name().into()),
+                    });
                     if let Err(e) = ready.validity.check(self.catalog()) {
                         ready.ctx.retire(Err(e))
                     } else {
@@ -697,8 +905,7 @@ impl Coordinator {
     async fn message_cluster_event(&mut self, event: ClusterEvent) {
         event!(Level::TRACE, event = format!("{:?}", event));
 
-        if let Some(segment_client) = &self.segment_client {
-            let env_id = &self.catalog().config().environment_id;
+        if self.segment_client.is_some() {
             let mut properties = json!({
                 "cluster_id": event.cluster_id.to_string(),
                 "replica_id": event.replica_id.to_string(),
@@ -718,13 +925,14 @@ impl Coordinator {
                     );
                 }
             };
-            segment_client.environment_track(
-                env_id,
-                "Cluster Changed Status",
-                properties,
-                EventDetails {
+            self.buffer_cluster_segment_event(
+                event.cluster_id,
+                event.replica_id,
+                event.process_id,
+                BufferedEvent {
+                    event: "Cluster Changed Status".into(),
+                    properties,
                     timestamp: Some(event.time),
-                    ..Default::default()
                 },
             );
         }
@@ -751,10 +959,8 @@ impl Coordinator {
                     old_process_status,
                     -1,
                 );
-            let builtin_table_retraction = self
-                .catalog()
-                .state()
-                .resolve_builtin_table_update(builtin_table_retraction);
+            let builtin_table_retraction =
+                self.resolve_cached_builtin_table_update(builtin_table_retraction);
 
             let new_process_status = ClusterReplicaProcessStatus {
                 status: event.status,
@@ -766,10 +972,8 @@ impl Coordinator {
                 &new_process_status,
                 1,
             );
-            let builtin_table_addition = self
-                .catalog()
-                .state()
-                .resolve_builtin_table_update(builtin_table_addition);
+            let builtin_table_addition =
+                self.resolve_cached_builtin_table_update(builtin_table_addition);
             self.cluster_replica_statuses.ensure_cluster_status(
                 event.cluster_id,
                 event.replica_id,
@@ -792,11 +996,19 @@ impl Coordinator {
                     .await;
             }
 
+            if event.status == ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)) {
+                self.handle_replica_oom_kill(event.cluster_id, event.replica_id, event.time);
+            }
+
             let cluster = self.catalog().get_cluster(event.cluster_id);
             let replica = cluster.replica(event.replica_id).expect("Replica exists");
             let new_replica_status = self
                 .cluster_replica_statuses
                 .get_cluster_replica_status(event.cluster_id, event.replica_id);
+            // The dataflows of the replica are shut down when any of its processes stops, and
+            // are restarted once all of them are ready again.
+            let replica_stopped = old_replica_status == ClusterStatus::Ready
+                && new_replica_status != ClusterStatus::Ready;
 
             if old_replica_status != new_replica_status {
                 self.broadcast_notice(AdapterNotice::ClusterReplicaStatusChanged {
@@ -805,6 +1017,23 @@ impl Coordinator {
                     status: new_replica_status,
                     time: event.time,
                 });
+                self.update_az_health(event.time);
+            }
+
+            if replica_stopped {
+                let reason = match event.status {
+                    ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)) => {
+                        DataflowShutdownReason::ReplicaOomKilled
+                    }
+                    _ => DataflowShutdownReason::ReplicaRestarted,
+                };
+                let shutdowns = self.replica_dataflow_shutdowns(
+                    event.cluster_id,
+                    event.replica_id,
+                    reason,
+                    None,
+                );
+                self.record_dataflow_shutdowns(shutdowns).await;
             }
         }
     }
@@ -815,6 +1044,15 @@ impl Coordinator {
     ///   containing timeline has advanced to that point in the future.
     ///   2. Confirming that we are still the current leader before sending results to the client.
     async fn message_linearize_reads(&mut self) {
+        // Only retries of deferred reads count as runs of the background task, not the first
+        // attempt of each read.
+        if self.background_task_pending(BackgroundTask::DeferredReadRetry)
+            && !self.begin_background_task(BackgroundTask::DeferredReadRetry, None)
+        {
+            self.retry_linearize_reads(Duration::from_millis(1_000));
+            return;
+        }
+
         let mut shortest_wait = Duration::from_millis(0);
         let mut ready_txns = Vec::new();
 
@@ -904,18 +1142,24 @@ impl Coordinator {
             }
         }
 
-        if !self.pending_linearize_read_txns.is_empty() {
-            // Cap wait time to 1s.
-            let remaining_ms = std::cmp::min(shortest_wait, Duration::from_millis(1_000));
-            let internal_cmd_tx = self.internal_cmd_tx.clone();
-            task::spawn(|| "deferred_read_txns", async move {
-                tokio::time::sleep(remaining_ms).await;
-                // It is not an error for this task to be running after `internal_cmd_rx` is dropped.
-                let result = internal_cmd_tx.send(Message::LinearizeReads);
-                if let Err(e) = result {
-                    warn!("internal_cmd_rx dropped before we could send: {:?}", e);
-                }
-            });
+        // Cap wait time to 1s.
+        self.retry_linearize_reads(std::cmp::min(shortest_wait, Duration::from_millis(1_000)));
+    }
+
+    /// Retries the pending strict serializable reads after `delay`, if there are any.
+    fn retry_linearize_reads(&mut self, delay: Duration) {
+        if self.pending_linearize_read_txns.is_empty() {
+            return;
         }
+        self.schedule_background_task(BackgroundTask::DeferredReadRetry, delay);
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "deferred_read_txns", async move {
+            tokio::time::sleep(delay).await;
+            // It is not an error for this task to be running after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::LinearizeReads);
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
     }
 }
@@ -0,0 +1,195 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sampling of the spans of coordinator messages.
+//!
+//! The coordinator starts an `INFO` span for every message it handles, which adds up under high
+//! message throughput. `coord_message_span_sampling` configures, per message kind, the level of
+//! the span and the fraction of messages that get one, as a comma-separated list of
+//! `kind=level` or `kind=level/n` entries, where `level` is one of `trace`, `debug`, `info`, or
+//! `off`, and `n` traces only every `n`th message of the kind. For example,
+//! `linearize_reads=debug,controller_ready=info/100` downgrades the spans of `linearize_reads`
+//! and traces only one in a hundred `controller_ready` messages. Messages of kinds that are not
+//! listed are always traced at `INFO`.
+
+use std::collections::BTreeMap;
+
+use mz_adapter_types::dyncfgs::COORD_MESSAGE_SPAN_SAMPLING;
+use tracing::{span, warn, Level, Span};
+
+use crate::coord::Coordinator;
+
+/// How the messages of a kind are traced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sampling {
+    /// The level of the spans, or `None` if the messages are not traced.
+    level: Option<Level>,
+    /// Only every `n`th message gets a span.
+    n: u64,
+}
+
+/// The sampling of the spans of coordinator messages, by message kind.
+#[derive(Debug, Default)]
+pub(crate) struct MessageSpans {
+    /// The configuration that `sampling` was parsed from.
+    config: String,
+    sampling: BTreeMap<String, Sampling>,
+    /// The number of messages of each sampled kind handled so far.
+    counts: BTreeMap<&'static str, u64>,
+}
+
+impl MessageSpans {
+    /// Parses the value of `coord_message_span_sampling`. Invalid entries are ignored, so that a
+    /// typo doesn't affect the tracing of the other kinds.
+    fn parse(config: &str) -> BTreeMap<String, Sampling> {
+        let mut sampling = BTreeMap::new();
+        for entry in config.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match Self::parse_entry(entry) {
+                Some((kind, s)) => {
+                    sampling.insert(kind.to_string(), s);
+                }
+                None => warn!(%entry, "ignoring invalid coord_message_span_sampling entry"),
+            }
+        }
+        sampling
+    }
+
+    fn parse_entry(entry: &str) -> Option<(&str, Sampling)> {
+        let (kind, value) = entry.split_once('=')?;
+        let kind = kind.trim();
+        if kind.is_empty() {
+            return None;
+        }
+        let (level, n) = match value.trim().split_once('/') {
+            Some((level, n)) => (level, n.trim().parse().ok().filter(|n| *n > 0)?),
+            None => (value, 1),
+        };
+        let level = match level.trim().to_lowercase().as_str() {
+            "trace" => Some(Level::TRACE),
+            "debug" => Some(Level::DEBUG),
+            "info" => Some(Level::INFO),
+            "off" => None,
+            _ => return None,
+        };
+        Some((kind, Sampling { level, n }))
+    }
+
+    /// Returns the span in which to handle the next message of kind `kind`.
+    pub(crate) fn span(&mut self, kind: &'static str) -> Span {
+        let Some(sampling) = self.sampling.get(kind) else {
+            return Self::new_span(Level::INFO, kind);
+        };
+        let Some(level) = sampling.level else {
+            return Span::none();
+        };
+        if sampling.n > 1 {
+            let count = self.counts.entry(kind).or_default();
+            let sampled = *count % sampling.n == 0;
+            *count = count.wrapping_add(1);
+            if !sampled {
+                return Span::none();
+            }
+        }
+        Self::new_span(level, kind)
+    }
+
+    fn new_span(level: Level, kind: &'static str) -> Span {
+        // `span!` requires a constant level.
+        macro_rules! new_span {
+            ($level:expr) => {
+                span!(
+                    target: "mz_adapter::coord::handle_message_loop",
+                    $level,
+                    "coord::handle_message",
+                    kind = kind
+                )
+            };
+        }
+        match level {
+            Level::TRACE => new_span!(Level::TRACE),
+            Level::DEBUG => new_span!(Level::DEBUG),
+            _ => new_span!(Level::INFO),
+        }
+    }
+}
+
+impl Coordinator {
+    /// Applies the current value of `coord_message_span_sampling`.
+    pub(crate) fn update_message_span_sampling(&mut self) {
+        let config = COORD_MESSAGE_SPAN_SAMPLING.get(self.catalog().system_config().dyncfgs());
+        if config == self.message_spans.config {
+            return;
+        }
+        self.message_spans.sampling = MessageSpans::parse(&config);
+        self.message_spans.counts.clear();
+        self.message_spans.config = config;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_message_span_sampling() {
+        let sampling = MessageSpans::parse(
+            " linearize_reads = debug, controller_ready=info/100,group_commit_initiate=OFF,",
+        );
+        assert_eq!(
+            sampling.get("linearize_reads"),
+            Some(&Sampling {
+                level: Some(Level::DEBUG),
+                n: 1
+            })
+        );
+        assert_eq!(
+            sampling.get("controller_ready"),
+            Some(&Sampling {
+                level: Some(Level::INFO),
+                n: 100
+            })
+        );
+        assert_eq!(
+            sampling.get("group_commit_initiate"),
+            Some(&Sampling { level: None, n: 1 })
+        );
+        assert_eq!(sampling.len(), 3);
+
+        // Invalid entries are ignored.
+        let sampling =
+            MessageSpans::parse("a=warn,b=info/0,c=info/x,d,e=trace/2,=info,f=debug/3/4");
+        assert_eq!(
+            sampling.into_iter().collect::<Vec<_>>(),
+            vec![(
+                "e".to_string(),
+                Sampling {
+                    level: Some(Level::TRACE),
+                    n: 2
+                }
+            )]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_message_span_sampling() {
+        let mut spans = MessageSpans {
+            sampling: MessageSpans::parse("sampled=info/3,off=off"),
+            ..Default::default()
+        };
+        // Only the kinds that are sampled are counted.
+        for _ in 0..7 {
+            let _ = spans.span("sampled");
+            let _ = spans.span("off");
+            let _ = spans.span("other");
+        }
+        assert_eq!(spans.counts.get("sampled"), Some(&7));
+        assert_eq!(spans.counts.get("off"), None);
+        assert_eq!(spans.counts.get("other"), None);
+    }
+}
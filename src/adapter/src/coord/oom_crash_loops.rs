@@ -0,0 +1,265 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Protection against replicas that crash loop because of OOM kills.
+//!
+//! A dataflow whose hydration exceeds the memory of a replica makes the replica get OOM-killed
+//! and restart, at which point it hydrates all its dataflows again and gets OOM-killed again,
+//! taking down the other dataflows of the replica with it. When a replica has been OOM-killed
+//! `oom_crash_loop_threshold` times within `oom_crash_loop_window`, we pause the hydration of the
+//! dataflows that were hydrating on the replica at every one of the kills, and notify the
+//! sessions of their owners. Dataflows that sequential hydration had queued behind other
+//! dataflows were not hydrating, so they are not blamed for the kills.
+//!
+//! Paused dataflows are not scheduled on the crash looping replica when it restarts, so the
+//! remaining dataflows can hydrate. Other replicas of the cluster are not affected. The dataflows
+//! are resumed after `oom_crash_loop_pause_duration`, or when the replica is dropped, for example
+//! because the cluster is resized.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use mz_adapter_types::dyncfgs::{
+    ENABLE_OOM_CRASH_LOOP_PROTECTION, OOM_CRASH_LOOP_PAUSE_DURATION, OOM_CRASH_LOOP_THRESHOLD,
+    OOM_CRASH_LOOP_WINDOW,
+};
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::now::EpochMillis;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use tracing::{debug, warn};
+
+use crate::coord::{Coordinator, Message};
+use crate::AdapterNotice;
+
+/// The OOM kills of replicas, and the dataflows paused because of them.
+#[derive(Debug, Default)]
+pub(crate) struct OomCrashLoops {
+    /// The recent OOM kills of each replica, with the collections that were hydrating on the
+    /// replica at the time.
+    kills: BTreeMap<ReplicaId, VecDeque<(EpochMillis, BTreeSet<GlobalId>)>>,
+    /// The collections whose hydration is paused, by the replica that crash looped.
+    paused: BTreeMap<ReplicaId, PausedHydrations>,
+    /// The generation of the next pause, used to ignore the timeouts of earlier pauses.
+    next_generation: u64,
+}
+
+/// The collections whose hydration is paused on a replica.
+#[derive(Debug)]
+struct PausedHydrations {
+    cluster_id: ClusterId,
+    ids: BTreeSet<GlobalId>,
+    /// The generation of the most recent pause, whose timeout resumes the collections.
+    generation: u64,
+}
+
+/// Returns the collections that were hydrating during every kill of a crash loop.
+fn crash_loop_culprits(kills: impl IntoIterator<Item = BTreeSet<GlobalId>>) -> BTreeSet<GlobalId> {
+    let mut culprits: Option<BTreeSet<GlobalId>> = None;
+    for hydrating in kills {
+        culprits = Some(match culprits {
+            Some(culprits) => culprits.intersection(&hydrating).copied().collect(),
+            None => hydrating,
+        });
+    }
+    culprits.unwrap_or_default()
+}
+
+impl Coordinator {
+    /// Records that a process of the replica `replica_id` was OOM-killed at `time`, and pauses the
+    /// hydration of the dataflows that make the replica crash loop.
+    pub(crate) fn handle_replica_oom_kill(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        time: DateTime<Utc>,
+    ) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        if !ENABLE_OOM_CRASH_LOOP_PROTECTION.get(dyncfgs) {
+            return;
+        }
+        let threshold = OOM_CRASH_LOOP_THRESHOLD.get(dyncfgs);
+        let window = OOM_CRASH_LOOP_WINDOW.get(dyncfgs);
+        let window = EpochMillis::try_from(window.as_millis()).unwrap_or(EpochMillis::MAX);
+        let pause_duration = OOM_CRASH_LOOP_PAUSE_DURATION.get(dyncfgs);
+
+        // Only compute clusters report hydration. The controller remembers the collections that
+        // were hydrating when the replica failed, even if it has rehydrated the replica since.
+        let Ok(Some(hydrating)) = self
+            .controller
+            .compute
+            .take_hydrating_collections(cluster_id, replica_id)
+        else {
+            return;
+        };
+
+        let time = EpochMillis::try_from(time.timestamp_millis()).unwrap_or(0);
+        let kills = self.oom_crash_loops.kills.entry(replica_id).or_default();
+        kills.push_back((time, hydrating));
+        while kills.front().map_or(false, |(killed_at, _)| {
+            time.saturating_sub(*killed_at) > window
+        }) {
+            kills.pop_front();
+        }
+        debug!(%replica_id, kills = kills.len(), "replica was OOM-killed");
+        if threshold == 0 || kills.len() < threshold {
+            return;
+        }
+
+        let kills = self
+            .oom_crash_loops
+            .kills
+            .remove(&replica_id)
+            .expect("known to exist");
+        let culprits = crash_loop_culprits(kills.into_iter().map(|(_, hydrating)| hydrating));
+        if culprits.is_empty() {
+            warn!(
+                %replica_id,
+                "replica is crash looping with OOM kills, but no dataflow was hydrating during all \
+                 of them"
+            );
+            return;
+        }
+
+        let mut paused = BTreeSet::new();
+        for id in culprits {
+            match self
+                .controller
+                .compute
+                .set_collection_hydration_paused(cluster_id, replica_id, id, true)
+            {
+                Ok(()) => {
+                    paused.insert(id);
+                }
+                Err(e) => warn!(%id, "failed to pause hydration: {e}"),
+            }
+        }
+        warn!(%replica_id, ?paused, "paused hydration of dataflows after OOM crash loop");
+        self.notify_paused_hydrations(cluster_id, replica_id, &paused);
+
+        let generation = self.oom_crash_loops.next_generation;
+        self.oom_crash_loops.next_generation += 1;
+        let entry = self
+            .oom_crash_loops
+            .paused
+            .entry(replica_id)
+            .or_insert_with(|| PausedHydrations {
+                cluster_id,
+                ids: BTreeSet::new(),
+                generation,
+            });
+        entry.ids.extend(paused);
+        entry.generation = generation;
+
+        if pause_duration > Duration::ZERO {
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "oom_crash_loop_pause_timeout", async move {
+                tokio::time::sleep(pause_duration).await;
+                if internal_cmd_tx
+                    .send(Message::ResumeOomPausedHydrations {
+                        replica_id,
+                        generation,
+                    })
+                    .is_err()
+                {
+                    // If sending fails, the main thread has shutdown.
+                }
+            });
+        }
+    }
+
+    /// Notifies the sessions of the owners of the collections `ids` that their hydration is paused.
+    fn notify_paused_hydrations(
+        &self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        ids: &BTreeSet<GlobalId>,
+    ) {
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            return;
+        };
+        let Some(replica) = cluster.replica(replica_id) else {
+            return;
+        };
+
+        for id in ids {
+            let Some(entry) = self.catalog().try_get_entry(id) else {
+                continue;
+            };
+            let notice = AdapterNotice::HydrationPausedAfterOomCrashLoop {
+                name: self
+                    .catalog()
+                    .resolve_full_name(entry.name(), None)
+                    .to_string(),
+                cluster: cluster.name.clone(),
+                replica: replica.name.clone(),
+            };
+            self.send_notice_to_owner(entry.owner_id(), notice);
+        }
+    }
+
+    /// Resumes the hydration of the dataflows paused on the replica `replica_id` by the pause of
+    /// the given `generation`, unless they have been paused again since.
+    pub(crate) fn resume_oom_paused_hydrations(&mut self, replica_id: ReplicaId, generation: u64) {
+        let Some(paused) = self.oom_crash_loops.paused.get(&replica_id) else {
+            return;
+        };
+        if paused.generation != generation {
+            return;
+        }
+
+        let paused = self
+            .oom_crash_loops
+            .paused
+            .remove(&replica_id)
+            .expect("known to exist");
+        for id in paused.ids {
+            // The collection may have been dropped in the meantime.
+            if let Err(e) = self.controller.compute.set_collection_hydration_paused(
+                paused.cluster_id,
+                replica_id,
+                id,
+                false,
+            ) {
+                debug!(%id, "not resuming hydration: {e}");
+            }
+        }
+        debug!(%replica_id, "resumed hydration of dataflows paused after OOM crash loop");
+    }
+
+    /// Forgets the OOM kills of the replica `replica_id` and the dataflows paused on it, which
+    /// the compute controller forgets when the replica is dropped.
+    pub(crate) fn forget_replica_oom_crash_loops(&mut self, replica_id: ReplicaId) {
+        self.oom_crash_loops.kills.remove(&replica_id);
+        self.oom_crash_loops.paused.remove(&replica_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_crash_loop_culprits() {
+        let ids = |ids: &[u64]| -> BTreeSet<GlobalId> {
+            ids.iter().map(|id| GlobalId::User(*id)).collect()
+        };
+
+        assert_eq!(crash_loop_culprits([]), ids(&[]));
+        assert_eq!(crash_loop_culprits([ids(&[1, 2])]), ids(&[1, 2]));
+        // Only the collections that were hydrating during every kill are culprits.
+        assert_eq!(
+            crash_loop_culprits([ids(&[1, 2, 3]), ids(&[2, 3]), ids(&[3, 4])]),
+            ids(&[3]),
+        );
+        // A kill during which nothing was hydrating clears all culprits.
+        assert_eq!(crash_loop_culprits([ids(&[1]), ids(&[])]), ids(&[]));
+    }
+}
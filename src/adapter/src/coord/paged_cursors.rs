@@ -0,0 +1,760 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Cursors whose results are computed in pages.
+//!
+//! The first `FETCH` of a cursor over a `SELECT` usually computes the whole result of the query,
+//! which `environmentd` then holds until the client has fetched all of it. When
+//! `cursor_page_size` is set, cursors whose `SELECT` reads from an index instead compute
+//! `cursor_page_size` rows at a time, whenever the client has fetched the previous ones. Every
+//! page is peeked at the timestamp of the first one, which the read holds of the cursor's
+//! transaction keep readable, so successive `FETCH`es observe the same snapshot.
+//!
+//! Each page past the first only peeks the rows ordered at or after the last row of the previous
+//! page, and skips the copies of that row it already returned, so the replica does not send the
+//! rows of earlier pages again. The finishing breaks ties between rows by their encoding, which a
+//! predicate can't express, so paged cursors order their rows by the columns of their `ORDER BY`
+//! followed by all remaining columns, in which order only identical rows tie.
+//!
+//! The execution of the cursor's `SELECT` ends when the cursor is closed, and accounts for the
+//! rows of all of its pages. A paged cursor may stay open for at most `max_cursor_lifetime`, after
+//! which it is closed and further `FETCH`es fail. Open paged cursors are recorded in
+//! `mz_internal.mz_cursors`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures::{FutureExt, Stream};
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::{CURSOR_PAGE_SIZE, MAX_CURSOR_LIFETIME};
+use mz_catalog::builtin::{BuiltinTable, MZ_CURSORS};
+use mz_compute_types::ComputeInstanceId;
+use mz_controller_types::ReplicaId;
+use mz_expr::{BinaryFunc, ColumnOrder, MapFilterProject, MirScalarExpr, RowSetFinishing};
+use mz_ore::cast::CastFrom;
+use mz_ore::now::EpochMillis;
+use mz_ore::num::NonNeg;
+use mz_ore::task;
+use mz_repr::{ColumnType, Datum, Diff, GlobalId, IntoRowIterator, Row};
+use mz_sql::ast::Statement;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::catalog::BuiltinTableUpdate;
+use crate::coord::peek::{
+    mfp_to_safe_plan, FastPathPlan, PeekPlan, PeekResponseUnary, PlannedPeek,
+};
+use crate::coord::{Coordinator, ExecuteContextExtra, Message};
+use crate::session::Session;
+use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
+use crate::{AdapterError, ExecuteResponse};
+
+/// The cursors whose results are computed in pages.
+#[derive(Debug, Default)]
+pub(crate) struct PagedCursors {
+    /// The cursor that each connection is about to start fetching from, if its result may be
+    /// computed in pages.
+    starting: BTreeMap<ConnectionId, String>,
+    /// The open paged cursors, by ID.
+    cursors: BTreeMap<Uuid, PagedCursor>,
+    /// The names of the paged cursors that exceeded `max_cursor_lifetime` but were not closed by
+    /// their clients yet, by ID.
+    expired: BTreeMap<Uuid, String>,
+}
+
+/// A cursor whose result is computed in pages.
+#[derive(Debug)]
+struct PagedCursor {
+    /// The name of the cursor.
+    name: String,
+    /// The ID of the session that declared the cursor.
+    session_id: Uuid,
+    /// The peek of the first page. Other pages also filter the rows of earlier pages out.
+    peek: PlannedPeek,
+    /// The types of the columns of the rows that the peek returns.
+    column_types: Vec<ColumnType>,
+    /// The finishing of the cursor's `SELECT`, ordered by all columns.
+    finishing: RowSetFinishing,
+    /// The cluster that computes the pages.
+    cluster_id: ComputeInstanceId,
+    /// The replica that computes the pages, if the session targets one.
+    target_replica: Option<ReplicaId>,
+    /// The index that the pages are peeked from.
+    index_id: GlobalId,
+    max_result_size: u64,
+    max_returned_query_size: Option<u64>,
+    /// The label of the database that the pages are attributed to in the metrics.
+    database: Option<String>,
+    /// The number of rows of each page.
+    page_size: usize,
+    /// The number of pages computed so far.
+    pages_fetched: usize,
+    /// The number of rows requested by the pages computed so far.
+    rows_requested: usize,
+    /// The number of rows returned by the pages computed so far.
+    rows_returned: usize,
+    /// The last row returned so far, if any.
+    position: Option<CursorPosition>,
+    opened_at: EpochMillis,
+    expires_at: EpochMillis,
+    /// The execution of the cursor's `SELECT`, which ends when the cursor is closed.
+    execution: ExecuteContextExtra,
+    /// Why a page of the cursor could not be computed, if one couldn't.
+    failure: Option<StatementEndedExecutionReason>,
+}
+
+/// The last row returned by a paged cursor.
+#[derive(Debug, Clone, PartialEq)]
+struct CursorPosition {
+    /// The row, with all of its columns.
+    row: Row,
+    /// The number of copies of the row returned so far.
+    copies: usize,
+}
+
+/// The rows of a page of a paged cursor that has been computed.
+#[derive(Debug)]
+pub(crate) struct ComputedCursorPage {
+    /// The number of rows of the page.
+    rows: usize,
+    /// The last row returned by the cursor after the page.
+    position: Option<CursorPosition>,
+}
+
+/// A page of a paged cursor.
+#[derive(Debug)]
+pub(crate) struct CursorPage {
+    rows: PeekResponseUnary,
+    /// The number of rows the page was requested to contain. Pages with fewer rows are the last.
+    limit: usize,
+}
+
+/// The rows of a paged cursor, which requests each page from the coordinator once the previous
+/// one has been consumed. Dropping the stream closes the cursor.
+pub(crate) struct CursorPages {
+    id: Uuid,
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+    /// The page being computed, if any.
+    page: Option<oneshot::Receiver<Option<CursorPage>>>,
+    /// Whether the cursor has been closed.
+    closed: bool,
+}
+
+impl CursorPages {
+    fn close(&mut self) {
+        if !self.closed {
+            self.closed = true;
+            // If sending fails, the main thread has shutdown.
+            let _ = self
+                .internal_cmd_tx
+                .send(Message::ClosePagedCursor(self.id));
+        }
+    }
+}
+
+impl Stream for CursorPages {
+    type Item = PeekResponseUnary;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.closed {
+            return Poll::Ready(None);
+        }
+        let page = this.page.get_or_insert_with(|| {
+            let (tx, rx) = oneshot::channel();
+            // If sending fails, the main thread has shutdown and `rx` returns an error.
+            let _ = this
+                .internal_cmd_tx
+                .send(Message::FetchCursorPage { id: this.id, tx });
+            rx
+        });
+        let page = ready!(page.poll_unpin(cx));
+        this.page = None;
+        match page {
+            Ok(Some(CursorPage { rows, limit })) => {
+                let last = match &rows {
+                    PeekResponseUnary::Rows(rows) => rows.count() < limit,
+                    PeekResponseUnary::Error(_) | PeekResponseUnary::Canceled => true,
+                };
+                if last {
+                    this.close();
+                }
+                Poll::Ready(Some(rows))
+            }
+            // The cursor has no more rows, or the coordinator has shut down.
+            Ok(None) | Err(_) => {
+                this.close();
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl Drop for CursorPages {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Coordinator {
+    /// Records whether the portal `portal_name` of `session`, which is about to be executed, is a
+    /// cursor whose result may be computed in pages.
+    ///
+    /// `fetched` indicates whether the portal is executed by a `FETCH`.
+    pub(crate) fn prepare_paged_cursor(
+        &mut self,
+        session: &Session,
+        portal_name: &str,
+        fetched: bool,
+    ) {
+        let conn_id = session.conn_id();
+        self.paged_cursors.starting.remove(conn_id);
+        if !fetched || CURSOR_PAGE_SIZE.get(self.catalog().system_config().dyncfgs()) == 0 {
+            return;
+        }
+        let Some(portal) = session.get_portal_unverified(portal_name) else {
+            return;
+        };
+        if portal.declared && matches!(portal.stmt.as_deref(), Some(Statement::Select(_))) {
+            self.paged_cursors
+                .starting
+                .insert(conn_id.clone(), portal_name.to_string());
+        }
+    }
+
+    /// Returns the name of the cursor that the connection `conn_id` starts fetching from, if
+    /// `peek` computes its result and it can be computed in pages.
+    pub(crate) fn take_paged_cursor(
+        &mut self,
+        conn_id: &ConnectionId,
+        peek: &PlannedPeek,
+    ) -> Option<String> {
+        let name = self.paged_cursors.starting.remove(conn_id)?;
+        // Only peeks of indexes are cheap enough to repeat for every page.
+        match &peek.plan {
+            PeekPlan::FastPath(FastPathPlan::PeekExisting(..)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Opens the paged cursor `name`, whose pages are computed by `peek`, and returns the
+    /// response that streams its rows.
+    ///
+    /// The execution of the cursor's `SELECT`, described by `ctx_extra`, ends when the cursor is
+    /// closed.
+    pub(crate) async fn open_paged_cursor(
+        &mut self,
+        ctx_extra: &mut ExecuteContextExtra,
+        session: &Session,
+        name: String,
+        peek: PlannedPeek,
+        column_types: Vec<ColumnType>,
+        mut finishing: RowSetFinishing,
+        cluster_id: ComputeInstanceId,
+        target_replica: Option<ReplicaId>,
+        max_result_size: u64,
+        max_returned_query_size: Option<u64>,
+        database: Option<String>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let PeekPlan::FastPath(FastPathPlan::PeekExisting(_, index_id, ..)) = &peek.plan else {
+            unreachable!("only peeks of indexes are paged");
+        };
+        let index_id = *index_id;
+
+        let ordered: BTreeSet<_> = finishing
+            .order_by
+            .iter()
+            .map(|order| order.column)
+            .collect();
+        let remaining = (0..column_types.len()).filter(|column| !ordered.contains(column));
+        finishing
+            .order_by
+            .extend(remaining.map(|column| ColumnOrder {
+                column,
+                desc: false,
+                nulls_last: true,
+            }));
+
+        let execution = std::mem::take(ctx_extra);
+        if let Some(id) = execution.contents() {
+            let timestamp = peek.determination.timestamp_context.timestamp_or_default();
+            self.set_statement_execution_timestamp(id, timestamp);
+        }
+
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let page_size = CURSOR_PAGE_SIZE.get(dyncfgs);
+        let lifetime = MAX_CURSOR_LIFETIME.get(dyncfgs);
+        let opened_at = self.now();
+        let expires_at = opened_at
+            .saturating_add(EpochMillis::try_from(lifetime.as_millis()).unwrap_or(u64::MAX));
+
+        let id = Uuid::new_v4();
+        let cursor = PagedCursor {
+            name,
+            session_id: session.uuid(),
+            peek,
+            column_types,
+            finishing,
+            cluster_id,
+            target_replica,
+            index_id,
+            max_result_size,
+            max_returned_query_size,
+            database,
+            page_size,
+            pages_fetched: 0,
+            rows_requested: 0,
+            rows_returned: 0,
+            position: None,
+            opened_at,
+            expires_at,
+            execution,
+            failure: None,
+        };
+        let update = pack_cursor_update(id, &cursor, 1);
+        self.paged_cursors.cursors.insert(id, cursor);
+        let update = self.catalog().state().resolve_builtin_table_update(update);
+        self.builtin_table_update().background(vec![update]);
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "paged_cursor_lifetime", async move {
+            tokio::time::sleep(lifetime).await;
+            if internal_cmd_tx
+                .send(Message::PagedCursorExpired(id))
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+
+        let (tx, rx) = oneshot::channel();
+        self.fetch_cursor_page(id, tx).await;
+        Ok(ExecuteResponse::SendingRowsStreaming {
+            rows: Box::new(CursorPages {
+                id,
+                internal_cmd_tx: self.internal_cmd_tx.clone(),
+                page: Some(rx),
+                closed: false,
+            }),
+            instance_id: cluster_id,
+            strategy: StatementExecutionStrategy::FastPath,
+        })
+    }
+
+    /// Computes the next page of the paged cursor `id`, and sends it to `tx` once it is
+    /// available, or `None` if the cursor has no more rows.
+    pub(crate) async fn fetch_cursor_page(
+        &mut self,
+        id: Uuid,
+        tx: oneshot::Sender<Option<CursorPage>>,
+    ) {
+        let send_error = |tx: oneshot::Sender<_>, error: String| {
+            let _ = tx.send(Some(CursorPage {
+                rows: PeekResponseUnary::Error(error),
+                limit: 0,
+            }));
+        };
+        let Some(cursor) = self.paged_cursors.cursors.get_mut(&id) else {
+            let error = match self.paged_cursors.expired.get(&id) {
+                Some(name) => format!("cursor \"{name}\" was closed after max_cursor_lifetime"),
+                None => "cursor was closed".into(),
+            };
+            return send_error(tx, error);
+        };
+
+        let limit = match cursor.finishing.limit {
+            Some(limit) => {
+                let limit = usize::cast_from(u64::from(limit));
+                cursor
+                    .page_size
+                    .min(limit.saturating_sub(cursor.rows_requested))
+            }
+            None => cursor.page_size,
+        };
+        // Pages past the first start at the last row returned, and only pages without rows have
+        // none.
+        let first = cursor.pages_fetched == 0;
+        if limit == 0 || (!first && cursor.position.is_none()) {
+            let _ = tx.send(None);
+            return;
+        }
+        // The index may have been dropped since the cursor was opened.
+        if self
+            .controller
+            .compute
+            .collection(cursor.cluster_id, cursor.index_id)
+            .is_err()
+        {
+            let error = format!("index of cursor \"{}\" was dropped", cursor.name);
+            cursor.failure = Some(StatementEndedExecutionReason::Errored {
+                error: error.clone(),
+            });
+            return send_error(tx, error);
+        }
+
+        // The first page skips the offset of the cursor's `SELECT` itself, so that it learns how
+        // many copies of its last row precede it. Later pages skip the copies of the last row that
+        // were returned already, so they must not be projected away.
+        let mut peek = cursor.peek.clone();
+        let mut finishing = cursor.finishing.clone();
+        finishing.project = (0..cursor.column_types.len()).collect();
+        let (skip, requested) = match &cursor.position {
+            Some(position) => {
+                let PeekPlan::FastPath(FastPathPlan::PeekExisting(_, _, _, mfp)) = &mut peek.plan
+                else {
+                    unreachable!("only peeks of indexes are paged");
+                };
+                let predicate =
+                    at_or_after(&finishing.order_by, &cursor.column_types, &position.row);
+                match mfp_to_safe_plan(MapFilterProject::clone(mfp).filter([predicate])) {
+                    Ok(filtered) => *mfp = filtered,
+                    Err(e) => {
+                        let error = e.to_string();
+                        cursor.failure = Some(StatementEndedExecutionReason::Errored {
+                            error: error.clone(),
+                        });
+                        return send_error(tx, error);
+                    }
+                }
+                finishing.offset = position.copies;
+                (0, limit)
+            }
+            None => {
+                finishing.offset = 0;
+                (
+                    cursor.finishing.offset,
+                    cursor.finishing.offset.saturating_add(limit),
+                )
+            }
+        };
+        finishing.limit = Some(
+            NonNeg::try_from(i64::try_from(requested).unwrap_or(i64::MAX))
+                .expect("known to be non-negative"),
+        );
+        let mut updates = vec![pack_cursor_update(id, cursor, -1)];
+        cursor.rows_requested = cursor.rows_requested.saturating_add(limit);
+        cursor.pages_fetched += 1;
+        updates.push(pack_cursor_update(id, cursor, 1));
+        let project = cursor.finishing.project.clone();
+        let position = cursor.position.clone();
+        let cluster_id = cursor.cluster_id;
+        let target_replica = cursor.target_replica;
+        let max_result_size = cursor.max_result_size;
+        let max_returned_query_size = cursor.max_returned_query_size;
+        let database = cursor.database.clone();
+
+        let updates = updates
+            .into_iter()
+            .map(|update| self.catalog().state().resolve_builtin_table_update(update))
+            .collect();
+        self.builtin_table_update().background(updates);
+
+        // The execution of the cursor's `SELECT` accounts for the pages once they are computed.
+        let rows = match self
+            .implement_peek_plan(
+                &mut ExecuteContextExtra::default(),
+                peek,
+                finishing,
+                cluster_id,
+                target_replica,
+                max_result_size,
+                max_returned_query_size,
+                database,
+            )
+            .await
+        {
+            Ok(ExecuteResponse::SendingRows { future, .. }) => future,
+            Ok(_) => unreachable!("peeks of indexes send rows"),
+            Err(e) => {
+                let error = e.to_string();
+                if let Some(cursor) = self.paged_cursors.cursors.get_mut(&id) {
+                    cursor.failure = Some(StatementEndedExecutionReason::Errored {
+                        error: error.clone(),
+                    });
+                }
+                return send_error(tx, error);
+            }
+        };
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "paged_cursor_page", async move {
+            let (rows, page) = match rows.await {
+                PeekResponseUnary::Rows(mut rows) => {
+                    let mut peeked = Vec::new();
+                    while let Some(row) = rows.next() {
+                        peeked.push(row.to_owned());
+                    }
+                    let position = next_position(position, &peeked);
+                    let rows: Vec<_> = peeked
+                        .iter()
+                        .skip(skip)
+                        .map(|row| {
+                            let datums = row.unpack();
+                            Row::pack(project.iter().map(|column| datums[*column]))
+                        })
+                        .collect();
+                    let page = ComputedCursorPage {
+                        rows: rows.len(),
+                        position,
+                    };
+                    (
+                        PeekResponseUnary::Rows(Box::new(rows.into_row_iter())),
+                        Ok(page),
+                    )
+                }
+                PeekResponseUnary::Error(error) => (
+                    PeekResponseUnary::Error(error.clone()),
+                    Err(StatementEndedExecutionReason::Errored { error }),
+                ),
+                PeekResponseUnary::Canceled => (
+                    PeekResponseUnary::Canceled,
+                    Err(StatementEndedExecutionReason::Canceled),
+                ),
+            };
+            // The coordinator learns about the page before the client can request the next one.
+            if internal_cmd_tx
+                .send(Message::CursorPageComputed { id, page })
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+            // The client may have closed the cursor in the meantime.
+            let _ = tx.send(Some(CursorPage { rows, limit }));
+        });
+    }
+
+    /// Records the page of the paged cursor `id` that has been computed.
+    pub(crate) fn cursor_page_computed(
+        &mut self,
+        id: Uuid,
+        page: Result<ComputedCursorPage, StatementEndedExecutionReason>,
+    ) {
+        // The cursor may have been closed in the meantime.
+        let Some(cursor) = self.paged_cursors.cursors.get_mut(&id) else {
+            return;
+        };
+        match page {
+            Ok(ComputedCursorPage { rows, position }) => {
+                cursor.rows_returned = cursor.rows_returned.saturating_add(rows);
+                cursor.position = position;
+            }
+            Err(reason) => cursor.failure = Some(reason),
+        }
+    }
+
+    /// Closes the paged cursor `id`, which has been open for `max_cursor_lifetime`.
+    pub(crate) fn expire_paged_cursor(&mut self, id: Uuid) {
+        if let Some(cursor) = self.remove_paged_cursor(id) {
+            self.paged_cursors.expired.insert(id, cursor.name);
+        }
+    }
+
+    /// Closes the paged cursor `id`, whose client is done with it.
+    pub(crate) fn close_paged_cursor(&mut self, id: Uuid) {
+        self.remove_paged_cursor(id);
+        self.paged_cursors.expired.remove(&id);
+    }
+
+    /// Forgets the cursor that the connection `conn_id` was about to start fetching from.
+    pub(crate) fn forget_starting_paged_cursor(&mut self, conn_id: &ConnectionId) {
+        self.paged_cursors.starting.remove(conn_id);
+    }
+
+    /// Removes the paged cursor `id`, and ends the execution of its `SELECT`.
+    fn remove_paged_cursor(&mut self, id: Uuid) -> Option<PagedCursor> {
+        let mut cursor = self.paged_cursors.cursors.remove(&id)?;
+        let update = pack_cursor_update(id, &cursor, -1);
+        let update = self.catalog().state().resolve_builtin_table_update(update);
+        self.builtin_table_update().background(vec![update]);
+        let reason = cursor
+            .failure
+            .take()
+            .unwrap_or(StatementEndedExecutionReason::Success {
+                rows_returned: Some(u64::cast_from(cursor.rows_returned)),
+                execution_strategy: Some(StatementExecutionStrategy::FastPath),
+                max_result_memory_bytes: None,
+            });
+        self.retire_execution(reason, std::mem::take(&mut cursor.execution));
+        Some(cursor)
+    }
+}
+
+/// Returns a predicate that holds for the rows ordered by `order_by` at or after `row`.
+///
+/// Comparisons with `NULL` are `NULL`, so every column is explicitly tested for `NULL`, which
+/// `order_by` orders before or after all other values.
+fn at_or_after(order_by: &[ColumnOrder], column_types: &[ColumnType], row: &Row) -> MirScalarExpr {
+    let datums = row.unpack();
+    order_by
+        .iter()
+        .rev()
+        .fold(MirScalarExpr::literal_true(), |rest, order| {
+            let column = MirScalarExpr::column(order.column);
+            let is_null = column.clone().call_is_null();
+            let datum = datums[order.column];
+            let (after, equal) = if datum.is_null() {
+                let after = if order.nulls_last {
+                    MirScalarExpr::literal_false()
+                } else {
+                    is_null.clone().not()
+                };
+                (after, is_null)
+            } else {
+                let scalar_type = column_types[order.column].scalar_type.clone();
+                let value = MirScalarExpr::literal_ok(datum, scalar_type);
+                let func = if order.desc {
+                    BinaryFunc::Lt
+                } else {
+                    BinaryFunc::Gt
+                };
+                let beyond = column.clone().call_binary(value.clone(), func);
+                let after = if order.nulls_last {
+                    is_null.clone().or(beyond)
+                } else {
+                    is_null.clone().not().and(beyond)
+                };
+                let equal = is_null.not().and(column.call_binary(value, BinaryFunc::Eq));
+                (after, equal)
+            };
+            after.or(equal.and(rest))
+        })
+}
+
+/// Returns the last row returned by a paged cursor after a page whose peek returned `peeked`,
+/// given the last row returned before the page, whose copies the peek skipped.
+fn next_position(position: Option<CursorPosition>, peeked: &[Row]) -> Option<CursorPosition> {
+    let Some(last) = peeked.last() else {
+        return position;
+    };
+    let mut copies = peeked.iter().rev().take_while(|row| *row == last).count();
+    if let Some(position) = position {
+        if copies == peeked.len() && position.row == *last {
+            copies += position.copies;
+        }
+    }
+    Some(CursorPosition {
+        row: last.clone(),
+        copies,
+    })
+}
+
+/// Packs the row of `mz_cursors` that describes the paged cursor `id`.
+fn pack_cursor_update(
+    id: Uuid,
+    cursor: &PagedCursor,
+    diff: Diff,
+) -> BuiltinTableUpdate<&'static BuiltinTable> {
+    let pack_time = |time: EpochMillis| {
+        Datum::TimestampTz(mz_ore::now::to_datetime(time).try_into().expect("must fit"))
+    };
+    let timestamp = cursor
+        .peek
+        .determination
+        .timestamp_context
+        .timestamp_or_default();
+    BuiltinTableUpdate {
+        id: &*MZ_CURSORS,
+        row: Row::pack_slice(&[
+            Datum::String(&id.to_string()),
+            Datum::Uuid(cursor.session_id),
+            Datum::String(&cursor.name),
+            Datum::String(&cursor.cluster_id.to_string()),
+            Datum::String(&cursor.index_id.to_string()),
+            Datum::MzTimestamp(timestamp),
+            Datum::UInt64(u64::cast_from(cursor.pages_fetched)),
+            pack_time(cursor.opened_at),
+            pack_time(cursor.expires_at),
+        ]),
+        diff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use mz_expr::compare_columns;
+    use mz_repr::{RowArena, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_next_position() {
+        let row = |i: i64| Row::pack_slice(&[Datum::Int64(i)]);
+        let position = |i: i64, copies: usize| {
+            Some(CursorPosition {
+                row: row(i),
+                copies,
+            })
+        };
+
+        assert_eq!(next_position(None, &[]), None);
+        assert_eq!(next_position(position(1, 2), &[]), position(1, 2));
+        assert_eq!(
+            next_position(None, &[row(1), row(2), row(2)]),
+            position(2, 2)
+        );
+        // The copies of the previous last row count if the page only contains more of them.
+        assert_eq!(next_position(position(2, 2), &[row(2)]), position(2, 3));
+        assert_eq!(
+            next_position(position(2, 2), &[row(2), row(3)]),
+            position(3, 1)
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_at_or_after() {
+        let column_types = vec![
+            ScalarType::Int64.nullable(true),
+            ScalarType::Int64.nullable(true),
+        ];
+        let rows: Vec<_> = [
+            [Datum::Int64(1), Datum::Int64(1)],
+            [Datum::Int64(1), Datum::Null],
+            [Datum::Int64(2), Datum::Int64(1)],
+            [Datum::Int64(2), Datum::Int64(2)],
+            [Datum::Null, Datum::Int64(1)],
+            [Datum::Null, Datum::Null],
+        ]
+        .iter()
+        .map(|datums| Row::pack_slice(datums))
+        .collect();
+        let orders = [(false, true), (true, true), (false, false), (true, false)];
+        for (desc, nulls_last) in orders {
+            let order_by: Vec<_> = (0..2)
+                .map(|column| ColumnOrder {
+                    column,
+                    desc,
+                    nulls_last,
+                })
+                .collect();
+            let mut sorted = rows.clone();
+            sorted.sort_by(|a, b| {
+                compare_columns(&order_by, &a.unpack(), &b.unpack(), || Ordering::Equal)
+            });
+            for (i, position) in sorted.iter().enumerate() {
+                let predicate = at_or_after(&order_by, &column_types, position);
+                let arena = RowArena::new();
+                let matching: Vec<_> = sorted
+                    .iter()
+                    .filter(|row| {
+                        let datums = row.unpack();
+                        predicate.eval(&datums, &arena) == Ok(Datum::True)
+                    })
+                    .cloned()
+                    .collect();
+                assert_eq!(
+                    matching,
+                    sorted[i..],
+                    "desc: {desc}, nulls_last: {nulls_last}"
+                );
+            }
+        }
+    }
+}
@@ -15,11 +15,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::num::NonZeroUsize;
+use std::time::Instant;
 
 use differential_dataflow::consolidation::consolidate;
 use futures::TryFutureExt;
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::ENABLE_PEEK_DEDUPLICATION;
 use mz_cluster_client::ReplicaId;
 use mz_compute_client::protocol::command::PeekTarget;
 use mz_compute_client::protocol::response::PeekResponse;
@@ -35,13 +37,17 @@ use mz_ore::cast::CastFrom;
 use mz_ore::str::{separated, StrExt};
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::text::DisplayText;
-use mz_repr::explain::{CompactScalars, IndexUsageType, PlanRenderingContext, UsedIndexes};
+use mz_repr::explain::{
+    CompactScalars, ExprHumanizer, IndexUsageType, PlanRenderingContext, UsedIndexes,
+};
 use mz_repr::{Diff, GlobalId, IntoRowIterator, RelationType, Row, RowCollection, RowIterator};
 use serde::{Deserialize, Serialize};
 use timely::progress::Timestamp;
 use tokio::sync::oneshot;
 use uuid::Uuid;
 
+use crate::coord::conn_registry::ConnRegistryKey;
+use crate::coord::peek_dedup::PeekDedupKey;
 use crate::coord::timestamp_selection::TimestampDetermination;
 use crate::optimize::OptimizerError;
 use crate::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
@@ -61,6 +67,10 @@ pub(crate) struct PendingPeek {
     pub(crate) is_fast_path: bool,
     pub(crate) limit: Option<usize>,
     pub(crate) offset: usize,
+    /// The label of the current database of the session that issued the peek in the metrics.
+    pub(crate) database: Option<String>,
+    /// When the peek was issued.
+    pub(crate) issued_at: Instant,
 }
 
 /// The response from a `Peek`, with row multiplicities represented in unary.
@@ -255,7 +265,7 @@ impl<'a, T: 'a> DisplayText<PlanRenderingContext<'a, T>> for FastPathPlan {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PlannedPeek {
     pub plan: PeekPlan,
     pub determination: TimestampDetermination<mz_repr::Timestamp>,
@@ -274,7 +284,7 @@ pub enum PeekPlan<T = mz_repr::Timestamp> {
 
 /// Convert `mfp` to an executable, non-temporal plan.
 /// It should be non-temporal, as OneShot preparation populates `mz_now`.
-fn mfp_to_safe_plan(
+pub(crate) fn mfp_to_safe_plan(
     mfp: mz_expr::MapFilterProject,
 ) -> Result<mz_expr::SafeMfpPlan, OptimizerError> {
     mfp.into_plan()
@@ -294,17 +304,73 @@ fn permute_oneshot_mfp_around_index(
     Ok(safe_mfp)
 }
 
+/// The reason a peek that looks like it could be served without a dataflow needs one anyway.
+///
+/// This is only reported for peeks that narrowly miss a [`FastPathPlan`], i.e., that read a single
+/// collection and that a user would reasonably expect to be fast.
+#[derive(Clone, Debug)]
+pub enum SlowPathReason {
+    /// The query reads an index, but its top-k is not covered by the finishing, e.g., because it
+    /// orders by different columns, is grouped, or has a non-literal limit.
+    TopKNotCoveredByFinishing,
+    /// The query filters an unindexed collection, which can only be read directly from persist
+    /// when all of its rows are returned.
+    PersistFilter(GlobalId),
+    /// The query orders the rows of an unindexed collection.
+    PersistOrderBy(GlobalId),
+    /// The query's LIMIT plus OFFSET on an unindexed collection is not below
+    /// `persist_fast_path_limit`.
+    PersistLimit {
+        id: GlobalId,
+        limit: usize,
+        max: usize,
+    },
+}
+
+impl SlowPathReason {
+    /// Describes the reason, using `humanizer` to name the collections involved.
+    pub fn humanize(&self, humanizer: &dyn ExprHumanizer) -> String {
+        let name = |id: &GlobalId| {
+            humanizer
+                .humanize_id(*id)
+                .unwrap_or_else(|| id.to_string())
+                .quoted()
+                .to_string()
+        };
+        match self {
+            SlowPathReason::TopKNotCoveredByFinishing => {
+                "the query's ORDER BY and LIMIT do not cover its top-k computation".into()
+            }
+            SlowPathReason::PersistFilter(id) => format!(
+                "{} is not indexed in this cluster and the query filters its rows",
+                name(id)
+            ),
+            SlowPathReason::PersistOrderBy(id) => format!(
+                "{} is not indexed in this cluster and the query orders its rows",
+                name(id)
+            ),
+            SlowPathReason::PersistLimit { id, limit, max } => format!(
+                "{} is not indexed in this cluster and the query's LIMIT plus OFFSET ({limit}) \
+                 is not below persist_fast_path_limit ({max})",
+                name(id)
+            ),
+        }
+    }
+}
+
 /// Determine if the dataflow plan can be implemented without an actual dataflow.
 ///
 /// If the optimized plan is a `Constant` or a `Get` of a maintained arrangement,
 /// we can avoid building a dataflow (and either just return the results, or peek
 /// out of the arrangement, respectively).
+///
+/// If not, returns the [`SlowPathReason`] for plans that narrowly miss the fast path.
 pub fn create_fast_path_plan<T: Timestamp>(
     dataflow_plan: &mut DataflowDescription<OptimizedMirRelationExpr, (), T>,
     view_id: GlobalId,
     finishing: Option<&RowSetFinishing>,
     persist_fast_path_limit: usize,
-) -> Result<Option<FastPathPlan>, OptimizerError> {
+) -> Result<Result<FastPathPlan, Option<SlowPathReason>>, OptimizerError> {
     // At this point, `dataflow_plan` contains our best optimized dataflow.
     // We will check the plan to see if there is a fast path to escape full dataflow construction.
 
@@ -315,7 +381,7 @@ pub fn create_fast_path_plan<T: Timestamp>(
         let mut mir = &*dataflow_plan.objects_to_build[0].plan.as_inner_mut();
         if let Some((rows, ..)) = mir.as_const() {
             // In the case of a constant, we can return the result now.
-            return Ok(Some(FastPathPlan::Constant(
+            return Ok(Ok(FastPathPlan::Constant(
                 rows.clone()
                     .map(|rows| rows.into_iter().map(|(row, diff)| (row, diff)).collect()),
                 // For best accuracy, we need to recalculate typ.
@@ -334,6 +400,7 @@ pub fn create_fast_path_plan<T: Timestamp>(
                 expected_group_size: _,
             } = mir
             {
+                let mut covered = false;
                 if let Some(finishing) = finishing {
                     if group_key.is_empty() && *order_key == finishing.order_by && *offset == 0 {
                         // The following is roughly `limit >= finishing.limit`, but with Options.
@@ -348,11 +415,32 @@ pub fn create_fast_path_plan<T: Timestamp>(
                                 }
                             }
                         };
-                        if finishing_limits_at_least_as_topk {
-                            mir = input;
-                        }
+                        covered = finishing_limits_at_least_as_topk;
                     }
                 }
+                if covered {
+                    mir = input;
+                } else {
+                    // Without the TopK, the peek would have read from an index.
+                    let (_mfp, input) = mz_expr::MapFilterProject::extract_from_expression(input);
+                    let reads_index = match input {
+                        MirRelationExpr::Get {
+                            id: Id::Global(get_id),
+                            ..
+                        } => dataflow_plan
+                            .index_imports
+                            .values()
+                            .any(|import| import.desc.on_id == *get_id),
+                        MirRelationExpr::Join { implementation, .. } => matches!(
+                            implementation,
+                            mz_expr::JoinImplementation::IndexedFilter(..)
+                        ),
+                        _ => false,
+                    };
+                    return Ok(Err(
+                        reads_index.then_some(SlowPathReason::TopKNotCoveredByFinishing)
+                    ));
+                }
             }
             // In the case of a linear operator around an indexed view, we
             // can skip creating a dataflow and instead pull all the rows in
@@ -367,7 +455,7 @@ pub fn create_fast_path_plan<T: Timestamp>(
                     // Nothing to be done if an arrangement does not exist
                     for (index_id, IndexImport { desc, .. }) in dataflow_plan.index_imports.iter() {
                         if desc.on_id == *get_id {
-                            return Ok(Some(FastPathPlan::PeekExisting(
+                            return Ok(Ok(FastPathPlan::PeekExisting(
                                 *get_id,
                                 *index_id,
                                 None,
@@ -378,29 +466,37 @@ pub fn create_fast_path_plan<T: Timestamp>(
                     // If there is no arrangement, consider peeking the persist shard directly
                     let safe_mfp = mfp_to_safe_plan(mfp)?;
                     let (_m, filters, _p) = safe_mfp.as_map_filter_project();
-                    let small_finish = match &finishing {
-                        None => false,
-                        Some(RowSetFinishing {
-                            order_by,
+                    // Without a LIMIT, the query can't expect to avoid a dataflow.
+                    let Some(RowSetFinishing {
+                        order_by,
+                        limit: Some(limit),
+                        offset,
+                        ..
+                    }) = finishing
+                    else {
+                        return Ok(Err(None));
+                    };
+                    let limit = usize::cast_from(*limit) + *offset;
+                    let reason = if !filters.is_empty() {
+                        SlowPathReason::PersistFilter(*get_id)
+                    } else if !order_by.is_empty() {
+                        SlowPathReason::PersistOrderBy(*get_id)
+                    } else if limit >= persist_fast_path_limit {
+                        SlowPathReason::PersistLimit {
+                            id: *get_id,
                             limit,
-                            offset,
-                            ..
-                        }) => {
-                            order_by.is_empty()
-                                && limit.iter().any(|l| {
-                                    usize::cast_from(*l) + *offset < persist_fast_path_limit
-                                })
+                            max: persist_fast_path_limit,
                         }
+                    } else {
+                        return Ok(Ok(FastPathPlan::PeekPersist(*get_id, safe_mfp)));
                     };
-                    if filters.is_empty() && small_finish {
-                        return Ok(Some(FastPathPlan::PeekPersist(*get_id, safe_mfp)));
-                    }
+                    return Ok(Err(Some(reason)));
                 }
                 MirRelationExpr::Join { implementation, .. } => {
                     if let mz_expr::JoinImplementation::IndexedFilter(coll_id, idx_id, key, vals) =
                         implementation
                     {
-                        return Ok(Some(FastPathPlan::PeekExisting(
+                        return Ok(Ok(FastPathPlan::PeekExisting(
                             *coll_id,
                             *idx_id,
                             Some(vals.clone()),
@@ -413,7 +509,7 @@ pub fn create_fast_path_plan<T: Timestamp>(
             }
         }
     }
-    Ok(None)
+    Ok(Err(None))
 }
 
 impl FastPathPlan {
@@ -446,6 +542,7 @@ impl crate::coord::Coordinator {
         target_replica: Option<ReplicaId>,
         max_result_size: u64,
         max_returned_query_size: Option<u64>,
+        database: Option<String>,
     ) -> Result<crate::ExecuteResponse, AdapterError> {
         let PlannedPeek {
             plan: fast_path,
@@ -483,6 +580,8 @@ impl crate::coord::Coordinator {
                 }
             }
             let row_collection = RowCollection::new(&results);
+            let max_result_memory_bytes =
+                u64::cast_from(RowSetFinishing::required_memory(&row_collection));
             let duration_histogram = self.metrics.row_set_finishing_seconds();
 
             let (ret, reason) = match finishing.finish(
@@ -498,6 +597,7 @@ impl crate::coord::Coordinator {
                         StatementEndedExecutionReason::Success {
                             rows_returned: Some(rows_returned),
                             execution_strategy: Some(StatementExecutionStrategy::Constant),
+                            max_result_memory_bytes: Some(max_result_memory_bytes),
                         },
                     )
                 }
@@ -604,48 +704,68 @@ impl crate::coord::Coordinator {
         // Endpoints for sending and receiving peek responses.
         let (rows_tx, rows_rx) = tokio::sync::oneshot::channel();
 
-        // Generate unique UUID. Guaranteed to be unique to all pending peeks, there's an very
-        // small but unlikely chance that it's not unique to completed peeks.
-        let mut uuid = Uuid::new_v4();
-        while self.pending_peeks.contains_key(&uuid) {
-            uuid = Uuid::new_v4();
-        }
-
         // The peek is ready to go for both cases, fast and non-fast.
         // Stash the response mechanism, and broadcast dataflow construction.
-        self.pending_peeks.insert(
-            uuid,
+        let key = self.pending_peeks.insert(
+            conn_id.clone(),
             PendingPeek {
                 sender: rows_tx,
-                conn_id: conn_id.clone(),
+                conn_id,
                 cluster_id: compute_instance,
                 depends_on: source_ids,
                 ctx_extra: std::mem::take(ctx_extra),
                 is_fast_path,
                 limit: finishing.limit.map(|x| usize::cast_from(u64::from(x))),
                 offset: finishing.offset,
+                database,
+                issued_at: Instant::now(),
             },
         );
-        self.client_pending_peeks
-            .entry(conn_id)
-            .or_default()
-            .insert(uuid, compute_instance);
+        // The UUID is unique to all pending peeks, and as keys are never reused, to completed
+        // peeks as well.
+        let uuid = self.peek_uuid(key);
         let (id, literal_constraints, timestamp, map_filter_project) = peek_command;
 
-        self.controller
-            .compute
-            .peek(
-                compute_instance,
-                id,
-                literal_constraints,
-                uuid,
-                timestamp,
-                finishing.clone(),
-                map_filter_project,
-                target_replica,
-                peek_target,
-            )
-            .unwrap_or_terminate("cannot fail to peek");
+        let dedup_key = (is_fast_path
+            && ENABLE_PEEK_DEDUPLICATION.get(self.catalog().system_config().dyncfgs()))
+        .then(|| PeekDedupKey {
+            cluster_id: compute_instance,
+            target_replica,
+            timestamp,
+            collection_id: id,
+            literal_constraints: literal_constraints.clone(),
+            map_filter_project: map_filter_project.clone(),
+            finishing: finishing.clone(),
+        });
+        match dedup_key
+            .as_ref()
+            .and_then(|key| self.peek_dedup.in_flight(key))
+        {
+            // An identical peek is in flight, so we wait for its response instead of peeking.
+            Some(in_flight) => {
+                self.peek_dedup.join(in_flight, uuid);
+                self.metrics.deduplicated_peeks.inc();
+            }
+            None => {
+                if let Some(key) = dedup_key {
+                    self.peek_dedup.insert(key, uuid);
+                }
+                self.controller
+                    .compute
+                    .peek(
+                        compute_instance,
+                        id,
+                        literal_constraints,
+                        uuid,
+                        timestamp,
+                        finishing.clone(),
+                        map_filter_project,
+                        target_replica,
+                        peek_target,
+                    )
+                    .unwrap_or_terminate("cannot fail to peek");
+            }
+        }
         let duration_histogram = self.metrics.row_set_finishing_seconds();
 
         // Prepare the receiver to return as a response.
@@ -684,15 +804,19 @@ impl crate::coord::Coordinator {
     /// Cancel and remove all pending peeks that were initiated by the client with `conn_id`.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) fn cancel_pending_peeks(&mut self, conn_id: &ConnectionId) {
-        if let Some(uuids) = self.client_pending_peeks.remove(conn_id) {
+        let peeks = self.pending_peeks.remove_connection(conn_id);
+        if !peeks.is_empty() {
             self.metrics
                 .canceled_peeks
                 .with_label_values(&[])
-                .inc_by(u64::cast_from(uuids.len()));
+                .inc_by(u64::cast_from(peeks.len()));
 
             let mut inverse: BTreeMap<ComputeInstanceId, BTreeSet<Uuid>> = Default::default();
-            for (uuid, compute_instance) in &uuids {
-                inverse.entry(*compute_instance).or_default().insert(*uuid);
+            for (key, peek) in &peeks {
+                // Compute peeks that other connections still wait for keep running.
+                if let Some(uuid) = self.peek_dedup.leave(self.peek_uuid(*key)) {
+                    inverse.entry(peek.cluster_id).or_default().insert(uuid);
+                }
             }
             for (compute_instance, uuids) in inverse {
                 // It's possible that this compute instance no longer exists because it was dropped
@@ -704,11 +828,7 @@ impl crate::coord::Coordinator {
                 }
             }
 
-            let peeks = uuids
-                .iter()
-                .filter_map(|(uuid, _)| self.pending_peeks.remove(uuid))
-                .collect::<Vec<_>>();
-            for peek in peeks {
+            for (_, peek) in peeks {
                 self.retire_execution(StatementEndedExecutionReason::Canceled, peek.ctx_extra);
                 let _ = peek.sender.send(PeekResponse::Canceled);
             }
@@ -720,6 +840,23 @@ impl crate::coord::Coordinator {
         uuid: Uuid,
         response: PeekResponse,
         otel_ctx: OpenTelemetryContext,
+    ) {
+        // All the peeks that share the compute peek `uuid` receive its response.
+        let mut waiters = self.peek_dedup.complete(uuid);
+        let last = waiters
+            .pop()
+            .expect("compute peeks have at least one waiter");
+        for waiter in waiters {
+            self.forward_peek_response(waiter, response.clone(), &otel_ctx);
+        }
+        self.forward_peek_response(last, response, &otel_ctx);
+    }
+
+    fn forward_peek_response(
+        &mut self,
+        uuid: Uuid,
+        response: PeekResponse,
+        otel_ctx: &OpenTelemetryContext,
     ) {
         // We expect exactly one peek response, which we forward. Then we clean up the
         // peek's state in the coordinator.
@@ -732,11 +869,17 @@ impl crate::coord::Coordinator {
             is_fast_path,
             limit,
             offset,
+            database,
+            issued_at,
         }) = self.remove_pending_peek(&uuid)
         {
+            self.record_database_peek(database.as_deref(), issued_at.elapsed(), &response);
             let reason = match &response {
                 PeekResponse::Rows(r) => {
                     let rows_returned = r.count(offset, limit);
+                    // The response is buffered until it has been finished, which
+                    // additionally requires a sorted view over the rows.
+                    let max_result_memory_bytes = RowSetFinishing::required_memory(r);
                     StatementEndedExecutionReason::Success {
                         rows_returned: Some(u64::cast_from(rows_returned)),
                         execution_strategy: Some(if is_fast_path {
@@ -744,6 +887,7 @@ impl crate::coord::Coordinator {
                         } else {
                             StatementExecutionStrategy::Standard
                         }),
+                        max_result_memory_bytes: Some(u64::cast_from(max_result_memory_bytes)),
                     }
                 }
                 PeekResponse::Error(e) => {
@@ -763,18 +907,30 @@ impl crate::coord::Coordinator {
 
     /// Clean up a peek's state.
     pub(crate) fn remove_pending_peek(&mut self, uuid: &Uuid) -> Option<PendingPeek> {
-        let pending_peek = self.pending_peeks.remove(uuid);
-        if let Some(pending_peek) = &pending_peek {
-            let uuids = self
-                .client_pending_peeks
-                .get_mut(&pending_peek.conn_id)
-                .expect("coord peek state is inconsistent");
-            uuids.remove(uuid);
-            if uuids.is_empty() {
-                self.client_pending_peeks.remove(&pending_peek.conn_id);
-            }
-        }
-        pending_peek
+        let key = self.peek_key(uuid)?;
+        self.pending_peeks.remove(key)
+    }
+
+    /// Returns the UUID and the state of each pending peek.
+    pub(crate) fn pending_peeks(&self) -> impl Iterator<Item = (Uuid, &PendingPeek)> {
+        self.pending_peeks
+            .iter()
+            .map(|(key, _, peek)| (self.peek_uuid(key), peek))
+    }
+
+    /// Returns the UUID that identifies the pending peek with `key` to the compute controller.
+    ///
+    /// The UUIDs of the peeks of this coordinator share a random prefix, so that they don't
+    /// collide with the UUIDs of the peeks of previous coordinators.
+    fn peek_uuid(&self, key: ConnRegistryKey) -> Uuid {
+        Uuid::from_u64_pair(self.peek_uuid_prefix, key.to_u64())
+    }
+
+    /// Returns the key of the pending peek identified by `uuid`, the inverse of
+    /// [`Self::peek_uuid`].
+    fn peek_key(&self, uuid: &Uuid) -> Option<ConnRegistryKey> {
+        let (prefix, key) = uuid.as_u64_pair();
+        (prefix == self.peek_uuid_prefix).then(|| ConnRegistryKey::from_u64(key))
     }
 
     /// Constructs an [`ExecuteResponse`] that that will send some rows to the
@@ -0,0 +1,176 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Deduplication of concurrent identical peeks.
+//!
+//! The console and other tools often issue the same catalog and introspection queries from
+//! several sessions at once. With `enable_peek_deduplication`, a fast-path peek that is identical
+//! to a peek that is still in flight, i.e. it reads the same collection on the same cluster and
+//! replica at the same timestamp with the same MFP and finishing, doesn't issue another compute
+//! peek. It instead waits for the response of the peek in flight, which is fanned out to all of
+//! its waiters.
+//!
+//! Each waiter still has its own pending peek, so that it is retired, canceled, and attributed to
+//! its connection like any other peek. The compute peek is only canceled once all of its waiters
+//! are canceled.
+//!
+//! Slow-path peeks are not deduplicated, as each one installs its own transient dataflow.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_cluster_client::ReplicaId;
+use mz_compute_types::ComputeInstanceId;
+use mz_expr::{RowSetFinishing, SafeMfpPlan};
+use mz_repr::{GlobalId, Row, Timestamp};
+use uuid::Uuid;
+
+/// The parts of a fast-path peek that determine its response.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct PeekDedupKey {
+    pub(crate) cluster_id: ComputeInstanceId,
+    pub(crate) target_replica: Option<ReplicaId>,
+    pub(crate) timestamp: Timestamp,
+    /// The index or storage collection that the peek reads.
+    pub(crate) collection_id: GlobalId,
+    pub(crate) literal_constraints: Option<Vec<Row>>,
+    pub(crate) map_filter_project: SafeMfpPlan,
+    pub(crate) finishing: RowSetFinishing,
+}
+
+/// A compute peek that is shared by identical peeks.
+#[derive(Debug)]
+struct SharedPeek {
+    key: PeekDedupKey,
+    /// The UUIDs of the pending peeks that wait for the response of the compute peek.
+    waiters: BTreeSet<Uuid>,
+}
+
+/// The compute peeks in flight that identical peeks can share.
+#[derive(Debug, Default)]
+pub(crate) struct PeekDedup {
+    /// The UUID of the compute peek in flight for each key.
+    by_key: BTreeMap<PeekDedupKey, Uuid>,
+    /// The shared compute peeks, by the UUID of the compute peek.
+    shared: BTreeMap<Uuid, SharedPeek>,
+    /// The UUID of the compute peek that each waiter waits for.
+    waiting_for: BTreeMap<Uuid, Uuid>,
+}
+
+impl PeekDedup {
+    /// Returns the UUID of the compute peek in flight for `key`, if any.
+    pub(crate) fn in_flight(&self, key: &PeekDedupKey) -> Option<Uuid> {
+        self.by_key.get(key).copied()
+    }
+
+    /// Registers the compute peek `uuid`, issued for the pending peek with the same UUID, as
+    /// shareable by later peeks with the same `key`.
+    pub(crate) fn insert(&mut self, key: PeekDedupKey, uuid: Uuid) {
+        let prev = self.by_key.insert(key.clone(), uuid);
+        assert!(prev.is_none(), "peek for {key:?} already in flight");
+        self.shared.insert(
+            uuid,
+            SharedPeek {
+                key,
+                waiters: BTreeSet::from([uuid]),
+            },
+        );
+        self.waiting_for.insert(uuid, uuid);
+    }
+
+    /// Lets the pending peek `waiter` wait for the response of the compute peek `uuid`.
+    pub(crate) fn join(&mut self, uuid: Uuid, waiter: Uuid) {
+        let shared = self.shared.get_mut(&uuid).expect("peek in flight");
+        shared.waiters.insert(waiter);
+        self.waiting_for.insert(waiter, uuid);
+    }
+
+    /// Removes the compute peek `uuid`, whose response has arrived, and returns the UUIDs of the
+    /// pending peeks to forward the response to.
+    pub(crate) fn complete(&mut self, uuid: Uuid) -> Vec<Uuid> {
+        match self.shared.remove(&uuid) {
+            Some(SharedPeek { key, waiters }) => {
+                self.by_key.remove(&key);
+                for waiter in &waiters {
+                    self.waiting_for.remove(waiter);
+                }
+                waiters.into_iter().collect()
+            }
+            None => vec![uuid],
+        }
+    }
+
+    /// Removes the pending peek `waiter`, which no longer waits for a response, and returns the
+    /// UUID of the compute peek to cancel, unless other pending peeks still wait for it.
+    pub(crate) fn leave(&mut self, waiter: Uuid) -> Option<Uuid> {
+        let Some(uuid) = self.waiting_for.remove(&waiter) else {
+            return Some(waiter);
+        };
+        let shared = self.shared.get_mut(&uuid).expect("peek in flight");
+        shared.waiters.remove(&waiter);
+        if !shared.waiters.is_empty() {
+            return None;
+        }
+        let shared = self.shared.remove(&uuid).expect("peek in flight");
+        self.by_key.remove(&shared.key);
+        Some(uuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::MapFilterProject;
+
+    use super::*;
+
+    fn key(timestamp: u64) -> PeekDedupKey {
+        PeekDedupKey {
+            cluster_id: ComputeInstanceId::User(1),
+            target_replica: None,
+            timestamp: Timestamp::new(timestamp),
+            collection_id: GlobalId::User(1),
+            literal_constraints: None,
+            map_filter_project: MapFilterProject::new(1)
+                .into_plan()
+                .unwrap()
+                .into_nontemporal()
+                .unwrap(),
+            finishing: RowSetFinishing::trivial(1),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_peek_dedup() {
+        let [a, b, c] = [1, 2, 3].map(|n| Uuid::from_u64_pair(0, n));
+        let mut dedup = PeekDedup::default();
+
+        dedup.insert(key(1), a);
+        assert_eq!(dedup.in_flight(&key(1)), Some(a));
+        assert_eq!(dedup.in_flight(&key(2)), None);
+        dedup.join(a, b);
+        dedup.join(a, c);
+
+        // The compute peek keeps running while some of its waiters remain.
+        assert_eq!(dedup.leave(a), None);
+        assert_eq!(dedup.leave(b), None);
+        assert_eq!(dedup.in_flight(&key(1)), Some(a));
+        assert_eq!(dedup.complete(a), vec![c]);
+        assert_eq!(dedup.in_flight(&key(1)), None);
+
+        // The compute peek is canceled with its last waiter.
+        dedup.insert(key(1), a);
+        dedup.join(a, b);
+        assert_eq!(dedup.leave(b), None);
+        assert_eq!(dedup.leave(a), Some(a));
+        assert_eq!(dedup.in_flight(&key(1)), None);
+
+        // Peeks that aren't shared are forwarded and canceled themselves.
+        assert_eq!(dedup.complete(b), vec![b]);
+        assert_eq!(dedup.leave(b), Some(b));
+    }
+}
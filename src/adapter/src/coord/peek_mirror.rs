@@ -0,0 +1,312 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Mirroring of peeks to a second cluster.
+//!
+//! A `SELECT` with the `MIRROR TO CLUSTER` option is also run on the mirror cluster, at the same
+//! timestamp as on the cluster that serves it. Once both results are in, their hashes are
+//! compared and discrepancies are recorded in `mz_internal.mz_peek_mirror_discrepancies`. The
+//! client only ever receives the primary result and does not wait for the mirror, which allows
+//! shadow testing a cluster, e.g., one running an upgraded version, against real queries.
+
+use std::collections::BTreeSet;
+
+use mz_compute_types::ComputeInstanceId;
+use mz_expr::RowSetFinishing;
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_repr::optimize::OverrideFrom;
+use mz_repr::{Datum, GlobalId, IntoRowIterator, Row, RowIterator, Timestamp};
+use mz_sql::plan::HirRelationExpr;
+use mz_storage_client::controller::IntrospectionType;
+use mz_transform::EmptyStatisticsOracle;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::command::{ExecuteResponse, RowsFuture};
+use crate::coord::peek::{PeekResponseUnary, PlannedPeek};
+use crate::coord::timestamp_selection::TimestampDetermination;
+use crate::coord::{Coordinator, Message};
+use crate::error::AdapterError;
+use crate::optimize::{self, Optimize};
+use crate::session::Session;
+
+/// The outcome of a peek, in the form in which it is compared across clusters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeekOutcome {
+    /// The peek returned `count` rows, whose order-insensitive hash is `hash`.
+    Rows { count: u64, hash: String },
+    /// The peek failed with the error.
+    Error(String),
+    /// The peek was canceled.
+    Canceled,
+}
+
+impl PeekOutcome {
+    /// Observes the outcome of `response`, and returns a response that delivers the same rows.
+    fn observe(response: PeekResponseUnary) -> (PeekResponseUnary, PeekOutcome) {
+        match response {
+            PeekResponseUnary::Rows(mut rows) => {
+                let mut collected = Vec::with_capacity(rows.count());
+                while let Some(row) = rows.next() {
+                    collected.push(row.to_owned());
+                }
+                let outcome = PeekOutcome::Rows {
+                    count: u64::cast_from(collected.len()),
+                    hash: hash_rows(&collected),
+                };
+                let rows = Box::new(collected.into_row_iter());
+                (PeekResponseUnary::Rows(rows), outcome)
+            }
+            PeekResponseUnary::Error(e) => {
+                (PeekResponseUnary::Error(e.clone()), PeekOutcome::Error(e))
+            }
+            PeekResponseUnary::Canceled => (PeekResponseUnary::Canceled, PeekOutcome::Canceled),
+        }
+    }
+
+    /// Returns the row count, the hash and the error of the outcome, as recorded in
+    /// `mz_peek_mirror_discrepancies`.
+    fn datums(&self) -> [Datum<'_>; 3] {
+        match self {
+            PeekOutcome::Rows { count, hash } => {
+                [Datum::UInt64(*count), Datum::String(hash), Datum::Null]
+            }
+            PeekOutcome::Error(e) => [Datum::Null, Datum::Null, Datum::String(e)],
+            PeekOutcome::Canceled => [Datum::Null, Datum::Null, Datum::String("canceled")],
+        }
+    }
+}
+
+/// Hashes `rows` independently of their order, as the order of the rows of a query without an
+/// `ORDER BY` is arbitrary.
+fn hash_rows(rows: &[Row]) -> String {
+    let mut sorted: Vec<_> = rows.iter().collect();
+    sorted.sort();
+    let mut hasher = Sha256::new();
+    for row in sorted {
+        hasher.update(u64::cast_from(row.data().len()).to_le_bytes());
+        hasher.update(row.data());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// A peek whose result differed between its cluster and its mirror cluster.
+#[derive(Debug)]
+pub struct PeekMirrorDiscrepancy {
+    session_id: Uuid,
+    cluster_id: ComputeInstanceId,
+    mirror_cluster_id: ComputeInstanceId,
+    outcome: PeekOutcome,
+    mirror_outcome: PeekOutcome,
+}
+
+impl Coordinator {
+    /// Mirrors a peek of `source`, whose primary peek responds with `primary`, to the cluster
+    /// `mirror_cluster_id`.
+    ///
+    /// Returns a response that delivers the primary result. A failure to issue the mirror peek
+    /// never fails the query, but is recorded as a discrepancy.
+    pub(crate) async fn mirror_peek(
+        &mut self,
+        session: &Session,
+        primary: ExecuteResponse,
+        mirror_cluster_id: ComputeInstanceId,
+        source: HirRelationExpr,
+        finishing: RowSetFinishing,
+        source_ids: BTreeSet<GlobalId>,
+        determination: &TimestampDetermination<Timestamp>,
+        max_query_result_size: Option<u64>,
+    ) -> ExecuteResponse {
+        let (primary, instance_id, strategy) = match primary {
+            ExecuteResponse::SendingRows {
+                future,
+                instance_id,
+                strategy,
+            } => (future, instance_id, strategy),
+            // Constant queries do not run on a cluster, so there is nothing to compare.
+            resp => return resp,
+        };
+
+        let mirror: RowsFuture = match self
+            .issue_mirror_peek(
+                session,
+                mirror_cluster_id,
+                source,
+                finishing,
+                source_ids,
+                determination,
+                max_query_result_size,
+            )
+            .await
+        {
+            Ok(mirror) => mirror,
+            Err(e) => Box::pin(std::future::ready(PeekResponseUnary::Error(e.to_string()))),
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let session_id = session.uuid();
+        let future = Box::pin(async move {
+            let (response, outcome) = PeekOutcome::observe(primary.await);
+            task::spawn(|| "peek_mirror_compare", async move {
+                let (_, mirror_outcome) = PeekOutcome::observe(mirror.await);
+                let canceled =
+                    outcome == PeekOutcome::Canceled || mirror_outcome == PeekOutcome::Canceled;
+                if !canceled && outcome != mirror_outcome {
+                    // It is not an error if the coordinator has shut down.
+                    let _ = internal_cmd_tx.send(Message::PeekMirrorDiscrepancy(
+                        PeekMirrorDiscrepancy {
+                            session_id,
+                            cluster_id: instance_id,
+                            mirror_cluster_id,
+                            outcome,
+                            mirror_outcome,
+                        },
+                    ));
+                }
+            });
+            response
+        });
+        ExecuteResponse::SendingRows {
+            future,
+            instance_id,
+            strategy,
+        }
+    }
+
+    /// Optimizes `source` for the cluster `mirror_cluster_id` and peeks it at the timestamp of
+    /// `determination`.
+    ///
+    /// The query is optimized on the coordinator's thread, which is acceptable for a facility
+    /// that is only used for testing.
+    async fn issue_mirror_peek(
+        &mut self,
+        session: &Session,
+        mirror_cluster_id: ComputeInstanceId,
+        source: HirRelationExpr,
+        finishing: RowSetFinishing,
+        source_ids: BTreeSet<GlobalId>,
+        determination: &TimestampDetermination<Timestamp>,
+        max_query_result_size: Option<u64>,
+    ) -> Result<RowsFuture, AdapterError> {
+        let Some(cluster) = self.catalog().try_get_cluster(mirror_cluster_id) else {
+            return Err(AdapterError::Internal(format!(
+                "mirror cluster {mirror_cluster_id} was dropped"
+            )));
+        };
+        let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
+            .override_from(&cluster.config.features())
+            .override_from(session.vars().optimizer_feature_flags());
+        let compute_instance = self
+            .instance_snapshot(mirror_cluster_id)
+            .expect("compute instance does not exist");
+
+        // The primary peek only holds back the collections it reads, so the mirror cluster's
+        // collections might not be readable at the timestamp anymore. The read holds need to
+        // last until the peek has been issued, after which the controller holds them.
+        let timestamp = determination.timestamp_context.timestamp_or_default();
+        let id_bundle = self
+            .dataflow_builder(mirror_cluster_id)
+            .sufficient_collections(&source_ids);
+        let read_holds = self.acquire_read_holds(&id_bundle);
+        if !read_holds.least_valid_read().less_equal(&timestamp) {
+            return Err(AdapterError::Internal(format!(
+                "mirror cluster {mirror_cluster_id} cannot read at {timestamp}"
+            )));
+        }
+
+        let view_id = self.allocate_transient_id();
+        let index_id = self.allocate_transient_id();
+        let mut optimizer = optimize::peek::Optimizer::new(
+            self.owned_catalog(),
+            compute_instance,
+            finishing.clone(),
+            view_id,
+            index_id,
+            optimizer_config,
+            self.optimizer_metrics(),
+        );
+        let local_mir_plan = optimizer.catch_unwind_optimize(source)?;
+        let local_mir_plan = local_mir_plan.resolve(
+            determination.timestamp_context.clone(),
+            &session.meta(),
+            Box::new(EmptyStatisticsOracle),
+        );
+        let global_lir_plan = optimizer.catch_unwind_optimize(local_mir_plan)?;
+        let (peek_plan, _df_meta, typ) = global_lir_plan.unapply();
+
+        let planned_peek = PlannedPeek {
+            plan: peek_plan,
+            determination: determination.clone(),
+            conn_id: session.conn_id().clone(),
+            source_arity: typ.arity(),
+            source_ids,
+        };
+        let max_result_size = self.catalog().system_config().max_result_size();
+        let database = self.database_metrics_label(session);
+        // The mirror peek is not a statement of its own, so it is not logged.
+        let resp = self
+            .implement_peek_plan(
+                &mut Default::default(),
+                planned_peek,
+                finishing,
+                mirror_cluster_id,
+                None,
+                max_result_size,
+                max_query_result_size,
+                database,
+            )
+            .await?;
+        drop(read_holds);
+
+        match resp {
+            ExecuteResponse::SendingRows { future, .. } => Ok(future),
+            ExecuteResponse::SendingRowsImmediate { rows } => {
+                Ok(Box::pin(std::future::ready(PeekResponseUnary::Rows(rows))))
+            }
+            _ => unreachable!("peeks respond with rows"),
+        }
+    }
+
+    /// Appends a discrepancy between a peek and its mirror to `mz_peek_mirror_discrepancies`.
+    pub(crate) async fn record_peek_mirror_discrepancy(
+        &mut self,
+        discrepancy: PeekMirrorDiscrepancy,
+    ) {
+        tracing::warn!(?discrepancy, "peek mirror discrepancy");
+
+        let PeekMirrorDiscrepancy {
+            session_id,
+            cluster_id,
+            mirror_cluster_id,
+            outcome,
+            mirror_outcome,
+        } = discrepancy;
+        let occurred_at = mz_ore::now::to_datetime((self.catalog().config().now)());
+        let [row_count, result_hash, error] = outcome.datums();
+        let [mirror_row_count, mirror_result_hash, mirror_error] = mirror_outcome.datums();
+        let row = Row::pack_slice(&[
+            Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+            Datum::Uuid(session_id),
+            Datum::String(&cluster_id.to_string()),
+            Datum::String(&mirror_cluster_id.to_string()),
+            row_count,
+            mirror_row_count,
+            result_hash,
+            mirror_result_hash,
+            error,
+            mirror_error,
+        ]);
+        self.append_introspection_updates(
+            IntrospectionType::PeekMirrorDiscrepancies,
+            vec![(row, 1)],
+        )
+        .await;
+    }
+}
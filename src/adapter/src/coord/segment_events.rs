@@ -0,0 +1,105 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Batching of frequent Segment events.
+//!
+//! Cluster status changes are reported once per process, so rollouts of large clusters produce
+//! bursts of events. Instead of sending them right away, the coordinator buffers them in a
+//! [`SegmentEventBatcher`] and sends the deduplicated batch every `segment_event_batch_interval`,
+//! dropping the events beyond `segment_event_batch_limit`.
+
+use mz_adapter_types::dyncfgs::{SEGMENT_EVENT_BATCH_INTERVAL, SEGMENT_EVENT_BATCH_LIMIT};
+use mz_controller::clusters::ProcessId;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+
+use crate::coord::{Coordinator, Message};
+use crate::telemetry::{BufferedEvent, EventDetails, SegmentClientExt, SegmentEventBatcher};
+
+/// The Segment events buffered by the coordinator.
+#[derive(Debug, Default)]
+pub(crate) struct SegmentEvents {
+    /// The status changes of cluster processes.
+    cluster_events: SegmentEventBatcher<(ClusterId, ReplicaId, ProcessId)>,
+    /// Whether a flush of the buffered events is scheduled.
+    flush_scheduled: bool,
+}
+
+impl Coordinator {
+    /// Buffers the status change `event` of the process `process_id` of the replica
+    /// `replica_id`.
+    pub(crate) fn buffer_cluster_segment_event(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        process_id: ProcessId,
+        event: BufferedEvent,
+    ) {
+        let duplicates = self
+            .segment_events
+            .cluster_events
+            .push((cluster_id, replica_id, process_id), event);
+        self.metrics
+            .segment_events_dropped
+            .with_label_values(&["duplicate"])
+            .inc_by(u64::cast_from(duplicates));
+
+        let interval = SEGMENT_EVENT_BATCH_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        if interval.is_zero() {
+            self.flush_segment_events();
+        } else if !self.segment_events.flush_scheduled
+            && !self.segment_events.cluster_events.is_empty()
+        {
+            self.segment_events.flush_scheduled = true;
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(|| "segment_events_flush", async move {
+                tokio::time::sleep(interval).await;
+                if internal_cmd_tx.send(Message::FlushSegmentEvents).is_err() {
+                    // If sending fails, the main thread has shutdown.
+                }
+            });
+        }
+    }
+
+    /// Sends the buffered Segment events, up to `segment_event_batch_limit` of them.
+    pub(crate) fn flush_segment_events(&mut self) {
+        self.segment_events.flush_scheduled = false;
+        let limit = SEGMENT_EVENT_BATCH_LIMIT.get(self.catalog().system_config().dyncfgs());
+        let flushed = self.segment_events.cluster_events.flush(limit);
+        self.metrics
+            .segment_events_dropped
+            .with_label_values(&["rate_limited"])
+            .inc_by(u64::cast_from(flushed.rate_limited));
+
+        let Some(segment_client) = &self.segment_client else {
+            return;
+        };
+        let env_id = &self.catalog().config().environment_id;
+        for event in flushed.events {
+            segment_client.environment_track(
+                env_id,
+                event.event,
+                event.properties,
+                EventDetails {
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            );
+            self.metrics.segment_events_sent.inc();
+        }
+    }
+
+    /// Forgets the buffered Segment events of the replica `replica_id`.
+    pub(crate) fn forget_replica_segment_events(&mut self, replica_id: ReplicaId) {
+        self.segment_events
+            .cluster_events
+            .retain(|(_, id, _)| *id != replica_id);
+    }
+}
@@ -62,6 +62,8 @@ mod alter_set_cluster;
 mod cluster;
 mod inner;
 
+pub(crate) use cluster::{InsufficientReplicaCapacity, ReplicaCreation};
+
 impl Coordinator {
     /// BOXED FUTURE: As of Nov 2023 the returned Future from this function was 34KB. This would
     /// get stored on the stack which is bad for runtime performance, and blow up our stack usage.
@@ -81,6 +83,19 @@ impl Coordinator {
                 return;
             }
 
+            if self.catalog().system_config().read_only_ddl() && !plan.allowed_with_read_only_ddl()
+            {
+                let name = plan.name().to_string();
+                ctx.retire(Err(AdapterError::ReadOnlyDdl(name)));
+                return;
+            }
+
+            // Changes to the catalog need a write timestamp from the timestamp oracle.
+            if !plan.allowed_with_read_only_ddl() && self.timestamp_oracle_unavailable() {
+                ctx.retire(Err(AdapterError::TimestampOracleUnavailable));
+                return;
+            }
+
             // Scope the borrow of the Catalog because we need to mutate the Coordinator state below.
             let target_cluster = match ctx.session().transaction().cluster() {
                 // Use the current transaction's cluster.
@@ -95,6 +110,15 @@ impl Coordinator {
                     )
                 }
             };
+            // Queries fail over to the next cluster in the session's cluster failover list if
+            // the preferred cluster isn't live.
+            let target_cluster = match (target_cluster, &plan) {
+                (
+                    TargetCluster::Active,
+                    Plan::Select(_) | Plan::Subscribe(_) | Plan::CopyTo(_) | Plan::ShowColumns(_),
+                ) => self.fail_over_active_cluster(ctx.session()),
+                (target_cluster, _) => target_cluster,
+            };
             let (target_cluster_id, target_cluster_name) = match self
                 .catalog()
                 .resolve_target_cluster(target_cluster, ctx.session())
@@ -138,6 +162,16 @@ impl Coordinator {
                 return ctx.retire(Err(e.into()));
             }
 
+            let (mut ctx, plan, resolved_ids) = match (target_cluster_id, &plan) {
+                (Some(cluster_id), Plan::Subscribe(_) | Plan::CopyTo(_)) => {
+                    match self.admit_compute_sink(cluster_id, ctx, plan, resolved_ids) {
+                        Some(admitted) => admitted,
+                        None => return,
+                    }
+                }
+                _ => (ctx, plan, resolved_ids),
+            };
+
             match plan {
                 Plan::CreateSource(plan) => {
                     let source_id =
@@ -184,14 +218,25 @@ impl Coordinator {
                     ctx.retire(result);
                 }
                 Plan::CreateCluster(plan) => {
-                    let result = self.sequence_create_cluster(ctx.session(), plan).await;
-                    ctx.retire(result);
+                    match self.sequence_create_cluster(ctx.session(), plan).await {
+                        Ok(creation) => {
+                            self.create_replicas_after_capacity_check(ctx, creation)
+                                .await
+                        }
+                        Err(e) => ctx.retire(Err(e)),
+                    }
                 }
                 Plan::CreateClusterReplica(plan) => {
-                    let result = self
+                    match self
                         .sequence_create_cluster_replica(ctx.session(), plan)
-                        .await;
-                    ctx.retire(result);
+                        .await
+                    {
+                        Ok(creation) => {
+                            self.create_replicas_after_capacity_check(ctx, creation)
+                                .await
+                        }
+                        Err(e) => ctx.retire(Err(e)),
+                    }
                 }
                 Plan::CreateTable(plan) => {
                     let result = self
@@ -229,8 +274,7 @@ impl Coordinator {
                     self.sequence_copy_to(ctx, plan, target_cluster).await;
                 }
                 Plan::DropObjects(plan) => {
-                    let result = self.sequence_drop_objects(ctx.session_mut(), plan).await;
-                    ctx.retire(result);
+                    self.sequence_drop_objects(ctx, plan).await;
                 }
                 Plan::DropOwned(plan) => {
                     let result = self.sequence_drop_owned(ctx.session_mut(), plan).await;
@@ -247,6 +291,10 @@ impl Coordinator {
                     let result = self.sequence_show_variable(ctx.session(), plan);
                     ctx.retire(result);
                 }
+                Plan::ShowTransactionWrites => {
+                    let result = self.sequence_show_transaction_writes(ctx.session());
+                    ctx.retire(result);
+                }
                 Plan::InspectShard(plan) => {
                     // TODO: Ideally, this await would happen off the main thread.
                     let result = self.sequence_inspect_shard(ctx.session(), plan).await;
@@ -362,6 +410,9 @@ impl Coordinator {
                     self.sequence_explain_pushdown(ctx, plan, target_cluster)
                         .await;
                 }
+                Plan::ExplainAnalyze(plan) => {
+                    self.sequence_explain_analyze(ctx, plan).await;
+                }
                 Plan::ExplainSinkSchema(plan) => {
                     let result = self.sequence_explain_schema(plan);
                     ctx.retire(result);
@@ -424,6 +475,10 @@ impl Coordinator {
                     // planning.
                     ctx.retire(Err(AdapterError::Unsupported("ALTER ... SWAP ...")));
                 }
+                Plan::AlterDatabase(plan) => {
+                    let result = self.sequence_alter_database(ctx.session_mut(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::AlterSchemaRename(plan) => {
                     let result = self
                         .sequence_alter_schema_rename(ctx.session_mut(), plan)
@@ -446,6 +501,17 @@ impl Coordinator {
                 Plan::AlterSink(plan) => {
                     self.sequence_alter_sink_prepare(ctx, plan).await;
                 }
+                Plan::AlterSource(plan::AlterSourcePlan {
+                    id,
+                    action:
+                        plan::AlterSourceAction::DropSubsourceExports {
+                            subsources,
+                            drop_ids,
+                        },
+                }) => {
+                    self.sequence_alter_source_drop_subsources(ctx, id, subsources, drop_ids)
+                        .await;
+                }
                 Plan::AlterSource(plan) => {
                     let result = self.sequence_alter_source(ctx.session_mut(), plan).await;
                     ctx.retire(result);
@@ -468,6 +534,19 @@ impl Coordinator {
                     let result = self.sequence_alter_table(ctx.session(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::AlterTableRowCountHint(plan) => {
+                    let result = self
+                        .sequence_alter_table_row_count_hint(ctx.session(), plan)
+                        .await;
+                    ctx.retire(result);
+                }
+                Plan::CancelQuery(plan) => {
+                    self.sequence_cancel_query(ctx, plan).await;
+                }
+                Plan::AlterSystemTerminateSessions(plan) => {
+                    self.sequence_alter_system_terminate_sessions(ctx, plan)
+                        .await;
+                }
                 Plan::DiscardTemp => {
                     self.drop_temp_items(ctx.session().conn_id()).await;
                     ctx.retire(Ok(ExecuteResponse::DiscardedTemp));
@@ -492,12 +571,29 @@ impl Coordinator {
                     name,
                     count,
                     timeout,
+                    progress,
                 }) => {
+                    // Only cursors over `SUBSCRIBE ... WITH (PROGRESS)` have progress messages.
+                    let missing_progress = progress
+                        && ctx
+                            .session()
+                            .get_portal_unverified(&name)
+                            .and_then(|portal| portal.desc.relation_desc.as_ref())
+                            .map_or(false, |desc| {
+                                desc.get_by_name(&"mz_progressed".into()).is_none()
+                            });
+                    if missing_progress {
+                        ctx.retire(Err(AdapterError::Unsupported(
+                            "FETCH WITH (PROGRESS) from cursors without progress messages",
+                        )));
+                        return;
+                    }
                     let ctx_extra = std::mem::take(ctx.extra_mut());
                     ctx.retire(Ok(ExecuteResponse::Fetch {
                         name,
                         count,
                         timeout,
+                        progress,
                         ctx_extra,
                     }));
                 }
@@ -508,6 +604,14 @@ impl Coordinator {
                         ctx.retire(Err(AdapterError::UnknownCursor(plan.name)));
                     }
                 }
+                Plan::HandoffCursor(plan) => {
+                    let result = self.sequence_handoff_cursor(ctx.session_mut(), plan);
+                    ctx.retire(result);
+                }
+                Plan::ClaimHandoff(plan) => {
+                    let result = self.sequence_claim_handoff(ctx.session_mut(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::Prepare(plan) => {
                     if ctx
                         .session()
@@ -599,19 +703,23 @@ impl Coordinator {
                     ctx.retire(result);
                 }
                 Plan::ValidateConnection(plan) => {
+                    let hosts = self.external_hosts([plan.id]);
                     let connection = plan
                         .connection
                         .into_inline_connection(self.catalog().state());
                     let current_storage_configuration = self.controller.storage.config().clone();
-                    mz_ore::task::spawn(|| "coord::validate_connection", async move {
-                        let res = match connection
-                            .validate(plan.id, &current_storage_configuration)
-                            .await
-                        {
-                            Ok(()) => Ok(ExecuteResponse::ValidatedConnection),
-                            Err(err) => Err(err.into()),
-                        };
-                        ctx.retire(res);
+                    self.spawn_external_task("validate_connection", hosts, ctx, move |ctx| {
+                        async move {
+                            let res = match connection
+                                .validate(plan.id, &current_storage_configuration)
+                                .await
+                            {
+                                Ok(()) => Ok(ExecuteResponse::ValidatedConnection),
+                                Err(err) => Err(err.into()),
+                            };
+                            ctx.retire(res);
+                        }
+                        .boxed()
                     });
                 }
             }
@@ -723,6 +831,7 @@ impl Coordinator {
         session: &mut Session,
         id: GlobalId,
         constants: MirRelationExpr,
+        dry_run: bool,
     ) -> Result<ExecuteResponse, AdapterError> {
         // Insert can be queued, so we need to re-verify the id exists.
         let desc = match catalog.try_get_entry(&id) {
@@ -752,6 +861,7 @@ impl Coordinator {
                     kind: MutationKind::Insert,
                     returning: Vec::new(),
                     max_result_size: catalog.system_config().max_result_size(),
+                    dry_run,
                 };
                 Self::send_diffs(session, diffs_plan)
             }
@@ -808,10 +918,16 @@ impl Coordinator {
             returning = plan.returning.len(),
         );
 
-        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
-            id: plan.id,
-            rows: plan.updates,
-        }]))?;
+        if plan.dry_run {
+            // The updates have been validated, so all that is left for a dry run is to report
+            // the rows that the write would have affected.
+            session.add_notice(AdapterNotice::WriteDryRun);
+        } else {
+            session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+                id: plan.id,
+                rows: plan.updates,
+            }]))?;
+        }
         if !plan.returning.is_empty() {
             let finishing = RowSetFinishing {
                 order_by: Vec::new(),
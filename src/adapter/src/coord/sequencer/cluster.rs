@@ -10,8 +10,12 @@
 //! Coordinator functionality to sequence cluster-related plans
 
 use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
 
 use mz_adapter_types::compaction::CompactionWindow;
+use mz_adapter_types::dyncfgs::{
+    REPLICA_CAPACITY_CHECK, REPLICA_CAPACITY_CHECK_TIMEOUT, REPLICA_CAPACITY_QUEUE_TIMEOUT,
+};
 use mz_catalog::memory::objects::{ClusterConfig, ClusterVariant, ClusterVariantManaged};
 use mz_compute_client::controller::ComputeReplicaConfig;
 use mz_controller::clusters::{
@@ -19,7 +23,9 @@ use mz_controller::clusters::{
     ReplicaLogging,
 };
 use mz_controller_types::{ClusterId, ReplicaId};
+use mz_orchestrator::ServiceCapacity;
 use mz_ore::cast::CastFrom;
+use mz_ore::task;
 use mz_repr::role_id::RoleId;
 use mz_sql::catalog::{CatalogCluster, ObjectType};
 use mz_sql::plan::{
@@ -30,13 +36,57 @@ use mz_sql::plan::{
 };
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
+use tracing::warn;
 
 use crate::catalog::{Op, ReplicaCreateDropReason};
-use crate::coord::Coordinator;
+use crate::coord::{Coordinator, Message};
 use crate::session::Session;
-use crate::{catalog, AdapterError, ExecuteResponse};
+use crate::{catalog, AdapterError, AdapterNotice, ExecuteContext, ExecuteResponse};
+
+/// How often the creation of cluster replicas that wait for capacity checks it again.
+const REPLICA_CAPACITY_QUEUE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A cluster, or cluster replicas, whose creation waits for the capacity check of the replicas.
+#[derive(Debug)]
+pub(crate) struct ReplicaCreation {
+    /// The catalog operations that create the cluster or replicas.
+    ops: Vec<catalog::Op>,
+    /// What the operations create.
+    target: ReplicaCreationTarget,
+    /// When the creation started to wait for capacity, if it does.
+    queued_since: Option<Instant>,
+}
+
+impl ReplicaCreation {
+    fn new(ops: Vec<catalog::Op>, target: ReplicaCreationTarget) -> Self {
+        ReplicaCreation {
+            ops,
+            target,
+            queued_since: None,
+        }
+    }
+}
+
+/// What a [`ReplicaCreation`] creates.
+#[derive(Debug)]
+enum ReplicaCreationTarget {
+    /// A cluster with its replicas.
+    Cluster(ClusterId),
+    /// A replica of an existing cluster.
+    Replica(ClusterId, ReplicaId),
+}
+
+/// The capacity that the orchestrator lacks for the replicas of one size.
+#[derive(Debug, Clone)]
+pub(crate) struct InsufficientReplicaCapacity {
+    size: String,
+    schedulable: u16,
+    required: u16,
+}
 
 impl Coordinator {
+    /// Prepares the creation of a cluster, which
+    /// [`Coordinator::create_replicas_after_capacity_check`] completes.
     #[mz_ore::instrument(level = "debug")]
     pub(super) async fn sequence_create_cluster(
         &mut self,
@@ -46,7 +96,7 @@ impl Coordinator {
             variant,
             workload_class,
         }: CreateClusterPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<ReplicaCreation, AdapterError> {
         tracing::debug!("sequence_create_cluster");
 
         let id = self.catalog_mut().allocate_user_cluster_id().await?;
@@ -116,7 +166,7 @@ impl Coordinator {
         }: CreateClusterManagedPlan,
         cluster_id: ClusterId,
         mut ops: Vec<catalog::Op>,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<ReplicaCreation, AdapterError> {
         tracing::debug!("sequence_create_managed_cluster");
 
         self.ensure_valid_azs(availability_zones.iter())?;
@@ -161,11 +211,10 @@ impl Coordinator {
             )?;
         }
 
-        self.catalog_transact(Some(session), ops).await?;
-
-        self.create_cluster(cluster_id).await;
-
-        Ok(ExecuteResponse::CreatedCluster)
+        Ok(ReplicaCreation::new(
+            ops,
+            ReplicaCreationTarget::Cluster(cluster_id),
+        ))
     }
 
     fn create_managed_cluster_replica_op(
@@ -221,6 +270,188 @@ impl Coordinator {
         Ok(())
     }
 
+    /// Creates the cluster or replicas of `creation` and retires `ctx`, once the orchestrator has
+    /// the capacity to run the replicas, as configured by `replica_capacity_check`. If it doesn't,
+    /// either fails, creates the replicas anyway and notifies the session that they will wait for
+    /// capacity, or waits for capacity.
+    ///
+    /// The orchestrator is asked off the coordinator's task. Replicas at the same location compete
+    /// for the same capacity, so they are checked together. The check is best-effort: if the
+    /// orchestrator cannot determine its capacity in time, the replicas are created without it.
+    pub(super) async fn create_replicas_after_capacity_check(
+        &mut self,
+        ctx: ExecuteContext,
+        creation: ReplicaCreation,
+    ) {
+        if REPLICA_CAPACITY_CHECK.get(self.catalog().system_config().dyncfgs()) == "off" {
+            let result = self.create_replicas(ctx.session(), creation).await;
+            ctx.retire(result);
+            return;
+        }
+        self.spawn_replica_capacity_check(ctx, creation, Duration::ZERO);
+    }
+
+    /// Spawns a task that checks the capacity for the replicas of `creation` after `delay`.
+    fn spawn_replica_capacity_check(
+        &self,
+        ctx: ExecuteContext,
+        creation: ReplicaCreation,
+        delay: Duration,
+    ) {
+        let mut locations: Vec<(&ManagedReplicaLocation, u16)> = Vec::new();
+        for op in &creation.ops {
+            let catalog::Op::CreateClusterReplica {
+                config:
+                    ReplicaConfig {
+                        location: ReplicaLocation::Managed(location),
+                        ..
+                    },
+                ..
+            } = op
+            else {
+                continue;
+            };
+            match locations.iter_mut().find(|(l, _)| *l == location) {
+                Some((_, count)) => *count = count.saturating_add(1),
+                None => locations.push((location, 1)),
+            }
+        }
+        let checks: Vec<_> = locations
+            .into_iter()
+            .map(|(location, count)| {
+                let check = self.controller.replica_capacity_check(location, count);
+                let required = location.allocation.scale.saturating_mul(count);
+                (location.size.clone(), required, check)
+            })
+            .collect();
+
+        let timeout = REPLICA_CAPACITY_CHECK_TIMEOUT.get(self.catalog().system_config().dyncfgs());
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "replica_capacity_check", async move {
+            tokio::time::sleep(delay).await;
+            let mut insufficient = Vec::new();
+            for (size, required, check) in checks {
+                match tokio::time::timeout(timeout, check.check()).await {
+                    Ok(Ok(ServiceCapacity::Insufficient { schedulable })) => {
+                        insufficient.push(InsufficientReplicaCapacity {
+                            size,
+                            schedulable,
+                            required,
+                        });
+                    }
+                    Ok(Ok(ServiceCapacity::Available | ServiceCapacity::Unknown)) => {}
+                    Ok(Err(e)) => warn!(%size, "skipping replica capacity check: {e:#}"),
+                    Err(_) => warn!(%size, "replica capacity check timed out"),
+                }
+            }
+            let result = internal_cmd_tx.send(Message::ReplicaCapacityChecked {
+                ctx,
+                creation,
+                insufficient,
+            });
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Creates the cluster or replicas of `creation`, fails, or checks their capacity again,
+    /// depending on the `insufficient` capacity that the orchestrator reported for them.
+    pub(crate) async fn handle_replica_capacity_checked(
+        &mut self,
+        ctx: ExecuteContext,
+        mut creation: ReplicaCreation,
+        insufficient: Vec<InsufficientReplicaCapacity>,
+    ) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let mode = REPLICA_CAPACITY_CHECK.get(dyncfgs);
+        let queue_timeout = REPLICA_CAPACITY_QUEUE_TIMEOUT.get(dyncfgs);
+        let Some(first) = insufficient.first() else {
+            let result = self.create_replicas(ctx.session(), creation).await;
+            ctx.retire(result);
+            return;
+        };
+
+        match mode {
+            "error" => {
+                let InsufficientReplicaCapacity {
+                    size,
+                    schedulable,
+                    required,
+                } = first.clone();
+                ctx.retire(Err(AdapterError::InsufficientReplicaCapacity {
+                    size,
+                    schedulable,
+                    required,
+                }));
+            }
+            "queue" => {
+                let queued_since = *creation.queued_since.get_or_insert_with(|| {
+                    for i in &insufficient {
+                        ctx.session()
+                            .add_progress_notice(AdapterNotice::ReplicaCapacityQueued {
+                                size: i.size.clone(),
+                                schedulable: i.schedulable,
+                                required: i.required,
+                            });
+                    }
+                    Instant::now()
+                });
+                if queued_since.elapsed() >= queue_timeout {
+                    let InsufficientReplicaCapacity {
+                        size,
+                        schedulable,
+                        required,
+                    } = first.clone();
+                    ctx.retire(Err(AdapterError::InsufficientReplicaCapacity {
+                        size,
+                        schedulable,
+                        required,
+                    }));
+                } else {
+                    self.spawn_replica_capacity_check(
+                        ctx,
+                        creation,
+                        REPLICA_CAPACITY_QUEUE_INTERVAL,
+                    );
+                }
+            }
+            _ => {
+                if mode == "notice" {
+                    for i in insufficient {
+                        ctx.session()
+                            .add_notice(AdapterNotice::InsufficientReplicaCapacity {
+                                size: i.size,
+                                schedulable: i.schedulable,
+                                required: i.required,
+                            });
+                    }
+                }
+                let result = self.create_replicas(ctx.session(), creation).await;
+                ctx.retire(result);
+            }
+        }
+    }
+
+    /// Creates the cluster or replicas of `creation`.
+    async fn create_replicas(
+        &mut self,
+        session: &Session,
+        ReplicaCreation { ops, target, .. }: ReplicaCreation,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        self.catalog_transact(Some(session), ops).await?;
+        match target {
+            ReplicaCreationTarget::Cluster(cluster_id) => {
+                self.create_cluster(cluster_id).await;
+                Ok(ExecuteResponse::CreatedCluster)
+            }
+            ReplicaCreationTarget::Replica(cluster_id, replica_id) => {
+                self.create_cluster_replica(cluster_id, replica_id).await;
+                Ok(ExecuteResponse::CreatedClusterReplica)
+            }
+        }
+    }
+
     fn ensure_valid_azs<'a, I: IntoIterator<Item = &'a String>>(
         &self,
         azs: I,
@@ -244,7 +475,7 @@ impl Coordinator {
         CreateClusterUnmanagedPlan { replicas }: CreateClusterUnmanagedPlan,
         id: ClusterId,
         mut ops: Vec<catalog::Op>,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<ReplicaCreation, AdapterError> {
         tracing::debug!("sequence_create_unmanaged_cluster");
 
         self.ensure_valid_azs(replicas.iter().filter_map(|(_, r)| {
@@ -353,11 +584,10 @@ impl Coordinator {
             });
         }
 
-        self.catalog_transact(Some(session), ops).await?;
-
-        self.create_cluster(id).await;
-
-        Ok(ExecuteResponse::CreatedCluster)
+        Ok(ReplicaCreation::new(
+            ops,
+            ReplicaCreationTarget::Cluster(id),
+        ))
     }
 
     pub(super) async fn create_cluster(&mut self, cluster_id: ClusterId) {
@@ -396,6 +626,8 @@ impl Coordinator {
         }
     }
 
+    /// Prepares the creation of a cluster replica, which
+    /// [`Coordinator::create_replicas_after_capacity_check`] completes.
     #[mz_ore::instrument(level = "debug")]
     pub(super) async fn sequence_create_cluster_replica(
         &mut self,
@@ -405,7 +637,7 @@ impl Coordinator {
             cluster_id,
             config,
         }: CreateClusterReplicaPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<ReplicaCreation, AdapterError> {
         // Choose default AZ if necessary
         let (compute, location) = match config {
             mz_sql::plan::ReplicaConfig::Unorchestrated {
@@ -509,11 +741,10 @@ impl Coordinator {
             reason: ReplicaCreateDropReason::Manual,
         };
 
-        self.catalog_transact(Some(session), vec![op]).await?;
-
-        self.create_cluster_replica(cluster_id, id).await;
-
-        Ok(ExecuteResponse::CreatedClusterReplica)
+        Ok(ReplicaCreation::new(
+            vec![op],
+            ReplicaCreationTarget::Replica(cluster_id, id),
+        ))
     }
 
     pub(super) async fn create_cluster_replica(
@@ -35,6 +35,7 @@ use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::explain::json::json_string;
 use mz_repr::explain::ExprHumanizer;
+use mz_repr::optimize::OverrideFrom;
 use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, Diff, GlobalId, IntoRowIterator, Row, RowArena, RowIterator, Timestamp};
 use mz_sql::ast::{
@@ -60,6 +61,7 @@ use prost::Message as _;
 use timely::progress::Timestamp as TimelyTimestamp;
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
 use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::DROP_OBJECTS_BATCH_SIZE;
 use mz_catalog::memory::objects::{
     CatalogItem, Cluster, Connection, DataSourceDesc, Sink, Source, Table, Type,
 };
@@ -94,7 +96,6 @@ use mz_storage_types::controller::StorageError;
 use mz_storage_types::stats::RelationPartStats;
 use mz_storage_types::AlterCompatible;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
-use mz_transform::EmptyStatisticsOracle;
 use timely::progress::Antichain;
 use tokio::sync::{oneshot, watch, OwnedMutexGuard};
 use tracing::{warn, Instrument, Span};
@@ -102,6 +103,10 @@ use tracing::{warn, Instrument, Span};
 use crate::catalog::{self, Catalog, ConnCatalog, DropObjectInfo, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
 use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::cancellation::{CancellationMethod, CancellationOutcome};
+use crate::coord::external_tasks::ExternalHost;
+use crate::coord::failpoints;
+use crate::coord::timestamp_selection::TimestampProvider;
 use crate::coord::{
     AlterConnectionValidationReady, AlterSinkReadyContext, Coordinator,
     CreateConnectionValidationReady, DeferredPlanStatement, ExecuteContext, ExplainContext,
@@ -109,6 +114,7 @@ use crate::coord::{
     StageResult, Staged, StagedContext, TargetCluster, WatchSetResponse,
 };
 use crate::error::AdapterError;
+use crate::explain::graph::PlanGraph;
 use crate::notice::{AdapterNotice, DroppedInUseIndex};
 use crate::optimize::dataflows::{prep_scalar_expr, EvalTime, ExprPrepStyle};
 use crate::optimize::{self, Optimize};
@@ -122,6 +128,7 @@ mod cluster;
 mod create_index;
 mod create_materialized_view;
 mod create_view;
+mod drop_objects;
 mod explain_timestamp;
 mod peek;
 mod secret;
@@ -687,10 +694,14 @@ impl Coordinator {
         if plan.validate {
             let internal_cmd_tx = self.internal_cmd_tx.clone();
             let transient_revision = self.catalog().transient_revision();
-            let conn_id = ctx.session().conn_id().clone();
             let otel_ctx = OpenTelemetryContext::obtain();
             let role_metadata = ctx.session().role_metadata().clone();
 
+            let mut hosts = self.external_hosts(resolved_ids.0.iter().copied());
+            hosts.extend(ExternalHost::for_connection(
+                connection_gid,
+                &plan.connection.connection,
+            ));
             let connection = plan
                 .connection
                 .connection
@@ -698,35 +709,39 @@ impl Coordinator {
                 .into_inline_connection(self.catalog().state());
 
             let current_storage_parameters = self.controller.storage.config().clone();
-            task::spawn(|| format!("validate_connection:{conn_id}"), async move {
-                let result = match connection
-                    .validate(connection_gid, &current_storage_parameters)
-                    .await
-                {
-                    Ok(()) => Ok(plan),
-                    Err(err) => Err(err.into()),
-                };
+            self.spawn_external_task("validate_connection", hosts, ctx, move |ctx| {
+                async move {
+                    let result = match connection
+                        .validate(connection_gid, &current_storage_parameters)
+                        .await
+                    {
+                        Ok(()) => Ok(plan),
+                        Err(err) => Err(err.into()),
+                    };
 
-                // It is not an error for validation to complete after `internal_cmd_rx` is dropped.
-                let result = internal_cmd_tx.send(Message::CreateConnectionValidationReady(
-                    CreateConnectionValidationReady {
-                        ctx,
-                        result,
-                        connection_gid,
-                        plan_validity: PlanValidity::new(
-                            transient_revision,
-                            resolved_ids.0.clone(),
-                            None,
-                            None,
-                            role_metadata,
-                        ),
-                        otel_ctx,
-                        dependency_ids: resolved_ids.0,
-                    },
-                ));
-                if let Err(e) = result {
-                    tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    // It is not an error for validation to complete after `internal_cmd_rx` is
+                    // dropped.
+                    let result = internal_cmd_tx.send(Message::CreateConnectionValidationReady(
+                        CreateConnectionValidationReady {
+                            ctx,
+                            result,
+                            connection_gid,
+                            plan_validity: PlanValidity::new(
+                                transient_revision,
+                                resolved_ids.0.clone(),
+                                None,
+                                None,
+                                role_metadata,
+                            ),
+                            otel_ctx,
+                            dependency_ids: resolved_ids.0,
+                        },
+                    ));
+                    if let Err(e) = result {
+                        tracing::warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
                 }
+                .boxed()
             });
         } else {
             let result = self
@@ -901,6 +916,8 @@ impl Coordinator {
             resolved_ids,
             custom_logical_compaction_window: table.compaction_window,
             is_retained_metrics_object: false,
+            change_audit: table.change_audit,
+            row_count_hint: table.row_count_hint,
         };
         let ops = vec![catalog::Op::CreateItem {
             id: table_id,
@@ -1060,9 +1077,15 @@ impl Coordinator {
             }
         };
 
-        self.create_storage_export(id, &catalog_sink)
-            .await
-            .unwrap_or_terminate("cannot fail to create exports");
+        if catalog_sink.connection.is_compute_sink() {
+            self.create_webhook_sink_dataflow(id, &catalog_sink)
+                .await
+                .unwrap_or_terminate("cannot fail to create webhook sink dataflows");
+        } else {
+            self.create_storage_export(id, &catalog_sink)
+                .await
+                .unwrap_or_terminate("cannot fail to create exports");
+        }
 
         ctx.retire(Ok(ExecuteResponse::CreatedSink))
     }
@@ -1153,20 +1176,63 @@ impl Coordinator {
     #[instrument]
     pub(super) async fn sequence_drop_objects(
         &mut self,
-        session: &Session,
+        ctx: ExecuteContext,
         plan::DropObjectsPlan {
             drop_ids,
             object_type,
             referenced_ids,
         }: plan::DropObjectsPlan,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) {
+        self.sequence_drop_ids(ctx, object_type, false, referenced_ids, drop_ids)
+            .await;
+    }
+
+    /// Drops the subsources `subsources` of the source `id`, and the objects that depend on them.
+    ///
+    /// The subsources are dropped like they are by `DROP SOURCE`, which makes the storage
+    /// controller remove their exports from the ingestion and restart it with the remaining
+    /// exports. The ingestion itself, and its progress subsource, which holds the remap shard
+    /// that the remaining subsources are reclocked with, are kept.
+    #[instrument]
+    pub(super) async fn sequence_alter_source_drop_subsources(
+        &mut self,
+        ctx: ExecuteContext,
+        id: GlobalId,
+        subsources: Vec<GlobalId>,
+        drop_ids: Vec<ObjectId>,
+    ) {
+        let progress_id = self.catalog().get_entry(&id).progress_id();
+        mz_ore::soft_assert_or_log!(
+            drop_ids.iter().all(|drop_id| match drop_id {
+                ObjectId::Item(drop_id) => *drop_id != id && Some(*drop_id) != progress_id,
+                _ => true,
+            }),
+            "ALTER SOURCE...DROP SUBSOURCE must not drop the source or its progress subsource: \
+             {drop_ids:?}",
+        );
+        let referenced_ids = subsources.into_iter().map(ObjectId::Item).collect();
+        self.sequence_drop_ids(ctx, ObjectType::Source, true, referenced_ids, drop_ids)
+            .await;
+    }
+
+    /// Drops `drop_ids`, which contain `referenced_ids` and the objects that depend on them, in
+    /// reverse dependency order. If `alter` is true, the drop is part of an `ALTER` of an object
+    /// of type `object_type`, and responds that the object was altered.
+    async fn sequence_drop_ids(
+        &mut self,
+        ctx: ExecuteContext,
+        object_type: ObjectType,
+        alter: bool,
+        referenced_ids: Vec<ObjectId>,
+        drop_ids: Vec<ObjectId>,
+    ) {
         let referenced_ids_hashset = referenced_ids.iter().collect::<HashSet<_>>();
         let mut objects = Vec::new();
         for obj_id in &drop_ids {
             if !referenced_ids_hashset.contains(obj_id) {
                 let object_info = ErrorMessageObjectDescription::from_id(
                     obj_id,
-                    &self.catalog().for_session(session),
+                    &self.catalog().for_session(ctx.session()),
                 )
                 .to_string();
                 objects.push(object_info);
@@ -1174,9 +1240,40 @@ impl Coordinator {
         }
 
         if !objects.is_empty() {
-            session.add_notice(AdapterNotice::CascadeDroppedObject { objects });
+            ctx.session()
+                .add_notice(AdapterNotice::CascadeDroppedObject { objects });
         }
 
+        // Large drops (e.g. `DROP SCHEMA ... CASCADE` of a big schema) are split into several
+        // catalog transactions so that we don't block the coordinator for the entire drop.
+        let batch_size = DROP_OBJECTS_BATCH_SIZE.get(self.catalog().system_config().dyncfgs());
+        let item_count = drop_ids
+            .iter()
+            .filter(|id| matches!(id, ObjectId::Item(_)))
+            .count();
+        if item_count > batch_size {
+            let stage = return_if_err!(
+                self.drop_objects_validate(ctx.session(), object_type, alter, drop_ids, batch_size)
+                    .await,
+                ctx
+            );
+            self.sequence_staged(ctx, Span::current(), stage).await;
+            return;
+        }
+
+        let result = self
+            .sequence_drop_objects_inner(ctx.session(), object_type, alter, drop_ids)
+            .await;
+        ctx.retire(result);
+    }
+
+    async fn sequence_drop_objects_inner(
+        &mut self,
+        session: &Session,
+        object_type: ObjectType,
+        alter: bool,
+        drop_ids: Vec<ObjectId>,
+    ) -> Result<ExecuteResponse, AdapterError> {
         let DropOps {
             ops,
             dropped_active_db,
@@ -1188,6 +1285,23 @@ impl Coordinator {
 
         fail::fail_point!("after_sequencer_drop_replica");
 
+        self.add_drop_notices(
+            session,
+            dropped_active_db,
+            dropped_active_cluster,
+            dropped_in_use_indexes,
+        );
+        Ok(drop_objects::drop_response(object_type, alter))
+    }
+
+    /// Adds the notices that describe the side effects of a completed drop to `session`.
+    fn add_drop_notices(
+        &self,
+        session: &Session,
+        dropped_active_db: bool,
+        dropped_active_cluster: bool,
+        dropped_in_use_indexes: Vec<DroppedInUseIndex>,
+    ) {
         if dropped_active_db {
             session.add_notice(AdapterNotice::DroppedActiveDatabase {
                 name: session.vars().database().to_string(),
@@ -1205,7 +1319,6 @@ impl Coordinator {
                 .with_label_values(&["DroppedInUseIndex"])
                 .inc_by(1);
         }
-        Ok(ExecuteResponse::DroppedObject(object_type))
     }
 
     fn validate_dropped_role_ownership(
@@ -1711,6 +1824,45 @@ impl Coordinator {
         Ok(Self::send_immediate_rows(rows))
     }
 
+    pub(super) fn sequence_show_transaction_writes(
+        &self,
+        session: &Session,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        // The number of inserted and deleted rows, by table.
+        let mut counts: BTreeMap<GlobalId, (Diff, Diff)> = BTreeMap::new();
+        for WriteOp { id, rows } in session.transaction().staged_writes() {
+            let (inserts, deletes) = counts.entry(*id).or_default();
+            for (_, diff) in rows {
+                if *diff > 0 {
+                    *inserts += *diff;
+                } else {
+                    *deletes -= *diff;
+                }
+            }
+        }
+
+        let rows: Vec<_> = counts
+            .into_iter()
+            .map(|(id, (inserts, deletes))| {
+                let id_str = id.to_string();
+                let name = self
+                    .catalog()
+                    .resolve_full_name(
+                        self.catalog().get_entry(&id).name(),
+                        Some(session.conn_id()),
+                    )
+                    .to_string();
+                Row::pack_slice(&[
+                    Datum::String(&id_str),
+                    Datum::String(&name),
+                    Datum::Int64(inserts),
+                    Datum::Int64(deletes),
+                ])
+            })
+            .collect();
+        Ok(Self::send_immediate_rows(rows))
+    }
+
     pub(super) fn sequence_show_variable(
         &mut self,
         session: &Session,
@@ -1763,7 +1915,11 @@ impl Coordinator {
             session.add_notice(AdapterNotice::DatabaseDoesNotExist { name });
         } else if variable.name() == vars::CLUSTER.name()
             && matches!(
-                self.catalog().resolve_cluster(&variable.value()),
+                self.catalog().resolve_cluster(
+                    self.catalog()
+                        .state()
+                        .preferred_cluster_name(&variable.value())
+                ),
                 Err(CatalogError::UnknownCluster(_))
             )
         {
@@ -1849,7 +2005,11 @@ impl Coordinator {
                     session.add_notice(AdapterNotice::DatabaseDoesNotExist { name });
                 } else if name.as_str() == vars::CLUSTER.name()
                     && matches!(
-                        self.catalog().resolve_cluster(vars.cluster()),
+                        self.catalog().resolve_cluster(
+                            self.catalog()
+                                .state()
+                                .preferred_cluster_name(vars.cluster())
+                        ),
                         Err(CatalogError::UnknownCluster(_))
                     )
                 {
@@ -2111,25 +2271,63 @@ impl Coordinator {
     ) {
         match plan {
             SideEffectingFunc::PgCancelBackend { connection_id } => {
+                let requested_by = ctx.session().user().name.clone();
                 if ctx.session().conn_id().unhandled() == connection_id {
                     // As a special case, if we're canceling ourselves, we send
                     // back a canceled resposne to the client issuing the query,
                     // and so we need to do no further processing of the cancel.
+                    self.record_cancellation(
+                        connection_id,
+                        CancellationMethod::PgCancelBackend,
+                        None,
+                        Some(&requested_by),
+                        CancellationOutcome::Canceled,
+                    )
+                    .await;
                     ctx.retire(Err(AdapterError::Canceled));
                     return;
                 }
 
-                let res = if let Some((id_handle, _conn_meta)) =
+                let (res, outcome) = if let Some((id_handle, _conn_meta)) =
                     self.active_conns.get_key_value(&connection_id)
                 {
                     // check_plan already verified role membership.
                     self.handle_privileged_cancel(id_handle.clone()).await;
-                    Datum::True
+                    (Datum::True, CancellationOutcome::Canceled)
                 } else {
-                    Datum::False
+                    (Datum::False, CancellationOutcome::UnknownConnection)
                 };
+                self.record_cancellation(
+                    connection_id,
+                    CancellationMethod::PgCancelBackend,
+                    None,
+                    Some(&requested_by),
+                    outcome,
+                )
+                .await;
                 ctx.retire(Ok(Self::send_immediate_rows(Row::pack_slice(&[res]))));
             }
+            SideEffectingFunc::MzVacuumCatalog => {
+                self.spawn_catalog_vacuum(Some(ctx));
+            }
+            SideEffectingFunc::MzTriggerBackgroundTask { name } => {
+                let res = self.trigger_background_task(&name).map(|triggered| {
+                    Self::send_immediate_rows(Row::pack_slice(&[Datum::from(triggered)]))
+                });
+                ctx.retire(res);
+            }
+            SideEffectingFunc::MzSkipBackgroundTask { name } => {
+                let res = self.skip_background_task(&name).map(|skipped| {
+                    Self::send_immediate_rows(Row::pack_slice(&[Datum::from(skipped)]))
+                });
+                ctx.retire(res);
+            }
+            SideEffectingFunc::MzSetFailpoint { name, actions } => {
+                let res = failpoints::set_failpoint(&name, &actions).map(|previous| {
+                    Self::send_immediate_rows(Row::pack_slice(&[Datum::from(previous.as_deref())]))
+                });
+                ctx.retire(res);
+            }
         }
     }
 
@@ -2196,9 +2394,18 @@ impl Coordinator {
     pub(super) async fn sequence_explain_plan(
         &mut self,
         ctx: ExecuteContext,
-        plan: plan::ExplainPlanPlan,
+        mut plan: plan::ExplainPlanPlan,
         target_cluster: TargetCluster,
     ) {
+        // The optimizer features of the session apply to the explained statement, but the
+        // `EXPLAIN ... WITH(...)` options take precedence.
+        plan.config.features = ctx
+            .session()
+            .vars()
+            .optimizer_feature_flags()
+            .clone()
+            .override_from(&plan.config.features);
+
         match &plan.explainee {
             plan::Explainee::Statement(stmt) => match stmt {
                 plan::ExplaineeStatement::CreateView { .. } => {
@@ -2213,17 +2420,20 @@ impl Coordinator {
                 plan::ExplaineeStatement::Select { .. } => {
                     self.explain_peek(ctx, plan, target_cluster).await;
                 }
+                plan::ExplaineeStatement::Subscribe { .. } => {
+                    self.explain_subscribe(ctx, plan, target_cluster).await;
+                }
             },
             plan::Explainee::View(_) => {
                 let result = self.explain_view(&ctx, plan);
                 ctx.retire(result);
             }
             plan::Explainee::MaterializedView(_) => {
-                let result = self.explain_materialized_view(&ctx, plan);
+                let result = self.explain_materialized_view(&ctx, plan).await;
                 ctx.retire(result);
             }
             plan::Explainee::Index(_) => {
-                let result = self.explain_index(&ctx, plan);
+                let result = self.explain_index(&ctx, plan).await;
                 ctx.retire(result);
             }
             plan::Explainee::ReplanView(_) => {
@@ -2274,6 +2484,29 @@ impl Coordinator {
         };
     }
 
+    pub(super) async fn sequence_explain_analyze(
+        &mut self,
+        ctx: ExecuteContext,
+        plan: plan::ExplainAnalyzePlan,
+    ) {
+        let select = return_if_err!(
+            self.explain_analyze_materialized_view(ctx.session(), &plan),
+            ctx
+        );
+        let stage = return_if_err!(
+            self.peek_validate(
+                ctx.session(),
+                select,
+                TargetCluster::Replica(plan.cluster_id, plan.replica_id),
+                None,
+                ExplainContext::None,
+                Some(ctx.session().vars().max_query_result_size()),
+            ),
+            ctx
+        );
+        self.sequence_staged(ctx, Span::current(), stage).await;
+    }
+
     async fn render_explain_pushdown(
         &self,
         ctx: ExecuteContext,
@@ -2430,8 +2663,13 @@ impl Coordinator {
             selection if selection.as_const().is_some() && plan.returning.is_empty() => {
                 let catalog = self.owned_catalog();
                 mz_ore::task::spawn(|| "coord::sequence_inner", async move {
-                    let result =
-                        Self::insert_constant(&catalog, ctx.session_mut(), plan.id, selection);
+                    let result = Self::insert_constant(
+                        &catalog,
+                        ctx.session_mut(),
+                        plan.id,
+                        selection,
+                        plan.dry_run,
+                    );
                     ctx.retire(result);
                 });
             }
@@ -2472,6 +2710,7 @@ impl Coordinator {
                     assignments: BTreeMap::new(),
                     kind: MutationKind::Insert,
                     returning: plan.returning,
+                    dry_run: plan.dry_run,
                 };
 
                 self.sequence_read_then_write(ctx, read_then_write_plan)
@@ -2501,6 +2740,7 @@ impl Coordinator {
             mut assignments,
             finishing,
             returning,
+            dry_run,
         } = plan;
 
         // Read then writes can be queued, so re-verify the id exists.
@@ -2623,6 +2863,7 @@ impl Coordinator {
                 when: QueryWhen::FreshestTableWrite,
                 finishing,
                 copy_to: None,
+                mirror: None,
             },
             TargetCluster::Active,
             None,
@@ -2858,6 +3099,7 @@ impl Coordinator {
                             kind,
                             returning: returning_rows,
                             max_result_size,
+                            dry_run,
                         },
                     );
                     ctx.retire(result);
@@ -2930,6 +3172,41 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(plan.object_type))
     }
 
+    #[instrument]
+    pub(super) async fn sequence_alter_table_row_count_hint(
+        &mut self,
+        session: &Session,
+        plan::AlterTableRowCountHintPlan { id, value, hint }: plan::AlterTableRowCountHintPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let ops = vec![catalog::Op::AlterTableRowCountHint { id, value, hint }];
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Table))
+    }
+
+    #[instrument]
+    pub(super) async fn sequence_alter_database(
+        &mut self,
+        session: &mut Session,
+        plan::AlterDatabasePlan {
+            id,
+            name,
+            default_cluster,
+        }: plan::AlterDatabasePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let op = catalog::Op::UpdateDatabaseDefaultCluster {
+            id,
+            name,
+            default_cluster_id: default_cluster,
+        };
+        match self
+            .catalog_transact_with_ddl_transaction(session, vec![op])
+            .await
+        {
+            Ok(()) => Ok(ExecuteResponse::AlteredObject(ObjectType::Database)),
+            Err(err) => Err(err),
+        }
+    }
+
     #[instrument]
     pub(super) async fn sequence_alter_schema_rename(
         &mut self,
@@ -4015,6 +4292,9 @@ impl Coordinator {
                 )
                 .await;
             }
+            plan::AlterSourceAction::DropSubsourceExports { .. } => {
+                unreachable!("sequenced by sequence_alter_source_drop_subsources")
+            }
         }
 
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
@@ -4551,9 +4831,11 @@ impl Coordinator {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct CachedStatisticsOracle {
     cache: BTreeMap<GlobalId, usize>,
+    /// User-supplied row counts, which take precedence over the statistics in `cache`.
+    row_count_hints: BTreeMap<GlobalId, usize>,
 }
 
 impl CachedStatisticsOracle {
@@ -4578,17 +4860,34 @@ impl CachedStatisticsOracle {
             }
         }
 
-        Ok(Self { cache })
+        Ok(Self {
+            cache,
+            row_count_hints: BTreeMap::new(),
+        })
+    }
+
+    fn with_row_count_hints(mut self, row_count_hints: BTreeMap<GlobalId, usize>) -> Self {
+        self.row_count_hints = row_count_hints;
+        self
     }
 }
 
 impl mz_transform::StatisticsOracle for CachedStatisticsOracle {
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
-        self.cache.get(&id).map(|estimate| *estimate)
+        self.row_count_hints
+            .get(&id)
+            .or_else(|| self.cache.get(&id))
+            .map(|estimate| *estimate)
     }
 
     fn as_map(&self) -> BTreeMap<GlobalId, usize> {
-        self.cache.clone()
+        let mut map = self.cache.clone();
+        map.extend(self.row_count_hints.iter().map(|(id, hint)| (*id, *hint)));
+        map
+    }
+
+    fn row_count_hints(&self) -> BTreeMap<GlobalId, usize> {
+        self.row_count_hints.clone()
     }
 }
 
@@ -4600,8 +4899,13 @@ impl Coordinator {
         query_as_of: &Antichain<Timestamp>,
         is_oneshot: bool,
     ) -> Result<Box<dyn mz_transform::StatisticsOracle>, AdapterError> {
+        // The hints apply even if the statistics are not collected.
+        let row_count_hints =
+            optimize::row_count_hints(self.catalog().state(), source_ids.iter().copied());
         if !session.vars().enable_session_cardinality_estimates() {
-            return Ok(Box::new(EmptyStatisticsOracle));
+            return Ok(Box::new(
+                CachedStatisticsOracle::default().with_row_count_hints(row_count_hints),
+            ));
         }
 
         let timeout = if is_oneshot {
@@ -4620,7 +4924,7 @@ impl Coordinator {
         .await;
 
         match cached_stats {
-            Ok(stats) => Ok(Box::new(stats)),
+            Ok(stats) => Ok(Box::new(stats.with_row_count_hints(row_count_hints))),
             Err(mz_ore::future::TimeoutError::DeadlineElapsed) => {
                 warn!(
                     is_oneshot = is_oneshot,
@@ -4628,11 +4932,122 @@ impl Coordinator {
                     timeout.as_millis()
                 );
 
-                Ok(Box::new(EmptyStatisticsOracle))
+                Ok(Box::new(
+                    CachedStatisticsOracle::default().with_row_count_hints(row_count_hints),
+                ))
             }
             Err(mz_ore::future::TimeoutError::Inner(e)) => Err(AdapterError::Storage(e)),
         }
     }
+
+    /// Returns the statistics oracle that [`Coordinator::statistics_oracle`] would return for
+    /// the `source_ids`, but built from `row_estimates` already collected by
+    /// [`Coordinator::explain_row_estimates`] instead of collecting the statistics again.
+    pub(super) fn statistics_oracle_from_row_estimates(
+        &self,
+        session: &Session,
+        source_ids: &BTreeSet<GlobalId>,
+        row_estimates: &BTreeMap<GlobalId, usize>,
+    ) -> Box<dyn mz_transform::StatisticsOracle> {
+        let row_count_hints =
+            optimize::row_count_hints(self.catalog().state(), source_ids.iter().copied());
+        let cache = if session.vars().enable_session_cardinality_estimates() {
+            row_estimates
+                .iter()
+                .filter(|(id, _)| source_ids.contains(id))
+                .map(|(id, estimate)| (*id, *estimate))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        Box::new(CachedStatisticsOracle {
+            cache,
+            row_count_hints,
+        })
+    }
+
+    /// Estimates the number of rows of each of the `source_ids` at `query_as_of` from persist
+    /// statistics, to annotate the sources in `EXPLAIN ... WITH (row estimates)`.
+    ///
+    /// Unlike [`Coordinator::statistics_oracle`], this ignores
+    /// `enable_session_cardinality_estimates`, as the estimates don't influence the plan. Sources
+    /// without statistics, or whose statistics could not be collected in time, have no estimate.
+    pub(super) async fn explain_row_estimates(
+        &self,
+        source_ids: &BTreeSet<GlobalId>,
+        query_as_of: &Antichain<Timestamp>,
+    ) -> BTreeMap<GlobalId, usize> {
+        let timeout = self
+            .catalog()
+            .system_config()
+            .optimizer_oneshot_stats_timeout();
+        let cached_stats = mz_ore::future::timeout(
+            timeout,
+            CachedStatisticsOracle::new(source_ids, query_as_of, self.controller.storage.as_ref()),
+        )
+        .await;
+
+        match cached_stats {
+            Ok(stats) => stats.cache,
+            Err(mz_ore::future::TimeoutError::DeadlineElapsed) => {
+                warn!(
+                    "row estimate collection timed out after {}ms",
+                    timeout.as_millis()
+                );
+                BTreeMap::new()
+            }
+            Err(mz_ore::future::TimeoutError::Inner(e)) => {
+                warn!("failed to collect row estimates: {e}");
+                BTreeMap::new()
+            }
+        }
+    }
+
+    /// Estimates the number of rows of the storage collections that the items in `ids`
+    /// transitively depend on, to annotate the sources and arrangements in `EXPLAIN ... WITH
+    /// (row estimates)` or `WITH (memory estimates)` of explainees that don't have a query
+    /// timestamp, like `CREATE INDEX` or existing indexes.
+    ///
+    /// The estimates are taken from persist statistics at the latest time that is complete for
+    /// all of the collections.
+    pub(super) async fn explain_latest_row_estimates(
+        &self,
+        ids: impl IntoIterator<Item = GlobalId>,
+    ) -> BTreeMap<GlobalId, usize> {
+        let storage_ids = self.explain_storage_ids(ids);
+        let id_bundle = crate::CollectionIdBundle {
+            storage_ids: storage_ids.clone(),
+            compute_ids: BTreeMap::new(),
+        };
+        let as_of = Self::largest_not_in_advance_of_upper(&self.least_valid_write(&id_bundle));
+        self.explain_row_estimates(&storage_ids, &Antichain::from_elem(as_of))
+            .await
+    }
+
+    /// Returns the storage collections among the items in `ids` and the items they transitively
+    /// depend on.
+    pub(super) fn explain_storage_ids(
+        &self,
+        ids: impl IntoIterator<Item = GlobalId>,
+    ) -> BTreeSet<GlobalId> {
+        let state = self.catalog().state();
+        ids.into_iter()
+            .flat_map(|id| state.transitive_uses(id))
+            .filter(|id| self.controller.storage.check_exists(*id).is_ok())
+            .collect()
+    }
+
+    /// Records `plan_graph` as the plan graph of the last statement of connection `conn_id` whose
+    /// optimization was traced, to be served by the internal HTTP server.
+    pub(super) fn record_plan_graph(
+        &mut self,
+        conn_id: &ConnectionId,
+        plan_graph: Option<PlanGraph>,
+    ) {
+        if let Some(conn) = self.active_conns.get_mut(conn_id) {
+            conn.plan_graph = plan_graph;
+        }
+    }
 }
 
 /// Checks whether we should emit diagnostic
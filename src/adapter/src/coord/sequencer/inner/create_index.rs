@@ -114,7 +114,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         // Not used in the EXPLAIN path so it's OK to generate a dummy value.
         let resolved_ids = ResolvedIds(Default::default());
@@ -169,7 +169,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         let explain_ctx = ExplainContext::Plan(ExplainPlanContext {
             broken,
@@ -188,7 +188,7 @@ impl Coordinator {
     }
 
     #[instrument]
-    pub(crate) fn explain_index(
+    pub(crate) async fn explain_index(
         &mut self,
         ctx: &ExecuteContext,
         plan::ExplainPlanPlan {
@@ -218,8 +218,11 @@ impl Coordinator {
             .override_from(&target_cluster.config.features())
             .override_from(&config.features);
 
-        // TODO(mgree): calculate statistics (need a timestamp)
-        let cardinality_stats = BTreeMap::new();
+        let cardinality_stats = if config.memory_estimates || config.row_estimates {
+            self.explain_latest_row_estimates([index.on]).await
+        } else {
+            BTreeMap::new()
+        };
 
         let explain = match stage {
             ExplainStage::GlobalPlan => {
@@ -331,8 +334,12 @@ impl Coordinator {
                 ), AdapterError> {
                     let _dispatch_guard = explain_ctx.dispatch_guard();
 
-                    let index_plan =
-                        optimize::index::Index::new(&plan.name, &plan.index.on, &plan.index.keys);
+                    let index_plan = optimize::index::Index::new(
+                        &plan.name,
+                        &plan.index.on,
+                        &plan.index.keys,
+                        plan.index.predicate.as_ref(),
+                    );
 
                     // MIR ⇒ MIR optimization (global)
                     let global_mir_plan = optimizer.catch_unwind_optimize(index_plan)?;
@@ -410,6 +417,7 @@ impl Coordinator {
                             create_sql,
                             on,
                             keys,
+                            predicate,
                             cluster_id,
                             compaction_window,
                         },
@@ -427,6 +435,7 @@ impl Coordinator {
             item: CatalogItem::Index(Index {
                 create_sql,
                 keys,
+                predicate,
                 on,
                 conn_id: None,
                 resolved_ids,
@@ -573,6 +582,13 @@ impl Coordinator {
             .override_from(&target_cluster.config.features())
             .override_from(&config.features);
 
+        let cardinality_stats = if config.memory_estimates || config.row_estimates {
+            self.explain_latest_row_estimates([index.on]).await
+        } else {
+            BTreeMap::new()
+        };
+
+        let plan_graph = optimizer_trace.plan_graph(&expr_humanizer);
         let rows = optimizer_trace
             .into_rows(
                 format,
@@ -582,11 +598,14 @@ impl Coordinator {
                 None,
                 Some(target_cluster),
                 df_meta,
+                cardinality_stats,
+                None,
                 stage,
                 plan::ExplaineeStatementKind::CreateIndex,
                 None,
             )
             .await?;
+        self.record_plan_graph(session.conn_id(), plan_graph);
 
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
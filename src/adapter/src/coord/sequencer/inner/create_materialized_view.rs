@@ -17,7 +17,7 @@ use mz_expr::{CollectionPlan, ResultSpec};
 use mz_ore::collections::CollectionExt;
 use mz_ore::instrument;
 use mz_ore::soft_panic_or_log;
-use mz_repr::explain::{ExprHumanizerExt, TransientItem};
+use mz_repr::explain::{ExplainConfig, ExplainFormat, ExprHumanizerExt, TransientItem};
 use mz_repr::optimize::OptimizerFeatures;
 use mz_repr::optimize::OverrideFrom;
 use mz_repr::refresh_schedule::RefreshSchedule;
@@ -42,6 +42,7 @@ use crate::coord::{
     ExplainPlanContext, Message, PlanValidity, StageResult, Staged,
 };
 use crate::error::AdapterError;
+use crate::explain::analyze;
 use crate::explain::explain_dataflow;
 use crate::explain::explain_plan;
 use crate::explain::optimizer_trace::OptimizerTrace;
@@ -142,7 +143,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         // Not used in the EXPLAIN path so it's OK to generate a dummy value.
         let resolved_ids = ResolvedIds(Default::default());
@@ -197,7 +198,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         let explain_ctx = ExplainContext::Plan(ExplainPlanContext {
             broken,
@@ -216,7 +217,7 @@ impl Coordinator {
     }
 
     #[instrument]
-    pub(super) fn explain_materialized_view(
+    pub(super) async fn explain_materialized_view(
         &mut self,
         ctx: &ExecuteContext,
         plan::ExplainPlanPlan {
@@ -250,7 +251,12 @@ impl Coordinator {
             .override_from(&target_cluster.config.features())
             .override_from(&config.features);
 
-        let cardinality_stats = BTreeMap::new();
+        let cardinality_stats = if config.memory_estimates || config.row_estimates {
+            let uses = self.catalog().get_entry(&id).uses();
+            self.explain_latest_row_estimates(uses).await
+        } else {
+            BTreeMap::new()
+        };
 
         let explain = match stage {
             ExplainStage::RawPlan => explain_plan(
@@ -827,6 +833,7 @@ impl Coordinator {
                     name,
                     materialized_view:
                         plan::MaterializedView {
+                            expr,
                             column_names,
                             cluster_id,
                             ..
@@ -863,6 +870,13 @@ impl Coordinator {
             .override_from(&target_cluster.config.features())
             .override_from(&config.features);
 
+        let cardinality_stats = if config.memory_estimates || config.row_estimates {
+            self.explain_latest_row_estimates(expr.depends_on()).await
+        } else {
+            BTreeMap::new()
+        };
+
+        let plan_graph = optimizer_trace.plan_graph(&expr_humanizer);
         let rows = optimizer_trace
             .into_rows(
                 format,
@@ -872,15 +886,77 @@ impl Coordinator {
                 None,
                 Some(target_cluster),
                 df_meta,
+                cardinality_stats,
+                None,
                 stage,
                 plan::ExplaineeStatementKind::CreateMaterializedView,
                 None,
             )
             .await?;
+        self.record_plan_graph(session.conn_id(), plan_graph);
 
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
 
+    /// Plans the introspection query that merges the operator statistics of
+    /// the materialized view's dataflow onto its physical plan.
+    pub(crate) fn explain_analyze_materialized_view(
+        &self,
+        session: &Session,
+        plan: &plan::ExplainAnalyzePlan,
+    ) -> Result<plan::SelectPlan, AdapterError> {
+        let id = plan.id;
+        let Some(plan) = self.catalog().try_get_physical_plan(&id).cloned() else {
+            tracing::error!("cannot find plan for materialized view {id} in catalog");
+            coord_bail!("cannot find plan for materialized view {id} in catalog");
+        };
+        let Some(dataflow_metainfo) = self.catalog().try_get_dataflow_metainfo(&id) else {
+            tracing::error!(
+                "cannot find dataflow metainformation for materialized view {id} in catalog"
+            );
+            coord_bail!(
+                "cannot find dataflow metainformation for materialized view {id} in catalog"
+            );
+        };
+
+        let CatalogItem::MaterializedView(view) = self.catalog().get_entry(&id).item() else {
+            unreachable!() // Asserted in `plan_explain_analyze`.
+        };
+        let features = OptimizerFeatures::from(self.catalog().system_config()).override_from(
+            &self
+                .catalog()
+                .get_cluster(view.cluster_id)
+                .config
+                .features(),
+        );
+        let config = ExplainConfig {
+            node_ids: true,
+            no_notices: true,
+            redacted: false,
+            ..Default::default()
+        };
+
+        let catalog = self.catalog().for_session(session);
+        let explain = explain_dataflow(
+            plan,
+            ExplainFormat::Text,
+            &config,
+            &features,
+            &catalog,
+            BTreeMap::new(),
+            None,
+            dataflow_metainfo,
+        )?;
+
+        let sql = analyze::query(id, &explain);
+        let stmt = mz_sql::parse::parse(&sql)?.into_element().ast;
+        let (stmt, resolved_ids) = mz_sql::names::resolve(&catalog, stmt)?;
+        match mz_sql::plan::plan(None, &catalog, stmt, &plan::Params::empty(), &resolved_ids)? {
+            plan::Plan::Select(select) => Ok(select),
+            plan => coord_bail!("unexpected plan type: {plan:?}"),
+        }
+    }
+
     pub(crate) async fn explain_pushdown_materialized_view(
         &mut self,
         ctx: ExecuteContext,
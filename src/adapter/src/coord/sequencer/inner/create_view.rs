@@ -112,7 +112,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         // Not used in the EXPLAIN path so it's OK to generate a dummy value.
         let resolved_ids = ResolvedIds(Default::default());
@@ -167,7 +167,7 @@ impl Coordinator {
 
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         let explain_ctx = ExplainContext::Plan(ExplainPlanContext {
             broken,
@@ -465,6 +465,7 @@ impl Coordinator {
         let features =
             OptimizerFeatures::from(self.catalog().system_config()).override_from(&config.features);
 
+        let plan_graph = optimizer_trace.plan_graph(&expr_humanizer);
         let rows = optimizer_trace
             .into_rows(
                 format,
@@ -474,11 +475,14 @@ impl Coordinator {
                 None,
                 None, // Views don't have a target cluster.
                 Default::default(),
+                Default::default(), // empty stats
+                None,
                 stage,
                 plan::ExplaineeStatementKind::CreateView,
                 None,
             )
             .await?;
+        self.record_plan_graph(session.conn_id(), plan_graph);
 
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
@@ -0,0 +1,308 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Batched sequencing of large `DROP` statements.
+//!
+//! A `DROP SCHEMA ... CASCADE` (or `DROP DATABASE ... CASCADE`) of a large schema can drop
+//! thousands of items. Dropping them all in one catalog transaction blocks the coordinator for
+//! the entire duration, so instead we split the drop into batches that are each applied in their
+//! own catalog transaction, yielding back to the coordinator's main loop in between.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use mz_ore::instrument;
+use mz_repr::GlobalId;
+use mz_sql::catalog::ObjectType;
+use mz_sql::names::ObjectId;
+use mz_sql::session::metadata::SessionMetadata;
+use tracing::{Instrument, Span};
+
+use crate::coord::sequencer::inner::DropOps;
+use crate::coord::{
+    Coordinator, DropObjectsBatch, DropObjectsStage, Message, PlanValidity, StageResult, Staged,
+};
+use crate::error::AdapterError;
+use crate::notice::AdapterNotice;
+use crate::session::Session;
+use crate::{ExecuteContext, ExecuteResponse};
+
+impl Staged for DropObjectsStage {
+    type Ctx = ExecuteContext;
+
+    fn validity(&mut self) -> &mut PlanValidity {
+        match self {
+            DropObjectsStage::Batch(stage) => &mut stage.validity,
+        }
+    }
+
+    async fn stage(
+        self,
+        coord: &mut Coordinator,
+        ctx: &mut ExecuteContext,
+    ) -> Result<StageResult<Box<Self>>, AdapterError> {
+        match self {
+            DropObjectsStage::Batch(stage) => coord.drop_objects_batch(ctx.session(), stage).await,
+        }
+    }
+
+    fn message(self, ctx: ExecuteContext, span: Span) -> Message {
+        Message::DropObjectsStageReady {
+            ctx,
+            span,
+            stage: self,
+        }
+    }
+
+    fn cancel_enabled(&self) -> bool {
+        // Each batch is committed in its own catalog transaction, so canceling in between batches
+        // would leave the drop partially applied.
+        false
+    }
+}
+
+impl Coordinator {
+    /// Validates a drop of `drop_ids` in its entirety and splits it into batches of at most
+    /// `batch_size` items.
+    #[instrument]
+    pub(super) async fn drop_objects_validate(
+        &mut self,
+        session: &Session,
+        object_type: ObjectType,
+        alter: bool,
+        drop_ids: Vec<ObjectId>,
+        batch_size: usize,
+    ) -> Result<DropObjectsStage, AdapterError> {
+        let total = drop_ids.len();
+
+        // Run the validation for the entire drop up front, so that we fail before having
+        // committed any batch. The ops are regenerated per batch.
+        let DropOps {
+            ops: _,
+            dropped_active_db,
+            dropped_active_cluster,
+            dropped_in_use_indexes,
+        } = self.sequence_drop_common(session, drop_ids.clone()).await?;
+
+        let batches = split_drop_batches(drop_ids, batch_size, |id| {
+            self.catalog().get_entry(&id).progress_id()
+        });
+        let validity = PlanValidity::new(
+            self.catalog().transient_revision(),
+            batch_item_ids(&batches),
+            None,
+            None,
+            session.role_metadata().clone(),
+        );
+
+        Ok(DropObjectsStage::Batch(DropObjectsBatch {
+            validity,
+            object_type,
+            alter,
+            batches,
+            dropped: 0,
+            total,
+            dropped_active_db,
+            dropped_active_cluster,
+            dropped_in_use_indexes,
+        }))
+    }
+
+    /// Drops the next batch of objects, and either yields back to the main loop or, if this was
+    /// the last batch, responds to the client.
+    #[instrument]
+    async fn drop_objects_batch(
+        &mut self,
+        session: &Session,
+        DropObjectsBatch {
+            validity: _,
+            object_type,
+            alter,
+            mut batches,
+            dropped,
+            total,
+            dropped_active_db,
+            dropped_active_cluster,
+            dropped_in_use_indexes,
+        }: DropObjectsBatch,
+    ) -> Result<StageResult<Box<DropObjectsStage>>, AdapterError> {
+        let batch = batches.pop_front().unwrap_or_default();
+        let dropped = dropped + batch.len();
+
+        // Notices about the drop as a whole were computed up front, so ignore the ones for this
+        // batch.
+        let DropOps { ops, .. } = self.sequence_drop_common(session, batch).await?;
+        self.catalog_transact(Some(session), ops).await?;
+
+        if batches.is_empty() {
+            fail::fail_point!("after_sequencer_drop_replica");
+
+            self.add_drop_notices(
+                session,
+                dropped_active_db,
+                dropped_active_cluster,
+                dropped_in_use_indexes,
+            );
+            return Ok(StageResult::Response(drop_response(object_type, alter)));
+        }
+
+        session.add_progress_notice(AdapterNotice::DropObjectsProgress { dropped, total });
+
+        let validity = PlanValidity::new(
+            self.catalog().transient_revision(),
+            batch_item_ids(&batches),
+            None,
+            None,
+            session.role_metadata().clone(),
+        );
+        let stage = DropObjectsStage::Batch(DropObjectsBatch {
+            validity,
+            object_type,
+            alter,
+            batches,
+            dropped,
+            total,
+            dropped_active_db,
+            dropped_active_cluster,
+            dropped_in_use_indexes,
+        });
+
+        // Yield to the main loop before dropping the next batch, so other work can make progress.
+        let span = Span::current();
+        Ok(StageResult::Handle(mz_ore::task::spawn(
+            || "drop objects batch",
+            async move { Ok(Box::new(stage)) }.instrument(span),
+        )))
+    }
+}
+
+/// Returns the response to a completed drop of objects of type `object_type`, or, if `alter` is
+/// true, to the `ALTER` of an object of type `object_type` that dropped them.
+pub(super) fn drop_response(object_type: ObjectType, alter: bool) -> ExecuteResponse {
+    if alter {
+        ExecuteResponse::AlteredObject(object_type)
+    } else {
+        ExecuteResponse::DroppedObject(object_type)
+    }
+}
+
+/// Splits `drop_ids`, which must be in reverse dependency order, into batches of at most
+/// `batch_size` items, such that dropping the batches in order never drops an object before
+/// the objects that depend on it.
+///
+/// All non-item objects (schemas, databases, clusters, ...) are placed in the final batch,
+/// after every item. A source and its progress collection, as returned by `progress_id`, are
+/// always placed in the same batch, which can make a batch exceed `batch_size`.
+fn split_drop_batches<F>(
+    drop_ids: Vec<ObjectId>,
+    batch_size: usize,
+    progress_id: F,
+) -> VecDeque<Vec<ObjectId>>
+where
+    F: Fn(GlobalId) -> Option<GlobalId>,
+{
+    let batch_size = batch_size.max(1);
+    let (items, others): (Vec<_>, Vec<_>) = drop_ids
+        .into_iter()
+        .partition(|id| matches!(id, ObjectId::Item(_)));
+
+    let mut batches = VecDeque::new();
+    let mut batch = Vec::new();
+    let mut seen = BTreeSet::new();
+    // Progress collections of sources in the current batch that we haven't seen yet.
+    let mut pending_progress = BTreeSet::new();
+    for id in items {
+        let ObjectId::Item(item_id) = &id else {
+            unreachable!("partitioned above");
+        };
+        seen.insert(*item_id);
+        pending_progress.remove(item_id);
+        if let Some(progress_id) = progress_id(*item_id) {
+            if !seen.contains(&progress_id) {
+                pending_progress.insert(progress_id);
+            }
+        }
+        batch.push(id);
+        if batch.len() >= batch_size && pending_progress.is_empty() {
+            batches.push_back(std::mem::take(&mut batch));
+        }
+    }
+
+    batch.extend(others);
+    if !batch.is_empty() || batches.is_empty() {
+        batches.push_back(batch);
+    }
+    batches
+}
+
+/// Returns the IDs of all items in `batches`.
+fn batch_item_ids(batches: &VecDeque<Vec<ObjectId>>) -> BTreeSet<GlobalId> {
+    batches
+        .iter()
+        .flatten()
+        .filter_map(|id| match id {
+            ObjectId::Item(id) => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::GlobalId;
+    use mz_sql::names::{
+        DatabaseId, ObjectId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
+    };
+
+    use super::split_drop_batches;
+
+    fn item(id: u64) -> ObjectId {
+        ObjectId::Item(GlobalId::User(id))
+    }
+
+    #[mz_ore::test]
+    fn test_split_drop_batches() {
+        let schema = ObjectId::Schema((
+            ResolvedDatabaseSpecifier::Id(DatabaseId::User(1)),
+            SchemaSpecifier::Id(SchemaId::User(1)),
+        ));
+        let ids = vec![item(1), item(2), item(3), item(4), item(5), schema.clone()];
+
+        let batches = split_drop_batches(ids.clone(), 2, |_| None);
+        assert_eq!(
+            Vec::from(batches),
+            vec![
+                vec![item(1), item(2)],
+                vec![item(3), item(4)],
+                vec![item(5), schema.clone()]
+            ]
+        );
+
+        // A source and its progress collection stay together.
+        let batches = split_drop_batches(ids.clone(), 2, |id| {
+            (id == GlobalId::User(2)).then_some(GlobalId::User(4))
+        });
+        assert_eq!(
+            Vec::from(batches),
+            vec![
+                vec![item(1), item(2), item(3), item(4)],
+                vec![item(5), schema.clone()]
+            ]
+        );
+
+        // Non-item objects always end up in the last batch.
+        let batches = split_drop_batches(ids, 5, |_| None);
+        assert_eq!(
+            Vec::from(batches),
+            vec![
+                vec![item(1), item(2), item(3), item(4), item(5)],
+                vec![schema]
+            ]
+        );
+    }
+}
@@ -311,6 +311,7 @@ impl Coordinator {
             timeline_context = TimelineContext::TimestampDependent;
         }
 
+        self.check_timestamp_oracle_for_read(session, &timeline_context, &when)?;
         let oracle_read_ts = self.oracle_read_ts(session, &timeline_context, &when).await;
 
         let determination = self.sequence_peek_timestamp(
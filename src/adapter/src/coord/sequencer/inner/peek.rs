@@ -18,9 +18,9 @@ use mz_controller_types::ClusterId;
 use mz_expr::{CollectionPlan, ResultSpec};
 use mz_ore::cast::CastFrom;
 use mz_ore::instrument;
-use mz_repr::explain::{ExprHumanizerExt, TransientItem};
+use mz_repr::explain::{ExplainConfig, ExplainFormat, ExprHumanizerExt, TransientItem};
 use mz_repr::optimize::{OptimizerFeatures, OverrideFrom};
-use mz_repr::{Datum, GlobalId, RowArena, Timestamp};
+use mz_repr::{Datum, GlobalId, Row, RowArena, Timestamp};
 use mz_sql::ast::{ExplainStage, Statement};
 use mz_sql::catalog::CatalogCluster;
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
@@ -28,6 +28,7 @@ use mz_catalog::memory::objects::CatalogItem;
 use mz_sql::plan::QueryWhen;
 use mz_sql::plan::{self, HirScalarExpr};
 use mz_sql::session::metadata::SessionMetadata;
+use mz_sql::session::vars::IsolationLevel;
 use mz_transform::EmptyStatisticsOracle;
 use tokio::sync::oneshot;
 use tracing::warn;
@@ -44,11 +45,14 @@ use crate::coord::timestamp_selection::{
 };
 use crate::coord::{
     Coordinator, CopyToContext, ExecuteContext, ExplainContext, ExplainPlanContext, Message,
-    PeekStage, PeekStageCopyTo, PeekStageExplainPlan, PeekStageExplainPushdown, PeekStageFinish,
-    PeekStageLinearizeTimestamp, PeekStageOptimize, PeekStageRealTimeRecency,
-    PeekStageTimestampReadHold, PlanValidity, StageResult, Staged, TargetCluster, WatchSetResponse,
+    PeekExplainBundleContext, PeekStage, PeekStageCopyTo, PeekStageExplainPlan,
+    PeekStageExplainPushdown, PeekStageFinish, PeekStageLinearizeTimestamp, PeekStageOptimize,
+    PeekStageRealTimeRecency, PeekStageTimestampReadHold, PlanValidity, StageResult, Staged,
+    TargetCluster, WatchSetResponse,
 };
 use crate::error::AdapterError;
+use crate::explain::bundle::{self, BundleCluster, ExplainBundle};
+use crate::explain::explain_plan;
 use crate::explain::insights::PlanInsightsContext;
 use crate::explain::optimizer_trace::OptimizerTrace;
 use crate::notice::AdapterNotice;
@@ -80,7 +84,7 @@ impl Staged for PeekStage {
     ) -> Result<StageResult<Box<Self>>, AdapterError> {
         match self {
             PeekStage::LinearizeTimestamp(stage) => {
-                coord.peek_linearize_timestamp(ctx.session(), stage).await
+                coord.peek_linearize_timestamp(ctx, stage).await
             }
             PeekStage::RealTimeRecency(stage) => {
                 coord.peek_real_time_recency(ctx.session(), stage).await
@@ -121,11 +125,12 @@ impl Coordinator {
     #[instrument]
     pub(crate) async fn sequence_peek(
         &mut self,
-        ctx: ExecuteContext,
+        mut ctx: ExecuteContext,
         plan: plan::SelectPlan,
         target_cluster: TargetCluster,
         max_query_result_size: Option<u64>,
     ) {
+        self.notice_pending_builtin_table_migrations(ctx.session_mut(), &plan.source.depends_on());
         let explain_ctx = if ctx.session().vars().emit_plan_insights_notice() {
             let optimizer_trace = OptimizerTrace::new(ExplainStage::PlanInsights.paths());
             ExplainContext::PlanInsightsNotice(optimizer_trace)
@@ -235,9 +240,20 @@ impl Coordinator {
             unreachable!()
         };
 
+        if !config.parameters.is_empty() {
+            let result = self.explain_parameterized_peek(
+                ctx.session(),
+                plan,
+                format,
+                &config,
+                target_cluster,
+            );
+            return ctx.retire(result);
+        }
+
         // Create an OptimizerTrace instance to collect plans emitted when
         // executing the optimizer pipeline.
-        let optimizer_trace = OptimizerTrace::new(stage.paths());
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
 
         let stage = return_if_err!(
             self.peek_validate(
@@ -261,6 +277,38 @@ impl Coordinator {
         self.sequence_staged(ctx, Span::current(), stage).await;
     }
 
+    /// Explains the raw plan of a `SELECT` whose parameters are only typed by `EXPLAIN
+    /// (PARAMETERS (...))`. The plan can't be optimized without values for the parameters, which
+    /// `plan_explain_plan` asserts by only allowing `RAW PLAN`.
+    fn explain_parameterized_peek(
+        &self,
+        session: &Session,
+        plan: mz_sql::plan::SelectPlan,
+        format: ExplainFormat,
+        config: &ExplainConfig,
+        target_cluster: TargetCluster,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let cluster = self
+            .catalog()
+            .resolve_target_cluster(target_cluster, session)?;
+        let features = OptimizerFeatures::from(self.catalog().system_config())
+            .override_from(&cluster.config.features())
+            .override_from(&config.features);
+
+        let explain = explain_plan(
+            plan.source,
+            format,
+            config,
+            &features,
+            &self.catalog().for_session(session),
+            BTreeMap::new(),
+            Some(cluster.name.as_str()),
+        )?;
+        let row = Row::pack_slice(&[Datum::from(explain.as_str())]);
+
+        Ok(Self::send_immediate_rows(row))
+    }
+
     /// Do some simple validation. We must defer most of it until after any off-thread work.
     #[instrument]
     pub fn peek_validate(
@@ -281,6 +329,7 @@ impl Coordinator {
         let view_id = self.allocate_transient_id();
         let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
             .override_from(&self.catalog.get_cluster(cluster.id()).config.features())
+            .override_from(session.vars().optimizer_feature_flags())
             .override_from(&explain_ctx);
 
         if cluster.replicas().next().is_none() && explain_ctx.needs_cluster() {
@@ -340,17 +389,21 @@ impl Coordinator {
             }
         };
 
-        let target_replica_name = session.vars().cluster_replica();
-        let mut target_replica = target_replica_name
-            .map(|name| {
-                cluster
-                    .replica_id(name)
-                    .ok_or(AdapterError::UnknownClusterReplica {
-                        cluster_name: cluster.name.clone(),
-                        replica_name: name.to_string(),
-                    })
-            })
-            .transpose()?;
+        let mut target_replica = match target_cluster {
+            TargetCluster::Replica(_, replica_id) => Some(replica_id),
+            _ => session
+                .vars()
+                .cluster_replica()
+                .map(|name| {
+                    cluster
+                        .replica_id(name)
+                        .ok_or(AdapterError::UnknownClusterReplica {
+                            cluster_name: cluster.name.clone(),
+                            replica_name: name.to_string(),
+                        })
+                })
+                .transpose()?,
+        };
 
         let source_ids = plan.source.depends_on();
         let mut timeline_context = self.validate_timeline_context(source_ids.clone())?;
@@ -395,8 +448,8 @@ impl Coordinator {
     /// Possibly linearize a timestamp from a `TimestampOracle`.
     #[instrument]
     async fn peek_linearize_timestamp(
-        &self,
-        session: &Session,
+        &mut self,
+        ctx: &mut ExecuteContext,
         PeekStageLinearizeTimestamp {
             validity,
             source_ids,
@@ -408,11 +461,48 @@ impl Coordinator {
             explain_ctx,
         }: PeekStageLinearizeTimestamp,
     ) -> Result<StageResult<Box<PeekStage>>, AdapterError> {
-        let isolation_level = session.vars().transaction_isolation().clone();
+        let isolation_level = ctx.session().vars().transaction_isolation().clone();
         let timeline = Coordinator::get_timeline(&timeline_context);
         let needs_linearized_read_ts =
             Coordinator::needs_linearized_read_ts(&isolation_level, &plan.when);
 
+        // Make it visible when the query can't provide the requested isolation level, as
+        // it's otherwise silently executed under weaker semantics.
+        let explain = matches!(
+            explain_ctx,
+            ExplainContext::Plan(_) | ExplainContext::Pushdown
+        );
+        if let Some(reason) = Coordinator::isolation_level_downgrade_reason(
+            &isolation_level,
+            &plan.when,
+            &timeline_context,
+            &source_ids,
+        )
+        .filter(|_| !explain)
+        {
+            let effective = IsolationLevel::Serializable;
+            if let Some(id) = ctx.extra().contents() {
+                self.set_statement_isolation_downgrade(id, &effective, reason);
+            }
+            ctx.session()
+                .add_notice(AdapterNotice::IsolationLevelDowngraded {
+                    isolation_level,
+                    effective,
+                    reason: reason.to_string(),
+                });
+        }
+
+        // Read-only replicas lag behind the leader, so they can't linearize
+        // reads against its writes.
+        if needs_linearized_read_ts && timeline.is_some() && self.read_only_replica {
+            return Err(AdapterError::StrictSerializableOnReadOnlyReplica);
+        }
+
+        // Reads that need the timestamp oracle would hang while it is unavailable.
+        if !explain {
+            self.check_timestamp_oracle_for_read(ctx.session(), &timeline_context, &plan.when)?;
+        }
+
         let build_stage = move |oracle_read_ts: Option<Timestamp>| PeekStageRealTimeRecency {
             validity,
             plan,
@@ -494,6 +584,21 @@ impl Coordinator {
             (&explain_ctx).into(),
         )?;
 
+        // `AS OF AT LEAST` queries may choose a timestamp that their dependencies can't read
+        // yet. Rather than installing a peek or dataflow that blocks until they can, we defer
+        // the query until its dependencies have advanced past the chosen timestamp.
+        let wait_until_readable = match (&plan.when, &explain_ctx) {
+            (
+                QueryWhen::AtLeastTimestamp(_),
+                ExplainContext::None | ExplainContext::PlanInsightsNotice(_),
+            ) if !determination.respond_immediately() => {
+                determination.timestamp_context.timestamp().cloned()
+            }
+            _ => None,
+        };
+        let readable = wait_until_readable
+            .map(|ts| self.install_peek_dependencies_watch_sets(session, &id_bundle, ts));
+
         let stage = PeekStage::Optimize(PeekStageOptimize {
             validity,
             plan,
@@ -505,7 +610,49 @@ impl Coordinator {
             optimizer,
             explain_ctx,
         });
-        Ok(StageResult::Immediate(Box::new(stage)))
+        match readable {
+            Some(readable) => {
+                let span = Span::current();
+                Ok(StageResult::Handle(mz_ore::task::spawn(
+                    || "peek wait until readable",
+                    async move {
+                        for rx in readable {
+                            // The watch sets are removed if the connection terminates.
+                            rx.await.map_err(|_| AdapterError::Canceled)?;
+                        }
+                        Ok(Box::new(stage))
+                    }
+                    .instrument(span),
+                )))
+            }
+            None => Ok(StageResult::Immediate(Box::new(stage))),
+        }
+    }
+
+    /// Installs watch sets that fire once all collections in `id_bundle` are readable at `ts`.
+    ///
+    /// Returns a receiver for each of the watch sets.
+    fn install_peek_dependencies_watch_sets(
+        &mut self,
+        session: &Session,
+        id_bundle: &CollectionIdBundle,
+        ts: Timestamp,
+    ) -> [oneshot::Receiver<()>; 2] {
+        let (storage_tx, storage_rx) = oneshot::channel();
+        self.install_storage_watch_set(
+            session.conn_id().clone(),
+            id_bundle.storage_ids.clone(),
+            ts,
+            WatchSetResponse::PeekDependenciesReadable(storage_tx),
+        );
+        let (compute_tx, compute_rx) = oneshot::channel();
+        self.install_compute_watch_set(
+            session.conn_id().clone(),
+            id_bundle.compute_ids.values().flatten().cloned().collect(),
+            ts,
+            WatchSetResponse::PeekDependenciesReadable(compute_tx),
+        );
+        [storage_rx, compute_rx]
     }
 
     #[instrument]
@@ -527,10 +674,34 @@ impl Coordinator {
         // Generate data structures that can be moved to another task where we will perform possibly
         // expensive optimizations.
         let timestamp_context = determination.timestamp_context.clone();
-        let stats = self
-            .statistics_oracle(session, &source_ids, &timestamp_context.antichain(), true)
-            .await
-            .unwrap_or_else(|_| Box::new(EmptyStatisticsOracle));
+        let estimate_ids = match &explain_ctx {
+            ExplainContext::Plan(explain_ctx) if explain_ctx.config.memory_estimates => {
+                // Arrangements can be built on top of the storage collections of views.
+                let mut ids = self.explain_storage_ids(source_ids.iter().copied());
+                ids.extend(source_ids.iter().copied());
+                ids
+            }
+            ExplainContext::Plan(explain_ctx) if explain_ctx.config.row_estimates => {
+                source_ids.clone()
+            }
+            _ => BTreeSet::new(),
+        };
+        // The statistics collected for the estimates also feed the optimizer, so that they are
+        // only collected once.
+        let (stats, row_estimates) = if estimate_ids.is_empty() {
+            let stats = self
+                .statistics_oracle(session, &source_ids, &timestamp_context.antichain(), true)
+                .await
+                .unwrap_or_else(|_| Box::new(EmptyStatisticsOracle));
+            (stats, BTreeMap::new())
+        } else {
+            let row_estimates = self
+                .explain_row_estimates(&estimate_ids, &timestamp_context.antichain())
+                .await;
+            let stats =
+                self.statistics_oracle_from_row_estimates(session, &source_ids, &row_estimates);
+            (stats, row_estimates)
+        };
         let session = session.meta();
         let now = self.catalog().config().now.clone();
         let catalog = self.owned_catalog();
@@ -544,10 +715,18 @@ impl Coordinator {
             }
         }
 
+        // The optimizer holds snapshots of the catalog until the optimization finishes.
+        let hold_timer = self
+            .metrics
+            .catalog_snapshot_hold_seconds
+            .with_label_values(&["optimize_peek"])
+            .start_timer();
+
         let span = Span::current();
         Ok(StageResult::Handle(mz_ore::task::spawn_blocking(
             || "optimize peek",
             move || {
+                let _hold_timer = hold_timer;
                 span.in_scope(|| {
                     let pipeline = || -> Result<Either<optimize::peek::GlobalLirPlan, optimize::copy_to::GlobalLirPlan>, AdapterError> {
                         let _dispatch_guard = explain_ctx.dispatch_guard();
@@ -613,16 +792,26 @@ impl Coordinator {
                             view_id: optimizer.select_id(),
                             index_id: optimizer.index_id(),
                             enable_re_optimize,
+                            slow_path_reason: global_lir_plan.slow_path_reason().cloned(),
                         });
                             match explain_ctx {
                                 ExplainContext::Plan(explain_ctx) => {
                                     let (_, df_meta, _) = global_lir_plan.unapply();
+                                    let bundle_ctx = PeekExplainBundleContext::new(
+                                        &explain_ctx,
+                                        &plan,
+                                        source_ids,
+                                        id_bundle,
+                                        determination,
+                                    );
                                     PeekStage::ExplainPlan(PeekStageExplainPlan {
                                         validity,
                                         optimizer,
                                         df_meta,
                                         explain_ctx,
                                         insights_ctx,
+                                        row_estimates,
+                                        bundle_ctx,
                                     })
                                 }
                                 ExplainContext::PlanInsightsNotice(optimizer_trace) => {
@@ -705,12 +894,21 @@ impl Coordinator {
                                 // and move to the next stage with default
                                 // parameters.
                                 tracing::error!("error while handling EXPLAIN statement: {}", err);
+                                let bundle_ctx = PeekExplainBundleContext::new(
+                                    &explain_ctx,
+                                    &plan,
+                                    source_ids,
+                                    id_bundle,
+                                    determination,
+                                );
                                 PeekStage::ExplainPlan(PeekStageExplainPlan {
                                     validity,
                                     optimizer,
                                     df_meta: Default::default(),
                                     explain_ctx,
                                     insights_ctx: None,
+                                    row_estimates,
+                                    bundle_ctx,
                             })
                             } else {
                                 // In regular `EXPLAIN` contexts, immediately retire
@@ -816,17 +1014,24 @@ impl Coordinator {
         let session = ctx.session_mut();
         let conn_id = session.conn_id().clone();
 
+        let slow_path_reason = global_lir_plan.slow_path_reason().cloned();
         let (peek_plan, df_meta, typ) = global_lir_plan.unapply();
         let source_arity = typ.arity();
 
         self.emit_optimizer_notices(&*session, &df_meta.optimizer_notices);
+        if let Some(reason) = slow_path_reason {
+            let reason = reason.humanize(&self.catalog().for_session(session));
+            session.add_notice(AdapterNotice::SlowPathPeek { reason });
+        }
 
         let target_cluster = self.catalog().get_cluster(optimizer.cluster_id());
 
         let features = OptimizerFeatures::from(self.catalog().system_config())
-            .override_from(&target_cluster.config.features());
+            .override_from(&target_cluster.config.features())
+            .override_from(session.vars().optimizer_feature_flags());
 
         if let Some(trace) = plan_insights_optimizer_trace {
+            let plan_graph = trace.plan_graph(&self.catalog().for_session(session));
             let insights = trace
                 .into_plan_insights(
                     &features,
@@ -838,6 +1043,7 @@ impl Coordinator {
                 )
                 .await?;
             session.add_notice(AdapterNotice::PlanInsights(insights));
+            self.record_plan_graph(&conn_id, plan_graph);
         }
 
         let planned_peek = PlannedPeek {
@@ -896,19 +1102,61 @@ impl Coordinator {
         }
 
         let max_result_size = self.catalog().system_config().max_result_size();
+        let database = self.database_metrics_label(ctx.session());
 
         // Implement the peek, and capture the response.
-        let resp = self
-            .implement_peek_plan(
-                ctx.extra_mut(),
-                planned_peek,
-                optimizer.finishing().clone(),
-                optimizer.cluster_id(),
-                target_replica,
-                max_result_size,
-                max_query_result_size,
-            )
-            .await?;
+        let resp = match self.take_paged_cursor(ctx.session().conn_id(), &planned_peek) {
+            Some(name) => {
+                let (session, extra) = (&ctx.session, &mut ctx.extra);
+                self.open_paged_cursor(
+                    extra,
+                    session,
+                    name,
+                    planned_peek,
+                    typ.column_types,
+                    optimizer.finishing().clone(),
+                    optimizer.cluster_id(),
+                    target_replica,
+                    max_result_size,
+                    max_query_result_size,
+                    database,
+                )
+                .await?
+            }
+            None => {
+                let mirror = plan
+                    .mirror
+                    .map(|cluster_id| (cluster_id, planned_peek.source_ids.clone()));
+                let resp = self
+                    .implement_peek_plan(
+                        ctx.extra_mut(),
+                        planned_peek,
+                        optimizer.finishing().clone(),
+                        optimizer.cluster_id(),
+                        target_replica,
+                        max_result_size,
+                        max_query_result_size,
+                        database,
+                    )
+                    .await?;
+                match mirror {
+                    Some((mirror_cluster_id, source_ids)) => {
+                        self.mirror_peek(
+                            ctx.session(),
+                            resp,
+                            mirror_cluster_id,
+                            plan.source,
+                            optimizer.finishing().clone(),
+                            source_ids,
+                            &determination,
+                            max_query_result_size,
+                        )
+                        .await
+                    }
+                    None => resp,
+                }
+            }
+        };
 
         if ctx.session().vars().emit_timestamp_notice() {
             let explanation = self.explain_timestamp(
@@ -991,12 +1239,14 @@ impl Coordinator {
 
     #[instrument]
     async fn peek_explain_plan(
-        &self,
+        &mut self,
         session: &Session,
         PeekStageExplainPlan {
             optimizer,
             insights_ctx,
             df_meta,
+            row_estimates,
+            bundle_ctx,
             explain_ctx:
                 ExplainPlanContext {
                     config,
@@ -1031,6 +1281,53 @@ impl Coordinator {
         let target_cluster = self.catalog().get_cluster(optimizer.cluster_id());
         let features = optimizer.config().features.clone();
 
+        let plan_graph = optimizer_trace.plan_graph(&expr_humanizer);
+
+        if let Some(PeekExplainBundleContext {
+            sql,
+            source_ids,
+            id_bundle,
+            determination,
+        }) = bundle_ctx
+        {
+            if format != ExplainFormat::Json {
+                coord_bail!("EXPLAIN BUNDLE only supports JSON format");
+            }
+
+            // Include the indexes the peek reads from along with the objects it
+            // depends on.
+            let dependencies = bundle::dependencies(
+                self.catalog(),
+                session,
+                source_ids.into_iter().chain(id_bundle.iter()),
+                config.redacted,
+            );
+            let timestamp =
+                self.explain_timestamp(session, optimizer.cluster_id(), &id_bundle, determination);
+            let trace = optimizer_trace.into_bundle_trace(
+                &config,
+                &features,
+                &expr_humanizer,
+                finishing,
+                Some(target_cluster),
+                df_meta,
+                row_estimates,
+            )?;
+            let bundle = ExplainBundle::new(
+                sql,
+                dependencies,
+                trace,
+                timestamp,
+                BundleCluster::from(target_cluster),
+                features.entries().into_iter().collect(),
+            );
+            let bundle = serde_json::to_string_pretty(&bundle).expect("JSON string");
+            let rows = vec![Row::pack_slice(&[Datum::from(bundle.as_str())])];
+            self.record_plan_graph(session.conn_id(), plan_graph);
+
+            return Ok(StageResult::Response(Self::send_immediate_rows(rows)));
+        }
+
         let rows = optimizer_trace
             .into_rows(
                 format,
@@ -1040,11 +1337,14 @@ impl Coordinator {
                 finishing,
                 Some(target_cluster),
                 df_meta,
+                row_estimates,
+                None,
                 stage,
                 plan::ExplaineeStatementKind::Select,
                 insights_ctx,
             )
             .await?;
+        self.record_plan_graph(session.conn_id(), plan_graph);
 
         Ok(StageResult::Response(Self::send_immediate_rows(rows)))
     }
@@ -7,22 +7,33 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use maplit::btreemap;
+use mz_expr::explain::ExplainSubscribe;
 use mz_ore::instrument;
-use mz_repr::optimize::OverrideFrom;
+use mz_repr::explain::{ExprHumanizerExt, TransientItem};
+use mz_repr::optimize::{OptimizerFeatures, OverrideFrom};
+use mz_repr::GlobalId;
 use mz_sql::plan::{self, QueryWhen};
 use mz_sql::session::metadata::SessionMetadata;
 use timely::progress::Antichain;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tracing::Span;
 
-use crate::active_compute_sink::{ActiveComputeSink, ActiveSubscribe};
+use crate::active_compute_sink::{
+    ActiveComputeSink, ActiveSubscribe, SubscribeBuffer, SubscribeRowStream,
+};
 use crate::command::ExecuteResponse;
 use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
 use crate::coord::{
-    Coordinator, Message, PlanValidity, StageResult, Staged, SubscribeFinish, SubscribeOptimizeMir,
-    SubscribeStage, SubscribeTimestampOptimizeLir, TargetCluster,
+    Coordinator, ExplainContext, ExplainPlanContext, Message, PlanValidity, StageResult, Staged,
+    SubscribeExplain, SubscribeFinish, SubscribeOptimizeMir, SubscribeStage,
+    SubscribeTimestampOptimizeLir, TargetCluster,
 };
 use crate::error::AdapterError;
+use crate::explain::optimizer_trace::OptimizerTrace;
 use crate::optimize::Optimize;
 use crate::session::{Session, TransactionOps};
 use crate::util::ResultExt;
@@ -36,6 +47,7 @@ impl Staged for SubscribeStage {
             SubscribeStage::OptimizeMir(stage) => &mut stage.validity,
             SubscribeStage::TimestampOptimizeLir(stage) => &mut stage.validity,
             SubscribeStage::Finish(stage) => &mut stage.validity,
+            SubscribeStage::Explain(stage) => &mut stage.validity,
         }
     }
 
@@ -52,6 +64,7 @@ impl Staged for SubscribeStage {
                 coord.subscribe_timestamp_optimize_lir(ctx, stage).await
             }
             SubscribeStage::Finish(stage) => coord.subscribe_finish(ctx, stage).await,
+            SubscribeStage::Explain(stage) => coord.subscribe_explain(ctx.session(), stage).await,
         }
     }
 
@@ -77,7 +90,55 @@ impl Coordinator {
         target_cluster: TargetCluster,
     ) {
         let stage = return_if_err!(
-            self.subscribe_validate(ctx.session_mut(), plan, target_cluster),
+            self.subscribe_validate(
+                ctx.session_mut(),
+                plan,
+                target_cluster,
+                ExplainContext::None
+            ),
+            ctx
+        );
+        self.sequence_staged(ctx, Span::current(), stage).await;
+    }
+
+    #[instrument]
+    pub(crate) async fn explain_subscribe(
+        &mut self,
+        mut ctx: ExecuteContext,
+        plan::ExplainPlanPlan {
+            stage,
+            format,
+            config,
+            explainee,
+        }: plan::ExplainPlanPlan,
+        target_cluster: TargetCluster,
+    ) {
+        let plan::Explainee::Statement(stmt) = explainee else {
+            // This is currently asserted in the `sequence_explain_plan` code that
+            // calls this method.
+            unreachable!()
+        };
+        let plan::ExplaineeStatement::Subscribe { broken, plan } = stmt else {
+            // This is currently asserted in the `sequence_explain_plan` code that
+            // calls this method.
+            unreachable!()
+        };
+
+        // Create an OptimizerTrace instance to collect plans emitted when
+        // executing the optimizer pipeline.
+        let optimizer_trace = OptimizerTrace::for_explain(stage, &config);
+
+        let explain_ctx = ExplainContext::Plan(ExplainPlanContext {
+            broken,
+            config,
+            format,
+            stage,
+            replan: None,
+            desc: None,
+            optimizer_trace,
+        });
+        let stage = return_if_err!(
+            self.subscribe_validate(ctx.session_mut(), plan, target_cluster, explain_ctx),
             ctx
         );
         self.sequence_staged(ctx, Span::current(), stage).await;
@@ -89,6 +150,7 @@ impl Coordinator {
         session: &mut Session,
         plan: plan::SubscribePlan,
         target_cluster: TargetCluster,
+        explain_ctx: ExplainContext,
     ) -> Result<SubscribeStage, AdapterError> {
         let plan::SubscribePlan { from, when, .. } = &plan;
 
@@ -111,8 +173,8 @@ impl Coordinator {
             .transpose()?;
 
         // SUBSCRIBE AS OF, similar to peeks, doesn't need to worry about transaction
-        // timestamp semantics.
-        if when == &QueryWhen::Immediately {
+        // timestamp semantics. An explained SUBSCRIBE doesn't run at all.
+        if when == &QueryWhen::Immediately && matches!(explain_ctx, ExplainContext::None) {
             // If this isn't a SUBSCRIBE AS OF, the SUBSCRIBE can be in a transaction if it's the
             // only operation.
             session.add_transaction_ops(TransactionOps::Subscribe)?;
@@ -153,6 +215,7 @@ impl Coordinator {
             dependency_ids: depends_on,
             cluster_id,
             replica_id,
+            explain_ctx,
         }))
     }
 
@@ -167,6 +230,7 @@ impl Coordinator {
             dependency_ids,
             cluster_id,
             replica_id,
+            explain_ctx,
         }: SubscribeOptimizeMir,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
         let plan::SubscribePlan {
@@ -188,7 +252,9 @@ impl Coordinator {
             .transpose()?;
         let debug_name = format!("subscribe-{}", sink_id);
         let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
-            .override_from(&self.catalog.get_cluster(cluster_id).config.features());
+            .override_from(&self.catalog.get_cluster(cluster_id).config.features())
+            .override_from(session.vars().optimizer_feature_flags())
+            .override_from(&explain_ctx);
 
         // Build an optimizer for this SUBSCRIBE.
         let mut optimizer = optimize::subscribe::Optimizer::new(
@@ -209,23 +275,65 @@ impl Coordinator {
             || "optimize subscribe (mir)",
             move || {
                 span.in_scope(|| {
-                    // MIR ⇒ MIR optimization (global)
-                    let global_mir_plan = optimizer.catch_unwind_optimize(plan.from.clone())?;
-                    // Add introduced indexes as validity dependencies.
-                    validity.extend_dependencies(
-                        global_mir_plan.id_bundle(optimizer.cluster_id()).iter(),
-                    );
-
-                    let stage =
-                        SubscribeStage::TimestampOptimizeLir(SubscribeTimestampOptimizeLir {
-                            validity,
-                            plan,
-                            timeline,
-                            optimizer,
-                            global_mir_plan,
-                            dependency_ids,
-                            replica_id,
-                        });
+                    let mut pipeline = || -> Result<
+                        optimize::subscribe::GlobalMirPlan<optimize::subscribe::Unresolved>,
+                        AdapterError,
+                    > {
+                        let _dispatch_guard = explain_ctx.dispatch_guard();
+
+                        // MIR ⇒ MIR optimization (global)
+                        let global_mir_plan = optimizer.catch_unwind_optimize(plan.from.clone())?;
+
+                        Ok(global_mir_plan)
+                    };
+
+                    let stage = match pipeline() {
+                        Ok(global_mir_plan) => {
+                            // Add introduced indexes as validity dependencies.
+                            validity.extend_dependencies(
+                                global_mir_plan.id_bundle(optimizer.cluster_id()).iter(),
+                            );
+
+                            SubscribeStage::TimestampOptimizeLir(SubscribeTimestampOptimizeLir {
+                                validity,
+                                plan,
+                                timeline,
+                                optimizer,
+                                global_mir_plan,
+                                dependency_ids,
+                                replica_id,
+                                explain_ctx,
+                            })
+                        }
+                        // Internal optimizer errors are handled differently
+                        // depending on the caller.
+                        Err(err) => {
+                            let ExplainContext::Plan(explain_ctx) = explain_ctx else {
+                                // In `sequence_~` contexts, immediately return the error.
+                                return Err(err);
+                            };
+
+                            if explain_ctx.broken {
+                                // In `EXPLAIN BROKEN` contexts, just log the error
+                                // and move to the explain stage with default
+                                // parameters.
+                                tracing::error!("error while handling EXPLAIN statement: {}", err);
+                                SubscribeStage::Explain(SubscribeExplain {
+                                    validity,
+                                    sink_id,
+                                    cluster_id,
+                                    plan,
+                                    df_meta: Default::default(),
+                                    explain_ctx,
+                                    subscribe: None,
+                                })
+                            } else {
+                                // In regular `EXPLAIN` contexts, immediately return the error.
+                                return Err(err);
+                            }
+                        }
+                    };
+
                     Ok(Box::new(stage))
                 })
             },
@@ -244,11 +352,18 @@ impl Coordinator {
             global_mir_plan,
             dependency_ids,
             replica_id,
+            explain_ctx,
         }: SubscribeTimestampOptimizeLir,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
-        let plan::SubscribePlan { when, .. } = &plan;
+        let plan::SubscribePlan {
+            when,
+            with_snapshot,
+            emit_progress,
+            ..
+        } = &plan;
 
         // Timestamp selection
+        self.check_timestamp_oracle_for_read(ctx.session(), &timeline, when)?;
         let oracle_read_ts = self.oracle_read_ts(ctx.session(), &timeline, when).await;
         let bundle = &global_mir_plan.id_bundle(optimizer.cluster_id());
         let (determination, read_holds) = self.determine_timestamp(
@@ -263,7 +378,7 @@ impl Coordinator {
 
         let as_of = determination.timestamp_context.timestamp_or_default();
 
-        if let Some(id) = ctx.extra().contents() {
+        if let (Some(id), ExplainContext::None) = (ctx.extra().contents(), &explain_ctx) {
             self.set_statement_execution_timestamp(id, as_of);
         }
         if let Some(up_to) = optimizer.up_to() {
@@ -275,7 +390,25 @@ impl Coordinator {
             }
         }
 
-        self.store_transaction_read_holds(ctx.session(), read_holds);
+        let subscribe = match &explain_ctx {
+            ExplainContext::Plan(_) => {
+                // An explained SUBSCRIBE doesn't ship a dataflow, so it doesn't
+                // need to hold back the compaction of its inputs.
+                drop(read_holds);
+                Some(ExplainSubscribe {
+                    as_of,
+                    since: determination.since.elements().to_vec(),
+                    upper: determination.upper.elements().to_vec(),
+                    up_to: optimizer.up_to(),
+                    with_snapshot: *with_snapshot,
+                    emit_progress: *emit_progress,
+                })
+            }
+            _ => {
+                self.store_transaction_read_holds(ctx.session(), read_holds);
+                None
+            }
+        };
 
         let global_mir_plan = global_mir_plan.resolve(Antichain::from_elem(as_of));
 
@@ -285,18 +418,70 @@ impl Coordinator {
             || "optimize subscribe (lir)",
             move || {
                 span.in_scope(|| {
-                    // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
-                    let global_lir_plan =
-                        optimizer.catch_unwind_optimize(global_mir_plan.clone())?;
-
-                    let stage = SubscribeStage::Finish(SubscribeFinish {
-                        validity,
-                        cluster_id: optimizer.cluster_id(),
-                        plan,
-                        global_lir_plan,
-                        dependency_ids,
-                        replica_id,
-                    });
+                    let mut pipeline =
+                        || -> Result<optimize::subscribe::GlobalLirPlan, AdapterError> {
+                            let _dispatch_guard = explain_ctx.dispatch_guard();
+
+                            // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+                            let global_lir_plan =
+                                optimizer.catch_unwind_optimize(global_mir_plan.clone())?;
+
+                            Ok(global_lir_plan)
+                        };
+
+                    let stage = match pipeline() {
+                        Ok(global_lir_plan) => {
+                            if let ExplainContext::Plan(explain_ctx) = explain_ctx {
+                                let (_, df_meta) = global_lir_plan.unapply();
+                                SubscribeStage::Explain(SubscribeExplain {
+                                    validity,
+                                    sink_id: optimizer.sink_id(),
+                                    cluster_id: optimizer.cluster_id(),
+                                    plan,
+                                    df_meta,
+                                    explain_ctx,
+                                    subscribe,
+                                })
+                            } else {
+                                SubscribeStage::Finish(SubscribeFinish {
+                                    validity,
+                                    cluster_id: optimizer.cluster_id(),
+                                    plan,
+                                    global_lir_plan,
+                                    dependency_ids,
+                                    replica_id,
+                                })
+                            }
+                        }
+                        // Internal optimizer errors are handled differently
+                        // depending on the caller.
+                        Err(err) => {
+                            let ExplainContext::Plan(explain_ctx) = explain_ctx else {
+                                // In `sequence_~` contexts, immediately return the error.
+                                return Err(err);
+                            };
+
+                            if explain_ctx.broken {
+                                // In `EXPLAIN BROKEN` contexts, just log the error
+                                // and move to the explain stage with default
+                                // parameters.
+                                tracing::error!("error while handling EXPLAIN statement: {}", err);
+                                SubscribeStage::Explain(SubscribeExplain {
+                                    validity,
+                                    sink_id: optimizer.sink_id(),
+                                    cluster_id: optimizer.cluster_id(),
+                                    plan,
+                                    df_meta: Default::default(),
+                                    explain_ctx,
+                                    subscribe,
+                                })
+                            } else {
+                                // In regular `EXPLAIN` contexts, immediately return the error.
+                                return Err(err);
+                            }
+                        }
+                    };
+
                     Ok(Box::new(stage))
                 })
             },
@@ -315,6 +500,8 @@ impl Coordinator {
                     copy_to,
                     emit_progress,
                     output,
+                    archive,
+                    deduplicate,
                     ..
                 },
             global_lir_plan,
@@ -323,21 +510,40 @@ impl Coordinator {
         }: SubscribeFinish,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
         let sink_id = global_lir_plan.sink_id();
+        let as_of = global_lir_plan
+            .as_of()
+            .expect("set to Some in an earlier stage");
 
         let (tx, rx) = mpsc::unbounded_channel();
+        let buffer = Arc::new(SubscribeBuffer::new(sink_id, self.internal_cmd_tx.clone()));
+        let mut rx = SubscribeRowStream::new(rx, Arc::clone(&buffer));
+        let mut complete_tx = None;
+        // A `SUBSCRIBE ... INTO` archives its rows instead of returning them, and needs their
+        // progress to do so.
+        if let Some(archive) = archive {
+            let uri = self.eval_subscribe_archive_uri(ctx.session(), archive.to.clone())?;
+            let (tx, complete_rx) = oneshot::channel();
+            complete_tx = Some(tx);
+            rx = self.spawn_subscribe_archive(sink_id, archive, uri, as_of, rx, complete_rx)?;
+        }
         let active_subscribe = ActiveSubscribe {
             conn_id: ctx.session().conn_id().clone(),
             session_uuid: ctx.session().uuid(),
             channel: tx,
-            emit_progress,
-            as_of: global_lir_plan
-                .as_of()
-                .expect("set to Some in an earlier stage"),
+            emit_progress: emit_progress || complete_tx.is_some(),
+            as_of,
             arity: global_lir_plan.sink_desc().from_desc.arity(),
             cluster_id,
             depends_on: dependency_ids,
             start_time: self.now(),
             output,
+            deduplicate,
+            rows_emitted: 0,
+            bytes_emitted: 0,
+            buffer,
+            paused: false,
+            reported_buffer: None,
+            complete_tx,
         };
         active_subscribe.initialize();
 
@@ -386,4 +592,75 @@ impl Coordinator {
         };
         Ok(StageResult::Response(resp))
     }
+
+    #[instrument]
+    async fn subscribe_explain(
+        &mut self,
+        session: &Session,
+        SubscribeExplain {
+            sink_id,
+            cluster_id,
+            plan,
+            df_meta,
+            explain_ctx:
+                ExplainPlanContext {
+                    config,
+                    format,
+                    stage,
+                    optimizer_trace,
+                    ..
+                },
+            subscribe,
+            ..
+        }: SubscribeExplain,
+    ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
+        let session_catalog = self.catalog().for_session(session);
+        let expr_humanizer = {
+            let transient_items = match &plan.from {
+                plan::SubscribeFrom::Query { desc, .. } => btreemap! {
+                    sink_id => TransientItem::new(
+                        Some(vec![GlobalId::Explain.to_string()]),
+                        Some(desc.iter_names().map(|c| c.to_string()).collect()),
+                    )
+                },
+                // A SUBSCRIBE to a named object doesn't build a plan of its own.
+                plan::SubscribeFrom::Id(_) => BTreeMap::new(),
+            };
+            ExprHumanizerExt::new(transient_items, &session_catalog)
+        };
+
+        let target_cluster = self.catalog().get_cluster(cluster_id);
+
+        let features = OptimizerFeatures::from(self.catalog().system_config())
+            .override_from(&target_cluster.config.features())
+            .override_from(&config.features);
+
+        let cardinality_stats = if config.memory_estimates || config.row_estimates {
+            self.explain_latest_row_estimates(plan.from.depends_on())
+                .await
+        } else {
+            BTreeMap::new()
+        };
+
+        let plan_graph = optimizer_trace.plan_graph(&expr_humanizer);
+        let rows = optimizer_trace
+            .into_rows(
+                format,
+                &config,
+                &features,
+                &expr_humanizer,
+                None,
+                Some(target_cluster),
+                df_meta,
+                cardinality_stats,
+                subscribe,
+                stage,
+                plan::ExplaineeStatementKind::Subscribe,
+                None,
+            )
+            .await?;
+        self.record_plan_graph(session.conn_id(), plan_graph);
+
+        Ok(StageResult::Response(Self::send_immediate_rows(rows)))
+    }
 }
@@ -0,0 +1,673 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A deterministic simulation harness for the coordinator's state machine.
+//!
+//! In production the coordinator's main loop pulls messages from many sources at once: client
+//! commands, controller responses, timers and internal tasks. The order in which they arrive is
+//! up to the scheduler, which makes bugs that only surface under some interleavings hard to
+//! reproduce.
+//!
+//! The harness replaces that loop. A [`Script`] holds one queue of scripted steps per actor (a
+//! client connection, the controller, a timer, ...), and a schedule decides which actor takes the
+//! next step. [`run`] drives a [`StateMachine`] one step at a time and checks its invariants after
+//! every step, so that a violation is reported together with the exact trace that caused it.
+//! Schedules are plain lists of actor indexes, which makes them easy to enumerate exhaustively
+//! with [`interleavings`] or to generate with `proptest`.
+//!
+//! [`simulate`] runs a script against a live [`Coordinator`]. Its actors are simulated client
+//! connections, the controller, the internal tasks that report back to the coordinator and the
+//! timers of the main loop, and each step is a [`SimulationStep`] that hands the coordinator a
+//! single [`Message`]. The invariants are the ones of [`Coordinator::check_consistency`], like
+//! read holds and watch sets belonging to active connections and pending peeks not outliving
+//! their connections. While a simulation runs, the coordinator doesn't process anything else:
+//! commands of real clients and other inputs of the main loop wait until it finishes.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Debug;
+use std::time::Duration;
+
+use futures::future::LocalBoxFuture;
+use futures::FutureExt;
+use mz_adapter_types::connection::ConnectionId;
+use mz_ore::collections::CollectionExt;
+use mz_ore::now::to_datetime;
+use mz_ore::tracing::OpenTelemetryContext;
+use mz_pgwire_common::Format;
+use mz_sql::session::user::SYSTEM_USER;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{info_span, Instrument};
+
+use crate::command::{Command, ExecuteResponse, Response, StartupResponse};
+use crate::coord::consistency::CoordinatorInconsistencies;
+use crate::coord::read_policy::ReadHoldsInner;
+use crate::coord::{Coordinator, Message, PendingReadTxn};
+use crate::error::AdapterError;
+use crate::session::{EndTransactionAction, Session, SessionConfig};
+use mz_repr::Timestamp;
+
+/// A state machine that can be driven one step at a time.
+pub(crate) trait StateMachine {
+    /// The inputs of the state machine.
+    type Step: Debug;
+    /// A description of the invariants that don't hold.
+    type Violation: Debug;
+
+    /// Applies a single step to the state machine.
+    fn step(&mut self, step: Self::Step) -> LocalBoxFuture<'_, ()>;
+
+    /// Checks that the invariants of the state machine hold.
+    fn check_invariants(&self) -> Result<(), Self::Violation>;
+}
+
+impl StateMachine for Coordinator {
+    type Step = Message;
+    type Violation = CoordinatorInconsistencies;
+
+    fn step(&mut self, msg: Message) -> LocalBoxFuture<'_, ()> {
+        let span = tracing::info_span!("coord::simulation::step", kind = msg.kind());
+        self.handle_message(span, msg)
+    }
+
+    fn check_invariants(&self) -> Result<(), CoordinatorInconsistencies> {
+        self.check_consistency()
+    }
+}
+
+/// The scripted steps of each actor of a simulation, in the order each actor takes them.
+#[derive(Debug)]
+pub(crate) struct Script<S> {
+    actors: Vec<VecDeque<S>>,
+}
+
+impl<S> Script<S> {
+    /// Creates a script from the steps of each actor.
+    pub(crate) fn new(actors: Vec<Vec<S>>) -> Self {
+        Script {
+            actors: actors.into_iter().map(VecDeque::from).collect(),
+        }
+    }
+
+    /// Returns the number of steps of each actor.
+    pub(crate) fn lens(&self) -> Vec<usize> {
+        self.actors.iter().map(|steps| steps.len()).collect()
+    }
+
+    /// Removes and returns the next step of `actor`, if it has any left.
+    fn next(&mut self, actor: usize) -> Option<S> {
+        self.actors.get_mut(actor)?.pop_front()
+    }
+}
+
+/// A violation of the invariants of a state machine during a simulation.
+#[derive(Debug, Serialize)]
+pub struct SimulationFailure<V> {
+    /// The steps that were applied, in order, including the step that caused the violation.
+    pub trace: Vec<(usize, String)>,
+    /// The invariants that don't hold.
+    pub violation: V,
+}
+
+/// Runs `script` against `machine`, checking its invariants before the first step and after
+/// every step. Returns the steps that were applied, in order.
+///
+/// Each entry of `schedule` is the index of the actor that takes the next step. Entries of
+/// unknown actors or of actors that have no steps left are skipped, so that any list of indexes
+/// is a valid schedule. Once the schedule is exhausted, the remaining steps are taken one actor
+/// after the other.
+pub(crate) async fn run<M: StateMachine>(
+    machine: &mut M,
+    mut script: Script<M::Step>,
+    schedule: &[usize],
+) -> Result<Vec<(usize, String)>, SimulationFailure<M::Violation>> {
+    let mut trace = Vec::new();
+    if let Err(violation) = machine.check_invariants() {
+        return Err(SimulationFailure { trace, violation });
+    }
+
+    let remaining = script
+        .lens()
+        .into_iter()
+        .enumerate()
+        .flat_map(|(actor, len)| std::iter::repeat(actor).take(len))
+        .collect::<Vec<_>>();
+    for actor in schedule.iter().chain(remaining.iter()).copied() {
+        let Some(step) = script.next(actor) else {
+            continue;
+        };
+        trace.push((actor, format!("{step:?}")));
+        machine.step(step).await;
+        if let Err(violation) = machine.check_invariants() {
+            return Err(SimulationFailure { trace, violation });
+        }
+    }
+    Ok(trace)
+}
+
+/// How long a step waits for the input it consumes before it's skipped.
+const STEP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A step of a simulation against a live [`Coordinator`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationStep {
+    /// Starts up simulated connection `conn`, as the system user.
+    Startup { conn: usize },
+    /// Executes `sql`, a single statement, on connection `conn`.
+    ///
+    /// Like a real client, a connection waits for the response to its previous statement before
+    /// it sends the next one, so the step is skipped if that response hasn't arrived yet.
+    /// Statements that fail to plan leave the connection idle.
+    Execute { conn: usize, sql: String },
+    /// Terminates connection `conn`, whether or not it's waiting for a response.
+    Terminate { conn: usize },
+    /// Processes the next response of the controller, if it becomes ready in time.
+    Controller,
+    /// Handles the next message of an internal task, if one arrives in time.
+    Internal,
+    /// Linearizes the strict serializable reads that are waiting for the timestamp oracle.
+    LinearizeReads,
+    /// Releases the read holds that have been dropped.
+    DropReadHolds,
+    /// Fires the timer that initiates a group commit and advances timelines.
+    GroupCommit,
+}
+
+impl SimulationStep {
+    /// Returns the simulated connection that takes the step, if any.
+    pub fn conn(&self) -> Option<usize> {
+        match self {
+            SimulationStep::Startup { conn }
+            | SimulationStep::Execute { conn, .. }
+            | SimulationStep::Terminate { conn } => Some(*conn),
+            SimulationStep::Controller
+            | SimulationStep::Internal
+            | SimulationStep::LinearizeReads
+            | SimulationStep::DropReadHolds
+            | SimulationStep::GroupCommit => None,
+        }
+    }
+}
+
+/// The result of a simulation against a live [`Coordinator`].
+pub type SimulationResult =
+    Result<Vec<(usize, String)>, SimulationFailure<CoordinatorInconsistencies>>;
+
+/// The inputs of the main loop that simulation steps consume.
+pub(crate) struct Inputs<'a> {
+    pub(crate) internal_cmd_rx: &'a mut mpsc::UnboundedReceiver<Message>,
+    pub(crate) strict_serializable_reads_rx:
+        &'a mut mpsc::UnboundedReceiver<(ConnectionId, PendingReadTxn)>,
+    pub(crate) dropped_read_holds_rx: &'a mut mpsc::UnboundedReceiver<ReadHoldsInner<Timestamp>>,
+}
+
+/// The protocol state of a simulated connection.
+enum SimulatedConn {
+    /// Waiting for the response to its startup.
+    Starting {
+        session: Session,
+        rx: oneshot::Receiver<Result<StartupResponse, AdapterError>>,
+    },
+    /// Waiting for the response to a statement.
+    Executing(oneshot::Receiver<Response<ExecuteResponse>>),
+    /// Waiting for the response to the end of the implicit transaction of a statement.
+    Committing(oneshot::Receiver<Response<ExecuteResponse>>),
+    /// Ready to execute a statement.
+    Idle(Session),
+    Terminated,
+}
+
+/// Drives a live [`Coordinator`] with [`SimulationStep`]s.
+struct Simulator<'a, 'b> {
+    coord: &'a mut Coordinator,
+    inputs: Inputs<'b>,
+    conn_ids: Vec<ConnectionId>,
+    conns: BTreeMap<usize, SimulatedConn>,
+}
+
+impl StateMachine for Simulator<'_, '_> {
+    type Step = SimulationStep;
+    type Violation = CoordinatorInconsistencies;
+
+    fn step(&mut self, step: SimulationStep) -> LocalBoxFuture<'_, ()> {
+        self.apply(step).boxed_local()
+    }
+
+    fn check_invariants(&self) -> Result<(), CoordinatorInconsistencies> {
+        self.coord.check_consistency()
+    }
+}
+
+impl Simulator<'_, '_> {
+    async fn apply(&mut self, step: SimulationStep) {
+        match step {
+            SimulationStep::Startup { conn } => {
+                let Some(conn_id) = self.conn_ids.get(conn).cloned() else {
+                    return;
+                };
+                if self.conns.contains_key(&conn) {
+                    return;
+                }
+                let session = Session::new(
+                    self.coord.catalog().config().build_info,
+                    SessionConfig {
+                        conn_id: conn_id.clone(),
+                        user: SYSTEM_USER.name.clone(),
+                        external_metadata_rx: None,
+                    },
+                    self.coord.metrics.session_metrics(),
+                );
+                let (tx, rx) = oneshot::channel();
+                self.command(Command::Startup {
+                    tx,
+                    user: session.user().clone(),
+                    conn_id,
+                    secret_key: session.secret_key(),
+                    uuid: session.uuid(),
+                    application_name: session.application_name().into(),
+                    notice_tx: session.retain_notice_transmitter(),
+                })
+                .await;
+                self.conns
+                    .insert(conn, SimulatedConn::Starting { session, rx });
+            }
+            SimulationStep::Execute { conn, sql } => {
+                let Some(mut session) = self.idle_session(conn).await else {
+                    return;
+                };
+                if let Err(e) = self.declare(&mut session, sql) {
+                    tracing::info!(conn, "simulated statement failed to plan: {e}");
+                    self.conns.insert(conn, SimulatedConn::Idle(session));
+                    return;
+                }
+                let (tx, rx) = oneshot::channel();
+                self.command(Command::Execute {
+                    portal_name: String::new(),
+                    session,
+                    tx,
+                    outer_ctx_extra: None,
+                })
+                .await;
+                self.conns.insert(conn, SimulatedConn::Executing(rx));
+            }
+            SimulationStep::Terminate { conn } => {
+                if matches!(
+                    self.conns.get(&conn),
+                    None | Some(SimulatedConn::Terminated)
+                ) {
+                    return;
+                }
+                self.terminate(conn).await;
+            }
+            SimulationStep::Controller => {
+                if tokio::time::timeout(STEP_TIMEOUT, self.coord.controller.ready())
+                    .await
+                    .is_ok()
+                {
+                    self.message(Message::ControllerReady).await;
+                }
+            }
+            SimulationStep::Internal => {
+                if let Ok(Some(msg)) =
+                    tokio::time::timeout(STEP_TIMEOUT, self.inputs.internal_cmd_rx.recv()).await
+                {
+                    self.message(msg).await;
+                }
+            }
+            SimulationStep::LinearizeReads => {
+                while let Ok((conn_id, pending_read_txn)) =
+                    self.inputs.strict_serializable_reads_rx.try_recv()
+                {
+                    self.coord
+                        .pending_linearize_read_txns
+                        .insert(conn_id, pending_read_txn);
+                }
+                self.message(Message::LinearizeReads).await;
+            }
+            SimulationStep::DropReadHolds => {
+                let mut dropped_read_holds = Vec::new();
+                while let Ok(dropped_read_hold) = self.inputs.dropped_read_holds_rx.try_recv() {
+                    dropped_read_holds.push(dropped_read_hold);
+                }
+                self.message(Message::DropReadHolds(dropped_read_holds))
+                    .await;
+            }
+            SimulationStep::GroupCommit => {
+                let span = info_span!(parent: None, "coord::simulation::group_commit");
+                self.message(Message::GroupCommitInitiate(span, None)).await;
+            }
+        }
+    }
+
+    /// Binds `sql` to the unnamed portal of `session` and starts its implicit transaction, like
+    /// [`crate::SessionClient::declare`] and the simple query protocol do.
+    fn declare(&self, session: &mut Session, sql: String) -> Result<(), AdapterError> {
+        let stmt = mz_sql::parse::parse(&sql)?.into_element().ast;
+        let catalog = self.coord.owned_catalog();
+        let desc = Coordinator::describe(&catalog, session, Some(stmt.clone()), vec![])?;
+        let result_formats = vec![Format::Text; desc.arity()];
+        let now = self.coord.now();
+        let logging = session.mint_logging(sql, Some(&stmt), now);
+        session.set_portal(
+            String::new(),
+            desc,
+            Some(stmt),
+            logging,
+            vec![],
+            result_formats,
+            catalog.transient_revision(),
+        )?;
+        session.start_transaction_implicit(to_datetime(now), 1);
+        Ok(())
+    }
+
+    /// Moves connection `conn` along its protocol as far as the responses it has received
+    /// allow. Returns its session, taking it out of the simulation, if the connection is idle.
+    async fn idle_session(&mut self, conn: usize) -> Option<Session> {
+        loop {
+            match self.conns.remove(&conn)? {
+                SimulatedConn::Starting {
+                    mut session,
+                    mut rx,
+                } => match rx.try_recv() {
+                    Ok(Ok(response)) => {
+                        session.initialize_role_metadata(response.role_id);
+                        let vars = session.vars_mut();
+                        for (name, val) in response.session_defaults {
+                            if let Err(err) = vars.set_default(&name, val.borrow()) {
+                                tracing::error!("failed to set peristed default, {err:?}");
+                            }
+                        }
+                        vars.end_transaction(EndTransactionAction::Commit);
+                        self.conns.insert(conn, SimulatedConn::Idle(session));
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => {
+                        self.conns
+                            .insert(conn, SimulatedConn::Starting { session, rx });
+                        return None;
+                    }
+                    Ok(Err(_)) | Err(oneshot::error::TryRecvError::Closed) => {
+                        self.conns.insert(conn, SimulatedConn::Terminated);
+                        return None;
+                    }
+                },
+                SimulatedConn::Executing(mut rx) => match rx.try_recv() {
+                    Ok(response) => {
+                        let (tx, rx) = oneshot::channel();
+                        self.command(Command::Commit {
+                            action: EndTransactionAction::Commit,
+                            session: response.session,
+                            tx,
+                        })
+                        .await;
+                        self.conns.insert(conn, SimulatedConn::Committing(rx));
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => {
+                        self.conns.insert(conn, SimulatedConn::Executing(rx));
+                        return None;
+                    }
+                    Err(oneshot::error::TryRecvError::Closed) => {
+                        self.conns.insert(conn, SimulatedConn::Terminated);
+                        return None;
+                    }
+                },
+                SimulatedConn::Committing(mut rx) => match rx.try_recv() {
+                    Ok(response) => {
+                        self.conns
+                            .insert(conn, SimulatedConn::Idle(response.session));
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => {
+                        self.conns.insert(conn, SimulatedConn::Committing(rx));
+                        return None;
+                    }
+                    Err(oneshot::error::TryRecvError::Closed) => {
+                        self.conns.insert(conn, SimulatedConn::Terminated);
+                        return None;
+                    }
+                },
+                SimulatedConn::Idle(session) => return Some(session),
+                SimulatedConn::Terminated => {
+                    self.conns.insert(conn, SimulatedConn::Terminated);
+                    return None;
+                }
+            }
+        }
+    }
+
+    async fn terminate(&mut self, conn: usize) {
+        let conn_id = self.conn_ids[conn].clone();
+        self.command(Command::Terminate { conn_id, tx: None }).await;
+        self.conns.insert(conn, SimulatedConn::Terminated);
+    }
+
+    async fn command(&mut self, cmd: Command) {
+        self.message(Message::Command(OpenTelemetryContext::obtain(), cmd))
+            .await;
+    }
+
+    async fn message(&mut self, msg: Message) {
+        let span = info_span!("coord::simulation::step", kind = msg.kind());
+        self.coord.handle_message(span, msg).await;
+    }
+}
+
+/// Runs `script` against `coord`, with one simulated connection per entry of `conn_ids`, and
+/// terminates the connections that are still open once the script is done.
+pub(crate) async fn simulate(
+    coord: &mut Coordinator,
+    inputs: Inputs<'_>,
+    conn_ids: Vec<ConnectionId>,
+    script: Vec<Vec<SimulationStep>>,
+    schedule: Vec<usize>,
+) -> Result<SimulationResult, AdapterError> {
+    if !coord.catalog().config().unsafe_mode {
+        return Err(AdapterError::Unsupported(
+            "coordinator simulations outside of unsafe mode",
+        ));
+    }
+    let mut simulator = Simulator {
+        coord,
+        inputs,
+        conn_ids,
+        conns: BTreeMap::new(),
+    };
+    let result = run(&mut simulator, Script::new(script), &schedule)
+        .instrument(info_span!("coord::simulation::run"))
+        .await;
+    let open = simulator
+        .conns
+        .iter()
+        .filter(|(_, state)| !matches!(state, SimulatedConn::Terminated))
+        .map(|(conn, _)| *conn)
+        .collect::<Vec<_>>();
+    for conn in open {
+        simulator.terminate(conn).await;
+    }
+    Ok(result)
+}
+
+/// Returns every schedule that interleaves actors with `lens` steps each, preserving the order of
+/// the steps of each actor.
+pub(crate) fn interleavings(lens: &[usize]) -> Vec<Vec<usize>> {
+    fn go(remaining: &mut [usize], prefix: &mut Vec<usize>, schedules: &mut Vec<Vec<usize>>) {
+        if remaining.iter().all(|len| *len == 0) {
+            schedules.push(prefix.clone());
+            return;
+        }
+        for actor in 0..remaining.len() {
+            if remaining[actor] > 0 {
+                remaining[actor] -= 1;
+                prefix.push(actor);
+                go(remaining, prefix, schedules);
+                prefix.pop();
+                remaining[actor] += 1;
+            }
+        }
+    }
+
+    let mut schedules = Vec::new();
+    go(&mut lens.to_vec(), &mut Vec::new(), &mut schedules);
+    schedules
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use futures::executor::block_on;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A model of the coordinator's bookkeeping of pending peeks.
+    #[derive(Debug, Default)]
+    struct Peeks {
+        active_conns: BTreeSet<u32>,
+        pending_peeks: BTreeMap<u32, u32>,
+        client_pending_peeks: BTreeMap<u32, BTreeSet<u32>>,
+        /// Whether terminating a connection forgets to cancel its pending peeks.
+        leak_on_terminate: bool,
+    }
+
+    #[derive(Debug)]
+    enum PeekStep {
+        Connect(u32),
+        Peek { conn: u32, peek: u32 },
+        Respond(u32),
+        Terminate(u32),
+    }
+
+    impl StateMachine for Peeks {
+        type Step = PeekStep;
+        type Violation = String;
+
+        fn step(&mut self, step: PeekStep) -> LocalBoxFuture<'_, ()> {
+            match step {
+                PeekStep::Connect(conn) => {
+                    self.active_conns.insert(conn);
+                }
+                PeekStep::Peek { conn, peek } => {
+                    if self.active_conns.contains(&conn) {
+                        self.pending_peeks.insert(peek, conn);
+                        self.client_pending_peeks
+                            .entry(conn)
+                            .or_default()
+                            .insert(peek);
+                    }
+                }
+                PeekStep::Respond(peek) => {
+                    if let Some(conn) = self.pending_peeks.remove(&peek) {
+                        let peeks = self.client_pending_peeks.get_mut(&conn).expect("tracked");
+                        peeks.remove(&peek);
+                        if peeks.is_empty() {
+                            self.client_pending_peeks.remove(&conn);
+                        }
+                    }
+                }
+                PeekStep::Terminate(conn) => {
+                    self.active_conns.remove(&conn);
+                    if !self.leak_on_terminate {
+                        for peek in self.client_pending_peeks.remove(&conn).unwrap_or_default() {
+                            self.pending_peeks.remove(&peek);
+                        }
+                    }
+                }
+            }
+            Box::pin(async {})
+        }
+
+        fn check_invariants(&self) -> Result<(), String> {
+            for (peek, conn) in &self.pending_peeks {
+                if !self.active_conns.contains(conn) {
+                    return Err(format!("peek {peek} outlived connection {conn}"));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// A client that peeks, and a controller that responds to the peek.
+    fn peek_script() -> Script<PeekStep> {
+        Script::new(vec![
+            vec![
+                PeekStep::Connect(1),
+                PeekStep::Peek { conn: 1, peek: 10 },
+                PeekStep::Terminate(1),
+            ],
+            vec![PeekStep::Respond(10)],
+        ])
+    }
+
+    #[mz_ore::test]
+    fn test_interleavings() {
+        assert_eq!(interleavings(&[]), vec![Vec::<usize>::new()]);
+        assert_eq!(interleavings(&[2]), vec![vec![0, 0]]);
+        assert_eq!(
+            interleavings(&[2, 1]),
+            vec![vec![0, 0, 1], vec![0, 1, 0], vec![1, 0, 0]]
+        );
+        // Three steps and two steps can be interleaved in (3 + 2)! / (3! * 2!) ways.
+        assert_eq!(interleavings(&[3, 2]).len(), 10);
+    }
+
+    #[mz_ore::test]
+    fn test_run_all_interleavings() {
+        for schedule in interleavings(&peek_script().lens()) {
+            let mut peeks = Peeks::default();
+            block_on(run(&mut peeks, peek_script(), &schedule)).expect("invariants hold");
+            assert!(peeks.pending_peeks.is_empty());
+            assert!(peeks.client_pending_peeks.is_empty());
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_run_reports_violation() {
+        // The leak doesn't surface if the controller responds between the peek and the
+        // termination of the connection.
+        let mut failures = 0;
+        for schedule in interleavings(&peek_script().lens()) {
+            let mut peeks = Peeks {
+                leak_on_terminate: true,
+                ..Default::default()
+            };
+            if let Err(failure) = block_on(run(&mut peeks, peek_script(), &schedule)) {
+                failures += 1;
+                assert_eq!(failure.violation, "peek 10 outlived connection 1");
+                assert_eq!(failure.trace.last(), Some(&(0, "Terminate(1)".to_string())));
+            }
+        }
+        assert_eq!(failures, 3);
+    }
+
+    proptest! {
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // slow
+        fn proptest_peeks_do_not_leak(schedule in proptest::collection::vec(0..3usize, 0..12)) {
+            let script = Script::new(vec![
+                vec![
+                    PeekStep::Connect(1),
+                    PeekStep::Peek { conn: 1, peek: 10 },
+                    PeekStep::Terminate(1),
+                ],
+                vec![
+                    PeekStep::Connect(2),
+                    PeekStep::Peek { conn: 2, peek: 20 },
+                    PeekStep::Peek { conn: 2, peek: 21 },
+                    PeekStep::Terminate(2),
+                ],
+                vec![PeekStep::Respond(10), PeekStep::Respond(21), PeekStep::Respond(20)],
+            ]);
+            let mut peeks = Peeks::default();
+            let result = block_on(run(&mut peeks, script, &schedule));
+            prop_assert!(result.is_ok(), "{result:?}");
+            prop_assert!(peeks.pending_peeks.is_empty());
+        }
+    }
+}
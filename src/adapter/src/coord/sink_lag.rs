@@ -0,0 +1,226 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Lag of sinks behind their upstream relations.
+//!
+//! Every `sink_lag_interval`, the coordinator records in `mz_internal.mz_sink_lag` the write
+//! frontier of the relation that each sink exports, the frontier up to which the sink has
+//! committed its output, and the difference between the two. When the lag of a sink starts to
+//! exceed `sink_lag_notice_threshold`, the coordinator sends a notice to the sessions of the
+//! sink's owner. It sends the next notice for that sink only after its lag has dropped below the
+//! threshold again.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mz_adapter_types::dyncfgs::{SINK_LAG_INTERVAL, SINK_LAG_NOTICE_THRESHOLD};
+use mz_repr::{GlobalId, Timestamp};
+use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
+
+use crate::coord::{Coordinator, Message};
+use crate::notice::AdapterNotice;
+use crate::util::diff_keyed_rows;
+
+/// A row of `mz_sink_lag`.
+#[derive(Clone, Debug, PartialEq)]
+struct SinkLagRow {
+    /// The write frontier of the sink's upstream relation, or `None` if it is empty.
+    upstream_frontier: Option<Timestamp>,
+    /// The frontier up to which the sink has committed its output, or `None` if it is empty.
+    write_frontier: Option<Timestamp>,
+    /// How far the sink trails its upstream relation, or `None` if unknown.
+    lag: Option<Duration>,
+}
+
+impl SinkLagRow {
+    /// Computes the row of a sink with write frontier `write` whose upstream relation has write
+    /// frontier `upstream`.
+    fn new(upstream: &Antichain<Timestamp>, write: &Antichain<Timestamp>) -> Self {
+        let upstream_frontier = upstream.as_option().copied();
+        let write_frontier = write.as_option().copied();
+        let lag = match (upstream_frontier, write_frontier) {
+            // A sink that has not reported any progress yet has no meaningful lag.
+            (Some(_), Some(write)) if write == Timestamp::minimum() => None,
+            (Some(upstream), Some(write)) => Some(Duration::from_millis(
+                u64::from(upstream).saturating_sub(u64::from(write)),
+            )),
+            // A sink whose upstream relation is complete can't catch up to it, and a complete
+            // sink can't fall behind.
+            _ => None,
+        };
+        SinkLagRow {
+            upstream_frontier,
+            write_frontier,
+            lag,
+        }
+    }
+}
+
+/// The rows last recorded in `mz_sink_lag`.
+#[derive(Debug, Default)]
+pub(crate) struct SinkLag {
+    reported: BTreeMap<GlobalId, SinkLagRow>,
+    /// The sinks whose lag exceeded `sink_lag_notice_threshold` when it was last recorded.
+    exceeding: BTreeSet<GlobalId>,
+}
+
+impl Coordinator {
+    /// Schedules the next refresh of `mz_sink_lag`, `sink_lag_interval` from now.
+    pub(crate) fn schedule_sink_lag_report(&self) {
+        let interval = SINK_LAG_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message("sink_lag_report", interval, Message::SinkLagReport);
+    }
+
+    /// Records the lag of each sink in `mz_sink_lag`, if enabled, notifies sessions of the sinks
+    /// whose lag started to exceed the notice threshold, and schedules the next refresh.
+    pub(crate) fn report_sink_lag(&mut self) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let interval = SINK_LAG_INTERVAL.get(dyncfgs);
+        let threshold = SINK_LAG_NOTICE_THRESHOLD.get(dyncfgs);
+
+        if interval.is_zero() {
+            self.replace_sink_lag(BTreeMap::new());
+            self.sink_lag.exceeding.clear();
+        } else {
+            let sinks: Vec<_> = self
+                .catalog()
+                .entries()
+                .filter_map(|entry| entry.sink().map(|sink| (entry.id(), sink)))
+                .map(|(sink_id, sink)| {
+                    let compute_cluster =
+                        sink.connection.is_compute_sink().then_some(sink.cluster_id);
+                    (sink_id, sink.from, compute_cluster)
+                })
+                .collect();
+            let rows = sinks
+                .into_iter()
+                .filter_map(|(sink_id, from_id, compute_cluster)| {
+                    // Sinks whose collections the controller doesn't know about yet, or anymore,
+                    // are left out until the next refresh.
+                    let write = match compute_cluster {
+                        Some(cluster_id) => self
+                            .controller
+                            .compute
+                            .collection(cluster_id, sink_id)
+                            .ok()?
+                            .write_frontier()
+                            .to_owned(),
+                        None => {
+                            self.controller
+                                .storage
+                                .collection_frontiers(sink_id)
+                                .ok()?
+                                .1
+                        }
+                    };
+                    let (_, upstream) =
+                        self.controller.storage.collection_frontiers(from_id).ok()?;
+                    Some((sink_id, SinkLagRow::new(&upstream, &write)))
+                })
+                .collect();
+            self.replace_sink_lag(rows);
+            self.notify_sink_lag(threshold);
+        }
+        self.schedule_sink_lag_report();
+    }
+
+    /// Sends a notice to the sessions of the owner of each sink whose lag has started to exceed
+    /// `threshold` since the previous refresh.
+    fn notify_sink_lag(&mut self, threshold: Duration) {
+        let exceeding: BTreeSet<_> = if threshold.is_zero() {
+            BTreeSet::new()
+        } else {
+            self.sink_lag
+                .reported
+                .iter()
+                .filter(|(_, row)| row.lag.map_or(false, |lag| lag > threshold))
+                .map(|(id, _)| *id)
+                .collect()
+        };
+        let previous = std::mem::replace(&mut self.sink_lag.exceeding, exceeding);
+
+        let notices: Vec<_> = self
+            .sink_lag
+            .exceeding
+            .difference(&previous)
+            .filter_map(|id| {
+                let entry = self.catalog().try_get_entry(id)?;
+                let lag = self.sink_lag.reported.get(id)?.lag?;
+                let sink = self
+                    .catalog()
+                    .resolve_full_name(entry.name(), None)
+                    .to_string();
+                let notice = AdapterNotice::SinkLagExceeded {
+                    sink,
+                    lag,
+                    threshold,
+                };
+                Some((*entry.owner_id(), notice))
+            })
+            .collect();
+        for (owner_id, notice) in notices {
+            self.send_notice_to_owner(&owner_id, notice);
+        }
+    }
+
+    /// Replaces the rows of `mz_sink_lag` with `rows`, updating only the rows of the sinks whose
+    /// lag changed.
+    fn replace_sink_lag(&mut self, rows: BTreeMap<GlobalId, SinkLagRow>) {
+        let previous = std::mem::replace(&mut self.sink_lag.reported, rows);
+        let state = self.catalog().state();
+        let updates: Vec<_> = diff_keyed_rows(&previous, &self.sink_lag.reported)
+            .into_iter()
+            .map(|((id, row), diff)| {
+                state.pack_sink_lag_update(
+                    *id,
+                    row.upstream_frontier,
+                    row.write_frontier,
+                    row.lag,
+                    diff,
+                )
+            })
+            .collect();
+        let updates = self.resolve_cached_builtin_table_updates(updates);
+        if !updates.is_empty() {
+            self.builtin_table_update().background(updates);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_sink_lag_row() {
+        let frontier = |ts: u64| Antichain::from_elem(Timestamp::from(ts));
+
+        let row = SinkLagRow::new(&frontier(5_000), &frontier(2_000));
+        assert_eq!(row.upstream_frontier, Some(Timestamp::from(5_000)));
+        assert_eq!(row.write_frontier, Some(Timestamp::from(2_000)));
+        assert_eq!(row.lag, Some(Duration::from_secs(3)));
+
+        // A sink that is ahead of its upstream relation, e.g. because their frontiers were read
+        // at slightly different times, doesn't lag.
+        let row = SinkLagRow::new(&frontier(2_000), &frontier(5_000));
+        assert_eq!(row.lag, Some(Duration::ZERO));
+
+        // A sink that has not made progress yet has no lag.
+        let row = SinkLagRow::new(&frontier(5_000), &frontier(0));
+        assert_eq!(row.lag, None);
+
+        // Neither do sinks whose upstream relation or output is complete.
+        let row = SinkLagRow::new(&Antichain::new(), &frontier(2_000));
+        assert_eq!(row.upstream_frontier, None);
+        assert_eq!(row.lag, None);
+        let row = SinkLagRow::new(&frontier(5_000), &Antichain::new());
+        assert_eq!(row.write_frontier, None);
+        assert_eq!(row.lag, None);
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Retirement of compute sinks whose clients have gone away.
+//!
+//! Compute sinks are usually retired when the connection that created them terminates. A
+//! subscribe that doesn't produce updates never notices if its client has gone away in some other
+//! way, e.g. because the portal of its cursor was dropped, or its connection vanished without the
+//! coordinator being told, and would otherwise keep its dataflow running and hold back the
+//! compaction of its inputs indefinitely. Every `compute_sink_liveness_check_interval`, the
+//! coordinator retires the compute sinks whose connection is no longer active or whose client has
+//! stopped listening for their results.
+//!
+//! Clients that vanish without closing their connection, e.g. because their host crashed or a
+//! network device between us and them dropped the connection, leave it half-open. The server sets
+//! TCP keepalives and a TCP user timeout on every connection, so that pgwire notices within ten
+//! minutes that such a client stopped responding, even while a `SUBSCRIBE` is idle, and terminates
+//! the connection, which retires its compute sinks.
+//!
+//! Cursors that have been handed off with `HANDOFF CURSOR` outlive their connection by design and
+//! are exempt until they are claimed.
+
+use std::collections::BTreeMap;
+
+use mz_adapter_types::dyncfgs::COMPUTE_SINK_LIVENESS_CHECK_INTERVAL;
+use tracing::info;
+
+use crate::active_compute_sink::{ActiveComputeSink, ActiveComputeSinkRetireReason};
+use crate::coord::{Coordinator, Message};
+
+impl Coordinator {
+    /// Schedules the next liveness check of the compute sinks,
+    /// `compute_sink_liveness_check_interval` from now.
+    pub(crate) fn schedule_compute_sink_liveness_check(&self) {
+        let interval =
+            COMPUTE_SINK_LIVENESS_CHECK_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message(
+            "compute_sink_liveness_check",
+            interval,
+            Message::CheckComputeSinkLiveness,
+        );
+    }
+
+    /// Retires the compute sinks whose clients have gone away, if enabled, and schedules the next
+    /// check.
+    pub(crate) async fn check_compute_sink_liveness(&mut self) {
+        let interval =
+            COMPUTE_SINK_LIVENESS_CHECK_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        if !interval.is_zero() {
+            let dead: BTreeMap<_, _> = self
+                .active_compute_sinks
+                .iter()
+                .filter(|(id, sink)| {
+                    if self.is_cursor_handed_off(**id) {
+                        return false;
+                    }
+                    let listening = match sink {
+                        ActiveComputeSink::Subscribe(subscribe) => !subscribe.channel.is_closed(),
+                        ActiveComputeSink::CopyTo(copy_to) => !copy_to.tx.is_closed(),
+                    };
+                    !listening || !self.active_conns.contains_key(sink.connection_id())
+                })
+                .map(|(id, sink)| {
+                    info!(
+                        sink_id = %id,
+                        conn_id = %sink.connection_id(),
+                        "retiring compute sink whose client has gone away"
+                    );
+                    (*id, ActiveComputeSinkRetireReason::Canceled)
+                })
+                .collect();
+            if !dead.is_empty() {
+                self.retire_compute_sinks(dead).await;
+            }
+        }
+        self.schedule_compute_sink_liveness_check();
+    }
+}
@@ -34,6 +34,11 @@ impl Coordinator {
         params: &mz_sql::plan::Params,
         resolved_ids: &ResolvedIds,
     ) -> Result<mz_sql::plan::Plan, AdapterError> {
+        let _timer = self
+            .metrics
+            .catalog_snapshot_hold_seconds
+            .with_label_values(&["plan"])
+            .start_timer();
         let pcx = session.pcx();
         let catalog = self.catalog().for_session(session);
         let plan = mz_sql::plan::plan(Some(pcx), &catalog, stmt, params, resolved_ids)?;
@@ -49,8 +54,14 @@ impl Coordinator {
         params: Params,
     ) {
         let catalog = self.owned_catalog();
+        let hold_timer = self
+            .metrics
+            .catalog_snapshot_hold_seconds
+            .with_label_values(&["declare"])
+            .start_timer();
         let now = self.now();
         mz_ore::task::spawn(|| "coord::declare", async move {
+            let _hold_timer = hold_timer;
             let result =
                 Self::declare_inner(ctx.session_mut(), &catalog, name, stmt, sql, params, now)
                     .map(|()| ExecuteResponse::DeclaredCursor);
@@ -77,7 +88,7 @@ impl Coordinator {
         let result_formats = vec![mz_pgwire_common::Format::Text; desc.arity()];
         let logging = session.mint_logging(sql, Some(&stmt), now);
         session.set_portal(
-            name,
+            name.clone(),
             desc,
             Some(stmt),
             logging,
@@ -85,6 +96,10 @@ impl Coordinator {
             result_formats,
             catalog.transient_revision(),
         )?;
+        session
+            .get_portal_unverified_mut(&name)
+            .expect("known to exist")
+            .declared = true;
         Ok(())
     }
 
@@ -242,6 +257,7 @@ impl Coordinator {
     ) -> BuiltinTableAppendNotify {
         let user = self.active_conns()[active_sink.connection_id()].user();
         let session_type = metrics::session_type_label_value(user);
+        self.compute_sink_installed(active_sink.connection_id());
 
         self.active_conns
             .get_mut(active_sink.connection_id())
@@ -262,6 +278,9 @@ impl Coordinator {
                     .with_label_values(&[session_type])
                     .inc();
 
+                let event = self.pack_subscription_event(id, active_subscribe, None);
+                self.record_subscription_events(vec![event]).await;
+
                 self.builtin_table_update().execute(vec![update]).await
             }
             ActiveComputeSink::CopyTo(_) => {
@@ -287,23 +306,42 @@ impl Coordinator {
         id: GlobalId,
     ) -> Option<ActiveComputeSink> {
         if let Some(sink) = self.active_compute_sinks.remove(&id) {
-            let user = self.active_conns()[sink.connection_id()].user();
-            let session_type = metrics::session_type_label_value(user);
+            // Cursors that have been handed off don't belong to any connection until they are
+            // claimed.
+            let session_type = match self.remove_cursor_handoff(id) {
+                Some(session_type) => session_type,
+                None => match self.active_conns.get_mut(sink.connection_id()) {
+                    Some(conn) => {
+                        let session_type = metrics::session_type_label_value(conn.user());
+                        conn.drop_sinks.remove(&id);
+                        session_type
+                    }
+                    // Sinks are retired with their connection, so this is only reachable for
+                    // sinks retired by `check_compute_sink_liveness` after their connection
+                    // vanished without them.
+                    None => {
+                        tracing::error!(
+                            sink_id = %id,
+                            conn_id = %sink.connection_id(),
+                            "removing compute sink of unknown connection"
+                        );
+                        "user"
+                    }
+                },
+            };
 
-            self.active_conns
-                .get_mut(sink.connection_id())
-                .expect("must exist for active compute sink")
-                .drop_sinks
-                .remove(&id);
+            self.schedule_compute_sink_admission(sink.cluster_id());
 
             match &sink {
                 ActiveComputeSink::Subscribe(active_subscribe) => {
-                    let update =
-                        self.catalog()
-                            .state()
-                            .pack_subscribe_update(id, active_subscribe, -1);
-                    let update = self.catalog().state().resolve_builtin_table_update(update);
-                    self.builtin_table_update().blocking(vec![update]).await;
+                    let state = self.catalog().state();
+                    let mut updates = vec![state.pack_subscribe_update(id, active_subscribe, -1)];
+                    updates.extend(state.pack_subscribe_buffer_update(id, active_subscribe, -1));
+                    let updates = updates
+                        .into_iter()
+                        .map(|update| state.resolve_builtin_table_update(update))
+                        .collect();
+                    self.builtin_table_update().blocking(updates).await;
 
                     self.metrics
                         .active_subscribes
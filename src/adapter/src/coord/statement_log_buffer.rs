@@ -0,0 +1,487 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A buffer of the events that wait to be written to the statement log.
+//!
+//! The buffer is drained in chunks of bounded size, so that writing a large backlog of events
+//! doesn't hold up the coordinator for long. If the coordinator can't keep up, and the events
+//! held in memory exceed a bound, further events are handed to a background task that spills
+//! them to a temporary file, and that reads them back once the events in memory have been
+//! drained. The spilled events are bounded too: beyond that bound, and whenever the spill task
+//! can't keep up, further events are dropped and counted.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_proto::RustType;
+use mz_repr::{Diff, ProtoRow, Row};
+use mz_storage_client::controller::IntrospectionType;
+use prost::Message;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// The number of commands that may wait for the spill task before further events are dropped.
+const SPILL_COMMAND_CAPACITY: usize = 1024;
+
+/// A collection of the statement log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum StatementLogCollection {
+    SessionHistory,
+    PreparedStatementHistory,
+    StatementExecutionHistory,
+    StatementLifecycleHistory,
+    SqlText,
+}
+
+impl StatementLogCollection {
+    const ALL: [StatementLogCollection; 5] = [
+        StatementLogCollection::SessionHistory,
+        StatementLogCollection::PreparedStatementHistory,
+        StatementLogCollection::StatementExecutionHistory,
+        StatementLogCollection::StatementLifecycleHistory,
+        StatementLogCollection::SqlText,
+    ];
+
+    /// The introspection collection that events of the collection are written to.
+    pub(crate) fn introspection_type(&self) -> IntrospectionType {
+        match self {
+            StatementLogCollection::SessionHistory => IntrospectionType::SessionHistory,
+            StatementLogCollection::PreparedStatementHistory => {
+                IntrospectionType::PreparedStatementHistory
+            }
+            StatementLogCollection::StatementExecutionHistory => {
+                IntrospectionType::StatementExecutionHistory
+            }
+            StatementLogCollection::StatementLifecycleHistory => {
+                IntrospectionType::StatementLifecycleHistory
+            }
+            StatementLogCollection::SqlText => IntrospectionType::SqlText,
+        }
+    }
+
+    fn tag(&self) -> u8 {
+        match self {
+            StatementLogCollection::SessionHistory => 0,
+            StatementLogCollection::PreparedStatementHistory => 1,
+            StatementLogCollection::StatementExecutionHistory => 2,
+            StatementLogCollection::StatementLifecycleHistory => 3,
+            StatementLogCollection::SqlText => 4,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<StatementLogCollection> {
+        StatementLogCollection::ALL
+            .into_iter()
+            .find(|collection| collection.tag() == tag)
+    }
+}
+
+/// An update of a collection of the statement log.
+type StatementLogEvent = (StatementLogCollection, Row, Diff);
+
+/// The pending events of the statement log, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct StatementLogBuffer {
+    /// The events held in memory, which are older than all spilled events.
+    events: VecDeque<StatementLogEvent>,
+    /// The number of bytes of the rows of `events`.
+    bytes: usize,
+    /// The task that spills events, once any had to be spilled.
+    spill_task: Option<SpillTask>,
+    /// The number of bytes of the rows of the events handed to the spill task that haven't come
+    /// back from it.
+    spilled_bytes: usize,
+    /// Whether spilled events were requested back, and haven't arrived yet.
+    unspilling: bool,
+    /// The number of events dropped since [`StatementLogBuffer::take_dropped_events`] was last
+    /// called.
+    dropped_events: u64,
+}
+
+/// The channels to the task that owns the spill file.
+#[derive(Debug)]
+struct SpillTask {
+    tx: mpsc::Sender<SpillCommand>,
+    unspilled_rx: mpsc::UnboundedReceiver<Unspilled>,
+}
+
+/// A command for the spill task.
+#[derive(Debug)]
+enum SpillCommand {
+    /// Append the event to the spill file.
+    Spill(StatementLogEvent),
+    /// Read back the oldest spilled events, at least one and until they exceed `max_bytes`.
+    Unspill { max_bytes: usize },
+}
+
+/// The events read back by the spill task in response to [`SpillCommand::Unspill`].
+#[derive(Debug, Default)]
+struct Unspilled {
+    events: Vec<StatementLogEvent>,
+    /// The number of bytes of the rows of the events that failed to be spilled or read back since
+    /// the previous response.
+    lost_bytes: usize,
+    /// The number of events that failed to be spilled or read back since the previous response.
+    lost_events: u64,
+}
+
+impl StatementLogBuffer {
+    /// Returns whether there are no pending events.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.spilled_bytes == 0 && !self.unspilling
+    }
+
+    /// Returns whether there are pending events in memory, which can be drained right away.
+    pub(crate) fn has_buffered_events(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    /// Returns the number of bytes of all pending events, including spilled events.
+    pub(crate) fn backlog_bytes(&self) -> usize {
+        self.bytes.saturating_add(self.spilled_bytes)
+    }
+
+    /// Returns the number of bytes of the pending events that are spilled to disk.
+    pub(crate) fn spilled_bytes(&self) -> usize {
+        self.spilled_bytes
+    }
+
+    /// Returns the number of events dropped since the previous call, and resets it.
+    pub(crate) fn take_dropped_events(&mut self) -> u64 {
+        std::mem::take(&mut self.dropped_events)
+    }
+
+    /// Adds an update of `row` to `collection` to the pending events.
+    ///
+    /// The update is spilled to disk if the events in memory would exceed `max_buffered_bytes`,
+    /// or if older events are spilled already. It is dropped if the spilled events would exceed
+    /// `max_spilled_bytes`, or if the spill task can't keep up.
+    pub(crate) fn push(
+        &mut self,
+        collection: StatementLogCollection,
+        row: Row,
+        diff: Diff,
+        max_buffered_bytes: usize,
+        max_spilled_bytes: usize,
+    ) {
+        let size = row.byte_len();
+        let spilling = self.spilled_bytes > 0 || self.unspilling;
+        if !spilling && self.bytes.saturating_add(size) <= max_buffered_bytes {
+            self.events.push_back((collection, row, diff));
+            self.bytes += size;
+            return;
+        }
+
+        if self.spilled_bytes.saturating_add(size) > max_spilled_bytes {
+            self.dropped_events += 1;
+            return;
+        }
+        let spill_task = self.spill_task.get_or_insert_with(SpillTask::spawn);
+        match spill_task
+            .tx
+            .try_send(SpillCommand::Spill((collection, row, diff)))
+        {
+            Ok(()) => self.spilled_bytes += size,
+            Err(_) => self.dropped_events += 1,
+        }
+    }
+
+    /// Removes the oldest pending events in memory, up to `max_bytes` of them but at least one,
+    /// and returns them by collection.
+    ///
+    /// Once the events in memory have been drained, spilled events are requested back from the
+    /// spill task. They are drained by a later call, once they have arrived.
+    pub(crate) fn drain(
+        &mut self,
+        max_bytes: usize,
+    ) -> BTreeMap<StatementLogCollection, Vec<(Row, Diff)>> {
+        self.receive_unspilled();
+
+        let mut chunk: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        let mut chunk_bytes: usize = 0;
+        while let Some((_, row, _)) = self.events.front() {
+            let size = row.byte_len();
+            if chunk_bytes > 0 && chunk_bytes.saturating_add(size) > max_bytes {
+                break;
+            }
+            let (collection, row, diff) = self.events.pop_front().expect("known to exist");
+            self.bytes -= size;
+            chunk_bytes = chunk_bytes.saturating_add(size);
+            chunk.entry(collection).or_default().push((row, diff));
+        }
+
+        if self.events.is_empty() && self.spilled_bytes > 0 && !self.unspilling {
+            let spill_task = self.spill_task.as_ref().expect("events were spilled");
+            // If the spill task is busy, the events are requested again by the next drain.
+            self.unspilling = spill_task
+                .tx
+                .try_send(SpillCommand::Unspill { max_bytes })
+                .is_ok();
+        }
+        chunk
+    }
+
+    /// Takes the events that the spill task read back, if they have arrived.
+    fn receive_unspilled(&mut self) {
+        let Some(spill_task) = &mut self.spill_task else {
+            return;
+        };
+        while let Ok(unspilled) = spill_task.unspilled_rx.try_recv() {
+            self.unspilling = false;
+            self.dropped_events += unspilled.lost_events;
+            self.spilled_bytes -= unspilled.lost_bytes;
+            for (collection, row, diff) in unspilled.events {
+                let size = row.byte_len();
+                self.spilled_bytes -= size;
+                self.bytes += size;
+                self.events.push_back((collection, row, diff));
+            }
+        }
+    }
+}
+
+impl SpillTask {
+    fn spawn() -> SpillTask {
+        let (tx, rx) = mpsc::channel(SPILL_COMMAND_CAPACITY);
+        let (unspilled_tx, unspilled_rx) = mpsc::unbounded_channel();
+        task::spawn_blocking(
+            || "statement_log_spill",
+            move || run_spill_task(rx, unspilled_tx),
+        );
+        SpillTask { tx, unspilled_rx }
+    }
+}
+
+/// Handles the commands for the spill file until the buffer is dropped.
+fn run_spill_task(
+    mut rx: mpsc::Receiver<SpillCommand>,
+    unspilled_tx: mpsc::UnboundedSender<Unspilled>,
+) {
+    let mut spill = None;
+    let mut lost = Unspilled::default();
+    while let Some(command) = rx.blocking_recv() {
+        match command {
+            SpillCommand::Spill((collection, row, diff)) => {
+                if let Err(e) = Spill::append(&mut spill, collection, &row, diff) {
+                    warn!("dropping statement log event that failed to be spilled: {e}");
+                    lost.lost_bytes += row.byte_len();
+                    lost.lost_events += 1;
+                }
+            }
+            SpillCommand::Unspill { max_bytes } => {
+                let mut unspilled = std::mem::take(&mut lost);
+                if let Some(s) = &mut spill {
+                    s.read_back(max_bytes, &mut unspilled);
+                    if s.events == 0 {
+                        spill = None;
+                    }
+                }
+                if unspilled_tx.send(unspilled).is_err() {
+                    // The buffer has been dropped.
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Events spilled to a temporary file, which is removed once the spill is dropped.
+#[derive(Debug)]
+struct Spill {
+    file: File,
+    /// The position of the oldest event that hasn't been read back.
+    read_offset: u64,
+    /// The number of events that haven't been read back.
+    events: usize,
+    /// The number of bytes of the rows of the events that haven't been read back.
+    bytes: usize,
+}
+
+impl Spill {
+    /// Appends an event to the spill file, which is created if necessary.
+    ///
+    /// Each event is encoded as the tag of its collection, its diff, the length of its encoded
+    /// row, and its encoded row.
+    fn append(
+        spill: &mut Option<Spill>,
+        collection: StatementLogCollection,
+        row: &Row,
+        diff: Diff,
+    ) -> Result<(), io::Error> {
+        if spill.is_none() {
+            *spill = Some(Spill {
+                file: tempfile::tempfile()?,
+                read_offset: 0,
+                events: 0,
+                bytes: 0,
+            });
+        }
+        let spill = spill.as_mut().expect("created above");
+
+        let encoded_row = row.into_proto().encode_to_vec();
+        let len = u32::try_from(encoded_row.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "row too large to spill"))?;
+        let mut buf = Vec::with_capacity(13 + encoded_row.len());
+        buf.push(collection.tag());
+        buf.extend(diff.to_le_bytes());
+        buf.extend(len.to_le_bytes());
+        buf.extend(encoded_row);
+
+        spill.file.seek(SeekFrom::End(0))?;
+        spill.file.write_all(&buf)?;
+        spill.events += 1;
+        spill.bytes += row.byte_len();
+        Ok(())
+    }
+
+    /// Reads spilled events back into `unspilled`, at least one and until they exceed
+    /// `max_bytes`.
+    fn read_back(&mut self, max_bytes: usize, unspilled: &mut Unspilled) {
+        let mut read_bytes: usize = 0;
+        while self.events > 0 && (read_bytes == 0 || read_bytes < max_bytes) {
+            match self.read_event() {
+                Ok((collection, row, diff)) => {
+                    let size = row.byte_len();
+                    self.events -= 1;
+                    self.bytes -= size;
+                    read_bytes = read_bytes.saturating_add(size);
+                    unspilled.events.push((collection, row, diff));
+                }
+                Err(e) => {
+                    warn!("dropping spilled statement log events that failed to be read: {e}");
+                    unspilled.lost_bytes += self.bytes;
+                    unspilled.lost_events += u64::cast_from(self.events);
+                    self.events = 0;
+                    self.bytes = 0;
+                }
+            }
+        }
+    }
+
+    /// Reads the oldest event that hasn't been read back.
+    fn read_event(&mut self) -> Result<StatementLogEvent, io::Error> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        self.file.seek(SeekFrom::Start(self.read_offset))?;
+        let mut header = [0; 13];
+        self.file.read_exact(&mut header)?;
+        let collection = StatementLogCollection::from_tag(header[0])
+            .ok_or_else(|| invalid(format!("unknown collection tag {}", header[0])))?;
+        let diff = Diff::from_le_bytes(header[1..9].try_into().expect("8 bytes"));
+        let len = u32::from_le_bytes(header[9..13].try_into().expect("4 bytes"));
+        let mut encoded_row = vec![0; usize::cast_from(len)];
+        self.file.read_exact(&mut encoded_row)?;
+        let row = ProtoRow::decode(&encoded_row[..])
+            .map_err(|e| invalid(e.to_string()))
+            .and_then(|proto| Row::from_proto(proto).map_err(|e| invalid(e.to_string())))?;
+
+        self.read_offset += u64::cast_from(header.len()) + u64::from(len);
+        Ok((collection, row, diff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mz_repr::Datum;
+
+    use super::*;
+
+    /// Drains `buffer` until it is empty, waiting for spilled events to be read back.
+    async fn drain_all(
+        buffer: &mut StatementLogBuffer,
+        max_bytes: usize,
+        max_events: usize,
+    ) -> BTreeMap<StatementLogCollection, Vec<Row>> {
+        let mut drained: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        while !buffer.is_empty() {
+            let chunk = buffer.drain(max_bytes);
+            let chunk_len: usize = chunk.values().map(|updates| updates.len()).sum();
+            assert!(chunk_len <= max_events);
+            if chunk_len == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            for (collection, updates) in chunk {
+                let drained = drained.entry(collection).or_default();
+                drained.extend(updates.into_iter().map(|(row, _)| row));
+            }
+        }
+        drained
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `tmpfile`
+    async fn test_statement_log_buffer() {
+        let rows: Vec<_> = (0..10)
+            .map(|i| Row::pack_slice(&[Datum::Int64(i)]))
+            .collect();
+        let size = rows[0].byte_len();
+
+        // Keep three events in memory, and spill the rest.
+        let mut buffer = StatementLogBuffer::default();
+        for (i, row) in rows.iter().enumerate() {
+            let collection = if i % 2 == 0 {
+                StatementLogCollection::StatementExecutionHistory
+            } else {
+                StatementLogCollection::SqlText
+            };
+            buffer.push(collection, row.clone(), 1, 3 * size, usize::MAX);
+        }
+        assert_eq!(buffer.backlog_bytes(), 10 * size);
+        assert_eq!(buffer.spilled_bytes(), 7 * size);
+
+        // Events are drained oldest first, whether they were spilled or not.
+        let drained = drain_all(&mut buffer, 4 * size, 4).await;
+        assert_eq!(
+            drained[&StatementLogCollection::StatementExecutionHistory],
+            rows.iter().step_by(2).cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            drained[&StatementLogCollection::SqlText],
+            rows.iter().skip(1).step_by(2).cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(buffer.backlog_bytes(), 0);
+        assert_eq!(buffer.take_dropped_events(), 0);
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `tmpfile`
+    async fn test_statement_log_buffer_max_spilled_bytes() {
+        let rows: Vec<_> = (0..10)
+            .map(|i| Row::pack_slice(&[Datum::Int64(i)]))
+            .collect();
+        let size = rows[0].byte_len();
+
+        // Keep three events in memory, spill five, and drop the rest.
+        let mut buffer = StatementLogBuffer::default();
+        for row in &rows {
+            buffer.push(
+                StatementLogCollection::SqlText,
+                row.clone(),
+                1,
+                3 * size,
+                5 * size,
+            );
+        }
+        assert_eq!(buffer.spilled_bytes(), 5 * size);
+        assert_eq!(buffer.take_dropped_events(), 2);
+        assert_eq!(buffer.take_dropped_events(), 0);
+
+        let drained = drain_all(&mut buffer, 2 * size, 2).await;
+        assert_eq!(
+            drained[&StatementLogCollection::SqlText],
+            rows[..8].to_vec()
+        );
+        assert_eq!(buffer.take_dropped_events(), 0);
+    }
+}
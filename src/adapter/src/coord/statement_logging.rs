@@ -9,6 +9,7 @@
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes::BytesMut;
 use mz_controller_types::ClusterId;
@@ -22,16 +23,17 @@ use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{AstInfo, Statement};
 use mz_sql::plan::Params;
 use mz_sql::session::metadata::SessionMetadata;
+use mz_sql::session::vars::IsolationLevel;
 use mz_sql_parser::ast::{statement_kind_label_value, StatementKind};
-use mz_storage_client::controller::IntrospectionType;
 use qcell::QCell;
 use rand::SeedableRng;
 use rand::{distributions::Bernoulli, prelude::Distribution, thread_rng};
 use sha2::{Digest, Sha256};
 use tokio::time::MissedTickBehavior;
-use tracing::debug;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
+use crate::coord::statement_log_buffer::{StatementLogBuffer, StatementLogCollection};
 use crate::coord::{ConnMeta, Coordinator};
 use crate::session::Session;
 use crate::statement_logging::{
@@ -41,6 +43,9 @@ use crate::statement_logging::{
 
 use super::Message;
 
+/// How often pending statement log events are written to the statement log.
+pub(crate) const STATEMENT_LOG_DRAIN_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Metadata required for logging a prepared statement.
 #[derive(Debug)]
 pub enum PreparedStatementLoggingInfo {
@@ -109,6 +114,13 @@ impl PreparedStatementLoggingInfo {
 #[derive(Copy, Clone, Debug, Ord, Eq, PartialOrd, PartialEq)]
 pub struct StatementLoggingId(Uuid);
 
+impl StatementLoggingId {
+    /// Returns the ID of the execution, as recorded in `mz_statement_execution_history`.
+    pub(crate) fn uuid(&self) -> Uuid {
+        self.0
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct PreparedStatementEvent {
     prepared_statement: Row,
@@ -135,10 +147,8 @@ pub(crate) struct StatementLogging {
     /// Controlled by the system var `statement_logging_use_reproducible_rng`.
     reproducible_rng: rand_chacha::ChaCha8Rng,
 
-    pending_statement_execution_events: Vec<(Row, Diff)>,
-    pending_prepared_statement_events: Vec<PreparedStatementEvent>,
-    pending_session_events: Vec<Row>,
-    pending_statement_lifecycle_events: Vec<Row>,
+    /// Events waiting to be written to the statement log.
+    pending_events: StatementLogBuffer,
 
     now: NowFn,
 
@@ -159,10 +169,7 @@ impl StatementLogging {
             executions_begun: BTreeMap::new(),
             unlogged_sessions: BTreeMap::new(),
             reproducible_rng: rand_chacha::ChaCha8Rng::seed_from_u64(42),
-            pending_statement_execution_events: Vec::new(),
-            pending_prepared_statement_events: Vec::new(),
-            pending_session_events: Vec::new(),
-            pending_statement_lifecycle_events: Vec::new(),
+            pending_events: StatementLogBuffer::default(),
             tokens: 0,
             last_logged_ts_seconds,
             now: now.clone(),
@@ -213,7 +220,7 @@ impl Coordinator {
             // Although... Logging every 5 seconds seems like it
             // should have acceptable cost for now, since we do a
             // group commit for tables every 1s anyway.
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+            let mut interval = tokio::time::interval(STATEMENT_LOG_DRAIN_INTERVAL);
             interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
             loop {
                 interval.tick().await;
@@ -222,45 +229,70 @@ impl Coordinator {
         });
     }
 
+    /// Writes the oldest pending events to the statement log, up to
+    /// `statement_logging_drain_max_bytes` of them.
+    ///
+    /// If events remain in memory, another drain is requested right away, so that the
+    /// coordinator can handle other messages between the chunks of a large backlog.
     #[mz_ore::instrument(level = "debug")]
     pub(crate) async fn drain_statement_log(&mut self) {
-        let session_updates = std::mem::take(&mut self.statement_logging.pending_session_events)
-            .into_iter()
-            .map(|update| (update, 1))
-            .collect();
-        let (prepared_statement_updates, sql_text_updates) =
-            std::mem::take(&mut self.statement_logging.pending_prepared_statement_events)
-                .into_iter()
-                .map(
-                    |PreparedStatementEvent {
-                         prepared_statement,
-                         sql_text,
-                     }| ((prepared_statement, 1), (sql_text, 1)),
-                )
-                .unzip::<_, _, Vec<_>, Vec<_>>();
-        let statement_execution_updates =
-            std::mem::take(&mut self.statement_logging.pending_statement_execution_events);
-        let statement_lifecycle_updates =
-            std::mem::take(&mut self.statement_logging.pending_statement_lifecycle_events)
-                .into_iter()
-                .map(|update| (update, 1))
-                .collect();
-
-        use IntrospectionType::*;
-        for (type_, updates) in [
-            (SessionHistory, session_updates),
-            (PreparedStatementHistory, prepared_statement_updates),
-            (StatementExecutionHistory, statement_execution_updates),
-            (StatementLifecycleHistory, statement_lifecycle_updates),
-            (SqlText, sql_text_updates),
-        ] {
-            if !updates.is_empty() && !self.controller.read_only() {
+        let max_bytes = self
+            .catalog()
+            .system_config()
+            .statement_logging_drain_max_bytes();
+        let chunk = self.statement_logging.pending_events.drain(max_bytes);
+        for (collection, updates) in chunk {
+            if !self.controller.read_only() {
                 self.controller
                     .storage
-                    .append_introspection_updates(type_, updates)
+                    .append_introspection_updates(collection.introspection_type(), updates)
                     .await;
             }
         }
+
+        let pending_events = &mut self.statement_logging.pending_events;
+        let dropped_events = pending_events.take_dropped_events();
+        if dropped_events > 0 {
+            warn!("dropped {dropped_events} statement log events");
+            self.metrics
+                .statement_logging_dropped_events
+                .inc_by(dropped_events);
+        }
+        self.metrics
+            .statement_logging_drain_backlog_bytes
+            .set(u64::cast_from(pending_events.backlog_bytes()));
+        self.metrics
+            .statement_logging_spilled_bytes
+            .set(u64::cast_from(pending_events.spilled_bytes()));
+        // Spilled events that are being read back are drained by the next periodic drain.
+        if pending_events.has_buffered_events() {
+            let _ = self.internal_cmd_tx.send(Message::DrainStatementLog);
+        }
+    }
+
+    /// Adds an update of `row` to `collection` to the events waiting to be written to the
+    /// statement log.
+    fn buffer_statement_log_event(
+        &mut self,
+        collection: StatementLogCollection,
+        row: Row,
+        diff: Diff,
+    ) {
+        let max_buffered_bytes = self
+            .catalog()
+            .system_config()
+            .statement_logging_max_buffered_bytes();
+        let max_spilled_bytes = self
+            .catalog()
+            .system_config()
+            .statement_logging_max_spilled_bytes();
+        self.statement_logging.pending_events.push(
+            collection,
+            row,
+            diff,
+            max_buffered_bytes,
+            max_spilled_bytes,
+        );
     }
 
     /// Check whether we need to do throttling (i.e., whether `STATEMENT_LOGGING_TARGET_DATA_RATE` is set).
@@ -387,11 +419,13 @@ impl Coordinator {
     /// (because it was not sampled). Requiring the opaque `StatementLoggingId` type,
     /// which is only instantiated by `begin_statement_execution` if the statement is actually logged,
     /// should prevent this.
+    ///
+    /// Returns the record of the beginning of the execution.
     pub fn end_statement_execution(
         &mut self,
         id: StatementLoggingId,
         reason: StatementEndedExecutionReason,
-    ) {
+    ) -> StatementBeganExecutionRecord {
         let StatementLoggingId(uuid) = id;
         let now = self.now();
         let ended_record = StatementEndedExecutionRecord {
@@ -410,15 +444,18 @@ impl Coordinator {
         for (row, diff) in
             Self::pack_statement_ended_execution_updates(&began_record, &ended_record)
         {
-            self.statement_logging
-                .pending_statement_execution_events
-                .push((row, diff));
+            self.buffer_statement_log_event(
+                StatementLogCollection::StatementExecutionHistory,
+                row,
+                diff,
+            );
         }
         self.record_statement_lifecycle_event(
             &id,
             &StatementLifecycleEvent::ExecutionFinished,
             now,
         );
+        began_record
     }
 
     fn pack_statement_execution_inner(
@@ -441,6 +478,8 @@ impl Coordinator {
             transaction_id,
             transient_index_id,
             mz_version,
+            effective_isolation: _,
+            isolation_downgrade_reason: _,
         } = record;
 
         let cluster = cluster_id.map(|id| id.to_string());
@@ -499,10 +538,25 @@ impl Coordinator {
             Datum::Null,
             // execution_status
             Datum::Null,
+            // max_result_memory_bytes
+            Datum::Null,
         ]);
+        Self::pack_statement_isolation_downgrade(record, &mut packer);
         row
     }
 
+    /// Packs the columns that describe an isolation level downgrade, which follow the columns
+    /// that are only known once the statement has finished.
+    fn pack_statement_isolation_downgrade(
+        record: &StatementBeganExecutionRecord,
+        packer: &mut RowPacker,
+    ) {
+        packer.extend([
+            record.effective_isolation.as_deref().into(),
+            record.isolation_downgrade_reason.as_deref().into(),
+        ]);
+    }
+
     fn pack_statement_prepared_update(record: &StatementPreparedRecord, packer: &mut RowPacker) {
         let StatementPreparedRecord {
             id,
@@ -560,23 +614,25 @@ impl Coordinator {
         let mut row = Row::default();
         let mut packer = row.packer();
         Self::pack_statement_execution_inner(began_record, &mut packer);
-        let (status, error_message, rows_returned, execution_strategy) = match &ended_record.reason
-        {
-            StatementEndedExecutionReason::Success {
-                rows_returned,
-                execution_strategy,
-            } => (
-                "success",
-                None,
-                rows_returned.map(|rr| i64::try_from(rr).expect("must fit")),
-                execution_strategy.map(|es| es.name()),
-            ),
-            StatementEndedExecutionReason::Canceled => ("canceled", None, None, None),
-            StatementEndedExecutionReason::Errored { error } => {
-                ("error", Some(error.as_str()), None, None)
-            }
-            StatementEndedExecutionReason::Aborted => ("aborted", None, None, None),
-        };
+        let (status, error_message, rows_returned, execution_strategy, max_result_memory_bytes) =
+            match &ended_record.reason {
+                StatementEndedExecutionReason::Success {
+                    rows_returned,
+                    execution_strategy,
+                    max_result_memory_bytes,
+                } => (
+                    "success",
+                    None,
+                    rows_returned.map(|rr| i64::try_from(rr).expect("must fit")),
+                    execution_strategy.map(|es| es.name()),
+                    max_result_memory_bytes.map(|b| i64::try_from(b).expect("must fit")),
+                ),
+                StatementEndedExecutionReason::Canceled => ("canceled", None, None, None, None),
+                StatementEndedExecutionReason::Errored { error } => {
+                    ("error", Some(error.as_str()), None, None, None)
+                }
+                StatementEndedExecutionReason::Aborted => ("aborted", None, None, None, None),
+            };
         packer.extend([
             Datum::TimestampTz(
                 to_datetime(ended_record.ended_at)
@@ -587,7 +643,9 @@ impl Coordinator {
             error_message.into(),
             rows_returned.into(),
             execution_strategy.into(),
+            max_result_memory_bytes.into(),
         ]);
+        Self::pack_statement_isolation_downgrade(began_record, &mut packer);
         row
     }
 
@@ -612,14 +670,18 @@ impl Coordinator {
             .get_mut(&id)
             .expect("mutate_record must not be called after execution ends");
         let retraction = Self::pack_statement_began_execution_update(record);
-        self.statement_logging
-            .pending_statement_execution_events
-            .push((retraction, -1));
         f(record);
         let update = Self::pack_statement_began_execution_update(record);
-        self.statement_logging
-            .pending_statement_execution_events
-            .push((update, 1));
+        self.buffer_statement_log_event(
+            StatementLogCollection::StatementExecutionHistory,
+            retraction,
+            -1,
+        );
+        self.buffer_statement_log_event(
+            StatementLogCollection::StatementExecutionHistory,
+            update,
+            1,
+        );
     }
 
     /// Set the `cluster_id` for a statement, once it's known.
@@ -646,6 +708,20 @@ impl Coordinator {
         });
     }
 
+    /// Record that a statement was executed under the `effective` isolation level instead of
+    /// the one of its session, for the given reason.
+    pub fn set_statement_isolation_downgrade(
+        &mut self,
+        id: StatementLoggingId,
+        effective: &IsolationLevel,
+        reason: &str,
+    ) {
+        self.mutate_record(id, |record| {
+            record.effective_isolation = Some(effective.to_string());
+            record.isolation_downgrade_reason = Some(reason.to_string());
+        });
+    }
+
     pub fn set_transient_index_id(&mut self, id: StatementLoggingId, transient_index_id: GlobalId) {
         self.mutate_record(id, |record| {
             record.transient_index_id = Some(transient_index_id)
@@ -738,6 +814,8 @@ impl Coordinator {
             cluster_name: None,
             execution_timestamp: None,
             transient_index_id: None,
+            effective_isolation: None,
+            isolation_downgrade_reason: None,
             database_name: session.vars().database().into(),
             search_path: session
                 .vars()
@@ -747,25 +825,39 @@ impl Coordinator {
                 .collect(),
         };
         let mseh_update = Self::pack_statement_began_execution_update(&record);
-        self.statement_logging
-            .pending_statement_execution_events
-            .push((mseh_update, 1));
+        self.buffer_statement_log_event(
+            StatementLogCollection::StatementExecutionHistory,
+            mseh_update,
+            1,
+        );
         self.statement_logging
             .executions_begun
             .insert(ev_id, record);
-        if let Some((ps_record, ps_update)) = ps_record {
-            self.statement_logging
-                .pending_prepared_statement_events
-                .push(ps_update);
+        if let Some((
+            ps_record,
+            PreparedStatementEvent {
+                prepared_statement,
+                sql_text,
+            },
+        )) = ps_record
+        {
+            self.buffer_statement_log_event(
+                StatementLogCollection::PreparedStatementHistory,
+                prepared_statement,
+                1,
+            );
+            self.buffer_statement_log_event(StatementLogCollection::SqlText, sql_text, 1);
             if let Some(sh) = self
                 .statement_logging
                 .unlogged_sessions
                 .remove(&ps_record.session_id)
             {
                 let sh_update = Self::pack_session_history_update(&sh);
-                self.statement_logging
-                    .pending_session_events
-                    .push(sh_update);
+                self.buffer_statement_log_event(
+                    StatementLogCollection::SessionHistory,
+                    sh_update,
+                    1,
+                );
             }
         }
         Some(StatementLoggingId(ev_id))
@@ -798,9 +890,11 @@ impl Coordinator {
             .get(self.catalog().system_config().dyncfgs())
         {
             let row = Self::pack_statement_lifecycle_event(id, event, when);
-            self.statement_logging
-                .pending_statement_lifecycle_events
-                .push(row);
+            self.buffer_statement_log_event(
+                StatementLogCollection::StatementLifecycleHistory,
+                row,
+                1,
+            );
         }
     }
 }
@@ -0,0 +1,261 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! `SUBSCRIBE ... INTO`, which archives the updates of a subscribe to S3.
+//!
+//! The subscribe runs with `PROGRESS`, but its rows go to a task instead of to the client. The
+//! task appends the updates to the current segment of an [`S3Archiver`]. Once
+//! `subscribe_archive_segment_interval` has passed, it finishes the segment at the next
+//! progressed timestamp and starts the next one. The client receives a row for each finished
+//! segment, with the segment's bounds, its number of updates and the URI of its manifest. When
+//! the subscribe completes, the task finishes the last segment without an upper bound.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use http::Uri;
+use mz_adapter_types::dyncfgs::SUBSCRIBE_ARCHIVE_SEGMENT_INTERVAL;
+use mz_compute_types::dyncfgs::{
+    COPY_TO_S3_ARROW_BUILDER_BUFFER_RATIO, COPY_TO_S3_MULTIPART_PART_SIZE_BYTES,
+    COPY_TO_S3_PARQUET_ROW_GROUP_FILE_RATIO,
+};
+use mz_ore::error::ErrorExt;
+use mz_ore::task;
+use mz_repr::{Datum, GlobalId, IntoRowIterator, Row, RowArena, Timestamp};
+use mz_sql::plan::{HirScalarExpr, SubscribeArchive};
+use mz_storage_operators::s3_oneshot_sink::archive::{S3Archiver, SegmentManifest};
+use mz_storage_operators::s3_oneshot_sink::CopyToParameters;
+use mz_storage_types::connections::Connection;
+use mz_storage_types::sinks::S3UploadInfo;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::active_compute_sink::{SubscribeBuffer, SubscribeRowStream};
+use crate::coord::peek::PeekResponseUnary;
+use crate::coord::Coordinator;
+use crate::error::AdapterError;
+use crate::optimize::dataflows::{prep_scalar_expr, EvalTime, ExprPrepStyle};
+use crate::session::Session;
+
+impl Coordinator {
+    /// Evaluates the target of a `SUBSCRIBE ... INTO` to an S3 URI.
+    pub(crate) fn eval_subscribe_archive_uri(
+        &self,
+        session: &Session,
+        to: HirScalarExpr,
+    ) -> Result<Uri, AdapterError> {
+        let style = ExprPrepStyle::OneShot {
+            logical_time: EvalTime::NotAvailable,
+            session,
+            catalog_state: self.catalog().state(),
+        };
+        let mut to = to.lower_uncorrelated()?;
+        prep_scalar_expr(&mut to, style)?;
+        let temp_storage = RowArena::new();
+        let evaled = to.eval(&[], &temp_storage)?;
+        if evaled == Datum::Null {
+            coord_bail!("SUBSCRIBE INTO target value can not be null");
+        }
+        match Uri::from_str(evaled.unwrap_str()) {
+            Ok(uri) if uri.scheme_str() == Some("s3") => Ok(uri),
+            Ok(_) => coord_bail!("only 's3://...' urls are supported as SUBSCRIBE INTO target"),
+            Err(e) => coord_bail!("could not parse SUBSCRIBE INTO target url: {}", e),
+        }
+    }
+
+    /// Spawns the task that archives the rows of the subscribe `sink_id`, which `rx` receives,
+    /// to `uri`. The subscribe must emit progress, and starts at `as_of`. `complete_rx` is
+    /// notified once it has sent all of its updates.
+    ///
+    /// Returns the stream from which the client receives a row for each archived segment.
+    pub(crate) fn spawn_subscribe_archive(
+        &self,
+        sink_id: GlobalId,
+        archive: SubscribeArchive,
+        uri: Uri,
+        as_of: Timestamp,
+        rx: SubscribeRowStream,
+        complete_rx: oneshot::Receiver<()>,
+    ) -> Result<SubscribeRowStream, AdapterError> {
+        let SubscribeArchive {
+            to: _,
+            connection,
+            connection_id,
+            desc,
+            format,
+            max_file_size,
+        } = archive;
+        let Connection::Aws(aws_connection) = connection else {
+            // It was already validated in planning that this is an aws connection.
+            coord_bail!("only aws connection is supported in SUBSCRIBE ... INTO");
+        };
+        let upload_info = S3UploadInfo {
+            uri: uri.to_string(),
+            max_file_size,
+            desc,
+            format,
+        };
+
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let params = CopyToParameters {
+            parquet_row_group_ratio: COPY_TO_S3_PARQUET_ROW_GROUP_FILE_RATIO.get(dyncfgs),
+            arrow_builder_buffer_ratio: COPY_TO_S3_ARROW_BUILDER_BUFFER_RATIO.get(dyncfgs),
+            s3_multipart_part_size_bytes: COPY_TO_S3_MULTIPART_PART_SIZE_BYTES.get(dyncfgs),
+        };
+        let segment_interval = SUBSCRIBE_ARCHIVE_SEGMENT_INTERVAL.get(dyncfgs);
+        let connection_context = self.connection_context().clone();
+
+        let (client_tx, client_rx) = mpsc::unbounded_channel();
+        let client_buffer = Arc::new(SubscribeBuffer::new(sink_id, self.internal_cmd_tx.clone()));
+        let client = SubscribeArchiveClient {
+            channel: client_tx,
+            buffer: Arc::clone(&client_buffer),
+        };
+
+        task::spawn(|| format!("subscribe_archive:{sink_id}"), async move {
+            let archiver = S3Archiver::new(
+                &connection_context,
+                &aws_connection,
+                connection_id,
+                upload_info,
+                sink_id,
+                as_of,
+                params,
+            )
+            .await;
+            match archiver {
+                Ok(archiver) => {
+                    archive_subscribe(archiver, as_of, rx, complete_rx, segment_interval, client)
+                        .await
+                }
+                Err(e) => client.send(PeekResponseUnary::Error(
+                    e.display_with_causes().to_string(),
+                )),
+            }
+        });
+
+        Ok(SubscribeRowStream::new(client_rx, client_buffer))
+    }
+}
+
+/// The channel on which the client of a `SUBSCRIBE ... INTO` receives its rows.
+struct SubscribeArchiveClient {
+    channel: mpsc::UnboundedSender<PeekResponseUnary>,
+    buffer: Arc<SubscribeBuffer>,
+}
+
+impl SubscribeArchiveClient {
+    fn send(&self, response: PeekResponseUnary) {
+        self.buffer.send(&self.channel, response);
+    }
+
+    /// Sends the client the row that describes the finished segment `manifest`.
+    fn send_manifest(&self, manifest: &SegmentManifest) {
+        let rows = i64::try_from(manifest.rows).expect("must fit");
+        let row = Row::pack_slice(&[
+            Datum::MzTimestamp(manifest.lower),
+            manifest.upper.map_or(Datum::Null, Datum::MzTimestamp),
+            Datum::Int64(rows),
+            Datum::String(&manifest.uri),
+        ]);
+        self.send(PeekResponseUnary::Rows(Box::new(row.into_row_iter())));
+    }
+}
+
+/// Archives the rows of a subscribe with progress, which `rx` receives, with `archiver`, until
+/// the subscribe is retired or archiving fails.
+async fn archive_subscribe(
+    mut archiver: S3Archiver,
+    as_of: Timestamp,
+    mut rx: SubscribeRowStream,
+    mut complete_rx: oneshot::Receiver<()>,
+    segment_interval: Duration,
+    client: SubscribeArchiveClient,
+) {
+    // The lower bound of the times in the current segment.
+    let mut lower = as_of;
+    // The latest progressed timestamp, i.e., the subscribe has sent all updates at times before
+    // it.
+    let mut frontier = as_of;
+    let mut segment_start = Instant::now();
+
+    // The subscribe sends all updates at times before a progressed timestamp before the progress
+    // row, so finishing a segment at a progressed timestamp never separates the updates at one
+    // time.
+    let result = 'archive: loop {
+        match rx.next().await {
+            Some(PeekResponseUnary::Rows(mut rows)) => {
+                let mut progressed = None;
+                let mut row_buf = Row::default();
+                while let Some(row) = rows.next() {
+                    let mut datums = row.iter();
+                    let time = datums.next().expect("missing mz_timestamp");
+                    if datums.next().expect("missing mz_progressed") == Datum::True {
+                        let time = Timestamp::try_from(time.unwrap_numeric().0)
+                            .expect("subscribe timestamps are valid");
+                        progressed = Some(time);
+                    } else {
+                        // Archive the updates without the `mz_progressed` column.
+                        let mut packer = row_buf.packer();
+                        packer.push(time);
+                        packer.extend(datums);
+                        if let Err(e) = archiver.append_row(&row_buf).await {
+                            break 'archive Err(e);
+                        }
+                    }
+                }
+                let Some(progressed) = progressed else {
+                    continue;
+                };
+                frontier = progressed;
+                if frontier > lower && segment_start.elapsed() >= segment_interval {
+                    match archiver.finish_segment(Some(frontier)).await {
+                        Ok(manifest) => client.send_manifest(&manifest),
+                        Err(e) => break Err(e),
+                    }
+                    lower = frontier;
+                    segment_start = Instant::now();
+                }
+            }
+            Some(PeekResponseUnary::Error(e)) => {
+                client.send(PeekResponseUnary::Error(e));
+                return;
+            }
+            Some(PeekResponseUnary::Canceled) => {
+                // Finish the archived updates, so they are readable, before reporting the
+                // cancellation.
+                if frontier > lower {
+                    match archiver.finish_segment(Some(frontier)).await {
+                        Ok(manifest) => client.send_manifest(&manifest),
+                        Err(e) => break Err(e),
+                    }
+                }
+                client.send(PeekResponseUnary::Canceled);
+                return;
+            }
+            None => {
+                // The subscribe was retired. Only a complete subscribe finishes the archive.
+                let upper = match complete_rx.try_recv() {
+                    Ok(()) => None,
+                    Err(_) => Some(frontier),
+                };
+                break archiver.finish_segment(upper).await.map(|manifest| {
+                    client.send_manifest(&manifest);
+                });
+            }
+        }
+    };
+
+    if let Err(e) = result {
+        client.send(PeekResponseUnary::Error(
+            e.display_with_causes().to_string(),
+        ));
+    }
+}
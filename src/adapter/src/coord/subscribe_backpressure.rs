@@ -0,0 +1,121 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Backpressure for subscribes driven by `FETCH`es of a cursor.
+//!
+//! A cursor's subscribe buffers the rows it has sent until its client fetches them. Once a buffer
+//! holds `subscribe_cursor_buffer_high_watermark` rows, we pause the subscribe, which makes its
+//! replicas hold back further updates. When the client has drained the buffer to
+//! `subscribe_cursor_buffer_low_watermark` rows, we resume the subscribe and the replicas send
+//! everything they held back in a single batch.
+//!
+//! The pause state of each cursor's subscribe is recorded in `mz_internal.mz_subscription_buffers`,
+//! together with the buffered rows at the time the subscribe was last paused or resumed. We don't
+//! record every change of the buffer, which would turn every batch sent to a cursor into a write
+//! to the table.
+
+use mz_adapter_types::dyncfgs::{
+    SUBSCRIBE_CURSOR_BUFFER_HIGH_WATERMARK, SUBSCRIBE_CURSOR_BUFFER_LOW_WATERMARK,
+};
+use mz_repr::GlobalId;
+use tracing::{debug, warn};
+
+use crate::active_compute_sink::ActiveComputeSink;
+use crate::coord::Coordinator;
+
+impl Coordinator {
+    /// Pauses the subscribe `sink_id` if it is driven by a cursor whose buffer has grown beyond
+    /// the high watermark.
+    ///
+    /// This must be called after the subscribe has sent a batch of rows to its client.
+    pub(crate) fn limit_subscribe_buffer(&mut self, sink_id: GlobalId) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let high_watermark = SUBSCRIBE_CURSOR_BUFFER_HIGH_WATERMARK.get(dyncfgs);
+        let low_watermark = SUBSCRIBE_CURSOR_BUFFER_LOW_WATERMARK
+            .get(dyncfgs)
+            .min(high_watermark);
+
+        let Some(ActiveComputeSink::Subscribe(subscribe)) =
+            self.active_compute_sinks.get_mut(&sink_id)
+        else {
+            return;
+        };
+        if subscribe.buffer.cursor().is_none() {
+            return;
+        }
+
+        let buffered_rows = subscribe.buffer.rows();
+        if high_watermark > 0
+            && !subscribe.paused
+            && buffered_rows >= high_watermark
+            && subscribe.buffer.pause(low_watermark)
+        {
+            debug!(%sink_id, buffered_rows, "pausing subscribe");
+            subscribe.paused = true;
+            if let Err(e) =
+                self.controller
+                    .compute
+                    .set_subscribe_paused(subscribe.cluster_id, sink_id, true)
+            {
+                warn!(%sink_id, "failed to pause subscribe: {e}");
+            }
+        }
+
+        self.report_subscribe_buffer(sink_id);
+    }
+
+    /// Resumes the subscribe `sink_id`, whose client has drained its buffer to the low watermark.
+    pub(crate) fn resume_subscribe(&mut self, sink_id: GlobalId) {
+        // The subscribe may have finished while it was paused.
+        let Some(ActiveComputeSink::Subscribe(subscribe)) =
+            self.active_compute_sinks.get_mut(&sink_id)
+        else {
+            return;
+        };
+        if !subscribe.paused {
+            return;
+        }
+
+        debug!(%sink_id, buffered_rows = subscribe.buffer.rows(), "resuming subscribe");
+        subscribe.paused = false;
+        if let Err(e) =
+            self.controller
+                .compute
+                .set_subscribe_paused(subscribe.cluster_id, sink_id, false)
+        {
+            warn!(%sink_id, "failed to resume subscribe: {e}");
+        }
+
+        self.report_subscribe_buffer(sink_id);
+    }
+
+    /// Records the current buffer state of the subscribe `sink_id` in `mz_subscription_buffers`,
+    /// if it is driven by a cursor and it hasn't been recorded yet or has been paused or resumed
+    /// since.
+    fn report_subscribe_buffer(&mut self, sink_id: GlobalId) {
+        let Some(ActiveComputeSink::Subscribe(subscribe)) =
+            self.active_compute_sinks.get_mut(&sink_id)
+        else {
+            return;
+        };
+        let reported_paused = subscribe.reported_buffer.map(|(_, paused)| paused);
+        if subscribe.buffer.cursor().is_none() || reported_paused == Some(subscribe.paused) {
+            return;
+        }
+        let current = Some((subscribe.buffer.rows(), subscribe.paused));
+
+        let state = self.catalog.state();
+        let mut updates = Vec::new();
+        updates.extend(state.pack_subscribe_buffer_update(sink_id, subscribe, -1));
+        subscribe.reported_buffer = current;
+        updates.extend(state.pack_subscribe_buffer_update(sink_id, subscribe, 1));
+        let updates = self.resolve_cached_builtin_table_updates(updates);
+        self.builtin_table_update().background(updates);
+    }
+}
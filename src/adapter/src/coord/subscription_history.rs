@@ -0,0 +1,56 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The history of subscriptions.
+//!
+//! Unlike `mz_subscriptions`, which only describes the subscriptions that are active,
+//! `mz_internal.mz_subscription_history` records when each subscription started and when and why
+//! it was retired, along with the number of rows and bytes it emitted to its client, to account
+//! for the usage of streaming consumers.
+
+use mz_repr::{Datum, GlobalId, Row};
+use mz_storage_client::controller::IntrospectionType;
+
+use crate::active_compute_sink::{ActiveComputeSinkRetireReason, ActiveSubscribe};
+use crate::coord::Coordinator;
+
+impl Coordinator {
+    /// Packs the row of `mz_subscription_history` that records an event of the subscribe `id`.
+    ///
+    /// `reason` is the reason for the subscribe's retirement, if the event is its retirement.
+    pub(crate) fn pack_subscription_event(
+        &self,
+        id: GlobalId,
+        subscribe: &ActiveSubscribe,
+        reason: Option<&ActiveComputeSinkRetireReason>,
+    ) -> Row {
+        let occurred_at = mz_ore::now::to_datetime((self.catalog().config().now)());
+        let event_type = match reason {
+            Some(_) => "ended",
+            None => "started",
+        };
+        Row::pack_slice(&[
+            Datum::TimestampTz(occurred_at.try_into().expect("must fit")),
+            Datum::String(&id.to_string()),
+            Datum::Uuid(subscribe.session_uuid),
+            Datum::String(&subscribe.cluster_id.to_string()),
+            Datum::String(event_type),
+            Datum::UInt64(subscribe.rows_emitted),
+            Datum::UInt64(subscribe.bytes_emitted),
+            Datum::from(reason.map(|reason| reason.name())),
+        ])
+    }
+
+    /// Appends the rows packed by `pack_subscription_event` to `mz_subscription_history`.
+    pub(crate) async fn record_subscription_events(&mut self, rows: Vec<Row>) {
+        let updates = rows.into_iter().map(|row| (row, 1)).collect();
+        self.append_introspection_updates(IntrospectionType::SubscriptionHistory, updates)
+            .await;
+    }
+}
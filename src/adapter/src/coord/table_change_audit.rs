@@ -0,0 +1,96 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The audit of writes to tables.
+//!
+//! Writes to tables that are created `WITH (CHANGE AUDIT)` are recorded in
+//! `mz_internal.mz_table_change_audit` when they are committed, along with the
+//! role, session and statement that made them, so that mutations of the table
+//! can be attributed after the fact. The commit timestamp of each write lets
+//! the contents of the table be inspected before and after the write.
+
+use std::collections::BTreeMap;
+
+use mz_catalog::memory::objects::CatalogItem;
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_sql::session::metadata::SessionMetadata;
+use mz_storage_client::controller::IntrospectionType;
+
+use crate::coord::Coordinator;
+use crate::session::WriteOp;
+use crate::ExecuteContext;
+
+/// The number of rows that a transaction inserted into and deleted from a table.
+#[derive(Debug, Default)]
+struct TableChanges {
+    inserted: u64,
+    deleted: u64,
+}
+
+impl TableChanges {
+    fn add(&mut self, rows: &[(Row, Diff)]) {
+        for (_, diff) in rows {
+            if *diff > 0 {
+                self.inserted += diff.unsigned_abs();
+            } else {
+                self.deleted += diff.unsigned_abs();
+            }
+        }
+    }
+}
+
+impl Coordinator {
+    /// Returns whether writes to the table `id` are audited.
+    fn is_change_audited(&self, id: &GlobalId) -> bool {
+        match self.catalog().try_get_entry(id).map(|entry| entry.item()) {
+            Some(CatalogItem::Table(table)) => table.change_audit,
+            _ => false,
+        }
+    }
+
+    /// Packs the rows of `mz_table_change_audit` that record the `writes` of the transaction of
+    /// `ctx`, which commit at `commit_ts`, to the tables whose writes are audited.
+    pub(crate) fn pack_table_changes(
+        &self,
+        ctx: &ExecuteContext,
+        writes: &[WriteOp],
+        commit_ts: Timestamp,
+    ) -> Vec<Row> {
+        let mut changes: BTreeMap<GlobalId, TableChanges> = BTreeMap::new();
+        for WriteOp { id, rows } in writes {
+            if self.is_change_audited(id) {
+                changes.entry(*id).or_default().add(rows);
+            }
+        }
+        let session = ctx.session();
+        let role_id = session.current_role_id().to_string();
+        let statement_id = ctx.extra().contents().map(|id| id.uuid());
+        changes
+            .into_iter()
+            .map(|(id, changes)| {
+                Row::pack_slice(&[
+                    Datum::String(&id.to_string()),
+                    Datum::MzTimestamp(commit_ts),
+                    Datum::String(&role_id),
+                    Datum::Uuid(session.uuid()),
+                    statement_id.map_or(Datum::Null, Datum::Uuid),
+                    Datum::UInt64(changes.inserted),
+                    Datum::UInt64(changes.deleted),
+                ])
+            })
+            .collect()
+    }
+
+    /// Appends the rows packed by `pack_table_changes` to `mz_table_change_audit`.
+    pub(crate) async fn record_table_changes(&mut self, rows: Vec<Row>) {
+        let updates = rows.into_iter().map(|row| (row, 1)).collect();
+        self.append_introspection_updates(IntrospectionType::TableChangeAudit, updates)
+            .await;
+    }
+}
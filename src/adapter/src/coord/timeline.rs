@@ -171,6 +171,32 @@ impl Coordinator {
         }
     }
 
+    /// Returns whether the timestamp oracle is unavailable, i.e., whether its
+    /// operations have been failing for longer than
+    /// `timestamp_oracle_circuit_breaker_timeout`.
+    ///
+    /// Operations that need the oracle are rejected while it is unavailable,
+    /// so nothing would notice it becoming available again. Instead, this
+    /// probes the oracle in the background, which closes the circuit breaker
+    /// once the probe succeeds.
+    pub(crate) fn timestamp_oracle_unavailable(&self) -> bool {
+        let Some(config) = self.pg_timestamp_oracle_config.as_ref() else {
+            return false;
+        };
+        if !config.circuit_breaker_open() {
+            return false;
+        }
+        if config.circuit_breaker.start_probe() {
+            let circuit_breaker = Arc::clone(&config.circuit_breaker);
+            let oracle = self.get_local_timestamp_oracle();
+            mz_ore::task::spawn(|| "timestamp_oracle_probe", async move {
+                oracle.read_ts().await;
+                circuit_breaker.finish_probe();
+            });
+        }
+        true
+    }
+
     /// Ensures that a global timeline state exists for `timeline`.
     pub(crate) async fn ensure_timeline_state<'a>(
         &'a mut self,
@@ -222,6 +248,7 @@ impl Coordinator {
                         "missing --timestamp-oracle-url even though the crdb-backed timestamp oracle was configured");
 
             let batching_metrics = Arc::clone(&pg_oracle_config.metrics);
+            let dynamic = Arc::clone(&pg_oracle_config.dynamic);
 
             let pg_oracle: Arc<dyn TimestampOracle<mz_repr::Timestamp> + Send + Sync> = Arc::new(
                 PostgresTimestampOracle::open(
@@ -234,7 +261,10 @@ impl Coordinator {
                 .await,
             );
 
-            let batching_oracle = BatchingTimestampOracle::new(batching_metrics, pg_oracle);
+            let batching_oracle =
+                BatchingTimestampOracle::new(batching_metrics, pg_oracle, move || {
+                    dynamic.batch_window()
+                });
 
             let oracle: Arc<dyn TimestampOracle<mz_repr::Timestamp> + Send + Sync> =
                 Arc::new(batching_oracle);
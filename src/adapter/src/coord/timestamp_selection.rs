@@ -9,6 +9,7 @@
 
 //! Logic for selecting timestamps for various operations on collections.
 
+use std::collections::BTreeSet;
 use std::fmt;
 
 use async_trait::async_trait;
@@ -34,6 +35,7 @@ use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::read_policy::ReadHolds;
 use crate::coord::timeline::TimelineContext;
 use crate::coord::Coordinator;
+use crate::notice::AdapterNotice;
 use crate::optimize::dataflows::{prep_scalar_expr, ExprPrepStyle};
 use crate::session::Session;
 use crate::AdapterError;
@@ -253,6 +255,31 @@ pub trait TimestampProvider {
                 ))
     }
 
+    /// Returns the reason why a query with the given configuration is executed under
+    /// serializable instead of the requested `isolation_level`, if it is.
+    ///
+    /// A query that does not depend on a timestamp has no timeline to read a linearized
+    /// timestamp from, so it is executed at an arbitrary timestamp.
+    fn isolation_level_downgrade_reason(
+        isolation_level: &IsolationLevel,
+        when: &QueryWhen,
+        timeline_context: &TimelineContext,
+        source_ids: &BTreeSet<GlobalId>,
+    ) -> Option<&'static str> {
+        let linearizes = matches!(
+            isolation_level,
+            IsolationLevel::StrictSerializable | IsolationLevel::StrongSessionSerializable
+        ) && when.can_advance_to_timeline_ts();
+        if !linearizes || Self::get_timeline(timeline_context).is_some() {
+            return None;
+        }
+        if source_ids.is_empty() {
+            Some("the query is constant")
+        } else {
+            Some("the query only reads views that do not depend on a timestamp")
+        }
+    }
+
     /// Determines the timestamp for a query.
     ///
     /// Timestamp determination may fail due to the restricted validity of
@@ -299,6 +326,18 @@ pub trait TimestampProvider {
 
         let timeline = Self::get_timeline(timeline_context);
 
+        // Timestamps of different timelines aren't comparable, so a consistency token can only
+        // order queries that are in the same timeline as the write that returned it.
+        if let (Some(required), Some(timeline)) = (when.timeline(), &timeline) {
+            if required != timeline {
+                coord_bail!(
+                    "consistency token of timeline {:?} cannot be used in timeline {:?}",
+                    required,
+                    timeline
+                );
+            }
+        }
+
         {
             // TODO: We currently split out getting the oracle timestamp because
             // it's a potentially expensive call, but a call that can be done in an
@@ -519,6 +558,35 @@ fn generate_timestamp_not_valid_error_msg(
 }
 
 impl Coordinator {
+    /// Checks whether a read of `timeline_ctx` at `when` can run even if the
+    /// timestamp oracle is unavailable.
+    ///
+    /// Reads that need a linearized read timestamp are rejected while the oracle
+    /// is unavailable. Other reads are rejected as well, unless
+    /// `enable_timestamp_oracle_degraded_reads` is enabled, in which case they run
+    /// at the locally known frontiers and `session` is notified.
+    pub(crate) fn check_timestamp_oracle_for_read(
+        &self,
+        session: &Session,
+        timeline_ctx: &TimelineContext,
+        when: &QueryWhen,
+    ) -> Result<(), AdapterError> {
+        if Coordinator::get_timeline(timeline_ctx).is_none() || !self.timestamp_oracle_unavailable()
+        {
+            return Ok(());
+        }
+        let isolation_level = session.vars().transaction_isolation();
+        let degraded_reads = self
+            .catalog()
+            .system_config()
+            .enable_timestamp_oracle_degraded_reads();
+        if !degraded_reads || Coordinator::needs_linearized_read_ts(isolation_level, when) {
+            return Err(AdapterError::TimestampOracleUnavailable);
+        }
+        session.add_notice(AdapterNotice::TimestampOracleUnavailable);
+        Ok(())
+    }
+
     pub(crate) async fn oracle_read_ts(
         &self,
         session: &Session,
@@ -0,0 +1,324 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Webhook sinks.
+//!
+//! Unlike other sinks, which are storage exports, webhook sinks are compute dataflows that POST
+//! the updates of each timestamp to a URL. The write frontier of a webhook sink is the frontier up
+//! to which it has delivered all updates. Every `webhook_sink_delivery_record_interval`, the
+//! coordinator records the write frontiers that advanced in
+//! `mz_internal.mz_webhook_sink_delivery_history`, retracting the entry it recorded before, so the
+//! collection only contains the latest entry of each sink. When it installs the dataflow of a
+//! webhook sink again, e.g. after a restart, the dataflow resumes from the latest recorded
+//! frontier without a snapshot. Only the timestamps delivered after that recording are delivered
+//! again, with the same idempotency keys.
+//!
+//! Webhook sinks report their status in `mz_internal.mz_sink_status_history`: `running` when the
+//! coordinator installs their dataflow, and `stalled` when their dataflow can't be installed or
+//! reports a failure.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_adapter_types::dyncfgs::WEBHOOK_SINK_DELIVERY_RECORD_INTERVAL;
+use mz_catalog::builtin::MZ_WEBHOOK_SINK_DELIVERY_HISTORY;
+use mz_catalog::memory::objects::Sink;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::client::{Status, StatusUpdate};
+use mz_storage_client::controller::IntrospectionType;
+use mz_storage_types::sinks::StorageSinkConnection;
+use timely::progress::Antichain;
+use timely::PartialOrder;
+use tracing::{info, warn};
+
+use crate::coord::{Coordinator, Message};
+use crate::error::AdapterError;
+use crate::optimize::{self, Optimize};
+use crate::util::ResultExt;
+
+/// The delivery progress last recorded for each webhook sink.
+#[derive(Debug, Default)]
+pub(crate) struct WebhookSinkDeliveries {
+    /// The latest write frontier recorded for each webhook sink, i.e., the sink has delivered all
+    /// updates at times not beyond it, and the row that recorded it.
+    recorded: BTreeMap<GlobalId, (Timestamp, Row)>,
+    /// Rows of `mz_webhook_sink_delivery_history` to retract with the next recording.
+    retractions: Vec<Row>,
+    /// The webhook sinks whose failure has been recorded since their dataflow was installed.
+    failed: BTreeSet<GlobalId>,
+}
+
+impl Coordinator {
+    /// Loads the latest delivery progress of each webhook sink from
+    /// `mz_webhook_sink_delivery_history`.
+    ///
+    /// Entries of dropped sinks, and all but the latest entry of each sink, e.g., because a
+    /// previous coordinator could not retract them anymore, are retracted with the next recording.
+    pub(crate) async fn bootstrap_webhook_sink_deliveries(&mut self) -> Result<(), AdapterError> {
+        let id = self
+            .catalog()
+            .resolve_builtin_storage_collection(&MZ_WEBHOOK_SINK_DELIVERY_HISTORY);
+        let rows = self.controller.storage.snapshot_latest(id).await?;
+
+        let mut retractions = Vec::new();
+        let mut recorded: BTreeMap<GlobalId, (Timestamp, Row)> = BTreeMap::new();
+        for row in rows {
+            // Skip `occurred_at`.
+            let mut iter = row.iter().skip(1);
+            let sink_id = iter.next().expect("missing sink_id").unwrap_str();
+            let write_frontier = iter
+                .next()
+                .expect("missing write_frontier")
+                .unwrap_mz_timestamp();
+            let Some(sink_id) = sink_id
+                .parse()
+                .ok()
+                .filter(|id| self.catalog().try_get_entry(id).is_some())
+            else {
+                retractions.push(row);
+                continue;
+            };
+            match recorded.get(&sink_id) {
+                Some((latest, _)) if *latest >= write_frontier => retractions.push(row),
+                _ => {
+                    if let Some((_, previous)) = recorded.insert(sink_id, (write_frontier, row)) {
+                        retractions.push(previous);
+                    }
+                }
+            }
+        }
+        self.webhook_sink_deliveries.recorded = recorded;
+        self.webhook_sink_deliveries.retractions = retractions;
+        Ok(())
+    }
+
+    /// Installs the dataflow of the webhook sink `id` on its cluster.
+    ///
+    /// A sink with recorded delivery progress resumes from there, without a snapshot. If its inputs
+    /// are not readable there anymore, the sink fails instead, because it would miss updates.
+    pub(crate) async fn create_webhook_sink_dataflow(
+        &mut self,
+        id: GlobalId,
+        sink: &Sink,
+    ) -> Result<(), AdapterError> {
+        let StorageSinkConnection::Webhook(connection) = &sink.connection else {
+            unreachable!("not a webhook sink: {:?}", sink.connection);
+        };
+
+        // Validate `sink.from` is in fact a storage collection
+        self.controller.storage.check_exists(sink.from)?;
+
+        let resume_from = self
+            .webhook_sink_deliveries
+            .recorded
+            .get(&id)
+            .map(|(write_frontier, _)| *write_frontier);
+        let with_snapshot = sink.with_snapshot && resume_from.is_none();
+
+        let compute_instance = self
+            .instance_snapshot(sink.cluster_id)
+            .expect("compute instance does not exist");
+        let entry = self.catalog().get_entry(&id);
+        let debug_name = self
+            .catalog()
+            .resolve_full_name(entry.name(), None)
+            .to_string();
+        let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config())
+            .override_from(&self.catalog.get_cluster(sink.cluster_id).config.features());
+        let mut optimizer = optimize::webhook_sink::Optimizer::new(
+            self.owned_catalog(),
+            compute_instance,
+            id,
+            connection.clone(),
+            with_snapshot,
+            debug_name,
+            optimizer_config,
+            self.optimizer_metrics(),
+        );
+
+        let mut global_mir_plan = optimizer.catch_unwind_optimize(sink.from)?;
+
+        // Hold back the inputs until the dataflow is installed, at which point compute will have
+        // put in its own read holds.
+        let id_bundle = global_mir_plan.id_bundle(sink.cluster_id);
+        let read_holds = self.acquire_read_holds(&id_bundle);
+        let mut as_of = self.least_valid_read(&read_holds);
+        if let Some(resume_from) = resume_from {
+            let resume_time = resume_from;
+            let resume_from = Antichain::from_elem(resume_from);
+            if PartialOrder::less_than(&resume_from, &as_of) {
+                // The controller holds back the inputs of a sink at its write frontier, so this
+                // only happens if the dataflow wasn't installed for a while.
+                warn!(%id, ?resume_from, ?as_of, "webhook sink cannot resume");
+                let error = format!(
+                    "cannot resume delivering after {resume_time}, the inputs are only readable \
+                     at {:?}; recreate the sink to deliver a new snapshot",
+                    as_of.elements(),
+                );
+                self.record_webhook_sink_status(id, Status::Stalled, Some(error))
+                    .await;
+                return Ok(());
+            }
+            as_of = resume_from;
+        }
+        info!(%id, ?as_of, with_snapshot, "installing webhook sink dataflow");
+        global_mir_plan.set_as_of(as_of);
+
+        let global_lir_plan = optimizer.catch_unwind_optimize(global_mir_plan)?;
+        let (df_desc, _df_meta) = global_lir_plan.unapply();
+        self.ship_dataflow(df_desc, sink.cluster_id).await;
+
+        drop(read_holds);
+        self.webhook_sink_deliveries.failed.remove(&id);
+        self.record_webhook_sink_status(id, Status::Running, None)
+            .await;
+        Ok(())
+    }
+
+    /// Records that the webhook sink `id` failed on `replica_id`, unless a failure has already been
+    /// recorded since its dataflow was installed.
+    pub(crate) async fn record_webhook_sink_failure(
+        &mut self,
+        id: GlobalId,
+        replica_id: ReplicaId,
+        error: String,
+    ) {
+        warn!(%id, %replica_id, "webhook sink failed: {error}");
+        if self.catalog().try_get_entry(&id).is_none()
+            || !self.webhook_sink_deliveries.failed.insert(id)
+        {
+            return;
+        }
+        self.record_webhook_sink_status(id, Status::Stalled, Some(error))
+            .await;
+    }
+
+    /// Records `status` for the webhook sink `id` in `mz_sink_status_history`.
+    async fn record_webhook_sink_status(
+        &mut self,
+        id: GlobalId,
+        status: Status,
+        error: Option<String>,
+    ) {
+        let occurred_at = mz_ore::now::to_datetime((self.catalog().config().now)());
+        let mut update = StatusUpdate::new(id, occurred_at, status);
+        update.error = error;
+        self.append_introspection_updates(
+            IntrospectionType::SinkStatusHistory,
+            vec![(Row::from(update), 1)],
+        )
+        .await;
+    }
+
+    /// Drops the dataflows of the given webhook sinks.
+    pub(crate) fn drop_webhook_sinks(&mut self, sinks: Vec<(ClusterId, GlobalId)>) {
+        let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        let deliveries = &mut self.webhook_sink_deliveries;
+        for (cluster_id, id) in sinks {
+            if let Some((_, row)) = deliveries.recorded.remove(&id) {
+                deliveries.retractions.push(row);
+            }
+            deliveries.failed.remove(&id);
+            by_cluster.entry(cluster_id).or_default().push(id);
+        }
+        for (cluster_id, ids) in by_cluster {
+            let compute = &mut self.controller.compute;
+            // A cluster could have been dropped, so verify it exists. A sink that failed to resume
+            // has no dataflow.
+            if compute.instance_exists(cluster_id) {
+                let ids = ids
+                    .into_iter()
+                    .filter(|id| compute.collection(cluster_id, *id).is_ok())
+                    .collect();
+                compute
+                    .drop_collections(cluster_id, ids)
+                    .unwrap_or_terminate("cannot fail to drop collections");
+            }
+        }
+    }
+
+    /// Schedules the next recording of webhook sink delivery progress,
+    /// `webhook_sink_delivery_record_interval` from now.
+    pub(crate) fn schedule_webhook_sink_delivery_record(&self) {
+        let interval =
+            WEBHOOK_SINK_DELIVERY_RECORD_INTERVAL.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message(
+            "webhook_sink_delivery_record",
+            interval,
+            Message::WebhookSinkDeliveryRecord,
+        );
+    }
+
+    /// Records the write frontier of each webhook sink that advanced since it was last recorded
+    /// in `mz_webhook_sink_delivery_history`, retracting the previous entries, and schedules the
+    /// next recording.
+    pub(crate) async fn record_webhook_sink_deliveries(&mut self) {
+        let interval =
+            WEBHOOK_SINK_DELIVERY_RECORD_INTERVAL.get(self.catalog().system_config().dyncfgs());
+
+        // The recorded frontiers must match the entries of the collection, which read-only
+        // environments don't write.
+        if !interval.is_zero() && !self.controller.read_only() {
+            let sinks: Vec<_> = self
+                .catalog()
+                .entries()
+                .filter_map(|entry| {
+                    let sink = entry.sink()?;
+                    sink.connection
+                        .is_compute_sink()
+                        .then(|| (entry.id(), sink.cluster_id))
+                })
+                .collect();
+
+            let mut advanced = Vec::new();
+            for (id, cluster_id) in sinks {
+                // Sinks whose collections the controller doesn't know about yet, or anymore, are
+                // left out until the next recording.
+                let Ok(collection) = self.controller.compute.collection(cluster_id, id) else {
+                    continue;
+                };
+                // A sink with an empty write frontier has nothing left to deliver.
+                let Some(write_frontier) = collection.write_frontier().as_option().copied() else {
+                    continue;
+                };
+                let recorded = self.webhook_sink_deliveries.recorded.get(&id);
+                if recorded.map_or(true, |(recorded, _)| *recorded < write_frontier) {
+                    advanced.push((id, write_frontier));
+                }
+            }
+
+            let occurred_at = mz_ore::now::to_datetime((self.catalog().config().now)());
+            let occurred_at = Datum::TimestampTz(occurred_at.try_into().expect("must fit"));
+            let deliveries = &mut self.webhook_sink_deliveries;
+            let mut updates: Vec<(Row, Diff)> = std::mem::take(&mut deliveries.retractions)
+                .into_iter()
+                .map(|row| (row, -1))
+                .collect();
+            for (id, write_frontier) in advanced {
+                let id_str = id.to_string();
+                let row = Row::pack_slice(&[
+                    occurred_at,
+                    Datum::String(&id_str),
+                    Datum::MzTimestamp(write_frontier),
+                ]);
+                updates.push((row.clone(), 1));
+                if let Some((_, previous)) = deliveries.recorded.insert(id, (write_frontier, row)) {
+                    updates.push((previous, -1));
+                }
+            }
+
+            self.append_introspection_updates(
+                IntrospectionType::WebhookSinkDeliveryHistory,
+                updates,
+            )
+            .await;
+        }
+        self.schedule_webhook_sink_delivery_record();
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Classification of statements into workload classes.
+//!
+//! The `workload_class_rules` system variable holds a JSON array of rules, e.g.:
+//!
+//! ```json
+//! [
+//!     {
+//!         "class": "dashboards",
+//!         "application_name": "^grafana",
+//!         "sql": "(?i)^select",
+//!         "cluster": "serving",
+//!         "priority": "high",
+//!         "statement_timeout": "30s",
+//!         "sample_rate": 0.01
+//!     }
+//! ]
+//! ```
+//!
+//! Rules are checked in the order they are defined, and the first rule whose conditions all match
+//! a statement determines its class. A rule can require an exact match of the current `role` and
+//! regular expression matches of the session's `application_name` and of the statement's `sql`.
+//! Rules without conditions match every statement.
+//!
+//! A class determines the `cluster`, `statement_timeout` and statement logging `sample_rate` of the
+//! statements it classifies, as if each statement were preceded by `SET LOCAL`. Settings the
+//! session has chosen itself take precedence, so a class only changes variables that still hold
+//! their system or role default. Its `priority` is recorded, along with its name, in the
+//! `mz_workload_class_statements_total` metric.
+
+use mz_adapter_types::dyncfgs::WORKLOAD_CLASS_RULES;
+use mz_sql::ast::display::AstDisplay;
+use mz_sql::ast::{Raw, Statement};
+use mz_sql::session::metadata::SessionMetadata;
+use mz_sql::session::vars::{
+    Var, VarInput, CLUSTER, STATEMENT_LOGGING_SAMPLE_RATE, STATEMENT_TIMEOUT,
+};
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use tracing::{debug, warn};
+
+use crate::coord::Coordinator;
+use crate::session::Session;
+
+/// A rule that classifies statements into a workload class.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkloadClassRule {
+    /// The name of the class.
+    class: String,
+    /// The role that must be executing the statement.
+    #[serde(default)]
+    role: Option<String>,
+    /// A pattern that the session's `application_name` must match.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    application_name: Option<Regex>,
+    /// A pattern that the statement's SQL must match.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    sql: Option<Regex>,
+    /// The default cluster of the class.
+    #[serde(default)]
+    cluster: Option<String>,
+    /// The priority of the class.
+    #[serde(default)]
+    priority: WorkloadPriority,
+    /// The default statement timeout of the class.
+    #[serde(default)]
+    statement_timeout: Option<String>,
+    /// The default statement logging sample rate of the class.
+    #[serde(default)]
+    sample_rate: Option<f64>,
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(buf) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    Regex::new(&buf).map(Some).map_err(serde::de::Error::custom)
+}
+
+impl WorkloadClassRule {
+    fn matches(&self, role: &str, application_name: &str, sql: &str) -> bool {
+        self.role.as_deref().map_or(true, |r| r == role)
+            && self
+                .application_name
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(application_name))
+            && self
+                .sql
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(sql))
+    }
+}
+
+/// The priority of a workload class.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkloadPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl WorkloadPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkloadPriority::Low => "low",
+            WorkloadPriority::Normal => "normal",
+            WorkloadPriority::High => "high",
+        }
+    }
+}
+
+/// The decoded `workload_class_rules`.
+///
+/// Decoding compiles the rules' patterns, so we only redo it when the system variable changes.
+#[derive(Debug)]
+pub(crate) struct WorkloadClassifier {
+    config: serde_json::Value,
+    rules: Vec<WorkloadClassRule>,
+}
+
+impl Default for WorkloadClassifier {
+    fn default() -> Self {
+        WorkloadClassifier {
+            config: serde_json::json!([]),
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl WorkloadClassifier {
+    /// Decodes `config` into rules, unless they are already decoded.
+    fn update(&mut self, config: serde_json::Value) {
+        if config == self.config {
+            return;
+        }
+        self.rules = match serde_json::from_value(config.clone()) {
+            Ok(rules) => rules,
+            Err(e) => {
+                warn!(%e, "failed to decode workload_class_rules");
+                Vec::new()
+            }
+        };
+        self.config = config;
+    }
+
+    /// Returns the rule that determines the class of a statement, if any.
+    fn classify(
+        &self,
+        role: &str,
+        application_name: &str,
+        sql: &str,
+    ) -> Option<&WorkloadClassRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(role, application_name, sql))
+    }
+}
+
+impl Coordinator {
+    /// Classifies `stmt` into a workload class and applies the class's settings to `session` for
+    /// the rest of the current transaction.
+    ///
+    /// This must be called before beginning to log the statement, so that its sample rate applies.
+    pub(crate) fn apply_workload_class(&mut self, session: &mut Session, stmt: &Statement<Raw>) {
+        if session.user().is_internal() {
+            return;
+        }
+        let config = WORKLOAD_CLASS_RULES.get(self.catalog().system_config().dyncfgs());
+        self.workload_classifier.update(config);
+        if self.workload_classifier.rules.is_empty() {
+            return;
+        }
+
+        let role = &self.catalog().get_role(session.current_role_id()).name;
+        let sql = stmt.to_ast_string();
+        let Some(rule) = self
+            .workload_classifier
+            .classify(role, session.application_name(), &sql)
+        else {
+            return;
+        };
+        debug!(class = %rule.class, priority = rule.priority.as_str(), "classified statement");
+        self.metrics
+            .workload_class_statements
+            .with_label_values(&[&rule.class, rule.priority.as_str()])
+            .inc();
+
+        let settings = [
+            (CLUSTER.name(), rule.cluster.clone()),
+            (STATEMENT_TIMEOUT.name(), rule.statement_timeout.clone()),
+            (
+                STATEMENT_LOGGING_SAMPLE_RATE.name(),
+                rule.sample_rate.map(|rate| rate.to_string()),
+            ),
+        ];
+        let system_vars = self.catalog().system_config();
+        for (name, value) in settings {
+            let Some(value) = value else {
+                continue;
+            };
+            let is_default = session
+                .vars()
+                .inspect(name)
+                .map_or(false, |var| var.is_default());
+            if !is_default {
+                continue;
+            }
+            if let Err(e) =
+                session
+                    .vars_mut()
+                    .set(Some(system_vars), name, VarInput::Flat(&value), true)
+            {
+                warn!(class = %rule.class, %e, "failed to apply workload class setting {name}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_classify() {
+        let mut classifier = WorkloadClassifier::default();
+        classifier.update(serde_json::json!([
+            {
+                "class": "etl",
+                "role": "loader",
+                "sql": "(?i)^insert",
+                "priority": "low",
+            },
+            {
+                "class": "dashboards",
+                "application_name": "^grafana",
+                "cluster": "serving",
+                "priority": "high",
+                "statement_timeout": "30s",
+                "sample_rate": 0.01,
+            },
+            { "class": "default" },
+        ]));
+
+        let class = |classifier: &WorkloadClassifier, role, application_name, sql| {
+            classifier
+                .classify(role, application_name, sql)
+                .map(|rule| (rule.class.clone(), rule.priority))
+        };
+        assert_eq!(
+            class(&classifier, "loader", "psql", "INSERT INTO t VALUES (1)"),
+            Some(("etl".into(), WorkloadPriority::Low))
+        );
+        assert_eq!(
+            class(&classifier, "loader", "grafana-7", "SELECT 1"),
+            Some(("dashboards".into(), WorkloadPriority::High))
+        );
+        assert_eq!(
+            class(&classifier, "analyst", "psql", "INSERT INTO t VALUES (1)"),
+            Some(("default".into(), WorkloadPriority::Normal))
+        );
+
+        // Invalid rules classify nothing.
+        classifier.update(serde_json::json!([{ "class": "broken", "sql": "(" }]));
+        assert_eq!(class(&classifier, "analyst", "psql", "SELECT 1"), None);
+        classifier.update(serde_json::json!([{ "class": "typo", "clutser": "serving" }]));
+        assert_eq!(class(&classifier, "analyst", "psql", "SELECT 1"), None);
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Detection of deferred writes that are starved of the write lock.
+//!
+//! Writes that can't acquire the coordinator's write lock are deferred in `write_lock_wait_group`
+//! until the lock is released. A write that holds the lock for a long time silently holds up all
+//! writes behind it. Once the oldest deferred write has waited for longer than
+//! `write_lock_wait_warning_threshold`, the coordinator logs the last recorded holder of the lock
+//! and the writes that wait for it, once per starved write. The deferred statements that have
+//! waited for longer than the threshold are listed in `mz_starved_write_lock_waiters`, with the
+//! holder of the lock at the time they were first found to be starved.
+//!
+//! With `abort_starved_write_lock_waiters`, the oldest deferred statement that has waited for
+//! longer than the threshold is additionally aborted with an error, so that its session doesn't
+//! wait indefinitely. The number of starved writes is reported in the
+//! `mz_starved_write_lock_waiters_total` metric.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use mz_adapter_types::connection::ConnectionId;
+use mz_adapter_types::dyncfgs::{
+    ABORT_STARVED_WRITE_LOCK_WAITERS, WRITE_LOCK_WAIT_WARNING_THRESHOLD,
+};
+use mz_ore::now::EpochMillis;
+use mz_ore::str::separated;
+use tracing::warn;
+
+use crate::coord::appends::Deferred;
+use crate::coord::{Coordinator, Message};
+use crate::util::diff_keyed_rows;
+use crate::AdapterError;
+
+/// The longest time between two checks while the check is enabled.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What the write lock was granted to.
+#[derive(Debug)]
+pub(crate) enum WriteLockGrantee {
+    /// A statement of a session, with its kind if known.
+    Statement {
+        conn_id: ConnectionId,
+        statement: Option<String>,
+    },
+    /// A group commit of the writes of the contained connections.
+    GroupCommit { conn_ids: Vec<ConnectionId> },
+}
+
+/// The last grant of the write lock.
+#[derive(Debug)]
+struct WriteLockHolder {
+    grantee: WriteLockGrantee,
+    granted_at: Instant,
+}
+
+/// A row of `mz_starved_write_lock_waiters`.
+#[derive(Clone, Debug, PartialEq)]
+struct StarvedWriteLockWaiter {
+    /// The kind of the deferred statement.
+    statement: String,
+    deferred_at: EpochMillis,
+    /// The holder of the write lock when the statement was first found to be starved.
+    lock_holder: Option<String>,
+}
+
+/// The state of the detection of starved deferred writes.
+#[derive(Debug, Default)]
+pub(crate) struct WriteLockWaits {
+    /// The last recorded holder of the write lock. It is stale once the lock has been released.
+    holder: Option<WriteLockHolder>,
+    /// The time at which the last reported starved write was deferred.
+    reported: Option<Instant>,
+    /// The rows of `mz_starved_write_lock_waiters`, by the time each statement was deferred and
+    /// its connection.
+    starved: BTreeMap<(Instant, ConnectionId), StarvedWriteLockWaiter>,
+}
+
+impl Coordinator {
+    /// Records that the write lock was granted to `grantee`.
+    pub(crate) fn record_write_lock_grant(&mut self, grantee: WriteLockGrantee) {
+        self.write_lock_waits.holder = Some(WriteLockHolder {
+            grantee,
+            granted_at: Instant::now(),
+        });
+    }
+
+    /// Schedules the next check for starved deferred writes.
+    pub(crate) fn schedule_write_lock_wait_check(&self) {
+        let threshold =
+            WRITE_LOCK_WAIT_WARNING_THRESHOLD.get(self.catalog().system_config().dyncfgs());
+        self.schedule_periodic_message(
+            "check_write_lock_waits",
+            threshold.min(MAX_CHECK_INTERVAL),
+            Message::CheckWriteLockWaits,
+        );
+    }
+
+    /// Logs the state of the write lock if the oldest deferred write has waited for longer than
+    /// the threshold, aborts the oldest starved statement if enabled, updates
+    /// `mz_starved_write_lock_waiters`, and schedules the next check.
+    pub(crate) fn check_write_lock_waits(&mut self) {
+        let dyncfgs = self.catalog().system_config().dyncfgs();
+        let threshold = WRITE_LOCK_WAIT_WARNING_THRESHOLD.get(dyncfgs);
+        let abort = ABORT_STARVED_WRITE_LOCK_WAITERS.get(dyncfgs);
+
+        let oldest = self
+            .write_lock_wait_group
+            .iter_with_pushed_at()
+            .next()
+            .map(|(pushed_at, _)| pushed_at);
+        match oldest {
+            Some(pushed_at) if !threshold.is_zero() && pushed_at.elapsed() > threshold => {
+                if self.write_lock_waits.reported != Some(pushed_at) {
+                    self.write_lock_waits.reported = Some(pushed_at);
+                    warn!(
+                        "deferred write waited for the write lock for {:?}\n{}",
+                        pushed_at.elapsed(),
+                        self.write_lock_diagnostics(),
+                    );
+                    self.metrics
+                        .starved_write_lock_waiters
+                        .with_label_values(&["reported"])
+                        .inc();
+                }
+                if abort {
+                    self.abort_oldest_write_lock_waiter(threshold);
+                }
+            }
+            Some(_) => {}
+            None => self.write_lock_waits.reported = None,
+        }
+
+        self.update_starved_write_lock_waiters(threshold);
+        self.schedule_write_lock_wait_check();
+    }
+
+    /// Replaces the rows of `mz_starved_write_lock_waiters` with the deferred statements that
+    /// have waited for longer than `threshold`.
+    fn update_starved_write_lock_waiters(&mut self, threshold: Duration) {
+        let now = (self.catalog().config().now)();
+        let lock_holder = self.write_lock_holder().map(|(holder, _)| holder);
+        let previous = std::mem::take(&mut self.write_lock_waits.starved);
+        let mut starved = BTreeMap::new();
+        if !threshold.is_zero() {
+            for (pushed_at, deferred) in self.write_lock_wait_group.iter_with_pushed_at() {
+                let Deferred::Plan(deferred) = deferred else {
+                    continue;
+                };
+                let waited = pushed_at.elapsed();
+                if waited <= threshold {
+                    continue;
+                }
+                let key = (pushed_at, deferred.ctx.session().conn_id().clone());
+                let waiter = match previous.get(&key) {
+                    Some(waiter) => waiter.clone(),
+                    None => StarvedWriteLockWaiter {
+                        statement: deferred.plan.name().into(),
+                        deferred_at: now.saturating_sub(
+                            EpochMillis::try_from(waited.as_millis()).unwrap_or(EpochMillis::MAX),
+                        ),
+                        lock_holder: lock_holder.clone(),
+                    },
+                };
+                starved.insert(key, waiter);
+            }
+        }
+        self.write_lock_waits.starved = starved;
+
+        let state = self.catalog().state();
+        let updates: Vec<_> = diff_keyed_rows(&previous, &self.write_lock_waits.starved)
+            .into_iter()
+            .map(|(((_, conn_id), waiter), diff)| {
+                state.pack_starved_write_lock_waiter_update(
+                    conn_id,
+                    &waiter.statement,
+                    waiter.deferred_at,
+                    waiter.lock_holder.as_deref(),
+                    diff,
+                )
+            })
+            .collect();
+        let updates = self.resolve_cached_builtin_table_updates(updates);
+        if !updates.is_empty() {
+            self.builtin_table_update().background(updates);
+        }
+    }
+
+    /// Aborts the oldest deferred statement that has waited for longer than `threshold`.
+    fn abort_oldest_write_lock_waiter(&mut self, threshold: Duration) {
+        let idx =
+            self.write_lock_wait_group
+                .iter_with_pushed_at()
+                .position(|(pushed_at, deferred)| {
+                    matches!(deferred, Deferred::Plan(_)) && pushed_at.elapsed() > threshold
+                });
+        let Some(idx) = idx else {
+            return;
+        };
+        let Some(Deferred::Plan(deferred)) = self.write_lock_wait_group.remove(idx) else {
+            unreachable!("known to be a deferred plan");
+        };
+        warn!(
+            "aborting {} of connection {}, which was starved of the write lock",
+            deferred.plan.name(),
+            deferred.ctx.session().conn_id(),
+        );
+        self.metrics
+            .starved_write_lock_waiters
+            .with_label_values(&["aborted"])
+            .inc();
+        // The lock acquisition that was spawned for the statement still grants the lock to the
+        // next waiter, or releases it if there is none.
+        deferred.ctx.retire(Err(AdapterError::WriteLockTimeout));
+    }
+
+    /// Describes the connection `conn_id`.
+    fn describe_write_lock_conn(&self, conn_id: &ConnectionId) -> String {
+        match self.active_conns.get(conn_id) {
+            Some(conn) => format!(
+                "connection {conn_id} (user {}, application {:?})",
+                conn.user().name,
+                conn.application_name(),
+            ),
+            None => format!("connection {conn_id}"),
+        }
+    }
+
+    /// Describes the holder of the write lock and for how long it has held the lock, if the lock
+    /// is held by a known holder.
+    fn write_lock_holder(&self) -> Option<(String, Duration)> {
+        if self.write_lock_wait_group.try_lock_owned().is_ok() {
+            return None;
+        }
+        let WriteLockHolder {
+            grantee,
+            granted_at,
+        } = self.write_lock_waits.holder.as_ref()?;
+        let grantee = match grantee {
+            WriteLockGrantee::Statement { conn_id, statement } => format!(
+                "{} of {}",
+                statement.as_deref().unwrap_or("statement"),
+                self.describe_write_lock_conn(conn_id),
+            ),
+            WriteLockGrantee::GroupCommit { conn_ids } => format!(
+                "group commit of the writes of connections [{}]",
+                separated(", ", conn_ids)
+            ),
+        };
+        Some((grantee, granted_at.elapsed()))
+    }
+
+    /// Describes the holder of the write lock and the writes that wait for it.
+    fn write_lock_diagnostics(&self) -> String {
+        let mut diagnostics = String::new();
+        match self.write_lock_holder() {
+            Some((grantee, held_for)) => {
+                let _ = writeln!(diagnostics, "write lock held by {grantee} for {held_for:?}");
+            }
+            None if self.write_lock_wait_group.try_lock_owned().is_err() => {
+                diagnostics.push_str("write lock held by an unknown holder\n")
+            }
+            None => diagnostics.push_str("write lock not held\n"),
+        }
+        for (pushed_at, deferred) in self.write_lock_wait_group.iter_with_pushed_at() {
+            let waiter = match deferred {
+                Deferred::Plan(deferred) => format!(
+                    "{} of {}",
+                    deferred.plan.name(),
+                    self.describe_write_lock_conn(deferred.ctx.session().conn_id()),
+                ),
+                Deferred::GroupCommit => "group commit".into(),
+            };
+            let _ = writeln!(
+                diagnostics,
+                "  waiting for {:?}: {waiter}",
+                pushed_at.elapsed()
+            );
+        }
+        diagnostics
+    }
+}
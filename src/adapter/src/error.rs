@@ -132,6 +132,12 @@ pub enum AdapterError {
         limit: String,
         current: String,
     },
+    /// The orchestrator does not have the capacity to run the replicas being created.
+    InsufficientReplicaCapacity {
+        size: String,
+        schedulable: u16,
+        required: u16,
+    },
     /// Result size of a query is too large.
     ResultSize(String),
     /// The specified feature is not permitted in safe mode.
@@ -144,6 +150,10 @@ pub enum AdapterError {
     Canceled,
     /// An idle session in a transaction has timed out.
     IdleInTransactionSessionTimeout,
+    /// The session was terminated with `ALTER SYSTEM TERMINATE SESSIONS`.
+    SessionTerminated,
+    /// The statement was aborted after it waited too long for the write lock.
+    WriteLockTimeout,
     /// The transaction is in single-subscribe mode.
     SubscribeOnlyTransaction,
     /// An error occurred in the optimizer.
@@ -155,8 +165,12 @@ pub enum AdapterError {
     },
     /// A user tried to perform an action that they were unauthorized to do.
     Unauthorized(rbac::UnauthorizedError),
+    /// The identified connection does not exist.
+    UnknownConnection(u32),
     /// The named cursor does not exist.
     UnknownCursor(String),
+    /// The cursor handoff token does not exist, has expired, or belongs to another role.
+    UnknownCursorHandoff,
     /// The named role does not exist.
     UnknownLoginRole(String),
     UnknownPreparedStatement(String),
@@ -230,10 +244,23 @@ pub enum AdapterError {
     /// Something attempted a write (to catalog, storage, tables, etc.) while in
     /// read-only mode.
     ReadOnly,
+    /// A statement that changes the catalog was attempted while `read_only_ddl`
+    /// is enabled.
+    ReadOnlyDdl(String),
+    /// A strict serializable read was attempted on a read-only replica.
+    StrictSerializableOnReadOnlyReplica,
+    /// An operation that needs the timestamp oracle was attempted while the
+    /// oracle is unavailable.
+    TimestampOracleUnavailable,
 }
 
 impl AdapterError {
     pub fn into_response(self, severity: Severity) -> ErrorResponse {
+        // Terminated sessions are closed, whatever the statement that observes the termination.
+        let severity = match self {
+            AdapterError::SessionTerminated => Severity::Fatal,
+            _ => severity,
+        };
         ErrorResponse {
             severity,
             code: self.code(),
@@ -343,6 +370,7 @@ impl AdapterError {
             AdapterError::RtrTimeout(name) => Some(format!("{name} failed to ingest data up to the real-time recency point")),
             AdapterError::RtrDropFailure(name) => Some(format!("{name} dropped before ingesting data to the real-time recency point")),
             AdapterError::UserSessionsDisallowed => Some("Your organization has been blocked. Please contact support.".to_string()),
+            AdapterError::ReadOnlyDdl(_) => Some("Changes to the catalog are disabled by the read_only_ddl system parameter, e.g. during a change freeze. Reads and subscribes are still allowed.".to_string()),
             _ => None,
         }
     }
@@ -389,6 +417,16 @@ impl AdapterError {
             AdapterError::ResourceExhaustion { resource_type, .. } => Some(format!(
                 "Drop an existing {resource_type} or contact support to request a limit increase."
             )),
+            AdapterError::InsufficientReplicaCapacity { .. } => Some(
+                "Choose a smaller size or fewer replicas, or try again once other replicas have \
+                 been dropped."
+                    .into(),
+            ),
+            AdapterError::WriteLockTimeout => Some(
+                "Another write held the write lock for longer than \
+                 write_lock_wait_warning_threshold. Retry the statement."
+                    .into(),
+            ),
             AdapterError::StatementTimeout => Some(
                 "Consider increasing the maximum allowed statement duration for this session by \
                  setting the statement_timeout session variable. For example, `SET \
@@ -413,6 +451,17 @@ impl AdapterError {
             Self::SubsourceAlreadyReferredTo { .. } => {
                 Some("Specify target table names using FOR TABLES (foo AS bar), or limit the upstream tables using FOR SCHEMAS (foo)".into())
             },
+            AdapterError::StrictSerializableOnReadOnlyReplica => Some(
+                "Use `SET TRANSACTION_ISOLATION = 'SERIALIZABLE'` or connect to the leader to run \
+                 strict serializable reads."
+                    .into(),
+            ),
+            AdapterError::TimestampOracleUnavailable => Some(
+                "Serializable reads can run while the timestamp oracle is unavailable if \
+                 enable_timestamp_oracle_degraded_reads is enabled. Writes, changes to the \
+                 catalog and strict serializable reads are rejected until it is available again."
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -484,9 +533,12 @@ impl AdapterError {
             AdapterError::IdleInTransactionSessionTimeout => {
                 SqlState::IDLE_IN_TRANSACTION_SESSION_TIMEOUT
             }
+            AdapterError::SessionTerminated => SqlState::ADMIN_SHUTDOWN,
+            AdapterError::WriteLockTimeout => SqlState::LOCK_NOT_AVAILABLE,
             AdapterError::RecursionLimit(_) => SqlState::INTERNAL_ERROR,
             AdapterError::RelationOutsideTimeDomain { .. } => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ResourceExhaustion { .. } => SqlState::INSUFFICIENT_RESOURCES,
+            AdapterError::InsufficientReplicaCapacity { .. } => SqlState::INSUFFICIENT_RESOURCES,
             AdapterError::ResultSize(_) => SqlState::OUT_OF_MEMORY,
             AdapterError::SafeModeViolation(_) => SqlState::INTERNAL_ERROR,
             AdapterError::SubscribeOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
@@ -511,7 +563,9 @@ impl AdapterError {
                 SqlState::S_R_E_PROHIBITED_SQL_STATEMENT_ATTEMPTED
             }
             AdapterError::Unauthorized(_) => SqlState::INSUFFICIENT_PRIVILEGE,
+            AdapterError::UnknownConnection(_) => SqlState::UNDEFINED_OBJECT,
             AdapterError::UnknownCursor(_) => SqlState::INVALID_CURSOR_NAME,
+            AdapterError::UnknownCursorHandoff => SqlState::INVALID_CURSOR_NAME,
             AdapterError::UnknownPreparedStatement(_) => SqlState::UNDEFINED_PSTATEMENT,
             AdapterError::UnknownLoginRole(_) => SqlState::INVALID_AUTHORIZATION_SPECIFICATION,
             AdapterError::UnknownClusterReplica { .. } => SqlState::UNDEFINED_OBJECT,
@@ -546,6 +600,9 @@ impl AdapterError {
             // In read-only mode all transactions are implicitly read-only
             // transactions.
             AdapterError::ReadOnly => SqlState::READ_ONLY_SQL_TRANSACTION,
+            AdapterError::ReadOnlyDdl(_) => SqlState::from_code("MZ011"),
+            AdapterError::StrictSerializableOnReadOnlyReplica => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::TimestampOracleUnavailable => SqlState::from_code("MZ012"),
         }
     }
 
@@ -650,6 +707,12 @@ impl fmt::Display for AdapterError {
                     "terminating connection due to idle-in-transaction timeout"
                 )
             }
+            AdapterError::SessionTerminated => {
+                write!(f, "terminating connection due to administrator command")
+            }
+            AdapterError::WriteLockTimeout => {
+                write!(f, "canceling statement due to write lock timeout")
+            }
             AdapterError::RecursionLimit(e) => e.fmt(f),
             AdapterError::RelationOutsideTimeDomain { .. } => {
                 write!(
@@ -670,6 +733,15 @@ impl fmt::Display for AdapterError {
                     "creating {resource_type} would violate {limit_name} limit (desired: {desired}, limit: {limit}, current: {current})"
                 )
             }
+            AdapterError::InsufficientReplicaCapacity {
+                size,
+                schedulable,
+                required,
+            } => write!(
+                f,
+                "insufficient capacity for replicas of size {}: only {schedulable} of {required} processes can be scheduled",
+                size.quoted()
+            ),
             AdapterError::ResultSize(e) => write!(f, "{e}"),
             AdapterError::SafeModeViolation(feature) => {
                 write!(f, "cannot create {} in safe mode", feature)
@@ -692,9 +764,15 @@ impl fmt::Display for AdapterError {
             AdapterError::Unauthorized(unauthorized) => {
                 write!(f, "{unauthorized}")
             }
+            AdapterError::UnknownConnection(id) => {
+                write!(f, "connection {id} does not exist")
+            }
             AdapterError::UnknownCursor(name) => {
                 write!(f, "cursor {} does not exist", name.quoted())
             }
+            AdapterError::UnknownCursorHandoff => {
+                f.write_str("cursor handoff does not exist or has expired")
+            }
             AdapterError::UnknownLoginRole(name) => {
                 write!(f, "role {} does not exist", name.quoted())
             }
@@ -773,6 +851,14 @@ impl fmt::Display for AdapterError {
             }
             AdapterError::UserSessionsDisallowed => write!(f, "login blocked"),
             AdapterError::ReadOnly => write!(f, "cannot write in read-only mode"),
+            AdapterError::ReadOnlyDdl(name) => write!(f, "cannot {name} while DDL is disabled"),
+            AdapterError::StrictSerializableOnReadOnlyReplica => write!(
+                f,
+                "strict serializable reads are not supported on read-only replicas"
+            ),
+            AdapterError::TimestampOracleUnavailable => {
+                write!(f, "timestamp oracle is unavailable")
+            }
         }
     }
 }
@@ -27,7 +27,10 @@ use mz_transform::notice::OptimizerNotice;
 
 use crate::AdapterError;
 
+pub(crate) mod analyze;
+pub(crate) mod bundle;
 pub(crate) mod fast_path;
+pub(crate) mod graph;
 pub(crate) mod hir;
 pub(crate) mod insights;
 pub(crate) mod lir;
@@ -75,11 +78,13 @@ where
         features,
         humanizer,
         cardinality_stats,
+        row_count_hints: Default::default(),
         used_indexes,
         finishing: Default::default(),
         duration: Default::default(),
         target_cluster,
         optimizer_notices,
+        subscribe: None,
     };
 
     Ok(Explainable::new(&mut plan).explain(&format, &context)?)
@@ -109,11 +114,13 @@ where
         features,
         humanizer,
         cardinality_stats,
+        row_count_hints: Default::default(),
         used_indexes: Default::default(),
         finishing: Default::default(),
         duration: Default::default(),
         target_cluster,
         optimizer_notices: Default::default(),
+        subscribe: None,
     };
 
     Ok(Explainable::new(&mut plan).explain(&format, &context)?)
@@ -0,0 +1,177 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Support for `EXPLAIN ANALYZE`.
+//!
+//! `EXPLAIN ANALYZE` merges the introspection data of a single replica onto
+//! the physical plan of an installed dataflow. When a replica renders an LIR
+//! node, it logs the range of the IDs of the operators it created for the node
+//! to `mz_lir_mapping`. The scheduling time and arrangement sizes of the
+//! operators in the range are summed up and attributed to the node.
+
+use std::fmt::Write;
+
+use mz_compute_types::plan::LirId;
+use mz_repr::GlobalId;
+
+/// The annotation that `EXPLAIN` appends to lines that render an LIR node.
+const NODE_ID_PREFIX: &str = " // { node_id: ";
+
+/// A line of a physical plan rendered with node IDs.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct PlanLine<'a> {
+    /// The ID of the LIR node that the line renders, if any.
+    pub(crate) lir_id: Option<LirId>,
+    /// The line, without its node ID annotation.
+    pub(crate) text: &'a str,
+}
+
+/// Splits a physical plan rendered with node IDs into its lines, skipping empty
+/// lines.
+pub(crate) fn plan_lines(explain: &str) -> Vec<PlanLine<'_>> {
+    explain
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let annotation = line.rfind(NODE_ID_PREFIX).and_then(|pos| {
+                let lir_id = line[pos + NODE_ID_PREFIX.len()..].strip_suffix(" }")?;
+                Some((pos, lir_id.parse().ok()?))
+            });
+            match annotation {
+                Some((pos, lir_id)) => PlanLine {
+                    lir_id: Some(lir_id),
+                    text: &line[..pos],
+                },
+                None => PlanLine {
+                    lir_id: None,
+                    text: line,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Returns a query that reports the lines of `explain`, the physical plan of
+/// the dataflow that exports `id` rendered with node IDs, along with the
+/// elapsed time, memory and records of the operators of each LIR node.
+///
+/// The query reads from per-replica introspection relations, so it must be run
+/// against the replica whose dataflow should be analyzed.
+pub(crate) fn query(id: GlobalId, explain: &str) -> String {
+    let mut values = String::new();
+    for (position, line) in plan_lines(explain).into_iter().enumerate() {
+        if position > 0 {
+            values.push_str(",\n        ");
+        }
+        let lir_id = match line.lir_id {
+            Some(lir_id) => lir_id.to_string(),
+            None => "NULL::int8".to_string(),
+        };
+        let text = line.text.replace('\'', "''");
+        write!(values, "({position}, {lir_id}, '{text}')").expect("writing to a string");
+    }
+
+    format!(
+        r"WITH
+    plan(position, lir_id, operator) AS (VALUES
+        {values}
+    ),
+    mapping AS (
+        SELECT m.lir_id::int8 AS lir_id, m.operator_id_start, m.operator_id_end
+        FROM mz_introspection.mz_lir_mapping m
+        JOIN mz_introspection.mz_compute_exports e ON m.dataflow_id = e.dataflow_id
+        WHERE e.export_id = '{id}'
+    ),
+    elapsed AS (
+        SELECT m.lir_id, sum(s.elapsed_ns) AS elapsed_ns
+        FROM mapping m
+        JOIN mz_introspection.mz_scheduling_elapsed s
+            ON s.id >= m.operator_id_start AND s.id < m.operator_id_end
+        GROUP BY m.lir_id
+    ),
+    arrangements AS (
+        SELECT m.lir_id, sum(a.size) AS size, sum(a.records) AS records
+        FROM mapping m
+        JOIN mz_introspection.mz_arrangement_sizes a
+            ON a.operator_id >= m.operator_id_start AND a.operator_id < m.operator_id_end
+        GROUP BY m.lir_id
+    )
+SELECT
+    p.operator AS "Operator",
+    ((e.elapsed_ns / 1000)::int8::text || ' microseconds')::interval AS "Elapsed",
+    a.size::int8 AS "Memory",
+    a.records::int8 AS "Records"
+FROM plan p
+LEFT JOIN elapsed e ON p.lir_id = e.lir_id
+LEFT JOIN arrangements a ON p.lir_id = a.lir_id
+ORDER BY p.position"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPLAIN: &str = "materialize.public.mv:
+  Reduce::Accumulable // { node_id: 2 }
+    simple_aggrs[0]=(0, 0, sum(#0))
+    Get::Collection materialize.public.t // { node_id: 1 }
+      raw=true
+
+Source materialize.public.t
+  filter=((#1 = 'it''s'))
+";
+
+    #[mz_ore::test]
+    fn test_plan_lines() {
+        assert_eq!(
+            plan_lines(EXPLAIN),
+            vec![
+                PlanLine {
+                    lir_id: None,
+                    text: "materialize.public.mv:",
+                },
+                PlanLine {
+                    lir_id: Some(2),
+                    text: "  Reduce::Accumulable",
+                },
+                PlanLine {
+                    lir_id: None,
+                    text: "    simple_aggrs[0]=(0, 0, sum(#0))",
+                },
+                PlanLine {
+                    lir_id: Some(1),
+                    text: "    Get::Collection materialize.public.t",
+                },
+                PlanLine {
+                    lir_id: None,
+                    text: "      raw=true",
+                },
+                PlanLine {
+                    lir_id: None,
+                    text: "Source materialize.public.t",
+                },
+                PlanLine {
+                    lir_id: None,
+                    text: "  filter=((#1 = 'it''s'))",
+                },
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_query() {
+        let query = query(GlobalId::User(7), EXPLAIN);
+        assert!(query.contains("(1, 2, '  Reduce::Accumulable')"));
+        assert!(query.contains("(0, NULL::int8, 'materialize.public.mv:')"));
+        assert!(query.contains("(6, NULL::int8, '  filter=((#1 = ''it''''s''))')"));
+        assert!(query.contains("WHERE e.export_id = 'u7'"));
+        mz_sql::parse::parse(&query).expect("query parses");
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Support bundles for `EXPLAIN BUNDLE`.
+//!
+//! An [`ExplainBundle`] collects everything that is needed to reproduce the
+//! planning of a statement in a single JSON document: the SQL of the
+//! statement, the definitions of all catalog items it depends on, the
+//! complete optimizer trace, the timestamp determination, and the cluster the
+//! statement was planned for. Users can attach a bundle to a bug report
+//! instead of gathering these pieces by hand.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_catalog::memory::objects::Cluster;
+use mz_controller::clusters::ReplicaLocation;
+use mz_controller_types::{ClusterId, ReplicaId};
+use mz_ore::collections::CollectionExt;
+use mz_repr::explain::json::EXPLAIN_VERSION;
+use mz_repr::explain::tracing::TraceEntry;
+use mz_repr::{GlobalId, Row, Timestamp};
+use mz_sql::ast::display::AstDisplay;
+use mz_sql::catalog::CatalogItem;
+use mz_sql::names::{ObjectId, ResolvedIds};
+use mz_sql::plan::{Plan, ShowCreatePlan};
+use mz_sql::rbac;
+use serde::Serialize;
+
+use crate::catalog::Catalog;
+use crate::coord::timestamp_selection::TimestampExplanation;
+use crate::session::Session;
+
+/// The complete context of the planning of a statement.
+#[derive(Debug, Serialize)]
+pub struct ExplainBundle {
+    /// The version of the `EXPLAIN` JSON schema that the plans in `trace` follow.
+    pub explain_version: u64,
+    /// The SQL of the explained statement.
+    pub sql: Option<String>,
+    /// The catalog items that the statement transitively depends on.
+    pub dependencies: Vec<BundleDependency>,
+    /// Every plan in the optimizer trace, in the order the optimizer produced them.
+    pub trace: Vec<BundleTraceEntry>,
+    /// The timestamp that was determined for the statement.
+    pub timestamp: TimestampExplanation<Timestamp>,
+    /// The cluster the statement was planned for.
+    pub cluster: BundleCluster,
+    /// The optimizer features that were in effect.
+    pub features: BTreeMap<&'static str, String>,
+}
+
+impl ExplainBundle {
+    /// Creates a bundle, filling in the version of the `EXPLAIN` JSON schema.
+    pub fn new(
+        sql: Option<String>,
+        dependencies: Vec<BundleDependency>,
+        trace: Vec<BundleTraceEntry>,
+        timestamp: TimestampExplanation<Timestamp>,
+        cluster: BundleCluster,
+        features: BTreeMap<&'static str, String>,
+    ) -> Self {
+        ExplainBundle {
+            explain_version: EXPLAIN_VERSION,
+            sql,
+            dependencies,
+            trace,
+            timestamp,
+            cluster,
+            features,
+        }
+    }
+}
+
+/// A catalog item that an explained statement depends on.
+#[derive(Debug, Serialize)]
+pub struct BundleDependency {
+    pub id: GlobalId,
+    pub name: String,
+    pub item_type: String,
+    /// The definition of the item, or `None` for builtin items and for items whose definition
+    /// the session is not allowed to see.
+    pub create_sql: Option<String>,
+}
+
+/// Returns the catalog items that the items in `ids` transitively depend on,
+/// including the items themselves, ordered by their IDs.
+///
+/// The definitions of the items are only included if `session` could see
+/// them with `SHOW CREATE`, since the statement may depend on items that are
+/// hidden from the session behind views. If `redacted` is set, the literals in
+/// the definitions of the items are redacted.
+pub fn dependencies(
+    catalog: &Catalog,
+    session: &Session,
+    ids: impl IntoIterator<Item = GlobalId>,
+    redacted: bool,
+) -> Vec<BundleDependency> {
+    let conn_catalog = catalog.for_session(session);
+    let may_show_create = |id: GlobalId| {
+        let plan = Plan::ShowCreate(ShowCreatePlan {
+            id: ObjectId::Item(id),
+            row: Row::default(),
+        });
+        rbac::check_plan(
+            &conn_catalog,
+            &BTreeMap::new(),
+            session,
+            &plan,
+            None,
+            &ResolvedIds(BTreeSet::new()),
+        )
+        .is_ok()
+    };
+
+    let mut seen = BTreeSet::new();
+    let mut todo: Vec<_> = ids.into_iter().collect();
+    while let Some(id) = todo.pop() {
+        if seen.insert(id) {
+            if let Some(entry) = catalog.try_get_entry(&id) {
+                todo.extend(entry.uses());
+            }
+        }
+    }
+
+    seen.into_iter()
+        .filter_map(|id| catalog.try_get_entry(&id))
+        .map(|entry| {
+            let visible = !entry.id().is_system() && may_show_create(entry.id());
+            let create_sql = visible.then(|| {
+                let create_sql = entry.create_sql();
+                if redacted {
+                    mz_sql::parse::parse(create_sql)
+                        .unwrap_or_else(|_| panic!("create_sql cannot be invalid: {}", create_sql))
+                        .into_element()
+                        .ast
+                        .to_ast_string_redacted()
+                } else {
+                    create_sql.to_string()
+                }
+            });
+            BundleDependency {
+                id: entry.id(),
+                name: catalog
+                    .resolve_full_name(entry.name(), Some(session.conn_id()))
+                    .to_string(),
+                item_type: entry.item_type().to_string(),
+                create_sql,
+            }
+        })
+        .collect()
+}
+
+/// A plan in the optimizer trace of an explained statement.
+#[derive(Debug, Serialize)]
+pub struct BundleTraceEntry {
+    /// The path of the optimizer stage that produced the plan.
+    pub path: String,
+    /// The time spent in the optimizer stage.
+    pub duration_ns: u64,
+    pub plan: String,
+}
+
+impl From<TraceEntry<String>> for BundleTraceEntry {
+    fn from(entry: TraceEntry<String>) -> Self {
+        BundleTraceEntry {
+            path: entry.path,
+            // The stage would have to take over 584 years to overflow a u64.
+            duration_ns: u64::try_from(entry.span_duration.as_nanos()).unwrap_or(u64::MAX),
+            plan: entry.plan,
+        }
+    }
+}
+
+/// The cluster an explained statement was planned for.
+#[derive(Debug, Serialize)]
+pub struct BundleCluster {
+    pub id: ClusterId,
+    pub name: String,
+    pub replicas: Vec<BundleReplica>,
+}
+
+/// A replica of a [`BundleCluster`].
+#[derive(Debug, Serialize)]
+pub struct BundleReplica {
+    pub id: ReplicaId,
+    pub name: String,
+    /// The size of the replica, or `None` for unmanaged replicas.
+    pub size: Option<String>,
+}
+
+impl From<&Cluster> for BundleCluster {
+    fn from(cluster: &Cluster) -> Self {
+        BundleCluster {
+            id: cluster.id,
+            name: cluster.name.clone(),
+            replicas: cluster
+                .replicas()
+                .map(|replica| BundleReplica {
+                    id: replica.replica_id,
+                    name: replica.name.clone(),
+                    size: match &replica.config.location {
+                        ReplicaLocation::Managed(location) => Some(location.size.clone()),
+                        ReplicaLocation::Unmanaged(_) => None,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
@@ -50,10 +50,18 @@ impl<'a> Explainable<'a, FastPathPlan> {
 
         let sources = vec![];
 
+        let related_ids = match self.0 {
+            FastPathPlan::Constant(..) => vec![],
+            FastPathPlan::PeekExisting(coll_id, ..) | FastPathPlan::PeekPersist(coll_id, ..) => {
+                vec![*coll_id]
+            }
+        };
+
         Ok(ExplainMultiPlan {
             context,
             sources,
             plans,
+            related_ids,
         })
     }
 }
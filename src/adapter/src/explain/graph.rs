@@ -0,0 +1,285 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Plan graphs for plan visualizers.
+//!
+//! A [`PlanGraph`] is a ready-to-render representation of the MIR plan of a statement: one node
+//! per operator, one edge per operator input, and the time spent in each traced optimizer stage.
+//! The coordinator records the graph of the last statement of each connection whose optimization
+//! was traced, which the internal HTTP server exposes under `/api/plan-graph/:conn_id`.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use mz_expr::explain::{HumanizedExplain, HumanizedExpr, HumanizerMode};
+use mz_expr::{Id, MirRelationExpr};
+use mz_repr::explain::ExprHumanizer;
+use serde::Serialize;
+use serde_json::json;
+
+/// The operators of a plan and the data flowing between them.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PlanGraph {
+    /// The path of the optimizer stage that produced the plan.
+    pub stage: String,
+    pub nodes: Vec<PlanGraphNode>,
+    pub edges: Vec<PlanGraphEdge>,
+    /// The time spent in each traced optimizer stage, in the order the stages ran.
+    pub timings: Vec<PlanGraphTiming>,
+}
+
+/// An operator of a [`PlanGraph`].
+#[derive(Clone, Debug, Serialize)]
+pub struct PlanGraphNode {
+    pub id: usize,
+    /// The object built by the plan rooted at this node, if this node is a root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<String>,
+    pub operator: &'static str,
+    /// Operator-specific details, like predicates or group keys.
+    pub attributes: BTreeMap<&'static str, serde_json::Value>,
+}
+
+/// Data flowing from node `from` into input `input` of node `to`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PlanGraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub input: usize,
+}
+
+/// The time spent in an optimizer stage.
+#[derive(Clone, Debug, Serialize)]
+pub struct PlanGraphTiming {
+    pub path: String,
+    pub duration_ns: u64,
+}
+
+impl PlanGraphTiming {
+    pub fn new(path: String, duration: Duration) -> Self {
+        PlanGraphTiming {
+            path,
+            // The stage would have to take over 584 years to overflow a u64.
+            duration_ns: u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+impl PlanGraph {
+    /// Creates an empty graph for a plan produced by the optimizer stage at `stage`.
+    pub fn new(stage: String) -> Self {
+        PlanGraph {
+            stage,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the operators of `expr` to the graph, naming its root `object`.
+    pub fn add_plan(
+        &mut self,
+        object: Option<String>,
+        expr: &MirRelationExpr,
+        humanizer: &dyn ExprHumanizer,
+    ) {
+        // Plans can be deeply nested, so we walk them with an explicit stack.
+        let mut todo = vec![(expr, None)];
+        let mut object = object;
+        while let Some((expr, consumer)) = todo.pop() {
+            let id = self.nodes.len();
+            let (operator, attributes) = describe(expr, humanizer);
+            self.nodes.push(PlanGraphNode {
+                id,
+                object: object.take(),
+                operator,
+                attributes,
+            });
+            if let Some((to, input)) = consumer {
+                self.edges.push(PlanGraphEdge {
+                    from: id,
+                    to,
+                    input,
+                });
+            }
+            let children: Vec<_> = expr.children().collect();
+            for (input, child) in children.into_iter().enumerate().rev() {
+                todo.push((child, Some((id, input))));
+            }
+        }
+    }
+}
+
+/// Returns the operator name and the attributes of the root of `expr`.
+fn describe(
+    expr: &MirRelationExpr,
+    humanizer: &dyn ExprHumanizer,
+) -> (&'static str, BTreeMap<&'static str, serde_json::Value>) {
+    let mode = HumanizedExplain::default();
+    let mut attributes = BTreeMap::new();
+    let operator = match expr {
+        MirRelationExpr::Constant { rows, .. } => {
+            match rows {
+                Ok(rows) => attributes.insert("rows", json!(rows.len())),
+                Err(e) => attributes.insert("error", json!(e.to_string())),
+            };
+            "Constant"
+        }
+        MirRelationExpr::Get { id, .. } => {
+            let name = match id {
+                Id::Global(id) => humanizer.humanize_id(*id).unwrap_or_else(|| id.to_string()),
+                Id::Local(id) => id.to_string(),
+            };
+            attributes.insert("id", json!(name));
+            "Get"
+        }
+        MirRelationExpr::Let { id, .. } => {
+            attributes.insert("id", json!(id.to_string()));
+            "Let"
+        }
+        MirRelationExpr::LetRec { ids, .. } => {
+            let ids: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+            attributes.insert("ids", json!(ids));
+            "LetRec"
+        }
+        MirRelationExpr::Project { outputs, .. } => {
+            attributes.insert("outputs", json!(outputs));
+            "Project"
+        }
+        MirRelationExpr::Map { scalars, .. } => {
+            attributes.insert("scalars", json!(humanize_all(&mode, scalars)));
+            "Map"
+        }
+        MirRelationExpr::FlatMap { func, exprs, .. } => {
+            attributes.insert("func", json!(func.to_string()));
+            attributes.insert("exprs", json!(humanize_all(&mode, exprs)));
+            "FlatMap"
+        }
+        MirRelationExpr::Filter { predicates, .. } => {
+            attributes.insert("predicates", json!(humanize_all(&mode, predicates)));
+            "Filter"
+        }
+        MirRelationExpr::Join {
+            equivalences,
+            implementation,
+            ..
+        } => {
+            let equivalences: Vec<_> = equivalences
+                .iter()
+                .map(|class| humanize_all(&mode, class))
+                .collect();
+            attributes.insert("equivalences", json!(equivalences));
+            if let Some(name) = implementation.name() {
+                attributes.insert("implementation", json!(name));
+            }
+            "Join"
+        }
+        MirRelationExpr::Reduce {
+            group_key,
+            aggregates,
+            monotonic,
+            expected_group_size,
+            ..
+        } => {
+            attributes.insert("group_key", json!(humanize_all(&mode, group_key)));
+            attributes.insert("aggregates", json!(humanize_all(&mode, aggregates)));
+            attributes.insert("monotonic", json!(monotonic));
+            if let Some(size) = expected_group_size {
+                attributes.insert("expected_group_size", json!(size));
+            }
+            "Reduce"
+        }
+        MirRelationExpr::TopK {
+            group_key,
+            order_key,
+            limit,
+            offset,
+            monotonic,
+            expected_group_size,
+            ..
+        } => {
+            attributes.insert("group_key", json!(group_key));
+            attributes.insert("order_key", json!(humanize_all(&mode, order_key)));
+            if let Some(limit) = limit {
+                attributes.insert("limit", json!(mode.expr(limit, None).to_string()));
+            }
+            attributes.insert("offset", json!(offset));
+            attributes.insert("monotonic", json!(monotonic));
+            if let Some(size) = expected_group_size {
+                attributes.insert("expected_group_size", json!(size));
+            }
+            "TopK"
+        }
+        MirRelationExpr::Negate { .. } => "Negate",
+        MirRelationExpr::Threshold { .. } => "Threshold",
+        MirRelationExpr::Union { .. } => "Union",
+        MirRelationExpr::ArrangeBy { keys, .. } => {
+            let keys: Vec<_> = keys.iter().map(|key| humanize_all(&mode, key)).collect();
+            attributes.insert("keys", json!(keys));
+            "ArrangeBy"
+        }
+    };
+    (operator, attributes)
+}
+
+/// Renders each of `exprs` like `EXPLAIN` does, redacting literals if required.
+fn humanize_all<T>(mode: &HumanizedExplain, exprs: &[T]) -> Vec<String>
+where
+    for<'a> HumanizedExpr<'a, T>: Display,
+{
+    mode.seq(exprs, None).map(|expr| expr.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::{func, MirScalarExpr, UnaryFunc};
+    use mz_repr::explain::DummyHumanizer;
+    use mz_repr::{GlobalId, RelationType, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_plan_graph() {
+        let typ = RelationType::new(vec![ScalarType::Bool.nullable(true)]);
+        let first = MirRelationExpr::global_get(GlobalId::User(1), typ.clone());
+        let second = MirRelationExpr::global_get(GlobalId::User(2), typ);
+        let expr = first.union(second).filter(vec![
+            MirScalarExpr::column(0).call_unary(UnaryFunc::Not(func::Not))
+        ]);
+
+        let mut graph = PlanGraph::new("optimize/global".into());
+        graph.add_plan(Some("t".into()), &expr, &DummyHumanizer);
+
+        let operators: Vec<_> = graph.nodes.iter().map(|node| node.operator).collect();
+        assert_eq!(operators, ["Filter", "Union", "Get", "Get"]);
+        assert_eq!(graph.nodes[0].object.as_deref(), Some("t"));
+        assert_eq!(graph.nodes[1].object, None);
+        assert_eq!(graph.nodes[2].attributes["id"], "u1");
+        assert_eq!(graph.nodes[3].attributes["id"], "u2");
+        assert_eq!(
+            graph.edges,
+            [
+                PlanGraphEdge {
+                    from: 1,
+                    to: 0,
+                    input: 0
+                },
+                PlanGraphEdge {
+                    from: 2,
+                    to: 1,
+                    input: 0
+                },
+                PlanGraphEdge {
+                    from: 3,
+                    to: 1,
+                    input: 1
+                },
+            ]
+        );
+    }
+}
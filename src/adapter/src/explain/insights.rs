@@ -13,14 +13,17 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use itertools::Itertools;
 use mz_compute_types::dataflows::{BuildDesc, DataflowDescription};
+use mz_expr::explain::{HumanizedExplain, HumanizerMode};
 use mz_expr::{
-    AccessStrategy, AggregateExpr, AggregateFunc, Id, MirRelationExpr, OptimizedMirRelationExpr,
-    RowSetFinishing,
+    AccessStrategy, AggregateExpr, AggregateFunc, BinaryFunc, Id, MirRelationExpr, MirScalarExpr,
+    OptimizedMirRelationExpr, RowSetFinishing, UnaryFunc, UnmaterializableFunc,
 };
 use mz_ore::num::NonNeg;
+use mz_ore::str::separated;
 use mz_repr::explain::ExprHumanizer;
-use mz_repr::{GlobalId, Timestamp};
+use mz_repr::{Datum, GlobalId, Timestamp};
 use mz_sql::ast::Statement;
 use mz_sql::names::Aug;
 use mz_sql::optimizer_metrics::OptimizerMetrics;
@@ -30,7 +33,7 @@ use mz_transform::EmptyStatisticsOracle;
 use serde::Serialize;
 
 use crate::catalog::Catalog;
-use crate::coord::peek::{FastPathPlan, PeekPlan};
+use crate::coord::peek::{FastPathPlan, PeekPlan, SlowPathReason};
 use crate::optimize::dataflows::ComputeInstanceSnapshot;
 use crate::optimize::{self, Optimize, OptimizerConfig, OptimizerError};
 use crate::session::SessionMeta;
@@ -56,6 +59,7 @@ pub struct PlanInsightsContext {
     pub view_id: GlobalId,
     pub index_id: GlobalId,
     pub enable_re_optimize: bool,
+    pub slow_path_reason: Option<SlowPathReason>,
 }
 
 /// Insights about an optimized plan.
@@ -74,6 +78,10 @@ pub struct PlanInsights {
     pub fast_path_limit: Option<usize>,
     /// Names of persist sources over which a count(*) is done.
     pub persist_count: Vec<Name>,
+    /// If this plan narrowly missed the fast path, the reason why.
+    pub slow_path_reason: Option<String>,
+    /// Temporal filters in the plan, i.e., filters that refer to `mz_now()`.
+    pub temporal_filters: Vec<TemporalFilterInsights>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -181,6 +189,199 @@ pub enum ImportType {
     Storage,
 }
 
+/// Insights about a temporal filter in a plan.
+///
+/// A temporal filter keeps a record between the times given by its lower and
+/// upper bounds on `mz_now()`. The stateful operators downstream of the filter
+/// maintain the record only for that window, so their memory usage is
+/// proportional to the number of records that arrive within a window.
+#[derive(Clone, Debug, Serialize)]
+pub struct TemporalFilterInsights {
+    /// The predicates of the filter that refer to `mz_now()`.
+    pub predicates: Vec<String>,
+    /// Whether the filter bounds `mz_now()` from above, so that records
+    /// eventually stop passing the filter.
+    pub bounded: bool,
+    /// For how many milliseconds a record passes the filter, if the filter
+    /// bounds `mz_now()` from below and from above by offsets of the same
+    /// expression.
+    pub retention_window_ms: Option<u64>,
+    /// The stateful operators that maintain the records that pass the filter,
+    /// starting with the nearest one.
+    pub stateful_operators: Vec<String>,
+    /// A warning about the memory usage implied by the filter, if any.
+    pub warning: Option<String>,
+}
+
+impl TemporalFilterInsights {
+    /// Returns insights about the filter with `predicates` that is consumed
+    /// by `stateful_operators`, if it is a temporal filter.
+    fn new(predicates: &[MirScalarExpr], stateful_operators: &[&str]) -> Option<Self> {
+        let predicates: Vec<_> = predicates
+            .iter()
+            .filter(|predicate| predicate.contains_temporal())
+            .collect();
+        if predicates.is_empty() {
+            return None;
+        }
+
+        let mut lower_bounds = vec![];
+        let mut upper_bounds = vec![];
+        for predicate in &predicates {
+            let (lower, upper) = temporal_bounds(predicate);
+            lower_bounds.extend(lower);
+            upper_bounds.extend(upper);
+        }
+
+        let bounded = !upper_bounds.is_empty();
+        let retention_window_ms = lower_bounds
+            .iter()
+            .cartesian_product(upper_bounds.iter())
+            .filter_map(|(lower, upper)| {
+                let (lower, lower_offset) = split_offset(lower);
+                let (upper, upper_offset) = split_offset(upper);
+                (lower == upper).then(|| upper_offset - lower_offset)
+            })
+            .min()
+            .map(|window| u64::try_from(window).unwrap_or(0));
+        let stateful_operators: Vec<_> = stateful_operators
+            .iter()
+            .rev()
+            .map(|operator| operator.to_string())
+            .collect();
+        let warning = match (bounded, stateful_operators.is_empty()) {
+            (true, _) => None,
+            (false, true) => Some(
+                "the filter does not bound mz_now() from above, so the records that pass it \
+                 are never retracted"
+                    .into(),
+            ),
+            (false, false) => Some(format!(
+                "the filter does not bound mz_now() from above, so the memory usage of {} \
+                 grows without bound",
+                separated(", ", &stateful_operators),
+            )),
+        };
+
+        let mode = HumanizedExplain::new(false);
+        Some(TemporalFilterInsights {
+            predicates: predicates
+                .into_iter()
+                .map(|predicate| mode.expr(predicate, None).to_string())
+                .collect(),
+            bounded,
+            retention_window_ms,
+            stateful_operators,
+            warning,
+        })
+    }
+}
+
+/// Returns the lower and upper bound on `mz_now()` that `predicate` imposes,
+/// if it compares `mz_now()` to an expression.
+fn temporal_bounds(predicate: &MirScalarExpr) -> (Option<&MirScalarExpr>, Option<&MirScalarExpr>) {
+    let is_mz_now = |expr: &MirScalarExpr| {
+        matches!(
+            expr,
+            MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
+        )
+    };
+    let MirScalarExpr::CallBinary { func, expr1, expr2 } = predicate else {
+        return (None, None);
+    };
+    let (bound, mz_now_first) = if is_mz_now(expr1) && !expr2.contains_temporal() {
+        (&**expr2, true)
+    } else if is_mz_now(expr2) && !expr1.contains_temporal() {
+        (&**expr1, false)
+    } else {
+        return (None, None);
+    };
+    match (func, mz_now_first) {
+        (BinaryFunc::Eq, _) => (Some(bound), Some(bound)),
+        (BinaryFunc::Gt | BinaryFunc::Gte, true) | (BinaryFunc::Lt | BinaryFunc::Lte, false) => {
+            (Some(bound), None)
+        }
+        (BinaryFunc::Lt | BinaryFunc::Lte, true) | (BinaryFunc::Gt | BinaryFunc::Gte, false) => {
+            (None, Some(bound))
+        }
+        _ => (None, None),
+    }
+}
+
+/// Splits a bound on `mz_now()` into an expression and a constant offset in
+/// milliseconds, looking through casts to `mz_timestamp`.
+fn split_offset(mut expr: &MirScalarExpr) -> (&MirScalarExpr, i128) {
+    let mut offset = 0;
+    loop {
+        match expr {
+            MirScalarExpr::CallUnary {
+                func:
+                    UnaryFunc::CastUint64ToMzTimestamp(_)
+                    | UnaryFunc::CastUint32ToMzTimestamp(_)
+                    | UnaryFunc::CastInt64ToMzTimestamp(_)
+                    | UnaryFunc::CastInt32ToMzTimestamp(_)
+                    | UnaryFunc::CastNumericToMzTimestamp(_)
+                    | UnaryFunc::CastTimestampToMzTimestamp(_)
+                    | UnaryFunc::CastTimestampTzToMzTimestamp(_),
+                expr: input,
+            } => expr = input,
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+                let delta = match expr2.as_literal() {
+                    Some(Ok(Datum::Interval(interval))) => interval.as_milliseconds(),
+                    Some(Ok(Datum::Int64(n))) => i128::from(n),
+                    Some(Ok(Datum::Int32(n))) => i128::from(n),
+                    _ => break,
+                };
+                match func {
+                    BinaryFunc::AddTimestampInterval
+                    | BinaryFunc::AddTimestampTzInterval
+                    | BinaryFunc::AddInt64
+                    | BinaryFunc::AddInt32 => offset += delta,
+                    BinaryFunc::SubTimestampInterval
+                    | BinaryFunc::SubTimestampTzInterval
+                    | BinaryFunc::SubInt64
+                    | BinaryFunc::SubInt32 => offset -= delta,
+                    _ => break,
+                }
+                expr = expr1;
+            }
+            _ => break,
+        }
+    }
+    (expr, offset)
+}
+
+/// Adds insights about the temporal filters in `expr`, which is consumed by
+/// `stateful_operators`.
+fn add_temporal_filter_insights(
+    insights: &mut PlanInsights,
+    expr: &MirRelationExpr,
+    stateful_operators: &mut Vec<&'static str>,
+) {
+    mz_ore::stack::maybe_grow(|| {
+        if let MirRelationExpr::Filter { predicates, .. } = expr {
+            insights
+                .temporal_filters
+                .extend(TemporalFilterInsights::new(predicates, stateful_operators));
+        }
+        let operator = match expr {
+            MirRelationExpr::Join { .. } => Some("Join"),
+            MirRelationExpr::Reduce { .. } => Some("Reduce"),
+            MirRelationExpr::TopK { .. } => Some("TopK"),
+            MirRelationExpr::Threshold { .. } => Some("Threshold"),
+            MirRelationExpr::ArrangeBy { .. } => Some("ArrangeBy"),
+            _ => None,
+        };
+        stateful_operators.extend(operator);
+        for child in expr.children() {
+            add_temporal_filter_insights(insights, child, stateful_operators);
+        }
+        if operator.is_some() {
+            stateful_operators.pop();
+        }
+    })
+}
+
 /// The name of a collection.
 #[derive(Debug, Clone, Serialize)]
 pub struct Name {
@@ -280,6 +481,7 @@ fn global_insights(
             let name = structured_name(humanizer, *id);
             insights.persist_count.push(name);
         });
+        add_temporal_filter_insights(&mut insights, &plan, &mut vec![]);
     }
     insights
 }
@@ -307,3 +509,72 @@ fn structured_name(humanizer: &dyn ExprHumanizer, id: GlobalId) -> Name {
         database: parts.pop(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::func;
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    fn mz_now() -> MirScalarExpr {
+        MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow)
+    }
+
+    /// Returns `#column + offset`, cast to `mz_timestamp`.
+    fn timestamp(column: usize, offset: i64) -> MirScalarExpr {
+        let mut expr = MirScalarExpr::column(column);
+        if offset != 0 {
+            let offset = MirScalarExpr::literal_ok(Datum::Int64(offset), ScalarType::Int64);
+            expr = expr.call_binary(offset, BinaryFunc::AddInt64);
+        }
+        expr.call_unary(UnaryFunc::CastInt64ToMzTimestamp(
+            func::CastInt64ToMzTimestamp,
+        ))
+    }
+
+    #[mz_ore::test]
+    fn test_temporal_filter_insights() {
+        let non_temporal = MirScalarExpr::column(1).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int64(1), ScalarType::Int64),
+            BinaryFunc::Gt,
+        );
+        assert!(TemporalFilterInsights::new(&[non_temporal.clone()], &["Reduce"]).is_none());
+
+        // mz_now() >= #0 AND mz_now() < #0 + 30000 AND #0 + 60000 > mz_now()
+        let predicates = [
+            mz_now().call_binary(timestamp(0, 0), BinaryFunc::Gte),
+            mz_now().call_binary(timestamp(0, 30_000), BinaryFunc::Lt),
+            timestamp(0, 60_000).call_binary(mz_now(), BinaryFunc::Gt),
+            non_temporal,
+        ];
+        let insights =
+            TemporalFilterInsights::new(&predicates, &["Join", "Reduce"]).expect("temporal");
+        assert_eq!(insights.predicates.len(), 3);
+        assert!(insights.bounded);
+        assert_eq!(insights.retention_window_ms, Some(30_000));
+        assert_eq!(insights.stateful_operators, vec!["Reduce", "Join"]);
+        assert_eq!(insights.warning, None);
+
+        // The window is unknown if the bounds aren't offsets of the same expression.
+        let predicates = [
+            mz_now().call_binary(timestamp(0, 0), BinaryFunc::Gte),
+            mz_now().call_binary(timestamp(1, 30_000), BinaryFunc::Lt),
+        ];
+        let insights = TemporalFilterInsights::new(&predicates, &[]).expect("temporal");
+        assert!(insights.bounded);
+        assert_eq!(insights.retention_window_ms, None);
+
+        let predicates = [timestamp(0, 0).call_binary(mz_now(), BinaryFunc::Lte)];
+        let insights = TemporalFilterInsights::new(&predicates, &["Reduce"]).expect("temporal");
+        assert!(!insights.bounded);
+        assert_eq!(insights.retention_window_ms, None);
+        assert_eq!(
+            insights.warning.as_deref(),
+            Some(
+                "the filter does not bound mz_now() from above, so the memory usage of Reduce \
+                 grows without bound"
+            )
+        );
+    }
+}
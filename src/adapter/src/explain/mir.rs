@@ -17,7 +17,7 @@
 //! implementation for [`MirRelationExpr`] in [`mz_expr`].
 
 use mz_compute_types::dataflows::DataflowDescription;
-use mz_compute_types::explain::export_ids_for;
+use mz_compute_types::explain::{export_ids_for, related_ids_for};
 use mz_expr::explain::{
     enforce_linear_chains, ExplainContext, ExplainMultiPlan, ExplainSinglePlan, ExplainSource,
 };
@@ -95,6 +95,7 @@ impl<'a> Explainable<'a, DataflowDescription<OptimizedMirRelationExpr>> {
         context: &'a ExplainContext<'a>,
     ) -> Result<ExplainMultiPlan<'a, MirRelationExpr>, ExplainError> {
         let export_ids = export_ids_for(self.0);
+        let related_ids = related_ids_for(self.0);
         let plans = self
             .0
             .objects_to_build
@@ -135,7 +136,13 @@ impl<'a> Explainable<'a, DataflowDescription<OptimizedMirRelationExpr>> {
             .iter_mut()
             .map(|(id, (source_desc, _))| {
                 let op = source_desc.arguments.operators.as_ref();
-                ExplainSource::new(*id, op, context.config.filter_pushdown)
+                ExplainSource::new(
+                    *id,
+                    op,
+                    context.config.filter_pushdown,
+                    context.row_estimate(*id),
+                    context.row_count_hint(*id),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -143,6 +150,7 @@ impl<'a> Explainable<'a, DataflowDescription<OptimizedMirRelationExpr>> {
             context,
             sources,
             plans,
+            related_ids,
         })
     }
 }
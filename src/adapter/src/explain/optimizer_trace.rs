@@ -9,35 +9,51 @@
 
 //! Tracing utilities for explainable plans.
 
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use mz_catalog::memory::objects::Cluster;
 use mz_compute_types::dataflows::DataflowDescription;
+use mz_compute_types::explain::export_ids_for;
 use mz_compute_types::plan::Plan;
-use mz_expr::explain::ExplainContext;
+use mz_expr::explain::{ExplainContext, ExplainSubscribe};
 use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_ore::collections::CollectionExt;
-use mz_repr::explain::tracing::{PlanTrace, TraceEntry};
+use mz_ore::str::Indent;
+use mz_repr::explain::text::text_string_at;
+use mz_repr::explain::tracing::{PlanTrace, TraceBudget, TraceEntry};
 use mz_repr::explain::{
-    Explain, ExplainConfig, ExplainError, ExplainFormat, ExprHumanizer, UsedIndexes,
+    Explain, ExplainConfig, ExplainError, ExplainFormat, ExprHumanizer, SubqueryLowerings,
+    UsedIndexes,
 };
 use mz_repr::optimize::OptimizerFeatures;
-use mz_repr::{Datum, Row};
+use mz_repr::{Datum, GlobalId, Row};
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::plan::{self, HirRelationExpr, HirScalarExpr};
 use mz_sql_parser::ast::{ExplainStage, NamedPlan};
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::notice::RawOptimizerNotice;
+use serde::Serialize;
 use smallvec::SmallVec;
 use tracing::dispatcher;
 use tracing_subscriber::prelude::*;
 
 use crate::coord::peek::FastPathPlan;
+use crate::explain::bundle::BundleTraceEntry;
+use crate::explain::graph::{PlanGraph, PlanGraphTiming};
 use crate::explain::insights::{self, PlanInsightsContext};
 use crate::explain::Explainable;
 use crate::AdapterError;
 
+/// The number of bytes that the rendered plans of each [`PlanTrace`] may use.
+///
+/// Plans beyond this budget are truncated, so that explaining pathological
+/// queries, whose traces can consist of many huge plans, cannot exhaust the
+/// memory of the coordinator.
+const MAX_TRACE_BYTES: usize = 64 << 20;
+
 /// Provides functionality for tracing plans generated by the execution of an
 /// optimization pipeline.
 ///
@@ -82,6 +98,7 @@ impl OptimizerTrace {
                 // Don't filter for FastPathPlan entries (there can be at most one).
                 .with(PlanTrace::<FastPathPlan>::new(None))
                 .with(PlanTrace::<UsedIndexes>::new(None))
+                .with(PlanTrace::<SubqueryLowerings>::new(filter()))
                 // All optimizer spans are `TRACE` and up. Technically this slows down the system
                 // by skipping the tracing fast path DURING an `EXPLAIN`, but we haven't
                 // seen this be a problem (yet).
@@ -106,12 +123,28 @@ impl OptimizerTrace {
                 .with(PlanTrace::<DataflowDescription<Plan>>::new(filter()))
                 .with(PlanTrace::<FastPathPlan>::new(None))
                 .with(PlanTrace::<UsedIndexes>::new(None))
+                .with(PlanTrace::<SubqueryLowerings>::new(filter()))
                 .with(tracing::level_filters::LevelFilter::TRACE);
 
             OptimizerTrace(dispatcher::Dispatch::new(subscriber))
         }
     }
 
+    /// Create a new [`OptimizerTrace`] for an `EXPLAIN` of the given `stage`.
+    ///
+    /// `EXPLAIN PLAN INSIGHTS WITH (timing)` reports the time spent in every
+    /// optimizer pass, so it accumulates all [`TraceEntry`] instances. Every
+    /// other stage only accumulates the entries along its [paths].
+    ///
+    /// [paths]: ExplainStage::paths
+    pub fn for_explain(stage: ExplainStage, config: &ExplainConfig) -> OptimizerTrace {
+        if stage == ExplainStage::PlanInsights && config.timing {
+            OptimizerTrace::new(None)
+        } else {
+            OptimizerTrace::new(stage.paths())
+        }
+    }
+
     /// Enter this [`OptimizerTrace`]'s tracing [`dispatcher::Dispatch`], returning a guard.
     ///
     /// Linked to this [`OptimizerTrace`] with a lifetime to ensure
@@ -137,6 +170,8 @@ impl OptimizerTrace {
         row_set_finishing: Option<RowSetFinishing>,
         target_cluster: Option<&Cluster>,
         dataflow_metainfo: DataflowMetainfo,
+        cardinality_stats: BTreeMap<GlobalId, usize>,
+        subscribe: Option<ExplainSubscribe>,
         stage: ExplainStage,
         stmt_kind: plan::ExplaineeStatementKind,
         insights_ctx: Option<PlanInsightsContext>,
@@ -150,17 +185,27 @@ impl OptimizerTrace {
                 row_set_finishing.clone(),
                 target_cluster.map(|c| c.name.as_str()),
                 dataflow_metainfo.clone(),
+                cardinality_stats.clone(),
+                subscribe.clone(),
             )
         };
 
         let rows = match stage {
             ExplainStage::Trace => {
                 // For the `Trace` (pseudo-)stage, return the entire trace as
-                // triples of (time, path, plan) values.
-                let rows = collect_all(format)?
-                    .0
+                // triples of (time, path, plan) values, preceded by a header
+                // if entries were truncated.
+                let traces = collect_all(format)?;
+                let header = traces.overflow_header().map(|header| {
+                    Row::pack_slice(&[
+                        Datum::from(0u64),
+                        Datum::from(""),
+                        Datum::from(header.as_str()),
+                    ])
+                });
+                let rows = header
                     .into_iter()
-                    .map(|entry| {
+                    .chain(traces.entries.into_iter().map(|entry| {
                         // The trace would have to take over 584 years to overflow a u64.
                         let span_duration = u64::try_from(entry.span_duration.as_nanos());
                         Row::pack_slice(&[
@@ -168,7 +213,7 @@ impl OptimizerTrace {
                             Datum::from(entry.path.as_str()),
                             Datum::from(entry.plan.as_str()),
                         ])
-                    })
+                    }))
                     .collect();
                 rows
             }
@@ -213,6 +258,10 @@ impl OptimizerTrace {
                         .as_ref()
                         .map(|s| Some(s.to_ast_string_redacted()));
                     if let (Some(plan_insights), false) = (plan_insights.as_mut(), is_fast_path) {
+                        plan_insights.slow_path_reason = insights_ctx
+                            .slow_path_reason
+                            .as_ref()
+                            .map(|reason| reason.humanize(humanizer));
                         if insights_ctx.enable_re_optimize {
                             plan_insights
                                 .compute_fast_path_clusters(humanizer, insights_ctx)
@@ -227,7 +276,9 @@ impl OptimizerTrace {
                     })
                 });
 
-                let output = serde_json::json!({
+                let features: BTreeMap<_, _> = features.entries().into_iter().collect();
+
+                let mut output = serde_json::json!({
                     "plans": {
                         "raw": get_plan(NamedPlan::Raw),
                         "optimized": {
@@ -237,11 +288,24 @@ impl OptimizerTrace {
                     },
                     "insights": plan_insights,
                     "cluster": cluster,
+                    "features": features,
                     "redacted_sql": redacted_sql,
                 });
+                if config.timing {
+                    output["timings"] = serde_json::json!(self.collect_pass_timings());
+                }
                 let output = serde_json::to_string_pretty(&output).expect("JSON string");
                 vec![Row::pack_slice(&[Datum::from(output.as_str())])]
             }
+            ExplainStage::Bundle => {
+                // Bundles are assembled by the coordinator from the output of
+                // `into_bundle_trace`.
+                coord_bail!("EXPLAIN BUNDLE is only supported for SELECT statements");
+            }
+            ExplainStage::Emptiness => {
+                // `EXPLAIN EMPTINESS` is planned as a `SELECT`.
+                coord_bail!("EXPLAIN EMPTINESS is only supported for SELECT statements");
+            }
             _ => {
                 // For everything else, return the plan for the stage identified
                 // by the corresponding path.
@@ -264,6 +328,34 @@ impl OptimizerTrace {
                     traces.remove(path)
                 };
 
+                // Annotate the decorrelated plan with how its subqueries were lowered.
+                let plan = match plan {
+                    Some(mut entry)
+                        if config.subquery_lowering
+                            && format == ExplainFormat::Text
+                            && entry.path == NamedPlan::Decorrelated.path() =>
+                    {
+                        let lowerings = self.collect_subquery_lowerings();
+                        if !lowerings.is_empty() {
+                            entry.plan.push('\n');
+                            entry
+                                .plan
+                                .push_str(&text_string_at(&lowerings, Indent::default));
+                        }
+                        Some(entry)
+                    }
+                    plan => plan,
+                };
+
+                // Precede text plans with a header if entries were truncated.
+                let plan = match (plan, traces.overflow_header()) {
+                    (Some(mut entry), Some(header)) if format == ExplainFormat::Text => {
+                        entry.plan = format!("{header}\n{}", entry.plan);
+                        Some(entry)
+                    }
+                    (plan, _) => plan,
+                };
+
                 let row = plan
                     .map(|entry| Row::pack_slice(&[Datum::from(entry.plan.as_str())]))
                     .ok_or_else(|| {
@@ -297,6 +389,41 @@ impl OptimizerTrace {
         Ok(rows)
     }
 
+    /// Collect the entire trace with plans rendered as text, for inclusion in
+    /// an [`ExplainBundle`](crate::explain::bundle::ExplainBundle).
+    pub fn into_bundle_trace(
+        self,
+        config: &ExplainConfig,
+        features: &OptimizerFeatures,
+        humanizer: &dyn ExprHumanizer,
+        row_set_finishing: Option<RowSetFinishing>,
+        target_cluster: Option<&Cluster>,
+        dataflow_metainfo: DataflowMetainfo,
+        cardinality_stats: BTreeMap<GlobalId, usize>,
+    ) -> Result<Vec<BundleTraceEntry>, AdapterError> {
+        let trace = self
+            .collect_all(
+                ExplainFormat::Text,
+                config,
+                features,
+                humanizer,
+                row_set_finishing,
+                target_cluster.map(|c| c.name.as_str()),
+                dataflow_metainfo,
+                cardinality_stats,
+                None,
+            )?
+            .entries
+            .into_iter()
+            .map(BundleTraceEntry::from)
+            .collect();
+
+        // See `into_rows` for why the interest cache needs to be rebuilt.
+        drop(self);
+        tracing_core::callsite::rebuild_interest_cache();
+        Ok(trace)
+    }
+
     /// Collect a [`insights::PlanInsights`] with insights about the the
     /// optimized plans rendered as a JSON `String`.
     pub async fn into_plan_insights(
@@ -317,6 +444,8 @@ impl OptimizerTrace {
                 row_set_finishing,
                 target_cluster,
                 dataflow_metainfo,
+                BTreeMap::new(),
+                None,
                 ExplainStage::PlanInsights,
                 plan::ExplaineeStatementKind::Select,
                 insights_ctx,
@@ -329,6 +458,103 @@ impl OptimizerTrace {
         Ok(rows.into_element().into_element().unwrap_str().into())
     }
 
+    /// Builds a [`PlanGraph`] of the optimized plan in the trace, or of the
+    /// last traced MIR plan if the trace has no optimized global plan.
+    pub fn plan_graph(&self, humanizer: &dyn ExprHumanizer) -> Option<PlanGraph> {
+        let mut graph = if let Some(global_plan) = self.collect_global_plan() {
+            let export_ids = export_ids_for(&global_plan);
+            let mut graph = PlanGraph::new(NamedPlan::Global.path().into());
+            for build in global_plan.objects_to_build.iter() {
+                let id = export_ids.get(&build.id).unwrap_or(&build.id);
+                let object = humanizer.humanize_id(*id).unwrap_or_else(|| id.to_string());
+                graph.add_plan(Some(object), build.plan.as_inner(), humanizer);
+            }
+            graph
+        } else {
+            let entry = self
+                .0
+                .downcast_ref::<PlanTrace<MirRelationExpr>>()
+                .and_then(|trace| trace.collect_as_vec().pop())?;
+            let mut graph = PlanGraph::new(entry.path);
+            graph.add_plan(None, &entry.plan, humanizer);
+            graph
+        };
+
+        let mut timings = itertools::chain!(
+            self.collect_timings::<HirRelationExpr>(),
+            self.collect_timings::<MirRelationExpr>(),
+            self.collect_timings::<DataflowDescription<OptimizedMirRelationExpr>>(),
+            self.collect_timings::<DataflowDescription<Plan>>(),
+            self.collect_timings::<FastPathPlan>(),
+        )
+        .collect::<Vec<_>>();
+        timings.sort_by_key(|(instant, _)| *instant);
+        graph.timings = timings.into_iter().map(|(_, timing)| timing).collect();
+        Some(graph)
+    }
+
+    /// Collects the time spent in each traced stage that produced a plan of
+    /// type `T`, along with the instant the plan was produced.
+    fn collect_timings<T>(&self) -> Vec<(Instant, PlanGraphTiming)>
+    where
+        T: Clone + Debug + 'static,
+    {
+        self.collect_durations::<T>()
+            .into_iter()
+            .map(|entry| {
+                let timing = PlanGraphTiming::new(entry.path, entry.span_duration);
+                (entry.instant, timing)
+            })
+            .collect()
+    }
+
+    /// Collects the time spent in each optimizer pass, by the path of the
+    /// pass, summed over all invocations of the pass.
+    fn collect_pass_timings(&self) -> BTreeMap<String, PassTiming> {
+        let entries = itertools::chain!(
+            self.collect_durations::<HirRelationExpr>(),
+            self.collect_durations::<MirRelationExpr>(),
+            self.collect_durations::<DataflowDescription<OptimizedMirRelationExpr>>(),
+            self.collect_durations::<DataflowDescription<Plan>>(),
+            self.collect_durations::<FastPathPlan>(),
+        );
+        // The optimizer would have to take over 584 years to overflow a u64.
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let mut timings = BTreeMap::<String, PassTiming>::new();
+        for entry in entries {
+            let timing = timings.entry(entry.path).or_default();
+            timing.invocations += 1;
+            timing.span_duration_ns = timing
+                .span_duration_ns
+                .saturating_add(nanos(entry.span_duration));
+            timing.full_duration_ns = timing.full_duration_ns.max(nanos(entry.full_duration));
+        }
+        timings
+    }
+
+    /// Collects the trace entries of plans of type `T`, without the plans.
+    fn collect_durations<T>(&self) -> Vec<TraceEntry<()>>
+    where
+        T: Clone + Debug + 'static,
+    {
+        self.0
+            .downcast_ref::<PlanTrace<T>>()
+            .map(|trace| {
+                trace
+                    .collect_as_vec()
+                    .into_iter()
+                    .map(|entry| TraceEntry {
+                        instant: entry.instant,
+                        span_duration: entry.span_duration,
+                        full_duration: entry.full_duration,
+                        path: entry.path,
+                        plan: (),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Collect all traced plans for all plan types `T` that are available in
     /// the wrapped [`dispatcher::Dispatch`].
     fn collect_all(
@@ -340,8 +566,11 @@ impl OptimizerTrace {
         row_set_finishing: Option<RowSetFinishing>,
         target_cluster: Option<&str>,
         dataflow_metainfo: DataflowMetainfo,
+        cardinality_stats: BTreeMap<GlobalId, usize>,
+        subscribe: Option<ExplainSubscribe>,
     ) -> Result<TraceEntries<String>, ExplainError> {
         let mut results = vec![];
+        let mut overflow = 0;
 
         // First, create an ExplainContext without `used_indexes`. We'll use this to, e.g., collect
         // HIR plans.
@@ -350,6 +579,7 @@ impl OptimizerTrace {
             features,
             humanizer,
             cardinality_stats: Default::default(), // empty stats
+            row_count_hints: Default::default(),
             used_indexes: Default::default(),
             finishing: row_set_finishing.clone(),
             duration: Default::default(),
@@ -359,12 +589,21 @@ impl OptimizerTrace {
                 humanizer,
                 config.redacted,
             )?,
+            subscribe: None,
         };
 
         // Collect trace entries of types produced by local optimizer stages.
         results.extend(itertools::chain!(
-            self.collect_explainable_entries::<HirRelationExpr>(&format, &mut context)?,
-            self.collect_explainable_entries::<MirRelationExpr>(&format, &mut context)?,
+            self.collect_explainable_entries::<HirRelationExpr>(
+                &format,
+                &mut context,
+                &mut overflow
+            )?,
+            self.collect_explainable_entries::<MirRelationExpr>(
+                &format,
+                &mut context,
+                &mut overflow
+            )?,
         ));
 
         // Collect trace entries of types produced by global optimizer stages.
@@ -372,7 +611,8 @@ impl OptimizerTrace {
             config,
             features,
             humanizer,
-            cardinality_stats: Default::default(), // empty stats
+            cardinality_stats,
+            row_count_hints: dataflow_metainfo.row_count_hints.clone(),
             used_indexes: Default::default(),
             finishing: row_set_finishing,
             duration: Default::default(),
@@ -382,22 +622,28 @@ impl OptimizerTrace {
                 humanizer,
                 config.redacted,
             )?,
+            subscribe,
         };
         results.extend(itertools::chain!(
             self.collect_explainable_entries::<DataflowDescription<OptimizedMirRelationExpr>>(
                 &format,
                 &mut context,
+                &mut overflow,
+            )?,
+            self.collect_explainable_entries::<DataflowDescription<Plan>>(
+                &format,
+                &mut context,
+                &mut overflow,
             )?,
-            self.collect_explainable_entries::<DataflowDescription<Plan>>(&format, &mut context)?,
-            self.collect_explainable_entries::<FastPathPlan>(&format, &mut context)?,
+            self.collect_explainable_entries::<FastPathPlan>(&format, &mut context, &mut overflow)?,
         ));
 
         // Collect trace entries of type String, HirScalarExpr, MirScalarExpr
         // which are useful for ad-hoc debugging.
         results.extend(itertools::chain!(
-            self.collect_scalar_entries::<HirScalarExpr>(),
-            self.collect_scalar_entries::<MirScalarExpr>(),
-            self.collect_string_entries(),
+            self.collect_scalar_entries::<HirScalarExpr>(&mut overflow),
+            self.collect_scalar_entries::<MirScalarExpr>(&mut overflow),
+            self.collect_string_entries(&mut overflow),
         ));
 
         // sort plans by instant (TODO: this can be implemented in a more
@@ -405,7 +651,10 @@ impl OptimizerTrace {
         // to `*.extend` the `results` vector is already sorted).
         results.sort_by_key(|x| x.instant);
 
-        Ok(TraceEntries(results))
+        Ok(TraceEntries {
+            entries: results,
+            overflow,
+        })
     }
 
     /// Collects the global optimized plan from the trace, if it exists.
@@ -424,12 +673,21 @@ impl OptimizerTrace {
             .map(|entry| entry.plan)
     }
 
+    /// Collects the subqueries lowered by the decorrelation stage from the trace.
+    fn collect_subquery_lowerings(&self) -> SubqueryLowerings {
+        self.0
+            .downcast_ref::<PlanTrace<SubqueryLowerings>>()
+            .and_then(|trace| trace.find(NamedPlan::Decorrelated.path()))
+            .map_or_else(Default::default, |entry| entry.plan)
+    }
+
     /// Collect all trace entries of a plan type `T` that implements
     /// [`Explainable`].
     fn collect_explainable_entries<T>(
         &self,
         format: &ExplainFormat,
         context: &mut ExplainContext,
+        overflow: &mut usize,
     ) -> Result<Vec<TraceEntry<String>>, ExplainError>
     where
         T: Clone + Debug + 'static,
@@ -438,8 +696,9 @@ impl OptimizerTrace {
         if let Some(trace) = self.0.downcast_ref::<PlanTrace<T>>() {
             // Get a handle of the associated `PlanTrace<UsedIndexes>`.
             let used_indexes_trace = self.0.downcast_ref::<PlanTrace<UsedIndexes>>();
+            let mut budget = TraceBudget::new(MAX_TRACE_BYTES);
 
-            trace
+            let entries = trace
                 .collect_as_vec()
                 .into_iter()
                 .map(|mut entry| {
@@ -450,19 +709,21 @@ impl OptimizerTrace {
                     let used_indexes = used_indexes_trace.map(|t| t.used_indexes_for(&entry.path));
 
                     // Render the EXPLAIN output string for this entry.
-                    let plan = if let Some(mut used_indexes) = used_indexes {
-                        // Temporary swap the found UsedIndexes with the default
-                        // one in the ExplainContext while explaining the plan
-                        // for this entry.
-                        std::mem::swap(&mut context.used_indexes, &mut used_indexes);
-                        let plan = Explainable::new(&mut entry.plan).explain(format, context)?;
-                        std::mem::swap(&mut context.used_indexes, &mut used_indexes);
-                        plan
-                    } else {
-                        // No UsedIndexes instance for this entry found - use
-                        // the default UsedIndexes in the ExplainContext.
-                        Explainable::new(&mut entry.plan).explain(format, context)?
-                    };
+                    let plan = budget.render(|| {
+                        if let Some(mut used_indexes) = used_indexes {
+                            // Temporary swap the found UsedIndexes with the default
+                            // one in the ExplainContext while explaining the plan
+                            // for this entry.
+                            std::mem::swap(&mut context.used_indexes, &mut used_indexes);
+                            let plan = Explainable::new(&mut entry.plan).explain(format, context);
+                            std::mem::swap(&mut context.used_indexes, &mut used_indexes);
+                            plan
+                        } else {
+                            // No UsedIndexes instance for this entry found - use
+                            // the default UsedIndexes in the ExplainContext.
+                            Explainable::new(&mut entry.plan).explain(format, context)
+                        }
+                    })?;
 
                     Ok(TraceEntry {
                         instant: entry.instant,
@@ -472,20 +733,23 @@ impl OptimizerTrace {
                         plan,
                     })
                 })
-                .collect()
+                .collect();
+            *overflow += budget.overflow();
+            entries
         } else {
             unreachable!("collect_explainable_entries called with wrong plan type T");
         }
     }
 
     /// Collect all trace entries of a plan type `T`.
-    fn collect_scalar_entries<T>(&self) -> Vec<TraceEntry<String>>
+    fn collect_scalar_entries<T>(&self, overflow: &mut usize) -> Vec<TraceEntry<String>>
     where
         T: Clone + Debug + 'static,
         T: Display,
     {
         if let Some(trace) = self.0.downcast_ref::<PlanTrace<T>>() {
-            trace
+            let mut budget = TraceBudget::new(MAX_TRACE_BYTES);
+            let entries = trace
                 .collect_as_vec()
                 .into_iter()
                 .map(|entry| TraceEntry {
@@ -493,18 +757,30 @@ impl OptimizerTrace {
                     span_duration: entry.span_duration,
                     full_duration: entry.full_duration,
                     path: entry.path,
-                    plan: entry.plan.to_string(),
+                    plan: budget.admit(entry.plan.to_string()),
                 })
-                .collect()
+                .collect();
+            *overflow += budget.overflow();
+            entries
         } else {
             vec![]
         }
     }
 
     /// Collect all trace entries with plans of type [`String`].
-    fn collect_string_entries(&self) -> Vec<TraceEntry<String>> {
+    fn collect_string_entries(&self, overflow: &mut usize) -> Vec<TraceEntry<String>> {
         if let Some(trace) = self.0.downcast_ref::<PlanTrace<String>>() {
-            trace.collect_as_vec()
+            let mut budget = TraceBudget::new(MAX_TRACE_BYTES);
+            let entries = trace
+                .collect_as_vec()
+                .into_iter()
+                .map(|entry| TraceEntry {
+                    plan: budget.admit(entry.plan),
+                    ..entry
+                })
+                .collect();
+            *overflow += budget.overflow();
+            entries
         } else {
             vec![]
         }
@@ -518,13 +794,41 @@ pub struct DispatchGuard<'a> {
 }
 
 /// A collection of optimizer trace entries with convenient accessor methods.
-pub struct TraceEntries<T>(pub Vec<TraceEntry<T>>);
+pub struct TraceEntries<T> {
+    pub entries: Vec<TraceEntry<T>>,
+    /// The number of entries whose plans were truncated because their trace
+    /// exceeded [`MAX_TRACE_BYTES`].
+    pub overflow: usize,
+}
 
 impl<T> TraceEntries<T> {
     // Removes the first (and by assumption the only) trace that matches the
     // given path from the collected trace.
     pub fn remove(&mut self, path: &'static str) -> Option<TraceEntry<T>> {
-        let index = self.0.iter().position(|entry| entry.path == path);
-        index.map(|index| self.0.remove(index))
+        let index = self.entries.iter().position(|entry| entry.path == path);
+        index.map(|index| self.entries.remove(index))
     }
+
+    /// A header that reports truncated entries, if there were any.
+    fn overflow_header(&self) -> Option<String> {
+        (self.overflow > 0).then(|| {
+            format!(
+                "Optimizer trace overflow: {} entries were truncated to fit the budget of {} bytes per trace\n",
+                self.overflow, MAX_TRACE_BYTES
+            )
+        })
+    }
+}
+
+/// The time spent in an optimizer pass, as reported by `EXPLAIN PLAN INSIGHTS
+/// WITH (timing)`.
+#[derive(Debug, Default, Serialize)]
+struct PassTiming {
+    /// The number of times the pass ran.
+    invocations: u64,
+    /// The time spent in the pass, summed over all invocations.
+    span_duration_ns: u64,
+    /// The time from the start of the optimization to the end of the last
+    /// invocation of the pass.
+    full_duration_ns: u64,
 }
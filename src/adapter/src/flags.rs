@@ -182,6 +182,8 @@ pub fn pg_timstamp_oracle_config(config: &SystemVars) -> PostgresTimestampOracle
         // oracle.
         pg_connection_pool_connect_timeout: Some(config.crdb_connect_timeout()),
         pg_connection_pool_tcp_user_timeout: Some(config.crdb_tcp_user_timeout()),
+        batch_window: Some(config.timestamp_oracle_batch_window()),
+        circuit_breaker_timeout: Some(config.timestamp_oracle_circuit_breaker_timeout()),
     }
 }
 
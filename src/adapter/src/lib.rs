@@ -57,12 +57,17 @@ pub mod statement_logging;
 pub mod telemetry;
 pub mod webhook;
 
+pub use crate::active_compute_sink::SubscribeRowStream;
 pub use crate::client::{Client, Handle, SessionClient};
-pub use crate::command::{ExecuteResponse, ExecuteResponseKind, RowsFuture, StartupResponse};
+pub use crate::command::{
+    ExecuteResponse, ExecuteResponseKind, RowsFuture, RowsStream, StartupResponse,
+};
+pub use crate::coord::conn_registry::{ConnRegistry, ConnRegistryKey};
 pub use crate::coord::id_bundle::CollectionIdBundle;
 pub use crate::coord::peek::PeekResponseUnary;
 pub use crate::coord::read_policy::ReadHolds;
 pub use crate::coord::read_policy::ReadHoldsInner;
+pub use crate::coord::simulation::SimulationStep;
 pub use crate::coord::timeline::TimelineContext;
 pub use crate::coord::timestamp_selection::{
     TimestampContext, TimestampExplanation, TimestampProvider,
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use mz_ore::metric;
-use mz_ore::metrics::MetricsRegistry;
+use mz_ore::metrics::{MetricsRegistry, UIntGauge};
 use mz_ore::stats::{histogram_milliseconds_buckets, histogram_seconds_buckets};
 use mz_sql::ast::{AstInfo, Statement, StatementKind, SubscribeOutput};
 use mz_sql::session::user::User;
@@ -28,10 +28,14 @@ pub struct Metrics {
     pub storage_usage_collection_time_seconds: HistogramVec,
     pub subscribe_outputs: IntCounterVec,
     pub canceled_peeks: IntCounterVec,
+    pub deduplicated_peeks: IntCounter,
     pub linearize_message_seconds: HistogramVec,
     pub time_to_first_row_seconds: HistogramVec,
     pub statement_logging_unsampled_bytes: IntCounterVec,
     pub statement_logging_actual_bytes: IntCounterVec,
+    pub statement_logging_drain_backlog_bytes: UIntGauge,
+    pub statement_logging_spilled_bytes: UIntGauge,
+    pub statement_logging_dropped_events: IntCounter,
     pub message_handling: HistogramVec,
     pub optimization_notices: IntCounterVec,
     pub append_table_duration_seconds: HistogramVec,
@@ -40,6 +44,24 @@ pub struct Metrics {
     pub check_scheduling_policies_seconds: HistogramVec,
     pub handle_scheduling_decisions_seconds: HistogramVec,
     pub row_set_finishing_seconds: HistogramVec,
+    pub catalog_vacuum_pruned_audit_log_events: IntCounter,
+    pub workload_class_statements: IntCounterVec,
+    pub catalog_transact_seconds: Histogram,
+    pub catalog_copy_on_write_seconds: Histogram,
+    pub catalog_snapshot_hold_seconds: HistogramVec,
+    pub catalog_outstanding_snapshots: UIntGauge,
+    pub pending_commands: UIntGauge,
+    pub introspection_subscribe_restarts: IntCounterVec,
+    pub database_statements: IntCounterVec,
+    pub database_statement_errors: IntCounterVec,
+    pub database_result_bytes: IntCounterVec,
+    pub database_peek_seconds: HistogramVec,
+    pub segment_events_sent: IntCounter,
+    pub segment_events_dropped: IntCounterVec,
+    pub external_tasks_running: IntGaugeVec,
+    pub external_tasks_queued: IntGaugeVec,
+    pub starved_write_lock_waiters: IntCounterVec,
+    pub builtin_table_id_cache_lookups: IntCounterVec,
 }
 
 impl Metrics {
@@ -100,6 +122,10 @@ impl Metrics {
                 name: "mz_canceled_peeks_total",
                 help: "The total number of canceled peeks since process start.",
             )),
+            deduplicated_peeks: registry.register(metric!(
+                name: "mz_deduplicated_peeks_total",
+                help: "The total number of peeks that waited for the response of an identical peek in flight instead of issuing a compute peek.",
+            )),
             linearize_message_seconds: registry.register(metric!(
                 name: "mz_linearize_message_seconds",
                 help: "The number of seconds it takes to linearize strict serializable messages",
@@ -120,6 +146,18 @@ impl Metrics {
                 name: "mz_statement_logging_actual_bytes",
                 help: "The total amount of SQL text that was logged by statement logging.",
             )),
+            statement_logging_drain_backlog_bytes: registry.register(metric!(
+                name: "mz_statement_logging_drain_backlog_bytes",
+                help: "The number of bytes of statement log events waiting to be written to the statement log after its most recent drain, including spilled events.",
+            )),
+            statement_logging_spilled_bytes: registry.register(metric!(
+                name: "mz_statement_logging_spilled_bytes",
+                help: "The number of bytes of statement log events spilled to disk after the most recent drain of the statement log.",
+            )),
+            statement_logging_dropped_events: registry.register(metric!(
+                name: "mz_statement_logging_dropped_events",
+                help: "The number of statement log events dropped because the spilled events exceeded their bound or could not be written or read back.",
+            )),
             message_handling: registry.register(metric!(
                 name: "mz_slow_message_handling",
                 help: "Latency for ALL coordinator messages. 'slow' is in the name for legacy reasons, but is not accurate.",
@@ -162,6 +200,94 @@ impl Metrics {
                 help: "The time it takes to run RowSetFinishing::finish.",
                 buckets: histogram_seconds_buckets(0.000_128, 16.0),
             )),
+            catalog_vacuum_pruned_audit_log_events: registry.register(metric!(
+                name: "mz_catalog_vacuum_pruned_audit_log_events",
+                help: "The total number of audit log events pruned by catalog vacuums.",
+            )),
+            workload_class_statements: registry.register(metric!(
+                name: "mz_workload_class_statements_total",
+                help: "The total number of statements classified into each workload class.",
+                var_labels: ["class", "priority"],
+            )),
+            catalog_transact_seconds: registry.register(metric!(
+                name: "mz_catalog_transact_seconds",
+                help: "The time the coordinator spends applying catalog transactions, during which it does not serve any other command.",
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
+            catalog_copy_on_write_seconds: registry.register(metric!(
+                name: "mz_catalog_copy_on_write_seconds",
+                help: "The time the coordinator spends copying the catalog before mutating it, because read-only snapshots of it are still held.",
+                buckets: histogram_seconds_buckets(0.000_128, 8.0),
+            )),
+            catalog_snapshot_hold_seconds: registry.register(metric!(
+                name: "mz_catalog_snapshot_hold_seconds",
+                help: "The time planning and optimization hold read-only references to the catalog, by holder.",
+                var_labels: ["holder"],
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
+            catalog_outstanding_snapshots: registry.register(metric!(
+                name: "mz_catalog_outstanding_snapshots",
+                help: "The number of read-only catalog snapshots held outside of the coordinator when it last mutated the catalog.",
+            )),
+            pending_commands: registry.register(metric!(
+                name: "mz_coord_pending_commands",
+                help: "The number of client commands waiting for the coordinator when it last received one.",
+            )),
+            introspection_subscribe_restarts: registry.register(metric!(
+                name: "mz_introspection_subscribe_restarts_total",
+                help: "The total number of times introspection subscribes were reinstalled after failing, per introspection collection.",
+                var_labels: ["introspection_type"],
+            )),
+            database_statements: registry.register(metric!(
+                name: "mz_database_statements_total",
+                help: "The total number of statements executed by sessions connected to each database.",
+                var_labels: ["database_id"],
+            )),
+            database_statement_errors: registry.register(metric!(
+                name: "mz_database_statement_errors_total",
+                help: "The total number of statements that failed in sessions connected to each database.",
+                var_labels: ["database_id"],
+            )),
+            database_result_bytes: registry.register(metric!(
+                name: "mz_database_result_bytes_total",
+                help: "The total number of bytes returned by peeks in sessions connected to each database.",
+                var_labels: ["database_id"],
+            )),
+            database_peek_seconds: registry.register(metric!(
+                name: "mz_database_peek_seconds",
+                help: "The time it takes clusters to respond to peeks of sessions connected to each database.",
+                var_labels: ["database_id"],
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
+            segment_events_sent: registry.register(metric!(
+                name: "mz_segment_events_sent_total",
+                help: "The total number of batched Segment events that were sent.",
+            )),
+            segment_events_dropped: registry.register(metric!(
+                name: "mz_segment_events_dropped_total",
+                help: "The total number of batched Segment events that were dropped, by reason.",
+                var_labels: ["reason"],
+            )),
+            external_tasks_running: registry.register(metric!(
+                name: "mz_external_tasks_running",
+                help: "The number of running purification and connection validation tasks that contact each kind of external system.",
+                var_labels: ["external_system"],
+            )),
+            external_tasks_queued: registry.register(metric!(
+                name: "mz_external_tasks_queued",
+                help: "The number of queued purification and connection validation tasks that wait for each kind of external system.",
+                var_labels: ["external_system"],
+            )),
+            starved_write_lock_waiters: registry.register(metric!(
+                name: "mz_starved_write_lock_waiters_total",
+                help: "The number of deferred writes that waited for the write lock for longer than the warning threshold, by whether they were aborted.",
+                var_labels: ["action"],
+            )),
+            builtin_table_id_cache_lookups: registry.register(metric!(
+                name: "mz_builtin_table_id_cache_lookups_total",
+                help: "The number of lookups of the IDs of builtin tables in the coordinator's cache, by whether they hit the cache.",
+                var_labels: ["result"],
+            )),
         }
     }
 
@@ -172,6 +298,8 @@ impl Metrics {
     pub(crate) fn session_metrics(&self) -> SessionMetrics {
         SessionMetrics {
             row_set_finishing_seconds: self.row_set_finishing_seconds(),
+            database_statement_errors: self.database_statement_errors.clone(),
+            current_database_statement_errors: None,
         }
     }
 }
@@ -180,12 +308,29 @@ impl Metrics {
 #[derive(Debug, Clone)]
 pub struct SessionMetrics {
     row_set_finishing_seconds: Histogram,
+    database_statement_errors: IntCounterVec,
+    /// The errors of the database that the statements of the session are attributed to.
+    current_database_statement_errors: Option<IntCounter>,
 }
 
 impl SessionMetrics {
     pub(crate) fn row_set_finishing_seconds(&self) -> &Histogram {
         &self.row_set_finishing_seconds
     }
+
+    /// Attributes the statements that the session executes from now on to the database with the
+    /// label `database`, or to no database.
+    pub(crate) fn set_database(&mut self, database: Option<&str>) {
+        self.current_database_statement_errors = database.map(|database| {
+            self.database_statement_errors
+                .with_label_values(&[database])
+        });
+    }
+
+    /// Returns the errors of the database that the statements of the session are attributed to.
+    pub(crate) fn database_statement_errors(&self) -> Option<&IntCounter> {
+        self.current_database_statement_errors.as_ref()
+    }
 }
 
 pub(crate) fn session_type_label_value(user: &User) -> &'static str {
@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::fmt;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
@@ -22,6 +23,7 @@ use mz_sql::catalog::ErrorMessageObjectDescription;
 use mz_sql::plan::PlanNotice;
 use mz_sql::session::vars::IsolationLevel;
 use tokio_postgres::error::SqlState;
+use uuid::Uuid;
 
 use crate::TimestampExplanation;
 
@@ -78,6 +80,10 @@ pub enum AdapterNotice {
     DroppedActiveCluster {
         name: String,
     },
+    ClusterFailover {
+        from: String,
+        to: String,
+    },
     QueryTimestamp {
         explanation: TimestampExplanation<mz_repr::Timestamp>,
     },
@@ -87,10 +93,20 @@ pub enum AdapterNotice {
     QueryTrace {
         trace_id: opentelemetry::trace::TraceId,
     },
+    StatementCorrelationId {
+        trace_id: Option<opentelemetry::trace::TraceId>,
+        statement_id: Uuid,
+    },
     UnimplementedIsolationLevel {
         isolation_level: String,
     },
     StrongSessionSerializable,
+    IsolationLevelDowngraded {
+        isolation_level: IsolationLevel,
+        effective: IsolationLevel,
+        reason: String,
+    },
+    TimestampOracleUnavailable,
     BadStartupSetting {
         name: String,
         reason: String,
@@ -125,6 +141,14 @@ pub enum AdapterNotice {
         url: url::Url,
     },
     DroppedInUseIndex(DroppedInUseIndex),
+    DropObjectsProgress {
+        dropped: usize,
+        total: usize,
+    },
+    SourcePurificationProgress {
+        validated: usize,
+        total: usize,
+    },
     PerReplicaLogRead {
         log_names: Vec<String>,
     },
@@ -134,8 +158,54 @@ pub enum AdapterNotice {
     },
     Welcome(String),
     PlanInsights(String),
+    SlowPathPeek {
+        reason: String,
+    },
+    CardinalityBlowup {
+        object: String,
+        replica: String,
+        operator: String,
+        factor: f64,
+    },
+    SinkLagExceeded {
+        sink: String,
+        lag: Duration,
+        threshold: Duration,
+    },
+    ScheduledClusterSuspension {
+        cluster: String,
+        grace_period: Duration,
+    },
+    HydrationPausedAfterOomCrashLoop {
+        name: String,
+        cluster: String,
+        replica: String,
+    },
+    InsufficientReplicaCapacity {
+        size: String,
+        schedulable: u16,
+        required: u16,
+    },
+    ReplicaCapacityQueued {
+        size: String,
+        schedulable: u16,
+        required: u16,
+    },
+    ComputeSinkWaiting {
+        cluster: String,
+    },
     IntrospectionClusterUsage,
     AutoRouteIntrospectionQueriesUsage,
+    WriteDryRun,
+    BuiltinTableMigrationPending {
+        name: String,
+    },
+    BuiltinTableMigrationComplete {
+        name: String,
+    },
+    ExternalTaskQueued {
+        hosts: Vec<String>,
+    },
 }
 
 impl AdapterNotice {
@@ -174,11 +244,15 @@ impl AdapterNotice {
             AdapterNotice::CascadeDroppedObject { .. } => Severity::Notice,
             AdapterNotice::DroppedActiveDatabase { .. } => Severity::Notice,
             AdapterNotice::DroppedActiveCluster { .. } => Severity::Notice,
+            AdapterNotice::ClusterFailover { .. } => Severity::Notice,
             AdapterNotice::QueryTimestamp { .. } => Severity::Notice,
             AdapterNotice::EqualSubscribeBounds { .. } => Severity::Notice,
             AdapterNotice::QueryTrace { .. } => Severity::Notice,
+            AdapterNotice::StatementCorrelationId { .. } => Severity::Notice,
             AdapterNotice::UnimplementedIsolationLevel { .. } => Severity::Notice,
             AdapterNotice::StrongSessionSerializable => Severity::Notice,
+            AdapterNotice::IsolationLevelDowngraded { .. } => Severity::Debug,
+            AdapterNotice::TimestampOracleUnavailable => Severity::Warning,
             AdapterNotice::BadStartupSetting { .. } => Severity::Notice,
             AdapterNotice::RbacUserDisabled => Severity::Notice,
             AdapterNotice::RoleMembershipAlreadyExists { .. } => Severity::Notice,
@@ -196,12 +270,26 @@ impl AdapterNotice {
             AdapterNotice::OptimizerNotice { .. } => Severity::Notice,
             AdapterNotice::WebhookSourceCreated { .. } => Severity::Notice,
             AdapterNotice::DroppedInUseIndex { .. } => Severity::Notice,
+            AdapterNotice::DropObjectsProgress { .. } => Severity::Notice,
+            AdapterNotice::SourcePurificationProgress { .. } => Severity::Notice,
             AdapterNotice::PerReplicaLogRead { .. } => Severity::Notice,
             AdapterNotice::VarDefaultUpdated { .. } => Severity::Notice,
             AdapterNotice::Welcome(_) => Severity::Notice,
             AdapterNotice::PlanInsights(_) => Severity::Notice,
+            AdapterNotice::SlowPathPeek { .. } => Severity::Notice,
+            AdapterNotice::CardinalityBlowup { .. } => Severity::Warning,
+            AdapterNotice::SinkLagExceeded { .. } => Severity::Warning,
+            AdapterNotice::ScheduledClusterSuspension { .. } => Severity::Warning,
+            AdapterNotice::HydrationPausedAfterOomCrashLoop { .. } => Severity::Warning,
+            AdapterNotice::InsufficientReplicaCapacity { .. } => Severity::Warning,
+            AdapterNotice::ReplicaCapacityQueued { .. } => Severity::Notice,
+            AdapterNotice::ComputeSinkWaiting { .. } => Severity::Notice,
             AdapterNotice::IntrospectionClusterUsage => Severity::Warning,
             AdapterNotice::AutoRouteIntrospectionQueriesUsage => Severity::Warning,
+            AdapterNotice::WriteDryRun => Severity::Notice,
+            AdapterNotice::BuiltinTableMigrationPending { .. } => Severity::Warning,
+            AdapterNotice::BuiltinTableMigrationComplete { .. } => Severity::Notice,
+            AdapterNotice::ExternalTaskQueued { .. } => Severity::Notice,
         }
     }
 
@@ -216,6 +304,15 @@ impl AdapterNotice {
                     .map(|obj_info| format!("drop cascades to {}", obj_info))
                     .join("\n"),
             ),
+            AdapterNotice::IsolationLevelDowngraded { .. } => Some(
+                "The result of the query does not depend on the timestamp it is executed at."
+                    .into(),
+            ),
+            AdapterNotice::TimestampOracleUnavailable => Some(
+                "Writes, changes to the catalog and strict serializable reads are rejected until \
+                 the timestamp oracle is available again."
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -246,8 +343,17 @@ impl AdapterNotice {
             ),
             AdapterNotice::OptimizerNotice { notice: _, hint } => Some(hint.clone()),
             AdapterNotice::DroppedInUseIndex(..) => Some("To free up the resources used by the index, recreate all the above-mentioned objects.".into()),
+            AdapterNotice::CardinalityBlowup { .. } => Some("Check the joins of the object for missing or unintended join conditions. mz_internal.mz_compute_cardinality_advisories lists all operators whose output exceeds their input.".into()),
+            AdapterNotice::SinkLagExceeded { .. } => Some("mz_internal.mz_sink_lag shows the lag of all sinks, and mz_internal.mz_sink_statuses shows whether the sink is healthy.".into()),
+            AdapterNotice::ScheduledClusterSuspension { .. } => Some("Queries and subscriptions that are still running on the cluster when it is turned off stall until the cluster is turned on again.".into()),
+            AdapterNotice::HydrationPausedAfterOomCrashLoop { .. } => Some("The object is paused until the replica is dropped. Resize the cluster or drop the object to resume hydration.".into()),
+            AdapterNotice::InsufficientReplicaCapacity { .. } => Some("The replicas will start once capacity becomes available. Choose a smaller size or fewer replicas to avoid waiting.".into()),
+            AdapterNotice::ReplicaCapacityQueued { .. } => Some("The statement creates the replicas once capacity becomes available, or fails after replica_capacity_queue_timeout. Choose a smaller size or fewer replicas to avoid waiting.".into()),
+            AdapterNotice::ComputeSinkWaiting { .. } => Some("The statement starts once another subscribe or copy to on the cluster finishes.".into()),
             AdapterNotice::IntrospectionClusterUsage => Some("Use the new name instead.".into()),
             AdapterNotice::AutoRouteIntrospectionQueriesUsage => Some("Use the new name instead.".into()),
+            AdapterNotice::BuiltinTableMigrationPending { .. } => Some("mz_internal.mz_builtin_table_migrations shows the progress of the migration. A notice is sent once it is complete.".into()),
+            AdapterNotice::ExternalTaskQueued { .. } => Some("The statement starts once other statements that contact the same external hosts finish.".into()),
             _ => None
         }
     }
@@ -274,11 +380,15 @@ impl AdapterNotice {
             AdapterNotice::CascadeDroppedObject { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::DroppedActiveDatabase { .. } => SqlState::from_code("MZ002"),
             AdapterNotice::DroppedActiveCluster { .. } => SqlState::from_code("MZ003"),
+            AdapterNotice::ClusterFailover { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::QueryTimestamp { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::EqualSubscribeBounds { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::QueryTrace { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::StatementCorrelationId { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::UnimplementedIsolationLevel { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::StrongSessionSerializable => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::IsolationLevelDowngraded { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::TimestampOracleUnavailable => SqlState::WARNING,
             AdapterNotice::BadStartupSetting { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::RbacUserDisabled => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::RoleMembershipAlreadyExists { .. } => SqlState::SUCCESSFUL_COMPLETION,
@@ -296,13 +406,27 @@ impl AdapterNotice {
             AdapterNotice::DefaultClusterDoesNotExist { .. } => SqlState::from_code("MZ005"),
             AdapterNotice::OptimizerNotice { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::DroppedInUseIndex { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::DropObjectsProgress { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::SourcePurificationProgress { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::WebhookSourceCreated { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::PerReplicaLogRead { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::VarDefaultUpdated { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::Welcome(_) => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::PlanInsights(_) => SqlState::from_code("MZ001"),
+            AdapterNotice::SlowPathPeek { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::CardinalityBlowup { .. } => SqlState::WARNING,
+            AdapterNotice::SinkLagExceeded { .. } => SqlState::WARNING,
+            AdapterNotice::ScheduledClusterSuspension { .. } => SqlState::WARNING,
+            AdapterNotice::HydrationPausedAfterOomCrashLoop { .. } => SqlState::WARNING,
+            AdapterNotice::InsufficientReplicaCapacity { .. } => SqlState::WARNING,
+            AdapterNotice::ReplicaCapacityQueued { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::ComputeSinkWaiting { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::IntrospectionClusterUsage => SqlState::WARNING,
             AdapterNotice::AutoRouteIntrospectionQueriesUsage => SqlState::WARNING,
+            AdapterNotice::WriteDryRun => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::BuiltinTableMigrationPending { .. } => SqlState::WARNING,
+            AdapterNotice::BuiltinTableMigrationComplete { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::ExternalTaskQueued { .. } => SqlState::SUCCESSFUL_COMPLETION,
         }
     }
 }
@@ -375,6 +499,12 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::DroppedActiveCluster { name } => {
                 write!(f, "active cluster {} has been dropped", name.quoted())
             }
+            AdapterNotice::ClusterFailover { from, to } => write!(
+                f,
+                "cluster {} has no live hydrated replicas; running on cluster {} instead",
+                from.quoted(),
+                to.quoted()
+            ),
             AdapterNotice::QueryTimestamp { .. } => write!(f, "EXPLAIN TIMESTAMP for query"),
             AdapterNotice::EqualSubscribeBounds { bound } => {
                 write!(f, "subscribe as of {bound} (inclusive) up to the same bound {bound} (exclusive) is guaranteed to be empty")
@@ -382,6 +512,13 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::QueryTrace { trace_id } => {
                 write!(f, "trace id: {}", trace_id)
             }
+            AdapterNotice::StatementCorrelationId {
+                trace_id,
+                statement_id,
+            } => match trace_id {
+                Some(trace_id) => write!(f, "correlation id: {trace_id}:{statement_id}"),
+                None => write!(f, "correlation id: {statement_id}"),
+            },
             AdapterNotice::UnimplementedIsolationLevel { isolation_level } => {
                 write!(
                     f,
@@ -395,6 +532,22 @@ impl fmt::Display for AdapterNotice {
                     "The Strong Session Serializable isolation level may exhibit consistency violations when reading from catalog objects",
                 )
             }
+            AdapterNotice::IsolationLevelDowngraded {
+                isolation_level,
+                effective,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "query executed under isolation level {effective} instead of {isolation_level}: {reason}"
+                )
+            }
+            AdapterNotice::TimestampOracleUnavailable => {
+                write!(
+                    f,
+                    "timestamp oracle is unavailable; query executed at the locally known frontiers"
+                )
+            }
             AdapterNotice::BadStartupSetting { name, reason } => {
                 write!(f, "startup setting {name} not set: {reason}")
             }
@@ -454,6 +607,12 @@ impl fmt::Display for AdapterNotice {
             }) => {
                 write!(f, "The dropped index {index_name} is being used by the following objects: {}. The index is now dropped from the catalog, but it will continue to be maintained and take up resources until all dependent objects are dropped, altered, or Materialize is restarted!", separated(", ", dependant_objects))
             }
+            AdapterNotice::DropObjectsProgress { dropped, total } => {
+                write!(f, "dropped {dropped} of {total} objects")
+            }
+            AdapterNotice::SourcePurificationProgress { validated, total } => {
+                write!(f, "validated {validated}/{total} tables")
+            }
             AdapterNotice::PerReplicaLogRead { log_names } => {
                 write!(f, "Queried introspection relations: {}. Unlike other objects in Materialize, results from querying these objects depend on the current values of the `cluster` and `cluster_replica` session variables.", log_names.join(", "))
             }
@@ -473,6 +632,74 @@ impl fmt::Display for AdapterNotice {
             }
             AdapterNotice::Welcome(message) => message.fmt(f),
             AdapterNotice::PlanInsights(message) => message.fmt(f),
+            AdapterNotice::SlowPathPeek { reason } => write!(
+                f,
+                "query requires a dataflow instead of the fast path: {reason}"
+            ),
+            AdapterNotice::CardinalityBlowup {
+                object,
+                replica,
+                operator,
+                factor,
+            } => write!(
+                f,
+                "operator {operator} of {object} on replica {replica} produces more than {factor} times as many records as it receives"
+            ),
+            AdapterNotice::SinkLagExceeded {
+                sink,
+                lag,
+                threshold,
+            } => write!(
+                f,
+                "sink {sink} lags {}s behind its upstream relation, more than the threshold of {}s",
+                lag.as_secs(),
+                threshold.as_secs()
+            ),
+            AdapterNotice::ScheduledClusterSuspension {
+                cluster,
+                grace_period,
+            } => {
+                if grace_period.is_zero() {
+                    write!(f, "cluster {cluster} is being turned off by its schedule")
+                } else {
+                    write!(
+                        f,
+                        "cluster {cluster} will be turned off by its schedule in {}s",
+                        grace_period.as_secs()
+                    )
+                }
+            }
+            AdapterNotice::HydrationPausedAfterOomCrashLoop {
+                name,
+                cluster,
+                replica,
+            } => write!(
+                f,
+                "paused the hydration of {name}, as replica {cluster}.{replica} was repeatedly OOM-killed while hydrating it"
+            ),
+            AdapterNotice::InsufficientReplicaCapacity {
+                size,
+                schedulable,
+                required,
+            } => write!(
+                f,
+                "insufficient capacity for replicas of size {}: only {schedulable} of {required} processes can be scheduled",
+                size.quoted()
+            ),
+            AdapterNotice::ReplicaCapacityQueued {
+                size,
+                schedulable,
+                required,
+            } => write!(
+                f,
+                "waiting for capacity for replicas of size {}: only {schedulable} of {required} processes can be scheduled",
+                size.quoted()
+            ),
+            AdapterNotice::ComputeSinkWaiting { cluster } => write!(
+                f,
+                "cluster {} has reached its limit of concurrent compute sinks; waiting",
+                cluster.quoted()
+            ),
             AdapterNotice::IntrospectionClusterUsage => write!(
                 f,
                 "The mz_introspection cluster has been renamed to mz_catalog_server."
@@ -481,6 +708,23 @@ impl fmt::Display for AdapterNotice {
                 f,
                 "The auto_route_introspection_queries variable has been renamed to auto_route_catalog_queries."
             ),
+            AdapterNotice::WriteDryRun => write!(
+                f,
+                "dry run: the write was validated but not committed"
+            ),
+            AdapterNotice::BuiltinTableMigrationPending { name } => write!(
+                f,
+                "system table {} is still being migrated and may be out of date",
+                name.quoted()
+            ),
+            AdapterNotice::BuiltinTableMigrationComplete { name } => {
+                write!(f, "the migration of system table {} is complete", name.quoted())
+            }
+            AdapterNotice::ExternalTaskQueued { hosts } => write!(
+                f,
+                "too many statements are contacting {} at the same time; waiting",
+                separated(", ", hosts)
+            ),
         }
     }
 }
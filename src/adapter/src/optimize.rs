@@ -59,19 +59,25 @@ pub mod materialized_view;
 pub mod peek;
 pub mod subscribe;
 pub mod view;
+pub mod webhook_sink;
 
+use std::collections::BTreeMap;
 use std::panic::AssertUnwindSafe;
 
+use mz_catalog::memory::objects::CatalogItem;
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::plan::Plan;
 use mz_expr::{EvalError, MirRelationExpr, OptimizedMirRelationExpr, UnmaterializableFunc};
+use mz_ore::cast::CastFrom;
 use mz_ore::stack::RecursionLimitError;
 use mz_repr::adt::timestamp::TimestampError;
 use mz_repr::optimize::{OptimizerFeatureOverrides, OptimizerFeatures, OverrideFrom};
 use mz_repr::GlobalId;
 use mz_sql::plan::PlanError;
 use mz_sql::session::vars::SystemVars;
-use mz_transform::{TransformCtx, TransformError};
+use mz_transform::{StatisticsOracle, TransformCtx, TransformError};
+
+use crate::catalog::CatalogState;
 
 // Alias types
 // -----------
@@ -251,6 +257,64 @@ impl From<&OptimizerConfig> for mz_sql::plan::HirToMirConfig {
     }
 }
 
+// Statistics
+// ==========
+
+/// A [`StatisticsOracle`] that only knows the `ROW COUNT HINT`s of the tables
+/// that a dataflow reads.
+///
+/// Used for dataflows whose optimization does not wait for statistics, which
+/// the hints would otherwise only be combined with.
+#[derive(Debug, Default)]
+pub struct RowCountHintOracle {
+    hints: BTreeMap<GlobalId, usize>,
+}
+
+impl RowCountHintOracle {
+    /// Collects the hints of the tables that `df_desc` imports directly or
+    /// through their indexes.
+    pub fn for_dataflow(catalog: &CatalogState, df_desc: &MirDataflowDescription) -> Self {
+        let ids = df_desc.source_imports.keys().copied().chain(
+            df_desc
+                .index_imports
+                .values()
+                .map(|import| import.desc.on_id),
+        );
+        RowCountHintOracle {
+            hints: row_count_hints(catalog, ids),
+        }
+    }
+}
+
+impl StatisticsOracle for RowCountHintOracle {
+    fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
+        self.hints.get(&id).copied()
+    }
+
+    fn as_map(&self) -> BTreeMap<GlobalId, usize> {
+        self.hints.clone()
+    }
+
+    fn row_count_hints(&self) -> BTreeMap<GlobalId, usize> {
+        self.hints.clone()
+    }
+}
+
+/// Returns the `ROW COUNT HINT`s of the tables among `ids`.
+pub fn row_count_hints(
+    catalog: &CatalogState,
+    ids: impl IntoIterator<Item = GlobalId>,
+) -> BTreeMap<GlobalId, usize> {
+    ids.into_iter()
+        .filter_map(|id| match catalog.try_get_entry(&id)?.item() {
+            CatalogItem::Table(table) => table
+                .row_count_hint
+                .map(|hint| (id, usize::cast_from(hint))),
+            _ => None,
+        })
+        .collect()
+}
+
 // OptimizerError
 // ===============
 
@@ -342,4 +406,10 @@ macro_rules! trace_plan {
 
 use trace_plan;
 
+/// Traces the effective optimizer feature flags under `optimize/features`, so
+/// that plan differences between environments can be attributed to them.
+fn trace_features(features: &OptimizerFeatures) {
+    trace_plan!(at: "features", &features.to_string());
+}
+
 use crate::coord::ExplainContext;
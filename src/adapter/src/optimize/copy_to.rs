@@ -40,8 +40,8 @@ use crate::optimize::dataflows::{
     ExprPrepStyle,
 };
 use crate::optimize::{
-    optimize_mir_local, trace_plan, LirDataflowDescription, MirDataflowDescription, Optimize,
-    OptimizeMode, OptimizerConfig, OptimizerError,
+    optimize_mir_local, trace_features, trace_plan, LirDataflowDescription, MirDataflowDescription,
+    Optimize, OptimizeMode, OptimizerConfig, OptimizerError,
 };
 use crate::TimestampContext;
 
@@ -152,6 +152,8 @@ impl Optimize<HirRelationExpr> for Optimizer {
     fn optimize(&mut self, expr: HirRelationExpr) -> Result<Self::To, OptimizerError> {
         let time = Instant::now();
 
+        trace_features(&self.config.features);
+
         // Trace the pipeline input under `optimize/raw`.
         trace_plan!(at: "raw", &expr);
 
@@ -102,6 +102,9 @@ pub struct DataflowBuilder<'a> {
     ///
     /// Bound from [`OptimizerConfig::replan`].
     pub replan: Option<GlobalId>,
+    /// The partial indexes that this builder offers, because they were imported by
+    /// [`DataflowBuilder::import_implied_partial_indexes`]. Other partial indexes are ignored.
+    pub partial_indexes: BTreeSet<GlobalId>,
     /// A guard for recursive operations in this [`DataflowBuilder`] instance.
     recursion_guard: RecursionGuard,
 }
@@ -155,6 +158,7 @@ impl<'a> DataflowBuilder<'a> {
             catalog,
             compute,
             replan: None,
+            partial_indexes: BTreeSet::new(),
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
@@ -233,6 +237,95 @@ impl<'a> DataflowBuilder<'a> {
         })
     }
 
+    /// Imports the partial indexes that can stand in for the collections that `dataflow` reads,
+    /// and offers them to the optimizer. Returns the IDs of the imported partial indexes.
+    ///
+    /// A partial index only contains the rows of its collection that satisfy its predicate, so it
+    /// can only stand in for the collection if `dataflow` reads no other rows of it: every `Get`
+    /// of the collection must be the input of a `Filter` whose predicates include each conjunct
+    /// of the predicate of the index. Collections that are provided by other indexes are left
+    /// alone.
+    pub fn import_implied_partial_indexes(
+        &mut self,
+        dataflow: &mut DataflowDesc,
+        features: &OptimizerFeatures,
+    ) -> Result<BTreeSet<GlobalId>, OptimizerError> {
+        // The predicates of the filters on the reads of each collection, or `None` for the reads
+        // that aren't filtered.
+        let mut reads: BTreeMap<GlobalId, Vec<Option<Vec<MirScalarExpr>>>> = BTreeMap::new();
+        for build in &dataflow.objects_to_build {
+            // The `Get`s that are the inputs of filters, which are visited after the filters.
+            let mut filtered_gets = BTreeSet::new();
+            build.plan.visit_pre(&mut |expr| match expr {
+                MirRelationExpr::Filter { input, predicates } => {
+                    if let MirRelationExpr::Get {
+                        id: Id::Global(id), ..
+                    } = &**input
+                    {
+                        reads.entry(*id).or_default().push(Some(predicates.clone()));
+                        filtered_gets.insert(&**input as *const MirRelationExpr);
+                    }
+                }
+                MirRelationExpr::Get {
+                    id: Id::Global(id), ..
+                } if !filtered_gets.contains(&(expr as *const MirRelationExpr)) => {
+                    reads.entry(*id).or_default().push(None);
+                }
+                _ => {}
+            })?;
+        }
+
+        let mut imported = BTreeSet::new();
+        for (id, filters) in reads {
+            let Some(filters) = filters.into_iter().collect::<Option<Vec<_>>>() else {
+                continue;
+            };
+            if dataflow
+                .index_imports
+                .values()
+                .any(|import| import.desc.on_id == id)
+            {
+                continue;
+            }
+            let Some(entry) = self.catalog.try_get_entry(&id) else {
+                continue;
+            };
+            let Ok(desc) = entry.desc(
+                &self
+                    .catalog
+                    .resolve_full_name(entry.name(), entry.conn_id()),
+            ) else {
+                continue;
+            };
+            let typ = desc.typ().clone();
+            let implied = self.partial_indexes_on(id).find(|(_, idx)| {
+                let mut conjuncts = idx.predicate.iter().cloned().collect();
+                mz_expr::canonicalize::canonicalize_predicates(&mut conjuncts, &typ.column_types);
+                filters.iter().all(|predicates| {
+                    conjuncts
+                        .iter()
+                        .all(|conjunct| predicates.contains(conjunct))
+                })
+            });
+            let Some((index_id, index)) = implied else {
+                continue;
+            };
+            let index_desc = IndexDesc {
+                on_id: id,
+                key: index.keys.clone(),
+            };
+
+            // The index stands in for the collection, which needs not be imported or built.
+            dataflow.source_imports.remove(&id);
+            dataflow.objects_to_build.retain(|build| build.id != id);
+            let monotonic = self.monotonic_object(id, features);
+            dataflow.import_index(index_id, index_desc, typ, monotonic);
+            self.partial_indexes.insert(index_id);
+            imported.insert(index_id);
+        }
+        Ok(imported)
+    }
+
     /// Imports the view with the specified ID and expression into the provided
     /// dataflow description. [`OptimizerFeatures`] is used while running
     /// expression [`mz_transform::analysis::Analysis`].
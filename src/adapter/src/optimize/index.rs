@@ -38,15 +38,15 @@ use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::normalize_lets::normalize_lets;
 use mz_transform::notice::{IndexAlreadyExists, IndexKeyEmpty};
 use mz_transform::typecheck::{empty_context, SharedContext as TypecheckContext};
-use mz_transform::TransformCtx;
+use mz_transform::{StatisticsOracle, TransformCtx};
 
 use crate::catalog::Catalog;
 use crate::optimize::dataflows::{
     prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot, DataflowBuilder, ExprPrepStyle,
 };
 use crate::optimize::{
-    trace_plan, LirDataflowDescription, MirDataflowDescription, Optimize, OptimizeMode,
-    OptimizerConfig, OptimizerError,
+    trace_features, trace_plan, LirDataflowDescription, MirDataflowDescription, Optimize,
+    OptimizeMode, OptimizerConfig, OptimizerError, RowCountHintOracle,
 };
 
 pub struct Optimizer {
@@ -91,6 +91,7 @@ pub struct Index {
     name: QualifiedItemName,
     on: GlobalId,
     keys: Vec<mz_expr::MirScalarExpr>,
+    predicate: Option<mz_expr::MirScalarExpr>,
 }
 
 impl Index {
@@ -98,11 +99,13 @@ impl Index {
         name: &QualifiedItemName,
         on: &GlobalId,
         keys: &Vec<mz_expr::MirScalarExpr>,
+        predicate: Option<&mz_expr::MirScalarExpr>,
     ) -> Self {
         Self {
             name: name.clone(),
             on: on.clone(),
             keys: keys.clone(),
+            predicate: predicate.cloned(),
         }
     }
 }
@@ -148,6 +151,8 @@ impl Optimize<Index> for Optimizer {
     fn optimize(&mut self, index: Index) -> Result<Self::To, OptimizerError> {
         let time = Instant::now();
 
+        trace_features(&self.config.features);
+
         let state = self.catalog.state();
         let on_entry = state.get_entry(&index.on);
         let full_name = state.resolve_full_name(&index.name, on_entry.conn_id());
@@ -169,7 +174,16 @@ impl Optimize<Index> for Optimizer {
             on_id: index.on,
             key: index.keys.clone(),
         };
-        df_desc.export_index(self.exported_index_id, index_desc, on_desc.typ().clone());
+        match &index.predicate {
+            // A partial index only arranges the rows of `on` that satisfy its predicate.
+            Some(predicate) => df_desc.export_filtered_index(
+                self.exported_index_id,
+                index_desc,
+                on_desc.typ().clone(),
+                predicate.clone(),
+            ),
+            None => df_desc.export_index(self.exported_index_id, index_desc, on_desc.typ().clone()),
+        }
 
         // Prepare expressions in the assembled dataflow.
         let style = ExprPrepStyle::Index;
@@ -179,16 +193,18 @@ impl Optimize<Index> for Optimizer {
         )?;
 
         // Construct TransformCtx for global optimization.
+        let stats = RowCountHintOracle::for_dataflow(self.catalog.state(), &df_desc);
         let mut df_meta = DataflowMetainfo::default();
         let mut transform_ctx = TransformCtx::global(
             &df_builder,
-            &mz_transform::EmptyStatisticsOracle, // TODO: wire proper stats
+            &stats, // TODO: wire proper stats besides the hints
             &self.config.features,
             &self.typecheck_ctx,
             &mut df_meta,
         );
         // Run global optimization.
         mz_transform::optimize_dataflow(&mut df_desc, &mut transform_ctx)?;
+        df_meta.row_count_hints = stats.row_count_hints();
 
         if self.config.mode == OptimizeMode::Explain {
             // Collect the list of indexes used by the dataflow at this point.
@@ -201,10 +217,11 @@ impl Optimize<Index> for Optimizer {
         }
 
         // Emit a notice for each available index identical to the one we are
-        // currently optimizing.
+        // currently optimizing. Partial indexes contain fewer rows than the
+        // indexes on the same keys.
         for (index_id, idx) in df_builder
             .indexes_on(index.on)
-            .filter(|(_id, idx)| idx.keys == index.keys)
+            .filter(|(_id, idx)| idx.keys == index.keys && index.predicate.is_none())
         {
             df_meta.push_optimizer_notice_dedup(IndexAlreadyExists {
                 index_id,
@@ -39,7 +39,7 @@ use mz_sql::plan::HirRelationExpr;
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::normalize_lets::normalize_lets;
 use mz_transform::typecheck::{empty_context, SharedContext as TypecheckContext};
-use mz_transform::TransformCtx;
+use mz_transform::{StatisticsOracle, TransformCtx};
 use timely::progress::Antichain;
 
 use crate::catalog::Catalog;
@@ -47,8 +47,8 @@ use crate::optimize::dataflows::{
     prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot, DataflowBuilder, ExprPrepStyle,
 };
 use crate::optimize::{
-    optimize_mir_local, trace_plan, LirDataflowDescription, MirDataflowDescription, Optimize,
-    OptimizeMode, OptimizerConfig, OptimizerError,
+    optimize_mir_local, trace_features, trace_plan, LirDataflowDescription, MirDataflowDescription,
+    Optimize, OptimizeMode, OptimizerConfig, OptimizerError, RowCountHintOracle,
 };
 
 pub struct Optimizer {
@@ -164,6 +164,8 @@ impl Optimize<HirRelationExpr> for Optimizer {
     fn optimize(&mut self, expr: HirRelationExpr) -> Result<Self::To, OptimizerError> {
         let time = Instant::now();
 
+        trace_features(&self.config.features);
+
         // Trace the pipeline input under `optimize/raw`.
         trace_plan!(at: "raw", &expr);
 
@@ -195,6 +197,8 @@ impl Optimize<OptimizedMirRelationExpr> for Optimizer {
     type To = GlobalMirPlan;
 
     fn optimize(&mut self, expr: OptimizedMirRelationExpr) -> Result<Self::To, OptimizerError> {
+        trace_features(&self.config.features);
+
         let expr = expr.into_inner();
         let df_meta = DataflowMetainfo::default();
         self.optimize(LocalMirPlan { expr, df_meta })
@@ -255,15 +259,17 @@ impl Optimize<LocalMirPlan> for Optimizer {
         )?;
 
         // Construct TransformCtx for global optimization.
+        let stats = RowCountHintOracle::for_dataflow(self.catalog.state(), &df_desc);
         let mut transform_ctx = TransformCtx::global(
             &df_builder,
-            &mz_transform::EmptyStatisticsOracle, // TODO: wire proper stats
+            &stats, // TODO: wire proper stats besides the hints
             &self.config.features,
             &self.typecheck_ctx,
             &mut df_meta,
         );
         // Run global optimization.
         mz_transform::optimize_dataflow(&mut df_desc, &mut transform_ctx)?;
+        df_meta.row_count_hints = stats.row_count_hints();
 
         if self.config.mode == OptimizeMode::Explain {
             // Collect the list of indexes used by the dataflow at this point.
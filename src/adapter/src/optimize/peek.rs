@@ -30,13 +30,13 @@ use timely::progress::Antichain;
 use tracing::{debug_span, warn};
 
 use crate::catalog::Catalog;
-use crate::coord::peek::{create_fast_path_plan, PeekDataflowPlan, PeekPlan};
+use crate::coord::peek::{create_fast_path_plan, PeekDataflowPlan, PeekPlan, SlowPathReason};
 use crate::optimize::dataflows::{
     prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot, DataflowBuilder, EvalTime,
     ExprPrepStyle,
 };
 use crate::optimize::{
-    optimize_mir_local, trace_plan, MirDataflowDescription, Optimize, OptimizeMode,
+    optimize_mir_local, trace_features, trace_plan, MirDataflowDescription, Optimize, OptimizeMode,
     OptimizerConfig, OptimizerError,
 };
 use crate::TimestampContext;
@@ -164,6 +164,7 @@ pub struct GlobalLirPlan {
     peek_plan: PeekPlan,
     df_meta: DataflowMetainfo,
     typ: RelationType,
+    slow_path_reason: Option<SlowPathReason>,
 }
 
 impl Optimize<HirRelationExpr> for Optimizer {
@@ -172,6 +173,8 @@ impl Optimize<HirRelationExpr> for Optimizer {
     fn optimize(&mut self, expr: HirRelationExpr) -> Result<Self::To, OptimizerError> {
         let time = Instant::now();
 
+        trace_features(&self.config.features);
+
         // Trace the pipeline input under `optimize/raw`.
         trace_plan!(at: "raw", &expr);
 
@@ -262,6 +265,8 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
             &self.config.features,
         )?;
         df_builder.maybe_reoptimize_imported_views(&mut df_desc, &self.config)?;
+        let partial_indexes =
+            df_builder.import_implied_partial_indexes(&mut df_desc, &self.config.features)?;
 
         // Resolve all unmaterializable function calls except mz_now(), because
         // we don't yet have a timestamp.
@@ -317,10 +322,13 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
         );
         // Run global optimization.
         mz_transform::optimize_dataflow(&mut df_desc, &mut transform_ctx)?;
+        df_meta.row_count_hints = stats.row_count_hints();
 
         if self.config.mode == OptimizeMode::Explain {
             // Collect the list of indexes used by the dataflow at this point.
-            trace_plan!(at: "global", &df_meta.used_indexes(&df_desc));
+            let mut used_indexes = df_meta.used_indexes(&df_desc);
+            used_indexes.mark_partial(&partial_indexes);
+            trace_plan!(at: "global", &used_indexes);
         }
 
         // Get the single timestamp representing the `as_of` time.
@@ -351,13 +359,14 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
         //     .map(|(_key, (_desc, typ))| typ.clone())
         //     .expect("GlobalMirPlan type");
 
+        let mut slow_path_reason = None;
         let peek_plan = match create_fast_path_plan(
             &mut df_desc,
             self.select_id,
             Some(&self.finishing),
             self.config.features.persist_fast_path_limit,
         )? {
-            Some(plan) if !self.config.no_fast_path => {
+            Ok(plan) if !self.config.no_fast_path => {
                 if self.config.mode == OptimizeMode::Explain {
                     // Trace the `used_indexes` for the FastPathPlan.
                     debug_span!(target: "optimizer", "fast_path").in_scope(|| {
@@ -367,7 +376,9 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
                         } else {
                             None
                         };
-                        trace_plan(&plan.used_indexes(finishing));
+                        let mut used_indexes = plan.used_indexes(finishing);
+                        used_indexes.mark_partial(&partial_indexes);
+                        trace_plan(&used_indexes);
                     });
                 }
                 // Trace the FastPathPlan.
@@ -379,7 +390,9 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
                 // Build the PeekPlan
                 PeekPlan::FastPath(plan)
             }
-            _ => {
+            fast_path => {
+                slow_path_reason = fast_path.err().flatten();
+
                 // Ensure all expressions are normalized before finalizing.
                 for build in df_desc.objects_to_build.iter_mut() {
                     normalize_lets(&mut build.plan.0, &self.config.features)?
@@ -410,11 +423,17 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
             peek_plan,
             df_meta,
             typ,
+            slow_path_reason,
         })
     }
 }
 
 impl GlobalLirPlan {
+    /// Returns why the plan narrowly missed the fast path, if it did.
+    pub fn slow_path_reason(&self) -> Option<&SlowPathReason> {
+        self.slow_path_reason.as_ref()
+    }
+
     /// Unwraps the parts of the final result of the optimization pipeline.
     pub fn unapply(self) -> (PeekPlan, DataflowMetainfo, RelationType) {
         (self.peek_plan, self.df_meta, self.typ)
@@ -20,13 +20,14 @@ use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc, SubscribeS
 use mz_compute_types::ComputeInstanceId;
 use mz_ore::collections::CollectionExt;
 use mz_ore::soft_assert_or_log;
+use mz_repr::explain::trace_plan;
 use mz_repr::{GlobalId, RelationDesc, Timestamp};
 use mz_sql::optimizer_metrics::OptimizerMetrics;
 use mz_sql::plan::SubscribeFrom;
 use mz_transform::dataflow::DataflowMetainfo;
 use mz_transform::normalize_lets::normalize_lets;
 use mz_transform::typecheck::{empty_context, SharedContext as TypecheckContext};
-use mz_transform::TransformCtx;
+use mz_transform::{StatisticsOracle, TransformCtx};
 use timely::progress::Antichain;
 
 use crate::catalog::Catalog;
@@ -35,8 +36,8 @@ use crate::optimize::dataflows::{
     DataflowBuilder, ExprPrepStyle,
 };
 use crate::optimize::{
-    optimize_mir_local, trace_plan, LirDataflowDescription, MirDataflowDescription, Optimize,
-    OptimizeMode, OptimizerConfig, OptimizerError,
+    optimize_mir_local, trace_features, trace_plan, LirDataflowDescription, MirDataflowDescription,
+    Optimize, OptimizeMode, OptimizerConfig, OptimizerError, RowCountHintOracle,
 };
 use crate::CollectionIdBundle;
 
@@ -114,6 +115,10 @@ impl Optimizer {
         self.compute_instance.instance_id()
     }
 
+    pub fn sink_id(&self) -> GlobalId {
+        self.sink_id
+    }
+
     pub fn up_to(&self) -> Option<Timestamp> {
         self.up_to.clone()
     }
@@ -183,6 +188,8 @@ impl Optimize<SubscribeFrom> for Optimizer {
     fn optimize(&mut self, plan: SubscribeFrom) -> Result<Self::To, OptimizerError> {
         let time = Instant::now();
 
+        trace_features(&self.config.features);
+
         let mut df_builder = {
             let catalog = self.catalog.state();
             let compute = self.compute_instance.clone();
@@ -265,15 +272,17 @@ impl Optimize<SubscribeFrom> for Optimizer {
         )?;
 
         // Construct TransformCtx for global optimization.
+        let stats = RowCountHintOracle::for_dataflow(self.catalog.state(), &df_desc);
         let mut transform_ctx = TransformCtx::global(
             &df_builder,
-            &mz_transform::EmptyStatisticsOracle, // TODO: wire proper stats
+            &stats, // TODO: wire proper stats besides the hints
             &self.config.features,
             &self.typecheck_ctx,
             &mut df_meta,
         );
         // Run global optimization.
         mz_transform::optimize_dataflow(&mut df_desc, &mut transform_ctx)?;
+        df_meta.row_count_hints = stats.row_count_hints();
 
         if self.config.mode == OptimizeMode::Explain {
             // Collect the list of indexes used by the dataflow at this point.
@@ -346,6 +355,9 @@ impl Optimize<GlobalMirPlan<Resolved>> for Optimizer {
         // - LIR ⇒ LIR transforms
         let df_desc = Plan::finalize_dataflow(df_desc, &self.config.features)?;
 
+        // Trace the pipeline output under `optimize`.
+        trace_plan(&df_desc);
+
         self.duration += time.elapsed();
         self.metrics
             .observe_e2e_optimization_time("subscribe", self.duration);
@@ -0,0 +1,232 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Optimizer implementation for webhook sinks.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use mz_compute_types::plan::Plan;
+use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc};
+use mz_compute_types::ComputeInstanceId;
+use mz_repr::{GlobalId, Timestamp};
+use mz_sql::optimizer_metrics::OptimizerMetrics;
+use mz_storage_types::sinks::WebhookSinkConnection;
+use mz_transform::dataflow::DataflowMetainfo;
+use mz_transform::normalize_lets::normalize_lets;
+use mz_transform::typecheck::{empty_context, SharedContext as TypecheckContext};
+use mz_transform::{StatisticsOracle, TransformCtx};
+use timely::progress::Antichain;
+
+use crate::catalog::Catalog;
+use crate::optimize::dataflows::{
+    dataflow_import_id_bundle, prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot,
+    DataflowBuilder, ExprPrepStyle,
+};
+use crate::optimize::{
+    trace_features, LirDataflowDescription, MirDataflowDescription, Optimize, OptimizerConfig,
+    OptimizerError, RowCountHintOracle,
+};
+use crate::CollectionIdBundle;
+
+pub struct Optimizer {
+    /// A typechecking context to use throughout the optimizer pipeline.
+    typecheck_ctx: TypecheckContext,
+    /// A snapshot of the catalog state.
+    catalog: Arc<Catalog>,
+    /// A snapshot of the cluster that will run the dataflows.
+    compute_instance: ComputeInstanceSnapshot,
+    /// The ID of the sink.
+    sink_id: GlobalId,
+    /// The receiver of the sink's updates.
+    connection: WebhookSinkConnection,
+    /// Should the plan produce an initial snapshot?
+    with_snapshot: bool,
+    /// A human-readable name exposed internally (useful for debugging).
+    debug_name: String,
+    /// Optimizer config.
+    config: OptimizerConfig,
+    /// Optimizer metrics.
+    metrics: OptimizerMetrics,
+    /// The time spent performing optimization so far.
+    duration: Duration,
+}
+
+impl std::fmt::Debug for Optimizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Optimizer")
+            .field("sink_id", &self.sink_id)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Optimizer {
+    pub fn new(
+        catalog: Arc<Catalog>,
+        compute_instance: ComputeInstanceSnapshot,
+        sink_id: GlobalId,
+        connection: WebhookSinkConnection,
+        with_snapshot: bool,
+        debug_name: String,
+        config: OptimizerConfig,
+        metrics: OptimizerMetrics,
+    ) -> Self {
+        Self {
+            typecheck_ctx: empty_context(),
+            catalog,
+            compute_instance,
+            sink_id,
+            connection,
+            with_snapshot,
+            debug_name,
+            config,
+            metrics,
+            duration: Default::default(),
+        }
+    }
+
+    pub fn cluster_id(&self) -> ComputeInstanceId {
+        self.compute_instance.instance_id()
+    }
+}
+
+/// The (sealed intermediate) result after:
+///
+/// 1. embedding the relation a webhook sink exports into a
+///    [`MirDataflowDescription`],
+/// 2. transitively inlining referenced views, and
+/// 3. jointly optimizing the `MIR` plans in the [`MirDataflowDescription`].
+#[derive(Clone, Debug)]
+pub struct GlobalMirPlan {
+    df_desc: MirDataflowDescription,
+    df_meta: DataflowMetainfo,
+}
+
+impl GlobalMirPlan {
+    /// Computes the [`CollectionIdBundle`] of the wrapped dataflow.
+    pub fn id_bundle(&self, compute_instance_id: ComputeInstanceId) -> CollectionIdBundle {
+        dataflow_import_id_bundle(&self.df_desc, compute_instance_id)
+    }
+
+    /// Sets the `as_of` from which the sink starts delivering updates.
+    pub fn set_as_of(&mut self, as_of: Antichain<Timestamp>) {
+        self.df_desc.set_as_of(as_of);
+    }
+}
+
+/// The (final) result after MIR ⇒ LIR lowering and optimizing the resulting
+/// `DataflowDescription` with `LIR` plans.
+#[derive(Clone, Debug)]
+pub struct GlobalLirPlan {
+    df_desc: LirDataflowDescription,
+    df_meta: DataflowMetainfo,
+}
+
+impl Optimize<GlobalId> for Optimizer {
+    type To = GlobalMirPlan;
+
+    fn optimize(&mut self, from_id: GlobalId) -> Result<Self::To, OptimizerError> {
+        let time = Instant::now();
+
+        trace_features(&self.config.features);
+
+        let mut df_builder = {
+            let catalog = self.catalog.state();
+            let compute = self.compute_instance.clone();
+            DataflowBuilder::new(catalog, compute).with_config(&self.config)
+        };
+        let mut df_desc = MirDataflowDescription::new(self.debug_name.clone());
+        let mut df_meta = DataflowMetainfo::default();
+
+        let from = self.catalog.get_entry(&from_id);
+        let from_desc = from
+            .desc(&self.catalog.state().resolve_full_name(from.name(), None))
+            .expect("sinks can only be created on items with descs")
+            .into_owned();
+
+        df_builder.import_into_dataflow(&from_id, &mut df_desc, &self.config.features)?;
+        df_builder.maybe_reoptimize_imported_views(&mut df_desc, &self.config)?;
+
+        let sink_description = ComputeSinkDesc {
+            from: from_id,
+            from_desc,
+            connection: ComputeSinkConnection::Webhook(self.connection.clone()),
+            with_snapshot: self.with_snapshot,
+            up_to: Antichain::default(),
+            // No `FORCE NOT NULL` for webhook sinks
+            non_null_assertions: vec![],
+            // No `REFRESH` for webhook sinks
+            refresh_schedule: None,
+        };
+        df_desc.export_sink(self.sink_id, sink_description);
+
+        // Prepare expressions in the assembled dataflow.
+        let style = ExprPrepStyle::Index;
+        df_desc.visit_children(
+            |r| prep_relation_expr(r, style),
+            |s| prep_scalar_expr(s, style),
+        )?;
+
+        // Construct TransformCtx for global optimization.
+        let stats = RowCountHintOracle::for_dataflow(self.catalog.state(), &df_desc);
+        let mut transform_ctx = TransformCtx::global(
+            &df_builder,
+            &stats, // TODO: wire proper stats besides the hints
+            &self.config.features,
+            &self.typecheck_ctx,
+            &mut df_meta,
+        );
+        // Run global optimization.
+        mz_transform::optimize_dataflow(&mut df_desc, &mut transform_ctx)?;
+        df_meta.row_count_hints = stats.row_count_hints();
+
+        self.duration += time.elapsed();
+
+        // Return the (sealed) plan at the end of this optimization step.
+        Ok(GlobalMirPlan { df_desc, df_meta })
+    }
+}
+
+impl Optimize<GlobalMirPlan> for Optimizer {
+    type To = GlobalLirPlan;
+
+    fn optimize(&mut self, plan: GlobalMirPlan) -> Result<Self::To, OptimizerError> {
+        let time = Instant::now();
+
+        let GlobalMirPlan {
+            mut df_desc,
+            df_meta,
+        } = plan;
+
+        // Ensure all expressions are normalized before finalizing.
+        for build in df_desc.objects_to_build.iter_mut() {
+            normalize_lets(&mut build.plan.0, &self.config.features)?
+        }
+
+        // Finalize the dataflow. This includes:
+        // - MIR ⇒ LIR lowering
+        // - LIR ⇒ LIR transforms
+        let df_desc = Plan::finalize_dataflow(df_desc, &self.config.features)?;
+
+        self.duration += time.elapsed();
+        self.metrics
+            .observe_e2e_optimization_time("webhook_sink", self.duration);
+
+        // Return the plan at the end of this `optimize` step.
+        Ok(GlobalLirPlan { df_desc, df_meta })
+    }
+}
+
+impl GlobalLirPlan {
+    /// Unwraps the parts of the final result of the optimization pipeline.
+    pub fn unapply(self) -> (LirDataflowDescription, DataflowMetainfo) {
+        (self.df_desc, self.df_meta)
+    }
+}
@@ -29,7 +29,7 @@ use mz_repr::role_id::RoleId;
 use mz_repr::user::ExternalUserMetadata;
 use mz_repr::{Datum, Diff, GlobalId, Row, RowIterator, ScalarType, TimestampManipulation};
 use mz_sql::ast::{AstInfo, Raw, Statement, TransactionAccessMode};
-use mz_sql::plan::{Params, PlanContext, QueryWhen, StatementDesc};
+use mz_sql::plan::{ConsistencyToken, Params, PlanContext, QueryWhen, StatementDesc};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::{
     RoleMetadata, User, INTERNAL_USER_NAME_TO_DEFAULT_CLUSTER, SYSTEM_USER,
@@ -42,6 +42,7 @@ use mz_sql::session::vars::{IsolationLevel, VarInput};
 use mz_sql_parser::ast::TransactionIsolationLevel;
 use mz_storage_types::sources::Timeline;
 use qcell::{QCell, QCellOwner};
+use rand::rngs::OsRng;
 use rand::Rng;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use tokio::sync::watch;
@@ -93,6 +94,9 @@ where
     vars: SessionVars,
     notices_tx: mpsc::UnboundedSender<AdapterNotice>,
     notices_rx: mpsc::UnboundedReceiver<AdapterNotice>,
+    /// A channel on which to send the client notices about the progress of the statement that
+    /// the session is executing, if the client is waiting for them.
+    progress_notices_tx: Option<mpsc::UnboundedSender<AdapterNotice>>,
     next_transaction_id: TransactionId,
     secret_key: u32,
     external_metadata_rx: Option<watch::Receiver<ExternalUserMetadata>>,
@@ -110,6 +114,9 @@ where
     #[derivative(Debug = "ignore")]
     qcell_owner: QCellOwner,
     session_oracles: BTreeMap<Timeline, InMemoryTimestampOracle<T, NowFn<T>>>,
+    /// The consistency token of the latest write of the session, if it hasn't been reported to
+    /// the client yet.
+    consistency_token: Option<ConsistencyToken>,
 }
 
 impl<T> SessionMetadata for Session<T>
@@ -296,11 +303,17 @@ impl<T: TimestampManipulation> Session<T> {
             vars,
             notices_tx,
             notices_rx,
+            progress_notices_tx: None,
             next_transaction_id: 0,
-            secret_key: rand::thread_rng().gen(),
+            // Drawn from the operating system's CSPRNG, so that the key of one
+            // connection can't be predicted from the keys of others. Pgwire
+            // only exchanges the key during startup, so every connection gets
+            // a fresh key but keeps it for its lifetime.
+            secret_key: OsRng.gen(),
             external_metadata_rx,
             qcell_owner: QCellOwner::new(),
             session_oracles: BTreeMap::new(),
+            consistency_token: None,
         }
     }
 
@@ -471,6 +484,31 @@ impl<T: TimestampManipulation> Session<T> {
         }
     }
 
+    /// Sets the channel on which to send notices about the progress of the statement that the
+    /// session is executing. Without one, progress notices are added to the session.
+    pub fn set_progress_notice_transmitter(
+        &mut self,
+        progress_notices_tx: Option<UnboundedSender<AdapterNotice>>,
+    ) {
+        self.progress_notices_tx = progress_notices_tx;
+    }
+
+    /// Adds a notice about the progress of the statement that the session is executing, which
+    /// is sent to the client right away if it is waiting for such notices.
+    pub fn add_progress_notice(&self, notice: AdapterNotice) {
+        let notice = match &self.progress_notices_tx {
+            Some(tx) => match self.notice_filter(notice) {
+                Some(notice) => match tx.send(notice) {
+                    Ok(()) => return,
+                    Err(mpsc::error::SendError(notice)) => notice,
+                },
+                None => return,
+            },
+            None => notice,
+        };
+        self.add_notice(notice);
+    }
+
     /// Awaits a possible notice.
     ///
     /// This method is cancel safe.
@@ -501,7 +539,7 @@ impl<T: TimestampManipulation> Session<T> {
     }
 
     /// Returns Some if the notice should be reported, otherwise None.
-    fn notice_filter(&mut self, notice: AdapterNotice) -> Option<AdapterNotice> {
+    fn notice_filter(&self, notice: AdapterNotice) -> Option<AdapterNotice> {
         // Filter out low threshold severity.
         let minimum_client_severity = self.vars.client_min_messages();
         let sev = notice.severity();
@@ -682,6 +720,7 @@ impl<T: TimestampManipulation> Session<T> {
                 result_formats: result_formats.into_iter().map(Into::into).collect(),
                 state: PortalState::NotStarted,
                 logging,
+                declared: false,
             },
         );
         Ok(())
@@ -694,6 +733,30 @@ impl<T: TimestampManipulation> Session<T> {
         self.portals.remove(portal_name).is_some()
     }
 
+    /// Removes the specified portal and returns it, if it exists.
+    pub(crate) fn take_portal(&mut self, portal_name: &str) -> Option<Portal> {
+        self.portals.remove(portal_name)
+    }
+
+    /// Installs a portal that was taken from another session.
+    ///
+    /// The portal's statement logging metadata is owned by the other session, so it is replaced
+    /// with fresh metadata owned by this one.
+    pub(crate) fn insert_portal(
+        &mut self,
+        portal_name: String,
+        mut portal: Portal,
+        sql: String,
+        now: EpochMillis,
+    ) -> Result<(), AdapterError> {
+        if self.portals.contains_key(&portal_name) {
+            return Err(AdapterError::DuplicateCursor(portal_name));
+        }
+        portal.logging = self.mint_logging(sql, portal.stmt.as_deref(), now);
+        self.portals.insert(portal_name, portal);
+        Ok(())
+    }
+
     /// Retrieves a reference to the specified portal.
     ///
     /// If there is no such portal, returns `None`.
@@ -733,6 +796,7 @@ impl<T: TimestampManipulation> Session<T> {
                         result_formats,
                         state: PortalState::NotStarted,
                         logging,
+                        declared: false,
                     });
                     return Ok(name);
                 }
@@ -841,10 +905,28 @@ impl<T: TimestampManipulation> Session<T> {
         }
     }
 
+    /// Records the consistency token of a write of the session, so that it can be reported to the
+    /// client along with the response to the write.
+    pub fn set_consistency_token(&mut self, token: ConsistencyToken) {
+        self.consistency_token = Some(token);
+    }
+
+    /// Returns the consistency token of the latest write of the session, if it hasn't been
+    /// reported to the client yet.
+    pub fn take_consistency_token(&mut self) -> Option<ConsistencyToken> {
+        self.consistency_token.take()
+    }
+
     /// Returns the [`SessionMetrics`] instance associated with this [`Session`].
     pub fn metrics(&self) -> &SessionMetrics {
         &self.metrics
     }
+
+    /// Returns a mutable reference to the [`SessionMetrics`] instance associated with this
+    /// [`Session`].
+    pub fn metrics_mut(&mut self) -> &mut SessionMetrics {
+        &mut self.metrics
+    }
 }
 
 /// A prepared statement.
@@ -897,6 +979,8 @@ pub struct Portal {
     /// The execution state of the portal.
     #[derivative(Debug = "ignore")]
     pub state: PortalState,
+    /// Whether the portal is a cursor created with `DECLARE`.
+    pub declared: bool,
 }
 
 /// Execution states of a portal.
@@ -996,6 +1080,14 @@ impl<T: TimestampManipulation> TransactionStatus<T> {
         }
     }
 
+    /// Returns the writes that the transaction has staged, but not yet committed.
+    pub fn staged_writes(&self) -> &[WriteOp] {
+        match self.inner().map(|txn| &txn.ops) {
+            Some(TransactionOps::Writes(writes)) => writes,
+            _ => &[],
+        }
+    }
+
     /// Whether the transaction's ops are DDL.
     pub fn is_ddl(&self) -> bool {
         match self {
@@ -7,11 +7,21 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::fmt::Debug;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
 use mz_controller_types::ClusterId;
 use mz_ore::cast::CastFrom;
+use mz_ore::future::OreFutureExt;
 use mz_ore::now::EpochMillis;
+use mz_ore::task;
 use mz_repr::GlobalId;
 use mz_sql_parser::ast::StatementKind;
+use tokio::sync::Semaphore;
+use tracing::warn;
 use uuid::Uuid;
 
 use crate::session::TransactionId;
@@ -58,6 +68,11 @@ pub struct StatementBeganExecutionRecord {
     pub transaction_id: TransactionId,
     pub transient_index_id: Option<GlobalId>,
     pub mz_version: String,
+    /// The isolation level that the statement was executed under, if it is weaker than
+    /// `transaction_isolation`.
+    pub effective_isolation: Option<String>,
+    /// Why the statement was executed under `effective_isolation`.
+    pub isolation_downgrade_reason: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -91,6 +106,9 @@ pub enum StatementEndedExecutionReason {
     Success {
         rows_returned: Option<u64>,
         execution_strategy: Option<StatementExecutionStrategy>,
+        /// The peak number of bytes that `environmentd` used to buffer the
+        /// results of the statement, if it buffered any.
+        max_result_memory_bytes: Option<u64>,
     },
     Canceled,
     Errored {
@@ -155,6 +173,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
                     StatementEndedExecutionReason::Success {
                         rows_returned: Some(u64::cast_from(rows.count())),
                         execution_strategy: Some(StatementExecutionStrategy::Constant),
+                        max_result_memory_bytes: None,
                     }
                 }
                 ExecuteResponse::SendingRows { .. } => {
@@ -171,7 +190,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             ExecuteResponse::Fetch { .. } => {
                 panic!("FETCHes terminate after a follow-up message is sent.")
             }
-            ExecuteResponse::SendingRows { .. } => {
+            ExecuteResponse::SendingRows { .. } | ExecuteResponse::SendingRowsStreaming { .. } => {
                 panic!("SELECTs terminate on peek finalization, not here.")
             }
             ExecuteResponse::Subscribing { .. } => {
@@ -182,6 +201,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
                 StatementEndedExecutionReason::Success {
                     rows_returned: Some(u64::cast_from(rows.count())),
                     execution_strategy: Some(StatementExecutionStrategy::Constant),
+                    max_result_memory_bytes: None,
                 }
             }
 
@@ -189,6 +209,8 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::AlteredObject(_)
             | ExecuteResponse::AlteredRole
             | ExecuteResponse::AlteredSystemConfiguration
+            | ExecuteResponse::CanceledQuery
+            | ExecuteResponse::ClaimedHandoff
             | ExecuteResponse::ClosedCursor
             | ExecuteResponse::Comment
             | ExecuteResponse::Copied(_)
@@ -233,8 +255,180 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
                 StatementEndedExecutionReason::Success {
                     rows_returned: None,
                     execution_strategy: None,
+                    max_result_memory_bytes: None,
                 }
             }
         }
     }
 }
+
+/// The metadata of a statement execution that is known when the execution
+/// begins, whether or not the execution is sampled for statement logging.
+#[derive(Clone, Debug)]
+pub struct StatementExecutionMetadata {
+    pub session_id: Uuid,
+    pub user: String,
+    pub application_name: String,
+    pub database_name: String,
+    pub began_at: EpochMillis,
+}
+
+/// The metadata and outcome of a statement execution, which is passed to
+/// [`StatementRetirementHook`]s once the execution is retired.
+#[derive(Clone, Debug)]
+pub struct StatementRetirement {
+    pub metadata: StatementExecutionMetadata,
+    /// The record of the beginning of the execution, if the execution was
+    /// sampled for statement logging.
+    pub began: Option<StatementBeganExecutionRecord>,
+    pub reason: StatementEndedExecutionReason,
+    pub ended_at: EpochMillis,
+}
+
+/// A hook that embedders of the adapter can install to run custom logic, like
+/// billing, caching, or replication, after every statement execution.
+pub trait StatementRetirementHook: Debug + Send + Sync + 'static {
+    /// The name of the hook, which is used in logs.
+    fn name(&self) -> &str;
+
+    /// Handles the retirement of a statement execution.
+    ///
+    /// The coordinator doesn't wait for the returned future, and ignores
+    /// hooks that fail, panic, or don't complete within
+    /// [`STATEMENT_RETIREMENT_HOOK_TIMEOUT`].
+    fn on_retire(
+        &self,
+        retirement: StatementRetirement,
+    ) -> BoxFuture<'static, Result<(), anyhow::Error>>;
+}
+
+/// How long a [`StatementRetirementHook`] may take to handle a retirement.
+pub const STATEMENT_RETIREMENT_HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many retirements a [`StatementRetirementHook`] may handle concurrently.
+/// Further retirements are dropped until the hook catches up.
+const STATEMENT_RETIREMENT_HOOK_MAX_IN_FLIGHT: usize = 1024;
+
+/// The [`StatementRetirementHook`]s that are installed in the adapter.
+#[derive(Clone, Debug, Default)]
+pub struct StatementRetirementHooks {
+    hooks: Vec<(Arc<dyn StatementRetirementHook>, Arc<Semaphore>)>,
+}
+
+impl StatementRetirementHooks {
+    /// Installs `hook`, which is invoked after every statement execution.
+    pub fn register(&mut self, hook: Arc<dyn StatementRetirementHook>) {
+        let in_flight = Arc::new(Semaphore::new(STATEMENT_RETIREMENT_HOOK_MAX_IN_FLIGHT));
+        self.hooks.push((hook, in_flight));
+    }
+
+    /// Reports whether no hooks are installed.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Invokes all hooks with `retirement`.
+    ///
+    /// Each invocation runs in its own task, so that hooks can't block the
+    /// caller or each other.
+    pub(crate) fn invoke(&self, retirement: StatementRetirement) {
+        for (hook, in_flight) in &self.hooks {
+            let Ok(permit) = Arc::clone(in_flight).try_acquire_owned() else {
+                warn!(
+                    hook = hook.name(),
+                    "dropping statement retirement: too many retirements in flight"
+                );
+                continue;
+            };
+            let hook = Arc::clone(hook);
+            let retirement = retirement.clone();
+            task::spawn(|| "statement_retirement_hook", async move {
+                let fut =
+                    AssertUnwindSafe(async { hook.on_retire(retirement).await }).ore_catch_unwind();
+                match tokio::time::timeout(STATEMENT_RETIREMENT_HOOK_TIMEOUT, fut).await {
+                    Ok(Ok(Ok(()))) => {}
+                    Ok(Ok(Err(e))) => {
+                        warn!(
+                            hook = hook.name(),
+                            "statement retirement hook failed: {e:#}"
+                        )
+                    }
+                    Ok(Err(_)) => warn!(hook = hook.name(), "statement retirement hook panicked"),
+                    Err(_) => warn!(hook = hook.name(), "statement retirement hook timed out"),
+                }
+                drop(permit);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct PanickingHook;
+
+    impl StatementRetirementHook for PanickingHook {
+        fn name(&self) -> &str {
+            "panicking"
+        }
+
+        fn on_retire(
+            &self,
+            _retirement: StatementRetirement,
+        ) -> BoxFuture<'static, Result<(), anyhow::Error>> {
+            async { panic!("hook panicked") }.boxed()
+        }
+    }
+
+    #[derive(Debug)]
+    struct ForwardingHook(mpsc::UnboundedSender<StatementRetirement>);
+
+    impl StatementRetirementHook for ForwardingHook {
+        fn name(&self) -> &str {
+            "forwarding"
+        }
+
+        fn on_retire(
+            &self,
+            retirement: StatementRetirement,
+        ) -> BoxFuture<'static, Result<(), anyhow::Error>> {
+            let result = self.0.send(retirement).map_err(anyhow::Error::from);
+            async { result }.boxed()
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_hooks_are_isolated() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut hooks = StatementRetirementHooks::default();
+        assert!(hooks.is_empty());
+        hooks.register(Arc::new(PanickingHook));
+        hooks.register(Arc::new(ForwardingHook(tx)));
+
+        for ended_at in [1, 2] {
+            hooks.invoke(StatementRetirement {
+                metadata: StatementExecutionMetadata {
+                    session_id: Uuid::nil(),
+                    user: "materialize".into(),
+                    application_name: "test".into(),
+                    database_name: "materialize".into(),
+                    began_at: 0,
+                },
+                began: None,
+                reason: StatementEndedExecutionReason::Canceled,
+                ended_at,
+            });
+        }
+        let mut ended_at = vec![
+            rx.recv().await.expect("hook invoked").ended_at,
+            rx.recv().await.expect("hook invoked").ended_at,
+        ];
+        ended_at.sort();
+        assert_eq!(ended_at, vec![1, 2]);
+    }
+}
@@ -9,6 +9,8 @@
 
 //! Telemetry utilities.
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use mz_audit_log::ObjectType;
 use mz_sql::catalog::EnvironmentId;
@@ -99,6 +101,108 @@ impl SegmentClientExt for mz_segment::Client {
     }
 }
 
+/// A Segment event buffered by a [`SegmentEventBatcher`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferedEvent {
+    /// The name of the event.
+    pub event: String,
+    /// The properties of the event.
+    pub properties: serde_json::Value,
+    /// The timestamp at which the event occurred.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// The events of a [`SegmentEventBatcher`] that are due to be sent.
+#[derive(Debug, Default, PartialEq)]
+pub struct FlushedEvents {
+    /// The events to send, in the order in which they were buffered.
+    pub events: Vec<BufferedEvent>,
+    /// The number of events that were dropped to stay within the rate limit.
+    pub rate_limited: usize,
+}
+
+/// Batches, deduplicates and rate limits frequent Segment events.
+///
+/// Events are buffered in order until the batch is flushed. An event is dropped if it has the
+/// same name and properties as the previous event with its key, whether that event is still
+/// buffered or was already sent, so that repeats are collapsed but every change is sent. Each
+/// flush sends at most a configured number of events and drops the remaining ones.
+#[derive(Debug)]
+pub struct SegmentEventBatcher<K> {
+    /// The buffered events, in the order in which they were buffered.
+    pending: Vec<(K, BufferedEvent)>,
+    /// The last buffered event of each key.
+    last_pending: BTreeMap<K, BufferedEvent>,
+    /// The last sent event of each key.
+    sent: BTreeMap<K, BufferedEvent>,
+}
+
+impl<K> Default for SegmentEventBatcher<K> {
+    fn default() -> Self {
+        SegmentEventBatcher {
+            pending: Vec::new(),
+            last_pending: BTreeMap::new(),
+            sent: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord + Clone> SegmentEventBatcher<K> {
+    /// Buffers `event` under `key`.
+    ///
+    /// Returns the number of events that were dropped as duplicates, which is one if `event`
+    /// repeats the previous event with the same key and zero otherwise.
+    pub fn push(&mut self, key: K, event: BufferedEvent) -> usize {
+        let previous = self.last_pending.get(&key).or_else(|| self.sent.get(&key));
+        let is_duplicate = previous.map_or(false, |previous| {
+            previous.event == event.event && previous.properties == event.properties
+        });
+        if is_duplicate {
+            return 1;
+        }
+        self.last_pending.insert(key.clone(), event.clone());
+        self.pending.push((key, event));
+        0
+    }
+
+    /// Reports whether there are no buffered events.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Takes the buffered events, keeping at most `limit` of them, or all of them if `limit`
+    /// is zero.
+    pub fn flush(&mut self, limit: usize) -> FlushedEvents {
+        let mut pending = std::mem::take(&mut self.pending);
+        self.last_pending.clear();
+        let rate_limited = if limit == 0 {
+            0
+        } else {
+            let rate_limited = pending.len().saturating_sub(limit);
+            pending.truncate(limit);
+            rate_limited
+        };
+        let events = pending
+            .into_iter()
+            .map(|(key, event)| {
+                self.sent.insert(key, event.clone());
+                event
+            })
+            .collect();
+        FlushedEvents {
+            events,
+            rate_limited,
+        }
+    }
+
+    /// Forgets the buffered and sent events of the keys for which `f` returns `false`.
+    pub fn retain<F: FnMut(&K) -> bool>(&mut self, mut f: F) {
+        self.pending.retain(|(key, _)| f(key));
+        self.last_pending.retain(|key, _| f(key));
+        self.sent.retain(|key, _| f(key));
+    }
+}
+
 /// Describes a way in which DDL statement execution can fail.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -170,3 +274,67 @@ pub fn analyze_audited_statement(
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(status: &str) -> BufferedEvent {
+        BufferedEvent {
+            event: "Cluster Changed Status".into(),
+            properties: json!({ "status": status }),
+            timestamp: None,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_segment_event_batcher() {
+        let mut batcher = SegmentEventBatcher::default();
+        assert!(batcher.is_empty());
+
+        // Every change of the status of a key is sent.
+        assert_eq!(batcher.push(1, event("not-ready")), 0);
+        assert_eq!(batcher.push(2, event("ready")), 0);
+        assert_eq!(batcher.push(1, event("ready")), 0);
+        assert_eq!(
+            batcher.flush(0),
+            FlushedEvents {
+                events: vec![event("not-ready"), event("ready"), event("ready")],
+                rate_limited: 0,
+            }
+        );
+        assert!(batcher.is_empty());
+
+        // Events that repeat the previous event of their key are collapsed, whether it was sent
+        // or is still buffered.
+        assert_eq!(batcher.push(1, event("ready")), 1);
+        assert!(batcher.is_empty());
+        assert_eq!(batcher.push(1, event("not-ready")), 0);
+        assert_eq!(batcher.push(1, event("not-ready")), 1);
+        assert_eq!(batcher.push(1, event("ready")), 0);
+        assert_eq!(
+            batcher.flush(0).events,
+            vec![event("not-ready"), event("ready")]
+        );
+
+        // Events beyond the limit are dropped.
+        assert_eq!(batcher.push(3, event("ready")), 0);
+        assert_eq!(batcher.push(2, event("not-ready")), 0);
+        assert_eq!(batcher.push(1, event("not-ready")), 0);
+        assert_eq!(
+            batcher.flush(2),
+            FlushedEvents {
+                events: vec![event("ready"), event("not-ready")],
+                rate_limited: 1,
+            }
+        );
+        // Dropped events are not considered sent.
+        assert_eq!(batcher.push(1, event("not-ready")), 0);
+        assert_eq!(batcher.flush(0).events, vec![event("not-ready")]);
+
+        // Forgotten keys are sent again.
+        batcher.retain(|key| *key != 3);
+        assert_eq!(batcher.push(3, event("ready")), 0);
+        assert_eq!(batcher.flush(0).events, vec![event("ready")]);
+    }
+}
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
 use mz_compute_client::controller::error::{
@@ -16,7 +17,7 @@ use mz_compute_client::controller::error::{
 use mz_controller_types::ClusterId;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{halt, soft_assert_no_log};
-use mz_repr::{RelationDesc, RowIterator, ScalarType};
+use mz_repr::{Diff, RelationDesc, RowIterator, ScalarType};
 use mz_sql::names::FullItemName;
 use mz_sql::plan::StatementDesc;
 use mz_sql::session::metadata::SessionMetadata;
@@ -451,6 +452,26 @@ pub(crate) fn viewable_variables<'a>(
         })
 }
 
+/// Returns the updates that turn the rows `previous` of a builtin table into the rows `current`,
+/// keyed by the object that each row describes.
+///
+/// Only the rows that changed are updated: the previous row of a key is retracted if its row
+/// changed or disappeared, and the current row is inserted if it changed or appeared.
+pub(crate) fn diff_keyed_rows<'a, K: Ord, V: PartialEq>(
+    previous: &'a BTreeMap<K, V>,
+    current: &'a BTreeMap<K, V>,
+) -> Vec<((&'a K, &'a V), Diff)> {
+    let retractions = previous
+        .iter()
+        .filter(|(key, row)| current.get(*key) != Some(*row))
+        .map(|row| (row, -1));
+    let insertions = current
+        .iter()
+        .filter(|(key, row)| previous.get(*key) != Some(*row))
+        .map(|row| (row, 1));
+    retractions.chain(insertions).collect()
+}
+
 /// Verify that the rows in [`RowIterator`] match the expected [`RelationDesc`].
 pub fn verify_datum_desc(
     desc: &RelationDesc,
@@ -489,3 +510,24 @@ pub fn verify_datum_desc(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_diff_keyed_rows() {
+        let previous = BTreeMap::from([(1, "a"), (2, "b"), (3, "c")]);
+        let current = BTreeMap::from([(1, "a"), (2, "x"), (4, "d")]);
+        assert_eq!(
+            diff_keyed_rows(&previous, &current),
+            vec![
+                ((&2, &"b"), -1),
+                ((&3, &"c"), -1),
+                ((&2, &"x"), 1),
+                ((&4, &"d"), 1),
+            ],
+        );
+        assert_eq!(diff_keyed_rows(&current, &current), vec![]);
+    }
+}
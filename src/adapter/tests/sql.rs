@@ -100,6 +100,8 @@ async fn datadriven() {
                                             resolved_ids: ResolvedIds(BTreeSet::new()),
                                             custom_logical_compaction_window: None,
                                             is_retained_metrics_object: false,
+                                            change_audit: false,
+                                            row_count_hint: None,
                                         }),
                                         owner_id: MZ_SYSTEM_ROLE_ID,
                                     }],
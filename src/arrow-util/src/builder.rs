@@ -24,7 +24,7 @@ use arrow::record_batch::RecordBatch;
 use chrono::Timelike;
 use mz_ore::cast::CastFrom;
 use mz_repr::adt::jsonb::JsonbRef;
-use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+use mz_repr::{Datum, RelationDesc, Row, RowRef, ScalarType};
 
 pub struct ArrowBuilder {
     columns: Vec<ArrowColumn>,
@@ -130,6 +130,16 @@ impl ArrowBuilder {
         Ok(())
     }
 
+    /// Appends the row referenced by `row` to the builder.
+    /// Errors if the row contains an unimplemented or out-of-range value.
+    pub fn add_row_ref(&mut self, row: &RowRef) -> Result<(), anyhow::Error> {
+        for (col, datum) in self.columns.iter_mut().zip(row.iter()) {
+            col.append_datum(datum)?;
+        }
+        self.row_size_bytes += row.data().len();
+        Ok(())
+    }
+
     pub fn row_size_bytes(&self) -> usize {
         self.row_size_bytes
     }
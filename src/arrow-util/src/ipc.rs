@@ -0,0 +1,62 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Encoding of rows as an [Arrow IPC stream].
+//!
+//! [Arrow IPC stream]: https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
+
+use arrow::ipc::writer::StreamWriter;
+use mz_repr::{RelationDesc, RowIterator};
+
+use crate::builder::ArrowBuilder;
+
+/// The media type of Arrow IPC streams.
+pub const STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+
+/// Incrementally encodes rows described by a [`RelationDesc`] as an Arrow IPC stream.
+///
+/// Each call returns the bytes of the stream that have been written since the previous call, so
+/// that the stream can be sent to a client as it is produced.
+pub struct StreamEncoder {
+    desc: RelationDesc,
+    writer: StreamWriter<Vec<u8>>,
+}
+
+impl StreamEncoder {
+    /// Starts a stream of rows described by `desc`, which begins with the stream's schema.
+    ///
+    /// Errors if the relation contains a type that cannot be encoded into Arrow.
+    pub fn new(desc: RelationDesc) -> Result<Self, anyhow::Error> {
+        let schema = ArrowBuilder::new(&desc, 0, 0)?.schema();
+        let writer = StreamWriter::try_new(Vec::new(), &schema)?;
+        Ok(StreamEncoder { desc, writer })
+    }
+
+    /// Returns the bytes written to the stream since the last call.
+    pub fn take(&mut self) -> Vec<u8> {
+        std::mem::take(self.writer.get_mut())
+    }
+
+    /// Encodes `rows` as a single record batch and returns the bytes written to the stream since
+    /// the last call.
+    pub fn encode(&mut self, rows: &mut dyn RowIterator) -> Result<Vec<u8>, anyhow::Error> {
+        let mut builder = ArrowBuilder::new(&self.desc, rows.count(), 0)?;
+        while let Some(row) = rows.next() {
+            builder.add_row_ref(row)?;
+        }
+        self.writer.write(&builder.to_record_batch()?)?;
+        Ok(self.take())
+    }
+
+    /// Ends the stream and returns the bytes written to it since the last call.
+    pub fn finish(mut self) -> Result<Vec<u8>, anyhow::Error> {
+        self.writer.finish()?;
+        Ok(self.take())
+    }
+}
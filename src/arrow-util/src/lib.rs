@@ -8,3 +8,5 @@
 // by the Apache License, Version 2.0.
 
 pub mod builder;
+pub mod ipc;
+pub mod parquet;
@@ -0,0 +1,72 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Incremental encoding of rows as a Parquet file.
+
+use mz_repr::{RelationDesc, RowIterator};
+use parquet::arrow::arrow_writer::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::{WriterProperties, WriterVersion};
+
+use crate::builder::ArrowBuilder;
+
+/// The number of bytes accumulated in a row group before it is written out.
+const ROW_GROUP_SIZE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Incrementally encodes rows described by a [`RelationDesc`] as a Parquet file, handing out the
+/// bytes of the file as soon as they are complete.
+///
+/// Encoded rows accumulate in the current row group, which is written out once it exceeds
+/// [`ROW_GROUP_SIZE_BYTES`], so about one row group is held in memory at a time.
+pub struct ParquetEncoder {
+    desc: RelationDesc,
+    writer: ArrowWriter<Vec<u8>>,
+}
+
+impl ParquetEncoder {
+    /// Starts a Parquet file of rows described by `desc`.
+    ///
+    /// Errors if the relation contains a type that cannot be encoded into Parquet.
+    pub fn new(desc: RelationDesc) -> Result<Self, anyhow::Error> {
+        let schema = ArrowBuilder::new(&desc, 0, 0)?.schema();
+        let props = WriterProperties::builder()
+            // Row groups are flushed based on their size instead.
+            .set_max_row_group_size(usize::MAX)
+            // Max compatibility
+            .set_writer_version(WriterVersion::PARQUET_1_0)
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let writer = ArrowWriter::try_new(Vec::new(), schema.into(), Some(props))?;
+        Ok(ParquetEncoder { desc, writer })
+    }
+
+    /// Encodes `rows` into the current row group and returns the bytes of the file that are
+    /// complete, which may be none.
+    pub fn encode(&mut self, rows: &mut dyn RowIterator) -> Result<Vec<u8>, anyhow::Error> {
+        let mut builder = ArrowBuilder::new(&self.desc, rows.count(), 0)?;
+        while let Some(row) = rows.next() {
+            builder.add_row_ref(row)?;
+        }
+        let batch = builder.to_record_batch()?;
+        if batch.num_rows() > 0 {
+            self.writer.write(&batch)?;
+        }
+        // The writer would only start a new row group based on the number of rows, so we flush
+        // based on the size of the data instead.
+        if self.writer.in_progress_size() > ROW_GROUP_SIZE_BYTES {
+            self.writer.flush()?;
+        }
+        Ok(std::mem::take(self.writer.inner_mut()))
+    }
+
+    /// Ends the file and returns its remaining bytes.
+    pub fn finish(self) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.writer.into_inner()?)
+    }
+}
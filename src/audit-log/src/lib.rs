@@ -72,6 +72,14 @@ impl VersionedEvent {
             VersionedEvent::V1(ev) => ev.id,
         }
     }
+
+    /// Returns the time at which the event occurred. All event versions must have
+    /// this field.
+    pub fn occurred_at(&self) -> EpochMillis {
+        match self {
+            VersionedEvent::V1(ev) => ev.occurred_at,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
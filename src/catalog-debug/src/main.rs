@@ -134,6 +134,16 @@ enum Action {
         /// Map of cluster name to resource specification. Check the README for latest values.
         cluster_replica_sizes: Option<String>,
     },
+    /// Prints the metadata of the catalog snapshot that was taken before the
+    /// catalog was last migrated to a new version.
+    Snapshot {
+        /// Write output to specified path. Default stdout.
+        target: Option<PathBuf>,
+    },
+    /// Restores the catalog to the snapshot that was taken before the catalog
+    /// was last migrated to a new version, so that the previous version can
+    /// open it again. Fences out any running environmentd.
+    RollbackToSnapshot,
 }
 
 #[tokio::main]
@@ -236,6 +246,15 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             };
             upgrade_check(openable_state, cluster_replica_sizes, start).await
         }
+        Action::Snapshot { target } => {
+            let target: Box<dyn Write> = if let Some(path) = target {
+                Box::new(File::create(path)?)
+            } else {
+                Box::new(io::stdout().lock())
+            };
+            snapshot(openable_state, target).await
+        }
+        Action::RollbackToSnapshot => rollback_to_snapshot(openable_state).await,
     }
 }
 
@@ -492,6 +511,25 @@ async fn epoch(
     Ok(())
 }
 
+async fn snapshot(
+    mut openable_state: Box<dyn OpenableDurableCatalogState>,
+    mut target: impl Write,
+) -> Result<(), anyhow::Error> {
+    match openable_state.get_catalog_snapshot_metadata().await? {
+        Some(metadata) => writeln!(&mut target, "Snapshot: {metadata:#?}")?,
+        None => writeln!(&mut target, "No snapshot")?,
+    }
+    Ok(())
+}
+
+async fn rollback_to_snapshot(
+    mut openable_state: Box<dyn OpenableDurableCatalogState>,
+) -> Result<(), anyhow::Error> {
+    let metadata = openable_state.rollback_to_snapshot().await?;
+    println!("restored snapshot: {metadata:#?}");
+    Ok(())
+}
+
 async fn upgrade_check(
     openable_state: Box<dyn OpenableDurableCatalogState>,
     cluster_replica_sizes: ClusterReplicaSizeMap,
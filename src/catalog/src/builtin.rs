@@ -52,7 +52,7 @@ use mz_storage_client::controller::IntrospectionType;
 use mz_storage_client::healthcheck::{
     MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_PREPARED_STATEMENT_HISTORY_DESC,
     MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC,
-    MZ_SQL_TEXT_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC,
+    MZ_SQL_TEXT_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC, MZ_WEBHOOK_SINK_DELIVERY_HISTORY_DESC,
 };
 use mz_storage_client::statistics::{MZ_SINK_STATISTICS_RAW_DESC, MZ_SOURCE_STATISTICS_RAW_DESC};
 use once_cell::sync::Lazy;
@@ -1844,6 +1844,14 @@ pub static MZ_COMPUTE_HYDRATION_TIMES_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LIR_MAPPING_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
+    name: "mz_lir_mapping_per_worker",
+    schema: MZ_INTROSPECTION_SCHEMA,
+    oid: oid::LOG_MZ_LIR_MAPPING_PER_WORKER_OID,
+    variant: LogVariant::Compute(ComputeLog::LirMapping),
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_ACTIVE_PEEKS_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
     name: "mz_active_peeks_per_worker",
     schema: MZ_INTROSPECTION_SCHEMA,
@@ -2013,6 +2021,17 @@ pub static MZ_OBJECT_DEPENDENCIES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTabl
     is_retained_metrics_object: true,
     access: vec![PUBLIC_SELECT],
 });
+pub static MZ_DEPENDENCY_GRAPH: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_dependency_graph",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_DEPENDENCY_GRAPH_OID,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("referenced_object_id", ScalarType::String.nullable(false))
+        .with_column("dependency_type", ScalarType::String.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_COMPUTE_DEPENDENCIES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_compute_dependencies",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2560,6 +2579,21 @@ pub static MZ_CLUSTER_SCHEDULES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_CLUSTER_SCHEDULING_DECISIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_cluster_scheduling_decisions",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_CLUSTER_SCHEDULING_DECISIONS_OID,
+    desc: RelationDesc::empty()
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("cluster_on", ScalarType::Bool.nullable(false))
+        .with_column(
+            "suspension_deferred_until",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SECRETS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_secrets",
     schema: MZ_CATALOG_SCHEMA,
@@ -2681,6 +2715,16 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY: Lazy<BuiltinSource> =
         access: vec![PUBLIC_SELECT],
     });
 
+pub static MZ_WEBHOOK_SINK_DELIVERY_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_webhook_sink_delivery_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_WEBHOOK_SINK_DELIVERY_HISTORY_OID,
+    data_source: IntrospectionType::WebhookSinkDeliveryHistory,
+    desc: MZ_WEBHOOK_SINK_DELIVERY_HISTORY_DESC.clone(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_aws_privatelink_connection_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2714,6 +2758,208 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUSES: Lazy<BuiltinView> = Lazy::new
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LEADERSHIP_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_leadership_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_LEADERSHIP_HISTORY_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("epoch", ScalarType::Int64.nullable(false))
+        .with_column("deploy_generation", ScalarType::UInt64.nullable(false))
+        .with_column("mz_version", ScalarType::String.nullable(false))
+        .with_column("previous_epoch", ScalarType::Int64.nullable(true))
+        .with_column(
+            "previous_deploy_generation",
+            ScalarType::UInt64.nullable(true),
+        )
+        .with_column("reason", ScalarType::String.nullable(false)),
+    data_source: IntrospectionType::LeadershipHistory,
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_CANCELLATION_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_cancellation_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_CANCELLATION_HISTORY_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("connection_id", ScalarType::UInt32.nullable(true))
+        .with_column("origin", ScalarType::String.nullable(true))
+        .with_column("requested_by", ScalarType::String.nullable(true))
+        .with_column("method", ScalarType::String.nullable(false))
+        .with_column("outcome", ScalarType::String.nullable(false))
+        .with_column("attempts", ScalarType::UInt64.nullable(false)),
+    data_source: IntrospectionType::CancellationHistory,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_CLUSTER_REPLICA_AZ_ADVISORIES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_cluster_replica_az_advisories",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_CLUSTER_REPLICA_AZ_ADVISORIES_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("availability_zone", ScalarType::String.nullable(false))
+        .with_column(
+            "recommended_availability_zone",
+            ScalarType::String.nullable(true),
+        )
+        .with_column(
+            "degraded_since",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    data_source: IntrospectionType::ReplicaAzAdvisories,
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_SUBSCRIPTION_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_subscription_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_SUBSCRIPTION_HISTORY_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("session_id", ScalarType::Uuid.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("event_type", ScalarType::String.nullable(false))
+        .with_column("rows_emitted", ScalarType::UInt64.nullable(false))
+        .with_column("bytes_emitted", ScalarType::UInt64.nullable(false))
+        .with_column("reason", ScalarType::String.nullable(true)),
+    data_source: IntrospectionType::SubscriptionHistory,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_STORAGE_SHARD_CARDINALITY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_storage_shard_cardinality",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_STORAGE_SHARD_CARDINALITY_OID,
+    desc: RelationDesc::empty()
+        .with_column("shard_id", ScalarType::String.nullable(false))
+        .with_column("approx_distinct_keys", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "collection_timestamp",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    data_source: IntrospectionType::StorageShardCardinality,
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_PEEK_MIRROR_DISCREPANCIES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_peek_mirror_discrepancies",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_PEEK_MIRROR_DISCREPANCIES_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("session_id", ScalarType::Uuid.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("mirror_cluster_id", ScalarType::String.nullable(false))
+        .with_column("row_count", ScalarType::UInt64.nullable(true))
+        .with_column("mirror_row_count", ScalarType::UInt64.nullable(true))
+        .with_column("result_hash", ScalarType::String.nullable(true))
+        .with_column("mirror_result_hash", ScalarType::String.nullable(true))
+        .with_column("error", ScalarType::String.nullable(true))
+        .with_column("mirror_error", ScalarType::String.nullable(true)),
+    data_source: IntrospectionType::PeekMirrorDiscrepancies,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_SYSTEM_PARAMETER_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_system_parameter_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_SYSTEM_PARAMETER_HISTORY_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("old_value", ScalarType::String.nullable(false))
+        .with_column("new_value", ScalarType::String.nullable(false))
+        .with_column("actor", ScalarType::String.nullable(true)),
+    data_source: IntrospectionType::SystemParameterHistory,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_TABLE_CHANGE_AUDIT: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_table_change_audit",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_TABLE_CHANGE_AUDIT_OID,
+    desc: RelationDesc::empty()
+        .with_column("table_id", ScalarType::String.nullable(false))
+        .with_column("commit_ts", ScalarType::MzTimestamp.nullable(false))
+        .with_column("role_id", ScalarType::String.nullable(false))
+        .with_column("session_id", ScalarType::Uuid.nullable(false))
+        .with_column("statement_id", ScalarType::Uuid.nullable(true))
+        .with_column("rows_inserted", ScalarType::UInt64.nullable(false))
+        .with_column("rows_deleted", ScalarType::UInt64.nullable(false)),
+    data_source: IntrospectionType::TableChangeAudit,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_DATAFLOW_SHUTDOWN_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_dataflow_shutdown_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_DATAFLOW_SHUTDOWN_HISTORY_OID,
+    desc: RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("replica_id", ScalarType::String.nullable(true))
+        .with_column("reason", ScalarType::String.nullable(false))
+        .with_column("session_id", ScalarType::Uuid.nullable(true)),
+    data_source: IntrospectionType::DataflowShutdownHistory,
+    is_retained_metrics_object: false,
+    access: vec![MONITOR_SELECT],
+});
+
+pub static MZ_CATALOG_CHANGES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_catalog_changes",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_CATALOG_CHANGES_OID,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("object_type", ScalarType::String.nullable(false))
+        .with_column("change_type", ScalarType::String.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("sql", ScalarType::String.nullable(true))
+        .with_column("user", ScalarType::String.nullable(true))
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    data_source: IntrospectionType::CatalogChangeHistory,
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_STATEMENT_EXECUTION_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_statement_execution_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2735,7 +2981,8 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_REDACTED: Lazy<BuiltinView> = Lazy::ne
 SELECT id, prepared_statement_id, sample_rate, cluster_id, application_name,
 cluster_name, database_name, search_path, transaction_isolation, execution_timestamp, transaction_id,
 transient_index_id, mz_version, began_at, finished_at, finished_status,
-rows_returned, execution_strategy
+rows_returned, execution_strategy, max_result_memory_bytes, effective_isolation,
+isolation_downgrade_reason
 FROM mz_internal.mz_statement_execution_history",
     access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
 }
@@ -2821,7 +3068,8 @@ pub static MZ_ACTIVITY_LOG_THINNED: Lazy<BuiltinView> = Lazy::new(|| {
         sql: "
 SELECT mseh.id AS execution_id, sample_rate, cluster_id, application_name, cluster_name, database_name, search_path,
 transaction_isolation, execution_timestamp, transient_index_id, params, mz_version, began_at, finished_at, finished_status,
-error_message, rows_returned, execution_strategy, transaction_id,
+error_message, rows_returned, execution_strategy, max_result_memory_bytes,
+effective_isolation, isolation_downgrade_reason, transaction_id,
 mpsh.id AS prepared_statement_id, sql_hash, mpsh.name AS prepared_statement_name,
 mpsh.session_id, prepared_at, statement_type, throttled_count,
 initial_application_name, authenticated_user
@@ -2869,7 +3117,9 @@ pub static MZ_RECENT_ACTIVITY_LOG_REDACTED: Lazy<BuiltinView> = Lazy::new(|| {
     sql: "SELECT mralt.execution_id, mralt.sample_rate, mralt.cluster_id, mralt.application_name,
     mralt.cluster_name, mralt.database_name, mralt.search_path, mralt.transaction_isolation, mralt.execution_timestamp,
     mralt.transient_index_id, mralt.params, mralt.mz_version, mralt.began_at, mralt.finished_at,
-    mralt.finished_status, mralt.rows_returned, mralt.execution_strategy, mralt.transaction_id,
+    mralt.finished_status, mralt.rows_returned, mralt.execution_strategy,
+    mralt.max_result_memory_bytes, mralt.effective_isolation,
+    mralt.isolation_downgrade_reason, mralt.transaction_id,
     mralt.prepared_statement_id, mralt.sql_hash, mralt.prepared_statement_name, mralt.session_id,
     mralt.prepared_at, mralt.statement_type, mralt.throttled_count,
     mralt.initial_application_name, mralt.authenticated_user,
@@ -2881,6 +3131,44 @@ WHERE mralt.sql_hash = mrst.sql_hash",
 }
 });
 
+pub static MZ_CLUSTER_STATEMENT_SLOS: Lazy<BuiltinView> = Lazy::new(|| {
+    BuiltinView {
+    name: "mz_cluster_statement_slos",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_CLUSTER_STATEMENT_SLOS_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    cluster_id,
+    cluster_name,
+    window_size,
+    count(*) AS logged_executions,
+    sum(weight) AS executions,
+    coalesce(sum(weight) FILTER (WHERE finished_status = 'success'), 0) AS successes,
+    coalesce(sum(weight) FILTER (WHERE finished_status = 'error'), 0) AS errors,
+    coalesce(sum(weight) FILTER (WHERE finished_status IN ('canceled', 'aborted')), 0) AS canceled,
+    coalesce(sum(weight) FILTER (WHERE finished_status = 'error'), 0) / sum(weight) AS error_ratio,
+    sum((finished_at - began_at) * weight) / sum(weight) AS avg_latency,
+    max(finished_at - began_at) AS max_latency,
+    coalesce(sum(weight) FILTER (WHERE finished_at - began_at <= INTERVAL '100 milliseconds'), 0) AS completed_within_100ms,
+    coalesce(sum(weight) FILTER (WHERE finished_at - began_at <= INTERVAL '1 second'), 0) AS completed_within_1s,
+    coalesce(sum(weight) FILTER (WHERE finished_at - began_at <= INTERVAL '10 seconds'), 0) AS completed_within_10s
+FROM
+    -- Each logged execution stands for `1 / sample_rate` executions.
+    (
+        SELECT cluster_id, cluster_name, began_at, finished_at, finished_status, 1 / sample_rate AS weight
+        FROM mz_internal.mz_statement_execution_history
+        WHERE sample_rate > 0
+    ) AS executions,
+    (VALUES (INTERVAL '5 minutes'), (INTERVAL '1 hour'), (INTERVAL '1 day')) AS windows (window_size)
+WHERE cluster_id IS NOT NULL
+AND finished_at IS NOT NULL
+AND began_at + window_size > mz_now()
+GROUP BY cluster_id, cluster_name, window_size",
+    access: vec![MONITOR_SELECT, MONITOR_REDACTED_SELECT, SUPPORT_SELECT],
+}
+});
+
 pub static MZ_STATEMENT_LIFECYCLE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_statement_lifecycle_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3093,6 +3381,33 @@ pub static MZ_CLUSTER_REPLICA_METRICS: Lazy<BuiltinTable> = Lazy::new(|| Builtin
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_REPLICA_UTILIZATION: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_replica_utilization",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_REPLICA_UTILIZATION_OID,
+    desc: RelationDesc::empty()
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("size", ScalarType::String.nullable(true))
+        .with_column("processes", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "allocated_cpu_nano_cores",
+            ScalarType::UInt64.nullable(true),
+        )
+        .with_column("allocated_memory_bytes", ScalarType::UInt64.nullable(true))
+        .with_column("cpu_nano_cores", ScalarType::UInt64.nullable(true))
+        .with_column("memory_bytes", ScalarType::UInt64.nullable(true))
+        .with_column("cpu_percent", ScalarType::Float64.nullable(true))
+        .with_column("memory_percent", ScalarType::Float64.nullable(true))
+        .with_column("hydration_fraction", ScalarType::Float64.nullable(true))
+        .with_column(
+            "last_heartbeat",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_CLUSTER_REPLICA_FRONTIERS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_cluster_replica_frontiers",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3172,6 +3487,146 @@ pub static MZ_SUBSCRIPTIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_SUBSCRIPTION_BUFFERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_subscription_buffers",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_SUBSCRIPTION_BUFFERS_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("cursor_name", ScalarType::String.nullable(false))
+        .with_column("buffered_rows", ScalarType::UInt64.nullable(false))
+        .with_column("paused", ScalarType::Bool.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_CURSORS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_cursors",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_CURSORS_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("session_id", ScalarType::Uuid.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("index_id", ScalarType::String.nullable(false))
+        .with_column("read_timestamp", ScalarType::MzTimestamp.nullable(false))
+        .with_column("pages_fetched", ScalarType::UInt64.nullable(false))
+        .with_column(
+            "opened_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column(
+            "expires_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_DEFERRED_MAINTENANCE: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_deferred_maintenance",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_DEFERRED_MAINTENANCE_OID,
+    desc: RelationDesc::empty()
+        .with_column("work", ScalarType::String.nullable(false))
+        .with_column("reason", ScalarType::String.nullable(false))
+        .with_column(
+            "deferred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column(
+            "resumes_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_BACKGROUND_TASKS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_background_tasks",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_BACKGROUND_TASKS_OID,
+    desc: RelationDesc::empty()
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column(
+            "last_run_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_column(
+            "next_run_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_column("skip_next_run", ScalarType::Bool.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_BUILTIN_TABLE_MIGRATIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_builtin_table_migrations",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_BUILTIN_TABLE_MIGRATIONS_OID,
+    desc: RelationDesc::empty()
+        .with_column("table_id", ScalarType::String.nullable(false))
+        .with_column("status", ScalarType::String.nullable(false))
+        .with_column(
+            "started_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column(
+            "finished_at",
+            ScalarType::TimestampTz { precision: None }.nullable(true),
+        )
+        .with_key(vec![0]),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_DATABASE_METRICS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_database_metrics",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_DATABASE_METRICS_OID,
+    desc: RelationDesc::empty()
+        .with_column("database_id", ScalarType::String.nullable(false))
+        .with_column("statements", ScalarType::UInt64.nullable(false))
+        .with_column("statements_per_second", ScalarType::Float64.nullable(false))
+        .with_column("errors", ScalarType::UInt64.nullable(false))
+        .with_column("result_bytes", ScalarType::UInt64.nullable(false))
+        .with_column("peeks", ScalarType::UInt64.nullable(false))
+        .with_column("mean_peek_seconds", ScalarType::Float64.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_SINK_LAG: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_sink_lag",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_SINK_LAG_OID,
+    desc: RelationDesc::empty()
+        .with_column("sink_id", ScalarType::String.nullable(false))
+        .with_column("upstream_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("write_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("lag", ScalarType::Interval.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_STARVED_WRITE_LOCK_WAITERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_starved_write_lock_waiters",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::TABLE_MZ_STARVED_WRITE_LOCK_WAITERS_OID,
+    desc: RelationDesc::empty()
+        .with_column("connection_id", ScalarType::UInt32.nullable(false))
+        .with_column("statement", ScalarType::String.nullable(false))
+        .with_column(
+            "deferred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("lock_holder", ScalarType::String.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_sessions",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3515,6 +3970,18 @@ WHERE worker_id = 0",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LIR_MAPPING: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_lir_mapping",
+    schema: MZ_INTROSPECTION_SCHEMA,
+    oid: oid::VIEW_MZ_LIR_MAPPING_OID,
+    column_defs: None,
+    sql: "
+SELECT dataflow_id, lir_id, operator_id_start, operator_id_end
+FROM mz_introspection.mz_lir_mapping_per_worker
+WHERE worker_id = 0",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_DATAFLOW_OPERATOR_DATAFLOWS_PER_WORKER: Lazy<BuiltinView> =
     Lazy::new(|| BuiltinView {
         name: "mz_dataflow_operator_dataflows_per_worker",
@@ -4800,6 +5267,20 @@ pub static MZ_COMPUTE_HYDRATION_TIMES: Lazy<BuiltinSource> = Lazy::new(|| Builti
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_COMPUTE_CARDINALITY_ADVISORIES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_compute_cardinality_advisories",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::SOURCE_MZ_COMPUTE_CARDINALITY_ADVISORIES_OID,
+    desc: RelationDesc::empty()
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("operator_id", ScalarType::UInt64.nullable(false))
+        .with_column("operator_name", ScalarType::String.nullable(false)),
+    data_source: IntrospectionType::ComputeCardinalityAdvisories,
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_COMPUTE_HYDRATION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_compute_hydration_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -5537,6 +6018,62 @@ WHERE s.database_id IS NULL OR d.name = current_database()",
     access: vec![PUBLIC_SELECT],
 });
 
+// Materialize has no `CREATE PUBLICATION`. Instead, every table and materialized view is published
+// for logical replication under its own name.
+pub static PG_PUBLICATION: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "pg_publication",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::VIEW_PG_PUBLICATION_OID,
+    column_defs: None,
+    sql: "SELECT
+    r.oid AS oid,
+    r.name AS pubname,
+    role_owner.oid AS pubowner,
+    false AS puballtables,
+    true AS pubinsert,
+    -- Updates are replicated as a delete and an insert.
+    false AS pubupdate,
+    true AS pubdelete,
+    false AS pubtruncate,
+    false AS pubviaroot
+FROM (
+    SELECT oid, schema_id, name, owner_id FROM mz_catalog.mz_tables
+    UNION ALL
+    SELECT oid, schema_id, name, owner_id FROM mz_catalog.mz_materialized_views
+) r
+JOIN mz_catalog.mz_schemas s ON s.id = r.schema_id
+JOIN mz_catalog.mz_databases d ON d.id = s.database_id
+JOIN mz_catalog.mz_roles role_owner ON role_owner.id = r.owner_id
+WHERE d.name = current_database()",
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static PG_PUBLICATION_TABLES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "pg_publication_tables",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::VIEW_PG_PUBLICATION_TABLES_OID,
+    column_defs: None,
+    sql: "SELECT
+    r.name AS pubname,
+    s.name AS schemaname,
+    r.name AS tablename,
+    (
+        SELECT pg_catalog.array_agg(c.name ORDER BY c.position)
+        FROM mz_catalog.mz_columns c
+        WHERE c.id = r.id
+    ) AS attnames,
+    NULL::pg_catalog.text AS rowfilter
+FROM (
+    SELECT id, schema_id, name FROM mz_catalog.mz_tables
+    UNION ALL
+    SELECT id, schema_id, name FROM mz_catalog.mz_materialized_views
+) r
+JOIN mz_catalog.mz_schemas s ON s.id = r.schema_id
+JOIN mz_catalog.mz_databases d ON d.id = s.database_id
+WHERE d.name = current_database()",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static INFORMATION_SCHEMA_APPLICABLE_ROLES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "applicable_roles",
     schema: INFORMATION_SCHEMA,
@@ -6635,6 +7172,22 @@ SELECT * FROM sinks"#,
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_OBJECT_HYDRATION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_object_hydration_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_OBJECT_HYDRATION_STATUSES_OID,
+    column_defs: None,
+    sql: "
+SELECT
+    object_id,
+    bool_or(hydrated) AS hydrated,
+    count(replica_id) AS replicas,
+    count(replica_id) FILTER (WHERE hydrated) AS hydrated_replicas
+FROM mz_internal.mz_hydration_statuses
+GROUP BY object_id",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_MATERIALIZATION_LAG: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_materialization_lag",
     schema: MZ_INTERNAL_SCHEMA,
@@ -7427,10 +7980,12 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_COMPUTE_IMPORT_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_ERROR_COUNTS_RAW),
         Builtin::Log(&MZ_COMPUTE_HYDRATION_TIMES_PER_WORKER),
+        Builtin::Log(&MZ_LIR_MAPPING_PER_WORKER),
         Builtin::Table(&MZ_KAFKA_SINKS),
         Builtin::Table(&MZ_KAFKA_CONNECTIONS),
         Builtin::Table(&MZ_KAFKA_SOURCES),
         Builtin::Table(&MZ_OBJECT_DEPENDENCIES),
+        Builtin::Table(&MZ_DEPENDENCY_GRAPH),
         Builtin::Table(&MZ_DATABASES),
         Builtin::Table(&MZ_SCHEMAS),
         Builtin::Table(&MZ_COLUMNS),
@@ -7461,11 +8016,13 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_CLUSTERS),
         Builtin::Table(&MZ_CLUSTER_WORKLOAD_CLASSES),
         Builtin::Table(&MZ_CLUSTER_SCHEDULES),
+        Builtin::Table(&MZ_CLUSTER_SCHEDULING_DECISIONS),
         Builtin::Table(&MZ_SECRETS),
         Builtin::Table(&MZ_CONNECTIONS),
         Builtin::Table(&MZ_SSH_TUNNEL_CONNECTIONS),
         Builtin::Table(&MZ_CLUSTER_REPLICAS),
         Builtin::Table(&MZ_CLUSTER_REPLICA_METRICS),
+        Builtin::Table(&MZ_REPLICA_UTILIZATION),
         Builtin::Table(&MZ_CLUSTER_REPLICA_SIZES),
         Builtin::Table(&MZ_CLUSTER_REPLICA_STATUSES),
         Builtin::Table(&MZ_INTERNAL_CLUSTER_REPLICAS),
@@ -7475,6 +8032,14 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_AWS_PRIVATELINK_CONNECTIONS),
         Builtin::Table(&MZ_AWS_CONNECTIONS),
         Builtin::Table(&MZ_SUBSCRIPTIONS),
+        Builtin::Table(&MZ_SUBSCRIPTION_BUFFERS),
+        Builtin::Table(&MZ_CURSORS),
+        Builtin::Table(&MZ_DEFERRED_MAINTENANCE),
+        Builtin::Table(&MZ_BACKGROUND_TASKS),
+        Builtin::Table(&MZ_BUILTIN_TABLE_MIGRATIONS),
+        Builtin::Table(&MZ_DATABASE_METRICS),
+        Builtin::Table(&MZ_SINK_LAG),
+        Builtin::Table(&MZ_STARVED_WRITE_LOCK_WAITERS),
         Builtin::Table(&MZ_SESSIONS),
         Builtin::Table(&MZ_DEFAULT_PRIVILEGES),
         Builtin::Table(&MZ_SYSTEM_PRIVILEGES),
@@ -7495,6 +8060,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_DATAFLOW_ADDRESSES),
         Builtin::View(&MZ_DATAFLOW_CHANNELS),
         Builtin::View(&MZ_DATAFLOW_OPERATORS),
+        Builtin::View(&MZ_LIR_MAPPING),
         Builtin::View(&MZ_DATAFLOW_OPERATOR_DATAFLOWS_PER_WORKER),
         Builtin::View(&MZ_DATAFLOW_OPERATOR_DATAFLOWS),
         Builtin::View(&MZ_OBJECT_TRANSITIVE_DEPENDENCIES),
@@ -7572,6 +8138,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&PG_USER),
         Builtin::View(&PG_VIEWS),
         Builtin::View(&PG_MATVIEWS),
+        Builtin::View(&PG_PUBLICATION),
+        Builtin::View(&PG_PUBLICATION_TABLES),
         Builtin::View(&PG_COLLATION),
         Builtin::View(&PG_POLICY),
         Builtin::View(&PG_INHERITS),
@@ -7620,6 +8188,17 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Source(&MZ_SOURCE_STATUS_HISTORY),
         Builtin::Source(&MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY),
         Builtin::View(&MZ_AWS_PRIVATELINK_CONNECTION_STATUSES),
+        Builtin::Source(&MZ_LEADERSHIP_HISTORY),
+        Builtin::Source(&MZ_CANCELLATION_HISTORY),
+        Builtin::Source(&MZ_CLUSTER_REPLICA_AZ_ADVISORIES),
+        Builtin::Source(&MZ_SUBSCRIPTION_HISTORY),
+        Builtin::Source(&MZ_STORAGE_SHARD_CARDINALITY),
+        Builtin::Source(&MZ_PEEK_MIRROR_DISCREPANCIES),
+        Builtin::Source(&MZ_SYSTEM_PARAMETER_HISTORY),
+        Builtin::Source(&MZ_TABLE_CHANGE_AUDIT),
+        Builtin::Source(&MZ_DATAFLOW_SHUTDOWN_HISTORY),
+        Builtin::Source(&MZ_CATALOG_CHANGES),
+        Builtin::Source(&MZ_WEBHOOK_SINK_DELIVERY_HISTORY),
         Builtin::Source(&MZ_STATEMENT_EXECUTION_HISTORY),
         Builtin::View(&MZ_STATEMENT_EXECUTION_HISTORY_REDACTED),
         Builtin::Source(&MZ_PREPARED_STATEMENT_HISTORY),
@@ -7633,6 +8212,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG_THINNED),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG_REDACTED),
+        Builtin::View(&MZ_CLUSTER_STATEMENT_SLOS),
         Builtin::Index(&MZ_RECENT_ACTIVITY_LOG_THINNED_IND),
         Builtin::View(&MZ_SOURCE_STATUSES),
         Builtin::Source(&MZ_STATEMENT_LIFECYCLE_HISTORY),
@@ -7656,10 +8236,12 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS),
         Builtin::Source(&MZ_COMPUTE_ERROR_COUNTS_RAW_UNIFIED),
         Builtin::Source(&MZ_COMPUTE_HYDRATION_TIMES),
+        Builtin::Source(&MZ_COMPUTE_CARDINALITY_ADVISORIES),
         Builtin::View(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES),
         Builtin::Source(&MZ_CLUSTER_REPLICA_FRONTIERS),
         Builtin::View(&MZ_COMPUTE_HYDRATION_STATUSES),
         Builtin::View(&MZ_HYDRATION_STATUSES),
+        Builtin::View(&MZ_OBJECT_HYDRATION_STATUSES),
         Builtin::Index(&MZ_SHOW_DATABASES_IND),
         Builtin::Index(&MZ_SHOW_SCHEMAS_IND),
         Builtin::Index(&MZ_SHOW_CONNECTIONS_IND),
@@ -24,6 +24,7 @@ use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::EpochMillis;
 use mz_persist_client::PersistClient;
 use mz_repr::GlobalId;
+use serde::{Deserialize, Serialize};
 
 use crate::durable::debug::{DebugCatalogState, Trace};
 pub use crate::durable::error::{CatalogError, DurableCatalogError};
@@ -74,6 +75,24 @@ pub struct BootstrapArgs {
 
 pub type Epoch = NonZeroI64;
 
+/// Metadata of the catalog snapshot that is taken before the catalog is migrated to a new version.
+/// See [`OpenableDurableCatalogState::rollback_to_snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogSnapshotMetadata {
+    /// The catalog version of the snapshot, i.e. the version the catalog was migrated from.
+    pub catalog_version: u64,
+    /// The version of `environmentd` that last wrote the contents of the snapshot, if known.
+    pub catalog_content_version: Option<String>,
+    /// The deploy generation of the catalog when the snapshot was taken.
+    pub deploy_generation: Option<u64>,
+    /// The epoch of the catalog when the snapshot was taken.
+    pub epoch: Epoch,
+    /// When the snapshot was taken.
+    pub taken_at: EpochMillis,
+    /// The number of catalog updates in the snapshot.
+    pub updates: usize,
+}
+
 /// An API for opening a durable catalog state.
 ///
 /// If a catalog is not opened, then resources should be release via [`Self::expire`].
@@ -175,6 +194,26 @@ pub trait OpenableDurableCatalogState: Debug + Send {
     /// Generate a consolidated [`Trace`] of catalog contents.
     async fn trace_consolidated(&mut self) -> Result<Trace, CatalogError>;
 
+    /// Returns the metadata of the catalog snapshot that was taken before the catalog was last
+    /// migrated to a new version, if any.
+    async fn get_catalog_snapshot_metadata(
+        &mut self,
+    ) -> Result<Option<CatalogSnapshotMetadata>, CatalogError>;
+
+    /// Restores the contents of the catalog to the snapshot that was taken before the catalog was
+    /// last migrated to a new version, and fences out all other catalogs.
+    ///
+    /// Opening a writable catalog that requires migrations first durably snapshots the
+    /// consolidated catalog contents. If the new version turns out to be unhealthy, rolling back
+    /// to the snapshot allows the version that wrote it to open the catalog again, as long as
+    /// persist can still read the catalog shard, i.e. for a downgrade by at most one minor version.
+    ///
+    /// Only the catalog is restored. Any state outside of the catalog, e.g. shards created by
+    /// builtin migrations, is left untouched.
+    ///
+    /// Returns the metadata of the restored snapshot.
+    async fn rollback_to_snapshot(&mut self) -> Result<CatalogSnapshotMetadata, CatalogError>;
+
     /// Politely releases all external resources that can only be released in an async context.
     async fn expire(self: Box<Self>);
 }
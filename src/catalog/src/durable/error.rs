@@ -53,6 +53,9 @@ pub enum DurableCatalogError {
     /// Catalog is uninitialized.
     #[error("uninitialized")]
     Uninitialized,
+    /// There is no catalog snapshot to roll back to.
+    #[error("no catalog snapshot to roll back to")]
+    MissingSnapshot,
     /// Catalog is not in a writable state.
     #[error("{0}")]
     NotWritable(String),
@@ -82,6 +85,7 @@ impl DurableCatalogError {
             | DurableCatalogError::IncompatiblePersistVersion { .. }
             | DurableCatalogError::Proto(_) => true,
             DurableCatalogError::Uninitialized
+            | DurableCatalogError::MissingSnapshot
             | DurableCatalogError::NotWritable(_)
             | DurableCatalogError::DuplicateKey
             | DurableCatalogError::UniquenessViolation
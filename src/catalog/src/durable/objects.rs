@@ -86,6 +86,7 @@ pub struct Database {
     pub name: String,
     pub owner_id: RoleId,
     pub privileges: Vec<MzAclItem>,
+    pub default_cluster_id: Option<ClusterId>,
 }
 
 impl DurableType for Database {
@@ -100,6 +101,7 @@ impl DurableType for Database {
                 name: self.name,
                 owner_id: self.owner_id,
                 privileges: self.privileges,
+                default_cluster_id: self.default_cluster_id,
             },
         )
     }
@@ -111,6 +113,7 @@ impl DurableType for Database {
             name: value.name,
             owner_id: value.owner_id,
             privileges: value.privileges,
+            default_cluster_id: value.default_cluster_id,
         }
     }
 
@@ -1042,6 +1045,7 @@ pub struct DatabaseValue {
     pub(crate) owner_id: RoleId,
     pub(crate) privileges: Vec<MzAclItem>,
     pub(crate) oid: u32,
+    pub(crate) default_cluster_id: Option<ClusterId>,
 }
 
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
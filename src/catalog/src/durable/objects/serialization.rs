@@ -474,6 +474,7 @@ impl RustType<proto::DatabaseValue> for DatabaseValue {
             owner_id: Some(self.owner_id.into_proto()),
             privileges: self.privileges.into_proto(),
             oid: self.oid,
+            default_cluster_id: self.default_cluster_id.into_proto(),
         }
     }
 
@@ -485,6 +486,7 @@ impl RustType<proto::DatabaseValue> for DatabaseValue {
                 .into_rust_if_some("DatabaseValue::owner_id")?),
             privileges: proto.privileges.into_rust()?,
             oid: proto.oid,
+            default_cluster_id: proto.default_cluster_id.into_rust()?,
         })
     }
 }
@@ -39,6 +39,7 @@ use mz_persist_types::codec_impls::UnitSchema;
 use mz_proto::{RustType, TryFromProtoError};
 use mz_repr::{Diff, RelationDesc, ScalarType};
 use mz_storage_types::sources::SourceData;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
 use tracing::{debug, error, info};
@@ -57,10 +58,11 @@ use crate::durable::objects::state_update::{
 };
 use crate::durable::objects::{AuditLogKey, Snapshot, StorageUsageKey};
 use crate::durable::transaction::TransactionBatch;
-use crate::durable::upgrade::upgrade;
+use crate::durable::upgrade::{upgrade, CATALOG_VERSION};
 use crate::durable::{
-    initialize, BootstrapArgs, CatalogError, DurableCatalogError, DurableCatalogState, Epoch,
-    OpenableDurableCatalogState, ReadOnlyDurableCatalogState, Transaction,
+    initialize, BootstrapArgs, CatalogError, CatalogSnapshotMetadata, DurableCatalogError,
+    DurableCatalogState, Epoch, OpenableDurableCatalogState, ReadOnlyDurableCatalogState,
+    Transaction, CATALOG_CONTENT_VERSION_KEY,
 };
 use crate::memory;
 
@@ -77,6 +79,8 @@ const MIN_EPOCH: Epoch = unsafe { Epoch::new_unchecked(1) };
 const CATALOG_SHARD_NAME: &str = "catalog";
 /// Human readable catalog upgrade shard name.
 const UPGRADE_SHARD_NAME: &str = "catalog_upgrade";
+/// Human readable catalog snapshot shard name.
+const SNAPSHOT_SHARD_NAME: &str = "catalog_snapshot";
 
 /// Seed used to generate the persist shard ID for the catalog.
 const CATALOG_SEED: usize = 1;
@@ -113,6 +117,13 @@ const CATALOG_SEED: usize = 1;
 const UPGRADE_SEED: usize = 2;
 /// Seed used to generate the persist shard ID for builtin table migrations.
 const BUILTIN_MIGRATION_SEED: usize = 3;
+/// Seed used to generate the persist shard ID for the catalog snapshot.
+///
+/// Before migrating the catalog to a new version, a writable catalog replaces the contents of the
+/// snapshot shard with the consolidated contents of the catalog shard, minus the epoch, and a
+/// [`CatalogSnapshotMetadata`] row. Operators can restore the catalog from the snapshot to
+/// downgrade after a failed upgrade, see [`OpenableDurableCatalogState::rollback_to_snapshot`].
+const SNAPSHOT_SEED: usize = 4;
 
 /// Durable catalog mode that dictates the effect of mutable operations.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -920,6 +931,16 @@ impl UnopenedPersistCatalogState {
 
         // Perform data migrations.
         if is_initialized && !read_only {
+            // Savepoint catalogs never make durable changes, so they don't need a snapshot.
+            if matches!(mode, Mode::Writable) {
+                let version = self
+                    .get_user_version()
+                    .await?
+                    .expect("initialized catalog must have a version");
+                if version < CATALOG_VERSION {
+                    self.write_catalog_snapshot(version, initial_ts).await?;
+                }
+            }
             upgrade(&mut self, mode.clone()).await?;
         }
 
@@ -1020,6 +1041,154 @@ impl UnopenedPersistCatalogState {
     pub(crate) async fn get_user_version(&mut self) -> Result<Option<u64>, CatalogError> {
         self.get_current_config(USER_VERSION_KEY).await
     }
+
+    /// Replaces the catalog snapshot with the current contents of the catalog, which are at
+    /// `catalog_version`.
+    ///
+    /// If the existing snapshot was written by the same version of `environmentd` at an older
+    /// catalog version, then a previous attempt to migrate the catalog from that snapshot was
+    /// interrupted. We keep that snapshot, since the older version can't read the partially
+    /// migrated catalog.
+    #[mz_ore::instrument]
+    async fn write_catalog_snapshot(
+        &mut self,
+        catalog_version: u64,
+        taken_at: EpochMillis,
+    ) -> Result<(), CatalogError> {
+        self.sync_to_current_upper().await?;
+        self.consolidate();
+        let epoch = self
+            .epoch
+            .validate()?
+            .expect("initialized catalog must have an epoch");
+
+        let mut catalog_content_version = None;
+        let mut updates = Vec::with_capacity(self.snapshot.len());
+        for (kind, _, diff) in &self.snapshot {
+            soft_assert_eq_or_log!(*diff, 1, "snapshot is consolidated");
+            if is_epoch(kind) {
+                continue;
+            }
+            if let Ok(StateUpdateKind::Setting(key, value)) =
+                <StateUpdateKindRaw as TryIntoStateUpdateKind>::try_into(kind.clone())
+            {
+                if key.name == CATALOG_CONTENT_VERSION_KEY {
+                    catalog_content_version = Some(value.value);
+                }
+            }
+            updates.push(kind.clone());
+        }
+        let metadata = CatalogSnapshotMetadata {
+            catalog_version,
+            catalog_content_version,
+            deploy_generation: self.update_applier.configs.get(DEPLOY_GENERATION).cloned(),
+            epoch,
+            taken_at,
+            updates: updates.len(),
+        };
+        let metadata_row = StateUpdateKindRaw::from_serde(&CatalogSnapshotMetadataRow {
+            catalog_snapshot_metadata: metadata.clone(),
+        });
+
+        let (mut write_handle, mut read_handle) = open_snapshot_shard(
+            &self.persist_client,
+            self.update_applier.organization_id,
+            "write durable catalog snapshot",
+        )
+        .await;
+        let mut upper = write_handle.fetch_recent_upper().await.clone();
+        loop {
+            let previous = read_snapshot_shard(&mut read_handle, &upper).await;
+            if let Some((previous_metadata, _)) = &previous.metadata {
+                if previous_metadata.catalog_content_version == metadata.catalog_content_version
+                    && previous_metadata.catalog_version < catalog_version
+                {
+                    info!(
+                        ?previous_metadata,
+                        "keeping catalog snapshot of partially migrated catalog"
+                    );
+                    break;
+                }
+            }
+
+            let ts = upper
+                .as_option()
+                .cloned()
+                .expect("we use a totally ordered time and never finalize the shard");
+            let retractions = previous
+                .updates
+                .into_iter()
+                .chain(previous.metadata.map(|(_, row)| row))
+                .map(|kind| ((SourceData::from(kind), ()), ts, -1));
+            let insertions = updates
+                .iter()
+                .cloned()
+                .chain(std::iter::once(metadata_row.clone()))
+                .map(|kind| ((SourceData::from(kind), ()), ts, 1));
+            let batch: Vec<_> = retractions.chain(insertions).collect();
+            let next_upper = Antichain::from_elem(ts.step_forward());
+            match write_handle
+                .compare_and_append(&batch, upper, next_upper)
+                .await
+                .expect("invalid usage")
+            {
+                Ok(()) => {
+                    // Allow persist to compact away the previous snapshot.
+                    read_handle.downgrade_since(&Antichain::from_elem(ts)).await;
+                    info!(?metadata, "wrote catalog snapshot before migrating catalog");
+                    break;
+                }
+                Err(upper_mismatch) => {
+                    upper = upper_mismatch.current;
+                }
+            }
+        }
+        write_handle.expire().await;
+        read_handle.expire().await;
+        Ok(())
+    }
+
+    /// Reads the catalog snapshot.
+    async fn read_catalog_snapshot(&self) -> CatalogSnapshot {
+        let (mut write_handle, mut read_handle) = open_snapshot_shard(
+            &self.persist_client,
+            self.update_applier.organization_id,
+            "read durable catalog snapshot",
+        )
+        .await;
+        let upper = write_handle.fetch_recent_upper().await.clone();
+        let snapshot = read_snapshot_shard(&mut read_handle, &upper).await;
+        write_handle.expire().await;
+        read_handle.expire().await;
+        snapshot
+    }
+
+    /// Replaces the contents of the catalog with the catalog updates of a snapshot.
+    #[mz_ore::instrument]
+    async fn rollback_to_snapshot_inner(
+        &mut self,
+        snapshot_updates: Vec<StateUpdateKindRaw>,
+    ) -> Result<(), CatalogError> {
+        self.sync_to_current_upper().await?;
+        self.consolidate();
+        let mut updates: Vec<_> = self
+            .snapshot
+            .iter()
+            .filter(|(kind, _, _)| !is_epoch(kind))
+            .map(|(kind, _, _)| (kind.clone(), -1))
+            .collect();
+        updates.extend(snapshot_updates.into_iter().map(|kind| (kind, 1)));
+        // We must fence out all other catalogs since we are writing.
+        let fence_updates = self.increment_epoch()?;
+        updates.extend(
+            fence_updates
+                .into_iter()
+                .map(|(kind, diff)| (StateUpdateKindRaw::from(kind), diff)),
+        );
+        differential_dataflow::consolidation::consolidate(&mut updates);
+        self.compare_and_append(updates).await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -1154,12 +1323,126 @@ impl OpenableDurableCatalogState for UnopenedPersistCatalogState {
         }
     }
 
+    #[mz_ore::instrument]
+    async fn get_catalog_snapshot_metadata(
+        &mut self,
+    ) -> Result<Option<CatalogSnapshotMetadata>, CatalogError> {
+        let snapshot = self.read_catalog_snapshot().await;
+        Ok(snapshot.metadata.map(|(metadata, _)| metadata))
+    }
+
+    #[mz_ore::instrument]
+    async fn rollback_to_snapshot(&mut self) -> Result<CatalogSnapshotMetadata, CatalogError> {
+        let snapshot = self.read_catalog_snapshot().await;
+        let Some((metadata, _)) = snapshot.metadata else {
+            return Err(DurableCatalogError::MissingSnapshot.into());
+        };
+        let (_, res) = retry(self, move |s| {
+            let updates = snapshot.updates.clone();
+            async {
+                let res = s.rollback_to_snapshot_inner(updates).await;
+                (s, res)
+            }
+        })
+        .await;
+        res?;
+        info!(?metadata, "rolled back catalog to snapshot");
+        Ok(metadata)
+    }
+
     #[mz_ore::instrument(level = "debug")]
     async fn expire(self: Box<Self>) {
         self.expire().await
     }
 }
 
+/// The contents of the catalog snapshot shard, see [`SNAPSHOT_SEED`].
+#[derive(Debug, Default)]
+struct CatalogSnapshot {
+    /// The metadata of the snapshot and the row that stores it, if a snapshot exists.
+    metadata: Option<(CatalogSnapshotMetadata, StateUpdateKindRaw)>,
+    /// The catalog updates of the snapshot.
+    updates: Vec<StateUpdateKindRaw>,
+}
+
+/// The row that stores [`CatalogSnapshotMetadata`] in the catalog snapshot shard.
+///
+/// Raw catalog updates are keyed by the name of their collection, so they never decode as this.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogSnapshotMetadataRow {
+    catalog_snapshot_metadata: CatalogSnapshotMetadata,
+}
+
+/// Opens handles to the catalog snapshot shard of `organization_id`.
+async fn open_snapshot_shard(
+    persist_client: &PersistClient,
+    organization_id: Uuid,
+    handle_purpose: &str,
+) -> (
+    WriteHandle<SourceData, (), Timestamp, Diff>,
+    ReadHandle<SourceData, (), Timestamp, Diff>,
+) {
+    persist_client
+        .open(
+            shard_id(organization_id, SNAPSHOT_SEED),
+            Arc::new(desc()),
+            Arc::new(UnitSchema::default()),
+            Diagnostics {
+                shard_name: SNAPSHOT_SHARD_NAME.to_string(),
+                handle_purpose: handle_purpose.to_string(),
+            },
+            false,
+        )
+        .await
+        .expect("invalid usage")
+}
+
+/// Reads the contents of the catalog snapshot shard, whose current upper is `upper`.
+async fn read_snapshot_shard(
+    read_handle: &mut ReadHandle<SourceData, (), Timestamp, Diff>,
+    upper: &Antichain<Timestamp>,
+) -> CatalogSnapshot {
+    let upper = upper
+        .as_option()
+        .cloned()
+        .expect("we use a totally ordered time and never finalize the shard");
+    let mut snapshot = CatalogSnapshot::default();
+    // Nothing has been written to the shard yet.
+    if upper == Timestamp::minimum() {
+        return snapshot;
+    }
+    let as_of = as_of(read_handle, upper);
+    let updates = read_handle
+        .snapshot_and_fetch(Antichain::from_elem(as_of))
+        .await
+        .expect("we have advanced the as_of by the since");
+    for update in updates {
+        let StateUpdate { kind, ts: _, diff } =
+            Into::<StateUpdate<StateUpdateKindRaw>>::into(update);
+        soft_assert_eq_or_log!(
+            diff,
+            1,
+            "snapshot_and_fetch guarantees a consolidated result"
+        );
+        match kind.try_to_serde::<CatalogSnapshotMetadataRow>() {
+            Ok(row) => snapshot.metadata = Some((row.catalog_snapshot_metadata, kind)),
+            Err(_) => snapshot.updates.push(kind),
+        }
+    }
+    snapshot
+}
+
+/// Reports whether `kind` is an update to the epoch of the catalog.
+///
+/// The epoch only ever increases, so it is neither snapshotted nor restored.
+fn is_epoch(kind: &StateUpdateKindRaw) -> bool {
+    matches!(
+        <StateUpdateKindRaw as TryIntoStateUpdateKind>::try_into(kind.clone()),
+        Ok(StateUpdateKind::Epoch(_))
+    )
+}
+
 /// Certain large collections are only needed during startup. This struct helps us cache these
 /// values during startup and ignore them at all other times.
 #[derive(Debug)]
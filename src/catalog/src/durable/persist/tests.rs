@@ -7,13 +7,22 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::Arc;
+
+use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::NOW_ZERO;
 use mz_persist_client::cache::PersistClientCache;
-use mz_persist_client::PersistLocation;
+use mz_persist_client::{PersistClient, PersistLocation};
 use uuid::Uuid;
 
-use crate::durable::persist::{fetch_catalog_upgrade_shard_version, shard_id, UPGRADE_SEED};
-use crate::durable::{test_bootstrap_args, test_persist_backed_catalog_state_with_version};
+use crate::durable::persist::{
+    fetch_catalog_upgrade_shard_version, shard_id, UnopenedPersistCatalogState, UPGRADE_SEED,
+};
+use crate::durable::{
+    test_bootstrap_args, test_persist_backed_catalog_state,
+    test_persist_backed_catalog_state_with_version, CatalogError, DurableCatalogError, Metrics,
+    OpenableDurableCatalogState, CATALOG_VERSION,
+};
 
 #[mz_ore::test(tokio::test)]
 #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
@@ -95,3 +104,122 @@ async fn test_upgrade_shard() {
         "opening a readonly catalog should not increment the upgrade version"
     );
 }
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_catalog_snapshot() {
+    let organization_id = Uuid::new_v4();
+    let deploy_generation = 0;
+    let persist_client = PersistClient::new_for_tests().await;
+    async fn unopened_state(
+        persist_client: &PersistClient,
+        organization_id: Uuid,
+    ) -> UnopenedPersistCatalogState {
+        let metrics = Arc::new(Metrics::new(&MetricsRegistry::new()));
+        UnopenedPersistCatalogState::new(
+            persist_client.clone(),
+            organization_id,
+            semver::Version::new(0, 0, 0),
+            metrics,
+        )
+        .await
+        .expect("failed to create persist catalog")
+    }
+
+    let _persist_state = test_persist_backed_catalog_state(persist_client.clone(), organization_id)
+        .await
+        .open(NOW_ZERO(), &test_bootstrap_args(), deploy_generation, None)
+        .await
+        .expect("failed to open persist catalog");
+
+    // Opening a catalog that doesn't need migrations doesn't take a snapshot.
+    let mut state = unopened_state(&persist_client, organization_id).await;
+    assert_eq!(
+        state
+            .get_catalog_snapshot_metadata()
+            .await
+            .expect("failed to read snapshot"),
+        None
+    );
+    let err = state
+        .rollback_to_snapshot()
+        .await
+        .expect_err("rolled back without a snapshot");
+    assert!(
+        matches!(
+            err,
+            CatalogError::Durable(DurableCatalogError::MissingSnapshot)
+        ),
+        "{err:?}"
+    );
+
+    state
+        .write_catalog_snapshot(CATALOG_VERSION - 1, 42)
+        .await
+        .expect("failed to write snapshot");
+    let metadata = state
+        .get_catalog_snapshot_metadata()
+        .await
+        .expect("failed to read snapshot")
+        .expect("missing snapshot");
+    assert_eq!(metadata.catalog_version, CATALOG_VERSION - 1);
+    assert_eq!(metadata.deploy_generation, Some(deploy_generation));
+    assert_eq!(metadata.taken_at, 42);
+    assert!(metadata.updates > 0);
+
+    // A later snapshot by the same version at a newer catalog version belongs to an interrupted
+    // migration, so the original snapshot is kept.
+    state
+        .write_catalog_snapshot(CATALOG_VERSION, 43)
+        .await
+        .expect("failed to write snapshot");
+    assert_eq!(
+        state
+            .get_catalog_snapshot_metadata()
+            .await
+            .expect("failed to read snapshot"),
+        Some(metadata.clone())
+    );
+
+    let mut persist_state =
+        test_persist_backed_catalog_state(persist_client.clone(), organization_id)
+            .await
+            .open(NOW_ZERO(), &test_bootstrap_args(), deploy_generation, None)
+            .await
+            .expect("failed to open persist catalog");
+    let id = persist_state
+        .allocate_user_id()
+        .await
+        .expect("failed to allocate id");
+
+    // Rolling back discards the allocation and fences out the open catalog.
+    let mut state = unopened_state(&persist_client, organization_id).await;
+    assert_eq!(
+        state
+            .rollback_to_snapshot()
+            .await
+            .expect("failed to roll back"),
+        metadata
+    );
+    let err = persist_state
+        .allocate_user_id()
+        .await
+        .expect_err("rolled back catalog should be fenced");
+    assert!(
+        matches!(err, CatalogError::Durable(DurableCatalogError::Fence(_))),
+        "{err:?}"
+    );
+
+    let mut persist_state = test_persist_backed_catalog_state(persist_client, organization_id)
+        .await
+        .open(NOW_ZERO(), &test_bootstrap_args(), deploy_generation, None)
+        .await
+        .expect("failed to open rolled back persist catalog");
+    assert_eq!(
+        persist_state
+            .allocate_user_id()
+            .await
+            .expect("failed to allocate id"),
+        id
+    );
+}
@@ -250,6 +250,7 @@ impl<'a> Transaction<'a> {
                 owner_id,
                 privileges,
                 oid,
+                default_cluster_id: None,
             },
             self.op_id,
         ) {
@@ -1055,6 +1056,14 @@ impl<'a> Transaction<'a> {
         Ok(())
     }
 
+    /// Removes all audit log events in `events` from the transaction.
+    pub fn remove_audit_log_events(&mut self, events: impl IntoIterator<Item = VersionedEvent>) {
+        let events = events
+            .into_iter()
+            .map(|event| (AuditLogKey { event }, -1, self.op_id));
+        self.audit_log_updates.extend(events);
+    }
+
     /// Removes all storage usage events in `events` from the transaction.
     pub(crate) fn remove_storage_usage_events(&mut self, events: Vec<VersionedStorageUsage>) {
         let events = events
@@ -170,14 +170,14 @@ macro_rules! objects {
     }
 }
 
-objects!(v54, v55, v56, v57, v58, v59, v60, v61);
+objects!(v54, v55, v56, v57, v58, v59, v60, v61, v62);
 
 /// The current version of the `Catalog`.
 ///
 /// We will initialize new `Catalog`es with this version, and migrate existing `Catalog`es to this
 /// version. Whenever the `Catalog` changes, e.g. the protobufs we serialize in the `Catalog`
 /// change, we need to bump this version.
-pub const CATALOG_VERSION: u64 = 61;
+pub const CATALOG_VERSION: u64 = 62;
 
 /// The minimum `Catalog` version number that we support migrating from.
 ///
@@ -196,6 +196,7 @@ mod v57_to_v58;
 mod v58_to_v59;
 mod v59_to_v60;
 mod v60_to_v61;
+mod v61_to_v62;
 
 /// Describes a single action to take during a migration from `V1` to `V2`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -297,6 +298,9 @@ async fn run_upgrade(
         60 => {
             run_versioned_upgrade(unopened_catalog_state, mode, version, v60_to_v61::upgrade).await
         }
+        61 => {
+            run_versioned_upgrade(unopened_catalog_state, mode, version, v61_to_v62::upgrade).await
+        }
 
         // Up-to-date, no migration needed!
         CATALOG_VERSION => Ok(CATALOG_VERSION),
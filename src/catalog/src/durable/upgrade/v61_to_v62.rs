@@ -0,0 +1,66 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_proto::wire_compatible;
+use mz_proto::wire_compatible::WireCompatible;
+
+use crate::durable::upgrade::MigrationAction;
+use crate::durable::upgrade::{objects_v61 as v61, objects_v62 as v62};
+
+wire_compatible!(v61::DatabaseKey with v62::DatabaseKey);
+wire_compatible!(v61::RoleId with v62::RoleId);
+wire_compatible!(v61::MzAclItem with v62::MzAclItem);
+
+/// In v62, we added a new optional `default_cluster_id` field to `DatabaseValue`.
+pub fn upgrade(
+    snapshot: Vec<v61::StateUpdateKind>,
+) -> Vec<MigrationAction<v61::StateUpdateKind, v62::StateUpdateKind>> {
+    snapshot
+        .into_iter()
+        .filter_map(|update| {
+            let v61::state_update_kind::Kind::Database(v61::state_update_kind::Database {
+                key,
+                value,
+            }) = update.kind.as_ref().expect("missing field")
+            else {
+                return None;
+            };
+
+            let old = v61::StateUpdateKind {
+                kind: Some(v61::state_update_kind::Kind::Database(
+                    v61::state_update_kind::Database {
+                        key: key.clone(),
+                        value: value.clone(),
+                    },
+                )),
+            };
+
+            let new = v62::StateUpdateKind {
+                kind: Some(v62::state_update_kind::Kind::Database(
+                    v62::state_update_kind::Database {
+                        key: key.as_ref().map(WireCompatible::convert),
+                        value: value.as_ref().map(|old_val| v62::DatabaseValue {
+                            name: old_val.name.clone(),
+                            owner_id: old_val.owner_id.as_ref().map(WireCompatible::convert),
+                            privileges: old_val
+                                .privileges
+                                .iter()
+                                .map(WireCompatible::convert)
+                                .collect(),
+                            oid: old_val.oid,
+                            default_cluster_id: None,
+                        }),
+                    },
+                )),
+            };
+
+            Some(MigrationAction::Update(old, new))
+        })
+        .collect()
+}
@@ -78,6 +78,7 @@ pub struct Database {
     pub schemas_by_name: BTreeMap<String, SchemaId>,
     pub owner_id: RoleId,
     pub privileges: PrivilegeMap,
+    pub default_cluster_id: Option<ClusterId>,
 }
 
 impl From<Database> for durable::Database {
@@ -88,6 +89,7 @@ impl From<Database> for durable::Database {
             name: database.name,
             owner_id: database.owner_id,
             privileges: database.privileges.into_all_values().collect(),
+            default_cluster_id: database.default_cluster_id,
         }
     }
 }
@@ -100,6 +102,7 @@ impl From<durable::Database> for Database {
             name,
             owner_id,
             privileges,
+            default_cluster_id,
         }: durable::Database,
     ) -> Database {
         Database {
@@ -110,6 +113,7 @@ impl From<durable::Database> for Database {
             name,
             owner_id,
             privileges: PrivilegeMap::from_mz_acl_items(privileges),
+            default_cluster_id,
         }
     }
 }
@@ -123,6 +127,7 @@ impl UpdateFrom<durable::Database> for Database {
             name,
             owner_id,
             privileges,
+            default_cluster_id,
         }: durable::Database,
     ) {
         self.id = id;
@@ -130,6 +135,7 @@ impl UpdateFrom<durable::Database> for Database {
         self.name = name;
         self.owner_id = owner_id;
         self.privileges = PrivilegeMap::from_mz_acl_items(privileges);
+        self.default_cluster_id = default_cluster_id;
     }
 }
 
@@ -543,6 +549,12 @@ pub struct Table {
     /// Whether the table's logical compaction window is controlled by
     /// METRICS_RETENTION
     pub is_retained_metrics_object: bool,
+    /// Whether writes to the table are recorded in
+    /// `mz_internal.mz_table_change_audit`.
+    pub change_audit: bool,
+    /// The number of rows that the optimizer assumes the table to have, as set with the
+    /// `ROW COUNT HINT` option.
+    pub row_count_hint: Option<u64>,
 }
 
 impl Table {
@@ -832,13 +844,19 @@ impl Sink {
     /// both the same (either avro or json), we return the value format name,
     /// otherwise we return a composite name.
     pub fn combined_format(&self) -> Cow<'_, str> {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        connection.format.get_format_name()
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => connection.format.get_format_name(),
+            // Webhook sinks always deliver JSON.
+            StorageSinkConnection::Webhook(_) => Cow::Borrowed("json"),
+        }
     }
 
     /// Output distinct key_format and value_format of the sink.
     pub fn formats(&self) -> (Option<&str>, &str) {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
+        let connection = match &self.connection {
+            StorageSinkConnection::Kafka(connection) => connection,
+            StorageSinkConnection::Webhook(_) => return (None, "json"),
+        };
         let key_format = connection
             .format
             .key_format
@@ -885,6 +903,8 @@ pub struct Index {
     pub create_sql: String,
     pub on: GlobalId,
     pub keys: Vec<MirScalarExpr>,
+    /// The predicate of a partial index, which only contains the rows of `on` that satisfy it.
+    pub predicate: Option<MirScalarExpr>,
     pub conn_id: Option<ConnectionId>,
     pub resolved_ids: ResolvedIds,
     pub cluster_id: ClusterId,
@@ -1300,6 +1320,47 @@ impl CatalogItem {
         Ok(res)
     }
 
+    /// Sets the `ROW COUNT HINT` option of a table to `value`, or removes it if `value` is `None`.
+    /// Returns the previous value of the option, or an error if this is not a table.
+    pub fn update_row_count_hint(
+        &mut self,
+        value: Option<Value>,
+        hint: Option<u64>,
+    ) -> Result<Option<WithOptionValue<Raw>>, ()> {
+        let update = |ast: &mut Statement<Raw>| {
+            let Statement::CreateTable(stmt) = ast else {
+                return Err(());
+            };
+            // In case there are ever multiple, look for the last one.
+            let pos = stmt
+                .with_options
+                .iter()
+                .rposition(|o| o.name == mz_sql_parser::ast::TableOptionName::RowCountHint);
+            let previous = match (value, pos) {
+                (Some(value), Some(idx)) => stmt.with_options[idx]
+                    .value
+                    .replace(WithOptionValue::Value(value)),
+                (Some(value), None) => {
+                    stmt.with_options.push(mz_sql_parser::ast::TableOption {
+                        name: mz_sql_parser::ast::TableOptionName::RowCountHint,
+                        value: Some(WithOptionValue::Value(value)),
+                    });
+                    None
+                }
+                (None, Some(idx)) => stmt.with_options.swap_remove(idx).value,
+                (None, None) => None,
+            };
+            Ok(previous)
+        };
+
+        let previous = self.update_sql(update)?;
+        let CatalogItem::Table(table) = self else {
+            return Err(());
+        };
+        table.row_count_hint = hint;
+        Ok(previous)
+    }
+
     /// Updates the create_sql field of this item. Returns an error if this is a builtin item,
     /// otherwise returns f's result.
     pub fn update_sql<F, T>(&mut self, f: F) -> Result<T, ()>
@@ -2161,6 +2222,10 @@ impl mz_sql::catalog::CatalogDatabase for Database {
     fn privileges(&self) -> &PrivilegeMap {
         &self.privileges
     }
+
+    fn default_cluster_id(&self) -> Option<ClusterId> {
+        self.default_cluster_id
+    }
 }
 
 impl mz_sql::catalog::CatalogSchema for Schema {
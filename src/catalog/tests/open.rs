@@ -296,6 +296,7 @@ async fn test_open_savepoint(
                     name: format!("db{i}"),
                     owner_id: RoleId::User(i),
                     privileges: Vec::new(),
+                    default_cluster_id: None,
                 },
                 Schema {
                     id: schema_id,
@@ -168,6 +168,71 @@ async fn test_get_and_prune_storage_usage(openable_state: Box<dyn OpenableDurabl
     Box::new(state).expire().await;
 }
 
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_persist_remove_audit_log_events() {
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let openable_state =
+        test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+    test_remove_audit_log_events(openable_state).await;
+}
+
+async fn test_remove_audit_log_events(openable_state: Box<dyn OpenableDurableCatalogState>) {
+    let old_event = VersionedEvent::V1(EventV1 {
+        id: 100,
+        event_type: EventType::Create,
+        object_type: mz_audit_log::ObjectType::View,
+        details: EventDetails::IdNameV1(IdNameV1 {
+            id: "1".to_string(),
+            name: "v".to_string(),
+        }),
+        user: Some("joe".to_string()),
+        occurred_at: 100,
+    });
+    let recent_event = VersionedEvent::V1(EventV1 {
+        id: 200,
+        event_type: EventType::Drop,
+        object_type: mz_audit_log::ObjectType::View,
+        details: EventDetails::IdNameV1(IdNameV1 {
+            id: "1".to_string(),
+            name: "v".to_string(),
+        }),
+        user: Some("joe".to_string()),
+        occurred_at: 200,
+    });
+
+    let deploy_generation = 0;
+    let mut state = openable_state
+        .open(
+            SYSTEM_TIME(),
+            &test_bootstrap_args(),
+            deploy_generation,
+            None,
+        )
+        .await
+        .unwrap();
+    // Drain initial updates.
+    let _ = state
+        .sync_to_current_updates()
+        .await
+        .expect("unable to sync");
+    let mut txn = state.transaction().await.unwrap();
+    txn.insert_audit_log_events([old_event.clone(), recent_event.clone()]);
+    // Drain txn updates.
+    let _ = txn.get_and_commit_op_updates();
+    txn.commit().await.unwrap();
+
+    let mut txn = state.transaction().await.unwrap();
+    txn.remove_audit_log_events([old_event]);
+    // Drain txn updates.
+    let _ = txn.get_and_commit_op_updates();
+    txn.commit().await.unwrap();
+
+    assert_eq!(state.get_audit_logs().await.unwrap(), vec![recent_event]);
+    Box::new(state).expire().await;
+}
+
 #[mz_ore::test(tokio::test)]
 #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
 async fn test_persist_allocate_id() {
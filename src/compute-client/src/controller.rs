@@ -28,7 +28,7 @@
 //! from compacting beyond the allowed compaction of each of its outputs, ensuring that we can
 //! recover each dataflow to its current state in case of failure or other reconfiguration.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroI64;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
@@ -61,8 +61,8 @@ use uuid::Uuid;
 
 use crate::controller::error::{
     CollectionLookupError, CollectionMissing, CollectionUpdateError, DataflowCreationError,
-    InstanceExists, InstanceMissing, PeekError, ReadPolicyError, ReplicaCreationError,
-    ReplicaDropError, SubscribeTargetError,
+    HydrationPauseError, InstanceExists, InstanceMissing, PeekError, ReadPolicyError,
+    ReplicaCreationError, ReplicaDropError, SubscribeTargetError,
 };
 use crate::controller::instance::Instance;
 use crate::controller::replica::ReplicaConfig;
@@ -115,6 +115,17 @@ pub enum ComputeControllerResponse<T> {
         /// The new upper frontier of the identified compute collection.
         upper: Antichain<T>,
     },
+    /// A response reporting that a sink failed on a replica.
+    ///
+    /// See [`crate::protocol::response::StatusResponse::SinkFailure`].
+    SinkFailure {
+        /// The ID of the failed sink.
+        id: GlobalId,
+        /// The ID of the replica on which the sink failed.
+        replica_id: ReplicaId,
+        /// A description of the failure.
+        error: String,
+    },
 }
 
 /// Replica configuration
@@ -362,6 +373,28 @@ impl<T: ComputeControllerTimestamp> ComputeController<T> {
         result
     }
 
+    /// Returns the IDs of the replicas of the given cluster on which all
+    /// non-transient collections are hydrated.
+    pub fn hydrated_replicas(
+        &self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<BTreeSet<ReplicaId>, InstanceMissing> {
+        Ok(self.instance(instance_id)?.hydrated_replicas().collect())
+    }
+
+    /// Returns the fraction of the non-transient collections of the given
+    /// cluster that are hydrated on the given replica, or `None` if the
+    /// replica does not exist.
+    pub fn replica_hydration_fraction(
+        &self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+    ) -> Result<Option<f64>, InstanceMissing> {
+        Ok(self
+            .instance(instance_id)?
+            .replica_hydration_fraction(replica_id))
+    }
+
     /// Returns `true` iff all collections have their write frontier (aka.
     /// upper) within `allowed_lag` of the "live" frontier reported in
     /// `live_frontiers`. The "live" frontiers are frontiers as reported by a
@@ -683,6 +716,54 @@ where
         Ok(())
     }
 
+    /// Pauses or resumes the emission of responses for a subscribe.
+    ///
+    /// Replicas hold back the updates of a paused subscribe until it is resumed, at which point
+    /// they report all held-back updates in a single batch.
+    pub fn set_subscribe_paused(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        subscribe_id: GlobalId,
+        paused: bool,
+    ) -> Result<(), CollectionUpdateError> {
+        self.instance_mut(instance_id)?
+            .set_subscribe_paused(subscribe_id, paused)?;
+        Ok(())
+    }
+
+    /// Pauses or resumes the hydration of a collection on a replica.
+    ///
+    /// A paused collection is not scheduled on the replica when the replica reconnects, so its
+    /// dataflow stays suspended there and doesn't consume resources. If the replica already runs
+    /// the dataflow, it is not affected. Other replicas are not affected either.
+    pub fn set_collection_hydration_paused(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+        collection_id: GlobalId,
+        paused: bool,
+    ) -> Result<(), HydrationPauseError> {
+        self.instance_mut(instance_id)?
+            .set_collection_hydration_paused(replica_id, collection_id, paused)?;
+        Ok(())
+    }
+
+    /// Returns the IDs of the non-transient collections of the given cluster that the given
+    /// replica is hydrating, or `None` if the replica does not exist.
+    ///
+    /// If the replica failed since the last call, returns the collections that were hydrating at
+    /// the time of the failure. Collections queued by sequential hydration and collections whose
+    /// hydration is paused on the replica are not included.
+    pub fn take_hydrating_collections(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+    ) -> Result<Option<BTreeSet<GlobalId>>, InstanceMissing> {
+        Ok(self
+            .instance_mut(instance_id)?
+            .take_hydrating_collections(replica_id))
+    }
+
     /// Adds replicas of an instance.
     pub fn add_replica_to_instance(
         &mut self,
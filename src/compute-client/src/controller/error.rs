@@ -251,6 +251,36 @@ impl From<instance::ReadPolicyError> for ReadPolicyError {
     }
 }
 
+/// Errors arising during pausing or resuming the hydration of a collection.
+#[derive(Error, Debug)]
+pub enum HydrationPauseError {
+    /// TODO(#25239): Add documentation.
+    #[error("instance does not exist: {0}")]
+    InstanceMissing(ComputeInstanceId),
+    /// TODO(#25239): Add documentation.
+    #[error("collection does not exist: {0}")]
+    CollectionMissing(GlobalId),
+    /// TODO(#25239): Add documentation.
+    #[error("replica does not exist: {0}")]
+    ReplicaMissing(ReplicaId),
+}
+
+impl From<InstanceMissing> for HydrationPauseError {
+    fn from(error: InstanceMissing) -> Self {
+        Self::InstanceMissing(error.0)
+    }
+}
+
+impl From<instance::HydrationPauseError> for HydrationPauseError {
+    fn from(error: instance::HydrationPauseError) -> Self {
+        use instance::HydrationPauseError::*;
+        match error {
+            CollectionMissing(id) => Self::CollectionMissing(id),
+            ReplicaMissing(id) => Self::ReplicaMissing(id),
+        }
+    }
+}
+
 /// Errors arising during subscribe target assignment.
 #[derive(Error, Debug)]
 pub enum SubscribeTargetError {
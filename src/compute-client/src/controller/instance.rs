@@ -27,7 +27,7 @@ use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSin
 use mz_compute_types::sources::SourceInstanceDesc;
 use mz_dyncfg::ConfigSet;
 use mz_expr::RowSetFinishing;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 use mz_ore::collections::CollectionExt;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::refresh_schedule::RefreshSchedule;
@@ -136,6 +136,20 @@ impl From<CollectionMissing> for ReadPolicyError {
     }
 }
 
+#[derive(Error, Debug)]
+pub(super) enum HydrationPauseError {
+    #[error("collection does not exist: {0}")]
+    CollectionMissing(GlobalId),
+    #[error("replica does not exist: {0}")]
+    ReplicaMissing(ReplicaId),
+}
+
+impl From<CollectionMissing> for HydrationPauseError {
+    fn from(error: CollectionMissing) -> Self {
+        Self::CollectionMissing(error.0)
+    }
+}
+
 #[derive(Error, Debug)]
 pub(super) enum SubscribeTargetError {
     #[error("subscribe does not exist: {0}")]
@@ -213,6 +227,16 @@ pub(super) struct Instance<T> {
     envd_epoch: NonZeroI64,
     /// Numbers that increase with each restart of a replica.
     replica_epochs: BTreeMap<ReplicaId, u64>,
+    /// The collections whose hydration is paused, by replica.
+    ///
+    /// Paused collections are not scheduled on their replica when it is rehydrated. Entries
+    /// survive the rehydration of a replica and are removed when the replica is dropped.
+    hydration_paused: BTreeMap<ReplicaId, BTreeSet<GlobalId>>,
+    /// The collections that were hydrating on failed replicas at the time of their last failure.
+    ///
+    /// Rehydration resets the hydration state of a replica, so this is the only place that still
+    /// knows which dataflows a replica was working on when it crashed.
+    hydrating_at_failure: BTreeMap<ReplicaId, BTreeSet<GlobalId>>,
     /// The registry the controller uses to report metrics.
     metrics: InstanceMetrics,
     /// A function that compute the lag between the given time and wallclock time.
@@ -310,6 +334,12 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
         for replica in self.replicas.values_mut() {
             replica.remove_collection(id);
         }
+        for paused in self.hydration_paused.values_mut() {
+            paused.remove(&id);
+        }
+        for hydrating in self.hydrating_at_failure.values_mut() {
+            hydrating.remove(&id);
+        }
 
         // Remove global collection state.
         self.collections.remove(&id);
@@ -574,6 +604,94 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
         all_hydrated
     }
 
+    /// Returns the IDs of the replicas on which all non-transient collections
+    /// are hydrated.
+    pub fn hydrated_replicas(&self) -> impl Iterator<Item = ReplicaId> + '_ {
+        self.replicas
+            .iter()
+            .filter(|(_, replica_state)| {
+                self.collections_iter()
+                    .filter(|(id, _)| !id.is_transient())
+                    .all(|(id, _)| {
+                        replica_state
+                            .collections
+                            .get(id)
+                            .map_or(false, |collection_state| collection_state.hydrated())
+                    })
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Returns the IDs of the non-transient collections that the given replica is hydrating, or
+    /// `None` if the replica does not exist.
+    ///
+    /// If the replica failed since the last call, the collections that were hydrating at the time
+    /// of the failure are returned, since rehydration reset the hydration state of the replica.
+    /// Collections that are paused on the replica, or that are queued behind other collections
+    /// by sequential hydration, are not hydrating.
+    pub fn take_hydrating_collections(
+        &mut self,
+        replica_id: ReplicaId,
+    ) -> Option<BTreeSet<GlobalId>> {
+        let replica_state = self.replicas.get(&replica_id)?;
+        if let Some(hydrating) = self.hydrating_at_failure.remove(&replica_id) {
+            return Some(hydrating);
+        }
+        Some(self.hydrating_collections(replica_state))
+    }
+
+    /// Returns the IDs of the non-transient collections the given replica is hydrating.
+    fn hydrating_collections(&self, replica_state: &ReplicaState<T>) -> BTreeSet<GlobalId> {
+        let paused = self.hydration_paused.get(&replica_state.id);
+        let queued = replica_state.client.queued_for_hydration();
+
+        self.collections_iter()
+            .filter(|(id, collection)| {
+                !id.is_transient() && !collection.log_collection && collection.scheduled
+            })
+            .filter(|(id, _)| {
+                !replica_state
+                    .collections
+                    .get(id)
+                    .map_or(false, |collection_state| collection_state.hydrated())
+            })
+            .filter(|(id, _)| !paused.map_or(false, |paused| paused.contains(id)))
+            .filter(|(id, _)| !queued.contains(id))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Returns the fraction of the non-transient collections that are
+    /// hydrated on the given replica, or `None` if the replica does not exist.
+    ///
+    /// A replica is considered hydrated if there are no non-transient
+    /// collections.
+    pub fn replica_hydration_fraction(&self, replica_id: ReplicaId) -> Option<f64> {
+        let replica_state = self.replicas.get(&replica_id)?;
+
+        let mut collections: usize = 0;
+        let mut hydrated: usize = 0;
+        for (id, _collection) in self.collections_iter() {
+            if id.is_transient() {
+                continue;
+            }
+            collections += 1;
+            if replica_state
+                .collections
+                .get(id)
+                .map_or(false, |collection_state| collection_state.hydrated())
+            {
+                hydrated += 1;
+            }
+        }
+
+        if collections == 0 {
+            Some(1.0)
+        } else {
+            Some(f64::cast_lossy(hydrated) / f64::cast_lossy(collections))
+        }
+    }
+
     /// Returns `true` iff all collections have their write frontier (aka.
     /// upper) within `allowed_lag` of the "live" frontier reported in
     /// `live_frontiers`. The "live" frontiers are frontiers as reported by a
@@ -715,6 +833,8 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             introspection_tx: _,
             envd_epoch,
             replica_epochs,
+            hydration_paused,
+            hydrating_at_failure,
             metrics: _,
             wallclock_lag: _,
             dyncfg: _,
@@ -749,6 +869,14 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             .iter()
             .map(|(id, epoch)| (id.to_string(), epoch))
             .collect();
+        let hydration_paused: BTreeMap<_, _> = hydration_paused
+            .iter()
+            .map(|(id, ids)| (id.to_string(), format!("{ids:?}")))
+            .collect();
+        let hydrating_at_failure: BTreeMap<_, _> = hydrating_at_failure
+            .iter()
+            .map(|(id, ids)| (id.to_string(), format!("{ids:?}")))
+            .collect();
 
         let map = serde_json::Map::from_iter([
             field("initialized", initialized)?,
@@ -760,6 +888,8 @@ impl<T: ComputeControllerTimestamp> Instance<T> {
             field("copy_tos", copy_tos)?,
             field("envd_epoch", envd_epoch)?,
             field("replica_epochs", replica_epochs)?,
+            field("hydration_paused", hydration_paused)?,
+            field("hydrating_at_failure", hydrating_at_failure)?,
         ]);
         Ok(serde_json::Value::Object(map))
     }
@@ -807,6 +937,8 @@ where
             introspection_tx,
             envd_epoch,
             replica_epochs: Default::default(),
+            hydration_paused: Default::default(),
+            hydrating_at_failure: Default::default(),
             metrics,
             wallclock_lag,
             dyncfg,
@@ -879,7 +1011,18 @@ where
         self.history.push(cmd.clone());
 
         // Clone the command for each active replica.
-        for replica in self.replicas.values_mut() {
+        for (replica_id, replica) in &mut self.replicas {
+            // Collections whose hydration is paused on a replica are not scheduled there.
+            if let ComputeCommand::Schedule(id) = &cmd {
+                if self
+                    .hydration_paused
+                    .get(replica_id)
+                    .map_or(false, |paused| paused.contains(id))
+                {
+                    continue;
+                }
+            }
+
             // If sending the command fails, the replica requires rehydration.
             if replica.client.send(cmd.clone()).is_err() {
                 replica.failed = true;
@@ -945,6 +1088,63 @@ where
         Ok(())
     }
 
+    /// Pauses or resumes the emission of responses for the identified subscribe.
+    ///
+    /// Replicas hold back the updates of a paused subscribe until it is resumed.
+    pub fn set_subscribe_paused(
+        &mut self,
+        id: GlobalId,
+        paused: bool,
+    ) -> Result<(), CollectionMissing> {
+        if !self.subscribes.contains_key(&id) {
+            return Err(CollectionMissing(id));
+        }
+
+        self.send(ComputeCommand::PauseSubscribe { id, paused });
+        Ok(())
+    }
+
+    /// Pauses or resumes the hydration of the identified collection on the identified replica.
+    ///
+    /// While a collection is paused on a replica, the replica doesn't receive its `Schedule`
+    /// command when it is rehydrated. Resuming the collection schedules it on the replica, if the
+    /// collection is scheduled already.
+    pub fn set_collection_hydration_paused(
+        &mut self,
+        replica_id: ReplicaId,
+        id: GlobalId,
+        paused: bool,
+    ) -> Result<(), HydrationPauseError> {
+        if !self.replica_exists(replica_id) {
+            return Err(HydrationPauseError::ReplicaMissing(replica_id));
+        }
+        let scheduled = self.collection(id)?.scheduled;
+
+        let paused_ids = self.hydration_paused.entry(replica_id).or_default();
+        let changed = if paused {
+            paused_ids.insert(id)
+        } else {
+            paused_ids.remove(&id)
+        };
+        if paused_ids.is_empty() {
+            self.hydration_paused.remove(&replica_id);
+        }
+
+        if changed && !paused && scheduled {
+            // The replica didn't receive the `Schedule` command if it was rehydrated while the
+            // collection was paused. This command is not recorded in the history, which contains
+            // the `Schedule` command already.
+            let replica = self
+                .replicas
+                .get_mut(&replica_id)
+                .expect("replica must exist");
+            if replica.client.send(ComputeCommand::Schedule(id)).is_err() {
+                replica.failed = true;
+            }
+        }
+        Ok(())
+    }
+
     /// Add a new instance replica, by ID.
     pub fn add_replica(
         &mut self,
@@ -987,8 +1187,15 @@ where
         // Take this opportunity to clean up the history we should present.
         self.history.reduce();
 
-        // Replay the commands at the client, creating new dataflow identifiers.
+        // Replay the commands at the client, creating new dataflow identifiers. Collections whose
+        // hydration is paused on the replica are not scheduled.
+        let paused = self.hydration_paused.get(&id);
         for command in self.history.iter() {
+            if let ComputeCommand::Schedule(id) = command {
+                if paused.map_or(false, |paused| paused.contains(id)) {
+                    continue;
+                }
+            }
             if client.send(command.clone()).is_err() {
                 // We swallow the error here. On the next send, we will fail again, and
                 // restart the connection as well as this rehydration.
@@ -1005,6 +1212,17 @@ where
 
     /// Remove an existing instance replica, by ID.
     pub fn remove_replica(&mut self, id: ReplicaId) -> Result<(), ReplicaMissing> {
+        self.remove_replica_state(id)?;
+
+        // The replica is gone for good, so its paused collections are forgotten.
+        self.hydration_paused.remove(&id);
+        self.hydrating_at_failure.remove(&id);
+
+        Ok(())
+    }
+
+    /// Remove the state of an existing instance replica, by ID.
+    fn remove_replica_state(&mut self, id: ReplicaId) -> Result<(), ReplicaMissing> {
         self.replicas.remove(&id).ok_or(ReplicaMissing(id))?;
 
         // Remove frontier tracking for this replica.
@@ -1055,8 +1273,12 @@ where
     ///
     /// Panics if the specified replica does not exist.
     fn rehydrate_replica(&mut self, id: ReplicaId) {
-        let config = self.replicas[&id].config.clone();
-        self.remove_replica(id).expect("replica must exist");
+        let replica_state = &self.replicas[&id];
+        let config = replica_state.config.clone();
+        let hydrating = self.hydrating_collections(replica_state);
+        self.hydrating_at_failure.insert(id, hydrating);
+
+        self.remove_replica_state(id).expect("replica must exist");
         let result = self.add_replica(id, config);
 
         match result {
@@ -1255,6 +1477,7 @@ where
                 ComputeSinkConnection::CopyToS3Oneshot(conn) => {
                     ComputeSinkConnection::CopyToS3Oneshot(conn)
                 }
+                ComputeSinkConnection::Webhook(conn) => ComputeSinkConnection::Webhook(conn),
             };
             let desc = ComputeSinkDesc {
                 from: se.from,
@@ -2113,6 +2336,16 @@ where
             StatusResponse::OperatorHydration(status) => {
                 self.update_operator_hydration_status(replica_id, status)
             }
+            StatusResponse::SinkFailure(status) => {
+                // We might not be tracking this sink anymore because it was dropped.
+                if self.collections.contains_key(&status.collection_id) {
+                    self.deliver_response(ComputeControllerResponse::SinkFailure {
+                        id: status.collection_id,
+                        replica_id,
+                        error: status.error,
+                    });
+                }
+            }
         }
     }
 
@@ -9,6 +9,7 @@
 
 //! A client for replicas of a compute instance.
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -18,6 +19,7 @@ use mz_cluster_client::client::{ClusterReplicaLocation, ClusterStartupEpoch, Tim
 use mz_dyncfg::ConfigSet;
 use mz_ore::retry::Retry;
 use mz_ore::task::AbortOnDropHandle;
+use mz_repr::GlobalId;
 use mz_service::client::{GenericClient, Partitioned};
 use mz_service::params::GrpcClientParameters;
 use tokio::select;
@@ -25,7 +27,7 @@ use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tracing::{debug, info, trace, warn};
 
-use crate::controller::sequential_hydration::SequentialHydration;
+use crate::controller::sequential_hydration::{HydrationQueue, SequentialHydration};
 use crate::controller::{ComputeControllerTimestamp, ReplicaId};
 use crate::logging::LoggingConfig;
 use crate::metrics::ReplicaMetrics;
@@ -62,6 +64,8 @@ pub(super) struct ReplicaClient<T> {
     _task: AbortOnDropHandle<()>,
     /// Replica metrics.
     metrics: ReplicaMetrics,
+    /// The collections the replica has queued for hydration.
+    hydration_queue: HydrationQueue,
 }
 
 impl<T> ReplicaClient<T>
@@ -82,6 +86,7 @@ where
         // the replica.
         let (command_tx, command_rx) = unbounded_channel();
         let (response_tx, response_rx) = unbounded_channel();
+        let hydration_queue = HydrationQueue::default();
 
         let task = mz_ore::task::spawn(
             || format!("active-replication-replica-{id}"),
@@ -94,6 +99,7 @@ where
                 epoch,
                 metrics: metrics.clone(),
                 dyncfg,
+                hydration_queue: Arc::clone(&hydration_queue),
             }
             .run(),
         );
@@ -103,9 +109,16 @@ where
             response_rx,
             _task: task.abort_on_drop(),
             metrics,
+            hydration_queue,
         }
     }
 
+    /// Returns the IDs of the collections the replica has queued for hydration, i.e., the
+    /// collections whose `Schedule` commands are held back by sequential hydration.
+    pub(super) fn queued_for_hydration(&self) -> BTreeSet<GlobalId> {
+        self.hydration_queue.lock().expect("lock poisoned").clone()
+    }
+
     /// Sends a command to this replica.
     pub(super) fn send(
         &self,
@@ -147,6 +160,8 @@ struct ReplicaTask<T> {
     metrics: ReplicaMetrics,
     /// Dynamic system configuration.
     dyncfg: Arc<ConfigSet>,
+    /// The collections the replica has queued for hydration, shared with the `ReplicaClient`.
+    hydration_queue: HydrationQueue,
 }
 
 impl<T> ReplicaTask<T>
@@ -189,7 +204,8 @@ where
                         Ok(client) => {
                             let dyncfg = Arc::clone(&self.dyncfg);
                             let metrics = self.metrics.clone();
-                            Ok(SequentialHydration::new(client, dyncfg, metrics))
+                            let queue = Arc::clone(&self.hydration_queue);
+                            Ok(SequentialHydration::new(client, dyncfg, metrics, queue))
                         }
                         Err(e) => {
                             if state.i >= mz_service::retry::INFO_MIN_RETRIES {
@@ -38,8 +38,8 @@
 //!    observe all compute commands. Clients behind `PartitionedState` are not guaranteed to do so,
 //!    since commands are only forwarded to the first process.
 
-use std::collections::{BTreeMap, VecDeque};
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use mz_compute_types::dyncfgs::HYDRATION_CONCURRENCY;
@@ -62,6 +62,12 @@ use crate::service::ComputeClient;
 /// A shareable token.
 type Token = Arc<()>;
 
+/// The IDs of the collections a replica has queued for hydration, shared with the controller.
+///
+/// The set may contain collections that have since been dropped or hydrated before their
+/// `Schedule` command was released.
+pub(super) type HydrationQueue = Arc<Mutex<BTreeSet<GlobalId>>>;
+
 /// A client enforcing sequential dataflow hydration.
 #[derive(Debug)]
 pub(super) struct SequentialHydration<C, T> {
@@ -79,6 +85,8 @@ pub(super) struct SequentialHydration<C, T> {
     collections: BTreeMap<GlobalId, Collection<T>>,
     /// A queue of scheduled collections that are awaiting hydration.
     hydration_queue: VecDeque<GlobalId>,
+    /// The IDs of the collections in `hydration_queue`, shared with the controller.
+    shared_hydration_queue: HydrationQueue,
     /// A token held by hydrating collections.
     ///
     /// Useful to efficiently determine how many collections are currently in the process of
@@ -92,13 +100,26 @@ where
     T: ComputeControllerTimestamp,
 {
     /// Create a new `SequentialHydration` client.
-    pub fn new(client: C, dyncfg: Arc<ConfigSet>, metrics: ReplicaMetrics) -> Self {
+    ///
+    /// The `shared_hydration_queue` is cleared, since a new client starts with an empty queue.
+    pub fn new(
+        client: C,
+        dyncfg: Arc<ConfigSet>,
+        metrics: ReplicaMetrics,
+        shared_hydration_queue: HydrationQueue,
+    ) -> Self {
+        shared_hydration_queue
+            .lock()
+            .expect("lock poisoned")
+            .clear();
+
         Self {
             client,
             dyncfg,
             metrics,
             collections: Default::default(),
             hydration_queue: Default::default(),
+            shared_hydration_queue,
             hydration_token: Default::default(),
         }
     }
@@ -127,9 +148,17 @@ where
             }
             ComputeCommand::Schedule(id) => {
                 if let Some(collection) = self.collections.get_mut(id) {
-                    debug!(%id, "enqueuing collection for hydration");
-                    self.hydration_queue.push_back(*id);
-                    collection.set_scheduled();
+                    // Repeated `Schedule` commands for collections that are already queued or
+                    // hydrating are redundant.
+                    if collection.state == State::Created {
+                        debug!(%id, "enqueuing collection for hydration");
+                        self.hydration_queue.push_back(*id);
+                        self.shared_hydration_queue
+                            .lock()
+                            .expect("lock poisoned")
+                            .insert(*id);
+                        collection.set_scheduled();
+                    }
                     forward = false;
                 }
             }
@@ -138,6 +167,10 @@ where
                 // to ensure we don't produce any more commands for it.
                 if self.collections.remove(id).is_some() {
                     debug!(%id, "collection dropped");
+                    self.shared_hydration_queue
+                        .lock()
+                        .expect("lock poisoned")
+                        .remove(id);
                 }
             }
             _ => (),
@@ -206,6 +239,10 @@ where
                 // Hydration queue is empty.
                 break;
             };
+            self.shared_hydration_queue
+                .lock()
+                .expect("lock poisoned")
+                .remove(&id);
             let Some(collection) = self.collections.get_mut(&id) else {
                 // Collection has already been dropped.
                 continue;
@@ -303,6 +303,8 @@ pub enum ComputeLog {
     ErrorCount,
     /// Hydration times of exported collections.
     HydrationTime,
+    /// Mappings from the LIR nodes of dataflows to the operators that implement them.
+    LirMapping,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -321,6 +323,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::ShutdownDuration => ShutdownDuration(()),
                 ComputeLog::ErrorCount => ErrorCount(()),
                 ComputeLog::HydrationTime => HydrationTime(()),
+                ComputeLog::LirMapping => LirMapping(()),
             }),
         }
     }
@@ -339,6 +342,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(ShutdownDuration(())) => Ok(ComputeLog::ShutdownDuration),
             Some(ErrorCount(())) => Ok(ComputeLog::ErrorCount),
             Some(HydrationTime(())) => Ok(ComputeLog::HydrationTime),
+            Some(LirMapping(())) => Ok(ComputeLog::LirMapping),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -499,6 +503,14 @@ impl LogVariant {
                 .with_column("worker_id", ScalarType::UInt64.nullable(false))
                 .with_column("time_ns", ScalarType::UInt64.nullable(true))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Compute(ComputeLog::LirMapping) => RelationDesc::empty()
+                .with_column("dataflow_id", ScalarType::UInt64.nullable(false))
+                .with_column("lir_id", ScalarType::UInt64.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("operator_id_start", ScalarType::UInt64.nullable(false))
+                .with_column("operator_id_end", ScalarType::UInt64.nullable(false))
+                .with_key(vec![0, 1, 2]),
         }
     }
 }
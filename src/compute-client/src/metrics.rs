@@ -451,6 +451,8 @@ pub struct CommandMetrics<M> {
     pub peek: M,
     /// Metrics for `CancelPeek`.
     pub cancel_peek: M,
+    /// Metrics for `PauseSubscribe`.
+    pub pause_subscribe: M,
     /// Metrics for `InitializationComplete`.
     pub initialization_complete: M,
     /// Metrics for `UpdateConfiguration`.
@@ -473,6 +475,7 @@ impl<M> CommandMetrics<M> {
             allow_compaction: build_metric("allow_compaction"),
             peek: build_metric("peek"),
             cancel_peek: build_metric("cancel_peek"),
+            pause_subscribe: build_metric("pause_subscribe"),
             initialization_complete: build_metric("initialization_complete"),
             update_configuration: build_metric("update_configuration"),
             allow_writes: build_metric("allow_writes"),
@@ -492,6 +495,7 @@ impl<M> CommandMetrics<M> {
         f(&self.allow_compaction);
         f(&self.peek);
         f(&self.cancel_peek);
+        f(&self.pause_subscribe);
     }
 
     /// TODO(#25239): Add documentation.
@@ -509,6 +513,7 @@ impl<M> CommandMetrics<M> {
             Peek(_) => &self.peek,
             CancelPeek { .. } => &self.cancel_peek,
             AllowWrites { .. } => &self.allow_writes,
+            PauseSubscribe { .. } => &self.pause_subscribe,
         }
     }
 
@@ -526,6 +531,7 @@ impl<M> CommandMetrics<M> {
             InitializationComplete(_) => &self.initialization_complete,
             UpdateConfiguration(_) => &self.update_configuration,
             AllowWrites(_) => &self.allow_writes,
+            PauseSubscribe(_) => &self.pause_subscribe,
         }
     }
 }
@@ -96,6 +96,7 @@
 //!   - [`AllowCompaction`]
 //!   - [`Peek`]
 //!   - [`CancelPeek`]
+//!   - [`PauseSubscribe`]
 //!   - [`UpdateConfiguration`]
 //!
 //! The compute controller must respect dependencies between commands. For example, it must send a
@@ -123,6 +124,7 @@
 //! [`AllowWrites`]: self::command::ComputeCommand::AllowWrites
 //! [`Peek`]: self::command::ComputeCommand::Peek
 //! [`CancelPeek`]: self::command::ComputeCommand::CancelPeek
+//! [`PauseSubscribe`]: self::command::ComputeCommand::PauseSubscribe
 //! [`UpdateConfiguration`]: self::command::ComputeCommand::UpdateConfiguration
 //! [`ComputeResponse`]: self::response::ComputeResponse
 //! [`Canceled`]: self::response::PeekResponse::Canceled
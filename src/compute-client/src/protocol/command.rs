@@ -178,6 +178,8 @@ pub enum ComputeCommand<T = mz_repr::Timestamp> {
     ///
     /// It is also invalid to send a `Schedule` command that references a collection that has,
     /// through an `AllowCompaction` command, been allowed to compact to the empty frontier before.
+    ///
+    /// The replica ignores `Schedule` commands for collections that are already scheduled.
     Schedule(GlobalId),
 
     /// `AllowCompaction` informs the replica about the relaxation of external read capabilities on
@@ -267,6 +269,26 @@ pub enum ComputeCommand<T = mz_repr::Timestamp> {
         /// This Value must match a [`Peek::uuid`] value transmitted in a previous `Peek` command.
         uuid: Uuid,
     },
+
+    /// `PauseSubscribe` instructs the replica to pause or resume sending responses for the
+    /// identified subscribe.
+    ///
+    /// While a subscribe is paused, the replica keeps computing its updates but holds them back,
+    /// instead of reporting them through [`SubscribeResponse`]s. Once the subscribe is resumed, the
+    /// replica reports all held-back updates in a single batch. This allows the controller to
+    /// apply backpressure to subscribes whose clients consume updates more slowly than they are
+    /// produced.
+    ///
+    /// The replica must ignore `PauseSubscribe` commands that do not reference a subscribe it has
+    /// created through a corresponding `CreateDataflow` command before.
+    ///
+    /// [`SubscribeResponse`]: super::response::SubscribeResponse
+    PauseSubscribe {
+        /// The identifier of the subscribe to pause or resume.
+        id: GlobalId,
+        /// Whether the subscribe should be paused.
+        paused: bool,
+    },
 }
 
 impl RustType<ProtoComputeCommand> for ComputeCommand<mz_repr::Timestamp> {
@@ -295,6 +317,12 @@ impl RustType<ProtoComputeCommand> for ComputeCommand<mz_repr::Timestamp> {
                 ComputeCommand::Peek(peek) => Peek(peek.into_proto()),
                 ComputeCommand::CancelPeek { uuid } => CancelPeek(uuid.into_proto()),
                 ComputeCommand::AllowWrites => AllowWrites(()),
+                ComputeCommand::PauseSubscribe { id, paused } => {
+                    PauseSubscribe(ProtoPauseSubscribe {
+                        id: Some(id.into_proto()),
+                        paused: *paused,
+                    })
+                }
             }),
         }
     }
@@ -329,6 +357,12 @@ impl RustType<ProtoComputeCommand> for ComputeCommand<mz_repr::Timestamp> {
                 uuid: uuid.into_rust()?,
             }),
             Some(AllowWrites(())) => Ok(ComputeCommand::AllowWrites),
+            Some(PauseSubscribe(ProtoPauseSubscribe { id, paused })) => {
+                Ok(ComputeCommand::PauseSubscribe {
+                    id: id.into_rust_if_some("ProtoPauseSubscribe::id")?,
+                    paused,
+                })
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeCommand::kind",
             )),
@@ -359,6 +393,9 @@ impl Arbitrary for ComputeCommand<mz_repr::Timestamp> {
             any_uuid()
                 .prop_map(|uuid| ComputeCommand::CancelPeek { uuid })
                 .boxed(),
+            (any::<GlobalId>(), any::<bool>())
+                .prop_map(|(id, paused)| ComputeCommand::PauseSubscribe { id, paused })
+                .boxed(),
         ])
     }
 }
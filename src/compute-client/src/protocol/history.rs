@@ -89,6 +89,7 @@ where
         let mut created_dataflows = Vec::new();
         let mut scheduled_collections = Vec::new();
         let mut live_peeks = BTreeMap::new();
+        let mut paused_subscribes = BTreeSet::new();
 
         let mut create_inst_command = None;
         let mut create_timely_command = None;
@@ -137,6 +138,13 @@ where
                 ComputeCommand::AllowWrites => {
                     read_only = false;
                 }
+                ComputeCommand::PauseSubscribe { id, paused } => {
+                    if paused {
+                        paused_subscribes.insert(id);
+                    } else {
+                        paused_subscribes.remove(&id);
+                    }
+                }
             }
         }
 
@@ -180,7 +188,11 @@ where
             .iter()
             .flat_map(|d| d.export_ids())
             .collect();
-        scheduled_collections.retain(|id| retained_collections.contains(id));
+        // Repeated `Schedule` commands for the same collection are redundant.
+        let mut seen_collections = BTreeSet::new();
+        scheduled_collections
+            .retain(|id| retained_collections.contains(id) && seen_collections.insert(*id));
+        paused_subscribes.retain(|id| retained_collections.contains(id));
 
         // Reconstitute the commands as a compact history.
 
@@ -221,6 +233,13 @@ where
             self.commands.push(ComputeCommand::Schedule(id));
         }
 
+        let count = u64::cast_from(paused_subscribes.len());
+        command_counts.pause_subscribe.borrow().set(count);
+        for id in paused_subscribes {
+            self.commands
+                .push(ComputeCommand::PauseSubscribe { id, paused: true });
+        }
+
         let count = u64::cast_from(live_peeks.len());
         command_counts.peek.borrow().set(count);
         for peek in live_peeks.into_values() {
@@ -271,3 +290,58 @@ where
         self.commands.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_compute_types::dataflows::{DataflowDescription, IndexDesc};
+    use mz_compute_types::ComputeInstanceId;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_repr::{GlobalId, RelationType};
+
+    use crate::metrics::ComputeControllerMetrics;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_reduce_deduplicates_schedule_commands() {
+        let metrics = ComputeControllerMetrics::new(MetricsRegistry::new())
+            .for_instance(ComputeInstanceId::User(1))
+            .for_history();
+        let mut history = ComputeCommandHistory::<_, mz_repr::Timestamp>::new(metrics);
+
+        let id = GlobalId::User(1);
+        let dataflow = DataflowDescription {
+            source_imports: Default::default(),
+            index_imports: Default::default(),
+            objects_to_build: Default::default(),
+            index_exports: [(
+                id,
+                (
+                    IndexDesc {
+                        on_id: GlobalId::User(2),
+                        key: Vec::new(),
+                    },
+                    RelationType::empty(),
+                ),
+            )]
+            .into(),
+            sink_exports: Default::default(),
+            as_of: Some(Antichain::from_elem(mz_repr::Timestamp::MIN)),
+            until: Antichain::new(),
+            initial_storage_as_of: None,
+            refresh_schedule: None,
+            debug_name: "test".into(),
+        };
+        history.push(ComputeCommand::CreateDataflow(dataflow));
+        history.push(ComputeCommand::Schedule(id));
+        history.push(ComputeCommand::Schedule(id));
+        history.push(ComputeCommand::Schedule(id));
+        history.reduce();
+
+        let schedules = history
+            .iter()
+            .filter(|command| matches!(command, ComputeCommand::Schedule(_)))
+            .count();
+        assert_eq!(schedules, 1);
+    }
+}
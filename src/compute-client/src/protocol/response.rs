@@ -588,6 +588,8 @@ impl Arbitrary for SubscribeBatch<mz_repr::Timestamp> {
 pub enum StatusResponse {
     /// Reports the hydration status of dataflow operators.
     OperatorHydration(OperatorHydrationStatus),
+    /// Reports that a sink has failed and stopped writing.
+    SinkFailure(SinkFailureStatus),
 }
 
 impl RustType<ProtoStatusResponse> for StatusResponse {
@@ -596,6 +598,7 @@ impl RustType<ProtoStatusResponse> for StatusResponse {
 
         let kind = match self {
             Self::OperatorHydration(status) => Kind::OperatorHydration(status.into_proto()),
+            Self::SinkFailure(status) => Kind::SinkFailure(status.into_proto()),
         };
         ProtoStatusResponse { kind: Some(kind) }
     }
@@ -607,6 +610,7 @@ impl RustType<ProtoStatusResponse> for StatusResponse {
             Some(Kind::OperatorHydration(status)) => {
                 Ok(Self::OperatorHydration(status.into_rust()?))
             }
+            Some(Kind::SinkFailure(status)) => Ok(Self::SinkFailure(status.into_rust()?)),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStatusResponse::kind",
             )),
@@ -649,6 +653,35 @@ impl RustType<ProtoOperatorHydrationStatus> for OperatorHydrationStatus {
     }
 }
 
+/// A report that a sink has failed.
+///
+/// A failed sink stops writing, but keeps its dataflow installed until it is dropped.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SinkFailureStatus {
+    /// The ID of the failed sink.
+    pub collection_id: GlobalId,
+    /// A description of the failure.
+    pub error: String,
+}
+
+impl RustType<ProtoSinkFailureStatus> for SinkFailureStatus {
+    fn into_proto(&self) -> ProtoSinkFailureStatus {
+        ProtoSinkFailureStatus {
+            collection_id: Some(self.collection_id.into_proto()),
+            error: self.error.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkFailureStatus) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            collection_id: proto
+                .collection_id
+                .into_rust_if_some("ProtoSinkFailureStatus::collection_id")?,
+            error: proto.error,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_ore::assert_ok;
@@ -312,33 +312,40 @@ where
                 assert_none!(novel, "Duplicate peek response");
                 // We may be ready to respond.
                 if entry.len() == self.parts {
-                    let mut response = PeekResponse::Rows(RowCollection::default());
+                    let mut parts = Vec::with_capacity(self.parts);
+                    let mut error = None;
+                    let mut canceled = false;
                     for (_part, r) in std::mem::take(entry).into_iter() {
-                        response = match (response, r) {
-                            (_, PeekResponse::Canceled) => PeekResponse::Canceled,
-                            (PeekResponse::Canceled, _) => PeekResponse::Canceled,
-                            (_, PeekResponse::Error(e)) => PeekResponse::Error(e),
-                            (PeekResponse::Error(e), _) => PeekResponse::Error(e),
-                            (PeekResponse::Rows(mut rows), PeekResponse::Rows(other)) => {
-                                let total_byte_size =
-                                    rows.byte_len().saturating_add(other.byte_len());
-
-                                if total_byte_size > usize::cast_from(self.max_result_size) {
-                                    // Note: We match on this specific error message in tests
-                                    // so it's important that nothing else returns the same
-                                    // string.
-                                    let err = format!(
-                                        "total result exceeds max size of {}",
-                                        ByteSize::b(self.max_result_size)
-                                    );
-                                    PeekResponse::Error(err)
-                                } else {
-                                    rows.merge(&other);
-                                    PeekResponse::Rows(rows)
-                                }
-                            }
-                        };
+                        match r {
+                            PeekResponse::Rows(rows) => parts.push(rows),
+                            PeekResponse::Error(e) => error = Some(e),
+                            PeekResponse::Canceled => canceled = true,
+                        }
                     }
+                    let total_byte_size = parts
+                        .iter()
+                        .fold(0usize, |total, rows| total.saturating_add(rows.byte_len()));
+                    let response = if canceled {
+                        PeekResponse::Canceled
+                    } else if let Some(e) = error {
+                        PeekResponse::Error(e)
+                    } else if total_byte_size > usize::cast_from(self.max_result_size) {
+                        // Note: We match on this specific error message in tests
+                        // so it's important that nothing else returns the same
+                        // string.
+                        let err = format!(
+                            "total result exceeds max size of {}",
+                            ByteSize::b(self.max_result_size)
+                        );
+                        PeekResponse::Error(err)
+                    } else {
+                        // Concatenate the parts at once, which copies the rows at most once
+                        // rather than once per part, and not at all if only a single part
+                        // returned rows. From here on the rows stay in the concatenated blob:
+                        // the coordinator finishes the peek over it in place and pgwire encodes
+                        // rows straight out of it, see `RowIterator::next_bytes`.
+                        PeekResponse::Rows(RowCollection::concat(parts))
+                    };
                     self.peek_responses.remove(&uuid);
                     // We take the otel_ctx from the last peek, but they should all be the same
                     Some(Ok(ComputeResponse::PeekResponse(uuid, response, otel_ctx)))
@@ -599,3 +606,86 @@ impl<T: ComputeControllerTimestamp> PendingSubscribe<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use mz_ore::tracing::OpenTelemetryContext;
+    use mz_repr::{Datum, Timestamp};
+
+    use super::*;
+
+    fn rows(n: usize) -> PeekResponse {
+        let row = Row::pack_slice(&[Datum::String("hello world")]);
+        let rows = vec![(row, NonZeroUsize::new(1).unwrap()); n];
+        PeekResponse::Rows(RowCollection::new(&rows))
+    }
+
+    /// Absorbs the response of each part to a peek, in order, and returns what the partitioned
+    /// state emits after each of them.
+    fn absorb_peek(
+        state: &mut PartitionedComputeState<Timestamp>,
+        responses: Vec<PeekResponse>,
+    ) -> Vec<Option<PeekResponse>> {
+        responses
+            .into_iter()
+            .enumerate()
+            .map(|(part, response)| {
+                let response = ComputeResponse::PeekResponse(
+                    Uuid::nil(),
+                    response,
+                    OpenTelemetryContext::empty(),
+                );
+                match state.absorb_response(part, response) {
+                    None => None,
+                    Some(Ok(ComputeResponse::PeekResponse(_, response, _))) => Some(response),
+                    Some(response) => panic!("unexpected response: {response:?}"),
+                }
+            })
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_peek_response_max_result_size() {
+        let mut state =
+            <(ComputeCommand<Timestamp>, ComputeResponse<Timestamp>) as Partitionable<_, _>>::new(
+                3,
+            );
+        let part_size = match rows(10) {
+            PeekResponse::Rows(rows) => rows.byte_len(),
+            response => panic!("unexpected response: {response:?}"),
+        };
+        state.max_result_size = u64::cast_from(2 * part_size);
+
+        // Each part is within the max size, but together they exceed it. The size is only checked
+        // once all parts have responded.
+        let responses = absorb_peek(&mut state, vec![rows(10), rows(10), rows(10)]);
+        assert_eq!(responses[..2], [None, None]);
+        let limit = ByteSize::b(state.max_result_size);
+        assert_eq!(
+            responses[2],
+            Some(PeekResponse::Error(format!(
+                "total result exceeds max size of {limit}"
+            )))
+        );
+
+        // Within the max size, the parts are concatenated.
+        let responses = absorb_peek(&mut state, vec![rows(10), rows(0), rows(10)]);
+        match &responses[2] {
+            Some(PeekResponse::Rows(rows)) => assert_eq!(rows.count(0, None), 20),
+            response => panic!("unexpected response: {response:?}"),
+        }
+
+        // Errors of parts take precedence over the max size, and cancellations over errors,
+        // regardless of the order in which the parts respond.
+        let error = || PeekResponse::Error("boom".into());
+        let responses = absorb_peek(&mut state, vec![rows(10), rows(10), error()]);
+        assert_eq!(responses[2], Some(error()));
+        let responses = absorb_peek(&mut state, vec![error(), rows(10), rows(10)]);
+        assert_eq!(responses[2], Some(error()));
+        let responses = absorb_peek(&mut state, vec![PeekResponse::Canceled, error(), rows(10)]);
+        assert_eq!(responses[2], Some(PeekResponse::Canceled));
+        assert!(state.peek_responses.is_empty());
+    }
+}
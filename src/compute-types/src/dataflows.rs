@@ -193,15 +193,39 @@ impl<T> DataflowDescription<OptimizedMirRelationExpr, (), T> {
     /// Future uses of `import_index` in other dataflow descriptions may use `id`,
     /// as long as this dataflow has not been terminated in the meantime.
     pub fn export_index(&mut self, id: GlobalId, description: IndexDesc, on_type: RelationType) {
+        let input = MirRelationExpr::global_get(description.on_id, on_type.clone());
+        self.export_arrangement(id, description, on_type, input);
+    }
+
+    /// Exports as `id` an index described by `description` that only contains the rows of the
+    /// indexed collection that satisfy `predicate`.
+    ///
+    /// Importing the index in other dataflow descriptions only provides those rows.
+    pub fn export_filtered_index(
+        &mut self,
+        id: GlobalId,
+        description: IndexDesc,
+        on_type: RelationType,
+        predicate: MirScalarExpr,
+    ) {
+        let input =
+            MirRelationExpr::global_get(description.on_id, on_type.clone()).filter(vec![predicate]);
+        self.export_arrangement(id, description, on_type, input);
+    }
+
+    fn export_arrangement(
+        &mut self,
+        id: GlobalId,
+        description: IndexDesc,
+        on_type: RelationType,
+        input: MirRelationExpr,
+    ) {
         // We first create a "view" named `id` that ensures that the
         // data are correctly arranged and available for export.
         self.insert_plan(
             id,
             OptimizedMirRelationExpr::declare_optimized(MirRelationExpr::ArrangeBy {
-                input: Box::new(MirRelationExpr::global_get(
-                    description.on_id,
-                    on_type.clone(),
-                )),
+                input: Box::new(input),
                 keys: vec![description.key.clone()],
             }),
         );
@@ -125,6 +125,36 @@ pub const PERSIST_SINK_OBEY_READ_ONLY: Config<bool> = Config::new(
     "Whether the compute persist_sink obeys read-only mode.",
 );
 
+/// The timeout of the requests with which webhook sinks deliver updates.
+pub const WEBHOOK_SINK_REQUEST_TIMEOUT: Config<Duration> = Config::new(
+    "compute_webhook_sink_request_timeout",
+    Duration::from_secs(30),
+    "The timeout of the requests with which webhook sinks deliver updates.",
+);
+
+/// The maximum size of the updates a webhook sink buffers before it fails.
+pub const WEBHOOK_SINK_MAX_BUFFERED_BYTES: Config<usize> = Config::new(
+    "compute_webhook_sink_max_buffered_bytes",
+    256 * 1024 * 1024,
+    "The maximum size, in bytes, of the rows a webhook sink buffers while they wait for \
+     delivery. A sink that exceeds it fails.",
+);
+
+/// The maximum number of updates a webhook sink delivers in a single request.
+pub const WEBHOOK_SINK_MAX_UPDATES_PER_REQUEST: Config<usize> = Config::new(
+    "compute_webhook_sink_max_updates_per_request",
+    1000,
+    "The maximum number of updates a webhook sink delivers in a single request. The updates \
+     of timestamps with more updates are split across several requests.",
+);
+
+/// The number of attempts after which a webhook sink gives up delivering a request.
+pub const WEBHOOK_SINK_MAX_DELIVERY_ATTEMPTS: Config<usize> = Config::new(
+    "compute_webhook_sink_max_delivery_attempts",
+    10,
+    "The number of attempts after which a webhook sink gives up delivering a request and fails.",
+);
+
 /// Adds the full set of all compute `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -142,4 +172,8 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&COPY_TO_S3_ARROW_BUILDER_BUFFER_RATIO)
         .add(&COPY_TO_S3_MULTIPART_PART_SIZE_BYTES)
         .add(&PERSIST_SINK_OBEY_READ_ONLY)
+        .add(&WEBHOOK_SINK_REQUEST_TIMEOUT)
+        .add(&WEBHOOK_SINK_MAX_BUFFERED_BYTES)
+        .add(&WEBHOOK_SINK_MAX_UPDATES_PER_REQUEST)
+        .add(&WEBHOOK_SINK_MAX_DELIVERY_ATTEMPTS)
 }
@@ -11,11 +11,11 @@
 
 pub(crate) mod text;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use mz_expr::explain::{enforce_linear_chains, ExplainContext, ExplainMultiPlan, ExplainSource};
-use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr};
-use mz_repr::explain::{AnnotatedPlan, Explain, ExplainError, UnsupportedFormat};
+use mz_expr::{Id, LocalId, MirRelationExpr, OptimizedMirRelationExpr};
+use mz_repr::explain::{AnnotatedPlan, Attributes, Explain, ExplainError, UnsupportedFormat};
 use mz_repr::GlobalId;
 
 use crate::dataflows::DataflowDescription;
@@ -44,10 +44,36 @@ impl<'a> DataflowDescription<Plan> {
         &'a mut self,
         context: &'a ExplainContext<'a>,
     ) -> Result<ExplainMultiPlan<'a, Plan>, ExplainError> {
-        let export_ids = export_ids_for(self);
-        let plans = self
+        let this: &'a Self = self;
+        let export_ids = export_ids_for(this);
+        let related_ids = related_ids_for(this);
+
+        // Estimate the rows of each plan node, in build order, so that later objects can use the
+        // estimates of the objects they read from.
+        let mut row_estimates = context.cardinality_stats.clone();
+        let mut annotations = this
             .objects_to_build
-            .iter_mut()
+            .iter()
+            .map(|build_desc| {
+                let mut annotations = BTreeMap::default();
+                if context.config.memory_estimates {
+                    let estimate = annotate_row_estimates(
+                        &build_desc.plan,
+                        &row_estimates,
+                        &mut BTreeMap::new(),
+                        &mut annotations,
+                    );
+                    if let Some(estimate) = estimate {
+                        row_estimates.insert(build_desc.id, estimate);
+                    }
+                }
+                annotations
+            })
+            .collect::<Vec<_>>();
+
+        let plans = this
+            .objects_to_build
+            .iter()
             .rev()
             .map(|build_desc| {
                 let public_id = export_ids
@@ -60,18 +86,24 @@ impl<'a> DataflowDescription<Plan> {
                     .unwrap_or_else(|| public_id.to_string());
                 let plan = AnnotatedPlan {
                     plan: &build_desc.plan,
-                    annotations: BTreeMap::default(),
+                    annotations: annotations.pop().unwrap_or_default(),
                 };
                 (id, plan)
             })
             .collect::<Vec<_>>();
 
-        let sources = self
+        let sources = this
             .source_imports
-            .iter_mut()
+            .iter()
             .map(|(id, (source_desc, _))| {
                 let op = source_desc.arguments.operators.as_ref();
-                ExplainSource::new(*id, op, context.config.filter_pushdown)
+                ExplainSource::new(
+                    *id,
+                    op,
+                    context.config.filter_pushdown,
+                    context.row_estimate(*id),
+                    context.row_count_hint(*id),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -79,6 +111,7 @@ impl<'a> DataflowDescription<Plan> {
             context,
             sources,
             plans,
+            related_ids,
         })
     }
 }
@@ -107,6 +140,7 @@ impl<'a> DataflowDescription<OptimizedMirRelationExpr> {
         context: &'a ExplainContext<'a>,
     ) -> Result<ExplainMultiPlan<'a, MirRelationExpr>, ExplainError> {
         let export_ids = export_ids_for(self);
+        let related_ids = related_ids_for(self);
         let plans = self
             .objects_to_build
             .iter_mut()
@@ -139,7 +173,13 @@ impl<'a> DataflowDescription<OptimizedMirRelationExpr> {
             .iter_mut()
             .map(|(id, (source_desc, _))| {
                 let op = source_desc.arguments.operators.as_ref();
-                ExplainSource::new(*id, op, context.config.filter_pushdown)
+                ExplainSource::new(
+                    *id,
+                    op,
+                    context.config.filter_pushdown,
+                    context.row_estimate(*id),
+                    context.row_count_hint(*id),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -147,10 +187,101 @@ impl<'a> DataflowDescription<OptimizedMirRelationExpr> {
             context,
             sources,
             plans,
+            related_ids,
         })
     }
 }
 
+/// The estimated size of a datum in an arrangement, in bytes.
+///
+/// Most datums are encoded as a tag byte followed by at most eight bytes of data.
+const ESTIMATED_DATUM_BYTES: usize = 9;
+
+/// The estimated overhead of an update in an arrangement, in bytes.
+///
+/// This covers the timestamp and the diff of the update as well as the offsets of its key and
+/// value in the batches of the arrangement.
+const ESTIMATED_UPDATE_OVERHEAD_BYTES: usize = 32;
+
+/// Estimates the memory footprint in bytes of an arrangement of `rows` rows with `key_arity` key
+/// columns and `value_arity` value columns.
+pub(crate) fn estimate_arrangement_bytes(
+    rows: usize,
+    key_arity: usize,
+    value_arity: usize,
+) -> usize {
+    let update_bytes =
+        ESTIMATED_UPDATE_OVERHEAD_BYTES + ESTIMATED_DATUM_BYTES * (key_arity + value_arity);
+    rows.saturating_mul(update_bytes)
+}
+
+/// Records an upper bound on the number of rows of every node of `plan` in `annotations`, and
+/// returns the bound for `plan` itself.
+///
+/// The bounds are derived from the estimated number of rows of the global collections in
+/// `imports` and of the local bindings in `lets`. Nodes that can produce more rows than they
+/// consume, like joins and flat maps, and the nodes that consume their output have no bound.
+fn annotate_row_estimates<'a>(
+    plan: &'a Plan,
+    imports: &BTreeMap<GlobalId, usize>,
+    lets: &mut BTreeMap<LocalId, usize>,
+    annotations: &mut BTreeMap<&'a Plan, Attributes>,
+) -> Option<usize> {
+    mz_ore::stack::maybe_grow(|| {
+        let estimate = match plan {
+            Plan::Constant { rows, .. } => Some(rows.as_ref().map_or(0, |rows| rows.len())),
+            Plan::Get { id, .. } => match id {
+                Id::Global(id) => imports.get(id).copied(),
+                Id::Local(id) => lets.get(id).copied(),
+            },
+            Plan::Let {
+                id, value, body, ..
+            } => {
+                if let Some(estimate) = annotate_row_estimates(value, imports, lets, annotations) {
+                    lets.insert(*id, estimate);
+                }
+                annotate_row_estimates(body, imports, lets, annotations)
+            }
+            Plan::LetRec { values, body, .. } => {
+                // The bindings of recursive plans can grow with every iteration.
+                for value in values {
+                    annotate_row_estimates(value, imports, lets, annotations);
+                }
+                annotate_row_estimates(body, imports, lets, annotations)
+            }
+            Plan::FlatMap { input, .. } => {
+                annotate_row_estimates(input, imports, lets, annotations);
+                None
+            }
+            Plan::Join { inputs, .. } => {
+                for input in inputs {
+                    annotate_row_estimates(input, imports, lets, annotations);
+                }
+                None
+            }
+            Plan::Union { inputs, .. } => {
+                let estimates = inputs
+                    .iter()
+                    .map(|input| annotate_row_estimates(input, imports, lets, annotations))
+                    .collect::<Vec<_>>();
+                estimates
+                    .into_iter()
+                    .try_fold(0usize, |sum, estimate| Some(sum.saturating_add(estimate?)))
+            }
+            Plan::Mfp { input, .. }
+            | Plan::Reduce { input, .. }
+            | Plan::TopK { input, .. }
+            | Plan::Negate { input, .. }
+            | Plan::Threshold { input, .. }
+            | Plan::ArrangeBy { input, .. } => {
+                annotate_row_estimates(input, imports, lets, annotations)
+            }
+        };
+        annotations.entry(plan).or_default().row_estimate = estimate;
+        estimate
+    })
+}
+
 /// TODO(#25239): Add documentation.
 pub fn export_ids_for<P, S, T>(dd: &DataflowDescription<P, S, T>) -> BTreeMap<GlobalId, GlobalId> {
     let mut map = BTreeMap::<GlobalId, GlobalId>::default();
@@ -192,3 +323,27 @@ pub fn export_ids_for<P, S, T>(dd: &DataflowDescription<P, S, T>) -> BTreeMap<Gl
 
     map
 }
+
+/// Returns the IDs of the objects that the dataflow `dd` builds and reads, in the order in which
+/// `EXPLAIN ... WITH (comments)` renders their comments: the built objects in the order in which
+/// their plans are rendered, followed by the collections that the dataflow imports.
+pub fn related_ids_for<P, S, T>(dd: &DataflowDescription<P, S, T>) -> Vec<GlobalId> {
+    let export_ids = export_ids_for(dd);
+
+    let built = dd
+        .objects_to_build
+        .iter()
+        .rev()
+        .map(|build_desc| *export_ids.get(&build_desc.id).unwrap_or(&build_desc.id));
+    let imported = dd
+        .index_imports
+        .values()
+        .map(|import| import.desc.on_id)
+        .chain(dd.source_imports.keys().copied());
+
+    let mut seen = BTreeSet::new();
+    built
+        .chain(imported)
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
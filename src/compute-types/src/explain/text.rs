@@ -33,6 +33,7 @@ use mz_repr::explain::{
     CompactScalarSeq, CompactScalars, ExplainConfig, Indices, PlanRenderingContext,
 };
 
+use crate::explain::estimate_arrangement_bytes;
 use crate::plan::join::delta_join::{DeltaPathPlan, DeltaStagePlan};
 use crate::plan::join::linear_join::LinearStagePlan;
 use crate::plan::join::{DeltaJoinPlan, JoinClosure, LinearJoinPlan};
@@ -453,6 +454,20 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                     }
                     mode.expr(input_mfp, None).fmt_text(f, ctx)?;
                     forms.fmt_text(f, ctx)?;
+                    if ctx.config.memory_estimates {
+                        let rows = ctx.annotations.get(self).and_then(|a| a.row_estimate);
+                        for (i, (key, _, thinning)) in forms.arranged.iter().enumerate() {
+                            write!(f, "{}estimated_memory[{}]=", ctx.indent, i)?;
+                            match rows {
+                                Some(rows) => {
+                                    let bytes =
+                                        estimate_arrangement_bytes(rows, key.len(), thinning.len());
+                                    writeln!(f, "{{ rows={}, bytes={} }}", rows, bytes)?;
+                                }
+                                None => writeln!(f, "unknown")?,
+                            }
+                        }
+                    }
                     // Render input
                     input.fmt_text(f, ctx)
                 })?;
@@ -14,7 +14,7 @@ use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::{GlobalId, RelationDesc, Timestamp};
 use mz_storage_types::connections::aws::AwsConnection;
 use mz_storage_types::controller::CollectionMetadata;
-use mz_storage_types::sinks::S3UploadInfo;
+use mz_storage_types::sinks::{S3UploadInfo, WebhookSinkConnection};
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -121,6 +121,8 @@ pub enum ComputeSinkConnection<S: 'static = ()> {
     Persist(PersistSinkConnection<S>),
     /// A compute sink to do a oneshot copy to s3.
     CopyToS3Oneshot(CopyToS3OneshotSinkConnection),
+    /// A compute sink that delivers updates over HTTP.
+    Webhook(WebhookSinkConnection),
 }
 
 impl<S> ComputeSinkConnection<S> {
@@ -130,6 +132,7 @@ impl<S> ComputeSinkConnection<S> {
             ComputeSinkConnection::Subscribe(_) => "subscribe",
             ComputeSinkConnection::Persist(_) => "persist",
             ComputeSinkConnection::CopyToS3Oneshot(_) => "copy_to_s3_oneshot",
+            ComputeSinkConnection::Webhook(_) => "webhook",
         }
     }
 
@@ -153,6 +156,7 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
                 ComputeSinkConnection::CopyToS3Oneshot(s3) => {
                     Kind::CopyToS3Oneshot(s3.into_proto())
                 }
+                ComputeSinkConnection::Webhook(webhook) => Kind::Webhook(webhook.into_proto()),
             }),
         }
     }
@@ -166,6 +170,7 @@ impl RustType<ProtoComputeSinkConnection> for ComputeSinkConnection<CollectionMe
             Kind::Subscribe(_) => ComputeSinkConnection::Subscribe(SubscribeSinkConnection {}),
             Kind::Persist(persist) => ComputeSinkConnection::Persist(persist.into_rust()?),
             Kind::CopyToS3Oneshot(s3) => ComputeSinkConnection::CopyToS3Oneshot(s3.into_rust()?),
+            Kind::Webhook(webhook) => ComputeSinkConnection::Webhook(webhook.into_rust()?),
         })
     }
 }
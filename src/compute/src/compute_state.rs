@@ -25,7 +25,7 @@ use mz_compute_client::protocol::command::{
 use mz_compute_client::protocol::history::ComputeCommandHistory;
 use mz_compute_client::protocol::response::{
     ComputeResponse, CopyToResponse, FrontiersResponse, OperatorHydrationStatus, PeekResponse,
-    StatusResponse, SubscribeResponse,
+    SinkFailureStatus, StatusResponse, SubscribeResponse,
 };
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::plan::flat_plan::FlatPlan;
@@ -64,6 +64,7 @@ use crate::logging::compute::{CollectionLogging, ComputeEvent};
 use crate::metrics::ComputeMetrics;
 use crate::render::{LinearJoinSpec, StartSignal};
 use crate::server::{ComputeInstanceContext, ResponseSender};
+use crate::sink::SubscribePause;
 
 /// Worker-local state that is maintained across dataflows.
 ///
@@ -93,6 +94,10 @@ pub struct ComputeState {
     /// The entries are pairs of sink identifier (to identify the s3 oneshot instance)
     /// and the response itself.
     pub copy_to_response_buffer: Rc<RefCell<Vec<(GlobalId, CopyToResponse)>>>,
+    /// Shared buffer with sink operator instances by which they can report failures.
+    ///
+    /// The entries are pairs of sink identifier and a description of the failure.
+    pub sink_failure_buffer: Rc<RefCell<Vec<(GlobalId, String)>>>,
     /// Peek commands that are awaiting fulfillment.
     pub pending_peeks: BTreeMap<Uuid, PendingPeek>,
     /// The logger, from Timely's logging framework, if logs are enabled.
@@ -181,6 +186,7 @@ impl ComputeState {
             traces,
             subscribe_response_buffer: Default::default(),
             copy_to_response_buffer: Default::default(),
+            sink_failure_buffer: Default::default(),
             pending_peeks: Default::default(),
             compute_logger: None,
             persist_clients,
@@ -337,6 +343,7 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
                 self.handle_peek(peek)
             }
             CancelPeek { uuid } => self.handle_cancel_peek(uuid),
+            PauseSubscribe { id, paused } => self.handle_pause_subscribe(id, paused),
             AllowWrites => {
                 self.compute_state
                     .read_only_tx
@@ -514,6 +521,18 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
         }
     }
 
+    fn handle_pause_subscribe(&mut self, id: GlobalId, paused: bool) {
+        let pause = self
+            .compute_state
+            .collections
+            .get(&id)
+            .and_then(|collection| collection.subscribe_pause.as_ref());
+        match pause {
+            Some(pause) => pause.set_paused(paused),
+            None => warn!(%id, "ignoring pause for unknown subscribe"),
+        }
+    }
+
     /// Arrange for the given collection to be dropped.
     ///
     /// Collection dropping occurs in three phases:
@@ -836,6 +855,26 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Scan the shared sink failure buffer, and report the failures.
+    pub fn report_sink_failures(&mut self) {
+        let failures = std::mem::take(&mut *self.compute_state.sink_failure_buffer.borrow_mut());
+        for (sink_id, error) in failures {
+            // The compute protocol forbids reporting `Status` about collections that have advanced
+            // to the empty frontier, so we ignore failures of those.
+            let collection = self.compute_state.collections.get(&sink_id);
+            if collection.map_or(true, |c| c.reported_frontiers().all_empty()) {
+                continue;
+            }
+
+            let status = SinkFailureStatus {
+                collection_id: sink_id,
+                error,
+            };
+            let response = ComputeResponse::Status(StatusResponse::SinkFailure(status));
+            self.send_compute_response(response);
+        }
+    }
+
     /// Send a response to the coordinator.
     fn send_compute_response(&self, response: ComputeResponse) {
         // Ignore send errors because the coordinator is free to ignore our
@@ -1471,6 +1510,10 @@ pub struct CollectionState {
     ///
     /// Only `Some` if the collection is a sink and *not* a subscribe.
     pub sink_write_frontier: Option<Rc<RefCell<Antichain<Timestamp>>>>,
+    /// A handle to pause the emission of subscribe responses.
+    ///
+    /// Only `Some` if the collection is a subscribe.
+    pub subscribe_pause: Option<Rc<SubscribePause>>,
     /// Frontier probes for every input to the collection.
     pub input_probes: BTreeMap<GlobalId, probe::Handle<Timestamp>>,
     /// A probe reporting the frontier of times through which all collection outputs have been
@@ -1490,6 +1533,7 @@ impl CollectionState {
             as_of,
             sink_token: None,
             sink_write_frontier: None,
+            subscribe_pause: None,
             input_probes: Default::default(),
             compute_probe: None,
             logging: None,
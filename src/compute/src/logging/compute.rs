@@ -18,6 +18,7 @@ use std::time::{Duration, Instant};
 use differential_dataflow::collection::AsCollection;
 use differential_dataflow::trace::{BatchReader, Cursor};
 use differential_dataflow::Collection;
+use mz_compute_types::plan::LirId;
 use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, Diff, GlobalId, Timestamp};
 use mz_timely_util::replay::MzReplay;
@@ -130,6 +131,14 @@ pub enum ComputeEvent {
     },
     /// A dataflow export was hydrated.
     Hydration { export_id: GlobalId },
+    /// The operators implementing LIR nodes of a dataflow were created.
+    LirMapping {
+        /// Timely worker index of the dataflow.
+        dataflow_index: usize,
+        /// The LIR nodes, with the range of the IDs of the operators that implement them. The
+        /// range includes the start ID and excludes the end ID.
+        mapping: Vec<(LirId, (usize, usize))>,
+    },
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -214,6 +223,7 @@ pub(super) fn construct<A: Allocate + 'static>(
             demux.new_output();
         let (mut error_count_out, error_count) = demux.new_output();
         let (mut hydration_time_out, hydration_time) = demux.new_output();
+        let (mut lir_mapping_out, lir_mapping) = demux.new_output();
 
         let mut demux_state = DemuxState::new(worker2);
         let mut demux_buffer = Vec::new();
@@ -230,6 +240,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                 let mut arrangement_heap_allocations = arrangement_heap_allocations_out.activate();
                 let mut error_count = error_count_out.activate();
                 let mut hydration_time = hydration_time_out.activate();
+                let mut lir_mapping = lir_mapping_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -246,6 +257,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                         arrangement_heap_allocations: arrangement_heap_allocations.session(&cap),
                         error_count: error_count.session(&cap),
                         hydration_time: hydration_time.session(&cap),
+                        lir_mapping: lir_mapping.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -382,6 +394,17 @@ pub(super) fn construct<A: Allocate + 'static>(
             }
         });
 
+        let mut packer = PermutedRowPacker::new(ComputeLog::LirMapping);
+        let lir_mapping = lir_mapping.as_collection().map(move |datum| {
+            packer.pack_slice(&[
+                Datum::UInt64(u64::cast_from(datum.dataflow_id)),
+                Datum::UInt64(datum.lir_id),
+                Datum::UInt64(u64::cast_from(worker_id)),
+                Datum::UInt64(u64::cast_from(datum.operator_span.0)),
+                Datum::UInt64(u64::cast_from(datum.operator_span.1)),
+            ])
+        });
+
         use ComputeLog::*;
         let logs = [
             (DataflowCurrent, dataflow_current),
@@ -395,6 +418,7 @@ pub(super) fn construct<A: Allocate + 'static>(
             (ArrangementHeapAllocations, arrangement_heap_allocations),
             (ErrorCount, error_count),
             (HydrationTime, hydration_time),
+            (LirMapping, lir_mapping),
         ];
 
         // Build the output arrangements.
@@ -448,6 +472,8 @@ struct DemuxState<A: Allocate> {
     peek_stash: BTreeMap<Uuid, Duration>,
     /// Arrangement size stash
     arrangement_size: BTreeMap<usize, ArrangementSizeState>,
+    /// Maps live dataflows to the operators implementing their LIR nodes.
+    lir_mapping: BTreeMap<usize, Vec<(LirId, (usize, usize))>>,
 }
 
 impl<A: Allocate> DemuxState<A> {
@@ -460,6 +486,7 @@ impl<A: Allocate> DemuxState<A> {
             shutdown_dataflows: Default::default(),
             peek_stash: Default::default(),
             arrangement_size: Default::default(),
+            lir_mapping: Default::default(),
         }
     }
 }
@@ -516,6 +543,7 @@ struct DemuxOutput<'a> {
     arrangement_heap_allocations: OutputSession<'a, ArrangementHeapDatum>,
     hydration_time: OutputSession<'a, HydrationTimeDatum>,
     error_count: OutputSession<'a, ErrorCountDatum>,
+    lir_mapping: OutputSession<'a, LirMappingDatum>,
 }
 
 #[derive(Clone)]
@@ -560,6 +588,13 @@ struct HydrationTimeDatum {
     time_ns: Option<u64>,
 }
 
+#[derive(Clone)]
+struct LirMappingDatum {
+    dataflow_id: usize,
+    lir_id: LirId,
+    operator_span: (usize, usize),
+}
+
 #[derive(Clone)]
 struct ErrorCountDatum {
     export_id: GlobalId,
@@ -638,6 +673,10 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             DataflowShutdown { dataflow_index } => self.handle_dataflow_shutdown(dataflow_index),
             ErrorCount { export_id, diff } => self.handle_error_count(export_id, diff),
             Hydration { export_id } => self.handle_hydration(export_id),
+            LirMapping {
+                dataflow_index,
+                mapping,
+            } => self.handle_lir_mapping(dataflow_index, mapping),
         }
     }
 
@@ -709,6 +748,17 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
     fn handle_dataflow_dropped(&mut self, id: usize) {
         self.state.dataflow_export_counts.remove(&id);
 
+        // Remove LIR mapping logging for this dataflow.
+        let ts = self.ts();
+        for (lir_id, operator_span) in self.state.lir_mapping.remove(&id).unwrap_or_default() {
+            let datum = LirMappingDatum {
+                dataflow_id: id,
+                lir_id,
+                operator_span,
+            };
+            self.output.lir_mapping.give((datum, ts, -1));
+        }
+
         if self.state.shutdown_dataflows.remove(&id) {
             // Dataflow has already shut down before it was dropped.
             self.output.shutdown_duration.give((0, self.ts(), 1));
@@ -798,6 +848,23 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
         export.hydration_time_ns = Some(nanos);
     }
 
+    fn handle_lir_mapping(&mut self, dataflow_id: usize, mapping: Vec<(LirId, (usize, usize))>) {
+        let ts = self.ts();
+        for &(lir_id, operator_span) in &mapping {
+            let datum = LirMappingDatum {
+                dataflow_id,
+                lir_id,
+                operator_span,
+            };
+            self.output.lir_mapping.give((datum, ts, 1));
+        }
+        self.state
+            .lir_mapping
+            .entry(dataflow_id)
+            .or_default()
+            .extend(mapping);
+    }
+
     fn handle_peek_install(&mut self, peek: Peek, peek_type: PeekType) {
         let uuid = peek.uuid;
         let ts = self.ts();
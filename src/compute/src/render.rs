@@ -146,7 +146,7 @@ use crate::arrangement::manager::TraceBundle;
 use crate::compute_state::ComputeState;
 use crate::extensions::arrange::{KeyCollection, MzArrange};
 use crate::extensions::reduce::MzReduce;
-use crate::logging::compute::LogDataflowErrors;
+use crate::logging::compute::{ComputeEvent, LogDataflowErrors};
 use crate::render::context::{
     ArrangementFlavor, Context, MzArrangement, MzArrangementImport, ShutdownToken,
 };
@@ -762,16 +762,28 @@ where
 
         // Rendered collections by their `LirId`.
         let mut collections = BTreeMap::new();
+        // The range of the IDs of the operators that implement each node.
+        let mut lir_mapping = Vec::new();
 
         for id in topological_order {
             let node = nodes.remove(&id).unwrap();
+            let operator_id_start = self.scope.peek_identifier();
             let mut bundle = self.render_plan_node(node, &collections);
 
             self.log_operator_hydration(&mut bundle, id);
+            let operator_id_end = self.scope.peek_identifier();
+            lir_mapping.push((id, (operator_id_start, operator_id_end)));
 
             collections.insert(id, bundle);
         }
 
+        if let Some(logger) = &self.lir_mapping_logger {
+            logger.log(ComputeEvent::LirMapping {
+                dataflow_index: self.dataflow_id,
+                mapping: lir_mapping,
+            });
+        }
+
         collections
             .remove(&root_id)
             .expect("FlatPlan invariant (1)")
@@ -42,6 +42,7 @@ use tracing::error;
 use crate::arrangement::manager::SpecializedTraceHandle;
 use crate::compute_state::{ComputeState, HydrationEvent};
 use crate::extensions::arrange::{KeyCollection, MzArrange};
+use crate::logging::compute::Logger as ComputeLogger;
 use crate::render::errors::ErrorLogger;
 use crate::render::{LinearJoinSpec, RenderTimestamp};
 use crate::typedefs::{ErrAgent, ErrEnter, ErrSpine, RowRowAgent, RowRowEnter, RowRowSpine};
@@ -85,6 +86,10 @@ where
     ///
     /// `None` if no hydration events should be logged in this context.
     pub(super) hydration_logger: Option<HydrationLogger>,
+    /// A logger for the operators that implement the LIR nodes of the dataflow.
+    ///
+    /// `None` if the LIR mapping should not be logged in this context.
+    pub(super) lir_mapping_logger: Option<ComputeLogger>,
     /// Specification for rendering linear joins.
     pub(super) linear_join_spec: LinearJoinSpec,
 }
@@ -108,14 +113,15 @@ where
 
         // Skip operator hydration logging for transient dataflows. We do this to avoid overhead
         // for slow-path peeks, but it also affects subscribes. For now that seems fine, but we may
-        // want to reconsider in the future.
-        let hydration_logger = if dataflow.is_transient() {
-            None
+        // want to reconsider in the future. The same goes for the LIR mapping.
+        let (hydration_logger, lir_mapping_logger) = if dataflow.is_transient() {
+            (None, None)
         } else {
-            Some(HydrationLogger {
+            let hydration_logger = HydrationLogger {
                 export_ids: dataflow.export_ids().collect(),
                 tx: compute_state.hydration_tx.clone(),
-            })
+            };
+            (Some(hydration_logger), compute_state.compute_logger.clone())
         };
 
         Self {
@@ -127,6 +133,7 @@ where
             bindings: BTreeMap::new(),
             shutdown_token: Default::default(),
             hydration_logger,
+            lir_mapping_logger,
             linear_join_spec: compute_state.linear_join_spec,
         }
     }
@@ -125,6 +125,7 @@ where
             ComputeSinkConnection::CopyToS3Oneshot(_) => {
                 format!("CopyToS3OneshotSink({:?})", sink_id)
             }
+            ComputeSinkConnection::Webhook(_) => format!("WebhookSink({:?})", sink_id),
         };
         self.scope
             .parent
@@ -179,5 +180,6 @@ where
         ComputeSinkConnection::Subscribe(connection) => Box::new(connection.clone()),
         ComputeSinkConnection::Persist(connection) => Box::new(connection.clone()),
         ComputeSinkConnection::CopyToS3Oneshot(connection) => Box::new(connection.clone()),
+        ComputeSinkConnection::Webhook(connection) => Box::new(connection.clone()),
     }
 }
@@ -12,3 +12,6 @@ mod correction;
 mod persist_sink;
 mod refresh;
 mod subscribe;
+mod webhook;
+
+pub(crate) use subscribe::SubscribePause;
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::DerefMut;
 use std::rc::Rc;
 
@@ -24,6 +24,7 @@ use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use timely::dataflow::Scope;
 use timely::progress::timestamp::Timestamp as TimelyTimestamp;
 use timely::progress::Antichain;
+use timely::scheduling::Activator;
 use timely::PartialOrder;
 
 use crate::render::sinks::SinkRender;
@@ -55,7 +56,7 @@ where
         })));
         let subscribe_protocol_weak = Rc::downgrade(&subscribe_protocol_handle);
 
-        subscribe(
+        let pause = subscribe(
             sinked_collection,
             err_collection,
             sink_id,
@@ -64,6 +65,8 @@ where
             sink.up_to.clone(),
             subscribe_protocol_handle,
         );
+        let collection = compute_state.expect_collection_mut(sink_id);
+        collection.subscribe_pause = Some(pause);
 
         // Inform the coordinator that we have been dropped,
         // and destroy the subscribe protocol so the sink operator
@@ -84,7 +87,8 @@ fn subscribe<G>(
     as_of: Antichain<G::Timestamp>,
     up_to: Antichain<G::Timestamp>,
     subscribe_protocol_handle: Rc<RefCell<Option<SubscribeProtocol>>>,
-) where
+) -> Rc<SubscribePause>
+where
     G: Scope<Timestamp = Timestamp>,
 {
     let name = format!("subscribe-{}", sink_id);
@@ -92,6 +96,13 @@ fn subscribe<G>(
     let mut ok_input = op.new_input(&sinked_collection.inner, Pipeline);
     let mut err_input = op.new_input(&err_collection.inner, Pipeline);
 
+    let info = op.operator_info();
+    let pause = Rc::new(SubscribePause {
+        paused: Cell::new(false),
+        activator: sinked_collection.scope().activator_for(&info.address[..]),
+    });
+    let operator_pause = Rc::clone(&pause);
+
     op.build(|_cap| {
         let mut rows_to_emit = Vec::new();
         let mut errors_to_emit = Vec::new();
@@ -139,6 +150,12 @@ fn subscribe<G>(
                 }
             });
 
+            // While paused, hold back all updates, including the final batch. They are sent once
+            // we are resumed and rescheduled.
+            if operator_pause.paused.get() {
+                return;
+            }
+
             if let Some(subscribe_protocol) = subscribe_protocol_handle.borrow_mut().deref_mut() {
                 subscribe_protocol.send_batch(
                     frontier.clone(),
@@ -162,6 +179,26 @@ fn subscribe<G>(
             }
         }
     });
+
+    pause
+}
+
+/// A handle to pause and resume the emission of responses by a subscribe sink operator.
+pub(crate) struct SubscribePause {
+    /// Whether the operator currently holds back its updates.
+    paused: Cell<bool>,
+    /// Reschedules the operator, so it sends the updates it held back.
+    activator: Activator,
+}
+
+impl SubscribePause {
+    /// Pauses or resumes the emission of responses.
+    pub(crate) fn set_paused(&self, paused: bool) {
+        let was_paused = self.paused.replace(paused);
+        if was_paused && !paused {
+            self.activator.activate();
+        }
+    }
 }
 
 /// A type that guides the transmission of rows back to the coordinator.
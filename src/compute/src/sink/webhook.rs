@@ -0,0 +1,452 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that delivers updates over HTTP.
+//!
+//! A single worker delivers the updates of each timestamp, in timestamp order, in `POST` requests
+//! of at most `compute_webhook_sink_max_updates_per_request` updates each. The `Idempotency-Key`
+//! of each request is derived from the sink ID, the timestamp and the index of the request among
+//! those of the timestamp. Failed requests are retried with an exponential backoff, so the sink
+//! delivers every timestamp at least once and receivers that discard requests with known keys see
+//! every timestamp exactly once. This also makes it safe for several replicas to run the same
+//! sink.
+//!
+//! The sink fails if a request still fails after `compute_webhook_sink_max_delivery_attempts`
+//! attempts, if it buffers more than `compute_webhook_sink_max_buffered_bytes` of updates, or if
+//! its input contains errors. A failed sink stops delivering and reports the failure to the
+//! controller.
+//!
+//! The sink only connects to the addresses the host of its URL resolves to when the sink starts,
+//! and doesn't follow redirects. If `storage_enforce_external_addresses` is set, these must be
+//! global addresses, as for the connections of sources and sinks.
+//!
+//! The write frontier the sink reports is one less than the frontier of the timestamps it has
+//! delivered, i.e., the sink has delivered all updates at times not beyond its write frontier. The
+//! adapter resumes the sink from that frontier without a snapshot, and the read holds the
+//! controller keeps at the write frontier guarantee that the inputs are still readable there.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::{Collection, Hashable};
+use futures::future::LocalBoxFuture;
+use futures::{FutureExt, StreamExt};
+use mz_compute_types::dyncfgs::{
+    WEBHOOK_SINK_MAX_BUFFERED_BYTES, WEBHOOK_SINK_MAX_DELIVERY_ATTEMPTS,
+    WEBHOOK_SINK_MAX_UPDATES_PER_REQUEST, WEBHOOK_SINK_REQUEST_TIMEOUT,
+};
+use mz_compute_types::sinks::ComputeSinkDesc;
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_repr::{ColumnName, ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::dyncfgs::ENFORCE_EXTERNAL_ADDRESSES;
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::WebhookSinkConnection;
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
+use timely::PartialOrder;
+use tracing::{error, warn};
+
+use crate::render::sinks::SinkRender;
+use crate::render::StartSignal;
+
+/// The longest the sink waits between two attempts to deliver a request.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The settings with which a webhook sink delivers its updates.
+#[derive(Clone, Debug)]
+struct DeliveryConfig {
+    sink_id: GlobalId,
+    url: String,
+    columns: Rc<Vec<(ColumnName, ColumnType)>>,
+    max_updates_per_request: usize,
+    max_attempts: usize,
+}
+
+impl<G> SinkRender<G> for WebhookSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn render_sink(
+        &self,
+        compute_state: &mut crate::compute_state::ComputeState,
+        sink: &ComputeSinkDesc<CollectionMetadata>,
+        sink_id: GlobalId,
+        as_of: Antichain<Timestamp>,
+        _start_signal: StartSignal,
+        sinked_collection: Collection<G, Row, Diff>,
+        err_collection: Collection<G, DataflowError, Diff>,
+    ) -> Option<Rc<dyn Any>> {
+        let scope = sinked_collection.scope();
+
+        // Only one worker delivers updates, to deliver them in timestamp order.
+        let hashed_id = sink_id.hashed();
+        let active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+        // Non-active workers report an empty frontier, so they don't hold back the frontier of the
+        // active one.
+        let shared_frontier = Rc::new(RefCell::new(if active_worker {
+            Antichain::from_elem(TimelyTimestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+        let collection = compute_state.expect_collection_mut(sink_id);
+        collection.sink_write_frontier = Some(Rc::clone(&shared_frontier));
+
+        let worker_config = &compute_state.worker_config;
+        let timeout = WEBHOOK_SINK_REQUEST_TIMEOUT.get(worker_config);
+        let max_buffered_bytes = WEBHOOK_SINK_MAX_BUFFERED_BYTES.get(worker_config);
+        let enforce_external_addresses = ENFORCE_EXTERNAL_ADDRESSES.get(worker_config);
+        let config = DeliveryConfig {
+            sink_id,
+            url: self.url.clone(),
+            columns: Rc::new(column_names_and_types(sink.from_desc.clone())),
+            max_updates_per_request: WEBHOOK_SINK_MAX_UPDATES_PER_REQUEST
+                .get(worker_config)
+                .max(1),
+            max_attempts: WEBHOOK_SINK_MAX_DELIVERY_ATTEMPTS.get(worker_config).max(1),
+        };
+        let failures = Rc::clone(&compute_state.sink_failure_buffer);
+        let fail = move |error: String| {
+            error!(%sink_id, "webhook sink failed: {error}");
+            failures.borrow_mut().push((sink_id, error));
+        };
+        let mut read_only = compute_state.read_only_rx.clone();
+        let with_snapshot = sink.with_snapshot;
+
+        let mut builder = AsyncOperatorBuilder::new(format!("WebhookSink({sink_id})"), scope);
+        let mut oks_input = builder
+            .new_disconnected_input(&sinked_collection.inner, Exchange::new(move |_| hashed_id));
+        let mut errs_input = builder
+            .new_disconnected_input(&err_collection.inner, Exchange::new(move |_| hashed_id));
+
+        let button = builder.build(move |_capabilities| async move {
+            if !active_worker {
+                return;
+            }
+
+            let client = match build_client(&config.url, enforce_external_addresses, timeout).await
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    fail(format!("cannot connect to {}: {e:#}", config.url));
+                    return;
+                }
+            };
+
+            // The updates at each timestamp that has not been delivered yet.
+            let mut pending: BTreeMap<Timestamp, Vec<(Row, Diff)>> = BTreeMap::new();
+            // The size of the rows in `pending`.
+            let mut buffered_bytes = 0;
+            // The timestamp whose updates are being delivered, if any, and the delivery.
+            let mut in_flight: Option<(Timestamp, LocalBoxFuture<'static, Result<(), String>>)> =
+                None;
+            // The number of errors at each timestamp that has not been delivered yet.
+            let mut errors: BTreeMap<Timestamp, Diff> = BTreeMap::new();
+            let mut oks_frontier = Antichain::from_elem(Timestamp::minimum());
+            let mut errs_frontier = Antichain::from_elem(Timestamp::minimum());
+            // The time of the first error in the input, at which the sink stops delivering.
+            let mut error_time: Option<Timestamp> = None;
+
+            loop {
+                tokio::select! {
+                    Some(event) = oks_input.next() => match event {
+                        Event::Data(_cap, data) => {
+                            for (row, time, diff) in data {
+                                // Without a snapshot, skip everything up to the `as_of`.
+                                if !with_snapshot && !as_of.less_than(&time) {
+                                    continue;
+                                }
+                                // The sink never delivers anything beyond an error.
+                                if error_time.is_some_and(|error_time| error_time <= time) {
+                                    continue;
+                                }
+                                buffered_bytes += row.byte_len();
+                                pending.entry(time).or_default().push((row, diff));
+                            }
+                            if buffered_bytes > max_buffered_bytes {
+                                fail(format!(
+                                    "buffered {buffered_bytes} bytes of undelivered updates, \
+                                     more than the limit of {max_buffered_bytes} bytes"
+                                ));
+                                return;
+                            }
+                            continue;
+                        }
+                        Event::Progress(frontier) => oks_frontier = frontier,
+                    },
+                    Some(event) = errs_input.next() => match event {
+                        Event::Data(_cap, data) => {
+                            for (_err, time, diff) in data {
+                                *errors.entry(time).or_default() += diff;
+                            }
+                            continue;
+                        }
+                        Event::Progress(frontier) => errs_frontier = frontier,
+                    },
+                    result = async { in_flight.as_mut().expect("in flight").1.as_mut().await },
+                        if in_flight.is_some() =>
+                    {
+                        in_flight = None;
+                        if let Err(e) = result {
+                            fail(e);
+                            return;
+                        }
+                    }
+                    _it_changed = read_only.changed() => {
+                        // We might have to deliver updates that we held back while in read-only
+                        // mode.
+                    }
+                    else => {
+                        // All inputs are exhausted, so we can shut down.
+                        return;
+                    }
+                }
+
+                if *read_only.borrow() {
+                    continue;
+                }
+
+                let frontier = oks_frontier.meet(&errs_frontier);
+                errors.retain(|_time, count| *count != 0);
+
+                // There is no way to deliver an error, so the sink stops at the first one, as
+                // Kafka sinks do.
+                if error_time.is_none() {
+                    if let Some(time) = errors.keys().find(|time| !frontier.less_equal(time)) {
+                        fail(format!("input contains errors at {time}"));
+                        error_time = Some(*time);
+                    }
+                }
+                let limit = match error_time {
+                    Some(time) => frontier.meet(&Antichain::from_elem(time)),
+                    None => frontier,
+                };
+
+                // Start delivering the next complete timestamp, unless a delivery is in flight.
+                while in_flight.is_none() {
+                    let Some(entry) = pending.first_entry() else {
+                        break;
+                    };
+                    let time = *entry.key();
+                    if limit.less_equal(&time) {
+                        break;
+                    }
+                    let mut updates = entry.remove();
+                    buffered_bytes -= updates.iter().map(|(row, _)| row.byte_len()).sum::<usize>();
+                    differential_dataflow::consolidation::consolidate(&mut updates);
+                    if !updates.is_empty() {
+                        let delivery = deliver(client.clone(), config.clone(), time, updates);
+                        in_flight = Some((time, delivery.boxed_local()));
+                    }
+                }
+
+                // Report that everything up to, but excluding, the first timestamp that hasn't been
+                // delivered yet has been delivered.
+                let undelivered = match &in_flight {
+                    Some((time, _)) => Antichain::from_elem(*time),
+                    None => match pending.keys().next() {
+                        Some(time) if !limit.less_equal(time) => Antichain::from_elem(*time),
+                        _ => limit,
+                    },
+                };
+                let write_frontier = match undelivered.as_option() {
+                    Some(time) => Antichain::from_elem(time.step_back().unwrap_or(*time)),
+                    None => Antichain::new(),
+                };
+                let mut shared_frontier = shared_frontier.borrow_mut();
+                if PartialOrder::less_than(&*shared_frontier, &write_frontier) {
+                    *shared_frontier = write_frontier;
+                }
+            }
+        });
+
+        Some(Rc::new(button.press_on_drop()))
+    }
+}
+
+/// Builds the client with which a webhook sink delivers requests to `url`.
+///
+/// The client only connects to the addresses the host of `url` resolves to now, which must be
+/// global if `enforce_external_addresses` is set, and doesn't follow redirects, which could lead
+/// it to other addresses.
+async fn build_client(
+    url: &str,
+    enforce_external_addresses: bool,
+    timeout: Duration,
+) -> Result<reqwest::Client, anyhow::Error> {
+    let url = reqwest::Url::parse(url)?;
+    let host = url.host_str().ok_or_else(|| anyhow!("URL has no host"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL has no port"))?;
+    // IPv6 hosts are enclosed in brackets.
+    let ip_or_domain = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<_> = mz_ore::netio::resolve_address(ip_or_domain, enforce_external_addresses)
+        .await?
+        .into_iter()
+        .map(|ip| SocketAddr::new(ip, port))
+        .collect();
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve_to_addrs(host, &addrs)
+        .build()?;
+    Ok(client)
+}
+
+/// POSTs the consolidated `updates` at `time` to the URL of the sink, in requests of at most
+/// `max_updates_per_request` updates each.
+///
+/// Each request is retried until the receiver accepts it, up to `max_attempts` times.
+async fn deliver(
+    client: reqwest::Client,
+    config: DeliveryConfig,
+    time: Timestamp,
+    updates: Vec<(Row, Diff)>,
+) -> Result<(), String> {
+    let DeliveryConfig {
+        sink_id,
+        url,
+        columns,
+        max_updates_per_request,
+        max_attempts,
+    } = config;
+
+    // Updates with a multiplicity larger than one are repeated.
+    let count: usize = updates
+        .iter()
+        .map(|(_row, diff)| usize::cast_from(diff.unsigned_abs()))
+        .sum();
+    let parts = count.div_ceil(max_updates_per_request);
+    let mut records = updates.iter().flat_map(|(row, diff)| {
+        std::iter::repeat((row, *diff < 0)).take(usize::cast_from(diff.unsigned_abs()))
+    });
+
+    for part in 0..parts {
+        let batch = records.by_ref().take(max_updates_per_request);
+        let body = encode_batch(time, part, parts, batch, &columns);
+        let idempotency_key = WebhookSinkConnection::idempotency_key(sink_id, time, part);
+        Retry::default()
+            .clamp_backoff(MAX_BACKOFF)
+            .max_tries(max_attempts)
+            .retry_async(|state| {
+                let request = client
+                    .post(&url)
+                    .header("content-type", "application/json")
+                    .header("idempotency-key", &idempotency_key)
+                    .body(body.clone());
+                async move {
+                    let result = request
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status());
+                    if let Err(e) = &result {
+                        warn!(
+                            %sink_id,
+                            %time,
+                            part,
+                            attempt = state.i,
+                            "webhook sink failed to deliver updates: {e}"
+                        );
+                    }
+                    result.map(|_| ())
+                }
+            })
+            .await
+            .map_err(|e| {
+                format!(
+                    "failed to deliver the updates at {time} after {max_attempts} attempts: {e}"
+                )
+            })?;
+    }
+    Ok(())
+}
+
+/// Encodes `records`, the `part`th of `parts` parts of the updates at `time`, as the JSON body of
+/// a webhook request.
+///
+/// Each record is a row and whether it is retracted, and is encoded like a record of a Kafka sink
+/// with `ENVELOPE DEBEZIUM`: retractions set `before` and insertions set `after`.
+fn encode_batch<'a>(
+    time: Timestamp,
+    part: usize,
+    parts: usize,
+    records: impl Iterator<Item = (&'a Row, bool)>,
+    columns: &[(ColumnName, ColumnType)],
+) -> Vec<u8> {
+    let records: Vec<_> = records
+        .map(|(row, retraction)| {
+            let value = encode_datums_as_json(row.iter(), columns);
+            if retraction {
+                serde_json::json!({ "before": value, "after": null })
+            } else {
+                serde_json::json!({ "before": null, "after": value })
+            }
+        })
+        .collect();
+    let body = serde_json::json!({
+        // Timestamps are strings, as they don't fit into the numbers of many JSON parsers.
+        "timestamp": time.to_string(),
+        "part": part,
+        "parts": parts,
+        "updates": records,
+    });
+    body.to_string().into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_encode_batch() {
+        let columns = vec![
+            (ColumnName::from("a"), ScalarType::Int32.nullable(false)),
+            (ColumnName::from("b"), ScalarType::String.nullable(true)),
+        ];
+        let x = Row::pack_slice(&[Datum::Int32(1), Datum::String("x")]);
+        let null = Row::pack_slice(&[Datum::Int32(2), Datum::Null]);
+        let records = [(&x, true), (&null, false), (&null, false)];
+        let body: serde_json::Value = serde_json::from_slice(&encode_batch(
+            Timestamp::from(42),
+            1,
+            3,
+            records.into_iter(),
+            &columns,
+        ))
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "timestamp": "42",
+                "part": 1,
+                "parts": 3,
+                "updates": [
+                    { "before": { "a": 1, "b": "x" }, "after": null },
+                    { "before": null, "after": { "a": 2, "b": null } },
+                    { "before": null, "after": { "a": 2, "b": null } },
+                ],
+            })
+        );
+    }
+}
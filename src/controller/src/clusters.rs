@@ -28,8 +28,8 @@ use mz_controller_types::dyncfgs::CONTROLLER_PAST_GENERATION_REPLICA_CLEANUP_RET
 use mz_controller_types::{is_cluster_size_v2, ClusterId, ReplicaId};
 use mz_orchestrator::NamespacedOrchestrator;
 use mz_orchestrator::{
-    CpuLimit, DiskLimit, LabelSelectionLogic, LabelSelector, MemoryLimit, Service, ServiceConfig,
-    ServiceEvent, ServicePort,
+    CpuLimit, DiskLimit, LabelSelectionLogic, LabelSelector, MemoryLimit, Service, ServiceCapacity,
+    ServiceConfig, ServiceEvent, ServicePort, ServiceResources,
 };
 use mz_ore::halt;
 use mz_ore::instrument;
@@ -317,6 +317,24 @@ pub struct ClusterEvent {
     pub time: DateTime<Utc>,
 }
 
+/// A check of whether the orchestrator has the capacity to run replicas, as returned by
+/// [`Controller::replica_capacity_check`].
+///
+/// The check does not borrow the controller, so it can be run off the caller's task, and as often
+/// as needed.
+#[derive(Debug, Clone)]
+pub struct ReplicaCapacityCheck {
+    orchestrator: Arc<dyn NamespacedOrchestrator>,
+    resources: ServiceResources,
+}
+
+impl ReplicaCapacityCheck {
+    /// Asks the orchestrator whether it can currently run the replicas.
+    pub async fn check(&self) -> Result<ServiceCapacity, anyhow::Error> {
+        self.orchestrator.check_capacity(&self.resources).await
+    }
+}
+
 impl<T> Controller<T>
 where
     T: ComputeControllerTimestamp,
@@ -567,6 +585,31 @@ where
 
         Box::pin(stream)
     }
+
+    /// Returns a check of whether the orchestrator has the capacity to run `count` replicas at
+    /// `location`.
+    pub fn replica_capacity_check(
+        &self,
+        location: &ManagedReplicaLocation,
+        count: u16,
+    ) -> ReplicaCapacityCheck {
+        let resources = ServiceResources {
+            memory_limit: location.allocation.memory_limit,
+            cpu_limit: location.allocation.cpu_limit,
+            scale: location.allocation.scale.saturating_mul(count),
+            disk: location.disk,
+            availability_zones: match &location.availability_zones {
+                ManagedReplicaAvailabilityZones::FromCluster(azs) => azs.clone(),
+                ManagedReplicaAvailabilityZones::FromReplica(az) => az.clone().map(|z| vec![z]),
+            },
+            node_selector: location.allocation.selectors.clone(),
+        };
+        ReplicaCapacityCheck {
+            orchestrator: Arc::clone(&self.orchestrator),
+            resources,
+        }
+    }
+
     /// Provisions a replica with the service orchestrator.
     fn provision_replica(
         &self,
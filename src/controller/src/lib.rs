@@ -119,6 +119,8 @@ pub enum ControllerResponse<T = mz_repr::Timestamp> {
     SubscribeResponse(GlobalId, SubscribeBatch<T>),
     /// The worker's next response to a specified copy to.
     CopyToResponse(GlobalId, Result<u64, anyhow::Error>),
+    /// Notification that a compute sink failed on a replica.
+    ComputeSinkFailure(GlobalId, ReplicaId, String),
     /// Notification that new resource usage metrics are available for a given replica.
     ComputeReplicaMetrics(ReplicaId, Vec<ServiceProcessMetrics>),
     /// Notification that a watch set has finished. See
@@ -514,6 +516,13 @@ where
             ComputeControllerResponse::FrontierUpper { id, upper } => {
                 self.handle_frontier_updates(&[(id, upper)])
             }
+            ComputeControllerResponse::SinkFailure {
+                id,
+                replica_id,
+                error,
+            } => Some(ControllerResponse::ComputeSinkFailure(
+                id, replica_id, error,
+            )),
         });
         Ok(response)
     }
@@ -16,5 +16,19 @@ fn main() -> Result<(), anyhow::Error> {
         .file("src/bin/environmentd/sys.c")
         .compile("environmentd_sys");
 
+    env::set_var("PROTOC", mz_build_tools::protoc());
+
+    let mut config = prost_build::Config::new();
+    config.btree_map(["."]);
+
+    tonic_build::configure()
+        // Enabling `emit_rerun_if_changed` will rerun the build script when
+        // anything in the include directory (..) changes. This causes quite a
+        // bit of spurious recompilation, so we disable it. The default behavior
+        // is to re-run if any file in the crate changes; that's still a bit too
+        // broad, but it's better.
+        .emit_rerun_if_changed(false)
+        .compile_with_config(config, &["environmentd/src/http/grpc.proto"], &[".."])?;
+
     mz_npm::ensure()
 }
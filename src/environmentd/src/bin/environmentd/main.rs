@@ -271,6 +271,11 @@ pub struct Args {
     /// The optional fs group for service's pods' `securityContext`.
     #[clap(long, env = "ORCHESTRATOR_KUBERNETES_SERVICE_FS_GROUP")]
     orchestrator_kubernetes_service_fs_group: Option<i64>,
+    /// Whether the Kubernetes orchestrator checks the capacity of the
+    /// Kubernetes cluster for new cluster replicas, which requires permission
+    /// to list nodes and the pods of all namespaces.
+    #[clap(long, env = "ORCHESTRATOR_KUBERNETES_CAPACITY_CHECKS")]
+    orchestrator_kubernetes_capacity_checks: bool,
     #[clap(long, env = "ORCHESTRATOR_PROCESS_WRAPPER")]
     orchestrator_process_wrapper: Option<String>,
     /// Where the process orchestrator should store secrets.
@@ -362,6 +367,16 @@ pub struct Args {
     /// transition of power from the prior generation.
     #[clap(long, env = "DEPLOY_GENERATION", default_value = "0")]
     deploy_generation: u64,
+    /// Whether to run as a read-only replica of the environment.
+    ///
+    /// A read-only replica never fences out the leader. It serves serializable
+    /// SELECTs and SUBSCRIBEs from its own cluster replicas and follows the
+    /// leader's catalog changes, restarting only when the leader changes
+    /// clusters, replicas or collections. It must run the same version
+    /// as the leader and a deploy generation that no leader or other read-only
+    /// replica uses.
+    #[clap(long, env = "READ_ONLY_REPLICA")]
+    read_only_replica: bool,
 
     // === Storage options. ===
     /// Where the persist library should store its blob data.
@@ -716,6 +731,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                             .orchestrator_kubernetes_ephemeral_volume_class
                             .clone(),
                         service_fs_group: args.orchestrator_kubernetes_service_fs_group.clone(),
+                        capacity_checks: args.orchestrator_kubernetes_capacity_checks,
                     }))
                     .context("creating kubernetes orchestrator")?,
             );
@@ -946,6 +962,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                 // Special modes.
                 unsafe_mode: args.unsafe_mode,
                 all_features: args.all_features,
+                read_only_replica: args.read_only_replica,
                 // Connection options.
                 tls,
                 tls_reload_certs: mz_server_core::default_cert_reload_ticker(),
@@ -975,6 +992,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
                     .collect(),
                 config_sync_timeout: args.config_sync_timeout,
                 config_sync_loop_interval: args.config_sync_loop_interval,
+                statement_retirement_hooks: Default::default(),
                 // Bootstrap options.
                 environment_id: args.environment_id,
                 bootstrap_role: args.bootstrap_role,
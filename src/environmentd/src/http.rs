@@ -29,14 +29,15 @@ use axum::extract::{DefaultBodyLimit, FromRequestParts, Query, Request, State};
 use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Redirect, Response};
 use axum::{routing, Extension, Json, Router};
-use futures::future::{FutureExt, Shared, TryFutureExt};
+use futures::future::{FutureExt, Shared};
 use headers::authorization::{Authorization, Basic, Bearer};
 use headers::{HeaderMapExt, HeaderName};
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
 use http::{Method, StatusCode};
 use hyper_openssl::client::legacy::MaybeHttpsStream;
 use hyper_openssl::SslStream;
-use hyper_util::rt::TokioIo;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
 use mz_adapter::session::{Session, SessionConfig};
 use mz_adapter::{AdapterError, AdapterNotice, Client, SessionClient, WebhookAppenderCache};
 use mz_frontegg_auth::{Authenticator as FronteggAuthentication, Error as FronteggError};
@@ -60,20 +61,25 @@ use tokio::net::TcpStream;
 use tokio::sync::{oneshot, watch};
 use tower::limit::GlobalConcurrencyLimitLayer;
 use tower::{Service, ServiceBuilder};
+use tower_http::compression::predicate::{DefaultPredicate, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing::{error, warn};
 
 use crate::deployment::state::DeploymentStateHandle;
+use crate::http::session::{HttpSessions, SESSION_HEADER};
 use crate::http::sql::SqlError;
 use crate::BUILD_INFO;
 
 mod catalog;
 mod console;
 mod control;
+pub mod grpc;
 mod memory;
 mod metrics;
 mod probe;
 mod root;
+mod session;
 mod sql;
 mod webhook;
 
@@ -83,6 +89,10 @@ pub use sql::{SqlResponse, WebSocketAuth, WebSocketResponse};
 /// Maximum allowed size for a request.
 pub const MAX_REQUEST_SIZE: usize = u64_to_usize(2 * bytesize::MB);
 
+/// Minimum size of a response to `/api/sql` before we compress it. Smaller
+/// responses are not worth the CPU time.
+const MIN_COMPRESSED_SQL_RESPONSE_SIZE: u16 = 8 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
     pub source: &'static str,
@@ -156,6 +166,7 @@ impl HttpServer {
             }))
             .layer(Extension(adapter_client_rx.clone()))
             .layer(Extension(Arc::clone(&active_connection_count)))
+            .layer(Extension(HttpSessions::new()))
             .layer(
                 CorsLayer::new()
                     .allow_credentials(false)
@@ -163,6 +174,7 @@ impl HttpServer {
                         AUTHORIZATION,
                         CONTENT_TYPE,
                         HeaderName::from_static("x-materialize-version"),
+                        HeaderName::from_static(SESSION_HEADER),
                     ])
                     .allow_methods(Any)
                     .allow_origin(allowed_origin)
@@ -233,11 +245,11 @@ impl Server for HttpServer {
             };
             let tower_svc = router.layer(Extension(conn_protocol));
             let hyper_svc = hyper::service::service_fn(|req| tower_svc.clone().call(req));
-            let http = hyper::server::conn::http1::Builder::new();
-            http.serve_connection(conn, hyper_svc)
-                .with_upgrades()
-                .err_into()
+            // HTTP/2 is required by the gRPC SQL API.
+            let http = auto::Builder::new(TokioExecutor::new());
+            http.serve_connection_with_upgrades(conn, hyper_svc)
                 .await
+                .map_err(|e| anyhow::anyhow!(e))
         })
     }
 }
@@ -346,6 +358,11 @@ impl InternalHttpServer {
                 "/api/catalog/dump",
                 routing::get(catalog::handle_catalog_dump),
             )
+            .route(
+                "/api/catalog/bundle",
+                routing::get(catalog::handle_catalog_bundle_export)
+                    .post(catalog::handle_catalog_bundle_apply),
+            )
             .route(
                 "/api/catalog/check",
                 routing::get(catalog::handle_catalog_check),
@@ -358,6 +375,14 @@ impl InternalHttpServer {
                 "/api/coordinator/dump",
                 routing::get(catalog::handle_coordinator_dump),
             )
+            .route(
+                "/api/coordinator/simulate",
+                routing::post(catalog::handle_coordinator_simulate),
+            )
+            .route(
+                "/api/plan-graph/:conn_id",
+                routing::get(catalog::handle_plan_graph),
+            )
             // This is called /api/control, because it's mean as a control endpoint.
             // Not controller, as in the thing that a Coordinator holds and which is
             // currently the only thing that this _can_ control.
@@ -380,7 +405,8 @@ impl InternalHttpServer {
             .layer(middleware::from_fn(internal_http_auth))
             .layer(Extension(adapter_client_rx.clone()))
             .layer(Extension(console_config))
-            .layer(Extension(Arc::clone(&active_connection_count)));
+            .layer(Extension(Arc::clone(&active_connection_count)))
+            .layer(Extension(HttpSessions::new()));
 
         let ws_router = Router::new()
             .route("/api/experimental/sql", routing::get(sql::handle_sql_ws))
@@ -439,11 +465,11 @@ impl Server for InternalHttpServer {
         let service = hyper::service::service_fn(move |req| router.clone().call(req));
 
         Box::pin(async {
-            let http = hyper::server::conn::http1::Builder::new();
-            http.serve_connection(TokioIo::new(conn), service)
-                .with_upgrades()
-                .err_into()
+            // HTTP/2 is required by the gRPC SQL API.
+            let http = auto::Builder::new(TokioExecutor::new());
+            http.serve_connection_with_upgrades(TokioIo::new(conn), service)
                 .await
+                .map_err(|e| anyhow::anyhow!(e))
         })
     }
 }
@@ -829,7 +855,21 @@ fn base_router(BaseRouterConfig { profiling }: BaseRouterConfig) -> Router {
             "/",
             routing::get(move || async move { root::handle_home(profiling).await }),
         )
-        .route("/api/sql", routing::post(sql::handle_sql))
+        .route(
+            "/api/sql",
+            routing::post(sql::handle_sql).layer(
+                // Compress large results for clients that ask for it with
+                // `Accept-Encoding`.
+                CompressionLayer::new().compress_when(
+                    DefaultPredicate::new().and(SizeAbove::new(MIN_COMPRESSED_SQL_RESPONSE_SIZE)),
+                ),
+            ),
+        )
+        .route(
+            "/api/sql/session",
+            routing::post(session::handle_create_session).delete(session::handle_delete_session),
+        )
+        .route_service(grpc::ROUTE, grpc::service())
         .route("/memory", routing::get(memory::handle_memory))
         .route(
             "/hierarchical-memory",
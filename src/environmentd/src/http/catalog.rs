@@ -9,10 +9,15 @@
 
 //! Catalog introspection HTTP endpoints.
 
+use axum::extract::{Path, Query};
 use axum::response::IntoResponse;
+use axum::Json;
 use axum_extra::TypedHeader;
 use headers::ContentType;
 use http::StatusCode;
+use mz_adapter::catalog::CatalogBundle;
+use mz_adapter::SimulationStep;
+use serde::Deserialize;
 
 use crate::http::AuthedClient;
 
@@ -23,6 +28,43 @@ pub async fn handle_catalog_dump(mut client: AuthedClient) -> impl IntoResponse
     }
 }
 
+pub async fn handle_catalog_bundle_export(mut client: AuthedClient) -> impl IntoResponse {
+    match client.client.export_catalog_bundle().await {
+        Ok(bundle) => Ok(Json(bundle)),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+}
+
+/// Query parameters for [`handle_catalog_bundle_apply`].
+#[derive(Deserialize)]
+pub struct CatalogBundleApplyParams {
+    /// Whether to only validate the bundle, without creating its objects.
+    #[serde(default)]
+    dry_run: bool,
+}
+
+pub async fn handle_catalog_bundle_apply(
+    mut client: AuthedClient,
+    Query(params): Query<CatalogBundleApplyParams>,
+    Json(bundle): Json<CatalogBundle>,
+) -> impl IntoResponse {
+    let (status, result) = match client
+        .client
+        .apply_catalog_bundle(bundle, params.dry_run)
+        .await
+    {
+        Ok(statements) => (
+            StatusCode::OK,
+            serde_json::json!({ "statements": statements, "dry_run": params.dry_run }),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            serde_json::json!({ "err": e.to_string() }),
+        ),
+    };
+    (status, TypedHeader(ContentType::json()), result.to_string())
+}
+
 pub async fn handle_catalog_check(mut client: AuthedClient) -> impl IntoResponse {
     let response = match client.client.check_catalog().await {
         Ok(_) => serde_json::Value::String("".to_string()),
@@ -49,3 +91,42 @@ pub async fn handle_coordinator_dump(mut client: AuthedClient) -> impl IntoRespo
     };
     (status, TypedHeader(ContentType::json()), result.to_string())
 }
+
+/// The body of [`handle_coordinator_simulate`].
+#[derive(Deserialize)]
+pub struct CoordinatorSimulation {
+    /// The steps of each actor of the simulation.
+    script: Vec<Vec<SimulationStep>>,
+    /// The actors that take the next step, in order.
+    #[serde(default)]
+    schedule: Vec<usize>,
+}
+
+pub async fn handle_coordinator_simulate(
+    mut client: AuthedClient,
+    Json(simulation): Json<CoordinatorSimulation>,
+) -> impl IntoResponse {
+    let response = match client
+        .client
+        .simulate_coordinator(simulation.script, simulation.schedule)
+        .await
+    {
+        Ok(trace) => serde_json::json!({ "trace": trace }),
+        Err(failure) => serde_json::json!({ "err": failure }),
+    };
+    (TypedHeader(ContentType::json()), response.to_string())
+}
+
+pub async fn handle_plan_graph(
+    mut client: AuthedClient,
+    Path(conn_id): Path<u32>,
+) -> impl IntoResponse {
+    let (status, result) = match client.client.get_plan_graph(conn_id).await {
+        Some(graph) => (StatusCode::OK, graph),
+        None => (
+            StatusCode::NOT_FOUND,
+            serde_json::json!({ "err": format!("no plan graph for connection {conn_id}") }),
+        ),
+    };
+    (status, TypedHeader(ContentType::json()), result.to_string())
+}
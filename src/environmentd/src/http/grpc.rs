@@ -0,0 +1,569 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The gRPC SQL API.
+//!
+//! The service is served by the HTTP servers alongside the HTTP SQL API, over
+//! HTTP/2. Requests pass through the same authentication middleware as the
+//! other HTTP routes, and each `Execute` or `Subscribe` call runs in a new
+//! session of the authenticated user, so the usual session machinery and
+//! access control apply. Statements are executed by the same code that
+//! executes requests to `/api/sql`.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::future::BoxFuture;
+use mz_adapter::client::RecordFirstRowStream;
+use mz_adapter::statement_logging::{StatementEndedExecutionReason, StatementExecutionStrategy};
+use mz_adapter::{
+    verify_datum_desc, AdapterError, AdapterNotice, ExecuteContextExtra, PeekResponseUnary,
+    SessionClient,
+};
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_repr::{RelationDesc, RowIterator};
+use mz_sql::ast::Statement;
+use mz_sql::parse::StatementParseResult;
+use mz_sql::session::vars::{Value as _, Var, VarInput, WELCOME_MESSAGE};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Extensions, Request, Response, Status};
+
+use crate::http::grpc::proto::sql_server::{Sql, SqlServer};
+use crate::http::grpc::proto::{
+    execute_request, statement_result, subscribe_response, CancelRequest, CancelResponse,
+    ExecuteRequest, ExecuteResponse, ExtendedQueries, SubscribeRequest, SubscribeResponse,
+};
+use crate::http::sql::{
+    await_rows, execute_request, make_notices, Description, Error, ExtendedRequest, Notice,
+    ResultSender, SqlError, SqlRequest, SqlResult, StatementResult,
+};
+use crate::http::{AuthedClient, AuthedUser, Delayed, SharedConnectionCounter};
+
+/// The messages and service of the gRPC SQL API.
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/mz_environmentd.grpc.rs"));
+}
+
+/// The route under which the HTTP servers serve the gRPC SQL API.
+pub(super) const ROUTE: &str = "/mz_environmentd.grpc.Sql/*rpc";
+
+/// The number of messages buffered for a `Subscribe` call before execution
+/// waits for the client to catch up.
+const SUBSCRIBE_BUFFER: usize = 16;
+
+/// Returns the service that implements the gRPC SQL API.
+pub(super) fn service() -> SqlServer<SqlService> {
+    SqlServer::new(SqlService)
+}
+
+/// The implementation of the gRPC SQL API.
+///
+/// The service is stateless: the adapter client and the authenticated user
+/// are provided by the HTTP server as request extensions.
+pub(super) struct SqlService;
+
+#[async_trait]
+impl Sql for SqlService {
+    async fn execute(
+        &self,
+        request: Request<ExecuteRequest>,
+    ) -> Result<Response<ExecuteResponse>, Status> {
+        let (_metadata, extensions, ExecuteRequest { options, request }) = request.into_parts();
+        let request = match request {
+            Some(execute_request::Request::Query(query)) => SqlRequest::Simple { query },
+            Some(execute_request::Request::Extended(ExtendedQueries { queries })) => {
+                SqlRequest::Extended {
+                    queries: queries
+                        .into_iter()
+                        .map(|query| ExtendedRequest {
+                            query: query.query,
+                            params: query.params.into_iter().map(|param| param.text).collect(),
+                        })
+                        .collect(),
+                }
+            }
+            None => return Err(Status::invalid_argument("missing query")),
+        };
+        let mut client = authed_client(&extensions, options).await?;
+        let mut response = ExecuteSender {
+            results: Vec::new(),
+        };
+        execute_request(&mut client, request, &mut response)
+            .await
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        Ok(Response::new(ExecuteResponse {
+            results: response.results,
+        }))
+    }
+
+    type SubscribeStream = ReceiverStream<Result<SubscribeResponse, Status>>;
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let (_metadata, extensions, request) = request.into_parts();
+        let stmts = mz_sql::parse::parse(&request.query)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        if !matches!(
+            stmts.as_slice(),
+            [StatementParseResult {
+                ast: Statement::Subscribe(_),
+                ..
+            }]
+        ) {
+            return Err(Status::invalid_argument(
+                "query must contain exactly 1 SUBSCRIBE statement",
+            ));
+        }
+
+        let mut client = authed_client(&extensions, request.options).await?;
+        let (tx, rx) = mpsc::channel(SUBSCRIBE_BUFFER);
+        let session = client.client.session();
+        let started = subscribe_response::Message::Session(proto::Session {
+            conn_id: session.conn_id().unhandled(),
+            secret_key: session.secret_key(),
+        });
+        tx.try_send(Ok(SubscribeResponse {
+            message: Some(started),
+        }))
+        .expect("channel known to be empty");
+
+        // Execution must outlive this handler so that it can keep feeding the
+        // response stream. The session is terminated once the client goes
+        // away.
+        task::spawn(|| "grpc_subscribe", async move {
+            let mut sender = SubscribeSender { tx };
+            let request = SqlRequest::Simple {
+                query: request.query,
+            };
+            if let Err(e) = execute_request(&mut client, request, &mut sender).await {
+                let _ = sender
+                    .send(subscribe_response::Message::Error(e.into()))
+                    .await;
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn cancel(
+        &self,
+        request: Request<CancelRequest>,
+    ) -> Result<Response<CancelResponse>, Status> {
+        let (_metadata, extensions, request) = request.into_parts();
+        let mut adapter_client = adapter_client(&extensions).await?;
+        // The HTTP server doesn't track the addresses of its clients.
+        adapter_client.cancel_request(request.conn_id, request.secret_key, None);
+        Ok(Response::new(CancelResponse {}))
+    }
+}
+
+async fn adapter_client(extensions: &Extensions) -> Result<mz_adapter::Client, Status> {
+    let adapter_client = extensions
+        .get::<Delayed<mz_adapter::Client>>()
+        .expect("provided by the HTTP server")
+        .clone();
+    adapter_client
+        .await
+        .map_err(|_| Status::unavailable("adapter client missing"))
+}
+
+/// Starts a session for the authenticated user of a request, like the
+/// [`AuthedClient`] extractor of the HTTP SQL API.
+async fn authed_client(
+    extensions: &Extensions,
+    options: BTreeMap<String, String>,
+) -> Result<AuthedClient, Status> {
+    let user = extensions
+        .get::<AuthedUser>()
+        .expect("provided by the authentication middleware");
+    let adapter_client = adapter_client(extensions).await?;
+    let active_connection_count = extensions
+        .get::<SharedConnectionCounter>()
+        .expect("provided by the HTTP server");
+    AuthedClient::new(
+        &adapter_client,
+        user.clone(),
+        Arc::clone(active_connection_count),
+        |session| {
+            session
+                .vars_mut()
+                .set_default(WELCOME_MESSAGE.name(), VarInput::Flat(&false.format()))
+                .expect("known to exist")
+        },
+        options,
+    )
+    .await
+    .map_err(|e| match e {
+        AdapterError::UserSessionsDisallowed => Status::permission_denied(e.to_string()),
+        _ => Status::internal(e.to_string()),
+    })
+}
+
+/// Encodes rows in the PostgreSQL text format.
+fn encode_rows(desc: &RelationDesc, rows: &mut dyn RowIterator) -> Vec<proto::Row> {
+    let typ = desc.typ();
+    let mut buf = BytesMut::new();
+    let mut encoded = Vec::new();
+    while let Some(row) = rows.next() {
+        let values = mz_pgrepr::values_from_row(row, typ)
+            .into_iter()
+            .map(|value| proto::Value {
+                text: value.map(|value| {
+                    buf.clear();
+                    value.encode_text(&mut buf);
+                    String::from_utf8_lossy(&buf).into_owned()
+                }),
+            })
+            .collect();
+        encoded.push(proto::Row { values });
+    }
+    encoded
+}
+
+fn columns(desc: &RelationDesc) -> Vec<proto::Column> {
+    Description::from(desc)
+        .columns
+        .into_iter()
+        .map(|column| proto::Column {
+            name: column.name,
+            type_oid: column.type_oid,
+            type_len: column.type_len.into(),
+            type_mod: column.type_mod,
+        })
+        .collect()
+}
+
+impl From<SqlError> for proto::Error {
+    fn from(error: SqlError) -> Self {
+        proto::Error {
+            code: error.code,
+            message: error.message,
+            detail: error.detail,
+            hint: error.hint,
+            position: error.position.map(u64::cast_from),
+        }
+    }
+}
+
+impl From<Error> for proto::Error {
+    fn from(error: Error) -> Self {
+        SqlError::from(error).into()
+    }
+}
+
+impl From<Notice> for proto::Notice {
+    fn from(notice: Notice) -> Self {
+        proto::Notice {
+            code: notice.code,
+            message: notice.message,
+            severity: notice.severity,
+            detail: notice.detail,
+            hint: notice.hint,
+        }
+    }
+}
+
+impl From<AdapterNotice> for proto::Notice {
+    fn from(notice: AdapterNotice) -> Self {
+        proto::Notice {
+            code: notice.code().code().to_string(),
+            message: notice.to_string(),
+            severity: notice.severity().as_str().to_lowercase(),
+            detail: notice.detail(),
+            hint: notice.hint(),
+        }
+    }
+}
+
+fn notices(notices: Vec<Notice>) -> Vec<proto::Notice> {
+    notices.into_iter().map(proto::Notice::from).collect()
+}
+
+/// Collects the results of an `Execute` call.
+struct ExecuteSender {
+    results: Vec<proto::StatementResult>,
+}
+
+#[async_trait]
+impl ResultSender for ExecuteSender {
+    // See the implementation for `WebSocket` for the meaning of the return value.
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> (
+        Result<Result<(), ()>, Error>,
+        Option<(StatementEndedExecutionReason, ExecuteContextExtra)>,
+    ) {
+        let (result, stmt_logging) = match res {
+            StatementResult::SqlResult(SqlResult::Ok {
+                ok,
+                notices: ok_notices,
+                parameters,
+            }) => {
+                let ok = proto::Ok {
+                    tag: ok,
+                    parameters: parameters
+                        .into_iter()
+                        .map(|param| proto::ParameterStatus {
+                            name: param.name,
+                            value: param.value,
+                        })
+                        .collect(),
+                };
+                (
+                    proto::StatementResult {
+                        result: Some(statement_result::Result::Ok(ok)),
+                        notices: notices(ok_notices),
+                    },
+                    None,
+                )
+            }
+            StatementResult::SqlResult(SqlResult::Err {
+                error,
+                notices: err_notices,
+            }) => (
+                proto::StatementResult {
+                    result: Some(statement_result::Result::Error(error.into())),
+                    notices: notices(err_notices),
+                },
+                None,
+            ),
+            StatementResult::SqlResult(SqlResult::Rows { .. }) => {
+                unreachable!("rows are produced by `rows_result`")
+            }
+            StatementResult::RawRows { desc, mut rows } => {
+                let result = match verify_datum_desc(&desc, &mut rows) {
+                    Ok(()) => {
+                        let rows = encode_rows(&desc, &mut *rows);
+                        statement_result::Result::Rows(proto::Rows {
+                            tag: format!("SELECT {}", rows.len()),
+                            columns: columns(&desc),
+                            rows,
+                        })
+                    }
+                    Err(err) => statement_result::Result::Error(SqlError::from(err).into()),
+                };
+                (
+                    proto::StatementResult {
+                        result: Some(result),
+                        notices: notices(make_notices(client)),
+                    },
+                    None,
+                )
+            }
+            StatementResult::Subscribe { ctx_extra, .. } => {
+                let error = Error::Unsupported("SUBSCRIBE outside the Subscribe RPC".into());
+                let message = error.to_string();
+                (
+                    proto::StatementResult {
+                        result: Some(statement_result::Result::Error(error.into())),
+                        notices: Vec::new(),
+                    },
+                    Some((
+                        StatementEndedExecutionReason::Errored { error: message },
+                        ctx_extra,
+                    )),
+                )
+            }
+        };
+        let is_err = matches!(result.result, Some(statement_result::Result::Error(_)));
+        self.results.push(result);
+        (Ok(if is_err { Err(()) } else { Ok(()) }), stmt_logging)
+    }
+
+    fn connection_error(&mut self) -> BoxFuture<Error> {
+        // Execution is dropped along with the call if the client goes away.
+        Box::pin(futures::future::pending())
+    }
+
+    fn allow_subscribe(&self) -> bool {
+        false
+    }
+
+    fn rows_result(
+        _client: &mut SessionClient,
+        rows: Box<dyn RowIterator + Send + Sync>,
+        desc: RelationDesc,
+    ) -> StatementResult {
+        StatementResult::RawRows { desc, rows }
+    }
+}
+
+/// Streams the updates of a `Subscribe` call.
+struct SubscribeSender {
+    tx: mpsc::Sender<Result<SubscribeResponse, Status>>,
+}
+
+impl SubscribeSender {
+    async fn send(&mut self, message: subscribe_response::Message) -> Result<(), Error> {
+        self.tx
+            .send(Ok(SubscribeResponse {
+                message: Some(message),
+            }))
+            .await
+            .map_err(|_| Error::Unstructured(anyhow!("client disconnected")))
+    }
+
+    async fn send_notices(&mut self, notices: Vec<Notice>) -> Result<(), Error> {
+        for notice in notices {
+            self.send(subscribe_response::Message::Notice(notice.into()))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Streams the updates of a subscription until it ends, returning the
+    /// error it ended with, if any.
+    async fn stream_updates(
+        &mut self,
+        client: &mut SessionClient,
+        desc: &RelationDesc,
+        rx: &mut RecordFirstRowStream,
+    ) -> Result<(Option<Error>, StatementEndedExecutionReason), Error> {
+        let mut rows_returned = 0;
+        loop {
+            match await_rows(self, client, rx.recv()).await? {
+                Some(PeekResponseUnary::Rows(mut rows)) => {
+                    if let Err(err) = verify_datum_desc(desc, &mut rows) {
+                        let error = err.to_string();
+                        return Ok((
+                            Some(err.into()),
+                            StatementEndedExecutionReason::Errored { error },
+                        ));
+                    }
+                    rows_returned += rows.count();
+                    let rows = encode_rows(desc, &mut *rows);
+                    self.send(subscribe_response::Message::Updates(proto::Updates {
+                        rows,
+                    }))
+                    .await?;
+                }
+                Some(PeekResponseUnary::Error(error)) => {
+                    return Ok((
+                        Some(Error::Unstructured(anyhow!(error.clone()))),
+                        StatementEndedExecutionReason::Errored { error },
+                    ));
+                }
+                Some(PeekResponseUnary::Canceled) => {
+                    return Ok((
+                        Some(AdapterError::Canceled.into()),
+                        StatementEndedExecutionReason::Canceled,
+                    ));
+                }
+                None => {
+                    return Ok((
+                        None,
+                        StatementEndedExecutionReason::Success {
+                            rows_returned: Some(u64::cast_from(rows_returned)),
+                            execution_strategy: Some(StatementExecutionStrategy::Standard),
+                            max_result_memory_bytes: None,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ResultSender for SubscribeSender {
+    const SUPPORTS_STREAMING_NOTICES: bool = true;
+
+    // See the implementation for `WebSocket` for the meaning of the return value.
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> (
+        Result<Result<(), ()>, Error>,
+        Option<(StatementEndedExecutionReason, ExecuteContextExtra)>,
+    ) {
+        match res {
+            StatementResult::SqlResult(SqlResult::Ok { notices, .. }) => {
+                (self.send_notices(notices).await.map(Ok), None)
+            }
+            StatementResult::SqlResult(SqlResult::Err { error, notices }) => {
+                let sent = match self.send_notices(notices).await {
+                    Ok(()) => {
+                        self.send(subscribe_response::Message::Error(error.into()))
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                (sent.map(|()| Err(())), None)
+            }
+            StatementResult::SqlResult(SqlResult::Rows { .. })
+            | StatementResult::RawRows { .. } => {
+                unreachable!("the Subscribe RPC only executes SUBSCRIBE statements")
+            }
+            StatementResult::Subscribe {
+                desc,
+                tag,
+                mut rx,
+                ctx_extra,
+            } => {
+                let columns = subscribe_response::Message::Columns(proto::Columns {
+                    columns: columns(&desc),
+                });
+                let streamed = match self.send(columns).await {
+                    Ok(()) => self.stream_updates(client, &desc, &mut rx).await,
+                    Err(e) => Err(e),
+                };
+                let (error, reason) = match streamed {
+                    Ok(streamed) => streamed,
+                    // We consider the remote breaking the connection to be a
+                    // cancellation, matching the behavior for pgwire.
+                    Err(e) => {
+                        return (
+                            Err(e),
+                            Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                        )
+                    }
+                };
+                let (message, res) = match error {
+                    Some(error) => (subscribe_response::Message::Error(error.into()), Err(())),
+                    None => (subscribe_response::Message::Complete(tag), Ok(())),
+                };
+                match self.send(message).await {
+                    Ok(()) => (Ok(res), Some((reason, ctx_extra))),
+                    Err(e) => (
+                        Err(e),
+                        Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn connection_error(&mut self) -> BoxFuture<Error> {
+        Box::pin(async {
+            self.tx.closed().await;
+            Error::Unstructured(anyhow!("client disconnected"))
+        })
+    }
+
+    fn allow_subscribe(&self) -> bool {
+        true
+    }
+
+    async fn emit_streaming_notices(&mut self, notices: Vec<AdapterNotice>) -> Result<(), Error> {
+        for notice in notices {
+            self.send(subscribe_response::Message::Notice(notice.into()))
+                .await?;
+        }
+        Ok(())
+    }
+}
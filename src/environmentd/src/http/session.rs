@@ -0,0 +1,287 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sticky sessions for the HTTP SQL API.
+//!
+//! By default, each request to `/api/sql` runs in a fresh session that is
+//! terminated when the request completes. Clients that need state across
+//! requests, like transactions, prepared statements or session variables, can
+//! instead create a session with `POST /api/sql/session` and pass the returned
+//! token in the [`SESSION_HEADER`] of later requests. The session lives until
+//! it is deleted with `DELETE /api/sql/session`, or until it has been idle for
+//! [`SESSION_IDLE_TIMEOUT`]. Each user may hold at most
+//! [`MAX_SESSIONS_PER_USER`] sessions at a time.
+
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use http::request::Parts;
+use http::{HeaderMap, StatusCode};
+use mz_ore::task;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde_json::json;
+use tokio::sync::OwnedMutexGuard;
+
+use crate::http::{AuthedClient, AuthedUser};
+
+/// The header that carries the token of the session to run a request in.
+pub const SESSION_HEADER: &str = "x-materialize-session";
+
+/// How long a session may go unused before it is terminated.
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How often sessions that have been idle for too long are terminated.
+const SESSION_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The maximum number of sessions a user may hold at a time.
+const MAX_SESSIONS_PER_USER: usize = 16;
+
+/// The number of characters in a session token.
+const SESSION_TOKEN_LEN: usize = 32;
+
+/// The sticky sessions of an HTTP server, by token.
+#[derive(Clone)]
+pub struct HttpSessions {
+    sessions: Arc<Mutex<BTreeMap<String, HttpSession>>>,
+}
+
+struct HttpSession {
+    /// The name of the user that created the session. Only that user may run
+    /// requests in the session.
+    user: String,
+    client: Arc<tokio::sync::Mutex<AuthedClient>>,
+    last_used: Instant,
+}
+
+impl HttpSessions {
+    /// Creates an empty set of sessions, and starts a task that terminates
+    /// the sessions that have been idle for too long, even if no further
+    /// requests arrive.
+    pub fn new() -> Self {
+        let sessions = Arc::new(Mutex::new(BTreeMap::new()));
+        let weak_sessions = Arc::downgrade(&sessions);
+        task::spawn(|| "http_session_reaper", Self::reap(weak_sessions));
+        HttpSessions { sessions }
+    }
+
+    /// Periodically removes idle sessions, until the sessions are dropped.
+    async fn reap(sessions: Weak<Mutex<BTreeMap<String, HttpSession>>>) {
+        let mut interval = tokio::time::interval(SESSION_REAP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(sessions) = sessions.upgrade() else {
+                break;
+            };
+            // Take the expired sessions out of the lock before dropping them,
+            // which terminates them.
+            let expired = {
+                let mut sessions = sessions.lock().expect("lock poisoned");
+                Self::expire_idle(&mut sessions)
+            };
+            drop(expired);
+        }
+    }
+
+    /// Stores `client` as the session of `user`, and returns the token that
+    /// identifies it.
+    ///
+    /// Fails if `user` already holds [`MAX_SESSIONS_PER_USER`] sessions.
+    fn insert(&self, user: String, client: AuthedClient) -> Result<String, Response> {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        Self::expire_idle(&mut sessions);
+        let user_sessions = sessions
+            .values()
+            .filter(|session| session.user == user)
+            .count();
+        if user_sessions >= MAX_SESSIONS_PER_USER {
+            return Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("user already holds the maximum of {MAX_SESSIONS_PER_USER} sessions"),
+            )
+                .into_response());
+        }
+        let token = loop {
+            let token: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(SESSION_TOKEN_LEN)
+                .map(char::from)
+                .collect();
+            if !sessions.contains_key(&token) {
+                break token;
+            }
+        };
+        sessions.insert(
+            token.clone(),
+            HttpSession {
+                user,
+                client: Arc::new(tokio::sync::Mutex::new(client)),
+                last_used: Instant::now(),
+            },
+        );
+        Ok(token)
+    }
+
+    /// Claims the session identified by `token` for a single request.
+    fn claim(&self, token: &str, user: &str) -> Result<StickyClient, Response> {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        Self::expire_idle(&mut sessions);
+        let session = match sessions.get_mut(token) {
+            Some(session) if session.user == user => session,
+            // Don't reveal the existence of sessions of other users.
+            _ => return Err(unknown_session()),
+        };
+        let client = Arc::clone(&session.client).try_lock_owned().map_err(|_| {
+            (StatusCode::CONFLICT, "session is in use by another request").into_response()
+        })?;
+        Ok(StickyClient {
+            client,
+            sessions: self.clone(),
+            token: token.to_string(),
+        })
+    }
+
+    /// Marks the session identified by `token` as used just now.
+    fn touch(&self, token: &str) {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        if let Some(session) = sessions.get_mut(token) {
+            session.last_used = Instant::now();
+        }
+    }
+
+    /// Removes the session identified by `token`, which terminates it once it
+    /// is no longer in use.
+    fn remove(&self, token: &str, user: &str) -> bool {
+        let mut sessions = self.sessions.lock().expect("lock poisoned");
+        match sessions.get(token) {
+            Some(session) if session.user == user => sessions.remove(token).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Removes and returns the sessions that are not in use and have been idle
+    /// for longer than [`SESSION_IDLE_TIMEOUT`].
+    fn expire_idle(sessions: &mut BTreeMap<String, HttpSession>) -> Vec<HttpSession> {
+        let expired: Vec<_> = sessions
+            .iter()
+            .filter(|(_token, session)| {
+                session.last_used.elapsed() >= SESSION_IDLE_TIMEOUT
+                    && session.client.try_lock().is_ok()
+            })
+            .map(|(token, _session)| token.clone())
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|token| sessions.remove(&token))
+            .collect()
+    }
+}
+
+fn unknown_session() -> Response {
+    (StatusCode::NOT_FOUND, "unknown session").into_response()
+}
+
+/// Returns the session token of a request, if it has one.
+fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// A sticky session claimed by a request.
+pub struct StickyClient {
+    client: OwnedMutexGuard<AuthedClient>,
+    sessions: HttpSessions,
+    token: String,
+}
+
+impl Drop for StickyClient {
+    fn drop(&mut self) {
+        // The session is idle from the moment the request completes.
+        self.sessions.touch(&self.token);
+    }
+}
+
+/// The client that a request to `/api/sql` runs in.
+pub enum SqlClient {
+    /// A fresh session that is terminated when the request completes.
+    Ephemeral(AuthedClient),
+    /// A sticky session that outlives the request.
+    Sticky(StickyClient),
+}
+
+impl Deref for SqlClient {
+    type Target = AuthedClient;
+
+    fn deref(&self) -> &AuthedClient {
+        match self {
+            SqlClient::Ephemeral(client) => client,
+            SqlClient::Sticky(sticky) => &sticky.client,
+        }
+    }
+}
+
+impl DerefMut for SqlClient {
+    fn deref_mut(&mut self) -> &mut AuthedClient {
+        match self {
+            SqlClient::Ephemeral(client) => client,
+            SqlClient::Sticky(sticky) => &mut sticky.client,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SqlClient
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(req: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(token) = session_token(&req.headers) else {
+            let client = AuthedClient::from_request_parts(req, state).await?;
+            return Ok(SqlClient::Ephemeral(client));
+        };
+        let user = req.extensions.get::<AuthedUser>().unwrap();
+        let sessions = req.extensions.get::<HttpSessions>().unwrap();
+        let sticky = sessions.claim(token, &user.name)?;
+        Ok(SqlClient::Sticky(sticky))
+    }
+}
+
+/// Creates a sticky session, configured by the `options` query parameter like
+/// the session of a regular request.
+pub async fn handle_create_session(
+    Extension(user): Extension<AuthedUser>,
+    Extension(sessions): Extension<HttpSessions>,
+    client: AuthedClient,
+) -> Response {
+    match sessions.insert(user.name, client) {
+        Ok(token) => (StatusCode::CREATED, Json(json!({ "session": token }))).into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Terminates the sticky session identified by the [`SESSION_HEADER`].
+pub async fn handle_delete_session(
+    Extension(user): Extension<AuthedUser>,
+    Extension(sessions): Extension<HttpSessions>,
+    headers: HeaderMap,
+) -> Response {
+    match session_token(&headers) {
+        Some(token) if sessions.remove(token, &user.name) => StatusCode::NO_CONTENT.into_response(),
+        _ => unknown_session(),
+    }
+}
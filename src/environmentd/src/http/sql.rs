@@ -15,13 +15,15 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use axum::body::Body;
 use axum::extract::ws::{CloseFrame, Message, WebSocket};
 use axum::extract::{State, WebSocketUpgrade};
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use futures::future::BoxFuture;
 use futures::Future;
-use http::StatusCode;
+use http::header::{ACCEPT, CONTENT_TYPE};
+use http::{HeaderMap, StatusCode};
 use itertools::izip;
 use mz_adapter::client::RecordFirstRowStream;
 use mz_adapter::session::{EndTransactionAction, TransactionStatus};
@@ -30,23 +32,27 @@ use mz_adapter::{
     verify_datum_desc, AdapterError, AdapterNotice, ExecuteContextExtra, ExecuteResponse,
     ExecuteResponseKind, PeekResponseUnary, SessionClient,
 };
+use mz_arrow_util::ipc::{StreamEncoder, STREAM_CONTENT_TYPE};
 use mz_interchange::encode::TypedDatum;
 use mz_interchange::json::{JsonNumberPolicy, ToJson};
 use mz_ore::cast::CastFrom;
 use mz_ore::result::ResultExt;
+use mz_ore::task;
 use mz_repr::{Datum, RelationDesc, RowArena, RowIterator};
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{CopyDirection, CopyStatement, CopyTarget, Raw, Statement, StatementKind};
 use mz_sql::parse::StatementParseResult;
-use mz_sql::plan::Plan;
+use mz_sql::plan::{Plan, CONSISTENCY_TOKEN_PARAMETER};
 use mz_sql::session::metadata::SessionMetadata;
 use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
 use tokio::{select, time};
 use tokio_postgres::error::SqlState;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::debug;
 use tungstenite::protocol::frame::coding::CloseCode;
 
+use crate::http::session::SqlClient;
 use crate::http::{init_ws, AuthedClient, AuthedUser, WsState, MAX_REQUEST_SIZE};
 
 #[derive(Debug, thiserror::Error)]
@@ -98,18 +104,79 @@ impl Error {
     }
 }
 
+/// The number of Arrow IPC stream chunks buffered for a client before execution waits for the
+/// client to catch up.
+const ARROW_STREAM_BUFFER: usize = 16;
+
 pub async fn handle_sql(
-    mut client: AuthedClient,
+    mut client: SqlClient,
+    headers: HeaderMap,
     Json(request): Json<SqlRequest>,
-) -> impl IntoResponse {
+) -> Response {
+    if accepts_arrow_stream(&headers) {
+        return handle_sql_arrow(client, request).await;
+    }
+
     let mut res = SqlResponse {
         results: Vec::new(),
     };
     // Don't need to worry about timeouts or resetting cancel here because there is always exactly 1
     // request.
     match execute_request(&mut client, request, &mut res).await {
-        Ok(()) => Ok(Json(res)),
-        Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
+        Ok(()) => Json(res).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+/// Reports whether the client asked for results as an Arrow IPC stream.
+fn accepts_arrow_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case(STREAM_CONTENT_TYPE))
+}
+
+/// Executes `request`, streaming the rows of the first statement that returns rows, including
+/// `SUBSCRIBE`, to the client as an Arrow IPC stream.
+///
+/// If no statement returns rows, or a statement fails before one does, the response is the same
+/// JSON [`SqlResponse`] that [`handle_sql`] would have returned.
+async fn handle_sql_arrow(mut client: SqlClient, request: SqlRequest) -> Response {
+    let (head_tx, head_rx) = oneshot::channel();
+    let (body_tx, body_rx) = mpsc::channel(ARROW_STREAM_BUFFER);
+    // Execution must outlive this handler so that it can keep feeding the response body.
+    task::spawn(|| "http_sql_arrow", async move {
+        let mut res = ArrowResponse {
+            head: Some(head_tx),
+            body: body_tx,
+            results: Vec::new(),
+        };
+        let result = execute_request(&mut client, request, &mut res).await;
+        match (res.head.take(), result) {
+            (Some(head), Ok(())) => {
+                let _ = head.send(ArrowHead::Json(SqlResponse {
+                    results: res.results,
+                }));
+            }
+            (Some(head), Err(e)) => {
+                let _ = head.send(ArrowHead::Err(e.to_string()));
+            }
+            (None, Ok(())) => {}
+            (None, Err(e)) => res.abort(e).await,
+        }
+    });
+    match head_rx.await {
+        Ok(ArrowHead::Stream) => (
+            [(CONTENT_TYPE, STREAM_CONTENT_TYPE)],
+            Body::from_stream(ReceiverStream::new(body_rx)),
+        )
+            .into_response(),
+        Ok(ArrowHead::Json(res)) => Json(res).into_response(),
+        Ok(ArrowHead::Err(e)) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     }
 }
 
@@ -339,10 +406,10 @@ pub enum SqlRequest {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ExtendedRequest {
     /// A query string containing zero or one queries.
-    query: String,
+    pub(super) query: String,
     /// Optional parameters for the query.
     #[serde(default)]
-    params: Vec<Option<String>>,
+    pub(super) params: Vec<Option<String>>,
 }
 
 /// The response to a `SqlRequest`.
@@ -352,7 +419,7 @@ pub struct SqlResponse {
     results: Vec<SqlResult>,
 }
 
-enum StatementResult {
+pub(super) enum StatementResult {
     SqlResult(SqlResult),
     Subscribe {
         desc: RelationDesc,
@@ -360,6 +427,11 @@ enum StatementResult {
         rx: RecordFirstRowStream,
         ctx_extra: ExecuteContextExtra,
     },
+    /// Rows that a [`ResultSender`] that overrides [`ResultSender::rows_result`] encodes itself.
+    RawRows {
+        desc: RelationDesc,
+        rows: Box<dyn RowIterator + Send + Sync>,
+    },
 }
 
 impl From<SqlResult> for StatementResult {
@@ -382,6 +454,11 @@ pub enum SqlResult {
         desc: Description,
         // Any notices generated during execution of the query.
         notices: Vec<Notice>,
+        /// Any parameters that may have changed.
+        ///
+        /// Note: skip serializing this field in a response if the list of parameters is empty.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        parameters: Vec<ParameterStatus>,
     },
     /// The query executed successfully but did not return rows.
     Ok {
@@ -442,6 +519,7 @@ impl SqlResult {
             rows,
             desc: Description::from(desc),
             notices: make_notices(client),
+            parameters: consistency_token_parameter(client).into_iter().collect(),
         }
     }
 
@@ -452,7 +530,8 @@ impl SqlResult {
         }
     }
 
-    fn ok(client: &mut SessionClient, tag: String, params: Vec<ParameterStatus>) -> SqlResult {
+    fn ok(client: &mut SessionClient, tag: String, mut params: Vec<ParameterStatus>) -> SqlResult {
+        params.extend(consistency_token_parameter(client));
         SqlResult::Ok {
             ok: tag,
             parameters: params,
@@ -461,6 +540,16 @@ impl SqlResult {
     }
 }
 
+/// Returns the parameter that reports the consistency token of the latest write of the session, if
+/// it hasn't been reported yet.
+fn consistency_token_parameter(client: &mut SessionClient) -> Option<ParameterStatus> {
+    let token = client.session().take_consistency_token()?;
+    Some(ParameterStatus {
+        name: CONSISTENCY_TOKEN_PARAMETER.into(),
+        value: token.to_string(),
+    })
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SqlError {
     pub message: String,
@@ -507,9 +596,9 @@ pub enum WebSocketResponse {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Notice {
-    message: String,
-    code: String,
-    severity: String,
+    pub(super) message: String,
+    pub(super) code: String,
+    pub(super) severity: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -555,8 +644,8 @@ pub struct Column {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ParameterStatus {
-    name: String,
-    value: String,
+    pub(super) name: String,
+    pub(super) value: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -575,7 +664,7 @@ pub struct CommandStarting {
 /// accumulate into a Vec and send all at once. WebSocket clients send each
 /// message as they occur.
 #[async_trait]
-trait ResultSender: Send {
+pub(super) trait ResultSender: Send {
     const SUPPORTS_STREAMING_NOTICES: bool = false;
 
     /// Adds a result to the client. The first component of the return value is
@@ -605,6 +694,23 @@ trait ResultSender: Send {
     async fn emit_streaming_notices(&mut self, _: Vec<AdapterNotice>) -> Result<(), Error> {
         unreachable!("streaming notices marked as unsupported")
     }
+
+    /// Converts the rows returned by a query into the result to add to the client.
+    fn rows_result(
+        client: &mut SessionClient,
+        rows: Box<dyn RowIterator + Send + Sync>,
+        desc: RelationDesc,
+    ) -> StatementResult {
+        SqlResult::rows(client, rows, &desc).into()
+    }
+
+    /// Reports the consistency token of the writes that an implicit transaction committed after
+    /// the results of its statements were added.
+    ///
+    /// Does nothing if the protocol of the sender can't report parameters.
+    async fn add_consistency_token(&mut self, _: ParameterStatus) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -647,6 +753,9 @@ impl ResultSender for SqlResponse {
                     )),
                 )
             }
+            StatementResult::RawRows { .. } => {
+                unreachable!("only produced by senders that encode rows themselves")
+            }
         };
         (Ok(res), stmt_logging)
     }
@@ -658,6 +767,16 @@ impl ResultSender for SqlResponse {
     fn allow_subscribe(&self) -> bool {
         false
     }
+
+    async fn add_consistency_token(&mut self, token: ParameterStatus) -> Result<(), Error> {
+        match self.results.last_mut() {
+            Some(SqlResult::Rows { parameters, .. } | SqlResult::Ok { parameters, .. }) => {
+                parameters.push(token)
+            }
+            Some(SqlResult::Err { .. }) | None => (),
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -682,6 +801,9 @@ impl ResultSender for WebSocket {
             StatementResult::SqlResult(SqlResult::Ok { .. }) => (false, false),
             StatementResult::SqlResult(SqlResult::Rows { .. }) => (true, false),
             StatementResult::Subscribe { .. } => (true, true),
+            StatementResult::RawRows { .. } => {
+                unreachable!("only produced by senders that encode rows themselves")
+            }
         };
         if let Err(e) = send_ws_response(
             self,
@@ -701,11 +823,17 @@ impl ResultSender for WebSocket {
                 rows,
                 desc,
                 notices,
+                parameters,
             }) => {
                 let mut msgs = vec![WebSocketResponse::Rows(desc)];
                 msgs.extend(rows.into_iter().map(WebSocketResponse::Row));
                 msgs.push(WebSocketResponse::CommandComplete(tag));
                 msgs.extend(notices.into_iter().map(WebSocketResponse::Notice));
+                msgs.extend(
+                    parameters
+                        .into_iter()
+                        .map(WebSocketResponse::ParameterStatus),
+                );
                 (false, msgs, None)
             }
             StatementResult::SqlResult(SqlResult::Ok {
@@ -727,6 +855,9 @@ impl ResultSender for WebSocket {
                 msgs.extend(notices.into_iter().map(WebSocketResponse::Notice));
                 (true, msgs, None)
             }
+            StatementResult::RawRows { .. } => {
+                unreachable!("only produced by senders that encode rows themselves")
+            }
             StatementResult::Subscribe {
                 ref desc,
                 tag,
@@ -824,6 +955,7 @@ impl ResultSender for WebSocket {
                                         execution_strategy: Some(
                                             StatementExecutionStrategy::Standard,
                                         ),
+                                        max_result_memory_bytes: None,
                                     },
                                     ctx_extra,
                                 )),
@@ -868,9 +1000,262 @@ impl ResultSender for WebSocket {
     async fn emit_streaming_notices(&mut self, notices: Vec<AdapterNotice>) -> Result<(), Error> {
         forward_notices(self, notices).await
     }
+
+    async fn add_consistency_token(&mut self, token: ParameterStatus) -> Result<(), Error> {
+        send_ws_response(self, WebSocketResponse::ParameterStatus(token)).await
+    }
+}
+
+/// How [`handle_sql_arrow`] should respond to the client.
+enum ArrowHead {
+    /// Respond with the Arrow IPC stream written to the response body.
+    Stream,
+    /// Respond with a JSON response, as no statement returned rows.
+    Json(SqlResponse),
+    /// Respond with an error, as the request could not be executed.
+    Err(String),
+}
+
+/// The response to a `SqlRequest` whose client accepts Arrow IPC streams.
+///
+/// The results of statements are collected into a JSON response until a statement returns
+/// rows, at which point the rows are streamed to the client instead. Once streaming has
+/// started, errors can only be reported by aborting the stream.
+struct ArrowResponse {
+    /// Sends how to respond to the client, until the response has started.
+    head: Option<oneshot::Sender<ArrowHead>>,
+    /// Sends the chunks of the Arrow IPC stream.
+    body: mpsc::Sender<Result<Vec<u8>, String>>,
+    /// The results to respond with if no statement returns rows.
+    results: Vec<SqlResult>,
+}
+
+impl ArrowResponse {
+    /// Starts streaming rows described by `desc` to the client.
+    fn start_stream(&mut self, desc: &RelationDesc) -> Result<StreamEncoder, Error> {
+        if self.head.is_none() {
+            return Err(Error::Unsupported(
+                "more than one statement returning rows in an Arrow response".into(),
+            ));
+        }
+        let encoder = StreamEncoder::new(desc.clone()).map_err(Error::Unstructured)?;
+        let head = self.head.take().expect("checked above");
+        // If the handler has gone away we'll notice once we send the first chunk.
+        let _ = head.send(ArrowHead::Stream);
+        Ok(encoder)
+    }
+
+    /// Sends `chunk` of the Arrow IPC stream to the client.
+    async fn send(&mut self, chunk: Vec<u8>) -> Result<(), Error> {
+        self.body
+            .send(Ok(chunk))
+            .await
+            .map_err(|_| Error::Unstructured(anyhow!("client disconnected")))
+    }
+
+    /// Reports that a statement failed with `error`.
+    async fn fail(&mut self, client: &mut SessionClient, error: Error) {
+        if self.head.is_some() {
+            self.results.push(SqlResult::err(client, error));
+        } else {
+            self.abort(error).await;
+        }
+    }
+
+    /// Aborts the Arrow IPC stream because of `error`, which the client will observe as an
+    /// incomplete stream.
+    async fn abort(&mut self, error: Error) {
+        let _ = self.body.send(Err(error.to_string())).await;
+    }
 }
 
-async fn await_rows<S, F, R>(sender: &mut S, client: &mut SessionClient, f: F) -> Result<R, Error>
+#[async_trait]
+impl ResultSender for ArrowResponse {
+    // See the implementation for `WebSocket` for the meaning of the return value.
+    async fn add_result(
+        &mut self,
+        client: &mut SessionClient,
+        res: StatementResult,
+    ) -> (
+        Result<Result<(), ()>, Error>,
+        Option<(StatementEndedExecutionReason, ExecuteContextExtra)>,
+    ) {
+        match res {
+            StatementResult::SqlResult(SqlResult::Err { error, notices }) => {
+                if self.head.is_some() {
+                    self.results.push(SqlResult::Err { error, notices });
+                } else {
+                    let _ = self.body.send(Err(error.message)).await;
+                }
+                (Ok(Err(())), None)
+            }
+            StatementResult::SqlResult(res) => {
+                // Once the stream has started, there is no way to report other results.
+                if self.head.is_some() {
+                    self.results.push(res);
+                }
+                (Ok(Ok(())), None)
+            }
+            StatementResult::RawRows { desc, mut rows } => {
+                if let Err(err) = verify_datum_desc(&desc, &mut rows) {
+                    self.fail(client, err.into()).await;
+                    return (Ok(Err(())), None);
+                }
+                let chunks = self.start_stream(&desc).and_then(|mut encoder| {
+                    let schema = encoder.take();
+                    let batch = encoder.encode(&mut *rows).map_err(Error::Unstructured)?;
+                    let end = encoder.finish().map_err(Error::Unstructured)?;
+                    Ok([schema, batch, end])
+                });
+                let chunks = match chunks {
+                    Ok(chunks) => chunks,
+                    Err(e) => {
+                        self.fail(client, e).await;
+                        return (Ok(Err(())), None);
+                    }
+                };
+                for chunk in chunks {
+                    if let Err(e) = self.send(chunk).await {
+                        return (Err(e), None);
+                    }
+                }
+                (Ok(Ok(())), None)
+            }
+            StatementResult::Subscribe {
+                desc,
+                tag: _,
+                mut rx,
+                ctx_extra,
+            } => {
+                let mut encoder = match self.start_stream(&desc) {
+                    Ok(encoder) => encoder,
+                    Err(e) => {
+                        let error = e.to_string();
+                        self.fail(client, e).await;
+                        return (
+                            Ok(Err(())),
+                            Some((StatementEndedExecutionReason::Errored { error }, ctx_extra)),
+                        );
+                    }
+                };
+                let schema = encoder.take();
+                if let Err(e) = self.send(schema).await {
+                    // We consider the remote breaking the connection to be a cancellation,
+                    // matching the behavior for pgwire
+                    return (
+                        Err(e),
+                        Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                    );
+                }
+
+                let mut rows_returned = 0;
+                let (error, reason) = loop {
+                    let res = match await_rows(self, client, rx.recv()).await {
+                        Ok(res) => res,
+                        Err(e) => {
+                            return (
+                                Err(e),
+                                Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                            );
+                        }
+                    };
+                    match res {
+                        Some(PeekResponseUnary::Rows(mut rows)) => {
+                            if let Err(err) = verify_datum_desc(&desc, &mut rows) {
+                                let error = err.to_string();
+                                break (
+                                    Some(err.into()),
+                                    StatementEndedExecutionReason::Errored { error },
+                                );
+                            }
+                            rows_returned += rows.count();
+                            let batch = match encoder.encode(&mut *rows) {
+                                Ok(batch) => batch,
+                                Err(e) => {
+                                    let error = e.to_string();
+                                    break (
+                                        Some(Error::Unstructured(e)),
+                                        StatementEndedExecutionReason::Errored { error },
+                                    );
+                                }
+                            };
+                            if let Err(e) = self.send(batch).await {
+                                return (
+                                    Err(e),
+                                    Some((StatementEndedExecutionReason::Canceled, ctx_extra)),
+                                );
+                            }
+                        }
+                        Some(PeekResponseUnary::Error(error)) => {
+                            break (
+                                Some(Error::Unstructured(anyhow!(error.clone()))),
+                                StatementEndedExecutionReason::Errored { error },
+                            );
+                        }
+                        Some(PeekResponseUnary::Canceled) => {
+                            break (
+                                Some(AdapterError::Canceled.into()),
+                                StatementEndedExecutionReason::Canceled,
+                            );
+                        }
+                        None => {
+                            break (
+                                None,
+                                StatementEndedExecutionReason::Success {
+                                    rows_returned: Some(u64::cast_from(rows_returned)),
+                                    execution_strategy: Some(StatementExecutionStrategy::Standard),
+                                    max_result_memory_bytes: None,
+                                },
+                            );
+                        }
+                    }
+                };
+                let res = match error {
+                    Some(error) => {
+                        self.abort(error).await;
+                        Err(())
+                    }
+                    None => match encoder.finish() {
+                        Ok(end) => match self.send(end).await {
+                            Ok(()) => Ok(()),
+                            Err(e) => return (Err(e), Some((reason, ctx_extra))),
+                        },
+                        Err(e) => {
+                            self.abort(Error::Unstructured(e)).await;
+                            Err(())
+                        }
+                    },
+                };
+                (Ok(res), Some((reason, ctx_extra)))
+            }
+        }
+    }
+
+    fn connection_error(&mut self) -> BoxFuture<Error> {
+        Box::pin(async {
+            self.body.closed().await;
+            Error::Unstructured(anyhow!("client disconnected"))
+        })
+    }
+
+    fn allow_subscribe(&self) -> bool {
+        true
+    }
+
+    fn rows_result(
+        _client: &mut SessionClient,
+        rows: Box<dyn RowIterator + Send + Sync>,
+        desc: RelationDesc,
+    ) -> StatementResult {
+        StatementResult::RawRows { desc, rows }
+    }
+}
+
+pub(super) async fn await_rows<S, F, R>(
+    sender: &mut S,
+    client: &mut SessionClient,
+    f: F,
+) -> Result<R, Error>
 where
     S: ResultSender,
     F: Future<Output = R> + Send,
@@ -958,7 +1343,7 @@ async fn execute_stmt_group<S: ResultSender>(
 ///
 /// See the user-facing documentation about the HTTP API for a description of
 /// the semantics of this function.
-async fn execute_request<S: ResultSender>(
+pub(super) async fn execute_request<S: ResultSender>(
     client: &mut AuthedClient,
     request: SqlRequest,
     sender: &mut S,
@@ -999,6 +1384,7 @@ async fn execute_request<S: ResultSender>(
                     | ExecuteResponseKind::Subscribing
                     | ExecuteResponseKind::CopyFrom
                     | ExecuteResponseKind::DeclaredCursor
+                    | ExecuteResponseKind::ClaimedHandoff
                     | ExecuteResponseKind::ClosedCursor => true,
                     // Various statements generate `PeekPlan` (`SELECT`, `COPY`,
                     // `EXPLAIN`, `SHOW`) which has both `SendRows` and `CopyTo` as its
@@ -1064,6 +1450,8 @@ async fn execute_request<S: ResultSender>(
             if let Err(err) = ended {
                 let err = SqlResult::err(client, err);
                 let _ = send_and_retire(StatementResult::SqlResult(err), client, sender).await?;
+            } else if let Some(token) = consistency_token_parameter(client) {
+                sender.add_consistency_token(token).await?;
             }
         }
         if executed?.is_err() {
@@ -1200,6 +1588,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedViews { .. }
         | ExecuteResponse::CreatedMaterializedView { .. }
         | ExecuteResponse::CreatedType
+        | ExecuteResponse::CanceledQuery
         | ExecuteResponse::Comment
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
@@ -1287,16 +1676,16 @@ async fn execute_stmt<S: ResultSender>(
                     return Ok(SqlResult::err(client, AdapterError::Canceled).into());
                 }
             };
-            SqlResult::rows(client, rows, &desc.relation_desc.expect("RelationDesc must exist")).into()
+            S::rows_result(client, rows, desc.relation_desc.expect("RelationDesc must exist"))
         }
         ExecuteResponse::SendingRowsImmediate { rows } => {
-            SqlResult::rows(client, rows, &desc.relation_desc.expect("RelationDesc must exist")).into()
+            S::rows_result(client, rows, desc.relation_desc.expect("RelationDesc must exist"))
         }
         ExecuteResponse::Subscribing { rx, ctx_extra, instance_id } => StatementResult::Subscribe {
             tag: "SUBSCRIBE".into(),
             desc: desc.relation_desc.unwrap(),
             rx: RecordFirstRowStream::new(
-                Box::new(UnboundedReceiverStream::new(rx)),
+                Box::new(rx),
                 execute_started,
                 client,
                 Some(instance_id),
@@ -1305,9 +1694,11 @@ async fn execute_stmt<S: ResultSender>(
             ctx_extra,
         },
         res @ (ExecuteResponse::Fetch { .. }
+        | ExecuteResponse::SendingRowsStreaming { .. }
         | ExecuteResponse::CopyTo { .. }
         | ExecuteResponse::CopyFrom { .. }
         | ExecuteResponse::DeclaredCursor
+        | ExecuteResponse::ClaimedHandoff
         | ExecuteResponse::ClosedCursor) => SqlResult::err(
             client,
             Error::Unstructured(anyhow!("internal error: encountered prohibited ExecuteResponse {:?}.\n\n
@@ -1318,7 +1709,7 @@ async fn execute_stmt<S: ResultSender>(
     })
 }
 
-fn make_notices(client: &mut SessionClient) -> Vec<Notice> {
+pub(super) fn make_notices(client: &mut SessionClient) -> Vec<Notice> {
     client
         .session()
         .drain_notices()
@@ -74,6 +74,9 @@ pub struct Config {
     /// Whether the environmentd is running on a local dev machine. This is
     /// never meant to run in production or CI.
     pub all_features: bool,
+    /// Whether to run as a read-only replica, which serves serializable reads
+    /// while following the catalog of the leader.
+    pub read_only_replica: bool,
 
     // === Connection options. ===
     /// TLS encryption and authentication configuration.
@@ -135,6 +138,8 @@ pub struct Config {
     pub config_sync_timeout: Duration,
     /// The interval in seconds at which to synchronize system parameter values.
     pub config_sync_loop_interval: Option<Duration>,
+    /// The hooks to invoke after every statement execution.
+    pub statement_retirement_hooks: mz_adapter::statement_logging::StatementRetirementHooks,
 
     // === Bootstrap options. ===
     /// The cloud ID of this environment.
@@ -456,30 +461,37 @@ impl Listeners {
         // Preflight checks determine whether to boot in read-only mode or not.
         let mut read_only = false;
         let mut clusters_hydrated_trigger = None;
-        let preflight_config = PreflightInput {
-            boot_ts,
-            environment_id: config.environment_id.clone(),
-            persist_client,
-            bootstrap_default_cluster_replica_size: config
-                .bootstrap_default_cluster_replica_size
-                .clone(),
-            bootstrap_role: config.bootstrap_role.clone(),
-            deploy_generation: config.controller.deploy_generation,
-            deployment_state: deployment_state.clone(),
-            openable_adapter_storage,
-            catalog_metrics: Arc::clone(&config.catalog_config.metrics),
-            hydration_max_wait: with_0dt_deployment_max_wait,
-        };
-        if enable_0dt_deployment {
-            PreflightOutput {
-                openable_adapter_storage,
-                read_only,
-                clusters_hydrated_trigger,
-            } = deployment::preflight::preflight_0dt(preflight_config).await?;
+        if config.read_only_replica {
+            // Read-only replicas never take over from the leader, so there is
+            // nothing to check.
+            info!("booting as a read-only replica");
+            read_only = true;
         } else {
-            openable_adapter_storage =
-                deployment::preflight::preflight_legacy(preflight_config).await?;
-        };
+            let preflight_config = PreflightInput {
+                boot_ts,
+                environment_id: config.environment_id.clone(),
+                persist_client: persist_client.clone(),
+                bootstrap_default_cluster_replica_size: config
+                    .bootstrap_default_cluster_replica_size
+                    .clone(),
+                bootstrap_role: config.bootstrap_role.clone(),
+                deploy_generation: config.controller.deploy_generation,
+                deployment_state: deployment_state.clone(),
+                openable_adapter_storage,
+                catalog_metrics: Arc::clone(&config.catalog_config.metrics),
+                hydration_max_wait: with_0dt_deployment_max_wait,
+            };
+            if enable_0dt_deployment {
+                PreflightOutput {
+                    openable_adapter_storage,
+                    read_only,
+                    clusters_hydrated_trigger,
+                } = deployment::preflight::preflight_0dt(preflight_config).await?;
+            } else {
+                openable_adapter_storage =
+                    deployment::preflight::preflight_legacy(preflight_config).await?;
+            };
+        }
 
         info!(
             "startup: envd serve: preflight checks complete in {:?}",
@@ -494,6 +506,27 @@ impl Listeners {
             bootstrap_role: config.bootstrap_role,
         };
 
+        // Read-only replicas follow the catalog changes of the leader through
+        // a separate read-only handle. We open it before our own catalog and
+        // consume everything that is visible already, so that it only reports
+        // changes made after that point. At worst, a change made while we open
+        // our own catalog causes a needless restart.
+        let catalog_follower = if config.read_only_replica {
+            let mut catalog_follower = mz_catalog::durable::persist_backed_catalog_state(
+                persist_client,
+                config.environment_id.organization_id(),
+                BUILD_INFO.semver_version(),
+                Arc::clone(&config.catalog_config.metrics),
+            )
+            .await?
+            .open_read_only(&bootstrap_args)
+            .await?;
+            catalog_follower.sync_to_current_updates().await?;
+            Some(catalog_follower)
+        } else {
+            None
+        };
+
         // Load the adapter durable storage.
         let adapter_storage = if read_only {
             // TODO: behavior of migrations when booting in savepoint mode is
@@ -555,7 +588,9 @@ impl Listeners {
         );
 
         // Initialize adapter.
-        let segment_client = config.segment_api_key.map(mz_segment::Client::new);
+        let segment_client = config
+            .segment_api_key
+            .map(|api_key| mz_segment::Client::new(api_key, &config.metrics_registry));
         let webhook_concurrency_limit = WebhookConcurrencyLimiter::default();
         let (adapter_handle, adapter_client) = mz_adapter::serve(mz_adapter::Config {
             connection_context: config.controller.connection_context.clone(),
@@ -596,6 +631,8 @@ impl Listeners {
             read_only_controllers: read_only,
             enable_0dt_deployment,
             clusters_hydrated_trigger,
+            catalog_follower,
+            statement_retirement_hooks: config.statement_retirement_hooks,
         })
         .instrument(info_span!("adapter::serve"))
         .await?;
@@ -102,6 +102,7 @@ pub struct TestHarness {
     orchestrator_tracing_cli_args: TracingCliArgs,
     bootstrap_role: Option<String>,
     deploy_generation: u64,
+    read_only_replica: bool,
     system_parameter_defaults: BTreeMap<String, String>,
     internal_console_redirect_url: Option<String>,
     metrics_registry: Option<MetricsRegistry>,
@@ -131,6 +132,7 @@ impl Default for TestHarness {
             enable_tracing: false,
             bootstrap_role: Some("materialize".into()),
             deploy_generation: 0,
+            read_only_replica: false,
             // This and startup_log_filter below are both (?) needed to suppress clusterd messages.
             // If we need those in the future, we might need to change both.
             system_parameter_defaults: BTreeMap::from([(
@@ -282,6 +284,11 @@ impl TestHarness {
         self
     }
 
+    pub fn with_read_only_replica(mut self) -> Self {
+        self.read_only_replica = true;
+        self
+    }
+
     pub fn with_system_parameter_default(mut self, param: String, value: String) -> Self {
         self.system_parameter_defaults.insert(param, value);
         self
@@ -495,6 +502,7 @@ impl Listeners {
                 frontegg: config.frontegg,
                 unsafe_mode: config.unsafe_mode,
                 all_features: false,
+                read_only_replica: config.read_only_replica,
                 metrics_registry: metrics_registry.clone(),
                 now: config.now,
                 environment_id: config.environment_id,
@@ -522,6 +530,7 @@ impl Listeners {
                 launchdarkly_key_map: Default::default(),
                 config_sync_timeout: Duration::from_secs(30),
                 config_sync_loop_interval: None,
+                statement_retirement_hooks: Default::default(),
                 bootstrap_role: config.bootstrap_role,
                 http_host_name: Some(host_name),
                 internal_console_redirect_url: config.internal_console_redirect_url,
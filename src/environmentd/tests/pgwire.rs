@@ -14,20 +14,27 @@ use std::net::TcpStream;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use fallible_iterator::FallibleIterator;
+use futures::StreamExt;
 use mz_adapter::session::DEFAULT_DATABASE_NAME;
 use mz_environmentd::test_util::{self, PostgresErrorExt};
 use mz_ore::collections::CollectionExt;
 use mz_ore::retry::Retry;
 use mz_ore::{assert_err, assert_ok};
 use mz_pgrepr::{Numeric, Record};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use postgres::binary_copy::BinaryCopyOutIter;
 use postgres::error::SqlState;
 use postgres::types::Type;
 use postgres::SimpleQueryMessage;
 use postgres_array::{Array, Dimension};
+use postgres_protocol::message::backend::{
+    LogicalReplicationMessage, ReplicationMessage, TupleData,
+};
 use tokio::sync::mpsc;
+use tokio_postgres::config::ReplicationMode;
+use tokio_postgres::replication::LogicalReplicationStream;
 
 #[mz_ore::test]
 fn test_bind_params() {
@@ -418,6 +425,35 @@ fn test_copy() {
             .unwrap();
         assert_eq!(buf, ",21,2\n\t,\"my,str\",4\n");
     }
+
+    // Test Parquet COPY.
+    {
+        let mut buf = Vec::new();
+        client
+            .copy_out(
+                "COPY (SELECT generate_series(1, 1000) AS a, 'x' AS b) TO STDOUT (FORMAT PARQUET)",
+            )
+            .unwrap()
+            .read_to_end(&mut buf)
+            .unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 1000);
+        let schema = batches[0].schema();
+        let columns: Vec<_> = schema.fields().iter().map(|field| field.name()).collect();
+        assert_eq!(columns, ["a", "b"]);
+
+        // Types that can't be encoded as Parquet are rejected up front.
+        let err = client
+            .copy_out("COPY (SELECT '1 day'::interval) TO STDOUT (FORMAT PARQUET)")
+            .unwrap_err()
+            .unwrap_db_error();
+        assert_eq!(err.code(), &SqlState::FEATURE_NOT_SUPPORTED);
+    }
 }
 
 #[mz_ore::test]
@@ -517,6 +553,141 @@ fn test_record_types() {
     assert_eq!(rows.len(), 2);
 }
 
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+async fn test_logical_replication() {
+    let server = test_util::TestHarness::default().start().await;
+    server
+        .enable_feature_flags(&["enable_logical_replication"])
+        .await;
+
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute("CREATE TABLE t (a int, b text); INSERT INTO t VALUES (1, 'one'), (2, NULL)")
+        .await
+        .unwrap();
+
+    // Every table is published under its own name.
+    let rows = client
+        .query(
+            "SELECT pubname, schemaname, tablename, attnames::text
+             FROM pg_publication_tables WHERE pubname = 't'",
+            &[],
+        )
+        .await
+        .unwrap();
+    let rows: Vec<(String, String, String, String)> = rows
+        .iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+        .collect();
+    assert_eq!(
+        rows,
+        [("t".into(), "public".into(), "t".into(), "{a,b}".into())]
+    );
+    let row = client
+        .query_one(
+            "SELECT pubinsert, pubupdate, pubdelete FROM pg_publication WHERE pubname = 't'",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!((row.get(0), row.get(1), row.get(2)), (true, false, true));
+
+    let mut config = server.connect().as_pg_config().clone();
+    config.replication_mode(ReplicationMode::Logical);
+    let client = server.connect().with_config(config).await.unwrap();
+
+    let messages = client.simple_query("IDENTIFY_SYSTEM").await.unwrap();
+    let row = messages
+        .into_iter()
+        .find_map(|message| match message {
+            tokio_postgres::SimpleQueryMessage::Row(row) => Some(row),
+            _ => None,
+        })
+        .unwrap();
+    let systemid = row.get("systemid").unwrap();
+    assert!(
+        systemid.parse::<u64>().is_ok(),
+        "systemid {systemid} is not numeric"
+    );
+    assert_eq!(row.get("dbname"), Some(DEFAULT_DATABASE_NAME));
+
+    // Unknown publications are rejected before the stream starts.
+    let err = client
+        .copy_both_simple::<Bytes>(
+            r#"START_REPLICATION SLOT s LOGICAL 0/0 (proto_version '1', publication_names 'missing')"#,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.unwrap_db_error().code(), &SqlState::UNDEFINED_OBJECT);
+    assert_eq!(
+        err.unwrap_db_error().message(),
+        r#"publication "missing" does not exist"#
+    );
+
+    let stream = client
+        .copy_both_simple::<Bytes>(
+            r#"START_REPLICATION SLOT s LOGICAL 0/0 (proto_version '1', publication_names 't')"#,
+        )
+        .await
+        .unwrap();
+    let mut stream = Box::pin(LogicalReplicationStream::new(stream));
+
+    // The stream starts with a snapshot of the table in a single transaction.
+    let mut messages = Vec::new();
+    loop {
+        let message = match stream.next().await.unwrap().unwrap() {
+            ReplicationMessage::XLogData(data) => data.into_data(),
+            ReplicationMessage::PrimaryKeepAlive(_) => continue,
+            message => panic!("unexpected replication message {message:?}"),
+        };
+        let done = matches!(message, LogicalReplicationMessage::Commit(_));
+        messages.push(message);
+        if done {
+            break;
+        }
+    }
+    let [LogicalReplicationMessage::Begin(_), LogicalReplicationMessage::Relation(relation), inserts @ .., LogicalReplicationMessage::Commit(_)] =
+        &messages[..]
+    else {
+        panic!("unexpected transaction {messages:?}");
+    };
+    assert_eq!(relation.namespace().unwrap(), "public");
+    assert_eq!(relation.name().unwrap(), "t");
+    let columns: Vec<_> = relation
+        .columns()
+        .iter()
+        .map(|column| column.name().unwrap())
+        .collect();
+    assert_eq!(columns, ["a", "b"]);
+    let mut tuples: Vec<Vec<Option<String>>> = inserts
+        .iter()
+        .map(|message| {
+            let LogicalReplicationMessage::Insert(insert) = message else {
+                panic!("unexpected message {message:?}");
+            };
+            assert_eq!(insert.rel_id(), relation.rel_id());
+            insert
+                .tuple()
+                .tuple_data()
+                .iter()
+                .map(|data| match data {
+                    TupleData::Null => None,
+                    TupleData::Text(text) => Some(String::from_utf8(text.to_vec()).unwrap()),
+                    TupleData::UnchangedToast => panic!("unexpected unchanged TOAST value"),
+                })
+                .collect()
+        })
+        .collect();
+    tuples.sort();
+    assert_eq!(
+        tuples,
+        [
+            vec![Some("1".into()), Some("one".into())],
+            vec![Some("2".into()), None],
+        ]
+    );
+}
+
 fn pg_test_inner(dir: PathBuf, flags: &[&'static str]) {
     // We want a new server per file, so we can't use pgtest::walk.
     datadriven::walk(dir.to_str().unwrap(), |tf| {
@@ -21,6 +21,7 @@ use std::time::{Duration, Instant};
 use std::{iter, thread};
 
 use anyhow::bail;
+use arrow::array::{Int32Array, Int64Array, UInt64Array};
 use chrono::{DateTime, Utc};
 use futures::FutureExt;
 use http::Request;
@@ -54,7 +55,7 @@ use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka::ClientConfig;
 use rdkafka_sys::RDKafkaErrorCode;
 use reqwest::blocking::Client;
-use reqwest::header::CONTENT_TYPE;
+use reqwest::header::{ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
 use reqwest::{StatusCode, Url};
 use serde::{Deserialize, Serialize};
 use tempfile::TempDir;
@@ -607,6 +608,79 @@ ORDER BY mseh.began_at",
     assert_eq!(sl_subscribes[1].finished_status, "canceled");
 }
 
+// Test that logged statements report their correlation id, and that the id can be used to look
+// up their lifecycle events.
+#[mz_ore::test]
+fn test_statement_correlation_id() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default(
+            "statement_logging_max_sample_rate".to_string(),
+            "1.0".to_string(),
+        )
+        .with_system_parameter_default(
+            "statement_logging_default_sample_rate".to_string(),
+            "1.0".to_string(),
+        )
+        .with_system_parameter_default(
+            "enable_statement_lifecycle_logging".to_string(),
+            "true".to_string(),
+        )
+        .start_blocking();
+
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let mut client = server
+        .pg_config()
+        .notice_callback(move |notice| {
+            tx.unbounded_send(notice).unwrap();
+        })
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    client
+        .batch_execute("SET emit_statement_correlation_id_notice = true")
+        .unwrap();
+    client.query_one("SELECT 1", &[]).unwrap();
+
+    let correlation_id = match rx.try_next() {
+        Ok(Some(notice)) => notice
+            .message()
+            .strip_prefix("correlation id: ")
+            .expect("not a correlation id notice")
+            .to_string(),
+        x => panic!("failed to read notice from channel, {:?}", x),
+    };
+    let statement_id = correlation_id
+        .rsplit(':')
+        .next()
+        .expect("correlation id contains a statement id");
+    assert_ok!(Uuid::parse_str(statement_id));
+
+    // Statement logging happens async, give it a chance to catch up.
+    let mut client = server.connect_internal(postgres::NoTls).unwrap();
+    let events = Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let events: Vec<String> = client
+                .query(
+                    "SELECT event_type
+                     FROM mz_internal.mz_statement_trace($1)
+                     ORDER BY occurred_at",
+                    &[&correlation_id],
+                )
+                .unwrap()
+                .into_iter()
+                .map(|row| row.get(0))
+                .collect();
+            if events.last().map(String::as_str) == Some("execution-finished") {
+                Ok(events)
+            } else {
+                Err(format!("statement has not finished yet: {events:?}"))
+            }
+        })
+        .unwrap();
+    assert_eq!(events.first().map(String::as_str), Some("execution-began"));
+}
+
 /// Test that we are sampling approximately 50% of statements.
 /// Relies on two assumptions:
 /// (1) that the effective sampling rate for the session is 50%,
@@ -1662,6 +1736,443 @@ fn test_max_statement_batch_size() {
     }
 }
 
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_consistency_tokens() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default("enable_consistency_tokens".to_string(), "true".to_string())
+        .start_blocking();
+    let token_timestamp = |token: &str| -> u64 {
+        let (timeline, timestamp) = token.rsplit_once('@').unwrap();
+        assert_eq!(timeline, "M");
+        timestamp.parse().unwrap()
+    };
+
+    let mut super_user = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    super_user
+        .batch_execute(&format!("CREATE ROLE {}", &HTTP_DEFAULT_USER.name))
+        .unwrap();
+    super_user
+        .batch_execute(&format!(
+            "GRANT USAGE ON CLUSTER quickstart TO {0};
+             GRANT USAGE ON DATABASE materialize TO {0};
+             GRANT USAGE ON SCHEMA materialize.public TO {0};",
+            &HTTP_DEFAULT_USER.name
+        ))
+        .unwrap();
+    let mut client = server
+        .pg_config()
+        .options("--transaction_isolation=serializable")
+        .connect(postgres::NoTls)
+        .unwrap();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE t (a int); GRANT ALL ON t TO {}",
+            &HTTP_DEFAULT_USER.name
+        ))
+        .unwrap();
+
+    // Reads that pass the token of a write observe the write, even with an
+    // isolation level that doesn't require it.
+    let mut observe = |token: &str, expected: &[i32]| {
+        let rows = client
+            .query(
+                &format!("SELECT a, mz_now()::text FROM t WITH (AFTER TOKEN = '{token}')"),
+                &[],
+            )
+            .unwrap();
+        let mut values: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+        values.sort();
+        assert_eq!(values, expected);
+        let read_ts: String = rows[0].get(1);
+        assert!(read_ts.parse::<u64>().unwrap() >= token_timestamp(token));
+    };
+
+    // http
+    let http_url = Url::parse(&format!(
+        "http://{}/api/sql",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+    let res: serde_json::Value = Client::new()
+        .post(http_url.clone())
+        .json(&serde_json::json!({ "query": "INSERT INTO t VALUES (1)" }))
+        .send()
+        .unwrap()
+        .json()
+        .unwrap();
+    let parameters = &res["results"][0]["parameters"];
+    assert_eq!(parameters[0]["name"], "mz_consistency_token", "{res}");
+    let token = parameters[0]["value"].as_str().unwrap().to_string();
+    observe(&token, &[1]);
+
+    // ws
+    let (mut ws, _resp) = tungstenite::connect(server.ws_addr()).unwrap();
+    test_util::auth_with_ws(&mut ws, BTreeMap::default()).unwrap();
+    let json = serde_json::json!({ "query": "INSERT INTO t VALUES (2)" });
+    ws.send(Message::Text(json.to_string())).unwrap();
+    let mut token = None;
+    loop {
+        let msg = ws.read().unwrap();
+        let msg: serde_json::Value = serde_json::from_str(msg.to_text().unwrap()).unwrap();
+        match msg["type"].as_str().unwrap() {
+            "ParameterStatus" if msg["payload"]["name"] == "mz_consistency_token" => {
+                token = Some(msg["payload"]["value"].as_str().unwrap().to_string());
+            }
+            "Error" => panic!("unexpected error: {msg}"),
+            "ReadyForQuery" => break,
+            _ => (),
+        }
+    }
+    let token = token.expect("write returns a token");
+    observe(&token, &[1, 2]);
+
+    // Statements that don't write don't return tokens.
+    let res: serde_json::Value = Client::new()
+        .post(http_url)
+        .json(&serde_json::json!({ "query": "SET application_name = 'a'" }))
+        .send()
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_none!(res["results"][0]["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|parameter| parameter["name"] == "mz_consistency_token"));
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_http_sql_compression() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let http_url = Url::parse(&format!(
+        "http://{}/api/sql",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+    let query = |query: &str| {
+        Client::new()
+            .post(http_url.clone())
+            .header(ACCEPT_ENCODING, "zstd")
+            .json(&serde_json::json!({ "query": query }))
+            .send()
+            .unwrap()
+    };
+
+    // Large results are compressed.
+    let res = query("SELECT repeat('a', 100) FROM generate_series(1, 10000)");
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()
+            .get(CONTENT_ENCODING)
+            .map(|v| v.to_str().unwrap()),
+        Some("zstd")
+    );
+    let body = res.bytes().unwrap();
+    assert!(body.len() < 100 * 10000, "{} bytes", body.len());
+
+    // Small results are not.
+    let res = query("SELECT 1");
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_none!(res.headers().get(CONTENT_ENCODING));
+    let body: serde_json::Value = res.json().unwrap();
+    assert_eq!(body["results"][0]["rows"], serde_json::json!([["1"]]));
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_http_sql_arrow() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let http_url = Url::parse(&format!(
+        "http://{}/api/sql",
+        server.inner().http_local_addr()
+    ))
+    .unwrap();
+    let query = |query: &str| {
+        Client::new()
+            .post(http_url.clone())
+            .header(ACCEPT, "application/vnd.apache.arrow.stream")
+            .json(&serde_json::json!({ "query": query }))
+            .send()
+            .unwrap()
+    };
+    let read_batches = |res: reqwest::blocking::Response| {
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).map(|v| v.to_str().unwrap()),
+            Some("application/vnd.apache.arrow.stream")
+        );
+        let body = res.bytes().unwrap();
+        arrow::ipc::reader::StreamReader::try_new(std::io::Cursor::new(body), None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+
+    // Peeks are returned as a single record batch.
+    let batches = read_batches(query("SELECT generate_series(1, 3) AS a ORDER BY a"));
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].schema().field(0).name(), "a");
+    let a = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(a.values(), &[1, 2, 3]);
+
+    // Statements that don't return rows fall back to the JSON response.
+    let res = query("CREATE TABLE t (a int)");
+    assert_eq!(
+        res.headers().get(CONTENT_TYPE).map(|v| v.to_str().unwrap()),
+        Some("application/json")
+    );
+    query("INSERT INTO t VALUES (1), (2)");
+    let batches = read_batches(query("SELECT mz_now() FROM t LIMIT 1"));
+    let ts = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap()
+        .value(0);
+
+    // Subscribes are streamed until they complete.
+    let batches = read_batches(query(&format!("SUBSCRIBE t AS OF {ts} UP TO {}", ts + 1)));
+    let schema = batches[0].schema();
+    let columns: Vec<_> = schema.fields().iter().map(|field| field.name()).collect();
+    assert_eq!(columns, ["mz_timestamp", "mz_diff", "a"]);
+    let mut rows = Vec::new();
+    for batch in &batches {
+        let diffs = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        let values = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        rows.extend(
+            diffs
+                .values()
+                .iter()
+                .zip(values.values())
+                .map(|(d, v)| (*d, *v)),
+        );
+    }
+    rows.sort();
+    assert_eq!(rows, [(1, 1), (1, 2)]);
+
+    // Errors fall back to the JSON response.
+    let res = query("SELECT 1/0");
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: serde_json::Value = res.json().unwrap();
+    assert_eq!(body["results"][0]["error"]["message"], "division by zero");
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_http_sql_sessions() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let base_url = format!("http://{}/api/sql", server.inner().http_local_addr());
+    let session_url = Url::parse(&format!("{base_url}/session")).unwrap();
+    let sql_url = Url::parse(&base_url).unwrap();
+
+    let create_session = || {
+        let res = Client::new().post(session_url.clone()).send().unwrap();
+        assert_eq!(res.status(), StatusCode::CREATED);
+        let body: serde_json::Value = res.json().unwrap();
+        body["session"].as_str().unwrap().to_string()
+    };
+    let query = |session: Option<&str>, query: &str| {
+        let mut req = Client::new()
+            .post(sql_url.clone())
+            .json(&serde_json::json!({ "query": query }));
+        if let Some(session) = session {
+            req = req.header("x-materialize-session", session);
+        }
+        let res = req.send().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: serde_json::Value = res.json().unwrap();
+        body["results"][0].clone()
+    };
+
+    let session = create_session();
+    query(None, "CREATE TABLE t (a int)");
+
+    // Transactions span requests.
+    assert_eq!(query(Some(&session), "BEGIN")["ok"], "BEGIN");
+    query(Some(&session), "INSERT INTO t VALUES (1)");
+    assert_eq!(
+        query(Some(&session), "SELECT count(*) FROM t")["rows"],
+        serde_json::json!([["1"]])
+    );
+    assert_eq!(
+        query(None, "SELECT count(*) FROM t")["rows"],
+        serde_json::json!([["0"]])
+    );
+    assert_eq!(query(Some(&session), "COMMIT")["ok"], "COMMIT");
+    assert_eq!(
+        query(None, "SELECT count(*) FROM t")["rows"],
+        serde_json::json!([["1"]])
+    );
+
+    // So do session variables and prepared statements.
+    query(Some(&session), "SET application_name = 'sticky'");
+    assert_eq!(
+        query(Some(&session), "SHOW application_name")["rows"],
+        serde_json::json!([["sticky"]])
+    );
+    query(Some(&session), "PREPARE p AS SELECT a + 1 FROM t");
+    assert_eq!(
+        query(Some(&session), "EXECUTE p")["rows"],
+        serde_json::json!([["2"]])
+    );
+
+    // Sessions are isolated from each other.
+    let other = create_session();
+    assert_eq!(
+        query(Some(&other), "EXECUTE p")["error"]["message"],
+        "prepared statement \"p\" does not exist"
+    );
+
+    // Deleted sessions can no longer be used.
+    let delete = |session: &str| {
+        Client::new()
+            .delete(session_url.clone())
+            .header("x-materialize-session", session)
+            .send()
+            .unwrap()
+            .status()
+    };
+    assert_eq!(delete(&session), StatusCode::NO_CONTENT);
+    assert_eq!(delete(&session), StatusCode::NOT_FOUND);
+    let res = Client::new()
+        .post(sql_url.clone())
+        .header("x-materialize-session", &session)
+        .json(&serde_json::json!({ "query": "SELECT 1" }))
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(delete(&other), StatusCode::NO_CONTENT);
+
+    // Each user may only hold a limited number of sessions.
+    let sessions: Vec<_> = (0..16).map(|_| create_session()).collect();
+    let res = Client::new().post(session_url.clone()).send().unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(delete(&sessions[0]), StatusCode::NO_CONTENT);
+    create_session();
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_grpc_sql() {
+    use mz_environmentd::http::grpc::proto::sql_client::SqlClient;
+    use mz_environmentd::http::grpc::proto::{
+        execute_request, statement_result, subscribe_response, CancelRequest, ExecuteRequest,
+        SubscribeRequest, SubscribeResponse,
+    };
+    use tonic::Streaming;
+
+    let server = test_util::TestHarness::default().start().await;
+    let mut client = SqlClient::connect(format!("http://{}", server.inner.http_local_addr()))
+        .await
+        .unwrap();
+    let execute = |query: &str| ExecuteRequest {
+        options: BTreeMap::new(),
+        request: Some(execute_request::Request::Query(query.into())),
+    };
+
+    let results = client
+        .execute(execute("CREATE TABLE t (a int, b text)"))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    let Some(statement_result::Result::Ok(ok)) = &results[0].result else {
+        panic!("expected ok: {results:?}");
+    };
+    assert_eq!(ok.tag, "CREATE TABLE");
+    client
+        .execute(execute("INSERT INTO t VALUES (1, NULL)"))
+        .await
+        .unwrap();
+    let results = client
+        .execute(execute("SELECT a, b FROM t; SELECT 1 / 0"))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    let Some(statement_result::Result::Rows(rows)) = &results[0].result else {
+        panic!("expected rows: {results:?}");
+    };
+    assert_eq!(rows.tag, "SELECT 1");
+    assert_eq!(rows.columns.len(), 2);
+    let values: Vec<_> = rows.rows[0].values.iter().map(|v| v.text.clone()).collect();
+    assert_eq!(values, vec![Some("1".to_string()), None]);
+    let Some(statement_result::Result::Error(error)) = &results[1].result else {
+        panic!("expected an error: {results:?}");
+    };
+    assert_eq!(error.message, "division by zero");
+
+    // SUBSCRIBE is only available through the Subscribe RPC.
+    let status = client.execute(execute("SUBSCRIBE t")).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    let mut stream = client
+        .subscribe(SubscribeRequest {
+            options: BTreeMap::new(),
+            query: "SUBSCRIBE t".into(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    async fn next(stream: &mut Streaming<SubscribeResponse>) -> subscribe_response::Message {
+        stream.message().await.unwrap().unwrap().message.unwrap()
+    }
+    let subscribe_response::Message::Session(session) = next(&mut stream).await else {
+        panic!("expected the session");
+    };
+    let subscribe_response::Message::Columns(columns) = next(&mut stream).await else {
+        panic!("expected the columns");
+    };
+    let names: Vec<_> = columns.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["mz_timestamp", "mz_diff", "a", "b"]);
+    let updates = loop {
+        match next(&mut stream).await {
+            subscribe_response::Message::Updates(updates) if !updates.rows.is_empty() => {
+                break updates;
+            }
+            subscribe_response::Message::Updates(_) => {}
+            message => panic!("unexpected message: {message:?}"),
+        }
+    };
+    assert_eq!(updates.rows[0].values[2].text.as_deref(), Some("1"));
+
+    client
+        .cancel(CancelRequest {
+            conn_id: session.conn_id,
+            secret_key: session.secret_key,
+        })
+        .await
+        .unwrap();
+    loop {
+        match next(&mut stream).await {
+            subscribe_response::Message::Error(error) => {
+                assert_eq!(error.message, "canceling statement due to user request");
+                break;
+            }
+            subscribe_response::Message::Updates(_) => {}
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+}
+
 #[mz_ore::test]
 fn test_mz_system_user_admin() {
     let server = test_util::TestHarness::default().start_blocking();
@@ -2276,11 +2787,267 @@ fn test_internal_console_proxy() {
         .send()
         .unwrap();
 
-    assert_eq!(res.status().is_success(), true);
+    assert_eq!(res.status().is_success(), true);
+    assert_contains!(
+        res.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
+        "text/html"
+    );
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_internal_plan_graph() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+
+    let pid: i32 = client
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+    let conn_id = u32::from_le_bytes(pid.to_le_bytes());
+    let url = Url::parse(&format!(
+        "http://{}/api/plan-graph/{conn_id}",
+        server.inner().internal_http_local_addr()
+    ))
+    .unwrap();
+
+    // Nothing has been explained yet.
+    let res = Client::new().get(url.clone()).send().unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    client.batch_execute("CREATE TABLE t (a int)").unwrap();
+    client
+        .batch_execute("EXPLAIN SELECT a FROM t WHERE a > 1")
+        .unwrap();
+
+    let res = Client::new().get(url).send().unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let graph: serde_json::Value = res.json().unwrap();
+    let operators: Vec<_> = graph["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|node| node["operator"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(operators, ["Filter", "Get"]);
+    assert_eq!(graph["edges"].as_array().unwrap().len(), 1);
+    assert!(!graph["timings"].as_array().unwrap().is_empty());
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_internal_coordinator_simulation() {
+    let server = test_util::TestHarness::default()
+        .unsafe_mode()
+        .start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (1)")
+        .unwrap();
+    let url = Url::parse(&format!(
+        "http://{}/api/coordinator/simulate",
+        server.inner().internal_http_local_addr()
+    ))
+    .unwrap();
+
+    // A connection that peeks and goes away, racing the controller that responds to the peek.
+    let script = serde_json::json!([
+        [
+            { "startup": { "conn": 0 } },
+            { "execute": { "conn": 0, "sql": "SELECT * FROM t" } },
+            { "terminate": { "conn": 0 } },
+        ],
+        ["controller", "drop_read_holds"],
+    ]);
+    for controller_steps in (0..5).combinations(2) {
+        let schedule: Vec<_> = (0..5)
+            .map(|step| usize::from(controller_steps.contains(&step)))
+            .collect();
+        let res = Client::new()
+            .post(url.clone())
+            .json(&serde_json::json!({ "script": script, "schedule": schedule }))
+            .send()
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: serde_json::Value = res.json().unwrap();
+        assert_none!(res.get("err"), "schedule {schedule:?} failed: {res}");
+        let actors: Vec<_> = res["trace"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|step| step[0].as_u64().unwrap())
+            .collect();
+        assert_eq!(
+            actors,
+            schedule
+                .iter()
+                .map(|a| u64::cast_from(*a))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // The coordinator is still consistent and serving queries once the simulations are done.
+    let res: serde_json::Value = Client::new()
+        .get(
+            Url::parse(&format!(
+                "http://{}/api/coordinator/check",
+                server.inner().internal_http_local_addr()
+            ))
+            .unwrap(),
+        )
+        .send()
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_eq!(res, serde_json::Value::String("".into()));
+    let count: i64 = client
+        .query_one("SELECT count(*) FROM t", &[])
+        .unwrap()
+        .get(0);
+    assert_eq!(count, 1);
+}
+
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_internal_catalog_bundle() {
+    let user_objects = |client: &mut postgres::Client| -> Vec<String> {
+        client
+            .query(
+                "SELECT name FROM mz_objects WHERE id LIKE 'u%' ORDER BY name",
+                &[],
+            )
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect()
+    };
+
+    let source = test_util::TestHarness::default().start_blocking();
+    let mut source_client = source.connect(postgres::NoTls).unwrap();
+    source_client
+        .batch_execute(
+            "CREATE SCHEMA s;
+            CREATE TABLE s.t (a int);
+            CREATE VIEW s.v AS SELECT a FROM s.t;
+            CREATE INDEX i ON s.v (a);
+            CREATE DATABASE d;
+            CREATE MATERIALIZED VIEW d.public.mv AS SELECT a FROM materialize.s.v;
+            CREATE TEMPORARY VIEW tv AS SELECT 1;",
+        )
+        .unwrap();
+    let url = Url::parse(&format!(
+        "http://{}/api/catalog/bundle",
+        source.inner().internal_http_local_addr()
+    ))
+    .unwrap();
+    let res = Client::new().get(url).send().unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let bundle: serde_json::Value = res.json().unwrap();
+    let statements: Vec<_> = bundle["statements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stmt| stmt.as_str().unwrap().to_string())
+        .collect();
+    // Objects follow the objects they reference, and temporary objects are skipped.
+    let position = |prefix: &str| {
+        statements
+            .iter()
+            .position(|stmt| stmt.starts_with(prefix))
+            .unwrap_or_else(|| panic!("missing {prefix} in {statements:?}"))
+    };
+    assert!(
+        position("CREATE SCHEMA IF NOT EXISTS \"materialize\".\"s\"") < position("CREATE TABLE")
+    );
+    assert!(position("CREATE TABLE") < position("CREATE VIEW"));
+    assert!(position("CREATE VIEW") < position("CREATE INDEX"));
+    assert!(position("CREATE VIEW") < position("CREATE MATERIALIZED VIEW"));
+    assert!(!statements.iter().any(|stmt| stmt.contains("tv")));
+
+    let target = test_util::TestHarness::default().start_blocking();
+    let mut target_client = target.connect(postgres::NoTls).unwrap();
+    let url = Url::parse(&format!(
+        "http://{}/api/catalog/bundle",
+        target.inner().internal_http_local_addr()
+    ))
+    .unwrap();
+
+    // A dry run only validates the bundle.
+    let mut dry_run_url = url.clone();
+    dry_run_url.set_query(Some("dry_run=true"));
+    let res = Client::new()
+        .post(dry_run_url.clone())
+        .json(&bundle)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let res: serde_json::Value = res.json().unwrap();
+    assert_eq!(res["statements"], statements.len());
+    assert!(user_objects(&mut target_client).is_empty());
+
+    let res = Client::new()
+        .post(url.clone())
+        .json(&bundle)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK, "{:?}", res.text());
+    source_client.batch_execute("DROP VIEW tv").unwrap();
+    assert_eq!(
+        user_objects(&mut target_client),
+        user_objects(&mut source_client)
+    );
+
+    // The objects of a bundle must not exist yet.
+    let res = Client::new()
+        .post(url.clone())
+        .json(&bundle)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res: serde_json::Value = res.json().unwrap();
+    assert_contains!(res["err"].as_str().unwrap(), "already exists");
+
+    // A failing statement drops the objects that the bundle already created.
+    let bundle = serde_json::json!({
+        "statements": [
+            "CREATE TABLE materialize.public.x (a int)",
+            "CREATE VIEW materialize.public.y AS SELECT b FROM materialize.public.x",
+        ]
+    });
+    let res = Client::new()
+        .post(url.clone())
+        .json(&bundle)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res: serde_json::Value = res.json().unwrap();
+    assert_contains!(res["err"].as_str().unwrap(), "column \"b\" does not exist");
+    let objects = user_objects(&mut target_client);
+    assert!(!objects.contains(&"x".to_string()), "{objects:?}");
+
+    // References to objects that neither exist nor are created by the bundle are rejected
+    // before the first object is created, even by a dry run.
+    let bundle = serde_json::json!({
+        "statements": [
+            "CREATE TABLE materialize.public.x (a int)",
+            "CREATE VIEW materialize.public.y AS SELECT a FROM materialize.public.missing",
+        ]
+    });
+    let res = Client::new()
+        .post(dry_run_url)
+        .json(&bundle)
+        .send()
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let res: serde_json::Value = res.json().unwrap();
     assert_contains!(
-        res.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
-        "text/html"
+        res["err"].as_str().unwrap(),
+        "catalog item 'materialize.public.missing' is neither in the catalog nor created by an earlier statement"
     );
+    let res = Client::new().post(url).json(&bundle).send().unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let objects = user_objects(&mut target_client);
+    assert!(!objects.contains(&"x".to_string()), "{objects:?}");
 }
 
 #[mz_ore::test]
@@ -2681,6 +3448,92 @@ async fn test_leader_promotion_mixed_code_version() {
     client_this.simple_query("SELECT 1").await.unwrap();
 }
 
+#[mz_ore::test(tokio::test(flavor = "multi_thread"))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_read_only_replica() {
+    let tmpdir = TempDir::new().unwrap();
+    let harness = test_util::TestHarness::default()
+        .unsafe_mode()
+        .data_directory(tmpdir.path())
+        .with_deploy_generation(1);
+
+    let server_leader = harness.clone().start().await;
+    let client_leader = server_leader.connect().await.unwrap();
+    client_leader
+        .batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (1);")
+        .await
+        .unwrap();
+
+    // The read-only replica must not fence out the leader.
+    let server_replica = harness
+        .with_deploy_generation(2)
+        .with_read_only_replica()
+        .start()
+        .await;
+    let client_replica = server_replica.connect().await.unwrap();
+    client_leader.simple_query("SELECT 1").await.unwrap();
+
+    // Strict serializable reads can't be linearized against the leader.
+    let err = client_replica
+        .query("SELECT a FROM t", &[])
+        .await
+        .unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::FEATURE_NOT_SUPPORTED);
+    assert_contains!(
+        err.message(),
+        "strict serializable reads are not supported on read-only replicas"
+    );
+
+    client_replica
+        .batch_execute("SET transaction_isolation = 'serializable'")
+        .await
+        .unwrap();
+    Retry::default()
+        .retry_async(|_| async {
+            let rows = client_replica.query("SELECT a FROM t", &[]).await?;
+            let rows: Vec<i32> = rows.into_iter().map(|row| row.get(0)).collect();
+            if rows == vec![1] {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("unexpected rows: {rows:?}"))
+            }
+        })
+        .await
+        .unwrap();
+
+    // Writes and DDL are left to the leader.
+    let err = client_replica
+        .batch_execute("INSERT INTO t VALUES (2)")
+        .await
+        .unwrap_db_error();
+    assert_contains!(err.message(), "cannot write in read-only mode");
+    let err = client_replica
+        .batch_execute("CREATE TABLE u (a int)")
+        .await
+        .unwrap_db_error();
+    assert_contains!(err.message(), "cannot write in read-only mode");
+    client_leader.simple_query("SELECT 1").await.unwrap();
+
+    // The replica applies the leader's catalog changes without restarting, so the same
+    // connection observes them.
+    client_leader
+        .batch_execute("CREATE VIEW v AS SELECT a + 1 AS b FROM t")
+        .await
+        .unwrap();
+    Retry::default()
+        .retry_async(|_| async {
+            let rows = client_replica.query("SELECT b FROM v", &[]).await?;
+            let rows: Vec<i32> = rows.into_iter().map(|row| row.get(0)).collect();
+            if rows == vec![2] {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("unexpected rows: {rows:?}"))
+            }
+        })
+        .await
+        .unwrap();
+}
+
 // Test that websockets observe cancellation.
 #[mz_ore::test]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
@@ -3093,6 +3946,93 @@ fn test_cancel_read_then_write() {
         .unwrap();
 }
 
+// Test that writes that are starved of the write lock by a slow read-then-write query are listed
+// in `mz_starved_write_lock_waiters`, and aborted once `abort_starved_write_lock_waiters` is
+// enabled.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+fn test_abort_starved_write_lock_waiters() {
+    let server = test_util::TestHarness::default()
+        .unsafe_mode()
+        .with_system_parameter_default(
+            "write_lock_wait_warning_threshold".to_string(),
+            "1s".to_string(),
+        )
+        .start_blocking();
+    server.enable_feature_flags(&["enable_unsafe_functions"]);
+
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE foo (a TEXT, ts INT)")
+        .unwrap();
+    client
+        .batch_execute("INSERT INTO foo VALUES ('hello', 10)")
+        .unwrap();
+
+    // The read-then-write query holds the write lock while it sleeps.
+    let mut client1 = server.connect(postgres::NoTls).unwrap();
+    let handle1 = thread::spawn(move || {
+        client1
+            .batch_execute(
+                "INSERT INTO foo SELECT a, CASE WHEN mz_unsafe.mz_sleep(ts) > 0 THEN 0 END FROM foo",
+            )
+            .unwrap();
+    });
+    std::thread::sleep(Duration::from_millis(500));
+    let mut client2 = server.connect(postgres::NoTls).unwrap();
+    let conn_id: i32 = client2
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+    let handle2 = thread::spawn(move || {
+        client2
+            .batch_execute("INSERT INTO foo VALUES ('blah', 1)")
+            .unwrap_err()
+    });
+
+    let statement: String = Retry::default()
+        .max_duration(Duration::from_secs(5))
+        .retry(|_| {
+            client.query_one(
+                "SELECT statement FROM mz_internal.mz_starved_write_lock_waiters
+                 WHERE connection_id = $1::int4::uint4",
+                &[&conn_id],
+            )
+        })
+        .unwrap()
+        .get(0);
+    assert_eq!(statement, "insert");
+
+    server
+        .connect_internal(postgres::NoTls)
+        .unwrap()
+        .batch_execute("ALTER SYSTEM SET abort_starved_write_lock_waiters = true")
+        .unwrap();
+    let err = handle2.join().unwrap();
+    assert_contains!(
+        err.to_string(),
+        "canceling statement due to write lock timeout"
+    );
+    handle1.join().unwrap();
+
+    // The aborted write is removed from the table in the background.
+    Retry::default()
+        .max_duration(Duration::from_secs(5))
+        .retry(|_| {
+            let starved: i64 = client
+                .query_one(
+                    "SELECT count(*) FROM mz_internal.mz_starved_write_lock_waiters",
+                    &[],
+                )?
+                .get(0);
+            if starved != 0 {
+                bail!("{starved} starved writes remain");
+            }
+            Ok(())
+        })
+        .unwrap();
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
 async fn test_http_metrics() {
@@ -4135,6 +5075,203 @@ async fn test_startup_cluster_notice() {
     "###);
 }
 
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_cluster_failover_list() {
+    let server = test_util::TestHarness::default().start().await;
+
+    let notices = Arc::new(Mutex::new(Vec::new()));
+    let notices_ = Arc::clone(&notices);
+    let client = server
+        .connect()
+        .notice_callback(move |notice| notices_.lock().expect("not poisoned").push(notice))
+        .await
+        .expect("success");
+    client
+        .batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (1)")
+        .await
+        .unwrap();
+    client
+        .batch_execute("CREATE CLUSTER down (SIZE '1', REPLICATION FACTOR 0)")
+        .await
+        .unwrap();
+    client
+        .batch_execute("SET cluster = 'down, quickstart'")
+        .await
+        .unwrap();
+
+    // Queries fail over to quickstart, once it is live.
+    let rows = Retry::default()
+        .max_duration(Duration::from_secs(60))
+        .retry_async(|_state| async { client.query("SELECT a FROM t", &[]).await })
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    // Give the server a moment to flush all notices.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let messages: Vec<_> = notices
+        .lock()
+        .expect("not poisoned")
+        .iter()
+        .map(|notice| notice.message().to_string())
+        .collect();
+    assert!(
+        messages.contains(
+            &"cluster \"down\" has no live hydrated replicas; running on cluster \"quickstart\" instead"
+                .to_string()
+        ),
+        "{messages:?}"
+    );
+
+    // Objects are still created on the first cluster in the list.
+    client
+        .batch_execute("CREATE INDEX i ON t (a)")
+        .await
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT c.name FROM mz_indexes i JOIN mz_clusters c ON i.cluster_id = c.id WHERE i.name = 'i'",
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, String>(0), "down");
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_slow_path_peek_notice() {
+    let server = test_util::TestHarness::default().start().await;
+
+    let notices = Arc::new(Mutex::new(Vec::new()));
+    let notices_ = Arc::clone(&notices);
+    let client = server
+        .connect()
+        .notice_callback(move |notice| notices_.lock().expect("not poisoned").push(notice))
+        .await
+        .expect("success");
+    client
+        .batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (1), (2)")
+        .await
+        .unwrap();
+
+    let slow_path_notices = |query: &'static str| {
+        let client = &client;
+        let notices = Arc::clone(&notices);
+        async move {
+            notices.lock().expect("not poisoned").clear();
+            client.query(query, &[]).await.unwrap();
+            // Give the server a moment to flush all notices.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            notices
+                .lock()
+                .expect("not poisoned")
+                .iter()
+                .map(|notice| notice.message().to_string())
+                .filter(|message| message.starts_with("query requires a dataflow"))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Small unfiltered reads of unindexed collections are served directly from persist.
+    assert_eq!(
+        slow_path_notices("SELECT a FROM t LIMIT 1").await,
+        Vec::<String>::new()
+    );
+    // Reads of unindexed collections without a LIMIT are never expected to be fast.
+    assert_eq!(
+        slow_path_notices("SELECT a FROM t WHERE a = 1").await,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        slow_path_notices("SELECT a FROM t WHERE a = 1 LIMIT 1").await,
+        vec![
+            "query requires a dataflow instead of the fast path: \"materialize.public.t\" is not \
+             indexed in this cluster and the query filters its rows"
+                .to_string()
+        ]
+    );
+    assert_eq!(
+        slow_path_notices("SELECT a FROM t ORDER BY a LIMIT 1").await,
+        vec![
+            "query requires a dataflow instead of the fast path: \"materialize.public.t\" is not \
+             indexed in this cluster and the query orders its rows"
+                .to_string()
+        ]
+    );
+
+    client
+        .batch_execute("CREATE DEFAULT INDEX ON t")
+        .await
+        .unwrap();
+    assert_eq!(
+        slow_path_notices("SELECT a FROM t WHERE a = 1").await,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        slow_path_notices("SELECT a FROM (SELECT a FROM t ORDER BY a LIMIT 1)").await,
+        vec![
+            "query requires a dataflow instead of the fast path: the query's ORDER BY and LIMIT \
+             do not cover its top-k computation"
+                .to_string()
+        ]
+    );
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_workload_class_rules() {
+    let server = test_util::TestHarness::default().start().await;
+
+    let mz_client = server.connect().internal().await.unwrap();
+    mz_client
+        .batch_execute(
+            r#"ALTER SYSTEM SET workload_class_rules = '[
+                {"class": "dashboards", "application_name": "^dashboard", "cluster": "serving"},
+                {"class": "reports", "sql": "(?i)^show statement_timeout", "statement_timeout": "42s"}
+            ]'"#,
+        )
+        .await
+        .unwrap();
+
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute("CREATE CLUSTER serving (SIZE '1', REPLICATION FACTOR 0)")
+        .await
+        .unwrap();
+    async fn show(client: &tokio_postgres::Client, var: &str) -> String {
+        client
+            .query_one(&format!("SHOW {var}"), &[])
+            .await
+            .unwrap()
+            .get(0)
+    }
+    assert_eq!(show(&client, "cluster").await, "quickstart");
+    assert_eq!(show(&client, "statement_timeout").await, "42 s");
+    // The class only applies to the statement it classifies.
+    assert_eq!(
+        client
+            .query_one("SELECT current_setting('statement_timeout')", &[])
+            .await
+            .unwrap()
+            .get::<_, String>(0),
+        "10 s"
+    );
+
+    let dashboard = server
+        .connect()
+        .application_name("dashboard")
+        .await
+        .unwrap();
+    assert_eq!(show(&dashboard, "cluster").await, "serving");
+    // Settings chosen by the session take precedence.
+    dashboard
+        .batch_execute("SET cluster = quickstart")
+        .await
+        .unwrap();
+    assert_eq!(show(&dashboard, "cluster").await, "quickstart");
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
 async fn test_durable_oids() {
@@ -4491,3 +5628,50 @@ async fn test_cert_reloading() {
     assert_eq!(resp_x509, next_x509);
     check_pgwire(&conn_str, &ca.ca_cert_path(), next_x509.clone()).await;
 }
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_compute_sink_waitlist_admission() {
+    let server = test_util::TestHarness::default().start().await;
+
+    let mz_client = server.connect().internal().await.unwrap();
+    mz_client
+        .batch_execute(
+            "ALTER SYSTEM SET enable_compute_sink_waitlist = true;
+             ALTER SYSTEM SET max_compute_sinks_per_cluster = 1;",
+        )
+        .await
+        .unwrap();
+
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute("CREATE TABLE t (a int)")
+        .await
+        .unwrap();
+    let subscribe = "BEGIN; DECLARE c CURSOR FOR SUBSCRIBE t; FETCH 0 c;";
+    client.batch_execute(subscribe).await.unwrap();
+
+    // Both statements wait for the compute sink of the first one to be retired.
+    let mut waiting = Vec::new();
+    for _ in 0..2 {
+        let client = server.connect().await.unwrap();
+        waiting.push(task::spawn(|| "waiting_subscribe", async move {
+            client.batch_execute(subscribe).await.unwrap();
+            client
+        }));
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(waiting.iter().all(|handle| !handle.is_finished()));
+
+    // Raising the limit admits all waiting statements that fit into it.
+    mz_client
+        .batch_execute("ALTER SYSTEM SET max_compute_sinks_per_cluster = 3")
+        .await
+        .unwrap();
+    for handle in waiting {
+        tokio::time::timeout(Duration::from_secs(30), handle)
+            .await
+            .expect("waiting subscribe was not admitted")
+            .unwrap();
+    }
+}
@@ -40,7 +40,7 @@ use mz_pgrepr::UInt4;
 use mz_repr::Timestamp;
 use mz_sql::session::user::{INTERNAL_USER_NAME_TO_DEFAULT_CLUSTER, SUPPORT_USER, SYSTEM_USER};
 use mz_storage_types::sources::Timeline;
-use postgres::Row;
+use postgres::{Row, SimpleQueryMessage};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka::ClientConfig;
 use rdkafka_sys::RDKafkaErrorCode;
@@ -376,6 +376,200 @@ fn test_subscribe_consolidation() {
     assert_eq!(row.get::<_, String>("data"), data);
 }
 
+#[mz_ore::test]
+fn test_cursor_handoff() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client_writes = server.connect(postgres::NoTls).unwrap();
+    let mut client_old = server.connect(postgres::NoTls).unwrap();
+
+    client_writes
+        .batch_execute("CREATE TABLE t (data text); INSERT INTO t VALUES ('a')")
+        .unwrap();
+    client_old
+        .batch_execute(
+            "BEGIN;
+         DECLARE c CURSOR FOR SUBSCRIBE t;",
+        )
+        .unwrap();
+    let row = client_old.query_one("FETCH 1 c", &[]).unwrap();
+    assert_eq!(row.get::<_, String>("data"), "a");
+
+    // The subscribe keeps running after the connection that handed it off goes away.
+    let token: String = client_old
+        .query_one("HANDOFF CURSOR c", &[])
+        .unwrap()
+        .get("token");
+    drop(client_old);
+    client_writes
+        .batch_execute("INSERT INTO t VALUES ('b')")
+        .unwrap();
+
+    let mut client_new = server.connect(postgres::NoTls).unwrap();
+    assert_contains!(
+        client_new
+            .batch_execute(&format!("CLAIM HANDOFF '{token}'"))
+            .unwrap_err()
+            .to_string(),
+        "CLAIM HANDOFF can only be used in transaction blocks"
+    );
+    client_new
+        .batch_execute(&format!("BEGIN; CLAIM HANDOFF '{token}'"))
+        .unwrap();
+    let row = client_new.query_one("FETCH 1 c", &[]).unwrap();
+    assert_eq!(row.get::<_, String>("data"), "b");
+    assert_eq!(row.get::<_, i64>("mz_diff"), 1);
+
+    // Tokens can only be claimed once.
+    let mut client_other = server.connect(postgres::NoTls).unwrap();
+    assert_contains!(
+        client_other
+            .batch_execute(&format!("BEGIN; CLAIM HANDOFF '{token}'"))
+            .unwrap_err()
+            .to_string(),
+        "cursor handoff does not exist or has expired"
+    );
+    assert_contains!(
+        client_other
+            .batch_execute("ROLLBACK; BEGIN; DECLARE d CURSOR FOR SELECT 1; HANDOFF CURSOR d")
+            .unwrap_err()
+            .to_string(),
+        "handoffs of cursors that are not fetching from a SUBSCRIBE are not supported"
+    );
+}
+
+#[mz_ore::test]
+fn test_paged_cursors() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let mut writer = server.connect(postgres::NoTls).unwrap();
+    let mut system_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    system_client
+        .batch_execute("ALTER SYSTEM SET cursor_page_size = 3")
+        .unwrap();
+
+    writer
+        .batch_execute(
+            "CREATE TABLE t (i INT8);
+             CREATE DEFAULT INDEX ON t;
+             INSERT INTO t SELECT generate_series(1, 10);",
+        )
+        .unwrap();
+    client
+        .batch_execute(
+            "BEGIN;
+         DECLARE c CURSOR FOR SELECT i FROM t;",
+        )
+        .unwrap();
+    let mut fetched: Vec<i64> = client
+        .query("FETCH 4 c", &[])
+        .unwrap()
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(fetched.len(), 4);
+
+    // The cursor reports the pages it has computed so far.
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let pages_fetched: Option<i64> = writer
+                .query_opt(
+                    "SELECT pages_fetched::int8 FROM mz_internal.mz_cursors WHERE name = 'c'",
+                    &[],
+                )
+                .unwrap()
+                .map(|row| row.get(0));
+            if pages_fetched == Some(2) {
+                Ok(())
+            } else {
+                Err(format!("pages_fetched = {pages_fetched:?}"))
+            }
+        })
+        .unwrap();
+
+    // Later pages are computed at the timestamp of the first one, so they don't observe writes
+    // that happen in the meantime.
+    writer
+        .batch_execute("INSERT INTO t SELECT generate_series(11, 20)")
+        .unwrap();
+    fetched.extend(
+        client
+            .query("FETCH ALL c", &[])
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get::<_, i64>(0)),
+    );
+    fetched.sort();
+    assert_eq!(fetched, (1..=10).collect::<Vec<_>>());
+    client.batch_execute("COMMIT").unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let cursors: i64 = writer
+                .query_one(
+                    "SELECT count(*) FROM mz_internal.mz_cursors WHERE name = 'c'",
+                    &[],
+                )
+                .unwrap()
+                .get(0);
+            if cursors == 0 {
+                Ok(())
+            } else {
+                Err(format!("{cursors} cursors remain open"))
+            }
+        })
+        .unwrap();
+
+    // Pages start after the last row of the previous page, even if copies of it span pages.
+    writer
+        .batch_execute(
+            "CREATE TABLE u (i INT8, j INT8);
+             CREATE DEFAULT INDEX ON u;
+             INSERT INTO u VALUES
+                 (1, NULL), (1, NULL), (1, NULL), (1, NULL), (1, NULL),
+                 (2, 1), (NULL, 1), (NULL, 1), (NULL, NULL), (3, 2);",
+        )
+        .unwrap();
+    let query = "SELECT i, j FROM u ORDER BY i DESC NULLS LAST OFFSET 1";
+    let rows = |rows: Vec<postgres::Row>| -> Vec<(Option<i64>, Option<i64>)> {
+        rows.into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect()
+    };
+    let mut expected = rows(writer.query(query, &[]).unwrap());
+    client
+        .batch_execute(&format!("BEGIN; DECLARE d CURSOR FOR {query};"))
+        .unwrap();
+    let mut fetched = rows(client.query("FETCH ALL d", &[]).unwrap());
+    // Rows that tie in the `ORDER BY` may be returned in any order.
+    assert_eq!(fetched[0], (Some(2), Some(1)));
+    fetched.sort();
+    expected.sort();
+    assert_eq!(fetched, expected);
+    client.batch_execute("COMMIT").unwrap();
+
+    // Cursors that outlive `max_cursor_lifetime` are closed.
+    system_client
+        .batch_execute("ALTER SYSTEM SET max_cursor_lifetime = '1s'")
+        .unwrap();
+    client
+        .batch_execute(
+            "BEGIN;
+         DECLARE c CURSOR FOR SELECT i FROM t;",
+        )
+        .unwrap();
+    assert_eq!(client.query("FETCH 1 c", &[]).unwrap().len(), 1);
+    std::thread::sleep(Duration::from_secs(2));
+    assert_contains!(
+        client.query("FETCH ALL c", &[]).unwrap_err().to_string(),
+        "was closed after max_cursor_lifetime"
+    );
+}
+
 #[mz_ore::test]
 fn test_subscribe_negative_diffs() {
     let server = test_util::TestHarness::default().start_blocking();
@@ -987,6 +1181,97 @@ fn test_subscribe_fetch_timeout() {
     }
 }
 
+#[mz_ore::test]
+fn test_subscribe_cursor_buffer_limits() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let mut writer = server.connect(postgres::NoTls).unwrap();
+    let mut system_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    system_client
+        .batch_execute("ALTER SYSTEM SET subscribe_cursor_buffer_high_watermark = 10")
+        .unwrap();
+    system_client
+        .batch_execute("ALTER SYSTEM SET subscribe_cursor_buffer_low_watermark = 5")
+        .unwrap();
+
+    client.batch_execute("CREATE TABLE t (i INT8)").unwrap();
+    client.batch_execute("INSERT INTO t VALUES (0)").unwrap();
+    client
+        .batch_execute(
+            "BEGIN;
+         DECLARE c CURSOR FOR SUBSCRIBE t;",
+        )
+        .unwrap();
+    let mut fetched = loop {
+        let rows = client.query("FETCH c WITH (TIMEOUT = '1s')", &[]).unwrap();
+        if !rows.is_empty() {
+            break rows.len();
+        }
+    };
+
+    // Each insert is sent to the cursor in its own batch, so the buffer grows past the high
+    // watermark while nobody fetches.
+    const INSERTS: usize = 20;
+    for i in 1..=INSERTS {
+        writer
+            .batch_execute(&format!("INSERT INTO t VALUES ({i})"))
+            .unwrap();
+    }
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let row = writer
+                .query_opt(
+                    "SELECT buffered_rows::int8, paused FROM mz_internal.mz_subscription_buffers
+                     WHERE cursor_name = 'c'",
+                    &[],
+                )
+                .unwrap()
+                .ok_or_else(|| "no buffer reported".to_string())?;
+            let buffered_rows: i64 = row.get(0);
+            let paused: bool = row.get(1);
+            if paused && buffered_rows >= 10 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "buffered_rows = {buffered_rows}, paused = {paused}"
+                ))
+            }
+        })
+        .unwrap();
+
+    // Draining the buffer resumes the subscribe, which then delivers the remaining inserts.
+    while fetched < INSERTS + 1 {
+        let rows = client
+            .query("FETCH ALL c WITH (TIMEOUT = '1s')", &[])
+            .unwrap();
+        fetched += rows.len();
+    }
+    assert_eq!(fetched, INSERTS + 1);
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let paused: bool = writer
+                .query_one(
+                    "SELECT paused FROM mz_internal.mz_subscription_buffers
+                     WHERE cursor_name = 'c'",
+                    &[],
+                )
+                .unwrap()
+                .get(0);
+            if paused {
+                Err("still paused")
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+}
+
 #[mz_ore::test]
 fn test_subscribe_fetch_wait() {
     let server = test_util::TestHarness::default().start_blocking();
@@ -1255,6 +1540,105 @@ fn test_explain_timestamp_json() {
     let _explain: TimestampExplanation<Timestamp> = serde_json::from_str(&explain).unwrap();
 }
 
+// Test that `EXPLAIN PLAN INSIGHTS WITH (timing)` reports the time spent in each optimizer pass.
+#[mz_ore::test]
+fn test_explain_plan_insights_timings() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client.batch_execute("CREATE TABLE t1 (i1 int)").unwrap();
+
+    let query = "SELECT i1 + 1 FROM t1 WHERE i1 > 0";
+    let row = client
+        .query_one(&format!("EXPLAIN PLAN INSIGHTS AS JSON FOR {query}"), &[])
+        .unwrap();
+    let explain: String = row.get(0);
+    let explain: serde_json::Value = serde_json::from_str(&explain).unwrap();
+    assert!(explain.get("timings").is_none());
+
+    let row = client
+        .query_one(
+            &format!("EXPLAIN PLAN INSIGHTS WITH (timing) AS JSON FOR {query}"),
+            &[],
+        )
+        .unwrap();
+    let explain: String = row.get(0);
+    let explain: serde_json::Value = serde_json::from_str(&explain).unwrap();
+    let timings = explain["timings"]
+        .as_object()
+        .expect("timings are reported");
+    assert!(timings.contains_key("optimize/raw"));
+    assert!(timings.contains_key("optimize/global"));
+    // The individual transforms of the optimizer pipeline are reported, too.
+    assert!(timings
+        .keys()
+        .any(|path| path.starts_with("optimize/local/")));
+    for timing in timings.values() {
+        assert!(timing["invocations"].as_u64().unwrap() >= 1);
+        assert!(timing["span_duration_ns"].is_u64());
+        assert!(timing["full_duration_ns"].is_u64());
+    }
+    // The plans are the same as without timings.
+    assert!(explain["plans"]["optimized"]["global"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("Explained Query"));
+}
+
+// Verify that `EXPLAIN ANALYZE` attributes the statistics of the operators of a dataflow to the
+// LIR nodes that they implement.
+#[mz_ore::test]
+fn test_explain_analyze() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let mut system_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    system_client
+        .batch_execute("ALTER SYSTEM SET enable_explain_analyze = true")
+        .unwrap();
+
+    client
+        .batch_execute(
+            "CREATE TABLE t (a int);
+             INSERT INTO t VALUES (1), (2), (2);
+             CREATE CLUSTER analyzed SIZE '1';
+             CREATE MATERIALIZED VIEW mv IN CLUSTER analyzed AS
+                 SELECT a, count(*) FROM t GROUP BY a;",
+        )
+        .unwrap();
+    // Wait for the materialized view to hydrate.
+    assert_eq!(client.query("SELECT * FROM mv", &[]).unwrap().len(), 2);
+
+    // The introspection data of the replica arrives asynchronously.
+    Retry::default()
+        .max_duration(Duration::from_secs(60))
+        .retry(|_| {
+            let rows: Vec<_> = client
+                .simple_query("EXPLAIN ANALYZE MATERIALIZED VIEW mv ON REPLICA r1")
+                .unwrap()
+                .into_iter()
+                .filter_map(|msg| match msg {
+                    SimpleQueryMessage::Row(row) => Some(row),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(rows[0].get("Operator"), Some("materialize.public.mv:"));
+            let reduce = rows
+                .iter()
+                .find(|row| row.get("Operator").unwrap().contains("Reduce"))
+                .expect("plan contains a reduction");
+            match (reduce.get("Elapsed"), reduce.get("Records")) {
+                (Some(_), Some(_)) => Ok(()),
+                (elapsed, records) => Err(format!(
+                    "missing statistics: elapsed {elapsed:?}, records {records:?}"
+                )),
+            }
+        })
+        .unwrap();
+}
+
 // Verify that `EXPLAIN TIMESTAMP ...` within acts like a peek within a transaction.
 // That is, ensure the following:
 // 1. Consistently returns its transaction timestamp as the "query timestamp"
@@ -2147,6 +2531,59 @@ fn test_load_generator() {
         .unwrap();
 }
 
+#[mz_ore::test]
+fn test_as_of_at_least_waits_until_readable() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE TABLE t (a int); INSERT INTO t VALUES (1)")
+        .unwrap();
+
+    let now: String = client
+        .query_one("SELECT mz_now()::text FROM t", &[])
+        .unwrap()
+        .get(0);
+    let now: u64 = now.parse().unwrap();
+
+    // `t` isn't readable at `next` yet, so the query waits until it is.
+    let next = now + 2_000;
+    let row = client
+        .query_one(
+            &format!("SELECT mz_now()::text, a FROM t AS OF AT LEAST {next}"),
+            &[],
+        )
+        .unwrap();
+    let ts: String = row.get(0);
+    let ts: u64 = ts.parse().unwrap();
+    assert!(
+        ts >= next,
+        "expected a timestamp of at least {next}, got {ts}"
+    );
+    assert_eq!(row.get::<_, i32>(1), 1);
+
+    // Waiting queries can be canceled.
+    let cancel_token = client.cancel_token();
+    let canceler = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(500));
+        cancel_token.cancel_query(postgres::NoTls).unwrap();
+    });
+    let far_future = now + 3_600_000;
+    let err = client
+        .query_one(&format!("SELECT a FROM t AS OF AT LEAST {far_future}"), &[])
+        .unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::QUERY_CANCELED);
+    canceler.join().unwrap();
+
+    // The connection is still usable afterwards.
+    assert_eq!(
+        client
+            .query_one("SELECT a FROM t", &[])
+            .unwrap()
+            .get::<_, i32>(0),
+        1
+    );
+}
+
 #[mz_ore::test]
 fn test_support_user_permissions() {
     let server = test_util::TestHarness::default().start_blocking();
@@ -3082,6 +3519,66 @@ fn test_auto_run_on_introspection_per_replica_relations() {
     assert_notice(Some(INTROSPECTION_NOTICE));
 }
 
+#[mz_ore::test]
+fn test_auto_route_replica_introspection_queries() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let mut client = server
+        .pg_config()
+        .notice_callback(move |notice| {
+            tx.unbounded_send(notice).unwrap();
+        })
+        .connect(postgres::NoTls)
+        .unwrap();
+    let mut take_notices = || {
+        let mut messages = Vec::new();
+        while let Ok(Some(notice)) = rx.try_next() {
+            messages.push(notice.message().to_string());
+        }
+        messages
+    };
+
+    client
+        .batch_execute("CREATE CLUSTER multi SIZE '1', REPLICATION FACTOR 2; SET cluster = multi")
+        .unwrap();
+
+    // Without a target replica, per-replica introspection can't be read on a replicated cluster.
+    let err = client
+        .query("SELECT * FROM mz_introspection.mz_active_peeks", &[])
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap_db_error().message(),
+        "log source reads must target a replica"
+    );
+    take_notices();
+
+    // Once enabled, such queries are routed to mz_catalog_server instead.
+    client
+        .batch_execute("SET auto_route_replica_introspection_queries = true")
+        .unwrap();
+    client
+        .query("SELECT * FROM mz_introspection.mz_active_peeks", &[])
+        .unwrap();
+    let notices = take_notices();
+    assert!(
+        notices
+            .iter()
+            .any(|n| n == "query was automatically run on the \"mz_catalog_server\" cluster"),
+        "{notices:?}"
+    );
+
+    // Queries that depend on user objects still run on the active cluster.
+    client.batch_execute("CREATE TABLE t (a int)").unwrap();
+    let err = client
+        .query("SELECT * FROM t, mz_introspection.mz_active_peeks", &[])
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap_db_error().message(),
+        "log source reads must target a replica"
+    );
+}
+
 #[mz_ore::test]
 fn test_pg_cancel_backend() {
     mz_ore::test::init_logging();
@@ -3198,6 +3695,225 @@ fn test_pg_cancel_backend() {
     );
 }
 
+#[mz_ore::test]
+fn test_cancel_query() {
+    mz_ore::test::init_logging();
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    mz_client
+        .batch_execute("ALTER SYSTEM SET enable_rbac_checks TO true")
+        .unwrap();
+
+    let mut client1 = server.connect(postgres::NoTls).unwrap();
+    let mut client2 = server.connect(postgres::NoTls).unwrap();
+
+    client1.batch_execute("CREATE TABLE t (i INT)").unwrap();
+    let conn_id: i32 = client1
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+
+    // Start a thread to perform the cancel while the SUBSCRIBE is running in this thread.
+    let handle = thread::spawn(move || {
+        // Wait for the subscription to start.
+        Retry::default()
+            .retry(|_| {
+                client2.query_one(
+                    "SELECT 1 FROM mz_internal.mz_subscriptions b JOIN mz_internal.mz_sessions s ON s.id = b.session_id",
+                    &[],
+                )
+            })
+            .unwrap();
+
+        // Only superusers may use `CANCEL QUERY`, even to cancel their own queries.
+        assert_contains!(
+            client2
+                .batch_execute(&format!("CANCEL QUERY {conn_id}"))
+                .unwrap_err()
+                .to_string(),
+            "permission denied to cancel queries"
+        );
+
+        mz_client
+            .batch_execute(&format!("CANCEL QUERY {conn_id}"))
+            .unwrap();
+        mz_client
+    });
+
+    let err = client1.query("SUBSCRIBE t", &[]).unwrap_err();
+    assert_contains!(err.to_string(), "canceling statement due to user request");
+
+    let mut mz_client = handle.join().unwrap();
+
+    // 99999 is an arbitrary connection ID that will not exist.
+    assert_contains!(
+        mz_client
+            .batch_execute("CANCEL QUERY 99999")
+            .unwrap_err()
+            .to_string(),
+        "connection 99999 does not exist"
+    );
+
+    // Send two pgwire cancellation requests for `client1` with a made up
+    // secret key. The connection is closed right away, whether the key is
+    // valid or not. The rejected requests are recorded as one row.
+    mz_client
+        .batch_execute("ALTER SYSTEM SET rejected_cancellation_aggregation_interval = '5s'")
+        .unwrap();
+    for _ in 0..2 {
+        use std::io::Write;
+        use std::net::TcpStream;
+
+        let mut stream = TcpStream::connect(server.inner.sql_local_addr()).unwrap();
+        let mut buf = vec![];
+        buf.extend(16_i32.to_be_bytes()); // frame length
+        buf.extend(80877102_i32.to_be_bytes()); // cancel request code
+        buf.extend(conn_id.to_be_bytes());
+        buf.extend(0_i32.to_be_bytes()); // secret key
+        stream.write_all(&buf).unwrap();
+        buf.clear();
+        stream.read_to_end(&mut buf).unwrap();
+        assert!(buf.is_empty());
+    }
+
+    // All cancellations that made it past planning are recorded.
+    let rows = Retry::default()
+        .retry(|_| {
+            let rows: Vec<(i64, Option<String>, Option<String>, String, String, i64)> = mz_client
+                .query(
+                    "SELECT connection_id::int8, origin, requested_by, method, outcome, attempts::int8
+                    FROM mz_internal.mz_cancellation_history
+                    ORDER BY occurred_at",
+                    &[],
+                )
+                .unwrap()
+                .into_iter()
+                .map(|row| {
+                    (
+                        row.get(0),
+                        row.get(1),
+                        row.get(2),
+                        row.get(3),
+                        row.get(4),
+                        row.get(5),
+                    )
+                })
+                .collect();
+            if rows.len() < 3 {
+                return Err(format!("expected 3 cancellations, found {rows:?}"));
+            }
+            Ok(rows)
+        })
+        .unwrap();
+    let conn_id = i64::from(conn_id);
+    let system_user = Some(SYSTEM_USER.name.clone());
+    assert_eq!(
+        rows,
+        vec![
+            (
+                conn_id,
+                None,
+                system_user.clone(),
+                "cancel_query".into(),
+                "canceled".into(),
+                1
+            ),
+            (
+                99999,
+                None,
+                system_user,
+                "cancel_query".into(),
+                "unknown_connection".into(),
+                1
+            ),
+            (
+                conn_id,
+                Some("127.0.0.1".into()),
+                None,
+                "protocol".into(),
+                "invalid_secret_key".into(),
+                2
+            ),
+        ]
+    );
+}
+
+#[mz_ore::test]
+fn test_alter_system_terminate_sessions() {
+    mz_ore::test::init_logging();
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .application_name("terminator")
+        .connect(postgres::NoTls)
+        .unwrap();
+    mz_client
+        .batch_execute("ALTER SYSTEM SET enable_rbac_checks TO true")
+        .unwrap();
+
+    let mut victim = server
+        .pg_config()
+        .application_name("dbt-core")
+        .connect(postgres::NoTls)
+        .unwrap();
+    let mut bystander = server
+        .pg_config()
+        .application_name("psql")
+        .connect(postgres::NoTls)
+        .unwrap();
+    let victim_id: i32 = victim
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+
+    // Only superusers may terminate sessions.
+    assert_contains!(
+        bystander
+            .batch_execute("ALTER SYSTEM TERMINATE SESSIONS WHERE role = 'materialize'")
+            .unwrap_err()
+            .to_string(),
+        "permission denied to terminate sessions"
+    );
+
+    // Only the sessions that match all filters are terminated.
+    let rows = mz_client
+        .query(
+            "ALTER SYSTEM TERMINATE SESSIONS \
+            WHERE role = 'materialize' AND application_name LIKE 'dbt%'",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, u32>(0), u32::try_from(victim_id).unwrap());
+    assert_eq!(rows[0].get::<_, String>(1), "materialize");
+    assert_eq!(rows[0].get::<_, String>(2), "dbt-core");
+    assert!(!rows[0].get::<_, bool>(5));
+
+    // The terminated session is closed the next time it executes a statement.
+    assert_contains!(
+        victim.batch_execute("SELECT 1").unwrap_err().to_string(),
+        "terminating connection due to administrator command"
+    );
+    bystander.batch_execute("SELECT 1").unwrap();
+
+    // The terminating session doesn't terminate itself.
+    let rows = mz_client
+        .query(
+            "ALTER SYSTEM TERMINATE SESSIONS WHERE application_name = 'terminator'",
+            &[],
+        )
+        .unwrap();
+    assert!(rows.is_empty());
+    mz_client.batch_execute("SELECT 1").unwrap();
+}
+
 // Test params in interesting places.
 #[mz_ore::test]
 fn test_params() {
@@ -21,7 +21,7 @@ use mz_repr::explain::{
     UnsupportedFormat, UsedIndexes,
 };
 use mz_repr::optimize::OptimizerFeatures;
-use mz_repr::GlobalId;
+use mz_repr::{GlobalId, Timestamp};
 
 use crate::interpret::{Interpreter, MfpEval, Trace};
 use crate::visit::Visit;
@@ -44,6 +44,8 @@ pub struct ExplainContext<'a> {
     pub features: &'a OptimizerFeatures,
     pub humanizer: &'a dyn ExprHumanizer,
     pub cardinality_stats: BTreeMap<GlobalId, usize>,
+    /// The row count hints that the optimizer used in place of statistics.
+    pub row_count_hints: BTreeMap<GlobalId, usize>,
     pub used_indexes: UsedIndexes,
     pub finishing: Option<RowSetFinishing>,
     pub duration: Duration,
@@ -53,6 +55,44 @@ pub struct ExplainContext<'a> {
     // this decision if we want to every make this print in the json output in a machine readable
     // way.
     pub optimizer_notices: Vec<String>,
+    // The subscribe whose dataflow is explained, if any.
+    pub subscribe: Option<ExplainSubscribe>,
+}
+
+/// The parameters of the sink of an explained `SUBSCRIBE`, which are only known
+/// once its `as_of` has been determined.
+#[derive(Debug, Clone)]
+pub struct ExplainSubscribe {
+    /// The timestamp as of which the subscribe starts.
+    pub as_of: Timestamp,
+    /// The since frontier of the inputs, which bounds `as_of` from below.
+    pub since: Vec<Timestamp>,
+    /// The upper frontier of the inputs.
+    pub upper: Vec<Timestamp>,
+    /// The timestamp before which the subscribe ends, if any.
+    pub up_to: Option<Timestamp>,
+    /// Whether the subscribe emits a snapshot as of `as_of`.
+    pub with_snapshot: bool,
+    /// Whether the subscribe emits progress updates.
+    pub emit_progress: bool,
+}
+
+impl<'a> ExplainContext<'a> {
+    /// Returns the number of rows of the source `id` estimated from persist
+    /// statistics, if requested and known.
+    pub fn row_estimate(&self, id: GlobalId) -> Option<usize> {
+        if self.config.row_estimates {
+            self.cardinality_stats.get(&id).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the row count hint that the optimizer used for the source `id`,
+    /// if any.
+    pub fn row_count_hint(&self, id: GlobalId) -> Option<usize> {
+        self.row_count_hints.get(&id).copied()
+    }
 }
 
 /// A structure produced by the `explain_$format` methods in
@@ -96,6 +136,11 @@ pub struct ExplainSource<'a> {
     pub id: GlobalId,
     pub op: Option<&'a MapFilterProject>,
     pub pushdown_info: Option<PushdownInfo<'a>>,
+    /// The number of rows in the source, estimated from persist statistics.
+    pub estimated_rows: Option<usize>,
+    /// The number of rows that the optimizer assumed for the source, from a
+    /// `ROW COUNT HINT`.
+    pub row_count_hint: Option<usize>,
 }
 
 impl<'a> ExplainSource<'a> {
@@ -103,6 +148,8 @@ impl<'a> ExplainSource<'a> {
         id: GlobalId,
         op: Option<&'a MapFilterProject>,
         filter_pushdown: bool,
+        estimated_rows: Option<usize>,
+        row_count_hint: Option<usize>,
     ) -> ExplainSource<'a> {
         let pushdown_info = if filter_pushdown {
             op.map(|op| {
@@ -123,6 +170,8 @@ impl<'a> ExplainSource<'a> {
             id,
             op,
             pushdown_info,
+            estimated_rows,
+            row_count_hint,
         }
     }
 
@@ -133,6 +182,12 @@ impl<'a> ExplainSource<'a> {
             None => false,
         }
     }
+
+    /// Whether the source has anything worth rendering.
+    #[inline]
+    pub fn is_explained(&self) -> bool {
+        !self.is_identity() || self.estimated_rows.is_some() || self.row_count_hint.is_some()
+    }
 }
 
 impl<'a, 'h, C, M> DisplayText<C> for HumanizedExpr<'a, ExplainSource<'a>, M>
@@ -153,6 +208,12 @@ where
             if let Some(pushdown_info) = &self.expr.pushdown_info {
                 self.child(pushdown_info).fmt_text(f, ctx)?;
             }
+            if let Some(estimated_rows) = self.expr.estimated_rows {
+                writeln!(f, "{}estimated_rows={}", ctx.as_mut(), estimated_rows)?;
+            }
+            if let Some(row_count_hint) = self.expr.row_count_hint {
+                writeln!(f, "{}row_count_hint={}", ctx.as_mut(), row_count_hint)?;
+            }
             Ok(())
         })
     }
@@ -170,6 +231,25 @@ pub struct ExplainMultiPlan<'a, T> {
     pub sources: Vec<ExplainSource<'a>>,
     // elements of the vector are in topological order
     pub plans: Vec<(String, AnnotatedPlan<'a, T>)>,
+    // The objects that the plans build or read, whose comments are rendered
+    // if the `comments` flag is set.
+    pub related_ids: Vec<GlobalId>,
+}
+
+impl<'a, T> ExplainMultiPlan<'a, T> {
+    /// Returns the comments on the `related_ids`, along with the ID of the
+    /// object and the name of the column they are on.
+    pub fn comments(&self) -> Vec<(GlobalId, Option<String>, String)> {
+        self.related_ids
+            .iter()
+            .flat_map(|id| {
+                let comments = self.context.humanizer.comments_for_id(*id);
+                comments
+                    .into_iter()
+                    .map(|(column, comment)| (*id, column, comment))
+            })
+            .collect()
+    }
 }
 
 impl<'a> Explain<'a> for MirRelationExpr {
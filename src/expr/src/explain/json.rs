@@ -9,16 +9,22 @@
 
 //! `EXPLAIN AS JSON` support for structures defined in this crate.
 
-use mz_repr::explain::json::DisplayJson;
+use mz_repr::explain::json::{DisplayJson, EXPLAIN_VERSION};
 
-use crate::explain::{ExplainMultiPlan, ExplainSinglePlan, ExplainSource, PushdownInfo};
+use crate::explain::{
+    ExplainMultiPlan, ExplainSinglePlan, ExplainSource, ExplainSubscribe, PushdownInfo,
+};
 
 impl<'a, T: 'a> DisplayJson for ExplainSinglePlan<'a, T>
 where
     T: serde::Serialize,
 {
     fn to_serde_value(&self) -> serde_json::Result<serde_json::Value> {
-        serde_json::to_value(self.plan.plan)
+        let plan = serde_json::to_value(self.plan.plan)?;
+
+        let result = serde_json::json!({ "explain_version": EXPLAIN_VERSION, "plan": plan });
+
+        Ok(result)
     }
 }
 
@@ -47,6 +53,8 @@ where
                      id,
                      op,
                      pushdown_info,
+                     estimated_rows,
+                     row_count_hint,
                  }| {
                     let mut json = serde_json::json!({
                         "id": id,
@@ -58,13 +66,198 @@ where
                         object.insert("pushdown".to_owned(), serde_json::json!(pushdown));
                     }
 
+                    if let Some(estimated_rows) = estimated_rows {
+                        let object = json.as_object_mut().unwrap();
+                        object.insert(
+                            "estimated_rows".to_owned(),
+                            serde_json::json!(estimated_rows),
+                        );
+                    }
+
+                    if let Some(row_count_hint) = row_count_hint {
+                        let object = json.as_object_mut().unwrap();
+                        object.insert(
+                            "row_count_hint".to_owned(),
+                            serde_json::json!(row_count_hint),
+                        );
+                    }
+
                     json
                 },
             )
             .collect::<Vec<_>>();
 
-        let result = serde_json::json!({ "plans": plans, "sources": sources });
+        let mut result = serde_json::json!({
+            "explain_version": EXPLAIN_VERSION,
+            "plans": plans,
+            "sources": sources,
+        });
+
+        if self.context.config.comments {
+            let comments = self
+                .comments()
+                .into_iter()
+                .map(|(id, column, comment)| {
+                    serde_json::json!({
+                        "id": id,
+                        "column": column,
+                        "comment": comment,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let object = result.as_object_mut().unwrap();
+            object.insert("comments".to_owned(), serde_json::json!(comments));
+        }
+
+        if let Some(subscribe) = &self.context.subscribe {
+            let ExplainSubscribe {
+                as_of,
+                since,
+                upper,
+                up_to,
+                with_snapshot,
+                emit_progress,
+            } = subscribe;
+            let object = result.as_object_mut().unwrap();
+            object.insert(
+                "subscribe".to_owned(),
+                serde_json::json!({
+                    "as_of": as_of,
+                    "since": since,
+                    "upper": upper,
+                    "up_to": up_to,
+                    "with_snapshot": with_snapshot,
+                    "emit_progress": emit_progress,
+                }),
+            );
+        }
 
         Ok(result)
     }
 }
+
+/// Tests that plans rendered as JSON conform to the schema documented in
+/// [`mz_repr::explain::json`].
+///
+/// The encoding of the plans themselves is pinned by the `*_as_json.slt`
+/// tests. Changes that require rewriting their results in a way that is not
+/// backwards-compatible must bump [`EXPLAIN_VERSION`].
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use mz_repr::explain::{AnnotatedPlan, DummyHumanizer, ExplainConfig};
+    use mz_repr::optimize::OptimizerFeatures;
+    use mz_repr::{GlobalId, RelationType};
+    use serde_json::Value;
+
+    use super::*;
+    use crate::explain::ExplainContext;
+    use crate::MirRelationExpr;
+
+    fn context<'a>(
+        config: &'a ExplainConfig,
+        features: &'a OptimizerFeatures,
+    ) -> ExplainContext<'a> {
+        ExplainContext {
+            config,
+            features,
+            humanizer: &DummyHumanizer,
+            cardinality_stats: Default::default(),
+            row_count_hints: Default::default(),
+            used_indexes: Default::default(),
+            finishing: None,
+            duration: Default::default(),
+            target_cluster: None,
+            optimizer_notices: Vec::new(),
+            subscribe: None,
+        }
+    }
+
+    fn keys(value: &Value) -> BTreeSet<&str> {
+        value
+            .as_object()
+            .expect("object")
+            .keys()
+            .map(|key| key.as_str())
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_single_plan_schema() {
+        let config = ExplainConfig::default();
+        let features = OptimizerFeatures::default();
+        let context = context(&config, &features);
+        let expr = MirRelationExpr::constant(vec![vec![]], RelationType::empty());
+
+        let explain = ExplainSinglePlan {
+            context: &context,
+            plan: AnnotatedPlan {
+                plan: &expr,
+                annotations: BTreeMap::new(),
+            },
+        };
+        let value = explain.to_serde_value().unwrap();
+
+        assert_eq!(keys(&value), BTreeSet::from(["explain_version", "plan"]));
+        assert_eq!(value["explain_version"], EXPLAIN_VERSION);
+        assert_eq!(value["plan"], serde_json::to_value(&expr).unwrap());
+    }
+
+    #[mz_ore::test]
+    fn test_multi_plan_schema() {
+        let config = ExplainConfig::default();
+        let features = OptimizerFeatures::default();
+        let context = context(&config, &features);
+        let expr = MirRelationExpr::constant(vec![vec![]], RelationType::empty());
+
+        let explain = ExplainMultiPlan {
+            context: &context,
+            sources: vec![
+                ExplainSource::new(GlobalId::User(1), None, false, None, None),
+                ExplainSource::new(GlobalId::User(2), None, false, Some(3), None),
+                ExplainSource::new(GlobalId::User(3), None, false, None, Some(1000)),
+            ],
+            plans: vec![(
+                "Explained Query".to_string(),
+                AnnotatedPlan {
+                    plan: &expr,
+                    annotations: BTreeMap::new(),
+                },
+            )],
+            related_ids: vec![],
+        };
+        let value = explain.to_serde_value().unwrap();
+
+        assert_eq!(
+            keys(&value),
+            BTreeSet::from(["explain_version", "plans", "sources"])
+        );
+        assert_eq!(value["explain_version"], EXPLAIN_VERSION);
+
+        let plans = value["plans"].as_array().unwrap();
+        assert_eq!(plans.len(), 1);
+        assert_eq!(keys(&plans[0]), BTreeSet::from(["id", "plan"]));
+        assert_eq!(plans[0]["id"], "Explained Query");
+        assert_eq!(plans[0]["plan"], serde_json::to_value(&expr).unwrap());
+
+        let sources = value["sources"].as_array().unwrap();
+        assert_eq!(sources.len(), 3);
+        assert_eq!(keys(&sources[0]), BTreeSet::from(["id", "op"]));
+        assert_eq!(
+            sources[0]["id"],
+            serde_json::to_value(GlobalId::User(1)).unwrap()
+        );
+        assert_eq!(sources[0]["op"], Value::Null);
+        assert_eq!(
+            keys(&sources[1]),
+            BTreeSet::from(["id", "op", "estimated_rows"])
+        );
+        assert_eq!(sources[1]["estimated_rows"], 3);
+        assert_eq!(
+            keys(&sources[2]),
+            BTreeSet::from(["id", "op", "row_count_hint"])
+        );
+        assert_eq!(sources[2]["row_count_hint"], 1000);
+    }
+}
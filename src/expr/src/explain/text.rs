@@ -21,10 +21,11 @@ use mz_repr::explain::{
     CompactScalars, ExprHumanizer, HumanizedAttributes, IndexUsageType, Indices,
     PlanRenderingContext, RenderingContext, ScalarOps,
 };
-use mz_repr::{Datum, Diff, GlobalId, Row};
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp};
+use mz_sql_parser::ast::display::escaped_string_literal;
 use mz_sql_parser::ast::Ident;
 
-use crate::explain::{ExplainMultiPlan, ExplainSinglePlan};
+use crate::explain::{ExplainMultiPlan, ExplainSinglePlan, ExplainSubscribe};
 use crate::{
     AccessStrategy, AggregateExpr, EvalError, Id, JoinImplementation, JoinInputCharacteristics,
     LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr, RowSetFinishing,
@@ -67,6 +68,15 @@ where
             writeln!(f, "Target cluster: {}", target_cluster)?;
         }
 
+        if !self.context.config.parameters.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Parameters:")?;
+            for (i, typ) in self.context.config.parameters.iter().enumerate() {
+                let typ = self.context.humanizer.humanize_scalar_type(typ);
+                writeln!(f, "  ${}: {}", i + 1, typ)?;
+            }
+        }
+
         if !self.context.optimizer_notices.is_empty() {
             writeln!(f)?;
             writeln!(f, "Notices:")?;
@@ -93,6 +103,31 @@ where
 
         let mode = HumanizedExplain::new(self.context.config.redacted);
 
+        // Render the comments on the objects ahead of the plans.
+        if self.context.config.comments {
+            let comments = self.comments();
+            if !comments.is_empty() {
+                writeln!(f, "{}Comments:", ctx.indent)?;
+                ctx.indented(|ctx| {
+                    for (id, column, comment) in comments.iter() {
+                        let name = ctx
+                            .humanizer
+                            .humanize_id(*id)
+                            .unwrap_or_else(|| id.to_string());
+                        let comment = escaped_string_literal(comment);
+                        match column {
+                            Some(column) => {
+                                writeln!(f, "{}{name}.{column}: {comment}", ctx.indent)?
+                            }
+                            None => writeln!(f, "{}{name}: {comment}", ctx.indent)?,
+                        }
+                    }
+                    Ok(())
+                })?;
+                writeln!(f)?;
+            }
+        }
+
         // Render plans.
         for (no, (id, plan)) in self.plans.iter().enumerate() {
             let mut ctx = PlanRenderingContext::new(
@@ -129,10 +164,10 @@ where
             })?;
         }
 
-        if self.sources.iter().any(|src| !src.is_identity()) {
+        if self.sources.iter().any(|src| src.is_explained()) {
             // Render one blank line between the plans and sources.
             writeln!(f)?;
-            for src in self.sources.iter().filter(|src| !src.is_identity()) {
+            for src in self.sources.iter().filter(|src| src.is_explained()) {
                 if self.context.config.humanized_exprs {
                     let mut cols = ctx.humanizer.column_names_for_id(src.id);
                     // The column names of the source needs to be extended with
@@ -158,6 +193,11 @@ where
             self.context.used_indexes.fmt_text(f, &mut ctx)?;
         }
 
+        if let Some(subscribe) = &self.context.subscribe {
+            writeln!(f)?;
+            fmt_text_subscribe(f, subscribe, self.context.config.redacted)?;
+        }
+
         if let Some(target_cluster) = self.context.target_cluster {
             writeln!(f)?;
             writeln!(f, "Target cluster: {}", target_cluster)?;
@@ -180,6 +220,34 @@ where
     }
 }
 
+/// Renders the parameters of the sink of an explained `SUBSCRIBE`.
+fn fmt_text_subscribe(
+    f: &mut fmt::Formatter<'_>,
+    subscribe: &ExplainSubscribe,
+    redacted: bool,
+) -> fmt::Result {
+    let ts = |ts: &Timestamp| {
+        if redacted {
+            "█".to_string()
+        } else {
+            ts.to_string()
+        }
+    };
+    let frontier =
+        |frontier: &[Timestamp]| format!("[{}]", separated(", ", frontier.iter().map(ts)));
+
+    writeln!(f, "Subscribe:")?;
+    writeln!(f, "  as_of: {}", ts(&subscribe.as_of))?;
+    writeln!(f, "  input since: {}", frontier(&subscribe.since))?;
+    writeln!(f, "  input upper: {}", frontier(&subscribe.upper))?;
+    if let Some(up_to) = &subscribe.up_to {
+        writeln!(f, "  up_to: {}", ts(up_to))?;
+    }
+    writeln!(f, "  snapshot: {}", subscribe.with_snapshot)?;
+    writeln!(f, "  progress: {}", subscribe.emit_progress)?;
+    Ok(())
+}
+
 impl<'a, C, M> DisplayText<C> for HumanizedExpr<'a, RowSetFinishing, M>
 where
     C: AsMut<Indent>,
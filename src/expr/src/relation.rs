@@ -3060,7 +3060,7 @@ impl JoinInputCharacteristics {
 /// keywords), whereas much of the rest of SQL is defined in terms of unordered
 /// multisets. But as it turns out, the same idea can be used to optimize
 /// trivial peeks.
-#[derive(Arbitrary, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Arbitrary, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RowSetFinishing<L = NonNeg<i64>> {
     /// Order rows by the given columns.
     pub order_by: Vec<ColumnOrder>,
@@ -3129,6 +3129,14 @@ impl RowSetFinishing {
         result
     }
 
+    /// Returns the number of bytes that finishing `rows` requires, which
+    /// includes the rows themselves and the sorted view over them.
+    pub fn required_memory(rows: &RowCollection) -> usize {
+        // How much additional memory is required to make a sorted view.
+        let sorted_view_mem = rows.entries().saturating_mul(std::mem::size_of::<usize>());
+        rows.byte_len().saturating_add(sorted_view_mem)
+    }
+
     /// Implementation for [`RowSetFinishing::finish`].
     fn finish_inner(
         &self,
@@ -3136,9 +3144,7 @@ impl RowSetFinishing {
         max_result_size: u64,
         max_returned_query_size: Option<u64>,
     ) -> Result<SortedRowCollectionIter, String> {
-        // How much additional memory is required to make a sorted view.
-        let sorted_view_mem = rows.entries().saturating_mul(std::mem::size_of::<usize>());
-        let required_memory = rows.byte_len().saturating_add(sorted_view_mem);
+        let required_memory = Self::required_memory(&rows);
 
         // Bail if creating the sorted view would require us to use too much memory.
         if required_memory > usize::cast_from(max_result_size) {
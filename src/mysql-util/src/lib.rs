@@ -28,7 +28,10 @@ pub use replication::{
 };
 
 pub mod schemas;
-pub use schemas::{schema_info, QualifiedTableRef, SchemaRequest};
+pub use schemas::{
+    schema_info, schema_tables, table_info, validate_column_refs, QualifiedTableRef, SchemaRequest,
+    SchemaTable,
+};
 
 pub mod privileges;
 pub use privileges::validate_source_privileges;
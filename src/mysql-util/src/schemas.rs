@@ -88,6 +88,13 @@ pub struct QualifiedTableRef<'a> {
     pub table_name: &'a str,
 }
 
+/// A table of an upstream MySQL database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaTable {
+    pub schema_name: String,
+    pub table_name: String,
+}
+
 /// Retrieve the tables and column descriptions for tables in the given schemas.
 pub async fn schema_info<'a, Q>(
     conn: &mut Q,
@@ -98,7 +105,31 @@ pub async fn schema_info<'a, Q>(
 where
     Q: Queryable,
 {
-    // Verify there are no duplicates in text_columns and ignore_columns
+    validate_column_refs(text_columns, ignore_columns)?;
+
+    let mut tables = vec![];
+    let mut error_cols = vec![];
+    for table in schema_tables(conn, schema_request).await? {
+        match table_info(conn, table, text_columns, ignore_columns).await {
+            Ok(table) => tables.push(table),
+            Err(MySqlError::UnsupportedDataTypes { columns }) => error_cols.extend(columns),
+            Err(e) => return Err(e),
+        }
+    }
+
+    if error_cols.len() > 0 {
+        Err(MySqlError::UnsupportedDataTypes {
+            columns: error_cols,
+        })?;
+    }
+    Ok(tables)
+}
+
+/// Verifies that no column is referenced both in `text_columns` and in `ignore_columns`.
+pub fn validate_column_refs<'a>(
+    text_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+    ignore_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+) -> Result<(), MySqlError> {
     for (table_ref, text_cols) in text_columns.iter() {
         if let Some(ignore_cols) = ignore_columns.get(table_ref) {
             let intersection: Vec<_> = text_cols.intersection(ignore_cols).collect();
@@ -114,6 +145,17 @@ where
         }
     }
 
+    Ok(())
+}
+
+/// Lists the tables requested by `schema_request`, without fetching their schemas.
+pub async fn schema_tables<'a, Q>(
+    conn: &mut Q,
+    schema_request: &SchemaRequest<'a>,
+) -> Result<Vec<SchemaTable>, MySqlError>
+where
+    Q: Queryable,
+{
     let table_rows: Vec<(String, String)> = match schema_request {
         SchemaRequest::All => {
             // Get all tables in non-system schemas.
@@ -163,126 +205,145 @@ where
         }
     };
 
-    let mut tables = vec![];
+    Ok(table_rows
+        .into_iter()
+        .map(|(table_name, schema_name)| SchemaTable {
+            schema_name,
+            table_name,
+        })
+        .collect())
+}
+
+/// Fetches the schema of the table `table`, ingesting the columns of the table in `text_columns`
+/// as text and ignoring the columns of the table in `ignore_columns`.
+///
+/// # Errors
+///
+/// - Columns of the table have unsupported types.
+pub async fn table_info<'a, Q>(
+    conn: &mut Q,
+    table: SchemaTable,
+    text_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+    ignore_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+) -> Result<MySqlTableDesc, MySqlError>
+where
+    Q: Queryable,
+{
+    let SchemaTable {
+        schema_name,
+        table_name,
+    } = table;
+    let table_text_cols = text_columns.get(&QualifiedTableRef {
+        schema_name: &schema_name,
+        table_name: &table_name,
+    });
+    let table_ignore_cols = ignore_columns.get(&QualifiedTableRef {
+        schema_name: &schema_name,
+        table_name: &table_name,
+    });
+
     let mut error_cols = vec![];
-    for (table_name, schema_name) in table_rows {
-        let table_text_cols = text_columns.get(&QualifiedTableRef {
-            schema_name: &schema_name,
-            table_name: &table_name,
-        });
-        let table_ignore_cols = ignore_columns.get(&QualifiedTableRef {
-            schema_name: &schema_name,
-            table_name: &table_name,
-        });
 
-        // NOTE: It's important that we order by ordinal_position ASC since we rely on this as
-        // the ordering in which columns are returned in a row.
-        let column_q = format!(
-            "SELECT {}
-             FROM information_schema.columns
-             WHERE table_name = ? AND table_schema = ?
-             ORDER BY ordinal_position ASC",
-            INFO_SCHEMA_COLS
-                .iter()
-                .map(|c| format!("{c} AS {c}"))
-                .join(", ")
-        );
-        let column_rows = conn
-            .exec::<InfoSchema, _, _>(column_q, (&table_name, &schema_name))
-            .await?;
-
-        let mut columns = Vec::with_capacity(column_rows.len());
-        for info in column_rows {
-            // If this column is designated as a text column and of a supported text-column type
-            // treat it as a string and skip type parsing.
-            if let Some(text_columns) = table_text_cols {
-                if text_columns.contains(&info.column_name.as_str()) {
-                    match parse_as_text_column(&info, &schema_name, &table_name) {
-                        Err(err) => error_cols.push(err),
-                        Ok((scalar_type, meta)) => columns.push(MySqlColumnDesc {
-                            name: info.column_name,
-                            column_type: Some(ColumnType {
-                                scalar_type,
-                                nullable: &info.is_nullable == "YES",
-                            }),
-                            meta,
-                        }),
-                    }
-                    continue;
-                }
-            }
+    // NOTE: It's important that we order by ordinal_position ASC since we rely on this as
+    // the ordering in which columns are returned in a row.
+    let column_q = format!(
+        "SELECT {}
+         FROM information_schema.columns
+         WHERE table_name = ? AND table_schema = ?
+         ORDER BY ordinal_position ASC",
+        INFO_SCHEMA_COLS
+            .iter()
+            .map(|c| format!("{c} AS {c}"))
+            .join(", ")
+    );
+    let column_rows = conn
+        .exec::<InfoSchema, _, _>(column_q, (&table_name, &schema_name))
+        .await?;
 
-            // If this column is ignored, use None for the column type to signal that it should be.
-            if let Some(ignore_cols) = table_ignore_cols {
-                if ignore_cols.contains(&info.column_name.as_str()) {
-                    columns.push(MySqlColumnDesc {
+    let mut columns = Vec::with_capacity(column_rows.len());
+    for info in column_rows {
+        // If this column is designated as a text column and of a supported text-column type
+        // treat it as a string and skip type parsing.
+        if let Some(text_columns) = table_text_cols {
+            if text_columns.contains(&info.column_name.as_str()) {
+                match parse_as_text_column(&info, &schema_name, &table_name) {
+                    Err(err) => error_cols.push(err),
+                    Ok((scalar_type, meta)) => columns.push(MySqlColumnDesc {
                         name: info.column_name,
-                        column_type: None,
-                        meta: None,
-                    });
-                    continue;
+                        column_type: Some(ColumnType {
+                            scalar_type,
+                            nullable: &info.is_nullable == "YES",
+                        }),
+                        meta,
+                    }),
                 }
+                continue;
             }
+        }
 
-            // Collect the parsed data types or errors for later reporting.
-            match parse_data_type(&info, &schema_name, &table_name) {
-                Err(err) => error_cols.push(err),
-                Ok(scalar_type) => columns.push(MySqlColumnDesc {
+        // If this column is ignored, use None for the column type to signal that it should be.
+        if let Some(ignore_cols) = table_ignore_cols {
+            if ignore_cols.contains(&info.column_name.as_str()) {
+                columns.push(MySqlColumnDesc {
                     name: info.column_name,
-                    column_type: Some(ColumnType {
-                        scalar_type,
-                        nullable: &info.is_nullable == "YES",
-                    }),
+                    column_type: None,
                     meta: None,
-                }),
+                });
+                continue;
             }
         }
 
-        // Query for primary key and unique constraints that do not contain expressions / functional key parts.
-        // When a constraint contains expressions, the column_name field is NULL.
-        let index_rows = conn
-            .exec::<(String, String), _, _>(
-                "SELECT
-                    index_name,
-                    column_name
-                FROM information_schema.statistics AS outt
-                WHERE
-                    table_schema NOT IN ('information_schema', 'performance_schema', 'mysql', 'sys')
-                    AND NOT EXISTS (
-                        SELECT 1
-                        FROM information_schema.statistics AS inn
-                        WHERE outt.index_name = inn.index_name AND inn.column_name IS NULL
-                    )
-                    AND non_unique = 0
-                    AND table_name = ?
-                    AND table_schema = ?
-                ORDER BY index_name, seq_in_index
-            ",
-                (&table_name, &schema_name),
-            )
-            .await?;
-
-        let mut indices = BTreeMap::new();
-        for (index_name, column) in index_rows {
-            indices
-                .entry(index_name)
-                .or_insert_with(Vec::new)
-                .push(column);
-        }
-        let mut keys = BTreeSet::new();
-        while let Some((index_name, columns)) = indices.pop_first() {
-            keys.insert(MySqlKeyDesc {
-                is_primary: &index_name == "PRIMARY",
-                name: index_name,
-                columns,
-            });
+        // Collect the parsed data types or errors for later reporting.
+        match parse_data_type(&info, &schema_name, &table_name) {
+            Err(err) => error_cols.push(err),
+            Ok(scalar_type) => columns.push(MySqlColumnDesc {
+                name: info.column_name,
+                column_type: Some(ColumnType {
+                    scalar_type,
+                    nullable: &info.is_nullable == "YES",
+                }),
+                meta: None,
+            }),
         }
+    }
 
-        tables.push(MySqlTableDesc {
-            schema_name,
-            name: table_name,
+    // Query for primary key and unique constraints that do not contain expressions / functional key parts.
+    // When a constraint contains expressions, the column_name field is NULL.
+    let index_rows = conn
+        .exec::<(String, String), _, _>(
+            "SELECT
+                index_name,
+                column_name
+            FROM information_schema.statistics AS outt
+            WHERE
+                table_schema NOT IN ('information_schema', 'performance_schema', 'mysql', 'sys')
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM information_schema.statistics AS inn
+                    WHERE outt.index_name = inn.index_name AND inn.column_name IS NULL
+                )
+                AND non_unique = 0
+                AND table_name = ?
+                AND table_schema = ?
+            ORDER BY index_name, seq_in_index
+        ",
+            (&table_name, &schema_name),
+        )
+        .await?;
+
+    let mut indices = BTreeMap::new();
+    for (index_name, column) in index_rows {
+        indices
+            .entry(index_name)
+            .or_insert_with(Vec::new)
+            .push(column);
+    }
+    let mut keys = BTreeSet::new();
+    while let Some((index_name, columns)) = indices.pop_first() {
+        keys.insert(MySqlKeyDesc {
+            is_primary: &index_name == "PRIMARY",
+            name: index_name,
             columns,
-            keys,
         });
     }
 
@@ -291,7 +352,12 @@ where
             columns: error_cols,
         })?;
     }
-    Ok(tables)
+    Ok(MySqlTableDesc {
+        schema_name,
+        name: table_name,
+        columns,
+        keys,
+    })
 }
 
 fn parse_data_type(
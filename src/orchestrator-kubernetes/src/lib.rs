@@ -23,8 +23,8 @@ use futures::TryFutureExt;
 use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
 use k8s_openapi::api::core::v1::{
     Affinity, Capabilities, Container, ContainerPort, EnvVar, EnvVarSource, EphemeralVolumeSource,
-    NodeAffinity, NodeSelector, NodeSelectorRequirement, NodeSelectorTerm, ObjectFieldSelector,
-    ObjectReference, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    Node, NodeAffinity, NodeSelector, NodeSelectorRequirement, NodeSelectorTerm,
+    ObjectFieldSelector, ObjectReference, PersistentVolumeClaim, PersistentVolumeClaimSpec,
     PersistentVolumeClaimTemplate, Pod, PodAffinity, PodAffinityTerm, PodAntiAffinity,
     PodSecurityContext, PodSpec, PodTemplateSpec, PreferredSchedulingTerm, ResourceRequirements,
     SeccompProfile, Secret, SecurityContext, Service as K8sService, ServicePort, ServiceSpec,
@@ -32,7 +32,7 @@ use k8s_openapi::api::core::v1::{
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
-use kube::api::{Api, DeleteParams, ObjectMeta, Patch, PatchParams};
+use kube::api::{Api, DeleteParams, ListParams, ObjectMeta, Patch, PatchParams};
 use kube::client::Client;
 use kube::error::Error as K8sError;
 use kube::runtime::{watcher, WatchStreamExt};
@@ -42,9 +42,10 @@ use mz_cloud_resources::crd::vpc_endpoint::v1::VpcEndpoint;
 use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_orchestrator::{
     scheduling_config::*, DiskLimit, LabelSelectionLogic, LabelSelector as MzLabelSelector,
-    NamespacedOrchestrator, NotReadyReason, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics, ServiceStatus,
+    NamespacedOrchestrator, NotReadyReason, Orchestrator, Service, ServiceCapacity, ServiceConfig,
+    ServiceEvent, ServiceProcessMetrics, ServiceResources, ServiceStatus,
 };
+use mz_ore::cast::CastFrom;
 use mz_ore::retry::Retry;
 use mz_ore::task::AbortOnDropHandle;
 use serde::Deserialize;
@@ -61,6 +62,9 @@ const NODE_FAILURE_THRESHOLD_SECONDS: i64 = 30;
 
 const POD_TEMPLATE_HASH_ANNOTATION: &str = "environmentd.materialize.cloud/pod-template-hash";
 
+/// The label of nodes that Karpenter provisioned for a node pool.
+const KARPENTER_NODE_POOL_LABEL: &str = "karpenter.sh/nodepool";
+
 /// Configures a [`KubernetesOrchestrator`].
 #[derive(Debug, Clone)]
 pub struct KubernetesOrchestratorConfig {
@@ -90,6 +94,11 @@ pub struct KubernetesOrchestratorConfig {
     pub ephemeral_volume_storage_class: Option<String>,
     /// The optional fs group for service's pods' `securityContext`.
     pub service_fs_group: Option<i64>,
+    /// Whether to check the capacity of the Kubernetes cluster against its nodes and the pods
+    /// running on them. This requires permission to list nodes and the pods of all namespaces.
+    ///
+    /// If disabled, the capacity is reported as unknown.
+    pub capacity_checks: bool,
 }
 
 /// Specifies whether Kubernetes should pull Docker images when creating pods.
@@ -167,6 +176,8 @@ impl Orchestrator for KubernetesOrchestrator {
 
             Arc::new(NamespacedKubernetesOrchestrator {
                 pod_api: Api::default_namespaced(self.client.clone()),
+                node_api: Api::all(self.client.clone()),
+                all_pods_api: Api::all(self.client.clone()),
                 kubernetes_namespace: self.kubernetes_namespace.clone(),
                 namespace: namespace.into(),
                 config: self.config.clone(),
@@ -189,6 +200,11 @@ struct ServiceInfo {
 
 struct NamespacedKubernetesOrchestrator {
     pod_api: Api<Pod>,
+    /// The nodes of the Kubernetes cluster, which are consulted by capacity checks.
+    node_api: Api<Node>,
+    /// The pods of all Kubernetes namespaces, whose resource requests are subtracted from the
+    /// capacity of the nodes they run on.
+    all_pods_api: Api<Pod>,
     kubernetes_namespace: String,
     namespace: String,
     config: KubernetesOrchestratorConfig,
@@ -416,6 +432,28 @@ impl NamespacedKubernetesOrchestrator {
     fn send_command(&self, cmd: WorkerCommand) {
         self.command_tx.send(cmd).expect("worker task not dropped");
     }
+
+    /// Returns the node selector for the pods of a service with the given `node_selector`.
+    fn node_selector(
+        &self,
+        disk: bool,
+        node_selector: BTreeMap<String, String>,
+    ) -> BTreeMap<String, String> {
+        let default_node_selector = if disk {
+            vec![("materialize.cloud/disk".to_string(), disk.to_string())]
+        } else {
+            // if the cluster doesn't require disk, we can omit the selector
+            // allowing it to be scheduled onto nodes with and without the
+            // selector
+            vec![]
+        };
+
+        default_node_selector
+            .into_iter()
+            .chain(self.config.service_node_selector.clone())
+            .chain(node_selector)
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -439,7 +477,7 @@ impl ScaledQuantity {
             }
         } else {
             for _ in 0..exponent {
-                result = result.checked_mul(2)?;
+                result = result.checked_mul(base)?;
             }
         }
         Some(result)
@@ -512,6 +550,21 @@ fn parse_k8s_quantity(s: &str) -> Result<ScaledQuantity, anyhow::Error> {
     })
 }
 
+/// Parses a k8s CPU `Quantity` into millicpus.
+fn parse_k8s_millicpus(quantity: &Quantity) -> Result<u64, anyhow::Error> {
+    parse_k8s_quantity(&quantity.0)?
+        .try_to_integer(-3, true)
+        .ok_or_else(|| anyhow!("invalid CPU quantity: {}", quantity.0))
+}
+
+/// Parses a k8s memory `Quantity` into bytes.
+fn parse_k8s_bytes(quantity: &Quantity) -> Result<u64, anyhow::Error> {
+    let parsed = parse_k8s_quantity(&quantity.0)?;
+    parsed
+        .try_to_integer(0, parsed.base10)
+        .ok_or_else(|| anyhow!("invalid memory quantity: {}", quantity.0))
+}
+
 #[async_trait]
 impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
     async fn fetch_service_metrics(
@@ -780,20 +833,7 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
             "karpenter.sh/do-not-disrupt".to_owned() => "true".to_string(),
         };
 
-        let default_node_selector = if disk {
-            vec![("materialize.cloud/disk".to_string(), disk.to_string())]
-        } else {
-            // if the cluster doesn't require disk, we can omit the selector
-            // allowing it to be scheduled onto nodes with and without the
-            // selector
-            vec![]
-        };
-
-        let node_selector: BTreeMap<String, String> = default_node_selector
-            .into_iter()
-            .chain(self.config.service_node_selector.clone())
-            .chain(node_selector)
-            .collect();
+        let node_selector = self.node_selector(disk, node_selector);
 
         let node_affinity = if let Some(availability_zones) = availability_zones {
             let selector = NodeSelectorTerm {
@@ -1266,6 +1306,129 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
     fn update_scheduling_config(&self, config: ServiceSchedulingConfig) {
         *self.scheduling_config.write().expect("poisoned") = config;
     }
+
+    /// Counts the processes that fit into the allocatable resources of the ready nodes matching
+    /// the node selector of the service, minus the resources requested by the pods already
+    /// running on them.
+    ///
+    /// Nodes that are provisioned by Karpenter come and go with demand, so the capacity of their
+    /// Kubernetes clusters is unknown. Node taints and pod affinities are not taken into account,
+    /// so a service reported to have insufficient capacity might still get scheduled eventually.
+    async fn check_capacity(
+        &self,
+        resources: &ServiceResources,
+    ) -> Result<ServiceCapacity, anyhow::Error> {
+        if !self.config.capacity_checks {
+            return Ok(ServiceCapacity::Unknown);
+        }
+
+        let ServiceResources {
+            memory_limit,
+            cpu_limit,
+            scale,
+            disk,
+            availability_zones,
+            node_selector,
+        } = resources;
+        let always_use_disk = self
+            .scheduling_config
+            .read()
+            .expect("poisoned")
+            .always_use_disk;
+        let node_selector = self.node_selector(always_use_disk || *disk, node_selector.clone());
+        let label_selector = node_selector
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let nodes = self
+            .node_api
+            .list(&ListParams::default().labels(&label_selector))
+            .await?;
+        if nodes
+            .iter()
+            .any(|node| node.labels().contains_key(KARPENTER_NODE_POOL_LABEL))
+        {
+            return Ok(ServiceCapacity::Unknown);
+        }
+        let pods = self
+            .all_pods_api
+            .list(&ListParams::default().fields("status.phase!=Succeeded,status.phase!=Failed"))
+            .await?;
+
+        // The millicpus and bytes of memory requested by the pods on each node.
+        let mut requested: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        for pod in pods {
+            let Some(spec) = pod.spec else { continue };
+            let Some(node_name) = spec.node_name else {
+                continue;
+            };
+            let (cpu, memory) = requested.entry(node_name).or_default();
+            for container in spec.containers {
+                let Some(requests) = container.resources.and_then(|r| r.requests) else {
+                    continue;
+                };
+                if let Some(quantity) = requests.get("cpu") {
+                    *cpu = cpu.saturating_add(parse_k8s_millicpus(quantity)?);
+                }
+                if let Some(quantity) = requests.get("memory") {
+                    *memory = memory.saturating_add(parse_k8s_bytes(quantity)?);
+                }
+            }
+        }
+
+        let mut schedulable: u64 = 0;
+        for node in nodes {
+            if let Some(availability_zones) = availability_zones {
+                let zone = node.labels().get("topology.kubernetes.io/zone");
+                if !zone.map_or(false, |zone| availability_zones.contains(zone)) {
+                    continue;
+                }
+            }
+            if node.spec.as_ref().and_then(|spec| spec.unschedulable) == Some(true) {
+                continue;
+            }
+            let Some(status) = &node.status else { continue };
+            let ready = status
+                .conditions
+                .iter()
+                .flatten()
+                .any(|condition| condition.type_ == "Ready" && condition.status == "True");
+            let Some(allocatable) = status.allocatable.as_ref().filter(|_| ready) else {
+                continue;
+            };
+
+            let (requested_cpu, requested_memory) =
+                requested.get(&node.name_any()).copied().unwrap_or_default();
+            let mut fits = u64::MAX;
+            if let Some(cpu_limit) = cpu_limit {
+                let allocatable = match allocatable.get("cpu") {
+                    Some(quantity) => parse_k8s_millicpus(quantity)?,
+                    None => 0,
+                };
+                let needed = u64::cast_from(cpu_limit.as_millicpus()).max(1);
+                fits = fits.min(allocatable.saturating_sub(requested_cpu) / needed);
+            }
+            if let Some(memory_limit) = memory_limit {
+                let allocatable = match allocatable.get("memory") {
+                    Some(quantity) => parse_k8s_bytes(quantity)?,
+                    None => 0,
+                };
+                let needed = memory_limit.0.as_u64().max(1);
+                fits = fits.min(allocatable.saturating_sub(requested_memory) / needed);
+            }
+            schedulable = schedulable.saturating_add(fits);
+        }
+
+        Ok(if schedulable >= u64::from(*scale) {
+            ServiceCapacity::Available
+        } else {
+            ServiceCapacity::Insufficient {
+                schedulable: u16::try_from(schedulable).expect("less than scale"),
+            }
+        })
+    }
 }
 
 impl OrchestratorWorker {
@@ -1663,3 +1826,31 @@ impl Service for KubernetesService {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_k8s_quantity() {
+        let millicpus = |s: &str| parse_k8s_millicpus(&Quantity(s.into())).unwrap();
+        assert_eq!(millicpus("2"), 2000);
+        assert_eq!(millicpus("1500m"), 1500);
+        assert_eq!(millicpus("250000u"), 250);
+        assert_eq!(millicpus("1k"), 1_000_000);
+
+        let bytes = |s: &str| parse_k8s_bytes(&Quantity(s.into())).unwrap();
+        assert_eq!(bytes("1024"), 1024);
+        assert_eq!(bytes("128974848"), 128974848);
+        assert_eq!(bytes("1k"), 1_000);
+        assert_eq!(bytes("129M"), 129_000_000);
+        assert_eq!(bytes("1G"), 1_000_000_000);
+        assert_eq!(bytes("123Mi"), 123 << 20);
+        assert_eq!(bytes("4Gi"), 4 << 30);
+
+        // Binary suffixes are not valid for CPU quantities.
+        assert!(parse_k8s_millicpus(&Quantity("1Ki".into())).is_err());
+        // Overflowing quantities are rejected rather than wrapped.
+        assert!(parse_k8s_bytes(&Quantity("20E".into())).is_err());
+    }
+}
@@ -25,8 +25,8 @@ use mz_build_info::BuildInfo;
 #[cfg(feature = "tokio-console")]
 use mz_orchestrator::ServicePort;
 use mz_orchestrator::{
-    NamespacedOrchestrator, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics,
+    NamespacedOrchestrator, Orchestrator, Service, ServiceCapacity, ServiceConfig, ServiceEvent,
+    ServiceProcessMetrics, ServiceResources,
 };
 use mz_ore::cli::KeyValueArg;
 use mz_ore::metrics::MetricsRegistry;
@@ -518,6 +518,13 @@ impl NamespacedOrchestrator for NamespacedTracingOrchestrator {
     ) {
         self.inner.update_scheduling_config(config)
     }
+
+    async fn check_capacity(
+        &self,
+        resources: &ServiceResources,
+    ) -> Result<ServiceCapacity, anyhow::Error> {
+        self.inner.check_capacity(resources).await
+    }
 }
 
 /// Specifies the format of a stderr log message.
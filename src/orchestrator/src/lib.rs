@@ -84,6 +84,18 @@ pub trait NamespacedOrchestrator: fmt::Debug + Send + Sync {
     ) -> Result<Vec<ServiceProcessMetrics>, anyhow::Error>;
 
     fn update_scheduling_config(&self, config: scheduling_config::ServiceSchedulingConfig);
+
+    /// Reports how many processes of a service that needs `resources` the orchestrator could
+    /// currently schedule.
+    ///
+    /// The result is a best-effort snapshot that can be outdated as soon as it is returned.
+    /// Orchestrators that cannot determine their capacity report [`ServiceCapacity::Unknown`].
+    async fn check_capacity(
+        &self,
+        _resources: &ServiceResources,
+    ) -> Result<ServiceCapacity, anyhow::Error> {
+        Ok(ServiceCapacity::Unknown)
+    }
 }
 
 /// An event describing a status change of an orchestrated service.
@@ -146,6 +158,35 @@ pub struct ServiceProcessMetrics {
     pub disk_usage_bytes: Option<u64>,
 }
 
+/// The resources needed to schedule the processes of a service.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceResources {
+    /// The memory limit of each process.
+    pub memory_limit: Option<MemoryLimit>,
+    /// The CPU limit of each process.
+    pub cpu_limit: Option<CpuLimit>,
+    /// The number of processes.
+    pub scale: u16,
+    /// Whether the processes need scratch disk space.
+    pub disk: bool,
+    /// The availability zones the processes can be run in. If `None`, any zone will do.
+    pub availability_zones: Option<Vec<String>>,
+    /// Node selector for the processes.
+    pub node_selector: BTreeMap<String, String>,
+}
+
+/// The capacity of an orchestrator to run a service, as reported by
+/// [`NamespacedOrchestrator::check_capacity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceCapacity {
+    /// All processes of the service can be scheduled.
+    Available,
+    /// Only `schedulable` of the processes of the service can be scheduled.
+    Insufficient { schedulable: u16 },
+    /// The orchestrator cannot determine its capacity.
+    Unknown,
+}
+
 /// A simple language for describing assertions about a label's existence and value.
 ///
 /// Used by [`LabelSelector`].
@@ -9,19 +9,23 @@
 
 //! Introspection of storage utilization by persist
 
-use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::Instant;
 
 use futures::stream::{FuturesUnordered, StreamExt};
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::Blob;
 use tokio::sync::Semaphore;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::cfg::PersistConfig;
+use crate::fetch::fetch_batch_part_blob;
 use crate::internal::paths::{BlobKey, BlobKeyPrefix, PartialBlobKey, WriterKey};
-use crate::internal::state::HollowBlobRef;
+use crate::internal::state::{BatchPart, HollowBlobRef};
 use crate::internal::state_versions::StateVersions;
 use crate::write::WriterId;
 use crate::{retry_external, Metrics, PersistClient, ShardId};
@@ -32,6 +36,7 @@ use crate::{retry_external, Metrics, PersistClient, ShardId};
 pub struct ShardUsageReferenced {
     pub(crate) batches_bytes: u64,
     pub(crate) rollup_bytes: u64,
+    pub(crate) approx_distinct_keys: Option<u64>,
 }
 
 impl ShardUsageReferenced {
@@ -40,9 +45,20 @@ impl ShardUsageReferenced {
         let Self {
             batches_bytes,
             rollup_bytes,
+            approx_distinct_keys: _,
         } = self;
         *batches_bytes + *rollup_bytes
     }
+
+    /// An estimate of the number of distinct keys in the batches referenced by
+    /// the most recent version of state, if it was requested.
+    ///
+    /// The estimate counts every key that has an update in the batches, so
+    /// keys whose updates have been retracted but not yet compacted away are
+    /// included.
+    pub fn approx_distinct_keys(&self) -> Option<u64> {
+        self.approx_distinct_keys
+    }
 }
 
 /// The referenced blob usage for a set of shards.
@@ -178,7 +194,14 @@ impl StorageUsageClient {
     }
 
     /// Computes [ShardUsageReferenced] for a single shard. Suitable for customer billing.
-    pub async fn shard_usage_referenced(&self, shard_id: ShardId) -> ShardUsageReferenced {
+    ///
+    /// If `approx_distinct_keys` is set, this also estimates the number of distinct keys in the
+    /// shard, which requires fetching every part referenced by the most recent version of state.
+    pub async fn shard_usage_referenced(
+        &self,
+        shard_id: ShardId,
+        approx_distinct_keys: bool,
+    ) -> ShardUsageReferenced {
         let mut start = Instant::now();
         let states_iter = self
             .state_versions
@@ -190,6 +213,7 @@ impl StorageUsageClient {
                 return ShardUsageReferenced {
                     batches_bytes: 0,
                     rollup_bytes: 0,
+                    approx_distinct_keys: approx_distinct_keys.then_some(0),
                 }
             }
         };
@@ -222,9 +246,23 @@ impl StorageUsageClient {
             })
         }) {}
 
+        let approx_distinct_keys = if approx_distinct_keys {
+            let parts: Vec<_> = states_iter
+                .state()
+                .collections
+                .trace
+                .batches()
+                .flat_map(|batch| batch.parts.iter().cloned())
+                .collect();
+            Some(self.approx_distinct_keys(shard_id, parts).await)
+        } else {
+            None
+        };
+
         let referenced = ShardUsageReferenced {
             batches_bytes: u64::cast_from(batches_bytes),
             rollup_bytes: u64::cast_from(rollup_bytes),
+            approx_distinct_keys,
         };
 
         let current_state_sizes = states_iter.state().size_metrics();
@@ -251,7 +289,13 @@ impl StorageUsageClient {
     }
 
     /// Computes [ShardUsageReferenced] for a given set of shards. Suitable for customer billing.
-    pub async fn shards_usage_referenced<I>(&self, shard_ids: I) -> ShardsUsageReferenced
+    ///
+    /// The number of distinct keys is only estimated for the shards in `approx_distinct_keys`.
+    pub async fn shards_usage_referenced<I>(
+        &self,
+        shard_ids: I,
+        approx_distinct_keys: &BTreeSet<ShardId>,
+    ) -> ShardsUsageReferenced
     where
         I: IntoIterator<Item = ShardId>,
     {
@@ -266,7 +310,9 @@ impl StorageUsageClient {
                     .acquire()
                     .await
                     .expect("acquiring permit from open semaphore");
-                let shard_usage = self.shard_usage_referenced(shard_id).await;
+                let shard_usage = self
+                    .shard_usage_referenced(shard_id, approx_distinct_keys.contains(&shard_id))
+                    .await;
                 (shard_id, shard_usage)
             };
             by_shard_futures.push(shard_usage_fut);
@@ -275,6 +321,59 @@ impl StorageUsageClient {
         ShardsUsageReferenced { by_shard }
     }
 
+    /// Estimates the number of distinct keys in the given parts of the shard.
+    ///
+    /// Parts that have been deleted since they were read from state are skipped.
+    async fn approx_distinct_keys(&self, shard_id: ShardId, parts: Vec<BatchPart<u64>>) -> u64 {
+        let shard_metrics = self.metrics.shards.shard(&shard_id, "unknown");
+        let read_metrics = &self.metrics.read.usage;
+        let mut sketch = DistinctKeysSketch::default();
+        for part in parts {
+            let parsed = match part {
+                BatchPart::Hollow(part) => {
+                    let buf = match fetch_batch_part_blob(
+                        &shard_id,
+                        self.blob.as_ref(),
+                        &self.metrics,
+                        &shard_metrics,
+                        read_metrics,
+                        &part,
+                    )
+                    .await
+                    {
+                        Ok(buf) => buf,
+                        Err(blob_key) => {
+                            info!(
+                                "part {} of shard {} was deleted before its keys were counted",
+                                blob_key, shard_id
+                            );
+                            continue;
+                        }
+                    };
+                    BlobTraceBatchPart::<u64>::decode(&buf, &self.metrics.columnar)
+                        .map_err(|err| err.to_string())
+                }
+                BatchPart::Inline { updates, .. } => updates
+                    .decode::<u64>(&self.metrics.columnar)
+                    .map_err(|err| err.to_string()),
+            };
+            match parsed {
+                Ok(parsed) => {
+                    for ((key, _val), _ts, _diff) in parsed.updates.records().iter() {
+                        sketch.insert(key);
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "couldn't decode part of shard {} to count its keys: {}",
+                        shard_id, err
+                    );
+                }
+            }
+        }
+        sketch.estimate()
+    }
+
     /// Computes [ShardUsageAudit] for a single shard.
     ///
     /// Performs a full scan of [Blob] and [mz_persist::location::Consensus] to compute a full audit
@@ -721,6 +820,65 @@ impl std::fmt::Display for HumanBytes {
     }
 }
 
+/// The number of bits of a key's hash that select its register in a [DistinctKeysSketch].
+///
+/// With `2^12` registers, the standard error of the estimate is about 1.6%.
+const DISTINCT_KEYS_SKETCH_PRECISION: u32 = 12;
+
+/// A HyperLogLog sketch of the distinct keys of a shard.
+#[derive(Debug)]
+struct DistinctKeysSketch {
+    /// For each register, the maximum rank of the hashes of the keys assigned to it.
+    registers: Vec<u8>,
+}
+
+impl Default for DistinctKeysSketch {
+    fn default() -> Self {
+        DistinctKeysSketch {
+            registers: vec![0; 1 << DISTINCT_KEYS_SKETCH_PRECISION],
+        }
+    }
+}
+
+impl DistinctKeysSketch {
+    /// Adds an encoded key to the sketch.
+    fn insert(&mut self, key: &[u8]) {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+        let register = usize::cast_from(hash >> (u64::BITS - DISTINCT_KEYS_SKETCH_PRECISION));
+        // The rank is the position of the first set bit in the remaining bits of the hash.
+        let remaining = hash << DISTINCT_KEYS_SKETCH_PRECISION;
+        let rank = remaining
+            .leading_zeros()
+            .min(u64::BITS - DISTINCT_KEYS_SKETCH_PRECISION)
+            + 1;
+        let rank = u8::try_from(rank).expect("rank is at most 64");
+        self.registers[register] = self.registers[register].max(rank);
+    }
+
+    /// Returns the estimated number of distinct keys added to the sketch.
+    fn estimate(&self) -> u64 {
+        let m = f64::cast_lossy(self.registers.len());
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|rank| 2f64.powi(-i32::from(*rank)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let empty = self.registers.iter().filter(|rank| **rank == 0).count();
+        // The raw estimate is biased for small cardinalities, for which linear counting
+        // is more accurate.
+        let estimate = if raw <= 2.5 * m && empty > 0 {
+            m * (m / f64::cast_lossy(empty)).ln()
+        } else {
+            raw
+        };
+        u64::cast_lossy(estimate.round())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -899,7 +1057,7 @@ mod tests {
         client.cfg.build_version.minor += 1;
         let usage = StorageUsageClient::open(client);
         let shard_usage_audit = usage.shard_usage_audit(shard_id).await;
-        let shard_usage_referenced = usage.shard_usage_referenced(shard_id).await;
+        let shard_usage_referenced = usage.shard_usage_referenced(shard_id, false).await;
         if !inline_writes_enabled {
             // We've written data.
             assert!(shard_usage_audit.current_state_batches_bytes > 0);
@@ -980,10 +1138,29 @@ mod tests {
             .await;
 
         let usage = StorageUsageClient::open(client);
-        let shard_usage_referenced = usage.shard_usage_referenced(shard_id).await;
+        let shard_usage_referenced = usage.shard_usage_referenced(shard_id, true).await;
 
         // with compaction disabled, we can do an exact match on batch part byte size
         assert_eq!(shard_usage_referenced.batches_bytes, batches_size);
+        // the sketch is exact for this few keys
+        assert_eq!(shard_usage_referenced.approx_distinct_keys(), Some(4));
+    }
+
+    #[mz_ore::test]
+    fn distinct_keys_sketch() {
+        let mut sketch = DistinctKeysSketch::default();
+        assert_eq!(sketch.estimate(), 0);
+
+        for key in 0..100_000u64 {
+            sketch.insert(&key.to_le_bytes());
+            // Duplicates don't count.
+            sketch.insert(&key.to_le_bytes());
+        }
+        let estimate = sketch.estimate();
+        assert!(
+            (95_000..=105_000).contains(&estimate),
+            "estimate {estimate} is off by more than 5%"
+        );
     }
 
     fn writer_id(x: char) -> WriterId {
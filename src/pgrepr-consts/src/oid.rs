@@ -702,3 +702,38 @@ pub const TABLE_MZ_CLUSTER_WORKLOAD_CLASSES_OID: u32 = 16979;
 pub const INDEX_MZ_CLUSTER_WORKLOAD_CLASSES_IND_OID: u32 = 16980;
 pub const VIEW_MZ_RECENT_STORAGE_USAGE_OID: u32 = 16981;
 pub const INDEX_MZ_RECENT_STORAGE_USAGE_IND_OID: u32 = 16982;
+pub const FUNC_MZ_VACUUM_CATALOG_OID: u32 = 16983;
+pub const SOURCE_MZ_LEADERSHIP_HISTORY_OID: u32 = 16984;
+pub const TABLE_MZ_SUBSCRIPTION_BUFFERS_OID: u32 = 16985;
+pub const TABLE_MZ_DEFERRED_MAINTENANCE_OID: u32 = 16986;
+pub const TABLE_MZ_BACKGROUND_TASKS_OID: u32 = 16987;
+pub const FUNC_MZ_TRIGGER_BACKGROUND_TASK_OID: u32 = 16988;
+pub const FUNC_MZ_SKIP_BACKGROUND_TASK_OID: u32 = 16989;
+pub const TABLE_MZ_DATABASE_METRICS_OID: u32 = 16990;
+pub const FUNC_MZ_SET_FAILPOINT_OID: u32 = 16991;
+pub const SOURCE_MZ_COMPUTE_CARDINALITY_ADVISORIES_OID: u32 = 16992;
+pub const SOURCE_MZ_CANCELLATION_HISTORY_OID: u32 = 16993;
+pub const TABLE_MZ_SINK_LAG_OID: u32 = 16994;
+pub const SOURCE_MZ_WEBHOOK_SINK_DELIVERY_HISTORY_OID: u32 = 16995;
+pub const TABLE_MZ_DEPENDENCY_GRAPH_OID: u32 = 16996;
+pub const SOURCE_MZ_CATALOG_CHANGES_OID: u32 = 16997;
+pub const TABLE_MZ_REPLICA_UTILIZATION_OID: u32 = 16998;
+pub const TABLE_MZ_CLUSTER_SCHEDULING_DECISIONS_OID: u32 = 16999;
+pub const VIEW_MZ_OBJECT_HYDRATION_STATUSES_OID: u32 = 17000;
+pub const FUNC_MZ_IS_HYDRATED_OID: u32 = 17001;
+pub const TABLE_MZ_CURSORS_OID: u32 = 17002;
+pub const SOURCE_MZ_CLUSTER_REPLICA_AZ_ADVISORIES_OID: u32 = 17003;
+pub const SOURCE_MZ_SUBSCRIPTION_HISTORY_OID: u32 = 17004;
+pub const SOURCE_MZ_STORAGE_SHARD_CARDINALITY_OID: u32 = 17005;
+pub const SOURCE_MZ_PEEK_MIRROR_DISCREPANCIES_OID: u32 = 17006;
+pub const SOURCE_MZ_SYSTEM_PARAMETER_HISTORY_OID: u32 = 17007;
+pub const VIEW_MZ_CLUSTER_STATEMENT_SLOS_OID: u32 = 17008;
+pub const SOURCE_MZ_TABLE_CHANGE_AUDIT_OID: u32 = 17009;
+pub const TABLE_MZ_BUILTIN_TABLE_MIGRATIONS_OID: u32 = 17010;
+pub const SOURCE_MZ_DATAFLOW_SHUTDOWN_HISTORY_OID: u32 = 17011;
+pub const FUNC_MZ_STATEMENT_TRACE_OID: u32 = 17012;
+pub const VIEW_PG_PUBLICATION_OID: u32 = 17013;
+pub const VIEW_PG_PUBLICATION_TABLES_OID: u32 = 17014;
+pub const TABLE_MZ_STARVED_WRITE_LOCK_WAITERS_OID: u32 = 17015;
+pub const LOG_MZ_LIR_MAPPING_PER_WORKER_OID: u32 = 17016;
+pub const VIEW_MZ_LIR_MAPPING_OID: u32 = 17017;
@@ -26,6 +26,7 @@ use mz_pgwire_common::{
     input_err, parse_frame_len, Conn, Cursor, DecodeState, ErrorResponse, FrontendMessage, Pgbuf,
     MAX_REQUEST_SIZE,
 };
+use mz_repr::ScalarType;
 use tokio::io::{self, AsyncRead, AsyncWrite, Interest, Ready};
 use tokio::time::{self, Duration};
 use tokio_util::codec::{Decoder, Encoder, Framed};
@@ -126,17 +127,21 @@ where
 
     /// Injects state that affects how certain backend messages are encoded.
     ///
-    /// Specifically, the encoding of `BackendMessage::DataRow` depends upon the
-    /// types of the datums in the row. To avoid including the same type
+    /// Specifically, the encoding of `BackendMessage::DataRow` and
+    /// `BackendMessage::DataRowBytes` depends upon the types of the datums in
+    /// the row. To avoid including the same type
     /// information in each message, we use this side channel to install the
     /// type information in the codec before sending any data row messages. This
     /// violates the abstraction boundary a bit but results in much better
     /// performance.
-    pub fn set_encode_state(
-        &mut self,
-        encode_state: Vec<(mz_pgrepr::Type, mz_pgwire_common::Format)>,
-    ) {
-        self.inner.get_mut().codec_mut().encode_state = encode_state;
+    pub fn set_encode_state(&mut self, encode_state: Vec<(ScalarType, mz_pgwire_common::Format)>) {
+        self.inner.get_mut().codec_mut().encode_state = encode_state
+            .into_iter()
+            .map(|(scalar_type, format)| {
+                let ty = mz_pgrepr::Type::from(&scalar_type);
+                (scalar_type, ty, format)
+            })
+            .collect();
     }
 
     /// Waits for the connection to be closed.
@@ -196,7 +201,7 @@ where
 
 struct Codec {
     decode_state: DecodeState,
-    encode_state: Vec<(mz_pgrepr::Type, mz_pgwire_common::Format)>,
+    encode_state: Vec<(ScalarType, mz_pgrepr::Type, mz_pgwire_common::Format)>,
 }
 
 impl Codec {
@@ -224,7 +229,7 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::AuthenticationOk => b'R',
             BackendMessage::AuthenticationCleartextPassword => b'R',
             BackendMessage::RowDescription(_) => b'T',
-            BackendMessage::DataRow(_) => b'D',
+            BackendMessage::DataRow(_) | BackendMessage::DataRowBytes(_) => b'D',
             BackendMessage::CommandComplete { .. } => b'C',
             BackendMessage::EmptyQueryResponse => b'I',
             BackendMessage::ReadyForQuery(_) => b'Z',
@@ -245,6 +250,7 @@ impl Encoder<BackendMessage> for Codec {
             }
             BackendMessage::CopyInResponse { .. } => b'G',
             BackendMessage::CopyOutResponse { .. } => b'H',
+            BackendMessage::CopyBothResponse { .. } => b'W',
             BackendMessage::CopyData(_) => b'd',
             BackendMessage::CopyDone => b'c',
         };
@@ -263,6 +269,10 @@ impl Encoder<BackendMessage> for Codec {
             | BackendMessage::CopyOutResponse {
                 overall_format,
                 column_formats,
+            }
+            | BackendMessage::CopyBothResponse {
+                overall_format,
+                column_formats,
             } => {
                 dst.put_format_i8(overall_format);
                 dst.put_length_i16(column_formats.len())?;
@@ -295,22 +305,15 @@ impl Encoder<BackendMessage> for Codec {
             }
             BackendMessage::DataRow(fields) => {
                 dst.put_length_i16(fields.len())?;
-                for (f, (ty, format)) in fields.iter().zip(&self.encode_state) {
-                    if let Some(f) = f {
-                        let base = dst.len();
-                        dst.put_u32(0);
-                        f.encode(ty, *format, dst)?;
-                        let len = dst.len() - base - 4;
-                        let len = i32::try_from(len).map_err(|_| {
-                            io::Error::new(
-                                io::ErrorKind::Other,
-                                "length of encoded data row field does not fit into an i32",
-                            )
-                        })?;
-                        dst[base..base + 4].copy_from_slice(&len.to_be_bytes());
-                    } else {
-                        dst.put_i32(-1);
-                    }
+                for (f, (_, ty, format)) in fields.iter().zip(&self.encode_state) {
+                    encode_data_row_field(f.as_ref(), ty, *format, dst)?;
+                }
+            }
+            BackendMessage::DataRowBytes(row) => {
+                dst.put_length_i16(self.encode_state.len())?;
+                for (datum, (scalar_type, ty, format)) in row.iter().zip(&self.encode_state) {
+                    let f = mz_pgrepr::Value::from_datum(datum, scalar_type);
+                    encode_data_row_field(f.as_ref(), ty, *format, dst)?;
                 }
             }
             BackendMessage::CommandComplete { tag } => {
@@ -387,6 +390,31 @@ impl Encoder<BackendMessage> for Codec {
     }
 }
 
+/// Encodes a single field of a data row, where `None` represents `NULL`.
+fn encode_data_row_field(
+    f: Option<&mz_pgrepr::Value>,
+    ty: &mz_pgrepr::Type,
+    format: mz_pgwire_common::Format,
+    dst: &mut BytesMut,
+) -> Result<(), io::Error> {
+    if let Some(f) = f {
+        let base = dst.len();
+        dst.put_u32(0);
+        f.encode(ty, format, dst)?;
+        let len = dst.len() - base - 4;
+        let len = i32::try_from(len).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "length of encoded data row field does not fit into an i32",
+            )
+        })?;
+        dst[base..base + 4].copy_from_slice(&len.to_be_bytes());
+    } else {
+        dst.put_i32(-1);
+    }
+    Ok(())
+}
+
 impl Decoder for Codec {
     type Item = FrontendMessage;
     type Error = io::Error;
@@ -11,7 +11,7 @@ use enum_kinds::EnumKind;
 use itertools::Itertools;
 use mz_adapter::session::TransactionCode;
 use mz_pgwire_common::ErrorResponse;
-use mz_repr::{ColumnName, RelationDesc};
+use mz_repr::{ColumnName, RelationDesc, RowBytes};
 
 /// Internal representation of a backend [message]
 ///
@@ -28,6 +28,10 @@ pub enum BackendMessage {
     ReadyForQuery(TransactionCode),
     RowDescription(Vec<FieldDescription>),
     DataRow(Vec<Option<mz_pgrepr::Value>>),
+    /// Like [`BackendMessage::DataRow`], but with the row still encoded, so that it is only
+    /// converted into values as it is written to the connection. The row usually shares the
+    /// buffer of the peek response it was read from.
+    DataRowBytes(RowBytes),
     ParameterStatus(&'static str, String),
     BackendKeyData {
         conn_id: u32,
@@ -48,6 +52,10 @@ pub enum BackendMessage {
         overall_format: mz_pgwire_common::Format,
         column_formats: Vec<mz_pgwire_common::Format>,
     },
+    CopyBothResponse {
+        overall_format: mz_pgwire_common::Format,
+        column_formats: Vec<mz_pgwire_common::Format>,
+    },
     CopyData(Vec<u8>),
     CopyDone,
 }
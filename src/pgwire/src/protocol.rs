@@ -26,9 +26,11 @@ use mz_adapter::{
     verify_datum_desc, AdapterError, AdapterNotice, ExecuteContextExtra, ExecuteResponse,
     PeekResponseUnary, RowsFuture,
 };
+use mz_arrow_util::parquet::ParquetEncoder;
 use mz_frontegg_auth::Authenticator as FronteggAuthentication;
 use mz_ore::cast::CastFrom;
 use mz_ore::netio::AsyncReady;
+use mz_ore::now::SYSTEM_TIME;
 use mz_ore::str::StrExt;
 use mz_ore::{assert_none, assert_ok, instrument};
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
@@ -38,22 +40,34 @@ use mz_repr::{
 };
 use mz_server_core::TlsMode;
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::{CopyDirection, CopyStatement, FetchDirection, Ident, Raw, Statement};
+use mz_sql::ast::{
+    AsOf, CopyDirection, CopyStatement, Expr, FetchDirection, Ident, Raw, RawItemName, Statement,
+    SubscribeOption, SubscribeOptionName, SubscribeOutput, SubscribeRelation, SubscribeStatement,
+    WithOptionValue,
+};
+use mz_sql::catalog::{CatalogItem, CatalogItemType, SessionCatalog};
 use mz_sql::parse::StatementParseResult;
-use mz_sql::plan::{CopyFormat, ExecuteTimeout, StatementDesc};
+use mz_sql::plan::{CopyFormat, ExecuteTimeout, StatementDesc, CONSISTENCY_TOKEN_PARAMETER};
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::INTERNAL_USER_NAMES;
-use mz_sql::session::vars::{ConnectionCounter, DropConnection, Var, VarInput, MAX_COPY_FROM_SIZE};
+use mz_sql::session::vars::{
+    ConnectionCounter, DropConnection, Var, VarInput, ENABLE_LOGICAL_REPLICATION,
+    MAX_COPY_FROM_SIZE,
+};
 use postgres::error::SqlState;
 use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::select;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
 use tokio::time::{self};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::{debug, debug_span, warn, Instrument};
 
 use crate::codec::FramedConn;
 use crate::message::{self, BackendMessage};
+use crate::replication::{
+    split_publication_names, system_identifier, Lsn, PgOutputEncoder, ReplicatedRelation,
+    ReplicationCommand, OUTPUT_PLUGIN,
+};
 
 /// Reports whether the given stream begins with a pgwire handshake.
 ///
@@ -132,6 +146,33 @@ where
 
     let user = params.remove("user").unwrap_or_else(String::new);
 
+    // Connections in database replication mode accept the commands of the
+    // logical replication protocol, in addition to SQL.
+    let replication = match params.remove("replication") {
+        None => false,
+        Some(value) => match value.to_lowercase().as_str() {
+            "database" => true,
+            "false" | "off" | "no" | "0" => false,
+            "true" | "on" | "yes" | "1" => {
+                return conn
+                    .send(ErrorResponse::fatal(
+                        SqlState::FEATURE_NOT_SUPPORTED,
+                        "physical replication is not supported",
+                    ))
+                    .await;
+            }
+            _ => {
+                let msg = format!(
+                    "invalid value for parameter \"replication\": {}",
+                    value.quoted()
+                );
+                return conn
+                    .send(ErrorResponse::fatal(SqlState::INVALID_PARAMETER_VALUE, msg))
+                    .await;
+            }
+        },
+    };
+
     if internal {
         // The internal server can only be used to connect to the internal users.
         if !INTERNAL_USER_NAMES.contains(&user) {
@@ -283,6 +324,7 @@ where
         conn,
         adapter_client,
         txn_needs_commit: false,
+        replication,
     };
 
     select! {
@@ -403,6 +445,8 @@ struct StateMachine<'a, A> {
     conn: &'a mut FramedConn<A>,
     adapter_client: mz_adapter::SessionClient,
     txn_needs_commit: bool,
+    /// Whether the connection is in database replication mode.
+    replication: bool,
 }
 
 enum SendRowsEndedReason {
@@ -521,6 +565,7 @@ where
                         portal_exec_message,
                         None,
                         ExecuteTimeout::None,
+                        false,
                         None,
                     )
                     .instrument(execute_root_span)
@@ -631,6 +676,7 @@ where
                     portal_exec_message,
                     None,
                     ExecuteTimeout::None,
+                    false,
                     execute_started,
                 )
                 .await
@@ -675,6 +721,12 @@ where
     // From https://www.postgresql.org/docs/current/protocol-flow.html
     #[instrument(level = "debug")]
     async fn query(&mut self, sql: String) -> Result<State, io::Error> {
+        if self.replication {
+            if let Some(cmd) = ReplicationCommand::parse(&sql) {
+                return self.replication_query(cmd).await;
+            }
+        }
+
         // Parse first before doing any transaction checking.
         let stmts = match self.parse_sql(&sql) {
             Ok(stmts) => stmts,
@@ -724,6 +776,425 @@ where
         self.ready().await
     }
 
+    /// Executes a command of the logical replication protocol, which is sent
+    /// as a simple query on a connection in database replication mode.
+    #[instrument(level = "debug")]
+    async fn replication_query(
+        &mut self,
+        cmd: Result<ReplicationCommand, String>,
+    ) -> Result<State, io::Error> {
+        let catalog = self.adapter_client.catalog_snapshot().await;
+        if let Err(e) =
+            ENABLE_LOGICAL_REPLICATION.enabled(Some(catalog.system_config()), None, None)
+        {
+            let err: AdapterError = e.into();
+            self.error(err.into_response(Severity::Error)).await?;
+            return self.ready().await;
+        }
+        let cmd = match cmd {
+            Ok(cmd) => cmd,
+            Err(msg) => {
+                self.error(ErrorResponse::error(SqlState::SYNTAX_ERROR, msg))
+                    .await?;
+                return self.ready().await;
+            }
+        };
+        if self.is_aborted_txn() {
+            self.aborted_txn_error().await?;
+            return self.ready().await;
+        }
+        if let TransactionStatus::InTransaction(_) = self.adapter_client.session().transaction() {
+            self.error(ErrorResponse::error(
+                SqlState::ACTIVE_SQL_TRANSACTION,
+                "replication commands cannot be executed inside a transaction block",
+            ))
+            .await?;
+            return self.ready().await;
+        }
+        self.ensure_transaction(1).await?;
+
+        let state = match cmd {
+            ReplicationCommand::IdentifySystem => {
+                let desc = RelationDesc::empty()
+                    .with_column("systemid", ScalarType::String.nullable(false))
+                    .with_column("timeline", ScalarType::Int32.nullable(false))
+                    .with_column("xlogpos", ScalarType::String.nullable(false))
+                    .with_column("dbname", ScalarType::String.nullable(true));
+                // LSNs are timestamps, so the current LSN is the current time.
+                let row = vec![
+                    Some(mz_pgrepr::Value::Text(
+                        system_identifier(&catalog.config().environment_id).to_string(),
+                    )),
+                    Some(mz_pgrepr::Value::Int4(1)),
+                    Some(mz_pgrepr::Value::Text(Lsn(SYSTEM_TIME()).to_string())),
+                    Some(mz_pgrepr::Value::Text(
+                        self.adapter_client.session().vars().database().to_string(),
+                    )),
+                ];
+                self.send_replication_result(&desc, row, "IDENTIFY_SYSTEM")
+                    .await?;
+                State::Ready
+            }
+            ReplicationCommand::CreateReplicationSlot { slot } => {
+                // Slots are not persisted, so creating a slot only reports
+                // that streams start at the beginning of time, with a snapshot.
+                let desc = RelationDesc::empty()
+                    .with_column("slot_name", ScalarType::String.nullable(false))
+                    .with_column("consistent_point", ScalarType::String.nullable(false))
+                    .with_column("snapshot_name", ScalarType::String.nullable(true))
+                    .with_column("output_plugin", ScalarType::String.nullable(false));
+                let row = vec![
+                    Some(mz_pgrepr::Value::Text(slot)),
+                    Some(mz_pgrepr::Value::Text(Lsn(0).to_string())),
+                    None,
+                    Some(mz_pgrepr::Value::Text(OUTPUT_PLUGIN.into())),
+                ];
+                self.send_replication_result(&desc, row, "CREATE_REPLICATION_SLOT")
+                    .await?;
+                State::Ready
+            }
+            ReplicationCommand::DropReplicationSlot { slot: _ } => {
+                self.send(BackendMessage::CommandComplete {
+                    tag: "DROP_REPLICATION_SLOT".into(),
+                })
+                .await?;
+                State::Ready
+            }
+            ReplicationCommand::StartReplication {
+                slot: _,
+                start_lsn,
+                options,
+            } => self.start_replication(start_lsn, &options).await?,
+        };
+
+        match state {
+            State::Done => Ok(State::Done),
+            _ => {
+                if self.adapter_client.session().transaction().is_implicit() {
+                    self.commit_transaction().await?;
+                }
+                self.ready().await
+            }
+        }
+    }
+
+    /// Sends the single row that results from a replication command.
+    async fn send_replication_result(
+        &mut self,
+        desc: &RelationDesc,
+        row: Vec<Option<mz_pgrepr::Value>>,
+        tag: &str,
+    ) -> Result<(), io::Error> {
+        let formats = vec![Format::Text; desc.arity()];
+        self.send_all([
+            BackendMessage::RowDescription(message::encode_row_description(desc, &formats)),
+            BackendMessage::DataRow(row),
+            BackendMessage::CommandComplete { tag: tag.into() },
+        ])
+        .await
+    }
+
+    /// Streams the changes to the relation that the publication of a
+    /// `START_REPLICATION` command names, until the client ends the stream.
+    ///
+    /// The changes are read from a `SUBSCRIBE` to the relation. A stream that
+    /// starts at LSN 0 starts with a snapshot of the relation, and a stream
+    /// that resumes from a later LSN only contains the transactions after it.
+    async fn start_replication(
+        &mut self,
+        start_lsn: Lsn,
+        options: &BTreeMap<String, String>,
+    ) -> Result<State, io::Error> {
+        let publications = options
+            .get("publication_names")
+            .map(|names| split_publication_names(names))
+            .unwrap_or_default();
+        let [publication] = &publications[..] else {
+            return self
+                .error(ErrorResponse::error(
+                    SqlState::FEATURE_NOT_SUPPORTED,
+                    "START_REPLICATION requires exactly one publication",
+                ))
+                .await;
+        };
+        let mut stmt = match self.replication_subscribe(publication) {
+            Ok(stmt) => stmt,
+            Err(err) => return self.error(err).await,
+        };
+        let relation = match self.replicated_relation(&stmt).await {
+            Ok(relation) => relation,
+            Err(err) => return self.error(err).await,
+        };
+        stmt.options.push(SubscribeOption {
+            name: SubscribeOptionName::Progress,
+            value: None,
+        });
+        if start_lsn > Lsn(0) {
+            stmt.options.push(SubscribeOption {
+                name: SubscribeOptionName::Snapshot,
+                value: Some(WithOptionValue::Value(mz_sql::ast::Value::Boolean(false))),
+            });
+            stmt.as_of = Some(AsOf::At(Expr::Value(mz_sql::ast::Value::Number(
+                start_lsn.0.to_string(),
+            ))));
+        }
+        let stmt = Statement::Subscribe(stmt);
+        let sql = stmt.to_ast_string();
+
+        const EMPTY_PORTAL: &str = "";
+        if let Err(e) = self
+            .adapter_client
+            .declare(EMPTY_PORTAL.to_string(), stmt, sql)
+            .await
+        {
+            return self.error(e.into_response(Severity::Error)).await;
+        }
+        let result = match self
+            .adapter_client
+            .execute(EMPTY_PORTAL.to_string(), self.conn.wait_closed(), None)
+            .await
+        {
+            Ok((
+                ExecuteResponse::Subscribing {
+                    rx,
+                    ctx_extra,
+                    instance_id,
+                },
+                execute_started,
+            )) => {
+                self.send_pending_notices().await?;
+                let stream = RecordFirstRowStream::new(
+                    Box::new(rx),
+                    execute_started,
+                    &self.adapter_client,
+                    Some(instance_id),
+                    None,
+                );
+                let (result, statement_ended_execution_reason) =
+                    match self.replicate(relation, stream).await {
+                        Err(e) => (Err(e), StatementEndedExecutionReason::Canceled),
+                        Ok((ok, SendRowsEndedReason::Canceled)) => {
+                            (Ok(ok), StatementEndedExecutionReason::Canceled)
+                        }
+                        Ok((ok, SendRowsEndedReason::Success { rows_returned })) => (
+                            Ok(ok),
+                            StatementEndedExecutionReason::Success {
+                                rows_returned: Some(rows_returned),
+                                execution_strategy: None,
+                                max_result_memory_bytes: None,
+                            },
+                        ),
+                        Ok((ok, SendRowsEndedReason::Errored { error })) => {
+                            (Ok(ok), StatementEndedExecutionReason::Errored { error })
+                        }
+                    };
+                self.adapter_client
+                    .retire_execute(ctx_extra, statement_ended_execution_reason);
+                result
+            }
+            Ok((response, _)) => unreachable!("SUBSCRIBE returned {response:?}"),
+            Err(e) => {
+                self.send_pending_notices().await?;
+                self.error(e.into_response(Severity::Error)).await
+            }
+        };
+
+        self.adapter_client.session().remove_portal(EMPTY_PORTAL);
+
+        result
+    }
+
+    /// Returns the `SUBSCRIBE` to the relation that `publication` names.
+    fn replication_subscribe(
+        &self,
+        publication: &str,
+    ) -> Result<SubscribeStatement<Raw>, ErrorResponse> {
+        // The publication is parsed as the relation of a `SUBSCRIBE`, which
+        // must not smuggle in other parts of the statement.
+        let sql = format!("SUBSCRIBE TO {publication}");
+        let invalid = || {
+            ErrorResponse::error(
+                SqlState::INVALID_NAME,
+                format!("invalid publication name {}", publication.quoted()),
+            )
+        };
+        let mut stmts = self.parse_sql(&sql).map_err(|_| invalid())?;
+        match stmts.pop() {
+            Some(StatementParseResult {
+                ast: Statement::Subscribe(stmt),
+                ..
+            }) if stmts.is_empty()
+                && matches!(stmt.relation, SubscribeRelation::Name(RawItemName::Name(_)))
+                && stmt.into.is_none()
+                && stmt.options.is_empty()
+                && stmt.as_of.is_none()
+                && stmt.up_to.is_none()
+                && matches!(stmt.output, SubscribeOutput::Diffs) =>
+            {
+                Ok(stmt)
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Describes the relation that `stmt` subscribes to for the `Relation`
+    /// message of the stream.
+    async fn replicated_relation(
+        &mut self,
+        stmt: &SubscribeStatement<Raw>,
+    ) -> Result<ReplicatedRelation, ErrorResponse> {
+        let SubscribeRelation::Name(RawItemName::Name(name)) = &stmt.relation else {
+            unreachable!("checked by replication_subscribe");
+        };
+        let name = mz_sql::normalize::unresolved_item_name(name.clone()).map_err(|e| {
+            let err: AdapterError = e.into();
+            err.into_response(Severity::Error)
+        })?;
+        let catalog = self.adapter_client.catalog_snapshot().await;
+        let conn_catalog = catalog.for_session(self.adapter_client.session());
+        // Every table and materialized view is published under its own name.
+        let item = conn_catalog.resolve_item(&name).map_err(|_| {
+            ErrorResponse::error(
+                SqlState::UNDEFINED_OBJECT,
+                format!("publication {} does not exist", name.to_string().quoted()),
+            )
+        })?;
+        if !matches!(
+            item.item_type(),
+            CatalogItemType::Table | CatalogItemType::MaterializedView
+        ) {
+            return Err(ErrorResponse::error(
+                SqlState::WRONG_OBJECT_TYPE,
+                format!(
+                    "{} is a {}, but only tables and materialized views can be replicated",
+                    name,
+                    item.item_type()
+                ),
+            ));
+        }
+        let full_name = conn_catalog.resolve_full_name(item.name());
+        let desc = item
+            .desc(&full_name)
+            .map_err(|e| {
+                let err: AdapterError = e.into();
+                err.into_response(Severity::Error)
+            })?
+            .into_owned();
+        Ok(ReplicatedRelation {
+            oid: item.oid(),
+            namespace: full_name.schema,
+            name: full_name.item,
+            columns: desc
+                .iter()
+                .map(|(name, typ)| (name.clone(), typ.clone()))
+                .collect(),
+        })
+    }
+
+    /// Sends the rows of a `SUBSCRIBE ... WITH (PROGRESS)` as a logical
+    /// replication stream.
+    ///
+    /// Standby status updates from the client are ignored, as slots are not
+    /// persisted.
+    async fn replicate(
+        &mut self,
+        relation: ReplicatedRelation,
+        mut stream: RecordFirstRowStream,
+    ) -> Result<(State, SendRowsEndedReason), io::Error> {
+        self.send(BackendMessage::CopyBothResponse {
+            overall_format: Format::Binary,
+            column_formats: vec![],
+        })
+        .await?;
+        self.conn.flush().await?;
+
+        let mut encoder = PgOutputEncoder::new(relation);
+        let mut out = Vec::new();
+        let mut count = 0;
+        loop {
+            tokio::select! {
+                batch = stream.recv() => match batch {
+                    None => break,
+                    Some(PeekResponseUnary::Error(text)) => {
+                        return self
+                            .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
+                            .await
+                            .map(|state| (state, SendRowsEndedReason::Errored { error: text }));
+                    }
+                    Some(PeekResponseUnary::Canceled) => {
+                        return self.error(ErrorResponse::error(
+                                SqlState::QUERY_CANCELED,
+                                "canceling statement due to user request",
+                            ))
+                            .await.map(|state| (state, SendRowsEndedReason::Canceled));
+                    }
+                    Some(PeekResponseUnary::Rows(mut rows)) => {
+                        count += rows.count();
+                        while let Some(row) = rows.next() {
+                            if let Err(error) = encoder.push(row, SYSTEM_TIME(), &mut out) {
+                                return self
+                                    .error(ErrorResponse::error(
+                                        SqlState::INTERNAL_ERROR,
+                                        error.clone(),
+                                    ))
+                                    .await
+                                    .map(|state| (state, SendRowsEndedReason::Errored { error }));
+                            }
+                            for msg in out.drain(..) {
+                                self.send(BackendMessage::CopyData(msg)).await?;
+                            }
+                        }
+                    }
+                },
+                message = self.conn.recv() => match message? {
+                    // Standby status updates and hot standby feedback.
+                    Some(FrontendMessage::CopyData(_)) => (),
+                    Some(FrontendMessage::CopyDone) => break,
+                    Some(FrontendMessage::CopyFail(err)) => {
+                        return self
+                            .error(ErrorResponse::error(
+                                SqlState::QUERY_CANCELED,
+                                format!("COPY from stdin failed: {}", err),
+                            ))
+                            .await
+                            .map(|state| (state, SendRowsEndedReason::Canceled));
+                    }
+                    Some(FrontendMessage::Terminate) | None => {
+                        return Ok((State::Done, SendRowsEndedReason::Canceled));
+                    }
+                    Some(message) => {
+                        let msg = format!(
+                            "unexpected message type {} during logical replication",
+                            message.name()
+                        );
+                        return self
+                            .error(ErrorResponse::fatal(SqlState::PROTOCOL_VIOLATION, msg))
+                            .await
+                            .map(|state| (state, SendRowsEndedReason::Canceled));
+                    }
+                },
+                notice = self.adapter_client.session().recv_notice() => {
+                    self.send(notice.into_response())
+                        .await?;
+                }
+            }
+
+            self.conn.flush().await?;
+        }
+
+        self.send(BackendMessage::CopyDone).await?;
+        self.send(BackendMessage::CommandComplete {
+            tag: "START_STREAMING".into(),
+        })
+        .await?;
+        Ok((
+            State::Ready,
+            SendRowsEndedReason::Success {
+                rows_returned: u64::cast_from(count),
+            },
+        ))
+    }
+
     #[instrument(level = "debug")]
     async fn parse(
         &mut self,
@@ -811,11 +1282,15 @@ where
     async fn end_transaction(&mut self, action: EndTransactionAction) -> Result<(), io::Error> {
         self.txn_needs_commit = false;
         let resp = self.adapter_client.end_transaction(action).await;
-        if let Err(err) = resp {
-            self.send(BackendMessage::ErrorResponse(
-                err.into_response(Severity::Error),
-            ))
-            .await?;
+        match resp {
+            Err(err) => {
+                self.send(BackendMessage::ErrorResponse(
+                    err.into_response(Severity::Error),
+                ))
+                .await?;
+            }
+            // Implicit transactions commit their writes after the statements completed.
+            Ok(_) => self.send_consistency_token().await?,
         }
         Ok(())
     }
@@ -965,6 +1440,7 @@ where
         Ok(State::Ready)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn execute(
         &mut self,
         portal_name: String,
@@ -972,6 +1448,7 @@ where
         get_response: GetResponse,
         fetch_portal_name: Option<String>,
         timeout: ExecuteTimeout,
+        progress: bool,
         outer_ctx_extra: Option<ExecuteContextExtra>,
     ) -> BoxFuture<'_, Result<State, io::Error>> {
         async move {
@@ -1017,15 +1494,26 @@ where
                 PortalState::NotStarted => {
                     // Start a transaction if we aren't in one.
                     self.ensure_transaction(1).await?;
-                    match self
+                    // Statements that take a while report their progress while they execute.
+                    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+                    self.adapter_client
+                        .session()
+                        .set_progress_notice_transmitter(Some(progress_tx));
+                    let result = self
                         .adapter_client
                         .execute(
                             portal_name.clone(),
-                            self.conn.wait_closed(),
+                            wait_closed_sending_notices(self.conn, &mut progress_rx),
                             outer_ctx_extra,
                         )
-                        .await
-                    {
+                        .await;
+                    self.adapter_client
+                        .session()
+                        .set_progress_notice_transmitter(None);
+                    while let Ok(notice) = progress_rx.try_recv() {
+                        self.send(notice.into_response()).await?;
+                    }
+                    match result {
                         Ok((response, execute_started)) => {
                             self.send_pending_notices().await?;
                             self.send_execute_response(
@@ -1036,6 +1524,7 @@ where
                                 get_response,
                                 fetch_portal_name,
                                 timeout,
+                                progress,
                                 execute_started,
                             )
                             .await
@@ -1057,6 +1546,7 @@ where
                             get_response,
                             fetch_portal_name,
                             timeout,
+                            progress,
                         )
                         .await
                     {
@@ -1085,6 +1575,7 @@ where
                             StatementEndedExecutionReason::Success {
                                 rows_returned: None,
                                 execution_strategy: None,
+                                max_result_memory_bytes: None,
                             },
                         ),
                         Ok((ok, SendRowsEndedReason::Errored { error })) => {
@@ -1111,6 +1602,7 @@ where
                             StatementEndedExecutionReason::Success {
                                 rows_returned: None,
                                 execution_strategy: None,
+                                max_result_memory_bytes: None,
                             },
                         );
                     }
@@ -1217,6 +1709,7 @@ where
         portal.state = PortalState::Completed(None);
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn fetch(
         &mut self,
         name: String,
@@ -1224,6 +1717,7 @@ where
         max_rows: ExecuteCount,
         fetch_portal_name: Option<String>,
         timeout: ExecuteTimeout,
+        progress: bool,
         ctx_extra: ExecuteContextExtra,
     ) -> Result<State, io::Error> {
         // Unlike Execute, no count specified in FETCH returns 1 row, and 0 means 0
@@ -1283,6 +1777,7 @@ where
             fetch_message,
             fetch_portal_name,
             timeout,
+            progress,
             Some(ctx_extra),
         )
         .await
@@ -1378,12 +1873,14 @@ where
         get_response: GetResponse,
         fetch_portal_name: Option<String>,
         timeout: ExecuteTimeout,
+        progress: bool,
         execute_started: Instant,
     ) -> Result<State, io::Error> {
         let mut tag = response.tag();
 
         macro_rules! command_complete {
             () => {{
+                self.send_consistency_token().await?;
                 self.send(BackendMessage::CommandComplete {
                     tag: tag
                         .take()
@@ -1403,6 +1900,10 @@ where
                 self.complete_portal(&portal_name);
                 command_complete!()
             }
+            ExecuteResponse::ClaimedHandoff => {
+                self.complete_portal(&portal_name);
+                command_complete!()
+            }
             ExecuteResponse::EmptyQuery => {
                 self.send(BackendMessage::EmptyQueryResponse).await?;
                 Ok(State::Ready)
@@ -1411,6 +1912,7 @@ where
                 name,
                 count,
                 timeout,
+                progress,
                 ctx_extra,
             } => {
                 self.fetch(
@@ -1419,6 +1921,7 @@ where
                     max_rows,
                     Some(portal_name.to_string()),
                     timeout,
+                    progress,
                     ctx_extra,
                 )
                 .await
@@ -1448,6 +1951,36 @@ where
                     get_response,
                     fetch_portal_name,
                     timeout,
+                    progress,
+                )
+                .instrument(span)
+                .await
+                .map(|(state, _)| state)
+            }
+            ExecuteResponse::SendingRowsStreaming {
+                rows,
+                instance_id,
+                strategy,
+            } => {
+                let row_desc = row_desc
+                    .expect("missing row description for ExecuteResponse::SendingRowsStreaming");
+
+                let span = tracing::debug_span!("sending_rows_streaming");
+                self.send_rows(
+                    row_desc,
+                    portal_name,
+                    InProgressRows::new(RecordFirstRowStream::new(
+                        rows,
+                        execute_started,
+                        &self.adapter_client,
+                        Some(instance_id),
+                        Some(strategy),
+                    )),
+                    max_rows,
+                    get_response,
+                    fetch_portal_name,
+                    timeout,
+                    progress,
                 )
                 .instrument(span)
                 .await
@@ -1475,6 +2008,7 @@ where
                     get_response,
                     fetch_portal_name,
                     timeout,
+                    progress,
                 )
                 .instrument(span)
                 .await
@@ -1521,12 +2055,15 @@ where
                 }
                 let row_desc =
                     row_desc.expect("missing row description for ExecuteResponse::Subscribing");
+                if fetch_portal_name.is_some() {
+                    rx.set_cursor(&portal_name);
+                }
                 let (result, statement_ended_execution_reason) = match self
                     .send_rows(
                         row_desc,
                         portal_name,
                         InProgressRows::new(RecordFirstRowStream::new(
-                            Box::new(UnboundedReceiverStream::new(rx)),
+                            Box::new(rx),
                             execute_started,
                             &self.adapter_client,
                             Some(instance_id),
@@ -1536,6 +2073,7 @@ where
                         get_response,
                         fetch_portal_name,
                         timeout,
+                        progress,
                     )
                     .await
                 {
@@ -1552,6 +2090,7 @@ where
                         StatementEndedExecutionReason::Success {
                             rows_returned: Some(rows_returned),
                             execution_strategy: None,
+                            max_result_memory_bytes: None,
                         },
                     ),
                     Ok((ok, SendRowsEndedReason::Errored { error })) => {
@@ -1576,7 +2115,7 @@ where
                                 format,
                                 row_desc,
                                 RecordFirstRowStream::new(
-                                    Box::new(UnboundedReceiverStream::new(rx)),
+                                    Box::new(rx),
                                     execute_started,
                                     &self.adapter_client,
                                     Some(instance_id),
@@ -1595,6 +2134,7 @@ where
                                 StatementEndedExecutionReason::Success {
                                     rows_returned: Some(rows_returned),
                                     execution_strategy: None,
+                                    max_result_memory_bytes: None,
                                 },
                             ),
                             Ok((state, SendRowsEndedReason::Errored { error })) => {
@@ -1722,6 +2262,7 @@ where
             | ExecuteResponse::CreatedType
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
+            | ExecuteResponse::CanceledQuery
             | ExecuteResponse::Comment
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::Deleted(..)
@@ -1761,6 +2302,7 @@ where
         get_response: GetResponse,
         fetch_portal_name: Option<String>,
         timeout: ExecuteTimeout,
+        progress: bool,
     ) -> Result<(State, SendRowsEndedReason), io::Error> {
         // If this portal is being executed from a FETCH then we need to use the result
         // format type of the outer portal.
@@ -1785,13 +2327,21 @@ where
             ),
             ExecuteTimeout::WaitOnce => (true, None),
         };
+        // If requested, stop once a progress message of a `SUBSCRIBE` has been sent.
+        let progress_col = if progress {
+            row_desc
+                .get_by_name(&"mz_progressed".into())
+                .map(|(idx, _)| idx)
+        } else {
+            None
+        };
 
         self.conn.set_encode_state(
             row_desc
                 .typ()
                 .column_types
                 .iter()
-                .map(|ty| mz_pgrepr::Type::from(&ty.scalar_type))
+                .map(|ty| ty.scalar_type.clone())
                 .zip(result_formats)
                 .collect(),
         );
@@ -1849,10 +2399,16 @@ where
 
                     // Send a portion of the rows.
                     let mut sent_rows = 0;
-                    let messages = (&mut batch_rows)
+                    let mut sent_progress = false;
+                    // The rows are handed to the codec still encoded, and usually still in the
+                    // buffer of the peek response, so that they are converted into values only
+                    // as they are written to the connection.
+                    let messages = iter::from_fn(|| batch_rows.next_bytes())
                         .map(|row| {
-                            let values = mz_pgrepr::values_from_row(row, row_desc.typ());
-                            BackendMessage::DataRow(values)
+                            if let Some(idx) = progress_col {
+                                sent_progress |= row.iter().nth(idx) == Some(Datum::True);
+                            }
+                            BackendMessage::DataRowBytes(row)
                         })
                         .inspect(|_| sent_rows += 1)
                         .take(want_rows);
@@ -1861,9 +2417,9 @@ where
                     total_sent_rows += sent_rows;
                     want_rows -= sent_rows;
 
-                    // If we have sent the number of requested rows, put the remainder of the batch
-                    // (if any) back and stop sending.
-                    if want_rows == 0 {
+                    // If we have sent the number of requested rows or a requested progress
+                    // message, put the remainder of the batch (if any) back and stop sending.
+                    if want_rows == 0 || sent_progress {
                         if batch_rows.peek().is_some() {
                             rows.current = Some(batch_rows);
                         }
@@ -1911,6 +2467,8 @@ where
                 .expect("valid fetch portal")
         });
         let response_message = get_response(max_rows, total_sent_rows, fetch_portal);
+        // Writes that return rows, like `INSERT ... RETURNING`, report their token too.
+        self.send_consistency_token().await?;
         self.send(response_message).await?;
         Ok((
             State::Ready,
@@ -1937,13 +2495,7 @@ where
                 CopyFormatParams::Csv(CopyCsvFormatParams::default()),
                 Format::Text,
             ),
-            CopyFormat::Parquet => {
-                let text = "Parquet format is not supported".to_string();
-                return self
-                    .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
-                    .await
-                    .map(|state| (state, SendRowsEndedReason::Errored { error: text }));
-            }
+            CopyFormat::Parquet => return self.copy_rows_parquet(row_desc, stream).await,
         };
 
         let encode_fn = |row: &RowRef, typ: &RelationType, out: &mut Vec<u8>| {
@@ -2031,6 +2583,101 @@ where
         ))
     }
 
+    /// Like [`StateMachine::copy_rows`], but sends the rows as a single Parquet file, which is
+    /// encoded incrementally as the rows arrive.
+    async fn copy_rows_parquet(
+        &mut self,
+        row_desc: RelationDesc,
+        mut stream: RecordFirstRowStream,
+    ) -> Result<(State, SendRowsEndedReason), io::Error> {
+        let mut encoder = match ParquetEncoder::new(row_desc.clone()) {
+            Ok(encoder) => encoder,
+            Err(e) => {
+                let text = e.to_string();
+                return self
+                    .error(ErrorResponse::error(
+                        SqlState::FEATURE_NOT_SUPPORTED,
+                        text.clone(),
+                    ))
+                    .await
+                    .map(|state| (state, SendRowsEndedReason::Errored { error: text }));
+            }
+        };
+
+        let column_formats = iter::repeat(Format::Binary)
+            .take(row_desc.typ().column_types.len())
+            .collect();
+        self.send(BackendMessage::CopyOutResponse {
+            overall_format: Format::Binary,
+            column_formats,
+        })
+        .await?;
+
+        let mut count = 0;
+        loop {
+            let encoded = tokio::select! {
+                e = self.conn.wait_closed() => return Err(e),
+                batch = stream.recv() => match batch {
+                    None => break,
+                    Some(PeekResponseUnary::Error(text)) => {
+                        return self
+                            .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
+                        .await
+                        .map(|state| (state, SendRowsEndedReason::Errored { error: text }));
+                    }
+                    Some(PeekResponseUnary::Canceled) => {
+                        return self.error(ErrorResponse::error(
+                                SqlState::QUERY_CANCELED,
+                                "canceling statement due to user request",
+                            ))
+                            .await.map(|state| (state, SendRowsEndedReason::Canceled));
+                    }
+                    Some(PeekResponseUnary::Rows(mut rows)) => {
+                        count += rows.count();
+                        encoder.encode(&mut *rows)
+                    }
+                },
+                notice = self.adapter_client.session().recv_notice() => {
+                    self.send(notice.into_response())
+                        .await?;
+                    Ok(Vec::new())
+                }
+            };
+            match encoded {
+                Ok(bytes) if bytes.is_empty() => {}
+                Ok(bytes) => self.send(BackendMessage::CopyData(bytes)).await?,
+                Err(e) => return self.copy_parquet_error(e).await,
+            }
+
+            self.conn.flush().await?;
+        }
+        match encoder.finish() {
+            Ok(bytes) => self.send(BackendMessage::CopyData(bytes)).await?,
+            Err(e) => return self.copy_parquet_error(e).await,
+        }
+
+        let tag = format!("COPY {}", count);
+        self.send(BackendMessage::CopyDone).await?;
+        self.send(BackendMessage::CommandComplete { tag }).await?;
+        Ok((
+            State::Ready,
+            SendRowsEndedReason::Success {
+                rows_returned: u64::cast_from(count),
+            },
+        ))
+    }
+
+    /// Reports that encoding rows as Parquet failed with `e`.
+    async fn copy_parquet_error(
+        &mut self,
+        e: anyhow::Error,
+    ) -> Result<(State, SendRowsEndedReason), io::Error> {
+        let text = format!("failed to encode rows as Parquet: {e}");
+        self.error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text.clone()))
+            .await
+            .map(|state| (state, SendRowsEndedReason::Errored { error: text }))
+    }
+
     /// Handles the copy-in mode of the postgres protocol from transferring
     /// data to the server.
     #[instrument(level = "debug")]
@@ -2174,7 +2821,7 @@ where
 
         if let Err(e) = self
             .adapter_client
-            .insert_rows(id, columns, rows, std::mem::take(ctx_extra))
+            .insert_rows(id, columns, rows, data.len(), std::mem::take(ctx_extra))
             .await
         {
             self.adapter_client.retire_execute(
@@ -2187,11 +2834,25 @@ where
         }
 
         let tag = format!("COPY {}", count);
+        self.send_consistency_token().await?;
         self.send(BackendMessage::CommandComplete { tag }).await?;
 
         Ok(State::Ready)
     }
 
+    /// Sends the consistency token of the latest write of the session to the client, unless it
+    /// was sent already.
+    async fn send_consistency_token(&mut self) -> Result<(), io::Error> {
+        if let Some(token) = self.adapter_client.session().take_consistency_token() {
+            self.send(BackendMessage::ParameterStatus(
+                CONSISTENCY_TOKEN_PARAMETER,
+                token.to_string(),
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
     #[instrument(level = "debug")]
     async fn send_pending_notices(&mut self) -> Result<(), io::Error> {
         let notices = self
@@ -2326,6 +2987,30 @@ enum ExecuteCount {
     Count(usize),
 }
 
+/// Waits for `conn` to close, sending it the notices received on `notices` in the meantime.
+async fn wait_closed_sending_notices<A>(
+    conn: &mut FramedConn<A>,
+    notices: &mut UnboundedReceiver<AdapterNotice>,
+) -> io::Error
+where
+    A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin,
+{
+    loop {
+        select! {
+            err = conn.wait_closed() => return err,
+            Some(notice) = notices.recv() => {
+                let sent = match conn.send(notice.into_response()).await {
+                    Ok(()) => conn.flush().await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = sent {
+                    return err;
+                }
+            }
+        }
+    }
+}
+
 // See postgres' backend/tcop/postgres.c IsTransactionExitStmt.
 fn is_txn_exit_stmt(stmt: Option<&Statement<Raw>>) -> bool {
     match stmt {
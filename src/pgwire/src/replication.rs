@@ -0,0 +1,669 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logical replication, with Materialize as the publisher.
+//!
+//! Clients that connect with the `replication=database` startup parameter can
+//! stream the changes to a relation with the `pgoutput` protocol of PostgreSQL
+//! logical replication, which lets off-the-shelf consumers subscribe to
+//! Materialize. The changes are read from a `SUBSCRIBE` to the relation, and
+//! each timestamp at which the relation changes is replicated as a transaction
+//! whose log sequence number (LSN) is the timestamp.
+//!
+//! Replication slots are not persisted: a slot only names a stream. A stream
+//! starts with a snapshot of the relation, unless it resumes from an LSN that
+//! the consumer has already received. Materialize has no `CREATE PUBLICATION`:
+//! every table and materialized view is published under its own name, as
+//! `pg_publication` and `pg_publication_tables` report, and each stream reads
+//! exactly one publication.
+//!
+//! # Useful references
+//!
+//!   * [Streaming Replication Protocol](https://www.postgresql.org/docs/current/protocol-replication.html)
+//!   * [Logical Replication Message Formats](https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html)
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bytes::{BufMut, BytesMut};
+use mz_repr::adt::numeric;
+use mz_repr::{ColumnName, ColumnType, Datum, RowRef};
+use mz_sql::catalog::EnvironmentId;
+
+/// The output plugin whose protocol is served.
+pub const OUTPUT_PLUGIN: &str = "pgoutput";
+
+/// The number of milliseconds from the Unix epoch to the PostgreSQL epoch,
+/// 2000-01-01.
+const PG_EPOCH_OFFSET_MILLIS: i64 = 946_684_800_000;
+
+/// A log sequence number, which is a Materialize timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Lsn(pub u64);
+
+impl Lsn {
+    /// Parses an LSN in the `X/Y` notation of PostgreSQL.
+    pub fn parse(s: &str) -> Option<Lsn> {
+        let (hi, lo) = s.split_once('/')?;
+        let hi = u32::from_str_radix(hi, 16).ok()?;
+        let lo = u32::from_str_radix(lo, 16).ok()?;
+        Some(Lsn((u64::from(hi) << 32) | u64::from(lo)))
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & u64::from(u32::MAX))
+    }
+}
+
+/// Returns the system identifier that `IDENTIFY_SYSTEM` reports for the
+/// environment `environment_id`.
+///
+/// PostgreSQL reports the 64-bit identifier of its database cluster, which
+/// consumers parse as a number, so it is derived from the organization ID and
+/// the ordinal of the environment.
+pub fn system_identifier(environment_id: &EnvironmentId) -> u64 {
+    let (high, low) = environment_id.organization_id().as_u64_pair();
+    (high ^ low).wrapping_add(environment_id.ordinal())
+}
+
+/// Converts milliseconds since the Unix epoch to the microseconds since the
+/// PostgreSQL epoch that replication messages carry.
+pub fn pg_timestamp(millis: u64) -> i64 {
+    i64::try_from(millis)
+        .unwrap_or(i64::MAX)
+        .saturating_sub(PG_EPOCH_OFFSET_MILLIS)
+        .saturating_mul(1_000)
+}
+
+/// A command of the replication protocol.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReplicationCommand {
+    /// `IDENTIFY_SYSTEM`
+    IdentifySystem,
+    /// `CREATE_REPLICATION_SLOT slot [TEMPORARY] LOGICAL plugin [...]`
+    CreateReplicationSlot { slot: String },
+    /// `DROP_REPLICATION_SLOT slot [WAIT]`
+    DropReplicationSlot { slot: String },
+    /// `START_REPLICATION [SLOT slot] LOGICAL lsn [(option 'value', ...)]`
+    StartReplication {
+        slot: Option<String>,
+        start_lsn: Lsn,
+        options: BTreeMap<String, String>,
+    },
+}
+
+impl ReplicationCommand {
+    /// Parses `sql` as a replication command.
+    ///
+    /// Returns `None` if `sql` is not a replication command, in which case it
+    /// is executed as SQL, like PostgreSQL does in database replication mode.
+    pub fn parse(sql: &str) -> Option<Result<ReplicationCommand, String>> {
+        let command = sql
+            .trim_start()
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .next()?
+            .to_ascii_uppercase();
+        if ![
+            "IDENTIFY_SYSTEM",
+            "CREATE_REPLICATION_SLOT",
+            "DROP_REPLICATION_SLOT",
+            "START_REPLICATION",
+        ]
+        .contains(&command.as_str())
+        {
+            return None;
+        }
+        Some(tokenize(sql).and_then(|tokens| {
+            let mut parser = CommandParser { tokens, pos: 1 };
+            let cmd = parser.parse(&command)?;
+            parser.expect_end()?;
+            Ok(cmd)
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// An unquoted word, like a keyword, an identifier or an LSN.
+    Word(String),
+    /// A double-quoted identifier.
+    QuotedIdent(String),
+    /// A single-quoted string.
+    String(String),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            // A trailing semicolon ends the command.
+            ';' => {
+                if chars.any(|c| !c.is_whitespace()) {
+                    return Err("replication commands cannot be combined".into());
+                }
+            }
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            '\'' | '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        // A doubled quote is an escaped quote.
+                        Some(q) if q == c && chars.peek() == Some(&c) => {
+                            chars.next();
+                            s.push(c);
+                        }
+                        Some(q) if q == c => break,
+                        Some(q) => s.push(q),
+                        None => return Err("unterminated quoted string".into()),
+                    }
+                }
+                tokens.push(if c == '\'' {
+                    Token::String(s)
+                } else {
+                    Token::QuotedIdent(s)
+                });
+            }
+            c => {
+                let mut s = String::from(c);
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || ['(', ')', ',', ';', '\'', '"'].contains(c) {
+                        break;
+                    }
+                    s.push(*c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(s));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct CommandParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl CommandParser {
+    fn parse(&mut self, command: &str) -> Result<ReplicationCommand, String> {
+        match command {
+            "IDENTIFY_SYSTEM" => Ok(ReplicationCommand::IdentifySystem),
+            "CREATE_REPLICATION_SLOT" => {
+                let slot = self.parse_name()?;
+                self.parse_keyword("TEMPORARY");
+                if self.parse_keyword("PHYSICAL") {
+                    return Err("physical replication is not supported".into());
+                }
+                self.expect_keyword("LOGICAL")?;
+                let plugin = self.parse_name()?;
+                if plugin != OUTPUT_PLUGIN {
+                    return Err(format!("output plugin \"{plugin}\" is not supported"));
+                }
+                // Snapshots are never exported, so the options that control
+                // them don't matter.
+                self.pos = self.tokens.len();
+                Ok(ReplicationCommand::CreateReplicationSlot { slot })
+            }
+            "DROP_REPLICATION_SLOT" => {
+                let slot = self.parse_name()?;
+                self.parse_keyword("WAIT");
+                Ok(ReplicationCommand::DropReplicationSlot { slot })
+            }
+            "START_REPLICATION" => {
+                let slot = if self.parse_keyword("SLOT") {
+                    Some(self.parse_name()?)
+                } else {
+                    None
+                };
+                if self.parse_keyword("PHYSICAL") {
+                    return Err("physical replication is not supported".into());
+                }
+                self.expect_keyword("LOGICAL")?;
+                let start_lsn = match self.next() {
+                    Some(Token::Word(lsn)) => {
+                        Lsn::parse(&lsn).ok_or_else(|| format!("invalid LSN \"{lsn}\""))?
+                    }
+                    _ => return Err("expected LSN".into()),
+                };
+                let mut options = BTreeMap::new();
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    loop {
+                        let name = self.parse_name()?;
+                        let value = match self.peek() {
+                            Some(Token::String(_)) => match self.next() {
+                                Some(Token::String(value)) => value,
+                                _ => unreachable!(),
+                            },
+                            _ => String::new(),
+                        };
+                        options.insert(name, value);
+                        match self.next() {
+                            Some(Token::Comma) => (),
+                            Some(Token::RParen) => break,
+                            _ => return Err("expected , or )".into()),
+                        }
+                    }
+                }
+                Ok(ReplicationCommand::StartReplication {
+                    slot,
+                    start_lsn,
+                    options,
+                })
+            }
+            _ => unreachable!("not a replication command"),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        if self.parse_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(format!("expected {keyword}"))
+        }
+    }
+
+    /// Parses an identifier, which is folded to lowercase unless it's quoted.
+    fn parse_name(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word.to_lowercase()),
+            Some(Token::QuotedIdent(ident)) => Ok(ident),
+            _ => Err("expected name".into()),
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), String> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err("unexpected trailing input in replication command".into()),
+        }
+    }
+}
+
+/// Splits the value of the `publication_names` option into the publication
+/// names, which are comma-separated and may be double-quoted.
+pub fn split_publication_names(value: &str) -> Vec<String> {
+    let mut names = vec![];
+    let mut name = String::new();
+    let mut quoted = false;
+    for c in value.chars() {
+        match c {
+            '"' => {
+                name.push(c);
+                quoted = !quoted;
+            }
+            ',' if !quoted => names.push(std::mem::take(&mut name).trim().to_string()),
+            c => name.push(c),
+        }
+    }
+    names.push(name.trim().to_string());
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+/// A relation that is replicated.
+#[derive(Debug, Clone)]
+pub struct ReplicatedRelation {
+    pub oid: u32,
+    pub namespace: String,
+    pub name: String,
+    pub columns: Vec<(ColumnName, ColumnType)>,
+}
+
+/// Encodes the rows of a `SUBSCRIBE ... WITH (PROGRESS)` to a replicated
+/// relation as `pgoutput` messages, each wrapped in an `XLogData` message.
+///
+/// The updates at each timestamp are replicated as a transaction, which is
+/// committed once the timestamp is complete.
+#[derive(Debug)]
+pub struct PgOutputEncoder {
+    relation: ReplicatedRelation,
+    /// Whether the `Relation` message that describes the relation was sent.
+    sent_relation: bool,
+    /// The timestamp of the open transaction, if any.
+    open: Option<u64>,
+    /// The ID of the most recent transaction.
+    xid: u32,
+}
+
+impl PgOutputEncoder {
+    pub fn new(relation: ReplicatedRelation) -> PgOutputEncoder {
+        PgOutputEncoder {
+            relation,
+            sent_relation: false,
+            open: None,
+            xid: 0,
+        }
+    }
+
+    /// Encodes a row of the subscribe, which is sent at `now` in milliseconds
+    /// since the Unix epoch, and appends the messages to send to `out`.
+    ///
+    /// Progress rows commit the open transaction if its timestamp is complete,
+    /// and are sent as keepalive messages.
+    pub fn push(&mut self, row: &RowRef, now: u64, out: &mut Vec<Vec<u8>>) -> Result<(), String> {
+        let mut datums = row.iter();
+        let ts = match datums.next() {
+            Some(Datum::Numeric(ts)) => numeric::cx_datum()
+                .try_into_u64(ts.0)
+                .map_err(|_| format!("invalid timestamp {ts}"))?,
+            _ => return Err("expected a timestamp".into()),
+        };
+        let progressed = datums.next() == Some(Datum::True);
+        let diff = datums.next();
+        if progressed {
+            if let Some(open) = self.open {
+                if open < ts {
+                    self.commit(now, out);
+                }
+            }
+            let wal_end = Lsn(ts.saturating_sub(1));
+            out.push(keepalive(wal_end, now));
+            return Ok(());
+        }
+        let diff = match diff {
+            Some(Datum::Int64(diff)) => diff,
+            _ => return Err("expected a diff".into()),
+        };
+        if self.open != Some(ts) {
+            if self.open.is_some() {
+                self.commit(now, out);
+            }
+            self.begin(ts, now, out);
+        }
+        let values: Vec<_> = datums.collect();
+        let mut msg = BytesMut::new();
+        if diff > 0 {
+            msg.put_u8(b'I');
+            msg.put_u32(self.relation.oid);
+            msg.put_u8(b'N');
+        } else {
+            // The old tuple of a delete contains all columns, like for a
+            // relation with `REPLICA IDENTITY FULL`.
+            msg.put_u8(b'D');
+            msg.put_u32(self.relation.oid);
+            msg.put_u8(b'O');
+        }
+        self.encode_tuple(&values, &mut msg)?;
+        for _ in 0..diff.unsigned_abs() {
+            out.push(xlog_data(Lsn(ts), now, &msg));
+        }
+        Ok(())
+    }
+
+    fn begin(&mut self, ts: u64, now: u64, out: &mut Vec<Vec<u8>>) {
+        self.xid = self.xid.wrapping_add(1);
+        let mut msg = BytesMut::new();
+        msg.put_u8(b'B');
+        msg.put_u64(ts);
+        msg.put_i64(pg_timestamp(ts));
+        msg.put_u32(self.xid);
+        out.push(xlog_data(Lsn(ts), now, &msg));
+        if !self.sent_relation {
+            out.push(xlog_data(Lsn(ts), now, &self.encode_relation()));
+            self.sent_relation = true;
+        }
+        self.open = Some(ts);
+    }
+
+    fn commit(&mut self, now: u64, out: &mut Vec<Vec<u8>>) {
+        let Some(ts) = self.open.take() else {
+            return;
+        };
+        let mut msg = BytesMut::new();
+        msg.put_u8(b'C');
+        msg.put_u8(0);
+        msg.put_u64(ts);
+        msg.put_u64(ts);
+        msg.put_i64(pg_timestamp(ts));
+        out.push(xlog_data(Lsn(ts), now, &msg));
+    }
+
+    fn encode_relation(&self) -> BytesMut {
+        let mut msg = BytesMut::new();
+        msg.put_u8(b'R');
+        msg.put_u32(self.relation.oid);
+        put_cstr(&mut msg, &self.relation.namespace);
+        put_cstr(&mut msg, &self.relation.name);
+        // All columns identify a row, like for `REPLICA IDENTITY FULL`.
+        msg.put_u8(b'f');
+        msg.put_u16(u16::try_from(self.relation.columns.len()).unwrap_or(u16::MAX));
+        for (name, typ) in &self.relation.columns {
+            let pg_type = mz_pgrepr::Type::from(&typ.scalar_type);
+            msg.put_u8(1);
+            put_cstr(&mut msg, name.as_str());
+            msg.put_u32(pg_type.oid());
+            msg.put_i32(pg_type.typmod());
+        }
+        msg
+    }
+
+    /// Encodes the values of a row as `TupleData` in text format.
+    fn encode_tuple(&self, values: &[Datum], msg: &mut BytesMut) -> Result<(), String> {
+        if values.len() != self.relation.columns.len() {
+            return Err(format!(
+                "expected {} columns, got {}",
+                self.relation.columns.len(),
+                values.len()
+            ));
+        }
+        msg.put_u16(u16::try_from(values.len()).unwrap_or(u16::MAX));
+        let mut buf = BytesMut::new();
+        for (datum, (_, typ)) in values.iter().zip(&self.relation.columns) {
+            match mz_pgrepr::Value::from_datum(*datum, &typ.scalar_type) {
+                None => msg.put_u8(b'n'),
+                Some(value) => {
+                    buf.clear();
+                    value.encode_text(&mut buf);
+                    msg.put_u8(b't');
+                    msg.put_u32(u32::try_from(buf.len()).map_err(|_| "value too large")?);
+                    msg.put_slice(&buf);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a message of the output plugin in an `XLogData` message.
+fn xlog_data(lsn: Lsn, now: u64, msg: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(25 + msg.len());
+    out.put_u8(b'w');
+    out.put_u64(lsn.0);
+    out.put_u64(lsn.0);
+    out.put_i64(pg_timestamp(now));
+    out.put_slice(msg);
+    out
+}
+
+/// Returns a primary keepalive message, which reports that all transactions up
+/// to `wal_end` have been sent.
+fn keepalive(wal_end: Lsn, now: u64) -> Vec<u8> {
+    let mut out = Vec::with_capacity(18);
+    out.put_u8(b'k');
+    out.put_u64(wal_end.0);
+    out.put_i64(pg_timestamp(now));
+    out.put_u8(0);
+    out
+}
+
+fn put_cstr(buf: &mut BytesMut, s: &str) {
+    buf.put_slice(s.as_bytes());
+    buf.put_u8(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::adt::numeric::Numeric;
+    use mz_repr::{Row, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_command() {
+        assert_eq!(ReplicationCommand::parse("SELECT 1"), None);
+        assert_eq!(
+            ReplicationCommand::parse("IDENTIFY_SYSTEM;"),
+            Some(Ok(ReplicationCommand::IdentifySystem))
+        );
+        assert_eq!(
+            ReplicationCommand::parse(
+                r#"CREATE_REPLICATION_SLOT "Slot" TEMPORARY LOGICAL pgoutput (SNAPSHOT 'nothing')"#
+            ),
+            Some(Ok(ReplicationCommand::CreateReplicationSlot {
+                slot: "Slot".into()
+            }))
+        );
+        assert_eq!(
+            ReplicationCommand::parse("create_replication_slot s LOGICAL test_decoding"),
+            Some(Err(
+                "output plugin \"test_decoding\" is not supported".into()
+            ))
+        );
+        assert_eq!(
+            ReplicationCommand::parse(
+                r#"START_REPLICATION SLOT s LOGICAL 1/A0 (proto_version '1', publication_names '"t"')"#
+            ),
+            Some(Ok(ReplicationCommand::StartReplication {
+                slot: Some("s".into()),
+                start_lsn: Lsn((1 << 32) | 0xA0),
+                options: [
+                    ("proto_version".into(), "1".into()),
+                    ("publication_names".into(), "\"t\"".into()),
+                ]
+                .into(),
+            }))
+        );
+        assert_eq!(
+            ReplicationCommand::parse("START_REPLICATION SLOT s PHYSICAL 0/0"),
+            Some(Err("physical replication is not supported".into()))
+        );
+        assert_eq!(
+            ReplicationCommand::parse("DROP_REPLICATION_SLOT s WAIT x"),
+            Some(Err(
+                "unexpected trailing input in replication command".into()
+            ))
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_lsn() {
+        assert_eq!(Lsn::parse("16/B374D848"), Some(Lsn(0x16_B374_D848)));
+        assert_eq!(Lsn(0x16_B374_D848).to_string(), "16/B374D848");
+        assert_eq!(Lsn::parse("0/0"), Some(Lsn(0)));
+        assert_eq!(Lsn::parse("16B374D848"), None);
+    }
+
+    #[mz_ore::test]
+    fn test_split_publication_names() {
+        assert_eq!(
+            split_publication_names(r#"a, "B,c",d"#),
+            vec!["a", r#""B,c""#, "d"]
+        );
+        assert_eq!(split_publication_names(""), Vec::<String>::new());
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decContextDefault` on OS `linux`
+    fn test_pgoutput_encoder() {
+        let mut encoder = PgOutputEncoder::new(ReplicatedRelation {
+            oid: 20_000,
+            namespace: "public".into(),
+            name: "t".into(),
+            columns: vec![("a".into(), ScalarType::Int32.nullable(true))],
+        });
+        let row = |ts: u64, progressed: bool, diff: Datum, a: Datum| {
+            Row::pack_slice(&[
+                Datum::from(Numeric::from(ts)),
+                Datum::from(progressed),
+                diff,
+                a,
+            ])
+        };
+
+        let mut out = vec![];
+        encoder
+            .push(
+                &row(5, false, Datum::Int64(2), Datum::Int32(1)),
+                0,
+                &mut out,
+            )
+            .unwrap();
+        encoder
+            .push(&row(5, false, Datum::Int64(-1), Datum::Null), 0, &mut out)
+            .unwrap();
+        encoder
+            .push(&row(6, true, Datum::Null, Datum::Null), 0, &mut out)
+            .unwrap();
+        let tags: Vec<_> = out.iter().map(|msg| (msg[0], msg[25])).collect();
+        assert_eq!(
+            tags,
+            vec![
+                (b'w', b'B'),
+                (b'w', b'R'),
+                (b'w', b'I'),
+                (b'w', b'I'),
+                (b'w', b'D'),
+                (b'w', b'C'),
+                (b'k', 0),
+            ]
+        );
+        // An insert of `1`: the relation, `N`, one column, and the text `1`.
+        assert_eq!(
+            &out[2][25..],
+            &[b'I', 0, 0, 0x4e, 0x20, b'N', 0, 1, b't', 0, 0, 0, 1, b'1']
+        );
+        // A delete of `NULL`.
+        assert_eq!(&out[4][25..], &[b'D', 0, 0, 0x4e, 0x20, b'O', 0, 1, b'n']);
+        // The keepalive reports that all transactions before 6 were sent.
+        assert_eq!(out[6].len(), 18);
+        assert_eq!(&out[6][1..9], &5u64.to_be_bytes());
+
+        // The relation is only described once.
+        out.clear();
+        encoder
+            .push(
+                &row(7, false, Datum::Int64(1), Datum::Int32(2)),
+                0,
+                &mut out,
+            )
+            .unwrap();
+        assert_eq!(out.len(), 2);
+        assert_eq!((out[0][25], out[1][25]), (b'B', b'I'));
+    }
+}
@@ -9,6 +9,7 @@
 
 use std::fmt;
 use std::future::Future;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 
@@ -75,7 +76,8 @@ impl mz_server_core::Server for Server {
         // Using fully-qualified syntax means we won't accidentally call
         // ourselves (i.e., silently infinitely recurse) if the name or type of
         // `crate::Server::handle_connection` changes.
-        Box::pin(crate::Server::handle_connection(self, conn))
+        let origin = conn.peer_addr().ok().map(|addr| addr.ip());
+        Box::pin(crate::Server::handle_connection(self, conn, origin))
     }
 }
 
@@ -92,10 +94,12 @@ impl Server {
         }
     }
 
+    /// Handles a connection from the client at `origin`, if known.
     #[mz_ore::instrument(level = "debug")]
     pub fn handle_connection<A>(
         &self,
         conn: A,
+        origin: Option<IpAddr>,
     ) -> impl Future<Output = Result<(), anyhow::Error>> + 'static + Send
     where
         A: AsyncRead + AsyncWrite + AsyncReady + Send + Sync + Unpin + fmt::Debug + 'static,
@@ -148,7 +152,7 @@ impl Server {
                                 conn_id,
                                 secret_key,
                             }) => {
-                                adapter_client.cancel_request(conn_id, secret_key);
+                                adapter_client.cancel_request(conn_id, secret_key, origin);
                                 // For security, the client is not told whether the cancel
                                 // request succeeds or fails.
                                 return Ok(());
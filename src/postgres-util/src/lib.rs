@@ -21,7 +21,9 @@ pub mod desc;
 #[cfg(feature = "schemas")]
 pub mod schemas;
 #[cfg(feature = "schemas")]
-pub use schemas::{get_schemas, publication_info};
+pub use schemas::{
+    get_schemas, publication_info, publication_tables, table_info, PublicationTable,
+};
 #[cfg(feature = "tunnel")]
 pub mod tunnel;
 #[cfg(feature = "tunnel")]
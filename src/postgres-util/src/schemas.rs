@@ -27,6 +27,14 @@ pub async fn get_schemas(client: &Client) -> Result<Vec<PostgresSchemaDesc>, Pos
         .collect::<Vec<_>>())
 }
 
+/// A table that is part of a publication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicationTable {
+    pub oid: Oid,
+    pub namespace: String,
+    pub name: String,
+}
+
 /// Fetches table schema information from an upstream Postgres source for tables
 /// that are part of a publication, given a connection string and the
 /// publication name.
@@ -39,6 +47,23 @@ pub async fn publication_info(
     client: &Client,
     publication: &str,
 ) -> Result<Vec<PostgresTableDesc>, PostgresError> {
+    let mut table_infos = vec![];
+    for table in publication_tables(client, publication).await? {
+        table_infos.push(table_info(client, table).await?);
+    }
+    Ok(table_infos)
+}
+
+/// Lists the tables that are part of the publication `publication`, without fetching their
+/// schemas.
+///
+/// # Errors
+///
+/// - Upstream publication does not exist.
+pub async fn publication_tables(
+    client: &Client,
+    publication: &str,
+) -> Result<Vec<PublicationTable>, PostgresError> {
     client
         .query(
             "SELECT oid FROM pg_publication WHERE pubname = $1",
@@ -65,13 +90,32 @@ pub async fn publication_info(
         .await
         .map_err(PostgresError::from)?;
 
-    let mut table_infos = vec![];
-    for row in tables {
-        let oid = row.get("oid");
+    Ok(tables
+        .into_iter()
+        .map(|row| PublicationTable {
+            oid: row.get("oid"),
+            namespace: row.get("schemaname"),
+            name: row.get("tablename"),
+        })
+        .collect())
+}
 
-        let columns = client
-            .query(
-                "SELECT
+/// Fetches the schema of the publication table `table`.
+///
+/// The queries of concurrent calls that share a `client` are pipelined over its connection.
+pub async fn table_info(
+    client: &Client,
+    table: PublicationTable,
+) -> Result<PostgresTableDesc, PostgresError> {
+    let PublicationTable {
+        oid,
+        namespace,
+        name,
+    } = table;
+
+    let columns = client
+        .query(
+            "SELECT
                         a.attname AS name,
                         a.atttypid AS typoid,
                         a.attnum AS colnum,
@@ -87,34 +131,34 @@ pub async fn publication_info(
                         AND NOT a.attisdropped
                         AND a.attrelid = $1
                     ORDER BY a.attnum",
-                &[&oid],
-            )
-            .await
-            .map_err(PostgresError::from)?
-            .into_iter()
-            .map(|row| {
-                let name: String = row.get("name");
-                let type_oid = row.get("typoid");
-                let col_num = row
-                    .get::<_, i16>("colnum")
-                    .try_into()
-                    .expect("non-negative values");
-                let type_mod: i32 = row.get("typmod");
-                let not_null: bool = row.get("not_null");
-                Ok(PostgresColumnDesc {
-                    name,
-                    col_num,
-                    type_oid,
-                    type_mod,
-                    nullable: !not_null,
-                })
+            &[&oid],
+        )
+        .await
+        .map_err(PostgresError::from)?
+        .into_iter()
+        .map(|row| {
+            let name: String = row.get("name");
+            let type_oid = row.get("typoid");
+            let col_num = row
+                .get::<_, i16>("colnum")
+                .try_into()
+                .expect("non-negative values");
+            let type_mod: i32 = row.get("typmod");
+            let not_null: bool = row.get("not_null");
+            Ok(PostgresColumnDesc {
+                name,
+                col_num,
+                type_oid,
+                type_mod,
+                nullable: !not_null,
             })
-            .collect::<Result<Vec<_>, PostgresError>>()?;
+        })
+        .collect::<Result<Vec<_>, PostgresError>>()?;
 
-        // PG 15 adds UNIQUE NULLS NOT DISTINCT, which would let us use `UNIQUE` constraints over
-        // nullable columns as keys; i.e. aligns a PG index's NULL handling with an arrangement's
-        // keys. For more info, see https://www.postgresql.org/about/featurematrix/detail/392/
-        let pg_15_plus_keys = "
+    // PG 15 adds UNIQUE NULLS NOT DISTINCT, which would let us use `UNIQUE` constraints over
+    // nullable columns as keys; i.e. aligns a PG index's NULL handling with an arrangement's
+    // keys. For more info, see https://www.postgresql.org/about/featurematrix/detail/392/
+    let pg_15_plus_keys = "
         SELECT
             pg_constraint.oid,
             pg_constraint.conkey,
@@ -131,8 +175,8 @@ pub async fn publication_info(
                 AND
             pg_constraint.contype =ANY (ARRAY['p', 'u']);";
 
-        // As above but for versions of PG without indnullsnotdistinct.
-        let pg_14_minus_keys = "
+    // As above but for versions of PG without indnullsnotdistinct.
+    let pg_14_minus_keys = "
         SELECT
             pg_constraint.oid,
             pg_constraint.conkey,
@@ -145,7 +189,7 @@ pub async fn publication_info(
                 AND
             pg_constraint.contype =ANY (ARRAY['p', 'u']);";
 
-        let keys = match client.query(pg_15_plus_keys, &[&oid]).await {
+    let keys = match client.query(pg_15_plus_keys, &[&oid]).await {
             Ok(keys) => keys,
             Err(e)
                 // PG versions prior to 15 do not contain this column.
@@ -157,36 +201,33 @@ pub async fn publication_info(
             e => e.map_err(PostgresError::from)?,
         };
 
-        let keys = keys
-            .into_iter()
-            .map(|row| {
-                let oid: u32 = row.get("oid");
-                let cols: Vec<i16> = row.get("conkey");
-                let name: String = row.get("conname");
-                let is_primary: bool = row.get("is_primary");
-                let nulls_not_distinct: bool = row.get("nulls_not_distinct");
-                let cols = cols
-                    .into_iter()
-                    .map(|col| u16::try_from(col).expect("non-negative colnums"))
-                    .collect();
-                PostgresKeyDesc {
-                    oid,
-                    name,
-                    cols,
-                    is_primary,
-                    nulls_not_distinct,
-                }
-            })
-            .collect();
-
-        table_infos.push(PostgresTableDesc {
-            oid,
-            namespace: row.get("schemaname"),
-            name: row.get("tablename"),
-            columns,
-            keys,
-        });
-    }
+    let keys = keys
+        .into_iter()
+        .map(|row| {
+            let oid: u32 = row.get("oid");
+            let cols: Vec<i16> = row.get("conkey");
+            let name: String = row.get("conname");
+            let is_primary: bool = row.get("is_primary");
+            let nulls_not_distinct: bool = row.get("nulls_not_distinct");
+            let cols = cols
+                .into_iter()
+                .map(|col| u16::try_from(col).expect("non-negative colnums"))
+                .collect();
+            PostgresKeyDesc {
+                oid,
+                name,
+                cols,
+                is_primary,
+                nulls_not_distinct,
+            }
+        })
+        .collect();
 
-    Ok(table_infos)
+    Ok(PostgresTableDesc {
+        oid,
+        namespace,
+        name,
+        columns,
+        keys,
+    })
 }
@@ -162,6 +162,8 @@ pub struct ExplainConfig {
     pub cardinality: bool,
     /// Show inferred column names.
     pub column_names: bool,
+    /// Show the comments on the explained objects and the relations they read.
+    pub comments: bool,
     /// Render implemented MIR `Join` nodes in a way which reflects the implementation.
     pub join_impls: bool,
     /// Use inferred column names when rendering scalar and aggregate expressions.
@@ -170,6 +172,8 @@ pub struct ExplainConfig {
     pub keys: bool,
     /// Restrict output trees to linear chains. Ignored if `raw_plans` is set.
     pub linear_chains: bool,
+    /// Annotate the arrangements in physical plans with estimated memory footprints.
+    pub memory_estimates: bool,
     /// Show the `non_negative` in the explanation if it is supported by the backing IR.
     pub non_negative: bool,
     /// Show the slow path plan even if a fast path plan was created. Useful for debugging.
@@ -179,12 +183,18 @@ pub struct ExplainConfig {
     pub no_notices: bool,
     /// Show node IDs in physical plans.
     pub node_ids: bool,
+    /// The types of the parameter placeholders the explainee was planned with.
+    pub parameters: Vec<ScalarType>,
     /// Don't normalize plans before explaining them.
     pub raw_plans: bool,
     /// Disable virtual syntax in the explanation.
     pub raw_syntax: bool,
     /// Anonymize literals in the plan.
     pub redacted: bool,
+    /// Annotate sources with row counts estimated from persist statistics.
+    pub row_estimates: bool,
+    /// Annotate decorrelated plans with how each subquery was lowered.
+    pub subquery_lowering: bool,
     /// Show the `subtree_size` attribute in the explanation if it is supported by the backing IR.
     pub subtree_size: bool,
     /// Print optimization timings.
@@ -205,17 +215,22 @@ impl Default for ExplainConfig {
             arity: false,
             cardinality: false,
             column_names: false,
+            comments: false,
             filter_pushdown: false,
             humanized_exprs: false,
             join_impls: true,
             keys: false,
             linear_chains: false,
+            memory_estimates: false,
             no_fast_path: true,
             no_notices: false,
             node_ids: false,
             non_negative: false,
+            parameters: vec![],
             raw_plans: true,
             raw_syntax: false,
+            row_estimates: false,
+            subquery_lowering: false,
             subtree_size: false,
             timing: false,
             types: false,
@@ -448,6 +463,13 @@ pub trait ExprHumanizer: fmt::Debug {
 
     /// Returns whether the specified id exists.
     fn id_exists(&self, id: GlobalId) -> bool;
+
+    /// Returns the comments on the object identified by `id` and on its
+    /// columns, along with the name of the column they are on, or `None` for
+    /// the comment on the object itself.
+    fn comments_for_id(&self, _id: GlobalId) -> Vec<(Option<String>, String)> {
+        vec![]
+    }
 }
 
 /// An [`ExprHumanizer`] that extends the `inner` instance with shadow items
@@ -520,6 +542,14 @@ impl<'a> ExprHumanizer for ExprHumanizerExt<'a> {
     fn id_exists(&self, id: GlobalId) -> bool {
         self.items.contains_key(&id) || self.inner.id_exists(id)
     }
+
+    fn comments_for_id(&self, id: GlobalId) -> Vec<(Option<String>, String)> {
+        match self.items.get(&id) {
+            // Transient items cannot be commented on.
+            Some(_) => vec![],
+            None => self.inner.comments_for_id(id),
+        }
+    }
 }
 
 /// A description of a catalog item that does not exist, but can be reported as
@@ -626,6 +656,8 @@ pub struct Attributes {
     pub keys: Option<Vec<Vec<usize>>>,
     pub cardinality: Option<String>,
     pub column_names: Option<Vec<String>>,
+    /// An upper bound on the number of rows of the node, if one could be estimated.
+    pub row_estimate: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -736,6 +768,123 @@ impl UsedIndexes {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Marks the usages of the indexes among `partial_indexes` as usages of partial indexes.
+    pub fn mark_partial(&mut self, partial_indexes: &BTreeSet<GlobalId>) {
+        self.0 = std::mem::take(&mut self.0)
+            .into_iter()
+            .map(|(id, mut usage_types)| {
+                if partial_indexes.contains(&id) {
+                    usage_types.push(IndexUsageType::PartialIndex);
+                }
+                (id, usage_types)
+            })
+            .collect();
+    }
+}
+
+/// The subqueries that were lowered while decorrelating the explained plan, in
+/// the order in which the lowering encountered them.
+#[derive(Clone, Debug, Default)]
+pub struct SubqueryLowerings(Vec<SubqueryLowering>);
+
+impl SubqueryLowerings {
+    pub fn new(values: Vec<SubqueryLowering>) -> SubqueryLowerings {
+        SubqueryLowerings(values)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// How a subquery was lowered while decorrelating a plan.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubqueryLowering {
+    /// The kind of the subquery.
+    pub kind: SubqueryKind,
+    /// The way in which the subquery is applied to the rows of its outer relation.
+    pub strategy: SubqueryLoweringStrategy,
+    /// The number of lowered subqueries that enclose the subquery.
+    pub depth: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubqueryKind {
+    /// A scalar subquery, e.g., `(SELECT max(b) FROM t WHERE t.a = outer.a)`.
+    Scalar,
+    /// An `EXISTS` subquery, or a quantified comparison rewritten into one.
+    Exists,
+    /// The right input of a `LATERAL` join.
+    Lateral,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubqueryLoweringStrategy {
+    /// The subquery only maps, filters, and projects the rows of the outer
+    /// relation, or calls table functions on them, so it is applied to each
+    /// outer row directly.
+    Direct,
+    /// The subquery doesn't reference the outer relation, so it is computed
+    /// once and cross joined with the outer relation.
+    Uncorrelated,
+    /// The subquery is computed once for each distinct value of the given
+    /// columns of the outer relation, and the results are joined back to the
+    /// outer relation on these columns.
+    PerDistinctKey { key: Vec<usize> },
+}
+
+impl SubqueryLowering {
+    /// A description of what the lowering of the subquery costs.
+    pub fn cost(&self) -> &'static str {
+        use SubqueryKind::*;
+        use SubqueryLoweringStrategy::*;
+        match (&self.strategy, self.kind) {
+            (Direct, _) => "stateless, each outer row is processed on its own",
+            (Uncorrelated, Scalar) => {
+                "the subquery result and a count of its rows, which guards against \
+                 more than one row, are maintained once"
+            }
+            (Uncorrelated, Exists) => "a single distinct row is maintained for the subquery",
+            (Uncorrelated, Lateral) => "the subquery result is maintained once",
+            (PerDistinctKey { .. }, Scalar) => {
+                "the distinct keys of the outer relation, the subquery result per key, \
+                 and a count of the rows per key, which guards against more than one row, \
+                 are maintained, and the result is joined back to the outer relation"
+            }
+            (PerDistinctKey { .. }, Exists) => {
+                "the distinct keys of the outer relation and the distinct keys with a \
+                 subquery result are maintained, and the result is joined back to the \
+                 outer relation"
+            }
+            (PerDistinctKey { .. }, Lateral) => {
+                "the distinct keys of the outer relation and the subquery result per key \
+                 are maintained, and the result is joined back to the outer relation"
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SubqueryKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SubqueryKind::Scalar => f.write_str("scalar subquery"),
+            SubqueryKind::Exists => f.write_str("EXISTS subquery"),
+            SubqueryKind::Lateral => f.write_str("LATERAL join"),
+        }
+    }
+}
+
+impl std::fmt::Display for SubqueryLoweringStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SubqueryLoweringStrategy::Direct => f.write_str("applied directly to the outer rows"),
+            SubqueryLoweringStrategy::Uncorrelated => f.write_str("uncorrelated, computed once"),
+            SubqueryLoweringStrategy::PerDistinctKey { key } => {
+                write!(f, "computed per distinct outer key ({})", Indices(key))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Arbitrary, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -768,6 +917,10 @@ pub enum IndexUsageType {
     /// Note: When a fast path peek does a lookup and also has a limit, the usage type will be
     /// `Lookup`. However, the smart limiting logic will still apply.
     FastPathLimit,
+    /// The index is a partial index that stands in for its collection, because the query only
+    /// reads rows that satisfy the predicate of the index. This accompanies the other usages of
+    /// the index.
+    PartialIndex,
     /// We saw a dangling `ArrangeBy`, i.e., where we have no idea what the arrangement will be used
     /// for. This is an internal error. Can be a bug either in `CollectIndexRequests`, or some
     /// other transform that messed up the plan. It's also possible that somebody is trying to add
@@ -813,6 +966,7 @@ impl std::fmt::Display for IndexUsageType {
                 IndexUsageType::SinkExport => "sink export",
                 IndexUsageType::IndexExport => "index export",
                 IndexUsageType::FastPathLimit => "fast path limit",
+                IndexUsageType::PartialIndex => "partial index",
                 IndexUsageType::DanglingArrangeBy => "*** INTERNAL ERROR (dangling ArrangeBy) ***",
                 IndexUsageType::Unknown => "*** INTERNAL ERROR (unknown usage) ***",
             }
@@ -923,17 +1077,22 @@ mod tests {
             arity: false,
             cardinality: false,
             column_names: false,
+            comments: false,
             filter_pushdown: false,
             humanized_exprs: false,
             join_impls: false,
             keys: false,
             linear_chains: false,
+            memory_estimates: false,
             no_fast_path: false,
             no_notices: false,
             node_ids: false,
             non_negative: false,
+            parameters: vec![],
             raw_plans: false,
             raw_syntax: false,
+            row_estimates: false,
+            subquery_lowering: false,
             subtree_size: false,
             timing: true,
             types: false,
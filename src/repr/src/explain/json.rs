@@ -8,9 +8,30 @@
 // by the Apache License, Version 2.0.
 
 //! Structs and traits for `EXPLAIN AS JSON`.
+//!
+//! Plans rendered as JSON are objects with an `explain_version` field that holds the
+//! [`EXPLAIN_VERSION`] of their schema, and either
+//!
+//! - a `plan` field holding a single plan, for plans explained before they
+//!   are lowered to a dataflow, or
+//! - a `plans` field holding the `id` and `plan` of each object built by the
+//!   dataflow, in topological order, and a `sources` field holding the `id`
+//!   of each source imported by the dataflow along with the optional `op`,
+//!   `pushdown` and `estimated_rows` fields. With the `comments` flag, a
+//!   `comments` field holds the `id`, `column` and `comment` of each comment
+//!   on the objects that the dataflow builds and reads. For subscribes, a
+//!   `subscribe` field holds the `as_of`, `since`, `upper`, `up_to`,
+//!   `with_snapshot` and `emit_progress` of the subscribe sink.
+//!
+//! Within a version, fields may be added, and new variants may appear in plans, but existing
+//! fields are never removed, renamed, or given a different meaning. Any such change bumps
+//! [`EXPLAIN_VERSION`], so that tools that depend on the format can detect it.
 
 use crate::explain::*;
 
+/// The version of the schema of plans rendered as [`ExplainFormat::Json`].
+pub const EXPLAIN_VERSION: u64 = 1;
+
 /// A trait implemented by explanation types that can be rendered as
 /// [`ExplainFormat::Json`].
 pub trait DisplayJson
@@ -15,7 +15,7 @@ use mz_ore::str::Indent;
 
 use crate::explain::{
     CompactScalarSeq, CompactScalars, ExprHumanizer, IndexUsageType, Indices, ScalarOps,
-    UnsupportedFormat, UsedIndexes,
+    SubqueryLowerings, UnsupportedFormat, UsedIndexes,
 };
 
 /// A trait implemented by explanation types that can be rendered as
@@ -84,6 +84,31 @@ where
     }
 }
 
+impl<C> DisplayText<C> for SubqueryLowerings
+where
+    C: AsMut<Indent>,
+{
+    fn fmt_text(&self, f: &mut fmt::Formatter<'_>, ctx: &mut C) -> fmt::Result {
+        writeln!(f, "{}Subquery Lowering:", ctx.as_mut())?;
+        *ctx.as_mut() += 1;
+        for lowering in &self.0 {
+            // Indent nested subqueries below the subqueries that enclose them.
+            *ctx.as_mut() += lowering.depth;
+            writeln!(
+                f,
+                "{}- {}: {}",
+                ctx.as_mut(),
+                lowering.kind,
+                lowering.strategy
+            )?;
+            writeln!(f, "{}  cost: {}", ctx.as_mut(), lowering.cost())?;
+            *ctx.as_mut() -= lowering.depth;
+        }
+        *ctx.as_mut() -= 1;
+        Ok(())
+    }
+}
+
 impl<'a> fmt::Display for Indices<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
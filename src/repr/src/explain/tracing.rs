@@ -342,6 +342,84 @@ impl PlanTrace<UsedIndexes> {
     }
 }
 
+/// A bound on the bytes of the plans rendered from the entries of a
+/// [`PlanTrace`].
+///
+/// Plans that don't fit into the remaining budget are truncated and end with a
+/// marker, and plans that are rendered after the budget is exhausted consist of
+/// the marker alone. Each such plan counts as an overflow.
+#[derive(Debug)]
+pub struct TraceBudget {
+    max_bytes: usize,
+    used_bytes: usize,
+    overflow: usize,
+}
+
+impl TraceBudget {
+    pub fn new(max_bytes: usize) -> TraceBudget {
+        TraceBudget {
+            max_bytes,
+            used_bytes: 0,
+            overflow: 0,
+        }
+    }
+
+    /// The number of bytes that plans may use in total.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// The number of plans that were truncated to fit the budget.
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+
+    /// Returns whether no bytes remain.
+    pub fn is_exhausted(&self) -> bool {
+        self.used_bytes >= self.max_bytes
+    }
+
+    /// Renders a plan with `render` and [admits](TraceBudget::admit) it, unless
+    /// the budget is exhausted, in which case `render` isn't called.
+    pub fn render<E>(&mut self, render: impl FnOnce() -> Result<String, E>) -> Result<String, E> {
+        if self.is_exhausted() {
+            self.overflow += 1;
+            Ok(self.marker())
+        } else {
+            Ok(self.admit(render()?))
+        }
+    }
+
+    /// Charges the bytes of `plan` to the budget, and returns `plan` truncated
+    /// to the remaining bytes if it exceeds them.
+    pub fn admit(&mut self, mut plan: String) -> String {
+        let remaining = self.max_bytes.saturating_sub(self.used_bytes);
+        if plan.len() <= remaining {
+            self.used_bytes += plan.len();
+            return plan;
+        }
+        let mut len = remaining;
+        while !plan.is_char_boundary(len) {
+            len -= 1;
+        }
+        plan.truncate(len);
+        self.used_bytes = self.max_bytes;
+        self.overflow += 1;
+        if !plan.is_empty() && !plan.ends_with('\n') {
+            plan.push('\n');
+        }
+        plan.push_str(&self.marker());
+        plan
+    }
+
+    fn marker(&self) -> String {
+        format!(
+            "<truncated: the trace exceeded its budget of {} bytes>\n",
+            self.max_bytes
+        )
+    }
+}
+
 /// Helper trait used to extract attributes of type `&'static str`.
 trait GetStr {
     fn get_str(&self, key: &'static str) -> Option<String>;
@@ -392,7 +470,7 @@ mod test {
     use tracing::dispatcher;
     use tracing_subscriber::prelude::*;
 
-    use super::{trace_plan, PlanTrace};
+    use super::{trace_plan, PlanTrace, TraceBudget};
 
     #[mz_ore::test]
     fn test_optimizer_trace() {
@@ -430,6 +508,26 @@ mod test {
         }
     }
 
+    #[mz_ore::test]
+    fn test_trace_budget() {
+        let mut budget = TraceBudget::new(10);
+        assert_eq!(budget.admit("12345".into()), "12345");
+        assert!(!budget.is_exhausted());
+        // Plans are truncated at a character boundary.
+        assert_eq!(
+            budget.admit("abcdé".into()),
+            "abcd\n<truncated: the trace exceeded its budget of 10 bytes>\n"
+        );
+        assert!(budget.is_exhausted());
+        // Plans aren't rendered once the budget is exhausted.
+        let plan = budget.render(|| -> Result<String, ()> { panic!("rendered") });
+        assert_eq!(
+            plan,
+            Ok("<truncated: the trace exceeded its budget of 10 bytes>\n".into())
+        );
+        assert_eq!(budget.overflow(), 2);
+    }
+
     #[instrument(level = "info")]
     fn optimize() {
         let mut plan = constant_plan(42);
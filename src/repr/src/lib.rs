@@ -53,7 +53,9 @@ pub use crate::relation::{
     arb_row_for_relation, ColumnName, ColumnType, NotNullViolation, ProtoColumnName,
     ProtoColumnType, ProtoRelationDesc, ProtoRelationType, RelationDesc, RelationType,
 };
-pub use crate::row::collection::{ProtoRowCollection, RowCollection, SortedRowCollectionIter};
+pub use crate::row::collection::{
+    ProtoRowCollection, RowBytes, RowCollection, SortedRowCollectionIter,
+};
 pub use crate::row::encoding::{
     DatumDecoderT, DatumEncoderT, DatumToPersist, DatumToPersistFn, RowDecoder, RowEncoder,
 };
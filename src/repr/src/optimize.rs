@@ -10,6 +10,8 @@
 //! Facilities for defining optimizer feature flags.
 
 use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +28,23 @@ macro_rules! optimizer_feature_flags {
             $(pub $feature: Option<$type>),*
         }
 
+        impl OptimizerFeatures {
+            /// Returns the name and the encoded value of each feature.
+            pub fn entries(&self) -> Vec<(&'static str, String)> {
+                vec![$((stringify!($feature), self.$feature.encode())),*]
+            }
+        }
+
+        /// Renders one `feature = value` line per feature.
+        impl fmt::Display for OptimizerFeatures {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (name, value) in self.entries() {
+                    writeln!(f, "{name} = {value}")?;
+                }
+                Ok(())
+            }
+        }
+
         impl Default for OptimizerFeatureOverrides {
             fn default() -> Self {
                 Self {
@@ -45,6 +64,45 @@ macro_rules! optimizer_feature_flags {
             }
         }
 
+        /// An [`OverrideFrom`] implementation that layers the values of
+        /// `overrides` on top of `self`.
+        impl OverrideFrom<OptimizerFeatureOverrides> for OptimizerFeatureOverrides {
+            fn override_from(mut self, overrides: &OptimizerFeatureOverrides) -> Self {
+                $(if let Some(feature_value) = overrides.$feature {
+                    self.$feature = Some(feature_value);
+                })*
+                self
+            }
+        }
+
+        /// Parses a comma-separated list of `feature = value` pairs, such as
+        /// `enable_eager_delta_joins = true, persist_fast_path_limit = 100`.
+        impl FromStr for OptimizerFeatureOverrides {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut overrides = OptimizerFeatureOverrides::default();
+
+                for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+                    let Some((name, value)) = pair.split_once('=') else {
+                        return Err(format!("expected `feature = value`, found `{pair}`"));
+                    };
+                    let (name, value) = (name.trim(), value.trim());
+                    match name {
+                        $(stringify!($feature) => {
+                            let value = <$type>::try_decode(value).ok_or_else(|| {
+                                format!("invalid value for optimizer feature {name}: {value}")
+                            })?;
+                            overrides.$feature = Some(value);
+                        }),*
+                        _ => return Err(format!("unknown optimizer feature: {name}")),
+                    }
+                }
+
+                Ok(overrides)
+            }
+        }
+
         /// An `OptimizerFeatureOverrides ⇒ BTreeMap<String, String>`
         /// conversion.
         ///
@@ -119,6 +177,21 @@ optimizer_feature_flags!({
     reoptimize_imported_views: bool,
 });
 
+/// Renders the overridden features as a comma-separated list of `feature =
+/// value` pairs, which [`OptimizerFeatureOverrides::from_str`] parses.
+impl fmt::Display for OptimizerFeatureOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let overrides = BTreeMap::<String, String>::from(self.clone());
+        for (i, (name, value)) in overrides.into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name} = {value}")?;
+        }
+        Ok(())
+    }
+}
+
 /// A trait used to implement layered config construction.
 pub trait OverrideFrom<T> {
     /// Override the configuration represented by [`Self`] with values
@@ -143,6 +216,9 @@ where
 trait OptimizerFeatureType {
     fn encode(self) -> String;
     fn decode(v: &str) -> Self;
+    fn try_decode(v: &str) -> Option<Self>
+    where
+        Self: Sized;
 }
 
 /// A macro that implements [`OptimizerFeatureType`] for most common types.
@@ -161,6 +237,10 @@ macro_rules! impl_optimizer_feature_type {
                 fn decode(v: &str) -> Self {
                     str::parse(&v).unwrap()
                 }
+
+                fn try_decode(v: &str) -> Option<Self> {
+                    str::parse(&v).ok()
+                }
             }
         )*
     };
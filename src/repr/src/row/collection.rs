@@ -10,6 +10,7 @@
 //! Defines types for working with collections of [`Row`].
 
 use std::num::NonZeroUsize;
+use std::ops::{Deref, Range};
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -25,6 +26,8 @@ include!(concat!(env!("OUT_DIR"), "/mz_repr.row.collection.rs"));
 
 /// Collection of [`Row`]s represented as a single blob.
 ///
+/// The blob is reference counted, so cloning a collection does not copy the encoded rows.
+///
 /// Note: the encoding format we use to represent [`Row`]s in this struct is
 /// not stable, and thus should never be persisted durably.
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -82,6 +85,40 @@ impl RowCollection {
         self.encoded = Bytes::from(new_bytes);
     }
 
+    /// Concatenates `collections` into a single [`RowCollection`].
+    ///
+    /// Unlike repeated calls to [`RowCollection::merge`], this copies the encoded rows at most
+    /// once, and not at all if at most one of the collections is non-empty, in which case the
+    /// blob of that collection is shared.
+    pub fn concat(collections: impl IntoIterator<Item = RowCollection>) -> RowCollection {
+        let mut non_empty: Vec<_> = collections
+            .into_iter()
+            .filter(|collection| collection.entries() > 0)
+            .collect();
+        if non_empty.len() <= 1 {
+            return non_empty.pop().unwrap_or_default();
+        }
+
+        let encoded_size = non_empty.iter().map(|c| c.encoded.len()).sum();
+        let entries = non_empty.iter().map(|c| c.entries()).sum();
+        let mut encoded = Vec::<u8>::with_capacity(encoded_size);
+        let mut metadata = Vec::<EncodedRowMetadata>::with_capacity(entries);
+
+        for collection in non_empty {
+            let base = encoded.len();
+            encoded.extend_from_slice(&collection.encoded[..]);
+            metadata.extend(collection.metadata.iter().map(|meta| EncodedRowMetadata {
+                offset: meta.offset + base,
+                diff: meta.diff,
+            }));
+        }
+
+        RowCollection {
+            encoded: Bytes::from(encoded),
+            metadata,
+        }
+    }
+
     /// Total count of [`Row`]s represented by this collection, considering a
     /// possible `OFFSET` and `LIMIT`.
     pub fn count(&self, offset: usize, limit: Option<usize>) -> usize {
@@ -116,6 +153,23 @@ impl RowCollection {
 
     /// Returns a [`RowRef`] for the entry at `idx`, if one exists.
     pub fn get(&self, idx: usize) -> Option<(&RowRef, &EncodedRowMetadata)> {
+        let (range, meta) = self.range(idx)?;
+        let row = RowRef::from_slice(&self.encoded[range]);
+
+        Some((row, meta))
+    }
+
+    /// Returns the entry at `idx` as [`RowBytes`] that share the blob of this collection, if one
+    /// exists.
+    pub fn get_bytes(&self, idx: usize) -> Option<RowBytes> {
+        let (range, _) = self.range(idx)?;
+        Some(RowBytes {
+            encoded: self.encoded.slice(range),
+        })
+    }
+
+    /// Returns the range of the blob that encodes the entry at `idx`, if one exists.
+    fn range(&self, idx: usize) -> Option<(Range<usize>, &EncodedRowMetadata)> {
         let (lower_offset, upper) = match idx {
             0 => (0, self.metadata.get(idx)?),
             _ => {
@@ -125,10 +179,7 @@ impl RowCollection {
             }
         };
 
-        let slice = &self.encoded[lower_offset..upper.offset];
-        let row = RowRef::from_slice(slice);
-
-        Some((row, upper))
+        Some((lower_offset..upper.offset, upper))
     }
 
     /// "Sorts" the [`RowCollection`] by returning a sorted view over the collection.
@@ -194,6 +245,32 @@ impl RustType<ProtoRowCollection> for RowCollection {
     }
 }
 
+/// A single encoded [`Row`] in a reference-counted buffer.
+///
+/// Rows read from a [`RowCollection`] with [`RowCollection::get_bytes`] share the blob of the
+/// collection, so they can be handed to other tasks without copying the row.
+#[derive(Debug, Clone)]
+pub struct RowBytes {
+    encoded: Bytes,
+}
+
+impl RowBytes {
+    /// Copies `row` into a new buffer.
+    pub fn copy_from(row: &RowRef) -> RowBytes {
+        RowBytes {
+            encoded: Bytes::copy_from_slice(row.data()),
+        }
+    }
+}
+
+impl Deref for RowBytes {
+    type Target = RowRef;
+
+    fn deref(&self) -> &RowRef {
+        RowRef::from_slice(&self.encoded)
+    }
+}
+
 /// Inner type of [`RowCollection`], describes a single Row.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct EncodedRowMetadata {
@@ -240,6 +317,14 @@ impl SortedRowCollection {
             .get(idx)
             .and_then(|inner_idx| self.collection.get(*inner_idx))
     }
+
+    /// Like [`SortedRowCollection::get`], but returns [`RowBytes`] that share the blob of the
+    /// collection.
+    pub fn get_bytes(&self, idx: usize) -> Option<RowBytes> {
+        self.sorted_view
+            .get(idx)
+            .and_then(|inner_idx| self.collection.get_bytes(*inner_idx))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -400,6 +485,30 @@ impl RowIterator for SortedRowCollectionIter {
         Some(row)
     }
 
+    fn next_bytes(&mut self) -> Option<RowBytes> {
+        // Projected rows don't exist in the collection, so we have to copy them.
+        if self.projection.is_some() {
+            return self.next().map(RowBytes::copy_from);
+        }
+
+        // Bail if we've reached our limit.
+        if let Some(0) = self.limit {
+            return None;
+        }
+
+        let row = self.collection.get_bytes(self.row_idx)?;
+
+        // If we're about to yield a row, then subtract from our limit.
+        if let Some(limit) = &mut self.limit {
+            *limit = limit.saturating_sub(1);
+        }
+
+        // Advance to the next row.
+        Self::advance_by(&self.collection, &mut self.row_idx, &mut self.diff_idx, 1);
+
+        Some(row)
+    }
+
     fn count(&self) -> usize {
         self.collection.collection.count(self.offset, self.limit)
     }
@@ -462,6 +571,28 @@ mod tests {
         assert_eq!(a_col.get(1).map(|(r, _)| r), Some(b.borrow()));
     }
 
+    #[mz_ore::test]
+    fn test_concat() {
+        let a = Row::pack_slice(&[Datum::False, Datum::String("hello world"), Datum::Int16(42)]);
+        let b = Row::pack_slice(&[Datum::MzTimestamp(crate::Timestamp::new(10))]);
+
+        let a_col = RowCollection::from([&a]);
+        let b_col = RowCollection::from([&b, &a]);
+
+        // A single non-empty collection is passed through without copying its rows.
+        let single = RowCollection::concat([RowCollection::default(), a_col.clone()]);
+        assert_eq!(single.encoded.as_ptr(), a_col.encoded.as_ptr());
+        assert_eq!(single, a_col);
+
+        let concat = RowCollection::concat([a_col, RowCollection::default(), b_col]);
+        assert_eq!(concat.count(0, None), 3);
+        assert_eq!(concat.get(0).map(|(r, _)| r), Some(a.borrow()));
+        assert_eq!(concat.get(1).map(|(r, _)| r), Some(b.borrow()));
+        assert_eq!(concat.get(2).map(|(r, _)| r), Some(a.borrow()));
+
+        assert_eq!(RowCollection::concat([]), RowCollection::default());
+    }
+
     #[mz_ore::test]
     fn test_sort() {
         let a = Row::pack_slice(&[Datum::False, Datum::String("hello world"), Datum::Int16(42)]);
@@ -665,6 +796,33 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[mz_ore::test]
+    fn test_row_bytes_iterator() {
+        let a = Row::pack_slice(&[Datum::String("hello world"), Datum::Int16(42)]);
+        let b = Row::pack_slice(&[Datum::String("goodbye"), Datum::Int16(7)]);
+        let col = RowCollection::new(&[
+            (a.clone(), NonZeroUsize::new(1).unwrap()),
+            (b.clone(), NonZeroUsize::new(2).unwrap()),
+        ]);
+        let blob = col.encoded.as_ptr_range();
+        let col = col.sorted_view(|a, b| a.cmp(b));
+
+        // Without a projection, the rows share the blob of the collection.
+        let mut iter = col.into_row_iter().with_limit(2);
+        for expected in [&b, &b] {
+            let row = iter.next_bytes().unwrap();
+            assert_eq!(&*row, expected.as_ref());
+            assert!(blob.contains(&row.encoded.as_ptr()));
+        }
+        assert_none!(iter.next_bytes());
+
+        // Projected rows are copied.
+        let mut iter = iter.into_inner().into_row_iter().with_projection(vec![1]);
+        let row = iter.next_bytes().unwrap();
+        assert_eq!(&*row, Row::pack_slice(&[Datum::Int16(7)]).as_ref());
+        assert!(!blob.contains(&row.encoded.as_ptr()));
+    }
+
     #[mz_ore::test]
     fn test_count_respects_limit_and_offset() {
         let a = Row::pack_slice(&[Datum::String("hello world")]);
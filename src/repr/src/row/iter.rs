@@ -11,6 +11,7 @@
 
 use std::fmt::Debug;
 
+use crate::row::collection::RowBytes;
 use crate::row::{Row, RowRef};
 
 /// An iterator that can borrow from `self` and yield [`RowRef`]s.
@@ -35,6 +36,14 @@ pub trait RowIterator: Debug {
     /// Returns the next [`RowRef`] without advancing the iterator.
     fn peek(&mut self) -> Option<&RowRef>;
 
+    /// Returns the next row as [`RowBytes`] advancing the iterator.
+    ///
+    /// Iterators over a [`RowCollection`](crate::RowCollection) return rows that share the blob
+    /// of the collection. By default, the row is copied.
+    fn next_bytes(&mut self) -> Option<RowBytes> {
+        self.next().map(RowBytes::copy_from)
+    }
+
     /// The total number of [`Row`]s this iterator could ever yield.
     ///
     /// Note: it _does not_ return the number of rows _remaining_, in otherwords calling `.next()`
@@ -59,6 +68,10 @@ impl<I: RowIterator + ?Sized> RowIterator for Box<I> {
         (**self).next()
     }
 
+    fn next_bytes(&mut self) -> Option<RowBytes> {
+        (**self).next_bytes()
+    }
+
     fn peek(&mut self) -> Option<&RowRef> {
         (**self).peek()
     }
@@ -73,6 +86,10 @@ impl<I: RowIterator + ?Sized> RowIterator for &mut I {
         (**self).next()
     }
 
+    fn next_bytes(&mut self) -> Option<RowBytes> {
+        (**self).next_bytes()
+    }
+
     fn peek(&mut self) -> Option<&RowRef> {
         (**self).peek()
     }
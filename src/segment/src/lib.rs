@@ -16,21 +16,30 @@
 //! [Segment]: https://segment.com
 //! [`segment`]: https://docs.rs/segment
 
+use std::collections::VecDeque;
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use segment::message::{Batch, BatchMessage, Group, Message, Track, User};
+use mz_ore::cast::CastFrom;
+use mz_ore::metric;
+use mz_ore::metrics::{IntCounter, MetricsRegistry, UIntGauge};
+use segment::message::{BatchMessage, Group, Track, User};
 use segment::{Batcher, Client as _, HttpClient};
 use time::OffsetDateTime;
-use tokio::sync::mpsc::error::TrySendError;
-use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::Notify;
 use tracing::{error, warn};
 use uuid::Uuid;
 
 /// The maximum number of undelivered events. Once this limit is reached,
-/// new events will be dropped.
+/// the oldest undelivered events are dropped to make room for new ones.
 const MAX_PENDING_EVENTS: usize = 32_768;
 
+/// The maximum amount of time to wait for Segment to accept a batch of events.
+/// Batches that are not accepted in time are dropped.
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// A [Segment] API client.
 ///
 /// Event delivery is best effort. There is no guarantee that a given event
@@ -39,7 +48,7 @@ const MAX_PENDING_EVENTS: usize = 32_768;
 /// [Segment]: https://segment.com
 #[derive(Clone)]
 pub struct Client {
-    tx: Sender<BatchMessage>,
+    handle: Arc<Handle>,
 }
 
 impl fmt::Debug for Client {
@@ -49,28 +58,31 @@ impl fmt::Debug for Client {
 }
 
 impl Client {
-    /// Creates a new client.
-    pub fn new(api_key: String) -> Client {
-        let (tx, rx) = mpsc::channel(MAX_PENDING_EVENTS);
+    /// Creates a new client that reports its metrics to `registry`.
+    pub fn new(api_key: String, registry: &MetricsRegistry) -> Client {
+        let queue = Arc::new(Queue::new(
+            MAX_PENDING_EVENTS,
+            Metrics::register_into(registry),
+        ));
 
         let send_task = SendTask {
             api_key,
             http_client: HttpClient::default(),
+            queue: Arc::clone(&queue),
         };
-        mz_ore::task::spawn(
-            || "segment_send_task",
-            async move { send_task.run(rx).await },
-        );
+        mz_ore::task::spawn(|| "segment_send_task", async move { send_task.run().await });
 
-        Client { tx }
+        Client {
+            handle: Arc::new(Handle { queue }),
+        }
     }
 
     /// Sends a new [track event] to Segment.
     ///
     /// Delivery happens asynchronously on a background thread. It is best
     /// effort. There is no guarantee that the event will be delivered to
-    /// Segment. Events may be dropped when the client is backlogged. Errors are
-    /// logged but not returned.
+    /// Segment. The oldest undelivered events are dropped when the client is
+    /// backlogged. Errors are logged but not returned.
     ///
     /// [track event]: https://segment.com/docs/connections/spec/track/
     pub fn track<S>(
@@ -111,8 +123,8 @@ impl Client {
     ///
     /// Delivery happens asynchronously on a background thread. It is best
     /// effort. There is no guarantee that the event will be delivered to
-    /// Segment. Events may be dropped when the client is backlogged. Errors are
-    /// logged but not returned.
+    /// Segment. The oldest undelivered events are dropped when the client is
+    /// backlogged. Errors are logged but not returned.
     ///
     /// [track event]: https://segment.com/docs/connections/spec/group/
     pub fn group(&self, user_id: Uuid, group_id: Uuid, traits: serde_json::Value) {
@@ -127,12 +139,159 @@ impl Client {
     }
 
     fn send(&self, message: BatchMessage) {
-        match self.tx.try_send(message) {
-            Ok(()) => (),
-            Err(TrySendError::Closed(_)) => panic!("receiver must not drop first"),
-            Err(TrySendError::Full(_)) => {
-                warn!("dropping segment event because queue is full");
+        self.handle.queue.push(message);
+    }
+}
+
+/// The part of a [`Client`] that is shared by all of its clones.
+struct Handle {
+    queue: Arc<Queue>,
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        // Let the send task deliver the remaining events and exit.
+        self.queue.close();
+    }
+}
+
+/// The metrics of a [`Client`].
+struct Metrics {
+    events_sent: IntCounter,
+    events_dropped_queue_full: IntCounter,
+    events_dropped_invalid: IntCounter,
+    events_dropped_send_failed: IntCounter,
+    events_dropped_timed_out: IntCounter,
+    queue_depth: UIntGauge,
+    healthy: UIntGauge,
+}
+
+impl Metrics {
+    fn register_into(registry: &MetricsRegistry) -> Metrics {
+        let events_dropped: mz_ore::metrics::raw::IntCounterVec = registry.register(metric!(
+            name: "mz_segment_client_events_dropped_total",
+            help: "The total number of Segment events that were dropped before delivery, by reason.",
+            var_labels: ["reason"],
+        ));
+        let healthy: UIntGauge = registry.register(metric!(
+            name: "mz_segment_client_healthy",
+            help: "Whether the most recent attempt to send events to Segment succeeded.",
+        ));
+        healthy.set(1);
+        Metrics {
+            events_sent: registry.register(metric!(
+                name: "mz_segment_client_events_sent_total",
+                help: "The total number of Segment events that were delivered.",
+            )),
+            events_dropped_queue_full: events_dropped.with_label_values(&["queue_full"]),
+            events_dropped_invalid: events_dropped.with_label_values(&["invalid"]),
+            events_dropped_send_failed: events_dropped.with_label_values(&["send_failed"]),
+            events_dropped_timed_out: events_dropped.with_label_values(&["timed_out"]),
+            queue_depth: registry.register(metric!(
+                name: "mz_segment_client_queue_depth",
+                help: "The number of Segment events that are waiting to be delivered.",
+            )),
+            healthy,
+        }
+    }
+}
+
+/// A bounded queue of undelivered events that drops the oldest events when it
+/// is full.
+///
+/// Pushing to the queue never blocks on the delivery of events, so callers
+/// like the coordinator's main loop are not slowed down when Segment is
+/// unavailable.
+struct Queue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+    metrics: Metrics,
+}
+
+struct QueueState {
+    events: VecDeque<BatchMessage>,
+    /// Whether all clients have been dropped.
+    closed: bool,
+}
+
+impl Queue {
+    fn new(capacity: usize, metrics: Metrics) -> Queue {
+        Queue {
+            state: Mutex::new(QueueState {
+                events: VecDeque::new(),
+                closed: false,
+            }),
+            notify: Notify::new(),
+            capacity,
+            metrics,
+        }
+    }
+
+    /// Adds `message` to the queue, dropping the oldest event if the queue is
+    /// full.
+    fn push(&self, message: BatchMessage) {
+        {
+            let mut state = self.state.lock().expect("lock poisoned");
+            if state.events.len() >= self.capacity {
+                state.events.pop_front();
+                self.metrics.events_dropped_queue_full.inc();
             }
+            state.events.push_back(message);
+            self.metrics
+                .queue_depth
+                .set(u64::cast_from(state.events.len()));
+        }
+        self.notify.notify_one();
+    }
+
+    /// Removes the oldest event from the queue, if any.
+    fn try_pop(&self) -> Option<BatchMessage> {
+        let mut state = self.state.lock().expect("lock poisoned");
+        let message = state.events.pop_front();
+        self.metrics
+            .queue_depth
+            .set(u64::cast_from(state.events.len()));
+        message
+    }
+
+    /// Waits for an event and removes it from the queue.
+    ///
+    /// Returns `None` once the queue is closed and empty.
+    async fn pop(&self) -> Option<BatchMessage> {
+        loop {
+            {
+                let state = self.state.lock().expect("lock poisoned");
+                if state.events.is_empty() && state.closed {
+                    return None;
+                }
+            }
+            if let Some(message) = self.try_pop() {
+                return Some(message);
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Marks the queue as closed.
+    fn close(&self) {
+        self.state.lock().expect("lock poisoned").closed = true;
+        self.notify.notify_one();
+    }
+}
+
+/// A batch of events that is being assembled for delivery.
+struct PendingBatch {
+    batcher: Batcher,
+    /// The number of events in the batch.
+    events: u64,
+}
+
+impl PendingBatch {
+    fn new() -> PendingBatch {
+        PendingBatch {
+            batcher: Batcher::new(None),
+            events: 0,
         }
     }
 }
@@ -140,57 +299,111 @@ impl Client {
 struct SendTask {
     api_key: String,
     http_client: HttpClient,
+    queue: Arc<Queue>,
 }
 
 impl SendTask {
-    async fn run(&self, mut rx: Receiver<BatchMessage>) {
+    async fn run(&self) {
         // On each turn of the loop, we accumulate all outstanding messages and
         // send them to Segment in the largest batches possible. We never have
-        // more than one outstanding request to Segment.
+        // more than one outstanding request to Segment, and messages stay in
+        // the bounded queue until they are added to a batch.
         loop {
-            let mut batcher = Batcher::new(None);
+            let mut batch = PendingBatch::new();
 
             // Wait for the first event to arrive.
-            match rx.recv().await {
-                Some(message) => batcher = self.enqueue(batcher, message).await,
+            match self.queue.pop().await {
+                Some(message) => batch = self.enqueue(batch, message).await,
                 None => return,
             };
 
             // Accumulate any other messages that are ready. `enqueue` may
             // flush the batch to Segment if we hit the maximum batch size.
-            while let Ok(message) = rx.try_recv() {
-                batcher = self.enqueue(batcher, message).await;
+            while let Some(message) = self.queue.try_pop() {
+                batch = self.enqueue(batch, message).await;
             }
 
             // Drain the queue.
-            self.flush(batcher).await;
+            self.flush(batch).await;
         }
     }
 
-    async fn enqueue(&self, mut batcher: Batcher, message: BatchMessage) -> Batcher {
-        match batcher.push(message) {
-            Ok(None) => (),
+    async fn enqueue(&self, mut batch: PendingBatch, message: BatchMessage) -> PendingBatch {
+        match batch.batcher.push(message) {
+            Ok(None) => batch.events += 1,
             Ok(Some(message)) => {
-                self.flush(batcher).await;
-                batcher = Batcher::new(None);
-                batcher
+                self.flush(batch).await;
+                batch = PendingBatch::new();
+                batch
+                    .batcher
                     .push(message)
                     .expect("message cannot fail to enqueue twice");
+                batch.events += 1;
             }
             Err(e) => {
                 warn!("error enqueueing segment message: {}", e);
+                self.queue.metrics.events_dropped_invalid.inc();
             }
         }
-        batcher
+        batch
     }
 
-    async fn flush(&self, batcher: Batcher) {
-        let message = batcher.into_message();
-        if matches!(&message, Message::Batch(Batch { batch , .. }) if batch.is_empty()) {
+    async fn flush(&self, batch: PendingBatch) {
+        if batch.events == 0 {
             return;
         }
-        if let Err(e) = self.http_client.send(self.api_key.clone(), message).await {
-            warn!("error sending message to segment: {}", e);
+        let metrics = &self.queue.metrics;
+        let message = batch.batcher.into_message();
+        let send = self.http_client.send(self.api_key.clone(), message);
+        match tokio::time::timeout(SEND_TIMEOUT, send).await {
+            Ok(Ok(())) => {
+                metrics.events_sent.inc_by(batch.events);
+                metrics.healthy.set(1);
+            }
+            Ok(Err(e)) => {
+                warn!("error sending message to segment: {}", e);
+                metrics.events_dropped_send_failed.inc_by(batch.events);
+                metrics.healthy.set(0);
+            }
+            Err(_) => {
+                warn!("timed out sending message to segment");
+                metrics.events_dropped_timed_out.inc_by(batch.events);
+                metrics.healthy.set(0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(group_id: &str) -> BatchMessage {
+        BatchMessage::Group(Group {
+            group_id: group_id.into(),
+            ..Default::default()
+        })
+    }
+
+    fn group_id(message: BatchMessage) -> String {
+        match message {
+            BatchMessage::Group(group) => group.group_id,
+            _ => panic!("unexpected message"),
         }
     }
+
+    #[mz_ore::test]
+    fn test_queue_drops_oldest() {
+        let queue = Queue::new(2, Metrics::register_into(&MetricsRegistry::new()));
+        queue.push(group("1"));
+        queue.push(group("2"));
+        queue.push(group("3"));
+
+        assert_eq!(queue.metrics.events_dropped_queue_full.get(), 1);
+        assert_eq!(queue.metrics.queue_depth.get(), 2);
+        assert_eq!(queue.try_pop().map(group_id).as_deref(), Some("2"));
+        assert_eq!(queue.try_pop().map(group_id).as_deref(), Some("3"));
+        assert!(queue.try_pop().is_none());
+        assert_eq!(queue.metrics.queue_depth.get(), 0);
+    }
 }
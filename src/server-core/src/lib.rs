@@ -22,7 +22,7 @@ use futures::stream::{BoxStream, Stream, StreamExt};
 use mz_ore::channel::trigger;
 use mz_ore::error::ErrorExt;
 use mz_ore::task::JoinSetExt;
-use openssl::ssl::{SslAcceptor, SslContext, SslFiletype, SslMethod};
+use openssl::ssl::{self, AlpnError, SslAcceptor, SslContext, SslFiletype, SslMethod};
 use socket2::{SockRef, TcpKeepalive};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot;
@@ -39,6 +39,11 @@ const KEEPALIVE: TcpKeepalive = TcpKeepalive::new()
     .with_interval(Duration::from_secs(60))
     .with_retries(9);
 
+/// How long data may remain unacknowledged before the connection is closed. Matches the time it
+/// takes for the keepalive settings above to detect a dead peer.
+#[cfg(target_os = "linux")]
+const USER_TIMEOUT: Duration = Duration::from_secs(60 + 60 * 9);
+
 /// A future that handles a connection.
 pub type ConnectionHandler = Pin<Box<dyn Future<Output = Result<(), anyhow::Error>> + Send>>;
 
@@ -133,6 +138,15 @@ where
                     error!("failed enabling keepalive: {e}");
                     continue;
                 }
+                // Keepalives are only sent while no data is in flight, so a client that
+                // vanishes without closing its connection while it has data left to
+                // acknowledge, e.g. the last updates of a `SUBSCRIBE`, would otherwise only be
+                // noticed once retransmissions give up, which takes up to half an hour.
+                #[cfg(target_os = "linux")]
+                if let Err(e) = SockRef::from(&conn).set_tcp_user_timeout(Some(USER_TIMEOUT)) {
+                    error!("failed setting TCP user timeout: {e}");
+                    continue;
+                }
                 let fut = server.handle_connection(conn);
                 set.spawn_named(|| &task_name, async {
                     if let Err(e) = fut.await {
@@ -229,6 +243,11 @@ impl TlsCertConfig {
         let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
         builder.set_certificate_chain_file(&self.cert)?;
         builder.set_private_key_file(&self.key, SslFiletype::PEM)?;
+        // Let HTTP clients negotiate HTTP/2, which gRPC clients require. Clients
+        // that don't ask for a protocol, like pgwire clients, are unaffected.
+        builder.set_alpn_select_callback(|_ssl, client_protos| {
+            ssl::select_next_proto(b"\x02h2\x08http/1.1", client_protos).ok_or(AlpnError::NOACK)
+        });
         Ok(builder.build().into_context())
     }
 
@@ -1283,6 +1283,9 @@ pub enum CreateSinkConnection<T: AstInfo> {
         key: Option<KafkaSinkKey>,
         headers: Option<Ident>,
     },
+    Webhook {
+        url: String,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
@@ -1309,6 +1312,11 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                     f.write_node(headers);
                 }
             }
+            CreateSinkConnection::Webhook { url } => {
+                f.write_str("WEBHOOK '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+            }
         }
     }
 }
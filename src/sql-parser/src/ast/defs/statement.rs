@@ -63,6 +63,7 @@ pub enum Statement<T: AstInfo> {
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
     AlterCluster(AlterClusterStatement<T>),
+    AlterDatabase(AlterDatabaseStatement<T>),
     AlterOwner(AlterOwnerStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterObjectSwap(AlterObjectSwapStatement),
@@ -75,9 +76,12 @@ pub enum Statement<T: AstInfo> {
     AlterSystemSet(AlterSystemSetStatement),
     AlterSystemReset(AlterSystemResetStatement),
     AlterSystemResetAll(AlterSystemResetAllStatement),
+    AlterSystemTerminateSessions(AlterSystemTerminateSessionsStatement),
     AlterConnection(AlterConnectionStatement<T>),
     AlterRole(AlterRoleStatement<T>),
     AlterTableAddColumn(AlterTableAddColumnStatement<T>),
+    AlterTableRowCountHint(AlterTableRowCountHintStatement<T>),
+    Cancel(CancelStatement),
     Discard(DiscardStatement),
     DropObjects(DropObjectsStatement),
     DropOwned(DropOwnedStatement<T>),
@@ -91,11 +95,14 @@ pub enum Statement<T: AstInfo> {
     Subscribe(SubscribeStatement<T>),
     ExplainPlan(ExplainPlanStatement<T>),
     ExplainPushdown(ExplainPushdownStatement<T>),
+    ExplainAnalyze(ExplainAnalyzeStatement<T>),
     ExplainTimestamp(ExplainTimestampStatement<T>),
     ExplainSinkSchema(ExplainSinkSchemaStatement<T>),
     Declare(DeclareStatement<T>),
     Fetch(FetchStatement<T>),
     Close(CloseStatement),
+    HandoffCursor(HandoffCursorStatement),
+    ClaimHandoff(ClaimHandoffStatement),
     Prepare(PrepareStatement<T>),
     Execute(ExecuteStatement<T>),
     Deallocate(DeallocateStatement),
@@ -136,6 +143,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterCluster(stmt) => f.write_node(stmt),
+            Statement::AlterDatabase(stmt) => f.write_node(stmt),
             Statement::AlterOwner(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterRetainHistory(stmt) => f.write_node(stmt),
@@ -148,9 +156,12 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterSystemSet(stmt) => f.write_node(stmt),
             Statement::AlterSystemReset(stmt) => f.write_node(stmt),
             Statement::AlterSystemResetAll(stmt) => f.write_node(stmt),
+            Statement::AlterSystemTerminateSessions(stmt) => f.write_node(stmt),
             Statement::AlterConnection(stmt) => f.write_node(stmt),
             Statement::AlterRole(stmt) => f.write_node(stmt),
             Statement::AlterTableAddColumn(stmt) => f.write_node(stmt),
+            Statement::AlterTableRowCountHint(stmt) => f.write_node(stmt),
+            Statement::Cancel(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
             Statement::DropOwned(stmt) => f.write_node(stmt),
@@ -164,10 +175,13 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Subscribe(stmt) => f.write_node(stmt),
             Statement::ExplainPlan(stmt) => f.write_node(stmt),
             Statement::ExplainPushdown(stmt) => f.write_node(stmt),
+            Statement::ExplainAnalyze(stmt) => f.write_node(stmt),
             Statement::ExplainTimestamp(stmt) => f.write_node(stmt),
             Statement::ExplainSinkSchema(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
             Statement::Close(stmt) => f.write_node(stmt),
+            Statement::HandoffCursor(stmt) => f.write_node(stmt),
+            Statement::ClaimHandoff(stmt) => f.write_node(stmt),
             Statement::Fetch(stmt) => f.write_node(stmt),
             Statement::Prepare(stmt) => f.write_node(stmt),
             Statement::Execute(stmt) => f.write_node(stmt),
@@ -212,6 +226,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
         StatementKind::AlterCluster => "alter_cluster",
+        StatementKind::AlterDatabase => "alter_database",
         StatementKind::AlterObjectRename => "alter_object_rename",
         StatementKind::AlterRetainHistory => "alter_retain_history",
         StatementKind::AlterObjectSwap => "alter_object_swap",
@@ -224,9 +239,12 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::AlterSystemSet => "alter_system_set",
         StatementKind::AlterSystemReset => "alter_system_reset",
         StatementKind::AlterSystemResetAll => "alter_system_reset_all",
+        StatementKind::AlterSystemTerminateSessions => "alter_system_terminate_sessions",
         StatementKind::AlterOwner => "alter_owner",
         StatementKind::AlterConnection => "alter_connection",
         StatementKind::AlterTableAddColumn => "alter_table",
+        StatementKind::AlterTableRowCountHint => "alter_table",
+        StatementKind::Cancel => "cancel",
         StatementKind::Discard => "discard",
         StatementKind::DropObjects => "drop_objects",
         StatementKind::DropOwned => "drop_owned",
@@ -240,11 +258,14 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Subscribe => "subscribe",
         StatementKind::ExplainPlan => "explain_plan",
         StatementKind::ExplainPushdown => "explain_pushdown",
+        StatementKind::ExplainAnalyze => "explain_analyze",
         StatementKind::ExplainTimestamp => "explain_timestamp",
         StatementKind::ExplainSinkSchema => "explain_sink_schema",
         StatementKind::Declare => "declare",
         StatementKind::Fetch => "fetch",
         StatementKind::Close => "close",
+        StatementKind::HandoffCursor => "handoff_cursor",
+        StatementKind::ClaimHandoff => "claim_handoff",
         StatementKind::Prepare => "prepare",
         StatementKind::Execute => "execute",
         StatementKind::Deallocate => "deallocate",
@@ -264,12 +285,18 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SelectStatement<T: AstInfo> {
     pub query: Query<T>,
+    pub options: Vec<SelectStatementOption<T>>,
     pub as_of: Option<AsOf<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for SelectStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_node(&self.query);
+        if !self.options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.options));
+            f.write_str(")");
+        }
         if let Some(as_of) = &self.as_of {
             f.write_str(" ");
             f.write_node(as_of);
@@ -278,6 +305,82 @@ impl<T: AstInfo> AstDisplay for SelectStatement<T> {
 }
 impl_display_t!(SelectStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SelectStatementOptionName {
+    AfterToken,
+    MirrorToCluster,
+}
+
+impl AstDisplay for SelectStatementOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            SelectStatementOptionName::AfterToken => f.write_str("AFTER TOKEN"),
+            SelectStatementOptionName::MirrorToCluster => f.write_str("MIRROR TO CLUSTER"),
+        }
+    }
+}
+impl_display!(SelectStatementOptionName);
+
+impl WithOptionName for SelectStatementOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            SelectStatementOptionName::AfterToken | SelectStatementOptionName::MirrorToCluster => {
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SelectStatementOption<T: AstInfo> {
+    pub name: SelectStatementOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(SelectStatementOption);
+impl_display_t!(SelectStatementOption);
+
+/// An option of an `INSERT`, `UPDATE` or `DELETE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WriteStatementOptionName {
+    /// Validate the write and report the rows it affects without committing it.
+    DryRun,
+}
+
+impl AstDisplay for WriteStatementOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            WriteStatementOptionName::DryRun => f.write_str("DRY RUN"),
+        }
+    }
+}
+impl_display!(WriteStatementOptionName);
+
+impl WithOptionName for WriteStatementOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            WriteStatementOptionName::DryRun => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WriteStatementOption<T: AstInfo> {
+    pub name: WriteStatementOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(WriteStatementOption);
+impl_display_t!(WriteStatementOption);
+
 /// `INSERT`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct InsertStatement<T: AstInfo> {
@@ -289,6 +392,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub source: InsertSource<T>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
+    /// `WITH`
+    pub options: Vec<WriteStatementOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for InsertStatement<T> {
@@ -306,6 +411,11 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
         }
+        if !self.options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(InsertStatement);
@@ -467,6 +577,8 @@ pub struct UpdateStatement<T: AstInfo> {
     pub assignments: Vec<Assignment<T>>,
     /// WHERE
     pub selection: Option<Expr<T>>,
+    /// `WITH`
+    pub options: Vec<WriteStatementOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for UpdateStatement<T> {
@@ -485,6 +597,11 @@ impl<T: AstInfo> AstDisplay for UpdateStatement<T> {
             f.write_str(" WHERE ");
             f.write_node(selection);
         }
+        if !self.options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(UpdateStatement);
@@ -500,6 +617,8 @@ pub struct DeleteStatement<T: AstInfo> {
     pub using: Vec<TableWithJoins<T>>,
     /// `WHERE`
     pub selection: Option<Expr<T>>,
+    /// `WITH`
+    pub options: Vec<WriteStatementOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
@@ -518,6 +637,11 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
             f.write_str(" WHERE ");
             f.write_node(selection);
         }
+        if !self.options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(DeleteStatement);
@@ -1468,6 +1592,10 @@ impl_display_t!(CreateTableStatement);
 pub enum TableOptionName {
     // The `RETAIN HISTORY` option
     RetainHistory,
+    /// The `CHANGE AUDIT` option
+    ChangeAudit,
+    /// The `ROW COUNT HINT` option
+    RowCountHint,
     /// A special option to test that we do redact values.
     RedactedTest,
 }
@@ -1478,6 +1606,12 @@ impl AstDisplay for TableOptionName {
             TableOptionName::RetainHistory => {
                 f.write_str("RETAIN HISTORY");
             }
+            TableOptionName::ChangeAudit => {
+                f.write_str("CHANGE AUDIT");
+            }
+            TableOptionName::RowCountHint => {
+                f.write_str("ROW COUNT HINT");
+            }
             TableOptionName::RedactedTest => {
                 f.write_str("REDACTED");
             }
@@ -1494,6 +1628,8 @@ impl WithOptionName for TableOptionName {
     fn redact_value(&self) -> bool {
         match self {
             TableOptionName::RetainHistory => false,
+            TableOptionName::ChangeAudit => false,
+            TableOptionName::RowCountHint => false,
             TableOptionName::RedactedTest => true,
         }
     }
@@ -1559,6 +1695,8 @@ pub struct CreateIndexStatement<T: AstInfo> {
     /// key_parts will be inferred from the named object.
     pub key_parts: Option<Vec<Expr<T>>>,
     pub with_options: Vec<IndexOption<T>>,
+    /// The predicate of a partial index, which only contains the rows that satisfy it.
+    pub predicate: Option<Expr<T>>,
     pub if_not_exists: bool,
 }
 
@@ -1593,6 +1731,10 @@ impl<T: AstInfo> AstDisplay for CreateIndexStatement<T> {
             f.write_node(&display::comma_separated(&self.with_options));
             f.write_str(")");
         }
+        if let Some(predicate) = &self.predicate {
+            f.write_str(" WHERE ");
+            f.write_node(predicate);
+        }
     }
 }
 impl_display_t!(CreateIndexStatement);
@@ -2053,6 +2195,82 @@ impl<T: AstInfo> AstDisplay for AlterClusterStatement<T> {
 }
 impl_display_t!(AlterClusterStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DatabaseOptionName {
+    /// The `DEFAULT CLUSTER [[=] <cluster>]` option.
+    DefaultCluster,
+}
+
+impl AstDisplay for DatabaseOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            DatabaseOptionName::DefaultCluster => f.write_str("DEFAULT CLUSTER"),
+        }
+    }
+}
+
+impl WithOptionName for DatabaseOptionName {
+    /// # WARNING
+    ///
+    /// Whenever implementing this trait consider very carefully whether or not
+    /// this value could contain sensitive user data. If you're uncertain, err
+    /// on the conservative side and return `true`.
+    fn redact_value(&self) -> bool {
+        match self {
+            DatabaseOptionName::DefaultCluster => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in an `ALTER DATABASE` statement.
+pub struct DatabaseOption<T: AstInfo> {
+    pub name: DatabaseOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+impl_display_for_with_option!(DatabaseOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterDatabaseAction<T: AstInfo> {
+    SetOptions(Vec<DatabaseOption<T>>),
+    ResetOptions(Vec<DatabaseOptionName>),
+}
+
+/// `ALTER DATABASE .. SET ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterDatabaseStatement<T: AstInfo> {
+    /// The `IF EXISTS` option.
+    pub if_exists: bool,
+    /// Name of the altered database.
+    pub name: UnresolvedDatabaseName,
+    /// The action.
+    pub action: AlterDatabaseAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterDatabaseStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER DATABASE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" ");
+        match &self.action {
+            AlterDatabaseAction::SetOptions(options) => {
+                f.write_str("SET (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
+            AlterDatabaseAction::ResetOptions(options) => {
+                f.write_str("RESET (");
+                f.write_node(&display::comma_separated(options));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(AlterDatabaseStatement);
+
 /// `CREATE CLUSTER REPLICA ..`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateClusterReplicaStatement<T: AstInfo> {
@@ -2759,6 +2977,48 @@ impl<T: AstInfo> AstDisplay for AlterTableAddColumnStatement<T> {
 
 impl_display_t!(AlterTableAddColumnStatement);
 
+/// `ALTER TABLE ... [RE]SET (ROW COUNT HINT [...])`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableRowCountHintStatement<T: AstInfo> {
+    pub if_exists: bool,
+    pub name: UnresolvedItemName,
+    /// The hinted row count, or `None` to reset the hint.
+    pub hint: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableRowCountHintStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER TABLE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        if let Some(hint) = &self.hint {
+            f.write_str(" SET (ROW COUNT HINT = ");
+            f.write_node(hint);
+        } else {
+            f.write_str(" RESET (ROW COUNT HINT");
+        }
+        f.write_str(")");
+    }
+}
+impl_display_t!(AlterTableRowCountHintStatement);
+
+/// `CANCEL QUERY <connection_id>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CancelStatement {
+    /// The ID of the connection whose query to cancel.
+    pub connection_id: u32,
+}
+
+impl AstDisplay for CancelStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CANCEL QUERY ");
+        f.write_str(self.connection_id);
+    }
+}
+impl_display!(CancelStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -2940,6 +3200,17 @@ impl AstDisplay for InspectShardStatement {
 }
 impl_display!(InspectShardStatement);
 
+/// `SHOW TRANSACTION WRITES`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowTransactionWritesStatement;
+
+impl AstDisplay for ShowTransactionWritesStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW TRANSACTION WRITES");
+    }
+}
+impl_display!(ShowTransactionWritesStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ShowObjectType<T: AstInfo> {
     MaterializedView {
@@ -3127,6 +3398,25 @@ impl<T: AstInfo> AstDisplay for ShowColumnsStatement<T> {
 }
 impl_display_t!(ShowColumnsStatement);
 
+/// `SHOW FRONTIERS FOR <object>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowFrontiersStatement<T: AstInfo> {
+    pub object_name: T::ItemName,
+    pub filter: Option<ShowStatementFilter<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ShowFrontiersStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW FRONTIERS FOR ");
+        f.write_node(&self.object_name);
+        if let Some(filter) = &self.filter {
+            f.write_str(" ");
+            f.write_node(filter);
+        }
+    }
+}
+impl_display_t!(ShowFrontiersStatement);
+
 /// `SHOW CREATE VIEW <view>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ShowCreateViewStatement<T: AstInfo> {
@@ -3310,6 +3600,12 @@ impl_display!(RollbackStatement);
 pub enum SubscribeOptionName {
     Snapshot,
     Progress,
+    AfterToken,
+    AwsConnection,
+    Format,
+    MaxFileSize,
+    OrderBy,
+    Deduplicate,
 }
 
 impl AstDisplay for SubscribeOptionName {
@@ -3317,6 +3613,12 @@ impl AstDisplay for SubscribeOptionName {
         match self {
             SubscribeOptionName::Snapshot => f.write_str("SNAPSHOT"),
             SubscribeOptionName::Progress => f.write_str("PROGRESS"),
+            SubscribeOptionName::AfterToken => f.write_str("AFTER TOKEN"),
+            SubscribeOptionName::AwsConnection => f.write_str("AWS CONNECTION"),
+            SubscribeOptionName::Format => f.write_str("FORMAT"),
+            SubscribeOptionName::MaxFileSize => f.write_str("MAX FILE SIZE"),
+            SubscribeOptionName::OrderBy => f.write_str("ORDER BY"),
+            SubscribeOptionName::Deduplicate => f.write_str("DEDUPLICATE"),
         }
     }
 }
@@ -3330,7 +3632,14 @@ impl WithOptionName for SubscribeOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            SubscribeOptionName::Snapshot | SubscribeOptionName::Progress => false,
+            SubscribeOptionName::Snapshot
+            | SubscribeOptionName::Progress
+            | SubscribeOptionName::AfterToken
+            | SubscribeOptionName::AwsConnection
+            | SubscribeOptionName::Format
+            | SubscribeOptionName::MaxFileSize
+            | SubscribeOptionName::OrderBy
+            | SubscribeOptionName::Deduplicate => false,
         }
     }
 }
@@ -3347,6 +3656,8 @@ impl_display_t!(SubscribeOption);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubscribeStatement<T: AstInfo> {
     pub relation: SubscribeRelation<T>,
+    /// `INTO <expr>`: the S3 path to archive the updates to, instead of returning them.
+    pub into: Option<Expr<T>>,
     pub options: Vec<SubscribeOption<T>>,
     pub as_of: Option<AsOf<T>>,
     pub up_to: Option<Expr<T>>,
@@ -3357,6 +3668,10 @@ impl<T: AstInfo> AstDisplay for SubscribeStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("SUBSCRIBE ");
         f.write_node(&self.relation);
+        if let Some(into) = &self.into {
+            f.write_str(" INTO ");
+            f.write_node(into);
+        }
         if !self.options.is_empty() {
             f.write_str(" WITH (");
             f.write_node(&display::comma_separated(&self.options));
@@ -3379,6 +3694,8 @@ impl_display_t!(SubscribeStatement);
 pub enum SubscribeRelation<T: AstInfo> {
     Name(T::ItemName),
     Query(Query<T>),
+    /// `(TO a, b, c)`: multiple relations that are subscribed to together.
+    Names(Vec<T::ItemName>),
 }
 
 impl<T: AstInfo> AstDisplay for SubscribeRelation<T> {
@@ -3390,6 +3707,11 @@ impl<T: AstInfo> AstDisplay for SubscribeRelation<T> {
                 f.write_node(query);
                 f.write_str(")");
             }
+            SubscribeRelation::Names(names) => {
+                f.write_str("(TO ");
+                f.write_node(&display::comma_separated(names));
+                f.write_str(")");
+            }
         }
     }
 }
@@ -3397,6 +3719,8 @@ impl_display_t!(SubscribeRelation);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainPlanStatement<T: AstInfo> {
+    /// The types of the parameter placeholders to plan the explainee with, if any.
+    pub parameters: Vec<T::DataType>,
     pub stage: Option<ExplainStage>,
     pub with_options: Vec<ExplainPlanOption<T>>,
     pub format: Option<ExplainFormat>,
@@ -3409,13 +3733,22 @@ impl<T: AstInfo> ExplainPlanStatement<T> {
     }
 
     pub fn format(&self) -> ExplainFormat {
-        self.format.unwrap_or(ExplainFormat::Text)
+        match self.stage {
+            // Bundles are meant to be consumed by tools, so they default to JSON.
+            Some(ExplainStage::Bundle) => self.format.unwrap_or(ExplainFormat::Json),
+            _ => self.format.unwrap_or(ExplainFormat::Text),
+        }
     }
 }
 
 impl<T: AstInfo> AstDisplay for ExplainPlanStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("EXPLAIN");
+        if !self.parameters.is_empty() {
+            f.write_str(" (PARAMETERS (");
+            f.write_node(&display::comma_separated(&self.parameters));
+            f.write_str("))");
+        }
         if let Some(stage) = &self.stage {
             f.write_str(" ");
             f.write_node(stage);
@@ -3445,11 +3778,13 @@ pub enum ExplainPlanOptionName {
     Arity,
     Cardinality,
     ColumnNames,
+    Comments,
     FilterPushdown,
     HumanizedExpressions,
     JoinImplementations,
     Keys,
     LinearChains,
+    MemoryEstimates,
     NonNegative,
     NoFastPath,
     NoNotices,
@@ -3458,6 +3793,8 @@ pub enum ExplainPlanOptionName {
     RawSyntax,
     Raw, // Listed after the `Raw~` variants to keep the parser happy!
     Redacted,
+    RowEstimates,
+    SubqueryLowering,
     SubtreeSize,
     Timing,
     Types,
@@ -3480,11 +3817,13 @@ impl WithOptionName for ExplainPlanOptionName {
             Self::Arity
             | Self::Cardinality
             | Self::ColumnNames
+            | Self::Comments
             | Self::FilterPushdown
             | Self::HumanizedExpressions
             | Self::JoinImplementations
             | Self::Keys
             | Self::LinearChains
+            | Self::MemoryEstimates
             | Self::NonNegative
             | Self::NoFastPath
             | Self::NoNotices
@@ -3493,6 +3832,8 @@ impl WithOptionName for ExplainPlanOptionName {
             | Self::RawSyntax
             | Self::Raw
             | Self::Redacted
+            | Self::RowEstimates
+            | Self::SubqueryLowering
             | Self::SubtreeSize
             | Self::Timing
             | Self::Types
@@ -3556,6 +3897,23 @@ impl<T: AstInfo> AstDisplay for ExplainPushdownStatement<T> {
 }
 impl_display_t!(ExplainPushdownStatement);
 
+/// `EXPLAIN ANALYZE MATERIALIZED VIEW mv ON REPLICA r`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainAnalyzeStatement<T: AstInfo> {
+    pub materialized_view: T::ItemName,
+    pub replica: Ident,
+}
+
+impl<T: AstInfo> AstDisplay for ExplainAnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN ANALYZE MATERIALIZED VIEW ");
+        f.write_node(&self.materialized_view);
+        f.write_str(" ON REPLICA ");
+        f.write_node(&self.replica);
+    }
+}
+impl_display_t!(ExplainAnalyzeStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainTimestampStatement<T: AstInfo> {
     pub format: Option<ExplainFormat>,
@@ -3564,7 +3922,11 @@ pub struct ExplainTimestampStatement<T: AstInfo> {
 
 impl<T: AstInfo> ExplainTimestampStatement<T> {
     pub fn format(&self) -> ExplainFormat {
-        self.format.unwrap_or(ExplainFormat::Text)
+        match self.stage {
+            // Bundles are meant to be consumed by tools, so they default to JSON.
+            Some(ExplainStage::Bundle) => self.format.unwrap_or(ExplainFormat::Json),
+            _ => self.format.unwrap_or(ExplainFormat::Text),
+        }
     }
 }
 
@@ -4032,6 +4394,10 @@ pub enum ExplainStage {
     Trace,
     /// Insights about the plan
     PlanInsights,
+    /// A support bundle with the complete context of the plan
+    Bundle,
+    /// The number of rows that each stage of the plan yields
+    Emptiness,
 }
 
 impl ExplainStage {
@@ -4046,6 +4412,8 @@ impl ExplainStage {
             Self::PhysicalPlan => Some(smallvec![Physical]),
             Self::Trace => None,
             Self::PlanInsights => Some(smallvec![Raw, Global, FastPath]),
+            Self::Bundle => None,
+            Self::Emptiness => None,
         }
     }
 
@@ -4060,6 +4428,8 @@ impl ExplainStage {
             Self::PhysicalPlan => true,
             Self::Trace => false,
             Self::PlanInsights => false,
+            Self::Bundle => false,
+            Self::Emptiness => false,
         }
     }
 }
@@ -4074,6 +4444,8 @@ impl AstDisplay for ExplainStage {
             Self::PhysicalPlan => f.write_str("PHYSICAL PLAN"),
             Self::Trace => f.write_str("OPTIMIZER TRACE"),
             Self::PlanInsights => f.write_str("PLAN INSIGHTS"),
+            Self::Bundle => f.write_str("BUNDLE"),
+            Self::Emptiness => f.write_str("EMPTINESS"),
         }
     }
 }
@@ -4133,6 +4505,9 @@ pub enum Explainee<T: AstInfo> {
     CreateView(Box<CreateViewStatement<T>>, bool),
     CreateMaterializedView(Box<CreateMaterializedViewStatement<T>>, bool),
     CreateIndex(Box<CreateIndexStatement<T>>, bool),
+    Subscribe(Box<SubscribeStatement<T>>, bool),
+    /// The statement of a prepared statement, with its parameters bound.
+    Execute(ExecuteStatement<T>),
 }
 
 impl<T: AstInfo> Explainee<T> {
@@ -4193,6 +4568,13 @@ impl<T: AstInfo> AstDisplay for Explainee<T> {
                 }
                 f.write_node(statement);
             }
+            Self::Subscribe(statement, broken) => {
+                if *broken {
+                    f.write_str("BROKEN ");
+                }
+                f.write_node(statement);
+            }
+            Self::Execute(statement) => f.write_node(statement),
         }
     }
 }
@@ -4256,15 +4638,47 @@ impl AstDisplay for CloseStatement {
 }
 impl_display!(CloseStatement);
 
+/// `HANDOFF CURSOR ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HandoffCursorStatement {
+    pub name: Ident,
+}
+
+impl AstDisplay for HandoffCursorStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("HANDOFF CURSOR ");
+        f.write_node(&self.name);
+    }
+}
+impl_display!(HandoffCursorStatement);
+
+/// `CLAIM HANDOFF '<token>'`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClaimHandoffStatement {
+    /// The token returned by the `HANDOFF CURSOR` statement.
+    pub token: String,
+}
+
+impl AstDisplay for ClaimHandoffStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CLAIM HANDOFF '");
+        f.write_node(&display::escape_single_quote_string(&self.token));
+        f.write_str("'");
+    }
+}
+impl_display!(ClaimHandoffStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FetchOptionName {
     Timeout,
+    Progress,
 }
 
 impl AstDisplay for FetchOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             FetchOptionName::Timeout => "TIMEOUT",
+            FetchOptionName::Progress => "PROGRESS",
         })
     }
 }
@@ -4277,7 +4691,7 @@ impl WithOptionName for FetchOptionName {
     /// on the conservative side and return `true`.
     fn redact_value(&self) -> bool {
         match self {
-            FetchOptionName::Timeout => false,
+            FetchOptionName::Timeout | FetchOptionName::Progress => false,
         }
     }
 }
@@ -4462,6 +4876,78 @@ impl AstDisplay for AlterSystemResetAllStatement {
 }
 impl_display!(AlterSystemResetAllStatement);
 
+/// `ALTER SYSTEM TERMINATE SESSIONS WHERE ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSystemTerminateSessionsStatement {
+    /// The filters that a session must all match to be terminated.
+    pub filters: Vec<SessionFilter>,
+}
+
+impl AstDisplay for AlterSystemTerminateSessionsStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SYSTEM TERMINATE SESSIONS WHERE ");
+        f.write_node(&display::separated(&self.filters, " AND "));
+    }
+}
+impl_display!(AlterSystemTerminateSessionsStatement);
+
+/// A filter on the sessions of `ALTER SYSTEM TERMINATE SESSIONS`, like `role = 'name'`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionFilter {
+    pub field: SessionFilterField,
+    pub op: SessionFilterOp,
+    pub value: String,
+}
+
+impl AstDisplay for SessionFilter {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.field);
+        f.write_node(&self.op);
+        f.write_str("'");
+        f.write_node(&display::escape_single_quote_string(&self.value));
+        f.write_str("'");
+    }
+}
+impl_display!(SessionFilter);
+
+/// The property of a session that a [`SessionFilter`] matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionFilterField {
+    /// The name of the role that the session authenticated as.
+    Role,
+    /// The `application_name` of the session.
+    ApplicationName,
+}
+
+impl AstDisplay for SessionFilterField {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SessionFilterField::Role => "role",
+            SessionFilterField::ApplicationName => "application_name",
+        });
+    }
+}
+impl_display!(SessionFilterField);
+
+/// How a [`SessionFilter`] compares the property of a session to its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionFilterOp {
+    /// The property equals the value.
+    Eq,
+    /// The property matches the value as a `LIKE` pattern.
+    Like,
+}
+
+impl AstDisplay for SessionFilterOp {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SessionFilterOp::Eq => " = ",
+            SessionFilterOp::Like => " LIKE ",
+        });
+    }
+}
+impl_display!(SessionFilterOp);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AsOf<T: AstInfo> {
     At(Expr<T>),
@@ -4486,6 +4972,7 @@ impl_display_t!(AsOf);
 pub enum ShowStatement<T: AstInfo> {
     ShowObjects(ShowObjectsStatement<T>),
     ShowColumns(ShowColumnsStatement<T>),
+    ShowFrontiers(ShowFrontiersStatement<T>),
     ShowCreateView(ShowCreateViewStatement<T>),
     ShowCreateMaterializedView(ShowCreateMaterializedViewStatement<T>),
     ShowCreateSource(ShowCreateSourceStatement<T>),
@@ -4495,6 +4982,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
     ShowCreateCluster(ShowCreateClusterStatement<T>),
     ShowVariable(ShowVariableStatement),
+    ShowTransactionWrites(ShowTransactionWritesStatement),
     InspectShard(InspectShardStatement),
 }
 
@@ -4503,6 +4991,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
         match self {
             ShowStatement::ShowObjects(stmt) => f.write_node(stmt),
             ShowStatement::ShowColumns(stmt) => f.write_node(stmt),
+            ShowStatement::ShowFrontiers(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateMaterializedView(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateSource(stmt) => f.write_node(stmt),
@@ -4512,6 +5001,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateCluster(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
+            ShowStatement::ShowTransactionWrites(stmt) => f.write_node(stmt),
             ShowStatement::InspectShard(stmt) => f.write_node(stmt),
         }
     }
@@ -384,6 +384,9 @@ impl<'a> Parser<'a> {
         match self.next_token() {
             Some(t) => match t {
                 Token::Keyword(CREATE) => Ok(self.parse_create()?),
+                Token::Keyword(CANCEL) => {
+                    Ok(self.parse_cancel().map_parser_err(StatementKind::Cancel)?)
+                }
                 Token::Keyword(DISCARD) => Ok(self
                     .parse_discard()
                     .map_parser_err(StatementKind::Discard)?),
@@ -435,6 +438,12 @@ impl<'a> Parser<'a> {
                 Token::Keyword(CLOSE) => {
                     Ok(self.parse_close().map_parser_err(StatementKind::Close)?)
                 }
+                Token::Keyword(HANDOFF) => Ok(self
+                    .parse_handoff_cursor()
+                    .map_parser_err(StatementKind::HandoffCursor)?),
+                Token::Keyword(CLAIM) => Ok(self
+                    .parse_claim_handoff()
+                    .map_parser_err(StatementKind::ClaimHandoff)?),
                 Token::Keyword(PREPARE) => Ok(self.parse_prepare()?),
                 Token::Keyword(EXECUTE) => Ok(self
                     .parse_execute()
@@ -476,7 +485,9 @@ impl<'a> Parser<'a> {
                     self.prev_token();
                     Ok(Statement::Select(SelectStatement {
                         query: self.parse_query().map_parser_err(StatementKind::Select)?,
-                        as_of: None, // Only the outermost SELECT may have an AS OF clause.
+                        // Only the outermost SELECT may have options or an AS OF clause.
+                        options: vec![],
+                        as_of: None,
                     }))
                 }
                 unexpected => self
@@ -3397,6 +3408,11 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
+        if self.parse_keyword(WEBHOOK) {
+            let url = self.parse_literal_string()?;
+            return Ok(CreateSinkConnection::Webhook { url });
+        }
+
         self.expect_keyword(KAFKA)?;
         self.expect_keyword(CONNECTION)?;
 
@@ -3659,12 +3675,19 @@ impl<'a> Parser<'a> {
             vec![]
         };
 
+        let predicate = if self.parse_keyword(WHERE) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
         Ok(Statement::CreateIndex(CreateIndexStatement {
             name,
             in_cluster,
             on_name,
             key_parts,
             with_options,
+            predicate,
             if_not_exists,
         }))
     }
@@ -3675,6 +3698,12 @@ impl<'a> Parser<'a> {
         if self.parse_keyword(REDACTED) {
             return Ok(TableOptionName::RedactedTest);
         }
+        if self.parse_keywords(&[CHANGE, AUDIT]) {
+            return Ok(TableOptionName::ChangeAudit);
+        }
+        if self.parse_keywords(&[ROW, COUNT, HINT]) {
+            return Ok(TableOptionName::RowCountHint);
+        }
         self.expect_keywords(&[RETAIN, HISTORY])?;
         Ok(TableOptionName::RetainHistory)
     }
@@ -3683,7 +3712,9 @@ impl<'a> Parser<'a> {
         let name = self.parse_table_option_name()?;
         let value = match name {
             TableOptionName::RetainHistory => self.parse_option_retain_history(),
-            TableOptionName::RedactedTest => self.parse_optional_option_value(),
+            TableOptionName::ChangeAudit
+            | TableOptionName::RowCountHint
+            | TableOptionName::RedactedTest => self.parse_optional_option_value(),
         }?;
         Ok(TableOption { name, value })
     }
@@ -4135,6 +4166,16 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_cancel(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(QUERY)?;
+        let pos = self.peek_pos();
+        let connection_id = self
+            .parse_literal_uint()?
+            .try_into()
+            .map_err(|_| ParserError::new(pos, "Expected a 32-bit unsigned integer"))?;
+        Ok(Statement::Cancel(CancelStatement { connection_id }))
+    }
+
     fn parse_discard(&mut self) -> Result<Statement<Raw>, ParserError> {
         let target = match self.expect_one_of_keywords(&[ALL, PLANS, SEQUENCES, TEMP, TEMPORARY])? {
             ALL => DiscardTarget::All,
@@ -4715,15 +4756,29 @@ impl<'a> Parser<'a> {
                     _ => unreachable!(),
                 }
             }
-            ObjectType::Database => {
-                let if_exists = self
-                    .parse_if_exists()
-                    .map_parser_err(StatementKind::AlterOwner)?;
-                let name = UnresolvedObjectName::Database(
-                    self.parse_database_name()
-                        .map_parser_err(StatementKind::AlterOwner)?,
-                );
-                self.expect_keywords(&[OWNER, TO])
+            ObjectType::Database => self.parse_alter_database(object_type),
+            ObjectType::Schema => self.parse_alter_schema(object_type),
+            ObjectType::Func | ObjectType::Subsource => parser_err!(
+                self,
+                self.peek_prev_pos(),
+                format!("Unsupported ALTER on {object_type}")
+            )
+            .map_no_statement_parser_err(),
+        }
+    }
+
+    fn parse_alter_database(
+        &mut self,
+        object_type: ObjectType,
+    ) -> Result<Statement<Raw>, ParserStatementError> {
+        let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
+        let name = self.parse_database_name().map_no_statement_parser_err()?;
+        let action = self
+            .expect_one_of_keywords(&[OWNER, RESET, SET])
+            .map_no_statement_parser_err()?;
+        match action {
+            OWNER => {
+                self.expect_keyword(TO)
                     .map_parser_err(StatementKind::AlterOwner)?;
                 let new_owner = self
                     .parse_identifier()
@@ -4731,20 +4786,53 @@ impl<'a> Parser<'a> {
                 Ok(Statement::AlterOwner(AlterOwnerStatement {
                     object_type,
                     if_exists,
-                    name,
+                    name: UnresolvedObjectName::Database(name),
                     new_owner,
                 }))
             }
-            ObjectType::Schema => self.parse_alter_schema(object_type),
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
-                self,
-                self.peek_prev_pos(),
-                format!("Unsupported ALTER on {object_type}")
-            )
-            .map_no_statement_parser_err(),
+            RESET => {
+                self.expect_token(&Token::LParen)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                let names = self
+                    .parse_comma_separated(Parser::parse_database_option_name)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                self.expect_token(&Token::RParen)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                Ok(Statement::AlterDatabase(AlterDatabaseStatement {
+                    if_exists,
+                    name,
+                    action: AlterDatabaseAction::ResetOptions(names),
+                }))
+            }
+            SET => {
+                self.expect_token(&Token::LParen)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                let options = self
+                    .parse_comma_separated(Parser::parse_database_option)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                self.expect_token(&Token::RParen)
+                    .map_parser_err(StatementKind::AlterDatabase)?;
+                Ok(Statement::AlterDatabase(AlterDatabaseStatement {
+                    if_exists,
+                    name,
+                    action: AlterDatabaseAction::SetOptions(options),
+                }))
+            }
+            _ => unreachable!(),
         }
     }
 
+    fn parse_database_option_name(&mut self) -> Result<DatabaseOptionName, ParserError> {
+        self.expect_keywords(&[DEFAULT, CLUSTER])?;
+        Ok(DatabaseOptionName::DefaultCluster)
+    }
+
+    fn parse_database_option(&mut self) -> Result<DatabaseOption<Raw>, ParserError> {
+        let name = self.parse_database_option_name()?;
+        let value = self.parse_optional_option_value()?;
+        Ok(DatabaseOption { name, value })
+    }
+
     fn parse_alter_cluster(
         &mut self,
         object_type: ObjectType,
@@ -5229,7 +5317,7 @@ impl<'a> Parser<'a> {
     /// Parse an ALTER SYSTEM statement.
     fn parse_alter_system(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
         match self
-            .expect_one_of_keywords(&[SET, RESET])
+            .expect_one_of_keywords(&[SET, RESET, TERMINATE])
             .map_no_statement_parser_err()?
         {
             SET => {
@@ -5260,10 +5348,45 @@ impl<'a> Parser<'a> {
                     }))
                 }
             }
+            TERMINATE => self
+                .parse_alter_system_terminate_sessions()
+                .map_parser_err(StatementKind::AlterSystemTerminateSessions),
             _ => unreachable!(),
         }
     }
 
+    fn parse_alter_system_terminate_sessions(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keywords(&[SESSIONS, WHERE])?;
+        let mut filters = vec![];
+        loop {
+            filters.push(self.parse_session_filter()?);
+            if !self.parse_keyword(AND) {
+                break;
+            }
+        }
+        Ok(Statement::AlterSystemTerminateSessions(
+            AlterSystemTerminateSessionsStatement { filters },
+        ))
+    }
+
+    fn parse_session_filter(&mut self) -> Result<SessionFilter, ParserError> {
+        let pos = self.peek_pos();
+        let field = match self.parse_identifier()?.as_str() {
+            "role" => SessionFilterField::Role,
+            "application_name" => SessionFilterField::ApplicationName,
+            _ => return parser_err!(self, pos, "Expected one of role or application_name"),
+        };
+        let op = if self.consume_token(&Token::Eq) {
+            SessionFilterOp::Eq
+        } else if self.parse_keyword(LIKE) {
+            SessionFilterOp::Like
+        } else {
+            return self.expected(self.peek_pos(), "= or LIKE", self.peek_token());
+        };
+        let value = self.parse_literal_string()?;
+        Ok(SessionFilter { field, op, value })
+    }
+
     fn parse_alter_connection(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
         let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
         let name = self.parse_item_name().map_no_statement_parser_err()?;
@@ -5496,6 +5619,22 @@ impl<'a> Parser<'a> {
                 } else {
                     self.expect_token(&Token::LParen)
                         .map_no_statement_parser_err()?;
+                    if object_type == ObjectType::Table && self.parse_keywords(&[ROW, COUNT, HINT])
+                    {
+                        let _ = self.consume_token(&Token::Eq);
+                        let hint = self
+                            .parse_option_value()
+                            .map_parser_err(StatementKind::AlterTableRowCountHint)?;
+                        self.expect_token(&Token::RParen)
+                            .map_parser_err(StatementKind::AlterTableRowCountHint)?;
+                        return Ok(Statement::AlterTableRowCountHint(
+                            AlterTableRowCountHintStatement {
+                                if_exists,
+                                name,
+                                hint: Some(hint),
+                            },
+                        ));
+                    }
                     self.expect_keywords(&[RETAIN, HISTORY])
                         .map_parser_err(StatementKind::AlterRetainHistory)?;
                     let history = self
@@ -5514,6 +5653,17 @@ impl<'a> Parser<'a> {
             RESET => {
                 self.expect_token(&Token::LParen)
                     .map_no_statement_parser_err()?;
+                if object_type == ObjectType::Table && self.parse_keywords(&[ROW, COUNT, HINT]) {
+                    self.expect_token(&Token::RParen)
+                        .map_parser_err(StatementKind::AlterTableRowCountHint)?;
+                    return Ok(Statement::AlterTableRowCountHint(
+                        AlterTableRowCountHintStatement {
+                            if_exists,
+                            name,
+                            hint: None,
+                        },
+                    ));
+                }
                 self.expect_keywords(&[RETAIN, HISTORY])
                     .map_parser_err(StatementKind::AlterRetainHistory)?;
                 self.expect_token(&Token::RParen)
@@ -6389,23 +6539,76 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
+        let options = self.parse_write_statement_options()?;
 
         Ok(Statement::Delete(DeleteStatement {
             table_name,
             alias,
             using,
             selection,
+            options,
         }))
     }
 
+    /// Parses the optional `WITH (...)` options of an `INSERT`, `UPDATE` or
+    /// `DELETE` statement.
+    fn parse_write_statement_options(
+        &mut self,
+    ) -> Result<Vec<WriteStatementOption<Raw>>, ParserError> {
+        Ok(if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Self::parse_write_statement_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        })
+    }
+
+    fn parse_write_statement_option(&mut self) -> Result<WriteStatementOption<Raw>, ParserError> {
+        self.expect_keywords(&[DRY, RUN])?;
+        Ok(WriteStatementOption {
+            name: WriteStatementOptionName::DryRun,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     /// Parses a SELECT (or WITH, VALUES, TABLE) statement with optional AS OF.
     fn parse_select_statement(&mut self) -> Result<SelectStatement<Raw>, ParserError> {
+        let query = self.parse_query()?;
+        let options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Self::parse_select_statement_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
         Ok(SelectStatement {
-            query: self.parse_query()?,
+            query,
+            options,
             as_of: self.parse_optional_as_of()?,
         })
     }
 
+    fn parse_select_statement_option(&mut self) -> Result<SelectStatementOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[AFTER, MIRROR])? {
+            AFTER => {
+                self.expect_keyword(TOKEN)?;
+                SelectStatementOptionName::AfterToken
+            }
+            MIRROR => {
+                self.expect_keywords(&[TO, CLUSTER])?;
+                SelectStatementOptionName::MirrorToCluster
+            }
+            _ => unreachable!(),
+        };
+        Ok(SelectStatementOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     /// Parse a query expression, i.e. a `SELECT` statement optionally
     /// preceded with some `WITH` CTE declarations and optionally followed
     /// by `ORDER BY`. Unlike some other parse_... methods, this one doesn't
@@ -6947,6 +7150,13 @@ impl<'a> Parser<'a> {
     fn parse_show(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
         if self.parse_one_of_keywords(&[COLUMNS, FIELDS]).is_some() {
             self.parse_show_columns()
+        } else if self.parse_keywords(&[FRONTIERS, FOR]) {
+            let object_name = self.parse_raw_name()?;
+            let filter = self.parse_show_statement_filter()?;
+            Ok(ShowStatement::ShowFrontiers(ShowFrontiersStatement {
+                object_name,
+                filter,
+            }))
         } else if self.parse_keyword(OBJECTS) {
             let from = if self.parse_keywords(&[FROM]) {
                 Some(self.parse_schema_name()?)
@@ -7108,6 +7318,10 @@ impl<'a> Parser<'a> {
                     cluster_name: RawClusterName::Unresolved(self.parse_identifier()?),
                 },
             ))
+        } else if self.parse_keywords(&[TRANSACTION, WRITES]) {
+            Ok(ShowStatement::ShowTransactionWrites(
+                ShowTransactionWritesStatement,
+            ))
         } else {
             let variable = if self.parse_keywords(&[TRANSACTION, ISOLATION, LEVEL]) {
                 ident!("transaction_isolation")
@@ -7430,11 +7644,13 @@ impl<'a> Parser<'a> {
             InsertSource::Query(self.parse_query()?)
         };
         let returning = self.parse_returning()?;
+        let options = self.parse_write_statement_options()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
             returning,
+            options,
         }))
     }
 
@@ -7463,12 +7679,14 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
+        let options = self.parse_write_statement_options()?;
 
         Ok(Statement::Update(UpdateStatement {
             table_name,
             alias,
             assignments,
             selection,
+            options,
         }))
     }
 
@@ -7702,12 +7920,23 @@ impl<'a> Parser<'a> {
     fn parse_subscribe(&mut self) -> Result<Statement<Raw>, ParserError> {
         let _ = self.parse_keyword(TO);
         let relation = if self.consume_token(&Token::LParen) {
-            let query = self.parse_query()?;
-            self.expect_token(&Token::RParen)?;
-            SubscribeRelation::Query(query)
+            if self.parse_keyword(TO) {
+                let names = self.parse_comma_separated(Parser::parse_raw_name)?;
+                self.expect_token(&Token::RParen)?;
+                SubscribeRelation::Names(names)
+            } else {
+                let query = self.parse_query()?;
+                self.expect_token(&Token::RParen)?;
+                SubscribeRelation::Query(query)
+            }
         } else {
             SubscribeRelation::Name(self.parse_raw_name()?)
         };
+        let into = if self.parse_keyword(INTO) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
         let options = if self.parse_keyword(WITH) {
             self.expect_token(&Token::LParen)?;
             let options = self.parse_comma_separated(Self::parse_subscribe_option)?;
@@ -7739,6 +7968,7 @@ impl<'a> Parser<'a> {
         };
         Ok(Statement::Subscribe(SubscribeStatement {
             relation,
+            into,
             options,
             as_of,
             up_to,
@@ -7747,9 +7977,39 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_subscribe_option(&mut self) -> Result<SubscribeOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[PROGRESS, SNAPSHOT])? {
+        let name = match self.expect_one_of_keywords(&[
+            AFTER,
+            PROGRESS,
+            SNAPSHOT,
+            AWS,
+            FORMAT,
+            MAX,
+            ORDER,
+            DEDUPLICATE,
+        ])? {
+            AFTER => {
+                self.expect_keyword(TOKEN)?;
+                SubscribeOptionName::AfterToken
+            }
             PROGRESS => SubscribeOptionName::Progress,
             SNAPSHOT => SubscribeOptionName::Snapshot,
+            AWS => {
+                self.expect_keyword(CONNECTION)?;
+                return Ok(SubscribeOption {
+                    name: SubscribeOptionName::AwsConnection,
+                    value: Some(self.parse_object_option_value()?),
+                });
+            }
+            FORMAT => SubscribeOptionName::Format,
+            MAX => {
+                self.expect_keywords(&[FILE, SIZE])?;
+                SubscribeOptionName::MaxFileSize
+            }
+            ORDER => {
+                self.expect_keyword(BY)?;
+                SubscribeOptionName::OrderBy
+            }
+            DEDUPLICATE => SubscribeOptionName::Deduplicate,
             _ => unreachable!(),
         };
         Ok(SubscribeOption {
@@ -7767,6 +8027,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[FILTER, PUSHDOWN]) {
             self.parse_explain_pushdown()
                 .map_parser_err(StatementKind::ExplainPushdown)
+        } else if self.parse_keyword(ANALYZE) {
+            self.parse_explain_analyze()
+                .map_parser_err(StatementKind::ExplainAnalyze)
         } else if self.peek_keyword(KEY) || self.peek_keyword(VALUE) {
             self.parse_explain_schema()
                 .map_parser_err(StatementKind::ExplainSinkSchema)
@@ -7795,6 +8058,14 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[REPLAN, INDEX]) {
             // Parse: `REPLAN INDEX name`
             Explainee::ReplanIndex(self.parse_raw_name()?)
+        } else if self.parse_keyword(EXECUTE) {
+            // Parse: `EXECUTE name [(params, ...)]`
+            let stmt = match self.parse_execute()? {
+                Statement::Execute(stmt) => stmt,
+                _ => panic!("Unexpected statement type return after parsing"),
+            };
+
+            Explainee::Execute(stmt)
         } else {
             let broken = self.parse_keyword(BROKEN);
 
@@ -7831,6 +8102,14 @@ impl<'a> Parser<'a> {
                 };
 
                 Explainee::CreateIndex(Box::new(stmt), broken)
+            } else if self.parse_keyword(SUBSCRIBE) {
+                // Parse: `BROKEN? SUBSCRIBE ...`
+                let stmt = match self.parse_subscribe()? {
+                    Statement::Subscribe(stmt) => stmt,
+                    _ => panic!("Unexpected statement type return after parsing"),
+                };
+
+                Explainee::Subscribe(Box::new(stmt), broken)
             } else {
                 // Parse: `BROKEN? query`
                 let query = self.parse_select_statement()?;
@@ -7844,6 +8123,20 @@ impl<'a> Parser<'a> {
     /// has already been consumed.
     fn parse_explain_plan(&mut self) -> Result<Statement<Raw>, ParserError> {
         let start = self.peek_pos();
+        let parameters = if self.peek_token() == Some(Token::LParen)
+            && self.peek_nth_token(1) == Some(Token::Keyword(PARAMETERS))
+        {
+            // Parse: `(PARAMETERS (data_type, ...))`
+            self.expect_token(&Token::LParen)?;
+            self.expect_keyword(PARAMETERS)?;
+            self.expect_token(&Token::LParen)?;
+            let parameters = self.parse_comma_separated(Parser::parse_data_type)?;
+            self.expect_token(&Token::RParen)?;
+            self.expect_token(&Token::RParen)?;
+            parameters
+        } else {
+            vec![]
+        };
         let (has_stage, stage) = match self.parse_one_of_keywords(&[
             RAW,
             DECORRELATED,
@@ -7852,6 +8145,8 @@ impl<'a> Parser<'a> {
             PHYSICAL,
             OPTIMIZER,
             PLAN,
+            BUNDLE,
+            EMPTINESS,
         ]) {
             Some(RAW) => {
                 self.expect_keyword(PLAN)?;
@@ -7877,6 +8172,8 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(TRACE)?;
                 (true, Some(ExplainStage::Trace))
             }
+            Some(BUNDLE) => (true, Some(ExplainStage::Bundle)),
+            Some(EMPTINESS) => (true, Some(ExplainStage::Emptiness)),
             Some(PLAN) => {
                 if self.parse_keyword(INSIGHTS) {
                     (true, Some(ExplainStage::PlanInsights))
@@ -7931,6 +8228,7 @@ impl<'a> Parser<'a> {
         }
 
         Ok(Statement::ExplainPlan(ExplainPlanStatement {
+            parameters,
             stage,
             with_options,
             format,
@@ -7957,6 +8255,20 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `EXPLAIN ANALYZE` statement, assuming that the `EXPLAIN
+    /// ANALYZE` tokens have already been consumed.
+    fn parse_explain_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keywords(&[MATERIALIZED, VIEW])?;
+        let materialized_view = self.parse_raw_name()?;
+        self.expect_keywords(&[ON, REPLICA])?;
+        let replica = self.parse_identifier()?;
+
+        Ok(Statement::ExplainAnalyze(ExplainAnalyzeStatement {
+            materialized_view,
+            replica,
+        }))
+    }
+
     /// Parse an `EXPLAIN TIMESTAMP` statement, assuming that the `EXPLAIN
     /// TIMESTAMP` tokens have already been consumed.
     fn parse_explain_timestamp(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -8050,6 +8362,22 @@ impl<'a> Parser<'a> {
         Ok(Statement::Close(CloseStatement { name }))
     }
 
+    /// Parse a `HANDOFF CURSOR` statement, assuming that the `HANDOFF` token
+    /// has already been consumed.
+    fn parse_handoff_cursor(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(CURSOR)?;
+        let name = self.parse_identifier()?;
+        Ok(Statement::HandoffCursor(HandoffCursorStatement { name }))
+    }
+
+    /// Parse a `CLAIM HANDOFF` statement, assuming that the `CLAIM` token
+    /// has already been consumed.
+    fn parse_claim_handoff(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(HANDOFF)?;
+        let token = self.parse_literal_string()?;
+        Ok(Statement::ClaimHandoff(ClaimHandoffStatement { token }))
+    }
+
     /// Parse a `PREPARE` statement, assuming that the `PREPARE` token
     /// has already been consumed.
     fn parse_prepare(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
@@ -8132,9 +8460,13 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_fetch_option(&mut self) -> Result<FetchOption<Raw>, ParserError> {
-        self.expect_keyword(TIMEOUT)?;
+        let name = match self.expect_one_of_keywords(&[TIMEOUT, PROGRESS])? {
+            TIMEOUT => FetchOptionName::Timeout,
+            PROGRESS => FetchOptionName::Progress,
+            _ => unreachable!(),
+        };
         Ok(FetchOption {
-            name: FetchOptionName::Timeout,
+            name,
             value: self.parse_optional_option_value()?,
         })
     }
@@ -154,6 +154,11 @@ pub(crate) fn doc_subscribe<T: AstInfo>(v: &SubscribeStatement<T>) -> RcDoc {
     let doc = match &v.relation {
         SubscribeRelation::Name(name) => nest_title("SUBSCRIBE", doc_display_pass(name)),
         SubscribeRelation::Query(query) => bracket("SUBSCRIBE (", doc_query(query), ")"),
+        SubscribeRelation::Names(names) => bracket(
+            "SUBSCRIBE (",
+            RcDoc::concat([RcDoc::text("TO "), comma_separate(doc_display_pass, names)]),
+            ")",
+        ),
     };
     let mut docs = vec![doc];
     if !v.options.is_empty() {
@@ -301,11 +306,23 @@ pub(crate) fn doc_insert<T: AstInfo>(v: &InsertStatement<T>) -> RcDoc {
             nest_title("RETURNING", comma_separate(doc_select_item, &v.returning)),
         )
     }
+    if !v.options.is_empty() {
+        doc = intersperse_line_nest([
+            doc,
+            bracket("WITH (", comma_separate(doc_display_pass, &v.options), ")"),
+        ]);
+    }
     doc
 }
 
 pub(crate) fn doc_select_statement<T: AstInfo>(v: &SelectStatement<T>) -> RcDoc {
     let mut doc = doc_query(&v.query);
+    if !v.options.is_empty() {
+        doc = intersperse_line_nest([
+            doc,
+            bracket("WITH (", comma_separate(doc_display_pass, &v.options), ")"),
+        ]);
+    }
     if let Some(as_of) = &v.as_of {
         doc = intersperse_line_nest([doc, doc_as_of(as_of)]);
     }
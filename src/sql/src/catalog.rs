@@ -29,7 +29,7 @@ use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem, PrivilegeMap};
 use mz_repr::explain::ExprHumanizer;
 use mz_repr::role_id::RoleId;
 use mz_repr::{ColumnName, GlobalId, RelationDesc};
-use mz_sql_parser::ast::{Expr, QualifiedReplica, UnresolvedItemName};
+use mz_sql_parser::ast::{Expr, QualifiedReplica, Raw, Statement, UnresolvedItemName};
 use mz_storage_types::connections::inline::{ConnectionResolver, ReferencedConnection};
 use mz_storage_types::connections::{Connection, ConnectionContext};
 use mz_storage_types::sources::SourceDesc;
@@ -102,6 +102,10 @@ pub trait SessionCatalog: fmt::Debug + ExprHumanizer + Send + Sync + ConnectionR
     /// None if the prepared statement does not exist.
     fn get_prepared_statement_desc(&self, name: &str) -> Option<&StatementDesc>;
 
+    /// Returns the statement of the named prepared statement on the session, or
+    /// None if the prepared statement does not exist or is empty.
+    fn get_prepared_statement(&self, name: &str) -> Option<&Statement<Raw>>;
+
     /// Resolves the named database.
     ///
     /// If `database_name` exists in the catalog, it returns a reference to the
@@ -402,6 +406,12 @@ pub trait CatalogDatabase {
 
     /// Returns the privileges associated with the database.
     fn privileges(&self) -> &PrivilegeMap;
+
+    /// Returns the ID of the cluster in which objects created in the database
+    /// are placed when no cluster is specified, if any.
+    ///
+    /// The cluster might no longer exist.
+    fn default_cluster_id(&self) -> Option<ClusterId>;
 }
 
 /// A schema in a [`SessionCatalog`].
@@ -34,7 +34,9 @@ use crate::plan::expr::{
 };
 use crate::plan::query::{self, ExprContext, QueryContext};
 use crate::plan::scope::Scope;
-use crate::plan::side_effecting_func::PG_CATALOG_SEF_BUILTINS;
+use crate::plan::side_effecting_func::{
+    SideEffectingFuncImpl, MZ_INTERNAL_SEF_BUILTINS, PG_CATALOG_SEF_BUILTINS,
+};
 use crate::plan::transform_ast;
 use crate::plan::typeconv::{self, CastContext};
 use crate::session::vars::{self, ENABLE_TIME_AT_TIME_ZONE};
@@ -429,7 +431,11 @@ fn sql_impl_table_func_inner(
         .expect_element(|| "static function definition must have exactly one statement")
         .ast
     {
-        Statement::Select(SelectStatement { query, as_of: None }) => query,
+        Statement::Select(SelectStatement {
+            query,
+            options,
+            as_of: None,
+        }) if options.is_empty() => query,
         _ => panic!("static function definition expected SELECT statement"),
     };
     let invoke = move |qcx: &QueryContext, types: Vec<ScalarType>| {
@@ -3437,10 +3443,19 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         }
     };
 
-    // Add side-effecting functions, which are defined in a separate module
-    // using a restricted set of function definition features (e.g., no
-    // overloads) to make them easier to plan.
-    for sef_builtin in PG_CATALOG_SEF_BUILTINS.values() {
+    add_sef_builtins(&mut builtins, &PG_CATALOG_SEF_BUILTINS);
+
+    builtins
+});
+
+/// Adds side-effecting functions, which are defined in a separate module
+/// using a restricted set of function definition features (e.g., no
+/// overloads) to make them easier to plan.
+fn add_sef_builtins(
+    builtins: &mut BTreeMap<&'static str, Func>,
+    sef_builtins: &'static BTreeMap<u32, SideEffectingFuncImpl>,
+) {
+    for sef_builtin in sef_builtins.values() {
         builtins.insert(
             sef_builtin.name,
             Func::Scalar(vec![FuncImpl {
@@ -3461,9 +3476,7 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             }]),
         );
     }
-
-    builtins
-});
+}
 
 pub static INFORMATION_SCHEMA_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
     use ParamType::*;
@@ -3886,7 +3899,7 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
 pub static MZ_INTERNAL_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
     use ParamType::*;
     use ScalarBaseType::*;
-    builtins! {
+    let mut builtins = builtins! {
         "aclitem_grantor" => Scalar {
             params!(AclItem) => UnaryFunc::AclItemGrantor(func::AclItemGrantor) => Oid, oid::FUNC_ACL_ITEM_GRANTOR_OID;
         },
@@ -4171,6 +4184,42 @@ pub static MZ_INTERNAL_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(
                 ) AS i
             )") => ScalarType::Array(Box::new(ScalarType::String)), oid::FUNC_MZ_NORMALIZE_SCHEMA_NAME;
         },
+        // Whether the index, materialized view or source named by the argument is hydrated on at
+        // least one replica.
+        "mz_is_hydrated" => Scalar {
+            params!(String) => sql_impl_func("
+                CASE
+                WHEN $1 IS NULL THEN NULL
+                ELSE (
+                    mz_unsafe.mz_error_if_null(
+                        (
+                            SELECT h.hydrated
+                            FROM mz_internal.mz_object_hydration_statuses AS h
+                            JOIN mz_catalog.mz_objects AS o ON o.id = h.object_id
+                            WHERE o.oid = $1::regclass::oid
+                        ),
+                        'object \"' || $1 || '\" is not maintained by a dataflow'
+                    )
+                )
+                END
+            ") => Bool, oid::FUNC_MZ_IS_HYDRATED_OID;
+        },
+        // Returns the lifecycle events of the statement identified by the argument, which is a
+        // correlation id as reported by `emit_statement_correlation_id_notice`, i.e., a trace id
+        // and a statement id separated by a colon, or just a statement id. The time between
+        // `execution-began` and `optimization-finished` is the time spent in the optimizer.
+        "mz_statement_trace" => Table {
+            params!(String) => sql_impl_table_func("
+                SELECT
+                    l.statement_id,
+                    l.event_type,
+                    l.occurred_at,
+                    l.occurred_at - pg_catalog.lag(l.occurred_at) OVER (ORDER BY l.occurred_at)
+                        AS since_previous
+                FROM mz_internal.mz_statement_lifecycle_history AS l
+                WHERE l.statement_id = pg_catalog.regexp_replace($1, '^.*:', '')::pg_catalog.uuid
+            ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_STATEMENT_TRACE_OID;
+        },
         "mz_render_typmod" => Scalar {
             params!(Oid, Int32) => BinaryFunc::MzRenderTypmod => String, oid::FUNC_MZ_RENDER_TYPMOD_OID;
         },
@@ -4267,7 +4316,11 @@ pub static MZ_INTERNAL_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(
         "mz_validate_role_privilege" => Scalar {
             params!(String) => UnaryFunc::MzValidateRolePrivilege(func::MzValidateRolePrivilege) => Bool, oid::FUNC_MZ_VALIDATE_ROLE_PRIVILEGE_OID;
         }
-    }
+    };
+
+    add_sef_builtins(&mut builtins, &MZ_INTERNAL_SEF_BUILTINS);
+
+    builtins
 });
 
 pub static MZ_UNSAFE_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
@@ -396,6 +396,7 @@ pub fn create_statement(
             in_cluster: _,
             key_parts,
             with_options: _,
+            predicate,
             if_not_exists,
             ..
         }) => {
@@ -408,6 +409,12 @@ pub fn create_statement(
                     }
                 }
             }
+            if let Some(predicate) = predicate {
+                normalizer.visit_expr_mut(predicate);
+                if let Some(err) = normalizer.err {
+                    return Err(err);
+                }
+            }
             *if_not_exists = false;
         }
 
@@ -35,7 +35,9 @@ use enum_kinds::EnumKind;
 use maplit::btreeset;
 use mz_adapter_types::compaction::CompactionWindow;
 use mz_controller_types::{ClusterId, ReplicaId};
-use mz_expr::{CollectionPlan, ColumnOrder, MirRelationExpr, MirScalarExpr, RowSetFinishing};
+use mz_expr::{
+    like_pattern, CollectionPlan, ColumnOrder, MirRelationExpr, MirScalarExpr, RowSetFinishing,
+};
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
@@ -43,11 +45,11 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::role_id::RoleId;
-use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_repr::{ColumnName, Datum, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
 use mz_sql_parser::ast::{
     AlterSourceAddSubsourceOption, ClusterAlterOptionValue, ConnectionOptionName, QualifiedReplica,
-    SelectStatement, TransactionIsolationLevel, TransactionMode, UnresolvedItemName, Value,
-    WithOptionValue,
+    SelectStatement, SessionFilterField, TransactionIsolationLevel, TransactionMode,
+    UnresolvedItemName, Value, WithOptionValue,
 };
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::sinks::{
@@ -66,7 +68,7 @@ use crate::catalog::{
     RoleAttributes,
 };
 use crate::names::{
-    Aug, CommentObjectId, FullItemName, ObjectId, QualifiedItemName, ResolvedDataType,
+    Aug, CommentObjectId, DatabaseId, FullItemName, ObjectId, QualifiedItemName, ResolvedDataType,
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaSpecifier, SystemObjectId,
 };
 
@@ -103,6 +105,7 @@ pub use statement::ddl::{
     AlterSourceAddSubsourceOptionExtracted, MySqlConfigOptionExtracted, PgConfigOptionExtracted,
     PlannedAlterRoleOption, PlannedRoleVariable,
 };
+pub use statement::show::humanize_sql_for_show_create;
 pub use statement::{
     describe, plan, plan_copy_from, resolve_cluster_for_materialized_view, StatementClassification,
     StatementContext, StatementDesc,
@@ -131,6 +134,7 @@ pub enum Plan {
     CreateIndex(CreateIndexPlan),
     CreateType(CreateTypePlan),
     Comment(CommentPlan),
+    CancelQuery(CancelQueryPlan),
     DiscardTemp,
     DiscardAll,
     DropObjects(DropObjectsPlan),
@@ -140,6 +144,7 @@ pub enum Plan {
     ShowCreate(ShowCreatePlan),
     ShowColumns(ShowColumnsPlan),
     ShowVariable(ShowVariablePlan),
+    ShowTransactionWrites,
     InspectShard(InspectShardPlan),
     SetVariable(SetVariablePlan),
     ResetVariable(ResetVariablePlan),
@@ -153,6 +158,7 @@ pub enum Plan {
     CopyTo(CopyToPlan),
     ExplainPlan(ExplainPlanPlan),
     ExplainPushdown(ExplainPushdownPlan),
+    ExplainAnalyze(ExplainAnalyzePlan),
     ExplainTimestamp(ExplainTimestampPlan),
     ExplainSinkSchema(ExplainSinkSchemaPlan),
     Insert(InsertPlan),
@@ -161,6 +167,7 @@ pub enum Plan {
     AlterNoop(AlterNoopPlan),
     AlterSetCluster(AlterSetClusterPlan),
     AlterConnection(AlterConnectionPlan),
+    AlterDatabase(AlterDatabasePlan),
     AlterSource(AlterSourcePlan),
     AlterClusterRename(AlterClusterRenamePlan),
     AlterClusterReplicaRename(AlterClusterReplicaRenamePlan),
@@ -173,12 +180,16 @@ pub enum Plan {
     AlterSystemSet(AlterSystemSetPlan),
     AlterSystemReset(AlterSystemResetPlan),
     AlterSystemResetAll(AlterSystemResetAllPlan),
+    AlterSystemTerminateSessions(AlterSystemTerminateSessionsPlan),
     AlterRole(AlterRolePlan),
     AlterOwner(AlterOwnerPlan),
     AlterTableAddColumn(AlterTablePlan),
+    AlterTableRowCountHint(AlterTableRowCountHintPlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
+    HandoffCursor(HandoffCursorPlan),
+    ClaimHandoff(ClaimHandoffPlan),
     ReadThenWrite(ReadThenWritePlan),
     Prepare(PreparePlan),
     Execute(ExecutePlan),
@@ -202,6 +213,7 @@ impl Plan {
         match stmt {
             StatementKind::AlterCluster => &[PlanKind::AlterNoop, PlanKind::AlterCluster],
             StatementKind::AlterConnection => &[PlanKind::AlterNoop, PlanKind::AlterConnection],
+            StatementKind::AlterDatabase => &[PlanKind::AlterNoop, PlanKind::AlterDatabase],
             StatementKind::AlterDefaultPrivileges => &[PlanKind::AlterDefaultPrivileges],
             StatementKind::AlterIndex => &[PlanKind::AlterRetainHistory, PlanKind::AlterNoop],
             StatementKind::AlterObjectRename => &[
@@ -231,10 +243,17 @@ impl Plan {
                 &[PlanKind::AlterNoop, PlanKind::AlterSystemResetAll]
             }
             StatementKind::AlterSystemSet => &[PlanKind::AlterNoop, PlanKind::AlterSystemSet],
+            StatementKind::AlterSystemTerminateSessions => {
+                &[PlanKind::AlterSystemTerminateSessions]
+            }
             StatementKind::AlterOwner => &[PlanKind::AlterNoop, PlanKind::AlterOwner],
             StatementKind::AlterTableAddColumn => {
                 &[PlanKind::AlterNoop, PlanKind::AlterTableAddColumn]
             }
+            StatementKind::AlterTableRowCountHint => {
+                &[PlanKind::AlterNoop, PlanKind::AlterTableRowCountHint]
+            }
+            StatementKind::ClaimHandoff => &[PlanKind::ClaimHandoff],
             StatementKind::Close => &[PlanKind::Close],
             StatementKind::Comment => &[PlanKind::Comment],
             StatementKind::Commit => &[PlanKind::CommitTransaction],
@@ -264,17 +283,20 @@ impl Plan {
             StatementKind::Deallocate => &[PlanKind::Deallocate],
             StatementKind::Declare => &[PlanKind::Declare],
             StatementKind::Delete => &[PlanKind::ReadThenWrite],
+            StatementKind::Cancel => &[PlanKind::CancelQuery],
             StatementKind::Discard => &[PlanKind::DiscardAll, PlanKind::DiscardTemp],
             StatementKind::DropObjects => &[PlanKind::DropObjects],
             StatementKind::DropOwned => &[PlanKind::DropOwned],
             StatementKind::Execute => &[PlanKind::Execute],
             StatementKind::ExplainPlan => &[PlanKind::ExplainPlan],
             StatementKind::ExplainPushdown => &[PlanKind::ExplainPushdown],
+            StatementKind::ExplainAnalyze => &[PlanKind::ExplainAnalyze],
             StatementKind::ExplainTimestamp => &[PlanKind::ExplainTimestamp],
             StatementKind::ExplainSinkSchema => &[PlanKind::ExplainSinkSchema],
             StatementKind::Fetch => &[PlanKind::Fetch],
             StatementKind::GrantPrivileges => &[PlanKind::GrantPrivileges],
             StatementKind::GrantRole => &[PlanKind::GrantRole],
+            StatementKind::HandoffCursor => &[PlanKind::HandoffCursor],
             StatementKind::Insert => &[PlanKind::Insert],
             StatementKind::Prepare => &[PlanKind::Prepare],
             StatementKind::Raise => &[PlanKind::Raise],
@@ -292,6 +314,7 @@ impl Plan {
                 PlanKind::ShowCreate,
                 PlanKind::ShowColumns,
                 PlanKind::ShowAllVariables,
+                PlanKind::ShowTransactionWrites,
                 PlanKind::InspectShard,
             ],
             StatementKind::StartTransaction => &[PlanKind::StartTransaction],
@@ -321,6 +344,7 @@ impl Plan {
             Plan::CreateIndex(_) => "create index",
             Plan::CreateType(_) => "create type",
             Plan::Comment(_) => "comment",
+            Plan::CancelQuery(_) => "cancel query",
             Plan::DiscardTemp => "discard temp",
             Plan::DiscardAll => "discard all",
             Plan::DropObjects(plan) => match plan.object_type {
@@ -346,6 +370,7 @@ impl Plan {
             Plan::ShowCreate(_) => "show create",
             Plan::ShowColumns(_) => "show columns",
             Plan::ShowVariable(_) => "show variable",
+            Plan::ShowTransactionWrites => "show transaction writes",
             Plan::InspectShard(_) => "inspect shard",
             Plan::SetVariable(_) => "set variable",
             Plan::ResetVariable(_) => "reset variable",
@@ -359,6 +384,7 @@ impl Plan {
             Plan::CopyTo(_) => "copy to",
             Plan::ExplainPlan(_) => "explain plan",
             Plan::ExplainPushdown(_) => "EXPLAIN FILTER PUSHDOWN",
+            Plan::ExplainAnalyze(_) => "EXPLAIN ANALYZE",
             Plan::ExplainTimestamp(_) => "explain timestamp",
             Plan::ExplainSinkSchema(_) => "explain schema",
             Plan::Insert(_) => "insert",
@@ -385,6 +411,7 @@ impl Plan {
             Plan::AlterClusterReplicaRename(_) => "alter cluster replica rename",
             Plan::AlterSetCluster(_) => "alter set cluster",
             Plan::AlterConnection(_) => "alter connection",
+            Plan::AlterDatabase(_) => "alter database",
             Plan::AlterSource(_) => "alter source",
             Plan::AlterItemRename(_) => "rename item",
             Plan::AlterItemSwap(_) => "swap item",
@@ -395,6 +422,7 @@ impl Plan {
             Plan::AlterSystemSet(_) => "alter system",
             Plan::AlterSystemReset(_) => "alter system",
             Plan::AlterSystemResetAll(_) => "alter system",
+            Plan::AlterSystemTerminateSessions(_) => "alter system terminate sessions",
             Plan::AlterRole(_) => "alter role",
             Plan::AlterOwner(plan) => match plan.object_type {
                 ObjectType::Table => "alter table owner",
@@ -414,9 +442,12 @@ impl Plan {
                 ObjectType::Func => "alter function owner",
             },
             Plan::AlterTableAddColumn(_) => "alter table add column",
+            Plan::AlterTableRowCountHint(_) => "alter table row count hint",
             Plan::Declare(_) => "declare",
             Plan::Fetch(_) => "fetch",
             Plan::Close(_) => "close",
+            Plan::HandoffCursor(_) => "handoff cursor",
+            Plan::ClaimHandoff(_) => "claim handoff",
             Plan::ReadThenWrite(plan) => match plan.kind {
                 MutationKind::Insert => "insert into select",
                 MutationKind::Update => "update",
@@ -459,17 +490,118 @@ impl Plan {
             Plan::ShowCreate(_) => true,
             Plan::ShowColumns(_) => true,
             Plan::ShowVariable(_) => true,
+            Plan::ShowTransactionWrites => true,
             Plan::InspectShard(_) => true,
             Plan::Subscribe(_) => true,
             Plan::CopyTo(_) => true,
             Plan::ExplainPlan(_) => true,
             Plan::ExplainPushdown(_) => true,
+            Plan::ExplainAnalyze(_) => true,
             Plan::ExplainTimestamp(_) => true,
             Plan::ExplainSinkSchema(_) => true,
             Plan::ValidateConnection(_) => true,
+            Plan::CancelQuery(_) => true,
+            Plan::AlterSystemTerminateSessions(_) => true,
             _ => false,
         }
     }
+
+    /// Returns `true` iff this `Plan` is allowed to be executed while
+    /// `read_only_ddl` is enabled, i.e. iff it doesn't change the objects in
+    /// the catalog.
+    ///
+    /// Changes to system parameters are allowed, so that `read_only_ddl` can
+    /// be disabled again.
+    pub fn allowed_with_read_only_ddl(&self) -> bool {
+        match self {
+            Plan::CreateConnection(_)
+            | Plan::CreateDatabase(_)
+            | Plan::CreateSchema(_)
+            | Plan::CreateRole(_)
+            | Plan::CreateCluster(_)
+            | Plan::CreateClusterReplica(_)
+            | Plan::CreateSource(_)
+            | Plan::CreateSources(_)
+            | Plan::CreateSecret(_)
+            | Plan::CreateSink(_)
+            | Plan::CreateTable(_)
+            | Plan::CreateView(_)
+            | Plan::CreateMaterializedView(_)
+            | Plan::CreateIndex(_)
+            | Plan::CreateType(_)
+            | Plan::Comment(_)
+            | Plan::DropObjects(_)
+            | Plan::DropOwned(_)
+            | Plan::AlterCluster(_)
+            | Plan::AlterClusterSwap(_)
+            | Plan::AlterSetCluster(_)
+            | Plan::AlterConnection(_)
+            | Plan::AlterDatabase(_)
+            | Plan::AlterSource(_)
+            | Plan::AlterClusterRename(_)
+            | Plan::AlterClusterReplicaRename(_)
+            | Plan::AlterItemRename(_)
+            | Plan::AlterItemSwap(_)
+            | Plan::AlterSchemaRename(_)
+            | Plan::AlterSchemaSwap(_)
+            | Plan::AlterSecret(_)
+            | Plan::AlterSink(_)
+            | Plan::AlterRole(_)
+            | Plan::AlterOwner(_)
+            | Plan::AlterTableAddColumn(_)
+            | Plan::AlterTableRowCountHint(_)
+            | Plan::AlterRetainHistory(_)
+            | Plan::GrantRole(_)
+            | Plan::RevokeRole(_)
+            | Plan::GrantPrivileges(_)
+            | Plan::RevokePrivileges(_)
+            | Plan::AlterDefaultPrivileges(_)
+            | Plan::ReassignOwned(_) => false,
+            Plan::CancelQuery(_)
+            | Plan::DiscardTemp
+            | Plan::DiscardAll
+            | Plan::EmptyQuery
+            | Plan::ShowAllVariables
+            | Plan::ShowCreate(_)
+            | Plan::ShowColumns(_)
+            | Plan::ShowVariable(_)
+            | Plan::ShowTransactionWrites
+            | Plan::InspectShard(_)
+            | Plan::SetVariable(_)
+            | Plan::ResetVariable(_)
+            | Plan::SetTransaction(_)
+            | Plan::StartTransaction(_)
+            | Plan::CommitTransaction(_)
+            | Plan::AbortTransaction(_)
+            | Plan::Select(_)
+            | Plan::Subscribe(_)
+            | Plan::CopyFrom(_)
+            | Plan::CopyTo(_)
+            | Plan::ExplainPlan(_)
+            | Plan::ExplainPushdown(_)
+            | Plan::ExplainAnalyze(_)
+            | Plan::ExplainTimestamp(_)
+            | Plan::ExplainSinkSchema(_)
+            | Plan::Insert(_)
+            | Plan::AlterNoop(_)
+            | Plan::AlterSystemSet(_)
+            | Plan::AlterSystemReset(_)
+            | Plan::AlterSystemResetAll(_)
+            | Plan::AlterSystemTerminateSessions(_)
+            | Plan::Declare(_)
+            | Plan::Fetch(_)
+            | Plan::Close(_)
+            | Plan::HandoffCursor(_)
+            | Plan::ClaimHandoff(_)
+            | Plan::ReadThenWrite(_)
+            | Plan::Prepare(_)
+            | Plan::Execute(_)
+            | Plan::Deallocate(_)
+            | Plan::Raise(_)
+            | Plan::SideEffectingFunc(_)
+            | Plan::ValidateConnection(_) => true,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -772,6 +904,8 @@ pub struct SelectPlan {
     pub when: QueryWhen,
     pub finishing: RowSetFinishing,
     pub copy_to: Option<CopyFormat>,
+    /// The cluster on which to also run the query to compare its results, if any.
+    pub mirror: Option<ClusterId>,
 }
 
 #[derive(Debug)]
@@ -800,6 +934,25 @@ pub struct SubscribePlan {
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
     pub output: SubscribeOutput,
+    /// Where to archive the updates to, instead of returning them, if anywhere.
+    pub archive: Option<SubscribeArchive>,
+    /// Whether to consolidate the updates of each timestamp before emitting them.
+    pub deduplicate: bool,
+}
+
+/// The destination of a `SUBSCRIBE ... INTO`.
+#[derive(Debug, Clone)]
+pub struct SubscribeArchive {
+    /// The scalar expression to be resolved to get the destination uri.
+    pub to: HirScalarExpr,
+    pub connection: mz_storage_types::connections::Connection<ReferencedConnection>,
+    /// The ID of the connection.
+    pub connection_id: GlobalId,
+    /// The description of the archived rows, i.e., of the output of the `SUBSCRIBE` without
+    /// `PROGRESS`.
+    pub desc: RelationDesc,
+    pub format: S3SinkFormat,
+    pub max_file_size: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -917,6 +1070,12 @@ pub enum ExplaineeStatement {
         broken: bool,
         plan: plan::CreateIndexPlan,
     },
+    /// The object to be explained is a SUBSCRIBE.
+    Subscribe {
+        /// Broken flag (see [`ExplaineeStatement::broken()`]).
+        broken: bool,
+        plan: plan::SubscribePlan,
+    },
 }
 
 impl ExplaineeStatement {
@@ -926,6 +1085,7 @@ impl ExplaineeStatement {
             Self::CreateView { plan, .. } => plan.view.expr.depends_on(),
             Self::CreateMaterializedView { plan, .. } => plan.materialized_view.expr.depends_on(),
             Self::CreateIndex { plan, .. } => btreeset! {plan.index.on},
+            Self::Subscribe { plan, .. } => plan.from.depends_on(),
         }
     }
 
@@ -945,6 +1105,7 @@ impl ExplaineeStatement {
             Self::CreateView { broken, .. } => *broken,
             Self::CreateMaterializedView { broken, .. } => *broken,
             Self::CreateIndex { broken, .. } => *broken,
+            Self::Subscribe { broken, .. } => *broken,
         }
     }
 }
@@ -954,9 +1115,12 @@ impl ExplaineeStatementKind {
         use ExplainStage::*;
         match self {
             Self::Select => true,
-            Self::CreateView => ![GlobalPlan, PhysicalPlan].contains(stage),
-            Self::CreateMaterializedView => true,
-            Self::CreateIndex => ![RawPlan, DecorrelatedPlan, LocalPlan].contains(stage),
+            Self::CreateView => ![GlobalPlan, PhysicalPlan, Bundle, Emptiness].contains(stage),
+            Self::CreateMaterializedView => ![Bundle, Emptiness].contains(stage),
+            Self::CreateIndex => {
+                ![RawPlan, DecorrelatedPlan, LocalPlan, Bundle, Emptiness].contains(stage)
+            }
+            Self::Subscribe => ![RawPlan, DecorrelatedPlan, Bundle, Emptiness].contains(stage),
         }
     }
 }
@@ -968,6 +1132,7 @@ impl std::fmt::Display for ExplaineeStatementKind {
             Self::CreateView => write!(f, "CREATE VIEW"),
             Self::CreateMaterializedView => write!(f, "CREATE MATERIALIZED VIEW"),
             Self::CreateIndex => write!(f, "CREATE INDEX"),
+            Self::Subscribe => write!(f, "SUBSCRIBE"),
         }
     }
 }
@@ -977,6 +1142,16 @@ pub struct ExplainPushdownPlan {
     pub explainee: Explainee,
 }
 
+#[derive(Clone, Debug)]
+pub struct ExplainAnalyzePlan {
+    /// The materialized view whose dataflow is analyzed.
+    pub id: GlobalId,
+    /// The cluster that maintains the materialized view.
+    pub cluster_id: ClusterId,
+    /// The replica to read the introspection data from.
+    pub replica_id: ReplicaId,
+}
+
 #[derive(Clone, Debug)]
 pub struct ExplainTimestampPlan {
     pub format: ExplainFormat,
@@ -997,6 +1172,8 @@ pub struct SendDiffsPlan {
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
     pub max_result_size: u64,
+    /// Whether to validate the updates without adding them to the transaction.
+    pub dry_run: bool,
 }
 
 #[derive(Debug)]
@@ -1004,6 +1181,8 @@ pub struct InsertPlan {
     pub id: GlobalId,
     pub values: HirRelationExpr,
     pub returning: Vec<mz_expr::MirScalarExpr>,
+    /// Whether the insert was planned `WITH (DRY RUN)`.
+    pub dry_run: bool,
 }
 
 #[derive(Debug)]
@@ -1014,6 +1193,8 @@ pub struct ReadThenWritePlan {
     pub assignments: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub kind: MutationKind,
     pub returning: Vec<mz_expr::MirScalarExpr>,
+    /// Whether the write was planned `WITH (DRY RUN)`.
+    pub dry_run: bool,
 }
 
 /// Generated by `ALTER ... IF EXISTS` if the named object did not exist.
@@ -1036,6 +1217,14 @@ pub struct AlterRetainHistoryPlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterTableRowCountHintPlan {
+    pub id: GlobalId,
+    /// The hinted row count as written in the statement, or `None` to reset the hint.
+    pub value: Option<Value>,
+    pub hint: Option<u64>,
+}
+
 #[derive(Debug, Clone)]
 
 pub enum AlterOptionParameter<T = String> {
@@ -1066,6 +1255,13 @@ pub enum AlterSourceAction {
         subsources: Vec<CreateSourcePlanBundle>,
         options: Vec<AlterSourceAddSubsourceOption<Aug>>,
     },
+    DropSubsourceExports {
+        /// The subsources named by the statement.
+        subsources: Vec<GlobalId>,
+        /// The subsources and the objects that depend on them, in the order in which they must
+        /// be dropped.
+        drop_ids: Vec<ObjectId>,
+    },
 }
 
 #[derive(Debug)]
@@ -1090,6 +1286,15 @@ pub struct AlterClusterPlan {
     pub strategy: AlterClusterPlanStrategy,
 }
 
+#[derive(Debug, Clone)]
+pub struct AlterDatabasePlan {
+    pub id: DatabaseId,
+    pub name: String,
+    /// The cluster in which objects created in the database are placed when no
+    /// cluster is specified, or `None` to use the session's cluster.
+    pub default_cluster: Option<ClusterId>,
+}
+
 #[derive(Debug)]
 pub struct AlterClusterRenamePlan {
     pub id: ClusterId,
@@ -1166,6 +1371,40 @@ pub struct AlterSystemResetPlan {
 #[derive(Debug)]
 pub struct AlterSystemResetAllPlan {}
 
+#[derive(Debug)]
+pub struct AlterSystemTerminateSessionsPlan {
+    /// The filters that a session must all match to be terminated.
+    pub filters: Vec<SessionFilterPlan>,
+}
+
+impl AlterSystemTerminateSessionsPlan {
+    /// Reports whether a session of the given `role` and `application_name` matches all filters.
+    pub fn matches(&self, role: &str, application_name: &str) -> bool {
+        self.filters.iter().all(|filter| {
+            let value = match filter.field {
+                SessionFilterField::Role => role,
+                SessionFilterField::ApplicationName => application_name,
+            };
+            match &filter.matcher {
+                SessionFilterMatcher::Equals(expected) => value == expected,
+                SessionFilterMatcher::Like(matcher) => matcher.is_match(value),
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SessionFilterPlan {
+    pub field: SessionFilterField,
+    pub matcher: SessionFilterMatcher,
+}
+
+#[derive(Debug)]
+pub enum SessionFilterMatcher {
+    Equals(String),
+    Like(like_pattern::Matcher),
+}
+
 #[derive(Debug)]
 pub struct AlterRolePlan {
     pub id: RoleId,
@@ -1200,6 +1439,8 @@ pub struct FetchPlan {
     pub name: String,
     pub count: Option<FetchDirection>,
     pub timeout: ExecuteTimeout,
+    /// Whether to return as soon as a progress message of the cursor's `SUBSCRIBE` was fetched.
+    pub progress: bool,
 }
 
 #[derive(Debug)]
@@ -1207,6 +1448,18 @@ pub struct ClosePlan {
     pub name: String,
 }
 
+#[derive(Debug)]
+pub struct HandoffCursorPlan {
+    /// The name of the cursor to hand off.
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct ClaimHandoffPlan {
+    /// The token returned when the cursor was handed off.
+    pub token: String,
+}
+
 #[derive(Debug)]
 pub struct PreparePlan {
     pub name: String,
@@ -1226,6 +1479,12 @@ pub struct DeallocatePlan {
     pub name: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct CancelQueryPlan {
+    /// The ID of the connection whose query to cancel.
+    pub connection_id: u32,
+}
+
 #[derive(Debug)]
 pub struct RaisePlan {
     pub severity: NoticeSeverity,
@@ -1315,6 +1574,11 @@ pub struct Table {
     pub defaults: Vec<Expr<Aug>>,
     pub temporary: bool,
     pub compaction_window: Option<CompactionWindow>,
+    /// Whether writes to the table are recorded in
+    /// `mz_internal.mz_table_change_audit`.
+    pub change_audit: bool,
+    /// The number of rows that the optimizer assumes the table to have.
+    pub row_count_hint: Option<u64>,
 }
 
 #[derive(Clone, Debug)]
@@ -1500,6 +1764,8 @@ pub struct Index {
     pub create_sql: String,
     pub on: GlobalId,
     pub keys: Vec<mz_expr::MirScalarExpr>,
+    /// The predicate of a partial index, which only contains the rows that satisfy it.
+    pub predicate: Option<mz_expr::MirScalarExpr>,
     pub compaction_window: Option<CompactionWindow>,
     pub cluster_id: ClusterId,
 }
@@ -1527,6 +1793,10 @@ pub enum QueryWhen {
     /// Same as Immediately, but will also advance to at least the specified
     /// expression.
     AtLeastTimestamp(MirScalarExpr),
+    /// Same as Immediately, but will also advance to at least the timestamp of
+    /// the specified token, so that the peek observes the write that returned
+    /// it.
+    AfterToken(ConsistencyToken),
 }
 
 impl QueryWhen {
@@ -1534,6 +1804,10 @@ impl QueryWhen {
     pub fn advance_to_timestamp(&self) -> Option<MirScalarExpr> {
         match self {
             QueryWhen::AtTimestamp(t) | QueryWhen::AtLeastTimestamp(t) => Some(t.clone()),
+            QueryWhen::AfterToken(token) => Some(MirScalarExpr::literal_ok(
+                Datum::MzTimestamp(token.timestamp),
+                ScalarType::MzTimestamp,
+            )),
             QueryWhen::Immediately | QueryWhen::FreshestTableWrite => None,
         }
     }
@@ -1542,6 +1816,7 @@ impl QueryWhen {
         match self {
             QueryWhen::Immediately
             | QueryWhen::AtLeastTimestamp(_)
+            | QueryWhen::AfterToken(_)
             | QueryWhen::FreshestTableWrite => true,
             QueryWhen::AtTimestamp(_) => false,
         }
@@ -1549,7 +1824,7 @@ impl QueryWhen {
     /// Returns whether the candidate can be advanced to the upper.
     pub fn can_advance_to_upper(&self) -> bool {
         match self {
-            QueryWhen::Immediately => true,
+            QueryWhen::Immediately | QueryWhen::AfterToken(_) => true,
             QueryWhen::FreshestTableWrite
             | QueryWhen::AtTimestamp(_)
             | QueryWhen::AtLeastTimestamp(_) => false,
@@ -1559,7 +1834,9 @@ impl QueryWhen {
     /// Returns whether the candidate can be advanced to the timeline's timestamp.
     pub fn can_advance_to_timeline_ts(&self) -> bool {
         match self {
-            QueryWhen::Immediately | QueryWhen::FreshestTableWrite => true,
+            QueryWhen::Immediately | QueryWhen::FreshestTableWrite | QueryWhen::AfterToken(_) => {
+                true
+            }
             QueryWhen::AtTimestamp(_) | QueryWhen::AtLeastTimestamp(_) => false,
         }
     }
@@ -1567,18 +1844,68 @@ impl QueryWhen {
     pub fn must_advance_to_timeline_ts(&self) -> bool {
         match self {
             QueryWhen::FreshestTableWrite => true,
-            QueryWhen::Immediately | QueryWhen::AtLeastTimestamp(_) | QueryWhen::AtTimestamp(_) => {
-                false
-            }
+            QueryWhen::Immediately
+            | QueryWhen::AtLeastTimestamp(_)
+            | QueryWhen::AtTimestamp(_)
+            | QueryWhen::AfterToken(_) => false,
         }
     }
     /// Returns whether the selected timestamp should be tracked within the current transaction.
     pub fn is_transactional(&self) -> bool {
         match self {
             QueryWhen::Immediately | QueryWhen::FreshestTableWrite => true,
-            QueryWhen::AtLeastTimestamp(_) | QueryWhen::AtTimestamp(_) => false,
+            QueryWhen::AtLeastTimestamp(_)
+            | QueryWhen::AtTimestamp(_)
+            | QueryWhen::AfterToken(_) => false,
         }
     }
+    /// Returns the timeline that the peek must occur in, if any.
+    pub fn timeline(&self) -> Option<&Timeline> {
+        match self {
+            QueryWhen::AfterToken(token) => Some(&token.timeline),
+            QueryWhen::Immediately
+            | QueryWhen::FreshestTableWrite
+            | QueryWhen::AtTimestamp(_)
+            | QueryWhen::AtLeastTimestamp(_) => None,
+        }
+    }
+}
+
+/// The name of the parameter that reports the consistency token of a write to
+/// the client. This is not a configuration parameter, so clients can't set or
+/// show it.
+pub const CONSISTENCY_TOKEN_PARAMETER: &str = "mz_consistency_token";
+
+/// Identifies a write, so that later reads can be required to observe it.
+///
+/// Writes return tokens to clients, which can pass them back to `SELECT` or
+/// `SUBSCRIBE` with the `AFTER TOKEN` option, possibly on another connection.
+/// Clients should treat tokens as opaque strings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ConsistencyToken {
+    /// The timeline of the write.
+    pub timeline: Timeline,
+    /// The timestamp of the write.
+    pub timestamp: Timestamp,
+}
+
+impl std::fmt::Display for ConsistencyToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.timeline.to_string(), self.timestamp)
+    }
+}
+
+impl std::str::FromStr for ConsistencyToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid consistency token: {s}");
+        let (timeline, timestamp) = s.rsplit_once('@').ok_or_else(invalid)?;
+        Ok(ConsistencyToken {
+            timeline: timeline.parse().map_err(|_| invalid())?,
+            timestamp: timestamp.parse().map_err(|_| invalid())?,
+        })
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1613,6 +1940,10 @@ pub enum IndexOption {
 pub enum TableOption {
     /// Configures the logical compaction window for a table.
     RetainHistory(CompactionWindow),
+    /// Records the writes to the table in `mz_internal.mz_table_change_audit`.
+    ChangeAudit,
+    /// The number of rows that the optimizer assumes the table to have.
+    RowCountHint(u64),
 }
 
 #[derive(Clone, Debug)]
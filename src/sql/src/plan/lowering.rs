@@ -36,6 +36,7 @@
 //! contain "holes" from prepared statements, as if the query was a subquery
 //! against a relation containing the assignments of values to those holes.
 
+use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, BTreeSet};
 use std::iter::repeat;
 
@@ -49,6 +50,9 @@ use itertools::Itertools;
 use mz_expr::{AccessStrategy, AggregateFunc, MirRelationExpr, MirScalarExpr};
 use mz_ore::collections::CollectionExt;
 use mz_ore::stack::maybe_grow;
+use mz_repr::explain::{
+    SubqueryKind, SubqueryLowering, SubqueryLoweringStrategy, SubqueryLowerings,
+};
 use mz_repr::*;
 
 mod variadic_left;
@@ -157,6 +161,32 @@ struct Context<'a> {
     /// Optional, because some callers don't have an `OptimizerMetrics` handy. When it's None, we
     /// simply don't write metrics.
     metrics: Option<&'a OptimizerMetrics>,
+    /// The subqueries lowered so far, for `EXPLAIN DECORRELATED PLAN WITH (subquery lowering)`.
+    subquery_lowerings: RefCell<Vec<SubqueryLowering>>,
+    /// The number of subqueries that enclose the subquery that is currently being lowered.
+    subquery_depth: Cell<usize>,
+}
+
+impl Context<'_> {
+    /// Records that a subquery of the given `kind` is lowered with `strategy`, and returns the
+    /// result of lowering it with `lower`, during which nested subqueries are recorded as such.
+    fn lower_subquery<R>(
+        &self,
+        kind: SubqueryKind,
+        strategy: SubqueryLoweringStrategy,
+        lower: impl FnOnce() -> R,
+    ) -> R {
+        let depth = self.subquery_depth.get();
+        self.subquery_lowerings.borrow_mut().push(SubqueryLowering {
+            kind,
+            strategy,
+            depth,
+        });
+        self.subquery_depth.set(depth + 1);
+        let result = lower();
+        self.subquery_depth.set(depth);
+        result
+    }
 }
 
 impl HirRelationExpr {
@@ -171,6 +201,8 @@ impl HirRelationExpr {
         let context = Context {
             config: &config.into(),
             metrics,
+            subquery_lowerings: RefCell::new(Vec::new()),
+            subquery_depth: Cell::new(0),
         };
         let result =
             match self {
@@ -203,6 +235,9 @@ impl HirRelationExpr {
                 }
             };
 
+        mz_repr::explain::trace_plan(&SubqueryLowerings::new(
+            context.subquery_lowerings.into_inner(),
+        ));
         mz_repr::explain::trace_plan(&result);
 
         Ok(result)
@@ -565,6 +600,7 @@ impl HirRelationExpr {
                             col_map,
                             cte_map,
                             *right,
+                            SubqueryKind::Lateral,
                             apply_requires_distinct_outer,
                             context,
                             |id_gen, right, get_left, col_map, cte_map, context| {
@@ -1659,13 +1695,15 @@ impl HirScalarExpr {
 /// that distinctifies `outer`.
 ///
 /// The caller must supply the `apply` function that applies the rewritten
-/// `inner` to `outer`.
+/// `inner` to `outer`, and the `kind` of subquery that `inner` is, which is
+/// recorded along with the chosen plan for `EXPLAIN`.
 fn branch<F>(
     id_gen: &mut mz_ore::id_gen::IdGen,
     outer: MirRelationExpr,
     col_map: &ColumnMap,
     cte_map: &mut CteMap,
     inner: HirRelationExpr,
+    kind: SubqueryKind,
     apply_requires_distinct_outer: bool,
     context: &Context,
     apply: F,
@@ -1739,8 +1777,10 @@ where
     });
     if is_simple && !apply_requires_distinct_outer {
         let new_col_map = col_map.enter_scope(outer.arity() - col_map.len());
-        return outer.let_in_fallible(id_gen, |id_gen, get_outer| {
-            apply(id_gen, inner, get_outer, &new_col_map, cte_map, context)
+        return context.lower_subquery(kind, SubqueryLoweringStrategy::Direct, || {
+            outer.let_in_fallible(id_gen, |id_gen, get_outer| {
+                apply(id_gen, inner, get_outer, &new_col_map, cte_map, context)
+            })
         });
     }
 
@@ -1816,39 +1856,46 @@ where
         }));
     }
     let new_col_map = ColumnMap::new(new_col_map);
-    outer.let_in_fallible(id_gen, |id_gen, get_outer| {
-        let keyed_outer = if key.is_empty() {
-            // Don't depend on outer at all if the branch is not correlated,
-            // which yields vastly better query plans. Note that this is a bit
-            // weird in that the branch will be computed even if outer has no
-            // rows, whereas if it had been correlated it would not (and *could*
-            // not) have been computed if outer had no rows, but the callers of
-            // this function don't mind these somewhat-weird semantics.
-            MirRelationExpr::constant(vec![vec![]], RelationType::new(vec![]))
-        } else {
-            get_outer.clone().distinct_by(key.clone())
-        };
-        keyed_outer.let_in_fallible(id_gen, |id_gen, get_keyed_outer| {
-            let oa = get_outer.arity();
-            let branch = apply(
-                id_gen,
-                inner,
-                get_keyed_outer,
-                &new_col_map,
-                cte_map,
-                context,
-            )?;
-            let ba = branch.arity();
-            let joined = MirRelationExpr::join(
-                vec![get_outer.clone(), branch],
-                key.iter()
-                    .enumerate()
-                    .map(|(i, &k)| vec![(0, k), (1, i)])
-                    .collect(),
-            )
-            // throw away the right-hand copy of the key we just joined on
-            .project((0..oa).chain((oa + key.len())..(oa + ba)).collect());
-            Ok(joined)
+    let strategy = if key.is_empty() {
+        SubqueryLoweringStrategy::Uncorrelated
+    } else {
+        SubqueryLoweringStrategy::PerDistinctKey { key: key.clone() }
+    };
+    context.lower_subquery(kind, strategy, || {
+        outer.let_in_fallible(id_gen, |id_gen, get_outer| {
+            let keyed_outer = if key.is_empty() {
+                // Don't depend on outer at all if the branch is not correlated,
+                // which yields vastly better query plans. Note that this is a bit
+                // weird in that the branch will be computed even if outer has no
+                // rows, whereas if it had been correlated it would not (and *could*
+                // not) have been computed if outer had no rows, but the callers of
+                // this function don't mind these somewhat-weird semantics.
+                MirRelationExpr::constant(vec![vec![]], RelationType::new(vec![]))
+            } else {
+                get_outer.clone().distinct_by(key.clone())
+            };
+            keyed_outer.let_in_fallible(id_gen, |id_gen, get_keyed_outer| {
+                let oa = get_outer.arity();
+                let branch = apply(
+                    id_gen,
+                    inner,
+                    get_keyed_outer,
+                    &new_col_map,
+                    cte_map,
+                    context,
+                )?;
+                let ba = branch.arity();
+                let joined = MirRelationExpr::join(
+                    vec![get_outer.clone(), branch],
+                    key.iter()
+                        .enumerate()
+                        .map(|(i, &k)| vec![(0, k), (1, i)])
+                        .collect(),
+                )
+                // throw away the right-hand copy of the key we just joined on
+                .project((0..oa).chain((oa + key.len())..(oa + ba)).collect());
+                Ok(joined)
+            })
         })
     })
 }
@@ -1868,6 +1915,7 @@ fn apply_scalar_subquery(
         col_map,
         cte_map,
         scalar_subquery,
+        SubqueryKind::Scalar,
         apply_requires_distinct_outer,
         context,
         |id_gen, expr, get_inner, col_map, cte_map, context| {
@@ -1925,6 +1973,7 @@ fn apply_existential_subquery(
         col_map,
         cte_map,
         subquery_expr,
+        SubqueryKind::Exists,
         apply_requires_distinct_outer,
         context,
         |id_gen, expr, get_inner, col_map, cte_map, context| {
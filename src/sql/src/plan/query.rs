@@ -933,6 +933,23 @@ pub fn plan_as_of(
     }
 }
 
+/// Applies the `AFTER TOKEN` option of a `SELECT` or `SUBSCRIBE` to `when`.
+pub fn plan_after_token(
+    scx: &StatementContext,
+    when: QueryWhen,
+    after_token: Option<String>,
+) -> Result<QueryWhen, PlanError> {
+    let Some(token) = after_token else {
+        return Ok(when);
+    };
+    scx.require_feature_flag(&vars::ENABLE_CONSISTENCY_TOKENS)?;
+    if when != QueryWhen::Immediately {
+        sql_bail!("AFTER TOKEN cannot be combined with AS OF");
+    }
+    let token = token.parse().map_err(|e: String| sql_err!("{}", e))?;
+    Ok(QueryWhen::AfterToken(token))
+}
+
 /// Plans an expression in the AS position of a `CREATE SECRET`.
 pub fn plan_secret_as(
     scx: &StatementContext,
@@ -1218,6 +1235,36 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the predicate of a partial index on a relation described by `on_desc`.
+pub fn plan_index_predicate<'a>(
+    scx: &'a StatementContext,
+    on_desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, on_desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Index);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CREATE INDEX ... WHERE",
+        scope: &scope,
+        relation_type: on_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    transform_ast::transform(scx, &mut expr)?;
+    let mut expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    if expr.contains_temporal() {
+        bail_unsupported!("mz_now() in the predicate of a partial index");
+    }
+    expr.reduce(&on_desc.typ().column_types);
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1791,6 +1838,9 @@ fn plan_set_expr(
                 ShowStatement::ShowColumns(stmt) => {
                     show::show_columns(qcx.scx, stmt)?.plan_hir(qcx)
                 }
+                ShowStatement::ShowFrontiers(stmt) => {
+                    show::show_frontiers(qcx.scx, stmt)?.plan_hir(qcx)
+                }
                 ShowStatement::ShowCreateConnection(stmt) => to_hirscope(
                     show::plan_show_create_connection(qcx.scx, stmt.clone())?,
                     show::describe_show_create_connection(qcx.scx, stmt)?,
@@ -1827,6 +1877,9 @@ fn plan_set_expr(
                     show::show_objects(qcx.scx, stmt)?.plan_hir(qcx)
                 }
                 ShowStatement::ShowVariable(_) => bail_unsupported!("SHOW variable in subqueries"),
+                ShowStatement::ShowTransactionWrites(_) => {
+                    bail_unsupported!("SHOW TRANSACTION WRITES in subqueries")
+                }
                 ShowStatement::InspectShard(_) => sql_bail!("unsupported INSPECT statement"),
             }
         }
@@ -35,6 +35,7 @@ use enum_kinds::EnumKind;
 use mz_ore::cast::ReinterpretCast;
 use mz_ore::collections::CollectionExt;
 use mz_ore::result::ResultExt;
+use mz_pgrepr::oid;
 use mz_repr::RelationType;
 use mz_repr::{ColumnType, Datum, RelationDesc, RowArena, ScalarType};
 use mz_sql_parser::ast::{CteBlock, Expr, Function, FunctionArgs, Select, SelectItem, SetExpr};
@@ -49,6 +50,7 @@ use crate::plan::statement::StatementContext;
 use crate::plan::typeconv::CastContext;
 use crate::plan::{HirScalarExpr, Params};
 use crate::plan::{PlanError, QueryContext};
+use crate::session::vars;
 
 /// A side-effecting function is a function whose evaluation triggers side
 /// effects.
@@ -62,6 +64,25 @@ pub enum SideEffectingFunc {
         // The ID of the connection to cancel.
         connection_id: u32,
     },
+    /// The `mz_vacuum_catalog` function.
+    MzVacuumCatalog,
+    /// The `mz_trigger_background_task` function.
+    MzTriggerBackgroundTask {
+        // The name of the background task to run.
+        name: String,
+    },
+    /// The `mz_skip_background_task` function.
+    MzSkipBackgroundTask {
+        // The name of the background task whose next run to skip.
+        name: String,
+    },
+    /// The `mz_set_failpoint` function.
+    MzSetFailpoint {
+        // The name of the failpoint to configure.
+        name: String,
+        // The actions to configure the failpoint with.
+        actions: String,
+    },
 }
 
 /// Describes a `SELECT` if it contains calls to side-effecting functions.
@@ -112,6 +133,9 @@ pub fn plan_select_if_side_effecting(
     }
 
     let func = (sef_call.imp.plan_fn)(&datums);
+    if let SideEffectingFunc::MzSetFailpoint { .. } = func {
+        scx.require_feature_flag(&vars::ENABLE_ADAPTER_FAILPOINTS)?;
+    }
 
     Ok(Some(func))
 }
@@ -132,12 +156,13 @@ fn extract_sef_call(
                 limit: None,
                 offset: None,
             },
+        options: statement_options,
         as_of: None,
     } = select
     else {
         return Ok(None);
     };
-    if !ctes.is_empty() || !order_by.is_empty() {
+    if !ctes.is_empty() || !order_by.is_empty() || !statement_options.is_empty() {
         return Ok(None);
     }
     let Select {
@@ -188,7 +213,10 @@ fn extract_sef_call(
     };
 
     // Check whether the implementation is a known side-effecting function.
-    let Some(sef_impl) = PG_CATALOG_SEF_BUILTINS.get(&func_impl.oid) else {
+    let Some(sef_impl) = PG_CATALOG_SEF_BUILTINS
+        .get(&func_impl.oid)
+        .or_else(|| MZ_INTERNAL_SEF_BUILTINS.get(&func_impl.oid))
+    else {
         return Ok(None);
     };
 
@@ -273,9 +301,24 @@ pub static PG_CATALOG_SEF_BUILTINS: Lazy<BTreeMap<u32, SideEffectingFuncImpl>> =
         .collect()
 });
 
+/// A map of the side-effecting functions in the `mz_internal` schema, keyed by
+/// OID.
+pub static MZ_INTERNAL_SEF_BUILTINS: Lazy<BTreeMap<u32, SideEffectingFuncImpl>> = Lazy::new(|| {
+    [
+        MZ_VACUUM_CATALOG,
+        MZ_TRIGGER_BACKGROUND_TASK,
+        MZ_SKIP_BACKGROUND_TASK,
+        MZ_SET_FAILPOINT,
+    ]
+    .into_iter()
+    .map(|f| (f.oid, f))
+    .collect()
+});
+
 // Implementations of each side-effecting function follow.
 //
-// If you add a new side-effecting function, be sure to add it to the map above.
+// If you add a new side-effecting function, be sure to add it to one of the maps
+// above.
 
 const PG_CANCEL_BACKEND: SideEffectingFuncImpl = SideEffectingFuncImpl {
     name: "pg_cancel_backend",
@@ -288,3 +331,48 @@ const PG_CANCEL_BACKEND: SideEffectingFuncImpl = SideEffectingFuncImpl {
         }
     },
 };
+
+const MZ_VACUUM_CATALOG: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_vacuum_catalog",
+    oid: oid::FUNC_MZ_VACUUM_CATALOG_OID,
+    param_types: &[],
+    return_type: ScalarType::UInt64.nullable(false),
+    plan_fn: |_datums| -> SideEffectingFunc { SideEffectingFunc::MzVacuumCatalog },
+};
+
+const MZ_TRIGGER_BACKGROUND_TASK: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_trigger_background_task",
+    oid: oid::FUNC_MZ_TRIGGER_BACKGROUND_TASK_OID,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Bool.nullable(false),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzTriggerBackgroundTask {
+            name: datums[0].unwrap_str().to_string(),
+        }
+    },
+};
+
+const MZ_SKIP_BACKGROUND_TASK: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_skip_background_task",
+    oid: oid::FUNC_MZ_SKIP_BACKGROUND_TASK_OID,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Bool.nullable(false),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzSkipBackgroundTask {
+            name: datums[0].unwrap_str().to_string(),
+        }
+    },
+};
+
+const MZ_SET_FAILPOINT: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_set_failpoint",
+    oid: oid::FUNC_MZ_SET_FAILPOINT_OID,
+    param_types: &[ScalarType::String, ScalarType::String],
+    return_type: ScalarType::String.nullable(true),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzSetFailpoint {
+            name: datums[0].unwrap_str().to_string(),
+            actions: datums[1].unwrap_str().to_string(),
+        }
+    },
+};
@@ -123,6 +123,7 @@ pub fn describe(
         // DDL statements.
         Statement::AlterCluster(stmt) => ddl::describe_alter_cluster_set_options(&scx, stmt)?,
         Statement::AlterConnection(stmt) => ddl::describe_alter_connection(&scx, stmt)?,
+        Statement::AlterDatabase(stmt) => ddl::describe_alter_database(&scx, stmt)?,
         Statement::AlterIndex(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterObjectSwap(stmt) => ddl::describe_alter_object_swap(&scx, stmt)?,
@@ -135,7 +136,13 @@ pub fn describe(
         Statement::AlterSystemSet(stmt) => ddl::describe_alter_system_set(&scx, stmt)?,
         Statement::AlterSystemReset(stmt) => ddl::describe_alter_system_reset(&scx, stmt)?,
         Statement::AlterSystemResetAll(stmt) => ddl::describe_alter_system_reset_all(&scx, stmt)?,
+        Statement::AlterSystemTerminateSessions(stmt) => {
+            ddl::describe_alter_system_terminate_sessions(&scx, stmt)?
+        }
         Statement::AlterTableAddColumn(stmt) => ddl::describe_alter_table_add_column(&scx, stmt)?,
+        Statement::AlterTableRowCountHint(stmt) => {
+            ddl::describe_alter_table_row_count_hint(&scx, stmt)?
+        }
         Statement::Comment(stmt) => ddl::describe_comment(&scx, stmt)?,
         Statement::CreateCluster(stmt) => ddl::describe_create_cluster(&scx, stmt)?,
         Statement::CreateClusterReplica(stmt) => ddl::describe_create_cluster_replica(&scx, stmt)?,
@@ -176,6 +183,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowColumns(stmt)) => {
             show::show_columns(&scx, stmt)?.describe()?
         }
+        Statement::Show(ShowStatement::ShowFrontiers(stmt)) => {
+            show::show_frontiers(&scx, stmt)?.describe()?
+        }
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::describe_show_create_connection(&scx, stmt)?
         }
@@ -206,23 +216,29 @@ pub fn describe(
 
         // SCL statements.
         Statement::Close(stmt) => scl::describe_close(&scx, stmt)?,
+        Statement::ClaimHandoff(stmt) => scl::describe_claim_handoff(&scx, stmt)?,
         Statement::Deallocate(stmt) => scl::describe_deallocate(&scx, stmt)?,
         Statement::Declare(stmt) => scl::describe_declare(&scx, stmt, param_types_in)?,
         Statement::Discard(stmt) => scl::describe_discard(&scx, stmt)?,
         Statement::Execute(stmt) => scl::describe_execute(&scx, stmt)?,
         Statement::Fetch(stmt) => scl::describe_fetch(&scx, stmt)?,
+        Statement::HandoffCursor(stmt) => scl::describe_handoff_cursor(&scx, stmt)?,
         Statement::Prepare(stmt) => scl::describe_prepare(&scx, stmt)?,
         Statement::ResetVariable(stmt) => scl::describe_reset_variable(&scx, stmt)?,
         Statement::SetVariable(stmt) => scl::describe_set_variable(&scx, stmt)?,
         Statement::Show(ShowStatement::ShowVariable(stmt)) => {
             scl::describe_show_variable(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::ShowTransactionWrites(stmt)) => {
+            scl::describe_show_transaction_writes(&scx, stmt)?
+        }
 
         // DML statements.
         Statement::Copy(stmt) => dml::describe_copy(&scx, stmt)?,
         Statement::Delete(stmt) => dml::describe_delete(&scx, stmt)?,
         Statement::ExplainPlan(stmt) => dml::describe_explain_plan(&scx, stmt)?,
         Statement::ExplainPushdown(stmt) => dml::describe_explain_pushdown(&scx, stmt)?,
+        Statement::ExplainAnalyze(stmt) => dml::describe_explain_analyze(&scx, stmt)?,
         Statement::ExplainTimestamp(stmt) => dml::describe_explain_timestamp(&scx, stmt)?,
         Statement::ExplainSinkSchema(stmt) => dml::describe_explain_schema(&scx, stmt)?,
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
@@ -237,6 +253,7 @@ pub fn describe(
         Statement::StartTransaction(stmt) => tcl::describe_start_transaction(&scx, stmt)?,
 
         // Other statements.
+        Statement::Cancel(stmt) => scl::describe_cancel(&scx, stmt)?,
         Statement::Raise(stmt) => raise::describe_raise(&scx, stmt)?,
         Statement::Show(ShowStatement::InspectShard(stmt)) => {
             scl::describe_inspect_shard(&scx, stmt)?
@@ -310,6 +327,7 @@ pub fn plan(
         // DDL statements.
         Statement::AlterCluster(stmt) => ddl::plan_alter_cluster(scx, stmt),
         Statement::AlterConnection(stmt) => ddl::plan_alter_connection(scx, stmt),
+        Statement::AlterDatabase(stmt) => ddl::plan_alter_database(scx, stmt),
         Statement::AlterIndex(stmt) => ddl::plan_alter_index_options(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
         Statement::AlterObjectSwap(stmt) => ddl::plan_alter_object_swap(scx, stmt),
@@ -322,7 +340,11 @@ pub fn plan(
         Statement::AlterSystemSet(stmt) => ddl::plan_alter_system_set(scx, stmt),
         Statement::AlterSystemReset(stmt) => ddl::plan_alter_system_reset(scx, stmt),
         Statement::AlterSystemResetAll(stmt) => ddl::plan_alter_system_reset_all(scx, stmt),
+        Statement::AlterSystemTerminateSessions(stmt) => {
+            ddl::plan_alter_system_terminate_sessions(scx, stmt)
+        }
         Statement::AlterTableAddColumn(stmt) => ddl::plan_alter_table_add_column(scx, stmt),
+        Statement::AlterTableRowCountHint(stmt) => ddl::plan_alter_table_row_count_hint(scx, stmt),
         Statement::Comment(stmt) => ddl::plan_comment(scx, stmt),
         Statement::CreateCluster(stmt) => ddl::plan_create_cluster(scx, stmt),
         Statement::CreateClusterReplica(stmt) => ddl::plan_create_cluster_replica(scx, stmt),
@@ -360,6 +382,7 @@ pub fn plan(
         Statement::Delete(stmt) => dml::plan_delete(scx, stmt, params),
         Statement::ExplainPlan(stmt) => dml::plan_explain_plan(scx, stmt, params),
         Statement::ExplainPushdown(stmt) => dml::plan_explain_pushdown(scx, stmt, params),
+        Statement::ExplainAnalyze(stmt) => dml::plan_explain_analyze(scx, stmt),
         Statement::ExplainTimestamp(stmt) => dml::plan_explain_timestamp(scx, stmt, params),
         Statement::ExplainSinkSchema(stmt) => dml::plan_explain_schema(scx, stmt),
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
@@ -369,6 +392,9 @@ pub fn plan(
 
         // `SHOW` statements.
         Statement::Show(ShowStatement::ShowColumns(stmt)) => show::show_columns(scx, stmt)?.plan(),
+        Statement::Show(ShowStatement::ShowFrontiers(stmt)) => {
+            show::show_frontiers(scx, stmt)?.plan()
+        }
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::plan_show_create_connection(scx, stmt).map(Plan::ShowCreate)
         }
@@ -397,15 +423,20 @@ pub fn plan(
 
         // SCL statements.
         Statement::Close(stmt) => scl::plan_close(scx, stmt),
+        Statement::ClaimHandoff(stmt) => scl::plan_claim_handoff(scx, stmt),
         Statement::Deallocate(stmt) => scl::plan_deallocate(scx, stmt),
         Statement::Declare(stmt) => scl::plan_declare(scx, stmt, params),
         Statement::Discard(stmt) => scl::plan_discard(scx, stmt),
         Statement::Execute(stmt) => scl::plan_execute(scx, stmt),
         Statement::Fetch(stmt) => scl::plan_fetch(scx, stmt),
+        Statement::HandoffCursor(stmt) => scl::plan_handoff_cursor(scx, stmt),
         Statement::Prepare(stmt) => scl::plan_prepare(scx, stmt),
         Statement::ResetVariable(stmt) => scl::plan_reset_variable(scx, stmt),
         Statement::SetVariable(stmt) => scl::plan_set_variable(scx, stmt),
         Statement::Show(ShowStatement::ShowVariable(stmt)) => scl::plan_show_variable(scx, stmt),
+        Statement::Show(ShowStatement::ShowTransactionWrites(stmt)) => {
+            scl::plan_show_transaction_writes(scx, stmt)
+        }
 
         // TCL statements.
         Statement::Commit(stmt) => tcl::plan_commit(scx, stmt),
@@ -414,6 +445,7 @@ pub fn plan(
         Statement::StartTransaction(stmt) => tcl::plan_start_transaction(scx, stmt),
 
         // Other statements.
+        Statement::Cancel(stmt) => scl::plan_cancel(scx, stmt),
         Statement::Raise(stmt) => raise::plan_raise(scx, stmt),
         Statement::Show(ShowStatement::InspectShard(stmt)) => scl::plan_inspect_shard(scx, stmt),
         Statement::ValidateConnection(stmt) => validate::plan_validate_connection(scx, stmt),
@@ -989,11 +1021,49 @@ pub fn resolve_cluster_for_materialized_view<'a>(
     stmt: &CreateMaterializedViewStatement<Aug>,
 ) -> Result<ClusterId, PlanError> {
     Ok(match &stmt.in_cluster {
-        None => catalog.resolve_cluster(None)?.id(),
+        None => {
+            let database = match normalize::unresolved_item_name(stmt.name.clone())?.database {
+                Some(database) => {
+                    ResolvedDatabaseSpecifier::Id(catalog.resolve_database(&database)?.id())
+                }
+                None => catalog
+                    .active_database()
+                    .map_or(ResolvedDatabaseSpecifier::Ambient, |id| {
+                        ResolvedDatabaseSpecifier::Id(*id)
+                    }),
+            };
+            resolve_default_cluster(catalog, &database)?
+        }
         Some(in_cluster) => in_cluster.id,
     })
 }
 
+/// Resolves the cluster in which an object created in `database` is placed if
+/// no cluster is specified.
+///
+/// This is the default cluster of the database, if it has one that still
+/// exists, or the active cluster of the session otherwise.
+pub(crate) fn resolve_default_cluster(
+    catalog: &dyn SessionCatalog,
+    database: &ResolvedDatabaseSpecifier,
+) -> Result<ClusterId, PlanError> {
+    let default_cluster = match database {
+        ResolvedDatabaseSpecifier::Id(id) => catalog.get_database(id).default_cluster_id(),
+        ResolvedDatabaseSpecifier::Ambient => None,
+    };
+    match default_cluster {
+        Some(id)
+            if catalog
+                .get_clusters()
+                .iter()
+                .any(|cluster| cluster.id() == id) =>
+        {
+            Ok(id)
+        }
+        _ => Ok(catalog.resolve_cluster(None)?.id()),
+    }
+}
+
 /// Statement classification as documented by [`plan`].
 #[derive(Debug, Clone, Copy)]
 pub enum StatementClassification {
@@ -1020,6 +1090,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             // DDL statements.
             Statement::AlterCluster(_) => DDL,
             Statement::AlterConnection(_) => DDL,
+            Statement::AlterDatabase(_) => DDL,
             Statement::AlterIndex(_) => DDL,
             Statement::AlterObjectRename(_) => DDL,
             Statement::AlterObjectSwap(_) => DDL,
@@ -1032,7 +1103,9 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::AlterSystemSet(_) => DDL,
             Statement::AlterSystemReset(_) => DDL,
             Statement::AlterSystemResetAll(_) => DDL,
+            Statement::AlterSystemTerminateSessions(_) => Other,
             Statement::AlterTableAddColumn(_) => DDL,
+            Statement::AlterTableRowCountHint(_) => DDL,
             Statement::Comment(_) => DDL,
             Statement::CreateCluster(_) => DDL,
             Statement::CreateClusterReplica(_) => DDL,
@@ -1068,6 +1141,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::Delete(_) => DML,
             Statement::ExplainPlan(_) => DML,
             Statement::ExplainPushdown(_) => DML,
+            Statement::ExplainAnalyze(_) => DML,
             Statement::ExplainTimestamp(_) => DML,
             Statement::ExplainSinkSchema(_) => DML,
             Statement::Insert(_) => DML,
@@ -1077,6 +1151,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
 
             // `SHOW` statements.
             Statement::Show(ShowStatement::ShowColumns(_)) => Show,
+            Statement::Show(ShowStatement::ShowFrontiers(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateConnection(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateCluster(_)) => Show,
             Statement::Show(ShowStatement::ShowCreateIndex(_)) => Show,
@@ -1089,15 +1164,18 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
 
             // SCL statements.
             Statement::Close(_) => SCL,
+            Statement::ClaimHandoff(_) => SCL,
             Statement::Deallocate(_) => SCL,
             Statement::Declare(_) => SCL,
             Statement::Discard(_) => SCL,
             Statement::Execute(_) => SCL,
             Statement::Fetch(_) => SCL,
+            Statement::HandoffCursor(_) => SCL,
             Statement::Prepare(_) => SCL,
             Statement::ResetVariable(_) => SCL,
             Statement::SetVariable(_) => SCL,
             Statement::Show(ShowStatement::ShowVariable(_)) => SCL,
+            Statement::Show(ShowStatement::ShowTransactionWrites(_)) => SCL,
 
             // TCL statements.
             Statement::Commit(_) => TCL,
@@ -1106,6 +1184,7 @@ impl<T: mz_sql_parser::ast::AstInfo> From<&Statement<T>> for StatementClassifica
             Statement::StartTransaction(_) => TCL,
 
             // Other statements.
+            Statement::Cancel(_) => Other,
             Statement::Raise(_) => Other,
             Statement::Show(ShowStatement::InspectShard(_)) => Other,
             Statement::ValidateConnection(_) => Other,
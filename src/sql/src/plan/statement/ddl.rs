@@ -22,7 +22,7 @@ use mz_adapter_types::compaction::{CompactionWindow, DEFAULT_LOGICAL_COMPACTION_
 use mz_controller_types::{
     is_cluster_size_v2, ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL,
 };
-use mz_expr::{CollectionPlan, UnmaterializableFunc};
+use mz_expr::{like_pattern, CollectionPlan, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, DocTarget};
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::collections::{CollectionExt, HashSet};
@@ -43,36 +43,38 @@ use mz_repr::{
 use mz_sql_parser::ast::display::comma_separated;
 use mz_sql_parser::ast::{
     self, AlterClusterAction, AlterClusterStatement, AlterConnectionAction, AlterConnectionOption,
-    AlterConnectionOptionName, AlterConnectionStatement, AlterIndexAction, AlterIndexStatement,
-    AlterObjectRenameStatement, AlterObjectSwapStatement, AlterRetainHistoryStatement,
-    AlterRoleOption, AlterRoleStatement, AlterSecretStatement, AlterSetClusterStatement,
-    AlterSinkAction, AlterSinkStatement, AlterSourceAction, AlterSourceAddSubsourceOption,
-    AlterSourceAddSubsourceOptionName, AlterSourceStatement, AlterSystemResetAllStatement,
-    AlterSystemResetStatement, AlterSystemSetStatement, AlterTableAddColumnStatement, AvroSchema,
-    AvroSchemaOption, AvroSchemaOptionName, ClusterAlterOption, ClusterAlterOptionName,
-    ClusterAlterOptionValue, ClusterFeature, ClusterFeatureName, ClusterOption, ClusterOptionName,
-    ClusterScheduleOptionValue, ColumnOption, CommentObjectType, CommentStatement,
-    CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
-    CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
-    CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
-    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
-    CreateSourceOption, CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
-    CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableFromSourceStatement,
-    CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
-    CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
-    CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format,
-    FormatSpecifier, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption,
-    KeyConstraint, LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
+    AlterConnectionOptionName, AlterConnectionStatement, AlterDatabaseAction,
+    AlterDatabaseStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
+    AlterObjectSwapStatement, AlterRetainHistoryStatement, AlterRoleOption, AlterRoleStatement,
+    AlterSecretStatement, AlterSetClusterStatement, AlterSinkAction, AlterSinkStatement,
+    AlterSourceAction, AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName,
+    AlterSourceStatement, AlterSystemResetAllStatement, AlterSystemResetStatement,
+    AlterSystemSetStatement, AlterSystemTerminateSessionsStatement, AlterTableAddColumnStatement,
+    AlterTableRowCountHintStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName,
+    ClusterAlterOption, ClusterAlterOptionName, ClusterAlterOptionValue, ClusterFeature,
+    ClusterFeatureName, ClusterOption, ClusterOptionName, ClusterScheduleOptionValue, ColumnOption,
+    CommentObjectType, CommentStatement, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnectionOption, CreateConnectionOptionName, CreateConnectionStatement,
+    CreateConnectionType, CreateDatabaseStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateRoleStatement, CreateSchemaStatement,
+    CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
+    CreateSinkStatement, CreateSourceConnection, CreateSourceOption, CreateSourceOptionName,
+    CreateSourceStatement, CreateSubsourceOption, CreateSubsourceOptionName,
+    CreateSubsourceStatement, CreateTableFromSourceStatement, CreateTableStatement, CreateTypeAs,
+    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
+    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
+    CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf,
+    CsvColumns, DatabaseOption, DatabaseOptionName, DeferredItemName, DocOnIdentifier, DocOnSchema,
+    DropObjectsStatement, DropOwnedStatement, Expr, Format, FormatSpecifier, Ident,
+    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
+    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
     MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
     PgConfigOptionName, ProtobufSchema, QualifiedReplica, RefreshAtOptionValue,
     RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
-    ReplicaOptionName, RoleAttribute, SetRoleVar, SourceErrorPolicy, SourceIncludeMetadata,
-    Statement, TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName,
-    UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
-    WithOptionValue,
+    ReplicaOptionName, RoleAttribute, SessionFilterOp, SetRoleVar, SourceErrorPolicy,
+    SourceIncludeMetadata, Statement, TableConstraint, TableOption, TableOptionName,
+    UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value,
+    ViewDefinition, WithOptionValue,
 };
 use mz_sql_parser::ident;
 use mz_sql_parser::parser::StatementParseResult;
@@ -80,7 +82,7 @@ use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnecti
 use mz_storage_types::connections::{Connection, KafkaTopicOptions};
 use mz_storage_types::sinks::{
     KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, SinkEnvelope,
-    SinkPartitionStrategy, StorageSinkConnection,
+    SinkPartitionStrategy, StorageSinkConnection, WebhookSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
     included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
@@ -130,23 +132,26 @@ use crate::plan::with_options::{OptionalDuration, OptionalString, TryFromValue};
 use crate::plan::{
     literal, plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterRenamePlan,
     AlterClusterReplicaRenamePlan, AlterClusterStrategyCondition, AlterClusterSwapPlan,
-    AlterConnectionPlan, AlterItemRenamePlan, AlterNoopPlan, AlterOptionParameter,
-    AlterRetainHistoryPlan, AlterRolePlan, AlterSchemaRenamePlan, AlterSchemaSwapPlan,
-    AlterSecretPlan, AlterSetClusterPlan, AlterSystemResetAllPlan, AlterSystemResetPlan,
-    AlterSystemSetPlan, AlterTablePlan, ClusterSchedule, CommentPlan, ComputeReplicaConfig,
-    ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
-    CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
+    AlterConnectionPlan, AlterDatabasePlan, AlterItemRenamePlan, AlterNoopPlan,
+    AlterOptionParameter, AlterRetainHistoryPlan, AlterRolePlan, AlterSchemaRenamePlan,
+    AlterSchemaSwapPlan, AlterSecretPlan, AlterSetClusterPlan, AlterSourcePlan,
+    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan,
+    AlterSystemTerminateSessionsPlan, AlterTablePlan, AlterTableRowCountHintPlan, ClusterSchedule,
+    CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan,
+    CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
     CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
     CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
     CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
     DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
+    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, SessionFilterMatcher,
+    SessionFilterPlan, Sink, Source, Table, Type, VariableValue, View, WebhookBodyFormat,
+    WebhookHeaderFilters, WebhookHeaders,
 };
 use crate::plan::{AlterClusterPlanStrategy, AlterSinkPlan, WebhookValidation};
 use crate::session::vars;
 use crate::session::vars::{
     ENABLE_CLUSTER_SCHEDULE_REFRESH, ENABLE_KAFKA_SINK_HEADERS, ENABLE_REFRESH_EVERY_MVS,
+    ENABLE_WEBHOOK_SINKS,
 };
 use crate::{names, parse};
 
@@ -384,13 +389,22 @@ pub fn plan_create_table(
 
     let options = plan_table_options(scx, with_options.clone())?;
     let compaction_window = options.iter().find_map(|o| {
-        #[allow(irrefutable_let_patterns)]
         if let crate::plan::TableOption::RetainHistory(lcw) = o {
             Some(lcw.clone())
         } else {
             None
         }
     });
+    let change_audit = options
+        .iter()
+        .any(|o| matches!(o, crate::plan::TableOption::ChangeAudit));
+    let row_count_hint = options.iter().find_map(|o| {
+        if let crate::plan::TableOption::RowCountHint(hint) = o {
+            Some(*hint)
+        } else {
+            None
+        }
+    });
 
     let table = Table {
         create_sql,
@@ -398,6 +412,8 @@ pub fn plan_create_table(
         defaults,
         temporary,
         compaction_window,
+        change_audit,
+        row_count_hint,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
         name,
@@ -469,17 +485,18 @@ pub fn plan_create_webhook_source(
 ) -> Result<Plan, PlanError> {
     // We will rewrite the cluster if one is not provided, so we must use the `in_cluster` value
     // we plan to normalize when we canonicalize the create statement.
-    let in_cluster = source_sink_cluster_config(scx, "source", &mut stmt.in_cluster)?;
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(stmt.name.clone())?)?;
+    let in_cluster = source_sink_cluster_config(scx, "source", &name, &mut stmt.in_cluster)?;
     let create_sql =
         normalize::create_statement(scx, Statement::CreateWebhookSource(stmt.clone()))?;
 
     let CreateWebhookSourceStatement {
-        name,
+        // We resolved `name` and `in_cluster` above, so we want to ignore them here.
+        name: _,
         if_not_exists,
         body_format,
         include_headers,
         validate_using,
-        // We resolved `in_cluster` above, so we want to ignore it here.
         in_cluster: _,
     } = stmt;
 
@@ -588,7 +605,6 @@ pub fn plan_create_webhook_source(
     let desc = RelationDesc::new(typ, column_names);
 
     // Check for an object in the catalog with this same name
-    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
     let full_name = scx.catalog.resolve_full_name(&name);
     let partial_name = PartialItemName::from(full_name.clone());
     if let (false, Ok(item)) = (if_not_exists, scx.catalog.resolve_item(&partial_name)) {
@@ -1382,7 +1398,7 @@ pub fn plan_create_source(
     // We will rewrite the cluster if one is not provided, so we must use the
     // `in_cluster` value we plan to normalize when we canonicalize the create
     // statement.
-    let in_cluster = source_sink_cluster_config(scx, "source", &mut stmt.in_cluster)?;
+    let in_cluster = source_sink_cluster_config(scx, "source", &name, &mut stmt.in_cluster)?;
 
     let create_sql = normalize::create_statement(scx, Statement::CreateSource(stmt))?;
 
@@ -1898,11 +1914,16 @@ fn get_encoding(
 fn source_sink_cluster_config(
     scx: &StatementContext,
     ty: &'static str,
+    name: &QualifiedItemName,
     in_cluster: &mut Option<ResolvedClusterName>,
 ) -> Result<ClusterId, PlanError> {
     let cluster = match in_cluster {
         None => {
-            let cluster = scx.catalog.resolve_cluster(None)?;
+            let cluster_id = crate::plan::statement::resolve_default_cluster(
+                scx.catalog,
+                &name.qualifiers.database_spec,
+            )?;
+            let cluster = scx.catalog.get_cluster(cluster_id);
             *in_cluster = Some(ResolvedClusterName {
                 id: cluster.id(),
                 print_name: None,
@@ -2678,6 +2699,13 @@ fn plan_sink(
         None => sql_bail!("ENVELOPE clause is required"),
     };
 
+    if let CreateSinkConnection::Webhook { .. } = &connection {
+        scx.require_feature_flag(&ENABLE_WEBHOOK_SINKS)?;
+        if envelope != SinkEnvelope::Debezium {
+            sql_bail!("webhook sinks only support ENVELOPE DEBEZIUM");
+        }
+    }
+
     let from_name = &from;
     let from = scx.get_item_by_resolved_name(&from)?;
     if from.id().is_system() {
@@ -2747,6 +2775,7 @@ fn plan_sink(
                 None
             }
         }
+        CreateSinkConnection::Webhook { .. } => None,
     };
 
     let headers_index = match &connection {
@@ -2820,6 +2849,7 @@ fn plan_sink(
             envelope,
             from.id(),
         )?,
+        CreateSinkConnection::Webhook { url } => webhook_sink_builder(url, format)?,
     };
 
     let CreateSinkOptionExtracted {
@@ -2844,7 +2874,7 @@ fn plan_sink(
     // We will rewrite the cluster if one is not provided, so we must use the
     // `in_cluster` value we plan to normalize when we canonicalize the create
     // statement.
-    let in_cluster = source_sink_cluster_config(scx, "sink", &mut stmt.in_cluster)?;
+    let in_cluster = source_sink_cluster_config(scx, "sink", &name, &mut stmt.in_cluster)?;
     let create_sql = normalize::create_statement(scx, Statement::CreateSink(stmt))?;
 
     Ok(Plan::CreateSink(CreateSinkPlan {
@@ -3217,6 +3247,28 @@ fn kafka_sink_builder(
     }))
 }
 
+fn webhook_sink_builder(
+    url: String,
+    format: Option<FormatSpecifier<Aug>>,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    // Webhook sinks always deliver JSON documents shaped like Debezium records.
+    if format.is_some() {
+        sql_bail!("webhook sinks do not support FORMAT");
+    }
+
+    let parsed = reqwest::Url::parse(&url).map_err(|e| sql_err!("invalid webhook URL: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        sql_bail!(
+            "invalid webhook URL: scheme must be http or https, found {}",
+            parsed.scheme()
+        );
+    }
+
+    Ok(StorageSinkConnection::Webhook(WebhookSinkConnection {
+        url,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -3234,6 +3286,7 @@ pub fn plan_create_index(
         in_cluster,
         key_parts,
         with_options,
+        predicate,
         if_not_exists,
     } = &mut stmt;
     let on = scx.get_item_by_resolved_name(on_name)?;
@@ -3271,6 +3324,17 @@ pub fn plan_create_index(
         }
     };
     let keys = query::plan_index_exprs(scx, &on_desc, filled_key_parts.clone())?;
+    let predicate = match predicate {
+        Some(predicate) => {
+            scx.require_feature_flag(&vars::ENABLE_PARTIAL_INDEXES)?;
+            Some(query::plan_index_predicate(
+                scx,
+                &on_desc,
+                predicate.clone(),
+            )?)
+        }
+        None => None,
+    };
 
     let index_name = if let Some(name) = name {
         QualifiedItemName {
@@ -3333,7 +3397,10 @@ pub fn plan_create_index(
 
     let options = plan_index_options(scx, with_options.clone())?;
     let cluster_id = match in_cluster {
-        None => scx.resolve_cluster(None)?.id(),
+        None => crate::plan::statement::resolve_default_cluster(
+            scx.catalog,
+            &index_name.qualifiers.database_spec,
+        )?,
         Some(in_cluster) => in_cluster.id,
     };
 
@@ -3365,6 +3432,7 @@ pub fn plan_create_index(
             create_sql,
             on: on.id(),
             keys,
+            predicate,
             cluster_id,
             compaction_window,
         },
@@ -4923,6 +4991,8 @@ fn plan_index_options(
 generate_extracted_config!(
     TableOption,
     (RetainHistory, OptionalDuration),
+    (ChangeAudit, bool, Default(false)),
+    (RowCountHint, u64),
     (RedactedTest, String)
 );
 
@@ -4932,6 +5002,8 @@ fn plan_table_options(
 ) -> Result<Vec<crate::plan::TableOption>, PlanError> {
     let TableOptionExtracted {
         retain_history,
+        change_audit,
+        row_count_hint,
         redacted_test,
         ..
     }: TableOptionExtracted = with_opts.try_into()?;
@@ -4940,10 +5012,17 @@ fn plan_table_options(
         scx.require_feature_flag(&vars::ENABLE_REDACTED_TEST_OPTION)?;
     }
 
-    let mut out = Vec::with_capacity(1);
+    let mut out = Vec::with_capacity(3);
     if let Some(cw) = plan_retain_history_option(scx, retain_history)? {
         out.push(crate::plan::TableOption::RetainHistory(cw));
     }
+    if change_audit {
+        out.push(crate::plan::TableOption::ChangeAudit);
+    }
+    if let Some(hint) = row_count_hint {
+        scx.require_feature_flag(&vars::ENABLE_ROW_COUNT_HINTS)?;
+        out.push(crate::plan::TableOption::RowCountHint(hint));
+    }
     Ok(out)
 }
 
@@ -5256,6 +5335,64 @@ pub fn plan_alter_cluster(
     }))
 }
 
+generate_extracted_config!(DatabaseOption, (DefaultCluster, Ident));
+
+pub fn describe_alter_database(
+    _: &StatementContext,
+    _: AlterDatabaseStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_database(
+    scx: &mut StatementContext,
+    AlterDatabaseStatement {
+        if_exists,
+        name,
+        action,
+    }: AlterDatabaseStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let database = match resolve_database(scx, &name, if_exists)? {
+        Some(database) => database,
+        None => {
+            scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                name: name.to_ast_string(),
+                object_type: ObjectType::Database,
+            });
+
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Database,
+            }));
+        }
+    };
+
+    let mut default_cluster = database.default_cluster_id();
+    match action {
+        AlterDatabaseAction::SetOptions(options) => {
+            let DatabaseOptionExtracted {
+                default_cluster: cluster_name,
+                seen: _,
+            }: DatabaseOptionExtracted = options.try_into()?;
+            if let Some(cluster_name) = cluster_name {
+                default_cluster = Some(scx.resolve_cluster(Some(&cluster_name))?.id());
+            }
+        }
+        AlterDatabaseAction::ResetOptions(options) => {
+            for option in options {
+                match option {
+                    DatabaseOptionName::DefaultCluster => default_cluster = None,
+                }
+            }
+        }
+    }
+
+    Ok(Plan::AlterDatabase(AlterDatabasePlan {
+        id: database.id(),
+        name: database.name().to_string(),
+        default_cluster,
+    }))
+}
+
 pub fn describe_alter_set_cluster(
     _: &StatementContext,
     _: AlterSetClusterStatement<Aug>,
@@ -6010,6 +6147,9 @@ pub fn plan_alter_sink(
             let Statement::CreateSink(mut stmt) = stmt.ast else {
                 unreachable!("invalid create SQL for sink item");
             };
+            if matches!(stmt.connection, CreateSinkConnection::Webhook { .. }) {
+                bail_unsupported!("ALTER SINK ... SET FROM for webhook sinks");
+            }
 
             // And then we find the existing version of the sink and increase it by one
             let cur_version = stmt
@@ -6030,6 +6170,11 @@ pub fn plan_alter_sink(
             let (mut stmt, _) = crate::names::resolve(scx.catalog, stmt)?;
             stmt.from = new_from;
 
+            // Replace the comments on the old relation with the ones on the new relation, which
+            // get frozen into the Avro schemas of the sink like during purification.
+            crate::pure::remove_unrelated_comments(scx.catalog, &mut stmt);
+            crate::pure::add_materialize_comments(scx.catalog, &mut stmt)?;
+
             // Finally re-plan the modified create sink statement to verify the new configuration is valid
             let Plan::CreateSink(plan) = plan_sink(scx, stmt)? else {
                 unreachable!("invalid plan for CREATE SINK statement");
@@ -6073,14 +6218,15 @@ pub fn plan_alter_source(
     } = stmt;
     let object_type = ObjectType::Source;
 
-    if resolve_item_or_type(scx, object_type, source_name.clone(), if_exists)?.is_none() {
+    let Some(source) = resolve_item_or_type(scx, object_type, source_name.clone(), if_exists)?
+    else {
         scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
             name: source_name.to_string(),
             object_type,
         });
 
         return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
-    }
+    };
 
     match action {
         AlterSourceAction::SetOptions(options) => {
@@ -6122,8 +6268,67 @@ pub fn plan_alter_source(
             }
             sql_bail!("Cannot modify the {} of a SOURCE.", option.to_ast_string());
         }
-        AlterSourceAction::DropSubsources { .. } => {
-            sql_bail!("ALTER SOURCE...DROP SUBSOURCE no longer supported; use DROP SOURCE")
+        AlterSourceAction::DropSubsources {
+            if_exists: subsources_if_exist,
+            cascade,
+            names,
+        } => {
+            let mut subsources = Vec::new();
+            for name in names {
+                let Some(id) = plan_drop_item(
+                    scx,
+                    ObjectType::Source,
+                    subsources_if_exist,
+                    name.clone(),
+                    cascade,
+                )?
+                else {
+                    scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                        name: name.to_ast_string(),
+                        object_type: ObjectType::Source,
+                    });
+                    continue;
+                };
+                let subsource = scx.catalog.get_item(&id);
+                // The progress subsource holds the remap shard of the other subsources, so it
+                // must live as long as the source itself.
+                if source.progress_id() == Some(id) {
+                    sql_bail!(
+                        "cannot drop progress subsource {} of {}; use DROP SOURCE to drop the \
+                         source",
+                        scx.catalog.minimal_qualification(subsource.name()),
+                        scx.catalog.minimal_qualification(source.name()),
+                    );
+                }
+                if subsource
+                    .subsource_details()
+                    .map(|(ingestion_id, _)| ingestion_id)
+                    != Some(source.id())
+                {
+                    sql_bail!(
+                        "{} is not a subsource of {}",
+                        scx.catalog.minimal_qualification(subsource.name()),
+                        scx.catalog.minimal_qualification(source.name()),
+                    );
+                }
+                if !subsources.contains(&id) {
+                    subsources.push(id);
+                }
+            }
+
+            if subsources.is_empty() {
+                return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
+            }
+
+            let referenced_ids: Vec<_> = subsources.iter().copied().map(ObjectId::Item).collect();
+            let drop_ids = scx.catalog.object_dependents(&referenced_ids);
+            Ok(Plan::AlterSource(AlterSourcePlan {
+                id: source.id(),
+                action: crate::plan::AlterSourceAction::DropSubsourceExports {
+                    subsources,
+                    drop_ids,
+                },
+            }))
         }
         AlterSourceAction::AddSubsources { .. } => {
             unreachable!("ALTER SOURCE...ADD SUBSOURCE must be purified")
@@ -6178,6 +6383,44 @@ pub fn plan_alter_system_reset_all(
     Ok(Plan::AlterSystemResetAll(AlterSystemResetAllPlan {}))
 }
 
+pub fn describe_alter_system_terminate_sessions(
+    _: &StatementContext,
+    _: AlterSystemTerminateSessionsStatement,
+) -> Result<StatementDesc, PlanError> {
+    let desc = RelationDesc::empty()
+        .with_column("connection_id", ScalarType::UInt32.nullable(false))
+        .with_column("user", ScalarType::String.nullable(false))
+        .with_column("application_name", ScalarType::String.nullable(false))
+        .with_column("canceled_peeks", ScalarType::UInt64.nullable(false))
+        .with_column("canceled_subscribes", ScalarType::UInt64.nullable(false))
+        .with_column("canceled_statement", ScalarType::Bool.nullable(false));
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_alter_system_terminate_sessions(
+    _: &StatementContext,
+    AlterSystemTerminateSessionsStatement { filters }: AlterSystemTerminateSessionsStatement,
+) -> Result<Plan, PlanError> {
+    let filters = filters
+        .into_iter()
+        .map(|filter| {
+            let matcher = match filter.op {
+                SessionFilterOp::Eq => SessionFilterMatcher::Equals(filter.value),
+                SessionFilterOp::Like => {
+                    SessionFilterMatcher::Like(like_pattern::compile(&filter.value, false)?)
+                }
+            };
+            Ok(SessionFilterPlan {
+                field: filter.field,
+                matcher,
+            })
+        })
+        .collect::<Result<_, PlanError>>()?;
+    Ok(Plan::AlterSystemTerminateSessions(
+        AlterSystemTerminateSessionsPlan { filters },
+    ))
+}
+
 pub fn describe_alter_role(
     _: &StatementContext,
     _: AlterRoleStatement<Aug>,
@@ -6268,6 +6511,51 @@ pub fn plan_alter_table_add_column(
     }))
 }
 
+pub fn describe_alter_table_row_count_hint(
+    _: &StatementContext,
+    _: AlterTableRowCountHintStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_table_row_count_hint(
+    scx: &StatementContext,
+    AlterTableRowCountHintStatement {
+        if_exists,
+        name,
+        hint,
+    }: AlterTableRowCountHintStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let object_type = ObjectType::Table;
+
+    scx.require_feature_flag(&vars::ENABLE_ROW_COUNT_HINTS)?;
+
+    let Some(entry) = resolve_item_or_type(scx, object_type, name.clone(), if_exists)? else {
+        scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+            name: name.to_ast_string(),
+            object_type,
+        });
+        return Ok(Plan::AlterNoop(AlterNoopPlan { object_type }));
+    };
+
+    // Save the original value so we can write it back down in the create_sql catalog item.
+    let (value, hint) = match hint {
+        Some(WithOptionValue::Value(value)) => {
+            let hint = u64::try_from_value(value.clone())?;
+            (Some(value), Some(hint))
+        }
+        // None is RESET, which removes the hint.
+        None => (None, None),
+        Some(_) => sql_bail!("invalid ROW COUNT HINT: expected a non-negative integer"),
+    };
+
+    Ok(Plan::AlterTableRowCountHint(AlterTableRowCountHintPlan {
+        id: entry.id(),
+        value,
+        hint,
+    }))
+}
+
 pub fn describe_comment(
     _: &StatementContext,
     _: CommentStatement<Aug>,
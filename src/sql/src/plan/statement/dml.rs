@@ -19,9 +19,11 @@ use itertools::Itertools;
 
 use mz_adapter_types::dyncfgs::DEFAULT_SINK_PARTITION_STRATEGY;
 use mz_arrow_util::builder::ArrowBuilder;
-use mz_expr::{MirRelationExpr, RowSetFinishing};
+use mz_expr::{BinaryFunc, ColumnOrder, Id, LocalId, MirRelationExpr, RowSetFinishing};
+use mz_ore::cast::CastFrom;
 use mz_ore::num::NonNeg;
 use mz_ore::soft_panic_or_log;
+use mz_ore::str::separated;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::bytes::ByteSize;
@@ -29,12 +31,13 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::{Datum, GlobalId, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
-    CreateSinkOption, CreateSinkOptionName, CteBlock, ExplainPlanOption, ExplainPlanOptionName,
-    ExplainPushdownStatement, ExplainSinkSchemaFor, ExplainSinkSchemaStatement,
-    ExplainTimestampStatement, Expr, IfExistsBehavior, OrderByExpr, SetExpr, SubscribeOutput,
-    UnresolvedItemName, Value, WithOptionValue,
+    CreateSinkOption, CreateSinkOptionName, CteBlock, ExplainAnalyzeStatement, ExplainPlanOption,
+    ExplainPlanOptionName, ExplainPushdownStatement, ExplainSinkSchemaFor,
+    ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr, IfExistsBehavior, OrderByExpr,
+    SetExpr, SubscribeOutput, UnresolvedItemName, Value, WithOptionValue,
 };
 use mz_sql_parser::ident;
+use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::sinks::{
     KafkaSinkConnection, KafkaSinkFormat, KafkaSinkFormatType, S3SinkFormat, StorageSinkConnection,
     MAX_S3_SINK_FILE_SIZE, MIN_S3_SINK_FILE_SIZE,
@@ -43,20 +46,23 @@ use mz_storage_types::sinks::{
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
-    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement, Query,
-    SelectStatement, SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement,
-    UpdateStatement,
+    DeleteStatement, ExecuteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident,
+    InsertStatement, Query, SelectStatement, SelectStatementOption, SelectStatementOptionName,
+    Statement, SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement,
+    UpdateStatement, WriteStatementOption, WriteStatementOptionName,
 };
-use crate::catalog::CatalogItemType;
-use crate::names::{Aug, ResolvedItemName};
+use crate::catalog::{CatalogError, CatalogItemType};
+use crate::names::{self, Aug, ResolvedDataType, ResolvedItemName};
 use crate::normalize;
+use crate::plan::expr::AggregateFunc;
 use crate::plan::query::{plan_expr, plan_up_to, ExprContext, QueryLifetime};
 use crate::plan::scope::Scope;
 use crate::plan::statement::{ddl, StatementContext, StatementDesc};
 use crate::plan::with_options::{self, TryFromValue};
 use crate::plan::{
-    self, side_effecting_func, transform_ast, CopyToPlan, CreateSinkPlan, ExplainPushdownPlan,
-    ExplainSinkSchemaPlan, ExplainTimestampPlan,
+    self, side_effecting_func, transform_ast, AggregateExpr, CopyToPlan, CreateSinkPlan,
+    ExplainAnalyzePlan, ExplainPushdownPlan, ExplainSinkSchemaPlan, ExplainTimestampPlan,
+    HirRelationExpr, HirScalarExpr, JoinKind,
 };
 use crate::plan::{
     query, CopyFormat, CopyFromPlan, ExplainPlanPlan, InsertPlan, MutationKind, Params, Plan,
@@ -64,6 +70,21 @@ use crate::plan::{
 };
 use crate::session::vars;
 
+generate_extracted_config!(WriteStatementOption, (DryRun, bool, Default(false)));
+
+/// Plans the `WITH` options of an `INSERT`, `UPDATE` or `DELETE` statement,
+/// returning whether the write is a dry run.
+fn plan_write_statement_options(
+    scx: &StatementContext,
+    options: Vec<WriteStatementOption<Aug>>,
+) -> Result<bool, PlanError> {
+    let WriteStatementOptionExtracted { dry_run, .. } = options.try_into()?;
+    if dry_run {
+        scx.require_feature_flag(&vars::ENABLE_WRITE_DRY_RUN)?;
+    }
+    Ok(dry_run)
+}
+
 // TODO(benesch): currently, describing a `SELECT` or `INSERT` query
 // plans the whole query to determine its shape and parameter types,
 // and then throws away that plan. If we were smarter, we'd stash that
@@ -77,6 +98,7 @@ pub fn describe_insert(
         columns,
         source,
         returning,
+        options: _,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
     let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
@@ -95,9 +117,11 @@ pub fn plan_insert(
         columns,
         source,
         returning,
+        options,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    let dry_run = plan_write_statement_options(scx, options)?;
     let (id, mut expr, returning) =
         query::plan_insert_query(scx, table_name, columns, source, returning)?;
     expr.bind_parameters(params)?;
@@ -111,6 +135,7 @@ pub fn plan_insert(
         id,
         values: expr,
         returning,
+        dry_run,
     }))
 }
 
@@ -124,11 +149,12 @@ pub fn describe_delete(
 
 pub fn plan_delete(
     scx: &StatementContext,
-    stmt: DeleteStatement<Aug>,
+    mut stmt: DeleteStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    let dry_run = plan_write_statement_options(scx, std::mem::take(&mut stmt.options))?;
     let rtw_plan = query::plan_delete_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Delete, params, rtw_plan)
+    plan_read_then_write(MutationKind::Delete, params, rtw_plan, dry_run)
 }
 
 pub fn describe_update(
@@ -141,11 +167,12 @@ pub fn describe_update(
 
 pub fn plan_update(
     scx: &StatementContext,
-    stmt: UpdateStatement<Aug>,
+    mut stmt: UpdateStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
+    let dry_run = plan_write_statement_options(scx, std::mem::take(&mut stmt.options))?;
     let rtw_plan = query::plan_update_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Update, params, rtw_plan)
+    plan_read_then_write(MutationKind::Update, params, rtw_plan, dry_run)
 }
 
 pub fn plan_read_then_write(
@@ -157,6 +184,7 @@ pub fn plan_read_then_write(
         finishing,
         assignments,
     }: query::ReadThenWritePlan,
+    dry_run: bool,
 ) -> Result<Plan, PlanError> {
     selection.bind_parameters(params)?;
     let mut assignments_outer = BTreeMap::new();
@@ -173,9 +201,16 @@ pub fn plan_read_then_write(
         assignments: assignments_outer,
         kind,
         returning: Vec::new(),
+        dry_run,
     }))
 }
 
+generate_extracted_config!(
+    SelectStatementOption,
+    (AfterToken, String),
+    (MirrorToCluster, Ident)
+);
+
 pub fn describe_select(
     scx: &StatementContext,
     stmt: SelectStatement<Aug>,
@@ -199,30 +234,55 @@ pub fn plan_select(
         return Ok(Plan::SideEffectingFunc(f));
     }
 
-    let (plan, _desc) = plan_select_inner(scx, select, params, copy_to)?;
+    let (plan, _desc) = plan_select_inner(scx, select, params, 0, copy_to)?;
     Ok(Plan::Select(plan))
 }
 
+/// Plans `select`, binding its parameters to `params`.
+///
+/// If `unbound_parameters` is not zero, the statement must only refer to that
+/// many parameters, which are left unbound. Their types must have been seeded
+/// with [`with_parameter_types`].
 fn plan_select_inner(
     scx: &StatementContext,
     select: SelectStatement<Aug>,
     params: &Params,
+    unbound_parameters: usize,
     copy_to: Option<CopyFormat>,
 ) -> Result<(SelectPlan, RelationDesc), PlanError> {
+    let SelectStatementOptionExtracted {
+        after_token,
+        mirror_to_cluster,
+        ..
+    } = select.options.clone().try_into()?;
     let when = query::plan_as_of(scx, select.as_of.clone())?;
+    let when = query::plan_after_token(scx, when, after_token)?;
+    let mirror = match mirror_to_cluster {
+        Some(name) => {
+            scx.require_feature_flag(&vars::ENABLE_PEEK_MIRRORING)?;
+            Some(scx.resolve_cluster(Some(&name))?.id())
+        }
+        None => None,
+    };
     let query::PlannedRootQuery {
         mut expr,
         desc,
         finishing,
         scope: _,
     } = query::plan_root_query(scx, select.query.clone(), QueryLifetime::OneShot)?;
-    expr.bind_parameters(params)?;
+    if unbound_parameters == 0 {
+        expr.bind_parameters(params)?;
+    } else {
+        check_parameters_declared(scx, unbound_parameters)?;
+    }
 
     // A top-level limit cannot be data dependent so eagerly evaluate it.
     let limit = match finishing.limit {
         None => None,
         Some(mut limit) => {
-            limit.bind_parameters(params)?;
+            if unbound_parameters == 0 {
+                limit.bind_parameters(params)?;
+            }
             let Some(limit) = limit.as_literal() else {
                 sql_bail!("Top-level LIMIT must be a constant expression")
             };
@@ -248,11 +308,43 @@ fn plan_select_inner(
         },
         copy_to,
         select: Some(select),
+        mirror,
     };
 
     Ok((plan, desc))
 }
 
+/// Runs `f` with the parameters of the statement typed as `parameters`.
+///
+/// This is how `EXPLAIN (PARAMETERS (...))` plans parameterized statements
+/// without values for their parameters, which stay unbound in the plan. The
+/// parameter types of the enclosing statement are restored afterwards.
+fn with_parameter_types<R>(
+    scx: &StatementContext,
+    parameters: &[ResolvedDataType],
+    f: impl FnOnce() -> Result<R, PlanError>,
+) -> Result<R, PlanError> {
+    let mut param_types = BTreeMap::new();
+    for (i, ty) in parameters.iter().enumerate() {
+        param_types.insert(i + 1, query::scalar_type_from_sql(scx, ty)?);
+    }
+    let outer = scx.param_types.replace(param_types);
+    let result = f().and_then(|result| {
+        check_parameters_declared(scx, parameters.len())?;
+        Ok(result)
+    });
+    scx.param_types.replace(outer);
+    result
+}
+
+/// Errors if the statement refers to parameters beyond the first `n`.
+fn check_parameters_declared(scx: &StatementContext, n: usize) -> Result<(), PlanError> {
+    match scx.param_types.borrow().keys().find(|i| **i > n) {
+        Some(i) => Err(PlanError::UnknownParameter(*i)),
+        None => Ok(()),
+    }
+}
+
 pub fn describe_explain_plan(
     scx: &StatementContext,
     explain: ExplainPlanStatement<Aug>,
@@ -290,8 +382,29 @@ pub fn describe_explain_plan(
             let name = "Plan Insights";
             relation_desc = relation_desc.with_column(name, ScalarType::String.nullable(false));
         }
+        ExplainStage::Bundle => {
+            let name = "Bundle";
+            relation_desc = relation_desc.with_column(name, ScalarType::String.nullable(false));
+        }
+        ExplainStage::Emptiness => {
+            relation_desc = relation_desc
+                .with_column("Stage", ScalarType::Int64.nullable(false))
+                .with_column("Operator", ScalarType::String.nullable(false))
+                .with_column("Rows", ScalarType::Int64.nullable(false))
+                .with_column("Eliminates All Rows", ScalarType::Bool.nullable(false));
+        }
     };
 
+    if !explain.parameters.is_empty() {
+        // The parameters are typed by the statement, so the explainee doesn't
+        // add any parameters to the `EXPLAIN` itself.
+        let Explainee::Select(select, _) = explain.explainee else {
+            sql_bail!("EXPLAIN with PARAMETERS is only supported for SELECT statements");
+        };
+        with_parameter_types(scx, &explain.parameters, || describe_select(scx, *select))?;
+        return Ok(StatementDesc::new(Some(relation_desc)));
+    }
+
     Ok(
         StatementDesc::new(Some(relation_desc)).with_params(match explain.explainee {
             Explainee::Select(select, _) => describe_select(scx, *select)?.param_types,
@@ -319,6 +432,19 @@ pub fn describe_explain_pushdown(
     )
 }
 
+pub fn describe_explain_analyze(
+    _: &StatementContext,
+    _: ExplainAnalyzeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    let relation_desc = RelationDesc::empty()
+        .with_column("Operator", ScalarType::String.nullable(false))
+        .with_column("Elapsed", ScalarType::Interval.nullable(true))
+        .with_column("Memory", ScalarType::Int64.nullable(true))
+        .with_column("Records", ScalarType::Int64.nullable(true));
+
+    Ok(StatementDesc::new(Some(relation_desc)))
+}
+
 pub fn describe_explain_timestamp(
     scx: &StatementContext,
     ExplainTimestampStatement { select, .. }: ExplainTimestampStatement<Aug>,
@@ -344,11 +470,13 @@ generate_extracted_config!(
     (Arity, bool, Default(false)),
     (Cardinality, bool, Default(false)),
     (ColumnNames, bool, Default(false)),
+    (Comments, bool, Default(false)),
     (FilterPushdown, bool, Default(false)),
     (HumanizedExpressions, bool, Default(false)),
     (JoinImplementations, bool, Default(false)),
     (Keys, bool, Default(false)),
     (LinearChains, bool, Default(false)),
+    (MemoryEstimates, bool, Default(false)),
     (NoFastPath, bool, Default(false)),
     (NonNegative, bool, Default(false)),
     (NoNotices, bool, Default(false)),
@@ -357,6 +485,8 @@ generate_extracted_config!(
     (RawPlans, bool, Default(false)),
     (RawSyntax, bool, Default(false)),
     (Redacted, bool, Default(false)),
+    (RowEstimates, bool, Default(false)),
+    (SubqueryLowering, bool, Default(false)),
     (SubtreeSize, bool, Default(false)),
     (Timing, bool, Default(false)),
     (Types, bool, Default(false)),
@@ -390,18 +520,24 @@ impl TryFrom<ExplainPlanOptionExtracted> for ExplainConfig {
             arity: v.arity || enable_on_prod,
             cardinality: v.cardinality,
             column_names: v.column_names,
+            comments: v.comments,
             filter_pushdown: v.filter_pushdown || enable_on_prod,
             humanized_exprs: !v.raw_plans && (v.humanized_expressions || enable_on_prod),
             join_impls: v.join_implementations,
             keys: v.keys,
             linear_chains: !v.raw_plans && v.linear_chains,
+            memory_estimates: v.memory_estimates,
             no_fast_path: v.no_fast_path,
             no_notices: v.no_notices,
             node_ids: v.node_identifiers,
             non_negative: v.non_negative,
+            // Set from the `PARAMETERS` of the statement, which are not a `WITH` option.
+            parameters: vec![],
             raw_plans: v.raw_plans,
             raw_syntax: v.raw_syntax,
             redacted: v.redacted,
+            row_estimates: v.row_estimates,
+            subquery_lowering: v.subquery_lowering,
             subtree_size: v.subtree_size,
             timing: v.timing,
             types: v.types,
@@ -426,6 +562,7 @@ fn plan_explainee(
     scx: &StatementContext,
     explainee: Explainee<Aug>,
     params: &Params,
+    unbound_parameters: usize,
 ) -> Result<plan::Explainee, PlanError> {
     use crate::plan::ExplaineeStatement;
 
@@ -469,7 +606,7 @@ fn plan_explainee(
             }
         }
         Explainee::Select(select, broken) => {
-            let (plan, desc) = plan_select_inner(scx, *select, params, None)?;
+            let (plan, desc) = plan_select_inner(scx, *select, params, unbound_parameters, None)?;
             crate::plan::Explainee::Statement(ExplaineeStatement::Select { broken, plan, desc })
         }
         Explainee::CreateView(mut stmt, broken) => {
@@ -535,6 +672,39 @@ fn plan_explainee(
 
             crate::plan::Explainee::Statement(ExplaineeStatement::CreateIndex { broken, plan })
         }
+        Explainee::Subscribe(stmt, broken) => {
+            if stmt.into.is_some() {
+                sql_bail!("EXPLAIN is not supported for SUBSCRIBE ... INTO");
+            }
+
+            let Plan::Subscribe(plan) = plan_subscribe(scx, *stmt, params, None)? else {
+                sql_bail!("expected SubscribePlan plan");
+            };
+
+            crate::plan::Explainee::Statement(ExplaineeStatement::Subscribe { broken, plan })
+        }
+        Explainee::Execute(ExecuteStatement {
+            name,
+            params: param_exprs,
+        }) => {
+            // Replay the prepared statement with its parameters bound, so that
+            // it is planned exactly like its execution would be.
+            let name = name.to_string();
+            let Some(desc) = scx.catalog.get_prepared_statement_desc(&name) else {
+                sql_bail!("unknown prepared statement {}", name);
+            };
+            let params = query::plan_params(scx, param_exprs, desc)?;
+            let Some(Statement::Select(select)) = scx.catalog.get_prepared_statement(&name) else {
+                sql_bail!("EXPLAIN EXECUTE is only supported for prepared SELECT statements");
+            };
+            let (select, _) = names::resolve(scx.catalog, select.clone())?;
+            let (plan, desc) = plan_select_inner(scx, select, &params, 0, None)?;
+            crate::plan::Explainee::Statement(ExplaineeStatement::Select {
+                broken: false,
+                plan,
+                desc,
+            })
+        }
     };
 
     Ok(explainee)
@@ -552,8 +722,26 @@ pub fn plan_explain_plan(
     };
     let stage = explain.stage();
 
+    if stage == ExplainStage::Bundle && !matches!(explain.explainee, Explainee::Select(..)) {
+        sql_bail!("EXPLAIN BUNDLE is only supported for SELECT statements");
+    }
+
+    if !explain.parameters.is_empty() {
+        if !matches!(explain.explainee, Explainee::Select(..)) {
+            sql_bail!("EXPLAIN with PARAMETERS is only supported for SELECT statements");
+        }
+        // Optimizing the plan requires values for the parameters.
+        if stage != ExplainStage::RawPlan {
+            sql_bail!("EXPLAIN with PARAMETERS is only supported for RAW PLAN");
+        }
+    }
+
+    if stage == ExplainStage::Emptiness {
+        return plan_explain_emptiness(scx, explain, params);
+    }
+
     // Plan ExplainConfig.
-    let config = {
+    let mut config = {
         let mut with_options = ExplainPlanOptionExtracted::try_from(explain.with_options)?;
 
         if with_options.filter_pushdown {
@@ -564,7 +752,19 @@ pub fn plan_explain_plan(
         ExplainConfig::try_from(with_options)?
     };
 
-    let explainee = plan_explainee(scx, explain.explainee, params)?;
+    let explainee = if explain.parameters.is_empty() {
+        plan_explainee(scx, explain.explainee, params, 0)?
+    } else {
+        config.parameters = explain
+            .parameters
+            .iter()
+            .map(|ty| query::scalar_type_from_sql(scx, ty))
+            .collect::<Result<_, _>>()?;
+        let unbound_parameters = explain.parameters.len();
+        with_parameter_types(scx, &explain.parameters, || {
+            plan_explainee(scx, explain.explainee, params, unbound_parameters)
+        })?
+    };
 
     Ok(Plan::ExplainPlan(ExplainPlanPlan {
         stage,
@@ -574,6 +774,239 @@ pub fn plan_explain_plan(
     }))
 }
 
+/// Plans `EXPLAIN EMPTINESS`, which counts the rows that each stage of a query
+/// yields at the timestamp the query would be executed at.
+///
+/// The stages are the inputs of the query and the operators that can change
+/// its number of rows, like filters and joins, numbered so that every stage
+/// comes after its inputs. A stage "eliminates all rows" if it yields no rows
+/// even though all of its inputs do, which points at the input or predicate
+/// that makes the query come back empty.
+///
+/// The result is computed by a single `SELECT`, so all stages observe the same
+/// timestamp.
+fn plan_explain_emptiness(
+    scx: &StatementContext,
+    explain: ExplainPlanStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    if explain.format() != mz_sql_parser::ast::ExplainFormat::Text {
+        sql_bail!("EXPLAIN EMPTINESS only supports TEXT format");
+    }
+    if !explain.with_options.is_empty() || !explain.parameters.is_empty() {
+        sql_bail!("EXPLAIN EMPTINESS does not support options");
+    }
+    let Explainee::Select(select, broken) = explain.explainee else {
+        sql_bail!("EXPLAIN EMPTINESS is only supported for SELECT statements");
+    };
+    if broken {
+        sql_bail!("EXPLAIN EMPTINESS does not support BROKEN");
+    }
+
+    let (plan, _desc) = plan_select_inner(scx, *select, params, 0, None)?;
+    let mut collector = EmptinessStages {
+        scx,
+        lets: vec![],
+        local_stages: BTreeMap::new(),
+        stages: vec![],
+    };
+    collector.collect(&plan.source);
+    let stages = collector.stages;
+
+    let count = |expr: HirRelationExpr| {
+        let count = AggregateExpr {
+            func: AggregateFunc::Count,
+            expr: Box::new(HirScalarExpr::literal_true()),
+            distinct: false,
+        };
+        expr.reduce(vec![], vec![count], None)
+    };
+    let zero = || HirScalarExpr::literal(Datum::Int64(0), ScalarType::Int64);
+    let source = stages
+        .iter()
+        .enumerate()
+        .map(|(i, stage)| {
+            // The row count of the stage is in the first column, followed by
+            // the row counts of its inputs.
+            let mut counts = count(stage.expr.clone());
+            for input in &stage.inputs {
+                let input = count(stages[*input].expr.clone());
+                counts = counts.join(input, HirScalarExpr::literal_true(), JoinKind::Inner);
+            }
+            let eliminates_all_rows = HirScalarExpr::variadic_and(
+                std::iter::once(HirScalarExpr::column(0).call_binary(zero(), BinaryFunc::Eq))
+                    .chain((1..=stage.inputs.len()).map(|column| {
+                        HirScalarExpr::column(column).call_binary(zero(), BinaryFunc::Gt)
+                    }))
+                    .collect(),
+            );
+            let arity = stage.inputs.len() + 1;
+            counts
+                .map(vec![
+                    HirScalarExpr::literal(Datum::Int64(i64::cast_from(i)), ScalarType::Int64),
+                    HirScalarExpr::literal(Datum::String(&stage.operator), ScalarType::String),
+                    eliminates_all_rows,
+                ])
+                .project(vec![arity, arity + 1, 0, arity + 2])
+        })
+        .reduce(|union, branch| union.union(branch))
+        .expect("every query has at least one stage");
+
+    Ok(Plan::Select(SelectPlan {
+        select: None,
+        source,
+        when: plan.when,
+        finishing: RowSetFinishing {
+            order_by: vec![ColumnOrder {
+                column: 0,
+                desc: false,
+                nulls_last: false,
+            }],
+            limit: None,
+            offset: 0,
+            project: (0..4).collect(),
+        },
+        copy_to: None,
+        mirror: None,
+    }))
+}
+
+/// A stage of a query that `EXPLAIN EMPTINESS` counts the rows of.
+struct EmptinessStage {
+    /// A description of the operator at the root of the stage.
+    operator: String,
+    /// The stage, as a query of its own.
+    expr: HirRelationExpr,
+    /// The indexes of the stages that are the inputs of this stage.
+    inputs: Vec<usize>,
+}
+
+/// Collects the [`EmptinessStage`]s of a query.
+struct EmptinessStages<'a> {
+    scx: &'a StatementContext<'a>,
+    /// The `Let` bindings that enclose the expression that is being collected.
+    lets: Vec<(String, LocalId, HirRelationExpr)>,
+    /// The stages at the root of the value of each `Let` binding.
+    local_stages: BTreeMap<LocalId, Vec<usize>>,
+    stages: Vec<EmptinessStage>,
+}
+
+impl EmptinessStages<'_> {
+    /// Collects the stages of `expr`, and returns the indexes of the stages
+    /// that are closest to its root.
+    fn collect(&mut self, expr: &HirRelationExpr) -> Vec<usize> {
+        // Correlated expressions can't be evaluated on their own.
+        if expr.is_correlated() {
+            return vec![];
+        }
+        match expr {
+            HirRelationExpr::Constant { .. } => self.push("Constant".into(), expr, vec![]),
+            HirRelationExpr::Get {
+                id: Id::Global(id), ..
+            } => {
+                let name = self
+                    .scx
+                    .catalog
+                    .resolve_full_name(self.scx.get_item(id).name());
+                self.push(format!("Get {name}"), expr, vec![])
+            }
+            HirRelationExpr::Get {
+                id: Id::Local(id), ..
+            } => self.local_stages.get(id).cloned().unwrap_or_default(),
+            HirRelationExpr::Let {
+                name,
+                id,
+                value,
+                body,
+            } => {
+                let value_stages = self.collect(value);
+                self.local_stages.insert(*id, value_stages);
+                self.lets.push((name.clone(), *id, (**value).clone()));
+                let stages = self.collect(body);
+                self.lets.pop();
+                stages
+            }
+            // The bindings of a recursive query can't be evaluated on their own.
+            HirRelationExpr::LetRec { .. } => {
+                self.push("With Mutually Recursive".into(), expr, vec![])
+            }
+            HirRelationExpr::CallTable { func, .. } => {
+                self.push(format!("CallTable {func}"), expr, vec![])
+            }
+            // These operators don't change the number of rows of their inputs.
+            HirRelationExpr::Project { input, .. }
+            | HirRelationExpr::Map { input, .. }
+            | HirRelationExpr::Negate { input } => self.collect(input),
+            HirRelationExpr::Filter { input, predicates } => {
+                let inputs = self.collect(input);
+                let predicates = separated(" AND ", predicates);
+                self.push(format!("Filter {predicates}"), expr, inputs)
+            }
+            HirRelationExpr::Join {
+                left,
+                right,
+                on,
+                kind,
+            } => {
+                let mut inputs = self.collect(left);
+                inputs.extend(self.collect(right));
+                let operator = if on.is_literal_true() && kind == &JoinKind::Inner {
+                    "CrossJoin".into()
+                } else {
+                    format!("{kind}Join {on}")
+                };
+                self.push(operator, expr, inputs)
+            }
+            HirRelationExpr::Reduce { input, .. } => {
+                let inputs = self.collect(input);
+                self.push("Reduce".into(), expr, inputs)
+            }
+            HirRelationExpr::Distinct { input } => {
+                let inputs = self.collect(input);
+                self.push("Distinct".into(), expr, inputs)
+            }
+            HirRelationExpr::TopK { input, .. } => {
+                let inputs = self.collect(input);
+                self.push("TopK".into(), expr, inputs)
+            }
+            HirRelationExpr::Threshold { input } => {
+                let inputs = self.collect(input);
+                self.push("Threshold".into(), expr, inputs)
+            }
+            HirRelationExpr::Union { base, inputs } => {
+                let mut stages = self.collect(base);
+                for input in inputs {
+                    stages.extend(self.collect(input));
+                }
+                self.push("Union".into(), expr, stages)
+            }
+        }
+    }
+
+    /// Adds the stage rooted at `expr`, wrapped in the enclosing `Let`
+    /// bindings, and returns its index.
+    fn push(&mut self, operator: String, expr: &HirRelationExpr, inputs: Vec<usize>) -> Vec<usize> {
+        let expr = self
+            .lets
+            .iter()
+            .rev()
+            .fold(expr.clone(), |body, (name, id, value)| {
+                HirRelationExpr::Let {
+                    name: name.clone(),
+                    id: *id,
+                    value: Box::new(value.clone()),
+                    body: Box::new(body),
+                }
+            });
+        self.stages.push(EmptinessStage {
+            operator,
+            expr,
+            inputs,
+        });
+        vec![self.stages.len() - 1]
+    }
+}
+
 pub fn plan_explain_schema(
     scx: &StatementContext,
     explain_schema: ExplainSinkSchemaStatement<Aug>,
@@ -644,10 +1077,40 @@ pub fn plan_explain_pushdown(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     scx.require_feature_flag(&vars::ENABLE_EXPLAIN_PUSHDOWN)?;
-    let explainee = plan_explainee(scx, statement.explainee, params)?;
+    let explainee = plan_explainee(scx, statement.explainee, params, 0)?;
     Ok(Plan::ExplainPushdown(ExplainPushdownPlan { explainee }))
 }
 
+pub fn plan_explain_analyze(
+    scx: &StatementContext,
+    statement: ExplainAnalyzeStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_EXPLAIN_ANALYZE)?;
+    let name = statement.materialized_view;
+    let item = scx.get_item_by_resolved_name(&name)?;
+    let item_type = item.item_type();
+    if item_type != CatalogItemType::MaterializedView {
+        sql_bail!("Expected {name} to be a materialized view, not a {item_type}");
+    }
+    let cluster_id = item
+        .cluster_id()
+        .expect("materialized views are maintained by a cluster");
+    let cluster = scx.catalog.get_cluster(cluster_id);
+    let Some(replica_id) = cluster.replica_ids().get(statement.replica.as_str()) else {
+        return Err(CatalogError::UnknownClusterReplica(format!(
+            "{}.{}",
+            cluster.name(),
+            statement.replica.as_str()
+        ))
+        .into());
+    };
+    Ok(Plan::ExplainAnalyze(ExplainAnalyzePlan {
+        id: item.id(),
+        cluster_id,
+        replica_id: *replica_id,
+    }))
+}
+
 pub fn plan_explain_timestamp(
     scx: &StatementContext,
     explain: ExplainTimestampStatement<Aug>,
@@ -670,7 +1133,9 @@ pub fn plan_explain_timestamp(
 
         raw_plan
     };
+    let SelectStatementOptionExtracted { after_token, .. } = explain.select.options.try_into()?;
     let when = query::plan_as_of(scx, explain.select.as_of)?;
+    let when = query::plan_after_token(scx, when, after_token)?;
 
     Ok(Plan::ExplainTimestamp(ExplainTimestampPlan {
         format,
@@ -705,7 +1170,17 @@ pub fn plan_query(
     })
 }
 
-generate_extracted_config!(SubscribeOption, (Snapshot, bool), (Progress, bool));
+generate_extracted_config!(
+    SubscribeOption,
+    (Snapshot, bool),
+    (Progress, bool),
+    (AfterToken, String),
+    (AwsConnection, with_options::Object),
+    (Format, String),
+    (MaxFileSize, ByteSize),
+    (OrderBy, Vec<Ident>),
+    (Deduplicate, bool)
+);
 
 pub fn describe_subscribe(
     scx: &StatementContext,
@@ -722,9 +1197,28 @@ pub fn describe_subscribe(
                 query::plan_root_query(scx, query, QueryLifetime::Subscribe)?;
             desc
         }
+        SubscribeRelation::Names(names) => plan_subscribe_relations(scx, &names)?.1,
     };
+    if stmt.into.is_some() {
+        // A `SUBSCRIBE ... INTO` returns a row for each segment it has archived.
+        let desc = RelationDesc::empty()
+            .with_column("lower", ScalarType::MzTimestamp.nullable(false))
+            .with_column("upper", ScalarType::MzTimestamp.nullable(true))
+            .with_column("rows", ScalarType::Int64.nullable(false))
+            .with_column("manifest", ScalarType::String.nullable(false));
+        return Ok(StatementDesc::new(Some(desc)));
+    }
     let SubscribeOptionExtracted { progress, .. } = stmt.options.try_into()?;
-    let progress = progress.unwrap_or(false);
+    let desc = subscribe_desc(relation_desc, progress.unwrap_or(false), &stmt.output)?;
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+/// Describes the output of a `SUBSCRIBE` to a relation with `relation_desc`.
+fn subscribe_desc(
+    relation_desc: RelationDesc,
+    progress: bool,
+    output: &SubscribeOutput<Aug>,
+) -> Result<RelationDesc, PlanError> {
     let mut desc = RelationDesc::empty().with_column(
         "mz_timestamp",
         ScalarType::Numeric {
@@ -736,8 +1230,8 @@ pub fn describe_subscribe(
         desc = desc.with_column("mz_progressed", ScalarType::Bool.nullable(false));
     }
 
-    let debezium = matches!(stmt.output, SubscribeOutput::EnvelopeDebezium { .. });
-    match stmt.output {
+    let debezium = matches!(output, SubscribeOutput::EnvelopeDebezium { .. });
+    match output {
         SubscribeOutput::Diffs | SubscribeOutput::WithinTimestampOrderBy { .. } => {
             desc = desc.with_column("mz_diff", ScalarType::Int64.nullable(true));
             for (name, mut ty) in relation_desc.into_iter() {
@@ -751,7 +1245,8 @@ pub fn describe_subscribe(
         | SubscribeOutput::EnvelopeDebezium { key_columns } => {
             desc = desc.with_column("mz_state", ScalarType::String.nullable(true));
             let key_columns = key_columns
-                .into_iter()
+                .iter()
+                .cloned()
                 .map(normalize::column_name)
                 .collect_vec();
             let mut before_values_desc = RelationDesc::empty();
@@ -794,13 +1289,14 @@ pub fn describe_subscribe(
             desc = desc.concat(after_values_desc);
         }
     }
-    Ok(StatementDesc::new(Some(desc)))
+    Ok(desc)
 }
 
 pub fn plan_subscribe(
     scx: &StatementContext,
     SubscribeStatement {
         relation,
+        into,
         options,
         as_of,
         up_to,
@@ -844,9 +1340,89 @@ pub fn plan_subscribe(
                 query.scope,
             )
         }
+        SubscribeRelation::Names(names) => {
+            scx.require_feature_flag(&vars::ENABLE_SUBSCRIBE_TO_MULTIPLE_RELATIONS)?;
+            if !matches!(output, SubscribeOutput::Diffs) {
+                sql_bail!(
+                    "SUBSCRIBE to multiple relations does not support ENVELOPE or \
+                     WITHIN TIMESTAMP ORDER BY"
+                );
+            }
+            let (expr, desc) = plan_subscribe_relations(scx, &names)?;
+            let scope = Scope::from_source(None, desc.iter_names());
+            (
+                SubscribeFrom::Query {
+                    expr: expr.lower(scx.catalog.system_vars(), None)?,
+                    desc: desc.clone(),
+                },
+                desc,
+                scope,
+            )
+        }
     };
 
+    let SubscribeOptionExtracted {
+        progress,
+        snapshot,
+        after_token,
+        aws_connection,
+        format,
+        max_file_size,
+        order_by: order_by_columns,
+        deduplicate,
+        ..
+    } = options.try_into()?;
+    let archive = match into {
+        Some(into) => {
+            scx.require_feature_flag(&vars::ENABLE_SUBSCRIBE_INTO)?;
+            if copy_to.is_some() {
+                sql_bail!("COPY does not support SUBSCRIBE ... INTO");
+            }
+            if progress.is_some() {
+                sql_bail!("SUBSCRIBE ... INTO does not support the PROGRESS option");
+            }
+            let (connection_id, connection) =
+                plan_aws_connection(scx, aws_connection, "SUBSCRIBE ... INTO")?;
+            let desc = subscribe_desc(desc.clone(), false, &output)?;
+            let format = match format
+                .as_ref()
+                .map(|format| format.to_lowercase())
+                .as_deref()
+            {
+                Some("json") => S3SinkFormat::Json,
+                Some("parquet") => {
+                    // Validate that the output desc can be formatted as parquet
+                    ArrowBuilder::validate_desc(&desc).map_err(|e| sql_err!("{}", e))?;
+                    S3SinkFormat::Parquet
+                }
+                Some(format) => sql_bail!("SUBSCRIBE ... INTO does not support FORMAT {}", format),
+                None => sql_bail!("SUBSCRIBE ... INTO requires a FORMAT option"),
+            };
+            Some(plan::SubscribeArchive {
+                to: plan_s3_target(scx, &into, "SUBSCRIBE INTO target")?,
+                connection,
+                connection_id,
+                desc,
+                format,
+                max_file_size: validate_max_file_size(
+                    max_file_size.unwrap_or(DEFAULT_S3_MAX_FILE_SIZE),
+                )?,
+            })
+        }
+        None => {
+            let archive_options = [
+                (aws_connection.is_some(), SubscribeOptionName::AwsConnection),
+                (format.is_some(), SubscribeOptionName::Format),
+                (max_file_size.is_some(), SubscribeOptionName::MaxFileSize),
+            ];
+            if let Some((_, name)) = archive_options.iter().find(|(set, _)| *set) {
+                sql_bail!("SUBSCRIBE option {} requires INTO", name.to_ast_string());
+            }
+            None
+        }
+    };
     let when = query::plan_as_of(scx, as_of)?;
+    let when = query::plan_after_token(scx, when, after_token)?;
     let up_to = up_to.map(|up_to| plan_up_to(scx, up_to)).transpose()?;
 
     let qcx = QueryContext::root(scx, QueryLifetime::Subscribe);
@@ -861,6 +1437,25 @@ pub fn plan_subscribe(
         allow_windows: false,
     };
 
+    // The `ORDER BY` option is shorthand for a `WITHIN TIMESTAMP ORDER BY`
+    // clause that orders the updates of each timestamp by the given columns.
+    let output = match (order_by_columns, output) {
+        (None, output) => output,
+        (Some(columns), SubscribeOutput::Diffs) => SubscribeOutput::WithinTimestampOrderBy {
+            order_by: columns
+                .into_iter()
+                .map(|ident| OrderByExpr {
+                    expr: Expr::Identifier(vec![ident]),
+                    asc: None,
+                    nulls_last: None,
+                })
+                .collect(),
+        },
+        (Some(_), _) => sql_bail!(
+            "SUBSCRIBE option ORDER BY is not supported with ENVELOPE or WITHIN TIMESTAMP ORDER BY"
+        ),
+    };
+
     let output_columns: Vec<_> = scope.column_names().enumerate().collect();
     let output = match output {
         SubscribeOutput::Diffs => plan::SubscribeOutput::Diffs,
@@ -917,7 +1512,7 @@ pub fn plan_subscribe(
             scx.require_feature_flag(&vars::ENABLE_WITHIN_TIMESTAMP_ORDER_BY_IN_SUBSCRIBE)?;
             let mz_diff = "mz_diff".into();
             let output_columns = std::iter::once((0, &mz_diff))
-                .chain(output_columns.into_iter().map(|(i, c)| (i + 1, c)))
+                .chain(output_columns.iter().map(|(i, c)| (i + 1, *c)))
                 .collect_vec();
             match query::plan_order_by_exprs(
                 &ExprContext {
@@ -948,9 +1543,6 @@ pub fn plan_subscribe(
         }
     };
 
-    let SubscribeOptionExtracted {
-        progress, snapshot, ..
-    } = options.try_into()?;
     Ok(Plan::Subscribe(SubscribePlan {
         from,
         when,
@@ -959,9 +1551,82 @@ pub fn plan_subscribe(
         copy_to,
         emit_progress: progress.unwrap_or(false),
         output,
+        archive,
+        deduplicate: deduplicate.unwrap_or(false),
     }))
 }
 
+/// Plans a `SUBSCRIBE (TO ...)` as the union of the listed relations.
+///
+/// The columns of all relations are laid out next to each other, after an
+/// `mz_relation` column with the name of the relation that each update
+/// belongs to. Updates leave the columns of the other relations `NULL`. All
+/// relations are read by the same dataflow, so their updates share the
+/// timestamps selected for the `SUBSCRIBE`.
+fn plan_subscribe_relations(
+    scx: &StatementContext,
+    names: &[ResolvedItemName],
+) -> Result<(HirRelationExpr, RelationDesc), PlanError> {
+    let mut relations = vec![];
+    for name in names {
+        let entry = scx.get_item_by_resolved_name(name)?;
+        let full_name = scx.catalog.resolve_full_name(entry.name());
+        let desc = match entry.desc(&full_name) {
+            Ok(desc) => desc.into_owned(),
+            Err(..) => sql_bail!(
+                "'{}' cannot be subscribed to because it is a {}",
+                name.full_name_str(),
+                entry.item_type(),
+            ),
+        };
+        relations.push((entry.id(), full_name.to_string(), desc));
+    }
+
+    let nullable = relations.len() > 1;
+    let mut desc =
+        RelationDesc::empty().with_column("mz_relation", ScalarType::String.nullable(false));
+    for (_, _, relation_desc) in &relations {
+        for (name, ty) in relation_desc.iter() {
+            desc = desc.with_column(
+                name,
+                ty.scalar_type.clone().nullable(ty.nullable || nullable),
+            );
+        }
+    }
+
+    let mut terms = vec![];
+    let mut offset = 1;
+    for (id, full_name, relation_desc) in relations {
+        let arity = relation_desc.arity();
+        let own_columns = offset..offset + arity;
+        let mut scalars = vec![HirScalarExpr::literal(
+            Datum::String(&full_name),
+            ScalarType::String,
+        )];
+        let mut outputs = vec![arity];
+        for (i, ty) in desc.typ().column_types.iter().enumerate().skip(1) {
+            if own_columns.contains(&i) {
+                outputs.push(i - offset);
+            } else {
+                outputs.push(arity + scalars.len());
+                scalars.push(HirScalarExpr::literal_null(ty.scalar_type.clone()));
+            }
+        }
+        let get = HirRelationExpr::Get {
+            id: Id::Global(id),
+            typ: relation_desc.typ().clone(),
+        };
+        terms.push(get.map(scalars).project(outputs));
+        offset += arity;
+    }
+    let expr = terms
+        .into_iter()
+        .reduce(HirRelationExpr::union)
+        .expect("at least one relation");
+
+    Ok((expr, desc))
+}
+
 pub fn describe_copy_from_table(
     scx: &StatementContext,
     table_name: <Aug as AstInfo>::ItemName,
@@ -1009,16 +1674,8 @@ fn plan_copy_to_expr(
     format: CopyFormat,
     options: CopyOptionExtracted,
 ) -> Result<Plan, PlanError> {
-    let conn_id = match options.aws_connection {
-        Some(conn_id) => GlobalId::from(conn_id),
-        None => sql_bail!("AWS CONNECTION is required for COPY ... TO <expr>"),
-    };
-    let connection = scx.get_item(&conn_id).connection()?;
-
-    match connection {
-        mz_storage_types::connections::Connection::Aws(_) => {}
-        _ => sql_bail!("only AWS CONNECTION is supported for COPY ... TO <expr>"),
-    }
+    let (conn_id, connection) =
+        plan_aws_connection(scx, options.aws_connection, "COPY ... TO <expr>")?;
 
     let format = match format {
         CopyFormat::Csv => {
@@ -1045,13 +1702,58 @@ fn plan_copy_to_expr(
         CopyFormat::Text => bail_unsupported!("FORMAT TEXT"),
     };
 
+    let to = plan_s3_target(scx, to, "COPY TO target")?;
+    let max_file_size = validate_max_file_size(options.max_file_size)?;
+
+    Ok(Plan::CopyTo(CopyToPlan {
+        select_plan,
+        desc,
+        to,
+        connection,
+        connection_id: conn_id,
+        format,
+        max_file_size,
+    }))
+}
+
+/// Plans the `AWS CONNECTION` option of `stmt`, which writes to S3.
+fn plan_aws_connection(
+    scx: &StatementContext,
+    aws_connection: Option<with_options::Object>,
+    stmt: &str,
+) -> Result<
+    (
+        GlobalId,
+        mz_storage_types::connections::Connection<ReferencedConnection>,
+    ),
+    PlanError,
+> {
+    let conn_id = match aws_connection {
+        Some(conn_id) => GlobalId::from(conn_id),
+        None => sql_bail!("AWS CONNECTION is required for {}", stmt),
+    };
+    let connection = scx.get_item(&conn_id).connection()?;
+
+    match connection {
+        mz_storage_types::connections::Connection::Aws(_) => {}
+        _ => sql_bail!("only AWS CONNECTION is supported for {}", stmt),
+    }
+    Ok((conn_id, connection.to_owned()))
+}
+
+/// Plans the expression `to` of the S3 URI to write to as a string expression.
+fn plan_s3_target(
+    scx: &StatementContext,
+    to: &Expr<Aug>,
+    name: &'static str,
+) -> Result<HirScalarExpr, PlanError> {
     // Converting the to expr to a HirScalarExpr
     let mut to_expr = to.clone();
     transform_ast::transform(scx, &mut to_expr)?;
     let relation_type = RelationDesc::empty();
     let ecx = &ExprContext {
         qcx: &QueryContext::root(scx, QueryLifetime::OneShot),
-        name: "COPY TO target",
+        name,
         scope: &Scope::empty(),
         relation_type: relation_type.typ(),
         allow_aggregates: false,
@@ -1060,30 +1762,24 @@ fn plan_copy_to_expr(
         allow_windows: false,
     };
 
-    let to = plan_expr(ecx, &to_expr)?.type_as(ecx, &ScalarType::String)?;
+    plan_expr(ecx, &to_expr)?.type_as(ecx, &ScalarType::String)
+}
 
-    if options.max_file_size.as_bytes() < MIN_S3_SINK_FILE_SIZE.as_bytes() {
+/// Validates the `MAX FILE SIZE` of files written to S3, returning it in bytes.
+fn validate_max_file_size(max_file_size: ByteSize) -> Result<u64, PlanError> {
+    if max_file_size.as_bytes() < MIN_S3_SINK_FILE_SIZE.as_bytes() {
         sql_bail!(
             "MAX FILE SIZE cannot be less than {}",
             MIN_S3_SINK_FILE_SIZE
         );
     }
-    if options.max_file_size.as_bytes() > MAX_S3_SINK_FILE_SIZE.as_bytes() {
+    if max_file_size.as_bytes() > MAX_S3_SINK_FILE_SIZE.as_bytes() {
         sql_bail!(
             "MAX FILE SIZE cannot be greater than {}",
             MAX_S3_SINK_FILE_SIZE
         );
     }
-
-    Ok(Plan::CopyTo(CopyToPlan {
-        select_plan,
-        desc,
-        to,
-        connection: connection.to_owned(),
-        connection_id: conn_id,
-        format,
-        max_file_size: options.max_file_size.as_bytes(),
-    }))
+    Ok(max_file_size.as_bytes())
 }
 
 fn plan_copy_from(
@@ -1148,6 +1844,9 @@ fn extract_byte_param_value(v: Option<String>, param_name: &str) -> Result<Optio
     }
 }
 
+/// The `MAX FILE SIZE` of files written to S3, if not specified.
+const DEFAULT_S3_MAX_FILE_SIZE: ByteSize = ByteSize::mb(256);
+
 generate_extracted_config!(
     CopyOption,
     (Format, String),
@@ -1157,7 +1856,7 @@ generate_extracted_config!(
     (Quote, String),
     (Header, bool),
     (AwsConnection, with_options::Object),
-    (MaxFileSize, ByteSize, Default(ByteSize::mb(256)))
+    (MaxFileSize, ByteSize, Default(DEFAULT_S3_MAX_FILE_SIZE))
 );
 
 pub fn plan_copy(
@@ -1249,7 +1948,11 @@ pub fn plan_copy(
                         limit: None,
                         offset: None,
                     };
-                    SelectStatement { query, as_of: None }
+                    SelectStatement {
+                        query,
+                        options: vec![],
+                        as_of: None,
+                    }
                 }
                 CopyRelation::Select(stmt) => {
                     if !stmt.query.order_by.is_empty() {
@@ -1260,7 +1963,7 @@ pub fn plan_copy(
                 _ => sql_bail!("COPY {} {} not supported", direction, target),
             };
 
-            let (plan, desc) = plan_select_inner(scx, stmt, &Params::empty(), None)?;
+            let (plan, desc) = plan_select_inner(scx, stmt, &Params::empty(), 0, None)?;
             plan_copy_to_expr(scx, plan, desc, to_expr, format, options)
         }
         _ => sql_bail!("COPY {} {} not supported", direction, target),
@@ -19,17 +19,18 @@ use uncased::UncasedStr;
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    CloseStatement, DeallocateStatement, DeclareStatement, DiscardStatement, DiscardTarget,
-    ExecuteStatement, FetchOption, FetchOptionName, FetchStatement, PrepareStatement,
-    ResetVariableStatement, SetVariableStatement, SetVariableTo, ShowVariableStatement,
+    CancelStatement, ClaimHandoffStatement, CloseStatement, DeallocateStatement, DeclareStatement,
+    DiscardStatement, DiscardTarget, ExecuteStatement, FetchOption, FetchOptionName,
+    FetchStatement, HandoffCursorStatement, PrepareStatement, ResetVariableStatement,
+    SetVariableStatement, SetVariableTo, ShowTransactionWritesStatement, ShowVariableStatement,
 };
 use crate::names::{self, Aug};
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::with_options::TryFromValue;
 use crate::plan::{
-    describe, query, ClosePlan, DeallocatePlan, DeclarePlan, ExecutePlan, ExecuteTimeout,
-    FetchPlan, InspectShardPlan, Params, Plan, PlanError, PreparePlan, ResetVariablePlan,
-    SetVariablePlan, ShowVariablePlan, VariableValue,
+    describe, query, CancelQueryPlan, ClaimHandoffPlan, ClosePlan, DeallocatePlan, DeclarePlan,
+    ExecutePlan, ExecuteTimeout, FetchPlan, HandoffCursorPlan, InspectShardPlan, Params, Plan,
+    PlanError, PreparePlan, ResetVariablePlan, SetVariablePlan, ShowVariablePlan, VariableValue,
 };
 use crate::session::vars;
 use crate::session::vars::{IsolationLevel, SCHEMA_ALIAS, TRANSACTION_ISOLATION_VAR_NAME};
@@ -129,6 +130,25 @@ pub fn plan_show_variable(
     }
 }
 
+pub fn describe_show_transaction_writes(
+    _: &StatementContext,
+    _: ShowTransactionWritesStatement,
+) -> Result<StatementDesc, PlanError> {
+    let desc = RelationDesc::empty()
+        .with_column("table_id", ScalarType::String.nullable(false))
+        .with_column("table", ScalarType::String.nullable(false))
+        .with_column("inserts", ScalarType::Int64.nullable(false))
+        .with_column("deletes", ScalarType::Int64.nullable(false));
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_show_transaction_writes(
+    _: &StatementContext,
+    _: ShowTransactionWritesStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::ShowTransactionWrites)
+}
+
 pub fn describe_inspect_shard(
     _: &StatementContext,
     InspectShardStatement { .. }: InspectShardStatement,
@@ -164,6 +184,20 @@ pub fn plan_discard(
     }
 }
 
+pub fn describe_cancel(
+    _: &StatementContext,
+    _: CancelStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_cancel(
+    _: &StatementContext,
+    CancelStatement { connection_id }: CancelStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::CancelQuery(CancelQueryPlan { connection_id }))
+}
+
 pub fn describe_declare(
     scx: &StatementContext,
     DeclareStatement { stmt, .. }: DeclareStatement<Aug>,
@@ -201,7 +235,11 @@ pub fn describe_fetch(
     Ok(StatementDesc::new(None))
 }
 
-generate_extracted_config!(FetchOption, (Timeout, Duration));
+generate_extracted_config!(
+    FetchOption,
+    (Timeout, Duration),
+    (Progress, bool, Default(false))
+);
 
 pub fn plan_fetch(
     _: &StatementContext,
@@ -211,7 +249,9 @@ pub fn plan_fetch(
         options,
     }: FetchStatement<Aug>,
 ) -> Result<Plan, PlanError> {
-    let FetchOptionExtracted { timeout, .. } = options.try_into()?;
+    let FetchOptionExtracted {
+        timeout, progress, ..
+    } = options.try_into()?;
     let timeout = match timeout {
         Some(timeout) => {
             // Limit FETCH timeouts to 1 day. If users have a legitimate need it can be
@@ -230,6 +270,7 @@ pub fn plan_fetch(
         name: name.to_string(),
         count,
         timeout,
+        progress,
     }))
 }
 
@@ -246,6 +287,37 @@ pub fn plan_close(
     }))
 }
 
+pub fn describe_handoff_cursor(
+    _: &StatementContext,
+    _: HandoffCursorStatement,
+) -> Result<StatementDesc, PlanError> {
+    let desc = RelationDesc::empty().with_column("token", ScalarType::String.nullable(false));
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_handoff_cursor(
+    _: &StatementContext,
+    HandoffCursorStatement { name }: HandoffCursorStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::HandoffCursor(HandoffCursorPlan {
+        name: name.to_string(),
+    }))
+}
+
+pub fn describe_claim_handoff(
+    _: &StatementContext,
+    _: ClaimHandoffStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_claim_handoff(
+    _: &StatementContext,
+    ClaimHandoffStatement { token }: ClaimHandoffStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::ClaimHandoff(ClaimHandoffPlan { token }))
+}
+
 pub fn describe_prepare(
     _: &StatementContext,
     _: PrepareStatement<Aug>,
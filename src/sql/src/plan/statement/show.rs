@@ -31,7 +31,7 @@ use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
     SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement, ShowCreateSinkStatement,
     ShowCreateSourceStatement, ShowCreateTableStatement, ShowCreateViewStatement,
-    ShowObjectsStatement, ShowStatementFilter, Statement, Value,
+    ShowFrontiersStatement, ShowObjectsStatement, ShowStatementFilter, Statement, Value,
 };
 use crate::catalog::{CatalogItemType, SessionCatalog};
 use crate::names::{
@@ -636,6 +636,65 @@ pub fn show_columns<'a>(
     })
 }
 
+/// Shows the frontiers of an object and of all objects it transitively depends on.
+///
+/// Objects that have no frontiers of their own, like types or connections, are omitted from the
+/// dependencies.
+pub fn show_frontiers<'a>(
+    scx: &'a StatementContext<'a>,
+    ShowFrontiersStatement {
+        object_name,
+        filter,
+    }: ShowFrontiersStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let entry = scx.get_item_by_resolved_name(&object_name)?;
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+
+    match entry.item_type() {
+        CatalogItemType::Source
+        | CatalogItemType::Table
+        | CatalogItemType::View
+        | CatalogItemType::MaterializedView
+        | CatalogItemType::Index
+        | CatalogItemType::Sink => (),
+        ty @ CatalogItemType::Connection
+        | ty @ CatalogItemType::Func
+        | ty @ CatalogItemType::Secret
+        | ty @ CatalogItemType::Type => {
+            sql_bail!("{full_name} is a {ty} and so does not have frontiers");
+        }
+    }
+
+    let query = format!(
+        "WITH objects AS (
+            SELECT '{id}' AS id
+            UNION
+            SELECT referenced_object_id
+            FROM mz_internal.mz_object_transitive_dependencies
+            WHERE object_id = '{id}'
+        )
+        SELECT
+            o.name,
+            o.id,
+            o.type,
+            f.read_frontier AS since,
+            f.write_frontier AS upper,
+            o.id = '{id}' AS is_target
+        FROM objects
+        JOIN mz_catalog.mz_objects o ON o.id = objects.id
+        LEFT JOIN mz_internal.mz_frontiers f ON f.object_id = o.id
+        WHERE o.id = '{id}' OR f.object_id IS NOT NULL",
+        id = entry.id(),
+    );
+    ShowSelect::new(
+        scx,
+        query,
+        filter,
+        Some("is_target DESC, name, id"),
+        Some(&["name", "id", "type", "since", "upper"]),
+    )
+}
+
 // The rationale for which fields to include in the tuples are those
 // that are mandatory when creating a replica as part of the CREATE
 // CLUSTER command, i.e., name and size.
@@ -925,7 +984,7 @@ impl<'a> ShowColumnsSelect<'a> {
 
 /// Convert a SQL statement into a form that could be used as input, as well as
 /// is more amenable to human consumption.
-fn humanize_sql_for_show_create(
+pub fn humanize_sql_for_show_create(
     catalog: &dyn SessionCatalog,
     id: GlobalId,
     sql: &str,
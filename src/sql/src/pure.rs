@@ -12,12 +12,14 @@
 //! See the [crate-level documentation](crate) for details.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
 use std::iter;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::ast::AlterSourceAddSubsourceOption;
 use anyhow::anyhow;
+use futures::{stream, Stream, StreamExt};
 use itertools::Itertools;
 use mz_adapter_types::dyncfgs;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError, Schema as CcsrSchema};
@@ -268,6 +270,53 @@ pub enum PurifiedExportDetails {
     },
 }
 
+/// The progress of the purification of a statement, reported while purification is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurificationProgress {
+    /// The schemas of `validated` of the `total` referenced upstream tables have been fetched.
+    TablesValidated { validated: usize, total: usize },
+}
+
+/// Receives the progress of the purification of a statement.
+pub type PurificationProgressFn<'a> = &'a (dyn Fn(PurificationProgress) + Send + Sync);
+
+/// Fetches the metadata of `items` from an upstream database over the connections `conns`, with
+/// each connection fetching the metadata of one item at a time.
+///
+/// Upstream databases execute the queries of a connection one after the other, so the fetches
+/// only run in parallel across connections. `fetch` hands the connection back along with the
+/// result of the item. Yields the index of each item in `items` along with its result, in the
+/// order in which the fetches complete.
+fn fetch_concurrently<'a, C, T, R, F, Fut>(
+    conns: Vec<C>,
+    items: Vec<T>,
+    fetch: F,
+) -> impl Stream<Item = (usize, R)> + 'a
+where
+    C: Send + 'a,
+    T: Send + 'a,
+    R: Send + 'a,
+    F: Fn(C, T) -> Fut + Clone + Send + 'a,
+    Fut: Future<Output = (C, R)> + Send + 'a,
+{
+    let queue = Arc::new(Mutex::new(items.into_iter().enumerate()));
+    let workers = conns.into_iter().map(move |conn| {
+        let queue = Arc::clone(&queue);
+        let fetch = fetch.clone();
+        stream::unfold(conn, move |conn| {
+            let next = queue.lock().expect("lock poisoned").next();
+            let fetch = fetch.clone();
+            async move {
+                let (index, item) = next?;
+                let (conn, result) = fetch(conn, item).await;
+                Some(((index, result), conn))
+            }
+        })
+        .boxed()
+    });
+    stream::select_all(workers)
+}
+
 /// Purifies a statement, removing any dependencies on external state.
 ///
 /// See the section on [purification](crate#purification) in the crate
@@ -277,22 +326,26 @@ pub enum PurifiedExportDetails {
 /// handled by [purify_create_materialized_view_options] instead.
 /// This could be made more consistent by a refactoring discussed here:
 /// <https://github.com/MaterializeInc/materialize/pull/23870#discussion_r1435922709>
+///
+/// Purification of statements that reference many upstream objects may take a while, so it
+/// reports its progress to `progress`.
 pub async fn purify_statement(
     catalog: impl SessionCatalog,
     now: u64,
     stmt: Statement<Aug>,
     storage_configuration: &StorageConfiguration,
+    progress: PurificationProgressFn<'_>,
 ) -> (Result<PurifiedStatement, PlanError>, Option<ClusterId>) {
     match stmt {
         Statement::CreateSource(stmt) => {
             let cluster_id = stmt.in_cluster.as_ref().map(|cluster| cluster.id.clone());
             (
-                purify_create_source(catalog, now, stmt, storage_configuration).await,
+                purify_create_source(catalog, now, stmt, storage_configuration, progress).await,
                 cluster_id,
             )
         }
         Statement::AlterSource(stmt) => (
-            purify_alter_source(catalog, stmt, storage_configuration).await,
+            purify_alter_source(catalog, stmt, storage_configuration, progress).await,
             None,
         ),
         Statement::CreateSink(stmt) => {
@@ -401,6 +454,55 @@ pub(crate) fn add_materialize_comments(
     Ok(())
 }
 
+/// Removes the Avro doc comments of the CREATE SINK statement that are on
+/// objects other than the sink's relation and the objects it depends on, which
+/// is the case for the comments of a sink whose relation was changed.
+///
+/// Such comments don't apply to the Avro schemas of the sink, and would
+/// otherwise keep the objects that they are on from being dropped.
+pub(crate) fn remove_unrelated_comments(
+    catalog: &dyn SessionCatalog,
+    stmt: &mut CreateSinkStatement<Aug>,
+) {
+    let from = catalog.get_item(stmt.from.item_id());
+    let object_ids = from
+        .references()
+        .0
+        .iter()
+        .copied()
+        .chain_one(from.id())
+        .collect::<BTreeSet<_>>();
+
+    if let Some(
+        FormatSpecifier::Bare(Format::Avro(AvroSchema::Csr { csr_connection }))
+        | FormatSpecifier::KeyValue {
+            key: _,
+            value: Format::Avro(AvroSchema::Csr { csr_connection }),
+        }
+        | FormatSpecifier::KeyValue {
+            key: Format::Avro(AvroSchema::Csr { csr_connection }),
+            value: _,
+        },
+    ) = &mut stmt.format
+    {
+        csr_connection
+            .connection
+            .options
+            .retain(|CsrConfigOption { name, .. }| match name {
+                CsrConfigOptionName::AvroDocOn(AvroDocOn {
+                    identifier:
+                        DocOnIdentifier::Type(ResolvedItemName::Item { id, .. })
+                        | DocOnIdentifier::Column(ColumnName {
+                            relation: ResolvedItemName::Item { id, .. },
+                            ..
+                        }),
+                    ..
+                }) => object_ids.contains(id),
+                _ => true,
+            });
+    }
+}
+
 /// Checks that the sink described in the statement can connect to its external
 /// resources.
 ///
@@ -506,6 +608,9 @@ async fn purify_create_sink(
                 Err(KafkaSinkPurificationError::ZeroBrokers)?;
             }
         }
+        // Webhook receivers are only contacted once the sink is running, as they might not
+        // accept requests without updates.
+        CreateSinkConnection::Webhook { .. } => {}
     }
 
     if let Some(format) = format {
@@ -577,6 +682,7 @@ async fn purify_create_source(
     now: u64,
     mut create_source_stmt: CreateSourceStatement<Aug>,
     storage_configuration: &StorageConfiguration,
+    progress: PurificationProgressFn<'_>,
 ) -> Result<PurifiedStatement, PlanError> {
     let CreateSourceStatement {
         name: source_name,
@@ -794,11 +900,14 @@ async fn purify_create_source(
             } = postgres::purify_source_exports(
                 &client,
                 &config,
+                &storage_configuration.connection_context.ssh_tunnel_manager,
                 &publication,
                 &connection,
                 external_references,
                 text_columns,
                 source_name,
+                dyncfgs::SOURCE_PURIFICATION_CONCURRENCY.get(catalog.system_vars().dyncfgs()),
+                progress,
             )
             .await?;
 
@@ -920,6 +1029,9 @@ async fn purify_create_source(
                 normalized_ignore_columns,
             } = mysql::purify_source_exports(
                 &mut conn,
+                &config,
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+                dyncfgs::SOURCE_PURIFICATION_CONCURRENCY.get(catalog.system_vars().dyncfgs()),
                 external_references,
                 text_columns,
                 ignore_columns,
@@ -1084,6 +1196,7 @@ async fn purify_alter_source(
     catalog: impl SessionCatalog,
     stmt: AlterSourceStatement<Aug>,
     storage_configuration: &StorageConfiguration,
+    progress: PurificationProgressFn<'_>,
 ) -> Result<PurifiedStatement, PlanError> {
     let scx = StatementContext::new(None, &catalog);
     let AlterSourceStatement {
@@ -1206,11 +1319,14 @@ async fn purify_alter_source(
             } = postgres::purify_source_exports(
                 &client,
                 &config,
+                &storage_configuration.connection_context.ssh_tunnel_manager,
                 &pg_source_connection.publication,
                 pg_connection,
                 &mut references,
                 text_columns,
                 &unresolved_source_name,
+                dyncfgs::SOURCE_PURIFICATION_CONCURRENCY.get(catalog.system_vars().dyncfgs()),
+                progress,
             )
             .await?;
 
@@ -1295,6 +1411,9 @@ async fn purify_alter_source(
                 normalized_ignore_columns,
             } = mysql::purify_source_exports(
                 &mut conn,
+                &config,
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+                dyncfgs::SOURCE_PURIFICATION_CONCURRENCY.get(catalog.system_vars().dyncfgs()),
                 &mut references,
                 text_columns,
                 ignore_columns,
@@ -1960,3 +2079,56 @@ impl VisitMut<'_, Aug> for MzNowPurifierVisitor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Poll;
+
+    use futures::future;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_fetch_concurrently() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let fetch = |conn: usize, item: usize| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let fetching = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(fetching, Ordering::SeqCst);
+                // Yield once, so that the fetches of the other connections can start.
+                let mut yielded = false;
+                future::poll_fn(|cx| {
+                    if yielded {
+                        Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                (conn, (conn, item * 10))
+            }
+        };
+
+        let items: Vec<_> = (0..10).collect();
+        let mut results: Vec<_> = futures::executor::block_on(
+            fetch_concurrently(vec![0, 1, 2], items, fetch).collect::<Vec<_>>(),
+        );
+        results.sort();
+
+        // Every item is fetched exactly once, and the indexes identify the items.
+        let fetched: Vec<_> = results.iter().map(|(index, (_, r))| (*index, *r)).collect();
+        let expected: Vec<_> = (0..10).map(|i| (i, i * 10)).collect();
+        assert_eq!(fetched, expected);
+        // Each connection fetches one item at a time, and all of them are used.
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        let conns: BTreeSet<_> = results.iter().map(|(_, (conn, _))| *conn).collect();
+        assert_eq!(conns, BTreeSet::from([0, 1, 2]));
+    }
+}
@@ -53,6 +53,8 @@ pub enum PgSourcePurificationError {
     },
     #[error("replication disabled on server")]
     ReplicationDisabled,
+    #[error("failed to fetch the schemas of referenced tables")]
+    TableInfoUnavailable { errors: Vec<(String, String)> },
 }
 
 impl PgSourcePurificationError {
@@ -95,6 +97,15 @@ impl PgSourcePurificationError {
                 count,
                 if *count == 1 { "" } else { "s" }
             )),
+            Self::TableInfoUnavailable { errors } => Some(format!(
+                "the schemas of the following tables could not be fetched:\n{}",
+                itertools::join(
+                    errors
+                        .iter()
+                        .map(|(table, error)| format!("{}: {}", table, error)),
+                    "\n"
+                )
+            )),
             _ => None,
         }
     }
@@ -253,6 +264,8 @@ pub enum MySqlSourcePurificationError {
     RequiresExternalReferences,
     #[error("No tables found in referenced schemas")]
     NoTablesFoundForSchemas(Vec<String>),
+    #[error("failed to fetch the schemas of referenced tables")]
+    TableInfoUnavailable { errors: Vec<(String, String)> },
 }
 
 impl MySqlSourcePurificationError {
@@ -295,6 +308,15 @@ impl MySqlSourcePurificationError {
                 "missing schemas: {}",
                 itertools::join(schemas.iter(), ", ")
             )),
+            Self::TableInfoUnavailable { errors } => Some(format!(
+                "the schemas of the following tables could not be fetched:\n{}",
+                itertools::join(
+                    errors
+                        .iter()
+                        .map(|(table, error)| format!("{}: {}", table, error)),
+                    "\n"
+                )
+            )),
             _ => None,
         }
     }
@@ -10,8 +10,11 @@
 //! MySQL utilities for SQL purification.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
 use std::ops::DerefMut;
 
+use futures::{future, StreamExt};
+
 use mz_mysql_util::{validate_source_privileges, MySqlError, MySqlTableDesc, QualifiedTableRef};
 use mz_proto::RustType;
 use mz_sql_parser::ast::display::AstDisplay;
@@ -20,8 +23,10 @@ use mz_sql_parser::ast::{
     ExternalReferences, Ident, IdentError, MySqlConfigOptionName, WithOptionValue,
 };
 use mz_sql_parser::ast::{UnresolvedItemName, Value};
+use mz_ssh_util::tunnel_manager::SshTunnelManager;
 use mz_storage_types::sources::{SourceExportStatementDetails, SourceReferenceResolver};
 use prost::Message;
+use tracing::warn;
 
 use crate::names::Aug;
 use crate::plan::{PlanError, StatementContext};
@@ -292,11 +297,98 @@ pub(super) struct PurifiedSourceExports {
     pub(super) normalized_ignore_columns: Vec<WithOptionValue<Aug>>,
 }
 
+/// Fetches the schemas of the tables requested by `schema_request`, fetching up to
+/// `concurrency` of them at a time.
+///
+/// MySQL executes the queries of a connection one at a time, so in addition to `conn`, this opens
+/// up to `concurrency - 1` connections with `config` for the duration of the fetch.
+///
+/// Fails with the unsupported columns of all tables if any, or otherwise with the errors of all
+/// the tables whose schemas could not be fetched.
+async fn schema_info<'a>(
+    conn: &mut mz_mysql_util::MySqlConn,
+    config: &mz_mysql_util::Config,
+    ssh_tunnel_manager: &SshTunnelManager,
+    concurrency: usize,
+    schema_request: &mz_mysql_util::SchemaRequest<'a>,
+    text_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+    ignore_columns: &BTreeMap<QualifiedTableRef<'a>, BTreeSet<&'a str>>,
+) -> Result<Vec<MySqlTableDesc>, PlanError> {
+    mz_mysql_util::validate_column_refs(text_columns, ignore_columns).map_err(|err| match err {
+        MySqlError::DuplicatedColumnNames {
+            qualified_table_name,
+            columns,
+        } => PlanError::from(MySqlSourcePurificationError::DuplicatedColumnNames(
+            qualified_table_name,
+            columns,
+        )),
+        _ => err.into(),
+    })?;
+    let tables = mz_mysql_util::schema_tables(conn.deref_mut(), schema_request).await?;
+
+    // If the upstream database refuses additional connections, e.g. because it reached
+    // `max_connections`, we make do with the ones we have.
+    let extra_connections = concurrency.min(tables.len()).saturating_sub(1);
+    let mut extra_conns: Vec<_> = future::join_all(
+        (0..extra_connections).map(|_| config.connect("mysql purification", ssh_tunnel_manager)),
+    )
+    .await
+    .into_iter()
+    .filter_map(|conn| match conn {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            warn!("failed to open additional purification connection: {e}");
+            None
+        }
+    })
+    .collect();
+    let conns: Vec<_> = iter::once(conn).chain(&mut extra_conns).collect();
+
+    let mut table_infos = super::fetch_concurrently(conns, tables, |conn, table| async move {
+        let name = format!("{}.{}", table.schema_name, table.table_name);
+        let table_info =
+            mz_mysql_util::table_info(conn.deref_mut(), table, text_columns, ignore_columns).await;
+        (conn, (name, table_info))
+    });
+    let mut tables = vec![];
+    let mut unsupported_cols = vec![];
+    let mut errors = vec![];
+    while let Some((index, (name, table_info))) = table_infos.next().await {
+        match table_info {
+            Ok(table) => tables.push((index, table)),
+            Err(MySqlError::UnsupportedDataTypes { columns }) => {
+                for c in columns {
+                    let col = (c.qualified_table_name, c.column_name, c.column_type);
+                    unsupported_cols.push((index, col));
+                }
+            }
+            Err(e) => errors.push((index, (name, e.to_string()))),
+        }
+    }
+
+    // Report the tables in the order of MySQL, regardless of when they were fetched.
+    if !unsupported_cols.is_empty() {
+        unsupported_cols.sort_by_key(|(index, _)| *index);
+        let cols = unsupported_cols.into_iter().map(|(_, col)| col).collect();
+        Err(MySqlSourcePurificationError::UnrecognizedTypes { cols })?;
+    }
+    if !errors.is_empty() {
+        errors.sort_by_key(|(index, _)| *index);
+        let errors = errors.into_iter().map(|(_, error)| error).collect();
+        Err(MySqlSourcePurificationError::TableInfoUnavailable { errors })?;
+    }
+    tables.sort_by_key(|(index, _)| *index);
+    Ok(tables.into_iter().map(|(_, table)| table).collect())
+}
+
 // Purify the requested external references, returning a set of purified
 // source exports corresponding to external tables, and and additional
 // fields necessary to generate relevant statements and update statement options
 pub(super) async fn purify_source_exports(
     conn: &mut mz_mysql_util::MySqlConn,
+    config: &mz_mysql_util::Config,
+    ssh_tunnel_manager: &SshTunnelManager,
+    concurrency: usize,
     external_references: &mut Option<ExternalReferences>,
     text_columns: Vec<UnresolvedItemName>,
     ignore_columns: Vec<UnresolvedItemName>,
@@ -338,31 +430,16 @@ pub(super) async fn purify_source_exports(
     // NOTE: mysql will only expose the schemas of tables we have at least one privilege on
     // and we can't tell if a table exists without a privilege, so in some cases we may
     // return an EmptyDatabase error in the case of privilege issues.
-    let tables = mz_mysql_util::schema_info(
-        conn.deref_mut(),
+    let tables = schema_info(
+        conn,
+        config,
+        ssh_tunnel_manager,
+        concurrency,
         &table_schema_request,
         &text_cols_map,
         &ignore_cols_map,
     )
-    .await
-    .map_err(|err| match err {
-        mz_mysql_util::MySqlError::UnsupportedDataTypes { columns } => {
-            PlanError::from(MySqlSourcePurificationError::UnrecognizedTypes {
-                cols: columns
-                    .into_iter()
-                    .map(|c| (c.qualified_table_name, c.column_name, c.column_type))
-                    .collect(),
-            })
-        }
-        mz_mysql_util::MySqlError::DuplicatedColumnNames {
-            qualified_table_name,
-            columns,
-        } => PlanError::from(MySqlSourcePurificationError::DuplicatedColumnNames(
-            qualified_table_name,
-            columns,
-        )),
-        _ => err.into(),
-    })?;
+    .await?;
 
     if tables.is_empty() {
         Err(MySqlSourcePurificationError::EmptyDatabase)?;
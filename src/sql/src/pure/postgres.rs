@@ -10,7 +10,10 @@
 //! Postgres utilities for SQL purification.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
+use std::time::{Duration, Instant};
 
+use futures::{future, stream, StreamExt};
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::Config;
 use mz_proto::RustType;
@@ -20,18 +23,26 @@ use mz_sql_parser::ast::{
     Value, WithOptionValue,
 };
 use mz_sql_parser::ast::{ExternalReferences, UnresolvedItemName};
+use mz_ssh_util::tunnel_manager::SshTunnelManager;
 use mz_storage_types::connections::PostgresConnection;
 use mz_storage_types::sources::{SourceExportStatementDetails, SourceReferenceResolver};
 use prost::Message;
 use tokio_postgres::types::Oid;
 use tokio_postgres::Client;
+use tracing::warn;
 
 use crate::names::{Aug, ResolvedItemName};
 use crate::normalize;
 use crate::plan::{PlanError, StatementContext};
 
 use super::error::PgSourcePurificationError;
-use super::{PartialItemName, PurifiedExportDetails, PurifiedSourceExport, RequestedSourceExport};
+use super::{
+    PartialItemName, PurificationProgress, PurificationProgressFn, PurifiedExportDetails,
+    PurifiedSourceExport, RequestedSourceExport,
+};
+
+/// How often the progress of fetching the schemas of upstream tables is reported.
+const PROGRESS_REPORT_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Ensure that we have select permissions on all tables; we have to do this before we
 /// start snapshotting because if we discover we cannot `COPY` from a table while
@@ -41,8 +52,12 @@ pub(super) async fn validate_requested_references_privileges(
     client: &Client,
     table_oids: &[Oid],
 ) -> Result<(), PlanError> {
-    privileges::check_table_privileges(config, client, table_oids).await?;
-    replica_identity::check_replica_identity_full(client, table_oids).await?;
+    // The checks are independent, so their queries can be pipelined.
+    future::try_join(
+        privileges::check_table_privileges(config, client, table_oids),
+        replica_identity::check_replica_identity_full(client, table_oids),
+    )
+    .await?;
 
     Ok(())
 }
@@ -290,19 +305,99 @@ pub(super) struct PurifiedSourceExports {
     pub(super) normalized_text_columns: Vec<WithOptionValue<Aug>>,
 }
 
+/// Fetches the schemas of the tables of the publication `publication`, fetching up to
+/// `concurrency` of them at a time, and periodically reports how many have been fetched to
+/// `progress`.
+///
+/// Postgres executes the queries of a connection one at a time, so in addition to `client`, this
+/// opens up to `concurrency - 1` connections with `config` for the duration of the fetch.
+///
+/// Fails with the errors of all the tables whose schemas could not be fetched.
+async fn publication_info(
+    client: &Client,
+    config: &Config,
+    ssh_tunnel_manager: &SshTunnelManager,
+    publication: &str,
+    concurrency: usize,
+    progress: PurificationProgressFn<'_>,
+) -> Result<Vec<PostgresTableDesc>, PlanError> {
+    let tables = mz_postgres_util::publication_tables(client, publication).await?;
+    let total = tables.len();
+
+    // If the upstream database refuses additional connections, e.g. because it reached
+    // `max_connections`, we make do with the ones we have.
+    let extra_connections = concurrency.min(total).saturating_sub(1);
+    let extra_clients: Vec<_> = future::join_all(
+        (0..extra_connections).map(|_| config.connect("postgres_purification", ssh_tunnel_manager)),
+    )
+    .await
+    .into_iter()
+    .filter_map(|client| match client {
+        Ok(client) => Some(client),
+        Err(e) => {
+            warn!("failed to open additional purification connection: {e}");
+            None
+        }
+    })
+    .collect();
+    let clients: Vec<_> = iter::once(client).chain(&extra_clients).collect();
+
+    let mut table_infos = super::fetch_concurrently(clients, tables, |client, table| async move {
+        let name = format!("{}.{}", table.namespace, table.name);
+        let table_info = mz_postgres_util::table_info(client, table).await;
+        (client, (name, table_info))
+    });
+    let mut tables = vec![];
+    let mut errors = vec![];
+    let mut last_report = Instant::now();
+    while let Some((index, (name, table_info))) = table_infos.next().await {
+        match table_info {
+            Ok(table) => tables.push((index, table)),
+            Err(e) => errors.push((index, (name, e.to_string()))),
+        }
+        if last_report.elapsed() >= PROGRESS_REPORT_INTERVAL {
+            last_report = Instant::now();
+            progress(PurificationProgress::TablesValidated {
+                validated: tables.len() + errors.len(),
+                total,
+            });
+        }
+    }
+
+    // Report the tables in the order of the publication, regardless of when they were fetched.
+    if !errors.is_empty() {
+        errors.sort_by_key(|(index, _)| *index);
+        let errors = errors.into_iter().map(|(_, error)| error).collect();
+        Err(PgSourcePurificationError::TableInfoUnavailable { errors })?;
+    }
+    tables.sort_by_key(|(index, _)| *index);
+    Ok(tables.into_iter().map(|(_, table)| table).collect())
+}
+
 // Purify the requested external references, returning a set of purified
 // source exports corresponding to external tables, and and additional
 // fields necessary to generate relevant statements and update statement options
 pub(super) async fn purify_source_exports(
     client: &Client,
     config: &mz_postgres_util::Config,
+    ssh_tunnel_manager: &SshTunnelManager,
     publication: &str,
     connection: &PostgresConnection,
     external_references: &mut Option<ExternalReferences>,
     mut text_columns: Vec<UnresolvedItemName>,
     unresolved_source_name: &UnresolvedItemName,
+    concurrency: usize,
+    progress: PurificationProgressFn<'_>,
 ) -> Result<PurifiedSourceExports, PlanError> {
-    let mut publication_tables = mz_postgres_util::publication_info(client, publication).await?;
+    let mut publication_tables = publication_info(
+        client,
+        config,
+        ssh_tunnel_manager,
+        publication,
+        concurrency,
+        progress,
+    )
+    .await?;
 
     if publication_tables.is_empty() {
         Err(PgSourcePurificationError::EmptyPublication(
@@ -760,6 +760,7 @@ fn generate_rbac_requirements(
             when: _,
             finishing: _,
             copy_to: _,
+            mirror,
         }) => {
             let mut privileges =
                 generate_read_privileges(catalog, source.depends_on().into_iter(), role_id);
@@ -770,6 +771,11 @@ fn generate_rbac_requirements(
             ) {
                 privileges.push(privilege);
             }
+            if let Some(privilege) =
+                generate_cluster_usage_privileges(source.as_const().is_some(), *mirror, role_id)
+            {
+                privileges.push(privilege);
+            }
             RbacRequirements {
                 privileges,
                 ..Default::default()
@@ -783,6 +789,8 @@ fn generate_rbac_requirements(
             copy_to: _,
             emit_progress: _,
             output: _,
+            archive: _,
+            deduplicate: _,
         }) => {
             let mut privileges =
                 generate_read_privileges(catalog, from.depends_on().into_iter(), role_id);
@@ -878,6 +886,26 @@ fn generate_rbac_requirements(
             },
             ..Default::default()
         },
+        Plan::ExplainAnalyze(plan::ExplainAnalyzePlan {
+            id,
+            cluster_id,
+            replica_id: _,
+        }) => RbacRequirements {
+            privileges: {
+                let item = catalog.get_item(id);
+                let schema_id: ObjectId = item.name().qualifiers.clone().into();
+                vec![
+                    (SystemObjectId::Object(schema_id), AclMode::USAGE, role_id),
+                    (
+                        SystemObjectId::Object((*cluster_id).into()),
+                        AclMode::USAGE,
+                        role_id,
+                    ),
+                ]
+            },
+            item_usage: &EMPTY_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::ExplainSinkSchema(plan::ExplainSinkSchemaPlan { sink_from, .. }) => {
             RbacRequirements {
                 privileges: {
@@ -909,6 +937,7 @@ fn generate_rbac_requirements(
             id,
             values,
             returning,
+            dry_run: _,
         }) => {
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -970,6 +999,14 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::AlterDatabase(plan::AlterDatabasePlan {
+            id,
+            name: _,
+            default_cluster: _,
+        }) => RbacRequirements {
+            ownership: vec![ObjectId::Database(*id)],
+            ..Default::default()
+        },
         Plan::AlterSetCluster(plan::AlterSetClusterPlan { id, set_cluster }) => RbacRequirements {
             ownership: vec![ObjectId::Item(*id)],
             privileges: vec![(
@@ -994,6 +1031,31 @@ fn generate_rbac_requirements(
             ownership: vec![ObjectId::Item(*id)],
             ..Default::default()
         },
+        Plan::AlterSource(plan::AlterSourcePlan {
+            id,
+            action: plan::AlterSourceAction::DropSubsourceExports { subsources, .. },
+        }) => {
+            // Dropping subsources requires the same privileges as dropping them with `DROP
+            // SOURCE`, in addition to owning the source.
+            let mut ownership = vec![ObjectId::Item(*id)];
+            ownership.extend(subsources.iter().copied().map(ObjectId::Item));
+            let privileges = subsources
+                .iter()
+                .map(|id| {
+                    let item = catalog.get_item(id);
+                    (
+                        SystemObjectId::Object(item.name().qualifiers.clone().into()),
+                        AclMode::USAGE,
+                        role_id,
+                    )
+                })
+                .collect();
+            RbacRequirements {
+                ownership,
+                privileges,
+                ..Default::default()
+            }
+        }
         Plan::AlterSource(plan::AlterSourcePlan { id, action: _ }) => RbacRequirements {
             ownership: vec![ObjectId::Item(*id)],
             item_usage: &CREATE_ITEM_USAGE,
@@ -1181,6 +1243,15 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::AlterTableRowCountHint(plan::AlterTableRowCountHintPlan {
+            id,
+            value: _,
+            hint: _,
+        }) => RbacRequirements {
+            ownership: vec![ObjectId::Item(*id)],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::ReadThenWrite(plan::ReadThenWritePlan {
             id,
             selection,
@@ -1188,6 +1259,7 @@ fn generate_rbac_requirements(
             assignments,
             kind,
             returning,
+            dry_run: _,
         }) => {
             let acl_mode = match kind {
                 MutationKind::Insert => AclMode::INSERT,
@@ -1370,20 +1442,44 @@ fn generate_rbac_requirements(
                 .collect(),
             ..Default::default()
         },
-        Plan::SideEffectingFunc(func) => {
-            let role_membership = match func {
-                SideEffectingFunc::PgCancelBackend { connection_id } => {
-                    match active_conns.get(connection_id) {
-                        Some(authenticated_role) => BTreeSet::from([*authenticated_role]),
-                        None => BTreeSet::new(),
-                    }
+        Plan::CancelQuery(plan::CancelQueryPlan { connection_id: _ }) => RbacRequirements {
+            superuser_action: Some("cancel queries".to_string()),
+            ..Default::default()
+        },
+        Plan::AlterSystemTerminateSessions(plan::AlterSystemTerminateSessionsPlan {
+            filters: _,
+        }) => RbacRequirements {
+            superuser_action: Some("terminate sessions".to_string()),
+            ..Default::default()
+        },
+        Plan::SideEffectingFunc(func) => match func {
+            SideEffectingFunc::PgCancelBackend { connection_id } => {
+                let role_membership = match active_conns.get(connection_id) {
+                    Some(authenticated_role) => BTreeSet::from([*authenticated_role]),
+                    None => BTreeSet::new(),
+                };
+                RbacRequirements {
+                    role_membership,
+                    ..Default::default()
                 }
-            };
-            RbacRequirements {
-                role_membership,
-                ..Default::default()
             }
-        }
+            SideEffectingFunc::MzVacuumCatalog => RbacRequirements {
+                superuser_action: Some("vacuum the catalog".to_string()),
+                ..Default::default()
+            },
+            SideEffectingFunc::MzTriggerBackgroundTask { .. } => RbacRequirements {
+                superuser_action: Some("trigger background tasks".to_string()),
+                ..Default::default()
+            },
+            SideEffectingFunc::MzSkipBackgroundTask { .. } => RbacRequirements {
+                superuser_action: Some("skip background tasks".to_string()),
+                ..Default::default()
+            },
+            SideEffectingFunc::MzSetFailpoint { .. } => RbacRequirements {
+                superuser_action: Some("set failpoints".to_string()),
+                ..Default::default()
+            },
+        },
         Plan::ValidateConnection(plan::ValidateConnectionPlan { id, connection: _ }) => {
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             RbacRequirements {
@@ -1399,6 +1495,7 @@ fn generate_rbac_requirements(
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
         | Plan::ShowVariable(plan::ShowVariablePlan { name: _ })
+        | Plan::ShowTransactionWrites
         | Plan::InspectShard(plan::InspectShardPlan { id: _ })
         | Plan::SetVariable(plan::SetVariablePlan {
             name: _,
@@ -1431,8 +1528,11 @@ fn generate_rbac_requirements(
             name: _,
             count: _,
             timeout: _,
+            progress: _,
         })
         | Plan::Close(plan::ClosePlan { name: _ })
+        | Plan::HandoffCursor(plan::HandoffCursorPlan { name: _ })
+        | Plan::ClaimHandoff(plan::ClaimHandoffPlan { token: _ })
         | Plan::Prepare(plan::PreparePlan {
             name: _,
             stmt: _,
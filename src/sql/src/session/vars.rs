@@ -80,6 +80,7 @@ use mz_persist_client::cfg::{CRDB_CONNECT_TIMEOUT, CRDB_TCP_USER_TIMEOUT};
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::bytes::ByteSize;
+use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::user::ExternalUserMetadata;
 use mz_tracing::{CloneableEnvFilter, SerializableDirective};
 use once_cell::sync::Lazy;
@@ -310,6 +311,12 @@ impl SessionVar {
             .unwrap_or(self.definition.value.value())
     }
 
+    /// Whether the variable holds its system or role default, i.e., it has not been set in this
+    /// session, transaction, or with `SET LOCAL`.
+    pub fn is_default(&self) -> bool {
+        self.local_value.is_none() && self.staged_value.is_none() && self.session_value.is_none()
+    }
+
     /// Returns the [`Value`] that is currently stored as the `session_value`.
     ///
     /// Note: This should __only__ be used for inspection, if you want to determine the current
@@ -381,9 +388,12 @@ impl SessionVars {
             &EMIT_PLAN_INSIGHTS_NOTICE,
             &EMIT_TIMESTAMP_NOTICE,
             &EMIT_TRACE_ID_NOTICE,
+            &EMIT_STATEMENT_CORRELATION_ID_NOTICE,
             &AUTO_ROUTE_CATALOG_QUERIES,
+            &AUTO_ROUTE_REPLICA_INTROSPECTION_QUERIES,
             &ENABLE_SESSION_RBAC_CHECKS,
             &ENABLE_SESSION_CARDINALITY_ESTIMATES,
+            &OPTIMIZER_FEATURE_FLAGS,
             &MAX_IDENTIFIER_LENGTH,
             &STATEMENT_LOGGING_SAMPLE_RATE,
             &EMIT_INTROSPECTION_QUERY_NOTICE,
@@ -770,11 +780,21 @@ impl SessionVars {
         *self.expect_value(&EMIT_TRACE_ID_NOTICE)
     }
 
+    /// Returns the value of `emit_statement_correlation_id_notice` configuration parameter.
+    pub fn emit_statement_correlation_id_notice(&self) -> bool {
+        *self.expect_value(&EMIT_STATEMENT_CORRELATION_ID_NOTICE)
+    }
+
     /// Returns the value of `auto_route_catalog_queries` configuration parameter.
     pub fn auto_route_catalog_queries(&self) -> bool {
         *self.expect_value(&AUTO_ROUTE_CATALOG_QUERIES)
     }
 
+    /// Returns the value of `auto_route_replica_introspection_queries` configuration parameter.
+    pub fn auto_route_replica_introspection_queries(&self) -> bool {
+        *self.expect_value(&AUTO_ROUTE_REPLICA_INTROSPECTION_QUERIES)
+    }
+
     /// Returns the value of `enable_session_rbac_checks` configuration parameter.
     pub fn enable_session_rbac_checks(&self) -> bool {
         *self.expect_value(&ENABLE_SESSION_RBAC_CHECKS)
@@ -785,6 +805,11 @@ impl SessionVars {
         *self.expect_value(&ENABLE_SESSION_CARDINALITY_ESTIMATES)
     }
 
+    /// Returns the value of `optimizer_feature_flags` configuration parameter.
+    pub fn optimizer_feature_flags(&self) -> &OptimizerFeatureOverrides {
+        self.expect_value(&OPTIMIZER_FEATURE_FLAGS)
+    }
+
     /// Returns the value of `is_superuser` configuration parameter.
     pub fn is_superuser(&self) -> bool {
         self.user.is_superuser()
@@ -1205,6 +1230,7 @@ impl SystemVars {
             &METRICS_RETENTION,
             &UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP,
             &ENABLE_RBAC_CHECKS,
+            &READ_ONLY_DDL,
             &PG_SOURCE_CONNECT_TIMEOUT,
             &PG_SOURCE_TCP_KEEPALIVES_IDLE,
             &PG_SOURCE_TCP_KEEPALIVES_INTERVAL,
@@ -1268,6 +1294,9 @@ impl SystemVars {
             &STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE,
             &STATEMENT_LOGGING_TARGET_DATA_RATE,
             &STATEMENT_LOGGING_MAX_DATA_CREDIT,
+            &STATEMENT_LOGGING_DRAIN_MAX_BYTES,
+            &STATEMENT_LOGGING_MAX_BUFFERED_BYTES,
+            &STATEMENT_LOGGING_MAX_SPILLED_BYTES,
             &OPTIMIZER_STATS_TIMEOUT,
             &OPTIMIZER_ONESHOT_STATS_TIMEOUT,
             &PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE,
@@ -1276,6 +1305,9 @@ impl SystemVars {
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_WAIT,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL,
             &PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER,
+            &TIMESTAMP_ORACLE_BATCH_WINDOW,
+            &TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT,
+            &ENABLE_TIMESTAMP_ORACLE_DEGRADED_READS,
             &USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION,
         ];
 
@@ -2001,6 +2033,11 @@ impl SystemVars {
         *self.expect_value(&ENABLE_RBAC_CHECKS)
     }
 
+    /// Returns the `read_only_ddl` configuration parameter.
+    pub fn read_only_ddl(&self) -> bool {
+        *self.expect_value(&READ_ONLY_DDL)
+    }
+
     /// Returns the `max_connections` configuration parameter.
     pub fn max_connections(&self) -> u32 {
         *self.expect_value(&MAX_CONNECTIONS)
@@ -2157,6 +2194,21 @@ impl SystemVars {
         *self.expect_value(&STATEMENT_LOGGING_MAX_DATA_CREDIT)
     }
 
+    /// Returns the `statement_logging_drain_max_bytes` configuration parameter.
+    pub fn statement_logging_drain_max_bytes(&self) -> usize {
+        *self.expect_value(&STATEMENT_LOGGING_DRAIN_MAX_BYTES)
+    }
+
+    /// Returns the `statement_logging_max_buffered_bytes` configuration parameter.
+    pub fn statement_logging_max_buffered_bytes(&self) -> usize {
+        *self.expect_value(&STATEMENT_LOGGING_MAX_BUFFERED_BYTES)
+    }
+
+    /// Returns the `statement_logging_max_spilled_bytes` configuration parameter.
+    pub fn statement_logging_max_spilled_bytes(&self) -> usize {
+        *self.expect_value(&STATEMENT_LOGGING_MAX_SPILLED_BYTES)
+    }
+
     /// Returns the `statement_logging_max_sample_rate` configuration parameter.
     pub fn statement_logging_max_sample_rate(&self) -> Numeric {
         *self.expect_value(&STATEMENT_LOGGING_MAX_SAMPLE_RATE)
@@ -2202,6 +2254,21 @@ impl SystemVars {
         *self.expect_value(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER)
     }
 
+    /// Returns the `timestamp_oracle_batch_window` configuration parameter.
+    pub fn timestamp_oracle_batch_window(&self) -> Duration {
+        *self.expect_value(&TIMESTAMP_ORACLE_BATCH_WINDOW)
+    }
+
+    /// Returns the `timestamp_oracle_circuit_breaker_timeout` configuration parameter.
+    pub fn timestamp_oracle_circuit_breaker_timeout(&self) -> Duration {
+        *self.expect_value(&TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT)
+    }
+
+    /// Returns the `enable_timestamp_oracle_degraded_reads` configuration parameter.
+    pub fn enable_timestamp_oracle_degraded_reads(&self) -> bool {
+        *self.expect_value(&ENABLE_TIMESTAMP_ORACLE_DEGRADED_READS)
+    }
+
     /// Returns the `user_storage_managed_collections_batch_duration` configuration parameter.
     pub fn user_storage_managed_collections_batch_duration(&self) -> Duration {
         *self.expect_value(&USER_STORAGE_MANAGED_COLLECTIONS_BATCH_DURATION)
@@ -2295,6 +2362,8 @@ pub fn is_pg_timestamp_oracle_config_var(name: &str) -> bool {
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_WAIT.name()
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL.name()
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER.name()
+        || name == TIMESTAMP_ORACLE_BATCH_WINDOW.name()
+        || name == TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT.name()
         || name == CRDB_CONNECT_TIMEOUT.name()
         || name == CRDB_TCP_USER_TIMEOUT.name()
 }
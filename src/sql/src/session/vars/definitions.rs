@@ -17,12 +17,13 @@ use derivative::Derivative;
 use mz_adapter_types::timestamp_oracle::{
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_SIZE, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_WAIT,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL_STAGGER,
+    DEFAULT_TIMESTAMP_ORACLE_BATCH_WINDOW, DEFAULT_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT,
 };
 use mz_ore::cast::{self, CastFrom};
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::bytes::ByteSize;
-use mz_repr::optimize::OptimizerFeatures;
+use mz_repr::optimize::{OptimizerFeatureOverrides, OptimizerFeatures};
 use mz_sql_parser::ast::Ident;
 use mz_sql_parser::ident;
 use mz_storage_types::parameters::{
@@ -636,6 +637,33 @@ pub static PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER: VarDefinition = VarD
     true,
 );
 
+/// Controls `mz_adapter::coord::timestamp_oracle::postgres_oracle::DynamicConfig::batch_window`.
+pub static TIMESTAMP_ORACLE_BATCH_WINDOW: VarDefinition = VarDefinition::new(
+    "timestamp_oracle_batch_window",
+    value!(Duration; DEFAULT_TIMESTAMP_ORACLE_BATCH_WINDOW),
+    "The time that the timestamp oracle waits for concurrent requests for read timestamps, \
+    to serve them with one round trip to Postgres/CRDB. With zero, only requests that \
+    are already waiting are served together.",
+    true,
+);
+
+/// Controls `mz_adapter::coord::timestamp_oracle::postgres_oracle::DynamicConfig::circuit_breaker_timeout`.
+pub static TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT: VarDefinition = VarDefinition::new(
+    "timestamp_oracle_circuit_breaker_timeout",
+    value!(Duration; DEFAULT_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT),
+    "The time that requests to the timestamp oracle have to be failing for the oracle to be \
+    considered unavailable, after which queries and writes that need it are rejected.",
+    true,
+);
+
+pub static ENABLE_TIMESTAMP_ORACLE_DEGRADED_READS: VarDefinition = VarDefinition::new(
+    "enable_timestamp_oracle_degraded_reads",
+    value!(bool; false),
+    "Whether to run queries under serializable isolation, using the locally known frontiers, \
+    while the timestamp oracle is unavailable (Materialize).",
+    true,
+);
+
 /// The default for the `DISK` option when creating managed clusters and cluster replicas.
 pub static DISK_CLUSTER_REPLICAS_DEFAULT: VarDefinition = VarDefinition::new(
     "disk_cluster_replicas_default",
@@ -1252,6 +1280,13 @@ pub static EMIT_TRACE_ID_NOTICE: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static EMIT_STATEMENT_CORRELATION_ID_NOTICE: VarDefinition = VarDefinition::new(
+    "emit_statement_correlation_id_notice",
+    value!(bool; false),
+    "Boolean flag indicating whether to send a NOTICE specifying the correlation id of each logged statement (Materialize).",
+    false,
+);
+
 pub static UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP: VarDefinition = VarDefinition::new(
     "unsafe_mock_audit_event_timestamp",
     value!(Option<mz_repr::Timestamp>; None),
@@ -1267,6 +1302,14 @@ pub static ENABLE_RBAC_CHECKS: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static READ_ONLY_DDL: VarDefinition = VarDefinition::new(
+    "read_only_ddl",
+    value!(bool; false),
+    "Whether to reject statements that change the catalog, e.g. during change freezes or \
+        incident response, while still allowing reads and subscribes (Materialize).",
+    false,
+);
+
 pub static ENABLE_SESSION_RBAC_CHECKS: VarDefinition = VarDefinition::new(
     "enable_session_rbac_checks",
     // TODO(jkosh44) Once RBAC is complete, change this to `true`.
@@ -1293,6 +1336,14 @@ pub static ENABLE_SESSION_CARDINALITY_ESTIMATES: VarDefinition = VarDefinition::
 )
 .with_feature_flag(&ENABLE_CARDINALITY_ESTIMATES);
 
+pub static OPTIMIZER_FEATURE_FLAGS: VarDefinition = VarDefinition::new_lazy(
+    "optimizer_feature_flags",
+    lazy_value!(OptimizerFeatureOverrides; OptimizerFeatureOverrides::default),
+    "Overrides optimizer feature flags for the queries, subscribes and EXPLAIN statements of the \
+        session, as a comma-separated list of `feature = value` pairs (Materialize).",
+    false,
+);
+
 pub static OPTIMIZER_STATS_TIMEOUT: VarDefinition = VarDefinition::new(
     "optimizer_stats_timeout",
     value!(Duration; Duration::from_millis(250)),
@@ -1355,6 +1406,27 @@ pub static STATEMENT_LOGGING_TARGET_DATA_RATE: VarDefinition = VarDefinition::ne
     true,
 );
 
+pub static STATEMENT_LOGGING_DRAIN_MAX_BYTES: VarDefinition = VarDefinition::new(
+    "statement_logging_drain_max_bytes",
+    value!(usize; 32 * 1024 * 1024),
+    "The maximum number of bytes of pending events written to the statement log at once (Materialize).",
+    true,
+);
+
+pub static STATEMENT_LOGGING_MAX_BUFFERED_BYTES: VarDefinition = VarDefinition::new(
+    "statement_logging_max_buffered_bytes",
+    value!(usize; 256 * 1024 * 1024),
+    "The maximum number of bytes of pending statement log events kept in memory, beyond which further events are spilled to disk (Materialize).",
+    true,
+);
+
+pub static STATEMENT_LOGGING_MAX_SPILLED_BYTES: VarDefinition = VarDefinition::new(
+    "statement_logging_max_spilled_bytes",
+    value!(usize; 4 * 1024 * 1024 * 1024),
+    "The maximum number of bytes of pending statement log events spilled to disk, beyond which further events are dropped (Materialize).",
+    true,
+);
+
 pub static STATEMENT_LOGGING_MAX_SAMPLE_RATE: VarDefinition = VarDefinition::new_lazy(
     "statement_logging_max_sample_rate",
     lazy_value!(Numeric; || 0.0.into()),
@@ -1379,6 +1451,13 @@ pub static AUTO_ROUTE_CATALOG_QUERIES: VarDefinition = VarDefinition::new(
     false,
 );
 
+pub static AUTO_ROUTE_REPLICA_INTROSPECTION_QUERIES: VarDefinition = VarDefinition::new(
+    "auto_route_replica_introspection_queries",
+    value!(bool; false),
+    "Whether to force queries that depend only on system tables and per-replica introspection relations, to run on the mz_catalog_server cluster (Materialize).",
+    false,
+);
+
 pub static MAX_CONNECTIONS: VarDefinition = VarDefinition::new(
     "max_connections",
     value!(u32; 5000),
@@ -1884,6 +1963,20 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_consistency_tokens,
+        desc: "consistency tokens",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_subscribe_to_multiple_relations,
+        desc: "`SUBSCRIBE (TO ..)` of multiple relations",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_cardinality_estimates,
         desc: "join planning with cardinality estimates",
@@ -2046,6 +2139,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_subscribe_into,
+        desc: "SUBSCRIBE ... INTO 's3://...'",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_session_timelines,
         desc: "strong session serializable isolation levels",
@@ -2081,6 +2181,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_webhook_sinks,
+        desc: "Enable CREATE SINK ... INTO WEBHOOK",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_unlimited_retain_history,
         desc: "Disable limits on RETAIN HISTORY (below 1s default, and 0 disables compaction).",
@@ -2123,6 +2230,55 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_adapter_failpoints,
+        desc: "mz_set_failpoint",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_explain_analyze,
+        desc: "EXPLAIN ANALYZE",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_peek_mirroring,
+        desc: "SELECT ... WITH (MIRROR TO CLUSTER ...)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_logical_replication,
+        desc: "logical replication connections",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_write_dry_run,
+        desc: "INSERT, UPDATE and DELETE WITH (DRY RUN)",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_partial_indexes,
+        desc: "CREATE INDEX ... WHERE",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_row_count_hints,
+        desc: "the ROW COUNT HINT table option",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
 );
 
 impl From<&super::SystemVars> for OptimizerFeatures {
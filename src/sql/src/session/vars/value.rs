@@ -18,6 +18,7 @@ use itertools::Itertools;
 use mz_pgwire_common::Severity;
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::strconv;
 use mz_rocksdb_types::config::{CompactionStyle, CompressionType};
 use mz_sql_parser::ast::{Ident, TransactionIsolationLevel};
@@ -479,6 +480,37 @@ impl Value for Vec<Ident> {
     }
 }
 
+impl Value for OptimizerFeatureOverrides {
+    fn type_name() -> Cow<'static, str>
+    where
+        Self: Sized,
+    {
+        "optimizer feature list".into()
+    }
+
+    fn parse(input: VarInput<'_>) -> Result<Self, VarParseError>
+    where
+        Self: Sized,
+    {
+        let values = input.to_vec();
+        values
+            .join(",")
+            .parse()
+            .map_err(|reason| VarParseError::InvalidParameterValue {
+                invalid_values: values,
+                reason,
+            })
+    }
+
+    fn box_clone(&self) -> Box<dyn Value> {
+        Box::new(self.clone())
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl Value for Vec<SerializableDirective> {
     fn type_name() -> Cow<'static, str>
     where
@@ -1059,6 +1059,7 @@ impl<'a> RunnerInner<'a> {
             cors_allowed_origin: AllowOrigin::list([]),
             unsafe_mode: true,
             all_features: false,
+            read_only_replica: false,
             metrics_registry,
             now,
             environment_id,
@@ -1089,6 +1090,7 @@ impl<'a> RunnerInner<'a> {
             launchdarkly_key_map: Default::default(),
             config_sync_timeout: Duration::from_secs(30),
             config_sync_loop_interval: None,
+            statement_retirement_hooks: Default::default(),
             bootstrap_role: Some("materialize".into()),
             http_host_name: Some(host_name),
             internal_console_redirect_url: None,
@@ -2349,6 +2351,7 @@ fn generate_view_sql(
             )
         },
         with_options: Vec::new(),
+        predicate: None,
         if_not_exists: false,
     })
     .to_ast_string_stable();
@@ -2394,6 +2397,7 @@ fn generate_view_sql(
             limit: None,
             offset: None,
         },
+        options: vec![],
         as_of: query_as_of.clone(),
     })
     .to_ast_string_stable();
@@ -2557,6 +2561,7 @@ fn mutate(sql: &str) -> Vec<String> {
                             .collect(),
                     ),
                     with_options: Vec::new(),
+                    predicate: None,
                     if_not_exists: false,
                 })
                 .to_ast_string_stable(),
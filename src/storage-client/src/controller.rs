@@ -87,9 +87,44 @@ pub enum IntrospectionType {
     ComputeMaterializedViewRefreshes,
     ComputeErrorCounts,
     ComputeHydrationTimes,
+    ComputeCardinalityAdvisories,
 
     // Written by the Adapter for tracking AWS PrivateLink Connection Status History
     PrivatelinkConnectionStatusHistory,
+
+    // Written by the Adapter whenever an `environmentd` takes over leadership
+    LeadershipHistory,
+
+    // Written by the Adapter whenever a query cancellation is requested
+    CancellationHistory,
+
+    // Written by the Adapter for tracking the delivery progress of webhook sinks
+    WebhookSinkDeliveryHistory,
+
+    // Written by the Adapter whenever it advises moving replicas away from an availability zone
+    ReplicaAzAdvisories,
+
+    // Written by the Adapter whenever a subscribe starts or is retired
+    SubscriptionHistory,
+
+    // Written by the Adapter whenever the storage usage collection estimates the number of
+    // distinct keys of the data shards
+    StorageShardCardinality,
+
+    // Written by the Adapter whenever the results of a peek and its mirror differ
+    PeekMirrorDiscrepancies,
+
+    // Written by the Adapter whenever the value of a system parameter changes
+    SystemParameterHistory,
+
+    // Written by the Adapter whenever a write to a table with `CHANGE AUDIT` commits
+    TableChangeAudit,
+
+    // Written by the Adapter whenever it shuts down a compute dataflow
+    DataflowShutdownHistory,
+
+    // Written by the Adapter whenever a catalog transaction changes user objects
+    CatalogChangeHistory,
 }
 
 /// Describes how data is written to the collection.
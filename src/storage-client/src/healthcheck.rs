@@ -95,6 +95,12 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|
         .with_column("error_message", ScalarType::String.nullable(true))
         .with_column("rows_returned", ScalarType::Int64.nullable(true))
         .with_column("execution_strategy", ScalarType::String.nullable(true))
+        .with_column("max_result_memory_bytes", ScalarType::Int64.nullable(true))
+        .with_column("effective_isolation", ScalarType::String.nullable(true))
+        .with_column(
+            "isolation_downgrade_reason",
+            ScalarType::String.nullable(true),
+        )
 });
 
 pub static MZ_SOURCE_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
@@ -121,6 +127,16 @@ pub static MZ_SINK_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         .with_column("details", ScalarType::Jsonb.nullable(true))
 });
 
+pub static MZ_WEBHOOK_SINK_DELIVERY_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("sink_id", ScalarType::String.nullable(false))
+        .with_column("write_frontier", ScalarType::MzTimestamp.nullable(false))
+});
+
 pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC: Lazy<RelationDesc> =
     Lazy::new(|| {
         RelationDesc::empty()
@@ -65,6 +65,11 @@ use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::inline::InlinedConnection;
 use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::controller::{AlterError, CollectionMetadata, StorageError, TxnsCodecRow};
+use mz_storage_types::dyncfgs::{
+    CANCELLATION_HISTORY_RETENTION_INTERVAL, CATALOG_CHANGE_HISTORY_RETENTION_INTERVAL,
+    DATAFLOW_SHUTDOWN_HISTORY_RETENTION_INTERVAL, STORAGE_SHARD_CARDINALITY_RETENTION_INTERVAL,
+    SUBSCRIPTION_HISTORY_RETENTION_INTERVAL,
+};
 use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::parameters::StorageParameters;
 use mz_storage_types::read_holds::{ReadHold, ReadHoldError};
@@ -2948,7 +2953,8 @@ where
             // it resilient to the upper moving concurrently.
             IntrospectionType::SourceStatusHistory
             | IntrospectionType::SinkStatusHistory
-            | IntrospectionType::PrivatelinkConnectionStatusHistory => {
+            | IntrospectionType::PrivatelinkConnectionStatusHistory
+            | IntrospectionType::WebhookSinkDeliveryHistory => {
                 if !self.read_only {
                     self.prepare_introspection_collection(id, introspection_type)
                         .await?;
@@ -2964,7 +2970,8 @@ where
             | IntrospectionType::ComputeOperatorHydrationStatus
             | IntrospectionType::ComputeMaterializedViewRefreshes
             | IntrospectionType::ComputeErrorCounts
-            | IntrospectionType::ComputeHydrationTimes => {
+            | IntrospectionType::ComputeHydrationTimes
+            | IntrospectionType::ComputeCardinalityAdvisories => {
                 self.collection_manager.register_differential_collection(
                     id,
                     read_handle_fn,
@@ -2992,6 +2999,54 @@ where
                 self.collection_manager
                     .register_append_only_collection(id, force_writable);
             }
+
+            // Only written once per leadership transition, so we keep the
+            // entire history.
+            IntrospectionType::LeadershipHistory => {
+                if !self.read_only {
+                    self.prepare_introspection_collection(id, introspection_type)
+                        .await?;
+                }
+
+                self.collection_manager
+                    .register_append_only_collection(id, force_writable);
+            }
+
+            // Audit-like histories of cancellation requests, catalog changes,
+            // subscriptions and dataflow shutdowns, and the estimates of the
+            // cardinality of shards, which are kept over time to follow the
+            // growth of the collections, so we keep the entries of their
+            // retention intervals.
+            IntrospectionType::CancellationHistory
+            | IntrospectionType::CatalogChangeHistory
+            | IntrospectionType::SubscriptionHistory
+            | IntrospectionType::DataflowShutdownHistory
+            | IntrospectionType::StorageShardCardinality => {
+                if !self.read_only {
+                    self.prepare_introspection_collection(id, introspection_type)
+                        .await?;
+                }
+
+                self.collection_manager
+                    .register_append_only_collection(id, force_writable);
+            }
+
+            // Discrepancies are rare and needed to investigate them after the
+            // fact, so we keep the entire history. The same goes for the
+            // advisories of degraded availability zones, changes to system
+            // parameters and the audit of table writes.
+            IntrospectionType::ReplicaAzAdvisories
+            | IntrospectionType::PeekMirrorDiscrepancies
+            | IntrospectionType::SystemParameterHistory
+            | IntrospectionType::TableChangeAudit => {
+                if !self.read_only {
+                    self.prepare_introspection_collection(id, introspection_type)
+                        .await?;
+                }
+
+                self.collection_manager
+                    .register_append_only_collection(id, force_writable);
+            }
         }
 
         Ok(())
@@ -3120,13 +3175,18 @@ where
                 )
                 .await;
             }
+            IntrospectionType::WebhookSinkDeliveryHistory => {
+                // Nothing to prepare, the coordinator retracts all but the latest entry of each
+                // webhook sink itself.
+            }
 
             // Truncate compute-maintained collections.
             IntrospectionType::ComputeDependencies
             | IntrospectionType::ComputeOperatorHydrationStatus
             | IntrospectionType::ComputeMaterializedViewRefreshes
             | IntrospectionType::ComputeErrorCounts
-            | IntrospectionType::ComputeHydrationTimes => {
+            | IntrospectionType::ComputeHydrationTimes
+            | IntrospectionType::ComputeCardinalityAdvisories => {
                 // Differential collections start with an empty
                 // desired state. No need to manually reset.
             }
@@ -3143,6 +3203,26 @@ where
                 // think about that! Issue:
                 // https://github.com/MaterializeInc/materialize/issues/25696
             }
+
+            IntrospectionType::LeadershipHistory => {
+                // Nothing to prepare, we never remove from this collection.
+            }
+
+            IntrospectionType::CancellationHistory
+            | IntrospectionType::CatalogChangeHistory
+            | IntrospectionType::SubscriptionHistory
+            | IntrospectionType::DataflowShutdownHistory
+            | IntrospectionType::StorageShardCardinality => {
+                self.partially_truncate_history_by_age(introspection_type)
+                    .await;
+            }
+
+            IntrospectionType::ReplicaAzAdvisories
+            | IntrospectionType::PeekMirrorDiscrepancies
+            | IntrospectionType::SystemParameterHistory
+            | IntrospectionType::TableChangeAudit => {
+                // Nothing to prepare, we never remove from this collection.
+            }
         }
 
         Ok(())
@@ -3360,29 +3440,136 @@ where
             }
         }
 
+        let expected_upper = upper.into_option().expect("checked above");
+        let deletions = deletions.into_iter().map(Row::pack).collect();
+        self.append_partial_truncation(id, deletions, expected_upper)
+            .await;
+
+        let mut row_buf = Row::default();
+        latest_row_per_id
+            .into_iter()
+            .filter_map(|(key, (_, row_vec))| {
+                match GlobalId::from_str(key.unwrap_str()) {
+                    Ok(id) => {
+                        let mut packer = row_buf.packer();
+                        packer.extend(row_vec.into_iter());
+                        Some((id, row_buf.clone()))
+                    }
+                    // Ignore any rows that can't be unwrapped correctly
+                    Err(_) => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Effectively truncates the history shard of `collection` except for the
+    /// entries whose time is within its retention interval.
+    ///
+    /// Like [`Self::partially_truncate_status_history`], this retracts old
+    /// updates of an append-only collection so that it does not grow
+    /// unboundedly.
+    async fn partially_truncate_history_by_age(&mut self, collection: IntrospectionType) {
+        let config_set = self.config.config_set();
+        let (retention, time_col) = match collection {
+            IntrospectionType::CancellationHistory => (
+                CANCELLATION_HISTORY_RETENTION_INTERVAL.get(config_set),
+                "occurred_at",
+            ),
+            IntrospectionType::CatalogChangeHistory => (
+                CATALOG_CHANGE_HISTORY_RETENTION_INTERVAL.get(config_set),
+                "occurred_at",
+            ),
+            IntrospectionType::SubscriptionHistory => (
+                SUBSCRIPTION_HISTORY_RETENTION_INTERVAL.get(config_set),
+                "occurred_at",
+            ),
+            IntrospectionType::DataflowShutdownHistory => (
+                DATAFLOW_SHUTDOWN_HISTORY_RETENTION_INTERVAL.get(config_set),
+                "occurred_at",
+            ),
+            IntrospectionType::StorageShardCardinality => (
+                STORAGE_SHARD_CARDINALITY_RETENTION_INTERVAL.get(config_set),
+                "collection_timestamp",
+            ),
+            _ => unreachable!(),
+        };
+        let retention = i64::try_from(retention.as_millis()).unwrap_or(i64::MAX);
+        let cutoff = i64::try_from((self.now)())
+            .expect("must fit")
+            .saturating_sub(retention);
+
+        let id = self.introspection_ids.lock().expect("poisoned")[&collection];
+        let time_col = self
+            .collection(id)
+            .expect("introspection collection exists")
+            .collection_metadata
+            .relation_desc
+            .get_by_name(&ColumnName::from(time_col))
+            .expect("schema has not changed")
+            .0;
+
+        let upper = self
+            .persist_monotonic_worker
+            .recent_upper(id)
+            .await
+            .expect("missing collection")
+            .expect("missing collection");
+
+        let mut rows = match upper.as_option() {
+            Some(f) if f > &T::minimum() => {
+                let as_of = f.step_back().unwrap();
+
+                self.snapshot(id, as_of).await.expect("snapshot succeeds")
+            }
+            // If collection is closed or the frontier is the minimum, we cannot
+            // or don't need to truncate (respectively).
+            _ => return,
+        };
+
+        // Consolidate the snapshot, so we can process it correctly below.
+        differential_dataflow::consolidation::consolidate(&mut rows);
+
+        let mut deletions = vec![];
+        for (row, diff) in rows {
+            let time = row
+                .iter()
+                .nth(time_col)
+                .expect("schema has not changed")
+                .unwrap_timestamptz()
+                .timestamp_millis();
+            if time < cutoff {
+                for _ in 0..diff {
+                    deletions.push(row.clone());
+                }
+            }
+        }
+
+        let expected_upper = upper.into_option().expect("checked above");
+        self.append_partial_truncation(id, deletions, expected_upper)
+            .await;
+    }
+
+    /// Retracts `deletions` from the history collection `id`, whose upper was `expected_upper`
+    /// when its contents were read.
+    async fn append_partial_truncation(
+        &mut self,
+        id: GlobalId,
+        deletions: Vec<Row>,
+        expected_upper: T,
+    ) {
         // It is very important that we append our retractions at the timestamp
         // right after the timestamp at which we got our snapshot. Otherwise,
         // it's possible for someone else to sneak in retractions or other
         // unexpected changes.
-        let expected_upper = upper.into_option().expect("checked above");
         let new_upper = TimestampManipulation::step_forward(&expected_upper);
 
-        let mut row_buf = Row::default();
         // Updates are only deletes because everything else is already in the shard.
         let updates = deletions
             .into_iter()
-            .map(|unpacked_row| {
-                // Re-pack all rows
-                let mut packer = row_buf.packer();
-                packer.extend(unpacked_row.into_iter());
-
-                let update = Update {
-                    row: row_buf.clone(),
-                    timestamp: expected_upper.clone(),
-                    diff: -1,
-                };
-
-                update
+            .map(|row| Update {
+                row,
+                timestamp: expected_upper.clone(),
+                diff: -1,
             })
             .collect();
 
@@ -3427,21 +3614,6 @@ where
                 }
             }
         }
-
-        latest_row_per_id
-            .into_iter()
-            .filter_map(|(key, (_, row_vec))| {
-                match GlobalId::from_str(key.unwrap_str()) {
-                    Ok(id) => {
-                        let mut packer = row_buf.packer();
-                        packer.extend(row_vec.into_iter());
-                        Some((id, row_buf.clone()))
-                    }
-                    // Ignore any rows that can't be unwrapped correctly
-                    Err(_) => None,
-                }
-            })
-            .collect()
     }
 
     /// Appends a new global ID, shard ID pair to the appropriate collection.
@@ -35,6 +35,8 @@ use timely::progress::Antichain;
 use timely::PartialOrder;
 use tracing::{debug, info};
 
+pub mod archive;
+mod json;
 mod parquet;
 mod pgcopy;
 
@@ -104,6 +106,18 @@ pub fn copy_to<G, F>(
             start_stream,
             params,
         ),
+        S3SinkFormat::Json => render_upload_operator::<G, json::JsonUploader>(
+            scope.clone(),
+            connection_context.clone(),
+            aws_connection.clone(),
+            connection_id,
+            connection_details,
+            sink_id,
+            input_collection,
+            up_to,
+            start_stream,
+            params,
+        ),
     };
 
     render_completion_operator(
@@ -536,6 +550,16 @@ impl S3KeyManager {
         )
     }
 
+    /// The S3 key prefix shared by the data files of a specific batch.
+    fn batch_prefix(&self, batch: u64) -> String {
+        format!("{}batch-{:04}-", self.object_key_prefix, batch)
+    }
+
+    /// The S3 key to use for the manifest of a specific batch of an archive.
+    fn manifest_key(&self, batch: u64) -> String {
+        format!("{}manifest-{:04}.json", self.object_key_prefix, batch)
+    }
+
     /// The S3 key to use for the incomplete sentinel file
     fn incomplete_sentinel_key(&self) -> String {
         format!("{}INCOMPLETE", self.object_key_prefix)
@@ -0,0 +1,225 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Archives a stream of updates to S3 in segments.
+//!
+//! Each segment holds the updates at the times in `[lower, upper)` and consists of one or more
+//! data files, uploaded with the same uploaders as `COPY ... TO`, followed by a manifest. A
+//! segment's `upper` is the next segment's `lower`, so a reader that processes the manifests in
+//! order sees every update exactly once. The manifest of the last segment of a complete stream
+//! has no `upper`.
+
+use aws_types::sdk_config::SdkConfig;
+use mz_ore::future::InTask;
+use mz_ore::task::JoinHandleExt;
+use mz_repr::{GlobalId, Row, Timestamp};
+use mz_storage_types::connections::aws::AwsConnection;
+use mz_storage_types::connections::ConnectionContext;
+use mz_storage_types::sinks::{S3SinkFormat, S3UploadInfo};
+use tracing::info;
+
+use super::json::JsonUploader;
+use super::parquet::ParquetUploader;
+use super::{CopyToParameters, CopyToS3Uploader, S3KeyManager};
+
+/// Archives a stream of updates to the S3 path of an [`S3UploadInfo`].
+pub struct S3Archiver {
+    sdk_config: SdkConfig,
+    upload_info: S3UploadInfo,
+    /// Identifies the archive, as a unique prefix for the objects it creates.
+    sink_id: GlobalId,
+    key_manager: S3KeyManager,
+    params: CopyToParameters,
+    /// The number of the current segment.
+    segment: u64,
+    /// The lower bound of the times in the current segment.
+    lower: Timestamp,
+    /// The uploader of the current segment, once it has a row.
+    uploader: Option<SegmentUploader>,
+    /// The number of rows in the current segment.
+    rows: u64,
+}
+
+/// The manifest of a segment that has been uploaded by an [`S3Archiver`].
+#[derive(Debug, Clone)]
+pub struct SegmentManifest {
+    /// The S3 URI of the manifest.
+    pub uri: String,
+    /// The lower bound of the times in the segment.
+    pub lower: Timestamp,
+    /// The upper bound of the times in the segment, or `None` if the archive is complete.
+    pub upper: Option<Timestamp>,
+    /// The number of rows in the segment.
+    pub rows: u64,
+}
+
+/// The uploader for the files of a segment in the format of the archive.
+enum SegmentUploader {
+    Json(JsonUploader),
+    Parquet(ParquetUploader),
+}
+
+impl SegmentUploader {
+    async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        match self {
+            SegmentUploader::Json(uploader) => uploader.append_row(row).await,
+            SegmentUploader::Parquet(uploader) => uploader.append_row(row).await,
+        }
+    }
+
+    async fn finish(&mut self) -> Result<(), anyhow::Error> {
+        match self {
+            SegmentUploader::Json(uploader) => uploader.finish().await,
+            SegmentUploader::Parquet(uploader) => uploader.finish().await,
+        }
+    }
+}
+
+impl S3Archiver {
+    /// Prepares an archive of the updates at times not before `lower` at the S3 path of
+    /// `upload_info`, which must be empty.
+    pub async fn new(
+        connection_context: &ConnectionContext,
+        aws_connection: &AwsConnection,
+        connection_id: GlobalId,
+        upload_info: S3UploadInfo,
+        sink_id: GlobalId,
+        lower: Timestamp,
+        params: CopyToParameters,
+    ) -> Result<Self, anyhow::Error> {
+        match upload_info.format {
+            S3SinkFormat::Json | S3SinkFormat::Parquet => {}
+            S3SinkFormat::PgCopy(_) => anyhow::bail!("Expected Json or Parquet format"),
+        }
+
+        let sdk_config = aws_connection
+            .load_sdk_config(connection_context, connection_id, InTask::No)
+            .await?;
+        let key_manager = S3KeyManager::new(&sink_id, &upload_info.uri);
+
+        let client = mz_aws_util::s3::new_client(&sdk_config);
+        let bucket = key_manager.bucket.clone();
+        let path_prefix = key_manager.path_prefix().to_string();
+        if let Some(files) =
+            mz_aws_util::s3::list_bucket_path(&client, &bucket, &path_prefix).await?
+        {
+            if !files.is_empty() {
+                anyhow::bail!(
+                    "S3 bucket path is not empty, contains {} objects",
+                    files.len()
+                );
+            }
+        }
+
+        Ok(S3Archiver {
+            sdk_config,
+            upload_info,
+            sink_id,
+            key_manager,
+            params,
+            segment: 0,
+            lower,
+            uploader: None,
+            rows: 0,
+        })
+    }
+
+    /// Appends `row` to the current segment.
+    pub async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        let uploader = match &mut self.uploader {
+            Some(uploader) => uploader,
+            None => {
+                let uploader = match self.upload_info.format {
+                    S3SinkFormat::Json => SegmentUploader::Json(JsonUploader::new(
+                        self.sdk_config.clone(),
+                        self.upload_info.clone(),
+                        &self.sink_id,
+                        self.segment,
+                        self.params.clone(),
+                    )?),
+                    S3SinkFormat::Parquet => SegmentUploader::Parquet(ParquetUploader::new(
+                        self.sdk_config.clone(),
+                        self.upload_info.clone(),
+                        &self.sink_id,
+                        self.segment,
+                        self.params.clone(),
+                    )?),
+                    S3SinkFormat::PgCopy(_) => unreachable!("rejected in `S3Archiver::new`"),
+                };
+                self.uploader.insert(uploader)
+            }
+        };
+        uploader.append_row(row).await?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Finishes the current segment, which holds the updates at times before `upper`, and uploads
+    /// its manifest. An `upper` of `None` completes the archive.
+    pub async fn finish_segment(
+        &mut self,
+        upper: Option<Timestamp>,
+    ) -> Result<SegmentManifest, anyhow::Error> {
+        if let Some(mut uploader) = self.uploader.take() {
+            uploader.finish().await?;
+        }
+
+        let client = mz_aws_util::s3::new_client(&self.sdk_config);
+        let bucket = self.key_manager.bucket.clone();
+        let files = mz_aws_util::s3::list_bucket_path(
+            &client,
+            &bucket,
+            &self.key_manager.batch_prefix(self.segment),
+        )
+        .await?
+        .unwrap_or_default();
+
+        let manifest = serde_json::json!({
+            "segment": self.segment,
+            // Timestamps are strings, as they don't fit into the numbers of many JSON parsers.
+            "lower": self.lower.to_string(),
+            "upper": upper.map(|upper| upper.to_string()),
+            "rows": self.rows,
+            "files": files
+                .iter()
+                .map(|key| format!("s3://{bucket}/{key}"))
+                .collect::<Vec<_>>(),
+        });
+        let key = self.key_manager.manifest_key(self.segment);
+        info!(sink_id = %self.sink_id, "uploading manifest: bucket {}, key {}", bucket, key);
+        let body = manifest.to_string().into_bytes();
+        let put_key = key.clone();
+        let put_bucket = bucket.clone();
+        mz_ore::task::spawn(|| "s3_archiver::manifest", async move {
+            client
+                .put_object()
+                .bucket(put_bucket)
+                .key(put_key)
+                .body(body.into())
+                .send()
+                .await?;
+            Ok::<(), anyhow::Error>(())
+        })
+        .wait_and_assert_finished()
+        .await?;
+
+        let manifest = SegmentManifest {
+            uri: format!("s3://{bucket}/{key}"),
+            lower: self.lower,
+            upper,
+            rows: self.rows,
+        };
+        self.segment += 1;
+        if let Some(upper) = upper {
+            self.lower = upper;
+        }
+        self.rows = 0;
+        Ok(manifest)
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use aws_types::sdk_config::SdkConfig;
+use mz_aws_util::s3_uploader::{
+    CompletedUpload, S3MultiPartUploadError, S3MultiPartUploader, S3MultiPartUploaderConfig,
+};
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_ore::assert_none;
+use mz_ore::cast::CastFrom;
+use mz_ore::task::JoinHandleExt;
+use mz_repr::{ColumnName, ColumnType, GlobalId, Row};
+use mz_storage_types::sinks::{S3SinkFormat, S3UploadInfo};
+use tracing::info;
+
+use super::{CopyToParameters, CopyToS3Uploader, S3KeyManager};
+
+/// Required state to upload batches to S3 as newline-delimited JSON.
+pub(super) struct JsonUploader {
+    /// The names and types of the output columns, with invented names for unnamed columns.
+    columns: Vec<(ColumnName, ColumnType)>,
+    /// The index of the current file within the batch.
+    file_index: usize,
+    /// Provides the appropriate bucket and object keys to use for uploads
+    key_manager: S3KeyManager,
+    /// Identifies the batch that files uploaded by this uploader belong to
+    batch: u64,
+    /// The desired file size. A new file upload will be started
+    /// when the size exceeds this amount.
+    max_file_size: u64,
+    /// The aws sdk config.
+    /// This is an option so that we can get an owned value later to move to a
+    /// spawned tokio task.
+    sdk_config: Option<SdkConfig>,
+    /// Multi-part uploader for the current file.
+    /// Keeping the uploader in an `Option` to later take owned value.
+    current_file_uploader: Option<S3MultiPartUploader>,
+    /// Upload parameters.
+    params: CopyToParameters,
+}
+
+impl CopyToS3Uploader for JsonUploader {
+    fn new(
+        sdk_config: SdkConfig,
+        connection_details: S3UploadInfo,
+        sink_id: &GlobalId,
+        batch: u64,
+        params: CopyToParameters,
+    ) -> Result<JsonUploader, anyhow::Error> {
+        match connection_details.format {
+            S3SinkFormat::Json => Ok(JsonUploader {
+                columns: column_names_and_types(connection_details.desc),
+                sdk_config: Some(sdk_config),
+                key_manager: S3KeyManager::new(sink_id, &connection_details.uri),
+                batch,
+                max_file_size: connection_details.max_file_size,
+                file_index: 0,
+                current_file_uploader: None,
+                params,
+            }),
+            _ => anyhow::bail!("Expected Json format"),
+        }
+    }
+
+    /// Finishes any remaining in-progress upload.
+    async fn finish(&mut self) -> Result<(), anyhow::Error> {
+        if let Some(uploader) = self.current_file_uploader.take() {
+            // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
+            let handle =
+                mz_ore::task::spawn(|| "s3_uploader::finish", async { uploader.finish().await });
+            let CompletedUpload {
+                part_count,
+                total_bytes_uploaded,
+                bucket,
+                key,
+            } = handle.wait_and_assert_finished().await?;
+            info!(
+                "finished upload: bucket {}, key {}, bytes_uploaded {}, parts_uploaded {}",
+                bucket, key, total_bytes_uploaded, part_count
+            );
+        }
+        Ok(())
+    }
+
+    /// Appends the row, encoded as a JSON object on its own line, to the in-progress upload. If
+    /// the row would exceed the max file size of the ongoing upload, it is appended to a new file
+    /// instead.
+    async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        let buf = encode_row(row, &self.columns);
+
+        if self.current_file_uploader.is_none() {
+            self.start_new_file_upload().await?;
+        }
+        let mut uploader = self.current_file_uploader.as_mut().expect("known exists");
+
+        match uploader.buffer_chunk(&buf) {
+            Ok(_) => Ok(()),
+            Err(S3MultiPartUploadError::UploadExceedsMaxFileLimit(_)) => {
+                // Start a multi part upload of next file.
+                self.start_new_file_upload().await?;
+                uploader = self.current_file_uploader.as_mut().expect("known exists");
+                uploader.buffer_chunk(&buf)?;
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl JsonUploader {
+    /// Creates the uploader for the next file and starts the multi part upload.
+    async fn start_new_file_upload(&mut self) -> Result<(), anyhow::Error> {
+        self.finish().await?;
+        assert_none!(self.current_file_uploader);
+
+        self.file_index += 1;
+        let object_key = self
+            .key_manager
+            .data_key(self.batch, self.file_index, "jsonl");
+        let bucket = self.key_manager.bucket.clone();
+        info!("starting upload: bucket {}, key {}", &bucket, &object_key);
+        let sdk_config = self
+            .sdk_config
+            .take()
+            .expect("sdk_config should always be present");
+        let max_file_size = self.max_file_size;
+        // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
+        let part_size_limit = u64::cast_from(self.params.s3_multipart_part_size_bytes);
+        let handle = mz_ore::task::spawn(|| "s3_uploader::try_new", async move {
+            let uploader = S3MultiPartUploader::try_new(
+                &sdk_config,
+                bucket,
+                object_key,
+                S3MultiPartUploaderConfig {
+                    part_size_limit,
+                    file_size_limit: max_file_size,
+                },
+            )
+            .await;
+            (uploader, sdk_config)
+        });
+        let (uploader, sdk_config) = handle.wait_and_assert_finished().await;
+        self.sdk_config = Some(sdk_config);
+        self.current_file_uploader = Some(uploader?);
+        Ok(())
+    }
+}
+
+/// Encodes `row` as a JSON object, followed by a newline.
+fn encode_row(row: &Row, columns: &[(ColumnName, ColumnType)]) -> Vec<u8> {
+    let mut buf = encode_datums_as_json(row.iter(), columns)
+        .to_string()
+        .into_bytes();
+    buf.push(b'\n');
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, ScalarType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_encode_row() {
+        let columns = vec![
+            (ColumnName::from("a"), ScalarType::Int32.nullable(false)),
+            (ColumnName::from("b"), ScalarType::String.nullable(true)),
+        ];
+        let row = Row::pack_slice(&[Datum::Int32(1), Datum::String("x")]);
+        assert_eq!(encode_row(&row, &columns), b"{\"a\":1,\"b\":\"x\"}\n");
+        let row = Row::pack_slice(&[Datum::Int32(2), Datum::Null]);
+        assert_eq!(encode_row(&row, &columns), b"{\"a\":2,\"b\":null}\n");
+    }
+}
@@ -198,6 +198,43 @@ pub const STORAGE_USE_RECLOCK_V2: Config<bool> = Config::new(
     "Whether to use the new reclock implementation.",
 );
 
+// Introspection
+
+/// How long entries of `mz_cancellation_history` are retained.
+pub const CANCELLATION_HISTORY_RETENTION_INTERVAL: Config<Duration> = Config::new(
+    "cancellation_history_retention_interval",
+    Duration::from_secs(30 * 24 * 60 * 60),
+    "How long entries of `mz_cancellation_history` are retained. Older entries are removed when environmentd starts.",
+);
+
+/// How long entries of `mz_catalog_changes` are retained.
+pub const CATALOG_CHANGE_HISTORY_RETENTION_INTERVAL: Config<Duration> = Config::new(
+    "catalog_change_history_retention_interval",
+    Duration::from_secs(30 * 24 * 60 * 60),
+    "How long entries of `mz_catalog_changes` are retained. Older entries are removed when environmentd starts.",
+);
+
+/// How long entries of `mz_subscription_history` are retained.
+pub const SUBSCRIPTION_HISTORY_RETENTION_INTERVAL: Config<Duration> = Config::new(
+    "subscription_history_retention_interval",
+    Duration::from_secs(30 * 24 * 60 * 60),
+    "How long entries of `mz_subscription_history` are retained. Older entries are removed when environmentd starts.",
+);
+
+/// How long entries of `mz_dataflow_shutdown_history` are retained.
+pub const DATAFLOW_SHUTDOWN_HISTORY_RETENTION_INTERVAL: Config<Duration> = Config::new(
+    "dataflow_shutdown_history_retention_interval",
+    Duration::from_secs(30 * 24 * 60 * 60),
+    "How long entries of `mz_dataflow_shutdown_history` are retained. Older entries are removed when environmentd starts.",
+);
+
+/// How long the estimates of `mz_storage_shard_cardinality` are retained.
+pub const STORAGE_SHARD_CARDINALITY_RETENTION_INTERVAL: Config<Duration> = Config::new(
+    "storage_shard_cardinality_retention_interval",
+    Duration::from_secs(30 * 24 * 60 * 60),
+    "How long the estimates of `mz_storage_shard_cardinality` are retained. Older estimates are removed when environmentd starts.",
+);
+
 /// Adds the full set of all storage `Config`s.
 pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
     configs
@@ -219,4 +256,9 @@ pub fn all_dyncfgs(configs: ConfigSet) -> ConfigSet {
         .add(&STORAGE_ROCKSDB_CLEANUP_TRIES)
         .add(&STORAGE_SUSPEND_AND_RESTART_DELAY)
         .add(&STORAGE_USE_RECLOCK_V2)
+        .add(&CANCELLATION_HISTORY_RETENTION_INTERVAL)
+        .add(&CATALOG_CHANGE_HISTORY_RETENTION_INTERVAL)
+        .add(&SUBSCRIPTION_HISTORY_RETENTION_INTERVAL)
+        .add(&DATAFLOW_SHUTDOWN_HISTORY_RETENTION_INTERVAL)
+        .add(&STORAGE_SHARD_CARDINALITY_RETENTION_INTERVAL)
 }
@@ -293,6 +293,9 @@ impl RustType<ProtoSinkPartitionStrategy> for SinkPartitionStrategy {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSinkConnection<C>),
+    /// A sink that delivers its updates over HTTP. Unlike Kafka sinks, webhook sinks are rendered
+    /// by compute rather than storage.
+    Webhook(WebhookSinkConnection),
 }
 
 impl<C: ConnectionAccess> StorageSinkConnection<C> {
@@ -312,6 +315,14 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
             (StorageSinkConnection::Kafka(s), StorageSinkConnection::Kafka(o)) => {
                 s.alter_compatible(id, o)?
             }
+            _ => {
+                tracing::warn!(
+                    "StorageSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+                return Err(AlterError { id });
+            }
         }
 
         Ok(())
@@ -324,6 +335,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<StorageSinkConnection, R>
     fn into_inline_connection(self, r: R) -> StorageSinkConnection {
         match self {
             Self::Kafka(conn) => StorageSinkConnection::Kafka(conn.into_inline_connection(r)),
+            Self::Webhook(conn) => StorageSinkConnection::Webhook(conn),
         }
     }
 }
@@ -335,6 +347,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 Self::Kafka(conn) => KafkaV2(conn.into_proto()),
+                Self::Webhook(conn) => Webhook(conn.into_proto()),
             }),
         }
     }
@@ -347,6 +360,7 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
 
         Ok(match kind {
             KafkaV2(proto) => Self::Kafka(proto.into_rust()?),
+            Webhook(proto) => Self::Webhook(proto.into_rust()?),
         })
     }
 }
@@ -357,6 +371,7 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Webhook(_) => None,
         }
     }
 
@@ -365,8 +380,49 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(_) => "kafka",
+            Webhook(_) => "webhook",
         }
     }
+
+    /// Reports whether the sink is rendered by compute rather than exported by storage.
+    pub fn is_compute_sink(&self) -> bool {
+        matches!(self, StorageSinkConnection::Webhook(_))
+    }
+}
+
+/// A sink that delivers the updates of each timestamp in a single HTTP `POST` request.
+///
+/// Every request carries an `Idempotency-Key` header derived from the ID of the sink and the
+/// timestamp of the delivered updates, so that receivers can discard the requests that are
+/// delivered more than once, e.g. by several replicas or after a restart.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSinkConnection {
+    /// The URL to deliver updates to.
+    pub url: String,
+}
+
+impl WebhookSinkConnection {
+    /// Returns the idempotency key of the request that delivers the `part`th part of the updates
+    /// of `sink_id` at `timestamp`.
+    pub fn idempotency_key(
+        sink_id: GlobalId,
+        timestamp: mz_repr::Timestamp,
+        part: usize,
+    ) -> String {
+        format!("{sink_id}-{timestamp}-{part}")
+    }
+}
+
+impl RustType<ProtoWebhookSinkConnection> for WebhookSinkConnection {
+    fn into_proto(&self) -> ProtoWebhookSinkConnection {
+        ProtoWebhookSinkConnection {
+            url: self.url.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoWebhookSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(WebhookSinkConnection { url: proto.url })
+    }
 }
 
 impl RustType<proto_kafka_sink_connection_v2::ProtoKeyDescAndIndices>
@@ -1000,6 +1056,8 @@ pub enum S3SinkFormat {
     PgCopy(CopyFormatParams<'static>),
     /// Encoded as Parquet.
     Parquet,
+    /// Encoded as newline-delimited JSON, with one object per row.
+    Json,
 }
 
 impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
@@ -1009,6 +1067,7 @@ impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
             kind: Some(match self {
                 Self::PgCopy(params) => Kind::PgCopy(params.into_proto()),
                 Self::Parquet => Kind::Parquet(()),
+                Self::Json => Kind::Json(()),
             }),
         }
     }
@@ -1022,6 +1081,7 @@ impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
         Ok(match kind {
             Kind::PgCopy(proto) => Self::PgCopy(proto.into_rust()?),
             Kind::Parquet(_) => Self::Parquet,
+            Kind::Json(_) => Self::Json,
         })
     }
 }
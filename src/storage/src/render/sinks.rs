@@ -260,5 +260,8 @@ where
 {
     match connection {
         StorageSinkConnection::Kafka(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Webhook(_) => {
+            unreachable!("webhook sinks are rendered by compute")
+        }
     }
 }
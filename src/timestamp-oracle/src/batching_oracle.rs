@@ -11,35 +11,44 @@
 //! to it.
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use mz_ore::cast::CastFrom;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot;
 
-use crate::metrics::Metrics;
+use crate::metrics::{BatchedOpMetrics, Metrics};
 use crate::{TimestampOracle, WriteTimestamp};
 
 /// A batching [`TimestampOracle`] backed by a [`TimestampOracle`]
 ///
-/// This will only batch calls to `read_ts` because the rest of the system
-/// already naturally does batching of write-related calls via the group commit
-/// mechanism. Write-related calls are passed straight through to the backing
-/// oracle.
+/// This will only batch calls to `read_ts` and `peek_write_ts` because the
+/// rest of the system already naturally does batching of write-related calls
+/// via the group commit mechanism. Also, `write_ts` must hand out a distinct
+/// timestamp to each caller, so its calls cannot share a result. Write-related
+/// calls are passed straight through to the backing oracle.
 ///
 /// For `read_ts` calls, we have to be careful to never cache results from the
 /// backing oracle: for the timestamp to be linearized we can never return a
 /// result as of an earlier moment, but batching them up is correct because this
 /// can only make it so that we return later timestamps. Those later timestamps
 /// still fall within the duration of the `read_ts` call and so are linearized.
+/// The same reasoning applies to `peek_write_ts`.
+///
+/// By default, a batch contains the calls that are waiting when the backing
+/// oracle becomes available. With a non-zero batch window, the first call of
+/// a batch also waits for the window to pass, trading latency for fewer round
+/// trips to the backing oracle when calls are concurrent but not simultaneous.
 pub struct BatchingTimestampOracle<T> {
     inner: Arc<dyn TimestampOracle<T> + Send + Sync>,
+    metrics: Arc<Metrics>,
     command_tx: UnboundedSender<Command<T>>,
 }
 
 /// A command on the internal batching command stream.
 enum Command<T> {
     ReadTs(oneshot::Sender<T>),
+    PeekWriteTs(oneshot::Sender<T>),
 }
 
 impl<T> std::fmt::Debug for BatchingTimestampOracle<T> {
@@ -53,37 +62,69 @@ where
     T: Clone + Send + Sync + 'static,
 {
     /// Crates a [`BatchingTimestampOracle`] that uses the given inner oracle.
-    pub fn new(metrics: Arc<Metrics>, oracle: Arc<dyn TimestampOracle<T> + Send + Sync>) -> Self {
+    ///
+    /// `batch_window` is consulted for each batch, so that changes to it take
+    /// effect without recreating the oracle.
+    pub fn new<W>(
+        metrics: Arc<Metrics>,
+        oracle: Arc<dyn TimestampOracle<T> + Send + Sync>,
+        batch_window: W,
+    ) -> Self
+    where
+        W: Fn() -> Duration + Send + 'static,
+    {
         let (command_tx, mut command_rx) = tokio::sync::mpsc::unbounded_channel();
 
         let task_oracle = Arc::clone(&oracle);
+        let task_metrics = Arc::clone(&metrics);
 
         mz_ore::task::spawn(|| "BatchingTimestampOracle Worker Task", async move {
-            let read_ts_metrics = &metrics.batching.read_ts;
+            let batching_metrics = &task_metrics.batching;
 
             // See comment on `BatchingTimestampOracle` for why this batching is
             // correct.
             while let Some(cmd) = command_rx.recv().await {
-                let mut pending_cmds = vec![cmd];
-                while let Ok(cmd) = command_rx.try_recv() {
-                    pending_cmds.push(cmd);
+                let batch_window = batch_window();
+                if !batch_window.is_zero() {
+                    tokio::time::sleep(batch_window).await;
                 }
 
-                read_ts_metrics
-                    .ops_count
-                    .inc_by(u64::cast_from(pending_cmds.len()));
-                read_ts_metrics.batches_count.inc();
-
-                let ts = task_oracle.read_ts().await;
-                for cmd in pending_cmds {
+                let mut read_ts_txs = Vec::new();
+                let mut peek_write_ts_txs = Vec::new();
+                let mut next_cmd = Some(cmd);
+                while let Some(cmd) = next_cmd {
                     match cmd {
-                        Command::ReadTs(response_tx) => {
-                            // It's okay if the receiver drops, just means
-                            // they're not interested anymore.
-                            let _ = response_tx.send(ts.clone());
-                        }
+                        Command::ReadTs(tx) => read_ts_txs.push(tx),
+                        Command::PeekWriteTs(tx) => peek_write_ts_txs.push(tx),
                     }
+                    next_cmd = command_rx.try_recv().ok();
                 }
+
+                let read_ts = async {
+                    if read_ts_txs.is_empty() {
+                        return;
+                    }
+                    batching_metrics.read_ts.record_batch(read_ts_txs.len());
+                    let ts = task_oracle.read_ts().await;
+                    for tx in read_ts_txs {
+                        // It's okay if the receiver drops, just means
+                        // they're not interested anymore.
+                        let _ = tx.send(ts.clone());
+                    }
+                };
+                let peek_write_ts = async {
+                    if peek_write_ts_txs.is_empty() {
+                        return;
+                    }
+                    batching_metrics
+                        .peek_write_ts
+                        .record_batch(peek_write_ts_txs.len());
+                    let ts = task_oracle.peek_write_ts().await;
+                    for tx in peek_write_ts_txs {
+                        let _ = tx.send(ts.clone());
+                    }
+                };
+                futures::join!(read_ts, peek_write_ts);
             }
 
             tracing::debug!("shutting down BatchingTimestampOracle task");
@@ -91,11 +132,34 @@ where
 
         Self {
             inner: oracle,
+            metrics,
             command_tx,
         }
     }
 }
 
+impl<T> BatchingTimestampOracle<T> {
+    /// Sends a command to the worker task and waits for its response.
+    async fn batched(
+        &self,
+        metrics: &BatchedOpMetrics,
+        cmd: impl FnOnce(oneshot::Sender<T>) -> Command<T>,
+    ) -> T {
+        let start = Instant::now();
+        let (tx, rx) = oneshot::channel();
+
+        self.command_tx.send(cmd(tx)).expect(
+            "worker task cannot stop while we still have senders for the command/request channel",
+        );
+
+        let ts = rx
+            .await
+            .expect("worker task cannot stop while there are outstanding commands/requests");
+        metrics.seconds.inc_by(start.elapsed().as_secs_f64());
+        ts
+    }
+}
+
 #[async_trait]
 impl<T> TimestampOracle<T> for BatchingTimestampOracle<T>
 where
@@ -106,18 +170,13 @@ where
     }
 
     async fn peek_write_ts(&self) -> T {
-        self.inner.peek_write_ts().await
+        self.batched(&self.metrics.batching.peek_write_ts, Command::PeekWriteTs)
+            .await
     }
 
     async fn read_ts(&self) -> T {
-        let (tx, rx) = oneshot::channel();
-
-        self.command_tx.send(Command::ReadTs(tx)).expect(
-            "worker task cannot stop while we still have senders for the command/request channel",
-        );
-
-        rx.await
-            .expect("worker task cannot stop while there are outstanding commands/requests")
+        self.batched(&self.metrics.batching.read_ts, Command::ReadTs)
+            .await
     }
 
     async fn apply_write(&self, write_ts: T) {
@@ -128,6 +187,8 @@ where
 #[cfg(test)]
 mod tests {
 
+    use std::sync::atomic::{AtomicU64, Ordering};
+
     use mz_ore::metrics::MetricsRegistry;
     use mz_repr::Timestamp;
     use tracing::info;
@@ -166,7 +227,9 @@ mod tests {
                     Arc::new(pg_oracle.await);
 
                 let batching_oracle =
-                    BatchingTimestampOracle::new(Arc::clone(&metrics), arced_pg_oracle);
+                    BatchingTimestampOracle::new(Arc::clone(&metrics), arced_pg_oracle, || {
+                        Duration::ZERO
+                    });
 
                 let arced_oracle: Arc<dyn TimestampOracle<Timestamp> + Send + Sync> =
                     Arc::new(batching_oracle);
@@ -178,4 +241,64 @@ mod tests {
 
         Ok(())
     }
+
+    /// An oracle that counts the calls to it.
+    #[derive(Debug, Default)]
+    struct CountingOracle {
+        read_ts_calls: AtomicU64,
+    }
+
+    #[async_trait]
+    impl TimestampOracle<Timestamp> for CountingOracle {
+        async fn write_ts(&self) -> WriteTimestamp<Timestamp> {
+            unimplemented!()
+        }
+
+        async fn peek_write_ts(&self) -> Timestamp {
+            unimplemented!()
+        }
+
+        async fn read_ts(&self) -> Timestamp {
+            let calls = self.read_ts_calls.fetch_add(1, Ordering::SeqCst);
+            Timestamp::from(calls)
+        }
+
+        async fn apply_write(&self, _write_ts: Timestamp) {
+            unimplemented!()
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn test_batching_timestamp_oracle_batch_window() {
+        let metrics = Arc::new(Metrics::new(&MetricsRegistry::new()));
+        let inner = Arc::new(CountingOracle::default());
+        let oracle = Arc::new(BatchingTimestampOracle::new(
+            Arc::clone(&metrics),
+            Arc::clone(&inner) as Arc<dyn TimestampOracle<Timestamp> + Send + Sync>,
+            || Duration::from_millis(100),
+        ));
+
+        // Calls that arrive within the window share one call to the inner
+        // oracle.
+        let first = {
+            let oracle = Arc::clone(&oracle);
+            mz_ore::task::spawn(|| "read_ts", async move { oracle.read_ts().await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let second = oracle.read_ts().await;
+        let first = first.await.expect("task panicked");
+        assert_eq!(first, second);
+        assert_eq!(inner.read_ts_calls.load(Ordering::SeqCst), 1);
+
+        // Calls after the window get a new timestamp.
+        let third = oracle.read_ts().await;
+        assert!(third > second);
+        assert_eq!(inner.read_ts_calls.load(Ordering::SeqCst), 2);
+
+        let read_ts_metrics = &metrics.batching.read_ts;
+        assert_eq!(read_ts_metrics.ops_count.get(), 3);
+        assert_eq!(read_ts_metrics.batches_count.get(), 2);
+        assert_eq!(read_ts_metrics.saved_count.get(), 1);
+    }
 }
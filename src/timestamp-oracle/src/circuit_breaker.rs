@@ -0,0 +1,102 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A circuit breaker that detects when the backend of a timestamp oracle is
+//! unavailable.
+//!
+//! Oracle operations retry until they succeed, so callers hang for as long as
+//! the backend is unavailable. The circuit breaker observes the operations and
+//! opens once they have been failing for longer than a timeout, which allows
+//! callers to fail fast instead of issuing more operations that would hang.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A circuit breaker for the operations of a timestamp oracle.
+///
+/// The breaker closes again as soon as any operation succeeds. As callers
+/// stop issuing operations while the breaker is open, they are expected to
+/// [probe](CircuitBreaker::start_probe) the oracle to find out when it is
+/// available again.
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    /// The time of the first failure since the last successful operation, if
+    /// any.
+    failing_since: Mutex<Option<Instant>>,
+    /// Whether a probe of the oracle is in progress.
+    probing: AtomicBool,
+}
+
+impl CircuitBreaker {
+    /// Runs the oracle operation `op` and records whether it succeeded.
+    pub(crate) async fn observe<R, E, F>(&self, op: F) -> Result<R, E>
+    where
+        F: Future<Output = Result<R, E>>,
+    {
+        let res = op.await;
+        let mut failing_since = self.failing_since.lock().expect("lock poisoned");
+        match &res {
+            Ok(_) => *failing_since = None,
+            Err(_) => {
+                failing_since.get_or_insert_with(Instant::now);
+            }
+        }
+        res
+    }
+
+    /// Returns whether the breaker is open, i.e., whether the operations of the
+    /// oracle have been failing for at least `timeout`.
+    pub fn is_open(&self, timeout: Duration) -> bool {
+        let failing_since = self.failing_since.lock().expect("lock poisoned");
+        failing_since.map_or(false, |since| since.elapsed() >= timeout)
+    }
+
+    /// Returns whether the caller should probe the oracle, which is the case
+    /// if no other probe is in progress. Callers that should probe must call
+    /// [`CircuitBreaker::finish_probe`] once the probe has finished.
+    pub fn start_probe(&self) -> bool {
+        !self.probing.swap(true, Ordering::SeqCst)
+    }
+
+    /// Marks the probe of the oracle as finished.
+    pub fn finish_probe(&self) {
+        self.probing.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn test_circuit_breaker() {
+        let breaker = CircuitBreaker::default();
+        assert!(!breaker.is_open(Duration::ZERO));
+
+        let _ = breaker.observe(async { Err::<(), _>("unavailable") }).await;
+        assert!(breaker.is_open(Duration::ZERO));
+        assert!(!breaker.is_open(Duration::from_secs(3600)));
+
+        // Further failures don't extend the time since the oracle is failing.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let _ = breaker.observe(async { Err::<(), _>("unavailable") }).await;
+        assert!(breaker.is_open(Duration::from_millis(20)));
+
+        let _ = breaker.observe(async { Ok::<_, ()>(()) }).await;
+        assert!(!breaker.is_open(Duration::ZERO));
+
+        assert!(breaker.start_probe());
+        assert!(!breaker.start_probe());
+        breaker.finish_probe();
+        assert!(breaker.start_probe());
+    }
+}
@@ -11,6 +11,7 @@
 
 use std::time::{Duration, Instant};
 
+use mz_ore::cast::CastFrom;
 use mz_ore::metric;
 use mz_ore::metrics::raw::{CounterVec, IntCounterVec};
 use mz_ore::metrics::{Counter, IntCounter, MetricsRegistry};
@@ -76,6 +77,8 @@ struct MetricsVecs {
 
     batched_op_count: IntCounterVec,
     batches_count: IntCounterVec,
+    batched_op_saved_count: IntCounterVec,
+    batched_op_seconds: CounterVec,
 }
 
 impl MetricsVecs {
@@ -134,6 +137,18 @@ impl MetricsVecs {
                 help: "count of batches of operations",
                 var_labels: ["op"],
             )),
+
+            batched_op_saved_count: registry.register(metric!(
+                name: "mz_ts_oracle_batched_op_saved_count",
+                help: "count of calls to the backing oracle saved by batching operations",
+                var_labels: ["op"],
+            )),
+
+            batched_op_seconds: registry.register(metric!(
+                name: "mz_ts_oracle_batched_op_seconds",
+                help: "time spent in batched operations, including waiting for the batch",
+                var_labels: ["op"],
+            )),
         }
     }
 
@@ -158,6 +173,7 @@ impl MetricsVecs {
     fn batching_metrics(&self) -> BatchingMetrics {
         BatchingMetrics {
             read_ts: self.batched_op_metrics("read_ts"),
+            peek_write_ts: self.batched_op_metrics("peek_write_ts"),
         }
     }
 
@@ -165,6 +181,8 @@ impl MetricsVecs {
         BatchedOpMetrics {
             ops_count: self.batched_op_count.with_label_values(&[op]),
             batches_count: self.batches_count.with_label_values(&[op]),
+            saved_count: self.batched_op_saved_count.with_label_values(&[op]),
+            seconds: self.batched_op_seconds.with_label_values(&[op]),
         }
     }
 
@@ -231,11 +249,25 @@ pub struct OracleMetrics {
 pub struct BatchedOpMetrics {
     pub ops_count: IntCounter,
     pub batches_count: IntCounter,
+    pub saved_count: IntCounter,
+    pub seconds: Counter,
+}
+
+impl BatchedOpMetrics {
+    /// Records a batch of `ops` operations that is served by one call to the
+    /// backing oracle.
+    pub(crate) fn record_batch(&self, ops: usize) {
+        let ops = u64::cast_from(ops);
+        self.ops_count.inc_by(ops);
+        self.batches_count.inc();
+        self.saved_count.inc_by(ops.saturating_sub(1));
+    }
 }
 
 #[derive(Debug)]
 pub struct BatchingMetrics {
     pub read_ts: BatchedOpMetrics,
+    pub peek_write_ts: BatchedOpMetrics,
 }
 
 #[derive(Debug)]
@@ -22,6 +22,7 @@ use mz_adapter_types::timestamp_oracle::{
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNECT_TIMEOUT, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_SIZE,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_WAIT, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL_STAGGER, DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT,
+    DEFAULT_TIMESTAMP_ORACLE_BATCH_WINDOW, DEFAULT_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT,
 };
 use mz_ore::error::ErrorExt;
 use mz_ore::instrument;
@@ -32,6 +33,7 @@ use mz_repr::Timestamp;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+use crate::circuit_breaker::CircuitBreaker;
 use crate::metrics::{Metrics, RetryMetrics};
 use crate::retry::Retry;
 use crate::WriteTimestamp;
@@ -66,6 +68,7 @@ where
     next: N,
     postgres_client: Arc<PostgresClient>,
     metrics: Arc<Metrics>,
+    circuit_breaker: Arc<CircuitBreaker>,
     /// A read-only timestamp oracle is NOT allowed to do operations that change
     /// the backing Postgres/CRDB state.
     read_only: bool,
@@ -80,6 +83,9 @@ pub struct PostgresTimestampOracleConfig {
 
     /// Configurations that can be dynamically updated.
     pub dynamic: Arc<DynamicConfig>,
+
+    /// The circuit breaker that observes all oracles opened with this config.
+    pub circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl From<PostgresTimestampOracleConfig> for PostgresClientConfig {
@@ -102,6 +108,7 @@ impl PostgresTimestampOracleConfig {
             url: url.to_string(),
             metrics,
             dynamic: Arc::new(dynamic),
+            circuit_breaker: Arc::new(CircuitBreaker::default()),
         }
     }
 
@@ -130,10 +137,19 @@ impl PostgresTimestampOracleConfig {
             url: url.to_string(),
             metrics: Arc::new(Metrics::new(&MetricsRegistry::new())),
             dynamic: Arc::new(dynamic),
+            circuit_breaker: Arc::new(CircuitBreaker::default()),
         };
 
         Some(config)
     }
+
+    /// Returns whether the operations of the oracles opened with this config
+    /// have been failing for at least
+    /// [`DynamicConfig::circuit_breaker_timeout`].
+    pub fn circuit_breaker_open(&self) -> bool {
+        self.circuit_breaker
+            .is_open(self.dynamic.circuit_breaker_timeout())
+    }
 }
 
 /// Part of [`PostgresTimestampOracleConfig`] that can be dynamically updated.
@@ -177,6 +193,15 @@ pub struct DynamicConfig {
     /// amount of time that transmitted data may remain unacknowledged before
     /// the TCP connection is forcibly closed.
     pg_connection_pool_tcp_user_timeout: RwLock<Duration>,
+
+    /// The time that the `BatchingTimestampOracle` waits for concurrent calls
+    /// after the first call of a batch, to serve them all with one round trip
+    /// to Postgres/CRDB.
+    batch_window: RwLock<Duration>,
+
+    /// The time that operations against Postgres/CRDB have to be failing for
+    /// the oracle to be considered unavailable.
+    circuit_breaker_timeout: RwLock<Duration>,
 }
 
 impl Default for DynamicConfig {
@@ -201,6 +226,8 @@ impl Default for DynamicConfig {
             pg_connection_pool_tcp_user_timeout: RwLock::new(
                 DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT,
             ),
+            batch_window: RwLock::new(DEFAULT_TIMESTAMP_ORACLE_BATCH_WINDOW),
+            circuit_breaker_timeout: RwLock::new(DEFAULT_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_TIMEOUT),
         }
     }
 }
@@ -245,6 +272,18 @@ impl DynamicConfig {
             .read()
             .expect("lock poisoned")
     }
+
+    /// The time that the `BatchingTimestampOracle` waits for concurrent calls
+    /// to batch up.
+    pub fn batch_window(&self) -> Duration {
+        *self.batch_window.read().expect("lock poisoned")
+    }
+
+    /// The time that operations have to be failing for the oracle to be
+    /// considered unavailable.
+    pub fn circuit_breaker_timeout(&self) -> Duration {
+        *self.circuit_breaker_timeout.read().expect("lock poisoned")
+    }
 }
 
 impl PostgresClientKnobs for PostgresTimestampOracleConfig {
@@ -300,6 +339,10 @@ pub struct PostgresTimestampOracleParameters {
     pub pg_connection_pool_connect_timeout: Option<Duration>,
     /// Configures `DynamicConfig::pg_connection_pool_tcp_user_timeout`.
     pub pg_connection_pool_tcp_user_timeout: Option<Duration>,
+    /// Configures `DynamicConfig::batch_window`.
+    pub batch_window: Option<Duration>,
+    /// Configures `DynamicConfig::circuit_breaker_timeout`.
+    pub circuit_breaker_timeout: Option<Duration>,
 }
 
 impl PostgresTimestampOracleParameters {
@@ -314,6 +357,8 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger: self_pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout: self_pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout: self_pg_connection_pool_tcp_user_timeout,
+            batch_window: self_batch_window,
+            circuit_breaker_timeout: self_circuit_breaker_timeout,
         } = self;
         let Self {
             pg_connection_pool_max_size: other_pg_connection_pool_max_size,
@@ -322,6 +367,8 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger: other_pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout: other_pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout: other_pg_connection_pool_tcp_user_timeout,
+            batch_window: other_batch_window,
+            circuit_breaker_timeout: other_circuit_breaker_timeout,
         } = other;
         if let Some(v) = other_pg_connection_pool_max_size {
             *self_pg_connection_pool_max_size = Some(v);
@@ -341,6 +388,12 @@ impl PostgresTimestampOracleParameters {
         if let Some(v) = other_pg_connection_pool_tcp_user_timeout {
             *self_pg_connection_pool_tcp_user_timeout = Some(v);
         }
+        if let Some(v) = other_batch_window {
+            *self_batch_window = Some(v);
+        }
+        if let Some(v) = other_circuit_breaker_timeout {
+            *self_circuit_breaker_timeout = Some(v);
+        }
     }
 
     /// Applies the parameter values to the given in-memory config object.
@@ -359,6 +412,8 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout,
+            batch_window,
+            circuit_breaker_timeout,
         } = self;
         if let Some(pg_connection_pool_max_size) = pg_connection_pool_max_size {
             cfg.dynamic
@@ -405,6 +460,18 @@ impl PostgresTimestampOracleParameters {
                 .expect("lock poisoned");
             *timeout = *pg_connection_pool_tcp_user_timeout;
         }
+        if let Some(batch_window) = batch_window {
+            let mut window = cfg.dynamic.batch_window.write().expect("lock poisoned");
+            *window = *batch_window;
+        }
+        if let Some(circuit_breaker_timeout) = circuit_breaker_timeout {
+            let mut timeout = cfg
+                .dynamic
+                .circuit_breaker_timeout
+                .write()
+                .expect("lock poisoned");
+            *timeout = *circuit_breaker_timeout;
+        }
     }
 }
 
@@ -426,6 +493,7 @@ where
 
         let fallible = || async {
             let metrics = Arc::clone(&config.metrics);
+            let circuit_breaker = Arc::clone(&config.circuit_breaker);
 
             let postgres_client = PostgresClient::open(config.clone().into())?;
 
@@ -452,6 +520,7 @@ where
                 next: next.clone(),
                 postgres_client: Arc::new(postgres_client),
                 metrics,
+                circuit_breaker,
                 read_only,
             };
 
@@ -487,7 +556,7 @@ where
 
         let metrics = &config.metrics.retries.open;
 
-        let oracle = retry_fallible(metrics, fallible).await;
+        let oracle = retry_fallible(metrics, || config.circuit_breaker.observe(fallible())).await;
 
         oracle
     }
@@ -680,8 +749,8 @@ where
     }
 }
 
-// A wrapper around the `fallible_` methods that adds operation metrics and
-// retries.
+// A wrapper around the `fallible_` methods that adds operation metrics,
+// retries, and reports to the circuit breaker.
 //
 // NOTE: This implementation is tied to [`mz_repr::Timestamp`]. We could change
 // that, and also make the types we store in the backing "Postgres" table
@@ -697,10 +766,12 @@ where
         let metrics = &self.metrics.retries.write_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .write_ts
-                .run_op(|| self.fallible_write_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .write_ts
+                    .run_op(|| self.fallible_write_ts()),
+            )
         })
         .await;
 
@@ -712,10 +783,12 @@ where
         let metrics = &self.metrics.retries.peek_write_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .peek_write_ts
-                .run_op(|| self.fallible_peek_write_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .peek_write_ts
+                    .run_op(|| self.fallible_peek_write_ts()),
+            )
         })
         .await;
 
@@ -727,10 +800,12 @@ where
         let metrics = &self.metrics.retries.read_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .read_ts
-                .run_op(|| self.fallible_read_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .read_ts
+                    .run_op(|| self.fallible_read_ts()),
+            )
         })
         .await;
 
@@ -742,10 +817,12 @@ where
         let metrics = &self.metrics.retries.apply_write;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .apply_write
-                .run_op(|| self.fallible_apply_write(write_ts.clone()))
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .apply_write
+                    .run_op(|| self.fallible_apply_write(write_ts.clone())),
+            )
         })
         .await;
 
@@ -957,7 +957,7 @@ impl<'a> CollectIndexRequests<'a> {
                                         // be saved as `FullScan` (NOT as `PlanRootNoArrangement`),
                                         // because we are going into the `try_full_scan` if.
                                     },
-                                    IndexUsageType::FastPathLimit => {
+                                    IndexUsageType::FastPathLimit | IndexUsageType::PartialIndex => {
                                         // These are created much later, not even inside
                                         // `prune_and_annotate_dataflow_index_imports`.
                                         unreachable!()
@@ -1225,6 +1225,9 @@ pub struct DataflowMetainfo<Notice = RawOptimizerNotice> {
     /// What kind of operation (full scan, lookup, ...) will access each index. Computed by
     /// `prune_and_annotate_dataflow_index_imports`.
     pub index_usage_types: BTreeMap<GlobalId, Vec<IndexUsageType>>,
+    /// The row count hints that the optimizer used in place of statistics, by the ID of the
+    /// hinted collection.
+    pub row_count_hints: BTreeMap<GlobalId, usize>,
 }
 
 impl Default for DataflowMetainfo {
@@ -1232,6 +1235,7 @@ impl Default for DataflowMetainfo {
         DataflowMetainfo {
             optimizer_notices: Vec::new(),
             index_usage_types: BTreeMap::new(),
+            row_count_hints: BTreeMap::new(),
         }
     }
 }
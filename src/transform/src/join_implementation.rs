@@ -273,11 +273,18 @@ impl JoinImplementation {
                 let push_down_factor = push_down_characteristics.worst_case_scaling_factor();
                 characteristics |= push_down_characteristics;
 
-                // Estimate cardinality
-                if features.enable_cardinality_estimates {
+                // Estimate cardinality, from the user-supplied row count hints alone
+                // unless cardinality estimates are enabled
+                let row_count_hints = stats.row_count_hints();
+                if features.enable_cardinality_estimates || !row_count_hints.is_empty() {
+                    let stats = if features.enable_cardinality_estimates {
+                        // TODO(mgree): it would be good to not have to copy the statistics here
+                        stats.as_map()
+                    } else {
+                        row_count_hints
+                    };
                     let mut builder = DerivedBuilder::new(features);
-                    // TODO(mgree): it would be good to not have to copy the statistics here
-                    builder.require(Cardinality::with_stats(stats.as_map()));
+                    builder.require(Cardinality::with_stats(stats));
                     let derived = builder.visit(input);
 
                     let estimate = *derived.as_view().value::<Cardinality>().unwrap();
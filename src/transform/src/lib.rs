@@ -255,6 +255,12 @@ pub trait StatisticsOracle: fmt::Debug + Send {
 
     /// Returns a map from identifiers to sizes
     fn as_map(&self) -> BTreeMap<GlobalId, usize>;
+
+    /// Returns the user-supplied row count hints among the estimates, which take
+    /// precedence over statistics
+    fn row_count_hints(&self) -> BTreeMap<GlobalId, usize> {
+        BTreeMap::new()
+    }
 }
 
 /// A [`StatisticsOracle`] that knows nothing and can give no estimates.
@@ -154,11 +154,13 @@ mod tests {
                     features: &features,
                     humanizer: cat,
                     cardinality_stats: Default::default(), // empty stats
+                    row_count_hints: Default::default(),
                     used_indexes: Default::default(),
                     finishing: Default::default(),
                     duration: Default::default(),
                     target_cluster: Default::default(),
                     optimizer_notices: Default::default(),
+                    subscribe: Default::default(),
                 };
 
                 Explainable(&mut rel.clone())
@@ -62,11 +62,13 @@ fn handle_explain(
         features: &features,
         humanizer: catalog,
         cardinality_stats: Default::default(), // empty stats
+        row_count_hints: Default::default(),
         used_indexes: Default::default(),
         finishing: Default::default(),
         duration: Default::default(),
         target_cluster: Default::default(),
         optimizer_notices: Default::default(),
+        subscribe: Default::default(),
     };
 
     // Parse the relation, returning early on parse error.